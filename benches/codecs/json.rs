@@ -0,0 +1,103 @@
+use std::{fmt, time::Duration};
+
+use bytes::BytesMut;
+use codecs::{
+    self, decoding::Deserializer, decoding::Framer, JsonDeserializer, NewlineDelimitedDecoder,
+};
+use criterion::{
+    criterion_group, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId, Criterion,
+    SamplingMode, Throughput,
+};
+use tokio_util::codec::Decoder;
+
+#[derive(Debug)]
+struct Param {
+    slug: &'static str,
+    simd: bool,
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.slug)
+    }
+}
+
+/// Newline delimited JSON objects, shaped like the application logs read from Kafka.
+fn input() -> BytesMut {
+    let mut input = String::new();
+    for (index, line) in include_str!("moby_dick.txt").lines().enumerate() {
+        input.push_str(&format!(
+            concat!(
+                r#"{{"timestamp":"2023-04-01T12:00:{:02}.000Z","level":"info","host":"web-{}","#,
+                r#""request":{{"method":"GET","path":"/books/{}","status":200,"duration_ms":{}.25}},"#,
+                r#""tags":["production","moby-dick"],"message":{}}}"#,
+                "\n"
+            ),
+            index % 60,
+            index % 8,
+            index,
+            index % 1000,
+            serde_json::to_string(line).unwrap(),
+        ));
+    }
+    BytesMut::from(input.as_str())
+}
+
+fn decoding(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> =
+        c.benchmark_group("vector::codecs::decoding::Decoder");
+    group.sampling_mode(SamplingMode::Auto);
+
+    let input = input();
+    for param in &[
+        Param {
+            slug: "serde_json",
+            simd: false,
+        },
+        Param {
+            slug: "simd_json",
+            simd: true,
+        },
+    ] {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("json", param), &param, |b, param| {
+            b.iter_batched(
+                || {
+                    let framer = Framer::NewlineDelimited(NewlineDelimitedDecoder::new());
+                    let deserializer = Deserializer::Json(JsonDeserializer::new(param.simd));
+                    let decoder = vector::codecs::Decoder::new(framer, deserializer);
+
+                    (Box::new(decoder), input.clone())
+                },
+                |(mut decoder, mut input)| loop {
+                    match decoder.decode_eof(&mut input) {
+                        Ok(Some(_)) => continue,
+                        Ok(None) => break,
+                        Err(_) => {
+                            unreachable!()
+                        }
+                    }
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default()
+        .warm_up_time(Duration::from_secs(5))
+        .measurement_time(Duration::from_secs(120))
+        // degree of noise to ignore in measurements, here 1%
+        .noise_threshold(0.01)
+        // likelihood of noise registering as difference, here 5%
+        .significance_level(0.05)
+        // likelihood of capturing the true runtime, here 95%
+        .confidence_level(0.95)
+        // total number of bootstrap resamples, higher is less noisy but slower
+        .nresamples(100_000)
+        // total samples to collect within the set measurement time
+        .sample_size(150);
+    targets = decoding
+);
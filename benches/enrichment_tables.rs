@@ -242,6 +242,7 @@ fn benchmark_enrichment_tables_geoip(c: &mut Criterion) {
         Geoip::new(GeoipConfig {
             path: path.to_string(),
             locale: "en".to_string(),
+            download: None,
         })
         .unwrap()
     };
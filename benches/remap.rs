@@ -5,7 +5,7 @@ use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use indexmap::IndexMap;
 use vector::{
     config::{DataType, Output},
-    event::{Event, LogEvent, Value},
+    event::{Event, EventArray, EventContainer, LogEvent, Metric, MetricKind, MetricValue, Value},
     transforms::{
         remap::{Remap, RemapConfig},
         SyncTransform, TransformOutputsBuf,
@@ -184,4 +184,75 @@ fn benchmark_remap(c: &mut Criterion) {
             BatchSize::SmallInput,
         );
     });
+
+    let metrics_remap = || -> Box<dyn SyncTransform> {
+        Box::new(
+            Remap::new_ast(
+                RemapConfig {
+                    source: Some(
+                        indoc! {r#".tags.env = "production"
+                            .name = "aggregated_" + string!(.name)
+                        "#}
+                        .to_owned(),
+                    ),
+                    file: None,
+                    timezone: TimeZone::default(),
+                    drop_on_error: true,
+                    drop_on_abort: true,
+                    ..Default::default()
+                },
+                &Default::default(),
+            )
+            .unwrap()
+            .0,
+        )
+    };
+
+    let metrics = (0..100)
+        .map(|index| {
+            Metric::new(
+                format!("requests_{}", index),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_namespace(Some("service"))
+        })
+        .collect::<Vec<_>>();
+
+    // The same batch of metrics, transformed one by one and as a whole.
+    group.bench_function("tag_metrics/remap", |b| {
+        let mut tform = metrics_remap();
+
+        b.iter_batched(
+            || metrics.clone(),
+            |metrics| {
+                let mut outputs = TransformOutputsBuf::new_with_capacity(
+                    vec![Output::default(DataType::all())],
+                    metrics.len(),
+                );
+                for metric in metrics {
+                    tform.transform(metric.into(), &mut outputs);
+                }
+                outputs.take_primary()
+            },
+            BatchSize::SmallInput,
+        );
+    });
+
+    group.bench_function("tag_metrics_batch/remap", |b| {
+        let mut tform = metrics_remap();
+
+        b.iter_batched(
+            || EventArray::from(metrics.clone()),
+            |metrics| {
+                let mut outputs = TransformOutputsBuf::new_with_capacity(
+                    vec![Output::default(DataType::all())],
+                    metrics.len(),
+                );
+                tform.transform_all(metrics, &mut outputs);
+                outputs.take_primary()
+            },
+            BatchSize::SmallInput,
+        );
+    });
 }
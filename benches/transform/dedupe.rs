@@ -23,6 +23,15 @@ impl fmt::Display for Param {
     }
 }
 
+fn cache_config(num_events: usize) -> CacheConfig {
+    CacheConfig {
+        num_events: NonZeroUsize::new(num_events).unwrap(),
+        time_window_secs: None,
+        persist: false,
+        data_dir: None,
+    }
+}
+
 fn dedupe(c: &mut Criterion) {
     let mut group: BenchmarkGroup<WallTime> =
         c.benchmark_group("vector::transforms::dedupe::Dedupe");
@@ -42,7 +51,7 @@ fn dedupe(c: &mut Criterion) {
                 fields: Some(FieldMatchConfig::IgnoreFields(vec![String::from(
                     "message",
                 )])),
-                cache: CacheConfig { num_events: 4 },
+                cache: cache_config(4),
             },
         },
         // Modification of previous where field "message" is matched.
@@ -51,7 +60,7 @@ fn dedupe(c: &mut Criterion) {
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
                 fields: Some(FieldMatchConfig::MatchFields(vec![String::from("message")])),
-                cache: CacheConfig { num_events: 4 },
+                cache: cache_config(4),
             },
         },
         // Measurement where ignore fields do not exist in the event.
@@ -59,7 +68,7 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_ignore_done",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
-                cache: CacheConfig { num_events: 4 },
+                cache: cache_config(4),
                 fields: Some(FieldMatchConfig::IgnoreFields(vec![
                     String::from("abcde"),
                     String::from("eabcd"),
@@ -75,7 +84,7 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_match_done",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
-                cache: CacheConfig { num_events: 4 },
+                cache: cache_config(4),
                 fields: Some(FieldMatchConfig::MatchFields(vec![
                     String::from("abcde"),
                     String::from("eabcd"),
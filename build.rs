@@ -105,6 +105,7 @@ fn main() {
         println!("cargo:rerun-if-changed=proto/dd_metric.proto");
         println!("cargo:rerun-if-changed=proto/google/pubsub/v1/pubsub.proto");
         println!("cargo:rerun-if-changed=proto/google/rpc/status.proto");
+        println!("cargo:rerun-if-changed=proto/profile.proto");
         println!("cargo:rerun-if-changed=proto/vector.proto");
 
         let mut prost_build = prost_build::Config::new();
@@ -122,6 +123,7 @@ fn main() {
                     "proto/dd_trace.proto",
                     "proto/google/pubsub/v1/pubsub.proto",
                     "proto/google/rpc/status.proto",
+                    "proto/profile.proto",
                     "proto/vector.proto",
                 ],
                 &["proto/", "lib/vector-core/proto/"],
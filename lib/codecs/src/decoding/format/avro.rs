@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use bytes::{Buf, Bytes};
+use chrono::{TimeZone, Utc};
+use lookup::PathPrefix;
+use ordered_float::NotNan;
+use smallvec::{smallvec, SmallVec};
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{Event, LogEvent, Value},
+};
+
+use super::Deserializer;
+
+/// The magic byte that starts every message in the [Confluent wire format][wire_format].
+///
+/// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+pub const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// The length of the prefix, the magic byte followed by a 4-byte schema ID, that starts every
+/// message in the Confluent wire format.
+pub const CONFLUENT_PREFIX_LEN: usize = 5;
+
+/// Returns the schema ID of a message in the Confluent wire format, or `None` if the message
+/// doesn't start with a valid prefix.
+pub fn confluent_schema_id(bytes: &[u8]) -> Option<u32> {
+    match bytes {
+        [CONFLUENT_MAGIC_BYTE, a, b, c, d, ..] => Some(u32::from_be_bytes([*a, *b, *c, *d])),
+        _ => None,
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a single Avro datum.
+#[derive(Debug, Clone)]
+pub struct AvroDeserializer {
+    schema: apache_avro::Schema,
+    strip_schema_id_prefix: bool,
+}
+
+impl AvroDeserializer {
+    /// Creates a new `AvroDeserializer`.
+    ///
+    /// When `strip_schema_id_prefix` is set, every frame is expected to start with the Confluent
+    /// wire format prefix, which is skipped before decoding the datum.
+    pub const fn new(schema: apache_avro::Schema, strip_schema_id_prefix: bool) -> Self {
+        Self {
+            schema,
+            strip_schema_id_prefix,
+        }
+    }
+}
+
+impl Deserializer for AvroDeserializer {
+    fn parse(
+        &self,
+        mut bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        if self.strip_schema_id_prefix {
+            if confluent_schema_id(&bytes).is_none() {
+                return Err("Avro message is missing the schema ID prefix.".into());
+            }
+            bytes.advance(CONFLUENT_PREFIX_LEN);
+        }
+
+        let value = apache_avro::from_avro_datum(&self.schema, &mut bytes.reader(), None)
+            .map_err(|error| format!("Error parsing Avro: {}", error))?;
+
+        let value = avro_to_value(value);
+        let log = match log_namespace {
+            LogNamespace::Vector => log_namespace.new_log_from_data(value),
+            LogNamespace::Legacy => {
+                let mut log = match value {
+                    Value::Object(fields) => LogEvent::from(fields),
+                    value => {
+                        let mut log = LogEvent::default();
+                        log.insert(log_schema().message_key(), value);
+                        log
+                    }
+                };
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    if !log.contains((PathPrefix::Event, timestamp_key)) {
+                        log.insert((PathPrefix::Event, timestamp_key), Utc::now());
+                    }
+                }
+
+                log
+            }
+        };
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+fn float_value(value: f64) -> Value {
+    NotNan::new(value).map(Value::Float).unwrap_or(Value::Null)
+}
+
+fn timestamp_value(seconds: i64, nanos: u32) -> Value {
+    Utc.timestamp_opt(seconds, nanos)
+        .single()
+        .map(Value::Timestamp)
+        .unwrap_or(Value::Null)
+}
+
+/// Converts a decoded Avro value into a Vector value.
+fn avro_to_value(value: apache_avro::types::Value) -> Value {
+    use apache_avro::types::Value as AvroValue;
+
+    match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(value) => Value::Boolean(value),
+        AvroValue::Int(value) | AvroValue::Date(value) | AvroValue::TimeMillis(value) => {
+            Value::Integer(value.into())
+        }
+        AvroValue::Long(value) | AvroValue::TimeMicros(value) => Value::Integer(value),
+        AvroValue::Float(value) => float_value(value.into()),
+        AvroValue::Double(value) => float_value(value),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => Value::Bytes(bytes.into()),
+        AvroValue::String(string) | AvroValue::Enum(_, string) => Value::Bytes(string.into()),
+        AvroValue::Union(_, value) => avro_to_value(*value),
+        AvroValue::Array(values) => Value::Array(values.into_iter().map(avro_to_value).collect()),
+        AvroValue::Map(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, avro_to_value(value)))
+                .collect(),
+        ),
+        AvroValue::Record(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, avro_to_value(value)))
+                .collect(),
+        ),
+        AvroValue::Decimal(decimal) => Vec::<u8>::try_from(decimal)
+            .map(|bytes| Value::Bytes(bytes.into()))
+            .unwrap_or(Value::Null),
+        AvroValue::TimestampMillis(millis) => timestamp_value(
+            millis.div_euclid(1_000),
+            (millis.rem_euclid(1_000) * 1_000_000) as u32,
+        ),
+        AvroValue::TimestampMicros(micros) => timestamp_value(
+            micros.div_euclid(1_000_000),
+            (micros.rem_euclid(1_000_000) * 1_000) as u32,
+        ),
+        AvroValue::Duration(duration) => Value::Object(BTreeMap::from([
+            (
+                "months".to_owned(),
+                Value::Integer(u32::from(duration.months()).into()),
+            ),
+            (
+                "days".to_owned(),
+                Value::Integer(u32::from(duration.days()).into()),
+            ),
+            (
+                "milliseconds".to_owned(),
+                Value::Integer(u32::from(duration.millis()).into()),
+            ),
+        ])),
+        AvroValue::Uuid(uuid) => Value::Bytes(uuid.to_string().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+    use indoc::indoc;
+    use tokio_util::codec::Encoder;
+    use value::btreemap;
+
+    use super::*;
+    use crate::encoding::AvroSerializerConfig;
+
+    const SCHEMA: &str = indoc! {r#"
+        {
+            "type": "record",
+            "name": "Log",
+            "fields": [
+                { "name": "message", "type": "string" },
+                { "name": "count", "type": "long" },
+                { "name": "tag", "type": ["null", "string"] }
+            ]
+        }
+    "#};
+
+    fn encode(event: Event) -> BytesMut {
+        let mut serializer = AvroSerializerConfig::new(SCHEMA.to_owned())
+            .build()
+            .unwrap();
+        let mut bytes = BytesMut::new();
+        serializer.encode(event, &mut bytes).unwrap();
+        bytes
+    }
+
+    fn event() -> Event {
+        LogEvent::from(btreemap! {
+            "message" => Value::from("hello"),
+            "count" => Value::from(3),
+            "tag" => Value::from("a"),
+        })
+        .into()
+    }
+
+    #[test]
+    fn deserialize_avro() {
+        let schema = apache_avro::Schema::parse_str(SCHEMA).unwrap();
+        let deserializer = AvroDeserializer::new(schema, false);
+        let bytes = encode(event()).freeze();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(bytes.clone(), namespace).unwrap();
+            assert_eq!(events.len(), 1);
+
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "hello".into());
+            assert_eq!(log["count"], 3.into());
+            assert_eq!(log["tag"], "a".into());
+            assert_eq!(
+                log.get((PathPrefix::Event, log_schema().timestamp_key().unwrap()))
+                    .is_some(),
+                namespace == LogNamespace::Legacy
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_avro_with_schema_id_prefix() {
+        let schema = apache_avro::Schema::parse_str(SCHEMA).unwrap();
+        let deserializer = AvroDeserializer::new(schema, true);
+
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(CONFLUENT_MAGIC_BYTE);
+        bytes.put_u32(42);
+        assert_eq!(confluent_schema_id(&bytes), Some(42));
+        bytes.extend_from_slice(&encode(event()));
+
+        let events = deserializer
+            .parse(bytes.freeze(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log()["message"], "hello".into());
+
+        let error = deserializer.parse(Bytes::from_static(b"\x01\x02"), LogNamespace::Vector);
+        assert!(error.is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::{
+    lookup_v2::{OwnedSegment, OwnedValuePath},
+    PathPrefix,
+};
+use smallvec::SmallVec;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+
+use super::Deserializer;
+
+/// Config used to build a `CsvDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct CsvDeserializerConfig {
+    /// Options for the CSV deserializer.
+    pub csv: CsvDeserializerOptions,
+}
+
+impl CsvDeserializerConfig {
+    /// Creates a new `CsvDeserializerConfig`.
+    pub const fn new(csv: CsvDeserializerOptions) -> Self {
+        Self { csv }
+    }
+
+    /// Build the `CsvDeserializer` from this configuration.
+    pub fn build(&self) -> vector_common::Result<CsvDeserializer> {
+        if self.csv.fields.is_empty() && !self.csv.headers {
+            Err(
+                "The CSV decoder must be configured with `fields`, or with `headers` enabled"
+                    .into(),
+            )
+        } else if self.csv.delimiter == self.csv.quote {
+            Err("The CSV delimiter and quote characters must differ".into())
+        } else {
+            Ok(CsvDeserializer::new(self.csv.clone()))
+        }
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(Kind::bytes());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        // The timestamp is only inserted if the record has no such column.
+                        Kind::bytes().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                Kind::object(value::kind::Collection::from_unknown(Kind::bytes())),
+                [log_namespace],
+            ),
+        }
+    }
+}
+
+/// CSV deserializer options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvDeserializerOptions {
+    /// The names of the fields the columns are decoded into, in order.
+    ///
+    /// The names are used as they are, as top-level fields. They take precedence over the names
+    /// read from the header row, and the columns past them are named from the header row, if any,
+    /// or dropped.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "message"))]
+    pub fields: Vec<String>,
+
+    /// Whether the first record of each input is a header row naming the columns.
+    ///
+    /// An input is a connection of the sources reading streams, and a message, request or
+    /// datagram of the other sources.
+    #[serde(default)]
+    pub headers: bool,
+
+    /// The field delimiter to use when reading CSV.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    pub delimiter: u8,
+
+    /// The quote character to use when reading CSV.
+    #[serde(default = "default_quote", with = "vector_core::serde::ascii_char")]
+    pub quote: u8,
+
+    /// Whether two consecutive quotes in a quoted field stand for a quote.
+    ///
+    /// When disabled, quotes are escaped with `escape` instead.
+    #[serde(default = "default_double_quote")]
+    pub double_quote: bool,
+
+    /// The escape character to use when reading CSV with `double_quote` disabled.
+    #[serde(default = "default_escape", with = "vector_core::serde::ascii_char")]
+    pub escape: u8,
+}
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+const fn default_double_quote() -> bool {
+    true
+}
+
+const fn default_escape() -> u8 {
+    b'\\'
+}
+
+impl Default for CsvDeserializerOptions {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            headers: false,
+            delimiter: default_delimiter(),
+            quote: default_quote(),
+            double_quote: default_double_quote(),
+            escape: default_escape(),
+        }
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing CSV records.
+///
+/// Each record is decoded into an event. When the options enable `headers`, the first record
+/// parsed is kept as the header row, which is why each input is parsed by a clone of the
+/// deserializer: clones start without a header row.
+#[derive(Debug)]
+pub struct CsvDeserializer {
+    options: CsvDeserializerOptions,
+    header: Mutex<Option<Vec<String>>>,
+}
+
+impl CsvDeserializer {
+    /// Creates a new `CsvDeserializer`.
+    pub const fn new(options: CsvDeserializerOptions) -> Self {
+        Self {
+            options,
+            header: Mutex::new(None),
+        }
+    }
+
+    fn reader<'a>(&self, bytes: &'a [u8]) -> csv::Reader<&'a [u8]> {
+        let mut builder = csv::ReaderBuilder::new();
+        builder
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(self.options.delimiter)
+            .quote(self.options.quote)
+            .double_quote(self.options.double_quote);
+        if !self.options.double_quote {
+            builder.escape(Some(self.options.escape));
+        }
+        builder.from_reader(bytes)
+    }
+
+    fn column_name<'a>(&'a self, header: Option<&'a [String]>, index: usize) -> Option<&'a str> {
+        self.options
+            .fields
+            .get(index)
+            .or_else(|| header.and_then(|header| header.get(index)))
+            .map(String::as_str)
+    }
+}
+
+impl Clone for CsvDeserializer {
+    fn clone(&self) -> Self {
+        Self::new(self.options.clone())
+    }
+}
+
+impl Deserializer for CsvDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let mut header = self.header.lock().expect("CSV header lock poisoned");
+        let mut events = SmallVec::new();
+        for record in self.reader(&bytes).records() {
+            let record = record.map_err(|error| format!("Error parsing CSV: {}", error))?;
+            if self.options.headers && header.is_none() {
+                *header = Some(record.iter().map(str::to_owned).collect());
+                continue;
+            }
+
+            let mut log = LogEvent::default();
+            for (index, value) in record.iter().enumerate() {
+                if let Some(name) = self.column_name(header.as_deref(), index) {
+                    let path = OwnedValuePath::from(vec![OwnedSegment::Field(name.into())]);
+                    log.insert((PathPrefix::Event, &path), value);
+                }
+            }
+
+            if log_namespace == LogNamespace::Legacy {
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    if !log.contains((PathPrefix::Event, timestamp_key)) {
+                        log.insert((PathPrefix::Event, timestamp_key), Utc::now());
+                    }
+                }
+            }
+            events.push(log.into());
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use tokio_util::codec::Encoder;
+    use value::{btreemap, Value};
+
+    use super::*;
+    use crate::encoding::{CsvSerializerConfig, CsvSerializerOptions};
+
+    fn deserialize(deserializer: &CsvDeserializer, input: &str) -> Vec<Value> {
+        deserializer
+            .parse(Bytes::from(input.to_owned()), LogNamespace::Vector)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.into_log().value().clone())
+            .collect()
+    }
+
+    #[test]
+    fn build_error_without_columns() {
+        let err = CsvDeserializerConfig::default().build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The CSV decoder must be configured with `fields`, or with `headers` enabled"
+        );
+    }
+
+    #[test]
+    fn deserialize_configured_fields() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            fields: vec!["host".to_owned(), "message".to_owned()],
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            deserialize(
+                &deserializer,
+                "web-1,\"said \"\"hi\"\", then left\"\nweb-2\nweb-3,bye,extra"
+            ),
+            vec![
+                Value::from(btreemap! {
+                    "host" => Value::from("web-1"),
+                    "message" => Value::from("said \"hi\", then left"),
+                }),
+                Value::from(btreemap! {
+                    "host" => Value::from("web-2"),
+                }),
+                Value::from(btreemap! {
+                    "host" => Value::from("web-3"),
+                    "message" => Value::from("bye"),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_header_row() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            fields: vec!["name".to_owned()],
+            headers: true,
+            delimiter: b';',
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        assert!(deserialize(&deserializer, "user;count").is_empty());
+        assert_eq!(
+            deserialize(&deserializer, "ada;3"),
+            vec![Value::from(btreemap! {
+                "count" => Value::from("3"),
+                "name" => Value::from("ada"),
+            })]
+        );
+
+        // Clones parse a new input, which starts with its own header row.
+        let clone = deserializer.clone();
+        assert_eq!(
+            deserialize(&clone, "user;total\nada;4"),
+            vec![Value::from(btreemap! {
+                "name" => Value::from("ada"),
+                "total" => Value::from("4"),
+            })]
+        );
+    }
+
+    #[test]
+    fn deserialize_errors() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            headers: true,
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+        assert!(deserializer
+            .parse(Bytes::from_static(b"a,\xff"), LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn round_trips_through_the_serializer() {
+        let value = Value::from(btreemap! {
+            "message" => Value::from("a \"quoted\", multi\nline message"),
+            "status" => Value::from("200"),
+        });
+        let mut bytes = BytesMut::new();
+        CsvSerializerConfig::new(CsvSerializerOptions {
+            header: true,
+            ..Default::default()
+        })
+        .build()
+        .unwrap()
+        .encode(Event::from(LogEvent::from(value.clone())), &mut bytes)
+        .unwrap();
+
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            headers: true,
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+        assert_eq!(
+            deserialize(&deserializer, std::str::from_utf8(&bytes).unwrap()),
+            vec![value]
+        );
+    }
+}
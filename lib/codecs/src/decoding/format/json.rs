@@ -6,6 +6,7 @@ use lookup::PathPrefix;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
 use value::Kind;
+use vector_config::configurable_component;
 use vector_core::{
     config::{log_schema, DataType, LogNamespace},
     event::Event,
@@ -16,9 +17,17 @@ use super::Deserializer;
 
 /// Config used to build a `JsonDeserializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct JsonDeserializerConfig;
+pub struct JsonDeserializerConfig {
+    /// Options for the JSON deserializer.
+    pub json: JsonDeserializerOptions,
+}
 
 impl JsonDeserializerConfig {
+    /// Creates a new `JsonDeserializerConfig`.
+    pub const fn new(json: JsonDeserializerOptions) -> Self {
+        Self { json }
+    }
+
     /// Build the `JsonDeserializer` from this configuration.
     pub fn build(&self) -> JsonDeserializer {
         Into::<JsonDeserializer>::into(self)
@@ -54,21 +63,65 @@ impl JsonDeserializerConfig {
     }
 }
 
-impl JsonDeserializerConfig {
-    /// Creates a new `JsonDeserializerConfig`.
-    pub fn new() -> Self {
-        Default::default()
-    }
+/// JSON deserializer options.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonDeserializerOptions {
+    /// Whether to parse the JSON with SIMD instructions.
+    ///
+    /// Parsing with SIMD instructions is faster on large or numerous events, at the cost of
+    /// copying each frame before parsing it. It falls back to the default parser on CPUs which
+    /// don't support the required instructions, AVX2 or SSE4.2 on x86-64 and NEON on AArch64, so
+    /// the events are the same whichever parser is used.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub simd: bool,
 }
 
 /// Deserializer that builds `Event`s from a byte frame containing JSON.
 #[derive(Debug, Clone, Default)]
-pub struct JsonDeserializer;
+pub struct JsonDeserializer {
+    simd: bool,
+}
 
 impl JsonDeserializer {
     /// Creates a new `JsonDeserializer`.
-    pub fn new() -> Self {
-        Default::default()
+    ///
+    /// The JSON is parsed with SIMD instructions if `simd` is set and the CPU supports them.
+    pub fn new(simd: bool) -> Self {
+        Self {
+            simd: simd && simd_supported(),
+        }
+    }
+}
+
+/// Whether the CPU supports the instructions `simd-json` parses with.
+fn simd_supported() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        std::is_x86_feature_detected!("avx2") || std::is_x86_feature_detected!("sse4.2")
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        std::arch::is_aarch64_feature_detected!("neon")
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        false
+    }
+}
+
+fn parse_json(bytes: &[u8], simd: bool) -> vector_common::Result<serde_json::Value> {
+    if simd {
+        // `simd-json` parses in place, so it needs a copy of the frame it can modify.
+        let mut bytes = bytes.to_vec();
+        simd_json::serde::from_slice(&mut bytes)
+            .map_err(|error| format!("Error parsing JSON: {:?}", error).into())
+    } else {
+        serde_json::from_slice(bytes)
+            .map_err(|error| format!("Error parsing JSON: {:?}", error).into())
     }
 }
 
@@ -84,8 +137,7 @@ impl Deserializer for JsonDeserializer {
             return Ok(smallvec![]);
         }
 
-        let json: serde_json::Value = serde_json::from_slice(&bytes)
-            .map_err(|error| format!("Error parsing JSON: {:?}", error))?;
+        let json = parse_json(&bytes, self.simd)?;
 
         // If the root is an Array, split it into multiple events
         let mut events = match json {
@@ -119,8 +171,8 @@ impl Deserializer for JsonDeserializer {
 }
 
 impl From<&JsonDeserializerConfig> for JsonDeserializer {
-    fn from(_: &JsonDeserializerConfig) -> Self {
-        Self
+    fn from(config: &JsonDeserializerConfig) -> Self {
+        Self::new(config.json.simd)
     }
 }
 
@@ -133,7 +185,7 @@ mod tests {
     #[test]
     fn deserialize_json() {
         let input = Bytes::from(r#"{ "foo": 123 }"#);
-        let deserializer = JsonDeserializer::new();
+        let deserializer = JsonDeserializer::default();
 
         for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
             let events = deserializer.parse(input.clone(), namespace).unwrap();
@@ -160,7 +212,7 @@ mod tests {
     #[test]
     fn deserialize_json_array() {
         let input = Bytes::from(r#"[{ "foo": 123 }, { "bar": 456 }]"#);
-        let deserializer = JsonDeserializer::new();
+        let deserializer = JsonDeserializer::default();
         for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
             let events = deserializer.parse(input.clone(), namespace).unwrap();
             let mut events = events.into_iter();
@@ -197,7 +249,7 @@ mod tests {
     #[test]
     fn deserialize_skip_empty() {
         let input = Bytes::from("");
-        let deserializer = JsonDeserializer::new();
+        let deserializer = JsonDeserializer::default();
 
         for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
             let events = deserializer.parse(input.clone(), namespace).unwrap();
@@ -205,10 +257,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserialize_simd_parity() {
+        let inputs = [
+            r#"{ "foo": 123, "bar": [1.5, -2, true, null], "baz": { "qux": "\u00e9\n" } }"#,
+            r#"[{ "foo": 123 }, { "bar": 12345678901234 }]"#,
+            r#"{ "foo": "unterminated"#,
+            "",
+        ];
+        let default = JsonDeserializer::default();
+        let simd = JsonDeserializer::new(true);
+
+        for input in inputs {
+            let input = Bytes::from(input);
+            let expected = default.parse(input.clone(), LogNamespace::Vector);
+            let events = simd.parse(input, LogNamespace::Vector);
+            match (expected, events) {
+                (Ok(expected), Ok(events)) => assert_eq!(expected, events),
+                (Err(_), Err(_)) => {}
+                (expected, events) => panic!("{:?} != {:?}", expected, events),
+            }
+        }
+    }
+
     #[test]
     fn deserialize_error_invalid_json() {
         let input = Bytes::from("{ foo");
-        let deserializer = JsonDeserializer::new();
+        let deserializer = JsonDeserializer::default();
 
         for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
             assert!(deserializer.parse(input.clone(), namespace).is_err());
@@ -0,0 +1,386 @@
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::{
+    lookup_v2::{OwnedSegment, OwnedValuePath},
+    PathPrefix,
+};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+use crate::logfmt::{default_flatten_separator, infer_value, unflatten_key};
+
+/// Config used to build a `LogfmtDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtDeserializerConfig {
+    /// Options for the logfmt deserializer.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub logfmt: LogfmtDeserializerOptions,
+}
+
+impl LogfmtDeserializerConfig {
+    /// Creates a new `LogfmtDeserializerConfig`.
+    pub const fn new(logfmt: LogfmtDeserializerOptions) -> Self {
+        Self { logfmt }
+    }
+
+    /// Build the `LogfmtDeserializer` from this configuration.
+    pub fn build(&self) -> LogfmtDeserializer {
+        LogfmtDeserializer::new(self.logfmt.clone())
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let kind = if self.logfmt.infer_types {
+            Kind::any()
+        } else if self.logfmt.unflatten {
+            Kind::json()
+        } else {
+            Kind::bytes().or_boolean()
+        };
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(kind.clone());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        // The timestamp is only inserted if the message has no such key.
+                        kind.or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                Kind::object(value::kind::Collection::from_unknown(kind)),
+                [log_namespace],
+            ),
+        }
+    }
+}
+
+/// Logfmt deserializer options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogfmtDeserializerOptions {
+    /// Whether to infer the types of unquoted values.
+    ///
+    /// When enabled, `true` and `false` are decoded as booleans, numbers as integers or floats,
+    /// RFC 3339 timestamps as timestamps, and empty values as null. Quoted values are always
+    /// decoded as strings. When disabled, all the values are decoded as strings.
+    ///
+    /// Keys without a value are decoded as `true` either way.
+    #[serde(default = "default_true")]
+    pub infer_types: bool,
+
+    /// Whether to unflatten keys into nested fields.
+    ///
+    /// When enabled, keys are split on `flatten_separator` into nested objects, and the parts
+    /// suffixed with an index in brackets, such as `tags[0]`, into arrays. Keys that can't be
+    /// split, such as ones with empty parts, are kept as they are.
+    #[serde(default = "default_true")]
+    pub unflatten: bool,
+
+    /// The separator joining the names of nested fields into keys.
+    #[serde(default = "default_flatten_separator")]
+    pub flatten_separator: String,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+impl Default for LogfmtDeserializerOptions {
+    fn default() -> Self {
+        Self {
+            infer_types: true,
+            unflatten: true,
+            flatten_separator: default_flatten_separator(),
+        }
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a logfmt message.
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtDeserializer {
+    options: LogfmtDeserializerOptions,
+}
+
+impl LogfmtDeserializer {
+    /// Creates a new `LogfmtDeserializer`.
+    pub const fn new(options: LogfmtDeserializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn value(&self, value: Option<Token<'_>>) -> Value {
+        match value {
+            // A key without a value is a flag.
+            None => Value::Boolean(true),
+            Some(Token::Quoted(value)) => Value::from(value),
+            Some(Token::Unquoted(value)) if self.options.infer_types => infer_value(value),
+            Some(Token::Unquoted(value)) => Value::from(value),
+        }
+    }
+}
+
+enum Token<'a> {
+    Quoted(String),
+    Unquoted(&'a str),
+}
+
+/// Parses the key-value pairs of a logfmt message.
+fn parse_pairs(message: &str) -> vector_common::Result<Vec<(&str, Option<Token<'_>>)>> {
+    let mut pairs = Vec::new();
+    let mut rest = message;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c <= ' ');
+        if rest.is_empty() {
+            return Ok(pairs);
+        }
+
+        let key_end = rest
+            .find(|c: char| c <= ' ' || c == '=')
+            .unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        rest = &rest[key_end..];
+        if key.is_empty() {
+            return Err("Error parsing logfmt: expected a key before `=`.".into());
+        }
+        if !rest.starts_with('=') {
+            pairs.push((key, None));
+            continue;
+        }
+
+        rest = &rest[1..];
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let (value, len) = parse_quoted(quoted).ok_or_else(|| {
+                format!(
+                    "Error parsing logfmt: unterminated quoted value of key {:?}.",
+                    key
+                )
+            })?;
+            pairs.push((key, Some(Token::Quoted(value))));
+            rest = &quoted[len..];
+        } else {
+            let value_end = rest.find(|c: char| c <= ' ').unwrap_or(rest.len());
+            pairs.push((key, Some(Token::Unquoted(&rest[..value_end]))));
+            rest = &rest[value_end..];
+        }
+    }
+}
+
+/// Parses a quoted value following its opening quote, returning it along with the length of its
+/// text including the closing quote.
+fn parse_quoted(quoted: &str) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, index + 1)),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let start = chars.next()?.0;
+                    let hex = quoted.get(start..start + 4)?;
+                    value.push(char::from_u32(u32::from_str_radix(hex, 16).ok()?)?);
+                    chars.nth(2)?;
+                }
+                // Other escaped characters, such as quotes and backslashes, stand for themselves.
+                c => value.push(c),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+impl Deserializer for LogfmtDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let message = std::str::from_utf8(&bytes)?;
+        let pairs = parse_pairs(message)?;
+        // Skip empty frames, as for the other line-based codecs.
+        if pairs.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut log = LogEvent::default();
+        for (key, value) in pairs {
+            let value = self.value(value);
+            if self.options.unflatten {
+                let path = unflatten_key(key, &self.options.flatten_separator);
+                log.insert((PathPrefix::Event, &path), value);
+            } else {
+                let path = OwnedValuePath::from(vec![OwnedSegment::Field(key.into())]);
+                log.insert((PathPrefix::Event, &path), value);
+            }
+        }
+
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                if !log.contains((PathPrefix::Event, timestamp_key)) {
+                    log.insert((PathPrefix::Event, timestamp_key), Utc::now());
+                }
+            }
+        }
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+impl From<&LogfmtDeserializerConfig> for LogfmtDeserializer {
+    fn from(config: &LogfmtDeserializerConfig) -> Self {
+        Self::new(config.logfmt.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::TimeZone;
+    use ordered_float::NotNan;
+    use tokio_util::codec::Encoder;
+    use value::btreemap;
+
+    use super::*;
+    use crate::encoding::{LogfmtSerializer, LogfmtSerializerOptions};
+
+    fn deserialize(options: LogfmtDeserializerOptions, input: &str) -> Value {
+        let events = LogfmtDeserializer::new(options)
+            .parse(Bytes::from(input.to_owned()), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        events
+            .into_iter()
+            .next()
+            .unwrap()
+            .into_log()
+            .value()
+            .clone()
+    }
+
+    #[test]
+    fn deserialize_heroku_router_log() {
+        let input = r#"at=info method=GET path="/users?page=2" fwd="10.0.0.1" dyno=web.1 service=18ms status=200 bytes=1548 tls"#;
+
+        assert_eq!(
+            deserialize(Default::default(), input),
+            Value::from(btreemap! {
+                "at" => Value::from("info"),
+                "bytes" => Value::from(1548),
+                "dyno" => Value::from("web.1"),
+                "fwd" => Value::from("10.0.0.1"),
+                "method" => Value::from("GET"),
+                "path" => Value::from("/users?page=2"),
+                "service" => Value::from("18ms"),
+                "status" => Value::from(200),
+                "tls" => Value::from(true),
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_without_inference() {
+        let options = LogfmtDeserializerOptions {
+            infer_types: false,
+            unflatten: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            deserialize(options, r#"status=200 empty= request.id=1 "#),
+            Value::from(btreemap! {
+                "empty" => Value::from(""),
+                "request.id" => Value::from("1"),
+                "status" => Value::from("200"),
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_quoted_values() {
+        assert_eq!(
+            deserialize(
+                Default::default(),
+                r#"msg="say \"hi\"\n\u00e9" count="3" empty="" null="#
+            ),
+            Value::from(btreemap! {
+                "count" => Value::from("3"),
+                "empty" => Value::from(""),
+                "msg" => Value::from("say \"hi\"\né"),
+                "null" => Value::Null,
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_errors() {
+        let deserializer = LogfmtDeserializer::default();
+        for input in [
+            Bytes::from(r#"msg="unterminated"#),
+            Bytes::from("=value"),
+            Bytes::from_static(b"msg=\xff"),
+        ] {
+            assert!(deserializer.parse(input, LogNamespace::Vector).is_err());
+        }
+    }
+
+    #[test]
+    fn deserialize_skip_empty() {
+        let deserializer = LogfmtDeserializer::default();
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(Bytes::from("  "), namespace).unwrap();
+            assert!(events.is_empty());
+        }
+    }
+
+    #[test]
+    fn round_trips_through_the_serializer() {
+        let timestamp = Utc
+            .timestamp_opt(1_680_350_400, 500_000_000)
+            .single()
+            .unwrap();
+        let value = Value::from(btreemap! {
+            "at" => Value::from("info"),
+            "elapsed" => Value::Float(NotNan::new(0.25).unwrap()),
+            "ok" => Value::from(false),
+            "parent" => Value::Null,
+            "request" => Value::from(btreemap! {
+                "id" => Value::from("42"),
+                "tags" => Value::from(vec![Value::from("a b"), Value::from(1)]),
+            }),
+            "time" => Value::from(timestamp),
+        });
+        let mut bytes = BytesMut::new();
+        LogfmtSerializer::new(LogfmtSerializerOptions::default())
+            .encode(Event::from(LogEvent::from(value.clone())), &mut bytes)
+            .unwrap();
+
+        assert_eq!(
+            deserialize(Default::default(), std::str::from_utf8(&bytes).unwrap()),
+            value
+        );
+    }
+}
@@ -3,25 +3,33 @@
 
 #![deny(missing_docs)]
 
+mod avro;
 mod bytes;
+mod csv;
 mod gelf;
 mod json;
+mod logfmt;
 mod native;
 mod native_json;
+mod protobuf;
 #[cfg(feature = "syslog")]
 mod syslog;
 
 use ::bytes::Bytes;
+pub use avro::{confluent_schema_id, AvroDeserializer, CONFLUENT_MAGIC_BYTE, CONFLUENT_PREFIX_LEN};
 use dyn_clone::DynClone;
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig};
-pub use json::{JsonDeserializer, JsonDeserializerConfig};
+pub use json::{JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions};
+pub use logfmt::{LogfmtDeserializer, LogfmtDeserializerConfig, LogfmtDeserializerOptions};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{NativeJsonDeserializer, NativeJsonDeserializerConfig};
+pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
 use smallvec::SmallVec;
 use vector_core::config::LogNamespace;
 use vector_core::event::Event;
 
 pub use self::bytes::{BytesDeserializer, BytesDeserializerConfig};
+pub use self::csv::{CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions};
 #[cfg(feature = "syslog")]
 pub use self::syslog::{SyslogDeserializer, SyslogDeserializerConfig};
 
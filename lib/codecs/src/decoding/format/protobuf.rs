@@ -0,0 +1,324 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use lookup::PathPrefix;
+use ordered_float::NotNan;
+use prost_reflect::{DynamicMessage, FieldDescriptor, MapKey, MessageDescriptor};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+use crate::protobuf::{get_message_descriptor, TIMESTAMP_MESSAGE};
+
+/// Config used to build a `ProtobufDeserializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtobufDeserializerConfig {
+    /// Options for the Protocol Buffers deserializer.
+    pub protobuf: ProtobufDeserializerOptions,
+}
+
+impl ProtobufDeserializerConfig {
+    /// Creates a new `ProtobufDeserializerConfig`.
+    pub const fn new(protobuf: ProtobufDeserializerOptions) -> Self {
+        Self { protobuf }
+    }
+
+    /// Build the `ProtobufDeserializer` from this configuration.
+    pub fn build(&self) -> vector_common::Result<ProtobufDeserializer> {
+        let message_descriptor =
+            get_message_descriptor(&self.protobuf.desc_file, &self.protobuf.message_type)?;
+        Ok(ProtobufDeserializer::new(message_descriptor))
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        // The Protobuf decoder inserts the current time into the "timestamp_key"
+                        // field, unless the message has a field of that name.
+                        Kind::any().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+    }
+}
+
+/// Protocol Buffers deserializer options.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtobufDeserializerOptions {
+    /// The path to the compiled file descriptor set of the message type.
+    ///
+    /// The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+    #[configurable(metadata(docs::examples = "/etc/vector/protobuf_descriptor_set.desc"))]
+    pub desc_file: PathBuf,
+
+    /// The fully qualified name of the message type to decode.
+    #[configurable(metadata(docs::examples = "package.Message"))]
+    pub message_type: String,
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a single Protocol Buffers
+/// message.
+#[derive(Debug, Clone)]
+pub struct ProtobufDeserializer {
+    message_descriptor: MessageDescriptor,
+}
+
+impl ProtobufDeserializer {
+    /// Creates a new `ProtobufDeserializer`, decoding messages of the given type.
+    pub const fn new(message_descriptor: MessageDescriptor) -> Self {
+        Self { message_descriptor }
+    }
+}
+
+impl Deserializer for ProtobufDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let message = DynamicMessage::decode(self.message_descriptor.clone(), bytes)
+            .map_err(|error| format!("Error parsing Protobuf: {}", error))?;
+
+        let value = message_to_value(&message);
+        let log = match log_namespace {
+            LogNamespace::Vector => log_namespace.new_log_from_data(value),
+            LogNamespace::Legacy => {
+                let mut log = match value {
+                    Value::Object(fields) => LogEvent::from(fields),
+                    value => {
+                        let mut log = LogEvent::default();
+                        log.insert(log_schema().message_key(), value);
+                        log
+                    }
+                };
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    if !log.contains((PathPrefix::Event, timestamp_key)) {
+                        log.insert((PathPrefix::Event, timestamp_key), Utc::now());
+                    }
+                }
+
+                log
+            }
+        };
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+/// Converts a decoded message into a Vector value.
+///
+/// Fields that aren't set are included with their default value, except for message fields and
+/// fields with explicit presence, such as `oneof` members, which are left out.
+fn message_to_value(message: &DynamicMessage) -> Value {
+    let descriptor = message.descriptor();
+    if descriptor.full_name() == TIMESTAMP_MESSAGE {
+        return timestamp_to_value(message);
+    }
+
+    let fields = descriptor
+        .fields()
+        .filter(|field| message.has_field(field) || !has_presence(field))
+        .map(|field| {
+            let value = field_to_value(&field, &message.get_field(&field));
+            (field.name().to_owned(), value)
+        })
+        .collect::<BTreeMap<_, _>>();
+    Value::Object(fields)
+}
+
+fn has_presence(field: &FieldDescriptor) -> bool {
+    field.containing_oneof().is_some()
+        || (field.kind().as_message().is_some() && !field.is_list() && !field.is_map())
+}
+
+fn timestamp_to_value(message: &DynamicMessage) -> Value {
+    let seconds = message
+        .get_field_by_name("seconds")
+        .and_then(|value| value.as_i64())
+        .unwrap_or_default();
+    let nanos = message
+        .get_field_by_name("nanos")
+        .and_then(|value| value.as_i32())
+        .unwrap_or_default();
+    u32::try_from(nanos)
+        .ok()
+        .and_then(|nanos| Utc.timestamp_opt(seconds, nanos).single())
+        .map(Value::Timestamp)
+        .unwrap_or(Value::Null)
+}
+
+fn float_value(value: f64) -> Value {
+    NotNan::new(value).map(Value::Float).unwrap_or(Value::Null)
+}
+
+fn field_to_value(field: &FieldDescriptor, value: &prost_reflect::Value) -> Value {
+    use prost_reflect::Value as ProtobufValue;
+
+    match value {
+        ProtobufValue::Bool(value) => Value::Boolean(*value),
+        ProtobufValue::I32(value) => Value::Integer((*value).into()),
+        ProtobufValue::I64(value) => Value::Integer(*value),
+        ProtobufValue::U32(value) => Value::Integer((*value).into()),
+        ProtobufValue::U64(value) => i64::try_from(*value)
+            .map(Value::Integer)
+            .unwrap_or_else(|_| float_value(*value as f64)),
+        ProtobufValue::F32(value) => float_value((*value).into()),
+        ProtobufValue::F64(value) => float_value(*value),
+        ProtobufValue::String(string) => Value::Bytes(string.clone().into()),
+        ProtobufValue::Bytes(bytes) => Value::Bytes(bytes.clone()),
+        // Enum values are decoded into their name, or their number if it isn't a known value.
+        ProtobufValue::EnumNumber(number) => field
+            .kind()
+            .as_enum()
+            .and_then(|descriptor| descriptor.get_value(*number))
+            .map(|value| Value::Bytes(value.name().to_owned().into()))
+            .unwrap_or_else(|| Value::Integer((*number).into())),
+        ProtobufValue::Message(message) => message_to_value(message),
+        ProtobufValue::List(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| field_to_value(field, value))
+                .collect(),
+        ),
+        ProtobufValue::Map(entries) => {
+            let value_field = field
+                .kind()
+                .as_message()
+                .map(|entry| entry.map_entry_value_field());
+            Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match &value_field {
+                            Some(value_field) => field_to_value(value_field, value),
+                            None => field_to_value(field, value),
+                        };
+                        (map_key_to_string(key), value)
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+fn map_key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(key) => key.to_string(),
+        MapKey::I32(key) => key.to_string(),
+        MapKey::I64(key) => key.to_string(),
+        MapKey::U32(key) => key.to_string(),
+        MapKey::U64(key) => key.to_string(),
+        MapKey::String(key) => key.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use tokio_util::codec::Encoder;
+    use value::btreemap;
+
+    use super::*;
+    use crate::{
+        encoding::ProtobufSerializer,
+        protobuf::{message_descriptor_from_bytes, test::file_descriptor_set},
+    };
+
+    fn descriptor() -> MessageDescriptor {
+        message_descriptor_from_bytes(&file_descriptor_set().encode_to_vec(), "test.Log").unwrap()
+    }
+
+    fn event() -> Event {
+        LogEvent::from(btreemap! {
+            "message" => Value::from("hello"),
+            "count" => Value::from(3),
+            "level" => Value::from("WARN"),
+            "tags" => Value::from(vec![Value::from("a"), Value::from("b")]),
+            "labels" => Value::from(btreemap! { "zone" => Value::from("eu") }),
+        })
+        .into()
+    }
+
+    fn encode(event: Event) -> Bytes {
+        let mut serializer = ProtobufSerializer::new(descriptor());
+        let mut bytes = bytes::BytesMut::new();
+        serializer.encode(event, &mut bytes).unwrap();
+        bytes.freeze()
+    }
+
+    #[test]
+    fn deserialize_protobuf() {
+        let deserializer = ProtobufDeserializer::new(descriptor());
+        let bytes = encode(event());
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(bytes.clone(), namespace).unwrap();
+            assert_eq!(events.len(), 1);
+
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "hello".into());
+            assert_eq!(log["count"], 3.into());
+            assert_eq!(log["level"], "WARN".into());
+            assert_eq!(log["tags"], vec![Value::from("a"), Value::from("b")].into());
+            assert_eq!(
+                log["labels"],
+                btreemap! { "zone" => Value::from("eu") }.into()
+            );
+            assert!(log.get("source").is_none());
+            assert_eq!(
+                log.get((PathPrefix::Event, log_schema().timestamp_key().unwrap()))
+                    .is_some(),
+                namespace == LogNamespace::Legacy
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_protobuf_defaults() {
+        let deserializer = ProtobufDeserializer::new(descriptor());
+
+        let events = deserializer
+            .parse(Bytes::from_static(b"\x0a\x02hi"), LogNamespace::Vector)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "hi".into());
+        assert_eq!(log["count"], 0.into());
+        assert_eq!(log["level"], "INFO".into());
+
+        assert!(deserializer
+            .parse(Bytes::from_static(b"\xff\xff"), LogNamespace::Vector)
+            .is_err());
+    }
+}
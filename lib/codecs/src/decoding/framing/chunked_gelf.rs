@@ -0,0 +1,445 @@
+use std::{
+    collections::HashMap,
+    io::Read,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::{Bytes, BytesMut};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio_util::codec::Decoder;
+use tracing::warn;
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, FramingError};
+use crate::decoding::StreamDecodingError;
+
+/// The magic bytes starting the chunks of a message.
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+/// The length of the header of the chunks: the magic bytes, the message ID, the sequence number and
+/// the sequence count.
+const CHUNK_HEADER_LENGTH: usize = 12;
+
+/// The maximum number of chunks of a message, as per the GELF specification.
+const MAX_CHUNKS: u8 = 128;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Config used to build a `ChunkedGelfDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ChunkedGelfDecoderConfig {
+    /// Options for the chunked GELF decoder.
+    #[serde(default)]
+    pub chunked_gelf: ChunkedGelfDecoderOptions,
+}
+
+impl ChunkedGelfDecoderConfig {
+    /// Build the `ChunkedGelfDecoder` from this configuration.
+    pub fn build(&self) -> ChunkedGelfDecoder {
+        ChunkedGelfDecoder::new(self.chunked_gelf.clone())
+    }
+}
+
+/// Options for building a `ChunkedGelfDecoder`.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChunkedGelfDecoderOptions {
+    /// The time to wait for all the chunks of a message, in seconds.
+    ///
+    /// The messages which are still incomplete after this time are discarded.
+    #[serde(
+        default = "default_timeout_secs",
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub timeout_secs: f64,
+
+    /// The maximum number of messages which are reassembled at the same time.
+    ///
+    /// The chunks starting new messages are discarded once the limit is reached.
+    ///
+    /// By default, there is no limit.
+    #[serde(skip_serializing_if = "vector_core::serde::skip_serializing_if_default")]
+    pub pending_messages_limit: Option<usize>,
+
+    /// The maximum length of the messages, once reassembled and decompressed.
+    ///
+    /// By default, there is no maximum length enforced.
+    #[serde(skip_serializing_if = "vector_core::serde::skip_serializing_if_default")]
+    pub max_length: Option<usize>,
+}
+
+const fn default_timeout_secs() -> f64 {
+    5.0
+}
+
+impl Default for ChunkedGelfDecoderOptions {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            pending_messages_limit: None,
+            max_length: None,
+        }
+    }
+}
+
+/// An error that occurred while reassembling or decompressing a GELF message.
+#[derive(Debug, Snafu)]
+pub enum ChunkedGelfDecoderError {
+    /// The header of a chunk is invalid.
+    #[snafu(display("Invalid GELF chunk: {}", reason))]
+    InvalidChunk {
+        /// What is wrong with the chunk.
+        reason: &'static str,
+    },
+    /// A chunk starts a new message while the limit of pending messages is reached.
+    #[snafu(display(
+        "Discarding GELF chunk, the limit of {} pending messages is reached",
+        limit
+    ))]
+    PendingMessagesLimit {
+        /// The limit of pending messages.
+        limit: usize,
+    },
+    /// The message is longer than the maximum length.
+    #[snafu(display("GELF message is longer than the maximum length of {}", max_length))]
+    MaxLengthExceeded {
+        /// The maximum length of the messages.
+        max_length: usize,
+    },
+    /// The message failed to be decompressed.
+    #[snafu(display("Failed to decompress GELF message: {}", source))]
+    Decompression {
+        /// The decompression error.
+        source: std::io::Error,
+    },
+}
+
+impl StreamDecodingError for ChunkedGelfDecoderError {
+    fn can_continue(&self) -> bool {
+        // Each datagram is a message or a chunk of its own, so the next ones are unaffected.
+        true
+    }
+}
+
+impl FramingError for ChunkedGelfDecoderError {}
+
+impl From<ChunkedGelfDecoderError> for BoxedFramingError {
+    fn from(error: ChunkedGelfDecoderError) -> Self {
+        Box::new(error)
+    }
+}
+
+/// A decoder for the [GELF][gelf] messages sent over UDP, which are possibly chunked and
+/// compressed.
+///
+/// Each datagram holds either a whole message or a chunk of one. The chunks are kept until all
+/// the chunks of their message are received, which is then reassembled. The messages compressed
+/// with gzip or zlib are decompressed.
+///
+/// The clones of the decoder share the pending messages, as the chunks of a message arrive in
+/// separate datagrams.
+///
+/// [gelf]: https://docs.graylog.org/docs/gelf
+#[derive(Debug, Clone)]
+pub struct ChunkedGelfDecoder {
+    timeout: Duration,
+    pending_messages_limit: Option<usize>,
+    max_length: Option<usize>,
+    pending: Arc<Mutex<HashMap<u64, PendingMessage>>>,
+}
+
+#[derive(Debug)]
+struct PendingMessage {
+    started: Instant,
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+}
+
+impl ChunkedGelfDecoder {
+    /// Creates a new `ChunkedGelfDecoder`.
+    pub fn new(options: ChunkedGelfDecoderOptions) -> Self {
+        Self {
+            timeout: Duration::from_secs_f64(options.timeout_secs),
+            pending_messages_limit: options.pending_messages_limit,
+            max_length: options.max_length,
+            pending: Default::default(),
+        }
+    }
+
+    /// Adds a chunk to its message, and returns the message once all of its chunks are received.
+    fn reassemble(&self, chunk: Bytes) -> Result<Option<Bytes>, ChunkedGelfDecoderError> {
+        if chunk.len() < CHUNK_HEADER_LENGTH {
+            return Err(ChunkedGelfDecoderError::InvalidChunk {
+                reason: "truncated header",
+            });
+        }
+        let id = u64::from_be_bytes(chunk[2..10].try_into().expect("the slice has 8 bytes"));
+        let (sequence_number, sequence_count) = (chunk[10], chunk[11]);
+        if sequence_count == 0 || sequence_count > MAX_CHUNKS {
+            return Err(ChunkedGelfDecoderError::InvalidChunk {
+                reason: "invalid sequence count",
+            });
+        }
+        if sequence_number >= sequence_count {
+            return Err(ChunkedGelfDecoderError::InvalidChunk {
+                reason: "sequence number out of range",
+            });
+        }
+
+        let mut pending = self.pending.lock().expect("poisoned lock");
+        let timeout = self.timeout;
+        pending.retain(|_, message| {
+            let expired = message.started.elapsed() > timeout;
+            if expired {
+                warn!(
+                    message = "Discarding incomplete GELF message.",
+                    received_chunks = message.received,
+                    total_chunks = message.chunks.len(),
+                    internal_log_rate_limit = true
+                );
+            }
+            !expired
+        });
+
+        if !pending.contains_key(&id) {
+            if let Some(limit) = self.pending_messages_limit {
+                if pending.len() >= limit {
+                    return Err(ChunkedGelfDecoderError::PendingMessagesLimit { limit });
+                }
+            }
+        }
+        let message = pending.entry(id).or_insert_with(|| PendingMessage {
+            started: Instant::now(),
+            chunks: vec![None; usize::from(sequence_count)],
+            received: 0,
+        });
+        if message.chunks.len() != usize::from(sequence_count) {
+            return Err(ChunkedGelfDecoderError::InvalidChunk {
+                reason: "sequence count differs from the previous chunks",
+            });
+        }
+
+        let slot = &mut message.chunks[usize::from(sequence_number)];
+        if slot.is_none() {
+            *slot = Some(chunk.slice(CHUNK_HEADER_LENGTH..));
+            message.received += 1;
+        }
+        if message.received < message.chunks.len() {
+            return Ok(None);
+        }
+
+        let message = pending.remove(&id).expect("the message is pending");
+        let payload =
+            message
+                .chunks
+                .into_iter()
+                .flatten()
+                .fold(BytesMut::new(), |mut payload, chunk| {
+                    payload.extend_from_slice(&chunk);
+                    payload
+                });
+        Ok(Some(payload.freeze()))
+    }
+
+    fn decompress(&self, payload: Bytes) -> Result<Bytes, ChunkedGelfDecoderError> {
+        let reader: Box<dyn Read + '_> = if payload.starts_with(&GZIP_MAGIC) {
+            Box::new(GzDecoder::new(payload.as_ref()))
+        } else if is_zlib(&payload) {
+            Box::new(ZlibDecoder::new(payload.as_ref()))
+        } else {
+            return self.check_length(payload);
+        };
+
+        // One more byte than the maximum length is read, to tell whether it is exceeded.
+        let limit = self
+            .max_length
+            .map_or(u64::MAX, |max_length| max_length as u64 + 1);
+        let mut decompressed = Vec::new();
+        reader
+            .take(limit)
+            .read_to_end(&mut decompressed)
+            .map_err(|source| ChunkedGelfDecoderError::Decompression { source })?;
+        self.check_length(decompressed.into())
+    }
+
+    fn check_length(&self, payload: Bytes) -> Result<Bytes, ChunkedGelfDecoderError> {
+        match self.max_length {
+            Some(max_length) if payload.len() > max_length => {
+                Err(ChunkedGelfDecoderError::MaxLengthExceeded { max_length })
+            }
+            _ => Ok(payload),
+        }
+    }
+}
+
+impl Default for ChunkedGelfDecoder {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+/// Whether the payload starts with a zlib header, whose first two bytes are a multiple of 31.
+fn is_zlib(payload: &[u8]) -> bool {
+    match payload {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && ((u16::from(*cmf) << 8) | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+impl Decoder for ChunkedGelfDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // The datagrams are only handled once they are whole.
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        let datagram = src.split().freeze();
+        let payload = if datagram.starts_with(&CHUNK_MAGIC) {
+            match self.reassemble(datagram)? {
+                Some(payload) => payload,
+                None => return Ok(None),
+            }
+        } else {
+            datagram
+        };
+        Ok(Some(self.decompress(payload)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{
+        write::{GzEncoder, ZlibEncoder},
+        Compression,
+    };
+
+    use super::*;
+
+    const MESSAGE: &str =
+        r#"{"version":"1.1","host":"example.org","short_message":"A short message"}"#;
+
+    fn chunks(id: u64, payload: &[u8], size: usize) -> Vec<BytesMut> {
+        let count = (payload.len() + size - 1) / size;
+        payload
+            .chunks(size)
+            .enumerate()
+            .map(|(number, data)| {
+                let mut chunk = BytesMut::from(CHUNK_MAGIC.as_slice());
+                chunk.extend_from_slice(&id.to_be_bytes());
+                chunk.extend_from_slice(&[number as u8, count as u8]);
+                chunk.extend_from_slice(data);
+                chunk
+            })
+            .collect()
+    }
+
+    fn decode(decoder: &mut ChunkedGelfDecoder, datagram: &mut BytesMut) -> Option<Bytes> {
+        assert_eq!(decoder.decode(datagram).unwrap(), None);
+        let frame = decoder.decode_eof(datagram).unwrap();
+        assert_eq!(decoder.decode_eof(datagram).unwrap(), None);
+        frame
+    }
+
+    #[test]
+    fn decode_unchunked_messages() {
+        let mut decoder = ChunkedGelfDecoder::default();
+        let mut datagram = BytesMut::from(MESSAGE);
+        assert_eq!(decode(&mut decoder, &mut datagram).unwrap(), MESSAGE);
+    }
+
+    #[test]
+    fn decode_chunked_messages() {
+        let mut decoder = ChunkedGelfDecoder::default();
+        let mut first = chunks(1, MESSAGE.as_bytes(), 10);
+        let mut second = chunks(2, MESSAGE.as_bytes(), 20);
+        let last = first.len() - 1;
+
+        // The chunks are received out of order, interleaved with the chunks of another message,
+        // and through clones of the decoder.
+        first.swap(0, last);
+        for chunk in &mut first[..last] {
+            assert_eq!(decode(&mut decoder.clone(), chunk), None);
+        }
+        for chunk in &mut second[1..] {
+            assert_eq!(decode(&mut decoder, chunk), None);
+        }
+        assert_eq!(decode(&mut decoder, &mut first[last]).unwrap(), MESSAGE);
+        assert_eq!(decode(&mut decoder, &mut second[0]).unwrap(), MESSAGE);
+        assert!(decoder.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn decode_compressed_messages() {
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(MESSAGE.as_bytes()).unwrap();
+        let mut datagram = BytesMut::from(gzip.finish().unwrap().as_slice());
+        assert_eq!(decode(&mut decoder, &mut datagram).unwrap(), MESSAGE);
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(MESSAGE.as_bytes()).unwrap();
+        let mut chunks = chunks(3, &zlib.finish().unwrap(), 16);
+        let (last, rest) = chunks.split_last_mut().unwrap();
+        for chunk in rest {
+            assert_eq!(decode(&mut decoder, chunk), None);
+        }
+        assert_eq!(decode(&mut decoder, last).unwrap(), MESSAGE);
+    }
+
+    #[test]
+    fn discard_expired_messages() {
+        let mut decoder = ChunkedGelfDecoder::new(ChunkedGelfDecoderOptions {
+            timeout_secs: 0.0,
+            pending_messages_limit: Some(1),
+            ..Default::default()
+        });
+        let mut first = chunks(1, MESSAGE.as_bytes(), 50);
+        let mut second = chunks(2, MESSAGE.as_bytes(), 50);
+
+        assert_eq!(decode(&mut decoder, &mut first[0]), None);
+        std::thread::sleep(Duration::from_millis(1));
+        // The first message expired, which makes room for the second one.
+        assert_eq!(decode(&mut decoder, &mut second[0]), None);
+        assert_eq!(decoder.pending.lock().unwrap().len(), 1);
+        assert!(decoder.pending.lock().unwrap().contains_key(&2));
+    }
+
+    #[test]
+    fn reject_invalid_messages() {
+        let mut decoder = ChunkedGelfDecoder::new(ChunkedGelfDecoderOptions {
+            pending_messages_limit: Some(1),
+            max_length: Some(MESSAGE.len() - 1),
+            ..Default::default()
+        });
+
+        let mut chunk = BytesMut::from(&[0x1e, 0x0f, 0, 0][..]);
+        assert!(decoder.decode_eof(&mut chunk).is_err());
+
+        let mut chunk = chunks(1, MESSAGE.as_bytes(), 50).remove(0);
+        chunk[10] = 2;
+        assert!(decoder.decode_eof(&mut chunk).is_err());
+
+        let mut first = chunks(1, MESSAGE.as_bytes(), 50);
+        let mut second = chunks(2, MESSAGE.as_bytes(), 50);
+        assert_eq!(decode(&mut decoder, &mut first[0]), None);
+        let error = decoder.decode_eof(&mut second[0]).unwrap_err();
+        assert!(error.can_continue());
+
+        let mut datagram = BytesMut::from(MESSAGE);
+        assert!(decoder.decode_eof(&mut datagram).is_err());
+    }
+}
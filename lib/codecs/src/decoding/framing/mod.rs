@@ -5,9 +5,11 @@
 
 mod bytes;
 mod character_delimited;
+mod chunked_gelf;
 mod length_delimited;
 mod newline_delimited;
 mod octet_counting;
+mod varint_length_delimited;
 
 use std::fmt::Debug;
 
@@ -15,6 +17,10 @@ use ::bytes::Bytes;
 pub use character_delimited::{
     CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions,
 };
+pub use chunked_gelf::{
+    ChunkedGelfDecoder, ChunkedGelfDecoderConfig, ChunkedGelfDecoderError,
+    ChunkedGelfDecoderOptions,
+};
 use dyn_clone::DynClone;
 pub use length_delimited::{LengthDelimitedDecoder, LengthDelimitedDecoderConfig};
 pub use newline_delimited::{
@@ -24,6 +30,9 @@ pub use octet_counting::{
     OctetCountingDecoder, OctetCountingDecoderConfig, OctetCountingDecoderOptions,
 };
 use tokio_util::codec::LinesCodecError;
+pub use varint_length_delimited::{
+    VarintLengthDelimitedDecoder, VarintLengthDelimitedDecoderConfig,
+};
 
 pub use self::bytes::{BytesDecoder, BytesDecoderConfig};
 use super::StreamDecodingError;
@@ -0,0 +1,157 @@
+use std::io;
+
+use bytes::{Buf, Bytes, BytesMut};
+use prost::encoding::decode_varint;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+
+use super::BoxedFramingError;
+
+/// The maximum length of a varint encoding a 64-bit integer.
+const MAX_VARINT_LENGTH: usize = 10;
+
+/// The maximum length of the frames, as the default of `LengthDelimitedDecoder`.
+const MAX_FRAME_LENGTH: u64 = 8 * 1024 * 1024;
+
+/// Config used to build a `VarintLengthDelimitedDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct VarintLengthDelimitedDecoderConfig;
+
+impl VarintLengthDelimitedDecoderConfig {
+    /// Build the `VarintLengthDelimitedDecoder` from this configuration.
+    pub const fn build(&self) -> VarintLengthDelimitedDecoder {
+        VarintLengthDelimitedDecoder
+    }
+}
+
+/// A codec for handling bytes sequences whose length is encoded as an unsigned varint in a frame
+/// head, as length-delimited protobuf messages are.
+#[derive(Debug, Clone, Default)]
+pub struct VarintLengthDelimitedDecoder;
+
+impl VarintLengthDelimitedDecoder {
+    /// Creates a new `VarintLengthDelimitedDecoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Decoder for VarintLengthDelimitedDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // The length is complete once there's a byte without the continuation bit.
+        if !src
+            .iter()
+            .take(MAX_VARINT_LENGTH)
+            .any(|byte| byte & 0x80 == 0)
+        {
+            return if src.len() < MAX_VARINT_LENGTH {
+                Ok(None)
+            } else {
+                Err(invalid_data("length of frame is not a valid varint").into())
+            };
+        }
+
+        let mut head = &src[..];
+        let length = decode_varint(&mut head).map_err(invalid_data)?;
+        if length > MAX_FRAME_LENGTH {
+            return Err(invalid_data(format!(
+                "frame of {} bytes exceeds the maximum of {} bytes",
+                length, MAX_FRAME_LENGTH
+            ))
+            .into());
+        }
+        let head_length = src.len() - head.len();
+        // The length is at most `MAX_FRAME_LENGTH`, which fits in a `usize`.
+        let frame_length = head_length + length as usize;
+
+        if src.len() < frame_length {
+            src.reserve(frame_length - src.len());
+            return Ok(None);
+        }
+        src.advance(head_length);
+        Ok(Some(src.split_to(length as usize).freeze()))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            None => Err(
+                io::Error::new(io::ErrorKind::UnexpectedEof, "bytes remaining on stream").into(),
+            ),
+        }
+    }
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_frames() {
+        let mut input = BytesMut::from("\x03foo\x03bar");
+        let mut decoder = VarintLengthDelimitedDecoder::new();
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "bar");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_frame_with_multibyte_length() {
+        let frame = "a".repeat(300);
+        let mut input = BytesMut::from(&b"\xac\x02"[..]);
+        input.extend_from_slice(frame.as_bytes());
+        let mut decoder = VarintLengthDelimitedDecoder::new();
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), frame);
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_frame_ignore_unexpected_eof() {
+        let mut input = BytesMut::from("\x03fo");
+        let mut decoder = VarintLengthDelimitedDecoder::new();
+
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+
+        let mut input = BytesMut::from(&b"\xac"[..]);
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_frame_exceeding_maximum_length() {
+        let mut input = BytesMut::from(&b"\xff\xff\xff\xff\x0f"[..]);
+        let mut decoder = VarintLengthDelimitedDecoder::new();
+
+        assert!(decoder.decode(&mut input).is_err());
+    }
+
+    #[test]
+    fn decode_eof_frame_unexpected_eof() {
+        let mut input = BytesMut::from("\x03fo");
+        let mut decoder = VarintLengthDelimitedDecoder::new();
+
+        assert!(decoder.decode_eof(&mut input).is_err());
+    }
+
+    #[test]
+    fn decode_eof_frames() {
+        let mut input = BytesMut::from("\x03foo\x03bar");
+        let mut decoder = VarintLengthDelimitedDecoder::new();
+
+        assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "bar");
+        assert_eq!(decoder.decode_eof(&mut input).unwrap(), None);
+    }
+}
@@ -8,18 +8,24 @@ pub mod framing;
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, NativeDeserializer,
-    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    confluent_schema_id, AvroDeserializer, BoxedDeserializer, BytesDeserializer,
+    BytesDeserializerConfig, CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions,
+    GelfDeserializer, GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig,
+    JsonDeserializerOptions, LogfmtDeserializer, LogfmtDeserializerConfig,
+    LogfmtDeserializerOptions, NativeDeserializer, NativeDeserializerConfig,
+    NativeJsonDeserializer, NativeJsonDeserializerConfig, ProtobufDeserializer,
+    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
 };
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
-    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
+    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, ChunkedGelfDecoderError, ChunkedGelfDecoderOptions, FramingError,
     LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
     NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
-    OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    OctetCountingDecoderConfig, OctetCountingDecoderOptions, VarintLengthDelimitedDecoder,
+    VarintLengthDelimitedDecoderConfig,
 };
 use smallvec::SmallVec;
 use std::fmt::Debug;
@@ -89,6 +95,21 @@ pub enum FramingConfig {
         character_delimited: CharacterDelimitedDecoderOptions,
     },
 
+    /// Byte frames which are [GELF][gelf] messages sent over UDP, possibly chunked and compressed.
+    ///
+    /// Each datagram holds either a whole message or a chunk of one. The chunks are reassembled
+    /// into their message, and the messages compressed with gzip or zlib are decompressed.
+    ///
+    /// [gelf]: https://docs.graylog.org/docs/gelf
+    ChunkedGelf {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Options for the chunked GELF decoder.
+        chunked_gelf: ChunkedGelfDecoderOptions,
+    },
+
     /// Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length.
     LengthDelimited,
 
@@ -113,6 +134,11 @@ pub enum FramingConfig {
         /// Options for the octet counting decoder.
         octet_counting: OctetCountingDecoderOptions,
     },
+
+    /// Byte frames which are prefixed by an unsigned varint indicating the length.
+    ///
+    /// This is how length-delimited Protocol Buffers messages are framed.
+    VarintLengthDelimited,
 }
 
 impl From<BytesDecoderConfig> for FramingConfig {
@@ -129,6 +155,14 @@ impl From<CharacterDelimitedDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<ChunkedGelfDecoderConfig> for FramingConfig {
+    fn from(config: ChunkedGelfDecoderConfig) -> Self {
+        Self::ChunkedGelf {
+            chunked_gelf: config.chunked_gelf,
+        }
+    }
+}
+
 impl From<LengthDelimitedDecoderConfig> for FramingConfig {
     fn from(_: LengthDelimitedDecoderConfig) -> Self {
         Self::LengthDelimited
@@ -151,6 +185,12 @@ impl From<OctetCountingDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<VarintLengthDelimitedDecoderConfig> for FramingConfig {
+    fn from(_: VarintLengthDelimitedDecoderConfig) -> Self {
+        Self::VarintLengthDelimited
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
     pub fn build(&self) -> Framer {
@@ -164,6 +204,12 @@ impl FramingConfig {
                 }
                 .build(),
             ),
+            FramingConfig::ChunkedGelf { chunked_gelf } => Framer::ChunkedGelf(
+                ChunkedGelfDecoderConfig {
+                    chunked_gelf: chunked_gelf.clone(),
+                }
+                .build(),
+            ),
             FramingConfig::LengthDelimited => {
                 Framer::LengthDelimited(LengthDelimitedDecoderConfig.build())
             }
@@ -179,6 +225,9 @@ impl FramingConfig {
                 }
                 .build(),
             ),
+            FramingConfig::VarintLengthDelimited => {
+                Framer::VarintLengthDelimited(VarintLengthDelimitedDecoderConfig.build())
+            }
         }
     }
 }
@@ -190,12 +239,16 @@ pub enum Framer {
     Bytes(BytesDecoder),
     /// Uses a `CharacterDelimitedDecoder` for framing.
     CharacterDelimited(CharacterDelimitedDecoder),
+    /// Uses a `ChunkedGelfDecoder` for framing.
+    ChunkedGelf(ChunkedGelfDecoder),
     /// Uses a `LengthDelimitedDecoder` for framing.
     LengthDelimited(LengthDelimitedDecoder),
     /// Uses a `NewlineDelimitedDecoder` for framing.
     NewlineDelimited(NewlineDelimitedDecoder),
     /// Uses a `OctetCountingDecoder` for framing.
     OctetCounting(OctetCountingDecoder),
+    /// Uses a `VarintLengthDelimitedDecoder` for framing.
+    VarintLengthDelimited(VarintLengthDelimitedDecoder),
     /// Uses an opaque `Framer` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -208,9 +261,11 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode(src),
             Framer::CharacterDelimited(framer) => framer.decode(src),
+            Framer::ChunkedGelf(framer) => framer.decode(src),
             Framer::LengthDelimited(framer) => framer.decode(src),
             Framer::NewlineDelimited(framer) => framer.decode(src),
             Framer::OctetCounting(framer) => framer.decode(src),
+            Framer::VarintLengthDelimited(framer) => framer.decode(src),
             Framer::Boxed(framer) => framer.decode(src),
         }
     }
@@ -219,9 +274,11 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode_eof(src),
             Framer::CharacterDelimited(framer) => framer.decode_eof(src),
+            Framer::ChunkedGelf(framer) => framer.decode_eof(src),
             Framer::LengthDelimited(framer) => framer.decode_eof(src),
             Framer::NewlineDelimited(framer) => framer.decode_eof(src),
             Framer::OctetCounting(framer) => framer.decode_eof(src),
+            Framer::VarintLengthDelimited(framer) => framer.decode_eof(src),
             Framer::Boxed(framer) => framer.decode_eof(src),
         }
     }
@@ -240,10 +297,44 @@ pub enum DeserializerConfig {
     /// Uses the raw bytes as-is.
     Bytes,
 
+    /// Decodes the raw bytes as [CSV][csv] records.
+    ///
+    /// Each record is decoded into an event, with its columns named from the configured `fields`,
+    /// or from a header row.
+    ///
+    /// [csv]: https://datatracker.ietf.org/doc/html/rfc4180
+    Csv {
+        /// CSV-specific decoding options.
+        csv: CsvDeserializerOptions,
+    },
+
     /// Decodes the raw bytes as [JSON][json].
     ///
     /// [json]: https://www.json.org/
-    Json,
+    Json {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// JSON-specific decoding options.
+        json: JsonDeserializerOptions,
+    },
+
+    /// Decodes the raw bytes as a [logfmt][logfmt] message.
+    ///
+    /// The types of unquoted values are inferred, and keys of nested fields are unflattened, so
+    /// that the events encoded with the `logfmt` encoder are decoded with the same fields and
+    /// types.
+    ///
+    /// [logfmt]: https://brandur.org/logfmt
+    Logfmt {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Logfmt-specific decoding options.
+        logfmt: LogfmtDeserializerOptions,
+    },
 
     #[cfg(feature = "syslog")]
     /// Decodes the raw bytes as a Syslog message.
@@ -275,6 +366,16 @@ pub enum DeserializerConfig {
     ///
     /// [gelf]: https://docs.graylog.org/docs/gelf
     Gelf,
+
+    /// Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
+    ///
+    /// The message type is looked up in a compiled file descriptor set.
+    ///
+    /// [protobuf]: https://protobuf.dev/
+    Protobuf {
+        /// Protocol Buffers-specific decoding options.
+        protobuf: ProtobufDeserializerOptions,
+    },
 }
 
 impl From<BytesDeserializerConfig> for DeserializerConfig {
@@ -284,8 +385,22 @@ impl From<BytesDeserializerConfig> for DeserializerConfig {
 }
 
 impl From<JsonDeserializerConfig> for DeserializerConfig {
-    fn from(_: JsonDeserializerConfig) -> Self {
-        Self::Json
+    fn from(config: JsonDeserializerConfig) -> Self {
+        Self::Json { json: config.json }
+    }
+}
+
+impl From<CsvDeserializerConfig> for DeserializerConfig {
+    fn from(config: CsvDeserializerConfig) -> Self {
+        Self::Csv { csv: config.csv }
+    }
+}
+
+impl From<LogfmtDeserializerConfig> for DeserializerConfig {
+    fn from(config: LogfmtDeserializerConfig) -> Self {
+        Self::Logfmt {
+            logfmt: config.logfmt,
+        }
     }
 }
 
@@ -302,21 +417,42 @@ impl From<GelfDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<ProtobufDeserializerConfig> for DeserializerConfig {
+    fn from(config: ProtobufDeserializerConfig) -> Self {
+        Self::Protobuf {
+            protobuf: config.protobuf,
+        }
+    }
+}
+
 impl DeserializerConfig {
     /// Build the `Deserializer` from this configuration.
-    pub fn build(&self) -> Deserializer {
+    pub fn build(&self) -> vector_common::Result<Deserializer> {
         match self {
-            DeserializerConfig::Bytes => Deserializer::Bytes(BytesDeserializerConfig.build()),
-            DeserializerConfig::Json => Deserializer::Json(JsonDeserializerConfig.build()),
+            DeserializerConfig::Bytes => Ok(Deserializer::Bytes(BytesDeserializerConfig.build())),
+            DeserializerConfig::Csv { csv } => Ok(Deserializer::Csv(
+                CsvDeserializerConfig::new(csv.clone()).build()?,
+            )),
+            DeserializerConfig::Json { json } => Ok(Deserializer::Json(
+                JsonDeserializerConfig::new(json.clone()).build(),
+            )),
+            DeserializerConfig::Logfmt { logfmt } => Ok(Deserializer::Logfmt(
+                LogfmtDeserializerConfig::new(logfmt.clone()).build(),
+            )),
             #[cfg(feature = "syslog")]
-            DeserializerConfig::Syslog => {
-                Deserializer::Syslog(SyslogDeserializerConfig::default().build())
+            DeserializerConfig::Syslog => Ok(Deserializer::Syslog(
+                SyslogDeserializerConfig::default().build(),
+            )),
+            DeserializerConfig::Native => {
+                Ok(Deserializer::Native(NativeDeserializerConfig.build()))
             }
-            DeserializerConfig::Native => Deserializer::Native(NativeDeserializerConfig.build()),
-            DeserializerConfig::NativeJson => {
-                Deserializer::NativeJson(NativeJsonDeserializerConfig.build())
-            }
-            DeserializerConfig::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
+            DeserializerConfig::NativeJson => Ok(Deserializer::NativeJson(
+                NativeJsonDeserializerConfig.build(),
+            )),
+            DeserializerConfig::Gelf => Ok(Deserializer::Gelf(GelfDeserializerConfig.build())),
+            DeserializerConfig::Protobuf { protobuf } => Ok(Deserializer::Protobuf(
+                ProtobufDeserializerConfig::new(protobuf.clone()).build()?,
+            )),
         }
     }
 
@@ -324,12 +460,18 @@ impl DeserializerConfig {
     pub fn default_stream_framing(&self) -> FramingConfig {
         match self {
             DeserializerConfig::Native => FramingConfig::LengthDelimited,
+            DeserializerConfig::Protobuf { .. } => FramingConfig::VarintLengthDelimited,
             DeserializerConfig::Bytes
-            | DeserializerConfig::Json
-            | DeserializerConfig::Gelf
+            | DeserializerConfig::Csv { .. }
+            | DeserializerConfig::Json { .. }
+            | DeserializerConfig::Logfmt { .. }
             | DeserializerConfig::NativeJson => FramingConfig::NewlineDelimited {
                 newline_delimited: Default::default(),
             },
+            // The GELF messages sent over TCP are delimited by null bytes.
+            DeserializerConfig::Gelf => FramingConfig::CharacterDelimited {
+                character_delimited: CharacterDelimitedDecoderOptions::new(0, None),
+            },
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => FramingConfig::NewlineDelimited {
                 newline_delimited: Default::default(),
@@ -341,12 +483,23 @@ impl DeserializerConfig {
     pub fn output_type(&self) -> DataType {
         match self {
             DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
-            DeserializerConfig::Json => JsonDeserializerConfig.output_type(),
+            DeserializerConfig::Csv { csv } => {
+                CsvDeserializerConfig::new(csv.clone()).output_type()
+            }
+            DeserializerConfig::Json { json } => {
+                JsonDeserializerConfig::new(json.clone()).output_type()
+            }
+            DeserializerConfig::Logfmt { logfmt } => {
+                LogfmtDeserializerConfig::new(logfmt.clone()).output_type()
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig::default().output_type(),
             DeserializerConfig::Native => NativeDeserializerConfig.output_type(),
             DeserializerConfig::NativeJson => NativeJsonDeserializerConfig.output_type(),
             DeserializerConfig::Gelf => GelfDeserializerConfig.output_type(),
+            DeserializerConfig::Protobuf { protobuf } => {
+                ProtobufDeserializerConfig::new(protobuf.clone()).output_type()
+            }
         }
     }
 
@@ -354,7 +507,15 @@ impl DeserializerConfig {
     pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
         match self {
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
-            DeserializerConfig::Json => JsonDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Csv { csv } => {
+                CsvDeserializerConfig::new(csv.clone()).schema_definition(log_namespace)
+            }
+            DeserializerConfig::Json { json } => {
+                JsonDeserializerConfig::new(json.clone()).schema_definition(log_namespace)
+            }
+            DeserializerConfig::Logfmt { logfmt } => {
+                LogfmtDeserializerConfig::new(logfmt.clone()).schema_definition(log_namespace)
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => {
                 SyslogDeserializerConfig::default().schema_definition(log_namespace)
@@ -364,6 +525,9 @@ impl DeserializerConfig {
                 NativeJsonDeserializerConfig.schema_definition(log_namespace)
             }
             DeserializerConfig::Gelf => GelfDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Protobuf { protobuf } => {
+                ProtobufDeserializerConfig::new(protobuf.clone()).schema_definition(log_namespace)
+            }
         }
     }
 
@@ -371,12 +535,12 @@ impl DeserializerConfig {
     pub const fn content_type(&self, framer: &FramingConfig) -> &'static str {
         match (&self, framer) {
             (
-                DeserializerConfig::Json | DeserializerConfig::NativeJson,
+                DeserializerConfig::Json { .. } | DeserializerConfig::NativeJson,
                 FramingConfig::NewlineDelimited { .. },
             ) => "application/x-ndjson",
             (
                 DeserializerConfig::Gelf
-                | DeserializerConfig::Json
+                | DeserializerConfig::Json { .. }
                 | DeserializerConfig::NativeJson,
                 FramingConfig::CharacterDelimited {
                     character_delimited:
@@ -386,12 +550,16 @@ impl DeserializerConfig {
                         },
                 },
             ) => "application/json",
-            (DeserializerConfig::Native, _) => "application/octet-stream",
+            (DeserializerConfig::Csv { .. }, _) => "text/csv",
+            (DeserializerConfig::Native | DeserializerConfig::Protobuf { .. }, _) => {
+                "application/octet-stream"
+            }
             (
-                DeserializerConfig::Json
+                DeserializerConfig::Json { .. }
                 | DeserializerConfig::NativeJson
                 | DeserializerConfig::Bytes
-                | DeserializerConfig::Gelf,
+                | DeserializerConfig::Gelf
+                | DeserializerConfig::Logfmt { .. },
                 _,
             ) => "text/plain",
             #[cfg(feature = "syslog")]
@@ -405,8 +573,12 @@ impl DeserializerConfig {
 pub enum Deserializer {
     /// Uses a `BytesDeserializer` for deserialization.
     Bytes(BytesDeserializer),
+    /// Uses a `CsvDeserializer` for deserialization.
+    Csv(CsvDeserializer),
     /// Uses a `JsonDeserializer` for deserialization.
     Json(JsonDeserializer),
+    /// Uses a `LogfmtDeserializer` for deserialization.
+    Logfmt(LogfmtDeserializer),
     #[cfg(feature = "syslog")]
     /// Uses a `SyslogDeserializer` for deserialization.
     Syslog(SyslogDeserializer),
@@ -418,6 +590,8 @@ pub enum Deserializer {
     Boxed(BoxedDeserializer),
     /// Uses a `GelfDeserializer` for deserialization.
     Gelf(GelfDeserializer),
+    /// Uses a `ProtobufDeserializer` for deserialization.
+    Protobuf(ProtobufDeserializer),
 }
 
 impl format::Deserializer for Deserializer {
@@ -428,13 +602,16 @@ impl format::Deserializer for Deserializer {
     ) -> vector_common::Result<SmallVec<[Event; 1]>> {
         match self {
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Csv(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Json(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Logfmt(deserializer) => deserializer.parse(bytes, log_namespace),
             #[cfg(feature = "syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Native(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::NativeJson(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Boxed(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Gelf(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Protobuf(deserializer) => deserializer.parse(bytes, log_namespace),
         }
     }
 }
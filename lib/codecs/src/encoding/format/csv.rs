@@ -1,7 +1,7 @@
 use crate::encoding::BuildError;
 use bytes::{BufMut, BytesMut};
 use chrono::SecondsFormat;
-use lookup::lookup_v2::ConfigTargetPath;
+use lookup::lookup_v2::{ConfigTargetPath, OwnedSegment, OwnedTargetPath, OwnedValuePath};
 use tokio_util::codec::Encoder;
 use vector_core::{
     config::DataType,
@@ -11,9 +11,10 @@ use vector_core::{
 
 /// Config used to build a `CsvSerializer`.
 #[crate::configurable_component]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CsvSerializerConfig {
     /// The CSV Serializer Options.
+    #[serde(default)]
     pub csv: CsvSerializerOptions,
 }
 
@@ -25,10 +26,10 @@ impl CsvSerializerConfig {
 
     /// Build the `CsvSerializer` from this configuration.
     pub fn build(&self) -> Result<CsvSerializer, BuildError> {
-        if self.csv.fields.is_empty() {
-            Err("At least one CSV field must be specified".into())
+        if self.csv.delimiter == self.csv.quote {
+            Err("The CSV delimiter and quote characters must differ".into())
         } else {
-            Ok(CsvSerializer::new(self.csv.fields.clone()))
+            Ok(CsvSerializer::new(self.csv.clone()))
         }
     }
 
@@ -45,6 +46,43 @@ impl CsvSerializerConfig {
     }
 }
 
+/// The quoting style to use when writing CSV data.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStyle {
+    /// Always puts quotes around every field.
+    Always,
+
+    /// Puts quotes around fields only when necessary.
+    ///
+    /// They are necessary when fields contain a quote, delimiter, or record terminator. Quotes are
+    /// also necessary when writing an empty record (which is indistinguishable from a record with
+    /// one empty field).
+    #[default]
+    Necessary,
+
+    /// Puts quotes around all fields that are non-numeric.
+    ///
+    /// This means that when writing a field that does not parse as a valid float or integer,
+    /// quotes are used even if they aren't strictly necessary.
+    NonNumeric,
+
+    /// Never writes quotes, even if it produces invalid CSV data.
+    Never,
+}
+
+impl From<QuoteStyle> for csv::QuoteStyle {
+    fn from(style: QuoteStyle) -> Self {
+        match style {
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+        }
+    }
+}
+
 /// Config used to build a `CsvSerializer`.
 #[crate::configurable_component]
 #[derive(Debug, Clone)]
@@ -56,19 +94,124 @@ pub struct CsvSerializerOptions {
     ///
     /// Values of type `Array`, `Object`, and `Regex` are not supported and the
     /// output will be an empty string.
+    ///
+    /// If no fields are configured, the top-level fields of the first event encoded are used, in
+    /// alphabetical order, for that event and the ones following it in the same output.
+    #[serde(default)]
     pub fields: Vec<ConfigTargetPath>,
+
+    /// Whether to write a header row with the names of the fields.
+    ///
+    /// The header row is written before the first event of each output: each batch of the sinks
+    /// sending batches of events, each file of the `file` sink, each connection of the sinks
+    /// streaming events, and each message of the sinks sending an event per message.
+    #[serde(default)]
+    pub header: bool,
+
+    /// The field delimiter to use when writing CSV.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    pub delimiter: u8,
+
+    /// The quote character to use when writing CSV.
+    #[serde(default = "default_quote", with = "vector_core::serde::ascii_char")]
+    pub quote: u8,
+
+    /// The quoting style to use when writing CSV data.
+    #[serde(default)]
+    pub quote_style: QuoteStyle,
+
+    /// Whether to escape quotes in fields by doubling them.
+    ///
+    /// When disabled, quotes are escaped with `escape` instead.
+    #[serde(default = "default_double_quote")]
+    pub double_quote: bool,
+
+    /// The escape character to use when writing CSV with `double_quote` disabled.
+    #[serde(default = "default_escape", with = "vector_core::serde::ascii_char")]
+    pub escape: u8,
+}
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+const fn default_double_quote() -> bool {
+    true
+}
+
+const fn default_escape() -> u8 {
+    b'\\'
+}
+
+impl Default for CsvSerializerOptions {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            header: false,
+            delimiter: default_delimiter(),
+            quote: default_quote(),
+            quote_style: QuoteStyle::default(),
+            double_quote: default_double_quote(),
+            escape: default_escape(),
+        }
+    }
+}
+
+/// A column of the CSV output.
+#[derive(Debug, Clone)]
+struct Column {
+    name: String,
+    path: OwnedTargetPath,
 }
 
 /// Serializer that converts an `Event` to bytes using the CSV format.
+///
+/// The serializer keeps track of whether it wrote the header row, and of the fields it inferred
+/// from the first event, so a clone of it is used for each output.
 #[derive(Debug, Clone)]
 pub struct CsvSerializer {
-    fields: Vec<ConfigTargetPath>,
+    options: CsvSerializerOptions,
+    columns: Vec<Column>,
+    header_written: bool,
 }
 
 impl CsvSerializer {
     /// Creates a new `CsvSerializer`.
-    pub const fn new(fields: Vec<ConfigTargetPath>) -> Self {
-        Self { fields }
+    pub fn new(options: CsvSerializerOptions) -> Self {
+        let columns = options
+            .fields
+            .iter()
+            .map(|field| Column {
+                name: String::from(field.0.path.clone()),
+                path: field.0.clone(),
+            })
+            .collect();
+        Self {
+            options,
+            columns,
+            header_written: false,
+        }
+    }
+
+    /// Marks the output as already started, such as when appending to an existing file, so
+    /// that no header row is written to it.
+    pub fn set_output_started(&mut self) {
+        self.header_written = true;
+    }
+
+    fn writer_builder(&self) -> csv::WriterBuilder {
+        let mut builder = csv::WriterBuilder::new();
+        builder
+            .delimiter(self.options.delimiter)
+            .quote(self.options.quote)
+            .quote_style(self.options.quote_style.into())
+            .double_quote(self.options.double_quote)
+            .escape(self.options.escape);
+        builder
     }
 }
 
@@ -77,9 +220,27 @@ impl Encoder<Event> for CsvSerializer {
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         let log = event.into_log();
-        let mut wtr = csv::Writer::from_writer(buffer.writer());
-        for field in &self.fields {
-            match log.get(field) {
+        if self.options.fields.is_empty() && self.columns.is_empty() {
+            if let Value::Object(map) = log.value() {
+                self.columns = map
+                    .keys()
+                    .map(|key| Column {
+                        name: key.clone(),
+                        path: OwnedTargetPath::event(OwnedValuePath::from(vec![
+                            OwnedSegment::Field(key.as_str().into()),
+                        ])),
+                    })
+                    .collect();
+            }
+        }
+
+        let mut wtr = self.writer_builder().from_writer(buffer.writer());
+        if self.options.header && !self.header_written {
+            wtr.write_record(self.columns.iter().map(|column| column.name.as_str()))?;
+            self.header_written = true;
+        }
+        for column in &self.columns {
+            match log.get(&column.path) {
                 Some(Value::Bytes(bytes)) => {
                     wtr.write_field(String::from_utf8_lossy(bytes).to_string())?
                 }
@@ -110,12 +271,93 @@ mod tests {
 
     use super::*;
 
+    fn serialize(serializer: &mut CsvSerializer, events: Vec<Event>) -> String {
+        let mut bytes = BytesMut::new();
+        for event in events {
+            serializer.encode(event, &mut bytes).unwrap();
+            bytes.extend_from_slice(b"\n");
+        }
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
     #[test]
-    fn build_error_on_empty_fields() {
-        let opts = CsvSerializerOptions { fields: vec![] };
-        let config = CsvSerializerConfig::new(opts);
+    fn build_error_on_same_delimiter_and_quote() {
+        let config = CsvSerializerConfig::new(CsvSerializerOptions {
+            delimiter: b'"',
+            ..Default::default()
+        });
         let err = config.build().unwrap_err();
-        assert_eq!(err.to_string(), "At least one CSV field must be specified");
+        assert_eq!(
+            err.to_string(),
+            "The CSV delimiter and quote characters must differ"
+        );
+    }
+
+    #[test]
+    fn serialize_inferred_fields_with_header() {
+        let config = CsvSerializerConfig::new(CsvSerializerOptions {
+            header: true,
+            ..Default::default()
+        });
+        let mut serializer = config.build().unwrap();
+        let events = vec![
+            Event::Log(LogEvent::from(btreemap! {
+                "status" => Value::from(200),
+                "message" => Value::from("ok"),
+            })),
+            Event::Log(LogEvent::from(btreemap! {
+                "message" => Value::from("not found"),
+                "path" => Value::from("/missing"),
+            })),
+        ];
+
+        assert_eq!(
+            serialize(&mut serializer, events),
+            "message,status\nok,200\nnot found,\n"
+        );
+    }
+
+    #[test]
+    fn serialize_header_once() {
+        let config = CsvSerializerConfig::new(CsvSerializerOptions {
+            fields: vec![ConfigTargetPath::try_from("a.b".to_string()).unwrap()],
+            header: true,
+            ..Default::default()
+        });
+        let mut serializer = config.build().unwrap();
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "a" => Value::from(btreemap! { "b" => Value::from(1) }),
+        }));
+
+        assert_eq!(
+            serialize(&mut serializer.clone(), vec![event.clone(), event.clone()]),
+            "a.b\n1\n1\n"
+        );
+
+        serializer.set_output_started();
+        assert_eq!(serialize(&mut serializer, vec![event]), "1\n");
+    }
+
+    #[test]
+    fn serialize_with_quoting_options() {
+        let config = CsvSerializerConfig::new(CsvSerializerOptions {
+            fields: vec![
+                ConfigTargetPath::try_from("name".to_string()).unwrap(),
+                ConfigTargetPath::try_from("count".to_string()).unwrap(),
+            ],
+            delimiter: b';',
+            quote: b'\'',
+            quote_style: QuoteStyle::NonNumeric,
+            double_quote: false,
+            ..Default::default()
+        });
+        let mut serializer = config.build().unwrap();
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "name" => Value::from("it's"),
+            "count" => Value::from(3),
+        }));
+
+        assert_eq!(serialize(&mut serializer, vec![event]), "'it\\'s';3\n");
     }
 
     #[test]
@@ -143,7 +385,10 @@ mod tests {
             ConfigTargetPath::try_from("quote".to_string()).unwrap(),
             ConfigTargetPath::try_from("bool".to_string()).unwrap(),
         ];
-        let config = CsvSerializerConfig::new(CsvSerializerOptions { fields });
+        let config = CsvSerializerConfig::new(CsvSerializerOptions {
+            fields,
+            ..Default::default()
+        });
         let mut serializer = config.build().unwrap();
         let mut bytes = BytesMut::new();
 
@@ -171,7 +416,10 @@ mod tests {
             ConfigTargetPath::try_from("field3".to_string()).unwrap(),
             ConfigTargetPath::try_from("field2".to_string()).unwrap(),
         ];
-        let config = CsvSerializerConfig::new(CsvSerializerOptions { fields });
+        let config = CsvSerializerConfig::new(CsvSerializerOptions {
+            fields,
+            ..Default::default()
+        });
         let mut serializer = config.build().unwrap();
         let mut bytes = BytesMut::new();
         serializer.encode(event, &mut bytes).unwrap();
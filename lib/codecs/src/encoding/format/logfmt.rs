@@ -1,22 +1,31 @@
 use bytes::BytesMut;
-use serde::{Deserialize, Serialize};
 use tokio_util::codec::Encoder;
-use vector_common::encode_logfmt;
+use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
 
+use crate::logfmt::{default_flatten_separator, flatten, write_key, write_scalar};
+
 /// Config used to build a `LogfmtSerializer`.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct LogfmtSerializerConfig;
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtSerializerConfig {
+    /// Options for the logfmt serializer.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub logfmt: LogfmtSerializerOptions,
+}
 
 impl LogfmtSerializerConfig {
     /// Creates a new `LogfmtSerializerConfig`.
-    pub const fn new() -> Self {
-        Self
+    pub const fn new(logfmt: LogfmtSerializerOptions) -> Self {
+        Self { logfmt }
     }
 
     /// Build the `LogfmtSerializer` from this configuration.
-    pub const fn build(&self) -> LogfmtSerializer {
-        LogfmtSerializer
+    pub fn build(&self) -> LogfmtSerializer {
+        LogfmtSerializer::new(self.logfmt.clone())
     }
 
     /// The data type of events that are accepted by `LogfmtSerializer`.
@@ -32,14 +41,62 @@ impl LogfmtSerializerConfig {
     }
 }
 
+/// Logfmt serializer options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogfmtSerializerOptions {
+    /// The keys to write first, in this order.
+    ///
+    /// The other keys are written after them in alphabetical order. A key listed here also
+    /// brings first the keys flattened from the fields nested in it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[configurable(metadata(docs::examples = "time", docs::examples = "level"))]
+    pub key_order: Vec<String>,
+
+    /// The separator joining the names of nested fields into the keys they are flattened to.
+    ///
+    /// The elements of arrays are flattened to keys suffixed with their index in brackets, such
+    /// as `tags[0]`.
+    #[serde(default = "default_flatten_separator")]
+    pub flatten_separator: String,
+}
+
+impl Default for LogfmtSerializerOptions {
+    fn default() -> Self {
+        Self {
+            key_order: Vec::new(),
+            flatten_separator: default_flatten_separator(),
+        }
+    }
+}
+
 /// Serializer that converts an `Event` to bytes using the logfmt format.
+///
+/// Nested fields are flattened, null values are written as empty values, and the strings that
+/// would be read back as another type, such as `"true"` or `"42"`, are quoted, so that the
+/// events decoded with the logfmt decoder have the same fields and types.
 #[derive(Debug, Clone)]
-pub struct LogfmtSerializer;
+pub struct LogfmtSerializer {
+    options: LogfmtSerializerOptions,
+}
 
 impl LogfmtSerializer {
     /// Creates a new `LogfmtSerializer`.
-    pub const fn new() -> Self {
-        Self
+    pub const fn new(options: LogfmtSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn order(&self, key: &str) -> usize {
+        let separator = &self.options.flatten_separator;
+        self.options
+            .key_order
+            .iter()
+            .position(|first| {
+                key.strip_prefix(first.as_str()).map_or(false, |rest| {
+                    rest.is_empty() || rest.starts_with(separator.as_str()) || rest.starts_with('[')
+                })
+            })
+            .unwrap_or(self.options.key_order.len())
     }
 }
 
@@ -47,8 +104,28 @@ impl Encoder<Event> for LogfmtSerializer {
     type Error = vector_common::Error;
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
-        let log = event.as_log();
-        let string = encode_logfmt::encode_value(log.value())?;
+        let log = event.into_log();
+        let mut pairs = Vec::new();
+        flatten(
+            String::new(),
+            log.value(),
+            &self.options.flatten_separator,
+            &mut pairs,
+        );
+        if !self.options.key_order.is_empty() {
+            // The sort is stable, so the other keys stay in alphabetical order.
+            pairs.sort_by_key(|(key, _)| self.order(key));
+        }
+
+        let mut string = String::new();
+        for (key, value) in pairs {
+            if !string.is_empty() {
+                string.push(' ');
+            }
+            write_key(&key, &mut string);
+            string.push('=');
+            write_scalar(value, &mut string);
+        }
         buffer.extend_from_slice(string.as_bytes());
 
         Ok(())
@@ -59,19 +136,100 @@ impl Encoder<Event> for LogfmtSerializer {
 mod tests {
     use super::*;
     use bytes::BytesMut;
+    use chrono::{TimeZone, Utc};
+    use ordered_float::NotNan;
     use value::btreemap;
     use vector_core::event::{LogEvent, Value};
 
+    fn serialize(options: LogfmtSerializerOptions, value: Value) -> String {
+        let mut serializer = LogfmtSerializer::new(options);
+        let mut bytes = BytesMut::new();
+        serializer
+            .encode(Event::Log(LogEvent::from(value)), &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
     #[test]
     fn serialize_logfmt() {
         let event = Event::Log(LogEvent::from(btreemap! {
             "foo" => Value::from("bar")
         }));
-        let mut serializer = LogfmtSerializer::new();
+        let mut serializer = LogfmtSerializer::new(Default::default());
         let mut bytes = BytesMut::new();
 
         serializer.encode(event, &mut bytes).unwrap();
 
         assert_eq!(bytes.freeze(), "foo=bar");
     }
+
+    #[test]
+    fn serialize_types() {
+        let timestamp = Utc
+            .timestamp_opt(1_680_350_400, 500_000_000)
+            .single()
+            .unwrap();
+        let value = Value::from(btreemap! {
+            "bool" => Value::from(true),
+            "float" => Value::Float(NotNan::new(1.0).unwrap()),
+            "int" => Value::from(200),
+            "null" => Value::Null,
+            "quoted" => Value::from("a \"quoted\" string"),
+            "string_bool" => Value::from("true"),
+            "string_empty" => Value::from(""),
+            "string_int" => Value::from("200"),
+            "timestamp" => Value::from(timestamp),
+        });
+
+        assert_eq!(
+            serialize(Default::default(), value),
+            r#"bool=true float=1.0 int=200 null= quoted="a \"quoted\" string" string_bool="true" string_empty="" string_int="200" timestamp=2023-04-01T12:00:00.500Z"#
+        );
+    }
+
+    #[test]
+    fn serialize_nested() {
+        let value = Value::from(btreemap! {
+            "request" => Value::from(btreemap! {
+                "method" => Value::from("GET"),
+                "tags" => Value::from(vec![Value::from("a"), Value::from("b")]),
+            }),
+            "status" => Value::from(200),
+        });
+
+        assert_eq!(
+            serialize(Default::default(), value.clone()),
+            "request.method=GET request.tags[0]=a request.tags[1]=b status=200"
+        );
+        assert_eq!(
+            serialize(
+                LogfmtSerializerOptions {
+                    flatten_separator: "_".to_owned(),
+                    ..Default::default()
+                },
+                value
+            ),
+            "request_method=GET request_tags[0]=a request_tags[1]=b status=200"
+        );
+    }
+
+    #[test]
+    fn serialize_key_order() {
+        let value = Value::from(btreemap! {
+            "at" => Value::from("info"),
+            "level" => Value::from("info"),
+            "msg" => Value::from("done"),
+            "request" => Value::from(btreemap! { "id" => Value::from(1) }),
+            "time" => Value::from(12),
+        });
+        let options = LogfmtSerializerOptions {
+            key_order: vec!["time".to_owned(), "request".to_owned(), "msg".to_owned()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, value),
+            "time=12 request.id=1 msg=done at=info level=info"
+        );
+    }
 }
@@ -10,19 +10,23 @@ mod json;
 mod logfmt;
 mod native;
 mod native_json;
+mod prometheus_text;
+mod protobuf;
 mod raw_message;
 mod text;
 
 use std::fmt::Debug;
 
-pub use self::csv::{CsvSerializer, CsvSerializerConfig};
+pub use self::csv::{CsvSerializer, CsvSerializerConfig, CsvSerializerOptions, QuoteStyle};
 pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig};
-pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
+pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig, LogfmtSerializerOptions};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+pub use prometheus_text::{PrometheusTextSerializer, PrometheusTextSerializerConfig};
+pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
@@ -0,0 +1,313 @@
+use std::fmt::Write as _;
+
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{
+        metric::{samples_to_buckets, Bucket, MetricSketch, MetricTags, Quantile},
+        Event, Metric, MetricValue, StatisticKind,
+    },
+    metrics::AgentDDSketch,
+    schema,
+};
+
+/// The upper limits of the buckets that distributions are aggregated into, the same as the
+/// defaults of the `prometheus_exporter` sink.
+const DEFAULT_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The quantiles computed for summaries and sketches, the same as the defaults of the
+/// `prometheus_exporter` sink.
+const DEFAULT_QUANTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+/// Config used to build a `PrometheusTextSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PrometheusTextSerializerConfig;
+
+impl PrometheusTextSerializerConfig {
+    /// Creates a new `PrometheusTextSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `PrometheusTextSerializer` from this configuration.
+    pub const fn build(&self) -> PrometheusTextSerializer {
+        PrometheusTextSerializer
+    }
+
+    /// The data type of events that are accepted by `PrometheusTextSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts a metric to bytes using the Prometheus text exposition format.
+///
+/// Each metric is encoded with its `# TYPE` line, followed by its samples. Distributions are
+/// aggregated into histograms or summaries, and sets are encoded as gauges of their size.
+/// Timestamps aren't encoded, as the textfile collector of the node exporter rejects them.
+#[derive(Debug, Clone)]
+pub struct PrometheusTextSerializer;
+
+impl PrometheusTextSerializer {
+    /// Creates a new `PrometheusTextSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for PrometheusTextSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let metric = event.into_metric();
+        let text = encode_metric(&metric);
+        // The lines are separated by the framer, so the last one isn't terminated here.
+        buffer.extend_from_slice(text.trim_end_matches('\n').as_bytes());
+
+        Ok(())
+    }
+}
+
+fn encode_metric(metric: &Metric) -> String {
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{}_{}", namespace, metric.name()),
+        None => metric.name().to_owned(),
+    };
+    let tags = metric.tags();
+    let mut text = String::new();
+
+    let metric_type = match metric.value() {
+        MetricValue::Counter { .. } => "counter",
+        MetricValue::Gauge { .. } | MetricValue::Set { .. } => "gauge",
+        MetricValue::Distribution {
+            statistic: StatisticKind::Histogram,
+            ..
+        }
+        | MetricValue::AggregatedHistogram { .. } => "histogram",
+        MetricValue::Distribution {
+            statistic: StatisticKind::Summary,
+            ..
+        }
+        | MetricValue::AggregatedSummary { .. }
+        | MetricValue::Sketch { .. } => "summary",
+    };
+    let _ = writeln!(text, "# TYPE {} {}", name, metric_type);
+
+    match metric.value() {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+            encode_sample(&mut text, &name, "", *value, tags, None);
+        }
+        MetricValue::Set { values } => {
+            encode_sample(&mut text, &name, "", values.len() as f64, tags, None);
+        }
+        MetricValue::Distribution {
+            samples,
+            statistic: StatisticKind::Histogram,
+        } => {
+            let (buckets, count, sum) = samples_to_buckets(samples, &DEFAULT_BUCKETS);
+            encode_histogram(&mut text, &name, &buckets, count, sum, tags);
+        }
+        MetricValue::Distribution {
+            samples,
+            statistic: StatisticKind::Summary,
+        } => {
+            let mut sketch = AgentDDSketch::with_agent_defaults();
+            for sample in samples {
+                sketch.insert_n(sample.value, sample.rate);
+            }
+            encode_sketch(&mut text, &name, &sketch, tags);
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => encode_histogram(&mut text, &name, buckets, *count, *sum, tags),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => encode_summary(&mut text, &name, quantiles, *count, *sum, tags),
+        MetricValue::Sketch { sketch } => match sketch {
+            MetricSketch::AgentDDSketch(sketch) => {
+                encode_sketch(&mut text, &name, sketch, tags);
+            }
+        },
+    }
+
+    text
+}
+
+fn encode_histogram(
+    text: &mut String,
+    name: &str,
+    buckets: &[Bucket],
+    count: u64,
+    sum: f64,
+    tags: Option<&MetricTags>,
+) {
+    // The buckets are cumulative in Prometheus, and always end with the `+Inf` bucket.
+    let mut bucket_count = 0;
+    for bucket in buckets {
+        if bucket.upper_limit.is_infinite() {
+            continue;
+        }
+        bucket_count += bucket.count;
+        let upper_limit = format_value(bucket.upper_limit);
+        let le = Some(("le", upper_limit.as_str()));
+        encode_sample(text, name, "_bucket", bucket_count as f64, tags, le);
+    }
+    encode_sample(
+        text,
+        name,
+        "_bucket",
+        count as f64,
+        tags,
+        Some(("le", "+Inf")),
+    );
+    encode_sample(text, name, "_sum", sum, tags, None);
+    encode_sample(text, name, "_count", count as f64, tags, None);
+}
+
+fn encode_summary(
+    text: &mut String,
+    name: &str,
+    quantiles: &[Quantile],
+    count: u64,
+    sum: f64,
+    tags: Option<&MetricTags>,
+) {
+    for quantile in quantiles {
+        let q = format_value(quantile.quantile);
+        let extra = Some(("quantile", q.as_str()));
+        encode_sample(text, name, "", quantile.value, tags, extra);
+    }
+    encode_sample(text, name, "_sum", sum, tags, None);
+    encode_sample(text, name, "_count", count as f64, tags, None);
+}
+
+fn encode_sketch(text: &mut String, name: &str, sketch: &AgentDDSketch, tags: Option<&MetricTags>) {
+    let quantiles = DEFAULT_QUANTILES
+        .iter()
+        .map(|&quantile| Quantile {
+            quantile,
+            value: sketch.quantile(quantile).unwrap_or(0.0),
+        })
+        .collect::<Vec<_>>();
+    let sum = sketch.sum().unwrap_or(0.0);
+    encode_summary(text, name, &quantiles, u64::from(sketch.count()), sum, tags);
+}
+
+fn encode_sample(
+    text: &mut String,
+    name: &str,
+    suffix: &str,
+    value: f64,
+    tags: Option<&MetricTags>,
+    extra: Option<(&str, &str)>,
+) {
+    let mut labels = tags
+        .into_iter()
+        .flat_map(MetricTags::iter_single)
+        .chain(extra)
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect::<Vec<_>>();
+    labels.sort();
+
+    text.push_str(name);
+    text.push_str(suffix);
+    if !labels.is_empty() {
+        let _ = write!(text, "{{{}}}", labels.join(","));
+    }
+    let _ = writeln!(text, " {}", format_value(value));
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_owned()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use indoc::indoc;
+    use vector_core::{
+        buckets,
+        event::{MetricKind, MetricValue},
+        metric_tags,
+    };
+
+    use super::*;
+
+    fn serialize(metric: Metric) -> String {
+        let mut bytes = BytesMut::new();
+        PrometheusTextSerializer::new()
+            .encode(Event::Metric(metric), &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_gauge() {
+        let metric = Metric::new(
+            "temperature",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 21.5 },
+        )
+        .with_namespace(Some("sensor"))
+        .with_tags(Some(
+            metric_tags!("room" => "kitchen", "note" => "a \"quoted\" value"),
+        ));
+
+        assert_eq!(
+            serialize(metric),
+            indoc! {r#"
+                # TYPE sensor_temperature gauge
+                sensor_temperature{note="a \"quoted\" value",room="kitchen"} 21.5"#}
+        );
+    }
+
+    #[test]
+    fn serialize_aggregated_histogram() {
+        let metric = Metric::new(
+            "request_duration_seconds",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: buckets![0.1 => 2, 1.0 => 3, f64::INFINITY => 1],
+                count: 6,
+                sum: 4.5,
+            },
+        );
+
+        assert_eq!(
+            serialize(metric),
+            indoc! {r#"
+                # TYPE request_duration_seconds histogram
+                request_duration_seconds_bucket{le="0.1"} 2
+                request_duration_seconds_bucket{le="1"} 5
+                request_duration_seconds_bucket{le="+Inf"} 6
+                request_duration_seconds_sum 4.5
+                request_duration_seconds_count 6"#}
+        );
+    }
+}
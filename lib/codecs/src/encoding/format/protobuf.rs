@@ -0,0 +1,316 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use bytes::BytesMut;
+use chrono::{DateTime, Utc};
+use prost::Message;
+use prost_reflect::{DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+use crate::{
+    encoding::BuildError,
+    protobuf::{get_message_descriptor, TIMESTAMP_MESSAGE},
+};
+
+/// Config used to build a `ProtobufSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtobufSerializerConfig {
+    /// Options for the Protocol Buffers serializer.
+    pub protobuf: ProtobufSerializerOptions,
+}
+
+impl ProtobufSerializerConfig {
+    /// Creates a new `ProtobufSerializerConfig`.
+    pub const fn new(protobuf: ProtobufSerializerOptions) -> Self {
+        Self { protobuf }
+    }
+
+    /// Build the `ProtobufSerializer` from this configuration.
+    pub fn build(&self) -> Result<ProtobufSerializer, BuildError> {
+        let message_descriptor =
+            get_message_descriptor(&self.protobuf.desc_file, &self.protobuf.message_type)
+                .map_err(|error| format!("Failed building Protobuf serializer: {}", error))?;
+        Ok(ProtobufSerializer::new(message_descriptor))
+    }
+
+    /// The data type of events that are accepted by `ProtobufSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        // TODO: Convert the message descriptor to a vector schema requirement.
+        schema::Requirement::empty()
+    }
+}
+
+/// Protocol Buffers serializer options.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtobufSerializerOptions {
+    /// The path to the compiled file descriptor set of the message type.
+    ///
+    /// The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+    #[configurable(metadata(docs::examples = "/etc/vector/protobuf_descriptor_set.desc"))]
+    pub desc_file: PathBuf,
+
+    /// The fully qualified name of the message type to encode.
+    #[configurable(metadata(docs::examples = "package.Message"))]
+    pub message_type: String,
+}
+
+/// Serializer that converts an `Event` to bytes using the Protocol Buffers format.
+///
+/// The fields of the event are encoded into the fields of the message with the same name, fields
+/// the message doesn't define are left out.
+#[derive(Debug, Clone)]
+pub struct ProtobufSerializer {
+    message_descriptor: MessageDescriptor,
+}
+
+impl ProtobufSerializer {
+    /// Creates a new `ProtobufSerializer`, encoding messages of the given type.
+    pub const fn new(message_descriptor: MessageDescriptor) -> Self {
+        Self { message_descriptor }
+    }
+}
+
+impl Encoder<Event> for ProtobufSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let message = match event.into_log().into_parts().0 {
+            Value::Object(fields) => encode_message(&self.message_descriptor, fields)?,
+            _ => return Err("Protobuf messages can only be encoded from objects.".into()),
+        };
+        message.encode(buffer)?;
+        Ok(())
+    }
+}
+
+fn encode_message(
+    descriptor: &MessageDescriptor,
+    fields: impl IntoIterator<Item = (String, Value)>,
+) -> vector_common::Result<DynamicMessage> {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for (name, value) in fields {
+        if matches!(value, Value::Null) {
+            continue;
+        }
+        if let Some(field) = descriptor.get_field_by_name(&name) {
+            let value = encode_field(&field, value)
+                .map_err(|error| format!("Failed encoding field {:?}: {}", name, error))?;
+            message.try_set_field(&field, value)?;
+        }
+    }
+    Ok(message)
+}
+
+fn encode_field(
+    field: &FieldDescriptor,
+    value: Value,
+) -> vector_common::Result<prost_reflect::Value> {
+    if field.is_map() {
+        let entry = match field.kind() {
+            Kind::Message(entry) => entry,
+            _ => unreachable!("map fields are message fields"),
+        };
+        let key_kind = entry.map_entry_key_field().kind();
+        let value_kind = entry.map_entry_value_field().kind();
+        match value {
+            Value::Object(entries) => entries
+                .into_iter()
+                .filter(|(_, value)| !matches!(value, Value::Null))
+                .map(|(key, value)| {
+                    Ok((
+                        encode_map_key(&key_kind, key)?,
+                        encode_value(&value_kind, value)?,
+                    ))
+                })
+                .collect::<vector_common::Result<HashMap<_, _>>>()
+                .map(prost_reflect::Value::Map),
+            value => Err(format!("expected an object, got {}", value.kind_str()).into()),
+        }
+    } else if field.is_list() {
+        let kind = field.kind();
+        match value {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|value| encode_value(&kind, value))
+                .collect::<vector_common::Result<Vec<_>>>()
+                .map(prost_reflect::Value::List),
+            // A single value is encoded as a list of one element.
+            value => {
+                encode_value(&kind, value).map(|value| prost_reflect::Value::List(vec![value]))
+            }
+        }
+    } else {
+        encode_value(&field.kind(), value)
+    }
+}
+
+fn encode_map_key(kind: &Kind, key: String) -> vector_common::Result<MapKey> {
+    let invalid = || format!("invalid map key {:?}", key);
+    Ok(match kind {
+        Kind::Bool => MapKey::Bool(key.parse().map_err(|_| invalid())?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            MapKey::I32(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            MapKey::I64(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Uint32 | Kind::Fixed32 => MapKey::U32(key.parse().map_err(|_| invalid())?),
+        Kind::Uint64 | Kind::Fixed64 => MapKey::U64(key.parse().map_err(|_| invalid())?),
+        _ => MapKey::String(key),
+    })
+}
+
+fn encode_value(kind: &Kind, value: Value) -> vector_common::Result<prost_reflect::Value> {
+    use prost_reflect::Value as ProtobufValue;
+
+    Ok(match (kind, value) {
+        (Kind::Double, Value::Float(value)) => ProtobufValue::F64(value.into_inner()),
+        (Kind::Double, Value::Integer(value)) => ProtobufValue::F64(value as f64),
+        (Kind::Float, Value::Float(value)) => ProtobufValue::F32(value.into_inner() as f32),
+        (Kind::Float, Value::Integer(value)) => ProtobufValue::F32(value as f32),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Value::Integer(value)) => {
+            ProtobufValue::I32(i32::try_from(value)?)
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Value::Integer(value)) => {
+            ProtobufValue::I64(value)
+        }
+        (Kind::Uint32 | Kind::Fixed32, Value::Integer(value)) => {
+            ProtobufValue::U32(u32::try_from(value)?)
+        }
+        (Kind::Uint64 | Kind::Fixed64, Value::Integer(value)) => {
+            ProtobufValue::U64(u64::try_from(value)?)
+        }
+        (Kind::Bool, Value::Boolean(value)) => ProtobufValue::Bool(value),
+        (Kind::String, Value::Bytes(bytes)) => {
+            ProtobufValue::String(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        (Kind::String, Value::Timestamp(timestamp)) => {
+            ProtobufValue::String(timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+        }
+        (Kind::Bytes, Value::Bytes(bytes)) => ProtobufValue::Bytes(bytes),
+        (Kind::Enum(descriptor), Value::Bytes(name)) => {
+            let name = String::from_utf8_lossy(&name);
+            let value = descriptor
+                .get_value_by_name(&name)
+                .ok_or_else(|| format!("unknown enum value {:?}", name))?;
+            ProtobufValue::EnumNumber(value.number())
+        }
+        (Kind::Enum(_), Value::Integer(number)) => {
+            ProtobufValue::EnumNumber(i32::try_from(number)?)
+        }
+        (Kind::Message(descriptor), Value::Timestamp(timestamp))
+            if descriptor.full_name() == TIMESTAMP_MESSAGE =>
+        {
+            ProtobufValue::Message(encode_timestamp(descriptor, timestamp)?)
+        }
+        (Kind::Message(descriptor), Value::Object(fields)) => {
+            ProtobufValue::Message(encode_message(descriptor, fields)?)
+        }
+        (kind, value) => {
+            return Err(format!("can't encode {} as {:?}", value.kind_str(), kind).into())
+        }
+    })
+}
+
+fn encode_timestamp(
+    descriptor: &MessageDescriptor,
+    timestamp: DateTime<Utc>,
+) -> vector_common::Result<DynamicMessage> {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    message.try_set_field_by_name("seconds", prost_reflect::Value::I64(timestamp.timestamp()))?;
+    message.try_set_field_by_name(
+        "nanos",
+        prost_reflect::Value::I32(timestamp.timestamp_subsec_nanos() as i32),
+    )?;
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::TimeZone;
+    use value::btreemap;
+    use vector_core::{config::LogNamespace, event::LogEvent};
+
+    use super::*;
+    use crate::{
+        decoding::{format::Deserializer, ProtobufDeserializer},
+        protobuf::{message_descriptor_from_bytes, test::file_descriptor_set},
+    };
+
+    fn descriptor() -> MessageDescriptor {
+        message_descriptor_from_bytes(&file_descriptor_set().encode_to_vec(), "test.Log").unwrap()
+    }
+
+    fn encode(fields: std::collections::BTreeMap<String, Value>) -> vector_common::Result<Bytes> {
+        let mut serializer = ProtobufSerializer::new(descriptor());
+        let mut bytes = BytesMut::new();
+        serializer.encode(LogEvent::from(fields).into(), &mut bytes)?;
+        Ok(bytes.freeze())
+    }
+
+    #[test]
+    fn serialize_protobuf() {
+        let time = Utc.timestamp_opt(1_600_000_000, 500).single().unwrap();
+        let bytes = encode(btreemap! {
+            "message" => Value::from("hello"),
+            "level" => Value::from(1),
+            "source" => Value::from(btreemap! { "host" => Value::from("a.example.com") }),
+            "time" => Value::from(time),
+            "unknown" => Value::from("ignored"),
+        })
+        .unwrap();
+
+        let events = ProtobufDeserializer::new(descriptor())
+            .parse(bytes, LogNamespace::Vector)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["level"], "WARN".into());
+        assert_eq!(
+            log["source"],
+            btreemap! { "host" => Value::from("a.example.com") }.into()
+        );
+        assert_eq!(log["time"], time.into());
+        assert!(log.get("unknown").is_none());
+    }
+
+    #[test]
+    fn serialize_protobuf_mismatched_types() {
+        assert!(encode(btreemap! { "count" => Value::from("three") }).is_err());
+        assert!(encode(btreemap! { "level" => Value::from("DEBUG") }).is_err());
+        assert!(encode(btreemap! { "source" => Value::from(true) }).is_err());
+    }
+
+    #[test]
+    fn build_from_descriptor_file() {
+        let path = std::env::temp_dir().join(format!(
+            "vector-protobuf-serializer-{}.desc",
+            std::process::id()
+        ));
+        std::fs::write(&path, file_descriptor_set().encode_to_vec()).unwrap();
+
+        let config = |message_type: &str| {
+            ProtobufSerializerConfig::new(ProtobufSerializerOptions {
+                desc_file: path.clone(),
+                message_type: message_type.to_owned(),
+            })
+        };
+        assert!(config("test.Log").build().is_ok());
+        assert!(config("test.Missing").build().is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+use bytes::BytesMut;
+use prost::encoding::{encode_varint, encoded_len_varint};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use super::BoxedFramingError;
+
+/// Config used to build a `VarintLengthDelimitedEncoder`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VarintLengthDelimitedEncoderConfig;
+
+impl VarintLengthDelimitedEncoderConfig {
+    /// Creates a `VarintLengthDelimitedEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `VarintLengthDelimitedEncoder` from this configuration.
+    pub const fn build(&self) -> VarintLengthDelimitedEncoder {
+        VarintLengthDelimitedEncoder
+    }
+}
+
+/// An encoder for handling bytes that are delimited by a length header encoded as an unsigned
+/// varint, as length-delimited protobuf messages are.
+#[derive(Debug, Clone, Default)]
+pub struct VarintLengthDelimitedEncoder;
+
+impl VarintLengthDelimitedEncoder {
+    /// Creates a `VarintLengthDelimitedEncoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<()> for VarintLengthDelimitedEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let frame = buffer.split();
+        let length = frame.len() as u64;
+        buffer.reserve(encoded_len_varint(length) + frame.len());
+        encode_varint(length, buffer);
+        buffer.extend_from_slice(&frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut codec = VarintLengthDelimitedEncoder::new();
+
+        let mut buffer = BytesMut::from("abc");
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"\x03abc");
+    }
+
+    #[test]
+    fn encode_multibyte_length() {
+        let mut codec = VarintLengthDelimitedEncoder::new();
+
+        let frame = "a".repeat(300);
+        let mut buffer = BytesMut::from(frame.as_str());
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..2], b"\xac\x02");
+        assert_eq!(&buffer[2..], frame.as_bytes());
+    }
+}
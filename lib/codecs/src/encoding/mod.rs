@@ -9,15 +9,18 @@ use std::fmt::Debug;
 use bytes::BytesMut;
 pub use format::{
     AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, CsvSerializer,
-    CsvSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
-    JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
-    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, RawMessageSerializer,
+    CsvSerializerConfig, CsvSerializerOptions, GelfSerializer, GelfSerializerConfig,
+    JsonSerializer, JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig,
+    LogfmtSerializerOptions, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
+    NativeSerializerConfig, PrometheusTextSerializer, PrometheusTextSerializerConfig,
+    ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions, RawMessageSerializer,
     RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
     CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
     LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    VarintLengthDelimitedEncoder, VarintLengthDelimitedEncoderConfig,
 };
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
@@ -73,6 +76,11 @@ pub enum FramingConfig {
 
     /// Event data is delimited by a newline (LF) character.
     NewlineDelimited,
+
+    /// Event data is prefixed with its length in bytes, as an unsigned varint.
+    ///
+    /// This is how length-delimited Protocol Buffers messages are framed.
+    VarintLengthDelimited,
 }
 
 impl From<BytesEncoderConfig> for FramingConfig {
@@ -101,6 +109,12 @@ impl From<NewlineDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<VarintLengthDelimitedEncoderConfig> for FramingConfig {
+    fn from(_: VarintLengthDelimitedEncoderConfig) -> Self {
+        Self::VarintLengthDelimited
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
     pub fn build(&self) -> Framer {
@@ -120,6 +134,9 @@ impl FramingConfig {
             FramingConfig::NewlineDelimited => {
                 Framer::NewlineDelimited(NewlineDelimitedEncoderConfig.build())
             }
+            FramingConfig::VarintLengthDelimited => {
+                Framer::VarintLengthDelimited(VarintLengthDelimitedEncoderConfig.build())
+            }
         }
     }
 }
@@ -135,6 +152,8 @@ pub enum Framer {
     LengthDelimited(LengthDelimitedEncoder),
     /// Uses a `NewlineDelimitedEncoder` for framing.
     NewlineDelimited(NewlineDelimitedEncoder),
+    /// Uses a `VarintLengthDelimitedEncoder` for framing.
+    VarintLengthDelimited(VarintLengthDelimitedEncoder),
     /// Uses an opaque `Encoder` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -163,6 +182,12 @@ impl From<NewlineDelimitedEncoder> for Framer {
     }
 }
 
+impl From<VarintLengthDelimitedEncoder> for Framer {
+    fn from(encoder: VarintLengthDelimitedEncoder) -> Self {
+        Self::VarintLengthDelimited(encoder)
+    }
+}
+
 impl From<BoxedFramer> for Framer {
     fn from(encoder: BoxedFramer) -> Self {
         Self::Boxed(encoder)
@@ -178,6 +203,7 @@ impl tokio_util::codec::Encoder<()> for Framer {
             Framer::CharacterDelimited(framer) => framer.encode((), buffer),
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
+            Framer::VarintLengthDelimited(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
         }
     }
@@ -197,10 +223,12 @@ pub enum SerializerConfig {
         avro: AvroSerializerOptions,
     },
 
-    /// Encodes an event as a CSV message.
+    /// Encodes an event as a [CSV][csv] record.
     ///
-    /// This codec must be configured with fields to encode.
+    /// The fields encoded, and their order, are configured with `fields`, or inferred from the
+    /// first event encoded.
     ///
+    /// [csv]: https://datatracker.ietf.org/doc/html/rfc4180
     Csv(
         /// Options for the CSV encoder.
         CsvSerializerConfig,
@@ -221,8 +249,18 @@ pub enum SerializerConfig {
 
     /// Encodes an event as a [logfmt][logfmt] message.
     ///
+    /// Nested fields are flattened, and the strings that would be decoded as another type are
+    /// quoted, so that the events decoded with the `logfmt` decoder have the same fields and types.
+    ///
     /// [logfmt]: https://brandur.org/logfmt
-    Logfmt,
+    Logfmt {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Logfmt-specific encoder options.
+        logfmt: LogfmtSerializerOptions,
+    },
 
     /// Encodes an event in Vector’s [native Protocol Buffers format][vector_native_protobuf].
     ///
@@ -240,6 +278,27 @@ pub enum SerializerConfig {
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
     NativeJson,
 
+    /// Encodes a metric in the [Prometheus text exposition format][prometheus_text].
+    ///
+    /// Each metric is encoded with its `# TYPE` line, so the metrics written to the same file
+    /// must have different names. Distributions are aggregated into histograms or summaries using
+    /// the default buckets and quantiles of the `prometheus_exporter` sink, and timestamps aren't
+    /// encoded, so the output can be read by the textfile collector of the node exporter.
+    ///
+    /// [prometheus_text]: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+    PrometheusText,
+
+    /// Encodes an event as a [Protocol Buffers][protobuf] message.
+    ///
+    /// The message type is looked up in a compiled file descriptor set. Fields of the event that
+    /// the message type doesn't define aren't encoded.
+    ///
+    /// [protobuf]: https://protobuf.dev/
+    Protobuf {
+        /// Protocol Buffers-specific encoder options.
+        protobuf: ProtobufSerializerOptions,
+    },
+
     /// No encoding.
     ///
     /// This "encoding" simply uses the `message` field of a log event.
@@ -288,8 +347,10 @@ impl From<JsonSerializerConfig> for SerializerConfig {
 }
 
 impl From<LogfmtSerializerConfig> for SerializerConfig {
-    fn from(_: LogfmtSerializerConfig) -> Self {
-        Self::Logfmt
+    fn from(config: LogfmtSerializerConfig) -> Self {
+        Self::Logfmt {
+            logfmt: config.logfmt,
+        }
     }
 }
 
@@ -305,6 +366,20 @@ impl From<NativeJsonSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<PrometheusTextSerializerConfig> for SerializerConfig {
+    fn from(_: PrometheusTextSerializerConfig) -> Self {
+        Self::PrometheusText
+    }
+}
+
+impl From<ProtobufSerializerConfig> for SerializerConfig {
+    fn from(config: ProtobufSerializerConfig) -> Self {
+        Self::Protobuf {
+            protobuf: config.protobuf,
+        }
+    }
+}
+
 impl From<RawMessageSerializerConfig> for SerializerConfig {
     fn from(_: RawMessageSerializerConfig) -> Self {
         Self::RawMessage
@@ -327,11 +402,19 @@ impl SerializerConfig {
             SerializerConfig::Csv(config) => Ok(Serializer::Csv(config.build()?)),
             SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
             SerializerConfig::Json(config) => Ok(Serializer::Json(config.build())),
-            SerializerConfig::Logfmt => Ok(Serializer::Logfmt(LogfmtSerializerConfig.build())),
+            SerializerConfig::Logfmt { logfmt } => Ok(Serializer::Logfmt(
+                LogfmtSerializerConfig::new(logfmt.clone()).build(),
+            )),
             SerializerConfig::Native => Ok(Serializer::Native(NativeSerializerConfig.build())),
             SerializerConfig::NativeJson => {
                 Ok(Serializer::NativeJson(NativeJsonSerializerConfig.build()))
             }
+            SerializerConfig::PrometheusText => Ok(Serializer::PrometheusText(
+                PrometheusTextSerializerConfig.build(),
+            )),
+            SerializerConfig::Protobuf { protobuf } => Ok(Serializer::Protobuf(
+                ProtobufSerializerConfig::new(protobuf.clone()).build()?,
+            )),
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
@@ -356,11 +439,13 @@ impl SerializerConfig {
             SerializerConfig::Avro { .. } | SerializerConfig::Native => {
                 FramingConfig::LengthDelimited
             }
+            SerializerConfig::Protobuf { .. } => FramingConfig::VarintLengthDelimited,
             SerializerConfig::Csv(_)
             | SerializerConfig::Gelf
             | SerializerConfig::Json(_)
-            | SerializerConfig::Logfmt
+            | SerializerConfig::Logfmt { .. }
             | SerializerConfig::NativeJson
+            | SerializerConfig::PrometheusText
             | SerializerConfig::RawMessage
             | SerializerConfig::Text(_) => FramingConfig::NewlineDelimited,
         }
@@ -375,9 +460,15 @@ impl SerializerConfig {
             SerializerConfig::Csv(config) => config.input_type(),
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::input_type(),
             SerializerConfig::Json(config) => config.input_type(),
-            SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
+            SerializerConfig::Logfmt { logfmt } => {
+                LogfmtSerializerConfig::new(logfmt.clone()).input_type()
+            }
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
+            SerializerConfig::PrometheusText => PrometheusTextSerializerConfig.input_type(),
+            SerializerConfig::Protobuf { protobuf } => {
+                ProtobufSerializerConfig::new(protobuf.clone()).input_type()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
             SerializerConfig::Text(config) => config.input_type(),
         }
@@ -392,9 +483,15 @@ impl SerializerConfig {
             SerializerConfig::Csv(config) => config.schema_requirement(),
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::schema_requirement(),
             SerializerConfig::Json(config) => config.schema_requirement(),
-            SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
+            SerializerConfig::Logfmt { logfmt } => {
+                LogfmtSerializerConfig::new(logfmt.clone()).schema_requirement()
+            }
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
+            SerializerConfig::PrometheusText => PrometheusTextSerializerConfig.schema_requirement(),
+            SerializerConfig::Protobuf { protobuf } => {
+                ProtobufSerializerConfig::new(protobuf.clone()).schema_requirement()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
             SerializerConfig::Text(config) => config.schema_requirement(),
         }
@@ -418,6 +515,10 @@ pub enum Serializer {
     Native(NativeSerializer),
     /// Uses a `NativeJsonSerializer` for serialization.
     NativeJson(NativeJsonSerializer),
+    /// Uses a `PrometheusTextSerializer` for serialization.
+    PrometheusText(PrometheusTextSerializer),
+    /// Uses a `ProtobufSerializer` for serialization.
+    Protobuf(ProtobufSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
     /// Uses a `TextSerializer` for serialization.
@@ -434,6 +535,8 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
+            | Serializer::PrometheusText(_)
+            | Serializer::Protobuf(_)
             | Serializer::RawMessage(_) => false,
         }
     }
@@ -454,6 +557,8 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
+            | Serializer::PrometheusText(_)
+            | Serializer::Protobuf(_)
             | Serializer::RawMessage(_) => {
                 panic!("Serializer does not support JSON")
             }
@@ -461,6 +566,16 @@ impl Serializer {
     }
 }
 
+impl Serializer {
+    /// Marks the output the serializer writes to as already started, such as when appending to
+    /// an existing file, so that it doesn't write a header before the first event.
+    pub fn set_output_started(&mut self) {
+        if let Serializer::Csv(serializer) = self {
+            serializer.set_output_started();
+        }
+    }
+}
+
 impl From<AvroSerializer> for Serializer {
     fn from(serializer: AvroSerializer) -> Self {
         Self::Avro(serializer)
@@ -503,6 +618,18 @@ impl From<NativeJsonSerializer> for Serializer {
     }
 }
 
+impl From<PrometheusTextSerializer> for Serializer {
+    fn from(serializer: PrometheusTextSerializer) -> Self {
+        Self::PrometheusText(serializer)
+    }
+}
+
+impl From<ProtobufSerializer> for Serializer {
+    fn from(serializer: ProtobufSerializer) -> Self {
+        Self::Protobuf(serializer)
+    }
+}
+
 impl From<RawMessageSerializer> for Serializer {
     fn from(serializer: RawMessageSerializer) -> Self {
         Self::RawMessage(serializer)
@@ -527,6 +654,8 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
+            Serializer::PrometheusText(serializer) => serializer.encode(event, buffer),
+            Serializer::Protobuf(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
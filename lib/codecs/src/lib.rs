@@ -7,25 +7,31 @@
 pub mod decoding;
 pub mod encoding;
 pub mod gelf;
+mod logfmt;
+mod protobuf;
 
 pub use decoding::{
-    BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
-    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, GelfDeserializer,
+    AvroDeserializer, BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
+    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, CsvDeserializer, CsvDeserializerConfig, GelfDeserializer,
     GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder,
-    LengthDelimitedDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
-    NativeJsonDeserializer, NativeJsonDeserializerConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, OctetCountingDecoder, OctetCountingDecoderConfig,
-    StreamDecodingError,
+    LengthDelimitedDecoderConfig, LogfmtDeserializer, LogfmtDeserializerConfig, NativeDeserializer,
+    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, OctetCountingDecoder,
+    OctetCountingDecoderConfig, ProtobufDeserializer, ProtobufDeserializerConfig,
+    StreamDecodingError, VarintLengthDelimitedDecoder, VarintLengthDelimitedDecoderConfig,
 };
 #[cfg(feature = "syslog")]
 pub use decoding::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use encoding::{
     BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig,
-    GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig,
-    LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer, LogfmtSerializerConfig,
-    NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    CsvSerializer, CsvSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
+    JsonSerializerConfig, LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer,
+    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
+    NativeSerializerConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    PrometheusTextSerializer, PrometheusTextSerializerConfig, ProtobufSerializer,
+    ProtobufSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
+    TextSerializerConfig, VarintLengthDelimitedEncoder, VarintLengthDelimitedEncoderConfig,
 };
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
 use vector_config::configurable_component;
@@ -0,0 +1,240 @@
+//! Conventions shared by the logfmt encoder and decoder.
+//!
+//! Nested fields are flattened into keys joined by a separator, with the indices of arrays in
+//! brackets, such as `request.headers[0]`. The types of unquoted values are inferred, so the
+//! encoder quotes the strings that would otherwise be read back as another type, such as `"200"`.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use lookup::lookup_v2::{OwnedSegment, OwnedValuePath};
+use ordered_float::NotNan;
+use vector_core::event::Value;
+
+/// The default separator joining the names of nested fields into keys.
+pub(crate) fn default_flatten_separator() -> String {
+    ".".to_owned()
+}
+
+/// Infers the value of an unquoted logfmt value.
+///
+/// An empty value is null, `true` and `false` are booleans, numbers are integers or floats, and
+/// RFC 3339 timestamps are timestamps. Everything else is a string.
+pub(crate) fn infer_value(value: &str) -> Value {
+    match value {
+        "" => Value::Null,
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        _ if looks_numeric(value) => {
+            if let Ok(int) = value.parse::<i64>() {
+                return Value::Integer(int);
+            }
+            match value
+                .parse::<f64>()
+                .ok()
+                .and_then(|float| NotNan::new(float).ok())
+            {
+                Some(float) if float.is_finite() => Value::Float(float),
+                _ => Value::from(value),
+            }
+        }
+        _ => match DateTime::parse_from_rfc3339(value) {
+            Ok(timestamp) => Value::Timestamp(timestamp.with_timezone(&Utc)),
+            Err(_) => Value::from(value),
+        },
+    }
+}
+
+fn looks_numeric(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+}
+
+/// Whether a string value must be quoted to be read back as the same string.
+fn needs_quotes(value: &str) -> bool {
+    value
+        .chars()
+        .any(|c| c <= ' ' || c == '=' || c == '"' || c.is_control())
+        || !matches!(infer_value(value), Value::Bytes(_))
+}
+
+/// Writes a string value, quoting it if needed.
+pub(crate) fn write_string(value: &str, out: &mut String) {
+    if !needs_quotes(value) {
+        out.push_str(value);
+        return;
+    }
+
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Writes a key, replacing the characters keys can't contain with underscores.
+pub(crate) fn write_key(key: &str, out: &mut String) {
+    if key.is_empty() {
+        out.push('_');
+    }
+    out.extend(key.chars().map(|c| {
+        if c <= ' ' || c == '=' || c == '"' || c.is_control() {
+            '_'
+        } else {
+            c
+        }
+    }));
+}
+
+/// Writes a value that isn't an object or an array.
+pub(crate) fn write_scalar(value: &Value, out: &mut String) {
+    match value {
+        Value::Bytes(bytes) => write_string(&String::from_utf8_lossy(bytes), out),
+        Value::Integer(int) => out.push_str(&int.to_string()),
+        // The debug representation always has a fractional part or an exponent, so that the float
+        // isn't read back as an integer.
+        Value::Float(float) if float.is_finite() => {
+            out.push_str(&format!("{:?}", float.into_inner()))
+        }
+        Value::Float(float) => write_string(&float.to_string(), out),
+        Value::Boolean(bool) => out.push_str(if *bool { "true" } else { "false" }),
+        Value::Timestamp(timestamp) => {
+            out.push_str(&timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        Value::Regex(regex) => write_string(regex.as_str(), out),
+        Value::Null | Value::Object(_) | Value::Array(_) => {}
+    }
+}
+
+/// Flattens `value` into the keys and values it is encoded as, in order.
+pub(crate) fn flatten<'a>(
+    key: String,
+    value: &'a Value,
+    separator: &str,
+    out: &mut Vec<(String, &'a Value)>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (field, value) in map {
+                let key = if key.is_empty() {
+                    field.clone()
+                } else {
+                    format!("{}{}{}", key, separator, field)
+                };
+                flatten(key, value, separator, out);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                flatten(format!("{}[{}]", key, index), value, separator, out);
+            }
+        }
+        _ => out.push((key, value)),
+    }
+}
+
+/// Parses a flattened key into the path of the field it stands for.
+///
+/// Keys that can't be parsed, such as ones with empty parts, stand for a field of the same name.
+pub(crate) fn unflatten_key(key: &str, separator: &str) -> OwnedValuePath {
+    parse_key(key, separator)
+        .unwrap_or_else(|| vec![OwnedSegment::Field(key.into())])
+        .into()
+}
+
+fn parse_key(key: &str, separator: &str) -> Option<Vec<OwnedSegment>> {
+    let mut segments = Vec::new();
+    let parts = if separator.is_empty() {
+        vec![key]
+    } else {
+        key.split(separator).collect()
+    };
+    for part in parts {
+        let (field, mut indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if field.is_empty() {
+            return None;
+        }
+        segments.push(OwnedSegment::Field(field.into()));
+        while !indices.is_empty() {
+            let end = indices.find(']')?;
+            let index = indices
+                .get(1..end)?
+                .parse::<isize>()
+                .ok()
+                .filter(|i| *i >= 0)?;
+            segments.push(OwnedSegment::Index(index));
+            indices = &indices[end + 1..];
+            if !indices.is_empty() && !indices.starts_with('[') {
+                return None;
+            }
+        }
+    }
+    Some(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_values() {
+        assert_eq!(infer_value(""), Value::Null);
+        assert_eq!(infer_value("true"), Value::Boolean(true));
+        assert_eq!(infer_value("-12"), Value::Integer(-12));
+        assert_eq!(
+            infer_value("1.5e3"),
+            Value::Float(NotNan::new(1500.0).unwrap())
+        );
+        assert_eq!(infer_value("1-2"), Value::from("1-2"));
+        assert_eq!(infer_value("1e999"), Value::from("1e999"));
+        assert!(matches!(
+            infer_value("2023-04-01T12:00:00.5Z"),
+            Value::Timestamp(_)
+        ));
+        assert_eq!(infer_value("GET"), Value::from("GET"));
+    }
+
+    #[test]
+    fn quotes_ambiguous_strings() {
+        for (value, expected) in [
+            ("GET", "GET"),
+            ("", r#""""#),
+            ("200", r#""200""#),
+            ("false", r#""false""#),
+            ("a b", r#""a b""#),
+            ("say \"hi\"\n", r#""say \"hi\"\n""#),
+        ] {
+            let mut out = String::new();
+            write_string(value, &mut out);
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(
+            unflatten_key("a.b[0][1].c", "."),
+            OwnedValuePath::from(vec![
+                OwnedSegment::Field("a".into()),
+                OwnedSegment::Field("b".into()),
+                OwnedSegment::Index(0),
+                OwnedSegment::Index(1),
+                OwnedSegment::Field("c".into()),
+            ])
+        );
+        for key in ["a..b", ".a", "a[x]", "a[0]b"] {
+            assert_eq!(
+                unflatten_key(key, "."),
+                OwnedValuePath::from(vec![OwnedSegment::Field(key.into())])
+            );
+        }
+    }
+}
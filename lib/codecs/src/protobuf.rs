@@ -0,0 +1,191 @@
+//! Contains common definitions for Protocol Buffers codec support
+
+use std::path::Path;
+
+use prost_reflect::{DescriptorPool, MessageDescriptor};
+
+/// The fully qualified name of the well-known `Timestamp` message, which is mapped to and from
+/// Vector timestamps.
+pub(crate) const TIMESTAMP_MESSAGE: &str = "google.protobuf.Timestamp";
+
+/// Loads the descriptor of the message `message_type` from the compiled file descriptor set at
+/// `path`, such as one produced by `protoc --include_imports --descriptor_set_out`.
+pub(crate) fn get_message_descriptor(
+    path: &Path,
+    message_type: &str,
+) -> vector_common::Result<MessageDescriptor> {
+    let bytes = std::fs::read(path).map_err(|error| {
+        format!(
+            "Failed reading the file descriptor set {:?}: {}",
+            path, error
+        )
+    })?;
+    message_descriptor_from_bytes(&bytes, message_type)
+}
+
+/// Loads the descriptor of the message `message_type` from an encoded file descriptor set.
+pub(crate) fn message_descriptor_from_bytes(
+    bytes: &[u8],
+    message_type: &str,
+) -> vector_common::Result<MessageDescriptor> {
+    let pool = DescriptorPool::decode(bytes)
+        .map_err(|error| format!("Failed parsing the file descriptor set: {}", error))?;
+    pool.get_message_by_name(message_type).ok_or_else(|| {
+        format!(
+            "The message type {:?} isn't defined in the file descriptor set.",
+            message_type
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use prost_reflect::prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, FileDescriptorSet, MessageOptions,
+    };
+
+    fn field(name: &str, number: i32, label: Label, r#type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(label as i32),
+            r#type: Some(r#type as i32),
+            json_name: Some(name.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn typed_field(
+        name: &str,
+        number: i32,
+        label: Label,
+        r#type: Type,
+        type_name: &str,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            type_name: Some(type_name.to_owned()),
+            ..field(name, number, label, r#type)
+        }
+    }
+
+    /// Returns the file descriptor set of the following definitions, along with the well-known
+    /// `google.protobuf.Timestamp` message:
+    ///
+    /// ```protobuf
+    /// syntax = "proto3";
+    /// package test;
+    ///
+    /// message Log {
+    ///   enum Level { INFO = 0; WARN = 1; }
+    ///   message Source { string host = 1; }
+    ///
+    ///   string message = 1;
+    ///   int64 count = 2;
+    ///   Level level = 3;
+    ///   repeated string tags = 4;
+    ///   map<string, string> labels = 5;
+    ///   Source source = 6;
+    ///   google.protobuf.Timestamp time = 7;
+    /// }
+    /// ```
+    pub(crate) fn file_descriptor_set() -> FileDescriptorSet {
+        let timestamp = FileDescriptorProto {
+            name: Some("google/protobuf/timestamp.proto".to_owned()),
+            package: Some("google.protobuf".to_owned()),
+            message_type: vec![DescriptorProto {
+                name: Some("Timestamp".to_owned()),
+                field: vec![
+                    field("seconds", 1, Label::Optional, Type::Int64),
+                    field("nanos", 2, Label::Optional, Type::Int32),
+                ],
+                ..Default::default()
+            }],
+            syntax: Some("proto3".to_owned()),
+            ..Default::default()
+        };
+
+        let log = DescriptorProto {
+            name: Some("Log".to_owned()),
+            field: vec![
+                field("message", 1, Label::Optional, Type::String),
+                field("count", 2, Label::Optional, Type::Int64),
+                typed_field("level", 3, Label::Optional, Type::Enum, ".test.Log.Level"),
+                field("tags", 4, Label::Repeated, Type::String),
+                typed_field(
+                    "labels",
+                    5,
+                    Label::Repeated,
+                    Type::Message,
+                    ".test.Log.LabelsEntry",
+                ),
+                typed_field(
+                    "source",
+                    6,
+                    Label::Optional,
+                    Type::Message,
+                    ".test.Log.Source",
+                ),
+                typed_field(
+                    "time",
+                    7,
+                    Label::Optional,
+                    Type::Message,
+                    ".google.protobuf.Timestamp",
+                ),
+            ],
+            nested_type: vec![
+                DescriptorProto {
+                    name: Some("Source".to_owned()),
+                    field: vec![field("host", 1, Label::Optional, Type::String)],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("LabelsEntry".to_owned()),
+                    field: vec![
+                        field("key", 1, Label::Optional, Type::String),
+                        field("value", 2, Label::Optional, Type::String),
+                    ],
+                    options: Some(MessageOptions {
+                        map_entry: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Level".to_owned()),
+                value: vec![
+                    EnumValueDescriptorProto {
+                        name: Some("INFO".to_owned()),
+                        number: Some(0),
+                        ..Default::default()
+                    },
+                    EnumValueDescriptorProto {
+                        name: Some("WARN".to_owned()),
+                        number: Some(1),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        FileDescriptorSet {
+            file: vec![
+                timestamp,
+                FileDescriptorProto {
+                    name: Some("test.proto".to_owned()),
+                    package: Some("test".to_owned()),
+                    dependency: vec!["google/protobuf/timestamp.proto".to_owned()],
+                    message_type: vec![log],
+                    syntax: Some("proto3".to_owned()),
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+}
@@ -329,6 +329,45 @@ impl Checkpointer {
         Ok(self.checkpoints.checkpoints.len())
     }
 
+    /// Serialize the current in-memory checkpoints to the given writer as
+    /// portable JSON, in the same versioned format that is persisted to disk.
+    ///
+    /// Callers will usually want to call `read_checkpoints` first in order to
+    /// load the state persisted by a previous run.
+    pub fn export_checkpoints<W: io::Write>(&self, writer: W) -> Result<usize, io::Error> {
+        let state = self.checkpoints.get_state();
+        serde_json::to_writer_pretty(writer, &state)?;
+
+        Ok(match state {
+            State::V1 { checkpoints } => checkpoints.len(),
+        })
+    }
+
+    /// Deserialize checkpoints previously written by `export_checkpoints` from
+    /// the given reader, merge them into the in-memory checkpoints, and
+    /// persist the result to disk.
+    ///
+    /// Imported checkpoints take precedence over existing checkpoints for the
+    /// same fingerprint. Returns the number of checkpoints imported.
+    pub fn import_checkpoints<R: io::Read>(&mut self, reader: R) -> Result<usize, io::Error> {
+        let state: State = serde_json::from_reader(reader)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let count = match &state {
+            State::V1 { checkpoints } => checkpoints.len(),
+        };
+
+        self.checkpoints.set_state(state, None);
+        // Ensure the imported state is written even if it matches the last
+        // state written by this checkpointer.
+        *self.last.lock().expect("Data poisoned.") = None;
+        if let Some(data_dir) = self.stable_file_path.parent() {
+            fs::create_dir_all(data_dir)?;
+        }
+        self.write_checkpoints()?;
+
+        Ok(count)
+    }
+
     /// Write checkpoints to disk in the legacy format. Used for compatibility
     /// testing only.
     #[cfg(test)]
@@ -782,6 +821,62 @@ mod test {
         assert_eq!(None, chkptr.get_checkpoint(old));
     }
 
+    #[test]
+    fn test_checkpointer_export_import() {
+        let fingerprints = vec![
+            FileFingerprint::DevInode(1, 2),
+            FileFingerprint::BytesChecksum(3456),
+            FileFingerprint::FirstLinesChecksum(78910),
+            FileFingerprint::Unknown(1337),
+        ];
+        let position: FilePosition = 1234;
+
+        let mut exported = Vec::new();
+        {
+            let data_dir = tempdir().unwrap();
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            for fingerprint in &fingerprints {
+                chkptr.update_checkpoint(*fingerprint, position);
+            }
+            chkptr.write_checkpoints().unwrap();
+
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.read_checkpoints(None);
+            assert_eq!(chkptr.export_checkpoints(&mut exported).unwrap(), 4);
+        }
+
+        let data_dir = tempdir().unwrap();
+        let new_data_dir = data_dir.path().join("file_source");
+        {
+            let mut chkptr = Checkpointer::new(&new_data_dir);
+            chkptr.update_checkpoint(FileFingerprint::BytesChecksum(3456), 1);
+            chkptr.update_checkpoint(FileFingerprint::BytesChecksum(1), 5678);
+            assert_eq!(chkptr.import_checkpoints(exported.as_slice()).unwrap(), 4);
+        }
+
+        let mut chkptr = Checkpointer::new(&new_data_dir);
+        chkptr.read_checkpoints(None);
+        for fingerprint in fingerprints {
+            assert_eq!(chkptr.get_checkpoint(fingerprint), Some(position));
+        }
+        assert_eq!(
+            chkptr.get_checkpoint(FileFingerprint::BytesChecksum(1)),
+            Some(5678)
+        );
+    }
+
+    #[test]
+    fn test_checkpointer_import_invalid() {
+        let data_dir = tempdir().unwrap();
+        let mut chkptr = Checkpointer::new(data_dir.path());
+
+        let error = chkptr
+            .import_checkpoints(r#"{"version":"0","checkpoints":[]}"#.as_bytes())
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(!data_dir.path().join(CHECKPOINT_FILE_NAME).exists());
+    }
+
     // guards against accidental changes to the checkpoint serialization
     #[test]
     fn test_checkpointer_serialization() {
@@ -2,17 +2,18 @@ use bytes::Bytes;
 use chrono::{DateTime, TimeZone, Utc};
 use lookup::path;
 use ordered_float::NotNan;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use value::Value;
 use vector_core::{
     config::{log_schema, LegacyKey, LogNamespace},
-    event::{Event, LogEvent},
+    event::{Event, LogEvent, TraceEvent},
 };
 
 use super::proto::{
-    common::v1::{any_value::Value as PBValue, KeyValue},
+    common::v1::{any_value::Value as PBValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
     resource::v1::Resource,
+    trace::v1::{span::SpanKind, status::StatusCode, ResourceSpans, Span},
 };
 
 const SOURCE_NAME: &str = "opentelemetry";
@@ -27,6 +28,27 @@ pub const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
 pub const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
 pub const FLAGS_KEY: &str = "flags";
 
+// The span `meta` keys holding the OTLP fields Datadog spans have no equivalent for, named as the
+// Datadog Agent names them when it ingests OTLP traces.
+pub const SPAN_KIND_KEY: &str = "span.kind";
+pub const OTEL_TRACE_ID_KEY: &str = "otel.trace_id";
+pub const STATUS_CODE_KEY: &str = "otel.status_code";
+pub const STATUS_DESCRIPTION_KEY: &str = "otel.status_description";
+pub const LIBRARY_NAME_KEY: &str = "otel.library.name";
+pub const LIBRARY_VERSION_KEY: &str = "otel.library.version";
+// The span `metrics` key Datadog marks the top-level spans of a service with.
+pub const TOP_LEVEL_KEY: &str = "_top_level";
+
+/// The trace fields that are taken from resource attributes, following the semantic conventions.
+pub const TRACE_RESOURCE_FIELDS: [(&str, &str); 5] = [
+    ("env", "deployment.environment"),
+    ("app_version", "service.version"),
+    ("container_id", "container.id"),
+    ("language_name", "telemetry.sdk.language"),
+    ("tracer_version", "telemetry.sdk.version"),
+];
+pub const HOST_NAME_ATTRIBUTE: &str = "host.name";
+
 impl ResourceLogs {
     pub fn into_event_iter(self, log_namespace: LogNamespace) -> impl Iterator<Item = Event> {
         let resource = self.resource;
@@ -70,14 +92,16 @@ struct ResourceLog {
 }
 
 fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
-    Value::Object(
-        arr.into_iter()
-            .filter_map(|kv| {
-                kv.value
-                    .map(|av| (kv.key, av.value.map(Into::into).unwrap_or(Value::Null)))
-            })
-            .collect::<BTreeMap<String, Value>>(),
-    )
+    Value::Object(kv_list_into_map(arr))
+}
+
+fn kv_list_into_map(arr: Vec<KeyValue>) -> BTreeMap<String, Value> {
+    arr.into_iter()
+        .filter_map(|kv| {
+            kv.value
+                .map(|av| (kv.key, av.value.map(Into::into).unwrap_or(Value::Null)))
+        })
+        .collect()
 }
 
 // https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/logs/data-model.md
@@ -221,3 +245,184 @@ impl ResourceLog {
         log.into()
     }
 }
+
+impl ResourceSpans {
+    /// Converts the spans into trace events, one per trace id.
+    ///
+    /// The spans take the shape of the ones produced by the `datadog_agent` source, so that traces
+    /// can be sent to any sink accepting them regardless of where they were received from.
+    pub fn into_trace_events(self) -> impl Iterator<Item = Event> {
+        let resources = self
+            .resource
+            .map(|resource| kv_list_into_map(resource.attributes))
+            .unwrap_or_default();
+        let service = resources.get("service.name").cloned();
+
+        let mut traces = BTreeMap::<Vec<u8>, Vec<BTreeMap<String, Value>>>::new();
+        for scope_spans in self.scope_spans {
+            let scope = scope_spans.scope.unwrap_or_default();
+            for span in scope_spans.spans {
+                traces
+                    .entry(span.trace_id.clone())
+                    .or_default()
+                    .push(convert_span(span, &scope, service.as_ref()));
+            }
+        }
+
+        traces.into_iter().map(move |(trace_id, mut spans)| {
+            mark_top_level(&mut spans);
+            let mut trace = TraceEvent::default();
+            trace.insert("trace_id", lower_id(&trace_id));
+            trace.insert(
+                "spans",
+                spans.into_iter().map(Value::Object).collect::<Vec<_>>(),
+            );
+            for (field, attribute) in TRACE_RESOURCE_FIELDS {
+                if let Some(value) = resources.get(attribute) {
+                    trace.insert(field, value.clone());
+                }
+            }
+            if let Some(host) = resources.get(HOST_NAME_ATTRIBUTE) {
+                trace.insert(log_schema().host_key(), host.clone());
+            }
+            if !resources.is_empty() {
+                trace.insert(RESOURCE_KEY, resources.clone());
+            }
+            trace.insert(log_schema().source_type_key(), SOURCE_NAME);
+            Event::Trace(trace)
+        })
+    }
+}
+
+/// Returns the lower 64 bits of an OTLP id, which is how the Datadog trace and span ids are derived.
+fn lower_id(id: &[u8]) -> i64 {
+    let start = id.len().saturating_sub(8);
+    id[start..]
+        .iter()
+        .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte)) as i64
+}
+
+/// Marks the spans whose parent isn't part of the trace with the `_top_level` metric, as the Datadog
+/// Agent does for the OTLP traces it receives, since Datadog only computes APM stats for those.
+///
+/// All the spans of a trace come from the same resource, so unlike in the Agent a parent can't
+/// belong to another service.
+fn mark_top_level(spans: &mut [BTreeMap<String, Value>]) {
+    let span_ids = spans
+        .iter()
+        .filter_map(|span| span.get("span_id").and_then(Value::as_integer))
+        .collect::<HashSet<_>>();
+    for span in spans {
+        let parent_id = span.get("parent_id").and_then(Value::as_integer);
+        if parent_id.map_or(true, |id| id == 0 || !span_ids.contains(&id)) {
+            if let Some(Value::Object(metrics)) = span.get_mut("metrics") {
+                metrics.insert(
+                    TOP_LEVEL_KEY.into(),
+                    Value::Float(NotNan::new(1.0).unwrap()),
+                );
+            }
+        }
+    }
+}
+
+fn convert_span(
+    span: Span,
+    scope: &InstrumentationScope,
+    service: Option<&Value>,
+) -> BTreeMap<String, Value> {
+    let start = span.start_time_unix_nano as i64;
+    let duration = (span.end_time_unix_nano as i64).saturating_sub(start);
+    let kind = SpanKind::from_i32(span.kind).unwrap_or(SpanKind::Unspecified);
+    let status = span.status.unwrap_or_default();
+    let status_code = StatusCode::from_i32(status.code).unwrap_or(StatusCode::Unset);
+
+    // As in Datadog spans, numeric attributes are metrics while all the others are tags.
+    let mut meta = BTreeMap::new();
+    let mut metrics = BTreeMap::new();
+    for (key, value) in kv_list_into_map(span.attributes) {
+        match value {
+            Value::Integer(value) => {
+                metrics.insert(key, Value::Float(NotNan::new(value as f64).unwrap()));
+            }
+            Value::Float(value) => {
+                metrics.insert(key, Value::Float(value));
+            }
+            value => {
+                meta.insert(key, Value::from(value.to_string_lossy().into_owned()));
+            }
+        }
+    }
+    meta.insert(
+        SPAN_KIND_KEY.into(),
+        Value::from(span_kind_name(kind).to_owned()),
+    );
+    meta.insert(
+        OTEL_TRACE_ID_KEY.into(),
+        Value::from(hex::encode(&span.trace_id)),
+    );
+    meta.insert(
+        STATUS_CODE_KEY.into(),
+        Value::from(status_code_name(status_code).to_owned()),
+    );
+    if !status.message.is_empty() {
+        meta.insert(STATUS_DESCRIPTION_KEY.into(), Value::from(status.message));
+    }
+    if !scope.name.is_empty() {
+        meta.insert(LIBRARY_NAME_KEY.into(), Value::from(scope.name.clone()));
+    }
+    if !scope.version.is_empty() {
+        meta.insert(
+            LIBRARY_VERSION_KEY.into(),
+            Value::from(scope.version.clone()),
+        );
+    }
+
+    let span_type = match kind {
+        SpanKind::Server => "web",
+        SpanKind::Client => "http",
+        _ => "custom",
+    };
+
+    let mut converted = BTreeMap::<String, Value>::new();
+    if let Some(service) = service {
+        converted.insert("service".into(), service.clone());
+    }
+    converted.insert("name".into(), Value::from(span.name.clone()));
+    converted.insert("resource".into(), Value::from(span.name));
+    converted.insert("type".into(), Value::from(span_type));
+    converted.insert("trace_id".into(), Value::from(lower_id(&span.trace_id)));
+    converted.insert("span_id".into(), Value::from(lower_id(&span.span_id)));
+    converted.insert(
+        "parent_id".into(),
+        Value::from(lower_id(&span.parent_span_id)),
+    );
+    converted.insert("start".into(), Value::from(Utc.timestamp_nanos(start)));
+    converted.insert("duration".into(), Value::from(duration));
+    converted.insert(
+        "error".into(),
+        Value::from(i64::from(status_code == StatusCode::Error)),
+    );
+    converted.insert("meta".into(), Value::from(meta));
+    converted.insert("metrics".into(), Value::from(metrics));
+
+    converted
+}
+
+pub const fn span_kind_name(kind: SpanKind) -> &'static str {
+    match kind {
+        SpanKind::Unspecified => "unspecified",
+        SpanKind::Internal => "internal",
+        SpanKind::Server => "server",
+        SpanKind::Client => "client",
+        SpanKind::Producer => "producer",
+        SpanKind::Consumer => "consumer",
+    }
+}
+
+pub const fn status_code_name(code: StatusCode) -> &'static str {
+    match code {
+        StatusCode::Unset => "Unset",
+        StatusCode::Ok => "Ok",
+        StatusCode::Error => "Error",
+    }
+}
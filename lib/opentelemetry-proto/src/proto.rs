@@ -5,6 +5,18 @@ pub mod collector {
             tonic::include_proto!("opentelemetry.proto.collector.logs.v1");
         }
     }
+
+    pub mod metrics {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.metrics.v1");
+        }
+    }
+
+    pub mod trace {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.trace.v1");
+        }
+    }
 }
 
 /// Common types used across all event types.
@@ -21,9 +33,23 @@ pub mod logs {
     }
 }
 
+/// Generated types used for metrics.
+pub mod metrics {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.metrics.v1");
+    }
+}
+
 /// Generated types used in resources.
 pub mod resource {
     pub mod v1 {
         tonic::include_proto!("opentelemetry.proto.resource.v1");
     }
 }
+
+/// Generated types used for traces.
+pub mod trace {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.trace.v1");
+    }
+}
@@ -37,6 +37,8 @@ struct RateKeyIdentifier {
     rate_limit_key_values: RateLimitedSpanKeys,
 }
 
+type SuppressedCallback = Box<dyn Fn() + Send + Sync>;
+
 pub struct RateLimitedLayer<S, L>
 where
     L: Layer<S> + Sized,
@@ -45,6 +47,7 @@ where
     events: DashMap<RateKeyIdentifier, State>,
     inner: L,
     internal_log_rate_limit: u64,
+    on_suppressed: Option<SuppressedCallback>,
     _subscriber: std::marker::PhantomData<S>,
 }
 
@@ -58,6 +61,7 @@ where
             events: Default::default(),
             internal_log_rate_limit: 10,
             inner: layer,
+            on_suppressed: None,
             _subscriber: std::marker::PhantomData,
         }
     }
@@ -66,6 +70,24 @@ where
         self.internal_log_rate_limit = internal_log_rate_limit;
         self
     }
+
+    /// Sets a callback invoked every time an event is suppressed by the rate limit.
+    ///
+    /// This is called once per suppressed event, including the one replaced by the notice that
+    /// the event is being rate limited.
+    pub fn with_suppressed_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_suppressed = Some(Box::new(callback));
+        self
+    }
+
+    fn suppressed(&self) {
+        if let Some(callback) = &self.on_suppressed {
+            callback();
+        }
+    }
 }
 
 impl<S, L> Layer<S> for RateLimitedLayer<S, L>
@@ -94,6 +116,12 @@ where
                 attrs.record(&mut fields);
                 extensions.insert(fields);
             };
+
+            let mut span_limit = SpanLimit::default();
+            attrs.record(&mut span_limit);
+            if span_limit.limit_secs.is_some() {
+                extensions.insert(span_limit);
+            }
         }
         self.inner.on_new_span(attrs, id, ctx);
     }
@@ -114,6 +142,12 @@ where
                     extensions.insert(fields);
                 }
             };
+
+            let mut span_limit = SpanLimit::default();
+            values.record(&mut span_limit);
+            if span_limit.limit_secs.is_some() {
+                extensions.replace(span_limit);
+            }
         }
         self.inner.on_record(id, values, ctx);
     }
@@ -134,10 +168,13 @@ where
             return self.inner.on_event(event, ctx);
         }
 
-        let limit = match limit_visitor.limit_secs {
-            Some(limit_secs) => limit_secs, // override the cli limit
-            None => self.internal_log_rate_limit,
-        };
+        // The limit set at the callsite takes precedence over the one set on the closest enclosing
+        // span, typically the one of the component emitting the event, which in turn overrides the
+        // cli limit.
+        let limit = limit_visitor
+            .limit_secs
+            .or_else(|| span_limit(&ctx))
+            .unwrap_or(self.internal_log_rate_limit);
 
         // Visit all of the spans in the scope of this event, looking for specific fields that we use to differentiate
         // rate-limited events. This ensures that we don't rate limit an event's _callsite_, but the specific usage of a
@@ -190,8 +227,9 @@ where
                     let message =
                         format!("Internal log [{}] is being rate limited.", state.message);
                     self.create_event(&ctx, metadata, message, state.limit);
+                    self.suppressed();
                 }
-                _ => {}
+                _ => self.suppressed(),
             }
         } else {
             // If we saw this event 3 or more times total, emit an event that indicates the total number of times we
@@ -275,6 +313,19 @@ where
     }
 }
 
+fn span_limit<S>(ctx: &Context<'_, S>) -> Option<u64>
+where
+    S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    for span in ctx.lookup_current()?.scope() {
+        let extensions = span.extensions();
+        if let Some(span_limit) = extensions.get::<SpanLimit>() {
+            return span_limit.limit_secs;
+        }
+    }
+    None
+}
+
 #[derive(Debug)]
 struct State {
     start: Instant,
@@ -421,6 +472,29 @@ impl Visit for LimitVisitor {
     fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
 }
 
+/// SpanLimit records the rate limit set on a span with the `internal_log_rate_secs` field, which
+/// applies to the rate limited events emitted within that span.
+#[derive(Default)]
+struct SpanLimit {
+    limit_secs: Option<u64>,
+}
+
+impl Visit for SpanLimit {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if field.name() == RATE_LIMIT_SECS_FIELD {
+            self.limit_secs = Some(u64::try_from(value).unwrap_or_default());
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == RATE_LIMIT_SECS_FIELD {
+            self.limit_secs = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
 #[derive(Default)]
 struct MessageVisitor {
     pub message: Option<String>,
@@ -560,6 +634,64 @@ mod test {
         );
     }
 
+    #[test]
+    fn override_rate_limit_in_span() {
+        let events: Arc<Mutex<Vec<String>>> = Default::default();
+
+        let recorder = RecordingLayer::new(Arc::clone(&events));
+        let sub = tracing_subscriber::registry::Registry::default()
+            .with(RateLimitedLayer::new(recorder).with_default_limit(100));
+        tracing::subscriber::with_default(sub, || {
+            let span = info_span!("span", component_id = "foo", internal_log_rate_secs = 1);
+            let _enter = span.enter();
+            for _ in 0..21 {
+                info!(message = "Hello world!", internal_log_rate_limit = true);
+                MockClock::advance(Duration::from_millis(100));
+            }
+        });
+
+        let events = events.lock().unwrap();
+
+        assert_eq!(
+            *events,
+            vec![
+                "Hello world!",
+                "Internal log [Hello world!] is being rate limited.",
+                "Internal log [Hello world!] has been rate limited 9 times.",
+                "Hello world!",
+                "Internal log [Hello world!] is being rate limited.",
+                "Internal log [Hello world!] has been rate limited 9 times.",
+                "Hello world!",
+            ]
+            .into_iter()
+            .map(std::borrow::ToOwned::to_owned)
+            .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn counts_suppressed_events() {
+        let events: Arc<Mutex<Vec<String>>> = Default::default();
+        let suppressed = Arc::new(Mutex::new(0));
+
+        let recorder = RecordingLayer::new(Arc::clone(&events));
+        let counter = Arc::clone(&suppressed);
+        let sub = tracing_subscriber::registry::Registry::default().with(
+            RateLimitedLayer::new(recorder)
+                .with_default_limit(1)
+                .with_suppressed_callback(move || *counter.lock().unwrap() += 1),
+        );
+        tracing::subscriber::with_default(sub, || {
+            for _ in 0..21 {
+                info!(message = "Hello world!", internal_log_rate_limit = true);
+                MockClock::advance(Duration::from_millis(100));
+            }
+        });
+
+        // Out of each window of 10 events, the first one is emitted and the others suppressed.
+        assert_eq!(*suppressed.lock().unwrap(), 18);
+    }
+
     #[test]
     fn rate_limit_by_span_key() {
         let events: Arc<Mutex<Vec<String>>> = Default::default();
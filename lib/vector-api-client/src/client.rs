@@ -11,12 +11,20 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    token: Option<String>,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL.
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self { url, token: None }
+    }
+
+    /// Sets the token sent as a bearer token with the queries, if the API requires one.
+    #[must_use]
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
     }
 
     pub async fn new_with_healthcheck(url: Url) -> Option<Self> {
@@ -56,9 +64,12 @@ impl Client {
     ) -> QueryResult<T> {
         let client = reqwest::Client::new();
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        let mut request = client.post(self.url.clone()).json(request_body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        request
             .send()
             .await
             .with_context(|| {
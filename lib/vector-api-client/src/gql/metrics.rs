@@ -177,6 +177,16 @@ impl component_sent_events_throughputs_subscription::ComponentSentEventsThroughp
 )]
 pub struct ComponentErrorsTotalsSubscription;
 
+/// ComponentErrorsTotalsByTypeSubscription contains the `component_errors_total` metric of
+/// specific components, broken down by `error_type` and `stage`.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_errors_totals_by_type.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentErrorsTotalsByTypeSubscription;
+
 /// Extension methods for metrics subscriptions
 pub trait MetricsSubscriptionExt {
     /// Executes an uptime metrics subscription.
@@ -258,6 +268,12 @@ pub trait MetricsSubscriptionExt {
         &self,
         interval: i64,
     ) -> crate::BoxedSubscription<ComponentErrorsTotalsSubscription>;
+
+    /// Executes a component errors totals by error type and stage subscription.
+    fn component_errors_totals_by_type_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentErrorsTotalsByTypeSubscription>;
 }
 
 impl MetricsSubscriptionExt for crate::SubscriptionClient {
@@ -421,4 +437,16 @@ impl MetricsSubscriptionExt for crate::SubscriptionClient {
 
         self.start::<ComponentErrorsTotalsSubscription>(&request_body)
     }
+
+    /// Executes a component errors totals by error type and stage subscription.
+    fn component_errors_totals_by_type_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentErrorsTotalsByTypeSubscription> {
+        let request_body = ComponentErrorsTotalsByTypeSubscription::build_query(
+            component_errors_totals_by_type_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentErrorsTotalsByTypeSubscription>(&request_body)
+    }
 }
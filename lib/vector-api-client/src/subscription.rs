@@ -39,12 +39,16 @@ pub struct Payload {
 }
 
 impl Payload {
-    /// Returns an "init" payload to confirm the connection to the server.
-    pub fn init(id: Uuid) -> Self {
+    /// Returns an "init" payload to confirm the connection to the server, along with the token
+    /// of the client if the API requires one.
+    pub fn init(id: Uuid, token: Option<&str>) -> Self {
         Self {
             id,
             payload_type: "connection_init".to_owned(),
-            payload: json!({}),
+            payload: match token {
+                Some(token) => json!({ "token": token }),
+                None => json!({}),
+            },
         }
     }
 
@@ -84,13 +88,18 @@ impl Payload {
 pub struct SubscriptionClient {
     tx: mpsc::UnboundedSender<Payload>,
     subscriptions: Arc<Mutex<HashMap<Uuid, Sender<Payload>>>>,
+    token: Option<String>,
     _shutdown_tx: oneshot::Sender<()>,
 }
 
 impl SubscriptionClient {
     /// Create a new subscription client. `tx` is a channel for sending `Payload`s to the
     /// GraphQL server; `rx` is a channel for `Payload` back.
-    fn new(tx: mpsc::UnboundedSender<Payload>, mut rx: mpsc::UnboundedReceiver<Payload>) -> Self {
+    fn new(
+        tx: mpsc::UnboundedSender<Payload>,
+        mut rx: mpsc::UnboundedReceiver<Payload>,
+        token: Option<String>,
+    ) -> Self {
         // Oneshot channel for cancelling the listener if SubscriptionClient is dropped
         let (_shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
 
@@ -136,6 +145,7 @@ impl SubscriptionClient {
         Self {
             tx,
             subscriptions,
+            token,
             _shutdown_tx,
         }
     }
@@ -159,7 +169,7 @@ impl SubscriptionClient {
         self.subscriptions.lock().unwrap().insert(id, tx);
 
         // Initialize the connection with the relevant control messages.
-        let _ = self.tx.send(Payload::init(id));
+        let _ = self.tx.send(Payload::init(id, self.token.as_deref()));
         let _ = self.tx.send(Payload::start::<T>(id, request_body));
 
         Box::pin(
@@ -172,9 +182,11 @@ impl SubscriptionClient {
 
 /// Connect to a new WebSocket GraphQL server endpoint, and return a `SubscriptionClient`.
 /// This method will a) connect to a ws(s):// endpoint, and perform the initial handshake, and b)
-/// set up channel forwarding to expose just the returned `Payload`s to the client.
+/// set up channel forwarding to expose just the returned `Payload`s to the client. The token, if
+/// any, is sent when initializing the subscriptions.
 pub async fn connect_subscription_client(
     url: Url,
+    token: Option<String>,
 ) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
     let (ws, _) = connect_async(url).await?;
     let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
@@ -200,5 +212,5 @@ pub async fn connect_subscription_client(
         }
     });
 
-    Ok(SubscriptionClient::new(send_tx, recv_rx))
+    Ok(SubscriptionClient::new(send_tx, recv_rx, token))
 }
@@ -1,9 +1,10 @@
 use std::{
+    collections::VecDeque,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use tokio::time::interval;
@@ -11,7 +12,10 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
+    internal_events::{
+        BufferCreated, BufferDataFilesReclaimed, BufferDrainEstimated, BufferEventsDropped,
+        BufferEventsReceived, BufferEventsSent, BufferRecordsCompressed,
+    },
     spawn_named,
 };
 
@@ -79,6 +83,156 @@ impl CategoryMetrics {
     }
 }
 
+/// Snapshot of compression metrics.
+struct CompressionSnapshot {
+    uncompressed_byte_size: u64,
+    compressed_byte_size: u64,
+}
+
+impl CompressionSnapshot {
+    /// Returns `true` if any of the values are non-zero.
+    fn has_updates(&self) -> bool {
+        self.uncompressed_byte_size > 0 || self.compressed_byte_size > 0
+    }
+}
+
+/// Compression metrics.
+///
+/// This tracks the size of records before, and after, being compressed by a buffer stage that stores records in a
+/// compressed form.
+#[derive(Debug, Default)]
+struct CompressionMetrics {
+    uncompressed_byte_size: AtomicU64,
+    compressed_byte_size: AtomicU64,
+}
+
+impl CompressionMetrics {
+    /// Increments the uncompressed and compressed byte sizes by the given amounts.
+    fn increment(&self, uncompressed_byte_size: u64, compressed_byte_size: u64) {
+        self.uncompressed_byte_size
+            .fetch_add(uncompressed_byte_size, Ordering::Relaxed);
+        self.compressed_byte_size
+            .fetch_add(compressed_byte_size, Ordering::Relaxed);
+    }
+
+    /// Gets a snapshot of the uncompressed and compressed byte sizes.
+    fn get(&self) -> CompressionSnapshot {
+        CompressionSnapshot {
+            uncompressed_byte_size: self.uncompressed_byte_size.load(Ordering::Acquire),
+            compressed_byte_size: self.compressed_byte_size.load(Ordering::Acquire),
+        }
+    }
+
+    /// Gets a snapshot of the uncompressed and compressed byte sizes by "consuming" the values.
+    fn consume(&self) -> CompressionSnapshot {
+        CompressionSnapshot {
+            uncompressed_byte_size: self.uncompressed_byte_size.swap(0, Ordering::AcqRel),
+            compressed_byte_size: self.compressed_byte_size.swap(0, Ordering::AcqRel),
+        }
+    }
+}
+
+/// The window over which the ingest and drain rates of a buffer stage are measured.
+const RATE_WINDOW: Duration = Duration::from_secs(30);
+
+/// Snapshot of drain estimates.
+#[derive(Debug, PartialEq)]
+struct DrainSnapshot {
+    oldest_event_age: Duration,
+    ingest_rate: f64,
+    drain_rate: f64,
+    time_to_drain: f64,
+}
+
+/// Drain estimates.
+///
+/// This tracks the total number of events written into, and drained out of, a buffer stage -- whether by being read,
+/// or by being dropped -- at each report. As buffers are drained in the order they're written to, the oldest event is
+/// the one following the drained ones, which was written in between the reports where the written total went past the
+/// drained total, and so its age is known at the resolution of the reports. Similarly, the ingest and drain rates are
+/// measured over the reports of the last `RATE_WINDOW`, and their difference tells how long the buffer would take to
+/// drain if they held.
+#[derive(Debug)]
+struct DrainEstimator {
+    received_total: u64,
+    drained_total: u64,
+    last_report: Instant,
+    /// The written total at each report where it grew, along with the time of the previous report, by which the events
+    /// written in between weren't written yet.
+    arrivals: VecDeque<(u64, Instant)>,
+    /// The written and drained totals at each report of the rate window.
+    window: VecDeque<(Instant, u64, u64)>,
+}
+
+impl DrainEstimator {
+    fn new(now: Instant) -> Self {
+        Self {
+            received_total: 0,
+            drained_total: 0,
+            last_report: now,
+            arrivals: VecDeque::new(),
+            window: VecDeque::from([(now, 0, 0)]),
+        }
+    }
+
+    /// Records the number of events written into, and drained out of, the buffer stage since the last report.
+    #[allow(clippy::cast_precision_loss)]
+    fn record(&mut self, received: u64, drained: u64, now: Instant) -> DrainSnapshot {
+        self.received_total += received;
+        self.drained_total += drained;
+        if received > 0 {
+            self.arrivals
+                .push_back((self.received_total, self.last_report));
+        }
+        self.last_report = now;
+
+        while matches!(self.arrivals.front(), Some((total, _)) if *total <= self.drained_total) {
+            self.arrivals.pop_front();
+        }
+        let oldest_event_age = self
+            .arrivals
+            .front()
+            .map_or(Duration::ZERO, |(_, written_after)| {
+                now.saturating_duration_since(*written_after)
+            });
+
+        // The oldest report kept is the last one from before the window, for the rates to span all of it.
+        self.window
+            .push_back((now, self.received_total, self.drained_total));
+        while self.window.len() > 2
+            && now.saturating_duration_since(self.window[1].0) >= RATE_WINDOW
+        {
+            self.window.pop_front();
+        }
+        let (since, received_since, drained_since) = self.window[0];
+        let elapsed = now.saturating_duration_since(since).as_secs_f64();
+        let (ingest_rate, drain_rate) = if elapsed > 0.0 {
+            (
+                (self.received_total - received_since) as f64 / elapsed,
+                (self.drained_total - drained_since) as f64 / elapsed,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        let buffered = self.received_total.saturating_sub(self.drained_total);
+        let time_to_drain = if buffered == 0 {
+            0.0
+        } else if drain_rate > ingest_rate {
+            buffered as f64 / (drain_rate - ingest_rate)
+        } else {
+            f64::INFINITY
+        };
+
+        DrainSnapshot {
+            oldest_event_age,
+            ingest_rate,
+            drain_rate,
+            time_to_drain,
+        }
+    }
+}
+
 /// Handle to buffer usage metrics for a specific buffer stage.
 #[derive(Clone, Debug)]
 pub struct BufferUsageHandle {
@@ -140,6 +294,24 @@ impl BufferUsageHandle {
             self.state.dropped.increment(count, byte_size);
         }
     }
+
+    /// Increments the size of records, before and after compression, written by this buffer component.
+    pub fn increment_compressed_byte_size(
+        &self,
+        uncompressed_byte_size: u64,
+        compressed_byte_size: u64,
+    ) {
+        self.state
+            .compression
+            .increment(uncompressed_byte_size, compressed_byte_size);
+    }
+
+    /// Increments the number of data files (and their total size) reclaimed by this buffer component.
+    ///
+    /// This represents storage freed once all of the records it held were acknowledged.
+    pub fn increment_reclaimed_data_file_count_and_byte_size(&self, count: u64, byte_size: u64) {
+        self.state.reclaimed.increment(count, byte_size);
+    }
 }
 
 #[derive(Debug, Default)]
@@ -150,6 +322,9 @@ struct BufferUsageData {
     dropped: CategoryMetrics,
     dropped_intentional: CategoryMetrics,
     max_size: CategoryMetrics,
+    compression: CompressionMetrics,
+    // The event count of this category tracks the number of data files, rather than events.
+    reclaimed: CategoryMetrics,
 }
 
 impl BufferUsageData {
@@ -166,6 +341,8 @@ impl BufferUsageData {
         let dropped = self.dropped.get();
         let dropped_intentional = self.dropped_intentional.get();
         let max_size = self.max_size.get();
+        let compression = self.compression.get();
+        let reclaimed = self.reclaimed.get();
 
         BufferUsageSnapshot {
             received_event_count: received.event_count,
@@ -181,6 +358,10 @@ impl BufferUsageData {
                 .event_count
                 .try_into()
                 .expect("should never be bigger than `usize`"),
+            uncompressed_byte_size: compression.uncompressed_byte_size,
+            compressed_byte_size: compression.compressed_byte_size,
+            reclaimed_data_file_count: reclaimed.event_count,
+            reclaimed_byte_size: reclaimed.event_byte_size,
         }
     }
 }
@@ -198,6 +379,10 @@ pub struct BufferUsageSnapshot {
     pub dropped_event_byte_size_intentional: u64,
     pub max_size_bytes: u64,
     pub max_size_events: usize,
+    pub uncompressed_byte_size: u64,
+    pub compressed_byte_size: u64,
+    pub reclaimed_data_file_count: u64,
+    pub reclaimed_byte_size: u64,
 }
 
 /// Builder for tracking buffer usage metrics.
@@ -247,10 +432,14 @@ impl BufferUsage {
 
         let task = async move {
             let mut interval = interval(Duration::from_secs(2));
+            let mut drains = stages
+                .iter()
+                .map(|_| DrainEstimator::new(Instant::now()))
+                .collect::<Vec<_>>();
             loop {
                 interval.tick().await;
 
-                for stage in &stages {
+                for (stage, drain) in stages.iter().zip(&mut drains) {
                     let max_size = stage.max_size.get();
                     emit(BufferCreated {
                         idx: stage.idx,
@@ -300,6 +489,37 @@ impl BufferUsage {
                             byte_size: dropped_intentional.event_byte_size,
                         });
                     }
+
+                    let drain = drain.record(
+                        received.event_count,
+                        sent.event_count + dropped.event_count + dropped_intentional.event_count,
+                        Instant::now(),
+                    );
+                    emit(BufferDrainEstimated {
+                        idx: stage.idx,
+                        oldest_event_age: drain.oldest_event_age,
+                        ingest_rate: drain.ingest_rate,
+                        drain_rate: drain.drain_rate,
+                        time_to_drain: drain.time_to_drain,
+                    });
+
+                    let compression = stage.compression.consume();
+                    if compression.has_updates() {
+                        emit(BufferRecordsCompressed {
+                            idx: stage.idx,
+                            uncompressed_byte_size: compression.uncompressed_byte_size,
+                            compressed_byte_size: compression.compressed_byte_size,
+                        });
+                    }
+
+                    let reclaimed = stage.reclaimed.consume();
+                    if reclaimed.has_updates() {
+                        emit(BufferDataFilesReclaimed {
+                            idx: stage.idx,
+                            count: reclaimed.event_count,
+                            byte_size: reclaimed.event_byte_size,
+                        });
+                    }
                 }
             }
         };
@@ -308,3 +528,48 @@ impl BufferUsage {
         spawn_named(task.instrument(span.or_current()), task_name.as_str());
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_oldest_event_age() {
+        let start = Instant::now();
+        let mut drain = DrainEstimator::new(start);
+
+        let snapshot = drain.record(10, 0, start + Duration::from_secs(2));
+        assert_eq!(snapshot.oldest_event_age, Duration::from_secs(2));
+
+        // The events written in between the first two reports are now the oldest.
+        drain.record(10, 0, start + Duration::from_secs(4));
+        let snapshot = drain.record(0, 10, start + Duration::from_secs(6));
+        assert_eq!(snapshot.oldest_event_age, Duration::from_secs(4));
+
+        let snapshot = drain.record(0, 10, start + Duration::from_secs(8));
+        assert_eq!(snapshot.oldest_event_age, Duration::ZERO);
+        assert_eq!(snapshot.time_to_drain, 0.0);
+    }
+
+    #[test]
+    fn estimates_time_to_drain() {
+        let start = Instant::now();
+        let mut drain = DrainEstimator::new(start);
+
+        // Growing, as events are drained slower than they're written.
+        let snapshot = drain.record(100, 20, start + Duration::from_secs(10));
+        assert_eq!(snapshot.ingest_rate, 10.0);
+        assert_eq!(snapshot.drain_rate, 2.0);
+        assert_eq!(snapshot.time_to_drain, f64::INFINITY);
+
+        drain.record(10, 30, start + Duration::from_secs(20));
+        drain.record(10, 30, start + Duration::from_secs(30));
+
+        // Once the first report falls out of the window, the rates are measured since the second one.
+        let snapshot = drain.record(10, 30, start + Duration::from_secs(40));
+        assert_eq!(snapshot.ingest_rate, 1.0);
+        assert_eq!(snapshot.drain_rate, 3.0);
+        assert_eq!(snapshot.time_to_drain, 10.0);
+    }
+}
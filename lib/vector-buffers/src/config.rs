@@ -38,7 +38,28 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+/// The `when_full` behaviors, including the one only supported by memory buffers.
+#[derive(Clone, Copy, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum WhenFullKind {
+    Block,
+    DropNewest,
+    Overflow,
+    OverflowToDisk,
+}
+
+impl WhenFullKind {
+    const fn when_full(self) -> Option<WhenFull> {
+        match self {
+            Self::Block => Some(WhenFull::Block),
+            Self::DropNewest => Some(WhenFull::DropNewest),
+            Self::Overflow => Some(WhenFull::Overflow),
+            Self::OverflowToDisk => None,
+        }
+    }
+}
+
+const ALL_FIELDS: [&str; 5] = ["type", "max_events", "max_size", "when_full", "compression"];
 
 struct BufferTypeVisitor;
 
@@ -50,7 +71,8 @@ impl BufferTypeVisitor {
         let mut kind: Option<BufferTypeKind> = None;
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
-        let mut when_full: Option<WhenFull> = None;
+        let mut when_full: Option<WhenFullKind> = None;
+        let mut compression: Option<DiskBufferCompression> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,36 +99,58 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "compression" => {
+                    if compression.is_some() {
+                        return Err(de::Error::duplicate_field("compression"));
+                    }
+                    compression = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
             }
         }
         let kind = kind.unwrap_or(BufferTypeKind::Memory);
-        let when_full = when_full.unwrap_or_default();
-        match kind {
-            BufferTypeKind::Memory => {
+        let when_full = when_full.unwrap_or(WhenFullKind::Block);
+        match (kind, when_full.when_full()) {
+            (BufferTypeKind::Memory, None) => Ok(BufferType::Tiered {
+                max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
+                max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
+                when_full: OverflowToDisk::OverflowToDisk,
+                compression: compression.unwrap_or_default(),
+            }),
+            (BufferTypeKind::Memory, Some(when_full)) => {
                 if max_size.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_size",
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if compression.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "compression",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
                 })
             }
-            BufferTypeKind::DiskV2 => {
+            (BufferTypeKind::DiskV2, None) => Err(de::Error::custom(
+                "`overflow_to_disk` is only supported by memory buffers",
+            )),
+            (BufferTypeKind::DiskV2, Some(when_full)) => {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "compression"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    compression: compression.unwrap_or_default(),
                 })
             }
         }
@@ -175,6 +219,38 @@ impl DiskUsage {
     }
 }
 
+/// Compression applied to records written to a disk buffer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskBufferCompression {
+    /// Records are written to disk as-is.
+    #[default]
+    None,
+
+    /// Records are compressed with [Zstandard][zstd] before being written to disk.
+    ///
+    /// This trades CPU time for disk space, and allows many more events to fit within the
+    /// configured maximum size of the buffer. Records that don't shrink when compressed are
+    /// written uncompressed.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+/// Overflowing from a memory buffer to a disk buffer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowToDisk {
+    /// Writes events to a disk buffer when the memory buffer is full.
+    ///
+    /// As soon as the memory buffer has free space again, events are buffered in memory again,
+    /// while the events written to disk are read back alongside them.
+    #[default]
+    OverflowToDisk,
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -218,10 +294,59 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        compression: DiskBufferCompression,
+    },
+
+    /// A buffer stage backed by memory, overflowing to disk.
+    ///
+    /// Events are buffered in memory, and only written to a buffer on disk when the memory buffer
+    /// is full, such as when the sink is slowed down by an outage downstream. This keeps the
+    /// latency of a memory buffer in the common case, and the durability of a disk buffer while
+    /// the memory buffer is full.
+    ///
+    /// This buffer stage must be the last one of the buffer.
+    #[configurable(title = "Events are buffered in memory, overflowing to disk.")]
+    #[serde(rename = "memory")]
+    Tiered {
+        /// The maximum number of events allowed in the memory buffer.
+        #[serde(default = "memory_buffer_default_max_events")]
+        max_events: NonZeroUsize,
+
+        /// The maximum size of the buffer on disk.
+        ///
+        /// Must be at least ~256 megabytes (268435488 bytes).
+        #[configurable(
+            validation(range(min = 268435488)),
+            metadata(docs::type_unit = "bytes")
+        )]
+        max_size: NonZeroU64,
+
+        #[configurable(derived)]
+        when_full: OverflowToDisk,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        compression: DiskBufferCompression,
     },
 }
 
 impl BufferType {
+    /// Gets the value the metrics of a buffer starting with this stage are tagged with as their
+    /// `buffer_type`.
+    ///
+    /// A memory buffer overflowing to disk is configured with the `memory` type, but is tagged as
+    /// `tiered` so that its metrics can be told apart from the ones of a memory buffer.
+    pub const fn metric_tag(&self) -> &'static str {
+        match self {
+            Self::Memory { .. } => "memory",
+            Self::DiskV2 { .. } => "disk",
+            Self::Tiered { .. } => "tiered",
+        }
+    }
+
     /// Gets the metadata around disk usage by the buffer, if supported.
     ///
     /// For buffer types that write to disk, `Some(value)` is returned with their usage metadata,
@@ -249,7 +374,7 @@ impl BufferType {
             None => None,
             Some(global_data_dir) => match self {
                 Self::Memory { .. } => None,
-                Self::DiskV2 { max_size, .. } => {
+                Self::DiskV2 { max_size, .. } | Self::Tiered { max_size, .. } => {
                     let data_dir = crate::variants::disk_v2::get_disk_v2_data_dir_path(
                         &global_data_dir,
                         id.id(),
@@ -286,9 +411,27 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                compression,
+            } => {
+                let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, max_size).with_compression(compression),
+                    when_full,
+                );
+            }
+            BufferType::Tiered {
+                max_events,
+                max_size,
+                compression,
+                ..
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                builder
+                    .stage(MemoryBuffer::new(max_events), WhenFull::Overflow)
+                    .stage(
+                        DiskV2Buffer::new(id, data_dir, max_size).with_compression(compression),
+                        WhenFull::Block,
+                    );
             }
         };
 
@@ -389,7 +532,8 @@ impl BufferConfig {
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
-    use crate::{BufferConfig, BufferType, WhenFull};
+    use super::OverflowToDisk;
+    use crate::{BufferConfig, BufferType, DiskBufferCompression, WhenFull};
 
     fn check_single_stage(source: &str, expected: BufferType) {
         let config: BufferConfig = serde_yaml::from_str(source).unwrap();
@@ -522,7 +666,101 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_disk_compression() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          compression: zstd
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::Zstd,
             },
         );
+
+        let source = r#"
+          type: memory
+          compression: zstd
+          "#;
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "data did not match any variant of untagged enum BufferConfig"
+        );
+    }
+
+    #[test]
+    fn parse_overflow_to_disk() {
+        check_single_stage(
+            r#"
+          type: memory
+          max_events: 100
+          when_full: overflow_to_disk
+          max_size: 1024
+          "#,
+            BufferType::Tiered {
+                max_events: NonZeroUsize::new(100).unwrap(),
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: OverflowToDisk::OverflowToDisk,
+                compression: DiskBufferCompression::None,
+            },
+        );
+
+        for source in [
+            r#"
+          type: memory
+          when_full: overflow_to_disk
+          "#,
+            r#"
+          type: disk
+          max_size: 1024
+          when_full: overflow_to_disk
+          "#,
+        ] {
+            let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "data did not match any variant of untagged enum BufferConfig"
+            );
+        }
+    }
+
+    #[test]
+    fn serialize_overflow_to_disk() {
+        let config = BufferConfig::Single(BufferType::Tiered {
+            max_events: NonZeroUsize::new(100).unwrap(),
+            max_size: NonZeroU64::new(1024).unwrap(),
+            when_full: OverflowToDisk::OverflowToDisk,
+            compression: DiskBufferCompression::Zstd,
+        });
+        let serialized = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<BufferConfig>(&serialized).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn overflow_to_disk_has_its_own_metric_tag() {
+        let memory = BufferType::Memory {
+            max_events: NonZeroUsize::new(100).unwrap(),
+            when_full: WhenFull::Block,
+        };
+        let tiered = BufferType::Tiered {
+            max_events: NonZeroUsize::new(100).unwrap(),
+            max_size: NonZeroU64::new(1024).unwrap(),
+            when_full: OverflowToDisk::OverflowToDisk,
+            compression: DiskBufferCompression::None,
+        };
+        assert_eq!(memory.metric_tag(), "memory");
+        assert_eq!(tiered.metric_tag(), "tiered");
     }
 }
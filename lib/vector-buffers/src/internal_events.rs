@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use metrics::{counter, decrement_gauge, gauge, increment_gauge};
 use vector_common::internal_event::{error_type, InternalEvent};
 
@@ -89,6 +91,63 @@ impl InternalEvent for BufferEventsDropped {
     }
 }
 
+pub struct BufferRecordsCompressed {
+    pub idx: usize,
+    pub uncompressed_byte_size: u64,
+    pub compressed_byte_size: u64,
+}
+
+impl InternalEvent for BufferRecordsCompressed {
+    #[allow(clippy::cast_precision_loss)]
+    fn emit(self) {
+        counter!("buffer_uncompressed_bytes_total", self.uncompressed_byte_size, "stage" => self.idx.to_string());
+        counter!("buffer_compressed_bytes_total", self.compressed_byte_size, "stage" => self.idx.to_string());
+        if self.compressed_byte_size != 0 {
+            gauge!(
+                "buffer_compression_ratio",
+                self.uncompressed_byte_size as f64 / self.compressed_byte_size as f64,
+                "stage" => self.idx.to_string()
+            );
+        }
+    }
+}
+
+pub struct BufferDrainEstimated {
+    pub idx: usize,
+    pub oldest_event_age: Duration,
+    pub ingest_rate: f64,
+    pub drain_rate: f64,
+    pub time_to_drain: f64,
+}
+
+impl InternalEvent for BufferDrainEstimated {
+    fn emit(self) {
+        gauge!("buffer_oldest_event_age_seconds", self.oldest_event_age.as_secs_f64(), "stage" => self.idx.to_string());
+        gauge!("buffer_ingest_events_per_second", self.ingest_rate, "stage" => self.idx.to_string());
+        gauge!("buffer_drain_events_per_second", self.drain_rate, "stage" => self.idx.to_string());
+        gauge!("buffer_time_to_drain_seconds", self.time_to_drain, "stage" => self.idx.to_string());
+    }
+}
+
+pub struct BufferDataFilesReclaimed {
+    pub idx: usize,
+    pub count: u64,
+    pub byte_size: u64,
+}
+
+impl InternalEvent for BufferDataFilesReclaimed {
+    fn emit(self) {
+        debug!(
+            message = "Reclaimed fully acknowledged data files.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+            stage = %self.idx,
+        );
+        counter!("buffer_reclaimed_data_files_total", self.count, "stage" => self.idx.to_string());
+        counter!("buffer_reclaimed_bytes_total", self.byte_size, "stage" => self.idx.to_string());
+    }
+}
+
 pub struct BufferReadError {
     pub error_code: &'static str,
     pub error: String,
@@ -391,6 +391,18 @@ where
             .increment_received_event_count_and_byte_size(event_count, record_size);
     }
 
+    /// Tracks the statistics of compressing a record before writing it.
+    pub fn track_compression(&self, uncompressed_size: u64, compressed_size: u64) {
+        self.usage_handle
+            .increment_compressed_byte_size(uncompressed_size, compressed_size);
+    }
+
+    /// Tracks the statistics of deleting a data file whose records have all been acknowledged.
+    pub fn track_data_file_reclaimed(&self, data_file_size: u64) {
+        self.usage_handle
+            .increment_reclaimed_data_file_count_and_byte_size(1, data_file_size);
+    }
+
     /// Tracks the statistics of multiple successful reads.
     pub fn track_reads(&self, event_count: u64, total_record_size: u64) {
         self.decrement_total_buffer_size(total_record_size);
@@ -62,6 +62,17 @@
 //! wrap around at 65,536 (2^16), the maximum data file size in total for a given buffer is ~8TB (6
 //! 5k files * 128MB).
 //!
+//! ## Compressing records
+//!
+//! When compression is enabled, the encoded payload of each record is compressed with Zstandard
+//! before being wrapped and serialized, and the record is marked as compressed by setting the
+//! highest bit of its metadata. Records that don't shrink when compressed are written as-is, and
+//! since the flag lives in each record, a buffer can freely mix compressed and uncompressed
+//! records, such as when compression is enabled or disabled for an existing buffer.
+//!
+//! As the buffer size limits are based on the size of records on disk, compression allows more
+//! events to fit in a buffer of a given size.
+//!
 //! ## Reading records
 //!
 //! Due to the on-disk layout, reading records is an incredibly straight-forward progress: we open a
@@ -82,6 +93,14 @@
 //! progress as records are acknowledged, even if the buffer is close to, or at the maximum buffer
 //! size limit.
 //!
+//! Every deleted data file, and the number of bytes it occupied on disk, is reported as reclaimed
+//! through the buffer usage metrics.
+//!
+//! There is no background compaction of data files: as a data file is deleted as soon as all of
+//! its records are acknowledged, there is never a fully acknowledged data file left to compact, and
+//! the records of a partially acknowledged one are still to be read in order. Compacting those
+//! would mean rewriting records the reader may be about to read, so it's deliberately left out.
+//!
 //! ## Record ID generation, and its relation of events
 //!
 //! While the buffer talks a lot about writing "records", records are ostensibly a single event, or
@@ -174,7 +193,7 @@ use crate::{
         builder::IntoBuffer,
         channel::{ReceiverAdapter, SenderAdapter},
     },
-    Bufferable,
+    Bufferable, DiskBufferCompression,
 };
 
 /// Error that occurred when creating/loading a disk buffer.
@@ -267,6 +286,7 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    compression: DiskBufferCompression,
 }
 
 impl DiskV2Buffer {
@@ -275,8 +295,16 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            compression: DiskBufferCompression::default(),
         }
     }
+
+    /// Sets the compression applied to records written to the buffer.
+    #[must_use]
+    pub fn with_compression(mut self, compression: DiskBufferCompression) -> Self {
+        self.compression = compression;
+        self
+    }
 }
 
 #[async_trait]
@@ -297,6 +325,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.compression,
         )
         .await?;
 
@@ -309,6 +338,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    compression: DiskBufferCompression,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -324,6 +354,7 @@ where
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
     let config = DiskBufferConfigBuilder::from_path(buffer_path)
         .max_buffer_size(max_size.get())
+        .compression(compression)
         .build()?;
     Buffer::from_config(config, usage_handle)
         .await
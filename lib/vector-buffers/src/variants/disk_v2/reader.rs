@@ -108,6 +108,13 @@ where
     #[snafu(display("record version not compatible: {}", reason))]
     Incompatible { reason: String },
 
+    /// The record payload could not be decompressed.
+    ///
+    /// At this stage, the record can be assumed to have been written correctly, and read correctly
+    /// from disk, as the checksum was also validated.
+    #[snafu(display("failed to decompress record: {}", reason))]
+    Decompression { reason: String },
+
     /// The reader detected that a data file contains a partially-written record.
     ///
     /// Records should never be partially written to a data file (we don't split records across data
@@ -145,6 +152,7 @@ where
             ReaderError::Checksum { .. } => "checksum_mismatch",
             ReaderError::Decode { .. } => "decode_failed",
             ReaderError::Incompatible { .. } => "incompatible_record_version",
+            ReaderError::Decompression { .. } => "decompression_failed",
             ReaderError::PartialWrite => "partial_write",
             ReaderError::EmptyRecord => "empty_record",
         }
@@ -160,6 +168,7 @@ where
             | ReaderError::Checksum { .. }
             | ReaderError::Decode { .. }
             | ReaderError::Incompatible { .. }
+            | ReaderError::Decompression { .. }
             | ReaderError::PartialWrite => Some(BufferReadError { error_code, error }),
         }
     }
@@ -186,9 +195,11 @@ impl<T: Bufferable> PartialEq for ReaderError<T> {
                 },
             ) => l_calculated == r_calculated && l_actual == r_actual,
             (Self::Decode { .. }, Self::Decode { .. }) => true,
-            (Self::Incompatible { reason: l_reason }, Self::Incompatible { reason: r_reason }) => {
-                l_reason == r_reason
-            }
+            (Self::Incompatible { reason: l_reason }, Self::Incompatible { reason: r_reason })
+            | (
+                Self::Decompression { reason: l_reason },
+                Self::Decompression { reason: r_reason },
+            ) => l_reason == r_reason,
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -546,6 +557,7 @@ where
             .delete_file(&data_file_path)
             .await?;
         self.ledger.increment_acked_reader_file_id();
+        self.ledger.track_data_file_reclaimed(metadata.len());
         self.ledger.flush()?;
 
         debug!("Flushed after deleting data file, notifying writers and continuing.");
@@ -1118,6 +1130,14 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         });
     }
 
-    // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    // Now we can finally try decoding, decompressing the payload first if need be.
+    if record.is_compressed() {
+        let payload =
+            zstd::stream::decode_all(record.payload()).map_err(|e| ReaderError::Decompression {
+                reason: e.to_string(),
+            })?;
+        T::decode(metadata, &payload[..]).context(DecodeSnafu)
+    } else {
+        T::decode(metadata, record.payload()).context(DecodeSnafu)
+    }
 }
@@ -15,6 +15,12 @@ use super::{
 
 pub const RECORD_HEADER_LEN: usize = align16(mem::size_of::<ArchivedRecord<'_>>() + 8);
 
+/// Record metadata flag indicating that the payload is compressed.
+///
+/// The highest bit of the record metadata is reserved for the buffer itself, and is never set in
+/// the metadata provided by `Encodable::Metadata`.
+pub const RECORD_COMPRESSED_FLAG: u32 = 1 << 31;
+
 /// Result of checking if a buffer contained a valid record.
 pub enum RecordStatus {
     /// The record was able to be read from the buffer, and the checksum is valid.
@@ -62,7 +68,7 @@ pub struct Record<'a> {
 
     /// The record metadata.
     ///
-    /// Based on `Encodable::Metadata`.
+    /// Based on `Encodable::Metadata`, with the addition of [`RECORD_COMPRESSED_FLAG`].
     pub(super) metadata: u32,
 
     /// The record payload.
@@ -129,8 +135,16 @@ impl<'a> Record<'a> {
 
 impl<'a> ArchivedRecord<'a> {
     /// Gets the metadata of this record.
+    ///
+    /// This is the metadata provided by `Encodable::Metadata`, without any flags reserved for the
+    /// buffer itself.
     pub fn metadata(&self) -> u32 {
-        self.metadata
+        self.metadata & !RECORD_COMPRESSED_FLAG
+    }
+
+    /// Whether or not the payload of this record is compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.metadata & RECORD_COMPRESSED_FLAG != 0
     }
 
     /// Gets the payload of this record.
@@ -3,6 +3,7 @@ use std::io::Cursor;
 use crate::{
     test::SizedRecord,
     variants::disk_v2::{reader::RecordReader, writer::RecordWriter},
+    DiskBufferCompression,
 };
 
 #[tokio::test]
@@ -10,7 +11,14 @@ async fn roundtrip_through_record_writer_and_record_reader() {
     // Create a duplex stream that's more than big enough to ship a record through.
     let (writer_io, reader_io) = tokio::io::duplex(4096);
 
-    let mut record_writer = RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048);
+    let mut record_writer = RecordWriter::new(
+        writer_io,
+        0,
+        16_384,
+        u64::MAX,
+        2048,
+        DiskBufferCompression::None,
+    );
     let mut record_reader = RecordReader::new(reader_io);
 
     let record = SizedRecord::new(73);
@@ -38,6 +46,72 @@ async fn roundtrip_through_record_writer_and_record_reader() {
     assert_eq!(record, roundtrip_record);
 }
 
+#[tokio::test]
+async fn roundtrip_compressed_record_through_record_writer_and_record_reader() {
+    let (writer_io, reader_io) = tokio::io::duplex(4096);
+
+    let mut record_writer = RecordWriter::new(
+        writer_io,
+        0,
+        16_384,
+        u64::MAX,
+        2048,
+        DiskBufferCompression::Zstd,
+    );
+    let mut record_reader = RecordReader::new(reader_io);
+
+    // Sized records are filled with a single repeated byte, so they compress very well.
+    let record = SizedRecord::new(1024);
+
+    let token = record_writer
+        .archive_record(314, record.clone())
+        .expect("archive should not fail");
+    let (uncompressed_len, compressed_len) = token
+        .compressed_len()
+        .expect("record should have been compressed");
+    assert!(compressed_len < uncompressed_len);
+
+    let (bytes_written, _) = record_writer
+        .flush_record(token)
+        .await
+        .expect("write should not fail");
+    record_writer.flush().await.expect("flush should not fail");
+    assert!(bytes_written < uncompressed_len);
+
+    let read_token = record_reader
+        .try_next_record(false)
+        .await
+        .expect("read should not fail")
+        .expect("record should be present");
+    assert_eq!(bytes_written, read_token.record_bytes());
+    assert_eq!(314, read_token.record_id());
+
+    let roundtrip_record = record_reader
+        .read_record(read_token)
+        .expect("read should not fail");
+    assert_eq!(record, roundtrip_record);
+}
+
+#[tokio::test]
+async fn record_not_shrinking_when_compressed_is_not_counted_as_compressed() {
+    let (writer_io, _reader_io) = tokio::io::duplex(4096);
+
+    let mut record_writer = RecordWriter::new(
+        writer_io,
+        0,
+        16_384,
+        u64::MAX,
+        2048,
+        DiskBufferCompression::Zstd,
+    );
+
+    // A record this small only grows once wrapped in a Zstandard frame.
+    let token = record_writer
+        .archive_record(314, SizedRecord::new(1))
+        .expect("archive should not fail");
+    assert_eq!(token.compressed_len(), None);
+}
+
 #[tokio::test]
 async fn record_reader_always_returns_none_when_no_data() {
     let reader_io = Cursor::new(Vec::new());
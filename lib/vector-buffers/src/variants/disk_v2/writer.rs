@@ -34,9 +34,9 @@ use crate::{
     variants::disk_v2::{
         io::AsyncFile,
         reader::decode_record_payload,
-        record::{try_as_record_archive, RECORD_HEADER_LEN},
+        record::{try_as_record_archive, RECORD_COMPRESSED_FLAG, RECORD_HEADER_LEN},
     },
-    Bufferable,
+    Bufferable, DiskBufferCompression,
 };
 
 /// Error that occurred during calls to [`Writer`].
@@ -208,6 +208,7 @@ where
 pub(super) struct WriteToken {
     event_count: usize,
     serialized_len: usize,
+    compressed_len: Option<(usize, usize)>,
 }
 
 impl WriteToken {
@@ -218,6 +219,14 @@ impl WriteToken {
     pub fn serialized_len(&self) -> usize {
         self.serialized_len
     }
+
+    /// Gets the length of the encoded record before and after compression.
+    ///
+    /// Only returns `Some` if the record is written compressed, so that the records which didn't
+    /// shrink when compressed don't count towards the compression ratio.
+    pub fn compressed_len(&self) -> Option<(usize, usize)> {
+        self.compressed_len
+    }
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -360,12 +369,14 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    compress_buf: Vec<u8>,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
     max_record_size: usize,
     current_data_file_size: u64,
     max_data_file_size: u64,
+    compression: DiskBufferCompression,
     _t: PhantomData<T>,
 }
 
@@ -384,6 +395,7 @@ where
         write_buffer_size: usize,
         max_data_file_size: u64,
         max_record_size: usize,
+        compression: DiskBufferCompression,
     ) -> Self {
         // These should also be getting checked at a higher level, but we're double-checking them here to be absolutely sure.
         let max_record_size_converted = u64::try_from(max_record_size)
@@ -410,12 +422,14 @@ where
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            compress_buf: Vec::new(),
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
             max_record_size,
             current_data_file_size,
             max_data_file_size,
+            compression,
             _t: PhantomData,
         }
     }
@@ -436,6 +450,28 @@ where
         self.current_data_file_size + amount <= self.max_data_file_size
     }
 
+    /// Compresses the encoded record into the compression buffer, if compression is enabled.
+    ///
+    /// The length of the compressed record is returned, or `None` if compression is disabled.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs during compression, an error variant will be returned describing the error.
+    fn compress_record(&mut self) -> Result<Option<usize>, WriterError<T>> {
+        match self.compression {
+            DiskBufferCompression::None => Ok(None),
+            DiskBufferCompression::Zstd => {
+                self.compress_buf.clear();
+                zstd::stream::copy_encode(&self.encode_buf[..], &mut self.compress_buf, 0)
+                    .map_err(|e| WriterError::FailedToSerialize {
+                        reason: format!("failed to compress encoded record: {e}"),
+                    })?;
+
+                Ok(Some(self.compress_buf.len()))
+            }
+        }
+    }
+
     /// Archives a record.
     ///
     /// This encodes the record, as well as serializes it into its archival format that will be
@@ -480,9 +516,18 @@ where
             });
         }
 
-        let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+        // Compress the encoded record if compression is enabled, marking it as compressed as long
+        // as compressing it actually made it smaller.
+        let compressed_len = self.compress_record()?;
+
+        let mut metadata = T::get_metadata().into_u32();
+        let payload = if compressed_len.map_or(false, |len| len < encoded_len) {
+            metadata |= RECORD_COMPRESSED_FLAG;
+            &self.compress_buf[..]
+        } else {
+            &self.encode_buf[..]
+        };
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -560,6 +605,9 @@ where
         Ok(WriteToken {
             event_count,
             serialized_len,
+            compressed_len: compressed_len
+                .filter(|len| *len < encoded_len)
+                .map(|len| (encoded_len, len)),
         })
     }
 
@@ -665,18 +713,9 @@ where
             }
         })?;
 
-        // Now we can actually decode it as `T`.
-        let record_metadata = T::Metadata::from_u32(wrapped_record.metadata()).ok_or(
-            WriterError::InconsistentState {
-                reason: "failed to decode record metadata immediately after encoding it"
-                    .to_string(),
-            },
-        )?;
-
-        T::decode(record_metadata, wrapped_record.payload()).map_err(|_| {
-            WriterError::InconsistentState {
-                reason: "failed to decode record immediately after encoding it".to_string(),
-            }
+        // Now we can actually decode it as `T`, which also takes care of decompressing it.
+        decode_record_payload(wrapped_record).map_err(|e| WriterError::InconsistentState {
+            reason: format!("failed to decode record immediately after encoding it: {e}"),
         })
     }
 
@@ -1126,6 +1165,7 @@ where
                     self.config.write_buffer_size,
                     self.config.max_data_file_size,
                     self.config.max_record_size,
+                    self.config.compression,
                 ));
                 self.data_file_size = data_file_size;
 
@@ -1233,6 +1273,7 @@ where
         //
         // Otherwise, we proceed with flushing like we normally would.
         let can_write_record = self.can_write_record(token.serialized_len());
+        let compressed_len = token.compressed_len();
         let writer = self
             .writer
             .as_mut()
@@ -1257,6 +1298,10 @@ where
         // setting the ledger state to a record ID that we may never have actually written, which
         // could lead to record ID gaps.
         self.track_write(record_events.get(), bytes_written as u64);
+        if let Some((uncompressed_len, compressed_len)) = compressed_len {
+            self.ledger
+                .track_compression(uncompressed_len as u64, compressed_len as u64);
+        }
 
         // If we did flush some buffered writes during this write, however, we now compensate for
         // that after updating our internal state.  We'll also notify the reader, too, since the
@@ -3,7 +3,14 @@
 //! as it flows through transforms, being duplicated and merged, and
 //! then report its status when the last copy is delivered or dropped.
 
-use std::{cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
+use std::{
+    cmp, fmt,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Poll,
+};
 
 use crossbeam_utils::atomic::AtomicCell;
 use futures::future::FutureExt;
@@ -86,6 +93,14 @@ impl EventFinalizers {
         }
     }
 
+    /// Records the details of the failure to deliver the events of all event finalizers in the
+    /// collection.
+    pub fn record_failure(&self, failure: &DeliveryFailure) {
+        for finalizer in &self.0 {
+            finalizer.batch.record_failure(failure);
+        }
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -157,22 +172,26 @@ impl Drop for EventFinalizer {
     }
 }
 
+/// The details of the last failure to deliver the events of a batch, as recorded by the sink.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeliveryFailure {
+    /// The status code returned for the request, for the protocols that have one.
+    pub status_code: Option<u16>,
+    /// The class of the error, such as the type of error returned by the service.
+    pub error_class: String,
+    /// The number of times the delivery was attempted.
+    pub attempts: usize,
+}
+
 /// A convenience newtype wrapper for the one-shot receiver for an
 /// individual batch status.
 #[pin_project::pin_project]
-pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+pub struct BatchStatusReceiver(oneshot::Receiver<(BatchStatus, Option<DeliveryFailure>)>);
 
 impl Future for BatchStatusReceiver {
     type Output = BatchStatus;
-    fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        match self.0.poll_unpin(ctx) {
-            Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(status)) => Poll::Ready(status),
-            Poll::Ready(Err(error)) => {
-                error!(%error, "Batch status receiver dropped before sending.");
-                Poll::Ready(BatchStatus::Errored)
-            }
-        }
+    fn poll(self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        self.poll_outcome(ctx).map(|(status, _)| status)
     }
 }
 
@@ -184,7 +203,27 @@ impl BatchStatusReceiver {
     /// - `TryRecvError::Empty` if no value has been sent yet.
     /// - `TryRecvError::Closed` if the sender has dropped without sending a value.
     pub fn try_recv(&mut self) -> Result<BatchStatus, oneshot::error::TryRecvError> {
-        self.0.try_recv()
+        self.0.try_recv().map(|(status, _)| status)
+    }
+
+    /// Waits for the status of the batch, along with the details of the last failure to deliver
+    /// its events, if any was recorded.
+    pub async fn outcome(mut self) -> (BatchStatus, Option<DeliveryFailure>) {
+        std::future::poll_fn(|ctx| Pin::new(&mut self).poll_outcome(ctx)).await
+    }
+
+    fn poll_outcome(
+        mut self: Pin<&mut Self>,
+        ctx: &mut std::task::Context<'_>,
+    ) -> Poll<(BatchStatus, Option<DeliveryFailure>)> {
+        match self.0.poll_unpin(ctx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(outcome)) => Poll::Ready(outcome),
+            Poll::Ready(Err(error)) => {
+                error!(%error, "Batch status receiver dropped before sending.");
+                Poll::Ready((BatchStatus::Errored, None))
+            }
+        }
     }
 }
 
@@ -201,11 +240,26 @@ impl BatchNotifier {
         let (sender, receiver) = oneshot::channel();
         let notifier = OwnedBatchNotifier {
             status: AtomicCell::new(BatchStatus::Delivered),
-            notifier: Some(sender),
+            failure: Mutex::new(None),
+            notifier: Some(Notify::Receiver(sender)),
         };
         (Self(Arc::new(notifier)), BatchStatusReceiver(receiver))
     }
 
+    /// Creates a new `BatchNotifier` calling `callback` with the finalization status of the batch,
+    /// from wherever its last event is finalized.
+    ///
+    /// This spares awaiting a receiver when all that's left to do once the batch is finalized is
+    /// quick and doesn't block, such as releasing resources held for its events.
+    #[must_use]
+    pub fn new_with_callback(callback: impl FnOnce(BatchStatus) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(OwnedBatchNotifier {
+            status: AtomicCell::new(BatchStatus::Delivered),
+            failure: Mutex::new(None),
+            notifier: Some(Notify::Callback(Box::new(callback))),
+        }))
+    }
+
     /// Optionally creates a new `BatchNotifier` along with the receiver used to await its finalization status.
     #[must_use]
     pub fn maybe_new_with_receiver(enabled: bool) -> (Option<Self>, Option<BatchStatusReceiver>) {
@@ -250,13 +304,40 @@ impl BatchNotifier {
                 .unwrap_or_else(|_| unreachable!());
         }
     }
+
+    /// Records the details of the failure to deliver the events of the batch, replacing those of
+    /// any earlier failure.
+    fn record_failure(&self, failure: &DeliveryFailure) {
+        let mut last = self
+            .0
+            .failure
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        *last = Some(failure.clone());
+    }
 }
 
 /// The non-shared data underlying the shared `BatchNotifier`
 #[derive(Debug)]
 pub struct OwnedBatchNotifier {
     status: AtomicCell<BatchStatus>,
-    notifier: Option<oneshot::Sender<BatchStatus>>,
+    failure: Mutex<Option<DeliveryFailure>>,
+    notifier: Option<Notify>,
+}
+
+/// How the status of a batch is sent back once it's finalized.
+enum Notify {
+    Receiver(oneshot::Sender<(BatchStatus, Option<DeliveryFailure>)>),
+    Callback(Box<dyn FnOnce(BatchStatus) + Send + Sync>),
+}
+
+impl fmt::Debug for Notify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Receiver(sender) => f.debug_tuple("Receiver").field(sender).finish(),
+            Self::Callback(_) => f.write_str("Callback"),
+        }
+    }
 }
 
 impl OwnedBatchNotifier {
@@ -264,9 +345,22 @@ impl OwnedBatchNotifier {
     fn send_status(&mut self) {
         if let Some(notifier) = self.notifier.take() {
             let status = self.status.load();
-            // Ignore the error case, as it will happen during normal
-            // source shutdown and we can't detect that here.
-            let _ = notifier.send(status);
+            match notifier {
+                Notify::Receiver(notifier) => {
+                    let failure = match status {
+                        BatchStatus::Delivered => None,
+                        BatchStatus::Errored | BatchStatus::Rejected => self
+                            .failure
+                            .get_mut()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner)
+                            .take(),
+                    };
+                    // Ignore the error case, as it will happen during normal
+                    // source shutdown and we can't detect that here.
+                    let _ = notifier.send((status, failure));
+                }
+                Notify::Callback(callback) => callback(status),
+            }
         }
     }
 }
@@ -418,6 +512,20 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[test]
+    fn calls_back_once_finalized() {
+        let status = Arc::new(Mutex::new(None));
+        let batch = BatchNotifier::new_with_callback({
+            let status = Arc::clone(&status);
+            move |batch_status| *status.lock().unwrap() = Some(batch_status)
+        });
+        let mut fin = EventFinalizers::new(EventFinalizer::new(batch));
+        fin.update_status(EventStatus::Rejected);
+        assert_eq!(*status.lock().unwrap(), None);
+        drop(fin);
+        assert_eq!(*status.lock().unwrap(), Some(BatchStatus::Rejected));
+    }
+
     #[test]
     fn early_update() {
         let (mut fin, mut receiver) = make_finalizer();
@@ -507,6 +615,31 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[tokio::test]
+    async fn reports_failure_of_failed_batch() {
+        let failure = DeliveryFailure {
+            status_code: Some(400),
+            error_class: "mapper_parsing_exception".into(),
+            attempts: 2,
+        };
+
+        let (fin, receiver) = make_finalizer();
+        fin.record_failure(&failure);
+        fin.update_status(EventStatus::Rejected);
+        drop(fin);
+        assert_eq!(
+            receiver.outcome().await,
+            (BatchStatus::Rejected, Some(failure.clone()))
+        );
+
+        // The failure of an attempt after which the events were delivered isn't reported.
+        let (fin, receiver) = make_finalizer();
+        fin.record_failure(&failure);
+        fin.update_status(EventStatus::Delivered);
+        drop(fin);
+        assert_eq!(receiver.outcome().await, (BatchStatus::Delivered, None));
+    }
+
     fn make_finalizer() -> (EventFinalizers, BatchStatusReceiver) {
         let (batch, receiver) = BatchNotifier::new_with_receiver();
         let finalizer = EventFinalizers::new(EventFinalizer::new(batch));
@@ -5,6 +5,7 @@ mod events_received;
 mod events_sent;
 mod prelude;
 pub mod service;
+mod tls;
 
 pub use metrics::SharedString;
 
@@ -15,6 +16,7 @@ pub use events_received::EventsReceived;
 pub use events_sent::{EventsSent, DEFAULT_OUTPUT};
 pub use prelude::{error_stage, error_type};
 pub use service::{CallError, PollReadyError};
+pub use tls::{TlsReloadError, TlsReloaded};
 
 pub trait InternalEvent: Sized {
     fn emit(self);
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use metrics::counter;
+
+use super::{error_type, InternalEvent};
+
+#[derive(Debug)]
+pub struct TlsReloaded<'a> {
+    pub files: &'a [PathBuf],
+}
+
+impl<'a> InternalEvent for TlsReloaded<'a> {
+    fn emit(self) {
+        info!(message = "Reloaded TLS certificates.", files = ?self.files);
+        counter!("tls_reloads_total", 1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("TlsReloaded")
+    }
+}
+
+#[derive(Debug)]
+pub struct TlsReloadError<'a, E> {
+    pub files: &'a [PathBuf],
+    pub error: E,
+    pub stage: &'static str,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for TlsReloadError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to reload TLS certificates, keeping the previous ones.",
+            files = ?self.files,
+            error = %self.error,
+            error_code = "tls_reload_failed",
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = self.stage,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "tls_reload_failed",
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => self.stage,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("TlsReloadError")
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::Add;
+use std::{fmt, ops::Add, str::FromStr};
 
 /// Metadata for batch requests.
 #[derive(Clone, Copy, Debug, Default)]
@@ -15,6 +15,8 @@ pub struct RequestMetadata {
     ///
     /// This is akin to the bytes sent/received over the network, regardless of whether or not compression was used.
     request_wire_size: usize,
+    /// The context of the trace of the request, tying the spans of its lifecycle together.
+    trace_context: Option<TraceContext>,
 }
 
 // TODO: Make this struct the object which emits the actual internal telemetry i.e. events sent, bytes sent, etc.
@@ -33,9 +35,17 @@ impl RequestMetadata {
             events_estimated_json_encoded_byte_size,
             request_encoded_size,
             request_wire_size,
+            trace_context: None,
         }
     }
 
+    /// Sets the context of the trace the spans of the request are part of.
+    #[must_use]
+    pub const fn with_trace_context(mut self, trace_context: Option<TraceContext>) -> Self {
+        self.trace_context = trace_context;
+        self
+    }
+
     #[must_use]
     pub const fn event_count(&self) -> usize {
         self.event_count
@@ -61,6 +71,11 @@ impl RequestMetadata {
         self.request_wire_size
     }
 
+    #[must_use]
+    pub const fn trace_context(&self) -> Option<TraceContext> {
+        self.trace_context
+    }
+
     /// Constructs a `RequestMetadata` by summation of the "batch" of `RequestMetadata` provided.
     #[must_use]
     pub fn from_batch<T: IntoIterator<Item = RequestMetadata>>(metadata_iter: T) -> Self {
@@ -85,6 +100,7 @@ impl<'a> Add<&'a RequestMetadata> for RequestMetadata {
                 + other.events_estimated_json_encoded_byte_size,
             request_encoded_size: self.request_encoded_size + other.request_encoded_size,
             request_wire_size: self.request_wire_size + other.request_wire_size,
+            trace_context: self.trace_context.or(other.trace_context),
         }
     }
 }
@@ -94,3 +110,76 @@ pub trait MetaDescriptive {
     /// Returns the `RequestMetadata` associated with this object.
     fn get_metadata(&self) -> RequestMetadata;
 }
+
+/// The context of a span of the internal traces, propagated along with a request so the spans of
+/// its lifecycle are part of the same trace, even when they are run by different tasks.
+///
+/// It is formatted as a [W3C `traceparent`][traceparent], though only the sampled flag is kept.
+///
+/// [traceparent]: https://www.w3.org/TR/trace-context/#traceparent-header
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TraceContext {
+    pub trace_id: u128,
+    pub span_id: u64,
+    pub sampled: bool,
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id,
+            self.span_id,
+            u8::from(self.sampled)
+        )
+    }
+}
+
+impl FromStr for TraceContext {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+        match (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) {
+            (Some("00"), Some(trace_id), Some(span_id), Some(flags), None)
+                if trace_id.len() == 32 && span_id.len() == 16 && flags.len() == 2 =>
+            {
+                let invalid = |_| "invalid trace context";
+                Ok(Self {
+                    trace_id: u128::from_str_radix(trace_id, 16).map_err(invalid)?,
+                    span_id: u64::from_str_radix(span_id, 16).map_err(invalid)?,
+                    sampled: u8::from_str_radix(flags, 16).map_err(invalid)? & 1 == 1,
+                })
+            }
+            _ => Err("invalid trace context"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_context_round_trips() {
+        let context = TraceContext {
+            trace_id: 0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736,
+            span_id: 0x00f0_67aa_0ba9_02b7,
+            sampled: true,
+        };
+        let formatted = context.to_string();
+        assert_eq!(
+            formatted,
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+        assert_eq!(formatted.parse::<TraceContext>(), Ok(context));
+        assert!("00-4bf9-00f0-01".parse::<TraceContext>().is_err());
+    }
+}
@@ -5,7 +5,7 @@ use toml::Value;
 use vector_config_common::attributes::CustomAttribute;
 
 use super::{ComponentMarker, GenerateConfig};
-use crate::schema::{SchemaGenerator, SchemaObject};
+use crate::schema::{Map, Schema, SchemaGenerator, SchemaObject, SchemaSettings};
 use crate::{schema, Configurable, ConfigurableRef, GenerateError, Metadata};
 
 #[derive(Debug, Snafu, Clone, PartialEq, Eq)]
@@ -15,6 +15,25 @@ pub enum ExampleError {
 
     #[snafu(display("component '{}' does not exist", component_name))]
     DoesNotExist { component_name: String },
+
+    #[snafu(display("unable to describe the options of this component: {}", message))]
+    InvalidSchema { message: String },
+}
+
+/// An option of a component, as described by the schema of its configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentOption {
+    /// The name of the option.
+    pub name: String,
+
+    /// The description of the option, if it has one.
+    pub description: Option<String>,
+
+    /// Whether the option must be set.
+    pub required: bool,
+
+    /// The default value of the option, if it has one.
+    pub default: Option<serde_json::Value>,
 }
 
 /// Description of a component.
@@ -75,6 +94,36 @@ where
             .and_then(|t| (t.example_value)().ok_or(ExampleError::MissingExample))
     }
 
+    /// Gets the options of the component with the given component name, in the order they're
+    /// declared.
+    ///
+    /// The options hidden from the documentation, and the deprecated ones, are left out.
+    ///
+    /// ## Errors
+    ///
+    /// If no component, identified by `T` and the given name, is registered, or if there is an
+    /// error generating the schema of its configuration, an error variant will be returned.
+    pub fn options(component_name: &str) -> Result<Vec<ComponentOption>, ExampleError> {
+        let description = inventory::iter::<ComponentDescription<T>>
+            .into_iter()
+            .find(|t| t.component_name == component_name)
+            .ok_or_else(|| ExampleError::DoesNotExist {
+                component_name: component_name.to_owned(),
+            })?;
+
+        let gen = RefCell::new(SchemaSettings::new().into_generator());
+        let schema =
+            schema::get_or_generate_schema(&description.config, &gen, None).map_err(|error| {
+                ExampleError::InvalidSchema {
+                    message: format!("{:?}", error),
+                }
+            })?;
+
+        let mut options = Vec::new();
+        collect_options(&schema, gen.borrow().definitions(), &mut options);
+        Ok(options)
+    }
+
     /// Gets a sorted list of all registered components of the given component type.
     pub fn types() -> Vec<&'static str> {
         let mut types = Vec::new();
@@ -127,3 +176,72 @@ where
         Ok(subschema)
     }
 }
+
+/// Follows the references of `schema` to the definition of its type.
+fn resolve<'a>(schema: &'a SchemaObject, definitions: &'a Map<String, Schema>) -> &'a SchemaObject {
+    let definition = schema
+        .reference
+        .as_deref()
+        .and_then(|reference| reference.rsplit('/').next())
+        .and_then(|name| definitions.get(name));
+    match definition {
+        Some(Schema::Object(definition)) => resolve(definition, definitions),
+        _ => schema,
+    }
+}
+
+/// Collects the options of the object described by `schema`, including its flattened fields.
+fn collect_options(
+    schema: &SchemaObject,
+    definitions: &Map<String, Schema>,
+    options: &mut Vec<ComponentOption>,
+) {
+    let schema = resolve(schema, definitions);
+
+    if let Some(object) = &schema.object {
+        for (name, property) in &object.properties {
+            let property = match property {
+                Schema::Object(property) => property,
+                Schema::Bool(_) => continue,
+            };
+            // The metadata of the field takes precedence over the metadata of its type.
+            let schemas = [property, resolve(property, definitions)];
+            let metadata = || {
+                schemas
+                    .iter()
+                    .filter_map(|schema| schema.metadata.as_deref())
+            };
+            if metadata().any(|metadata| metadata.deprecated)
+                || schemas.iter().any(|schema| is_hidden(schema))
+            {
+                continue;
+            }
+
+            options.push(ComponentOption {
+                name: name.clone(),
+                description: metadata().find_map(|metadata| metadata.description.clone()),
+                required: object.required.contains(name),
+                default: metadata().find_map(|metadata| metadata.default.clone()),
+            });
+        }
+    }
+
+    // The flattened fields are in the `allOf` subschemas.
+    let all_of = schema
+        .subschemas
+        .as_ref()
+        .and_then(|subschemas| subschemas.all_of.as_ref());
+    for subschema in all_of.into_iter().flatten() {
+        if let Schema::Object(subschema) = subschema {
+            collect_options(subschema, definitions, options);
+        }
+    }
+}
+
+fn is_hidden(schema: &SchemaObject) -> bool {
+    schema
+        .extensions
+        .get("_metadata")
+        .and_then(|metadata| metadata.get("docs::hidden"))
+        .is_some()
+}
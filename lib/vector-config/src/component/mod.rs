@@ -2,7 +2,7 @@ mod description;
 mod generate;
 mod marker;
 
-pub use self::description::{ComponentDescription, ExampleError};
+pub use self::description::{ComponentDescription, ComponentOption, ExampleError};
 pub use self::generate::GenerateConfig;
 pub use self::marker::{
     ComponentMarker, EnrichmentTableComponent, ProviderComponent, SecretsComponent, SinkComponent,
@@ -5,7 +5,9 @@ use vector_common::TimeZone;
 use vector_config::configurable_component;
 
 use super::super::default_data_dir;
-use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
+use super::{
+    proxy::ProxyConfig, AcknowledgementsConfig, LogSchema, MemoryBudgetConfig, TelemetryConfig,
+};
 use crate::serde::bool_or_struct;
 
 #[derive(Debug, Snafu)]
@@ -115,6 +117,54 @@ pub struct GlobalOptions {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub expire_metrics_secs: Option<f64>,
+
+    /// The maximum number of bytes per second sent by all of the sinks together.
+    ///
+    /// The requests of the sinks are delayed once the limit is reached, in addition to the limit
+    /// of each sink set with its `request.rate_limit_bytes_per_sec` option. The sinks which don't
+    /// support that option aren't limited either.
+    ///
+    /// Not set by default, which doesn't limit the bandwidth.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub egress_rate_limit_bytes_per_sec: Option<u64>,
+
+    /// The maximum age, in seconds, of the events sent by the sinks.
+    ///
+    /// The events whose timestamp is older than this, once they go into a sink, are dropped
+    /// instead of being sent, such as when catching up after a long outage of the downstream
+    /// service. The dropped events are counted in the `dropped_stale_events_total` internal
+    /// metric. The events without a timestamp are always sent. Each sink can override this with
+    /// its own `max_event_age_secs` option.
+    ///
+    /// Not set by default, which sends the events regardless of their age.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub max_event_age_secs: Option<u64>,
+
+    /// The memory budget shared by all of the components.
+    ///
+    /// Not set by default, which doesn't limit the memory used by the events in flight, nor
+    /// account for it.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub memory_budget: Option<MemoryBudgetConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub telemetry: TelemetryConfig,
 }
 
 impl GlobalOptions {
@@ -189,6 +239,26 @@ impl GlobalOptions {
             errors.push("conflicting values for 'proxy.no_proxy' found".to_owned());
         }
 
+        if conflicts(
+            &self.egress_rate_limit_bytes_per_sec,
+            &with.egress_rate_limit_bytes_per_sec,
+        ) {
+            errors
+                .push("conflicting values for 'egress_rate_limit_bytes_per_sec' found".to_owned());
+        }
+
+        if conflicts(&self.max_event_age_secs, &with.max_event_age_secs) {
+            errors.push("conflicting values for 'max_event_age_secs' found".to_owned());
+        }
+
+        if conflicts(&self.memory_budget, &with.memory_budget) {
+            errors.push("conflicting values for 'memory_budget' found".to_owned());
+        }
+
+        if conflicts(&self.telemetry.traces, &with.telemetry.traces) {
+            errors.push("conflicting values for 'telemetry.traces' found".to_owned());
+        }
+
         if conflicts(&self.timezone, &with.timezone) {
             errors.push("conflicting values for 'timezone' found".to_owned());
         }
@@ -227,6 +297,14 @@ impl GlobalOptions {
                 proxy: self.proxy.merge(&with.proxy),
                 expire_metrics: self.expire_metrics.or(with.expire_metrics),
                 expire_metrics_secs: self.expire_metrics_secs.or(with.expire_metrics_secs),
+                egress_rate_limit_bytes_per_sec: self
+                    .egress_rate_limit_bytes_per_sec
+                    .or(with.egress_rate_limit_bytes_per_sec),
+                max_event_age_secs: self.max_event_age_secs.or(with.max_event_age_secs),
+                memory_budget: self.memory_budget.or(with.memory_budget),
+                telemetry: TelemetryConfig {
+                    traces: self.telemetry.traces.clone().or(with.telemetry.traces),
+                },
             })
         } else {
             Err(errors)
@@ -350,6 +428,45 @@ mod tests {
         assert_eq!(merge(Some(4.0), Some(5.0)), Ok(Some(4.0))); // Uses minimum
     }
 
+    #[test]
+    fn merges_egress_rate_limit() {
+        let merge = |a, b| {
+            merge("egress_rate_limit_bytes_per_sec", a, b, |result| {
+                result.egress_rate_limit_bytes_per_sec
+            })
+        };
+
+        assert_eq!(merge(None, None), Ok(None));
+        assert_eq!(merge(Some(1000), None), Ok(Some(1000)));
+        assert_eq!(merge(None, Some(2000)), Ok(Some(2000)));
+        assert_eq!(merge(Some(3000), Some(3000)), Ok(Some(3000)));
+        assert_eq!(
+            merge(Some(4000), Some(5000)),
+            Err(vec![
+                "conflicting values for 'egress_rate_limit_bytes_per_sec' found".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn merges_max_event_age() {
+        let merge = |a, b| {
+            merge("max_event_age_secs", a, b, |result| {
+                result.max_event_age_secs
+            })
+        };
+
+        assert_eq!(merge(None, None), Ok(None));
+        assert_eq!(merge(Some(3600), None), Ok(Some(3600)));
+        assert_eq!(merge(None, Some(7200)), Ok(Some(7200)));
+        assert_eq!(
+            merge(Some(3600), Some(7200)),
+            Err(vec![
+                "conflicting values for 'max_event_age_secs' found".into()
+            ])
+        );
+    }
+
     fn merge<P: Debug, T>(
         name: &str,
         dd1: Option<P>,
@@ -0,0 +1,48 @@
+use std::num::NonZeroUsize;
+
+use vector_config::configurable_component;
+
+/// The memory budget shared by all of the components.
+///
+/// The events sent by the sources are counted against the budget until they are delivered by the
+/// sinks, or written to a disk buffer. Once the budget is used up, the sources are backpressured,
+/// or their events are dropped, according to the policy.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryBudgetConfig {
+    /// The maximum number of bytes of the events in flight.
+    ///
+    /// The size of the events is approximated by the memory they use, which doesn't account for
+    /// the overhead of the allocator and of the components themselves.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub policy: MemoryBudgetPolicy,
+}
+
+/// The policy applied to the events of the sources once the memory budget is used up.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryBudgetPolicy {
+    /// Wait for enough of the events in flight to be delivered before sending more.
+    ///
+    /// This applies backpressure to the sources, the same way a full buffer does.
+    #[default]
+    Block,
+
+    /// Drop the events sent while the budget is used up.
+    DropNewest,
+}
+
+impl MemoryBudgetPolicy {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::DropNewest => "drop_newest",
+        }
+    }
+}
@@ -6,13 +6,17 @@ use chrono::{DateTime, Utc};
 
 mod global_options;
 mod log_schema;
+mod memory_budget;
 pub mod proxy;
+mod telemetry;
 
 use crate::event::LogEvent;
 pub use global_options::GlobalOptions;
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 use lookup::{lookup_v2::ValuePath, path, PathPrefix};
+pub use memory_budget::{MemoryBudgetConfig, MemoryBudgetPolicy};
 use serde::{Deserialize, Serialize};
+pub use telemetry::{TelemetryConfig, TracesConfig};
 use value::Value;
 pub use vector_common::config::ComponentKey;
 use vector_config::configurable_component;
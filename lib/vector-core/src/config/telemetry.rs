@@ -0,0 +1,68 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use serde_with::serde_as;
+use vector_config::configurable_component;
+
+/// The telemetry of Vector itself.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryConfig {
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub traces: Option<TracesConfig>,
+}
+
+/// The traces of the lifecycle of the requests of the sinks, exported over OTLP.
+///
+/// Each trace covers a request from the moment it is built, through it being sent and retried,
+/// to its events being acknowledged.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TracesConfig {
+    /// The endpoint of the OTLP gRPC collector to export the traces to.
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    pub endpoint: String,
+
+    /// The ratio of the traces to sample, from `0.0` for none to `1.0` for all of them.
+    #[serde(default = "default_sampling_ratio")]
+    #[configurable(metadata(docs::examples = 0.01))]
+    pub sampling_ratio: f64,
+
+    /// The name of the service the traces are exported for.
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+
+    /// The interval between the exports of the spans.
+    #[serde(default = "default_export_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "export_interval_secs")]
+    pub export_interval: Duration,
+
+    /// The maximum number of spans waiting to be exported.
+    ///
+    /// The spans ending while the queue is full are dropped.
+    #[serde(default = "default_max_queued_spans")]
+    pub max_queued_spans: NonZeroUsize,
+}
+
+const fn default_sampling_ratio() -> f64 {
+    0.1
+}
+
+fn default_service_name() -> String {
+    "vector".to_owned()
+}
+
+const fn default_export_interval() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn default_max_queued_spans() -> NonZeroUsize {
+    NonZeroUsize::new(2048).expect("not zero")
+}
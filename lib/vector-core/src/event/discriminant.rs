@@ -3,6 +3,8 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use serde::{Deserialize, Serialize};
+
 use super::{LogEvent, Value};
 
 // TODO: if we had `Value` implement `Eq` and `Hash`, the implementation here
@@ -16,7 +18,8 @@ use super::{LogEvent, Value};
 /// Intended for dissecting streams of events to sub-streams, for instance to
 /// be able to allocate a buffer per sub-stream.
 /// Implements `PartialEq`, `Eq` and `Hash` to enable use as a `HashMap` key.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(transparent)]
 pub struct Discriminant {
     values: Vec<Option<Value>>,
 }
@@ -80,13 +80,9 @@ impl<'a> FromLua<'a> for TagValueSet {
         match value {
             LuaValue::Nil => Ok(Self::Single(TagValue::Bare)),
             LuaValue::Table(table) => {
-                let mut string_values: Vec<String> = vec![];
-                for value in table.sequence_values() {
-                    match value {
-                        Ok(value) => string_values.push(value),
-                        Err(_) => unimplemented!(),
-                    }
-                }
+                let string_values = table
+                    .sequence_values::<String>()
+                    .collect::<LuaResult<Vec<_>>>()?;
                 Ok(Self::from(string_values))
             }
             LuaValue::String(x) => Ok(Self::from([x.to_string_lossy().to_string()])),
@@ -273,7 +269,9 @@ impl<'a> FromLua<'a> for Metric {
             let rates: Vec<u32> = distribution.raw_get("sample_rates")?;
             MetricValue::Distribution {
                 samples: metric::zip_samples(values, rates),
-                statistic: distribution.raw_get("statistic")?,
+                statistic: distribution
+                    .raw_get::<_, Option<StatisticKind>>("statistic")?
+                    .unwrap_or(StatisticKind::Histogram),
             }
         } else if let Some(aggregated_histogram) =
             table.raw_get::<_, Option<LuaTable>>("aggregated_histogram")?
@@ -754,6 +752,36 @@ mod test {
         assert_event_data_eq!(Lua::new().load(value).eval::<Metric>().unwrap(), expected);
     }
 
+    #[test]
+    fn from_lua_distribution_default_statistic() {
+        let value = r#"{
+            name = "example distribution",
+            distribution = {
+                values = { 1.0 },
+                sample_rates = { 10 }
+            }
+        }"#;
+        let expected = Metric::new(
+            "example distribution",
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: crate::samples![1.0 => 10],
+                statistic: StatisticKind::Histogram,
+            },
+        );
+        assert_event_data_eq!(Lua::new().load(value).eval::<Metric>().unwrap(), expected);
+    }
+
+    #[test]
+    fn from_lua_invalid_tag_values() {
+        let value = r#"{
+            name = "example counter",
+            tags = { example = { "a", {} } },
+            counter = { value = 1 }
+        }"#;
+        assert!(Lua::new().load(value).eval::<Metric>().is_err());
+    }
+
     #[test]
     fn from_lua_aggregated_histogram() {
         let value = r#"{
@@ -35,6 +35,11 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// Whether the value of the event is a frame relayed as it was received, without being
+    /// decoded.
+    #[serde(default, skip)]
+    pass_through: bool,
 }
 
 fn default_metadata_value() -> Value {
@@ -98,6 +103,7 @@ impl Default for EventMetadata {
             secrets: Secrets::new(),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
+            pass_through: false,
         }
     }
 }
@@ -202,6 +208,16 @@ impl EventMetadata {
     pub fn set_schema_definition(&mut self, definition: &Arc<schema::Definition>) {
         self.schema_definition = Arc::clone(definition);
     }
+
+    /// Returns whether the value of the event is a frame relayed without being decoded.
+    pub fn is_pass_through(&self) -> bool {
+        self.pass_through
+    }
+
+    /// Marks the value of the event as a frame relayed without being decoded.
+    pub fn set_pass_through(&mut self) {
+        self.pass_through = true;
+    }
 }
 
 impl EventDataEq for EventMetadata {
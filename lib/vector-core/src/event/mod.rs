@@ -9,8 +9,8 @@ pub use ::value::Value;
 pub use array::{into_event_stream, EventArray, EventContainer, LogArray, MetricArray, TraceArray};
 pub use estimated_json_encoded_size_of::EstimatedJsonEncodedSizeOf;
 pub use finalization::{
-    BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
-    Finalizable,
+    BatchNotifier, BatchStatus, BatchStatusReceiver, DeliveryFailure, EventFinalizer,
+    EventFinalizers, EventStatus, Finalizable,
 };
 pub use log_event::LogEvent;
 pub use metadata::{EventMetadata, WithMetadata};
@@ -21,7 +21,7 @@ pub use trace::TraceEvent;
 use vector_buffers::EventCount;
 use vector_common::{finalization, EventDataEq};
 #[cfg(feature = "vrl")]
-pub use vrl_target::{TargetEvents, VrlTarget};
+pub use vrl_target::{MetricFields, TargetEvents, VrlTarget};
 
 pub mod array;
 pub mod discriminant;
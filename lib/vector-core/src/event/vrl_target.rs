@@ -1,19 +1,20 @@
 use std::borrow::Cow;
-use std::{collections::BTreeMap, convert::TryFrom, marker::PhantomData};
+use std::{collections::BTreeMap, convert::TryFrom, marker::PhantomData, num::NonZeroU32};
 
 use lookup::lookup_v2::OwnedSegment;
 use lookup::{OwnedTargetPath, OwnedValuePath, PathPrefix};
 use snafu::Snafu;
 use vrl_lib::{prelude::VrlValueConvert, ProgramInfo, SecretTarget};
 
-use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, TraceEvent, Value};
+use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, TraceEvent, Value};
 use crate::config::log_schema;
 use crate::event::metric::TagValue;
 
-const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .kind, .tags";
+const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .interval_ms, .kind, .tags";
 
 /// We can get the `type` of the metric in Remap, but can't set it.
-const VALID_METRIC_PATHS_GET: &str = ".name, .namespace, .timestamp, .kind, .tags, .type";
+const VALID_METRIC_PATHS_GET: &str =
+    ".name, .namespace, .timestamp, .interval_ms, .kind, .tags, .type";
 
 /// Metrics aren't interested in paths that have a length longer than 3.
 ///
@@ -86,8 +87,83 @@ impl Iterator for TargetIter<TraceEvent> {
     }
 }
 
+/// The fields of the metrics accessed by a program.
+///
+/// These only depend on the program, so they can be looked up once for all the events it runs
+/// on.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MetricFields {
+    fields: Vec<MetricField>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MetricField {
+    Name,
+    Kind,
+    Type,
+    Namespace,
+    Timestamp,
+    IntervalMs,
+    Tags,
+}
+
+impl MetricFields {
+    /// Looks up the fields of the metrics queried by the program.
+    #[must_use]
+    pub fn new(info: &ProgramInfo) -> Self {
+        let mut fields = Vec::new();
+        for target_path in &info.target_queries {
+            // Accessing a root path requires us to pre-populate all fields.
+            if target_path == &OwnedTargetPath::event_root() {
+                fields = vec![
+                    MetricField::Name,
+                    MetricField::Kind,
+                    MetricField::Type,
+                    MetricField::Namespace,
+                    MetricField::Timestamp,
+                    MetricField::IntervalMs,
+                    MetricField::Tags,
+                ];
+                break;
+            }
+
+            let field = match target_path.path.segments.first() {
+                Some(OwnedSegment::Field(field)) => match field.as_ref() {
+                    "name" => MetricField::Name,
+                    "kind" => MetricField::Kind,
+                    "type" => MetricField::Type,
+                    "namespace" => MetricField::Namespace,
+                    "timestamp" => MetricField::Timestamp,
+                    "interval_ms" => MetricField::IntervalMs,
+                    "tags" => MetricField::Tags,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        Self { fields }
+    }
+}
+
 impl VrlTarget {
     pub fn new(event: Event, info: &ProgramInfo, multi_value_metric_tags: bool) -> Self {
+        let metric_fields = if matches!(event, Event::Metric(_)) {
+            MetricFields::new(info)
+        } else {
+            MetricFields::default()
+        };
+        Self::new_with_metric_fields(event, &metric_fields, multi_value_metric_tags)
+    }
+
+    /// Creates the target of an event, for a program accessing the given fields of the metrics.
+    pub fn new_with_metric_fields(
+        event: Event,
+        metric_fields: &MetricFields,
+        multi_value_metric_tags: bool,
+    ) -> Self {
         match event {
             Event::Log(event) => {
                 let (value, metadata) = event.into_parts();
@@ -97,7 +173,7 @@ impl VrlTarget {
                 // We pre-generate [`Value`] types for the metric fields accessed in
                 // the event. This allows us to then return references to those
                 // values, even if the field is accessed more than once.
-                let value = precompute_metric_value(&metric, info);
+                let value = precompute_metric_value(&metric, metric_fields);
 
                 VrlTarget::Metric {
                     metric,
@@ -261,8 +337,15 @@ impl vrl_lib::Target for VrlTarget {
                                     value.clone().try_timestamp().map_err(|e| e.to_string())?;
                                 metric.data.time.timestamp = Some(value);
                             }
+                            ["interval_ms"] => {
+                                let value =
+                                    value.clone().try_integer().map_err(|e| e.to_string())?;
+                                metric.data.time.interval_ms = Some(metric_interval_ms(value)?);
+                            }
                             ["kind"] => {
-                                metric.data.kind = MetricKind::try_from(value.clone())?;
+                                let kind = MetricKind::try_from(value.clone())?;
+                                check_metric_kind_conversion(metric, kind)?;
+                                metric.data.kind = kind;
                             }
                             _ => {
                                 return Err(MetricPathError::InvalidPath {
@@ -349,6 +432,12 @@ impl vrl_lib::Target for VrlTarget {
                         let removed_value = match paths.as_slice() {
                             ["namespace"] => metric.series.name.namespace.take().map(Into::into),
                             ["timestamp"] => metric.data.time.timestamp.take().map(Into::into),
+                            ["interval_ms"] => metric
+                                .data
+                                .time
+                                .interval_ms
+                                .take()
+                                .map(|interval| i64::from(interval.get()).into()),
                             ["tags"] => metric.series.tags.take().map(|map| {
                                 map.into_iter_single()
                                     .map(|(k, v)| (k, v.into()))
@@ -399,6 +488,7 @@ impl SecretTarget for VrlTarget {
 /// - name
 /// - namespace
 /// - timestamp
+/// - interval_ms
 /// - kind
 /// - tags
 /// - tags.<tagname>
@@ -418,7 +508,7 @@ fn target_get_metric<'a>(
     for paths in path.to_alternative_components(MAX_METRIC_PATH_DEPTH) {
         match paths.as_slice() {
             ["name"] | ["kind"] | ["type"] | ["tags", _] => return Ok(value),
-            ["namespace"] | ["timestamp"] | ["tags"] => {
+            ["namespace"] | ["timestamp"] | ["interval_ms"] | ["tags"] => {
                 if let Some(value) = value {
                     return Ok(Some(value));
                 }
@@ -451,7 +541,7 @@ fn target_get_mut_metric<'a>(
     for paths in path.to_alternative_components(MAX_METRIC_PATH_DEPTH) {
         match paths.as_slice() {
             ["name"] | ["kind"] | ["tags", _] => return Ok(value),
-            ["namespace"] | ["timestamp"] | ["tags"] => {
+            ["namespace"] | ["timestamp"] | ["interval_ms"] | ["tags"] => {
                 if let Some(value) = value {
                     return Ok(Some(value));
                 }
@@ -471,104 +561,82 @@ fn target_get_mut_metric<'a>(
     Ok(None)
 }
 
+fn metric_interval_ms(value: i64) -> Result<NonZeroU32, String> {
+    u32::try_from(value)
+        .ok()
+        .and_then(NonZeroU32::new)
+        .ok_or_else(|| {
+            format!(
+                "invalid metric interval {value}, interval must be a positive number of milliseconds"
+            )
+        })
+}
+
+/// Checks that the kind of the metric can be changed to `kind` without changing the meaning of its
+/// value.
+///
+/// Changing the kind only reinterprets the value, so it is rejected for values that can't be
+/// combined incrementally, such as aggregated summaries, whose quantiles can't be summed.
+fn check_metric_kind_conversion(metric: &Metric, kind: MetricKind) -> Result<(), String> {
+    match (kind, metric.value()) {
+        (MetricKind::Incremental, MetricValue::AggregatedSummary { .. })
+            if metric.kind() == MetricKind::Absolute =>
+        {
+            Err(format!(
+                "cannot change the kind of {} metric to `incremental`",
+                metric.value().as_name()
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
 /// pre-compute the `Value` structure of the metric.
 ///
 /// This structure is partially populated based on the fields accessed by
 /// the VRL program as informed by `ProgramInfo`.
-fn precompute_metric_value(metric: &Metric, info: &ProgramInfo) -> Value {
+fn precompute_metric_value(metric: &Metric, metric_fields: &MetricFields) -> Value {
     let mut map = BTreeMap::default();
 
-    let mut set_name = false;
-    let mut set_kind = false;
-    let mut set_type = false;
-    let mut set_namespace = false;
-    let mut set_timestamp = false;
-    let mut set_tags = false;
-
-    for target_path in &info.target_queries {
-        // Accessing a root path requires us to pre-populate all fields.
-        if target_path == &OwnedTargetPath::event_root() {
-            if !set_name {
+    for field in &metric_fields.fields {
+        match field {
+            MetricField::Name => {
                 map.insert("name".to_owned(), metric.name().to_owned().into());
             }
-
-            if !set_kind {
+            MetricField::Kind => {
                 map.insert("kind".to_owned(), metric.kind().into());
             }
-
-            if !set_type {
+            MetricField::Type => {
                 map.insert("type".to_owned(), metric.value().clone().into());
             }
-
-            if !set_namespace {
+            MetricField::Namespace => {
                 if let Some(namespace) = metric.namespace() {
                     map.insert("namespace".to_owned(), namespace.to_owned().into());
                 }
             }
-
-            if !set_timestamp {
+            MetricField::Timestamp => {
                 if let Some(timestamp) = metric.timestamp() {
                     map.insert("timestamp".to_owned(), timestamp.into());
                 }
             }
-
-            if !set_tags {
-                if let Some(tags) = metric.tags().cloned() {
+            MetricField::IntervalMs => {
+                if let Some(interval_ms) = metric.interval_ms() {
                     map.insert(
-                        "tags".to_owned(),
-                        tags.into_iter_single()
-                            .map(|(tag, value)| (tag, value.into()))
-                            .collect::<BTreeMap<_, _>>()
-                            .into(),
+                        "interval_ms".to_owned(),
+                        i64::from(interval_ms.get()).into(),
                     );
                 }
             }
-
-            break;
-        }
-
-        // For non-root paths, we continuously populate the value with the
-        // relevant data.
-        if let Some(OwnedSegment::Field(field)) = target_path.path.segments.first() {
-            match field.as_ref() {
-                "name" if !set_name => {
-                    set_name = true;
-                    map.insert("name".to_owned(), metric.name().to_owned().into());
-                }
-                "kind" if !set_kind => {
-                    set_kind = true;
-                    map.insert("kind".to_owned(), metric.kind().into());
-                }
-                "type" if !set_type => {
-                    set_type = true;
-                    map.insert("type".to_owned(), metric.value().clone().into());
-                }
-                "namespace" if !set_namespace && metric.namespace().is_some() => {
-                    set_namespace = true;
-                    map.insert(
-                        "namespace".to_owned(),
-                        metric.namespace().unwrap().to_owned().into(),
-                    );
-                }
-                "timestamp" if !set_timestamp && metric.timestamp().is_some() => {
-                    set_timestamp = true;
-                    map.insert("timestamp".to_owned(), metric.timestamp().unwrap().into());
-                }
-                "tags" if !set_tags && metric.tags().is_some() => {
-                    set_tags = true;
+            MetricField::Tags => {
+                if let Some(tags) = metric.tags().cloned() {
                     map.insert(
                         "tags".to_owned(),
-                        metric
-                            .tags()
-                            .cloned()
-                            .unwrap()
-                            .into_iter_single()
+                        tags.into_iter_single()
                             .map(|(tag, value)| (tag, value.into()))
                             .collect::<BTreeMap<_, _>>()
                             .into(),
                     );
                 }
-                _ => {}
             }
         }
     }
@@ -593,7 +661,7 @@ mod test {
     use value::btreemap;
     use vrl_lib::Target;
 
-    use super::super::MetricValue;
+    use super::super::{MetricValue, StatisticKind};
     use super::*;
     use crate::metric_tags;
 
@@ -978,6 +1046,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn metric_fields_are_looked_up_once() {
+        let info = ProgramInfo {
+            fallible: false,
+            abortable: false,
+            target_queries: vec![
+                OwnedTargetPath::event(owned_value_path!("tags", "tig")),
+                OwnedTargetPath::event(owned_value_path!("name")),
+                OwnedTargetPath::event(owned_value_path!("tags")),
+                OwnedTargetPath::event(owned_value_path!("foo")),
+            ],
+            target_assignments: vec![],
+        };
+        let metric_fields = MetricFields::new(&info);
+
+        for (name, tags) in [("zub", Some(metric_tags!("tig" => "tog"))), ("zab", None)] {
+            let metric = Metric::new(
+                name,
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 1.23 },
+            )
+            .with_namespace(Some("zoob"))
+            .with_tags(tags.clone());
+            let target =
+                VrlTarget::new_with_metric_fields(Event::Metric(metric), &metric_fields, false);
+
+            let mut expected: BTreeMap<String, Value> = btreemap! { "name" => name };
+            if tags.is_some() {
+                expected.insert("tags".to_owned(), btreemap! { "tig" => "tog" }.into());
+            }
+            assert_eq!(
+                Ok(Some(expected.into())),
+                target
+                    .target_get(&OwnedTargetPath::event_root())
+                    .map(Option::<&Value>::cloned)
+            );
+        }
+    }
+
     #[test]
     fn metric_fields() {
         let metric = Metric::new(
@@ -1009,6 +1116,12 @@ mod test {
                     .into(),
                 true,
             ),
+            (
+                owned_value_path!("interval_ms"),
+                None,
+                ::value::Value::from(10_000),
+                true,
+            ),
             (
                 owned_value_path!("kind"),
                 Some(::value::Value::from("absolute")),
@@ -1105,12 +1218,20 @@ mod test {
             ".name",
             ".namespace",
             ".timestamp",
+            ".interval_ms",
             ".kind",
             ".tags",
             ".type",
         ];
 
-        let validpaths_set = vec![".name", ".namespace", ".timestamp", ".kind", ".tags"];
+        let validpaths_set = vec![
+            ".name",
+            ".namespace",
+            ".timestamp",
+            ".interval_ms",
+            ".kind",
+            ".tags",
+        ];
 
         let info = ProgramInfo {
             fallible: false,
@@ -1158,6 +1279,100 @@ mod test {
         );
     }
 
+    #[test]
+    fn metric_invalid_values() {
+        let metric = Metric::new(
+            "name",
+            MetricKind::Absolute,
+            MetricValue::AggregatedSummary {
+                quantiles: vec![],
+                count: 1,
+                sum: 1.0,
+            },
+        );
+        let info = ProgramInfo {
+            fallible: false,
+            abortable: false,
+            target_queries: vec![],
+            target_assignments: vec![],
+        };
+        let mut target = VrlTarget::new(Event::Metric(metric), &info, false);
+
+        assert_eq!(
+            Err("cannot change the kind of aggregated summary metric to `incremental`".to_owned()),
+            target.target_insert(
+                &OwnedTargetPath::event(owned_value_path!("kind")),
+                "incremental".into()
+            )
+        );
+        assert_eq!(
+            Ok(()),
+            target.target_insert(
+                &OwnedTargetPath::event(owned_value_path!("kind")),
+                "absolute".into()
+            )
+        );
+        assert_eq!(
+            Err(
+                "invalid metric interval 0, interval must be a positive number of milliseconds"
+                    .to_owned()
+            ),
+            target.target_insert(
+                &OwnedTargetPath::event(owned_value_path!("interval_ms")),
+                0.into()
+            )
+        );
+    }
+
+    #[test]
+    fn metric_allows_safe_kind_conversions() {
+        let info = ProgramInfo {
+            fallible: false,
+            abortable: false,
+            target_queries: vec![],
+            target_assignments: vec![],
+        };
+        let values = vec![
+            MetricValue::Counter { value: 1.0 },
+            MetricValue::Gauge { value: 1.0 },
+            MetricValue::AggregatedHistogram {
+                buckets: vec![],
+                count: 1,
+                sum: 1.0,
+            },
+            MetricValue::Distribution {
+                samples: vec![],
+                statistic: StatisticKind::Histogram,
+            },
+        ];
+
+        for value in values {
+            let metric = Metric::new("name", MetricKind::Absolute, value);
+            let mut target = VrlTarget::new(Event::Metric(metric), &info, false);
+            assert_eq!(
+                Ok(()),
+                target.target_insert(
+                    &OwnedTargetPath::event(owned_value_path!("kind")),
+                    "incremental".into()
+                )
+            );
+        }
+
+        let metric = Metric::new(
+            "name",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let mut target = VrlTarget::new(Event::Metric(metric), &info, false);
+        assert_eq!(
+            Ok(()),
+            target.target_insert(
+                &OwnedTargetPath::event(owned_value_path!("kind")),
+                "absolute".into()
+            )
+        );
+    }
+
     #[test]
     fn test_metric_insert_get_multi_value_tag() {
         let metric = Metric::new(
@@ -41,6 +41,35 @@ static CARDINALITY_KEY: Key = Key::from_static_name(CARDINALITY_KEY_NAME);
 const CARDINALITY_COUNTER_KEY_NAME: &str = "internal_metrics_cardinality_total";
 static CARDINALITY_COUNTER_KEY: Key = Key::from_static_name(CARDINALITY_COUNTER_KEY_NAME);
 
+// The metrics recorded by the core set besides those of components and buffers.
+const CORE_METRIC_NAMES: [&str; 2] = ["build_info", "uptime_seconds"];
+
+/// The set of internal metrics recorded.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MetricSet {
+    /// All the internal metrics.
+    #[default]
+    All,
+
+    /// Only the counters and gauges of components and buffers, along with the uptime and build
+    /// information, to lower the memory used by the metrics of hosts with little of it.
+    Core,
+}
+
+impl MetricSet {
+    fn records(self, name: &str, histogram: bool) -> bool {
+        match self {
+            Self::All => true,
+            Self::Core => {
+                !histogram
+                    && (name.starts_with("component_")
+                        || name.starts_with("buffer_")
+                        || CORE_METRIC_NAMES.contains(&name))
+            }
+        }
+    }
+}
+
 /// Controller allows capturing metric snapshots.
 pub struct Controller {
     recorder: VectorRecorder,
@@ -104,7 +133,16 @@ fn init(recorder: VectorRecorder) -> Result<()> {
 ///
 /// This function will error if it is called multiple times.
 pub fn init_global() -> Result<()> {
-    init(VectorRecorder::new_global())
+    init_global_with(MetricSet::All)
+}
+
+/// Initialize the default metrics sub-system, recording only the metrics of `metric_set`.
+///
+/// # Errors
+///
+/// This function will error if it is called multiple times.
+pub fn init_global_with(metric_set: MetricSet) -> Result<()> {
+    init(VectorRecorder::new_global(metric_set))
 }
 
 /// Initialize the thread-local metrics sub-system. This function will loop until a recorder is
@@ -154,6 +192,16 @@ impl Controller {
         Ok(())
     }
 
+    /// Set the bucket upper limits of histograms, by metric name, replacing any previously set.
+    ///
+    /// Histograms not listed use the default buckets, which are powers of two. This only applies
+    /// to histograms created after this call, existing histograms keep their buckets.
+    pub fn set_histogram_buckets(&self, buckets: impl IntoIterator<Item = (String, Vec<f64>)>) {
+        let buckets = buckets.into_iter().collect();
+        self.recorder
+            .with_registry(|registry| registry.set_histogram_buckets(buckets));
+    }
+
     /// Take a snapshot of all gathered metrics and expose them as metric
     /// [`Event`](crate::event::Event)s.
     pub fn capture_metrics(&self) -> Vec<Metric> {
@@ -331,6 +379,60 @@ mod tests {
         assert_eq!(controller.capture_metrics().len(), 3);
     }
 
+    #[test]
+    fn uses_histogram_buckets() {
+        let controller = init_metrics();
+        controller.set_histogram_buckets([("test9".to_owned(), vec![1.0, 10.0])]);
+
+        metrics::histogram!("test9", 5.0);
+        metrics::histogram!("test10", 5.0);
+
+        let metrics = controller.capture_metrics();
+        let buckets = |name: &str| match metrics
+            .iter()
+            .find(|metric| metric.name() == name)
+            .map(Metric::value)
+        {
+            Some(MetricValue::AggregatedHistogram { buckets, .. }) => buckets.len(),
+            value => panic!("Invalid metric value {value:?}"),
+        };
+        assert_eq!(buckets("test9"), 3);
+        assert_eq!(buckets("test10"), 20);
+
+        controller.set_histogram_buckets([]);
+    }
+
+    #[test]
+    fn records_core_metrics() {
+        let registry = Registry::with_metric_set(MetricSet::Core);
+        for name in [
+            "component_received_events_total",
+            "buffer_events",
+            "uptime_seconds",
+            "http_client_responses_total",
+        ] {
+            registry.get_counter(&Key::from_name(name)).increment(1);
+        }
+        registry
+            .get_histogram(&Key::from_name("component_received_events_count"))
+            .record(1.0);
+
+        let mut names = registry
+            .visit_metrics()
+            .into_iter()
+            .map(|metric| metric.name().to_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "buffer_events",
+                "component_received_events_total",
+                "uptime_seconds"
+            ]
+        );
+    }
+
     #[test]
     fn skips_expiring_registered() {
         let controller = init_metrics();
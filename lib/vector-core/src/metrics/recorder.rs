@@ -8,7 +8,8 @@ use once_cell::unsync::OnceCell;
 use quanta::Clock;
 
 use super::recency::{GenerationalStorage, Recency};
-use super::storage::VectorStorage;
+use super::storage::{HistogramBuckets, VectorStorage};
+use super::MetricSet;
 use crate::event::{Metric, MetricValue};
 
 thread_local!(static LOCAL_REGISTRY: OnceCell<Registry> = const { OnceCell::new() });
@@ -16,14 +17,23 @@ thread_local!(static LOCAL_REGISTRY: OnceCell<Registry> = const { OnceCell::new(
 #[allow(dead_code)]
 pub(super) struct Registry {
     registry: MetricsRegistry<Key, GenerationalStorage<VectorStorage>>,
+    storage: VectorStorage,
     recency: RwLock<Option<Recency<Key>>>,
+    metric_set: MetricSet,
 }
 
 impl Registry {
     fn new() -> Self {
+        Self::with_metric_set(MetricSet::All)
+    }
+
+    pub(super) fn with_metric_set(metric_set: MetricSet) -> Self {
+        let storage = VectorStorage::default();
         Self {
-            registry: MetricsRegistry::new(GenerationalStorage::new(VectorStorage)),
+            registry: MetricsRegistry::new(GenerationalStorage::new(storage.clone())),
+            storage,
             recency: RwLock::new(None),
+            metric_set,
         }
     }
 
@@ -36,6 +46,10 @@ impl Registry {
         *(self.recency.write()).expect("Failed to acquire write lock on recency map") = recency;
     }
 
+    pub(super) fn set_histogram_buckets(&self, buckets: HistogramBuckets) {
+        self.storage.set_histogram_buckets(buckets);
+    }
+
     pub(super) fn visit_metrics(&self) -> Vec<Metric> {
         let timestamp = Utc::now();
 
@@ -77,16 +91,25 @@ impl Registry {
         metrics
     }
 
-    fn get_counter(&self, key: &Key) -> Counter {
+    pub(super) fn get_counter(&self, key: &Key) -> Counter {
+        if !self.metric_set.records(key.name(), false) {
+            return Counter::noop();
+        }
         self.registry
             .get_or_create_counter(key, |c| c.clone().into())
     }
 
-    fn get_gauge(&self, key: &Key) -> Gauge {
+    pub(super) fn get_gauge(&self, key: &Key) -> Gauge {
+        if !self.metric_set.records(key.name(), false) {
+            return Gauge::noop();
+        }
         self.registry.get_or_create_gauge(key, |c| c.clone().into())
     }
 
-    fn get_histogram(&self, key: &Key) -> Histogram {
+    pub(super) fn get_histogram(&self, key: &Key) -> Histogram {
+        if !self.metric_set.records(key.name(), true) {
+            return Histogram::noop();
+        }
         self.registry
             .get_or_create_histogram(key, |c| c.clone().into())
     }
@@ -101,8 +124,8 @@ pub(super) enum VectorRecorder {
 }
 
 impl VectorRecorder {
-    pub(super) fn new_global() -> Self {
-        Self::Global(Arc::new(Registry::new()))
+    pub(super) fn new_global(metric_set: MetricSet) -> Self {
+        Self::Global(Arc::new(Registry::with_metric_set(metric_set)))
     }
 
     pub(super) fn new_test() -> Self {
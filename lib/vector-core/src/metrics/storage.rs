@@ -1,30 +1,61 @@
-use std::sync::{
-    atomic::{AtomicU32, AtomicU64, Ordering},
-    Arc,
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, RwLock,
+    },
 };
 
-use metrics::{GaugeFn, HistogramFn};
+use metrics::{GaugeFn, HistogramFn, Key};
 use metrics_util::registry::Storage;
 
 use crate::event::{metric::Bucket, MetricValue};
 
-pub(super) struct VectorStorage;
+/// The bucket upper limits of the histograms not using the default buckets, by metric name.
+pub(super) type HistogramBuckets = HashMap<String, Vec<f64>>;
 
-impl<K> Storage<K> for VectorStorage {
+#[derive(Clone, Default)]
+pub(super) struct VectorStorage {
+    histogram_buckets: Arc<RwLock<HashMap<String, Arc<[f64]>>>>,
+}
+
+impl VectorStorage {
+    /// Sets the buckets of the histograms created from now on. Existing histograms keep their
+    /// buckets.
+    pub(super) fn set_histogram_buckets(&self, buckets: HistogramBuckets) {
+        let buckets = buckets
+            .into_iter()
+            .map(|(name, bounds)| (name, Histogram::normalize_bounds(bounds)))
+            .collect();
+        *self
+            .histogram_buckets
+            .write()
+            .expect("Failed to acquire write lock on histogram buckets") = buckets;
+    }
+}
+
+impl Storage<Key> for VectorStorage {
     type Counter = Arc<AtomicU64>;
     type Gauge = Arc<AtomicF64>;
     type Histogram = Arc<Histogram>;
 
-    fn counter(&self, _: &K) -> Self::Counter {
+    fn counter(&self, _: &Key) -> Self::Counter {
         Arc::new(AtomicU64::new(0))
     }
 
-    fn gauge(&self, _: &K) -> Self::Gauge {
+    fn gauge(&self, _: &Key) -> Self::Gauge {
         Arc::new(AtomicF64::new(0.0))
     }
 
-    fn histogram(&self, _: &K) -> Self::Histogram {
-        Arc::new(Histogram::new())
+    fn histogram(&self, key: &Key) -> Self::Histogram {
+        let buckets = self
+            .histogram_buckets
+            .read()
+            .expect("Failed to acquire read lock on histogram buckets");
+        match buckets.get(key.name()) {
+            Some(bounds) => Arc::new(Histogram::with_bounds(bounds)),
+            None => Arc::new(Histogram::new()),
+        }
     }
 }
 
@@ -74,7 +105,8 @@ impl GaugeFn for AtomicF64 {
 
 #[derive(Debug)]
 pub(super) struct Histogram {
-    buckets: Box<[(f64, AtomicU32); 20]>,
+    buckets: Box<[(f64, AtomicU32)]>,
+    custom_buckets: bool,
     count: AtomicU64,
     sum: AtomicF64,
 }
@@ -94,7 +126,7 @@ impl Histogram {
         // around but never quite get to zero with an increasingly coarse
         // long-tail. This also lets us find the right bucket to record into using simple
         // constant-time math operations instead of a loop-and-compare construct.
-        let buckets = Box::new([
+        let buckets: Box<[(f64, AtomicU32)]> = Box::new([
             ((-6_f64).exp2(), AtomicU32::new(0)),
             ((-5_f64).exp2(), AtomicU32::new(0)),
             ((-4_f64).exp2(), AtomicU32::new(0)),
@@ -118,11 +150,36 @@ impl Histogram {
         ]);
         Self {
             buckets,
+            custom_buckets: false,
+            count: AtomicU64::new(0),
+            sum: AtomicF64::new(0.0),
+        }
+    }
+
+    /// Creates a histogram with the given bucket upper limits, as returned by `normalize_bounds`.
+    pub(crate) fn with_bounds(bounds: &[f64]) -> Self {
+        let buckets = bounds
+            .iter()
+            .map(|upper_limit| (*upper_limit, AtomicU32::new(0)))
+            .collect();
+        Self {
+            buckets,
+            custom_buckets: true,
             count: AtomicU64::new(0),
             sum: AtomicF64::new(0.0),
         }
     }
 
+    /// Sorts and deduplicates the given bucket upper limits, dropping the non-finite ones, and
+    /// adds the final unbounded bucket.
+    fn normalize_bounds(mut bounds: Vec<f64>) -> Arc<[f64]> {
+        bounds.retain(|bound| bound.is_finite());
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        bounds.push(f64::INFINITY);
+        bounds.into()
+    }
+
     pub(self) fn bucket_index(value: f64) -> usize {
         // The buckets are all powers of two, so compute the ceiling of the log_2 of the
         // value. Apply a lower bound to prevent zero or negative values from blowing up the log.
@@ -164,7 +221,14 @@ impl Histogram {
 
 impl HistogramFn for Histogram {
     fn record(&self, value: f64) {
-        let index = Self::bucket_index(value);
+        let index = if self.custom_buckets {
+            // The last bucket is unbounded, so this only goes past it for `NaN` values.
+            self.buckets
+                .partition_point(|(upper_limit, _)| *upper_limit < value)
+                .min(self.buckets.len() - 1)
+        } else {
+            Self::bucket_index(value)
+        };
         self.buckets[index].1.fetch_add(1, Ordering::Relaxed);
         self.count.fetch_add(1, Ordering::Relaxed);
         self.sum
@@ -177,7 +241,7 @@ mod test {
     use metrics::HistogramFn;
     use quickcheck::{QuickCheck, TestResult};
 
-    use super::Histogram;
+    use super::{Histogram, Ordering};
 
     // Adapted from https://users.rust-lang.org/t/assert-eq-for-float-numbers/7034/4?u=blt
     fn nearly_equal(a: f64, b: f64) -> bool {
@@ -241,4 +305,23 @@ mod test {
             .max_tests(2_000)
             .quickcheck(inner as fn(Vec<f64>) -> TestResult);
     }
+
+    #[test]
+    fn custom_buckets() {
+        let bounds = Histogram::normalize_bounds(vec![10.0, 1.0, f64::NAN, 5.0, 1.0]);
+        assert_eq!(&*bounds, &[1.0, 5.0, 10.0, f64::INFINITY]);
+
+        let sut = Histogram::with_bounds(&bounds);
+        for value in [0.5, 1.0, 3.0, 7.0, 10.0, 100.0] {
+            sut.record(value);
+        }
+
+        let counts = sut
+            .buckets
+            .iter()
+            .map(|(_, count)| count.load(Ordering::Relaxed))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![2, 1, 2, 1]);
+        assert_eq!(sut.count(), 6);
+    }
 }
@@ -1,14 +1,17 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use data::BatchData;
 use limiter::BatchLimiter;
 
 use super::{data, limiter};
+use crate::stream::DynamicLimits;
 
 pub struct BatchConfigParts<L, D> {
     pub batch_limiter: L,
     pub batch_data: D,
     pub timeout: Duration,
+    /// The limits of the sink that can be changed while it runs, overriding `timeout`.
+    pub limits: Option<Arc<DynamicLimits>>,
 }
 
 pub trait BatchConfig<T> {
@@ -75,6 +78,8 @@ where
     }
 
     fn timeout(&self) -> Duration {
-        self.timeout
+        self.limits
+            .as_ref()
+            .map_or(self.timeout, |limits| limits.batch_timeout(self.timeout))
     }
 }
@@ -1,4 +1,9 @@
-use crate::{stream::batcher::data::BatchData, ByteSizeOf};
+use std::sync::Arc;
+
+use crate::{
+    stream::{batcher::data::BatchData, DynamicLimits},
+    ByteSizeOf,
+};
 
 pub trait BatchLimiter<T, B> {
     type ItemMetadata;
@@ -35,6 +40,27 @@ pub struct SizeLimit<I> {
 
     pub current_size: usize,
     pub item_size_calculator: I,
+
+    /// The limits of the sink that can be changed while it runs, overriding the limits above.
+    pub limits: Option<Arc<DynamicLimits>>,
+}
+
+impl<I> SizeLimit<I> {
+    fn size_limit(&self) -> usize {
+        self.limits
+            .as_ref()
+            .map_or(self.batch_size_limit, |limits| {
+                limits.batch_max_bytes(self.batch_size_limit)
+            })
+    }
+
+    fn item_limit(&self) -> usize {
+        self.limits
+            .as_ref()
+            .map_or(self.batch_item_limit, |limits| {
+                limits.batch_max_events(self.batch_item_limit)
+            })
+    }
 }
 
 impl<T, B, I> BatchLimiter<T, B> for SizeLimit<I>
@@ -45,7 +71,7 @@ where
     type ItemMetadata = usize;
 
     fn is_batch_full(&self, batch: &B) -> bool {
-        batch.len() >= self.batch_item_limit || self.current_size >= self.batch_size_limit
+        batch.len() >= self.item_limit() || self.current_size >= self.size_limit()
     }
 
     fn item_fits_in_batch(&self, item: &T, batch: &B) -> (bool, Self::ItemMetadata) {
@@ -54,7 +80,7 @@ where
             // make sure any individual item can always fit in a batch
             return (true, item_size);
         }
-        let fits = self.current_size + item_size <= self.batch_size_limit;
+        let fits = self.current_size + item_size <= self.size_limit();
         (fits, item_size)
     }
 
@@ -114,12 +114,12 @@ where
 #[cfg(test)]
 #[allow(clippy::similar_names)]
 mod test {
-    use std::{num::NonZeroUsize, time::Duration};
+    use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
     use futures::stream;
 
     use super::*;
-    use crate::stream::BatcherSettings;
+    use crate::stream::{with_dynamic_limits, BatcherSettings, DynamicLimits, Limit};
 
     #[tokio::test]
     async fn item_limit() {
@@ -163,6 +163,27 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn dynamic_item_limit() {
+        let limits = Arc::new(DynamicLimits::default());
+        limits.set_scheduled(Limit::BatchMaxEvents, Some(3));
+        let batches: Vec<_> = with_dynamic_limits(limits, async {
+            let settings = BatcherSettings::new(
+                Duration::from_millis(100),
+                NonZeroUsize::new(10000).unwrap(),
+                NonZeroUsize::new(2).unwrap(),
+            );
+            Batcher::new(
+                stream::iter([1, 2, 3, 4]),
+                settings.into_item_size_config(|x: &u32| *x as usize),
+            )
+            .collect()
+            .await
+        })
+        .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4]]);
+    }
+
     #[tokio::test]
     async fn timeout_limit() {
         tokio::time::pause();
@@ -0,0 +1,100 @@
+//! Tracking of the attempts made to deliver a request.
+//!
+//! The retry policy of a service records each attempt it sees with [`record_attempt`], while the
+//! request is sent within [`track_delivery_attempts`], so that the details of the last failure can
+//! be attached to the events of the request if it isn't delivered in the end.
+use std::{cell::RefCell, future::Future};
+
+use crate::event::DeliveryFailure;
+
+tokio::task_local! {
+    static DELIVERY_ATTEMPTS: RefCell<DeliveryAttempts>;
+}
+
+/// The attempts made to deliver a request.
+#[derive(Clone, Debug, Default)]
+pub struct DeliveryAttempts {
+    count: usize,
+    status_code: Option<u16>,
+    error_class: Option<String>,
+}
+
+impl DeliveryAttempts {
+    /// Returns the details of the failure to deliver the request, using `error_class` if the last
+    /// attempt recorded none.
+    ///
+    /// The request is counted as attempted once if no attempt was recorded, as for the services
+    /// without a retry policy.
+    #[must_use]
+    pub fn failure(self, error_class: &str) -> DeliveryFailure {
+        DeliveryFailure {
+            status_code: self.status_code,
+            error_class: self.error_class.unwrap_or_else(|| error_class.to_owned()),
+            attempts: self.count.max(1),
+        }
+    }
+}
+
+/// Records an attempt to deliver the request being sent, along with the status code and the class
+/// of the error it failed with, if it did.
+///
+/// Does nothing outside of [`track_delivery_attempts`].
+pub fn record_attempt(status_code: Option<u16>, error_class: Option<String>) {
+    // The attempts are only tracked for the requests sent within `track_delivery_attempts`.
+    let _ = DELIVERY_ATTEMPTS.try_with(|attempts| {
+        let mut attempts = attempts.borrow_mut();
+        attempts.count += 1;
+        attempts.status_code = status_code;
+        attempts.error_class = error_class;
+    });
+}
+
+/// Runs `future`, sending a request, and returns its output along with the attempts recorded
+/// while it ran.
+pub async fn track_delivery_attempts<F: Future>(future: F) -> (F::Output, DeliveryAttempts) {
+    DELIVERY_ATTEMPTS
+        .scope(RefCell::new(DeliveryAttempts::default()), async move {
+            let output = future.await;
+            let attempts = DELIVERY_ATTEMPTS.with(|attempts| attempts.take());
+            (output, attempts)
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tracks_the_last_attempt() {
+        let ((), attempts) = track_delivery_attempts(async {
+            record_attempt(Some(503), Some("response".to_owned()));
+            record_attempt(Some(400), Some("response".to_owned()));
+        })
+        .await;
+
+        assert_eq!(
+            attempts.failure("request_failed"),
+            DeliveryFailure {
+                status_code: Some(400),
+                error_class: "response".to_owned(),
+                attempts: 2,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn counts_untracked_requests_once() {
+        record_attempt(Some(503), None);
+        let ((), attempts) = track_delivery_attempts(async {}).await;
+
+        assert_eq!(
+            attempts.failure("request_failed"),
+            DeliveryFailure {
+                status_code: None,
+                error_class: "request_failed".to_owned(),
+                attempts: 1,
+            }
+        );
+    }
+}
@@ -5,12 +5,12 @@ use tokio::{pin, select};
 use tower::Service;
 use tracing::Instrument;
 use vector_common::internal_event::{
-    register, ByteSize, BytesSent, CallError, CountByteSize, EventsSent, InternalEventHandle as _,
-    Output, PollReadyError, Registered, SharedString,
+    error_type, register, ByteSize, BytesSent, CallError, CountByteSize, EventsSent,
+    InternalEventHandle as _, Output, PollReadyError, Registered, SharedString,
 };
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 
-use super::FuturesUnorderedCount;
+use super::{track_delivery_attempts, DeliveryAttempts, FuturesUnorderedCount};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_event::emit,
@@ -169,18 +169,29 @@ where
                         let events_sent = events_sent.clone();
 
                         let metadata = req.get_metadata();
+                        // The span is part of the trace of the building of the request, if any.
+                        let trace_parent = metadata
+                            .trace_context()
+                            .map(|trace_context| trace_context.to_string())
+                            .unwrap_or_default();
+                        let span = info_span!(
+                            "request",
+                            request_id,
+                            otel.name = "sink.request",
+                            trace_parent = trace_parent.as_str(),
+                        );
 
-                        let fut = svc.call(req)
-                            .err_into()
-                            .map(move |result| Self::handle_response(
+                        let fut = track_delivery_attempts(svc.call(req).err_into())
+                            .map(move |(result, attempts)| Self::handle_response(
                                 result,
+                                attempts,
                                 request_id,
                                 finalizers,
                                 &metadata,
                                 &bytes_sent,
                                 &events_sent,
                             ))
-                            .instrument(info_span!("request", request_id).or_current());
+                            .instrument(span.or_current());
 
                         in_flight.push(fut);
                     }
@@ -200,19 +211,25 @@ where
 
     fn handle_response(
         result: Result<Svc::Response, Svc::Error>,
+        attempts: DeliveryAttempts,
         request_id: usize,
         finalizers: EventFinalizers,
         metadata: &RequestMetadata,
         bytes_sent: &Option<Registered<BytesSent>>,
         events_sent: &Registered<EventsSent>,
     ) {
+        let _finalize = info_span!("finalize", otel.name = "sink.request.finalize").entered();
         match result {
             Err(error) => {
                 Self::emit_call_error(Some(error), request_id, metadata.event_count());
+                finalizers.record_failure(&attempts.failure(error_type::REQUEST_FAILED));
                 finalizers.update_status(EventStatus::Rejected);
             }
             Ok(response) => {
                 trace!(message = "Service call succeeded.", request_id);
+                if response.event_status() != EventStatus::Delivered {
+                    finalizers.record_failure(&attempts.failure(error_type::REQUEST_FAILED));
+                }
                 finalizers.update_status(response.event_status());
                 if response.event_status() == EventStatus::Delivered {
                     if let Some(bytes_sent) = bytes_sent {
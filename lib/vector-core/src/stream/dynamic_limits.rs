@@ -0,0 +1,197 @@
+//! Limits of a sink that can be changed while it runs.
+//!
+//! The topology runs each sink within [`with_dynamic_limits`]. The batchers and concurrency
+//! controllers that the sink builds pick up the [`DynamicLimits`] of the sink from there, and
+//! read the limits in effect from it as they go, falling back to their configured values for the
+//! limits that aren't set.
+//!
+//! A limit can be set at two levels: an override, such as set through the API, and a scheduled
+//! value, such as set according to the time of day. The override wins over the scheduled value.
+//! Either is capped to the largest value the sink supports for the limit, if it has one, as it
+//! declares while it's built with [`DynamicLimits::set_max`].
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+tokio::task_local! {
+    static LIMITS: Arc<DynamicLimits>;
+}
+
+/// A limit that can be changed while the sink runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Limit {
+    /// The maximum size of a batch, in bytes.
+    BatchMaxBytes,
+
+    /// The maximum number of events in a batch.
+    BatchMaxEvents,
+
+    /// The maximum age of a batch, in milliseconds.
+    BatchTimeoutMs,
+
+    /// The concurrency limit of requests, or its maximum when the concurrency is adaptive.
+    Concurrency,
+}
+
+impl Limit {
+    /// All the limits.
+    pub const ALL: [Limit; 4] = [
+        Limit::BatchMaxBytes,
+        Limit::BatchMaxEvents,
+        Limit::BatchTimeoutMs,
+        Limit::Concurrency,
+    ];
+
+    /// The name of the limit, as in the configuration of the sink.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Limit::BatchMaxBytes => "batch.max_bytes",
+            Limit::BatchMaxEvents => "batch.max_events",
+            Limit::BatchTimeoutMs => "batch.timeout_ms",
+            Limit::Concurrency => "request.concurrency",
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The limits of a sink that can be changed while it runs.
+///
+/// A value of zero means the limit isn't set at that level, or has no maximum.
+#[derive(Debug, Default)]
+pub struct DynamicLimits {
+    overrides: [Arc<AtomicU64>; 4],
+    scheduled: [AtomicU64; 4],
+    maxima: [AtomicU64; 4],
+}
+
+impl DynamicLimits {
+    /// Returns the value holding the override of `limit`, so that it can be changed from
+    /// elsewhere.
+    pub fn override_value(&self, limit: Limit) -> &Arc<AtomicU64> {
+        &self.overrides[limit.index()]
+    }
+
+    /// Sets the scheduled value of `limit`, or unsets it.
+    pub fn set_scheduled(&self, limit: Limit, value: Option<u64>) {
+        self.scheduled[limit.index()].store(value.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Sets the largest value of `limit` the sink supports.
+    pub fn set_max(&self, limit: Limit, max: u64) {
+        self.maxima[limit.index()].store(max, Ordering::Relaxed);
+    }
+
+    /// Returns the largest value of `limit` the sink supports, if it has one.
+    pub fn max(&self, limit: Limit) -> Option<u64> {
+        match self.maxima[limit.index()].load(Ordering::Relaxed) {
+            0 => None,
+            max => Some(max),
+        }
+    }
+
+    /// Returns the value of `limit` in effect, if it is set.
+    pub fn get(&self, limit: Limit) -> Option<u64> {
+        let value = match self.overrides[limit.index()].load(Ordering::Relaxed) {
+            0 => self.scheduled[limit.index()].load(Ordering::Relaxed),
+            value => value,
+        };
+        (value != 0).then(|| self.max(limit).map_or(value, |max| value.min(max)))
+    }
+
+    fn get_usize(&self, limit: Limit, configured: usize) -> usize {
+        self.get(limit).map_or(configured, |value| {
+            usize::try_from(value).unwrap_or(usize::MAX)
+        })
+    }
+
+    /// Returns the maximum size of a batch in effect, in bytes, or `configured` if it isn't set.
+    pub fn batch_max_bytes(&self, configured: usize) -> usize {
+        self.get_usize(Limit::BatchMaxBytes, configured)
+    }
+
+    /// Returns the maximum number of events in a batch in effect, or `configured` if it isn't set.
+    pub fn batch_max_events(&self, configured: usize) -> usize {
+        self.get_usize(Limit::BatchMaxEvents, configured)
+    }
+
+    /// Returns the maximum age of a batch in effect, or `configured` if it isn't set.
+    pub fn batch_timeout(&self, configured: Duration) -> Duration {
+        self.get(Limit::BatchTimeoutMs)
+            .map_or(configured, Duration::from_millis)
+    }
+
+    /// Returns the concurrency limit in effect, or `configured` if it isn't set.
+    pub fn concurrency(&self, configured: usize) -> usize {
+        self.get_usize(Limit::Concurrency, configured)
+    }
+}
+
+/// Runs `future`, which builds or runs a sink, making `limits` the limits of the batchers and
+/// concurrency controllers it builds.
+pub async fn with_dynamic_limits<F: Future>(limits: Arc<DynamicLimits>, future: F) -> F::Output {
+    LIMITS.scope(limits, future).await
+}
+
+/// Returns the limits of the sink currently being built or run, if any.
+pub fn current() -> Option<Arc<DynamicLimits>> {
+    LIMITS.try_with(Arc::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_win_over_scheduled_values() {
+        let limits = DynamicLimits::default();
+        assert_eq!(limits.batch_max_events(10), 10);
+
+        limits.set_scheduled(Limit::BatchMaxEvents, Some(100));
+        assert_eq!(limits.batch_max_events(10), 100);
+
+        limits
+            .override_value(Limit::BatchMaxEvents)
+            .store(1000, Ordering::Relaxed);
+        assert_eq!(limits.batch_max_events(10), 1000);
+
+        limits
+            .override_value(Limit::BatchMaxEvents)
+            .store(0, Ordering::Relaxed);
+        limits.set_scheduled(Limit::BatchMaxEvents, None);
+        assert_eq!(limits.batch_max_events(10), 10);
+    }
+
+    #[test]
+    fn limits_are_capped_to_their_maximum() {
+        let limits = DynamicLimits::default();
+        limits.set_max(Limit::BatchMaxBytes, 1000);
+        assert_eq!(limits.batch_max_bytes(10), 10);
+
+        limits.set_scheduled(Limit::BatchMaxBytes, Some(100));
+        assert_eq!(limits.batch_max_bytes(10), 100);
+
+        limits.set_scheduled(Limit::BatchMaxBytes, Some(10_000));
+        assert_eq!(limits.batch_max_bytes(10), 1000);
+    }
+
+    #[tokio::test]
+    async fn limits_are_scoped() {
+        assert!(current().is_none());
+        let limits = Arc::new(DynamicLimits::default());
+        limits.set_scheduled(Limit::BatchTimeoutMs, Some(500));
+        let timeout = with_dynamic_limits(limits, async {
+            current().map(|limits| limits.batch_timeout(Duration::from_secs(1)))
+        })
+        .await;
+        assert_eq!(timeout, Some(Duration::from_millis(500)));
+    }
+}
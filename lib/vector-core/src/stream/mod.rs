@@ -1,10 +1,14 @@
 pub mod batcher;
 mod concurrent_map;
+mod delivery_attempts;
 mod driver;
+pub mod dynamic_limits;
 mod futures_unordered_count;
 mod partitioned_batcher;
 
 pub use concurrent_map::ConcurrentMap;
+pub use delivery_attempts::{record_attempt, track_delivery_attempts, DeliveryAttempts};
 pub use driver::{Driver, DriverResponse};
+pub use dynamic_limits::{with_dynamic_limits, DynamicLimits, Limit};
 pub(self) use futures_unordered_count::FuturesUnorderedCount;
 pub use partitioned_batcher::{BatcherSettings, ExpirationQueue, PartitionedBatcher};
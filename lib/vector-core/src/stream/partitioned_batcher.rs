@@ -5,6 +5,7 @@ use std::{
     mem,
     num::NonZeroUsize,
     pin::Pin,
+    sync::Arc,
     task::{ready, Context, Poll},
     time::Duration,
 };
@@ -16,10 +17,13 @@ use twox_hash::XxHash64;
 
 use crate::{
     partition::Partitioner,
-    stream::batcher::{
-        config::BatchConfigParts,
-        data::BatchReduce,
-        limiter::{ByteSizeOfItemSize, ItemBatchSize, SizeLimit},
+    stream::{
+        batcher::{
+            config::BatchConfigParts,
+            data::BatchReduce,
+            limiter::{ByteSizeOfItemSize, ItemBatchSize, SizeLimit},
+        },
+        dynamic_limits::{self, DynamicLimits},
     },
     time::KeyedTimer,
     ByteSizeOf,
@@ -98,6 +102,10 @@ where
             }
         }
     }
+
+    fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
 }
 
 /// A batch for use by `Batcher`
@@ -255,9 +263,11 @@ impl BatcherSettings {
                 batch_item_limit: self.item_limit,
                 current_size: 0,
                 item_size_calculator: item_size,
+                limits: dynamic_limits::current(),
             },
             batch_data: vec![],
             timeout: self.timeout,
+            limits: dynamic_limits::current(),
         }
     }
 
@@ -279,9 +289,11 @@ impl BatcherSettings {
                 batch_item_limit: self.item_limit,
                 current_size: 0,
                 item_size_calculator: item_size,
+                limits: dynamic_limits::current(),
             },
             batch_data: BatchReduce::new(reducer),
             timeout: self.timeout,
+            limits: dynamic_limits::current(),
         }
     }
 }
@@ -305,6 +317,10 @@ where
     closed_batches: Vec<(Prt::Key, Vec<Prt::Item>)>,
     /// The queue of pending batch expirations
     timer: KT,
+    /// The configured timeout of batches, if the timer uses one that can be changed.
+    timeout: Option<Duration>,
+    /// The limits of the sink that can be changed while it runs, overriding the configured ones.
+    limits: Option<Arc<DynamicLimits>>,
     /// The partitioner for this `Batcher`
     partitioner: Prt,
     #[pin]
@@ -326,6 +342,8 @@ where
             batches: HashMap::default(),
             closed_batches: Vec::default(),
             timer: ExpirationQueue::new(settings.timeout),
+            timeout: Some(settings.timeout),
+            limits: dynamic_limits::current(),
             partitioner,
             stream: stream.fuse(),
         }
@@ -353,6 +371,8 @@ where
             batches: HashMap::default(),
             closed_batches: Vec::default(),
             timer,
+            timeout: None,
+            limits: dynamic_limits::current(),
             partitioner,
             stream: stream.fuse(),
         }
@@ -413,8 +433,15 @@ where
                 }
                 Poll::Ready(Some(item)) => {
                     let item_key = this.partitioner.partition(&item);
-                    let item_limit: usize = *this.batch_item_limit;
-                    let alloc_limit: usize = *this.batch_allocation_limit;
+                    let mut item_limit: usize = *this.batch_item_limit;
+                    let mut alloc_limit: usize = *this.batch_allocation_limit;
+                    if let Some(limits) = this.limits.as_ref() {
+                        item_limit = limits.batch_max_events(item_limit);
+                        alloc_limit = limits.batch_max_bytes(alloc_limit);
+                        if let Some(timeout) = *this.timeout {
+                            this.timer.set_timeout(limits.batch_timeout(timeout));
+                        }
+                    }
 
                     if let Some(batch) = this.batches.get_mut(&item_key) {
                         if batch.has_space(&item) {
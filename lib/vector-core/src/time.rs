@@ -1,6 +1,9 @@
 //! Time utilities for vector-core
 
-use std::task::{Context, Poll};
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
 
 /// A trait for representing a timer which holds multiple subtimers, mapped by an arbitrary key, `K`.
 ///
@@ -31,4 +34,9 @@ pub trait KeyedTimer<K> {
     ///
     /// Used primarily for property testing vis-á-vis `vector_core::stream::batcher::Batcher`.
     fn poll_expired(&mut self, cx: &mut Context) -> Poll<Option<K>>;
+
+    /// Changes the timeout of the subtimers inserted or reset from now on.
+    ///
+    /// Timers whose timeout is fixed can ignore this.
+    fn set_timeout(&mut self, _timeout: Duration) {}
 }
@@ -20,8 +20,8 @@ use tokio_openssl::SslStream;
 use tonic::transport::{server::Connected, Certificate};
 
 use super::{
-    CreateAcceptorSnafu, HandshakeSnafu, IncomingListenerSnafu, MaybeTlsSettings, MaybeTlsStream,
-    SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
+    proxy_protocol, CreateAcceptorSnafu, HandshakeSnafu, IncomingListenerSnafu, MaybeTlsSettings,
+    MaybeTlsStream, ProxyProtocolSnafu, SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
 };
 use crate::tcp::{self, TcpKeepaliveConfig};
 
@@ -43,29 +43,68 @@ impl MaybeTlsSettings {
     pub async fn bind(&self, addr: &SocketAddr) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
 
-        let acceptor = match self {
-            Self::Tls(tls) => Some(tls.acceptor()?),
-            Self::Raw(()) => None,
+        let (acceptor, tls) = match self {
+            Self::Tls(tls) => (Some(tls.acceptor()?), Some(tls.clone())),
+            Self::Raw(()) => (None, None),
         };
 
-        Ok(MaybeTlsListener { listener, acceptor })
+        Ok(MaybeTlsListener {
+            listener,
+            acceptor,
+            tls,
+            generation: 0,
+            proxy_protocol: false,
+        })
     }
 }
 
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    tls: Option<TlsSettings>,
+    /// The generation of the reloaded settings the acceptor was built with.
+    generation: u64,
+    proxy_protocol: bool,
 }
 
 impl MaybeTlsListener {
+    /// Expects the connections to start with a PROXY protocol header, ahead of the TLS handshake.
+    ///
+    /// The peer address of the accepted streams is replaced by the address of the client, once
+    /// their handshake completes.
+    #[must_use]
+    pub fn with_proxy_protocol(mut self, proxy_protocol: bool) -> Self {
+        self.proxy_protocol = proxy_protocol;
+        self
+    }
+
     pub async fn accept(&mut self) -> crate::tls::Result<MaybeTlsIncomingStream<TcpStream>> {
-        self.listener
+        let (stream, peer_addr) = self
+            .listener
             .accept()
             .await
-            .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
-            })
-            .context(IncomingListenerSnafu)
+            .context(IncomingListenerSnafu)?;
+        self.reload_acceptor();
+        Ok(MaybeTlsIncomingStream::new(
+            stream,
+            peer_addr,
+            self.acceptor.clone(),
+            self.proxy_protocol,
+        ))
+    }
+
+    /// Rebuilds the acceptor if the settings were reloaded since it was built. The connections
+    /// accepted before keep the acceptor they were accepted with.
+    fn reload_acceptor(&mut self) {
+        let reloaded = match self.tls.as_ref().and_then(TlsSettings::reloaded) {
+            Some(reloaded) if reloaded.generation != self.generation => reloaded,
+            _ => return,
+        };
+        match reloaded.settings.acceptor() {
+            Ok(acceptor) => self.acceptor = Some(acceptor),
+            Err(error) => error!(message = "Failed to build TLS acceptor.", %error),
+        }
+        self.generation = reloaded.generation;
     }
 
     async fn into_accept(
@@ -134,6 +173,9 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            tls: None,
+            generation: 0,
+            proxy_protocol: false,
         }
     }
 }
@@ -148,7 +190,8 @@ pub struct MaybeTlsIncomingStream<S> {
 
 enum StreamState<S> {
     Accepted(MaybeTlsStream<S>),
-    Accepting(BoxFuture<'static, Result<SslStream<S>, TlsError>>),
+    /// Reads the PROXY protocol header if there is one, then performs the TLS handshake if enabled.
+    Accepting(BoxFuture<'static, Result<(MaybeTlsStream<S>, SocketAddr), TlsError>>),
     AcceptError(String),
     Closed,
 }
@@ -198,33 +241,55 @@ impl<S> MaybeTlsIncomingStream<S> {
 
 impl MaybeTlsIncomingStream<TcpStream> {
     pub(super) fn new(
-        stream: TcpStream,
+        mut stream: TcpStream,
         peer_addr: SocketAddr,
         acceptor: Option<SslAcceptor>,
+        proxy_protocol: bool,
     ) -> Self {
-        let state = match acceptor {
-            Some(acceptor) => StreamState::Accepting(
-                async move {
-                    let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
-                    let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
-                    Pin::new(&mut stream)
-                        .accept()
+        if acceptor.is_none() && !proxy_protocol {
+            return Self {
+                state: StreamState::Accepted(MaybeTlsStream::Raw(stream)),
+                peer_addr,
+            };
+        }
+
+        let state = StreamState::Accepting(
+            async move {
+                let mut peer_addr = peer_addr;
+                if proxy_protocol {
+                    if let Some(client_addr) = proxy_protocol::read_header(&mut stream)
                         .await
-                        .context(HandshakeSnafu)?;
-                    Ok(stream)
+                        .context(ProxyProtocolSnafu)?
+                    {
+                        peer_addr = client_addr;
+                    }
                 }
-                .boxed(),
-            ),
-            None => StreamState::Accepted(MaybeTlsStream::Raw(stream)),
-        };
+
+                let stream = match acceptor {
+                    Some(acceptor) => {
+                        let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
+                        let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
+                        Pin::new(&mut stream)
+                            .accept()
+                            .await
+                            .context(HandshakeSnafu)?;
+                        MaybeTlsStream::Tls(stream)
+                    }
+                    None => MaybeTlsStream::Raw(stream),
+                };
+                Ok((stream, peer_addr))
+            }
+            .boxed(),
+        );
         Self { state, peer_addr }
     }
 
     // Explicit handshake method
     pub async fn handshake(&mut self) -> crate::tls::Result<()> {
         if let StreamState::Accepting(fut) = &mut self.state {
-            let stream = fut.await?;
-            self.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+            let (stream, peer_addr) = fut.await?;
+            self.state = StreamState::Accepted(stream);
+            self.peer_addr = peer_addr;
         }
 
         Ok(())
@@ -268,8 +333,9 @@ impl MaybeTlsIncomingStream<TcpStream> {
             return match &mut this.state {
                 StreamState::Accepted(stream) => poll_fn(Pin::new(stream), cx),
                 StreamState::Accepting(fut) => match std::task::ready!(fut.as_mut().poll(cx)) {
-                    Ok(stream) => {
-                        this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                    Ok((stream, peer_addr)) => {
+                        this.state = StreamState::Accepted(stream);
+                        this.peer_addr = peer_addr;
                         continue;
                     }
                     Err(error) => {
@@ -317,8 +383,9 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
                 poll_result => poll_result,
             },
             StreamState::Accepting(fut) => match std::task::ready!(fut.as_mut().poll(cx)) {
-                Ok(stream) => {
-                    this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                Ok((stream, peer_addr)) => {
+                    this.state = StreamState::Accepted(stream);
+                    this.peer_addr = peer_addr;
                     Poll::Pending
                 }
                 Err(error) => {
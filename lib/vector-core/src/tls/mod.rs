@@ -16,6 +16,8 @@ use crate::tcp::{self, TcpKeepaliveConfig};
 mod incoming;
 mod maybe_tls;
 mod outgoing;
+mod proxy_protocol;
+mod reload;
 mod settings;
 
 pub use incoming::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener};
@@ -82,6 +84,8 @@ pub enum TlsError {
     MissingRequiredIdentity,
     #[snafu(display("TLS handshake failed: {}", source))]
     Handshake { source: openssl::ssl::Error },
+    #[snafu(display("Invalid PROXY protocol header: {}", source))]
+    ProxyProtocol { source: std::io::Error },
     #[snafu(display("Incoming listener failed: {}", source))]
     IncomingListener { source: tokio::io::Error },
     #[snafu(display("Creating the TLS acceptor failed: {}", source))]
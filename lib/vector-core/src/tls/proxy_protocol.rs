@@ -0,0 +1,188 @@
+//! Parsing of the PROXY protocol header, which load balancers send ahead of the connections they
+//! proxy to pass on the address of the client.
+//!
+//! Both versions of the protocol are supported, as described in
+//! <https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt>.
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The longest header of the first version, including its line ending.
+const V1_MAX_LENGTH: usize = 107;
+
+/// Reads the header from the start of the stream, and returns the address of the client.
+///
+/// `None` means that the header doesn't designate a client, like for the health checks of the load
+/// balancer, in which case the address of the peer is kept.
+pub(super) async fn read_header<S: AsyncRead + Unpin>(
+    stream: &mut S,
+) -> io::Result<Option<SocketAddr>> {
+    // Both versions of the header are longer than the signature of the second one.
+    let mut start = [0; 12];
+    stream.read_exact(&mut start).await?;
+
+    if start == V2_SIGNATURE {
+        read_v2(stream).await
+    } else if start.starts_with(b"PROXY ") {
+        let mut line = start.to_vec();
+        while !line.ends_with(b"\r\n") {
+            if line.len() >= V1_MAX_LENGTH {
+                return Err(invalid("too long"));
+            }
+            line.push(stream.read_u8().await?);
+        }
+        parse_v1(&line[..line.len() - 2])
+    } else {
+        Err(invalid("missing"))
+    }
+}
+
+fn parse_v1(line: &[u8]) -> io::Result<Option<SocketAddr>> {
+    let line = std::str::from_utf8(line).map_err(|_| invalid("not ASCII"))?;
+    let fields = line.split(' ').collect::<Vec<_>>();
+    match fields.as_slice() {
+        ["PROXY", "UNKNOWN", ..] => Ok(None),
+        ["PROXY", protocol @ ("TCP4" | "TCP6"), source, _destination, port, _destination_port] => {
+            let address = source
+                .parse::<IpAddr>()
+                .map_err(|_| invalid("invalid source address"))?;
+            if address.is_ipv4() != (*protocol == "TCP4") {
+                return Err(invalid("source address doesn't match the protocol"));
+            }
+            let port = port
+                .parse::<u16>()
+                .map_err(|_| invalid("invalid source port"))?;
+            Ok(Some(SocketAddr::new(address, port)))
+        }
+        _ => Err(invalid("malformed")),
+    }
+}
+
+async fn read_v2<S: AsyncRead + Unpin>(stream: &mut S) -> io::Result<Option<SocketAddr>> {
+    let mut header = [0; 4];
+    stream.read_exact(&mut header).await?;
+    let [version_command, family, length @ ..] = header;
+    if version_command >> 4 != 2 {
+        return Err(invalid("unsupported version"));
+    }
+
+    let mut addresses = vec![0; u16::from_be_bytes(length) as usize];
+    stream.read_exact(&mut addresses).await?;
+    match version_command & 0x0f {
+        // The `LOCAL` command, for the connections made by the load balancer itself.
+        0 => Ok(None),
+        // The `PROXY` command.
+        1 => parse_v2_addresses(family, &addresses),
+        _ => Err(invalid("unsupported command")),
+    }
+}
+
+fn parse_v2_addresses(family: u8, addresses: &[u8]) -> io::Result<Option<SocketAddr>> {
+    match family >> 4 {
+        // AF_INET, with the source and destination addresses followed by their ports.
+        1 if addresses.len() >= 12 => {
+            let address = Ipv4Addr::new(addresses[0], addresses[1], addresses[2], addresses[3]);
+            let port = u16::from_be_bytes([addresses[8], addresses[9]]);
+            Ok(Some(SocketAddr::new(address.into(), port)))
+        }
+        // AF_INET6, laid out the same way.
+        2 if addresses.len() >= 36 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&addresses[..16]);
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+            Ok(Some(SocketAddr::new(Ipv6Addr::from(octets).into(), port)))
+        }
+        1 | 2 => Err(invalid("truncated addresses")),
+        // AF_UNSPEC and AF_UNIX don't designate a network address.
+        _ => Ok(None),
+    }
+}
+
+fn invalid(reason: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn read(mut data: &[u8]) -> io::Result<Option<SocketAddr>> {
+        let result = read_header(&mut data).await;
+        // The data following the header is left in the stream.
+        if result.is_ok() {
+            assert_eq!(data, b"data");
+        }
+        result
+    }
+
+    #[tokio::test]
+    async fn reads_v1_headers() {
+        assert_eq!(
+            read(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\ndata")
+                .await
+                .unwrap(),
+            Some("192.0.2.1:56324".parse().unwrap())
+        );
+        assert_eq!(
+            read(b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\ndata")
+                .await
+                .unwrap(),
+            Some("[2001:db8::1]:56324".parse().unwrap())
+        );
+        assert_eq!(read(b"PROXY UNKNOWN\r\ndata").await.unwrap(), None);
+
+        assert!(read(b"PROXY TCP6 192.0.2.1 198.51.100.1 56324 443\r\ndata")
+            .await
+            .is_err());
+        assert!(read(b"PROXY TCP4 192.0.2.1 56324\r\ndata").await.is_err());
+        assert!(read(&[b"PROXY ".as_slice(), &[b'A'; 120]].concat())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn reads_v2_headers() {
+        let mut header = V2_SIGNATURE.to_vec();
+        header.extend_from_slice(&[0x21, 0x11, 0, 12]);
+        header.extend_from_slice(&[192, 0, 2, 1, 198, 51, 100, 1, 0xdc, 0x04, 0x01, 0xbb]);
+        header.extend_from_slice(b"data");
+        assert_eq!(
+            read(&header).await.unwrap(),
+            Some("192.0.2.1:56324".parse().unwrap())
+        );
+
+        let source = "2001:db8::1".parse::<Ipv6Addr>().unwrap();
+        let mut header = V2_SIGNATURE.to_vec();
+        // The addresses are followed by a TLV, which is skipped.
+        header.extend_from_slice(&[0x21, 0x21, 0, 40]);
+        header.extend_from_slice(&source.octets());
+        header.extend_from_slice(&[0; 16]);
+        header.extend_from_slice(&[0xdc, 0x04, 0x01, 0xbb, 0x04, 0, 1, 0]);
+        header.extend_from_slice(b"data");
+        assert_eq!(
+            read(&header).await.unwrap(),
+            Some("[2001:db8::1]:56324".parse().unwrap())
+        );
+
+        let mut header = V2_SIGNATURE.to_vec();
+        header.extend_from_slice(&[0x20, 0x00, 0, 0]);
+        header.extend_from_slice(b"data");
+        assert_eq!(read(&header).await.unwrap(), None);
+
+        let mut header = V2_SIGNATURE.to_vec();
+        header.extend_from_slice(&[0x21, 0x11, 0, 4, 192, 0, 2, 1]);
+        assert!(read(&header).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_headers() {
+        assert!(read(b"<13>Feb 13 20:07:26 host app: message\n")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,218 @@
+//! Reloading of the TLS settings when the files they were loaded from change.
+//!
+//! The files are checked for changes at most once per interval, whenever a connection is accepted
+//! or made. New connections then use the reloaded settings, while established connections keep
+//! the settings they were made with.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use openssl::ssl::{ConnectConfiguration, SslContext};
+use vector_common::internal_event::{emit, error_stage, TlsReloadError, TlsReloaded};
+
+use super::{tls_connector_builder, MaybeTls, Result, TlsConfig, TlsSettings, PEM_START_MARKER};
+
+/// How often the files are checked for changes, at most.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The modification time and length of a file, or `None` if it can't be read.
+type FileVersion = Option<(SystemTime, u64)>;
+
+pub(super) struct Reloader {
+    options: TlsConfig,
+    for_server: bool,
+    files: Vec<PathBuf>,
+    state: Mutex<State>,
+}
+
+struct State {
+    checked_at: Instant,
+    versions: Vec<FileVersion>,
+    reloaded: Option<Reloaded>,
+}
+
+/// The settings reloaded from the files, along with the context of the clients using them.
+#[derive(Clone)]
+pub(super) struct Reloaded {
+    /// Incremented on every reload, so that the users of the settings know when to rebuild.
+    pub(super) generation: u64,
+    pub(super) settings: Arc<TlsSettings>,
+    client_context: Option<SslContext>,
+}
+
+impl Reloader {
+    /// Watches the files of the options, unless they are all given inline or there are none.
+    pub(super) fn new(options: &TlsConfig, for_server: bool) -> Option<Arc<Self>> {
+        let files = [&options.ca_file, &options.crt_file, &options.key_file]
+            .into_iter()
+            .flatten()
+            .filter(|file| !file.to_string_lossy().contains(PEM_START_MARKER))
+            .cloned()
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            return None;
+        }
+
+        let versions = files.iter().map(|file| version(file)).collect();
+        Some(Arc::new(Self {
+            options: options.clone(),
+            for_server,
+            files,
+            state: Mutex::new(State {
+                checked_at: Instant::now(),
+                versions,
+                reloaded: None,
+            }),
+        }))
+    }
+
+    /// Returns the latest reloaded settings, after checking the files for changes if they weren't
+    /// checked recently. `None` means the files didn't change since the settings were first loaded.
+    pub(super) fn latest(&self) -> Option<Reloaded> {
+        let mut state = self.state.lock().expect("poisoned lock");
+        if state.checked_at.elapsed() < CHECK_INTERVAL {
+            return state.reloaded.clone();
+        }
+        state.checked_at = Instant::now();
+
+        let versions = self
+            .files
+            .iter()
+            .map(|file| version(file))
+            .collect::<Vec<_>>();
+        if versions != state.versions {
+            match self.reload() {
+                Ok((settings, client_context)) => {
+                    let generation = state
+                        .reloaded
+                        .as_ref()
+                        .map_or(1, |reloaded| reloaded.generation + 1);
+                    state.reloaded = Some(Reloaded {
+                        generation,
+                        settings: Arc::new(settings),
+                        client_context,
+                    });
+                    state.versions = versions;
+                    emit(TlsReloaded { files: &self.files });
+                }
+                // The versions aren't updated, so that the files are loaded again on the next
+                // check. This covers rotations replacing the certificate and the key one after the
+                // other, which fail to load in between.
+                Err(error) => emit(TlsReloadError {
+                    files: &self.files,
+                    error,
+                    stage: self.stage(),
+                }),
+            }
+        }
+
+        state.reloaded.clone()
+    }
+
+    /// Switches a client connection over to the reloaded settings, if there are any.
+    pub(super) fn configure(&self, connection: &mut ConnectConfiguration) {
+        if let Some(Reloaded {
+            client_context: Some(context),
+            ..
+        }) = self.latest()
+        {
+            if let Err(error) = connection.set_ssl_context(&context) {
+                emit(TlsReloadError {
+                    files: &self.files,
+                    error,
+                    stage: error_stage::SENDING,
+                });
+            }
+        }
+    }
+
+    fn reload(&self) -> Result<(TlsSettings, Option<SslContext>)> {
+        let settings = TlsSettings::load(&self.options, self.for_server)?;
+        if self.for_server {
+            // The acceptor is built ahead of the listeners, as a check that the identity is usable.
+            if settings.identity.is_some() {
+                settings.acceptor()?;
+            }
+            Ok((settings, None))
+        } else {
+            let context = tls_connector_builder(&MaybeTls::Tls(settings.clone()))?
+                .build()
+                .into_context();
+            Ok((settings, Some(context)))
+        }
+    }
+
+    const fn stage(&self) -> &'static str {
+        if self.for_server {
+            error_stage::RECEIVING
+        } else {
+            error_stage::SENDING
+        }
+    }
+}
+
+fn version(file: &Path) -> FileVersion {
+    let metadata = fs::metadata(file).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{
+        TEST_PEM_CLIENT_CRT_PATH, TEST_PEM_CLIENT_KEY_PATH, TEST_PEM_CRT_PATH, TEST_PEM_KEY_PATH,
+    };
+
+    #[test]
+    fn reloads_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let crt_file = dir.path().join("tls.crt");
+        let key_file = dir.path().join("tls.key");
+        fs::copy(TEST_PEM_CRT_PATH, &crt_file).unwrap();
+        fs::copy(TEST_PEM_KEY_PATH, &key_file).unwrap();
+
+        let options = TlsConfig {
+            crt_file: Some(crt_file.clone()),
+            key_file: Some(key_file.clone()),
+            ..Default::default()
+        };
+        let settings = TlsSettings::from_options_base(&Some(options), true).unwrap();
+        let reloader = settings.reloader.as_ref().unwrap();
+        let original = settings.identity_pem().unwrap();
+
+        // Unchanged files aren't reloaded.
+        reloader.state.lock().unwrap().checked_at -= CHECK_INTERVAL;
+        assert!(reloader.latest().is_none());
+
+        // A certificate without its matching key fails, and is loaded again on the next check.
+        fs::copy(TEST_PEM_CLIENT_CRT_PATH, &crt_file).unwrap();
+        reloader.state.lock().unwrap().checked_at -= CHECK_INTERVAL;
+        assert!(reloader.latest().is_none());
+
+        fs::copy(TEST_PEM_CLIENT_KEY_PATH, &key_file).unwrap();
+        reloader.state.lock().unwrap().checked_at -= CHECK_INTERVAL;
+        let reloaded = reloader.latest().unwrap();
+        assert_eq!(reloaded.generation, 1);
+        assert_ne!(reloaded.settings.identity_pem().unwrap(), original);
+        assert!(reloaded.settings.reloader.is_none());
+
+        // Files aren't checked again within the interval.
+        fs::remove_file(&crt_file).unwrap();
+        assert_eq!(reloader.latest().unwrap().generation, 1);
+    }
+
+    #[test]
+    fn ignores_inline_files() {
+        let crt = fs::read_to_string(TEST_PEM_CRT_PATH).unwrap();
+        let key = fs::read_to_string(TEST_PEM_KEY_PATH).unwrap();
+        let options = TlsConfig {
+            crt_file: Some(crt.into()),
+            key_file: Some(key.into()),
+            ..Default::default()
+        };
+        assert!(Reloader::new(&options, true).is_none());
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     fs::File,
     io::Read,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use lookup::lookup_v2::OptionalValuePath;
@@ -17,6 +18,7 @@ use snafu::ResultExt;
 use vector_config::configurable_component;
 
 use super::{
+    reload::{Reloaded, Reloader},
     AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
     EncodeAlpnProtocolsSnafu, FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu,
     NewStoreBuilderSnafu, ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result,
@@ -80,6 +82,11 @@ pub struct TlsSourceConfig {
 }
 
 /// TLS configuration.
+///
+/// The CA, certificate, and key files are watched for changes, and reloaded without restarting the
+/// component. New connections use the reloaded files, while established connections are kept. Only
+/// the connections made with OpenSSL are reloaded, which excludes proxied connections and the
+/// clients of some components, such as `kafka`.
 #[configurable_component]
 #[configurable(metadata(docs::advanced))]
 #[derive(Clone, Debug, Default)]
@@ -165,6 +172,8 @@ pub struct TlsSettings {
     authorities: Vec<X509>,
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
     alpn_protocols: Option<Vec<u8>>,
+    /// Reloads the settings when the files they were loaded from change.
+    pub(super) reloader: Option<Arc<Reloader>>,
 }
 
 #[derive(Clone)]
@@ -193,15 +202,31 @@ impl TlsSettings {
             }
         }
 
+        Ok(Self {
+            reloader: Reloader::new(options, for_server),
+            ..Self::load(options, for_server)?
+        })
+    }
+
+    /// Loads the settings from the options, without watching their files.
+    pub(super) fn load(options: &TlsConfig, for_server: bool) -> Result<Self> {
         Ok(Self {
             verify_certificate: options.verify_certificate.unwrap_or(!for_server),
             verify_hostname: options.verify_hostname.unwrap_or(!for_server),
             authorities: options.load_authorities()?,
             identity: options.load_identity()?,
             alpn_protocols: options.parse_alpn_protocols()?,
+            reloader: None,
         })
     }
 
+    /// The settings reloaded since these were loaded, if their files changed.
+    pub(super) fn reloaded(&self) -> Option<Reloaded> {
+        self.reloader
+            .as_ref()
+            .and_then(|reloader| reloader.latest())
+    }
+
     fn identity(&self) -> Option<ParsedPkcs12_2> {
         // This data was test-built previously, so we can just use it
         // here and expect the results will not fail. This can all be
@@ -249,6 +274,10 @@ impl TlsSettings {
     }
 
     pub(super) fn apply_context(&self, context: &mut SslContextBuilder) -> Result<()> {
+        if let Some(reloaded) = self.reloaded() {
+            return reloaded.settings.apply_context(context);
+        }
+
         context.set_verify(if self.verify_certificate {
             SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT
         } else {
@@ -299,8 +328,15 @@ impl TlsSettings {
         Ok(())
     }
 
+    /// Configures a connection made with a connector built from these settings.
+    ///
+    /// This switches the connection over to the reloaded settings, if the files changed since the
+    /// connector was built.
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
+        if let Some(reloader) = &self.reloader {
+            reloader.configure(connection);
+        }
     }
 }
 
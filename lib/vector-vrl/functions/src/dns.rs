@@ -0,0 +1,253 @@
+//! The resolver shared by the DNS functions.
+//!
+//! Lookups run on a small runtime of their own, so that they proceed while the function that
+//! started them waits for the answer. The answers are cached for as long as their TTL allows, and
+//! an expired answer is still returned when a lookup doesn't complete in time.
+//!
+//! VRL functions are synchronous, so waiting for an answer blocks the thread running the program
+//! for up to the timeout, which is capped at `MAX_TIMEOUT`. On a multi-threaded Tokio runtime the
+//! wait goes through `block_in_place`, so that the other tasks of the worker are moved to another
+//! thread instead of being stalled with it.
+use std::{
+    net::IpAddr,
+    num::NonZeroUsize,
+    sync::{mpsc, Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use ::value::Value;
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use tokio::{
+    runtime::{Handle, Runtime, RuntimeFlavor},
+    sync::Semaphore,
+    task::block_in_place,
+};
+use trust_dns_resolver::{error::ResolveErrorKind, TokioAsyncResolver};
+use vrl::prelude::ExpressionError;
+
+/// The number of answers to keep in the cache.
+const CACHE_SIZE: usize = 10_000;
+
+/// The number of lookups that may run concurrently.
+const MAX_CONCURRENT_LOOKUPS: usize = 64;
+
+/// How long to cache failed lookups that don't come with a TTL of their own.
+const ERROR_TTL: Duration = Duration::from_secs(5);
+
+/// How long a lookup is waited for by default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The longest a lookup is waited for, whatever the timeout given.
+const MAX_TIMEOUT: Duration = Duration::from_secs(5);
+
+static RESOLVER: Lazy<Resolver> = Lazy::new(Resolver::new);
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub(crate) enum Query {
+    /// Looks up the addresses of a host name.
+    Lookup(String),
+    /// Looks up the host names of an address.
+    Reverse(IpAddr),
+}
+
+type Answer = Result<Vec<String>, String>;
+
+#[derive(Clone)]
+struct Entry {
+    answer: Answer,
+    expires_at: Instant,
+}
+
+struct Resolver {
+    runtime: Runtime,
+    resolver: Result<Arc<TokioAsyncResolver>, String>,
+    permits: Arc<Semaphore>,
+    cache: Arc<Mutex<LruCache<Query, Entry>>>,
+}
+
+impl Resolver {
+    fn new() -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("vrl-dns")
+            .enable_all()
+            .build()
+            .expect("failed to start the DNS runtime");
+        let resolver = {
+            let _guard = runtime.enter();
+            TokioAsyncResolver::tokio_from_system_conf()
+                .map(Arc::new)
+                .map_err(|error| format!("unable to configure the DNS resolver: {}", error))
+        };
+        let cache_size = NonZeroUsize::new(CACHE_SIZE).expect("static non-zero number");
+
+        Self {
+            runtime,
+            resolver,
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_LOOKUPS)),
+            cache: Arc::new(Mutex::new(LruCache::new(cache_size))),
+        }
+    }
+
+    fn resolve(&self, query: Query, timeout: Duration) -> Answer {
+        let stale = match self.cache.lock().expect("poisoned lock").get(&query) {
+            Some(entry) if entry.expires_at > Instant::now() => return entry.answer.clone(),
+            Some(entry) => Some(entry.answer.clone()),
+            None => None,
+        };
+
+        let resolver = match &self.resolver {
+            Ok(resolver) => Arc::clone(resolver),
+            Err(error) => return stale.unwrap_or_else(|| Err(error.clone())),
+        };
+
+        // The lookups that were given up on still complete, so they are bounded to not pile up.
+        let permit = match Arc::clone(&self.permits).try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                return stale.unwrap_or_else(|| Err("too many concurrent DNS lookups".into()))
+            }
+        };
+
+        let (tx, rx) = mpsc::sync_channel(1);
+        let cache = Arc::clone(&self.cache);
+        self.runtime.spawn(async move {
+            let entry = lookup(&resolver, &query).await;
+            drop(permit);
+            let answer = entry.answer.clone();
+            cache.lock().expect("poisoned lock").put(query, entry);
+            // The function stopped waiting if this fails, the answer is only cached then.
+            let _ = tx.send(answer);
+        });
+
+        match wait(|| rx.recv_timeout(timeout.min(MAX_TIMEOUT))) {
+            Ok(answer) => answer,
+            Err(_) => stale.unwrap_or_else(|| Err("DNS lookup timed out".into())),
+        }
+    }
+}
+
+/// Runs the blocking wait for an answer, letting the runtime of the caller know about it.
+///
+/// `block_in_place` panics on a current-thread runtime, where the wait blocks the runtime as a
+/// whole instead.
+fn wait<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => block_in_place(f),
+        _ => f(),
+    }
+}
+
+async fn lookup(resolver: &TokioAsyncResolver, query: &Query) -> Entry {
+    let result = match query {
+        Query::Lookup(name) => resolver.lookup_ip(name.as_str()).await.map(|lookup| {
+            let addresses = lookup.iter().map(|address| address.to_string()).collect();
+            (addresses, lookup.valid_until())
+        }),
+        Query::Reverse(address) => resolver.reverse_lookup(*address).await.map(|lookup| {
+            let names = lookup
+                .iter()
+                .map(|name| name.to_utf8().trim_end_matches('.').to_owned())
+                .collect();
+            (names, lookup.valid_until())
+        }),
+    };
+
+    match result {
+        Ok((records, valid_until)) => Entry {
+            answer: Ok(records),
+            expires_at: valid_until,
+        },
+        Err(error) => {
+            let ttl = match error.kind() {
+                ResolveErrorKind::NoRecordsFound {
+                    negative_ttl: Some(ttl),
+                    ..
+                } => Duration::from_secs(u64::from(*ttl)),
+                _ => ERROR_TTL,
+            };
+            Entry {
+                answer: Err(error.to_string()),
+                expires_at: Instant::now() + ttl,
+            }
+        }
+    }
+}
+
+/// Resolves the query with the shared resolver, waiting at most for the given timeout.
+pub(crate) fn resolve(query: Query, timeout: Duration) -> Answer {
+    RESOLVER.resolve(query, timeout)
+}
+
+/// Reads the `timeout` argument of the DNS functions, in seconds.
+///
+/// Timeouts longer than `MAX_TIMEOUT` are shortened to it when waiting.
+pub(crate) fn timeout(value: Option<Value>) -> std::result::Result<Duration, ExpressionError> {
+    let secs = match value {
+        None => return Ok(DEFAULT_TIMEOUT),
+        Some(Value::Integer(secs)) => secs as f64,
+        Some(Value::Float(secs)) => secs.into_inner(),
+        Some(value) => return Err(format!("invalid timeout: {}", value).into()),
+    };
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid timeout: {}", secs).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_addresses_without_lookup() {
+        let query = Query::Lookup("127.0.0.1".to_owned());
+        assert_eq!(
+            resolve(query, DEFAULT_TIMEOUT),
+            Ok(vec!["127.0.0.1".to_owned()])
+        );
+    }
+
+    #[test]
+    fn serves_cached_answers() {
+        let query = Query::Reverse("192.0.2.1".parse().unwrap());
+        let answer = Ok(vec!["cached.example.com".to_owned()]);
+        RESOLVER.cache.lock().unwrap().put(
+            query.clone(),
+            Entry {
+                answer: answer.clone(),
+                expires_at: Instant::now() + Duration::from_secs(60),
+            },
+        );
+
+        assert_eq!(resolve(query, DEFAULT_TIMEOUT), answer);
+    }
+
+    #[test]
+    fn falls_back_to_expired_answers() {
+        let query = Query::Lookup("stale.invalid".to_owned());
+        let answer = Ok(vec!["192.0.2.2".to_owned()]);
+        RESOLVER.cache.lock().unwrap().put(
+            query.clone(),
+            Entry {
+                answer: answer.clone(),
+                expires_at: Instant::now(),
+            },
+        );
+
+        assert_eq!(resolve(query, Duration::ZERO), answer);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn waits_in_place_on_multi_threaded_runtimes() {
+        let query = Query::Lookup("::1".to_owned());
+        assert_eq!(resolve(query, DEFAULT_TIMEOUT), Ok(vec!["::1".to_owned()]));
+    }
+
+    #[tokio::test]
+    async fn waits_on_current_thread_runtimes() {
+        let query = Query::Lookup("127.0.0.2".to_owned());
+        assert_eq!(
+            resolve(query, DEFAULT_TIMEOUT),
+            Ok(vec!["127.0.0.2".to_owned()])
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use ::value::{kind::Collection, Kind, Value};
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::dns::{self, Query};
+
+fn dns_lookup(value: Value, timeout: Option<Value>) -> Resolved {
+    let name = value.try_bytes_utf8_lossy()?.into_owned();
+    let timeout = dns::timeout(timeout)?;
+
+    let addresses = dns::resolve(Query::Lookup(name), timeout)
+        .map_err(|error| format!("unable to look up host: {}", error))?;
+
+    Ok(addresses
+        .into_iter()
+        .map(Value::from)
+        .collect::<Vec<_>>()
+        .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DnsLookup;
+
+impl Function for DnsLookup {
+    fn identifier(&self) -> &'static str {
+        "dns_lookup"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timeout",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Look up an address",
+            source: r#"dns_lookup!("127.0.0.1")"#,
+            result: Ok(r#"["127.0.0.1"]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timeout = arguments.optional("timeout");
+        Ok(DnsLookupFn { value, timeout }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DnsLookupFn {
+    value: Box<dyn Expression>,
+    timeout: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DnsLookupFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let timeout = self
+            .timeout
+            .as_ref()
+            .map(|timeout| timeout.resolve(ctx))
+            .transpose()?;
+        dns_lookup(value, timeout)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible()
+    }
+}
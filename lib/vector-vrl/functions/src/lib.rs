@@ -1,7 +1,14 @@
 #![deny(warnings)]
 
+#[cfg(feature = "dns")]
+mod dns;
+#[cfg(feature = "dns")]
+pub mod dns_lookup;
 pub mod get_secret;
+pub mod library;
 pub mod remove_secret;
+#[cfg(feature = "dns")]
+pub mod reverse_dns;
 pub mod set_secret;
 pub mod set_semantic_meaning;
 
@@ -22,5 +29,9 @@ pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
         Box::new(get_secret::GetSecret) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
+        #[cfg(feature = "dns")]
+        Box::new(dns_lookup::DnsLookup) as _,
+        #[cfg(feature = "dns")]
+        Box::new(reverse_dns::ReverseDns) as _,
     ]
 }
@@ -0,0 +1,450 @@
+//! Functions defined in VRL, in libraries shared by several programs.
+//!
+//! A library defines functions with `fn name(argument, ...) { ... }`, whose body is a VRL program
+//! returning the result of the function. The body runs against a target of its own, holding the
+//! arguments, so that a function only depends on what it's called with.
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use ::value::{kind::Collection, Kind, Secrets, Value};
+use once_cell::sync::Lazy;
+use vrl::{
+    diagnostic::Formatter, prelude::*, state::TypeState, CompileConfig, Program, TargetValue,
+};
+
+/// The identifiers and parameters of the library functions, which VRL requires to be static.
+///
+/// They are interned rather than leaked on every load, as libraries are loaded again whenever the
+/// programs using them are.
+static INTERNED: Lazy<Mutex<HashMap<(String, Vec<String>), Signature>>> =
+    Lazy::new(Default::default);
+
+#[derive(Clone, Copy, Debug)]
+struct Signature {
+    identifier: &'static str,
+    parameters: &'static [Parameter],
+}
+
+impl Signature {
+    fn intern(name: String, parameters: Vec<String>) -> Self {
+        *INTERNED
+            .lock()
+            .expect("poisoned lock")
+            .entry((name.clone(), parameters.clone()))
+            .or_insert_with(|| Signature {
+                identifier: Box::leak(name.into_boxed_str()),
+                parameters: Box::leak(
+                    parameters
+                        .into_iter()
+                        .map(|keyword| Parameter {
+                            keyword: Box::leak(keyword.into_boxed_str()),
+                            kind: kind::ANY,
+                            required: true,
+                        })
+                        .collect(),
+                ),
+            })
+    }
+}
+
+/// A function parsed from a library, before its body is compiled.
+#[derive(Debug, PartialEq)]
+struct Definition {
+    name: String,
+    parameters: Vec<String>,
+    body: String,
+}
+
+/// Compiles the functions defined in the library, and adds them to the given functions.
+///
+/// The bodies of the functions can call the given functions, which includes the functions defined
+/// before them in the library.
+pub fn load(
+    source: &str,
+    functions: &mut Vec<Box<dyn Function>>,
+    config: impl Fn() -> CompileConfig,
+) -> Result<(), String> {
+    for definition in parse(source)? {
+        if functions
+            .iter()
+            .any(|function| function.identifier() == definition.name)
+        {
+            return Err(format!("function `{}` is already defined", definition.name));
+        }
+
+        // The arguments are bound to variables ahead of the body, on its first line so that the
+        // lines of the diagnostics still match the library.
+        let source = definition
+            .parameters
+            .iter()
+            .map(|parameter| format!("{0} = .{0}; ", parameter))
+            .chain(std::iter::once(definition.body))
+            .collect::<String>();
+        let arguments = definition
+            .parameters
+            .iter()
+            .fold(Collection::empty(), |arguments, parameter| {
+                arguments.with_known(parameter.as_str(), Kind::any())
+            });
+        let state = TypeState {
+            local: Default::default(),
+            external: vrl::state::ExternalEnv::new_with_kind(
+                Kind::object(arguments),
+                Kind::object(Collection::empty()),
+            ),
+        };
+
+        let program = vrl::compile_with_state(&source, functions, &state, config())
+            .map_err(|diagnostics| {
+                format!(
+                    "function `{}` failed to compile:\n{}",
+                    definition.name,
+                    Formatter::new(&source, diagnostics)
+                )
+            })?
+            .program;
+
+        functions.push(Box::new(LibraryFunction {
+            signature: Signature::intern(definition.name, definition.parameters),
+            program: Arc::new(program),
+        }));
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+struct LibraryFunction {
+    signature: Signature,
+    program: Arc<Program>,
+}
+
+impl Function for LibraryFunction {
+    fn identifier(&self) -> &'static str {
+        self.signature.identifier
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.signature.parameters
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let arguments = self
+            .signature
+            .parameters
+            .iter()
+            .map(|parameter| arguments.required(parameter.keyword))
+            .collect();
+        Ok(LibraryFunctionFn {
+            function: self.clone(),
+            arguments,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LibraryFunctionFn {
+    function: LibraryFunction,
+    arguments: Vec<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for LibraryFunctionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut arguments = BTreeMap::new();
+        for (parameter, argument) in self
+            .function
+            .signature
+            .parameters
+            .iter()
+            .zip(&self.arguments)
+        {
+            arguments.insert(parameter.keyword.to_owned(), argument.resolve(ctx)?);
+        }
+
+        let mut target = TargetValue {
+            value: Value::Object(arguments),
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut state = vrl::state::Runtime::default();
+        let mut body_ctx = Context::new(&mut target, &mut state, ctx.timezone());
+        self.function.program.resolve(&mut body_ctx)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        let info = self.function.program.info();
+        if info.fallible || info.abortable {
+            TypeDef::any().fallible()
+        } else {
+            TypeDef::any().infallible()
+        }
+    }
+}
+
+/// Splits the library into the definitions of its functions.
+fn parse(source: &str) -> Result<Vec<Definition>, String> {
+    let mut parser = Parser {
+        source,
+        position: 0,
+    };
+    let mut definitions = Vec::new();
+
+    loop {
+        parser.skip_trivia();
+        if parser.rest().is_empty() {
+            return Ok(definitions);
+        }
+
+        if parser.identifier()? != "fn" {
+            return Err(parser.error("expected a function definition"));
+        }
+        let name = parser.identifier()?;
+
+        parser.expect("(")?;
+        let mut parameters = Vec::new();
+        loop {
+            parser.skip_trivia();
+            if parser.eat(")") {
+                break;
+            }
+            let parameter = parser.identifier()?;
+            if parameters.contains(&parameter) {
+                return Err(parser.error(&format!("duplicate parameter `{}`", parameter)));
+            }
+            parameters.push(parameter);
+            parser.skip_trivia();
+            if !parser.eat(",") {
+                parser.expect(")")?;
+                break;
+            }
+        }
+
+        parser.expect("{")?;
+        let body = parser
+            .body()
+            .ok_or_else(|| format!("unterminated body of function `{}`", name))?;
+
+        definitions.push(Definition {
+            name,
+            parameters,
+            body,
+        });
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn error(&self, message: &str) -> String {
+        let line = self.source[..self.position].matches('\n').count() + 1;
+        format!("{} on line {}", message, line)
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if !trimmed.starts_with('#') {
+                return;
+            }
+            self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.position += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        self.skip_trivia();
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", token)))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, String> {
+        self.skip_trivia();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if len == 0 || rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(self.error("expected an identifier"));
+        }
+        self.position += len;
+        Ok(rest[..len].to_owned())
+    }
+
+    /// Reads the body of a function up to its closing brace, which is consumed but not included.
+    ///
+    /// Braces in strings and comments are skipped over, so that they don't end the body.
+    fn body(&mut self) -> Option<String> {
+        let rest = self.rest();
+        let mut chars = rest.char_indices().peekable();
+        let mut depth = 0;
+        let mut previous = ' ';
+
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => {
+                    self.position += index + 1;
+                    return Some(rest[..index].to_owned());
+                }
+                '}' => depth -= 1,
+                '#' => {
+                    chars.by_ref().find(|(_, c)| *c == '\n');
+                }
+                '"' => skip_string(&mut chars, '"')?,
+                // Raw strings, regular expressions and timestamps are quoted with `'`.
+                's' | 'r' | 't'
+                    if !(previous.is_ascii_alphanumeric() || previous == '_')
+                        && matches!(chars.peek(), Some((_, '\''))) =>
+                {
+                    chars.next();
+                    skip_string(&mut chars, '\'')?;
+                }
+                _ => (),
+            }
+            previous = c;
+        }
+
+        None
+    }
+}
+
+/// Skips past the closing quote of a string, or fails if there is none.
+fn skip_string(chars: &mut impl Iterator<Item = (usize, char)>, quote: char) -> Option<()> {
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next()?;
+            }
+            c if c == quote => return Some(()),
+            _ => (),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::{Runtime, TimeZone};
+
+    use super::*;
+
+    #[test]
+    fn parses_definitions() {
+        let source = r#"
+            # Comments and "strings" with braces are skipped: }
+            fn first() { "}" }
+
+            fn second(value, fallback,) {
+                if value == null { # {
+                    fallback
+                } else {
+                    s'{' + r'}'
+                }
+            }
+        "#;
+
+        let definitions = parse(source).unwrap();
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(
+            definitions[0],
+            Definition {
+                name: "first".to_owned(),
+                parameters: vec![],
+                body: r#" "}" "#.to_owned(),
+            }
+        );
+        assert_eq!(definitions[1].name, "second");
+        assert_eq!(definitions[1].parameters, vec!["value", "fallback"]);
+        assert!(definitions[1]
+            .body
+            .trim_end()
+            .ends_with("s'{' + r'}'\n                }"));
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        assert_eq!(
+            parse("fn first() {}\n.foo = 1").unwrap_err(),
+            "expected an identifier on line 2"
+        );
+        assert_eq!(
+            parse("fn first(a, a) {}").unwrap_err(),
+            "duplicate parameter `a` on line 1"
+        );
+        assert_eq!(
+            parse("fn first() { \"}").unwrap_err(),
+            "unterminated body of function `first`"
+        );
+    }
+
+    #[test]
+    fn calls_library_functions() {
+        let library = r#"
+            fn or_default(value, default) {
+                if value == null { default } else { value }
+            }
+
+            fn pair(first, second) {
+                [or_default(first, "none"), or_default(second, "none")]
+            }
+        "#;
+
+        let mut functions = Vec::new();
+        load(library, &mut functions, CompileConfig::default).unwrap();
+        assert_eq!(
+            load(library, &mut functions, CompileConfig::default).unwrap_err(),
+            "function `or_default` is already defined"
+        );
+
+        let program = vrl::compile_with_state(
+            "pair(.message, null)",
+            &functions,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .unwrap()
+        .program;
+
+        let mut target = TargetValue {
+            value: vrl::value!({"message": "hello"}),
+            metadata: vrl::value!({}),
+            secrets: Secrets::new(),
+        };
+        let result = Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap();
+        assert_eq!(result, vrl::value!(["hello", "none"]));
+    }
+}
@@ -0,0 +1,86 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::dns::{self, Query};
+
+fn reverse_dns(value: Value, timeout: Option<Value>) -> Resolved {
+    let address = value
+        .try_bytes_utf8_lossy()?
+        .parse::<IpAddr>()
+        .map_err(|error| format!("unable to parse IP address: {}", error))?;
+    let timeout = dns::timeout(timeout)?;
+
+    let names = dns::resolve(Query::Reverse(address), timeout)
+        .map_err(|error| format!("unable to look up address: {}", error))?;
+
+    names
+        .into_iter()
+        .next()
+        .map(Value::from)
+        .ok_or_else(|| "no host name found for address".into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ReverseDns;
+
+impl Function for ReverseDns {
+    fn identifier(&self) -> &'static str {
+        "reverse_dns"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timeout",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        // The result depends on the DNS servers of the host.
+        &[]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timeout = arguments.optional("timeout");
+        Ok(ReverseDnsFn { value, timeout }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ReverseDnsFn {
+    value: Box<dyn Expression>,
+    timeout: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ReverseDnsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let timeout = self
+            .timeout
+            .as_ref()
+            .map(|timeout| timeout.resolve(ctx))
+            .transpose()?;
+        reverse_dns(value, timeout)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
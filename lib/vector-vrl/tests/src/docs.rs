@@ -20,6 +20,8 @@ const SKIP_FUNCTION_EXAMPLES: &[&str] = &[
     "get_hostname",
     "now",
     "get_env_var",
+    "dns_lookup",
+    "reverse_dns",
 ];
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,278 @@
+//! Authorization of the requests to the API, with the tokens configured in `api.tokens`.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery},
+    parser::types::{ExecutableDocument, OperationDefinition, OperationType, Selection},
+    ServerError, ServerResult, Variables,
+};
+use warp::{
+    http::{header, StatusCode},
+    reject::Reject,
+    Filter, Rejection, Reply,
+};
+
+use crate::config::api::{ApiScope, ApiToken};
+
+/// The tokens which can access the API, which are updated on reload.
+pub(super) type Tokens = Arc<RwLock<Vec<ApiToken>>>;
+
+/// The subscription tapping the events of the components.
+const TAP_FIELD: &str = "outputEventsByComponentIdPatterns";
+
+/// What the client of a request is allowed to do.
+#[derive(Clone, Debug)]
+pub(super) struct Grant {
+    scopes: HashSet<ApiScope>,
+    /// The patterns of the components that can be tapped, or `None` if any can.
+    tap_components: Option<Vec<glob::Pattern>>,
+}
+
+impl Grant {
+    /// Grants everything, for when no tokens are configured.
+    fn all() -> Self {
+        Self {
+            scopes: HashSet::from([ApiScope::Read, ApiScope::Tap, ApiScope::Mutate]),
+            tap_components: None,
+        }
+    }
+
+    fn from_token(token: &ApiToken) -> Self {
+        Self {
+            scopes: token.scopes.iter().copied().collect(),
+            // Invalid patterns don't match any component.
+            tap_components: Some(
+                token
+                    .tap_components
+                    .iter()
+                    .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                    .collect(),
+            ),
+        }
+    }
+
+    pub(super) fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    /// Returns the patterns of the components that can be tapped, or `None` if any can.
+    pub(super) fn tap_components(&self) -> Option<Vec<glob::Pattern>> {
+        self.tap_components.clone()
+    }
+}
+
+/// Returns the grant of the given token, which is required unless no tokens are configured.
+pub(super) fn authorize(tokens: &Tokens, token: Option<&str>) -> Option<Grant> {
+    let tokens = tokens.read().expect("tokens lock poisoned");
+    if tokens.is_empty() {
+        return Some(Grant::all());
+    }
+
+    let token = token?;
+    tokens
+        .iter()
+        .find(|configured| constant_time_eq(configured.token.inner(), token))
+        .map(Grant::from_token)
+}
+
+/// Returns the token of an `Authorization` header using the bearer scheme.
+pub(super) fn bearer_token(header: Option<&str>) -> Option<&str> {
+    header?
+        .split_once(' ')
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim())
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && openssl::memcmp::eq(a.as_bytes(), b.as_bytes())
+}
+
+#[derive(Debug)]
+struct Unauthorized;
+
+impl Reject for Unauthorized {}
+
+#[derive(Debug)]
+struct Forbidden;
+
+impl Reject for Forbidden {}
+
+/// Extracts the grant of the bearer token of the request, rejecting it if the token is invalid.
+pub(super) fn grant(tokens: Tokens) -> impl Filter<Extract = (Grant,), Error = Rejection> + Clone {
+    warp::header::optional::<String>(header::AUTHORIZATION.as_str()).and_then(
+        move |authorization: Option<String>| {
+            let grant = authorize(&tokens, bearer_token(authorization.as_deref()));
+            async move { grant.ok_or_else(|| warp::reject::custom(Unauthorized)) }
+        },
+    )
+}
+
+/// Rejects the request unless its bearer token grants the given scope.
+pub(super) fn scope(
+    tokens: Tokens,
+    scope: ApiScope,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    grant(tokens)
+        .and_then(move |grant: Grant| async move {
+            if grant.allows(scope) {
+                Ok(())
+            } else {
+                Err(warp::reject::custom(Forbidden))
+            }
+        })
+        .untuple_one()
+}
+
+/// Replies to the rejected requests with the status of their authorization failure, if any.
+pub(super) async fn recover(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<Unauthorized>().is_some() {
+        Ok(warp::reply::with_header(
+            warp::reply::with_status("Unauthorized.", StatusCode::UNAUTHORIZED),
+            header::WWW_AUTHENTICATE,
+            "Bearer",
+        )
+        .into_response())
+    } else if rejection.find::<Forbidden>().is_some() {
+        Ok(warp::reply::with_status("Forbidden.", StatusCode::FORBIDDEN).into_response())
+    } else {
+        Err(rejection)
+    }
+}
+
+/// Rejects the GraphQL operations that the grant of their request doesn't allow.
+pub(super) struct Authorization;
+
+impl ExtensionFactory for Authorization {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(AuthorizationExtension)
+    }
+}
+
+struct AuthorizationExtension;
+
+#[async_trait::async_trait]
+impl Extension for AuthorizationExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let grant = ctx
+            .data_opt::<Grant>()
+            .ok_or_else(|| ServerError::new("Unauthorized.", None))?;
+
+        for (_, operation) in document.operations.iter() {
+            for scope in required_scopes(&operation.node) {
+                if !grant.allows(scope) {
+                    return Err(ServerError::new(
+                        format!("The token doesn't have the `{}` scope.", scope.as_str()),
+                        Some(operation.pos),
+                    ));
+                }
+            }
+        }
+        Ok(document)
+    }
+}
+
+/// Returns the scopes required to run an operation.
+///
+/// The health of Vector can be queried by any client, and tapping the events requires the `tap`
+/// scope rather than `read`, which is also checked when the subscription starts, for it to be
+/// required even if it's selected through a fragment.
+fn required_scopes(operation: &OperationDefinition) -> HashSet<ApiScope> {
+    if operation.ty == OperationType::Mutation {
+        return HashSet::from([ApiScope::Mutate]);
+    }
+
+    operation
+        .selection_set
+        .node
+        .items
+        .iter()
+        .filter_map(|selection| match &selection.node {
+            Selection::Field(field) => match field.node.name.node.as_str() {
+                "health" | "__typename" if operation.ty == OperationType::Query => None,
+                TAP_FIELD if operation.ty == OperationType::Subscription => Some(ApiScope::Tap),
+                _ => Some(ApiScope::Read),
+            },
+            Selection::FragmentSpread(_) | Selection::InlineFragment(_) => Some(ApiScope::Read),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::parser::parse_query;
+
+    use super::*;
+
+    fn token(scopes: Vec<ApiScope>) -> ApiToken {
+        ApiToken {
+            token: "secret".to_owned().into(),
+            scopes,
+            tap_components: vec!["app_*".to_owned()],
+        }
+    }
+
+    fn scopes(query: &str) -> HashSet<ApiScope> {
+        let document = parse_query(query).unwrap();
+        let (_, operation) = document.operations.iter().next().unwrap();
+        required_scopes(&operation.node)
+    }
+
+    #[test]
+    fn authorizes_configured_tokens() {
+        let tokens: Tokens = Arc::new(RwLock::new(Vec::new()));
+        assert!(authorize(&tokens, None).unwrap().allows(ApiScope::Mutate));
+
+        *tokens.write().unwrap() = vec![token(vec![ApiScope::Tap])];
+        assert!(authorize(&tokens, None).is_none());
+        assert!(authorize(&tokens, Some("other")).is_none());
+
+        let grant = authorize(&tokens, Some("secret")).unwrap();
+        assert!(grant.allows(ApiScope::Tap));
+        assert!(!grant.allows(ApiScope::Read));
+        let patterns = grant.tap_components().unwrap();
+        assert!(patterns[0].matches("app_logs"));
+        assert!(!patterns[0].matches("billing"));
+    }
+
+    #[test]
+    fn parses_bearer_tokens() {
+        assert_eq!(bearer_token(Some("Bearer secret")), Some("secret"));
+        assert_eq!(bearer_token(Some("bearer secret")), Some("secret"));
+        assert_eq!(bearer_token(Some("Basic c2VjcmV0")), None);
+        assert_eq!(bearer_token(None), None);
+    }
+
+    #[test]
+    fn requires_operation_scopes() {
+        assert!(scopes("{ health }").is_empty());
+        assert_eq!(
+            scopes("{ health components { edges { node { componentId } } } }"),
+            HashSet::from([ApiScope::Read])
+        );
+        assert_eq!(
+            scopes("mutation { drain { ok } }"),
+            HashSet::from([ApiScope::Mutate])
+        );
+        assert_eq!(
+            scopes(
+                r#"subscription { outputEventsByComponentIdPatterns(outputsPatterns: ["*"]) { __typename } }"#
+            ),
+            HashSet::from([ApiScope::Tap])
+        );
+        assert_eq!(
+            scopes("subscription { heartbeat { utc } }"),
+            HashSet::from([ApiScope::Read])
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use std::sync::{
     atomic::{self, AtomicBool},
-    Arc,
+    Arc, RwLock,
 };
 
 use serde_json::json;
 use warp::{reply::json, Rejection, Reply};
 
+use super::readiness;
+use crate::config::api::ReadinessOptions;
+
 // Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
 // when shutting down
 pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
@@ -21,3 +24,28 @@ pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejec
         ))
     }
 }
+
+// Readiness handler, responds with '{ ready: true }' when running and none of the configured
+// readiness conditions hold, and with '{ ready: false, reasons: [...] }' otherwise
+pub(super) async fn ready(
+    running: Arc<AtomicBool>,
+    options: Arc<RwLock<ReadinessOptions>>,
+) -> Result<impl Reply, Rejection> {
+    let reasons = if running.load(atomic::Ordering::Relaxed) {
+        readiness::unready_reasons(&options.read().expect("readiness lock poisoned"))
+    } else {
+        vec!["Vector is shutting down.".to_owned()]
+    };
+
+    if reasons.is_empty() {
+        Ok(warp::reply::with_status(
+            json(&json!({"ready": true})),
+            warp::http::StatusCode::OK,
+        ))
+    } else {
+        Ok(warp::reply::with_status(
+            json(&json!({"ready": false, "reasons": reasons})),
+            warp::http::StatusCode::SERVICE_UNAVAILABLE,
+        ))
+    }
+}
@@ -1,5 +1,9 @@
 #![allow(missing_docs)]
+mod auth;
 mod handler;
+#[cfg(unix)]
+mod profile;
+mod readiness;
 mod schema;
 mod server;
 pub mod tap;
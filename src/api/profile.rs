@@ -0,0 +1,225 @@
+//! The CPU profile of the components, in the pprof format.
+//!
+//! Each sample is the CPU time used by a component over the profiled period, with a stack made of
+//! the kind, the type, and the ID of the component, so the flame graph of the profile shows which
+//! components the CPU time is spent in. The CPU time used outside of the components, such as by the
+//! API or the runtime, is attributed to `(other)`.
+use std::{
+    collections::HashMap,
+    io::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use flate2::{write::GzEncoder, Compression};
+use prost::Message;
+use serde::Deserialize;
+use warp::{http::Response, Rejection, Reply};
+
+use crate::internal_telemetry::cpu::{self, Component};
+
+mod proto {
+    include!(concat!(env!("OUT_DIR"), "/perftools.profiles.rs"));
+}
+
+const DEFAULT_SECONDS: u64 = 30;
+const MAX_SECONDS: u64 = 300;
+
+#[derive(Deserialize)]
+pub(super) struct ProfileQuery {
+    seconds: Option<u64>,
+}
+
+// Profile handler, responds with the gzipped pprof profile of the CPU time used by the components
+// over the requested number of seconds
+pub(super) async fn profile(query: ProfileQuery) -> Result<impl Reply, Rejection> {
+    if !cpu::is_enabled() {
+        return Err(warp::reject::not_found());
+    }
+    let duration = Duration::from_secs(query.seconds.unwrap_or(DEFAULT_SECONDS).min(MAX_SECONDS));
+
+    let start = SystemTime::now();
+    let start_components = cpu::component_cpu_times();
+    let start_process = cpu::process_cpu_time();
+    tokio::time::sleep(duration).await;
+    let components = cpu::component_cpu_times();
+    let process = cpu::process_cpu_time().saturating_sub(start_process);
+
+    let samples = components
+        .into_iter()
+        .map(|(component, time)| {
+            let start_time = start_components
+                .iter()
+                .find(|(started, _)| *started == component)
+                .map_or(Duration::ZERO, |(_, time)| *time);
+            (component, time.saturating_sub(start_time))
+        })
+        .filter(|(_, time)| !time.is_zero())
+        .collect::<Vec<_>>();
+
+    let profile = encode(start, duration, process, &samples);
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let body = encoder
+        .write_all(&profile.encode_to_vec())
+        .and_then(|_| encoder.finish())
+        .map_err(|_| warp::reject::reject())?;
+
+    Ok(Response::builder()
+        .header("content-type", "application/octet-stream")
+        .header(
+            "content-disposition",
+            "attachment; filename=\"vector.pb.gz\"",
+        )
+        .body(body))
+}
+
+/// The strings and functions of a profile, deduplicated.
+#[derive(Default)]
+struct Builder {
+    strings: Vec<String>,
+    string_ids: HashMap<String, i64>,
+    functions: Vec<proto::Function>,
+    locations: Vec<proto::Location>,
+    location_ids: HashMap<String, u64>,
+}
+
+impl Builder {
+    fn string(&mut self, string: &str) -> i64 {
+        if let Some(id) = self.string_ids.get(string) {
+            return *id;
+        }
+        let id = self.strings.len() as i64;
+        self.strings.push(string.to_owned());
+        self.string_ids.insert(string.to_owned(), id);
+        id
+    }
+
+    /// Returns the location of a frame, with a function of its own.
+    fn location(&mut self, frame: &str) -> u64 {
+        if let Some(id) = self.location_ids.get(frame) {
+            return *id;
+        }
+        // The IDs start at 1, as 0 is reserved.
+        let id = self.locations.len() as u64 + 1;
+        let name = self.string(frame);
+        self.functions.push(proto::Function { id, name });
+        self.locations.push(proto::Location {
+            id,
+            line: vec![proto::Line { function_id: id }],
+        });
+        self.location_ids.insert(frame.to_owned(), id);
+        id
+    }
+}
+
+fn encode(
+    start: SystemTime,
+    duration: Duration,
+    process: Duration,
+    samples: &[(Component, Duration)],
+) -> proto::Profile {
+    let mut builder = Builder::default();
+    builder.string("");
+    let value_type = proto::ValueType {
+        r#type: builder.string("cpu"),
+        unit: builder.string("nanoseconds"),
+    };
+
+    let mut sample = Vec::with_capacity(samples.len() + 1);
+    for (component, time) in samples {
+        // From the leaf to the root.
+        let location_id = vec![
+            builder.location(&component.id),
+            builder.location(&component.component_type),
+            builder.location(&component.kind),
+        ];
+        sample.push(proto::Sample {
+            location_id,
+            value: vec![time.as_nanos() as i64],
+        });
+    }
+
+    let attributed = samples.iter().map(|(_, time)| *time).sum::<Duration>();
+    let other = process.saturating_sub(attributed);
+    if !other.is_zero() {
+        sample.push(proto::Sample {
+            location_id: vec![builder.location("(other)")],
+            value: vec![other.as_nanos() as i64],
+        });
+    }
+
+    proto::Profile {
+        sample_type: vec![value_type.clone()],
+        sample,
+        location: builder.locations,
+        function: builder.functions,
+        string_table: builder.strings,
+        time_nanos: start
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_nanos() as i64),
+        duration_nanos: duration.as_nanos() as i64,
+        period_type: Some(value_type),
+        period: 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(kind: &str, id: &str, component_type: &str) -> Component {
+        Component {
+            kind: kind.to_owned(),
+            id: id.to_owned(),
+            component_type: component_type.to_owned(),
+        }
+    }
+
+    #[test]
+    fn encodes_the_components_as_stacks() {
+        let profile = encode(
+            UNIX_EPOCH,
+            Duration::from_secs(30),
+            Duration::from_secs(10),
+            &[
+                (
+                    component("transform", "parse", "remap"),
+                    Duration::from_secs(6),
+                ),
+                (
+                    component("sink", "es", "elasticsearch"),
+                    Duration::from_secs(3),
+                ),
+            ],
+        );
+
+        let name = |location_id: u64| {
+            let function = &profile.function[location_id as usize - 1];
+            profile.string_table[function.name as usize].as_str()
+        };
+        let stacks = profile
+            .sample
+            .iter()
+            .map(|sample| {
+                (
+                    sample
+                        .location_id
+                        .iter()
+                        .copied()
+                        .map(name)
+                        .collect::<Vec<_>>(),
+                    sample.value[0],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(profile.string_table[0], "");
+        assert_eq!(
+            stacks,
+            vec![
+                (vec!["parse", "remap", "transform"], 6_000_000_000),
+                (vec!["es", "elasticsearch", "sink"], 3_000_000_000),
+                (vec!["(other)"], 1_000_000_000),
+            ]
+        );
+    }
+}
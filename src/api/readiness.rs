@@ -0,0 +1,105 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::{
+    config::api::ReadinessOptions,
+    event::{Metric, MetricValue},
+    metrics::Controller,
+    topology::backpressure,
+};
+
+/// Returns the reasons why Vector isn't ready, according to the configured conditions.
+pub(super) fn unready_reasons(options: &ReadinessOptions) -> Vec<String> {
+    let mut reasons = Vec::new();
+
+    if let Some(max_percent) = options.max_buffer_usage_percent {
+        if let Ok(controller) = Controller::get() {
+            reasons.extend(full_buffers(&controller.capture_metrics(), max_percent));
+        }
+    }
+
+    if let Some(max_secs) = options.max_source_blocked_secs {
+        reasons.extend(
+            backpressure::blocked_outputs(Duration::from_secs(max_secs))
+                .into_iter()
+                .map(|(output, blocked)| {
+                    format!(
+                        "Source output {:?} has been blocked by backpressure for {} seconds.",
+                        output.to_string(),
+                        blocked.as_secs()
+                    )
+                }),
+        );
+    }
+
+    reasons
+}
+
+/// Returns the buffers limited by bytes which are fuller than `max_percent`, as reported by their
+/// metrics.
+fn full_buffers(metrics: &[Metric], max_percent: u8) -> Vec<String> {
+    // The byte size and the maximum byte size of the buffers, by component and stage.
+    let mut buffers = HashMap::<(String, String), (f64, Option<f64>)>::new();
+    for metric in metrics {
+        let value = match metric.value() {
+            MetricValue::Gauge { value } => *value,
+            _ => continue,
+        };
+        let buffer = (
+            metric.tag_value("component_id").unwrap_or_default(),
+            metric.tag_value("stage").unwrap_or_default(),
+        );
+        match metric.name() {
+            "buffer_byte_size" => buffers.entry(buffer).or_default().0 = value,
+            "buffer_max_byte_size" => buffers.entry(buffer).or_default().1 = Some(value),
+            _ => {}
+        }
+    }
+
+    let mut reasons = buffers
+        .into_iter()
+        .filter_map(|((component_id, stage), (byte_size, max_byte_size))| {
+            let percent = byte_size / max_byte_size.filter(|max| *max > 0.0)? * 100.0;
+            (percent > f64::from(max_percent)).then(|| {
+                format!(
+                    "Buffer stage {} of sink {:?} is {:.0}% full.",
+                    stage, component_id, percent
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    reasons.sort();
+    reasons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{metric::MetricTags, MetricKind};
+
+    fn gauge(name: &str, component_id: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value }).with_tags(Some(
+            MetricTags::from([
+                ("component_id".to_owned(), component_id.to_owned()),
+                ("stage".to_owned(), "0".to_owned()),
+            ]),
+        ))
+    }
+
+    #[test]
+    fn reports_full_buffers() {
+        let metrics = [
+            gauge("buffer_byte_size", "full", 90.0),
+            gauge("buffer_max_byte_size", "full", 100.0),
+            gauge("buffer_byte_size", "empty", 10.0),
+            gauge("buffer_max_byte_size", "empty", 100.0),
+            // Buffers which aren't limited by bytes are never full.
+            gauge("buffer_byte_size", "memory", 1000.0),
+        ];
+
+        assert_eq!(
+            full_buffers(&metrics, 80),
+            vec![r#"Buffer stage 0 of sink "full" is 90% full."#.to_owned()]
+        );
+        assert!(full_buffers(&metrics, 95).is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+use async_graphql::{Enum, Object};
+use chrono::{DateTime, Utc};
+
+use crate::sinks::util::adaptive_concurrency::{self, ControllerSnapshot};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum DecisionAction {
+    /// The concurrency limit was increased
+    Increase,
+    /// The concurrency limit was decreased
+    Decrease,
+    /// The concurrency limit was left unchanged
+    Hold,
+}
+
+impl From<adaptive_concurrency::DecisionAction> for DecisionAction {
+    fn from(action: adaptive_concurrency::DecisionAction) -> Self {
+        match action {
+            adaptive_concurrency::DecisionAction::Increase => Self::Increase,
+            adaptive_concurrency::DecisionAction::Decrease => Self::Decrease,
+            adaptive_concurrency::DecisionAction::Hold => Self::Hold,
+        }
+    }
+}
+
+pub struct AdaptiveConcurrencyDecision(adaptive_concurrency::Decision);
+
+#[Object]
+impl AdaptiveConcurrencyDecision {
+    /// Time at which the decision was made
+    pub async fn timestamp(&self) -> DateTime<Utc> {
+        self.0.timestamp
+    }
+
+    /// Adjustment made to the concurrency limit
+    pub async fn action(&self) -> DecisionAction {
+        self.0.action.into()
+    }
+
+    /// Concurrency limit after the decision
+    pub async fn concurrency_limit(&self) -> i64 {
+        self.0.concurrency_limit as i64
+    }
+
+    /// Whether the concurrency limit was reached since the previous decision
+    pub async fn reached_limit(&self) -> bool {
+        self.0.reached_limit
+    }
+
+    /// Whether responses signaled back pressure since the previous decision
+    pub async fn had_back_pressure(&self) -> bool {
+        self.0.had_back_pressure
+    }
+
+    /// Average RTT of the responses since the previous decision, in seconds
+    pub async fn current_rtt_seconds(&self) -> Option<f64> {
+        self.0.current_rtt.map(|rtt| rtt.as_secs_f64())
+    }
+
+    /// RTT the current RTT was compared with, in seconds, which is either the target RTT or the
+    /// average of past RTT measurements
+    pub async fn reference_rtt_seconds(&self) -> f64 {
+        self.0.reference_rtt.as_secs_f64()
+    }
+
+    /// Deviation of past RTT measurements, in seconds
+    pub async fn rtt_deviation_seconds(&self) -> f64 {
+        self.0.rtt_deviation.as_secs_f64()
+    }
+}
+
+pub struct AdaptiveConcurrencyController(ControllerSnapshot);
+
+impl From<ControllerSnapshot> for AdaptiveConcurrencyController {
+    fn from(snapshot: ControllerSnapshot) -> Self {
+        Self(snapshot)
+    }
+}
+
+#[Object]
+impl AdaptiveConcurrencyController {
+    /// Whether the concurrency limit is managed adaptively, rather than fixed by the `concurrency`
+    /// option of the sink
+    pub async fn adaptive(&self) -> bool {
+        self.0.adaptive
+    }
+
+    /// Current concurrency limit
+    pub async fn concurrency_limit(&self) -> i64 {
+        self.0.concurrency_limit as i64
+    }
+
+    /// Maximum concurrency limit
+    pub async fn max_concurrency_limit(&self) -> i64 {
+        self.0.max_concurrency_limit as i64
+    }
+
+    /// Number of requests currently in flight
+    pub async fn in_flight(&self) -> i64 {
+        self.0.in_flight as i64
+    }
+
+    /// Moving average of past RTT measurements, in seconds
+    pub async fn measured_rtt_seconds(&self) -> Option<f64> {
+        self.0.measured_rtt.map(|rtt| rtt.as_secs_f64())
+    }
+
+    /// Deviation of past RTT measurements, in seconds
+    pub async fn measured_rtt_deviation_seconds(&self) -> Option<f64> {
+        self.0.measured_rtt_deviation.map(|rtt| rtt.as_secs_f64())
+    }
+
+    /// Configured target RTT, in seconds
+    pub async fn target_rtt_seconds(&self) -> Option<f64> {
+        self.0.target_rtt.map(|rtt| rtt.as_secs_f64())
+    }
+
+    /// Most recent decisions, oldest first
+    pub async fn decisions(&self) -> Vec<AdaptiveConcurrencyDecision> {
+        self.0
+            .decisions
+            .iter()
+            .cloned()
+            .map(AdaptiveConcurrencyDecision)
+            .collect()
+    }
+}
@@ -1,3 +1,5 @@
+pub mod adaptive_concurrency;
+pub mod request_log;
 pub mod sink;
 pub mod source;
 pub mod state;
@@ -0,0 +1,85 @@
+use async_graphql::{Object, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use crate::http::request_log;
+
+/// Header of a failed request or of its response
+#[derive(SimpleObject)]
+pub struct HttpHeader {
+    /// Name of the header
+    name: String,
+
+    /// Value of the header, or `[REDACTED]` if it is sensitive
+    value: String,
+}
+
+fn headers(headers: &[(String, String)]) -> Vec<HttpHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| HttpHeader {
+            name: name.clone(),
+            value: value.clone(),
+        })
+        .collect()
+}
+
+pub struct FailedRequest(request_log::FailedRequest);
+
+impl From<request_log::FailedRequest> for FailedRequest {
+    fn from(failed: request_log::FailedRequest) -> Self {
+        Self(failed)
+    }
+}
+
+#[Object]
+impl FailedRequest {
+    /// Time at which the request failed
+    pub async fn timestamp(&self) -> DateTime<Utc> {
+        self.0.timestamp
+    }
+
+    /// Method of the request
+    pub async fn method(&self) -> &str {
+        &self.0.method
+    }
+
+    /// URI of the request, with its user information and sensitive query parameters redacted
+    pub async fn uri(&self) -> &str {
+        &self.0.uri
+    }
+
+    /// Headers of the request
+    pub async fn request_headers(&self) -> Vec<HttpHeader> {
+        headers(&self.0.request_headers)
+    }
+
+    /// Body of the request, truncated and redacted
+    pub async fn request_body(&self) -> &str {
+        &self.0.request_body
+    }
+
+    /// Status of the response, if one was received
+    pub async fn status(&self) -> Option<i32> {
+        self.0.status.map(Into::into)
+    }
+
+    /// Headers of the response, if one was received
+    pub async fn response_headers(&self) -> Vec<HttpHeader> {
+        headers(&self.0.response_headers)
+    }
+
+    /// Body of the response, truncated and redacted, if one was received
+    pub async fn response_body(&self) -> Option<&str> {
+        self.0.response_body.as_deref()
+    }
+
+    /// Error that prevented a response from being received, if any
+    pub async fn error(&self) -> Option<&str> {
+        self.0.error.as_deref()
+    }
+
+    /// Time it took to send the request and receive the response or error, in seconds
+    pub async fn roundtrip_seconds(&self) -> f64 {
+        self.0.roundtrip.as_secs_f64()
+    }
+}
@@ -2,7 +2,10 @@ use std::cmp;
 
 use async_graphql::{Enum, InputObject, Object};
 
-use super::{source, state, transform, Component};
+use super::{
+    adaptive_concurrency::AdaptiveConcurrencyController, request_log::FailedRequest, source, state,
+    transform, Component,
+};
 use crate::{
     api::schema::{
         filter,
@@ -11,6 +14,8 @@ use crate::{
     },
     config::{ComponentKey, Inputs, OutputId},
     filter_check,
+    http::request_log,
+    sinks::util::adaptive_concurrency,
 };
 
 #[derive(Debug, Clone)]
@@ -118,6 +123,40 @@ impl Sink {
         metrics::by_component_key(self.get_component_key())
             .into_sink_metrics(self.get_component_type())
     }
+
+    /// Sink history of the received and sent events, and errors, over the last `minutes`, in 10
+    /// second buckets, oldest first
+    pub async fn throughput_history(
+        &self,
+        #[graphql(default = 5, validator(minimum = 1, maximum = 15))] minutes: i32,
+    ) -> Vec<metrics::ThroughputBucket> {
+        metrics::component_history(self.get_component_key(), minutes)
+    }
+
+    /// Current depth of the buffer of the sink
+    pub async fn buffer(&self) -> Option<metrics::ComponentBufferDepth> {
+        metrics::buffer_depths(&metrics::by_component_key(self.get_component_key()))
+            .into_iter()
+            .next()
+    }
+
+    /// Adaptive request concurrency controllers of the sink, one for each endpoint or partition
+    /// it sends requests to
+    pub async fn adaptive_concurrency(&self) -> Vec<AdaptiveConcurrencyController> {
+        adaptive_concurrency::controllers(self.get_component_key())
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Last requests of the sink that failed, or were answered with an error status, oldest
+    /// first. Requests are only recorded when the `request_log` option of the sink is set
+    pub async fn failed_requests(&self) -> Vec<FailedRequest> {
+        request_log::failed_requests(self.get_component_key())
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -138,6 +138,15 @@ impl Source {
         metrics::by_component_key(&self.0.component_key)
             .into_source_metrics(self.get_component_type())
     }
+
+    /// Source history of the received and sent events, and errors, over the last `minutes`, in 10
+    /// second buckets, oldest first
+    pub async fn throughput_history(
+        &self,
+        #[graphql(default = 5, validator(minimum = 1, maximum = 15))] minutes: i32,
+    ) -> Vec<metrics::ThroughputBucket> {
+        metrics::component_history(&self.0.component_key, minutes)
+    }
 }
 
 #[derive(Default, InputObject)]
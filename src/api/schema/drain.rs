@@ -0,0 +1,80 @@
+use async_graphql::{Context, Object, SimpleObject};
+use tokio::time::Duration;
+
+use crate::{
+    signal::{SignalTo, SignalTx},
+    topology::drain,
+};
+
+/// Progress of the drain of Vector, or of its shutdown
+#[derive(SimpleObject)]
+pub struct DrainStatus {
+    /// Seconds since the drain started
+    elapsed_secs: i64,
+
+    /// Seconds left until the remaining components are killed, or null once the deadline passed
+    remaining_secs: Option<i64>,
+
+    /// Number of components running when the drain started
+    total_components: i64,
+
+    /// IDs of the components which are still running, flushing their events
+    remaining_components: Vec<String>,
+}
+
+impl From<drain::DrainStatus> for DrainStatus {
+    fn from(status: drain::DrainStatus) -> Self {
+        Self {
+            elapsed_secs: i64::try_from(status.elapsed.as_secs()).unwrap_or(i64::MAX),
+            remaining_secs: status
+                .time_remaining
+                .map(|remaining| i64::try_from(remaining.as_secs()).unwrap_or(i64::MAX)),
+            total_components: i64::try_from(status.total_components).unwrap_or(i64::MAX),
+            remaining_components: status
+                .remaining_components
+                .into_iter()
+                .map(|key| key.id().to_owned())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DrainQuery;
+
+#[Object]
+impl DrainQuery {
+    /// Progress of the drain, or of the shutdown, of Vector. Null while Vector is running
+    async fn drain_status(&self) -> Option<DrainStatus> {
+        drain::status().map(Into::into)
+    }
+}
+
+#[derive(Default)]
+pub struct DrainMutation;
+
+#[Object]
+impl DrainMutation {
+    /// Drains Vector, then exits. The sources stop accepting new events, and the events in
+    /// flight are flushed by the sinks. The components still running once the deadline passed
+    /// are killed. The deadline defaults to the graceful shutdown limit. Returns `true` once
+    /// the drain is requested; its progress is reported by `drainStatus`
+    async fn drain(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(minimum = 1))] deadline_secs: Option<i64>,
+    ) -> async_graphql::Result<bool> {
+        // Any signal sent while stopping makes Vector quit immediately, instead of draining.
+        if drain::status().is_some() {
+            return Err("Vector is already stopping.".into());
+        }
+        let deadline = deadline_secs
+            .map(u64::try_from)
+            .transpose()?
+            .map(Duration::from_secs);
+        ctx.data::<SignalTx>()?
+            .send(SignalTo::Drain(deadline))
+            .map_err(|_| "Vector is stopping.")?;
+        Ok(true)
+    }
+}
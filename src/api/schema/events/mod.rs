@@ -15,7 +15,12 @@ use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::{auth::Grant, tap::TapController},
+    conditions::{Condition, ConditionalConfig, VrlConfig},
+    config::api::ApiScope,
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
 /// flowing into (for_inputs) or out of (for_outputs) specified components
@@ -23,6 +28,8 @@ use crate::{api::tap::TapController, topology::WatchRx};
 pub struct TapPatterns {
     pub for_outputs: HashSet<String>,
     pub for_inputs: HashSet<String>,
+    /// The components that can be tapped, or `None` if any can.
+    pub permitted: Option<Vec<glob::Pattern>>,
 }
 
 impl TapPatterns {
@@ -30,9 +37,25 @@ impl TapPatterns {
         Self {
             for_outputs,
             for_inputs,
+            permitted: None,
         }
     }
 
+    /// Restricts the components that can be tapped to those matching the given patterns.
+    pub fn with_permitted(mut self, permitted: Option<Vec<glob::Pattern>>) -> Self {
+        self.permitted = permitted;
+        self
+    }
+
+    /// Returns whether the events of a component can be tapped.
+    pub fn permits(&self, component_id: &str) -> bool {
+        self.permitted.as_ref().map_or(true, |permitted| {
+            permitted
+                .iter()
+                .any(|pattern| pattern.matches(component_id))
+        })
+    }
+
     /// Get all user-specified patterns
     pub fn all_patterns(&self) -> HashSet<String> {
         self.for_outputs
@@ -56,24 +79,54 @@ impl EventsSubscription {
         inputs_patterns: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+        condition: Option<String>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
-        let patterns = TapPatterns {
-            for_outputs: outputs_patterns.into_iter().collect(),
-            for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
+        // Only the components permitted by the token of the request can be tapped.
+        let permitted = match ctx.data_opt::<Grant>() {
+            Some(grant) if grant.allows(ApiScope::Tap) => grant.tap_components(),
+            Some(_) => return Err("The token doesn't have the `tap` scope.".into()),
+            None => None,
         };
+
+        let patterns = TapPatterns::new(
+            outputs_patterns.into_iter().collect(),
+            inputs_patterns.unwrap_or_default().into_iter().collect(),
+        )
+        .with_permitted(permitted);
+
+        // The condition is compiled upfront, so that invalid expressions are reported to the client.
+        let condition = condition
+            .map(|source| {
+                VrlConfig {
+                    source,
+                    ..Default::default()
+                }
+                .build(&Default::default())
+            })
+            .transpose()
+            .map_err(|error| format!("Invalid condition: {}", error))?;
+
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            condition,
+            interval as u64,
+            limit as usize,
+        ))
     }
 }
 
 /// Creates an events stream based on component ids, and a provided interval. Will emit
 /// control messages that bubble up the application if the sink goes away. The stream contains
-/// all matching events; filtering should be done at the caller level.
+/// all matching events that satisfy the condition, if any; further filtering should be done at
+/// the caller level.
 pub(crate) fn create_events_stream(
     watch_rx: WatchRx,
     patterns: TapPatterns,
+    condition: Option<Condition>,
     interval: u64,
     limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
@@ -91,7 +144,7 @@ pub(crate) fn create_events_stream(
     tokio::spawn(async move {
         // Create a tap controller. When this drops out of scope, clean up will be performed on the
         // event handlers and topology observation that the tap controller provides.
-        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns);
+        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns, condition);
 
         // A tick interval to represent when to 'cut' the results back to the client.
         let mut interval = time::interval(time::Duration::from_millis(interval));
@@ -0,0 +1,78 @@
+use async_graphql::{Object, SimpleObject};
+
+use crate::memory_budget;
+
+/// The memory budget shared by the sources
+#[derive(SimpleObject)]
+pub struct MemoryBudget {
+    /// Size of the budget, in bytes
+    max_bytes: i64,
+
+    /// Bytes of the events in flight, counted against the budget
+    used_bytes: i64,
+
+    /// Policy applied to the events of the sources once the budget is used up
+    policy: String,
+}
+
+impl From<memory_budget::BudgetUsage> for MemoryBudget {
+    fn from(usage: memory_budget::BudgetUsage) -> Self {
+        Self {
+            max_bytes: i64::try_from(usage.max_bytes).unwrap_or(i64::MAX),
+            used_bytes: i64::try_from(usage.used_bytes).unwrap_or(i64::MAX),
+            policy: usage.policy.as_str().to_owned(),
+        }
+    }
+}
+
+/// Approximate memory used by a running component
+#[derive(SimpleObject)]
+pub struct ComponentMemory {
+    /// Component ID
+    component_id: String,
+
+    /// Component kind, such as `source`
+    component_kind: String,
+
+    /// Bytes of the events sent by a source which aren't delivered yet, wherever they are,
+    /// including in the buffers
+    in_flight_bytes: i64,
+
+    /// Bytes of the events batched by a sink, or in the requests it's sending
+    batched_bytes: i64,
+
+    /// Bytes of the state held by a transform, such as the metrics it aggregates
+    state_bytes: i64,
+}
+
+impl From<memory_budget::ComponentUsage> for ComponentMemory {
+    fn from(usage: memory_budget::ComponentUsage) -> Self {
+        Self {
+            component_id: usage.component_key.id().to_owned(),
+            component_kind: usage.kind.to_owned(),
+            in_flight_bytes: i64::try_from(usage.in_flight_bytes).unwrap_or(i64::MAX),
+            batched_bytes: i64::try_from(usage.batched_bytes).unwrap_or(i64::MAX),
+            state_bytes: i64::try_from(usage.state_bytes).unwrap_or(i64::MAX),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MemoryQuery;
+
+#[Object]
+impl MemoryQuery {
+    /// Memory budget shared by the sources, if one is configured
+    async fn memory_budget(&self) -> Option<MemoryBudget> {
+        memory_budget::budget_usage().map(Into::into)
+    }
+
+    /// Approximate memory used by the running components. The events are only accounted for
+    /// while a memory budget is configured
+    async fn component_memory(&self) -> Vec<ComponentMemory> {
+        memory_budget::component_usage()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+}
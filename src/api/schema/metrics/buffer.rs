@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use async_graphql::Object;
+
+use crate::{
+    config::ComponentKey,
+    event::{Metric, MetricValue},
+};
+
+/// The depth of a stage of the buffer of a component.
+#[derive(Clone, Debug, Default)]
+pub struct BufferStageDepth {
+    stage: i64,
+    events: f64,
+    byte_size: f64,
+    max_events: Option<f64>,
+    max_byte_size: Option<f64>,
+}
+
+#[Object]
+impl BufferStageDepth {
+    /// Index of the stage, the first one being `0`
+    pub async fn stage(&self) -> i64 {
+        self.stage
+    }
+
+    /// Events currently held by the stage
+    pub async fn events(&self) -> i64 {
+        self.events as i64
+    }
+
+    /// Bytes currently held by the stage
+    pub async fn byte_size(&self) -> i64 {
+        self.byte_size as i64
+    }
+
+    /// Events the stage can hold, if it's limited by events
+    pub async fn max_events(&self) -> Option<i64> {
+        self.max_events.map(|max| max as i64)
+    }
+
+    /// Bytes the stage can hold, if it's limited by bytes
+    pub async fn max_byte_size(&self) -> Option<i64> {
+        self.max_byte_size.map(|max| max as i64)
+    }
+}
+
+/// The depth of the buffer of a component.
+#[derive(Clone, Debug)]
+pub struct ComponentBufferDepth {
+    component_key: ComponentKey,
+    buffer_type: Option<String>,
+    stages: Vec<BufferStageDepth>,
+}
+
+#[Object]
+impl ComponentBufferDepth {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Type of the first stage of the buffer, either `memory`, `disk` or `tiered`
+    async fn buffer_type(&self) -> Option<&str> {
+        self.buffer_type.as_deref()
+    }
+
+    /// Events currently held by the buffer, across its stages
+    async fn events(&self) -> i64 {
+        self.stages.iter().map(|stage| stage.events).sum::<f64>() as i64
+    }
+
+    /// Bytes currently held by the buffer, across its stages
+    async fn byte_size(&self) -> i64 {
+        self.stages.iter().map(|stage| stage.byte_size).sum::<f64>() as i64
+    }
+
+    /// Stages of the buffer, in the order events go through them
+    async fn stages(&self) -> &[BufferStageDepth] {
+        &self.stages
+    }
+}
+
+/// Returns the depth of the buffers reported by the given metrics, sorted by component.
+pub fn buffer_depths(metrics: &[Metric]) -> Vec<ComponentBufferDepth> {
+    let mut buffers =
+        BTreeMap::<ComponentKey, (Option<String>, BTreeMap<i64, BufferStageDepth>)>::new();
+
+    for metric in metrics.iter().filter(|m| m.name().starts_with("buffer_")) {
+        let value = match metric.value() {
+            MetricValue::Gauge { value } => *value,
+            _ => continue,
+        };
+        let (component_id, stage) = match (
+            metric.tag_value("component_id"),
+            metric
+                .tag_value("stage")
+                .and_then(|stage| stage.parse::<i64>().ok()),
+        ) {
+            (Some(component_id), Some(stage)) => (component_id, stage),
+            _ => continue,
+        };
+
+        let (buffer_type, stages) = buffers.entry(ComponentKey::from(component_id)).or_default();
+        if buffer_type.is_none() {
+            *buffer_type = metric.tag_value("buffer_type");
+        }
+        let depth = stages.entry(stage).or_insert_with(|| BufferStageDepth {
+            stage,
+            ..Default::default()
+        });
+        match metric.name() {
+            "buffer_events" => depth.events += value,
+            "buffer_byte_size" => depth.byte_size += value,
+            "buffer_max_event_size" => depth.max_events = Some(value),
+            "buffer_max_byte_size" => depth.max_byte_size = Some(value),
+            _ => {}
+        }
+    }
+
+    buffers
+        .into_iter()
+        .map(
+            |(component_key, (buffer_type, stages))| ComponentBufferDepth {
+                component_key,
+                buffer_type,
+                stages: stages.into_values().collect(),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::metric_tags;
+
+    use super::*;
+    use crate::event::metric::MetricKind;
+
+    fn gauge(name: &str, component_id: &str, stage: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value }).with_tags(Some(
+            metric_tags!(
+                "component_id" => component_id,
+                "stage" => stage,
+                "buffer_type" => "tiered",
+            ),
+        ))
+    }
+
+    #[test]
+    fn sums_buffer_stages() {
+        let depths = buffer_depths(&[
+            gauge("buffer_events", "out", "0", 100.0),
+            gauge("buffer_byte_size", "out", "0", 2048.0),
+            gauge("buffer_max_event_size", "out", "0", 500.0),
+            gauge("buffer_events", "out", "1", 3.0),
+            gauge("buffer_byte_size", "out", "1", 64.0),
+            gauge("buffer_max_byte_size", "out", "1", 1024.0),
+            gauge("component_allocated_bytes", "in", "0", 1.0),
+        ]);
+
+        assert_eq!(depths.len(), 1);
+        let out = &depths[0];
+        assert_eq!(out.component_key, ComponentKey::from("out"));
+        assert_eq!(out.buffer_type.as_deref(), Some("tiered"));
+        assert_eq!(out.stages.len(), 2);
+        assert_eq!(out.stages[0].events, 100.0);
+        assert_eq!(out.stages[0].max_events, Some(500.0));
+        assert_eq!(out.stages[1].byte_size, 64.0);
+        assert_eq!(out.stages[1].max_byte_size, Some(1024.0));
+    }
+}
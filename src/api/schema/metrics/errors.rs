@@ -69,3 +69,51 @@ impl ComponentErrorsTotal {
         ErrorsTotal::new(self.metric.clone())
     }
 }
+
+pub struct ComponentErrorsByType {
+    component_key: ComponentKey,
+    error_type: Option<String>,
+    stage: Option<String>,
+    errors_total: f64,
+}
+
+impl ComponentErrorsByType {
+    /// Returns a new `ComponentErrorsByType` struct, which is a GraphQL type. The errors of the
+    /// component are summed for each `error_type` and `stage` pair.
+    pub const fn new(
+        component_key: ComponentKey,
+        error_type: Option<String>,
+        stage: Option<String>,
+        errors_total: f64,
+    ) -> Self {
+        Self {
+            component_key,
+            error_type,
+            stage,
+            errors_total,
+        }
+    }
+}
+
+#[Object]
+impl ComponentErrorsByType {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Type of the errors, from the `error_type` tag
+    async fn error_type(&self) -> Option<&str> {
+        self.error_type.as_deref()
+    }
+
+    /// Stage of the component the errors occurred in, from the `stage` tag
+    async fn stage(&self) -> Option<&str> {
+        self.stage.as_deref()
+    }
+
+    /// Total error count
+    async fn errors_total(&self) -> f64 {
+        self.errors_total
+    }
+}
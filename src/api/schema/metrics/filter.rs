@@ -5,8 +5,8 @@ use tokio::time::Duration;
 use tokio_stream::{Stream, StreamExt};
 
 use super::{
-    filter_output_metric, EventsInTotal, EventsOutTotal, OutputThroughput, ProcessedBytesTotal,
-    ProcessedEventsTotal, ReceivedEventsTotal, SentEventsTotal,
+    filter_output_metric, ComponentErrorsByType, EventsInTotal, EventsOutTotal, OutputThroughput,
+    ProcessedBytesTotal, ProcessedEventsTotal, ReceivedEventsTotal, SentEventsTotal,
 };
 use crate::{
     config::ComponentKey,
@@ -187,6 +187,11 @@ pub fn get_all_metrics(interval: i32) -> impl Stream<Item = Vec<Metric>> {
     }
 }
 
+/// Returns all the current metrics.
+pub fn capture_metrics() -> Vec<Metric> {
+    get_controller().capture_metrics()
+}
+
 /// Return [`Vec<Metric>`] based on a component id tag.
 pub fn by_component_key(component_key: &ComponentKey) -> Vec<Metric> {
     get_controller()
@@ -397,6 +402,49 @@ pub fn component_sent_events_total_throughputs_with_outputs(
         .skip(1)
 }
 
+/// Returns a stream of `Vec<ComponentErrorsByType>`, summing the `component_errors_total` metric
+/// of each component by its `error_type` and `stage` tags. Like `component_counter_metrics`, only
+/// the totals that increased since the previous iteration are returned.
+pub fn component_errors_totals_by_type(
+    interval: i32,
+) -> impl Stream<Item = Vec<ComponentErrorsByType>> {
+    let mut cache = BTreeMap::new();
+
+    component_to_filtered_metrics(interval, &|m| m.name() == "component_errors_total").map(
+        move |map| {
+            map.into_iter()
+                .flat_map(|(id, metrics)| {
+                    metrics
+                        .into_iter()
+                        .filter_map(|m| match m.value() {
+                            MetricValue::Counter { value } => {
+                                Some(((m.tag_value("error_type"), m.tag_value("stage")), *value))
+                            }
+                            _ => None,
+                        })
+                        .fold(BTreeMap::new(), |mut totals, (labels, value)| {
+                            *totals.entry(labels).or_insert(0.00) += value;
+                            totals
+                        })
+                        .into_iter()
+                        .map(move |((error_type, stage), value)| {
+                            (id.clone(), error_type, stage, value)
+                        })
+                })
+                .filter(|(id, error_type, stage, value)| {
+                    cache
+                        .insert((id.clone(), error_type.clone(), stage.clone()), *value)
+                        .unwrap_or(0.00)
+                        < *value
+                })
+                .map(|(id, error_type, stage, value)| {
+                    ComponentErrorsByType::new(ComponentKey::from(id), error_type, stage, value)
+                })
+                .collect()
+        },
+    )
+}
+
 /// Returns a map of Component ID to list of metrics where metrics have been
 /// filtered by `filter_fn`
 fn component_to_filtered_metrics(
@@ -0,0 +1,268 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use tokio::time::{interval, Duration};
+
+use crate::{
+    config::ComponentKey,
+    event::{Metric, MetricValue},
+    metrics::Controller,
+};
+
+/// How long each bucket of the history spans, in seconds.
+const BUCKET_SECS: u64 = 10;
+
+/// How long the history is kept for, in minutes.
+const MAX_HISTORY_MINUTES: i32 = 15;
+
+const MAX_BUCKETS: usize = MAX_HISTORY_MINUTES as usize * 60 / BUCKET_SECS as usize;
+
+static HISTORY: Lazy<Mutex<History>> = Lazy::new(Default::default);
+
+/// The events and errors of a component over a bucket of its history.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ThroughputBucket {
+    timestamp: DateTime<Utc>,
+    received_events: f64,
+    sent_events: f64,
+    errors: f64,
+}
+
+#[Object]
+impl ThroughputBucket {
+    /// End of the bucket
+    pub async fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Length of the bucket, in seconds
+    pub async fn duration_secs(&self) -> i64 {
+        BUCKET_SECS as i64
+    }
+
+    /// Events received over the bucket
+    pub async fn received_events(&self) -> i64 {
+        self.received_events as i64
+    }
+
+    /// Events sent over the bucket
+    pub async fn sent_events(&self) -> i64 {
+        self.sent_events as i64
+    }
+
+    /// Errors over the bucket
+    pub async fn errors(&self) -> i64 {
+        self.errors as i64
+    }
+}
+
+pub struct ComponentThroughputHistory {
+    component_key: ComponentKey,
+    buckets: Vec<ThroughputBucket>,
+}
+
+#[Object]
+impl ComponentThroughputHistory {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Buckets of the history, oldest first
+    async fn buckets(&self) -> &[ThroughputBucket] {
+        &self.buckets
+    }
+}
+
+/// The totals of the counters of a component, as of the last recording.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Totals {
+    received_events: f64,
+    sent_events: f64,
+    errors: f64,
+}
+
+impl Totals {
+    fn add(&mut self, metric: &Metric) {
+        let value = match metric.value() {
+            MetricValue::Counter { value } => *value,
+            _ => return,
+        };
+        match metric.name() {
+            "component_received_events_total" => self.received_events += value,
+            "component_sent_events_total" => self.sent_events += value,
+            name if name.ends_with("_errors_total") => self.errors += value,
+            _ => {}
+        }
+    }
+}
+
+/// Returns the increase of a counter since it was last recorded, which is its whole value if it
+/// was reset in the meantime.
+fn increase(previous: f64, current: f64) -> f64 {
+    if current >= previous {
+        current - previous
+    } else {
+        current
+    }
+}
+
+#[derive(Default)]
+struct History {
+    totals: HashMap<ComponentKey, Totals>,
+    buckets: HashMap<ComponentKey, VecDeque<ThroughputBucket>>,
+}
+
+impl History {
+    /// Records the metrics captured at `timestamp` as the latest bucket of each component.
+    ///
+    /// The components which no longer report any metrics are forgotten.
+    fn record(&mut self, timestamp: DateTime<Utc>, metrics: &[Metric]) {
+        let mut totals = HashMap::<ComponentKey, Totals>::new();
+        for metric in metrics {
+            if let Some(component_id) = metric.tag_value("component_id") {
+                totals
+                    .entry(ComponentKey::from(component_id))
+                    .or_default()
+                    .add(metric);
+            }
+        }
+
+        self.buckets.retain(|key, _| totals.contains_key(key));
+        for (key, current) in &totals {
+            // The first recording of a component is only the baseline of its next bucket.
+            if let Some(previous) = self.totals.get(key) {
+                let buckets = self.buckets.entry(key.clone()).or_default();
+                if buckets.len() == MAX_BUCKETS {
+                    buckets.pop_front();
+                }
+                buckets.push_back(ThroughputBucket {
+                    timestamp,
+                    received_events: increase(previous.received_events, current.received_events),
+                    sent_events: increase(previous.sent_events, current.sent_events),
+                    errors: increase(previous.errors, current.errors),
+                });
+            }
+        }
+        self.totals = totals;
+    }
+
+    /// Returns the buckets of a component over the last `minutes`, oldest first.
+    fn last(&self, key: &ComponentKey, minutes: i32) -> Vec<ThroughputBucket> {
+        let count = minutes.clamp(0, MAX_HISTORY_MINUTES) as usize * 60 / BUCKET_SECS as usize;
+        self.buckets
+            .get(key)
+            .map(|buckets| {
+                buckets
+                    .iter()
+                    .skip(buckets.len().saturating_sub(count))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Records the history of the throughput of the components, for as long as the returned future
+/// is polled.
+pub async fn record_history() {
+    let controller = Controller::get().expect("Metrics system not initialized. Please report.");
+    let mut interval = interval(Duration::from_secs(BUCKET_SECS));
+
+    loop {
+        interval.tick().await;
+        let metrics = controller.capture_metrics();
+        HISTORY
+            .lock()
+            .expect("history lock poisoned")
+            .record(Utc::now(), &metrics);
+    }
+}
+
+/// Returns the history of a component over the last `minutes`, oldest first.
+pub fn component_history(key: &ComponentKey, minutes: i32) -> Vec<ThroughputBucket> {
+    HISTORY
+        .lock()
+        .expect("history lock poisoned")
+        .last(key, minutes)
+}
+
+/// Returns the history of every component over the last `minutes`.
+pub fn component_histories(minutes: i32) -> Vec<ComponentThroughputHistory> {
+    let history = HISTORY.lock().expect("history lock poisoned");
+    let mut histories = history
+        .buckets
+        .keys()
+        .map(|key| ComponentThroughputHistory {
+            component_key: key.clone(),
+            buckets: history.last(key, minutes),
+        })
+        .collect::<Vec<_>>();
+    histories.sort_by(|a, b| a.component_key.cmp(&b.component_key));
+    histories
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::metric_tags;
+
+    use super::*;
+    use crate::event::metric::MetricKind;
+
+    fn counter(name: &str, component_id: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value })
+            .with_tags(Some(metric_tags!("component_id" => component_id)))
+    }
+
+    #[test]
+    fn records_buckets() {
+        let mut history = History::default();
+        let key = ComponentKey::from("in");
+        let timestamp = |secs| Utc.timestamp_opt(secs, 0).unwrap();
+
+        history.record(
+            timestamp(0),
+            &[
+                counter("component_received_events_total", "in", 10.0),
+                counter("component_sent_events_total", "in", 5.0),
+            ],
+        );
+        assert!(history.last(&key, 5).is_empty());
+
+        history.record(
+            timestamp(10),
+            &[
+                counter("component_received_events_total", "in", 25.0),
+                counter("component_sent_events_total", "in", 2.0),
+                counter("component_errors_total", "in", 1.0),
+            ],
+        );
+        assert_eq!(
+            history.last(&key, 5),
+            vec![ThroughputBucket {
+                timestamp: timestamp(10),
+                received_events: 15.0,
+                sent_events: 2.0,
+                errors: 1.0,
+            }]
+        );
+
+        for secs in 2..100 {
+            history.record(
+                timestamp(secs * 10),
+                &[counter("component_received_events_total", "in", 25.0)],
+            );
+        }
+        assert_eq!(history.last(&key, 1).len(), 6);
+        assert_eq!(history.last(&key, 60).len(), MAX_BUCKETS);
+
+        history.record(timestamp(1000), &[]);
+        assert!(history.last(&key, 5).is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 mod allocated_bytes;
+mod buffer;
 mod errors;
 mod events_in;
 mod events_out;
 pub mod filter;
+mod history;
 mod output;
 mod processed_bytes;
 mod processed_events;
@@ -18,11 +20,16 @@ mod host;
 
 pub use allocated_bytes::{AllocatedBytes, ComponentAllocatedBytes};
 use async_graphql::{Interface, Object, Subscription};
+pub use buffer::{buffer_depths, BufferStageDepth, ComponentBufferDepth};
 use chrono::{DateTime, Utc};
-pub use errors::{ComponentErrorsTotal, ErrorsTotal};
+pub use errors::{ComponentErrorsByType, ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::EventsInTotal;
 pub use events_out::EventsOutTotal;
 pub use filter::*;
+pub use history::{
+    component_histories, component_history, record_history, ComponentThroughputHistory,
+    ThroughputBucket,
+};
 pub use output::*;
 pub use processed_bytes::{
     ComponentProcessedBytesThroughput, ComponentProcessedBytesTotal, ProcessedBytesTotal,
@@ -60,6 +67,30 @@ impl MetricsQuery {
     async fn host_metrics(&self) -> host::HostMetrics {
         host::HostMetrics::new()
     }
+
+    /// History of the received and sent events, and errors, of a component over the last
+    /// `minutes`, in 10 second buckets, oldest first
+    async fn component_throughput_history(
+        &self,
+        component_id: String,
+        #[graphql(default = 5, validator(minimum = 1, maximum = 15))] minutes: i32,
+    ) -> Vec<ThroughputBucket> {
+        component_history(&ComponentKey::from(component_id), minutes)
+    }
+
+    /// History of the received and sent events, and errors, of every component over the last
+    /// `minutes`, in 10 second buckets
+    async fn component_throughput_histories(
+        &self,
+        #[graphql(default = 5, validator(minimum = 1, maximum = 15))] minutes: i32,
+    ) -> Vec<ComponentThroughputHistory> {
+        component_histories(minutes)
+    }
+
+    /// Current depth of the memory and disk buffers of the components
+    async fn component_buffer_depths(&self) -> Vec<ComponentBufferDepth> {
+        buffer_depths(&capture_metrics())
+    }
 }
 
 #[derive(Default)]
@@ -309,6 +340,14 @@ impl MetricsSubscription {
             .map(|m| m.into_iter().map(ComponentAllocatedBytes::new).collect())
     }
 
+    /// Depth of the memory and disk buffers of the components over `interval`.
+    async fn component_buffer_depths(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentBufferDepth>> {
+        get_all_metrics(interval).map(|metrics| buffer_depths(&metrics))
+    }
+
     /// Component error metrics over `interval`.
     async fn component_errors_totals(
         &self,
@@ -318,6 +357,15 @@ impl MetricsSubscription {
             .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
     }
 
+    /// Component `component_errors_total` metrics over `interval`, broken down by the
+    /// `error_type` and `stage` of the errors.
+    async fn component_errors_totals_by_type(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentErrorsByType>> {
+        component_errors_totals_by_type(interval)
+    }
+
     /// All metrics.
     async fn metrics(
         &self,
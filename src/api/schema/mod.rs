@@ -1,13 +1,17 @@
 pub mod components;
+mod drain;
 pub mod events;
 pub mod filter;
 mod health;
+mod memory;
 mod meta;
 mod metrics;
+mod options;
 mod relay;
 pub mod sort;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
+pub(super) use metrics::record_history;
 
 #[derive(MergedObject, Default)]
 pub struct Query(
@@ -15,8 +19,14 @@ pub struct Query(
     components::ComponentsQuery,
     metrics::MetricsQuery,
     meta::MetaQuery,
+    options::OptionsQuery,
+    memory::MemoryQuery,
+    drain::DrainQuery,
 );
 
+#[derive(MergedObject, Default)]
+pub struct Mutation(options::OptionsMutation, drain::DrainMutation);
+
 #[derive(MergedSubscription, Default)]
 pub struct Subscription(
     health::HealthSubscription,
@@ -26,6 +36,10 @@ pub struct Subscription(
 );
 
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
 }
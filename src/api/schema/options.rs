@@ -0,0 +1,150 @@
+use async_graphql::{Enum, Object, SimpleObject};
+use tracing::level_filters::LevelFilter;
+
+use crate::{api::schema::components::state, config::ComponentKey, runtime_options};
+
+/// An option of a running component, which can be changed without reloading the configuration
+#[derive(SimpleObject)]
+pub struct ComponentOption {
+    /// Component ID
+    component_id: String,
+
+    /// Name of the option
+    option: String,
+
+    /// Current value of the option
+    value: i64,
+}
+
+impl From<runtime_options::OptionValue> for ComponentOption {
+    fn from(value: runtime_options::OptionValue) -> Self {
+        Self {
+            component_id: value.component_key.id().to_owned(),
+            option: value.option.to_owned(),
+            value: i64::try_from(value.value).unwrap_or(i64::MAX),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum LogLevel {
+    /// All the logs are emitted
+    Trace,
+    /// Debug logs and above are emitted
+    Debug,
+    /// Info logs and above are emitted
+    Info,
+    /// Warn logs and above are emitted
+    Warn,
+    /// Only error logs are emitted
+    Error,
+    /// No logs are emitted
+    Off,
+}
+
+impl From<LevelFilter> for LogLevel {
+    fn from(level: LevelFilter) -> Self {
+        match level {
+            LevelFilter::TRACE => Self::Trace,
+            LevelFilter::DEBUG => Self::Debug,
+            LevelFilter::INFO => Self::Info,
+            LevelFilter::WARN => Self::Warn,
+            LevelFilter::ERROR => Self::Error,
+            _ => Self::Off,
+        }
+    }
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => Self::TRACE,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Off => Self::OFF,
+        }
+    }
+}
+
+/// The level of the internal logs of a component
+#[derive(SimpleObject)]
+pub struct ComponentLogLevel {
+    /// Component ID
+    component_id: String,
+
+    /// Level of the logs of the component, or null for the level given on startup
+    level: Option<LogLevel>,
+}
+
+#[derive(Default)]
+pub struct OptionsQuery;
+
+#[Object]
+impl OptionsQuery {
+    /// Options of the running components that can be changed without reloading the configuration
+    async fn component_options(&self) -> Vec<ComponentOption> {
+        runtime_options::values()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Components whose logs have a level of their own, apart from the level given on startup
+    async fn component_log_levels(&self) -> Vec<ComponentLogLevel> {
+        runtime_options::log_levels()
+            .into_iter()
+            .map(|(component_key, level)| ComponentLogLevel {
+                component_id: component_key.id().to_owned(),
+                level: Some(level.into()),
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct OptionsMutation;
+
+#[Object]
+impl OptionsMutation {
+    /// Changes an option of a running component, without reloading the configuration. Only the
+    /// options listed by `componentOptions` can be changed. Changes are logged by Vector, and
+    /// are lost when the component is rebuilt, such as when the configuration is reloaded.
+    async fn set_component_option(
+        &self,
+        component_id: String,
+        option: String,
+        #[graphql(validator(minimum = 0))] value: i64,
+    ) -> async_graphql::Result<ComponentOption> {
+        let component_key = ComponentKey::from(component_id);
+        let value = u64::try_from(value)?;
+        runtime_options::set(&component_key, &option, value)?;
+
+        Ok(ComponentOption {
+            component_id: component_key.id().to_owned(),
+            option,
+            value: i64::try_from(value)?,
+        })
+    }
+
+    /// Changes the level of the internal logs of a running component, or resets it to the level
+    /// given on startup when null. Changes are logged by Vector, and are kept when the
+    /// configuration is reloaded.
+    async fn set_component_log_level(
+        &self,
+        component_id: String,
+        level: Option<LogLevel>,
+    ) -> async_graphql::Result<ComponentLogLevel> {
+        let component_key = ComponentKey::from(component_id);
+        if state::component_by_component_key(&component_key).is_none() {
+            return Err(format!("Component \"{}\" isn't running.", component_key).into());
+        }
+        runtime_options::set_log_level(&component_key, level.map(Into::into));
+
+        Ok(ComponentLogLevel {
+            component_id: component_key.id().to_owned(),
+            level,
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use std::{
     convert::Infallible,
     net::SocketAddr,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{atomic::AtomicBool, Arc, RwLock},
 };
 
 use async_graphql::{
@@ -12,16 +12,23 @@ use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
 use tokio::sync::oneshot;
 use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
 
-use super::{handler, schema, ShutdownTx};
+use super::{
+    auth::{self, Tokens},
+    handler, schema, ShutdownTx,
+};
 use crate::{
     config,
     internal_events::{SocketBindError, SocketMode},
+    signal::SignalTx,
     topology,
 };
 
 pub struct Server {
     _shutdown: ShutdownTx,
     addr: SocketAddr,
+    readiness: Arc<RwLock<config::api::ReadinessOptions>>,
+    tokens: Tokens,
+    history: tokio::task::JoinHandle<()>,
 }
 
 impl Server {
@@ -31,9 +38,20 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        signal_tx: SignalTx,
         runtime: &tokio::runtime::Runtime,
     ) -> crate::Result<Self> {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+        let readiness = Arc::new(RwLock::new(config.api.readiness));
+        let tokens = Arc::new(RwLock::new(config.api.tokens.clone()));
+        set_profiling(config.api.profiling);
+        let routes = make_routes(
+            config.api.playground,
+            watch_rx,
+            running,
+            Arc::clone(&readiness),
+            Arc::clone(&tokens),
+            signal_tx,
+        );
 
         let (_shutdown, rx) = oneshot::channel();
         // warp uses `tokio::spawn` and so needs us to enter the runtime context.
@@ -56,10 +74,18 @@ impl Server {
         // Update component schema with the config before starting the server.
         schema::components::update_config(config);
 
-        // Spawn the server in the background.
+        // Spawn the server in the background, along with the recording of the throughput history
+        // of the components.
         runtime.spawn(server);
+        let history = runtime.spawn(schema::record_history());
 
-        Ok(Self { _shutdown, addr })
+        Ok(Self {
+            _shutdown,
+            addr,
+            readiness,
+            tokens,
+            history,
+        })
     }
 
     /// Returns a copy of the SocketAddr that the server was started on.
@@ -71,35 +97,75 @@ impl Server {
     /// directly involve `self`, it provides a neater API to expose an internal implementation
     /// detail than exposing the function of the sub-mod directly.
     pub fn update_config(&self, config: &config::Config) {
+        *self.readiness.write().expect("readiness lock poisoned") = config.api.readiness;
+        *self.tokens.write().expect("tokens lock poisoned") = config.api.tokens.clone();
+        set_profiling(config.api.profiling);
         schema::components::update_config(config)
     }
 }
 
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.history.abort();
+    }
+}
+
+/// Starts or stops the accounting of the CPU time of the components, which the profile endpoint
+/// is only served while.
+fn set_profiling(enabled: bool) {
+    #[cfg(unix)]
+    crate::internal_telemetry::cpu::set_enabled(enabled);
+    #[cfg(not(unix))]
+    if enabled {
+        warn!(message = "CPU profiling is only supported on Unix, `api.profiling` ignored.");
+    }
+}
+
 fn make_routes(
     playground: bool,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    readiness: Arc<RwLock<config::api::ReadinessOptions>>,
+    tokens: Tokens,
+    signal_tx: SignalTx,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
     // Health.
     let health = warp::path("health")
-        .and(with_shared(running))
+        .and(with_shared(Arc::clone(&running)))
         .and_then(handler::health);
 
+    // Readiness.
+    let ready = warp::path("ready")
+        .and(with_shared(running))
+        .and(warp::any().map(move || Arc::clone(&readiness)))
+        .and_then(handler::ready);
+
     // 404.
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
     // GraphQL subscription handler. Creates a Warp WebSocket handler and for each connection,
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
-    // topology changes.
-    let graphql_subscription_handler =
-        warp::ws()
-            .and(graphql_protocol())
-            .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+    // topology changes. The schema holds the `SignalTx` channel sender, for the mutations which
+    // control Vector itself, such as draining it.
+    //
+    // The token of the connection is either sent in the `Authorization` header of the upgrade
+    // request, or in the `connection_init` payload since browsers can't set headers on WebSockets.
+    let subscription_signal_tx = signal_tx.clone();
+    let subscription_tokens = Arc::clone(&tokens);
+    let graphql_subscription_handler = warp::ws()
+        .and(graphql_protocol())
+        .and(warp::header::optional::<String>("authorization"))
+        .map(
+            move |ws: Ws, protocol: WebSocketProtocols, authorization: Option<String>| {
+                let schema = schema::build_schema()
+                    .data(subscription_signal_tx.clone())
+                    .extension(auth::Authorization)
+                    .finish();
                 let watch_tx = watch_tx.clone();
+                let tokens = Arc::clone(&subscription_tokens);
 
                 let reply = ws.on_upgrade(move |socket| {
                     let mut data = Data::default();
@@ -107,6 +173,16 @@ fn make_routes(
 
                     GraphQLWebSocket::new(socket, schema, protocol)
                         .with_data(data)
+                        .on_connection_init(move |payload| async move {
+                            let token = auth::bearer_token(authorization.as_deref())
+                                .or_else(|| payload.get("token").and_then(|token| token.as_str()));
+                            let grant = auth::authorize(&tokens, token)
+                                .ok_or_else(|| async_graphql::Error::new("Unauthorized."))?;
+
+                            let mut data = Data::default();
+                            data.insert(grant);
+                            Ok(data)
+                        })
                         .serve()
                 });
 
@@ -115,18 +191,28 @@ fn make_routes(
                     "Sec-WebSocket-Protocol",
                     protocol.sec_websocket_protocol(),
                 )
-            });
+            },
+        );
 
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
     // All other queries will fall back to the default HTTP handler.
-    let graphql_handler = warp::path("graphql").and(graphql_subscription_handler.or(
-        async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-            |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
-            },
-        ),
-    ));
+    let graphql_handler = warp::path("graphql").and(
+        graphql_subscription_handler.or(auth::grant(Arc::clone(&tokens))
+            .and(async_graphql_warp::graphql(
+                schema::build_schema()
+                    .data(signal_tx)
+                    .extension(auth::Authorization)
+                    .finish(),
+            ))
+            .and_then(
+                |grant: auth::Grant, (schema, request): (Schema<_, _, _>, Request)| async move {
+                    Ok::<_, Infallible>(GraphQLResponse::from(
+                        schema.execute(request.data(grant)).await,
+                    ))
+                },
+            )),
+    );
 
     // Provide a playground for executing GraphQL queries/mutations/subscriptions.
     let graphql_playground = if playground {
@@ -143,12 +229,31 @@ fn make_routes(
         not_found.boxed()
     };
 
-    // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
-    // cross-origin interaction with the Vector API.
+    // CPU profile of the components, in the pprof format. Not found unless the profiling is
+    // enabled, which can change on reload.
+    #[cfg(unix)]
+    let profile = warp::path!("debug" / "pprof" / "profile")
+        .and(warp::get())
+        .and(auth::scope(tokens, config::api::ApiScope::Read))
+        .and(warp::query())
+        .and_then(super::profile::profile)
+        .boxed();
+    #[cfg(not(unix))]
+    let profile = {
+        drop(tokens);
+        not_found.boxed()
+    };
+
+    // Wire up the health, readiness + GraphQL endpoints. Provides a permissive CORS policy to
+    // allow for cross-origin interaction with the Vector API, and replies to the requests which
+    // aren't authorized with their status.
     health
+        .or(ready)
         .or(graphql_handler)
         .or(graphql_playground)
+        .or(profile)
         .or(not_found)
+        .recover(auth::recover)
         .with(
             warp::cors()
                 .allow_any_origin()
@@ -161,6 +266,7 @@ fn make_routes(
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
                     "Content-Type",
+                    "Authorization",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
                     "Host",
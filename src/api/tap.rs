@@ -20,8 +20,9 @@ use super::{
     ShutdownRx, ShutdownTx,
 };
 use crate::{
+    conditions::Condition,
     config::ComponentKey,
-    event::{EventArray, LogArray, MetricArray, TraceArray},
+    event::{Event, EventArray, LogArray, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
 };
 
@@ -126,22 +127,66 @@ impl TapPayload {
 }
 
 /// A `TapTransformer` transforms raw events and ships them to the global tap receiver.
+///
+/// When a condition is provided, only the events matching it are shipped.
 #[derive(Clone)]
 pub struct TapTransformer {
     tap_tx: TapSender,
     output: TapOutput,
+    condition: Option<Condition>,
 }
 
 impl TapTransformer {
-    pub const fn new(tap_tx: TapSender, output: TapOutput) -> Self {
-        Self { tap_tx, output }
+    pub const fn new(tap_tx: TapSender, output: TapOutput, condition: Option<Condition>) -> Self {
+        Self {
+            tap_tx,
+            output,
+            condition,
+        }
+    }
+
+    /// Keeps the events matching the condition, if any.
+    fn filter<T>(
+        &self,
+        events: Vec<T>,
+        into_event: fn(T) -> Event,
+        from_event: fn(Event) -> T,
+    ) -> Vec<T> {
+        match &self.condition {
+            None => events,
+            Some(condition) => events
+                .into_iter()
+                .filter_map(|event| {
+                    let (matched, event) = condition.check(into_event(event));
+                    matched.then(|| from_event(event))
+                })
+                .collect(),
+        }
     }
 
     pub fn try_send(&mut self, events: EventArray) {
         let payload = match events {
-            EventArray::Logs(logs) => TapPayload::Log(self.output.clone(), logs),
-            EventArray::Metrics(metrics) => TapPayload::Metric(self.output.clone(), metrics),
-            EventArray::Traces(traces) => TapPayload::Trace(self.output.clone(), traces),
+            EventArray::Logs(logs) => {
+                let logs = self.filter(logs, Event::Log, Event::into_log);
+                if logs.is_empty() {
+                    return;
+                }
+                TapPayload::Log(self.output.clone(), logs)
+            }
+            EventArray::Metrics(metrics) => {
+                let metrics = self.filter(metrics, Event::Metric, Event::into_metric);
+                if metrics.is_empty() {
+                    return;
+                }
+                TapPayload::Metric(self.output.clone(), metrics)
+            }
+            EventArray::Traces(traces) => {
+                let traces = self.filter(traces, Event::Trace, Event::into_trace);
+                if traces.is_empty() {
+                    return;
+                }
+                TapPayload::Trace(self.output.clone(), traces)
+            }
         };
 
         if let Err(TrySendError::Closed(payload)) = self.tap_tx.try_send(payload) {
@@ -165,10 +210,21 @@ impl TapController {
     /// Creates a new tap sink, and spawns a handler for watching for topology changes
     /// and a separate inner handler for events. Uses a oneshot channel to trigger shutdown
     /// of handlers when the `TapSink` drops out of scope.
-    pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: TapPatterns) -> Self {
+    pub fn new(
+        watch_rx: WatchRx,
+        tap_tx: TapSender,
+        patterns: TapPatterns,
+        condition: Option<Condition>,
+    ) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
 
-        tokio::spawn(tap_handler(patterns, tap_tx, watch_rx, shutdown_rx));
+        tokio::spawn(tap_handler(
+            patterns,
+            condition,
+            tap_tx,
+            watch_rx,
+            shutdown_rx,
+        ));
 
         Self { _shutdown }
     }
@@ -237,6 +293,7 @@ async fn send_invalid_output_pattern_match(
 /// `LogEvent`s` when a component matches one or more of the provided patterns.
 async fn tap_handler(
     patterns: TapPatterns,
+    condition: Option<Condition>,
     tx: TapSender,
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
@@ -298,8 +355,8 @@ async fn tap_handler(
                 }
 
                 // Loop over all outputs, and connect sinks for the components that match one
-                // or more patterns.
-                for (output, control_tx) in outputs.iter() {
+                // or more patterns, among those which can be tapped.
+                for (output, control_tx) in outputs.iter().filter(|(output, _)| patterns.permits(output.output_id.component.id())) {
                     match component_id_patterns
                         .iter()
                         .filter(|pattern| pattern.matches_glob(&output.output_id.to_string()))
@@ -316,7 +373,7 @@ async fn tap_handler(
                             // wrap each event payload with the necessary metadata before forwarding
                             // it to our global tap receiver.
                             let (tap_buffer_tx, mut tap_buffer_rx) = TopologyBuilder::standalone_memory(TAP_BUFFER_SIZE, WhenFull::DropNewest).await;
-                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone());
+                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone(), condition.clone());
 
                             tokio::spawn(async move {
                                 while let Some(events) = tap_buffer_rx.next().await {
@@ -426,6 +483,7 @@ mod tests {
     use super::*;
     use crate::api::schema::events::output::OutputEventsPayload;
     use crate::api::schema::events::{create_events_stream, log, metric};
+    use crate::conditions::{ConditionalConfig, VrlConfig};
     use crate::config::{Config, OutputId};
     use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
     use crate::sinks::blackhole::BlackholeConfig;
@@ -486,6 +544,7 @@ mod tests {
                 HashSet::from([pattern_matched.to_string(), pattern_not_matched.to_string()]),
                 HashSet::new(),
             ),
+            None,
         );
 
         // Add the outputs to trigger a change event.
@@ -543,6 +602,41 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    /// A tap transformer should only send the events matching its condition.
+    async fn transformer_filters_events() {
+        let (tap_tx, mut tap_rx) = tokio_mpsc::channel(10);
+        let output = TapOutput {
+            output_id: OutputId::from(&ComponentKey::from("test")),
+            component_kind: "source",
+            component_type: "demo".to_string(),
+        };
+        let condition = VrlConfig {
+            source: ".keep == true".to_string(),
+            ..Default::default()
+        }
+        .build(&Default::default())
+        .unwrap();
+        let mut tap_transformer = TapTransformer::new(tap_tx, output, Some(condition));
+
+        let mut kept = LogEvent::from("kept");
+        kept.insert("keep", true);
+        let dropped = LogEvent::from("dropped");
+
+        tap_transformer.try_send(vec![dropped.clone()].into());
+        tap_transformer.try_send(vec![kept, dropped].into());
+        drop(tap_transformer);
+
+        match tap_rx.recv().await {
+            Some(TapPayload::Log(_, logs)) => {
+                assert_eq!(logs.len(), 1);
+                assert_eq!(logs[0]["message"], "kept".into());
+            }
+            _ => panic!("unexpected payload"),
+        }
+        assert!(tap_rx.recv().await.is_none());
+    }
+
     fn assert_notification(payload: OutputEventsPayload) -> Notification {
         if let OutputEventsPayload::Notification(event_notification) = payload {
             event_notification.notification
@@ -596,6 +690,7 @@ mod tests {
         let source_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["in".to_string()]), HashSet::new()),
+            None,
             500,
             100,
         );
@@ -631,7 +726,8 @@ mod tests {
             &["in"],
             LogToMetricConfig {
                 metrics: vec![MetricConfig {
-                    field: "message".try_into().expect("Fixed template string"),
+                    field: Some("message".try_into().expect("Fixed template string")),
+                    value_source: None,
                     name: None,
                     namespace: None,
                     tags: None,
@@ -654,6 +750,7 @@ mod tests {
         let source_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["to_metric".to_string()]), HashSet::new()),
+            None,
             500,
             100,
         );
@@ -704,6 +801,7 @@ mod tests {
         let transform_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["transform".to_string()]), HashSet::new()),
+            None,
             500,
             100,
         );
@@ -760,6 +858,7 @@ mod tests {
                 HashSet::new(),
                 HashSet::from(["transform".to_string(), "in".to_string()]),
             ),
+            None,
             500,
             100,
         );
@@ -831,6 +930,7 @@ mod tests {
         let tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::new(), HashSet::from(["out".to_string()])),
+            None,
             500,
             100,
         );
@@ -894,6 +994,7 @@ mod tests {
                 HashSet::from(["transform.dropped".to_string()]),
                 HashSet::new(),
             ),
+            None,
             500,
             100,
         );
@@ -967,6 +1068,7 @@ mod tests {
         let mut transform_tap_all_outputs_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["transform*".to_string()]), HashSet::new()),
+            None,
             500,
             100,
         );
@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 #[cfg(feature = "api")]
 use std::sync::{atomic::AtomicBool, Arc};
-use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf};
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf, time::Duration};
 
 use exitcode::ExitCode;
 use futures::StreamExt;
@@ -24,7 +24,7 @@ use crate::metrics;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
-    cli::{handle_config_errors, LogFormat, Opts, RootOpts},
+    cli::{handle_config_errors, LogFormat, Opts, Profile, RootOpts},
     config::{self, Config, ConfigPath},
     heartbeat,
     signal::{SignalHandler, SignalPair, SignalRx, SignalTo},
@@ -34,6 +34,12 @@ use crate::{
 
 pub static WORKER_THREADS: OnceNonZeroUsize = OnceNonZeroUsize::new();
 
+/// The maximum number of worker threads of the `small` profile, unless set with `--threads`.
+const SMALL_PROFILE_WORKER_THREADS: usize = 2;
+
+/// The maximum number of threads running blocking tasks of the `small` profile.
+const SMALL_PROFILE_MAX_BLOCKING_THREADS: usize = 32;
+
 use crate::internal_events::{
     VectorConfigLoadError, VectorQuit, VectorRecoveryError, VectorReloadError, VectorReloaded,
     VectorStarted, VectorStopped,
@@ -54,6 +60,7 @@ pub struct ApplicationConfig {
 
 pub struct Application {
     pub require_healthy: Option<bool>,
+    pub graceful_shutdown_limit: Duration,
     pub config: ApplicationConfig,
     pub signals: SignalPair,
 }
@@ -94,7 +101,7 @@ impl ApplicationConfig {
             .ok_or(exitcode::CONFIG)?;
 
         #[cfg(feature = "api")]
-        let api = config.api;
+        let api = config.api.clone();
 
         let result = topology::start_validated(config, diff, pieces).await;
         let (topology, (graceful_crash_sender, graceful_crash_receiver)) =
@@ -114,12 +121,17 @@ impl ApplicationConfig {
 
     /// Configure the API server, if applicable
     #[cfg(feature = "api")]
-    pub fn setup_api(&self, runtime: &Runtime) -> Option<api::Server> {
+    pub fn setup_api(
+        &self,
+        runtime: &Runtime,
+        signal_tx: crate::signal::SignalTx,
+    ) -> Option<api::Server> {
         if self.api.enabled {
             let api_server = api::Server::start(
                 self.topology.config(),
                 self.topology.watch(),
                 Arc::<AtomicBool>::clone(&self.topology.running),
+                signal_tx,
                 runtime,
             );
 
@@ -167,7 +179,7 @@ impl Application {
     }
 
     pub fn prepare_from_opts(opts: Opts) -> Result<(Runtime, Self), ExitCode> {
-        init_global();
+        init_global(opts.root.profile);
 
         let color = opts.root.color.use_color();
 
@@ -178,7 +190,7 @@ impl Application {
             opts.root.internal_log_rate_limit,
         );
 
-        let runtime = build_runtime(opts.root.threads, "vector-worker")?;
+        let runtime = build_runtime(opts.root.threads, opts.root.profile, "vector-worker")?;
 
         // Signal handler for OS and provider messages.
         let mut signals = SignalPair::new(&runtime);
@@ -196,6 +208,9 @@ impl Application {
             runtime,
             Self {
                 require_healthy: opts.root.require_healthy,
+                graceful_shutdown_limit: Duration::from_secs(
+                    opts.root.graceful_shutdown_limit_secs.get(),
+                ),
                 config,
                 signals,
             },
@@ -212,16 +227,18 @@ impl Application {
 
         let Self {
             require_healthy,
+            graceful_shutdown_limit,
             config,
             signals,
         } = self;
 
         let topology_controller = TopologyController {
             #[cfg(feature = "api")]
-            api_server: config.setup_api(runtime),
+            api_server: config.setup_api(runtime, signals.handler.clone_tx()),
             topology: config.topology,
             config_paths: config.config_paths.clone(),
             require_healthy,
+            graceful_shutdown_limit,
             #[cfg(feature = "enterprise")]
             enterprise_reporter: config.enterprise,
         };
@@ -285,6 +302,10 @@ impl StartedApplication {
                                 break signal;
                             }
                         },
+                        Ok(SignalTo::ReloadEnrichmentTables(tables)) => {
+                            topology_controller.topology.reload_enrichment_tables(&tables).await;
+                        }
+                        Ok(SignalTo::DumpRequestLogs) => crate::http::request_log::dump(),
                         Err(RecvError::Lagged(amt)) => warn!("Overflow, dropped {} signals.", amt),
                         Err(RecvError::Closed) => break SignalTo::Shutdown,
                         Ok(signal) => break signal,
@@ -320,10 +341,18 @@ impl FinishedApplication {
         } = self;
 
         match signal {
-            SignalTo::Shutdown => {
+            SignalTo::Shutdown | SignalTo::Drain(_) => {
+                let mut limit = topology_controller.graceful_shutdown_limit;
+                if let SignalTo::Drain(deadline) = signal {
+                    limit = deadline.unwrap_or(limit);
+                    info!(
+                        message = "Draining the topology before stopping.",
+                        deadline_secs = limit.as_secs()
+                    );
+                }
                 emit!(VectorStopped);
                 tokio::select! {
-                    _ = topology_controller.stop() => (), // Graceful shutdown finished
+                    _ = topology_controller.stop(limit) => (), // Graceful shutdown finished
                     _ = signal_rx.recv() => {
                         // It is highly unlikely that this event will exit from topology.
                         emit!(VectorQuit);
@@ -345,6 +374,7 @@ struct TopologyController {
     topology: RunningTopology,
     config_paths: Vec<config::ConfigPath>,
     require_healthy: Option<bool>,
+    graceful_shutdown_limit: Duration,
     #[cfg(feature = "enterprise")]
     enterprise_reporter: Option<EnterpriseReporter<BoxFuture<'static, ()>>>,
     #[cfg(feature = "api")]
@@ -412,16 +442,18 @@ impl TopologyController {
         self.topology.sources_finished().await;
     }
 
-    async fn stop(self) {
-        self.topology.stop().await;
+    async fn stop(self, limit: Duration) {
+        self.topology.stop_within(limit).await;
     }
 }
 
-pub fn init_global() {
+pub fn init_global(profile: Profile) {
     openssl_probe::init_ssl_cert_env_vars();
 
     #[cfg(not(feature = "enterprise-tests"))]
-    metrics::init_global().expect("metrics initialization failed");
+    metrics::init_global_with(profile.metric_set()).expect("metrics initialization failed");
+    #[cfg(feature = "enterprise-tests")]
+    let _ = profile;
 }
 
 fn get_log_levels(default: &str) -> String {
@@ -452,10 +484,22 @@ fn get_log_levels(default: &str) -> String {
         })
 }
 
-pub fn build_runtime(threads: Option<usize>, thread_name: &str) -> Result<Runtime, ExitCode> {
+pub fn build_runtime(
+    threads: Option<usize>,
+    profile: Profile,
+    thread_name: &str,
+) -> Result<Runtime, ExitCode> {
     let mut rt_builder = runtime::Builder::new_multi_thread();
     rt_builder.enable_all().thread_name(thread_name);
 
+    let threads = match profile {
+        Profile::Default => threads,
+        Profile::Small => {
+            rt_builder.max_blocking_threads(SMALL_PROFILE_MAX_BLOCKING_THREADS);
+            threads.or_else(|| Some(crate::num_threads().min(SMALL_PROFILE_WORKER_THREADS)))
+        }
+    };
+
     if let Some(threads) = threads {
         if threads < 1 {
             #[allow(clippy::print_stderr)]
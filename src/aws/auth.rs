@@ -1,15 +1,19 @@
-use std::time::Duration;
+use std::{collections::HashMap, iter, sync::Mutex, time::Duration};
 
 use aws_config::{
     default_provider::credentials::DefaultCredentialsChain,
     imds,
+    meta::credentials::LazyCachingCredentialsProvider,
     profile::{
         profile_file::{ProfileFileKind, ProfileFiles},
         ProfileFileCredentialsProvider,
     },
+    provider_config::ProviderConfig,
     sts::AssumeRoleProviderBuilder,
+    web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider},
 };
 use aws_types::{credentials::SharedCredentialsProvider, region::Region, Credentials};
+use once_cell::sync::Lazy;
 use serde_with::serde_as;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
@@ -18,6 +22,14 @@ use vector_config::configurable_component;
 // default rather than relying on the SDK default to not change
 const DEFAULT_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_PROFILE_NAME: &str = "default";
+const DEFAULT_SESSION_NAME: &str = "vector";
+// matches the default TTL of the IMDSv2 session tokens in the SDK
+const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(21_600);
+
+/// The credentials providers of the strategies assuming roles, shared by the components
+/// configured with the same strategy, for them to not each request the credentials from STS.
+static ROLE_PROVIDERS: Lazy<Mutex<HashMap<(String, Region), SharedCredentialsProvider>>> =
+    Lazy::new(Default::default);
 
 /// IMDS Client Configuration for authenticating with AWS.
 #[serde_as]
@@ -44,6 +56,16 @@ pub struct ImdsAuthentication {
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     #[derivative(Default(value = "default_timeout()"))]
     read_timeout: Duration,
+
+    /// How long the IMDSv2 session tokens are valid for.
+    ///
+    /// The metadata is fetched with IMDSv2, requesting a session token which is reused until it
+    /// expires.
+    #[serde(default = "default_token_ttl")]
+    #[serde(rename = "token_ttl_seconds")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_token_ttl()"))]
+    token_ttl: Duration,
 }
 
 const fn default_max_attempts() -> u32 {
@@ -54,6 +76,28 @@ const fn default_timeout() -> Duration {
     Duration::from_secs(1)
 }
 
+const fn default_token_ttl() -> Duration {
+    DEFAULT_TOKEN_TTL
+}
+
+/// A role assumed with the credentials of the previous role of a chain.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainedRole {
+    /// The ARN of an [IAM role][iam_role] to assume.
+    ///
+    /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
+    #[configurable(metadata(docs::examples = "arn:aws:iam::210987654321:role/other_role"))]
+    assume_role: String,
+
+    /// The [external ID][external_id] the trust policy of the role requires, if any.
+    ///
+    /// [external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+    #[configurable(metadata(docs::examples = "MyExternalId"))]
+    external_id: Option<String>,
+}
+
 /// Configuration of the authentication strategy for interacting with AWS services.
 #[configurable_component]
 #[derive(Clone, Debug, Derivative)]
@@ -76,10 +120,21 @@ pub enum AwsAuthentication {
         #[configurable(metadata(docs::examples = "arn:aws:iam::123456789098:role/my_role"))]
         assume_role: Option<String>,
 
+        /// The [external ID][external_id] the trust policy of `assume_role` requires, if any.
+        ///
+        /// [external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+        #[configurable(metadata(docs::examples = "MyExternalId"))]
+        external_id: Option<String>,
+
+        /// The roles to assume in turn once `assume_role` is, each with the credentials of the
+        /// previous one, such as to reach a role in another account.
+        #[serde(default)]
+        assume_role_chain: Vec<ChainedRole>,
+
         /// The [AWS region][aws_region] to send STS requests to.
         ///
         /// If not set, this will default to the configured region
-        /// for the service itself.
+        /// for the service itself. The requests are sent to the regional STS endpoint.
         ///
         /// [aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
         #[configurable(metadata(docs::examples = "us-west-2"))]
@@ -104,6 +159,45 @@ pub enum AwsAuthentication {
         profile: String,
     },
 
+    /// Assume the given role ARN with a web identity token.
+    ///
+    /// This is how the pods of EKS are given a role through [IAM roles for service
+    /// accounts][irsa], the token being mounted in the pods.
+    ///
+    /// [irsa]: https://docs.aws.amazon.com/eks/latest/userguide/iam-roles-for-service-accounts.html
+    WebIdentity {
+        /// Path to the file of the web identity token.
+        #[configurable(metadata(
+            docs::examples = "/var/run/secrets/eks.amazonaws.com/serviceaccount/token"
+        ))]
+        web_identity_token_file: String,
+
+        /// The ARN of an [IAM role][iam_role] to assume.
+        ///
+        /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
+        #[configurable(metadata(docs::examples = "arn:aws:iam::123456789098:role/my_role"))]
+        assume_role: String,
+
+        /// The name of the session of the role assumed with the web identity token.
+        #[serde(default = "default_session_name")]
+        #[configurable(metadata(docs::examples = "my-session"))]
+        session_name: String,
+
+        /// The roles to assume in turn once `assume_role` is, each with the credentials of the
+        /// previous one, such as to reach a role in another account.
+        #[serde(default)]
+        assume_role_chain: Vec<ChainedRole>,
+
+        /// The [AWS region][aws_region] to send STS requests to.
+        ///
+        /// If not set, this defaults to the configured region
+        /// for the service itself. The requests are sent to the regional STS endpoint.
+        ///
+        /// [aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
+        #[configurable(metadata(docs::examples = "us-west-2"))]
+        region: Option<String>,
+    },
+
     /// Assume the given role ARN.
     Role {
         /// The ARN of an [IAM role][iam_role] to assume.
@@ -112,6 +206,17 @@ pub enum AwsAuthentication {
         #[configurable(metadata(docs::examples = "arn:aws:iam::123456789098:role/my_role"))]
         assume_role: String,
 
+        /// The [external ID][external_id] the trust policy of `assume_role` requires, if any.
+        ///
+        /// [external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+        #[configurable(metadata(docs::examples = "MyExternalId"))]
+        external_id: Option<String>,
+
+        /// The roles to assume in turn once `assume_role` is, each with the credentials of the
+        /// previous one, such as to reach a role in another account.
+        #[serde(default)]
+        assume_role_chain: Vec<ChainedRole>,
+
         /// Timeout for assuming the role, in seconds.
         ///
         /// Relevant when the default credentials chain or `assume_role` is used.
@@ -126,7 +231,7 @@ pub enum AwsAuthentication {
         /// The [AWS region][aws_region] to send STS requests to.
         ///
         /// If not set, this defaults to the configured region
-        /// for the service itself.
+        /// for the service itself. The requests are sent to the regional STS endpoint.
         ///
         /// [aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
         #[configurable(metadata(docs::examples = "us-west-2"))]
@@ -153,16 +258,67 @@ fn default_profile() -> String {
     DEFAULT_PROFILE_NAME.to_string()
 }
 
+fn default_session_name() -> String {
+    DEFAULT_SESSION_NAME.to_string()
+}
+
 impl AwsAuthentication {
     pub async fn credentials_provider(
         &self,
         service_region: Region,
+    ) -> crate::Result<SharedCredentialsProvider> {
+        if !self.assumes_role() {
+            return self.build_credentials_provider(service_region).await;
+        }
+
+        // The strategies are told apart by their whole configuration, along with the region of
+        // the service the STS requests default to.
+        let key = (serde_json::to_string(self)?, service_region.clone());
+        if let Some(provider) = ROLE_PROVIDERS
+            .lock()
+            .expect("role providers lock poisoned")
+            .get(&key)
+        {
+            return Ok(provider.clone());
+        }
+
+        let provider = SharedCredentialsProvider::new(
+            LazyCachingCredentialsProvider::builder()
+                .load(self.build_credentials_provider(service_region).await?)
+                .build(),
+        );
+        Ok(ROLE_PROVIDERS
+            .lock()
+            .expect("role providers lock poisoned")
+            .entry(key)
+            .or_insert(provider)
+            .clone())
+    }
+
+    /// Returns whether the credentials are requested from STS.
+    fn assumes_role(&self) -> bool {
+        match self {
+            Self::AccessKey {
+                assume_role,
+                assume_role_chain,
+                ..
+            } => assume_role.is_some() || !assume_role_chain.is_empty(),
+            Self::WebIdentity { .. } | Self::Role { .. } => true,
+            Self::File { .. } | Self::Default { .. } => false,
+        }
+    }
+
+    async fn build_credentials_provider(
+        &self,
+        service_region: Region,
     ) -> crate::Result<SharedCredentialsProvider> {
         match self {
             Self::AccessKey {
                 access_key_id,
                 secret_access_key,
                 assume_role,
+                external_id,
+                assume_role_chain,
                 region,
             } => {
                 let provider = SharedCredentialsProvider::new(Credentials::from_keys(
@@ -170,14 +326,16 @@ impl AwsAuthentication {
                     secret_access_key.inner(),
                     None,
                 ));
-                if let Some(assume_role) = assume_role {
-                    let auth_region = region.clone().map(Region::new).unwrap_or(service_region);
-                    let provider = AssumeRoleProviderBuilder::new(assume_role)
-                        .region(auth_region)
-                        .build(provider);
-                    return Ok(SharedCredentialsProvider::new(provider));
-                }
-                Ok(provider)
+                let auth_region = region.clone().map(Region::new).unwrap_or(service_region);
+                let roles = assume_role.iter().map(|assume_role| ChainedRole {
+                    assume_role: assume_role.clone(),
+                    external_id: external_id.clone(),
+                });
+                Ok(assume_roles(
+                    provider,
+                    roles.chain(assume_role_chain.iter().cloned()),
+                    &auth_region,
+                ))
             }
             AwsAuthentication::File {
                 credentials_file,
@@ -194,21 +352,51 @@ impl AwsAuthentication {
                     .build();
                 Ok(SharedCredentialsProvider::new(profile_provider))
             }
+            AwsAuthentication::WebIdentity {
+                web_identity_token_file,
+                assume_role,
+                session_name,
+                assume_role_chain,
+                region,
+            } => {
+                let auth_region = region.clone().map(Region::new).unwrap_or(service_region);
+                let provider = WebIdentityTokenCredentialsProvider::builder()
+                    .configure(
+                        &ProviderConfig::without_region().with_region(Some(auth_region.clone())),
+                    )
+                    .static_configuration(StaticConfiguration {
+                        web_identity_token_file: web_identity_token_file.into(),
+                        role_arn: assume_role.clone(),
+                        session_name: session_name.clone(),
+                    })
+                    .build();
+                Ok(assume_roles(
+                    SharedCredentialsProvider::new(provider),
+                    assume_role_chain.iter().cloned(),
+                    &auth_region,
+                ))
+            }
             AwsAuthentication::Role {
                 assume_role,
+                external_id,
+                assume_role_chain,
                 load_timeout_secs,
                 imds,
                 region,
             } => {
                 let auth_region = region.clone().map(Region::new).unwrap_or(service_region);
-                let provider = AssumeRoleProviderBuilder::new(assume_role)
-                    .region(auth_region.clone())
-                    .build(
-                        default_credentials_provider(auth_region, *load_timeout_secs, *imds)
-                            .await?,
-                    );
-
-                Ok(SharedCredentialsProvider::new(provider))
+                let provider =
+                    default_credentials_provider(auth_region.clone(), *load_timeout_secs, *imds)
+                        .await?;
+                let role = ChainedRole {
+                    assume_role: assume_role.clone(),
+                    external_id: external_id.clone(),
+                };
+                Ok(assume_roles(
+                    provider,
+                    iter::once(role).chain(assume_role_chain.iter().cloned()),
+                    &auth_region,
+                ))
             }
             AwsAuthentication::Default {
                 load_timeout_secs,
@@ -225,11 +413,28 @@ impl AwsAuthentication {
             access_key_id: "dummy".to_string().into(),
             secret_access_key: "dummy".to_string().into(),
             assume_role: None,
+            external_id: None,
+            assume_role_chain: Vec::new(),
             region: None,
         }
     }
 }
 
+/// Assumes the given roles in turn, starting with the credentials of `provider`.
+fn assume_roles(
+    provider: SharedCredentialsProvider,
+    roles: impl Iterator<Item = ChainedRole>,
+    region: &Region,
+) -> SharedCredentialsProvider {
+    roles.fold(provider, |provider, role| {
+        let mut builder = AssumeRoleProviderBuilder::new(role.assume_role).region(region.clone());
+        if let Some(external_id) = role.external_id {
+            builder = builder.external_id(external_id);
+        }
+        SharedCredentialsProvider::new(builder.build(provider))
+    })
+}
+
 async fn default_credentials_provider(
     region: Region,
     load_timeout_secs: Option<u64>,
@@ -239,6 +444,7 @@ async fn default_credentials_provider(
         .max_attempts(imds.max_attempts)
         .connect_timeout(imds.connect_timeout)
         .read_timeout(imds.read_timeout)
+        .token_ttl(imds.token_ttl)
         .build()
         .await?;
 
@@ -317,6 +523,7 @@ mod tests {
                     max_attempts: 5,
                     connect_timeout: CONNECT_TIMEOUT,
                     read_timeout: READ_TIMEOUT,
+                    token_ttl: DEFAULT_TOKEN_TTL,
                 },
             }
         ));
@@ -362,11 +569,15 @@ mod tests {
         match config.auth {
             AwsAuthentication::Role {
                 assume_role,
+                external_id,
+                assume_role_chain,
                 load_timeout_secs,
                 imds,
                 region,
             } => {
                 assert_eq!(&assume_role, "root");
+                assert_eq!(external_id, None);
+                assert!(assume_role_chain.is_empty());
                 assert_eq!(load_timeout_secs, None);
                 assert!(matches!(
                     imds,
@@ -374,6 +585,7 @@ mod tests {
                         max_attempts: 5,
                         connect_timeout: CONNECT_TIMEOUT,
                         read_timeout: READ_TIMEOUT,
+                        token_ttl: DEFAULT_TOKEN_TTL,
                     }
                 ));
                 assert_eq!(region, None);
@@ -400,6 +612,7 @@ mod tests {
                 load_timeout_secs,
                 imds,
                 region,
+                ..
             } => {
                 assert_eq!(&assume_role, "auth.root");
                 assert_eq!(load_timeout_secs, Some(10));
@@ -410,6 +623,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parsing_assume_role_chain() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.assume_role = "root"
+            auth.external_id = "root-id"
+            auth.assume_role_chain = [
+                { assume_role = "other", external_id = "other-id" },
+                { assume_role = "last" },
+            ]
+            auth.imds.token_ttl_seconds = 60
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::Role {
+                assume_role,
+                external_id,
+                assume_role_chain,
+                imds,
+                ..
+            } => {
+                assert_eq!(&assume_role, "root");
+                assert_eq!(external_id.as_deref(), Some("root-id"));
+                assert_eq!(
+                    assume_role_chain,
+                    vec![
+                        ChainedRole {
+                            assume_role: "other".to_string(),
+                            external_id: Some("other-id".to_string()),
+                        },
+                        ChainedRole {
+                            assume_role: "last".to_string(),
+                            external_id: None,
+                        },
+                    ]
+                );
+                assert_eq!(imds.token_ttl, Duration::from_secs(60));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_web_identity() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.web_identity_token_file = "/path/to/token"
+            auth.assume_role = "root"
+            auth.region = "us-west-2"
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::WebIdentity {
+                web_identity_token_file,
+                assume_role,
+                session_name,
+                assume_role_chain,
+                region,
+            } => {
+                assert_eq!(&web_identity_token_file, "/path/to/token");
+                assert_eq!(&assume_role, "root");
+                assert_eq!(&session_name, DEFAULT_SESSION_NAME);
+                assert!(assume_role_chain.is_empty());
+                assert_eq!(region.as_deref(), Some("us-west-2"));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn shares_role_providers() {
+        let auth = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.access_key_id = "key"
+            auth.secret_access_key = "secret"
+            auth.assume_role = "arn:aws:iam::123456789098:role/shared_role"
+        "#,
+        )
+        .unwrap()
+        .auth;
+        let region = Region::new("us-east-1");
+        let role_providers = || ROLE_PROVIDERS.lock().unwrap().len();
+
+        let count = role_providers();
+        auth.credentials_provider(region.clone()).await.unwrap();
+        auth.credentials_provider(region.clone()).await.unwrap();
+        assert_eq!(role_providers(), count + 1);
+        auth.credentials_provider(Region::new("us-west-2"))
+            .await
+            .unwrap();
+        assert_eq!(role_providers(), count + 2);
+
+        // The providers of the keys aren't shared, as they don't request STS.
+        AwsAuthentication::test_auth()
+            .credentials_provider(region)
+            .await
+            .unwrap();
+        assert_eq!(role_providers(), count + 2);
+    }
+
     #[test]
     fn parsing_static() {
         let config = toml::from_str::<ComponentConfig>(
@@ -0,0 +1,290 @@
+//! Functionality shared by the `azure_event_hubs` source and sink.
+//!
+//! Both components talk to Event Hubs over AMQP 1.0, authenticating with a shared access policy of
+//! the namespace, or of the event hub, through SASL PLAIN.
+use fe2o3_amqp::{
+    connection::{ConnectionHandle, OpenError},
+    sasl_profile::SaslProfile,
+    session::{BeginError, SessionHandle},
+    types::{
+        messaging::{ApplicationProperties, Body, Message, Properties},
+        primitives::{OrderedMap, SimpleValue, Value},
+    },
+    Connection, Delivery, Receiver, Sender, Session,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+/// The address of the management node of an Event Hubs namespace.
+const MANAGEMENT_ADDRESS: &str = "$management";
+
+#[derive(Debug, Snafu)]
+pub(crate) enum EventHubsError {
+    #[snafu(display("Invalid Event Hubs connection string: {}", reason))]
+    InvalidConnectionString { reason: &'static str },
+    #[snafu(display(
+        "The event hub name must be set with `event_hub_name` or `EntityPath` in the connection string"
+    ))]
+    MissingEventHubName,
+    #[snafu(display("Could not connect to Event Hubs: {}", source))]
+    Open { source: OpenError },
+    #[snafu(display("Could not begin an Event Hubs session: {}", source))]
+    Begin { source: BeginError },
+    #[snafu(display("Could not read the properties of the event hub: {}", source))]
+    Management { source: crate::Error },
+}
+
+/// Connection options for Azure Event Hubs.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub(crate) struct EventHubsConnectionConfig {
+    /// The connection string of a shared access policy.
+    ///
+    /// The policy can either be one of the Event Hubs namespace, or one of the event hub itself, in
+    /// which case the connection string ends with the `EntityPath` of the event hub.
+    #[configurable(metadata(
+        docs::examples = "Endpoint=sb://my-namespace.servicebus.windows.net/;SharedAccessKeyName=my-policy;SharedAccessKey=keybase64encoded"
+    ))]
+    pub(crate) connection_string: SensitiveString,
+
+    /// The name of the event hub.
+    ///
+    /// Required unless the connection string contains the `EntityPath` of the event hub.
+    #[configurable(metadata(docs::examples = "my-event-hub"))]
+    pub(crate) event_hub_name: Option<String>,
+}
+
+/// The parsed connection options of an event hub.
+#[derive(Clone)]
+pub(crate) struct EventHub {
+    /// The fully qualified host name of the namespace.
+    pub(crate) namespace: String,
+    /// The name of the event hub.
+    pub(crate) name: String,
+    key_name: String,
+    key: String,
+}
+
+impl EventHubsConnectionConfig {
+    pub(crate) fn event_hub(&self) -> Result<EventHub, EventHubsError> {
+        let mut endpoint = None;
+        let mut key_name = None;
+        let mut key = None;
+        let mut entity_path = None;
+        for pair in self.connection_string.inner().split(';') {
+            let pair = pair.trim();
+            if pair.is_empty() {
+                continue;
+            }
+            // Keys are base64-encoded, so values can contain `=` themselves.
+            let (name, value) =
+                pair.split_once('=')
+                    .ok_or(EventHubsError::InvalidConnectionString {
+                        reason: "expected `key=value` pairs",
+                    })?;
+            match name.to_ascii_lowercase().as_str() {
+                "endpoint" => endpoint = Some(value),
+                "sharedaccesskeyname" => key_name = Some(value),
+                "sharedaccesskey" => key = Some(value),
+                "entitypath" => entity_path = Some(value),
+                _ => {}
+            }
+        }
+
+        let endpoint = endpoint.ok_or(EventHubsError::InvalidConnectionString {
+            reason: "`Endpoint` is missing",
+        })?;
+        let namespace = endpoint
+            .split_once("://")
+            .map_or(endpoint, |(_, host)| host)
+            .trim_end_matches('/');
+        if namespace.is_empty() {
+            return Err(EventHubsError::InvalidConnectionString {
+                reason: "`Endpoint` has no host",
+            });
+        }
+        let key_name = key_name.ok_or(EventHubsError::InvalidConnectionString {
+            reason: "`SharedAccessKeyName` is missing",
+        })?;
+        let key = key.ok_or(EventHubsError::InvalidConnectionString {
+            reason: "`SharedAccessKey` is missing",
+        })?;
+        let name = self
+            .event_hub_name
+            .as_deref()
+            .or(entity_path)
+            .ok_or(EventHubsError::MissingEventHubName)?;
+
+        Ok(EventHub {
+            namespace: namespace.to_owned(),
+            name: name.to_owned(),
+            key_name: key_name.to_owned(),
+            key: key.to_owned(),
+        })
+    }
+}
+
+impl EventHub {
+    /// Opens a connection to the namespace, along with a session on it.
+    pub(crate) async fn connect(
+        &self,
+        container_id: &str,
+    ) -> Result<(ConnectionHandle<()>, SessionHandle<()>), EventHubsError> {
+        let url = format!("amqps://{}", self.namespace);
+        let mut connection = Connection::builder()
+            .container_id(container_id)
+            // Event Hubs expects TLS to be established before the AMQP protocol header is sent.
+            .alt_tls_establishment(true)
+            .sasl_profile(SaslProfile::Plain {
+                username: self.key_name.clone(),
+                password: self.key.clone(),
+            })
+            .open(url.as_str())
+            .await
+            .context(OpenSnafu)?;
+        let session = Session::begin(&mut connection).await.context(BeginSnafu)?;
+        Ok((connection, session))
+    }
+
+    /// Reads the identifiers of the partitions of the event hub from the management node.
+    pub(crate) async fn partition_ids(
+        &self,
+        session: &mut SessionHandle<()>,
+    ) -> Result<Vec<String>, EventHubsError> {
+        self.read_partition_ids(session)
+            .await
+            .context(ManagementSnafu)
+    }
+
+    async fn read_partition_ids(
+        &self,
+        session: &mut SessionHandle<()>,
+    ) -> crate::Result<Vec<String>> {
+        let link_name = format!("vector-management-{}", uuid::Uuid::new_v4());
+        let mut sender =
+            Sender::attach(session, format!("{}-sender", link_name), MANAGEMENT_ADDRESS).await?;
+        let mut receiver = Receiver::builder()
+            .name(format!("{}-receiver", link_name))
+            .source(MANAGEMENT_ADDRESS)
+            .target(link_name.as_str())
+            .attach(session)
+            .await?;
+
+        let request = Message::builder()
+            .properties(
+                Properties::builder()
+                    .message_id(link_name.clone())
+                    .reply_to(link_name.clone())
+                    .build(),
+            )
+            .application_properties(
+                ApplicationProperties::builder()
+                    .insert("operation", "READ")
+                    .insert("type", "com.microsoft:eventhub")
+                    .insert("name", self.name.as_str())
+                    .build(),
+            )
+            .value(())
+            .build();
+        sender
+            .send(request)
+            .await?
+            .accepted_or("The management request was not accepted")?;
+
+        let response: Delivery<Body<Value>> = receiver.recv().await?;
+        receiver.accept(&response).await?;
+        let _ = sender.close().await;
+        let _ = receiver.close().await;
+
+        let status = response
+            .message()
+            .application_properties
+            .as_ref()
+            .and_then(|properties| properties.0.get("status-code"))
+            .cloned();
+        if !matches!(status, Some(SimpleValue::Int(200))) {
+            return Err(format!("Unexpected management response status {:?}", status).into());
+        }
+
+        let properties = match response.body() {
+            Body::Value(value) => match &value.0 {
+                Value::Map(properties) => properties,
+                _ => return Err("Unexpected management response body".into()),
+            },
+            _ => return Err("Unexpected management response body".into()),
+        };
+        partition_ids_from(properties).ok_or_else(|| "Missing partition ids".into())
+    }
+}
+
+fn partition_ids_from(properties: &OrderedMap<Value, Value>) -> Option<Vec<String>> {
+    match properties.get(&Value::String("partition_ids".to_owned()))? {
+        Value::Array(ids) => ids
+            .0
+            .iter()
+            .map(|id| match id {
+                Value::String(id) => Some(id.clone()),
+                _ => None,
+            })
+            .collect(),
+        Value::List(ids) => ids
+            .iter()
+            .map(|id| match id {
+                Value::String(id) => Some(id.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(connection_string: &str, event_hub_name: Option<&str>) -> EventHubsConnectionConfig {
+        EventHubsConnectionConfig {
+            connection_string: connection_string.to_owned().into(),
+            event_hub_name: event_hub_name.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn parses_connection_string() {
+        let event_hub = config(
+            "Endpoint=sb://ns.servicebus.windows.net/;SharedAccessKeyName=policy;SharedAccessKey=a2V5==;EntityPath=hub",
+            None,
+        )
+        .event_hub()
+        .unwrap();
+        assert_eq!(event_hub.namespace, "ns.servicebus.windows.net");
+        assert_eq!(event_hub.name, "hub");
+        assert_eq!(event_hub.key_name, "policy");
+        assert_eq!(event_hub.key, "a2V5==");
+
+        let event_hub = config(
+            "Endpoint=sb://ns.servicebus.windows.net/;SharedAccessKeyName=policy;SharedAccessKey=a2V5",
+            Some("other"),
+        )
+        .event_hub()
+        .unwrap();
+        assert_eq!(event_hub.name, "other");
+    }
+
+    #[test]
+    fn rejects_incomplete_connection_string() {
+        assert!(matches!(
+            config(
+                "Endpoint=sb://ns.servicebus.windows.net/;SharedAccessKeyName=policy;SharedAccessKey=a2V5",
+                None,
+            )
+            .event_hub(),
+            Err(EventHubsError::MissingEventHubName)
+        ));
+        assert!(matches!(
+            config("Endpoint=sb://ns.servicebus.windows.net/", Some("hub")).event_hub(),
+            Err(EventHubsError::InvalidConnectionString { .. })
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+#![allow(missing_docs)]
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use file_source::Checkpointer;
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    sub_command: SubCommand,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+enum SubCommand {
+    /// Export the checkpoints of a `file` source to a portable JSON document.
+    Export(ExportOpts),
+
+    /// Import checkpoints previously exported with `vector checkpoints export`.
+    ///
+    /// Vector must not be running with the target source while importing, otherwise the imported
+    /// checkpoints will be overwritten.
+    Import(ImportOpts),
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct ExportOpts {
+    /// The data directory of the source, which is the `data_dir` of the source, or the global
+    /// `data_dir` joined with the component ID of the source.
+    #[arg(short, long)]
+    data_dir: PathBuf,
+
+    /// The file to write the checkpoints to. Defaults to stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct ImportOpts {
+    /// The data directory of the source, which is the `data_dir` of the source, or the global
+    /// `data_dir` joined with the component ID of the source.
+    #[arg(short, long)]
+    data_dir: PathBuf,
+
+    /// The file to read the checkpoints from. Defaults to stdin.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.sub_command {
+        SubCommand::Export(opts) => export(opts),
+        SubCommand::Import(opts) => import(opts),
+    }
+}
+
+/// Export the checkpoints persisted in `data_dir` to `writer`, returning the number of
+/// checkpoints exported.
+pub fn export_checkpoints<W: io::Write>(data_dir: &Path, writer: W) -> io::Result<usize> {
+    if !data_dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("data directory {:?} does not exist", data_dir),
+        ));
+    }
+
+    let mut checkpointer = Checkpointer::new(data_dir);
+    checkpointer.read_checkpoints(None);
+    checkpointer.export_checkpoints(writer)
+}
+
+/// Import the checkpoints read from `reader` into `data_dir`, merging them with any checkpoints
+/// already persisted there, and returning the number of checkpoints imported.
+pub fn import_checkpoints<R: io::Read>(data_dir: &Path, reader: R) -> io::Result<usize> {
+    let mut checkpointer = Checkpointer::new(data_dir);
+    checkpointer.read_checkpoints(None);
+    checkpointer.import_checkpoints(reader)
+}
+
+fn export(opts: &ExportOpts) -> exitcode::ExitCode {
+    let result = match &opts.output {
+        Some(path) => fs::File::create(path)
+            .and_then(|file| export_checkpoints(&opts.data_dir, io::BufWriter::new(file))),
+        None => export_checkpoints(&opts.data_dir, io::stdout().lock()),
+    };
+
+    match result {
+        Ok(count) => {
+            info!(message = "Exported checkpoints.", count, data_dir = ?opts.data_dir);
+            exitcode::OK
+        }
+        Err(error) => {
+            error!(message = "Failed to export checkpoints.", %error);
+            exitcode::IOERR
+        }
+    }
+}
+
+fn import(opts: &ImportOpts) -> exitcode::ExitCode {
+    let result = match &opts.input {
+        Some(path) => fs::File::open(path)
+            .and_then(|file| import_checkpoints(&opts.data_dir, io::BufReader::new(file))),
+        None => import_checkpoints(&opts.data_dir, io::stdin().lock()),
+    };
+
+    match result {
+        Ok(count) => {
+            info!(message = "Imported checkpoints.", count, data_dir = ?opts.data_dir);
+            exitcode::OK
+        }
+        Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+            error!(message = "Invalid checkpoints.", %error);
+            exitcode::DATAERR
+        }
+        Err(error) => {
+            error!(message = "Failed to import checkpoints.", %error);
+            exitcode::IOERR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use file_source::FileFingerprint;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn export_then_import() {
+        let source_dir = tempdir().unwrap();
+        let checkpointer = Checkpointer::new(source_dir.path());
+        checkpointer
+            .view()
+            .update(FileFingerprint::FirstLinesChecksum(1234), 5678);
+        checkpointer.write_checkpoints().unwrap();
+
+        let mut exported = Vec::new();
+        assert_eq!(
+            export_checkpoints(source_dir.path(), &mut exported).unwrap(),
+            1
+        );
+
+        let target_dir = tempdir().unwrap();
+        let data_dir = target_dir.path().join("in");
+        assert_eq!(
+            import_checkpoints(&data_dir, exported.as_slice()).unwrap(),
+            1
+        );
+
+        let mut checkpointer = Checkpointer::new(&data_dir);
+        checkpointer.read_checkpoints(None);
+        assert_eq!(
+            checkpointer
+                .view()
+                .get(FileFingerprint::FirstLinesChecksum(1234)),
+            Some(5678)
+        );
+    }
+
+    #[test]
+    fn export_missing_data_dir() {
+        let dir = tempdir().unwrap();
+        let error = export_checkpoints(&dir.path().join("missing"), io::sink()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -1,15 +1,19 @@
 #![allow(missing_docs)]
-use std::path::PathBuf;
+use std::{num::NonZeroU64, path::PathBuf};
 
 use clap::{ArgAction, CommandFactory, FromArgMatches, Parser};
 
+#[cfg(feature = "sources-file")]
+use crate::checkpoints;
+#[cfg(feature = "sources-replay")]
+use crate::replay;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+use crate::{config, convert_config, generate, get_version, graph, list, unit_test, validate};
 use crate::{generate_schema, signal};
 
 #[derive(Parser, Debug)]
@@ -124,6 +128,18 @@ pub struct RootOpts {
     #[arg(short, long, env = "VECTOR_THREADS")]
     pub threads: Option<usize>,
 
+    /// Set the runtime profile.
+    ///
+    /// The `small` profile lowers the memory used by Vector, for hosts with little of it. Unless
+    /// set with `--threads`, it runs at most two worker threads, which also shrinks the buffers
+    /// between components. It limits the threads running blocking tasks, and only records the
+    /// internal metrics of components and buffers, along with the uptime and build information.
+    ///
+    /// The profile doesn't change which components are compiled in and registered: to leave out
+    /// the unused ones, build Vector with only the features of the components it runs.
+    #[arg(long, default_value = "default", env = "VECTOR_PROFILE")]
+    pub profile: Profile,
+
     /// Enable more detailed internal logging. Repeat to increase level. Overridden by `--quiet`.
     #[arg(short, long, action = ArgAction::Count)]
     pub verbose: u8,
@@ -159,6 +175,16 @@ pub struct RootOpts {
     )]
     pub internal_log_rate_limit: u64,
 
+    /// Set the duration in seconds to wait for the components to gracefully shut down, or drain,
+    /// once Vector is asked to stop. After the duration has passed, the remaining components are
+    /// killed.
+    #[arg(
+        long,
+        env = "VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS",
+        default_value = "60"
+    )]
+    pub graceful_shutdown_limit_secs: NonZeroU64,
+
     /// Set runtime allocation tracing
     #[cfg(feature = "allocation-tracing")]
     #[arg(long, env = "ALLOCATION_TRACING", default_value = "false")]
@@ -202,6 +228,12 @@ pub enum SubCommand {
     /// Generate a Vector configuration containing a list of components.
     Generate(generate::Opts),
 
+    /// Convert a Vector configuration file/dir to another format, e.g. from TOML to YAML.
+    ///
+    /// The comments of TOML and YAML configs are carried over, and the converted configuration is
+    /// checked to produce the same topology before being written.
+    ConvertConfig(convert_config::Opts),
+
     /// Generate the configuration schema for this version of Vector. (experimental)
     ///
     /// A JSON Schema document will be written to stdout that represents the valid schema for a
@@ -224,6 +256,15 @@ pub enum SubCommand {
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Export or import the checkpoints of a `file` source, e.g. to migrate them to another host.
+    #[cfg(feature = "sources-file")]
+    Checkpoints(checkpoints::Opts),
+
+    /// Replay the input recorded from sources through a topology, e.g. to reproduce an incident
+    /// against a candidate configuration.
+    #[cfg(feature = "sources-replay")]
+    Replay(replay::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -248,11 +289,16 @@ impl SubCommand {
         color: bool,
     ) -> exitcode::ExitCode {
         match self {
+            #[cfg(feature = "sources-file")]
+            Self::Checkpoints(c) => checkpoints::cmd(c),
             Self::Config(c) => config::cmd(c),
+            Self::ConvertConfig(c) => convert_config::cmd(c),
             Self::Generate(g) => generate::cmd(g),
             Self::GenerateSchema => generate_schema::cmd(),
             Self::Graph(g) => graph::cmd(g),
             Self::List(l) => list::cmd(l),
+            #[cfg(feature = "sources-replay")]
+            Self::Replay(r) => replay::cmd(r, signals.receiver).await,
             #[cfg(windows)]
             Self::Service(s) => service::cmd(s),
             #[cfg(feature = "api-client")]
@@ -291,6 +337,21 @@ impl Color {
     }
 }
 
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Default,
+    Small,
+}
+
+impl Profile {
+    pub const fn metric_set(self) -> crate::metrics::MetricSet {
+        match self {
+            Profile::Default => crate::metrics::MetricSet::All,
+            Profile::Small => crate::metrics::MetricSet::Core,
+        }
+    }
+}
+
 #[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogFormat {
     Text,
@@ -2,7 +2,10 @@ use codecs::decoding::{DeserializerConfig, FramingConfig};
 use serde::{Deserialize, Serialize};
 use vector_core::config::LogNamespace;
 
-use crate::codecs::Decoder;
+use crate::{
+    codecs::{DecodeErrorPolicy, Decoder},
+    recording,
+};
 
 /// Config used to build a `Decoder`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -13,6 +16,12 @@ pub struct DecodingConfig {
     decoding: DeserializerConfig,
     /// The namespace used when decoding.
     log_namespace: LogNamespace,
+    /// What to do with the frames failing to be decoded.
+    #[serde(default)]
+    error_policy: DecodeErrorPolicy,
+    /// Whether to relay the frames without decoding them.
+    #[serde(default)]
+    pass_through: bool,
 }
 
 impl DecodingConfig {
@@ -27,9 +36,23 @@ impl DecodingConfig {
             framing,
             decoding,
             log_namespace,
+            error_policy: DecodeErrorPolicy::Drop,
+            pass_through: false,
         }
     }
 
+    /// Sets what to do with the frames failing to be decoded.
+    pub const fn with_error_policy(mut self, error_policy: DecodeErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Sets whether to relay the frames without decoding them.
+    pub const fn with_pass_through(mut self, pass_through: bool) -> Self {
+        self.pass_through = pass_through;
+        self
+    }
+
     /// Get the decoding configuration.
     pub const fn config(&self) -> &DeserializerConfig {
         &self.decoding
@@ -41,13 +64,23 @@ impl DecodingConfig {
     }
 
     /// Builds a `Decoder` from the provided configuration.
-    pub fn build(&self) -> Decoder {
+    pub fn build(&self) -> vector_common::Result<Decoder> {
         // Build the framer.
         let framer = self.framing.build();
 
         // Build the deserializer.
-        let deserializer = self.decoding.build();
+        let deserializer = self.decoding.build()?;
+
+        // Record the frames if the source being built or run is recorded.
+        let recorder = recording::current();
+        if let Some(recorder) = &recorder {
+            recorder.set_decoding(&self.decoding);
+        }
 
-        Decoder::new(framer, deserializer).with_log_namespace(self.log_namespace)
+        Ok(Decoder::new(framer, deserializer)
+            .with_log_namespace(self.log_namespace)
+            .with_error_policy(self.error_policy)
+            .with_pass_through(self.pass_through)
+            .with_recorder(recorder))
     }
 }
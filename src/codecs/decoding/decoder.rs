@@ -1,14 +1,19 @@
+use std::sync::Arc;
+
 use bytes::{Bytes, BytesMut};
 use codecs::decoding::{
     format::Deserializer as _, BoxedFramingError, BytesDeserializer, Deserializer, Error, Framer,
     NewlineDelimitedDecoder,
 };
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 use vector_core::config::LogNamespace;
 
+use super::error_policy::{raw_event, DecodeErrorPolicy};
 use crate::{
+    codecs::pass_through_event,
     event::Event,
     internal_events::{DecoderDeserializeError, DecoderFramingError},
+    recording::Recorder,
 };
 
 /// A decoder that can decode structured events from a byte stream / byte
@@ -18,6 +23,9 @@ pub struct Decoder {
     framer: Framer,
     deserializer: Deserializer,
     log_namespace: LogNamespace,
+    error_policy: DecodeErrorPolicy,
+    pass_through: bool,
+    recorder: Option<Arc<Recorder>>,
 }
 
 impl Default for Decoder {
@@ -26,6 +34,9 @@ impl Default for Decoder {
             framer: Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
             deserializer: Deserializer::Bytes(BytesDeserializer::new()),
             log_namespace: LogNamespace::Legacy,
+            error_policy: DecodeErrorPolicy::Drop,
+            pass_through: false,
+            recorder: None,
         }
     }
 }
@@ -39,6 +50,9 @@ impl Decoder {
             framer,
             deserializer,
             log_namespace: LogNamespace::Legacy,
+            error_policy: DecodeErrorPolicy::Drop,
+            pass_through: false,
+            recorder: None,
         }
     }
 
@@ -48,10 +62,35 @@ impl Decoder {
         self
     }
 
+    /// Sets what to do with the frames failing to be parsed.
+    pub const fn with_error_policy(mut self, error_policy: DecodeErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Sets whether to relay the frames without decoding them, as the sinks consuming the events
+    /// write them as they were received.
+    pub const fn with_pass_through(mut self, pass_through: bool) -> Self {
+        self.pass_through = pass_through;
+        self
+    }
+
+    /// Sets the recording the frames are recorded to, before being decoded.
+    pub fn with_recorder(mut self, recorder: Option<Arc<Recorder>>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// Returns whether the frames are relayed without being decoded.
+    pub const fn is_pass_through(&self) -> bool {
+        self.pass_through
+    }
+
     /// Handles the framing result and parses it into a structured event, if
     /// possible.
     ///
-    /// Emits logs if either framing or parsing failed.
+    /// Emits logs if either framing or parsing failed. A frame failing to be parsed is kept as a
+    /// raw event, unless the error policy drops it.
     fn handle_framing_result(
         &mut self,
         frame: Result<Option<Bytes>, BoxedFramingError>,
@@ -66,15 +105,29 @@ impl Decoder {
             _ => return Ok(None),
         };
 
+        if let Some(recorder) = &self.recorder {
+            recorder.record(&frame);
+        }
+
         let byte_size = frame.len();
+        if self.pass_through {
+            return Ok(Some((smallvec![pass_through_event(frame)], byte_size)));
+        }
+
         // Parse structured events from the byte frame.
-        self.deserializer
-            .parse(frame, self.log_namespace)
-            .map(|events| Some((events, byte_size)))
-            .map_err(|error| {
+        match self.deserializer.parse(frame.clone(), self.log_namespace) {
+            Ok(events) => Ok(Some((events, byte_size))),
+            Err(error) => {
                 emit!(DecoderDeserializeError { error: &error });
-                Error::ParsingError(error)
-            })
+                match self.error_policy {
+                    DecodeErrorPolicy::Drop => Err(Error::ParsingError(error)),
+                    DecodeErrorPolicy::ForwardRaw | DecodeErrorPolicy::Route => {
+                        let event = raw_event(frame, error.to_string(), self.log_namespace);
+                        Ok(Some((smallvec![event], byte_size)))
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -96,6 +149,7 @@ impl tokio_util::codec::Decoder for Decoder {
 #[cfg(test)]
 mod tests {
     use super::Decoder;
+    use crate::codecs::{is_decode_error, pass_through_frame, DecodeErrorPolicy};
     use bytes::Bytes;
     use codecs::{
         decoding::{Deserializer, Framer},
@@ -116,7 +170,7 @@ mod tests {
         let reader = StreamReader::new(stream);
         let decoder = Decoder::new(
             Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
-            Deserializer::Json(JsonDeserializer::new()),
+            Deserializer::Json(JsonDeserializer::default()),
         );
         let mut stream = FramedRead::new(reader, decoder);
 
@@ -132,4 +186,54 @@ mod tests {
         let event = next.unwrap().0.pop().unwrap().into_log();
         assert_eq!(event.get("bar").unwrap(), &Value::from(2));
     }
+
+    #[tokio::test]
+    async fn framed_read_forward_raw_on_error() {
+        let iter = stream::iter(
+            ["{ \"foo\": 1 }\n", "invalid\n"]
+                .into_iter()
+                .map(Bytes::from),
+        );
+        let stream = iter.map(Ok::<_, std::io::Error>);
+        let reader = StreamReader::new(stream);
+        let decoder = Decoder::new(
+            Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+            Deserializer::Json(JsonDeserializer::default()),
+        )
+        .with_error_policy(DecodeErrorPolicy::ForwardRaw);
+        let mut stream = FramedRead::new(reader, decoder);
+
+        let next = stream.next().await.unwrap();
+        let event = next.unwrap().0.pop().unwrap().into_log();
+        assert!(!is_decode_error(&event));
+
+        let next = stream.next().await.unwrap();
+        let event = next.unwrap().0.pop().unwrap().into_log();
+        assert!(is_decode_error(&event));
+        assert_eq!(event.get("message").unwrap(), &Value::from("invalid"));
+    }
+
+    #[tokio::test]
+    async fn framed_read_pass_through() {
+        let iter = stream::iter(
+            ["{ \"foo\": 1 }\n", "invalid\n"]
+                .into_iter()
+                .map(Bytes::from),
+        );
+        let stream = iter.map(Ok::<_, std::io::Error>);
+        let reader = StreamReader::new(stream);
+        let decoder = Decoder::new(
+            Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+            Deserializer::Json(JsonDeserializer::default()),
+        )
+        .with_pass_through(true);
+        let mut stream = FramedRead::new(reader, decoder);
+
+        for expected in ["{ \"foo\": 1 }", "invalid"] {
+            let (mut events, byte_size) = stream.next().await.unwrap().unwrap();
+            assert_eq!(byte_size, expected.len());
+            let event = events.pop().unwrap();
+            assert_eq!(pass_through_frame(&event), Some(&Bytes::from(expected)));
+        }
+    }
 }
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use codecs::{BytesDeserializer, BytesDeserializerConfig};
+use lookup::{metadata_path, owned_value_path};
+use value::{kind::Collection, Kind, Value};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{DataType, LogNamespace, Output},
+    event::{Event, LogEvent},
+};
+
+/// The output the payloads failing to be decoded are routed to, with the `route` policy.
+pub const DECODE_ERRORS_OUTPUT: &str = "errors";
+
+/// The metadata field of the raw events, in the `vector` namespace, describing the decode error.
+const DECODE_ERROR_KEY: &str = "decode_error";
+
+/// What to do with the payloads failing to be decoded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DecodeErrorPolicy {
+    /// Drop the payload, after logging the error.
+    #[default]
+    Drop,
+
+    /// Forward the payload as is, to the default output.
+    ///
+    /// The payload is the message of a log event, with the error in its `%vector.decode_error`
+    /// metadata field.
+    ForwardRaw,
+
+    /// Route the payload as is, to the `errors` output.
+    ///
+    /// The payload is the message of a log event, with the error in its `%vector.decode_error`
+    /// metadata field.
+    Route,
+}
+
+impl DecodeErrorPolicy {
+    /// Returns the `errors` output of a source, if its payloads failing to be decoded are routed.
+    pub fn output(self, log_namespace: LogNamespace) -> Option<Output> {
+        (self == Self::Route).then(|| {
+            let definition = BytesDeserializerConfig::new()
+                .schema_definition(log_namespace)
+                .with_metadata_field(
+                    &owned_value_path!("vector", DECODE_ERROR_KEY),
+                    Kind::object(Collection::empty().with_known("message", Kind::bytes())),
+                    None,
+                )
+                .with_standard_vector_source_metadata();
+            Output::default(DataType::Log)
+                .with_port(DECODE_ERRORS_OUTPUT)
+                .with_schema_definition(definition)
+        })
+    }
+}
+
+/// Creates the log event carrying a payload which failed to be decoded.
+pub(super) fn raw_event(frame: Bytes, error: String, log_namespace: LogNamespace) -> Event {
+    let mut log = BytesDeserializer::new().parse_single(frame, log_namespace);
+    log.insert(
+        metadata_path!("vector", DECODE_ERROR_KEY),
+        BTreeMap::from([("message".to_owned(), Value::from(error))]),
+    );
+    log.into()
+}
+
+/// Returns whether the event carries a payload which failed to be decoded.
+pub fn is_decode_error(log: &LogEvent) -> bool {
+    log.get(metadata_path!("vector", DECODE_ERROR_KEY))
+        .is_some()
+}
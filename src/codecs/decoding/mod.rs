@@ -1,5 +1,7 @@
 mod config;
 mod decoder;
+mod error_policy;
 
 pub use config::DecodingConfig;
 pub use decoder::Decoder;
+pub use error_policy::{is_decode_error, DecodeErrorPolicy, DECODE_ERRORS_OUTPUT};
@@ -2,6 +2,7 @@ use crate::codecs::Transformer;
 use codecs::{
     encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
     CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
+    VarintLengthDelimitedEncoder,
 };
 use vector_config::configurable_component;
 
@@ -36,6 +37,12 @@ impl EncodingConfig {
         &self.encoding
     }
 
+    /// Get the serializer configuration, if the events are serialized without being transformed,
+    /// such that the frames relayed without being decoded can be written as they are.
+    pub fn pass_through_serializer(&self) -> Option<&SerializerConfig> {
+        (self.transformer == Transformer::default()).then_some(&self.encoding)
+    }
+
     /// Build the `Serializer` for this config.
     pub fn build(&self) -> crate::Result<Serializer> {
         self.encoding.build()
@@ -93,6 +100,12 @@ impl EncodingConfigWithFraming {
         (&self.framing, &self.encoding.encoding)
     }
 
+    /// Get the serializer configuration, if the events are serialized without being transformed,
+    /// such that the frames relayed without being decoded can be written as they are.
+    pub fn pass_through_serializer(&self) -> Option<&SerializerConfig> {
+        self.encoding.pass_through_serializer()
+    }
+
     /// Build the `Framer` and `Serializer` for this config.
     pub fn build(&self, sink_type: SinkType) -> crate::Result<(Framer, Serializer)> {
         let framer = self.framing.as_ref().map(|framing| framing.build());
@@ -107,12 +120,14 @@ impl EncodingConfigWithFraming {
             (None, Serializer::Avro(_) | Serializer::Native(_)) => {
                 LengthDelimitedEncoder::new().into()
             }
+            (None, Serializer::Protobuf(_)) => VarintLengthDelimitedEncoder::new().into(),
             (
                 None,
                 Serializer::Csv(_)
                 | Serializer::Gelf(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
+                | Serializer::PrometheusText(_)
                 | Serializer::RawMessage(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::new().into(),
@@ -6,6 +6,7 @@ use codecs::{
 use tokio_util::codec::Encoder as _;
 
 use crate::{
+    codecs::pass_through_frame,
     event::Event,
     internal_events::{EncoderFramingError, EncoderSerializeError},
 };
@@ -54,8 +55,20 @@ where
         Ok(())
     }
 
+    /// Mark the output the encoder writes to as already started, such as when appending to an
+    /// existing file, so that no header is written before the first event.
+    pub fn set_output_started(&mut self) {
+        self.serializer.set_output_started();
+    }
+
     /// Serialize the event without applying framing, at the start of the provided buffer.
+    ///
+    /// The frames relayed without being decoded are written as they are.
     fn serialize_at_start(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Error> {
+        if let Some(frame) = pass_through_frame(&event) {
+            buffer.extend_from_slice(frame);
+            return Ok(());
+        }
         self.serializer.encode(event, buffer).map_err(|error| {
             emit!(EncoderSerializeError { error: &error });
             Error::SerializingError(error)
@@ -113,10 +126,11 @@ impl Encoder<Framer> {
                 Serializer::Gelf(_) | Serializer::Json(_) | Serializer::NativeJson(_),
                 Framer::CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' }),
             ) => "application/json",
-            (Serializer::Native(_), _) => "application/octet-stream",
+            (Serializer::Native(_) | Serializer::Protobuf(_), _) => "application/octet-stream",
+            (Serializer::PrometheusText(_), _) => "text/plain; version=0.0.4",
+            (Serializer::Csv(_), _) => "text/csv",
             (
                 Serializer::Avro(_)
-                | Serializer::Csv(_)
                 | Serializer::Gelf(_)
                 | Serializer::Json(_)
                 | Serializer::Logfmt(_)
@@ -183,13 +197,14 @@ impl tokio_util::codec::Encoder<Event> for Encoder<()> {
 
 #[cfg(test)]
 mod tests {
-    use bytes::BufMut;
+    use bytes::{BufMut, Bytes};
     use codecs::encoding::BoxedFramingError;
     use futures_util::{SinkExt, StreamExt};
     use tokio_util::codec::FramedWrite;
     use vector_core::event::LogEvent;
 
     use super::*;
+    use crate::codecs::pass_through_event;
 
     #[derive(Debug, Clone)]
     struct ParenEncoder;
@@ -322,4 +337,22 @@ mod tests {
         let sink = framed.into_inner();
         assert_eq!(sink, b"(foo)(bar)");
     }
+
+    #[tokio::test]
+    async fn test_encode_events_sink_pass_through() {
+        let encoder = Encoder::<Framer>::new(
+            Framer::Boxed(Box::new(ParenEncoder::new())),
+            codecs::NativeJsonSerializerConfig.build().into(),
+        );
+        let source = futures::stream::iter(vec![
+            pass_through_event(Bytes::from(r#"{"log":{"message":"foo"}}"#)),
+            pass_through_event(Bytes::from("not json")),
+        ])
+        .map(Ok);
+        let sink = Vec::new();
+        let mut framed = FramedWrite::new(sink, encoder);
+        source.forward(&mut framed).await.unwrap();
+        let sink = framed.into_inner();
+        assert_eq!(sink, br#"({"log":{"message":"foo"}})(not json)"#);
+    }
 }
@@ -5,10 +5,14 @@
 
 mod decoding;
 mod encoding;
+mod pass_through;
 mod ready_frames;
 
-pub use decoding::{Decoder, DecodingConfig};
+pub use decoding::{
+    is_decode_error, DecodeErrorPolicy, Decoder, DecodingConfig, DECODE_ERRORS_OUTPUT,
+};
 pub use encoding::{
     Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType, TimestampFormat, Transformer,
 };
+pub use pass_through::{can_pass_through, pass_through_event, pass_through_frame};
 pub use ready_frames::ReadyFrames;
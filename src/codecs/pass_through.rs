@@ -0,0 +1,86 @@
+//! Relaying frames without decoding them.
+//!
+//! When all the consumers of a source are sinks writing each event as its codec would have
+//! read it, such as a `bytes` source feeding a `text` sink, decoding the frames into events only
+//! to encode them back is wasted work. Such sources relay each frame as it is instead, as the
+//! value of a log event marked as passed through, which the encoders of the sinks write as it is.
+//!
+//! As the frames relayed are written as they were received, the sources don't add their metadata or
+//! any other field to the events relaying them, which they tell apart with
+//! [`EventMetadata::is_pass_through`], or for all of their events with
+//! [`Decoder::is_pass_through`](crate::codecs::Decoder::is_pass_through).
+
+use bytes::Bytes;
+use codecs::{decoding::DeserializerConfig, encoding::SerializerConfig};
+use value::Value;
+use vector_core::event::{Event, EventMetadata, LogEvent};
+
+/// Creates the event relaying `frame` without decoding it.
+pub fn pass_through_event(frame: Bytes) -> Event {
+    let mut metadata = EventMetadata::default();
+    metadata.set_pass_through();
+    LogEvent::from_parts(Value::Bytes(frame), metadata).into()
+}
+
+/// Returns the frame relayed by `event`, if it was relayed without being decoded.
+pub fn pass_through_frame(event: &Event) -> Option<&Bytes> {
+    match event {
+        Event::Log(log) if log.metadata().is_pass_through() => match log.value() {
+            Value::Bytes(frame) => Some(frame),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns whether the frames decoded with `decoding` are written unchanged by encoding the events
+/// decoded from them with `serializer`, so that they can be relayed without decoding them.
+pub const fn can_pass_through(
+    decoding: &DeserializerConfig,
+    serializer: &SerializerConfig,
+) -> bool {
+    matches!(
+        (decoding, serializer),
+        (
+            DeserializerConfig::Bytes,
+            SerializerConfig::RawMessage | SerializerConfig::Text(_)
+        ) | (DeserializerConfig::Native, SerializerConfig::Native)
+            | (DeserializerConfig::NativeJson, SerializerConfig::NativeJson)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::encoding::{JsonSerializerConfig, TextSerializerConfig};
+
+    use super::*;
+
+    #[test]
+    fn relays_frames() {
+        let event = pass_through_event(Bytes::from("foo"));
+        assert_eq!(pass_through_frame(&event), Some(&Bytes::from("foo")));
+        assert_eq!(pass_through_frame(&LogEvent::from("foo").into()), None);
+    }
+
+    #[test]
+    fn matches_codecs() {
+        assert!(can_pass_through(
+            &DeserializerConfig::Bytes,
+            &SerializerConfig::Text(TextSerializerConfig::default())
+        ));
+        assert!(can_pass_through(
+            &DeserializerConfig::Native,
+            &SerializerConfig::Native
+        ));
+        assert!(!can_pass_through(
+            &DeserializerConfig::Json {
+                json: Default::default()
+            },
+            &SerializerConfig::Json(JsonSerializerConfig::default())
+        ));
+        assert!(!can_pass_through(
+            &DeserializerConfig::Native,
+            &SerializerConfig::NativeJson
+        ));
+    }
+}
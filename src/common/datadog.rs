@@ -73,3 +73,20 @@ pub(crate) fn get_api_base_endpoint(endpoint: Option<&String>, site: &str) -> St
         .cloned()
         .unwrap_or_else(|| format!("https://api.{}", site))
 }
+
+/// The field of the trace events holding a profile received from the Datadog Agent, as the payload
+/// of its request, which is forwarded as is to the profiling intake.
+pub(crate) const PROFILE_KEY: &str = "dd_profile";
+
+/// The field of the trace events holding a profile received from the Datadog Agent, holding the
+/// headers of its request which are forwarded along with the payload.
+pub(crate) const PROFILE_HEADERS_KEY: &str = "dd_profile_headers";
+
+/// The headers of the profile requests of the Datadog Agent which are forwarded along with their
+/// payload.
+pub(crate) const PROFILE_FORWARDED_HEADERS: [&str; 4] = [
+    "Content-Type",
+    "Content-Encoding",
+    "DD-EVP-ORIGIN",
+    "DD-EVP-ORIGIN-VERSION",
+];
@@ -9,6 +9,12 @@
 ))]
 pub(crate) mod datadog;
 
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sinks-aws_cloudwatch_logs"
+))]
+pub(crate) mod cloudwatch_logs;
+
 #[cfg(any(
     feature = "sources-aws_sqs",
     feature = "sinks-aws_sqs",
@@ -16,5 +22,9 @@ pub(crate) mod datadog;
 ))]
 pub(crate) mod sqs;
 
-#[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
+#[cfg(any(
+    feature = "sources-aws_s3",
+    feature = "sinks-aws_s3",
+    feature = "providers-aws_s3"
+))]
 pub(crate) mod s3;
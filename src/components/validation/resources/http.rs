@@ -69,11 +69,12 @@ impl HttpResourceConfig {
         codec: ResourceCodec,
         output_tx: mpsc::Sender<Event>,
         task_coordinator: &TaskCoordinator<Configuring>,
-    ) {
+    ) -> Result<(), String> {
         match direction {
             // We'll pull data from the sink.
             ResourceDirection::Pull => {
-                spawn_output_http_client(self, codec, output_tx, task_coordinator)
+                spawn_output_http_client(self, codec, output_tx, task_coordinator);
+                Ok(())
             }
             // The sink will push data to us.
             ResourceDirection::Push => {
@@ -232,12 +233,12 @@ fn spawn_output_http_server(
     codec: ResourceCodec,
     output_tx: mpsc::Sender<Event>,
     task_coordinator: &TaskCoordinator<Configuring>,
-) {
+) -> Result<(), String> {
     // This HTTP server will wait for events to be sent by a sink, and collect them and send them on
     // via an output sender. We accept/collect events until we're told to shutdown.
 
     // First, we'll build and spawn our HTTP server.
-    let decoder = codec.into_decoder();
+    let decoder = codec.into_decoder()?;
 
     let (_, http_server_shutdown_tx) =
         spawn_http_server(task_coordinator, &config, move |request| {
@@ -283,6 +284,8 @@ fn spawn_output_http_server(
 
         debug!("HTTP server external output resource completed.");
     });
+
+    Ok(())
 }
 
 /// Spawns an HTTP client that pulls events by making requests to an HTTP server driven by a sink.
@@ -421,7 +424,7 @@ pub fn encode_test_event(
             let mut alt_encoder = if encoder.serializer().supports_json() {
                 Encoder::<encoding::Framer>::new(
                     LengthDelimitedEncoder::new().into(),
-                    LogfmtSerializer::new().into(),
+                    LogfmtSerializer::new(Default::default()).into(),
                 )
             } else {
                 Encoder::<encoding::Framer>::new(
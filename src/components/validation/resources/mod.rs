@@ -2,12 +2,18 @@ mod event;
 mod http;
 
 use codecs::{
-    decoding::{self, DeserializerConfig, NewlineDelimitedDecoderOptions},
+    decoding::{
+        self, CsvDeserializerOptions, DeserializerConfig, LogfmtDeserializerOptions,
+        NewlineDelimitedDecoderOptions, ProtobufDeserializerOptions,
+    },
     encoding::{
-        self, Framer, FramingConfig, JsonSerializerConfig, SerializerConfig, TextSerializerConfig,
+        self, CsvSerializerConfig, CsvSerializerOptions, Framer, FramingConfig,
+        JsonSerializerConfig, LogfmtSerializerOptions, ProtobufSerializerOptions, SerializerConfig,
+        TextSerializerConfig,
     },
     BytesEncoder,
 };
+use lookup::lookup_v2::{ConfigTargetPath, OwnedSegment, OwnedTargetPath, OwnedValuePath};
 use tokio::sync::mpsc;
 use vector_core::{config::DataType, event::Event};
 
@@ -96,24 +102,31 @@ impl ResourceCodec {
     ///
     /// The decoder is generated as an inverse to the input codec: if an encoding configuration was
     /// given, we generate a decoder that satisfies that encoding configuration, and vise versa.
-    pub fn into_decoder(&self) -> Decoder {
+    ///
+    /// # Errors
+    ///
+    /// If the codec is an encoding whose output can't be decoded back into events, an error is
+    /// returned, as the events written by the component can't be validated.
+    pub fn into_decoder(&self) -> Result<Decoder, String> {
         let (framer, deserializer) = match self {
-            Self::Decoding(config) => return config.build(),
+            Self::Decoding(config) => {
+                return Ok(config.build().expect("building decoder should never fail"))
+            }
             Self::Encoding(config) => (
                 encoder_framing_to_decoding_framer(config.config().default_stream_framing()),
-                serializer_config_to_deserializer(config.config()),
+                serializer_config_to_deserializer(config.config())?,
             ),
             Self::EncodingWithFraming(config) => {
                 let (maybe_framing, serializer) = config.config();
                 let framing = maybe_framing.clone().unwrap_or(FramingConfig::Bytes);
                 (
                     encoder_framing_to_decoding_framer(framing),
-                    serializer_config_to_deserializer(serializer),
+                    serializer_config_to_deserializer(serializer)?,
                 )
             }
         };
 
-        Decoder::new(framer, deserializer)
+        Ok(Decoder::new(framer, deserializer))
     }
 }
 
@@ -141,15 +154,49 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
         // "bytes" can be a top-level field and we aren't implicitly decoding everything into the
         // `message` field... but it's close enough for now.
         DeserializerConfig::Bytes => SerializerConfig::Text(TextSerializerConfig::default()),
-        DeserializerConfig::Json => SerializerConfig::Json(JsonSerializerConfig::default()),
+        DeserializerConfig::Csv { csv } => {
+            SerializerConfig::Csv(CsvSerializerConfig::new(CsvSerializerOptions {
+                // The decoder names the columns with top-level fields.
+                fields: csv
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        ConfigTargetPath(OwnedTargetPath::event(OwnedValuePath::from(vec![
+                            OwnedSegment::Field(field.as_str().into()),
+                        ])))
+                    })
+                    .collect(),
+                header: csv.headers,
+                delimiter: csv.delimiter,
+                quote: csv.quote,
+                double_quote: csv.double_quote,
+                escape: csv.escape,
+                ..Default::default()
+            }))
+        }
+        DeserializerConfig::Json { .. } => SerializerConfig::Json(JsonSerializerConfig::default()),
+        DeserializerConfig::Logfmt { logfmt } => SerializerConfig::Logfmt {
+            logfmt: LogfmtSerializerOptions {
+                flatten_separator: logfmt.flatten_separator.clone(),
+                ..Default::default()
+            },
+        },
         // TODO: We need to create an Avro serializer because, certainly, for any source decoding
         // the data as Avro, we can't possibly send anything else without the source just
         // immediately barfing.
         #[cfg(feature = "sources-syslog")]
-        DeserializerConfig::Syslog => SerializerConfig::Logfmt,
+        DeserializerConfig::Syslog => SerializerConfig::Logfmt {
+            logfmt: Default::default(),
+        },
         DeserializerConfig::Native => SerializerConfig::Native,
         DeserializerConfig::NativeJson => SerializerConfig::NativeJson,
         DeserializerConfig::Gelf => SerializerConfig::Gelf,
+        DeserializerConfig::Protobuf { protobuf } => SerializerConfig::Protobuf {
+            protobuf: ProtobufSerializerOptions {
+                desc_file: protobuf.desc_file.clone(),
+                message_type: protobuf.message_type.clone(),
+            },
+        },
     };
 
     serializer_config
@@ -167,6 +214,7 @@ fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> enco
                 delimiter: character_delimited.delimiter,
             },
         },
+        decoding::FramingConfig::ChunkedGelf { .. } => encoding::FramingConfig::Bytes,
         decoding::FramingConfig::LengthDelimited => encoding::FramingConfig::LengthDelimited,
         decoding::FramingConfig::NewlineDelimited { .. } => {
             encoding::FramingConfig::NewlineDelimited
@@ -174,24 +222,67 @@ fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> enco
         // TODO: There's no equivalent octet counting framer for encoding... although
         // there's no particular reason that would make it hard to write.
         decoding::FramingConfig::OctetCounting { .. } => todo!(),
+        decoding::FramingConfig::VarintLengthDelimited => {
+            encoding::FramingConfig::VarintLengthDelimited
+        }
     };
 
     framing_config.build()
 }
 
-fn serializer_config_to_deserializer(config: &SerializerConfig) -> decoding::Deserializer {
+fn serializer_config_to_deserializer(
+    config: &SerializerConfig,
+) -> Result<decoding::Deserializer, String> {
     let deserializer_config = match config {
-        SerializerConfig::Avro { .. } => todo!(),
-        SerializerConfig::Csv { .. } => todo!(),
+        SerializerConfig::Avro { .. } => return Err(unsupported_codec("avro")),
+        SerializerConfig::Csv(config) => DeserializerConfig::Csv {
+            csv: CsvDeserializerOptions {
+                fields: config
+                    .csv
+                    .fields
+                    .iter()
+                    .map(|field| String::from(field.0.path.clone()))
+                    .collect(),
+                headers: config.csv.header,
+                delimiter: config.csv.delimiter,
+                quote: config.csv.quote,
+                double_quote: config.csv.double_quote,
+                escape: config.csv.escape,
+            },
+        },
         SerializerConfig::Gelf => DeserializerConfig::Gelf,
-        SerializerConfig::Json(_) => DeserializerConfig::Json,
-        SerializerConfig::Logfmt => todo!(),
+        SerializerConfig::Json(_) => DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        SerializerConfig::Logfmt { logfmt } => DeserializerConfig::Logfmt {
+            logfmt: LogfmtDeserializerOptions {
+                flatten_separator: logfmt.flatten_separator.clone(),
+                ..Default::default()
+            },
+        },
         SerializerConfig::Native => DeserializerConfig::Native,
         SerializerConfig::NativeJson => DeserializerConfig::NativeJson,
+        // The metrics are written as text, which has no decoder.
+        SerializerConfig::PrometheusText => return Err(unsupported_codec("prometheus_text")),
+        SerializerConfig::Protobuf { protobuf } => DeserializerConfig::Protobuf {
+            protobuf: ProtobufDeserializerOptions {
+                desc_file: protobuf.desc_file.clone(),
+                message_type: protobuf.message_type.clone(),
+            },
+        },
         SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
     };
 
-    deserializer_config.build()
+    Ok(deserializer_config
+        .build()
+        .expect("building deserializer should never fail"))
+}
+
+fn unsupported_codec(codec: &str) -> String {
+    format!(
+        "Unsupported codec `{}`: the events encoded with it can't be decoded for validation.",
+        codec
+    )
 }
 
 fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decoding::Framer {
@@ -209,6 +300,9 @@ fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decod
         encoding::FramingConfig::NewlineDelimited => decoding::FramingConfig::NewlineDelimited {
             newline_delimited: NewlineDelimitedDecoderOptions::default(),
         },
+        encoding::FramingConfig::VarintLengthDelimited => {
+            decoding::FramingConfig::VarintLengthDelimited
+        }
     };
 
     framing_config.build()
@@ -308,11 +402,15 @@ impl ExternalResource {
     }
 
     /// Spawns this resource for use as an output for a sink.
+    ///
+    /// # Errors
+    ///
+    /// If the events written by the sink can't be decoded, an error is returned.
     pub fn spawn_as_output(
         self,
         output_tx: mpsc::Sender<Event>,
         task_coordinator: &TaskCoordinator<Configuring>,
-    ) {
+    ) -> Result<(), String> {
         match self.definition {
             ResourceDefinition::Http(http_config) => {
                 http_config.spawn_as_output(self.direction, self.codec, output_tx, task_coordinator)
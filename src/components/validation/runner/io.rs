@@ -19,8 +19,9 @@ use crate::{
         TestEvent,
     },
     proto::vector::{
-        Client as VectorClient, HealthCheckRequest, HealthCheckResponse, PushEventsRequest,
-        PushEventsResponse, Server as VectorServer, Service as VectorService, ServingStatus,
+        Client as VectorClient, HealthCheckRequest, HealthCheckResponse,
+        PushCompressedEventsRequest, PushEventsRequest, PushEventsResponse, Server as VectorServer,
+        Service as VectorService, ServingStatus,
     },
     sources::util::grpc::run_grpc_server,
 };
@@ -54,6 +55,14 @@ impl VectorService for EventForwardService {
         Ok(tonic::Response::new(PushEventsResponse {}))
     }
 
+    async fn push_compressed_events(
+        &self,
+        request: tonic::Request<PushCompressedEventsRequest>,
+    ) -> Result<tonic::Response<PushEventsResponse>, Status> {
+        self.push_events(tonic::Request::new(request.into_inner().decompress()?))
+            .await
+    }
+
     async fn health_check(
         &self,
         _: tonic::Request<HealthCheckRequest>,
@@ -38,11 +38,7 @@ impl ConditionalConfig for VrlConfig {
         //     },
         // };
 
-        let functions = vrl_stdlib::all()
-            .into_iter()
-            .chain(enrichment::vrl_functions().into_iter())
-            .chain(vector_vrl_functions::vrl_functions())
-            .collect::<Vec<_>>();
+        let functions = crate::vrl_functions();
 
         let state = vrl::state::TypeState::default();
 
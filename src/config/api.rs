@@ -1,10 +1,11 @@
 use std::net::{Ipv4Addr, SocketAddr};
 
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -18,6 +19,106 @@ pub struct Options {
     /// Whether or not to expose the GraphQL playground on the API endpoint.
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// Whether or not to expose the CPU profile of the components on the API endpoint.
+    ///
+    /// The profile is served in the pprof format at `/debug/pprof/profile`, and attributes the CPU
+    /// time used over the requested number of seconds to the components, so it can be read with
+    /// `go tool pprof`. Accounting the CPU time adds a small overhead to the components, so it is
+    /// only done while this is enabled.
+    #[serde(default)]
+    pub profiling: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub readiness: ReadinessOptions,
+
+    /// The tokens which can access the API, along with what each of them is allowed to do.
+    ///
+    /// When set, the GraphQL API and the CPU profile can only be accessed with one of the tokens,
+    /// sent as a bearer token in the `Authorization` header, or as the `token` field of the
+    /// `connection_init` payload of GraphQL subscriptions over WebSockets. The `/health` and
+    /// `/ready` endpoints are left open to probes.
+    ///
+    /// By default, the API can be accessed by anyone who can reach its address.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tokens: Vec<ApiToken>,
+}
+
+/// A token which can access the API.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ApiToken {
+    /// The token.
+    #[configurable(metadata(docs::examples = "${VECTOR_API_TOKEN}"))]
+    pub token: SensitiveString,
+
+    /// What the token is allowed to do.
+    ///
+    /// Any token can check the health of Vector through the `health` query.
+    #[configurable(metadata(docs::examples = "read", docs::examples = "tap"))]
+    pub scopes: Vec<ApiScope>,
+
+    /// The glob patterns of the components whose events the token can tap, with the `tap` scope.
+    ///
+    /// The events emitted by the other components are never sent to the token, even when the
+    /// tapped patterns match them, or when they flow into a component which can be tapped.
+    #[serde(default = "default_tap_components")]
+    #[configurable(metadata(docs::examples = "app_*"))]
+    pub tap_components: Vec<String>,
+}
+
+/// What a token is allowed to do with the API.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// Run queries, such as those of the components and their metrics, subscribe to the metrics,
+    /// and read the CPU profile.
+    Read,
+
+    /// Tap the events of the components matching `tap_components`.
+    Tap,
+
+    /// Run mutations, such as changing options or draining Vector.
+    Mutate,
+}
+
+impl ApiScope {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Tap => "tap",
+            Self::Mutate => "mutate",
+        }
+    }
+}
+
+fn default_tap_components() -> Vec<String> {
+    vec!["*".to_owned()]
+}
+
+/// Readiness options.
+///
+/// The `/ready` endpoint of the API reports Vector as not ready while any of the configured
+/// conditions holds, so that load balancers, such as Kubernetes services, can stop routing events
+/// to an overwhelmed instance.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReadinessOptions {
+    /// The maximum percentage of a buffer limited by bytes, such as a disk buffer, that can be used.
+    ///
+    /// Not set by default, which doesn't check the usage of the buffers.
+    #[configurable(metadata(docs::examples = 80))]
+    pub max_buffer_usage_percent: Option<u8>,
+
+    /// The maximum amount of time a source can be paused by backpressure, in seconds.
+    ///
+    /// Not set by default, which doesn't check the backpressure of the sources.
+    #[configurable(metadata(docs::examples = 30))]
+    pub max_source_blocked_secs: Option<u64>,
 }
 
 impl Default for Options {
@@ -25,7 +126,10 @@ impl Default for Options {
         Self {
             enabled: default_enabled(),
             playground: default_playground(),
+            profiling: false,
             address: default_address(),
+            readiness: ReadinessOptions::default(),
+            tokens: Vec::new(),
         }
     }
 }
@@ -66,10 +170,25 @@ impl Options {
             }
         };
 
+        // Try to merge readiness
+        let readiness = match (self.readiness, other.readiness) {
+            (a, b) if a == b => a,
+            (a, b) if b == ReadinessOptions::default() => a,
+            (a, b) if a == ReadinessOptions::default() => b,
+            (_, _) => return Err("Conflicting `api.readiness` options.".to_owned()),
+        };
+
+        // The tokens of all the configs can access the API.
+        let mut tokens = std::mem::take(&mut self.tokens);
+        tokens.extend(other.tokens);
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            profiling: self.profiling | other.profiling,
+            readiness,
+            tokens,
         };
 
         *self = options;
@@ -83,6 +202,9 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        profiling: true,
+        readiness: ReadinessOptions::default(),
+        tokens: Vec::new(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -93,6 +215,9 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            profiling: true,
+            readiness: ReadinessOptions::default(),
+            tokens: Vec::new(),
         }
     );
 }
@@ -104,6 +229,9 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        profiling: false,
+        readiness: ReadinessOptions::default(),
+        tokens: Vec::new(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -114,6 +242,9 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            profiling: false,
+            readiness: ReadinessOptions::default(),
+            tokens: Vec::new(),
         }
     );
 }
@@ -132,3 +263,47 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn readiness_merge() {
+    let readiness = ReadinessOptions {
+        max_buffer_usage_percent: Some(80),
+        max_source_blocked_secs: None,
+    };
+    let mut a = Options {
+        readiness,
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.readiness, readiness);
+
+    let b = Options {
+        readiness: ReadinessOptions {
+            max_source_blocked_secs: Some(30),
+            ..readiness
+        },
+        ..Options::default()
+    };
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn tokens_merge() {
+    let token = |token: &str| ApiToken {
+        token: token.to_owned().into(),
+        scopes: vec![ApiScope::Read],
+        tap_components: default_tap_components(),
+    };
+    let mut a = Options {
+        tokens: vec![token("a")],
+        ..Options::default()
+    };
+    let b = Options {
+        tokens: vec![token("b")],
+        ..Options::default()
+    };
+
+    a.merge(b).unwrap();
+    assert_eq!(a.tokens, vec![token("a"), token("b")]);
+}
@@ -18,8 +18,8 @@ use super::api;
 #[cfg(feature = "enterprise")]
 use super::enterprise;
 use super::{
-    compiler, schema, BoxedTransform, ComponentKey, Config, EnrichmentTableOuter,
-    HealthcheckOptions, SinkOuter, SourceOuter, TestDefinition, TransformOuter,
+    compiler, schema, BoxedTransform, CanaryOptions, ComponentKey, Config, EnrichmentTableOuter,
+    HealthcheckOptions, SinkOuter, SourceOuter, TenantConfig, TestDefinition, TransformOuter,
 };
 
 /// A complete Vector configuration.
@@ -49,6 +49,10 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub canary: CanaryOptions,
+
     /// All configured enrichment tables.
     #[serde(default)]
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
@@ -65,6 +69,13 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub transforms: IndexMap<ComponentKey, TransformOuter<String>>,
 
+    /// All configured tenants.
+    ///
+    /// Sources are assigned to a tenant with their `tenant` option, and share its resource quotas.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "A tenant."))]
+    pub tenants: IndexMap<String, TenantConfig>,
+
     /// All configured unit tests.
     #[serde(default)]
     pub tests: Vec<TestDefinition<String>>,
@@ -89,10 +100,14 @@ struct ConfigBuilderHash<'a> {
     schema: &'a schema::Options,
     global: &'a GlobalOptions,
     healthchecks: &'a HealthcheckOptions,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    canary: &'a CanaryOptions,
     enrichment_tables: BTreeMap<&'a ComponentKey, &'a EnrichmentTableOuter>,
     sources: BTreeMap<&'a ComponentKey, &'a SourceOuter>,
     sinks: BTreeMap<&'a ComponentKey, &'a SinkOuter<String>>,
     transforms: BTreeMap<&'a ComponentKey, &'a TransformOuter<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    tenants: BTreeMap<&'a String, &'a TenantConfig>,
     tests: &'a Vec<TestDefinition<String>>,
     provider: &'a Option<Providers>,
     secret: BTreeMap<&'a ComponentKey, &'a SecretBackends>,
@@ -168,10 +183,12 @@ impl<'a> From<&'a ConfigBuilder> for ConfigBuilderHash<'a> {
             schema: &value.schema,
             global: &value.global,
             healthchecks: &value.healthchecks,
+            canary: &value.canary,
             enrichment_tables: value.enrichment_tables.iter().collect(),
             sources: value.sources.iter().collect(),
             sinks: value.sinks.iter().collect(),
             transforms: value.transforms.iter().collect(),
+            tenants: value.tenants.iter().collect(),
             tests: &value.tests,
             provider: &value.provider,
             secret: value.secret.iter().collect(),
@@ -189,10 +206,12 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            canary,
             enrichment_tables,
             sources,
             sinks,
             transforms,
+            tenants,
             tests,
             secret,
             hash: _,
@@ -218,10 +237,12 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            canary,
             enrichment_tables,
             sources,
             sinks,
             transforms,
+            tenants,
             provider: None,
             tests,
             secret,
@@ -331,6 +352,8 @@ impl ConfigBuilder {
 
         self.healthchecks.merge(with.healthchecks);
 
+        self.canary.merge(with.canary);
+
         with.enrichment_tables.keys().for_each(|k| {
             if self.enrichment_tables.contains_key(k) {
                 errors.push(format!("duplicate enrichment_table name found: {}", k));
@@ -351,6 +374,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform id found: {}", k));
             }
         });
+        with.tenants.keys().for_each(|k| {
+            if self.tenants.contains_key(k) {
+                errors.push(format!("duplicate tenant name found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -369,6 +397,7 @@ impl ConfigBuilder {
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
+        self.tenants.extend(with.tenants);
         self.tests.extend(with.tests);
         self.secret.extend(with.secret);
 
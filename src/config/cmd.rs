@@ -1,9 +1,12 @@
-use std::path::PathBuf;
+use std::{fmt::Write, path::PathBuf};
 
 use clap::Parser;
 use serde_json::Value;
 
-use super::{load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder};
+use super::{
+    load_builder_from_paths, load_source_from_paths, load_source_with_provenance_from_paths,
+    process_paths, ConfigBuilder, Provenance,
+};
 use crate::cli::handle_config_errors;
 use crate::config;
 
@@ -26,21 +29,22 @@ pub struct Opts {
         id = "config",
         short,
         long,
+        global = true,
         env = "VECTOR_CONFIG",
         value_delimiter(',')
     )]
     paths: Vec<PathBuf>,
 
     /// Vector config files in TOML format.
-    #[arg(id = "config-toml", long, value_delimiter(','))]
+    #[arg(id = "config-toml", long, global = true, value_delimiter(','))]
     paths_toml: Vec<PathBuf>,
 
     /// Vector config files in JSON format.
-    #[arg(id = "config-json", long, value_delimiter(','))]
+    #[arg(id = "config-json", long, global = true, value_delimiter(','))]
     paths_json: Vec<PathBuf>,
 
     /// Vector config files in YAML format.
-    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    #[arg(id = "config-yaml", long, global = true, value_delimiter(','))]
     paths_yaml: Vec<PathBuf>,
 
     /// Read configuration from files in one or more directories.
@@ -51,10 +55,21 @@ pub struct Opts {
         id = "config-dir",
         short = 'C',
         long,
+        global = true,
         env = "VECTOR_CONFIG_DIR",
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    #[command(subcommand)]
+    sub_command: Option<SubCommand>,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+enum SubCommand {
+    /// Print the merged configuration as TOML, with a comment naming the file each value is set
+    /// in. Environment variables are not interpolated.
+    Render,
 }
 
 impl Opts {
@@ -163,6 +178,10 @@ fn serialize_to_json(
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    if let Some(SubCommand::Render) = opts.sub_command {
+        return render(opts);
+    }
+
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
@@ -190,6 +209,84 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
     exitcode::OK
 }
 
+/// Function used by the `vector config render` subcommand for debugging how config files and
+/// the files they include are merged together.
+fn render(opts: &Opts) -> exitcode::ExitCode {
+    let paths = match process_paths(&opts.paths_with_formats()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+    // Rendering an invalid config would be misleading, so error out as `vector config` does.
+    if let Err(errs) = load_builder_from_paths(&paths) {
+        return handle_config_errors(errs);
+    }
+
+    let (source, warnings) = match load_source_with_provenance_from_paths(&paths) {
+        Ok(loaded) => loaded,
+        Err(errs) => return handle_config_errors(errs),
+    };
+    for warning in warnings {
+        warn!("{}", warning);
+    }
+
+    #[allow(clippy::print_stdout)]
+    {
+        print!("{}", render_toml(&source.table, &source.provenance));
+    }
+
+    exitcode::OK
+}
+
+/// Renders a TOML table, commenting each value with the file it was set in.
+fn render_toml(table: &toml::value::Table, provenance: &Provenance) -> String {
+    let mut output = String::new();
+    render_table(&mut output, &mut Vec::new(), table, provenance);
+    output
+}
+
+fn render_table(
+    output: &mut String,
+    key: &mut Vec<String>,
+    table: &toml::value::Table,
+    provenance: &Provenance,
+) {
+    // Values have to come before the sub-tables of a table.
+    for (name, value) in table.iter().filter(|(_, value)| !value.is_table()) {
+        key.push(name.clone());
+        write!(output, "{} = {}", render_key(name), value).expect("write to String never fails");
+        if let Some(file) = provenance.get(key) {
+            write!(output, " # {}", file.display()).expect("write to String never fails");
+        }
+        output.push('\n');
+        key.pop();
+    }
+
+    for (name, value) in table {
+        if let toml::Value::Table(table) = value {
+            key.push(name.clone());
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            let header = key.iter().map(|key| render_key(key)).collect::<Vec<_>>();
+            writeln!(output, "[{}]", header.join(".")).expect("write to String never fails");
+            render_table(output, key, table, provenance);
+            key.pop();
+        }
+    }
+}
+
+fn render_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_owned()
+    } else {
+        toml::Value::String(key.to_owned()).to_string()
+    }
+}
+
 #[cfg(all(test, feature = "sources", feature = "transforms", feature = "sinks"))]
 mod tests {
     use std::collections::HashMap;
@@ -202,12 +299,17 @@ mod tests {
     use serde_json::json;
     use vector_config::component::{SinkDescription, SourceDescription, TransformDescription};
 
+    use indoc::indoc;
+
     use crate::{
-        config::{cmd::serialize_to_json, vars, ConfigBuilder},
+        config::{
+            cmd::serialize_to_json, load_source_with_provenance_from_paths, vars, ConfigBuilder,
+            ConfigPath,
+        },
         generate::{generate_example, TransformInputsStrategy},
     };
 
-    use super::merge_json;
+    use super::{merge_json, render_toml};
 
     #[test]
     fn test_array_override() {
@@ -276,6 +378,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_comments_values_with_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let included = dir.path().join("sources.toml");
+        std::fs::write(
+            &included,
+            indoc! {r#"
+                [sources.in]
+                type = "demo_logs"
+                format = "json"
+            "#},
+        )
+        .unwrap();
+        let main = dir.path().join("vector.toml");
+        std::fs::write(
+            &main,
+            indoc! {r#"
+                include = ["sources.toml"]
+                data_dir = "/tmp"
+                [sources.in]
+                format = "syslog"
+                ["sinks".'my sink']
+                type = "blackhole"
+            "#},
+        )
+        .unwrap();
+
+        let (source, warnings) =
+            load_source_with_provenance_from_paths(&[ConfigPath::File(main.clone(), None)])
+                .unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(
+            render_toml(&source.table, &source.provenance),
+            format!(
+                indoc! {r#"
+                    data_dir = "/tmp" # {main}
+
+                    [sinks]
+
+                    [sinks."my sink"]
+                    type = "blackhole" # {main}
+
+                    [sources]
+
+                    [sources.in]
+                    format = "syslog" # {main}
+                    type = "demo_logs" # {included}
+                "#},
+                main = main.display(),
+                included = included.display(),
+            )
+        );
+    }
+
     /// Select any 2-4 sources
     fn arb_sources() -> impl Strategy<Value = Vec<&'static str>> {
         sample::subsequence(SourceDescription::types(), 2..=4)
@@ -35,6 +35,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(output_errors);
     }
 
+    if let Err(tenant_errors) = validation::check_tenants(&builder) {
+        errors.extend(tenant_errors);
+    }
+
     #[cfg(feature = "enterprise")]
     let hash = Some(builder.sha256_hash());
 
@@ -49,10 +53,12 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         #[cfg(feature = "enterprise")]
         enterprise,
         healthchecks,
+        canary,
         enrichment_tables,
         sources,
         sinks,
         transforms,
+        tenants,
         tests,
         provider: _,
         secret,
@@ -105,15 +111,19 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             enterprise,
             hash,
             healthchecks,
+            canary,
             enrichment_tables,
             sources,
             sinks,
             transforms,
+            tenants,
             tests,
             secret,
         };
 
         config.propagate_acknowledgements()?;
+        config.propagate_links()?;
+        config.propagate_pass_through();
 
         let warnings = validation::warnings(&config);
 
@@ -18,8 +18,23 @@ impl ConfigDiff {
     }
 
     pub fn new(old: &Config, new: &Config) -> Self {
+        let mut sources = Difference::new(&old.sources, &new.sources);
+        // Sources relaying frames without decoding them are rebuilt once their consumers change
+        // such that the frames have to be decoded, and conversely. So are the sources once a sink
+        // starts or stops committing their progress.
+        sources.to_change.extend(
+            old.sources
+                .iter()
+                .filter(|(key, old)| {
+                    new.sources.get(*key).map_or(false, |new| {
+                        new.pass_through != old.pass_through || new.linked != old.linked
+                    })
+                })
+                .map(|(key, _)| key.clone()),
+        );
+
         ConfigDiff {
-            sources: Difference::new(&old.sources, &new.sources),
+            sources,
             transforms: Difference::new(&old.transforms, &new.transforms),
             sinks: Difference::new(&old.sinks, &new.sinks),
             enrichment_tables: Difference::new(&old.enrichment_tables, &new.enrichment_tables),
@@ -1,3 +1,5 @@
+use std::{num::NonZeroU64, path::PathBuf, time::Duration};
+
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
 use vector_config::{configurable_component, NamedComponent};
@@ -11,16 +13,47 @@ use crate::enrichment_tables::EnrichmentTables;
 pub struct EnrichmentTableOuter {
     #[serde(flatten)]
     pub inner: EnrichmentTables,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub reload: ReloadOptions,
 }
 
 impl EnrichmentTableOuter {
     pub fn new<I: Into<EnrichmentTables>>(inner: I) -> Self {
         Self {
             inner: inner.into(),
+            reload: ReloadOptions::default(),
         }
     }
 }
 
+/// Options for reloading the enrichment table while Vector is running.
+///
+/// Reloading an enrichment table doesn't reload the rest of the configuration. If the reloaded
+/// data can't be loaded, the previously loaded data keeps being used.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReloadOptions {
+    /// How often to reload the enrichment table, in seconds.
+    ///
+    /// By default, the enrichment table is only reloaded along with the configuration, unless it
+    /// keeps its data up to date by itself, as the `geoip` enrichment table does when it downloads
+    /// its database.
+    #[serde(default)]
+    pub interval_secs: Option<NonZeroU64>,
+
+    /// Whether or not to reload the enrichment table as soon as the files it's loaded from change.
+    ///
+    /// This has no effect on enrichment tables that aren't loaded from local files.
+    #[serde(default)]
+    pub watch: bool,
+}
+
 /// Generalized interface for describing and building enrichment table components.
 #[async_trait]
 #[enum_dispatch]
@@ -38,4 +71,19 @@ pub trait EnrichmentTableConfig: NamedComponent + core::fmt::Debug + Send + Sync
         &self,
         globals: &GlobalOptions,
     ) -> crate::Result<Box<dyn enrichment::Table + Send + Sync>>;
+
+    /// Gets the local files the enrichment table is loaded from.
+    ///
+    /// These are the files watched for changes when the enrichment table is reloaded on change.
+    fn files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Gets how often the enrichment table is reloaded when `reload.interval_secs` isn't set.
+    ///
+    /// This is for the enrichment tables which keep their data up to date by themselves, and are
+    /// otherwise only reloaded along with the configuration.
+    fn default_reload_interval(&self) -> Option<Duration> {
+        None
+    }
 }
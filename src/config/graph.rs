@@ -17,6 +17,7 @@ pub enum Node {
     },
     Sink {
         ty: DataType,
+        outputs: Vec<Output>,
     },
 }
 
@@ -88,6 +89,7 @@ impl Graph {
                 id.clone(),
                 Node::Sink {
                     ty: config.inner.input().data_type(),
+                    outputs: config.outputs(),
                 },
             );
         }
@@ -154,7 +156,7 @@ impl Graph {
         match self.nodes[key] {
             Node::Source { .. } => panic!("no inputs on sources"),
             Node::Transform { in_ty, .. } => in_ty,
-            Node::Sink { ty } => ty,
+            Node::Sink { ty, .. } => ty,
         }
     }
 
@@ -162,16 +164,17 @@ impl Graph {
     ///
     /// # Panics
     ///
-    /// Will panic if the given id is not present in the graph or identifies a sink, which can't
-    /// have inputs.
+    /// Will panic if the given id is not present in the graph or doesn't identify one of the
+    /// outputs of that component.
     fn get_output_type(&self, id: &OutputId) -> DataType {
         match &self.nodes[&id.component] {
-            Node::Source { outputs } | Node::Transform { outputs, .. } => outputs
+            Node::Source { outputs }
+            | Node::Transform { outputs, .. }
+            | Node::Sink { outputs, .. } => outputs
                 .iter()
                 .find(|output| output.port == id.port)
                 .map(|output| output.ty)
                 .expect("output didn't exist"),
-            Node::Sink { .. } => panic!("no outputs on sinks"),
         }
     }
 
@@ -256,14 +259,15 @@ impl Graph {
         self.nodes
             .iter()
             .flat_map(|(key, node)| match node {
-                Node::Sink { .. } => vec![],
-                Node::Source { outputs } | Node::Transform { outputs, .. } => outputs
+                Node::Source { outputs }
+                | Node::Transform { outputs, .. }
+                | Node::Sink { outputs, .. } => outputs
                     .iter()
                     .map(|output| OutputId {
                         component: key.clone(),
                         port: output.port.clone(),
                     })
-                    .collect(),
+                    .collect::<Vec<_>>(),
             })
             .collect()
     }
@@ -341,7 +345,7 @@ impl Graph {
             .into_iter()
             .filter(|path| {
                 if let Some(key) = path.last() {
-                    matches!(self.nodes.get(key), Some(Node::Sink { ty: _ }))
+                    matches!(self.nodes.get(key), Some(Node::Sink { .. }))
                 } else {
                     false
                 }
@@ -403,7 +407,13 @@ mod test {
         fn add_sink(&mut self, id: &str, ty: DataType, inputs: Vec<&str>) {
             let id = ComponentKey::from(id);
             let inputs = clean_inputs(inputs);
-            self.nodes.insert(id.clone(), Node::Sink { ty });
+            self.nodes.insert(
+                id.clone(),
+                Node::Sink {
+                    ty,
+                    outputs: vec![],
+                },
+            );
             for from in inputs {
                 self.edges.push(Edge {
                     from,
@@ -412,6 +422,16 @@ mod test {
             }
         }
 
+        fn add_sink_output(&mut self, id: &str, name: &str) {
+            let id = id.into();
+            match self.nodes.get_mut(&id) {
+                Some(Node::Sink { ty, outputs }) => {
+                    outputs.push(Output::default(*ty).with_port(name))
+                }
+                _ => panic!("invalid sink"),
+            }
+        }
+
         fn test_add_input(&mut self, node: &str, input: &str) -> Result<(), String> {
             let available_inputs = self.input_map().unwrap();
             self.add_input(input, &node.into(), &available_inputs)
@@ -606,6 +626,51 @@ mod test {
         );
     }
 
+    #[test]
+    fn allows_sink_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("log_source", DataType::Log);
+        graph.add_sink("log_sink", DataType::Log, vec!["log_source"]);
+        graph.add_sink_output("log_sink", "dlq");
+        graph.add_sink("dlq_sink", DataType::Log, vec![]);
+        graph.add_sink("metric_sink", DataType::Metric, vec![]);
+
+        assert_eq!(Ok(()), graph.test_add_input("dlq_sink", "log_sink.dlq"));
+        assert_eq!(Ok(()), graph.typecheck());
+        graph.check_for_cycles().unwrap();
+
+        let expected =
+            "Input \"dlq_sink.dlq\" for sink \"metric_sink\" doesn't match any components."
+                .to_string();
+        assert_eq!(
+            Err(expected),
+            graph.test_add_input("metric_sink", "dlq_sink.dlq")
+        );
+
+        assert_eq!(Ok(()), graph.test_add_input("metric_sink", "log_sink.dlq"));
+        assert_eq!(
+            Err(vec![
+                "Data type mismatch between log_sink.dlq (Log) and metric_sink (Metric)".into()
+            ]),
+            graph.typecheck()
+        );
+    }
+
+    #[test]
+    fn paths_detects_cycles_through_sinks() {
+        let mut graph = Graph::default();
+        graph.add_source("in", DataType::Log);
+        graph.add_transform("one", DataType::Log, DataType::Log, vec!["in"]);
+        graph.add_sink("out", DataType::Log, vec!["one"]);
+        graph.add_sink_output("out", "dlq");
+        graph.test_add_input("one", "out.dlq").unwrap();
+
+        assert_eq!(
+            Err("Cyclic dependency detected in the chain [ out -> one ]".into()),
+            graph.check_for_cycles()
+        );
+    }
+
     #[test]
     fn disallows_ambiguous_inputs() {
         let mut graph = Graph::default();
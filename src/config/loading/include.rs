@@ -0,0 +1,412 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use toml::value::{Table, Value};
+
+use super::{loader::process::Process, read_dir, Format};
+
+/// The key of a config file listing the files and directories to merge into it.
+const INCLUDE_KEY: &str = "include";
+
+/// The files that set the values of a config, keyed by the path to each value that isn't a table.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Provenance(BTreeMap<Vec<String>, PathBuf>);
+
+impl Provenance {
+    fn new(table: &Table, file: &Path) -> Self {
+        let mut provenance = Self::default();
+        provenance.insert_table(&mut Vec::new(), table, file);
+        provenance
+    }
+
+    fn insert_table(&mut self, key: &mut Vec<String>, table: &Table, file: &Path) {
+        for (name, value) in table {
+            key.push(name.clone());
+            if let Value::Table(table) = value {
+                self.insert_table(key, table, file);
+            } else {
+                self.0.insert(key.clone(), file.to_path_buf());
+            }
+            key.pop();
+        }
+    }
+
+    /// Gets the file that set the value at the given key.
+    pub fn get(&self, key: &[String]) -> Option<&Path> {
+        self.0.get(key).map(PathBuf::as_path)
+    }
+
+    /// Gets a file that set the given key, or one of the values nested under it.
+    fn find(&self, key: &[String]) -> Option<&Path> {
+        self.0
+            .range(key.to_vec()..)
+            .next()
+            .filter(|(found, _)| found.starts_with(key))
+            .map(|(_, file)| file.as_path())
+    }
+}
+
+/// A config table, along with the files its values were set in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourcedTable {
+    pub table: Table,
+    pub provenance: Provenance,
+}
+
+impl SourcedTable {
+    fn new(table: Table, file: &Path) -> Self {
+        let provenance = Provenance::new(&table, file);
+        Self { table, provenance }
+    }
+
+    /// Deep-merges `other` into this table. Tables are merged key by key, while any other value of
+    /// `other` replaces the one set here.
+    ///
+    /// Unless the override is `explicit`, values replaced by a different one are reported as
+    /// warnings, since neither file is meant to take precedence over the other. Setting a key to a
+    /// table in one file and to a value in the other is an error.
+    pub(super) fn merge(
+        &mut self,
+        other: SourcedTable,
+        explicit: bool,
+    ) -> Result<Vec<String>, Vec<String>> {
+        let mut merger = Merger {
+            base: &self.provenance,
+            other: &other.provenance,
+            explicit,
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        };
+        merger.merge_tables(&mut Vec::new(), &mut self.table, other.table);
+
+        let Merger {
+            warnings, errors, ..
+        } = merger;
+        if errors.is_empty() {
+            self.provenance.0.extend(other.provenance.0);
+            Ok(warnings)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+struct Merger<'a> {
+    base: &'a Provenance,
+    other: &'a Provenance,
+    explicit: bool,
+    warnings: Vec<String>,
+    errors: Vec<String>,
+}
+
+impl Merger<'_> {
+    fn merge_tables(&mut self, key: &mut Vec<String>, base: &mut Table, other: Table) {
+        for (name, value) in other {
+            key.push(name.clone());
+            if let Some(existing) = base.get_mut(&name) {
+                self.merge_values(key, existing, value);
+            } else {
+                base.insert(name, value);
+            }
+            key.pop();
+        }
+    }
+
+    fn merge_values(&mut self, key: &mut Vec<String>, existing: &mut Value, value: Value) {
+        match (existing, value) {
+            (Value::Table(base), Value::Table(other)) => {
+                self.merge_tables(key, base, other);
+            }
+            (Value::Table(_), _) | (_, Value::Table(_)) => {
+                self.errors.push(format!(
+                    "Config key `{}` is set to a table in {} and to a value in {}.",
+                    key.join("."),
+                    describe(self.base.find(key).or_else(|| self.other.find(key))),
+                    describe(self.other.find(key).or_else(|| self.base.find(key))),
+                ));
+            }
+            (existing, value) => {
+                if !self.explicit && *existing != value {
+                    self.warnings.push(format!(
+                        "Config key `{}` set in {} is overridden by {}.",
+                        key.join("."),
+                        describe(self.base.get(key)),
+                        describe(self.other.get(key)),
+                    ));
+                }
+                *existing = value;
+            }
+        }
+    }
+}
+
+fn describe(file: Option<&Path>) -> String {
+    file.map_or_else(|| "an empty table".to_owned(), |file| format!("{:?}", file))
+}
+
+/// Merges the files and directories listed by the `include` key of a loaded config file into it.
+///
+/// Included entries are merged in the order they are listed, each one overriding the previous
+/// ones, and the including file overrides all of them. Directories contribute the config files
+/// they directly contain, in file name order. Relative paths are resolved against the directory
+/// of the including file.
+pub(super) fn resolve<P: Process + ?Sized>(
+    loader: &mut P,
+    path: &Path,
+    mut table: Table,
+) -> Result<(SourcedTable, Vec<String>), Vec<String>> {
+    let mut stack = Vec::new();
+    if table.contains_key(INCLUDE_KEY) {
+        stack.push(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+    }
+    resolve_nested(loader, path, table.remove(INCLUDE_KEY), table, &mut stack)
+}
+
+/// Loads a config file along with the files and directories it includes.
+pub(super) fn load_file<P: Process + ?Sized>(
+    loader: &mut P,
+    path: &Path,
+    format: Format,
+) -> Result<(SourcedTable, Vec<String>), Vec<String>> {
+    load_included(loader, path, format, &mut Vec::new())
+}
+
+fn load_included<P: Process + ?Sized>(
+    loader: &mut P,
+    path: &Path,
+    format: Format,
+    stack: &mut Vec<PathBuf>,
+) -> Result<(SourcedTable, Vec<String>), Vec<String>> {
+    let canonical = path
+        .canonicalize()
+        .map_err(|err| vec![format!("Could not open config file: {:?}, {}.", path, err)])?;
+    if stack.contains(&canonical) {
+        return Err(vec![format!("Config file {:?} includes itself.", path)]);
+    }
+    let file = File::open(path)
+        .map_err(|err| vec![format!("Could not open config file: {:?}, {}.", path, err)])?;
+
+    let (mut table, mut warnings): (Table, _) = loader.load(file, format)?;
+    stack.push(canonical);
+    let resolved = resolve_nested(loader, path, table.remove(INCLUDE_KEY), table, stack);
+    stack.pop();
+
+    let (table, warns) = resolved?;
+    warnings.extend(warns);
+    Ok((table, warnings))
+}
+
+fn resolve_nested<P: Process + ?Sized>(
+    loader: &mut P,
+    path: &Path,
+    include: Option<Value>,
+    table: Table,
+    stack: &mut Vec<PathBuf>,
+) -> Result<(SourcedTable, Vec<String>), Vec<String>> {
+    let own = SourcedTable::new(table, path);
+    let include = match include {
+        Some(include) => include,
+        None => return Ok((own, Vec::new())),
+    };
+
+    let mut merged = SourcedTable::default();
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+    for (file, format) in included_files(path, include)? {
+        match load_included(loader, &file, format, stack).and_then(|(table, warns)| {
+            warnings.extend(warns);
+            merged.merge(table, false)
+        }) {
+            Ok(warns) => warnings.extend(warns),
+            Err(errs) => errors.extend(errs),
+        }
+    }
+
+    if errors.is_empty() {
+        warnings.extend(merged.merge(own, true)?);
+        Ok((merged, warnings))
+    } else {
+        Err(errors)
+    }
+}
+
+/// Expands the value of an `include` key into the config files to load, in merge order.
+fn included_files(path: &Path, include: Value) -> Result<Vec<(PathBuf, Format)>, Vec<String>> {
+    let invalid = || {
+        vec![format!(
+            "The `{}` key of config file {:?} must be a path or a list of paths.",
+            INCLUDE_KEY, path
+        )]
+    };
+    let entries = match include {
+        Value::String(entry) => vec![entry],
+        Value::Array(entries) => entries
+            .into_iter()
+            .map(|entry| match entry {
+                Value::String(entry) => Ok(entry),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_, _>>()?,
+        _ => return Err(invalid()),
+    };
+
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = base.join(entry);
+        if entry.is_dir() {
+            files.extend(dir_files(&entry)?);
+        } else {
+            let format = Format::from_path(&entry).map_err(|entry| {
+                vec![format!(
+                    "Unknown format of included config file: {:?}.",
+                    entry
+                )]
+            })?;
+            files.push((entry, format));
+        }
+    }
+    Ok(files)
+}
+
+/// Lists the config files directly contained in a directory, sorted by file name. Hidden files
+/// and files without a known extension are skipped.
+fn dir_files(path: &Path) -> Result<Vec<(PathBuf, Format)>, Vec<String>> {
+    let mut files = Vec::new();
+    for entry in read_dir(path)? {
+        let entry = entry
+            .map_err(|err| {
+                vec![format!(
+                    "Could not read entry in config dir: {:?}, {}.",
+                    path, err
+                )]
+            })?
+            .path();
+        let hidden = entry
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(true, |name| name.starts_with('.'));
+        if hidden || !entry.is_file() {
+            continue;
+        }
+        if let Ok(format) = Format::from_path(&entry) {
+            files.push((entry, format));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::loading::SourceLoader;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn key(key: &str) -> Vec<String> {
+        key.split('.').map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn merges_included_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "conf.d/10-base.toml",
+            indoc! {r#"
+                data_dir = "/var/lib/vector"
+                [sources.in]
+                type = "stdin"
+                decoding.codec = "bytes"
+            "#},
+        );
+        write(
+            dir.path(),
+            "conf.d/20-json.yaml",
+            indoc! {r#"
+                sources:
+                  in:
+                    decoding:
+                      codec: json
+            "#},
+        );
+        write(dir.path(), "conf.d/.hidden.toml", "data_dir = \"/hidden\"");
+        let main = write(
+            dir.path(),
+            "vector.toml",
+            indoc! {r#"
+                include = ["conf.d"]
+                data_dir = "/tmp/vector"
+            "#},
+        );
+
+        let (loaded, warnings) = load_file(&mut SourceLoader::new(), &main, Format::Toml).unwrap();
+        let expected: Table = toml::from_str(indoc! {r#"
+            data_dir = "/tmp/vector"
+            [sources.in]
+            type = "stdin"
+            decoding.codec = "json"
+        "#})
+        .unwrap();
+        assert_eq!(loaded.table, expected);
+
+        // The including file explicitly overrides its includes, but included files don't have a
+        // precedence over each other.
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`sources.in.decoding.codec`"));
+
+        let provenance = &loaded.provenance;
+        assert_eq!(provenance.get(&key("data_dir")), Some(main.as_path()));
+        assert_eq!(
+            provenance.get(&key("sources.in.type")),
+            Some(dir.path().join("conf.d/10-base.toml").as_path())
+        );
+        assert_eq!(
+            provenance.get(&key("sources.in.decoding.codec")),
+            Some(dir.path().join("conf.d/20-json.yaml").as_path())
+        );
+    }
+
+    #[test]
+    fn rejects_include_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = write(dir.path(), "a.toml", "include = [\"b.toml\"]");
+        write(dir.path(), "b.toml", "include = [\"a.toml\"]");
+
+        let errors = load_file(&mut SourceLoader::new(), &main, Format::Toml).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("includes itself"));
+    }
+
+    #[test]
+    fn rejects_table_value_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "sinks.toml", "sinks = \"none\"");
+        let main = write(
+            dir.path(),
+            "vector.toml",
+            indoc! {r#"
+                include = "sinks.toml"
+                [sinks.out]
+                type = "blackhole"
+            "#},
+        );
+
+        let errors = load_file(&mut SourceLoader::new(), &main, Format::Toml).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("`sinks`"));
+    }
+}
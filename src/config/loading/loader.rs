@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use serde_toml_merge::merge_into_table;
 use toml::value::{Table, Value};
 
-use super::{component_name, open_file, read_dir, Format};
+use super::{component_name, include, open_file, read_dir, Format};
 use crate::config::format;
 
 /// Provides a hint to the loading system of the type of components that should be found
@@ -227,11 +227,14 @@ where
     /// Consumes Self, and returns the final, deserialized `T`.
     fn take(self) -> T;
 
-    /// Deserializes a file with the provided format, and makes the result available via `take`.
-    /// Returns a vector of non-fatal warnings on success, or a vector of error strings on failure.
+    /// Deserializes a file with the provided format, along with the files it includes, and makes
+    /// the result available via `take`. Returns a vector of non-fatal warnings on success, or a
+    /// vector of error strings on failure.
     fn load_from_file(&mut self, path: &Path, format: Format) -> Result<Vec<String>, Vec<String>> {
-        if let Some((_, table, warnings)) = self.load_file(path, format)? {
-            self.merge(table, None)?;
+        if let Some((_, table, mut warnings)) = self.load_file(path, format)? {
+            let (loaded, warns) = include::resolve(self, path, table)?;
+            warnings.extend(warns);
+            self.merge(loaded.table, None)?;
             Ok(warnings)
         } else {
             Ok(vec![])
@@ -1,4 +1,5 @@
 mod config_builder;
+mod include;
 mod loader;
 mod secret;
 mod source;
@@ -14,6 +15,7 @@ use std::{
 use config_builder::ConfigBuilderLoader;
 pub use config_builder::*;
 use glob::glob;
+pub use include::{Provenance, SourcedTable};
 use loader::process::Process;
 pub use loader::*;
 pub use secret::*;
@@ -140,11 +142,13 @@ pub async fn load_from_paths_with_provider_and_secrets(
     let (mut secrets_backends_loader, secrets_warning) =
         load_secret_backends_from_paths(config_paths)?;
     // And then, if needed, retrieve secrets from configured backends
+    let mut secrets_refresh_after = None;
     let (mut builder, load_warnings) = if secrets_backends_loader.has_secrets_to_retrieve() {
         debug!(message = "Secret placeholders found, retrieving secrets from configured backends.");
         let resolved_secrets = secrets_backends_loader
             .retrieve(&mut signal_handler.subscribe())
             .map_err(|e| vec![e])?;
+        secrets_refresh_after = secrets_backends_loader.refresh_after();
         load_builder_from_paths_with_secrets(config_paths, resolved_secrets)?
     } else {
         debug!(message = "No secret placeholder found, skipping secret resolution.");
@@ -154,6 +158,19 @@ pub async fn load_from_paths_with_provider_and_secrets(
     validation::check_provider(&builder)?;
     signal_handler.clear();
 
+    // Reload the configuration before the retrieved secrets expire, retrieving them again. This
+    // replaces the refresh scheduled by the previous load, so that there's only ever one pending.
+    match secrets_refresh_after {
+        Some(refresh_after) => {
+            debug!(
+                message = "Scheduling secret refresh.",
+                refresh_after_secs = refresh_after.as_secs()
+            );
+            signal_handler.schedule_secrets_refresh(secret::refresh_secrets(refresh_after));
+        }
+        None => signal_handler.cancel_secrets_refresh(),
+    }
+
     // If there's a provider, overwrite the existing config builder with the remote variant.
     if let Some(mut provider) = builder.provider {
         builder = provider.build(signal_handler).await?;
@@ -164,6 +181,11 @@ pub async fn load_from_paths_with_provider_and_secrets(
 
     validation::check_buffer_preconditions(&new_config).await?;
 
+    // Reload the enrichment tables on their own schedule, if they have one.
+    if let Some(signals) = crate::enrichment_tables::reload_signals(&new_config.enrichment_tables) {
+        signal_handler.add(signals);
+    }
+
     for warning in secrets_warning
         .into_iter()
         .chain(load_warnings)
@@ -238,6 +260,46 @@ pub fn load_source_from_paths(
     loader_from_paths(SourceLoader::new(), config_paths)
 }
 
+/// Uses `SourceLoader` to process config files and the files they include, deep-merging them into
+/// a toml `SourcedTable` that records the file each value was set in.
+pub fn load_source_with_provenance_from_paths(
+    config_paths: &[ConfigPath],
+) -> Result<(SourcedTable, Vec<String>), Vec<String>> {
+    let mut loader = SourceLoader::new();
+    let mut result = SourcedTable::default();
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+
+    for config_path in config_paths {
+        let loaded = match config_path {
+            ConfigPath::File(path, format_hint) => include::load_file(
+                &mut loader,
+                path,
+                format_hint
+                    .or_else(move || Format::from_path(&path).ok())
+                    .unwrap_or_default(),
+            ),
+            ConfigPath::Dir(path) => Err(vec![format!(
+                "Config dir {:?} can't be rendered, include it from a config file instead.",
+                path
+            )]),
+        };
+        match loaded.and_then(|(table, warns)| {
+            warnings.extend(warns);
+            result.merge(table, false)
+        }) {
+            Ok(warns) => warnings.extend(warns),
+            Err(errs) => errors.extend(errs),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok((result, warnings))
+    } else {
+        Err(errors)
+    }
+}
+
 /// Uses `SecretBackendLoader` to process `ConfigPaths`, deserializing to a `SecretBackends`.
 pub fn load_secret_backends_from_paths(
     config_paths: &[ConfigPath],
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     io::Read,
+    time::Duration,
 };
 
 use indexmap::IndexMap;
@@ -82,6 +83,23 @@ impl SecretBackendLoader {
     pub(crate) fn has_secrets_to_retrieve(&self) -> bool {
         !self.secret_keys.is_empty()
     }
+
+    /// Returns how long until the first of the retrieved secrets expires, if any backend must
+    /// retrieve its secrets again before then.
+    pub(crate) fn refresh_after(&self) -> Option<Duration> {
+        self.secret_keys
+            .keys()
+            .filter_map(|backend_name| self.backends.get(&ComponentKey::from(backend_name.clone())))
+            .filter_map(SecretBackend::refresh_after)
+            .min()
+    }
+}
+
+/// Requests a reload of the configuration once the retrieved secrets must be refreshed.
+pub(crate) async fn refresh_secrets(refresh_after: Duration) -> signal::SignalTo {
+    tokio::time::sleep(refresh_after).await;
+    info!(message = "Refreshing secrets before they expire.");
+    signal::SignalTo::ReloadFromDisk
 }
 
 impl Process for SecretBackendLoader {
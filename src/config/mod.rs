@@ -7,7 +7,9 @@ use std::{
     path::PathBuf,
 };
 
+use codecs::decoding::DeserializerConfig;
 use indexmap::IndexMap;
+use vector_buffers::BufferType;
 pub use vector_config::component::{GenerateConfig, SinkDescription, TransformDescription};
 use vector_config::configurable_component;
 pub use vector_core::config::{
@@ -15,7 +17,9 @@ pub use vector_core::config::{
     SourceAcknowledgementsConfig,
 };
 
-use crate::{conditions, event::Metric, secrets::SecretBackends, serde::OneOrMany};
+use crate::{
+    codecs::can_pass_through, conditions, event::Metric, secrets::SecretBackends, serde::OneOrMany,
+};
 
 pub mod api;
 mod builder;
@@ -34,6 +38,7 @@ pub mod schema;
 mod secret;
 mod sink;
 mod source;
+mod tenant;
 mod transform;
 pub mod unit_test;
 mod validation;
@@ -43,18 +48,25 @@ pub mod watcher;
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
 pub use diff::ConfigDiff;
-pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter};
+pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter, ReloadOptions};
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, Inputs, OutputId};
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
-    load_from_str, load_source_from_paths, merge_path_lists, process_paths, CONFIG_PATHS,
+    load_from_str, load_source_from_paths, load_source_with_provenance_from_paths,
+    merge_path_lists, process_paths, Provenance, SourcedTable, CONFIG_PATHS,
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
-pub use sink::{SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
+pub use sink::{
+    OnSchemaViolation, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter,
+    SinkSchemaConfig, DLQ_OUTPUT, DROPPED_OUTPUT,
+};
 pub use source::{SourceConfig, SourceContext, SourceOuter};
-pub use transform::{BoxedTransform, TransformConfig, TransformContext, TransformOuter};
+pub use tenant::TenantConfig;
+pub use transform::{
+    BoxedTransform, TransformConfig, TransformContext, TransformOuter, TransformReload,
+};
 pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
 pub use validation::warnings;
 pub use vector_core::config::{log_schema, proxy::ProxyConfig, LogSchema};
@@ -103,10 +115,12 @@ pub struct Config {
     pub enterprise: Option<enterprise::Options>,
     pub global: GlobalOptions,
     pub healthchecks: HealthcheckOptions,
+    pub canary: CanaryOptions,
     sources: IndexMap<ComponentKey, SourceOuter>,
     sinks: IndexMap<ComponentKey, SinkOuter<OutputId>>,
     transforms: IndexMap<ComponentKey, TransformOuter<OutputId>>,
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
+    pub tenants: IndexMap<String, TenantConfig>,
     tests: Vec<TestDefinition>,
     secret: IndexMap<ComponentKey, SecretBackends>,
 }
@@ -132,6 +146,13 @@ impl Config {
         self.transforms.get(id)
     }
 
+    pub(crate) fn transform_mut(
+        &mut self,
+        id: &ComponentKey,
+    ) -> Option<&mut TransformOuter<OutputId>> {
+        self.transforms.get_mut(id)
+    }
+
     pub fn sinks(&self) -> impl Iterator<Item = (&ComponentKey, &SinkOuter<OutputId>)> {
         self.sinks.iter()
     }
@@ -140,6 +161,14 @@ impl Config {
         self.sinks.get(id)
     }
 
+    /// Enables the health checks of all sinks, including those disabled by the configuration.
+    pub fn enable_healthchecks(&mut self) {
+        self.healthchecks.enabled = true;
+        for sink in self.sinks.values_mut() {
+            sink.enable_healthcheck();
+        }
+    }
+
     pub fn inputs_for_node(&self, id: &ComponentKey) -> Option<&[OutputId]> {
         self.transforms
             .get(id)
@@ -167,6 +196,113 @@ impl Config {
         Ok(())
     }
 
+    /// Marks the sources whose frames can be relayed without being decoded.
+    ///
+    /// That is the case when the only consumers of the default output of a source are sinks
+    /// writing each event only by serializing it with a codec matching the decoding of the source,
+    /// keeping the events in memory, and whose own outputs aren't consumed.
+    pub fn propagate_pass_through(&mut self) {
+        // The inputs consumed from each component, along with their consumer.
+        let mut consumers = HashMap::<&ComponentKey, Vec<(&OutputId, &ComponentKey)>>::new();
+        let inputs = self
+            .transforms
+            .iter()
+            .map(|(key, transform)| (key, &transform.inputs))
+            .chain(self.sinks.iter().map(|(key, sink)| (key, &sink.inputs)));
+        for (key, inputs) in inputs {
+            for input in inputs.iter() {
+                consumers
+                    .entry(&input.component)
+                    .or_default()
+                    .push((input, key));
+            }
+        }
+
+        let writes_frames = |key: &ComponentKey, decoding: &DeserializerConfig| {
+            self.sinks.get(key).map_or(false, |sink| {
+                sink.inner
+                    .pass_through_serializer()
+                    .map_or(false, |serializer| can_pass_through(decoding, serializer))
+                    && sink.outputs().is_empty()
+                    && sink.schema.is_none()
+                    && sink.max_event_age_secs(&self.global).is_none()
+                    && sink
+                        .buffer
+                        .stages()
+                        .iter()
+                        .all(|stage| matches!(stage, BufferType::Memory { .. }))
+                    && !consumers.contains_key(key)
+            })
+        };
+        let eligible = self
+            .sources
+            .iter()
+            .filter(|(key, source)| {
+                source
+                    .inner
+                    .pass_through_decoding()
+                    .zip(consumers.get(key))
+                    .map_or(false, |(decoding, consumers)| {
+                        consumers.iter().all(|(input, consumer)| {
+                            input.port.is_none() && writes_frames(*consumer, &decoding)
+                        })
+                    })
+            })
+            .map(|(key, _)| key.clone())
+            .collect::<HashSet<_>>();
+
+        for (key, source) in self.sources.iter_mut() {
+            source.pass_through = eligible.contains(key);
+        }
+    }
+
+    /// Marks the sources whose progress is committed by a sink, checking that they can be.
+    ///
+    /// Committing the progress of a source relies on the acknowledgements of its events, and a
+    /// source can only have its progress committed by one sink.
+    pub fn propagate_links(&mut self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut linked = HashMap::<ComponentKey, &ComponentKey>::new();
+        for (key, sink) in self.sinks.iter() {
+            let id = match sink.inner.linked_source() {
+                Some(id) => id,
+                None => continue,
+            };
+            let source_key = ComponentKey::from(id);
+            let source = match self.sources.get(&source_key) {
+                Some(source) => source,
+                None => {
+                    errors.push(format!(
+                        "Sink {key} commits the progress of undefined source: `{id}`"
+                    ));
+                    continue;
+                }
+            };
+
+            if let Err(error) = source.inner.check_linked() {
+                errors.push(format!(
+                    "Sink {key} can't commit the progress of source `{id}`: {error}"
+                ));
+            } else if !source.sink_acknowledgements {
+                errors.push(format!(
+                    "Sink {key} can't commit the progress of source `{id}`: end-to-end acknowledgements aren't enabled for the source"
+                ));
+            } else if let Some(other) = linked.insert(source_key, key) {
+                errors.push(format!(
+                    "Sinks {other} and {key} both commit the progress of source `{id}`"
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        for (key, source) in self.sources.iter_mut() {
+            source.linked = linked.contains_key(key);
+        }
+        Ok(())
+    }
+
     fn propagate_acks_rec(&mut self, sink_inputs: Vec<(ComponentKey, OutputId)>) {
         for (sink, input) in sink_inputs {
             let component = &input.component;
@@ -232,6 +368,56 @@ impl Default for HealthcheckOptions {
     }
 }
 
+/// Canary options of the configuration reloads.
+///
+/// When enabled, the new and changed transforms and sinks of a reloaded configuration are first
+/// built alongside the running topology, and the healthchecks of the sinks have to pass, even if
+/// healthy sinks aren't otherwise required. Live traffic can then be mirrored through the
+/// transforms for a while. If any of it fails, the reload is aborted and the running topology is
+/// left untouched.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct CanaryOptions {
+    /// Whether or not to try out the changed components of a configuration before reloading it.
+    pub enabled: bool,
+
+    /// How long, in seconds, to mirror live traffic through the changed transforms.
+    ///
+    /// The mirrored events are dropped once through the transforms: the changed sinks are only
+    /// built and health checked, so that no event is sent twice to their destinations. A transform
+    /// failing or stopping during this time aborts the reload.
+    ///
+    /// When set to `0`, no traffic is mirrored.
+    pub mirror_secs: u64,
+
+    /// The ratio of the batches of live events to mirror, between `0` and `1`.
+    ///
+    /// The mirrored batches are dropped when the changed transforms can't keep up with them, so
+    /// that they never slow down the running topology.
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub mirror_ratio: f64,
+}
+
+impl CanaryOptions {
+    fn merge(&mut self, other: Self) {
+        // The options are the ones of the last configuration enabling canary reloads.
+        if other.enabled {
+            *self = other;
+        }
+    }
+}
+
+impl Default for CanaryOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mirror_secs: 0,
+            mirror_ratio: 0.1,
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! impl_generate_config_from_default {
     ($type:ty) => {
@@ -378,19 +564,22 @@ impl TestDefinition<String> {
                 let TestOutput {
                     extract_from,
                     conditions,
+                    metrics,
                 } = old;
 
-                (extract_from.to_vec(), conditions)
+                (extract_from.to_vec(), conditions, metrics)
             })
-            .filter_map(|(extract_from, conditions)| {
+            .filter_map(|(extract_from, conditions, metrics)| {
                 let mut outputs = Vec::new();
                 for from in extract_from {
                     if let Some(output_id) = output_map.get(&from) {
                         outputs.push(output_id.clone());
                     } else {
                         errors.push(format!(
-                            r#"Invalid extract_from target in test '{}': '{}' does not exist"#,
-                            name, from
+                            r#"Invalid extract_from target in test '{}': '{}' does not exist{}"#,
+                            name,
+                            from,
+                            available_outputs_hint(&from, output_map.keys())
                         ));
                     }
                 }
@@ -400,6 +589,7 @@ impl TestDefinition<String> {
                     Some(TestOutput {
                         extract_from: outputs.into(),
                         conditions,
+                        metrics,
                     })
                 }
             })
@@ -412,8 +602,10 @@ impl TestDefinition<String> {
                     Some(output_id.clone())
                 } else {
                     errors.push(format!(
-                        r#"Invalid no_outputs_from target in test '{}': '{}' does not exist"#,
-                        name, o
+                        r#"Invalid no_outputs_from target in test '{}': '{}' does not exist{}"#,
+                        name,
+                        o,
+                        available_outputs_hint(&o, output_map.keys())
                     ));
                     None
                 }
@@ -434,6 +626,33 @@ impl TestDefinition<String> {
     }
 }
 
+/// Lists the outputs of the component of a missing output, such as when the output of a transform
+/// is misspelled, or when the transform only has it with some options enabled.
+fn available_outputs_hint<'a>(output: &str, outputs: impl Iterator<Item = &'a String>) -> String {
+    let component = match output.rsplit_once('.') {
+        Some((component, _)) => component,
+        None => return String::new(),
+    };
+    let mut available = outputs
+        .filter(|candidate| {
+            candidate.as_str() == component
+                || candidate
+                    .strip_prefix(component)
+                    .map_or(false, |port| port.starts_with('.'))
+        })
+        .map(|candidate| format!("'{}'", candidate))
+        .collect::<Vec<_>>();
+    if available.is_empty() {
+        return String::new();
+    }
+    available.sort();
+    format!(
+        ", the outputs of '{}' are: {}",
+        component,
+        available.join(", ")
+    )
+}
+
 impl TestDefinition<OutputId> {
     fn stringify(self) -> TestDefinition<String> {
         let TestDefinition {
@@ -455,6 +674,7 @@ impl TestDefinition<OutputId> {
                     .collect::<Vec<_>>()
                     .into(),
                 conditions: old.conditions,
+                metrics: old.metrics,
             })
             .collect();
 
@@ -539,6 +759,39 @@ pub struct TestOutput<T: 'static = OutputId> {
 
     /// The conditions to run against the output to validate that they were transformed as expected.
     pub conditions: Option<Vec<conditions::AnyCondition>>,
+
+    /// The metrics expected in the output.
+    ///
+    /// Each of them must match one of the output events. Unlike conditions, these can check the
+    /// value of the metrics.
+    #[serde(default)]
+    pub metrics: Vec<TestMetric>,
+}
+
+/// A metric expected in the output of a unit test.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TestMetric {
+    /// The name of the metric.
+    pub name: String,
+
+    /// The namespace of the metric.
+    ///
+    /// If not set, the namespace of the metric isn't checked.
+    pub namespace: Option<String>,
+
+    /// The tags the metric must have, along with their values.
+    ///
+    /// The metric may have other tags.
+    #[serde(default)]
+    pub tags: IndexMap<String, String>,
+
+    /// The value of the metric.
+    ///
+    /// Only counters and gauges have a single value to check. If not set, the value of the metric
+    /// isn't checked.
+    pub value: Option<f64>,
 }
 
 #[cfg(all(test, feature = "sources-file", feature = "sinks-console"))]
@@ -637,6 +890,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn undefined_tenant() {
+        let err = load(
+            r#"
+            [tenants.team_a]
+            max_events_per_sec = 100
+
+            [sources.a]
+            type = "test_basic"
+            tenant = "team_a"
+
+            [sources.b]
+            type = "test_basic"
+            tenant = "team_b"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["a", "b"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err, vec!["Source b references undefined tenant: `team_b`"]);
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn conflicting_stdin_and_fd_resources() {
@@ -942,6 +1222,39 @@ mod tests {
         assert_eq!(config.tests.len(), 1);
     }
 
+    #[test]
+    fn config_append_canary() {
+        let mut config: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                [canary]
+                  enabled = true
+                  mirror_secs = 60
+                  mirror_ratio = 0.5
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        config
+            .append(
+                format::deserialize(
+                    indoc! {r#"
+                        [canary]
+                          enabled = true
+                          mirror_secs = 10
+                    "#},
+                    Format::Toml,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        config.append(ConfigBuilder::default()).unwrap();
+
+        assert!(config.canary.enabled);
+        assert_eq!(config.canary.mirror_secs, 10);
+        assert_eq!(config.canary.mirror_ratio, 0.1);
+    }
+
     #[test]
     fn config_append_collisions() {
         let mut config: ConfigBuilder = format::deserialize(
@@ -1272,6 +1585,174 @@ mod acknowledgements_tests {
     }
 }
 
+#[cfg(all(test, feature = "sources-socket", feature = "sinks-file"))]
+mod pass_through_tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn propagates_pass_through() {
+        // The topology:
+        // in1 => out1
+        // in2 => parse2 => out2
+        // in3 (json) => out3 (json)
+        // in4 => out4, out4_dynamic (templated path)
+        // in5 => out5 (rerouting failed events)
+        let config: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                [sources.in1]
+                    type = "socket"
+                    mode = "tcp"
+                    address = "0.0.0.0:9001"
+                [sources.in2]
+                    type = "socket"
+                    mode = "tcp"
+                    address = "0.0.0.0:9002"
+                [sources.in3]
+                    type = "socket"
+                    mode = "tcp"
+                    address = "0.0.0.0:9003"
+                    decoding.codec = "json"
+                [sources.in4]
+                    type = "socket"
+                    mode = "tcp"
+                    address = "0.0.0.0:9004"
+                [sources.in5]
+                    type = "socket"
+                    mode = "tcp"
+                    address = "0.0.0.0:9005"
+                [transforms.parse2]
+                    type = "test_basic"
+                    inputs = ["in2"]
+                    increase = 0.0
+                    suffix = ""
+                [sinks.out1]
+                    type = "file"
+                    inputs = ["in1"]
+                    encoding.codec = "text"
+                    path = "/path/to/out1"
+                [sinks.out2]
+                    type = "file"
+                    inputs = ["parse2"]
+                    encoding.codec = "text"
+                    path = "/path/to/out2"
+                [sinks.out3]
+                    type = "file"
+                    inputs = ["in3"]
+                    encoding.codec = "json"
+                    path = "/path/to/out3"
+                [sinks.out4]
+                    type = "file"
+                    inputs = ["in4"]
+                    encoding.codec = "text"
+                    path = "/path/to/out4"
+                [sinks.out4_dynamic]
+                    type = "file"
+                    inputs = ["in4"]
+                    encoding.codec = "text"
+                    path = "/path/to/{{ host }}"
+                [sinks.out5]
+                    type = "file"
+                    inputs = ["in5"]
+                    encoding.codec = "text"
+                    path = "/path/to/out5"
+                    reroute_failed = true
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        let config = config.build().unwrap();
+
+        let get = |key: &str| config.sources.get(&ComponentKey::from(key)).unwrap();
+        assert!(get("in1").pass_through);
+        for key in ["in2", "in3", "in4", "in5"] {
+            assert!(!get(key).pass_through, "{} should not pass through", key);
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-kafka",
+    feature = "sources-file",
+    feature = "sinks-kafka"
+))]
+mod link_tests {
+    use indoc::{formatdoc, indoc};
+
+    use super::*;
+
+    fn load(sink: &str) -> Result<Config, Vec<String>> {
+        load_from_str(
+            &formatdoc! {r#"
+                [sources.kafka_in]
+                    type = "kafka"
+                    bootstrap_servers = "localhost:9092"
+                    group_id = "vector"
+                    topics = ["logs"]
+                [sources.assigned]
+                    type = "kafka"
+                    bootstrap_servers = "localhost:9092"
+                    topics = ["logs"]
+                    assignments = [{{ topic = "logs", partition = 0 }}]
+                [sources.file_in]
+                    type = "file"
+                    include = ["/var/log/**/*.log"]
+                [sinks.out]
+                    type = "kafka"
+                    inputs = ["kafka_in", "assigned", "file_in"]
+                    bootstrap_servers = "localhost:9092"
+                    topic = "out"
+                    encoding.codec = "json"
+                    transactional_id = "vector-0"
+                {sink}
+            "#},
+            Format::Toml,
+        )
+    }
+
+    #[test]
+    fn propagates_links() {
+        let config = load(indoc! {r#"
+            transactional_source = "kafka_in"
+            acknowledgements = true
+        "#})
+        .unwrap();
+
+        let get = |key: &str| config.sources.get(&ComponentKey::from(key)).unwrap();
+        assert!(get("kafka_in").linked);
+        assert!(!get("assigned").linked);
+        assert!(!get("file_in").linked);
+    }
+
+    #[test]
+    fn rejects_invalid_links() {
+        let error = |sink: &str| load(sink).unwrap_err().join("\n");
+
+        assert!(error(indoc! {r#"
+            transactional_source = "kafka_typo"
+            acknowledgements = true
+        "#})
+        .contains("undefined source: `kafka_typo`"));
+        assert!(error(indoc! {r#"
+            transactional_source = "file_in"
+            acknowledgements = true
+        "#})
+        .contains("the source doesn't support it"));
+        assert!(error(indoc! {r#"
+            transactional_source = "assigned"
+            acknowledgements = true
+        "#})
+        .contains("doesn't use a consumer group"));
+        assert!(error(indoc! {r#"
+            transactional_source = "kafka_in"
+        "#})
+        .contains("end-to-end acknowledgements aren't enabled"));
+    }
+}
+
 #[cfg(test)]
 mod resource_tests {
     use std::{
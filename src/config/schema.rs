@@ -18,6 +18,14 @@ pub struct Options {
 
     /// Whether or not to enable log namespacing.
     pub log_namespace: Option<bool>,
+
+    /// Whether or not to attach source origin metadata to every event.
+    ///
+    /// When enabled, every event emitted by a source carries `%vector.source_type`,
+    /// `%vector.component_id`, and `%vector.ingest_timestamp` in its metadata, regardless of the
+    /// log namespace in use. Fields already set by the source are left untouched.
+    #[serde(default = "default_origin_metadata")]
+    pub origin_metadata: bool,
 }
 
 impl Options {
@@ -47,6 +55,7 @@ impl Options {
         // If either config enables these flags, it is enabled.
         self.enabled |= with.enabled;
         self.validation |= with.validation;
+        self.origin_metadata |= with.origin_metadata;
     }
 }
 
@@ -56,6 +65,7 @@ impl Default for Options {
             enabled: default_enabled(),
             validation: default_validation(),
             log_namespace: None,
+            origin_metadata: default_origin_metadata(),
         }
     }
 }
@@ -68,6 +78,10 @@ const fn default_validation() -> bool {
     false
 }
 
+const fn default_origin_metadata() -> bool {
+    false
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -81,16 +95,19 @@ mod test {
                     enabled: false,
                     validation: false,
                     log_namespace: None,
+                    origin_metadata: false,
                 },
                 Options {
                     enabled: false,
                     validation: false,
                     log_namespace: Some(true),
+                    origin_metadata: false,
                 },
                 Some(Options {
                     enabled: false,
                     validation: false,
                     log_namespace: Some(true),
+                    origin_metadata: false,
                 }),
             ),
             (
@@ -99,11 +116,13 @@ mod test {
                     enabled: false,
                     validation: false,
                     log_namespace: Some(false),
+                    origin_metadata: false,
                 },
                 Options {
                     enabled: false,
                     validation: false,
                     log_namespace: Some(true),
+                    origin_metadata: false,
                 },
                 None,
             ),
@@ -113,16 +132,19 @@ mod test {
                     enabled: false,
                     validation: false,
                     log_namespace: None,
+                    origin_metadata: false,
                 },
                 Options {
                     enabled: true,
                     validation: false,
                     log_namespace: None,
+                    origin_metadata: false,
                 },
                 Some(Options {
                     enabled: true,
                     validation: false,
                     log_namespace: None,
+                    origin_metadata: false,
                 }),
             ),
             (
@@ -131,16 +153,40 @@ mod test {
                     enabled: false,
                     validation: false,
                     log_namespace: None,
+                    origin_metadata: false,
                 },
                 Options {
                     enabled: false,
                     validation: true,
                     log_namespace: None,
+                    origin_metadata: false,
                 },
                 Some(Options {
                     enabled: false,
                     validation: true,
                     log_namespace: None,
+                    origin_metadata: false,
+                }),
+            ),
+            (
+                "enable origin metadata",
+                Options {
+                    enabled: false,
+                    validation: false,
+                    log_namespace: None,
+                    origin_metadata: false,
+                },
+                Options {
+                    enabled: false,
+                    validation: false,
+                    log_namespace: None,
+                    origin_metadata: true,
+                },
+                Some(Options {
+                    enabled: false,
+                    validation: false,
+                    log_namespace: None,
+                    origin_metadata: true,
                 }),
             ),
         ] {
@@ -1,4 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use enum_dispatch::enum_dispatch;
 use vector_config::NamedComponent;
@@ -13,4 +16,10 @@ pub trait SecretBackend: NamedComponent + core::fmt::Debug + Send + Sync {
         secret_keys: HashSet<String>,
         signal_rx: &mut signal::SignalRx,
     ) -> crate::Result<HashMap<String, String>>;
+
+    /// Returns how long until the last retrieved secrets expire, if they must be retrieved again
+    /// before then.
+    fn refresh_after(&self) -> Option<Duration> {
+        None
+    }
 }
@@ -1,15 +1,34 @@
+use std::path::PathBuf;
+
 use async_trait::async_trait;
+use codecs::encoding::SerializerConfig;
 use enum_dispatch::enum_dispatch;
 use serde::Serialize;
 use vector_buffers::{BufferConfig, BufferType};
 use vector_config::{configurable_component, Configurable, NamedComponent};
 use vector_core::{
-    config::{AcknowledgementsConfig, GlobalOptions, Input},
+    config::{AcknowledgementsConfig, GlobalOptions, Input, Output},
     sink::VectorSink,
 };
 
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{util::UriSerde, Healthcheck, Sinks};
+use crate::{
+    http::RequestLogConfig,
+    sinks::{
+        util::{dynamic_limits::ScheduledLimitsConfig, UriSerde},
+        Healthcheck, Sinks,
+    },
+};
+
+/// The name of the output that sinks reroute the events they failed to deliver to.
+pub const DLQ_OUTPUT: &str = "dlq";
+
+/// The name of the output that sinks route the events violating their schema to.
+pub const DROPPED_OUTPUT: &str = "dropped";
+
+const fn default_reroute_failed_max_pending() -> usize {
+    100_000
+}
 
 /// Fully resolved sink component.
 #[configurable_component]
@@ -47,6 +66,72 @@ where
     )]
     proxy: ProxyConfig,
 
+    /// Reroutes the events the sink failed to deliver to a named output.
+    ///
+    /// Normally, events that the sink rejects, or that still fail once its retries are
+    /// exhausted, are dropped. In some cases, it may be desirable to keep the events around for
+    /// further analysis, debugging, or retrying.
+    ///
+    /// In these cases, `reroute_failed` can be set to `true` which forwards the original event
+    /// to a specially-named output, `dlq`, that other components can consume by using
+    /// `<sink_id>.dlq` as an input. The event is annotated with additional fields describing the
+    /// sink that failed to deliver it, whether the failure was transient (`errored`) or
+    /// permanent (`rejected`), and, when the sink recorded them, the `status_code` and
+    /// `error_class` of the last failed attempt and the number of `attempts`. Wildcards in
+    /// `inputs` never match `dlq` outputs.
+    ///
+    /// A copy of every event is kept until the sink finalizes it, which increases the memory
+    /// used by the sink, up to `reroute_failed_max_pending` copies.
+    #[serde(default = "crate::serde::default_false")]
+    #[configurable(metadata(docs::advanced))]
+    pub reroute_failed: bool,
+
+    /// The maximum number of copies of events kept by `reroute_failed` at once.
+    ///
+    /// The events going into the sink while this many are pending are not rerouted if they fail.
+    /// They are counted in the `dlq_untracked_events_total` internal metric.
+    #[serde(default = "default_reroute_failed_max_pending")]
+    #[configurable(metadata(docs::advanced, docs::type_unit = "events"))]
+    pub reroute_failed_max_pending: usize,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SinkSchemaConfig>,
+
+    /// The maximum age, in seconds, of the events sent by this sink.
+    ///
+    /// Overrides the global `max_event_age_secs` option for this sink. The events whose timestamp
+    /// is older than this, once they go into the sink, are dropped instead of being sent. The
+    /// dropped events are counted in the `dropped_stale_events_total` internal metric.
+    #[configurable(metadata(docs::advanced, docs::examples = 86400))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_event_age_secs: Option<u64>,
+
+    /// The rate limit, in seconds, of the internal logs emitted by this component.
+    ///
+    /// Overrides the `--internal-log-rate-limit` option for this component. Each rate limited
+    /// internal log is emitted at most once per this many seconds, after which a summary of how
+    /// many times it was suppressed is emitted. The suppressed logs are counted in the
+    /// `internal_logs_suppressed_total` internal metric.
+    #[configurable(metadata(docs::advanced, docs::examples = 60))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_log_rate_limit_secs: Option<u64>,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_log: Option<RequestLogConfig>,
+
+    /// Limits of the sink applied during periods of the day.
+    ///
+    /// Each period replaces the batch and concurrency limits it sets, from its `start` until its
+    /// `end`. When periods overlap, the first one applies. The limits can also be changed while the
+    /// sink runs through the `setComponentOption` mutation of the API, with the options
+    /// `batch.max_bytes`, `batch.max_events`, `batch.timeout_ms` and `request.concurrency`, which
+    /// take precedence over the schedule.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub limit_schedule: Vec<ScheduledLimitsConfig>,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: Sinks,
@@ -68,7 +153,41 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            reroute_failed: false,
+            reroute_failed_max_pending: default_reroute_failed_max_pending(),
+            schema: None,
+            max_event_age_secs: None,
+            internal_log_rate_limit_secs: None,
+            request_log: None,
+            limit_schedule: Vec::new(),
+        }
+    }
+
+    pub fn outputs(&self) -> Vec<Output> {
+        let mut outputs = Vec::new();
+        if self.reroute_failed {
+            outputs.push(Output::default(self.inner.input().data_type()).with_port(DLQ_OUTPUT));
+        }
+        if self.routes_dropped() {
+            outputs.push(Output::default(self.inner.input().data_type()).with_port(DROPPED_OUTPUT));
         }
+        outputs
+    }
+
+    /// Whether the events violating the schema of the sink are routed to its `dropped` output.
+    pub const fn routes_dropped(&self) -> bool {
+        matches!(
+            self.schema,
+            Some(SinkSchemaConfig {
+                on_violation: OnSchemaViolation::RouteToDropped,
+                ..
+            })
+        )
+    }
+
+    /// The maximum age of the events sent by the sink, overriding the global one.
+    pub fn max_event_age_secs(&self, globals: &GlobalOptions) -> Option<u64> {
+        self.max_event_age_secs.or(globals.max_event_age_secs)
     }
 
     pub fn resources(&self, id: &ComponentKey) -> Vec<Resource> {
@@ -76,7 +195,9 @@ where
         for stage in self.buffer.stages() {
             match stage {
                 BufferType::Memory { .. } => {}
-                BufferType::DiskV2 { .. } => resources.push(Resource::DiskBuffer(id.to_string())),
+                BufferType::DiskV2 { .. } | BufferType::Tiered { .. } => {
+                    resources.push(Resource::DiskBuffer(id.to_string()))
+                }
             }
         }
         resources
@@ -100,6 +221,10 @@ where
         }
     }
 
+    pub(super) fn enable_healthcheck(&mut self) {
+        self.healthcheck.enabled = true;
+    }
+
     pub const fn proxy(&self) -> &ProxyConfig {
         &self.proxy
     }
@@ -124,10 +249,69 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            reroute_failed: self.reroute_failed,
+            reroute_failed_max_pending: self.reroute_failed_max_pending,
+            schema: self.schema,
+            max_event_age_secs: self.max_event_age_secs,
+            internal_log_rate_limit_secs: self.internal_log_rate_limit_secs,
+            request_log: self.request_log,
+            limit_schedule: self.limit_schedule,
         }
     }
 }
 
+/// Validation of the events going into the sink against a schema.
+///
+/// Only log events are validated. The schema is a [JSON Schema][json_schema], which is checked
+/// against the fields of each event before it is encoded. The `type`, `properties`, `required`,
+/// `additionalProperties`, `items`, `enum`, and `const` keywords are supported, and any other
+/// keyword affecting validation is rejected when the sink is built.
+///
+/// [json_schema]: https://json-schema.org/
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SinkSchemaConfig {
+    /// The path to a file holding the schema, in JSON.
+    ///
+    /// Either `path` or `definition` must be set.
+    #[configurable(metadata(docs::examples = "/etc/vector/schemas/orders.json"))]
+    pub path: Option<PathBuf>,
+
+    /// The schema, given inline.
+    ///
+    /// Either `path` or `definition` must be set.
+    pub definition: Option<toml::Value>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_violation: OnSchemaViolation,
+}
+
+/// What to do with the events violating the schema.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnSchemaViolation {
+    /// Drop the event, rejecting it.
+    #[default]
+    Drop,
+
+    /// Route the event to the `dropped` output of the sink, instead of sending it.
+    ///
+    /// The event is annotated with the reason it violates the schema, and can be consumed by
+    /// other components by using `<sink_id>.dropped` as an input. Wildcards in `inputs` never
+    /// match `dropped` outputs.
+    RouteToDropped,
+
+    /// Attempt to repair the event, then drop it if it still violates the schema.
+    ///
+    /// Values of the wrong type are converted to the expected type when possible, such as
+    /// `"42"` to `42` for an `integer` field. Fields not allowed by `additionalProperties` are
+    /// removed. Missing required fields can't be repaired.
+    Coerce,
+}
+
 /// Healthcheck configuration.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -199,6 +383,21 @@ pub trait SinkConfig: NamedComponent + core::fmt::Debug + Send + Sync {
 
     /// Gets the acknowledgements configuration for this sink.
     fn acknowledgements(&self) -> &AcknowledgementsConfig;
+
+    /// Gets the ID of the source whose progress this sink commits along with the events it
+    /// sends, if any, such as the consumer offsets of a `kafka` source committed in the
+    /// transactions of a `kafka` sink.
+    fn linked_source(&self) -> Option<&str> {
+        None
+    }
+
+    /// Gets the serializer of this sink, if it writes each event only by serializing it.
+    ///
+    /// Such sinks write the frames relayed without being decoded as they are, which lets the
+    /// sources whose only consumers are sinks with a matching codec skip decoding the frames.
+    fn pass_through_serializer(&self) -> Option<&SerializerConfig> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
+use codecs::decoding::DeserializerConfig;
 use enum_dispatch::enum_dispatch;
 use vector_config::{configurable_component, NamedComponent};
 use vector_core::{
@@ -11,7 +12,7 @@ use vector_core::{
 };
 
 use super::{schema, ComponentKey, ProxyConfig, Resource};
-use crate::{shutdown::ShutdownSignal, sources::Sources, SourceSender};
+use crate::{recording::RecordConfig, shutdown::ShutdownSignal, sources::Sources, SourceSender};
 
 /// Fully resolved source component.
 #[configurable_component]
@@ -25,9 +26,37 @@ pub struct SourceOuter {
     )]
     pub proxy: ProxyConfig,
 
+    /// The rate limit, in seconds, of the internal logs emitted by this component.
+    ///
+    /// Overrides the `--internal-log-rate-limit` option for this component. Each rate limited
+    /// internal log is emitted at most once per this many seconds, after which a summary of how
+    /// many times it was suppressed is emitted. The suppressed logs are counted in the
+    /// `internal_logs_suppressed_total` internal metric.
+    #[configurable(metadata(docs::advanced, docs::examples = 60))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_log_rate_limit_secs: Option<u64>,
+
+    /// The tenant this source belongs to.
+    ///
+    /// The events sent by this source count towards the resource quotas of the tenant, as defined
+    /// in the top-level `tenants` section.
+    #[configurable(metadata(docs::advanced, docs::examples = "team_a"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record: Option<RecordConfig>,
+
     #[serde(default, skip)]
     pub sink_acknowledgements: bool,
 
+    #[serde(default, skip)]
+    pub pass_through: bool,
+
+    #[serde(default, skip)]
+    pub linked: bool,
+
     #[configurable(metadata(docs::hidden))]
     #[serde(flatten)]
     pub(crate) inner: Sources,
@@ -37,7 +66,12 @@ impl SourceOuter {
     pub(crate) fn new<I: Into<Sources>>(inner: I) -> Self {
         Self {
             proxy: Default::default(),
+            internal_log_rate_limit_secs: None,
+            tenant: None,
+            record: None,
             sink_acknowledgements: false,
+            pass_through: false,
+            linked: false,
             inner: inner.into(),
         }
     }
@@ -84,6 +118,41 @@ pub trait SourceConfig: NamedComponent + core::fmt::Debug + Send + Sync {
     /// well as emit contextual warnings when end-to-end acknowledgements are enabled, but the
     /// topology as configured does not actually support the use of end-to-end acknowledgements.
     fn can_acknowledge(&self) -> bool;
+
+    /// Checks that the source can connect and authenticate to the systems it reads events from,
+    /// without reading any events.
+    ///
+    /// This is only run on demand, such as by `vector validate --check-connectivity`, as sources
+    /// otherwise report these errors once they are running. Sources that don't connect to other
+    /// systems, such as those listening on a socket, return `None`.
+    async fn healthcheck(&self, _proxy: &ProxyConfig) -> Option<crate::Result<()>> {
+        None
+    }
+
+    /// Gets the decoding of the frames this source can relay without decoding them, if it can.
+    ///
+    /// When all the consumers of the source are sinks writing the events as their codec would
+    /// have decoded them, the source is built with [`SourceContext::pass_through`] set, and relays
+    /// the frames as they are instead.
+    fn pass_through_decoding(&self) -> Option<DeserializerConfig> {
+        None
+    }
+
+    /// Checks that a sink can commit the progress of this source along with the events it sends,
+    /// as it does for the source returned by [`SinkConfig::linked_source`].
+    ///
+    /// The source is then built with [`SourceContext::linked`] set, and leaves committing its
+    /// progress to the sink.
+    ///
+    /// # Errors
+    ///
+    /// If the progress of the source can't be committed by a sink, an error explaining why is
+    /// returned.
+    ///
+    /// [`SinkConfig::linked_source`]: super::SinkConfig::linked_source
+    fn check_linked(&self) -> Result<(), String> {
+        Err("the source doesn't support it".to_owned())
+    }
 }
 
 pub struct SourceContext {
@@ -95,6 +164,14 @@ pub struct SourceContext {
     pub acknowledgements: bool,
     pub schema: schema::Options,
 
+    /// Whether to relay the frames without decoding them, as all the consumers of the source
+    /// write them as they are.
+    pub pass_through: bool,
+
+    /// Whether a sink commits the progress of the source along with the events it sends, instead
+    /// of the source committing it itself.
+    pub linked: bool,
+
     /// Tracks the schema IDs assigned to schemas exposed by the source.
     ///
     /// Given a source can expose multiple [`Output`] channels, the ID is tied to the identifier of
@@ -120,6 +197,8 @@ impl SourceContext {
                 acknowledgements: false,
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
+                pass_through: false,
+                linked: false,
             },
             shutdown,
         )
@@ -139,6 +218,8 @@ impl SourceContext {
             acknowledgements: false,
             schema_definitions: schema_definitions.unwrap_or_default(),
             schema: Default::default(),
+            pass_through: false,
+            linked: false,
         }
     }
 
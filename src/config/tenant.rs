@@ -0,0 +1,27 @@
+use std::num::{NonZeroU32, NonZeroUsize};
+
+use vector_config::configurable_component;
+
+/// Resource quotas shared by all the sources assigned to a tenant.
+///
+/// Each tenant's quotas are enforced independently, so one tenant exhausting its quota only
+/// applies backpressure to its own sources.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantConfig {
+    /// The maximum number of events per second sent by all the sources of the tenant combined.
+    ///
+    /// Bursts of up to one second's worth of events are allowed. Sources that exceed the rate are
+    /// slowed down rather than having their events dropped.
+    #[configurable(metadata(docs::examples = 10000))]
+    pub max_events_per_sec: Option<NonZeroU32>,
+
+    /// The maximum number of bytes of the tenant's events held in memory across the topology.
+    ///
+    /// An event counts towards this limit from the time its source sends it until every sink it
+    /// is routed to has finished processing it, including while it sits in a sink's buffer.
+    /// Sources of the tenant wait for in-flight events to be processed before sending more.
+    #[configurable(metadata(docs::type_unit = "bytes", docs::examples = 104857600))]
+    pub max_buffer_bytes: Option<NonZeroUsize>,
+}
@@ -56,6 +56,16 @@ where
     #[configurable(derived)]
     pub inputs: Inputs<T>,
 
+    /// The rate limit, in seconds, of the internal logs emitted by this component.
+    ///
+    /// Overrides the `--internal-log-rate-limit` option for this component. Each rate limited
+    /// internal log is emitted at most once per this many seconds, after which a summary of how
+    /// many times it was suppressed is emitted. The suppressed logs are counted in the
+    /// `internal_logs_suppressed_total` internal metric.
+    #[configurable(metadata(docs::advanced, docs::examples = 60))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_log_rate_limit_secs: Option<u64>,
+
     #[configurable(metadata(docs::hidden))]
     #[serde(flatten)]
     pub inner: BoxedTransform,
@@ -72,7 +82,11 @@ where
     {
         let inputs = Inputs::from_iter(inputs);
         let inner = inner.into();
-        TransformOuter { inputs, inner }
+        TransformOuter {
+            inputs,
+            internal_log_rate_limit_secs: None,
+            inner,
+        }
     }
 
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> TransformOuter<U>
@@ -90,6 +104,7 @@ where
     {
         TransformOuter {
             inputs: Inputs::from_iter(inputs),
+            internal_log_rate_limit_secs: self.internal_log_rate_limit_secs,
             inner: self.inner,
         }
     }
@@ -120,6 +135,12 @@ pub struct TransformContext {
     pub merged_schema_definition: schema::Definition,
 
     pub schema: SchemaOptions,
+
+    /// Whether the transform is only tried out by a canary before the configuration is reloaded.
+    ///
+    /// The state the transform persists belongs to its running instance, if any, so it mustn't be
+    /// loaded or saved by the one tried out alongside it.
+    pub canary: bool,
 }
 
 impl Default for TransformContext {
@@ -131,10 +152,45 @@ impl Default for TransformContext {
             schema_definitions: HashMap::from([(None, schema::Definition::any())]),
             merged_schema_definition: schema::Definition::any(),
             schema: SchemaOptions::default(),
+            canary: false,
         }
     }
 }
 
+/// A change to a running transform, prepared by [`TransformConfig::reload`].
+///
+/// The change isn't applied until the reload of the topology it's part of succeeds, so that the
+/// running instance is left as is if the reload is rolled back.
+pub struct TransformReload {
+    config: BoxedTransform,
+    apply: Box<dyn FnOnce() + Send>,
+}
+
+impl TransformReload {
+    /// Creates a change which, once applied with `apply`, makes the running instance match
+    /// `config`.
+    pub fn new(config: BoxedTransform, apply: impl FnOnce() + Send + 'static) -> Self {
+        Self {
+            config,
+            apply: Box::new(apply),
+        }
+    }
+
+    /// Applies the change to the running instance, returning the configuration it now matches.
+    pub fn apply(self) -> BoxedTransform {
+        (self.apply)();
+        self.config
+    }
+}
+
+impl core::fmt::Debug for TransformReload {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TransformReload")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
 impl TransformContext {
     // clippy allow avoids an issue where vrl is flagged off and `globals` is
     // the sole field in the struct
@@ -217,6 +273,25 @@ pub trait TransformConfig: DynClone + NamedComponent + core::fmt::Debug + Send +
         false
     }
 
+    /// Prepares applying a new configuration to the running instance of this transform, without
+    /// rebuilding it.
+    ///
+    /// This is only attempted when the inputs and outputs of the transform are unchanged. If the
+    /// change can be applied, it's returned, to be applied once the reload of the topology
+    /// succeeds. Otherwise, `Ok(None)` is returned and the transform is rebuilt as usual.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs while preparing the change, an error variant explaining the issue is
+    /// returned, and the transform is rebuilt as usual.
+    fn reload(
+        &self,
+        _new: &dyn TransformConfig,
+        _context: &TransformContext,
+    ) -> crate::Result<Option<TransformReload>> {
+        Ok(None)
+    }
+
     /// Whether or not this transform can be nested, given the types of transforms it would be
     /// nested within.
     ///
@@ -19,12 +19,11 @@ use value::Kind;
 use vector_core::config::LogNamespace;
 
 pub use self::unit_test_components::{
-    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSourceConfig,
-    UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
+    UnitTestOutputChecks, UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult,
+    UnitTestSourceConfig, UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
 };
 use super::{compiler::expand_globs, graph::Graph, OutputId};
 use crate::{
-    conditions::Condition,
     config::{
         self, loading, ComponentKey, Config, ConfigBuilder, ConfigPath, SinkOuter, SourceOuter,
         TestDefinition, TestInput, TestInputValue, TestOutput,
@@ -535,8 +534,8 @@ fn build_and_validate_inputs(
 
 fn build_outputs(
     test_outputs: &[TestOutput],
-) -> Result<IndexMap<Vec<OutputId>, Vec<Vec<Condition>>>, Vec<String>> {
-    let mut outputs: IndexMap<Vec<OutputId>, Vec<Vec<Condition>>> = IndexMap::new();
+) -> Result<IndexMap<Vec<OutputId>, Vec<UnitTestOutputChecks>>, Vec<String>> {
+    let mut outputs: IndexMap<Vec<OutputId>, Vec<UnitTestOutputChecks>> = IndexMap::new();
     let mut errors = Vec::new();
 
     for output in test_outputs {
@@ -557,10 +556,14 @@ fn build_outputs(
             }
         }
 
+        let checks = UnitTestOutputChecks {
+            conditions,
+            metrics: output.metrics.clone(),
+        };
         outputs
             .entry(output.extract_from.clone().to_vec())
-            .and_modify(|existing_conditions| existing_conditions.push(conditions.clone()))
-            .or_insert(vec![conditions.clone()]);
+            .and_modify(|existing_checks| existing_checks.push(checks.clone()))
+            .or_insert(vec![checks]);
     }
 
     if errors.is_empty() {
@@ -835,6 +835,117 @@ async fn test_metric_input() {
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
 
+#[tokio::test]
+async fn test_metric_assertions() {
+    let config: ConfigBuilder = toml::from_str(indoc! { r#"
+          [transforms.foo]
+            inputs = ["ignored"]
+            type = "remap"
+            source = '''
+            .tags.new_tag = "new value added"
+            '''
+
+          [transforms.bar]
+            inputs = ["foo"]
+            type = "remap"
+            drop_on_abort = true
+            reroute_dropped = true
+            source = '''
+            if .tags.tagfoo == "valfoo" { abort }
+            '''
+
+          [[tests]]
+            name = "successful test with metric assertions"
+
+            [tests.input]
+              insert_at = "foo"
+              type = "metric"
+              [tests.input.metric]
+                kind = "incremental"
+                name = "foometric"
+                namespace = "foo"
+                [tests.input.metric.tags]
+                  tagfoo = "valfoo"
+                [tests.input.metric.counter]
+                  value = 100.0
+
+            [[tests.outputs]]
+              extract_from = "bar.dropped"
+              [[tests.outputs.metrics]]
+                name = "foometric"
+                namespace = "foo"
+                value = 100.0
+                [tests.outputs.metrics.tags]
+                  tagfoo = "valfoo"
+                  new_tag = "new value added"
+
+          [[tests]]
+            name = "failing test with metric assertions"
+
+            [tests.input]
+              insert_at = "foo"
+              type = "metric"
+              [tests.input.metric]
+                kind = "incremental"
+                name = "foometric"
+                [tests.input.metric.tags]
+                  tagfoo = "valfoo"
+                [tests.input.metric.counter]
+                  value = 100.0
+
+            [[tests.outputs]]
+              extract_from = "bar.dropped"
+              [[tests.outputs.metrics]]
+                name = "foometric"
+                value = 10.0
+      "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+    let errors = tests.remove(0).run().await.errors;
+    assert_eq!(
+        errors[1],
+        r#"  metric[0]: expected value 10 for metric "foometric", got 100"#
+    );
+}
+
+#[tokio::test]
+async fn parse_missing_named_output() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.bar]
+          inputs = ["foo"]
+          type = "remap"
+          reroute_dropped = true
+          source = '''
+          .my_string_field = "string value"
+          '''
+
+          [[tests]]
+            name = "broken test"
+
+          [tests.input]
+            insert_at = "bar"
+            value = "any value"
+
+          [[tests.outputs]]
+            extract_from = "bar.droped"
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = ""
+    "#})
+    .unwrap();
+
+    let errs = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errs,
+        vec![indoc! {r#"
+            Failed to build test 'broken test':
+              Invalid extract_from target in test 'broken test': 'bar.droped' does not exist, the outputs of 'bar' are: 'bar', 'bar.dropped'"#}
+        .to_owned(),]
+    );
+}
+
 #[tokio::test]
 async fn test_success_over_gap() {
     let config: ConfigBuilder = toml::from_str(indoc! { r#"
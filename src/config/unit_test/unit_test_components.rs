@@ -6,13 +6,15 @@ use tokio::sync::{oneshot, Mutex};
 use vector_config::configurable_component;
 use vector_core::{
     config::{DataType, Input, LogNamespace, Output},
-    event::Event,
+    event::{Event, MetricValue},
     sink::{StreamSink, VectorSink},
 };
 
 use crate::{
     conditions::Condition,
-    config::{AcknowledgementsConfig, SinkConfig, SinkContext, SourceConfig, SourceContext},
+    config::{
+        AcknowledgementsConfig, SinkConfig, SinkContext, SourceConfig, SourceContext, TestMetric,
+    },
     impl_generate_config_from_default,
     sinks::Healthcheck,
     sources,
@@ -104,10 +106,18 @@ impl SourceConfig for UnitTestStreamSourceConfig {
     }
 }
 
+/// The conditions and metrics, from one of the `outputs` of a test, that the received events must
+/// satisfy.
+#[derive(Clone, Default)]
+pub struct UnitTestOutputChecks {
+    pub conditions: Vec<Condition>,
+    pub metrics: Vec<TestMetric>,
+}
+
 #[derive(Clone, Default)]
 pub enum UnitTestSinkCheck {
-    /// Check all events that are received against the list of conditions.
-    Checks(Vec<Vec<Condition>>),
+    /// Check all events that are received against the list of conditions and metrics.
+    Checks(Vec<UnitTestOutputChecks>),
 
     /// Check that no events were received.
     NoOutputs,
@@ -200,21 +210,21 @@ impl StreamSink<Event> for UnitTestSink {
                 } else {
                     for (i, check) in checks.iter().enumerate() {
                         let mut check_errors = Vec::new();
-                        for (j, condition) in check.iter().enumerate() {
-                            let mut condition_errors = Vec::new();
-                            for event in output_events.iter() {
-                                match condition.check_with_context(event.clone()).0 {
-                                    Ok(_) => {
-                                        condition_errors.clear();
-                                        break;
-                                    }
-                                    Err(error) => {
-                                        condition_errors
-                                            .push(format!("  condition[{}]: {}", j, error));
-                                    }
-                                }
-                            }
-                            check_errors.extend(condition_errors);
+                        for (j, condition) in check.conditions.iter().enumerate() {
+                            check_errors.extend(
+                                check_any_event(&output_events, |event| {
+                                    condition.check_with_context(event.clone()).0
+                                })
+                                .into_iter()
+                                .map(|error| format!("  condition[{}]: {}", j, error)),
+                            );
+                        }
+                        for (j, metric) in check.metrics.iter().enumerate() {
+                            check_errors.extend(
+                                check_any_event(&output_events, |event| metric.check(event))
+                                    .into_iter()
+                                    .map(|error| format!("  metric[{}]: {}", j, error)),
+                            );
                         }
                         // If there are errors, add a preamble to the output
                         if !check_errors.is_empty() {
@@ -303,6 +313,91 @@ impl SinkConfig for UnitTestStreamSinkConfig {
     }
 }
 
+/// Returns the errors of checking each of the events, unless one of them passes the check.
+fn check_any_event(events: &[Event], check: impl Fn(&Event) -> Result<(), String>) -> Vec<String> {
+    let mut errors = Vec::new();
+    for event in events {
+        match check(event) {
+            Ok(()) => return Vec::new(),
+            Err(error) => errors.push(error),
+        }
+    }
+    errors
+}
+
+impl TestMetric {
+    /// Checks that the event is the expected metric, with an error describing the first difference
+    /// otherwise.
+    fn check(&self, event: &Event) -> Result<(), String> {
+        let metric = match event {
+            Event::Metric(metric) => metric,
+            _ => {
+                return Err(format!(
+                    "expected metric {:?}, got a non-metric event",
+                    self.name
+                ))
+            }
+        };
+
+        if metric.name() != self.name {
+            return Err(format!(
+                "expected metric {:?}, got {:?}",
+                self.name,
+                metric.name()
+            ));
+        }
+        if let Some(namespace) = &self.namespace {
+            if metric.namespace() != Some(namespace.as_str()) {
+                return Err(format!(
+                    "expected namespace {:?} for metric {:?}, got {:?}",
+                    namespace,
+                    self.name,
+                    metric.namespace()
+                ));
+            }
+        }
+        for (key, expected) in &self.tags {
+            match metric.tag_value(key) {
+                Some(value) if value == *expected => {}
+                Some(value) => {
+                    return Err(format!(
+                        "expected tag {:?} of metric {:?} to be {:?}, got {:?}",
+                        key, self.name, expected, value
+                    ))
+                }
+                None => {
+                    return Err(format!(
+                        "expected tag {:?} of metric {:?} to be {:?}, but it is missing",
+                        key, self.name, expected
+                    ))
+                }
+            }
+        }
+        if let Some(expected) = self.value {
+            let value = match metric.value() {
+                MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+                value => {
+                    return Err(format!(
+                        "expected value {} for metric {:?}, but it is a {} without a single value",
+                        expected,
+                        self.name,
+                        value.as_name()
+                    ))
+                }
+            };
+            // Allow for the rounding errors of values computed by transforms.
+            if (value - expected).abs() > f64::EPSILON * expected.abs().max(1.0) {
+                return Err(format!(
+                    "expected value {} for metric {:?}, got {}",
+                    expected, self.name, value
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 fn events_to_string(events: &[Event]) -> String {
     events
         .iter()
@@ -145,6 +145,27 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check that every tenant referenced by a source is defined.
+pub fn check_tenants(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let errors = config
+        .sources
+        .iter()
+        .filter_map(|(key, source)| {
+            source
+                .tenant
+                .as_ref()
+                .filter(|tenant| !config.tenants.contains_key(*tenant))
+                .map(|tenant| format!("Source {key} references undefined tenant: `{tenant}`"))
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// To avoid collisions between `output` metric tags, check that a component
 /// does not have a named output with the name [`DEFAULT_OUTPUT`]
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -357,7 +378,20 @@ pub fn warnings(config: &Config) -> Vec<String> {
             .collect::<Vec<_>>()
     });
 
-    for (input_type, id) in transform_ids.chain(source_ids) {
+    let sink_ids = config.sinks.iter().flat_map(|(key, sink)| {
+        sink.outputs()
+            .iter()
+            .map(|output| {
+                if let Some(port) = &output.port {
+                    ("sink", OutputId::from((key, port.clone())))
+                } else {
+                    ("sink", OutputId::from(key))
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for (input_type, id) in transform_ids.chain(source_ids).chain(sink_ids) {
         if !config
             .transforms
             .iter()
@@ -0,0 +1,1066 @@
+#![allow(missing_docs)]
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    cli::handle_config_errors,
+    config::{self, format, ConfigBuilder, ConfigPath},
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The config file to convert, or a directory of config files to convert recursively.
+    input_path: PathBuf,
+
+    /// The file to write the converted config to, or the directory to write the converted config
+    /// files to when converting a directory.
+    ///
+    /// Existing files are never overwritten.
+    output_path: PathBuf,
+
+    /// The format of the config files to convert.
+    ///
+    /// Defaults to the format of the extension of each file.
+    #[arg(long, value_enum)]
+    input_format: Option<Format>,
+
+    /// The format to convert the configs to.
+    ///
+    /// Defaults to the format of the extension of the output file. Required when converting a
+    /// directory.
+    #[arg(long, value_enum)]
+    output_format: Option<Format>,
+
+    /// Merge the files included by the configs into the converted configs.
+    ///
+    /// Otherwise the `include` paths are kept as is, and still point to the original files.
+    #[arg(long)]
+    resolve_includes: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Some(Self::Toml),
+            Some("yaml") | Some("yml") => Some(Self::Yaml),
+            Some("json") => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    const fn extension(self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl From<Format> for config::Format {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Toml => Self::Toml,
+            Format::Yaml => Self::Yaml,
+            Format::Json => Self::Json,
+        }
+    }
+}
+
+/// The comments of a config, by the path of the key or the sequence item they precede.
+type Comments = HashMap<Vec<String>, Vec<String>>;
+
+/// A config converted to another format.
+#[derive(Debug)]
+struct Converted {
+    output: String,
+    warnings: Vec<String>,
+}
+
+/// Converts a config to another format, checking that the converted config holds the same values
+/// and produces the same topology.
+///
+/// Environment variables aren't interpolated in the converted config. The comments preceding
+/// keys, tables, and sequence items are carried over, but JSON can't hold them.
+fn convert(
+    path: &Path,
+    input: &str,
+    input_format: Format,
+    output_format: Format,
+    resolve_includes: bool,
+) -> Result<Converted, Vec<String>> {
+    let mut warnings = Vec::new();
+    let mut value: Value = format::deserialize(input, input_format.into())?;
+    let includes = value.get("include").is_some();
+    if includes && resolve_includes {
+        let (source, warns) = config::load_source_with_provenance_from_paths(&[ConfigPath::File(
+            path.to_path_buf(),
+            Some(input_format.into()),
+        )])?;
+        warnings.extend(warns);
+        value = serde_yaml::to_value(source.table).map_err(|error| vec![error.to_string()])?;
+    }
+
+    let comments = match input_format {
+        Format::Toml => toml_comments(input),
+        Format::Yaml => yaml_comments(input),
+        Format::Json => Comments::new(),
+    };
+    let output = match output_format {
+        Format::Toml => render_toml(&value, &comments).map_err(|error| vec![error])?,
+        Format::Yaml => render_yaml(&value, &comments),
+        Format::Json => {
+            if !comments.is_empty() {
+                warnings.push(format!(
+                    "The comments of {:?} are dropped, as JSON can't hold comments.",
+                    path
+                ));
+            }
+            let mut output = serde_json::to_string_pretty(&value)
+                .map_err(|error| vec![format!("The config isn't valid JSON: {}", error)])?;
+            output.push('\n');
+            output
+        }
+    };
+
+    let converted: Value =
+        format::deserialize(&output, output_format.into()).map_err(|errors| {
+            vec![format!(
+                "The converted config is invalid: {}",
+                errors.join(", ")
+            )]
+        })?;
+    if converted != value {
+        return Err(vec![
+            "The converted config doesn't hold the same values as the original one.".to_owned(),
+        ]);
+    }
+
+    if includes && !resolve_includes {
+        warnings.push(format!(
+            "The `include` paths of {:?} are kept as is, so the topology of the converted config \
+             isn't checked.",
+            path
+        ));
+        return Ok(Converted { output, warnings });
+    }
+    let original = if includes {
+        config::load_builder_from_paths(&[ConfigPath::File(
+            path.to_path_buf(),
+            Some(input_format.into()),
+        )])
+    } else {
+        config::load::<_, ConfigBuilder>(input.as_bytes(), input_format.into())
+    };
+    match original {
+        Ok((original, _)) => {
+            let (converted, _) =
+                config::load::<_, ConfigBuilder>(output.as_bytes(), output_format.into())?;
+            if serde_json::to_value(original).ok() != serde_json::to_value(converted).ok() {
+                return Err(vec![
+                    "The converted config doesn't produce the same topology as the original one."
+                        .to_owned(),
+                ]);
+            }
+        }
+        Err(errors) => warnings.push(format!(
+            "{:?} doesn't load as a whole config, so the topology of the converted config isn't \
+             checked: {}",
+            path,
+            errors.join(", ")
+        )),
+    }
+
+    Ok(Converted { output, warnings })
+}
+
+/// Collects the comments preceding the keys and the table headers of a TOML config.
+///
+/// The comments within the values spanning several lines are dropped.
+fn toml_comments(input: &str) -> Comments {
+    let mut comments = Comments::new();
+    let mut pending = Vec::new();
+    let mut table = Vec::new();
+    // The number of tables of each array of tables.
+    let mut arrays = HashMap::<Vec<String>, usize>::new();
+    // The value spanning several lines being skipped: a multi-line string, or nested brackets.
+    let mut string_end = None;
+    let mut nesting = 0;
+
+    for line in input.lines() {
+        let line = line.trim();
+        if let Some(delimiter) = string_end {
+            if line.matches(delimiter).count() % 2 == 1 {
+                string_end = None;
+            }
+            continue;
+        }
+        if nesting > 0 {
+            nesting += toml_nesting(line);
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            pending.push(comment.to_owned());
+            continue;
+        }
+
+        let path = if let Some(header) = line.strip_prefix("[[") {
+            let header = toml_key_segments(header.split("]]").next().unwrap_or_default());
+            let mut path = resolve_toml_header(&arrays, header);
+            let count = arrays.entry(path.clone()).or_insert(0);
+            path.push(count.to_string());
+            *count += 1;
+            table = path.clone();
+            path
+        } else if let Some(header) = line.strip_prefix('[') {
+            let header = toml_key_segments(header.split(']').next().unwrap_or_default());
+            table = resolve_toml_header(&arrays, header);
+            table.clone()
+        } else if let Some((key, value)) = split_toml_key(line) {
+            string_end = ["\"\"\"", "'''"].into_iter().find(|delimiter| {
+                value.starts_with(delimiter) && value.matches(delimiter).count() == 1
+            });
+            if string_end.is_none() {
+                nesting = toml_nesting(value);
+            }
+            table
+                .iter()
+                .cloned()
+                .chain(toml_key_segments(key))
+                .collect()
+        } else {
+            continue;
+        };
+
+        if !pending.is_empty() {
+            comments.insert(path, std::mem::take(&mut pending));
+        }
+    }
+
+    comments
+}
+
+/// Resolves the path of a TOML table header, going through the last table of the arrays of tables
+/// it's nested in.
+fn resolve_toml_header(arrays: &HashMap<Vec<String>, usize>, header: Vec<String>) -> Vec<String> {
+    let mut path = Vec::new();
+    for segment in header {
+        if let Some(count) = arrays.get(&path) {
+            path.push((count - 1).to_string());
+        }
+        path.push(segment);
+    }
+    path
+}
+
+/// Splits a TOML line into its key and its value, unless it doesn't hold a key.
+fn split_toml_key(line: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '=') => return Some((line[..index].trim(), line[index + 1..].trim())),
+            (None, '#') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits a dotted TOML key into its segments.
+fn toml_key_segments(key: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    let mut quote = None;
+    for c in key.chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '.') => segments.push(std::mem::take(&mut segment)),
+            (None, c) if c.is_whitespace() => {}
+            _ => segment.push(c),
+        }
+    }
+    segments.push(segment);
+    segments
+}
+
+/// Returns how much deeper the brackets of a TOML value nest after it, to skip the arrays and
+/// the inline tables spanning several lines.
+fn toml_nesting(value: &str) -> isize {
+    let mut nesting = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in value.chars() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '{') => nesting += 1,
+            (None, ']' | '}') => nesting -= 1,
+            (None, '#') => break,
+            _ => {}
+        }
+        escaped = false;
+    }
+    nesting
+}
+
+/// Collects the comments preceding the keys and the sequence items of a YAML config written in
+/// block style.
+fn yaml_comments(input: &str) -> Comments {
+    let mut comments = Comments::new();
+    let mut pending = Vec::new();
+    // The keys and the sequence items the line is nested in: their indentation, whether they're
+    // sequence items, and their paths.
+    let mut parents: Vec<(usize, bool, Vec<String>)> = Vec::new();
+    // The number of items of each sequence.
+    let mut items = HashMap::<Vec<String>, usize>::new();
+    // The indentation of the key or the sequence item whose block scalar is being skipped.
+    let mut block = None;
+
+    for line in input.lines() {
+        let content = line.trim_start();
+        let mut indent = line.len() - content.len();
+        if let Some(block_indent) = block {
+            if content.is_empty() || indent > block_indent {
+                continue;
+            }
+            block = None;
+        }
+        if let Some(comment) = content.strip_prefix('#') {
+            pending.push(comment.to_owned());
+            continue;
+        }
+        if content.is_empty() || content.starts_with("---") {
+            continue;
+        }
+
+        let mut content = content.trim_end();
+        let mut path = None;
+        // Sequence items can start on the line of the item they're nested in.
+        while content == "-" || content.starts_with("- ") {
+            while parents.last().map_or(false, |(parent, is_item, _)| {
+                *parent > indent || (*parent == indent && *is_item)
+            }) {
+                parents.pop();
+            }
+            let mut item = parents
+                .last()
+                .map(|(_, _, path)| path.clone())
+                .unwrap_or_default();
+            let count = items.entry(item.clone()).or_insert(0);
+            item.push(count.to_string());
+            *count += 1;
+            parents.push((indent, true, item.clone()));
+            path.get_or_insert(item);
+
+            let rest = content[1..].trim_start();
+            indent += content.len() - rest.len();
+            content = rest;
+        }
+
+        if let Some((key, value)) = split_yaml_key(content) {
+            while parents
+                .last()
+                .map_or(false, |(parent, _, _)| *parent >= indent)
+            {
+                parents.pop();
+            }
+            let mut key_path = parents
+                .last()
+                .map(|(_, _, path)| path.clone())
+                .unwrap_or_default();
+            key_path.push(key);
+            if value.starts_with('|') || value.starts_with('>') {
+                block = Some(indent);
+            }
+            parents.push((indent, false, key_path.clone()));
+            path.get_or_insert(key_path);
+        } else if content.starts_with('|') || content.starts_with('>') {
+            block = parents.last().map(|(parent, _, _)| *parent);
+        }
+
+        if let Some(path) = path {
+            if !pending.is_empty() {
+                comments.insert(path, std::mem::take(&mut pending));
+            }
+        }
+    }
+
+    comments
+}
+
+/// Splits the content of a YAML line into its key and its value, unless it doesn't hold a key.
+fn split_yaml_key(content: &str) -> Option<(String, &str)> {
+    let mut quote = None;
+    for (index, c) in content.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'' | '[' | '{') if index > 0 => {}
+            (None, '"' | '\'') => quote = Some(c),
+            // Flow collections don't hold keys of block mappings.
+            (None, '[' | '{') => return None,
+            (Some(q), c) if c == q => quote = None,
+            (None, ':') => {
+                let value = &content[index + 1..];
+                if value.is_empty() || value.starts_with(' ') {
+                    let key = content[..index].trim();
+                    let key = match key.chars().next() {
+                        Some(quote @ ('"' | '\'')) if key.len() > 1 && key.ends_with(quote) => {
+                            &key[1..key.len() - 1]
+                        }
+                        _ => key,
+                    };
+                    return Some((key.to_owned(), value.trim()));
+                }
+            }
+            (None, '#') if content[..index].ends_with(' ') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Renders a config in TOML, preceding its keys and its tables with their comments.
+fn render_toml(value: &Value, comments: &Comments) -> Result<String, String> {
+    let mapping = value
+        .as_mapping()
+        .ok_or_else(|| "The config isn't a table.".to_owned())?;
+    let mut output = String::new();
+    render_toml_table(
+        &mut output,
+        &mut Vec::new(),
+        &mut Vec::new(),
+        mapping,
+        comments,
+    )?;
+    Ok(output)
+}
+
+fn render_toml_table(
+    output: &mut String,
+    header: &mut Vec<String>,
+    path: &mut Vec<String>,
+    mapping: &Mapping,
+    comments: &Comments,
+) -> Result<(), String> {
+    let is_table = |value: &Value| match value {
+        Value::Mapping(_) => true,
+        Value::Sequence(items) => !items.is_empty() && items.iter().all(Value::is_mapping),
+        _ => false,
+    };
+
+    // Values have to come before the sub-tables of a table.
+    for (key, value) in mapping.iter().filter(|(_, value)| !is_table(value)) {
+        let key = toml_key(key)?;
+        path.push(key.clone());
+        write_comments(output, comments.get(path), 0);
+        let value = toml::Value::try_from(value).map_err(|error| {
+            format!(
+                "`{}` can't be represented in TOML: {}",
+                path.join("."),
+                error
+            )
+        })?;
+        writeln!(output, "{} = {}", render_toml_key(&key), value)
+            .expect("write to String never fails");
+        path.pop();
+    }
+
+    for (key, value) in mapping.iter().filter(|(_, value)| is_table(value)) {
+        let key = toml_key(key)?;
+        header.push(key.clone());
+        path.push(key);
+        match value {
+            Value::Mapping(mapping) => {
+                // Tables only holding sub-tables don't need their own header.
+                let values = mapping.values().any(|value| !is_table(value));
+                if values || mapping.is_empty() {
+                    start_toml_section(output);
+                    write_comments(output, comments.get(path), 0);
+                    writeln!(output, "[{}]", render_toml_header(header))
+                        .expect("write to String never fails");
+                } else if let Some(lines) = comments.get(path) {
+                    start_toml_section(output);
+                    write_comments(output, Some(lines), 0);
+                }
+                render_toml_table(output, header, path, mapping, comments)?;
+            }
+            Value::Sequence(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    path.push(index.to_string());
+                    start_toml_section(output);
+                    write_comments(output, comments.get(path), 0);
+                    writeln!(output, "[[{}]]", render_toml_header(header))
+                        .expect("write to String never fails");
+                    let mapping = item
+                        .as_mapping()
+                        .expect("arrays of tables only hold tables");
+                    render_toml_table(output, header, path, mapping, comments)?;
+                    path.pop();
+                }
+            }
+            _ => unreachable!("only tables and arrays of tables are rendered as tables"),
+        }
+        header.pop();
+        path.pop();
+    }
+
+    Ok(())
+}
+
+fn toml_key(key: &Value) -> Result<String, String> {
+    key.as_str()
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| format!("The key {:?} can't be represented in TOML.", key))
+}
+
+/// Separates the tables with blank lines, unless they follow their own comments.
+fn start_toml_section(output: &mut String) {
+    let last_line = output.lines().last().unwrap_or_default();
+    if !output.is_empty() && !last_line.starts_with('#') {
+        output.push('\n');
+    }
+}
+
+fn render_toml_header(header: &[String]) -> String {
+    header
+        .iter()
+        .map(|key| render_toml_key(key))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn render_toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_owned()
+    } else {
+        toml::Value::String(key.to_owned()).to_string()
+    }
+}
+
+/// Renders a config in YAML, in block style, preceding its keys and its sequence items with their
+/// comments.
+fn render_yaml(value: &Value, comments: &Comments) -> String {
+    let mut output = String::new();
+    match value {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            render_yaml_mapping(&mut output, &mut Vec::new(), mapping, 0, comments)
+        }
+        Value::Sequence(items) if !items.is_empty() => {
+            render_yaml_sequence(&mut output, &mut Vec::new(), items, 0, comments)
+        }
+        value => {
+            output.push_str(&render_yaml_scalar(value, 2));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn render_yaml_mapping(
+    output: &mut String,
+    path: &mut Vec<String>,
+    mapping: &Mapping,
+    indent: usize,
+    comments: &Comments,
+) {
+    for (key, value) in mapping {
+        path.push(match key {
+            Value::String(key) => key.clone(),
+            key => render_yaml_inline(key),
+        });
+        write_comments(output, comments.get(path), indent);
+        write!(output, "{:indent$}{}:", "", render_yaml_inline(key))
+            .expect("write to String never fails");
+        render_yaml_value(output, path, value, indent, comments);
+        path.pop();
+    }
+}
+
+fn render_yaml_sequence(
+    output: &mut String,
+    path: &mut Vec<String>,
+    items: &[Value],
+    indent: usize,
+    comments: &Comments,
+) {
+    for (index, item) in items.iter().enumerate() {
+        path.push(index.to_string());
+        write_comments(output, comments.get(path), indent);
+        match item {
+            Value::Mapping(mapping) if !mapping.is_empty() => {
+                let mut rendered = String::new();
+                render_yaml_mapping(&mut rendered, path, mapping, indent + 2, comments);
+                // The first key of the item goes on the line of its dash.
+                let mut start = 0;
+                for line in rendered.split_inclusive('\n') {
+                    if !line.trim_start().starts_with('#') {
+                        break;
+                    }
+                    start += line.len();
+                }
+                rendered.replace_range(start + indent..start + indent + 2, "- ");
+                output.push_str(&rendered);
+            }
+            item => {
+                write!(output, "{:indent$}-", "").expect("write to String never fails");
+                render_yaml_value(output, path, item, indent, comments);
+            }
+        }
+        path.pop();
+    }
+}
+
+/// Renders the value following a key or the dash of a sequence item.
+fn render_yaml_value(
+    output: &mut String,
+    path: &mut Vec<String>,
+    value: &Value,
+    indent: usize,
+    comments: &Comments,
+) {
+    match value {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            output.push('\n');
+            render_yaml_mapping(output, path, mapping, indent + 2, comments);
+        }
+        Value::Sequence(items) if !items.is_empty() => {
+            output.push('\n');
+            render_yaml_sequence(output, path, items, indent + 2, comments);
+        }
+        value => {
+            output.push(' ');
+            output.push_str(&render_yaml_scalar(value, indent + 2));
+            output.push('\n');
+        }
+    }
+}
+
+/// Renders a scalar, rendering the multi-line strings, such as VRL programs, as literal block
+/// scalars indented by `indent`.
+fn render_yaml_scalar(value: &Value, indent: usize) -> String {
+    let string = match value {
+        Value::String(string) if string.contains('\n') => string,
+        value => return render_yaml_inline(value),
+    };
+    let body = string.trim_end_matches('\n');
+    let first_line = body.lines().find(|line| !line.is_empty());
+    // Literal block scalars can't start with an indented line, nor hold carriage returns.
+    let literal =
+        first_line.map_or(false, |line| !line.starts_with([' ', '\t'])) && !string.contains('\r');
+    if !literal {
+        return render_yaml_inline(value);
+    }
+
+    let trailing_newlines = string.len() - body.len();
+    let mut rendered = match trailing_newlines {
+        0 => "|-".to_owned(),
+        1 => "|".to_owned(),
+        _ => "|+".to_owned(),
+    };
+    for line in body.split('\n') {
+        rendered.push('\n');
+        if !line.is_empty() {
+            write!(rendered, "{:indent$}{}", "", line).expect("write to String never fails");
+        }
+    }
+    for _ in 1..trailing_newlines {
+        rendered.push('\n');
+    }
+    rendered
+}
+
+/// Renders a value on a single line, falling back to JSON, which YAML is a superset of.
+fn render_yaml_inline(value: &Value) -> String {
+    match serde_yaml::to_string(value) {
+        Ok(rendered) if !rendered.trim_end().contains('\n') => rendered.trim_end().to_owned(),
+        _ => serde_json::to_string(value).expect("YAML values are always serializable"),
+    }
+}
+
+fn write_comments(output: &mut String, comments: Option<&Vec<String>>, indent: usize) {
+    for comment in comments.into_iter().flatten() {
+        writeln!(output, "{:indent$}#{}", "", comment).expect("write to String never fails");
+    }
+}
+
+/// Collects the config files of a directory and of its sub-directories.
+fn config_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            config_files(&path, files)?;
+        } else if Format::from_path(&path).is_some() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let files = if opts.input_path.is_dir() {
+        let output_format = match opts.output_format {
+            Some(format) => format,
+            None => {
+                return handle_config_errors(vec![
+                    "The output format is required to convert a config dir, set it with \
+                     `--output-format`."
+                        .to_owned(),
+                ])
+            }
+        };
+        let mut files = Vec::new();
+        if let Err(error) = config_files(&opts.input_path, &mut files) {
+            return handle_config_errors(vec![format!(
+                "Could not read config dir {:?}: {}.",
+                opts.input_path, error
+            )]);
+        }
+        files
+            .into_iter()
+            .map(|file| {
+                let output = opts
+                    .output_path
+                    .join(file.strip_prefix(&opts.input_path).unwrap_or(&file))
+                    .with_extension(output_format.extension());
+                (file, output, output_format)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        let output_format = match opts
+            .output_format
+            .or_else(|| Format::from_path(&opts.output_path))
+        {
+            Some(format) => format,
+            None => {
+                return handle_config_errors(vec![format!(
+                    "Unknown format of output file {:?}, set it with `--output-format`.",
+                    opts.output_path
+                )])
+            }
+        };
+        vec![(
+            opts.input_path.clone(),
+            opts.output_path.clone(),
+            output_format,
+        )]
+    };
+
+    // Nothing is written unless all the files are converted.
+    let mut converted = Vec::new();
+    let mut errors = Vec::new();
+    for (file, output, output_format) in files {
+        let input_format = match opts.input_format.or_else(|| Format::from_path(&file)) {
+            Some(format) => format,
+            None => {
+                errors.push(format!(
+                    "Unknown format of config file {:?}, set it with `--input-format`.",
+                    file
+                ));
+                continue;
+            }
+        };
+        let result = fs::read_to_string(&file)
+            .map_err(|error| vec![error.to_string()])
+            .and_then(|input| {
+                convert(
+                    &file,
+                    &input,
+                    input_format,
+                    output_format,
+                    opts.resolve_includes,
+                )
+            });
+        match result {
+            Ok(conversion) => converted.push((file, output, conversion)),
+            Err(errs) => errors.extend(
+                errs.into_iter()
+                    .map(|error| format!("Could not convert config file {:?}: {}", file, error)),
+            ),
+        }
+    }
+    if !errors.is_empty() {
+        return handle_config_errors(errors);
+    }
+
+    for (file, output, conversion) in converted {
+        for warning in conversion.warnings {
+            warn!("{}", warning);
+        }
+        if let Err(error) = write_file(&output, &conversion.output) {
+            error!(message = "Could not write converted config.", path = ?output, %error);
+            return exitcode::IOERR;
+        }
+        info!(message = "Converted config file.", input = ?file, output = ?output);
+    }
+
+    exitcode::OK
+}
+
+fn write_file(path: &Path, body: &str) -> Result<(), crate::Error> {
+    if path.exists() {
+        // The converted configs never replace existing files.
+        Err(format!("{:?} already exists", path).into())
+    } else {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, body).map_err(Into::into)
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-stdin",
+    feature = "transforms-remap",
+    feature = "sinks-console"
+))]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn converts_toml_to_yaml_with_comments() {
+        let input = indoc! {r#"
+            # Where the logs come from.
+            [sources.in]
+            type = "stdin"
+
+            [transforms.parse]
+            type = "remap"
+            inputs = ["in"]
+            # Parses the JSON logs.
+            source = """
+            . = parse_json!(.message)
+            """
+
+            [sinks.out]
+            inputs = ["parse"]
+            type = "console"
+            encoding.codec = "json"
+        "#};
+
+        let converted = convert(
+            Path::new("vector.toml"),
+            input,
+            Format::Toml,
+            Format::Yaml,
+            false,
+        )
+        .unwrap();
+        assert!(converted.warnings.is_empty(), "{:?}", converted.warnings);
+        assert_eq!(
+            converted.output,
+            indoc! {"
+                sources:
+                  # Where the logs come from.
+                  in:
+                    type: stdin
+                transforms:
+                  parse:
+                    type: remap
+                    inputs:
+                      - in
+                    # Parses the JSON logs.
+                    source: |
+                      . = parse_json!(.message)
+                sinks:
+                  out:
+                    inputs:
+                      - parse
+                    type: console
+                    encoding:
+                      codec: json
+            "}
+        );
+    }
+
+    #[test]
+    fn converts_yaml_to_toml_with_comments() {
+        let input = indoc! {"
+            # Where the logs come from.
+            sources:
+              in:
+                type: stdin
+            sinks:
+              out:
+                # Reads from the source.
+                inputs: [in]
+                type: console
+                encoding:
+                  codec: json
+        "};
+
+        let converted = convert(
+            Path::new("vector.yaml"),
+            input,
+            Format::Yaml,
+            Format::Toml,
+            false,
+        )
+        .unwrap();
+        assert!(converted.warnings.is_empty(), "{:?}", converted.warnings);
+        assert_eq!(
+            converted.output,
+            indoc! {r#"
+                # Where the logs come from.
+                [sources.in]
+                type = "stdin"
+
+                [sinks.out]
+                # Reads from the source.
+                inputs = ["in"]
+                type = "console"
+
+                [sinks.out.encoding]
+                codec = "json"
+            "#}
+        );
+    }
+
+    #[test]
+    fn collects_the_comments_of_arrays_of_tables_and_sequences() {
+        let comments = toml_comments(indoc! {r#"
+            [[tests]]
+            name = "first"
+            # The first input.
+            [[tests.inputs]]
+            insert_at = "parse"
+            [[tests]]
+            # The second test.
+            name = "second"
+        "#});
+        assert_eq!(
+            comments,
+            Comments::from([
+                (
+                    vec!["tests".into(), "0".into(), "inputs".into(), "0".into()],
+                    vec![" The first input.".into()]
+                ),
+                (
+                    vec!["tests".into(), "1".into(), "name".into()],
+                    vec![" The second test.".into()]
+                ),
+            ])
+        );
+
+        let comments = yaml_comments(indoc! {"
+            tests:
+              - name: first
+                # The first input.
+                inputs:
+                  - insert_at: parse
+              # The second test.
+              - name: second
+        "});
+        assert_eq!(
+            comments,
+            Comments::from([
+                (
+                    vec!["tests".into(), "0".into(), "inputs".into()],
+                    vec![" The first input.".into()]
+                ),
+                (
+                    vec!["tests".into(), "1".into()],
+                    vec![" The second test.".into()]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn converts_to_json_without_comments() {
+        let input = indoc! {"
+            # Where the logs come from.
+            sources:
+              in:
+                type: stdin
+        "};
+
+        let converted = convert(
+            Path::new("vector.yaml"),
+            input,
+            Format::Yaml,
+            Format::Json,
+            false,
+        )
+        .unwrap();
+        assert_eq!(converted.warnings.len(), 1);
+        assert!(converted.warnings[0].contains("as JSON can't hold comments"));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&converted.output).unwrap(),
+            serde_json::json!({ "sources": { "in": { "type": "stdin" } } })
+        );
+    }
+
+    #[test]
+    fn rejects_values_toml_cant_represent() {
+        let errors = convert(
+            Path::new("vector.yaml"),
+            "sources:\n  in:\n    type: stdin\n    host_key: null\n",
+            Format::Yaml,
+            Format::Toml,
+            false,
+        )
+        .unwrap_err();
+        assert!(errors[0].contains("`sources.in.host_key` can't be represented in TOML"));
+    }
+
+    #[test]
+    fn keeps_or_resolves_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("sources.toml"),
+            "[sources.in]\ntype = \"stdin\"\n",
+        )
+        .unwrap();
+        let main = dir.path().join("vector.toml");
+        let input = indoc! {r#"
+            include = ["sources.toml"]
+
+            [sinks.out]
+            inputs = ["in"]
+            type = "console"
+            encoding.codec = "json"
+        "#};
+        fs::write(&main, input).unwrap();
+
+        let kept = convert(&main, input, Format::Toml, Format::Yaml, false).unwrap();
+        assert!(kept.output.starts_with("include:\n  - sources.toml\n"));
+        assert!(kept.warnings[0].contains("are kept as is"));
+
+        let resolved = convert(&main, input, Format::Toml, Format::Yaml, true).unwrap();
+        assert!(resolved.warnings.is_empty(), "{:?}", resolved.warnings);
+        assert!(!resolved.output.contains("include"));
+        assert!(resolved.output.contains("type: stdin"));
+    }
+}
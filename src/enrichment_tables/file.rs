@@ -20,7 +20,7 @@ use crate::config::EnrichmentTableConfig;
 #[configurable_component]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
-enum Encoding {
+pub(super) enum Encoding {
     /// Decodes the file as a [CSV][csv] (comma-separated values) file.
     ///
     /// [csv]: https://wikipedia.org/wiki/Comma-separated_values
@@ -123,6 +123,25 @@ const fn default_delimiter() -> char {
 }
 
 impl FileConfig {
+    /// Creates the config of a table loaded from the file at the given path.
+    pub(super) fn with_path(
+        path: PathBuf,
+        encoding: Encoding,
+        schema: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            file: FileSettings { path, encoding },
+            schema,
+        }
+    }
+
+    /// Loads the table from its file.
+    pub(super) fn load(&self, timezone: TimeZone) -> crate::Result<File> {
+        let (headers, data, modified) = self.load_file(timezone)?;
+
+        Ok(File::new(self.clone(), modified, data, headers))
+    }
+
     fn parse_column(
         &self,
         timezone: TimeZone,
@@ -242,9 +261,11 @@ impl EnrichmentTableConfig for FileConfig {
         &self,
         globals: &crate::config::GlobalOptions,
     ) -> crate::Result<Box<dyn Table + Send + Sync>> {
-        let (headers, data, modified) = self.load_file(globals.timezone())?;
+        Ok(Box::new(self.load(globals.timezone())?))
+    }
 
-        Ok(Box::new(File::new(self.clone(), modified, data, headers)))
+    fn files(&self) -> Vec<PathBuf> {
+        vec![self.file.path.clone()]
     }
 }
 
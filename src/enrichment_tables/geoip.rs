@@ -2,19 +2,44 @@
 //! Enrichment data is loaded from one of the MaxMind GeoIP databases,
 //! [MaxMind GeoIP2][maxmind] or [GeoLite2 binary city database][geolite].
 //!
+//! The database can also be downloaded from MaxMind, and kept up to date, by the table itself.
+//!
 //! [maxmind]: https://dev.maxmind.com/geoip/geoip2/downloadable
 //! [geolite]: https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access
-use std::{collections::BTreeMap, fs, net::IpAddr, sync::Arc, time::SystemTime};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Read,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use enrichment::{Case, Condition, IndexHandle, Table};
+use flate2::read::GzDecoder;
+use http::{header::LOCATION, Request};
+use hyper::Body;
 use maxminddb::{
     geoip2::{City, ConnectionType, Isp},
     MaxMindDBError, Reader,
 };
+use url::Url;
 use value::Value;
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
-use crate::config::{EnrichmentTableConfig, GenerateConfig};
+use crate::{
+    config::{EnrichmentTableConfig, GenerateConfig, GlobalOptions, ProxyConfig},
+    http::{Auth, HttpClient},
+    tls::TlsSettings,
+};
+
+/// How many redirects are followed when downloading the database.
+const MAX_REDIRECTS: usize = 5;
+
+/// The size of the blocks of tar archives.
+const TAR_BLOCK_SIZE: usize = 512;
 
 // MaxMind GeoIP database files have a type field we can use to recognize specific
 // products. If we encounter one of these two types, we look for ASN/ISP information;
@@ -64,6 +89,67 @@ pub struct GeoipConfig {
     /// [locale_docs]: https://support.maxmind.com/hc/en-us/articles/4414877149467-IP-Geolocation-Data#h_01FRRGRYTGZB29ERDBZCX3MR8Q
     #[serde(default = "default_locale")]
     pub locale: String,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download: Option<GeoipDownloadConfig>,
+}
+
+/// Options for downloading the database from MaxMind, and keeping it up to date.
+///
+/// The database is downloaded to `path` whenever it's missing or older than
+/// `refresh_interval_secs`, and replaced in a single step so that a partially downloaded database
+/// is never loaded. If it can't be downloaded, the database already at `path`, if any, keeps being
+/// used.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GeoipDownloadConfig {
+    /// The ID of the MaxMind account to download the database with.
+    #[configurable(metadata(docs::examples = "${MAXMIND_ACCOUNT_ID}"))]
+    pub account_id: String,
+
+    /// The license key of the MaxMind account to download the database with.
+    #[configurable(metadata(docs::examples = "${MAXMIND_LICENSE_KEY}"))]
+    pub license_key: SensitiveString,
+
+    /// The [edition][editions] of the database to download.
+    ///
+    /// [editions]: https://dev.maxmind.com/geoip/updating-databases#directly-downloading-databases
+    #[serde(default = "default_edition_id")]
+    #[configurable(metadata(docs::examples = "GeoLite2-ASN"))]
+    #[configurable(metadata(docs::examples = "GeoIP2-City"))]
+    pub edition_id: String,
+
+    /// How old the database can get before it's downloaded again, in seconds.
+    ///
+    /// MaxMind updates most databases weekly, or twice a week.
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+
+    /// The endpoint to download the database from.
+    #[serde(default = "default_download_endpoint")]
+    pub endpoint: String,
+
+    /// The timeout for downloading the database, in seconds.
+    #[serde(default = "default_download_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_edition_id() -> String {
+    "GeoLite2-City".to_owned()
+}
+
+const fn default_refresh_interval_secs() -> u64 {
+    86400
+}
+
+fn default_download_endpoint() -> String {
+    "https://download.maxmind.com".to_owned()
+}
+
+const fn default_download_timeout_secs() -> u64 {
+    300
 }
 
 fn default_locale() -> String {
@@ -83,6 +169,7 @@ impl GenerateConfig for GeoipConfig {
         toml::Value::try_from(Self {
             path: "/path/to/GeoLite2-City.mmdb".to_string(),
             locale: default_locale(),
+            download: None,
         })
         .unwrap()
     }
@@ -90,12 +177,150 @@ impl GenerateConfig for GeoipConfig {
 
 #[async_trait::async_trait]
 impl EnrichmentTableConfig for GeoipConfig {
-    async fn build(
-        &self,
-        _: &crate::config::GlobalOptions,
-    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+    async fn build(&self, globals: &GlobalOptions) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        if let Some(download) = &self.download {
+            let path = Path::new(&self.path);
+            if download.is_stale(path) {
+                let proxy = ProxyConfig::merge_with_env(&globals.proxy, &ProxyConfig::default());
+                if let Err(error) = download.download(path, &proxy).await {
+                    if !path.exists() {
+                        return Err(error);
+                    }
+                    warn!(
+                        message = "Failed to download GeoIP database, using the existing database instead.",
+                        edition_id = %download.edition_id,
+                        %error,
+                    );
+                }
+            }
+        }
+
         Ok(Box::new(Geoip::new(self.clone())?))
     }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![PathBuf::from(&self.path)]
+    }
+
+    /// The downloaded database is checked hourly at most, so that it's retried soon enough when it
+    /// couldn't be downloaded.
+    fn default_reload_interval(&self) -> Option<Duration> {
+        self.download
+            .as_ref()
+            .map(|download| Duration::from_secs(download.refresh_interval_secs.clamp(1, 3600)))
+    }
+}
+
+impl GeoipDownloadConfig {
+    /// Whether the database at the given path is missing, or due to be downloaded again.
+    fn is_stale(&self, path: &Path) -> bool {
+        let age = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map(|modified| modified.elapsed().unwrap_or_default());
+        match age {
+            Ok(age) => age >= Duration::from_secs(self.refresh_interval_secs),
+            Err(_) => true,
+        }
+    }
+
+    /// Downloads the database to the given path.
+    async fn download(&self, path: &Path, proxy: &ProxyConfig) -> crate::Result<()> {
+        let client = HttpClient::new(TlsSettings::from_options(&None)?, proxy)?;
+        let auth = Auth::Basic {
+            user: self.account_id.clone(),
+            password: self.license_key.clone(),
+        };
+
+        let mut uri = format!(
+            "{}/geoip/databases/{}/download?suffix=tar.gz",
+            self.endpoint.trim_end_matches('/'),
+            self.edition_id
+        );
+        let timeout = Duration::from_secs(self.timeout_secs);
+        let archive = tokio::time::timeout(timeout, async {
+            for redirect in 0..=MAX_REDIRECTS {
+                let mut request = Request::get(uri.as_str()).body(Body::empty())?;
+                // The archive is served from elsewhere, which mustn't be sent the credentials.
+                if redirect == 0 {
+                    auth.apply(&mut request);
+                }
+
+                let response = client.send(request).await?;
+                let status = response.status();
+                if status.is_redirection() {
+                    let location = response
+                        .headers()
+                        .get(LOCATION)
+                        .and_then(|location| location.to_str().ok())
+                        .ok_or("Redirected without a location.")?;
+                    uri = Url::parse(&uri)?.join(location)?.to_string();
+                } else if status.is_success() {
+                    return Ok(hyper::body::to_bytes(response.into_body()).await?);
+                } else {
+                    return Err(format!("Unexpected response status: {}.", status).into());
+                }
+            }
+            Err::<_, crate::Error>("Too many redirects.".into())
+        })
+        .await??;
+
+        let database = extract_database(&archive)?;
+        // Checked before it replaces the current database, which keeps being used otherwise.
+        Reader::from_source(database.as_slice())?;
+
+        // The database is written aside first, so that a partially written database is never
+        // loaded.
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, &database).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+
+        info!(
+            message = "Downloaded GeoIP database.",
+            edition_id = %self.edition_id,
+            bytes = database.len(),
+        );
+        Ok(())
+    }
+}
+
+/// Extracts the database from a gzipped tar archive, as served by MaxMind along with the license
+/// and copyright files.
+fn extract_database(archive: &[u8]) -> crate::Result<Vec<u8>> {
+    let mut tar = Vec::new();
+    GzDecoder::new(archive).read_to_end(&mut tar)?;
+
+    let mut offset = 0;
+    while let Some(header) = tar.get(offset..offset + TAR_BLOCK_SIZE) {
+        // The archive ends with empty blocks.
+        if header.iter().all(|byte| *byte == 0) {
+            break;
+        }
+
+        let name = tar_header_field(&header[0..100]);
+        let size = usize::from_str_radix(tar_header_field(&header[124..136]).trim(), 8)
+            .map_err(|_| "Invalid size of archive entry.")?;
+        let start = offset + TAR_BLOCK_SIZE;
+        let data = tar
+            .get(start..start + size)
+            .ok_or("The archive is truncated.")?;
+
+        // Regular files have either of these types, depending on the format of the archive.
+        if matches!(header[156], b'0' | 0) && name.ends_with(".mmdb") {
+            return Ok(data.to_vec());
+        }
+        offset = start + (size + TAR_BLOCK_SIZE - 1) / TAR_BLOCK_SIZE * TAR_BLOCK_SIZE;
+    }
+
+    Err("The archive doesn't contain a database.".into())
+}
+
+/// Returns the text of a field of a tar header, which ends at the first NUL byte.
+fn tar_header_field(field: &[u8]) -> &str {
+    let end = field
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(field.len());
+    std::str::from_utf8(&field[..end]).unwrap_or_default()
 }
 
 #[derive(Clone)]
@@ -289,9 +514,15 @@ impl Table for Geoip {
         Vec::new()
     }
 
-    /// Returns true if the underlying data has changed and the table needs reloading.
+    /// Returns true if the underlying data has changed and the table needs reloading, or the
+    /// database is due to be downloaded again.
     fn needs_reload(&self) -> bool {
-        matches!(fs::metadata(&self.config.path)
+        let stale = self.config.download.as_ref().map_or(false, |download| {
+            download.is_stale(Path::new(&self.config.path))
+        });
+
+        stale
+            || matches!(fs::metadata(&self.config.path)
             .and_then(|metadata| metadata.modified()),
             Ok(modified) if modified > self.last_modified)
     }
@@ -309,7 +540,13 @@ impl std::fmt::Debug for Geoip {
 
 #[cfg(test)]
 mod tests {
+    use std::{convert::Infallible, io::Write};
+
+    use flate2::{write::GzEncoder, Compression};
+    use http::{header::AUTHORIZATION, Response, StatusCode};
+
     use super::*;
+    use crate::test_util::{http::spawn_blackhole_http_server, temp_dir};
 
     #[test]
     fn city_lookup() {
@@ -434,6 +671,108 @@ mod tests {
         assert!(values.is_none());
     }
 
+    /// Builds a gzipped tar archive of the given files, as MaxMind serves the databases.
+    fn archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = Vec::new();
+        for (name, data) in files {
+            let mut header = [0; TAR_BLOCK_SIZE];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+            header[156] = b'0';
+            tar.extend_from_slice(&header);
+            tar.extend_from_slice(data);
+            tar.resize(
+                (tar.len() + TAR_BLOCK_SIZE - 1) / TAR_BLOCK_SIZE * TAR_BLOCK_SIZE,
+                0,
+            );
+        }
+        tar.resize(tar.len() + 2 * TAR_BLOCK_SIZE, 0);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&tar).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn extracts_database_from_archive() {
+        let database = fs::read("tests/data/GeoIP2-City-Test.mmdb").unwrap();
+        let archive = archive(&[
+            ("GeoIP2-City-Test_20230101/LICENSE.txt", &b"license"[..]),
+            (
+                "GeoIP2-City-Test_20230101/GeoIP2-City-Test.mmdb",
+                database.as_slice(),
+            ),
+        ]);
+
+        assert_eq!(extract_database(&archive).unwrap(), database);
+        assert!(extract_database(&self::archive(&[("LICENSE.txt", &b"license"[..])])).is_err());
+    }
+
+    #[tokio::test]
+    async fn downloads_database() {
+        let database = fs::read("tests/data/GeoIP2-City-Test.mmdb").unwrap();
+        let archive = archive(&[(
+            "GeoIP2-City-Test_20230101/GeoIP2-City-Test.mmdb",
+            database.as_slice(),
+        )]);
+        let uri = spawn_blackhole_http_server(move |request: Request<Body>| {
+            let archive = archive.clone();
+            async move {
+                let authorized = request.headers().contains_key(AUTHORIZATION);
+                let response = match request.uri().path() {
+                    "/geoip/databases/GeoIP2-City-Test/download" if authorized => {
+                        Response::builder()
+                            .status(StatusCode::FOUND)
+                            .header(LOCATION, "/archive.tar.gz")
+                            .body(Body::empty())
+                    }
+                    // The credentials aren't sent along the redirect.
+                    "/archive.tar.gz" if !authorized => {
+                        Response::builder().body(Body::from(archive))
+                    }
+                    _ => Response::builder()
+                        .status(StatusCode::UNAUTHORIZED)
+                        .body(Body::empty()),
+                };
+                Ok::<_, Infallible>(response.unwrap())
+            }
+        })
+        .await;
+
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("GeoIP2-City-Test.mmdb");
+        let config = GeoipConfig {
+            path: path.to_str().unwrap().to_owned(),
+            locale: default_locale(),
+            download: Some(GeoipDownloadConfig {
+                account_id: "123".to_owned(),
+                license_key: "key".to_owned().into(),
+                edition_id: "GeoIP2-City-Test".to_owned(),
+                refresh_interval_secs: default_refresh_interval_secs(),
+                endpoint: uri.to_string(),
+                timeout_secs: default_download_timeout_secs(),
+            }),
+        };
+
+        let table = config.build(&GlobalOptions::default()).await.unwrap();
+        assert_eq!(fs::read(&path).unwrap(), database);
+        assert!(!table.needs_reload());
+
+        let row = table
+            .find_table_row(
+                Case::Insensitive,
+                &[Condition::Equals {
+                    field: "ip",
+                    value: "2.125.160.216".into(),
+                }],
+                Some(&["city_name".to_string()]),
+                None,
+            )
+            .unwrap();
+        assert_eq!(row["city_name"], Value::from("Boxford"));
+    }
+
     fn find(ip: &str, database: &str) -> Option<BTreeMap<String, Value>> {
         find_select(ip, database, None)
     }
@@ -446,6 +785,7 @@ mod tests {
         Geoip::new(GeoipConfig {
             path: database.to_string(),
             locale: default_locale(),
+            download: None,
         })
         .unwrap()
         .find_table_rows(
@@ -0,0 +1,350 @@
+//! Handles enrichment tables for `type = http`.
+//! The table is fetched from an HTTP(S) endpoint, and cached in the data directory so that
+//! it's only fetched again once it changed, and it's still available when the endpoint isn't.
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use enrichment::{Case, Condition, IndexHandle, Table};
+use http::{
+    header::{HeaderValue, ETAG, IF_NONE_MATCH},
+    Request, StatusCode,
+};
+use hyper::Body;
+use url::Url;
+use value::Value;
+use vector_config::configurable_component;
+
+use super::file::{Encoding, File, FileConfig};
+use crate::{
+    config::{EnrichmentTableConfig, GenerateConfig, GlobalOptions, ProxyConfig},
+    http::{Auth, HttpClient},
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `http` enrichment table.
+#[configurable_component(enrichment_table("http"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpConfig {
+    /// The URL to fetch the enrichment table from.
+    ///
+    /// Currently, only [CSV][csv] tables are supported.
+    ///
+    /// [csv]: https://en.wikipedia.org/wiki/Comma-separated_values
+    #[configurable(metadata(docs::examples = "https://example.com/hosts.csv"))]
+    url: Url,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    encoding: Encoding,
+
+    /// Key/value pairs representing mapped column names and types.
+    ///
+    /// The available types are the same as for the `schema` of the `file` enrichment table.
+    #[serde(default)]
+    schema: HashMap<String, String>,
+
+    /// The timeout for fetching the enrichment table, in seconds.
+    #[serde(default = "default_timeout_secs")]
+    timeout_secs: u64,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+
+    /// The directory used to cache the fetched enrichment table.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    data_dir: Option<PathBuf>,
+}
+
+const fn default_timeout_secs() -> u64 {
+    60
+}
+
+impl GenerateConfig for HttpConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            url: Url::parse("https://example.com/hosts.csv").unwrap(),
+            encoding: Encoding::default(),
+            schema: HashMap::new(),
+            timeout_secs: default_timeout_secs(),
+            auth: None,
+            tls: None,
+            proxy: ProxyConfig::default(),
+            data_dir: None,
+        })
+        .unwrap()
+    }
+}
+
+impl HttpConfig {
+    /// Fetches the table to the given path, unless the table cached there is still current.
+    async fn fetch(&self, path: &Path, proxy: &ProxyConfig) -> crate::Result<()> {
+        let etag_path = path.with_extension("etag");
+        let etag = if path.exists() {
+            tokio::fs::read_to_string(&etag_path).await.ok()
+        } else {
+            None
+        };
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+
+        let mut request = Request::get(self.url.as_str()).body(Body::empty())?;
+        if let Some(etag) = &etag {
+            request
+                .headers_mut()
+                .insert(IF_NONE_MATCH, HeaderValue::from_str(etag.trim())?);
+        }
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response =
+            tokio::time::timeout(Duration::from_secs(self.timeout_secs), client.send(request))
+                .await??;
+
+        match response.status() {
+            StatusCode::NOT_MODIFIED if etag.is_some() => {
+                debug!(message = "Enrichment table not modified.", url = %self.url);
+                Ok(())
+            }
+            status if status.is_success() => {
+                let etag = response.headers().get(ETAG).cloned();
+                let body = hyper::body::to_bytes(response.into_body()).await?;
+
+                // The table is written aside first, so that a partially written table is never
+                // loaded.
+                let tmp_path = path.with_extension("tmp");
+                tokio::fs::write(&tmp_path, &body).await?;
+                tokio::fs::rename(&tmp_path, path).await?;
+                match etag {
+                    Some(etag) => tokio::fs::write(&etag_path, etag.as_bytes()).await?,
+                    None if etag_path.exists() => tokio::fs::remove_file(&etag_path).await?,
+                    None => (),
+                }
+
+                debug!(message = "Fetched enrichment table.", url = %self.url, bytes = body.len());
+                Ok(())
+            }
+            status => Err(format!("Unexpected response status: {}.", status).into()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EnrichmentTableConfig for HttpConfig {
+    async fn build(&self, globals: &GlobalOptions) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        let data_dir =
+            globals.resolve_and_make_data_subdir(self.data_dir.as_ref(), "enrichment_tables")?;
+        let path = data_dir.join(format!(
+            "{:016x}.csv",
+            seahash::hash(self.url.as_str().as_bytes())
+        ));
+
+        let proxy = ProxyConfig::merge_with_env(&globals.proxy, &self.proxy);
+        if let Err(error) = self.fetch(&path, &proxy).await {
+            if !path.exists() {
+                return Err(error);
+            }
+            warn!(
+                message = "Failed to fetch enrichment table, using the cached table instead.",
+                url = %self.url,
+                %error,
+            );
+        }
+
+        let file = FileConfig::with_path(path, self.encoding.clone(), self.schema.clone())
+            .load(globals.timezone())?;
+
+        Ok(Box::new(Http { file }))
+    }
+}
+
+/// A struct that implements [enrichment::Table] to handle loading enrichment data from an HTTP
+/// endpoint.
+#[derive(Clone)]
+pub struct Http {
+    file: File,
+}
+
+impl Table for Http {
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&'a [String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        self.file.find_table_row(case, condition, select, index)
+    }
+
+    fn find_table_rows<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&'a [String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        self.file.find_table_rows(case, condition, select, index)
+    }
+
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        self.file.add_index(case, fields)
+    }
+
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        self.file.index_fields()
+    }
+
+    /// The endpoint is always asked whether the table changed, which it answers cheaply when it
+    /// supports entity tags.
+    fn needs_reload(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Debug for Http {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Http {:?}", self.file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use http::Response;
+
+    use super::*;
+    use crate::test_util::{http::spawn_blackhole_http_server, temp_dir};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HttpConfig>();
+    }
+
+    fn http_config(url: &str, data_dir: &Path) -> HttpConfig {
+        toml::from_str(&format!(
+            r#"
+            url = "{}"
+            data_dir = "{}"
+            "#,
+            url,
+            data_dir.display()
+        ))
+        .unwrap()
+    }
+
+    fn find_name(table: &dyn Table, id: &str) -> Result<Value, String> {
+        let condition = [Condition::Equals {
+            field: "id",
+            value: id.into(),
+        }];
+        table
+            .find_table_row(Case::Sensitive, &condition, None, None)
+            .map(|row| row["name"].clone())
+    }
+
+    #[tokio::test]
+    async fn revalidates_cached_table() {
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let uri = spawn_blackhole_http_server({
+            let fetched = Arc::clone(&fetched);
+            move |request: Request<Body>| {
+                let fetched = Arc::clone(&fetched);
+                async move {
+                    let response = if request.headers().get(IF_NONE_MATCH)
+                        == Some(&HeaderValue::from_static("\"v1\""))
+                    {
+                        Response::builder()
+                            .status(StatusCode::NOT_MODIFIED)
+                            .body(Body::empty())
+                    } else {
+                        fetched.fetch_add(1, Ordering::Relaxed);
+                        Response::builder()
+                            .header(ETAG, "\"v1\"")
+                            .body(Body::from("id,name\n1,first\n"))
+                    };
+                    Ok::<_, Infallible>(response.unwrap())
+                }
+            }
+        })
+        .await;
+
+        let data_dir = temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let config = http_config(&uri.to_string(), &data_dir);
+        let globals = GlobalOptions::default();
+
+        let table = config.build(&globals).await.unwrap();
+        assert_eq!(find_name(table.as_ref(), "1"), Ok(Value::from("first")));
+        assert!(table.needs_reload());
+
+        let table = config.build(&globals).await.unwrap();
+        assert_eq!(find_name(table.as_ref(), "1"), Ok(Value::from("first")));
+        assert_eq!(fetched.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_cached_table() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let uri = spawn_blackhole_http_server({
+            let requests = Arc::clone(&requests);
+            move |_| {
+                // Only the first request succeeds.
+                let response = if requests.fetch_add(1, Ordering::Relaxed) == 0 {
+                    Response::new(Body::from("id,name\n1,first\n"))
+                } else {
+                    Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::empty())
+                        .unwrap()
+                };
+                async move { Ok::<_, Infallible>(response) }
+            }
+        })
+        .await;
+
+        let data_dir = temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let config = http_config(&uri.to_string(), &data_dir);
+        let globals = GlobalOptions::default();
+
+        config.build(&globals).await.unwrap();
+        let table = config.build(&globals).await.unwrap();
+        assert_eq!(find_name(table.as_ref(), "1"), Ok(Value::from("first")));
+
+        let empty_dir = temp_dir();
+        std::fs::create_dir_all(&empty_dir).unwrap();
+        let error = http_config(&uri.to_string(), &empty_dir)
+            .build(&globals)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unexpected response status: 500 Internal Server Error."
+        );
+    }
+}
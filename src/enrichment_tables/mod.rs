@@ -10,6 +10,15 @@ pub mod file;
 #[cfg(feature = "enrichment-tables-geoip")]
 pub mod geoip;
 
+#[cfg(feature = "enrichment-tables-http")]
+pub mod http;
+
+#[cfg(feature = "enrichment-tables-redis")]
+pub mod redis;
+
+mod reload;
+pub use reload::reload_signals;
+
 /// Configurable enrichment tables.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -25,6 +34,20 @@ pub enum EnrichmentTables {
     /// [geoip2]: https://www.maxmind.com/en/geoip2-databases
     #[cfg(feature = "enrichment-tables-geoip")]
     Geoip(geoip::GeoipConfig),
+
+    /// Exposes data fetched from an HTTP(S) endpoint as an enrichment table.
+    ///
+    /// The fetched data is cached, and only fetched again once the endpoint serves new data.
+    #[cfg(feature = "enrichment-tables-http")]
+    Http(http::HttpConfig),
+
+    /// Exposes data looked up by key in [Redis][redis] as an enrichment table.
+    ///
+    /// The looked up rows are cached for a while, as are the keys that weren't found.
+    ///
+    /// [redis]: https://redis.io/
+    #[cfg(feature = "enrichment-tables-redis")]
+    Redis(redis::RedisConfig),
 }
 
 // TODO: Use `enum_dispatch` here.
@@ -34,6 +57,10 @@ impl NamedComponent for EnrichmentTables {
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "enrichment-tables-geoip")]
             Self::Geoip(config) => config.get_component_name(),
+            #[cfg(feature = "enrichment-tables-http")]
+            Self::Http(config) => config.get_component_name(),
+            #[cfg(feature = "enrichment-tables-redis")]
+            Self::Redis(config) => config.get_component_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
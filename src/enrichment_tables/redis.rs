@@ -0,0 +1,542 @@
+//! Handles enrichment tables for `type = redis`.
+//! Each row of the table is stored by its key in Redis, and looked up when it's searched for.
+//! The looked up rows, along with the keys that weren't found, are cached in the process.
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use enrichment::{Case, Condition, IndexHandle, Table};
+use lru::LruCache;
+use redis::{Client, Connection};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use value::Value;
+use vector_config::configurable_component;
+
+use crate::config::{EnrichmentTableConfig, GenerateConfig, GlobalOptions};
+
+type Row = BTreeMap<String, Value>;
+
+/// Redis data type the rows are stored as.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DataType {
+    /// The Redis `string` type.
+    ///
+    /// The value is available in the `value` column of the row.
+    ///
+    /// This is the default.
+    #[default]
+    String,
+
+    /// The Redis `string` type, holding a JSON object.
+    ///
+    /// The fields of the object are the columns of the row.
+    Json,
+
+    /// The Redis `hash` type.
+    ///
+    /// The fields of the hash are the columns of the row.
+    Hash,
+}
+
+/// Caching options.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    /// The maximum number of keys to cache.
+    ///
+    /// Once reached, the least recently looked up keys are evicted first.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: NonZeroUsize,
+
+    /// How long to cache the rows that were found, in seconds.
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: u64,
+
+    /// How long to cache the keys that weren't found, in seconds.
+    ///
+    /// This keeps keys that aren't in Redis from being looked up for each event.
+    #[serde(default = "default_negative_ttl_secs")]
+    pub negative_ttl_secs: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: default_max_entries(),
+            ttl_secs: default_ttl_secs(),
+            negative_ttl_secs: default_negative_ttl_secs(),
+        }
+    }
+}
+
+fn default_max_entries() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+const fn default_ttl_secs() -> u64 {
+    60
+}
+
+const fn default_negative_ttl_secs() -> u64 {
+    10
+}
+
+fn default_key_field() -> String {
+    "key".to_owned()
+}
+
+const fn default_timeout_ms() -> u64 {
+    1000
+}
+
+/// Configuration for the `redis` enrichment table.
+#[configurable_component(enrichment_table("redis"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RedisConfig {
+    /// The URL of the Redis endpoint to connect to.
+    ///
+    /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    data_type: DataType,
+
+    /// The field that rows are searched by.
+    ///
+    /// Searches must have a single condition, on this field.
+    #[serde(default = "default_key_field")]
+    key_field: String,
+
+    /// The prefix added to the searched values to form the Redis keys.
+    #[configurable(metadata(docs::examples = "users:"))]
+    #[serde(default)]
+    key_prefix: String,
+
+    /// The timeout for connecting to Redis and for each lookup, in milliseconds.
+    #[serde(default = "default_timeout_ms")]
+    timeout_ms: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    cache: CacheConfig,
+}
+
+impl GenerateConfig for RedisConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "redis://127.0.0.1:6379/0".to_owned(),
+            data_type: DataType::default(),
+            key_field: default_key_field(),
+            key_prefix: String::new(),
+            timeout_ms: default_timeout_ms(),
+            cache: CacheConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl EnrichmentTableConfig for RedisConfig {
+    async fn build(&self, _: &GlobalOptions) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        Ok(Box::new(Redis::new(self.clone())?))
+    }
+}
+
+#[derive(Clone)]
+struct CachedRow {
+    expires_at: Instant,
+    row: Option<Row>,
+}
+
+/// A struct that implements [enrichment::Table] to handle looking up enrichment data in Redis.
+#[derive(Clone)]
+pub struct Redis {
+    config: RedisConfig,
+    client: Client,
+    connection: Arc<Mutex<Option<Connection>>>,
+    cache: Arc<Mutex<LruCache<String, CachedRow>>>,
+    indexes: Vec<Vec<String>>,
+}
+
+impl Redis {
+    /// Creates a new Redis table from the provided config.
+    ///
+    /// The connection to Redis is only opened once a row is looked up.
+    pub fn new(config: RedisConfig) -> crate::Result<Self> {
+        let client = Client::open(config.endpoint.as_str())?;
+        let cache = LruCache::new(config.cache.max_entries);
+
+        Ok(Self {
+            config,
+            client,
+            connection: Arc::new(Mutex::new(None)),
+            cache: Arc::new(Mutex::new(cache)),
+            indexes: Vec::new(),
+        })
+    }
+
+    fn key(&self, condition: &[Condition]) -> Result<String, String> {
+        match condition {
+            [Condition::Equals { field, value }] if *field == self.config.key_field => {
+                let value = match value {
+                    Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+                    value => value.to_string_lossy().into_owned(),
+                };
+                Ok(format!("{}{}", self.config.key_prefix, value))
+            }
+            _ => Err(format!(
+                "only a single condition on the `{}` field is supported",
+                self.config.key_field
+            )),
+        }
+    }
+
+    /// Looks the row of the key up in the cache first, then in Redis.
+    fn lookup(&self, key: String) -> Result<Option<Row>, String> {
+        let now = Instant::now();
+        if let Some(cached) = self.cache.lock().expect("poisoned lock").get(&key) {
+            if cached.expires_at > now {
+                return Ok(cached.row.clone());
+            }
+        }
+
+        let row = blocking(|| self.fetch(&key))?;
+        let ttl = if row.is_some() {
+            self.config.cache.ttl_secs
+        } else {
+            self.config.cache.negative_ttl_secs
+        };
+        if ttl > 0 {
+            let cached = CachedRow {
+                expires_at: now + Duration::from_secs(ttl),
+                row: row.clone(),
+            };
+            self.cache.lock().expect("poisoned lock").put(key, cached);
+        }
+
+        Ok(row)
+    }
+
+    fn fetch(&self, key: &str) -> Result<Option<Row>, String> {
+        let mut connection = self.connection.lock().expect("poisoned lock");
+        if connection.is_none() {
+            *connection = Some(self.connect().map_err(|error| error.to_string())?);
+        }
+        let row = self.query(connection.as_mut().expect("connected above"), key);
+
+        // The connection is opened again for the next lookup, in case it was broken.
+        row.map_err(|error| {
+            *connection = None;
+            error.to_string()
+        })
+    }
+
+    fn connect(&self) -> redis::RedisResult<Connection> {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let connection = self.client.get_connection_with_timeout(timeout)?;
+        connection.set_read_timeout(Some(timeout))?;
+        connection.set_write_timeout(Some(timeout))?;
+        Ok(connection)
+    }
+
+    fn query(&self, connection: &mut Connection, key: &str) -> redis::RedisResult<Option<Row>> {
+        let fields = match self.config.data_type {
+            DataType::String => redis::cmd("GET")
+                .arg(key)
+                .query::<Option<Vec<u8>>>(connection)?
+                .map(|value| Row::from([("value".to_owned(), Value::Bytes(value.into()))])),
+            DataType::Json => redis::cmd("GET")
+                .arg(key)
+                .query::<Option<Vec<u8>>>(connection)?
+                .map(|value| parse_json(&value))
+                .transpose()?,
+            DataType::Hash => {
+                let fields = redis::cmd("HGETALL")
+                    .arg(key)
+                    .query::<HashMap<String, Vec<u8>>>(connection)?;
+                // Redis doesn't tell missing hashes apart from empty ones.
+                (!fields.is_empty()).then(|| {
+                    fields
+                        .into_iter()
+                        .map(|(field, value)| (field, Value::Bytes(value.into())))
+                        .collect()
+                })
+            }
+        };
+
+        Ok(fields)
+    }
+
+    fn row(&self, key: String, select: Option<&[String]>) -> Result<Option<Row>, String> {
+        let row = self.lookup(key)?;
+        Ok(row.map(|row| match select {
+            Some(select) => row
+                .into_iter()
+                .filter(|(column, _)| select.contains(column))
+                .collect(),
+            None => row,
+        }))
+    }
+}
+
+fn parse_json(value: &[u8]) -> redis::RedisResult<Row> {
+    let invalid = |detail: String| {
+        redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            "value isn't a JSON object",
+            detail,
+        ))
+    };
+    match serde_json::from_slice(value).map_err(|error| invalid(error.to_string()))? {
+        serde_json::Value::Object(object) => Ok(object
+            .into_iter()
+            .map(|(field, value)| (field, Value::from(value)))
+            .collect()),
+        value => Err(invalid(value.to_string())),
+    }
+}
+
+/// Runs the lookup without holding up the other tasks of the runtime, when it has other threads
+/// to run them on.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+impl Table for Redis {
+    fn find_table_row<'a>(
+        &self,
+        _: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&'a [String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Row, String> {
+        self.row(self.key(condition)?, select)?
+            .ok_or_else(|| "no rows found".to_string())
+    }
+
+    fn find_table_rows<'a>(
+        &self,
+        _: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&'a [String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<Row>, String> {
+        Ok(self
+            .row(self.key(condition)?, select)?
+            .into_iter()
+            .collect())
+    }
+
+    /// Rows can only be searched by their key, so there's nothing to index. This only checks that
+    /// the searches will be supported.
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        if case == Case::Insensitive {
+            return Err("case insensitive searches aren't supported".to_string());
+        }
+        if fields != [self.config.key_field.as_str()] {
+            return Err(format!(
+                "only a single condition on the `{}` field is supported",
+                self.config.key_field
+            ));
+        }
+        if self.indexes.is_empty() {
+            self.indexes.push(vec![self.config.key_field.clone()]);
+        }
+        Ok(IndexHandle(0))
+    }
+
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        self.indexes
+            .iter()
+            .map(|fields| (Case::Sensitive, fields.clone()))
+            .collect()
+    }
+
+    /// The rows are always looked up in Redis, so the table is only rebuilt to apply a changed
+    /// config, which also starts over with an empty cache.
+    fn needs_reload(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Debug for Redis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Redis {} cached key(s)",
+            self.cache.lock().expect("poisoned lock").len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisConfig>();
+    }
+
+    fn table(config: &str) -> Redis {
+        Redis::new(toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn condition(field: &'static str, value: &str) -> [Condition<'static>; 1] {
+        [Condition::Equals {
+            field,
+            value: value.into(),
+        }]
+    }
+
+    #[test]
+    fn only_supports_searching_by_key() {
+        let mut table = table(
+            r#"
+            endpoint = "redis://127.0.0.1:6379/0"
+            key_field = "user"
+            key_prefix = "users:"
+            "#,
+        );
+
+        assert_eq!(
+            table.add_index(Case::Sensitive, &["user"]),
+            Ok(IndexHandle(0))
+        );
+        assert!(table.add_index(Case::Insensitive, &["user"]).is_err());
+        assert!(table.add_index(Case::Sensitive, &["user", "name"]).is_err());
+        assert_eq!(
+            table.index_fields(),
+            vec![(Case::Sensitive, vec!["user".to_owned()])]
+        );
+
+        assert_eq!(
+            table.key(&condition("user", "alice")),
+            Ok("users:alice".to_owned())
+        );
+        assert!(table.key(&condition("name", "alice")).is_err());
+    }
+
+    #[test]
+    fn serves_cached_rows_until_they_expire() {
+        // Nothing listens there, so any lookup that isn't cached fails.
+        let table = table(
+            r#"
+            endpoint = "redis://127.0.0.1:1/0"
+            timeout_ms = 100
+            "#,
+        );
+        let expired = Instant::now() - Duration::from_secs(1);
+        let row = Row::from([("value".to_owned(), Value::from("account"))]);
+        {
+            let mut cache = table.cache.lock().unwrap();
+            for (key, row, expires_at) in [
+                (
+                    "found",
+                    Some(row.clone()),
+                    Instant::now() + Duration::from_secs(60),
+                ),
+                ("missing", None, Instant::now() + Duration::from_secs(60)),
+                ("expired", Some(row.clone()), expired),
+            ] {
+                cache.put(key.to_owned(), CachedRow { expires_at, row });
+            }
+        }
+
+        let find =
+            |key: &str| table.find_table_row(Case::Sensitive, &condition("key", key), None, None);
+        assert_eq!(find("found"), Ok(row));
+        assert_eq!(find("missing"), Err("no rows found".to_owned()));
+        assert!(find("expired").unwrap_err() != "no rows found");
+    }
+
+    #[test]
+    fn parses_json_objects() {
+        assert_eq!(
+            parse_json(br#"{"account": "acme", "seats": 3}"#).unwrap(),
+            Row::from([
+                ("account".to_owned(), Value::from("acme")),
+                ("seats".to_owned(), Value::from(3)),
+            ])
+        );
+        assert!(parse_json(b"[1, 2]").is_err());
+        assert!(parse_json(b"account").is_err());
+    }
+}
+
+#[cfg(feature = "redis-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    fn redis_server() -> String {
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/0".to_owned())
+    }
+
+    fn find(table: &Redis, key: &str) -> Result<Row, String> {
+        let condition = [Condition::Equals {
+            field: "key",
+            value: key.into(),
+        }];
+        table.find_table_row(Case::Sensitive, &condition, None, None)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn looks_up_hashes() {
+        let prefix = format!("test-{}:", crate::test_util::random_string(10));
+        let mut connection = Client::open(redis_server())
+            .unwrap()
+            .get_connection()
+            .unwrap();
+        redis::cmd("HSET")
+            .arg(format!("{}alice", prefix))
+            .arg("account")
+            .arg("acme")
+            .query::<()>(&mut connection)
+            .unwrap();
+
+        let table = Redis::new(
+            toml::from_str(&format!(
+                r#"
+                endpoint = "{}"
+                data_type = "hash"
+                key_prefix = "{}"
+                "#,
+                redis_server(),
+                prefix
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            find(&table, "alice"),
+            Ok(Row::from([("account".to_owned(), Value::from("acme"))]))
+        );
+
+        // The missing key stays missing until its negative result expires.
+        assert_eq!(find(&table, "bob"), Err("no rows found".to_owned()));
+        redis::cmd("HSET")
+            .arg(format!("{}bob", prefix))
+            .arg("account")
+            .arg("initech")
+            .query::<()>(&mut connection)
+            .unwrap();
+        assert_eq!(find(&table, "bob"), Err("no rows found".to_owned()));
+    }
+}
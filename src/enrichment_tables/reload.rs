@@ -0,0 +1,189 @@
+//! Schedules the reload of enrichment tables while Vector is running.
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt,
+};
+use indexmap::IndexMap;
+use notify::{recommended_watcher, EventKind, RecursiveMode, Watcher};
+use tokio::{
+    sync::mpsc,
+    time::{interval_at, Instant},
+};
+use tokio_stream::wrappers::IntervalStream;
+
+use crate::{
+    config::{ComponentKey, EnrichmentTableConfig, EnrichmentTableOuter},
+    signal::SignalTo,
+};
+
+/// How long to wait for the changes of a watched file to settle before reloading its table.
+const WATCH_DELAY: Duration = Duration::from_secs(1);
+
+/// Signals the reload of the enrichment tables that are configured to be reloaded, if any.
+pub fn reload_signals(
+    tables: &IndexMap<ComponentKey, EnrichmentTableOuter>,
+) -> Option<impl Stream<Item = SignalTo>> {
+    let mut streams: Vec<BoxStream<'static, Vec<ComponentKey>>> = Vec::new();
+
+    for (key, table) in tables {
+        let period = table
+            .reload
+            .interval_secs
+            .map(|interval_secs| Duration::from_secs(interval_secs.get()))
+            .or_else(|| table.inner.default_reload_interval());
+        if let Some(period) = period {
+            let key = key.clone();
+            streams.push(
+                IntervalStream::new(interval_at(Instant::now() + period, period))
+                    .map(move |_| vec![key.clone()])
+                    .boxed(),
+            );
+        }
+    }
+
+    let watched = tables
+        .iter()
+        .filter(|(_, table)| table.reload.watch)
+        .flat_map(|(key, table)| {
+            table
+                .inner
+                .files()
+                .into_iter()
+                .map(move |path| (path, key.clone()))
+        })
+        .collect::<Vec<_>>();
+    if !watched.is_empty() {
+        match watch_files(watched) {
+            Ok(stream) => streams.push(stream.boxed()),
+            Err(error) => error!(message = "Failed to watch enrichment table files.", %error),
+        }
+    }
+
+    (!streams.is_empty()).then(|| stream::select_all(streams).map(SignalTo::ReloadEnrichmentTables))
+}
+
+/// Yields the tables whose files changed, once no change happened for a while.
+///
+/// The directories of the files are watched rather than the files themselves, so that files
+/// replaced by a rename are still watched afterwards.
+fn watch_files(
+    files: Vec<(PathBuf, ComponentKey)>,
+) -> crate::Result<impl Stream<Item = Vec<ComponentKey>>> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = recommended_watcher(move |event: notify::Result<notify::Event>| {
+        // Sending only fails once the stream, and the watcher along with it, is dropped.
+        let _ = tx.send(event);
+    })?;
+
+    let mut tables = HashMap::<PathBuf, Vec<ComponentKey>>::new();
+    for (path, key) in files {
+        let path = absolute_path(&path)?;
+        if let Some(dir) = path.parent() {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        tables.entry(path).or_default().push(key);
+    }
+
+    info!(message = "Watching enrichment table files.");
+
+    Ok(async_stream::stream! {
+        // The watcher stops watching once dropped.
+        let _watcher = watcher;
+        while let Some(event) = rx.recv().await {
+            let mut changed = changed_tables(&tables, event);
+            if changed.is_empty() {
+                continue;
+            }
+
+            // Accumulate the changes until none happened for the delay.
+            while let Ok(Some(event)) = tokio::time::timeout(WATCH_DELAY, rx.recv()).await {
+                changed.extend(changed_tables(&tables, event));
+            }
+            let mut seen = HashSet::new();
+            changed.retain(|key| seen.insert(key.clone()));
+
+            info!(message = "Enrichment table files changed.", tables = ?changed);
+            yield changed;
+        }
+    })
+}
+
+fn changed_tables(
+    tables: &HashMap<PathBuf, Vec<ComponentKey>>,
+    event: notify::Result<notify::Event>,
+) -> Vec<ComponentKey> {
+    match event {
+        Ok(event)
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_)
+            ) =>
+        {
+            event
+                .paths
+                .iter()
+                .filter_map(|path| tables.get(path))
+                .flatten()
+                .cloned()
+                .collect()
+        }
+        Ok(_) => Vec::new(),
+        Err(error) => {
+            warn!(message = "Failed to watch enrichment table files.", %error);
+            Vec::new()
+        }
+    }
+}
+
+/// Resolves the directory of the path, as the paths of the watched events are resolved.
+fn absolute_path(path: &Path) -> crate::Result<PathBuf> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.canonicalize()?,
+        _ => std::env::current_dir()?,
+    };
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Enrichment table path {:?} isn't a file.", path))?;
+
+    Ok(dir.join(name))
+}
+
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[tokio::test]
+    async fn watch_yields_changed_tables() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let first = dir.join("first.csv");
+        let second = dir.join("second.csv");
+        fs::write(&first, "a\n1\n").unwrap();
+        fs::write(&second, "a\n1\n").unwrap();
+
+        let stream = watch_files(vec![
+            (first.clone(), ComponentKey::from("first")),
+            (second, ComponentKey::from("second")),
+        ])
+        .unwrap();
+        tokio::pin!(stream);
+
+        fs::write(dir.join("unrelated.csv"), "a\n1\n").unwrap();
+        fs::write(&first, "a\n2\n").unwrap();
+
+        let changed = tokio::time::timeout(Duration::from_secs(10), stream.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(changed, vec![ComponentKey::from("first")]);
+    }
+}
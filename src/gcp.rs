@@ -1,10 +1,13 @@
 #![allow(missing_docs)]
 use std::{
+    collections::HashMap,
     sync::{Arc, RwLock},
     time::Duration,
 };
 
 use base64::prelude::{Engine as _, BASE64_URL_SAFE};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 pub use goauth::scopes::Scope;
 use goauth::{
     auth::{JwtClaims, Token, TokenErr},
@@ -12,8 +15,9 @@ use goauth::{
     GoErr,
 };
 use http::{uri::PathAndQuery, Uri};
-use hyper::header::AUTHORIZATION;
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
 use once_cell::sync::Lazy;
+use serde::Deserialize;
 use smpl_jwt::Jwt;
 use snafu::{ResultExt, Snafu};
 use tokio::{sync::watch, time::Instant};
@@ -25,6 +29,18 @@ use crate::{config::ProxyConfig, http::HttpClient, http::HttpError};
 const SERVICE_ACCOUNT_TOKEN_URL: &str =
     "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
 
+const IAM_CREDENTIALS_URL: &str =
+    "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts";
+
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+const DEFAULT_IMPERSONATION_LIFETIME_SECS: u64 = 3600;
+
+// The organization policies can extend the lifetime of the impersonated tokens up to 12 hours.
+const MAX_IMPERSONATION_LIFETIME_SECS: u64 = 43200;
+
 pub const PUBSUB_URL: &str = "https://pubsub.googleapis.com";
 
 pub static PUBSUB_ADDRESS: Lazy<String> = Lazy::new(|| {
@@ -56,6 +72,35 @@ pub enum GcpError {
     TokenJsonFromStr { source: serde_json::Error },
     #[snafu(display("Failed to build HTTP client: {}", source))]
     BuildHttpClient { source: HttpError },
+    #[snafu(display("Failed to read GCP credentials: {}", source))]
+    ReadCredentials { source: std::io::Error },
+    #[snafu(display("Invalid GCP external account credentials: {}", source))]
+    InvalidExternalAccount { source: serde_json::Error },
+    #[snafu(display(
+        "Only the `file` and `url` credential sources of GCP external accounts are supported"
+    ))]
+    UnsupportedCredentialSource,
+    #[snafu(display("Failed to read external account subject token: {}", source))]
+    ReadSubjectToken { source: std::io::Error },
+    #[snafu(display("Missing external account subject token field {:?}", field))]
+    MissingSubjectToken { field: String },
+    #[snafu(display("Failed to build OAuth token request: {}", source))]
+    BuildTokenRequest { source: http::Error },
+    #[snafu(display("Failed to send OAuth token request: {}", source))]
+    SendTokenRequest { source: HttpError },
+    #[snafu(display("Failed to impersonate service account ({}): {}", status, body))]
+    ImpersonateServiceAccount {
+        status: http::StatusCode,
+        body: String,
+    },
+    #[snafu(display("Service accounts can't be impersonated with an API key"))]
+    ImpersonationWithApiKey,
+    #[snafu(display(
+        "The impersonation lifetime must be between 1 and {} seconds, got {}",
+        MAX_IMPERSONATION_LIFETIME_SECS,
+        lifetime_secs
+    ))]
+    InvalidImpersonationLifetime { lifetime_secs: u64 },
 }
 
 /// Configuration of the authentication strategy for interacting with GCP services.
@@ -89,9 +134,30 @@ pub struct GcpAuthConfig {
     /// running on. If this is not on a GCE instance, then you must define it with an API key or service account
     /// credentials JSON file.
     ///
+    /// The file can also hold the credentials of an [external account][gcp_workload_identity_federation], exchanging the
+    /// token of another identity provider, read from a file or a URL, for a GCP one through workload identity
+    /// federation, so that no service account key is needed.
+    ///
     /// [gcp_service_account_credentials]: https://cloud.google.com/docs/authentication/production#manually
+    /// [gcp_workload_identity_federation]: https://cloud.google.com/iam/docs/workload-identity-federation
     pub credentials_path: Option<String>,
 
+    /// The email address of a [service account][gcp_impersonation] to impersonate.
+    ///
+    /// The tokens of the service account are requested with the credentials otherwise in use, whose principal must be
+    /// granted the `roles/iam.serviceAccountTokenCreator` role on it. This can't be combined with an API key.
+    ///
+    /// [gcp_impersonation]: https://cloud.google.com/iam/docs/service-account-impersonation
+    #[configurable(metadata(docs::examples = "vector@my-project.iam.gserviceaccount.com"))]
+    pub impersonate_service_account: Option<String>,
+
+    /// How long the tokens of the impersonated service account are valid for, in seconds.
+    ///
+    /// Defaults to an hour, which is the longest allowed unless an organization policy extends it, up to 12 hours.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 3600))]
+    pub impersonation_lifetime_secs: Option<u64>,
+
     /// Skip all authentication handling. For use with integration tests only.
     #[serde(default, skip_serializing)]
     #[configurable(metadata(docs::hidden))]
@@ -103,15 +169,35 @@ impl GcpAuthConfig {
         Ok(if self.skip_authentication {
             GcpAuthenticator::None
         } else {
+            let impersonation = self.impersonation()?;
             let gap = std::env::var("GOOGLE_APPLICATION_CREDENTIALS").ok();
             let creds_path = self.credentials_path.as_ref().or(gap.as_ref());
             match (&creds_path, &self.api_key) {
-                (Some(path), _) => GcpAuthenticator::from_file(path, scope).await?,
+                (Some(path), _) => GcpAuthenticator::from_file(path, scope, impersonation).await?,
+                (None, Some(_)) if impersonation.is_some() => {
+                    return Err(GcpError::ImpersonationWithApiKey.into())
+                }
                 (None, Some(api_key)) => GcpAuthenticator::from_api_key(api_key.inner())?,
-                (None, None) => GcpAuthenticator::new_implicit().await?,
+                (None, None) => GcpAuthenticator::new_implicit(scope, impersonation).await?,
             }
         })
     }
+
+    fn impersonation(&self) -> Result<Option<Impersonation>, GcpError> {
+        let lifetime_secs = self
+            .impersonation_lifetime_secs
+            .unwrap_or(DEFAULT_IMPERSONATION_LIFETIME_SECS);
+        if !(1..=MAX_IMPERSONATION_LIFETIME_SECS).contains(&lifetime_secs) {
+            return Err(GcpError::InvalidImpersonationLifetime { lifetime_secs });
+        }
+        Ok(self
+            .impersonate_service_account
+            .as_ref()
+            .map(|service_account| Impersonation {
+                url: format!("{IAM_CREDENTIALS_URL}/{service_account}:generateAccessToken"),
+                lifetime_secs,
+            }))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -123,22 +209,65 @@ pub enum GcpAuthenticator {
 
 #[derive(Debug)]
 pub struct InnerCreds {
-    creds: Option<(Credentials, Scope)>,
+    fetcher: TokenFetcher,
     token: RwLock<Token>,
 }
 
+/// The `type` of a credentials JSON file, telling the external accounts apart from the service
+/// accounts.
+#[derive(Deserialize)]
+struct CredentialsType {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
 impl GcpAuthenticator {
-    async fn from_file(path: &str, scope: Scope) -> crate::Result<Self> {
-        let creds = Credentials::from_file(path).context(InvalidCredentialsSnafu)?;
-        let token = RwLock::new(fetch_token(&creds, &scope).await?);
-        let creds = Some((creds, scope));
-        Ok(Self::Credentials(Arc::new(InnerCreds { creds, token })))
+    async fn from_file(
+        path: &str,
+        scope: Scope,
+        impersonation: Option<Impersonation>,
+    ) -> crate::Result<Self> {
+        let data = tokio::fs::read(path).await.context(ReadCredentialsSnafu)?;
+        let is_external_account = serde_json::from_slice::<CredentialsType>(&data)
+            .ok()
+            .and_then(|credentials| credentials.kind)
+            .map_or(false, |kind| kind == "external_account");
+
+        let mut impersonations = Vec::new();
+        let source = if is_external_account {
+            let account: ExternalAccount =
+                serde_json::from_slice(&data).context(InvalidExternalAccountSnafu)?;
+            impersonations.extend(account.impersonation());
+            TokenSource::ExternalAccount(account)
+        } else {
+            TokenSource::ServiceAccount(
+                Credentials::from_file(path).context(InvalidCredentialsSnafu)?,
+            )
+        };
+        impersonations.extend(impersonation);
+        Self::from_fetcher(TokenFetcher {
+            source,
+            scope,
+            impersonations,
+        })
+        .await
     }
 
-    async fn new_implicit() -> crate::Result<Self> {
-        let token = RwLock::new(get_token_implicit().await?);
-        let creds = None;
-        Ok(Self::Credentials(Arc::new(InnerCreds { creds, token })))
+    async fn new_implicit(
+        scope: Scope,
+        impersonation: Option<Impersonation>,
+    ) -> crate::Result<Self> {
+        Self::from_fetcher(TokenFetcher {
+            source: TokenSource::Implicit,
+            scope,
+            impersonations: impersonation.into_iter().collect(),
+        })
+        .await
+    }
+
+    async fn from_fetcher(fetcher: TokenFetcher) -> crate::Result<Self> {
+        let token = RwLock::new(fetcher.fetch().await?);
+        Ok(Self::Credentials(Arc::new(InnerCreds { fetcher, token })))
     }
 
     fn from_api_key(api_key: &str) -> crate::Result<Self> {
@@ -223,10 +352,7 @@ impl GcpAuthenticator {
 
 impl InnerCreds {
     async fn regenerate_token(&self) -> crate::Result<()> {
-        let token = match &self.creds {
-            Some((creds, scope)) => fetch_token(creds, scope).await?,
-            None => get_token_implicit().await?,
-        };
+        let token = self.fetcher.fetch().await?;
         *self.token.write().unwrap() = token;
         Ok(())
     }
@@ -237,6 +363,242 @@ impl InnerCreds {
     }
 }
 
+/// Fetches the tokens of the credentials, impersonating the service accounts in turn, if any.
+#[derive(Debug)]
+struct TokenFetcher {
+    source: TokenSource,
+    scope: Scope,
+    impersonations: Vec<Impersonation>,
+}
+
+#[derive(Debug)]
+enum TokenSource {
+    ServiceAccount(Credentials),
+    ExternalAccount(ExternalAccount),
+    Implicit,
+}
+
+impl TokenFetcher {
+    async fn fetch(&self) -> crate::Result<Token> {
+        // The tokens impersonating a service account are requested with a token of the
+        // cloud platform scope, only the last one being given the scope it's used with.
+        let source_scope = if self.impersonations.is_empty() {
+            &self.scope
+        } else {
+            &Scope::CloudPlatform
+        };
+        let mut token = match &self.source {
+            TokenSource::ServiceAccount(creds) => fetch_token(creds, source_scope).await?,
+            TokenSource::ExternalAccount(account) => account.fetch_token(source_scope).await?,
+            TokenSource::Implicit => get_token_implicit().await?,
+        };
+
+        for (index, impersonation) in self.impersonations.iter().enumerate() {
+            let scope = if index + 1 == self.impersonations.len() {
+                &self.scope
+            } else {
+                &Scope::CloudPlatform
+            };
+            token = impersonation.fetch_token(&token, scope).await?;
+        }
+        Ok(token)
+    }
+}
+
+/// The credentials of an [external account][external_account], whose token from another identity
+/// provider is exchanged for a GCP one.
+///
+/// [external_account]: https://google.aip.dev/auth/4117
+#[derive(Debug, Deserialize)]
+struct ExternalAccount {
+    audience: String,
+    subject_token_type: String,
+    token_url: String,
+    credential_source: CredentialSource,
+    service_account_impersonation_url: Option<String>,
+    #[serde(default)]
+    service_account_impersonation: ServiceAccountImpersonation,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ServiceAccountImpersonation {
+    token_lifetime_seconds: Option<u64>,
+}
+
+impl ExternalAccount {
+    /// Returns the impersonation of the service account the external account is given access
+    /// through, if any.
+    fn impersonation(&self) -> Option<Impersonation> {
+        self.service_account_impersonation_url
+            .as_ref()
+            .map(|url| Impersonation {
+                url: url.clone(),
+                lifetime_secs: self
+                    .service_account_impersonation
+                    .token_lifetime_seconds
+                    .unwrap_or(DEFAULT_IMPERSONATION_LIFETIME_SECS),
+            })
+    }
+
+    async fn fetch_token(&self, scope: &Scope) -> crate::Result<Token> {
+        let subject_token = self.credential_source.subject_token().await?;
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", TOKEN_EXCHANGE_GRANT_TYPE)
+            .append_pair("audience", &self.audience)
+            .append_pair("scope", &scope.url())
+            .append_pair("requested_token_type", ACCESS_TOKEN_TYPE)
+            .append_pair("subject_token", &subject_token)
+            .append_pair("subject_token_type", &self.subject_token_type)
+            .finish();
+
+        debug!(
+            message = "Exchanging external account token for GCP authentication token.",
+            audience = %self.audience,
+            token_url = %self.token_url,
+        );
+        let request = http::Request::post(&self.token_url)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(hyper::Body::from(body))
+            .context(BuildTokenRequestSnafu)?;
+        let (_, bytes) = send_token_request(request).await?;
+        parse_token(&bytes).map_err(Into::into)
+    }
+}
+
+/// Where the subject token of an external account is read from.
+#[derive(Debug, Deserialize)]
+struct CredentialSource {
+    file: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    format: CredentialSourceFormat,
+}
+
+#[derive(Debug, Deserialize, Derivative)]
+#[derivative(Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CredentialSourceFormat {
+    #[derivative(Default)]
+    Text,
+    Json {
+        subject_token_field_name: String,
+    },
+}
+
+impl CredentialSource {
+    async fn subject_token(&self) -> Result<String, GcpError> {
+        let data = match (&self.file, &self.url) {
+            (Some(file), _) => {
+                Bytes::from(tokio::fs::read(file).await.context(ReadSubjectTokenSnafu)?)
+            }
+            (None, Some(url)) => {
+                let mut request = http::Request::get(url);
+                for (name, value) in &self.headers {
+                    request = request.header(name, value);
+                }
+                let request = request
+                    .body(hyper::Body::empty())
+                    .context(BuildTokenRequestSnafu)?;
+                send_token_request(request).await?.1
+            }
+            (None, None) => return Err(GcpError::UnsupportedCredentialSource),
+        };
+        self.format.subject_token(&data)
+    }
+}
+
+impl CredentialSourceFormat {
+    fn subject_token(&self, data: &[u8]) -> Result<String, GcpError> {
+        match self {
+            Self::Text => Ok(String::from_utf8_lossy(data).trim().to_owned()),
+            Self::Json {
+                subject_token_field_name,
+            } => serde_json::from_slice::<serde_json::Value>(data)
+                .context(TokenJsonFromStrSnafu)?
+                .get(subject_token_field_name)
+                .and_then(serde_json::Value::as_str)
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| GcpError::MissingSubjectToken {
+                    field: subject_token_field_name.clone(),
+                }),
+        }
+    }
+}
+
+/// The impersonation of a service account, whose tokens are requested with the ones of another
+/// principal.
+#[derive(Debug)]
+struct Impersonation {
+    url: String,
+    lifetime_secs: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateAccessTokenResponse {
+    access_token: String,
+    expire_time: DateTime<Utc>,
+}
+
+impl Impersonation {
+    async fn fetch_token(&self, token: &Token, scope: &Scope) -> crate::Result<Token> {
+        let body = serde_json::json!({
+            "scope": [scope.url()],
+            "lifetime": format!("{}s", self.lifetime_secs),
+        });
+
+        debug!(message = "Impersonating GCP service account.", url = %self.url);
+        let request = http::Request::post(&self.url)
+            .header(
+                AUTHORIZATION,
+                format!("{} {}", token.token_type(), token.access_token()),
+            )
+            .header(CONTENT_TYPE, "application/json")
+            .body(hyper::Body::from(body.to_string()))
+            .context(BuildTokenRequestSnafu)?;
+        let (status, bytes) = send_token_request(request).await?;
+        if !status.is_success() {
+            return Err(GcpError::ImpersonateServiceAccount {
+                status,
+                body: String::from_utf8_lossy(&bytes).into_owned(),
+            }
+            .into());
+        }
+
+        // The impersonated tokens are given the time they expire at rather than how long they're
+        // valid for.
+        let response: GenerateAccessTokenResponse =
+            serde_json::from_slice(&bytes).context(TokenJsonFromStrSnafu)?;
+        let expires_in = (response.expire_time - Utc::now()).num_seconds().max(0);
+        serde_json::from_value(serde_json::json!({
+            "access_token": response.access_token,
+            "token_type": "Bearer",
+            "expires_in": expires_in,
+        }))
+        .context(TokenJsonFromStrSnafu)
+        .map_err(Into::into)
+    }
+}
+
+async fn send_token_request(
+    request: http::Request<hyper::Body>,
+) -> Result<(http::StatusCode, Bytes), GcpError> {
+    let proxy = ProxyConfig::from_env();
+    let res = HttpClient::new(None, &proxy)
+        .context(BuildHttpClientSnafu)?
+        .send(request)
+        .await
+        .context(SendTokenRequestSnafu)?;
+
+    let status = res.status();
+    let bytes = hyper::body::to_bytes(res.into_body())
+        .await
+        .context(GetTokenBytesSnafu)?;
+    Ok((status, bytes))
+}
+
 async fn fetch_token(creds: &Credentials, scope: &Scope) -> crate::Result<Token> {
     let claims = JwtClaims::new(creds.iss(), scope, creds.token_uri(), None, None);
     let rsa_key = creds.rsa_key().context(InvalidRsaKeySnafu)?;
@@ -273,10 +635,14 @@ async fn get_token_implicit() -> Result<Token, GcpError> {
         .await
         .context(GetTokenBytesSnafu)?;
 
+    parse_token(&bytes)
+}
+
+fn parse_token(bytes: &[u8]) -> Result<Token, GcpError> {
     // Token::from_str is irresponsible and may panic!
-    match serde_json::from_slice::<Token>(&bytes) {
+    match serde_json::from_slice::<Token>(bytes) {
         Ok(token) => Ok(token),
-        Err(error) => Err(match serde_json::from_slice::<TokenErr>(&bytes) {
+        Err(error) => Err(match serde_json::from_slice::<TokenErr>(bytes) {
             Ok(error) => GcpError::TokenFromJson { source: error },
             Err(_) => GcpError::TokenJsonFromStr { source: error },
         }),
@@ -343,6 +709,80 @@ mod tests {
         assert_downcast_matches!(error, GcpError, GcpError::InvalidApiKey { .. });
     }
 
+    #[tokio::test]
+    async fn fails_impersonation_with_api_key() {
+        let error = build_auth(
+            r#"
+                api_key = "testing"
+                impersonate_service_account = "vector@my-project.iam.gserviceaccount.com"
+            "#,
+        )
+        .await
+        .expect_err("build failed to error");
+        assert_downcast_matches!(error, GcpError, GcpError::ImpersonationWithApiKey);
+    }
+
+    #[tokio::test]
+    async fn fails_bad_impersonation_lifetime() {
+        let error = build_auth(
+            r#"
+                impersonate_service_account = "vector@my-project.iam.gserviceaccount.com"
+                impersonation_lifetime_secs = 86400
+            "#,
+        )
+        .await
+        .expect_err("build failed to error");
+        assert_downcast_matches!(
+            error,
+            GcpError,
+            GcpError::InvalidImpersonationLifetime {
+                lifetime_secs: 86400
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_external_account_subject_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let token_path = dir.path().join("token.json");
+        std::fs::write(&token_path, r#"{"id_token": "subject-token"}"#).unwrap();
+
+        let account: ExternalAccount = serde_json::from_value(serde_json::json!({
+            "type": "external_account",
+            "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/provider",
+            "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+            "token_url": "https://sts.googleapis.com/v1/token",
+            "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/vector@my-project.iam.gserviceaccount.com:generateAccessToken",
+            "service_account_impersonation": { "token_lifetime_seconds": 600 },
+            "credential_source": {
+                "file": token_path,
+                "format": { "type": "json", "subject_token_field_name": "id_token" },
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            account.credential_source.subject_token().await.unwrap(),
+            "subject-token"
+        );
+        let impersonation = account.impersonation().unwrap();
+        assert!(impersonation.url.ends_with(":generateAccessToken"));
+        assert_eq!(impersonation.lifetime_secs, 600);
+
+        let format = CredentialSourceFormat::default();
+        assert_eq!(
+            format.subject_token(b"subject-token\n").unwrap(),
+            "subject-token"
+        );
+        let format = CredentialSourceFormat::Json {
+            subject_token_field_name: "access_token".into(),
+        };
+        assert!(matches!(
+            format.subject_token(b"{}"),
+            Err(GcpError::MissingSubjectToken { .. })
+        ));
+    }
+
     fn apply_uri(auth: &GcpAuthenticator, uri: &str) -> String {
         let mut uri: Uri = uri.parse().unwrap();
         auth.apply_uri(&mut uri);
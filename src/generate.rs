@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 use std::{
     fs::{create_dir_all, File},
-    io::Write,
+    io::{self, BufRead, Write},
     path::{Path, PathBuf},
 };
 
@@ -11,7 +11,7 @@ use indexmap::IndexMap;
 use serde::Serialize;
 use toml::{map::Map, Value};
 use vector_config::component::{
-    ExampleError, SinkDescription, SourceDescription, TransformDescription,
+    ComponentOption, ExampleError, SinkDescription, SourceDescription, TransformDescription,
 };
 use vector_core::{buffers::BufferConfig, config::GlobalOptions, default_data_dir};
 
@@ -53,13 +53,59 @@ pub struct Opts {
     /// from the last transform or, if none are specified, from all sources. It
     /// is then up to you to restructure the `inputs` of each component to build
     /// the topology you need.
-    expression: String,
+    #[arg(required_unless_present = "interactive")]
+    expression: Option<String>,
+
+    /// Prompt for the components to generate, and for the format of the config.
+    ///
+    /// The generated config is commented with the options of each component.
+    #[arg(short, long, conflicts_with = "expression")]
+    interactive: bool,
+
+    /// Comment the generated config with the options of each component.
+    ///
+    /// Each option is described along with its default value, and whether it's required. JSON
+    /// configs can't hold comments, so they're never commented.
+    #[arg(long)]
+    commented: bool,
+
+    /// The format of the generated config.
+    ///
+    /// Defaults to the format of the extension of `--file`, or else to TOML.
+    #[arg(long, value_enum)]
+    format: Option<Format>,
 
     /// Generate config as a file
     #[arg(long)]
     file: Option<PathBuf>,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum Format {
+    #[default]
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Some(Self::Toml),
+            Some("yaml") | Some("yml") => Some(Self::Yaml),
+            Some("json") => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// How the generated config is rendered.
+#[derive(Clone, Copy, Debug, Default)]
+struct Rendering {
+    format: Format,
+    commented: bool,
+}
+
 #[derive(Serialize)]
 pub struct SinkOuter {
     pub inputs: Vec<String>,
@@ -78,8 +124,11 @@ pub struct TransformOuter {
 
 #[derive(Serialize, Default)]
 pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sources: Option<IndexMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transforms: Option<IndexMap<String, TransformOuter>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sinks: Option<IndexMap<String, SinkOuter>>,
 }
 
@@ -104,6 +153,22 @@ pub(crate) fn generate_example(
     expression: &str,
     file: &Option<PathBuf>,
     transform_inputs_strategy: TransformInputsStrategy,
+) -> Result<String, Vec<String>> {
+    generate_config(
+        include_globals,
+        expression,
+        file,
+        transform_inputs_strategy,
+        Rendering::default(),
+    )
+}
+
+fn generate_config(
+    include_globals: bool,
+    expression: &str,
+    file: &Option<PathBuf>,
+    transform_inputs_strategy: TransformInputsStrategy,
+    rendering: Rendering,
 ) -> Result<String, Vec<String>> {
     let components: Vec<Vec<_>> = expression
         .split(|c| c == '|' || c == '/')
@@ -305,8 +370,116 @@ pub(crate) fn generate_example(
         return Err(errs);
     }
 
+    let builder = match rendering.format {
+        Format::Toml => render_toml(&globals, include_globals, config, rendering.commented),
+        Format::Yaml => render_yaml(&globals, include_globals, config, rendering.commented),
+        Format::Json => render_json(&globals, include_globals, config),
+    }?;
+
+    if file.is_some() {
+        #[allow(clippy::print_stdout)]
+        match write_config(file.as_ref().unwrap(), &builder) {
+            Ok(_) => {
+                println!(
+                    "Config file written to {:?}",
+                    &file.as_ref().unwrap().join("\n")
+                )
+            }
+            Err(e) => errs.push(format!("failed to write to file: {}", e)),
+        };
+    };
+
+    if !errs.is_empty() {
+        Err(errs)
+    } else {
+        Ok(builder)
+    }
+}
+
+/// The kind of a component, to look up the options of its type.
+#[derive(Clone, Copy)]
+enum Kind {
+    Source,
+    Transform,
+    Sink,
+}
+
+impl Kind {
+    fn options(self, component_type: &str) -> Result<Vec<ComponentOption>, ExampleError> {
+        match self {
+            Self::Source => SourceDescription::options(component_type),
+            Self::Transform => TransformDescription::options(component_type),
+            Self::Sink => SinkDescription::options(component_type),
+        }
+    }
+}
+
+/// The components of each section of the config, each with its kind and its serialized value.
+fn sections(config: Config) -> Vec<(&'static str, Vec<(String, Kind, Value)>)> {
+    fn section<T: Serialize>(
+        components: Option<IndexMap<String, T>>,
+        kind: Kind,
+    ) -> Vec<(String, Kind, Value)> {
+        components
+            .into_iter()
+            .flatten()
+            .map(|(name, component)| {
+                let value = Value::try_from(component).expect("components are always tables");
+                (name, kind, value)
+            })
+            .collect()
+    }
+
+    vec![
+        ("sources", section(config.sources, Kind::Source)),
+        ("transforms", section(config.transforms, Kind::Transform)),
+        ("sinks", section(config.sinks, Kind::Sink)),
+    ]
+}
+
+/// Describes the options of a component as comments, each line starting with `indent`.
+fn describe_options(kind: Kind, component: &Value, indent: &str) -> Result<String, String> {
+    let component_type = component
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let options = kind
+        .options(component_type)
+        .map_err(|error| format!("failed to describe '{}': {}", component_type, error))?;
+
+    let mut lines = vec![format!("Options of `{}`:", component_type), String::new()];
+    for option in options {
+        if option.required {
+            lines.push(format!("{} (required)", option.name));
+        } else {
+            lines.push(option.name);
+        }
+        // Only the first paragraph of the description, which summarizes the option.
+        if let Some(description) = option.description {
+            let summary = description.split("\n\n").next().unwrap_or_default();
+            lines.extend(summary.lines().map(|line| format!("  {}", line.trim())));
+        }
+        if let Some(default) = option.default.filter(|default| !default.is_null()) {
+            lines.push(format!("  Default: {}", default));
+        }
+    }
+
+    Ok(lines
+        .into_iter()
+        .map(|line| format!("{}# {}", indent, line).trim_end().to_owned() + "\n")
+        .collect())
+}
+
+fn render_toml(
+    globals: &GlobalOptions,
+    include_globals: bool,
+    config: Config,
+    commented: bool,
+) -> Result<String, Vec<String>> {
+    let mut errs = Vec::new();
+
     let mut builder = if include_globals {
-        match toml::to_string(&globals) {
+        match toml::to_string(globals) {
             Ok(s) => s,
             Err(err) => {
                 errs.push(format!("failed to marshal globals: {}", err));
@@ -316,53 +489,113 @@ pub(crate) fn generate_example(
     } else {
         String::new()
     };
-    if let Some(sources) = config.sources {
-        match toml::to_string(&{
-            Config {
-                sources: Some(sources),
-                ..Default::default()
+
+    if !commented {
+        if let Some(sources) = config.sources {
+            match toml::to_string(&{
+                Config {
+                    sources: Some(sources),
+                    ..Default::default()
+                }
+            }) {
+                Ok(v) => builder = [builder, v].join("\n"),
+                Err(e) => errs.push(format!("failed to marshal sources: {}", e)),
             }
-        }) {
-            Ok(v) => builder = [builder, v].join("\n"),
-            Err(e) => errs.push(format!("failed to marshal sources: {}", e)),
         }
-    }
-    if let Some(transforms) = config.transforms {
-        match toml::to_string(&{
-            Config {
-                transforms: Some(transforms),
-                ..Default::default()
+        if let Some(transforms) = config.transforms {
+            match toml::to_string(&{
+                Config {
+                    transforms: Some(transforms),
+                    ..Default::default()
+                }
+            }) {
+                Ok(v) => builder = [builder, v].join("\n"),
+                Err(e) => errs.push(format!("failed to marshal transforms: {}", e)),
             }
-        }) {
-            Ok(v) => builder = [builder, v].join("\n"),
-            Err(e) => errs.push(format!("failed to marshal transforms: {}", e)),
         }
-    }
-    if let Some(sinks) = config.sinks {
-        match toml::to_string(&{
-            Config {
-                sinks: Some(sinks),
-                ..Default::default()
+        if let Some(sinks) = config.sinks {
+            match toml::to_string(&{
+                Config {
+                    sinks: Some(sinks),
+                    ..Default::default()
+                }
+            }) {
+                Ok(v) => builder = [builder, v].join("\n"),
+                Err(e) => errs.push(format!("failed to marshal sinks: {}", e)),
+            }
+        }
+    } else {
+        // Each component is marshalled on its own, to describe its options right above it.
+        for (section, components) in sections(config) {
+            for (name, kind, component) in components {
+                let comments = match describe_options(kind, &component, "") {
+                    Ok(comments) => comments,
+                    Err(e) => {
+                        errs.push(e);
+                        continue;
+                    }
+                };
+                let mut table = Map::new();
+                table.insert(
+                    section.to_owned(),
+                    Value::Table(Map::from_iter([(name, component)])),
+                );
+                match toml::to_string(&table) {
+                    Ok(v) => builder = [builder, comments + &v].join("\n"),
+                    Err(e) => errs.push(format!("failed to marshal {}: {}", section, e)),
+                }
             }
-        }) {
-            Ok(v) => builder = [builder, v].join("\n"),
-            Err(e) => errs.push(format!("failed to marshal sinks: {}", e)),
         }
     }
 
-    if file.is_some() {
-        #[allow(clippy::print_stdout)]
-        match write_config(file.as_ref().unwrap(), &builder) {
-            Ok(_) => {
-                println!(
-                    "Config file written to {:?}",
-                    &file.as_ref().unwrap().join("\n")
-                )
+    if !errs.is_empty() {
+        Err(errs)
+    } else {
+        Ok(builder)
+    }
+}
+
+fn render_yaml(
+    globals: &GlobalOptions,
+    include_globals: bool,
+    config: Config,
+    commented: bool,
+) -> Result<String, Vec<String>> {
+    let mut errs = Vec::new();
+
+    let mut builder = if include_globals {
+        match serde_yaml::to_string(globals) {
+            Ok(s) => s,
+            Err(err) => {
+                errs.push(format!("failed to marshal globals: {}", err));
+                return Err(errs);
             }
-            Err(e) => errs.push(format!("failed to write to file: {}", e)),
-        };
+        }
+    } else {
+        String::new()
     };
 
+    // Each component is marshalled on its own, then nested under its section, to describe its
+    // options right above it.
+    for (section, components) in sections(config) {
+        if components.is_empty() {
+            continue;
+        }
+        builder = [builder, format!("{}:\n", section)].join("\n");
+        for (name, kind, component) in components {
+            if commented {
+                match describe_options(kind, &component, "  ") {
+                    Ok(comments) => builder += &comments,
+                    Err(e) => errs.push(e),
+                }
+            }
+            match serde_yaml::to_string(&IndexMap::from_iter([(name, component)])) {
+                Ok(v) => builder.extend(v.lines().map(|line| format!("  {}\n", line))),
+                Err(e) => errs.push(format!("failed to marshal {}: {}", section, e)),
+            }
+        }
+    }
+
     if !errs.is_empty() {
         Err(errs)
     } else {
@@ -370,12 +603,147 @@ pub(crate) fn generate_example(
     }
 }
 
+fn render_json(
+    globals: &GlobalOptions,
+    include_globals: bool,
+    config: Config,
+) -> Result<String, Vec<String>> {
+    let mut document = serde_json::Map::new();
+    if include_globals {
+        match serde_json::to_value(globals) {
+            Ok(serde_json::Value::Object(globals)) => document.extend(globals),
+            Ok(_) => {}
+            Err(err) => return Err(vec![format!("failed to marshal globals: {}", err)]),
+        }
+    }
+    match serde_json::to_value(&config) {
+        Ok(serde_json::Value::Object(config)) => document.extend(config),
+        Ok(_) => {}
+        Err(err) => return Err(vec![format!("failed to marshal components: {}", err)]),
+    }
+
+    serde_json::to_string_pretty(&document)
+        .map(|document| document + "\n")
+        .map_err(|err| vec![format!("failed to marshal config: {}", err)])
+}
+
+/// Prompts for the components to generate, returning the expression generating them.
+fn prompt_expression(input: &mut impl BufRead, output: &mut impl Write) -> io::Result<String> {
+    let sources = prompt_components(input, output, "sources", &SourceDescription::types())?;
+    let transforms =
+        prompt_components(input, output, "transforms", &TransformDescription::types())?;
+    let sinks = prompt_components(input, output, "sinks", &SinkDescription::types())?;
+    Ok(format!(
+        "{}/{}/{}",
+        sources.join(","),
+        transforms.join(","),
+        sinks.join(",")
+    ))
+}
+
+fn prompt_components(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    kind: &str,
+    types: &[&str],
+) -> io::Result<Vec<String>> {
+    writeln!(output, "Available {}: {}", kind, types.join(", "))?;
+    loop {
+        write!(
+            output,
+            "Which {} to add, separated by commas and optionally named as `<name>:<type>`? ",
+            kind
+        )?;
+        output.flush()?;
+
+        let components = read_line(input)?
+            .split(',')
+            .map(|component| component.trim().to_owned())
+            .filter(|component| !component.is_empty())
+            .collect::<Vec<_>>();
+        let unknown = components
+            .iter()
+            .map(|component| {
+                component
+                    .split_once(':')
+                    .map_or(component.as_str(), |(_, component_type)| component_type)
+            })
+            .filter(|component_type| !types.contains(component_type))
+            .collect::<Vec<_>>();
+        if unknown.is_empty() {
+            writeln!(output)?;
+            return Ok(components);
+        }
+        writeln!(output, "Unknown {}: {}", kind, unknown.join(", "))?;
+    }
+}
+
+fn prompt_format(input: &mut impl BufRead, output: &mut impl Write) -> io::Result<Format> {
+    loop {
+        write!(
+            output,
+            "Which format to generate the config in, toml, yaml, or json? [toml] "
+        )?;
+        output.flush()?;
+
+        match read_line(input)?.trim() {
+            "" | "toml" => return Ok(Format::Toml),
+            "yaml" | "yml" => return Ok(Format::Yaml),
+            "json" => return Ok(Format::Json),
+            format => writeln!(output, "Unknown format: {}", format)?,
+        }
+    }
+}
+
+fn read_line(input: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no answer was given",
+        ));
+    }
+    Ok(line)
+}
+
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
-    match generate_example(
+    let (expression, format) = if opts.interactive {
+        let mut input = io::stdin().lock();
+        // The prompts are written to stderr, to write the config to stdout.
+        let mut output = io::stderr();
+        let prompted = prompt_expression(&mut input, &mut output).and_then(|expression| {
+            let format = match opts.format {
+                Some(format) => format,
+                None => prompt_format(&mut input, &mut output)?,
+            };
+            Ok((expression, Some(format)))
+        });
+        match prompted {
+            Ok(prompted) => prompted,
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("{}", format!("failed to prompt: {}", error).red());
+                }
+                return exitcode::IOERR;
+            }
+        }
+    } else {
+        (opts.expression.clone().unwrap_or_default(), opts.format)
+    };
+    let rendering = Rendering {
+        format: format
+            .or_else(|| opts.file.as_deref().and_then(Format::from_path))
+            .unwrap_or_default(),
+        commented: opts.interactive || opts.commented,
+    };
+
+    match generate_config(
         !opts.fragment,
-        &opts.expression,
+        &expression,
         &opts.file,
         TransformInputsStrategy::Auto,
+        rendering,
     ) {
         Ok(s) => {
             #[allow(clippy::print_stdout)]
@@ -670,4 +1038,72 @@ mod tests {
             .to_string())
         );
     }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-console"))]
+    #[test]
+    fn generate_commented() {
+        let rendering = Rendering {
+            format: Format::Toml,
+            commented: true,
+        };
+        let cfg = generate_config(
+            true,
+            "stdin//console",
+            &None,
+            TransformInputsStrategy::Auto,
+            rendering,
+        )
+        .unwrap();
+        assert!(cfg.contains("# Options of `stdin`:\n"));
+        assert!(cfg.contains("\n# max_length\n"));
+        assert!(cfg.contains("# Options of `console`:\n"));
+        assert!(cfg.contains("\n# encoding (required)\n"));
+        toml::from_str::<crate::config::ConfigBuilder>(&cfg).unwrap();
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-console"))]
+    #[test]
+    fn generate_yaml_and_json() {
+        for (format, config_format) in [
+            (Format::Yaml, crate::config::Format::Yaml),
+            (Format::Json, crate::config::Format::Json),
+        ] {
+            let rendering = Rendering {
+                format,
+                commented: true,
+            };
+            let cfg = generate_config(
+                true,
+                "stdin/test_basic/console",
+                &None,
+                TransformInputsStrategy::Auto,
+                rendering,
+            )
+            .unwrap();
+            let config: crate::config::ConfigBuilder =
+                crate::config::format::deserialize(&cfg, config_format).unwrap();
+            assert_eq!(config.sources.len(), 1);
+            assert_eq!(config.transforms.len(), 1);
+            assert_eq!(config.sinks.len(), 1);
+        }
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-console"))]
+    #[test]
+    fn prompts_for_the_components() {
+        let mut input = io::Cursor::new("in:stdin\n\nunknown, console\nconsole\nxml\nyaml\n");
+        let mut output = Vec::new();
+
+        let expression = prompt_expression(&mut input, &mut output).unwrap();
+        assert_eq!(expression, "in:stdin//console");
+        assert_eq!(
+            prompt_format(&mut input, &mut output).unwrap(),
+            Format::Yaml
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Unknown sinks: unknown\n"));
+        assert!(output.contains("Unknown format: xml\n"));
+        assert!(prompt_format(&mut input, &mut Vec::new()).is_err());
+    }
 }
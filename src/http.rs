@@ -1,10 +1,13 @@
 #![allow(missing_docs)]
 use std::{
     fmt,
+    sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
-use futures::future::BoxFuture;
+use bytes::Bytes;
+use futures::future::{BoxFuture, FutureExt};
 use headers::{Authorization, HeaderMapExt};
 use http::{header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Uri};
 use hyper::{
@@ -15,6 +18,7 @@ use hyper::{
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use snafu::{ResultExt, Snafu};
+use tokio::net::TcpStream;
 use tower::Service;
 use tracing::Instrument;
 use vector_common::sensitive_string::SensitiveString;
@@ -26,6 +30,13 @@ use crate::{
     tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
 };
 
+mod oauth2;
+pub mod request_log;
+mod socks;
+
+use self::request_log::{PendingRequest, RequestLog};
+pub use self::{oauth2::OAuth2Config, request_log::RequestLogConfig, socks::SocksConnector};
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)))]
 pub enum HttpError {
@@ -35,16 +46,23 @@ pub enum HttpError {
     MakeHttpsConnector { source: openssl::error::ErrorStack },
     #[snafu(display("Failed to build Proxy connector: {}", source))]
     MakeProxyConnector { source: InvalidUri },
+    #[snafu(display("Failed to build SOCKS proxy connector: {}", source))]
+    MakeSocksConnector { source: url::ParseError },
     #[snafu(display("Failed to make HTTP(S) request: {}", source))]
     CallRequest { source: hyper::Error },
     #[snafu(display("Failed to build HTTP request: {}", source))]
     BuildRequest { source: http::Error },
+    #[snafu(display("Failed to read HTTP request body: {}", source))]
+    ReadRequestBody { source: crate::Error },
 }
 
 impl HttpError {
     pub const fn is_retriable(&self) -> bool {
         match self {
-            HttpError::BuildRequest { .. } | HttpError::MakeProxyConnector { .. } => false,
+            HttpError::BuildRequest { .. }
+            | HttpError::ReadRequestBody { .. }
+            | HttpError::MakeProxyConnector { .. }
+            | HttpError::MakeSocksConnector { .. } => false,
             HttpError::CallRequest { .. }
             | HttpError::BuildTlsConnector { .. }
             | HttpError::MakeHttpsConnector { .. } => true,
@@ -55,15 +73,16 @@ impl HttpError {
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
 
 pub struct HttpClient<B = Body> {
-    client: Client<ProxyConnector<HttpsConnector<HttpConnector>>, B>,
+    client: Client<ProxyConnector<HttpsConnector<SocksConnector>>, B>,
     user_agent: HeaderValue,
+    request_log: Option<Arc<RequestLog>>,
 }
 
 impl<B> HttpClient<B>
 where
-    B: fmt::Debug + HttpBody + Send + 'static,
+    B: fmt::Debug + HttpBody + From<Bytes> + Send + 'static,
     B::Data: Send,
-    B::Error: Into<crate::Error>,
+    B::Error: Into<crate::Error> + Send,
 {
     pub fn new(
         tls_settings: impl Into<MaybeTlsSettings>,
@@ -72,6 +91,10 @@ where
         HttpClient::new_with_custom_client(tls_settings, proxy_config, &mut Client::builder())
     }
 
+    /// Creates a client with a custom `client_builder`.
+    ///
+    /// If the client is built within a sink that has a request log, the requests that fail are
+    /// recorded in it.
     pub fn new_with_custom_client(
         tls_settings: impl Into<MaybeTlsSettings>,
         proxy_config: &ProxyConfig,
@@ -84,7 +107,11 @@ where
         let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
             .expect("Invalid header value for version!");
 
-        Ok(HttpClient { client, user_agent })
+        Ok(HttpClient {
+            client,
+            user_agent,
+            request_log: request_log::current(),
+        })
     }
 
     pub fn send(
@@ -98,54 +125,106 @@ where
 
         emit!(http_client::AboutToSendHttpRequest { request: &request });
 
-        let response = self.client.request(request);
-
-        let fut = async move {
-            // Capture the time right before we issue the request.
-            // Request doesn't start the processing until we start polling it.
-            let before = std::time::Instant::now();
-
-            // Send request and wait for the result.
-            let response_result = response.await;
-
-            // Compute the roundtrip time it took to send the request and get
-            // the response or error.
-            let roundtrip = before.elapsed();
-
-            // Handle the errors and extract the response.
-            let response = response_result
-                .map_err(|error| {
-                    // Emit the error into the internal events system.
-                    emit!(http_client::GotHttpWarning {
-                        error: &error,
-                        roundtrip
-                    });
-                    error
-                })
-                .context(CallRequestSnafu)?;
-
-            // Emit the response into the internal events system.
-            emit!(http_client::GotHttpResponse {
-                response: &response,
+        let fut = match self.request_log.clone() {
+            None => {
+                let response = self.client.request(request);
+                async move { measure_roundtrip(response).await.0 }.boxed()
+            }
+            // The bodies are buffered so that they can be recorded if the request fails.
+            Some(request_log) => {
+                let client = self.client.clone();
+                async move {
+                    let (parts, body) = request.into_parts();
+                    let body = hyper::body::to_bytes(body).await.map_err(|error| {
+                        HttpError::ReadRequestBody {
+                            source: error.into(),
+                        }
+                    })?;
+                    let pending = PendingRequest::new(&parts, body.clone());
+
+                    let response = client.request(Request::from_parts(parts, B::from(body)));
+                    let (result, roundtrip) = measure_roundtrip(response).await;
+                    match result {
+                        Ok(response)
+                            if response.status().is_client_error()
+                                || response.status().is_server_error() =>
+                        {
+                            let (parts, body) = response.into_parts();
+                            match hyper::body::to_bytes(body).await {
+                                Ok(body) => {
+                                    request_log.record_response(pending, &parts, &body, roundtrip);
+                                    Ok(http::Response::from_parts(parts, Body::from(body)))
+                                }
+                                Err(error) => {
+                                    request_log.record_error(pending, &error, roundtrip);
+                                    Err(HttpError::CallRequest { source: error })
+                                }
+                            }
+                        }
+                        Ok(response) => Ok(response),
+                        Err(error) => {
+                            request_log.record_error(pending, &error, roundtrip);
+                            Err(error)
+                        }
+                    }
+                }
+                .boxed()
+            }
+        };
+
+        Box::pin(fut.instrument(span.clone().or_current()))
+    }
+}
+
+/// Waits for the `response` to a request, and returns it along with the roundtrip time.
+async fn measure_roundtrip(
+    response: client::ResponseFuture,
+) -> (Result<http::Response<Body>, HttpError>, Duration) {
+    // Capture the time right before we issue the request.
+    // Request doesn't start the processing until we start polling it.
+    let before = std::time::Instant::now();
+
+    // Send request and wait for the result.
+    let response_result = response.await;
+
+    // Compute the roundtrip time it took to send the request and get
+    // the response or error.
+    let roundtrip = before.elapsed();
+
+    // Handle the errors and extract the response.
+    let response = response_result
+        .map_err(|error| {
+            // Emit the error into the internal events system.
+            emit!(http_client::GotHttpWarning {
+                error: &error,
                 roundtrip
             });
-            Ok(response)
-        }
-        .instrument(span.clone().or_current());
-
-        Box::pin(fut)
+            error
+        })
+        .context(CallRequestSnafu);
+
+    // Emit the response into the internal events system.
+    if let Ok(response) = &response {
+        emit!(http_client::GotHttpResponse {
+            response,
+            roundtrip
+        });
     }
+    (response, roundtrip)
 }
 
 pub fn build_proxy_connector(
     tls_settings: MaybeTlsSettings,
     proxy_config: &ProxyConfig,
-) -> Result<ProxyConnector<HttpsConnector<HttpConnector>>, HttpError> {
+) -> Result<ProxyConnector<HttpsConnector<SocksConnector>>, HttpError> {
     // Create dedicated TLS connector for the proxied connection with user TLS settings.
     let tls = tls_connector_builder(&tls_settings)
         .context(BuildTlsConnectorSnafu)?
         .build();
-    let https = build_tls_connector(tls_settings)?;
+    // SOCKS proxies tunnel the connections below TLS, while HTTP(S) proxies are installed on top.
+    let socks =
+        SocksConnector::new(http_connector(), proxy_config).context(MakeSocksConnectorSnafu)?;
+    let https = build_tls_connector_with(socks, tls_settings)?;
     let mut proxy = ProxyConnector::new(https).unwrap();
     // Make proxy connector aware of user TLS settings by setting the TLS connector:
     // https://github.com/vectordotdev/vector/issues/13683
@@ -159,9 +238,24 @@ pub fn build_proxy_connector(
 pub fn build_tls_connector(
     tls_settings: MaybeTlsSettings,
 ) -> Result<HttpsConnector<HttpConnector>, HttpError> {
+    build_tls_connector_with(http_connector(), tls_settings)
+}
+
+fn http_connector() -> HttpConnector {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
+    http
+}
 
+fn build_tls_connector_with<C>(
+    http: C,
+    tls_settings: MaybeTlsSettings,
+) -> Result<HttpsConnector<C>, HttpError>
+where
+    C: Service<Uri, Response = TcpStream> + Send,
+    C::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    C::Future: Unpin + Send + 'static,
+{
     let tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
     let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnectorSnafu)?;
 
@@ -194,7 +288,7 @@ fn default_request_headers<B>(request: &mut Request<B>, user_agent: &HeaderValue
 
 impl<B> Service<Request<B>> for HttpClient<B>
 where
-    B: fmt::Debug + HttpBody + Send + 'static,
+    B: fmt::Debug + HttpBody + From<Bytes> + Send + 'static,
     B::Data: Send,
     B::Error: Into<crate::Error> + Send,
 {
@@ -216,6 +310,7 @@ impl<B> Clone for HttpClient<B> {
         Self {
             client: self.client.clone(),
             user_agent: self.user_agent.clone(),
+            request_log: self.request_log.clone(),
         }
     }
 }
@@ -225,6 +320,7 @@ impl<B> fmt::Debug for HttpClient<B> {
         f.debug_struct("HttpClient")
             .field("client", &self.client)
             .field("user_agent", &self.user_agent)
+            .field("request_log", &self.request_log.is_some())
             .finish()
     }
 }
@@ -262,6 +358,16 @@ pub enum Auth {
         /// The bearer authentication token.
         token: SensitiveString,
     },
+
+    /// OAuth 2.0 authentication, with the client credentials grant.
+    ///
+    /// An access token is requested from the authorization server with the client credentials, and
+    /// refreshed ahead of its expiry. It's passed as a bearer token.
+    ///
+    /// This is supported by the `http` and `prometheus_remote_write` sinks, and the `http_client`
+    /// and `prometheus_scrape` sources.
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2Config),
 }
 
 pub trait MaybeAuth: Sized {
@@ -279,6 +385,17 @@ impl MaybeAuth for Option<Auth> {
 }
 
 impl Auth {
+    /// Fetches the first access token of the `oauth2` strategy, and keeps it refreshed.
+    ///
+    /// This must be awaited before the requests are authenticated with the `oauth2` strategy, and
+    /// does nothing for the other strategies.
+    pub async fn start(&self, client: &HttpClient) -> crate::Result<()> {
+        match self {
+            Auth::OAuth2(oauth2) => oauth2.start(client).await,
+            Auth::Basic { .. } | Auth::Bearer { .. } => Ok(()),
+        }
+    }
+
     pub fn apply<B>(&self, req: &mut Request<B>) {
         self.apply_headers_map(req.headers_mut())
     }
@@ -300,6 +417,7 @@ impl Auth {
                 Ok(auth) => map.typed_insert(auth),
                 Err(error) => error!(message = "Invalid bearer token.", token = %token, %error),
             },
+            Auth::OAuth2(oauth2) => oauth2.apply_headers_map(map),
         }
     }
 }
@@ -351,4 +469,50 @@ mod tests {
             Some(&HeaderValue::from_static("foo"))
         );
     }
+
+    #[tokio::test]
+    async fn records_failed_requests() {
+        use hyper::{
+            service::{make_service_fn, service_fn},
+            Response, Server,
+        };
+
+        use crate::{config::ComponentKey, test_util::next_addr};
+
+        let addr = next_addr();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, hyper::Error>(service_fn(|_| async {
+                Response::builder()
+                    .status(400)
+                    .body(Body::from("invalid event"))
+            }))
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+
+        let config = serde_json::from_value::<RequestLogConfig>(serde_json::json!({})).unwrap();
+        let request_log =
+            RequestLog::new(ComponentKey::from("records_failed_requests"), &config).unwrap();
+        let client = request_log::with_request_log(Some(Arc::clone(&request_log)), async {
+            HttpClient::new(None, &ProxyConfig::default()).unwrap()
+        })
+        .await;
+
+        let request = Request::post(format!("http://{}/_bulk", addr))
+            .header("Authorization", "Bearer token")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = client.send(request).await.unwrap();
+        assert_eq!(response.status(), 400);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(body, "invalid event");
+
+        let failed = request_log.entries().remove(0);
+        assert_eq!(failed.uri, format!("http://{}/_bulk", addr));
+        assert_eq!(failed.request_body, "{}");
+        assert_eq!(failed.status, Some(400));
+        assert_eq!(failed.response_body.as_deref(), Some("invalid event"));
+        assert!(failed
+            .request_headers
+            .contains(&("authorization".to_owned(), "[REDACTED]".to_owned())));
+    }
 }
@@ -0,0 +1,353 @@
+//! The `oauth2` authentication strategy, using the client credentials grant.
+//!
+//! The access token is fetched when the component starts, then refreshed ahead of its expiry by a
+//! task living as long as the configuration it was fetched for.
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock, Weak,
+    },
+    time::Duration,
+};
+
+use headers::{Authorization, HeaderMapExt};
+use http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    uri::InvalidUri,
+    HeaderMap, Request, StatusCode, Uri,
+};
+use hyper::Body;
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{HttpClient, HttpError};
+
+/// The lifetime assumed for the access tokens whose expiry isn't given.
+const DEFAULT_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// How long to wait before trying again to refresh an access token.
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Snafu)]
+enum OAuth2Error {
+    #[snafu(display("Invalid token URL: {}", source))]
+    InvalidTokenUrl { source: InvalidUri },
+    #[snafu(display("Failed to request an access token: {}", source))]
+    RequestToken { source: HttpError },
+    #[snafu(display("Failed to read the access token response: {}", source))]
+    ReadToken { source: hyper::Error },
+    #[snafu(display("Token endpoint responded with {}: {}", status, body))]
+    TokenRejected { status: StatusCode, body: String },
+    #[snafu(display("Failed to parse the access token response: {}", source))]
+    ParseToken { source: serde_json::Error },
+    #[snafu(display("Unsupported access token type {:?}", token_type))]
+    UnsupportedTokenType { token_type: String },
+}
+
+/// OAuth 2.0 client credentials grant configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OAuth2Config {
+    /// The URL of the token endpoint of the authorization server.
+    #[configurable(metadata(docs::examples = "https://auth.example.com/oauth2/token"))]
+    pub token_url: String,
+
+    /// The client identifier.
+    #[configurable(metadata(docs::examples = "${CLIENT_ID}"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub client_id: String,
+
+    /// The client secret.
+    #[configurable(metadata(docs::examples = "${CLIENT_SECRET}"))]
+    pub client_secret: SensitiveString,
+
+    /// The scopes to request the access token for.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "logs.write"))]
+    pub scopes: Vec<String>,
+
+    /// The audience to request the access token for.
+    ///
+    /// This isn't part of the OAuth 2.0 specification, but some authorization servers require it.
+    #[configurable(metadata(docs::examples = "https://api.example.com"))]
+    pub audience: Option<String>,
+
+    #[serde(skip)]
+    token: SharedToken,
+}
+
+/// The access token, shared by the clones of the configuration it was fetched for.
+#[derive(Clone, Default)]
+struct SharedToken(Arc<TokenState>);
+
+#[derive(Default)]
+struct TokenState {
+    started: AtomicBool,
+    access_token: RwLock<Option<SensitiveString>>,
+}
+
+impl TokenState {
+    fn set(&self, access_token: String) {
+        *self.access_token.write().expect("poisoned lock") = Some(access_token.into());
+    }
+}
+
+impl fmt::Debug for SharedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedToken")
+            .field("started", &self.0.started.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+// The token is runtime state, which doesn't make configurations any different.
+impl PartialEq for SharedToken {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SharedToken {}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: String,
+    expires_in: Option<u64>,
+}
+
+impl OAuth2Config {
+    /// Fetches the first access token, then spawns a task refreshing it ahead of its expiry.
+    ///
+    /// Only the first call fetches the token, the clones of this configuration then share it.
+    pub async fn start(&self, client: &HttpClient) -> crate::Result<()> {
+        if self.token.0.started.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let lifetime = match self.fetch(client).await {
+            Ok(token) => {
+                let lifetime = token
+                    .expires_in
+                    .map_or(DEFAULT_LIFETIME, Duration::from_secs);
+                self.token.0.set(token.access_token);
+                lifetime
+            }
+            Err(error) => {
+                self.token.0.started.store(false, Ordering::SeqCst);
+                return Err(error.into());
+            }
+        };
+
+        // The task only holds a weak reference to the token, so that it stops along with the
+        // clones of this configuration.
+        let state = Arc::downgrade(&self.token.0);
+        let config = Self {
+            token: SharedToken::default(),
+            ..self.clone()
+        };
+        tokio::spawn(config.refresh(client.clone(), state, lifetime));
+        Ok(())
+    }
+
+    async fn refresh(self, client: HttpClient, state: Weak<TokenState>, lifetime: Duration) {
+        let mut delay = lifetime.mul_f64(0.75);
+        loop {
+            tokio::time::sleep(delay).await;
+            let state = match state.upgrade() {
+                Some(state) => state,
+                None => return,
+            };
+
+            debug!(message = "Refreshing OAuth2 access token.", token_url = %self.token_url);
+            delay = match self.fetch(&client).await {
+                Ok(token) => {
+                    let lifetime = token
+                        .expires_in
+                        .map_or(DEFAULT_LIFETIME, Duration::from_secs);
+                    state.set(token.access_token);
+                    lifetime.mul_f64(0.75)
+                }
+                Err(error) => {
+                    error!(message = "Failed to refresh OAuth2 access token.", %error);
+                    RETRY_DELAY
+                }
+            };
+        }
+    }
+
+    async fn fetch(&self, client: &HttpClient) -> Result<TokenResponse, OAuth2Error> {
+        let uri = self
+            .token_url
+            .parse::<Uri>()
+            .context(InvalidTokenUrlSnafu)?;
+
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        form.append_pair("grant_type", "client_credentials");
+        if !self.scopes.is_empty() {
+            form.append_pair("scope", &self.scopes.join(" "));
+        }
+        if let Some(audience) = &self.audience {
+            form.append_pair("audience", audience);
+        }
+
+        let mut request = Request::post(uri)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(ACCEPT, "application/json")
+            .body(Body::from(form.finish()))
+            .expect("the token request is valid");
+        // The client credentials are form-encoded before being passed with basic authentication,
+        // as per RFC 6749, section 2.3.1.
+        let client_id =
+            url::form_urlencoded::byte_serialize(self.client_id.as_bytes()).collect::<String>();
+        let client_secret =
+            url::form_urlencoded::byte_serialize(self.client_secret.inner().as_bytes())
+                .collect::<String>();
+        request
+            .headers_mut()
+            .typed_insert(Authorization::basic(&client_id, &client_secret));
+
+        let response = client.send(request).await.context(RequestTokenSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadTokenSnafu)?;
+        if !status.is_success() {
+            return Err(OAuth2Error::TokenRejected {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+
+        let token = serde_json::from_slice::<TokenResponse>(&body).context(ParseTokenSnafu)?;
+        if !token.token_type.eq_ignore_ascii_case("bearer") {
+            return Err(OAuth2Error::UnsupportedTokenType {
+                token_type: token.token_type,
+            });
+        }
+        Ok(token)
+    }
+
+    pub(super) fn apply_headers_map(&self, map: &mut HeaderMap) {
+        let access_token = self.token.0.access_token.read().expect("poisoned lock");
+        match access_token.as_ref().map(|token| Authorization::bearer(token.inner())) {
+            Some(Ok(auth)) => map.typed_insert(auth),
+            Some(Err(error)) => error!(message = "Invalid OAuth2 access token.", %error),
+            None => error!(
+                message = "No OAuth2 access token was fetched, the component doesn't support the `oauth2` strategy.",
+                internal_log_rate_limit = true,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Response, Server,
+    };
+
+    use super::*;
+    use crate::{config::ProxyConfig, test_util::next_addr};
+
+    #[tokio::test]
+    async fn fetches_and_refreshes_tokens() {
+        let addr = next_addr();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let server = Server::bind(&addr).serve(make_service_fn(move |_| {
+            let counter = Arc::clone(&counter);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let count = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    async move {
+                        assert_eq!(
+                            request
+                                .headers()
+                                .typed_get::<Authorization<headers::authorization::Basic>>(),
+                            Some(Authorization::basic("vector", "s3cr%2Ft")),
+                        );
+                        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                        assert_eq!(body, "grant_type=client_credentials&scope=a+b&audience=api");
+                        let token = format!(
+                            r#"{{"access_token":"token-{}","token_type":"Bearer","expires_in":1}}"#,
+                            count
+                        );
+                        Ok::<_, Infallible>(Response::new(Body::from(token)))
+                    }
+                }))
+            }
+        }));
+        tokio::spawn(server);
+
+        let config: OAuth2Config = toml::from_str(&format!(
+            r#"
+            token_url = "http://{}/token"
+            client_id = "vector"
+            client_secret = "s3cr/t"
+            scopes = ["a", "b"]
+            audience = "api"
+            "#,
+            addr
+        ))
+        .unwrap();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+
+        let clone = config.clone();
+        config.start(&client).await.unwrap();
+        clone.start(&client).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(bearer(&clone), "Bearer token-1");
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(requests.load(Ordering::SeqCst) >= 2);
+        assert_ne!(bearer(&config), "Bearer token-1");
+    }
+
+    #[tokio::test]
+    async fn fails_on_rejected_credentials() {
+        let addr = next_addr();
+        let server = Server::bind(&addr).serve(make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|_| async {
+                Ok::<_, Infallible>(
+                    Response::builder()
+                        .status(StatusCode::UNAUTHORIZED)
+                        .body(Body::from(r#"{"error":"invalid_client"}"#))
+                        .unwrap(),
+                )
+            }))
+        }));
+        tokio::spawn(server);
+
+        let config: OAuth2Config = toml::from_str(&format!(
+            r#"
+            token_url = "http://{}/token"
+            client_id = "vector"
+            client_secret = "wrong"
+            "#,
+            addr
+        ))
+        .unwrap();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+
+        let error = config.start(&client).await.unwrap_err();
+        assert!(error.to_string().contains("401"), "{}", error);
+        assert!(!config.token.0.started.load(Ordering::SeqCst));
+    }
+
+    fn bearer(config: &OAuth2Config) -> String {
+        let mut headers = HeaderMap::new();
+        config.apply_headers_map(&mut headers);
+        headers["authorization"].to_str().unwrap().to_owned()
+    }
+}
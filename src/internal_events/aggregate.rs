@@ -1,6 +1,9 @@
 use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::io_error_code;
+
 #[derive(Debug)]
 pub struct AggregateEventRecorded;
 
@@ -27,3 +30,29 @@ impl InternalEvent for AggregateUpdateFailed {
         counter!("aggregate_failed_updates", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct AggregateStateError<'a> {
+    pub path: &'a std::path::Path,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for AggregateStateError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to access the saved state of the aggregated metrics.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
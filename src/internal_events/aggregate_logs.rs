@@ -0,0 +1,20 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AggregateLogsEventRecorded;
+
+impl InternalEvent for AggregateLogsEventRecorded {
+    fn emit(self) {
+        counter!("aggregate_logs_events_recorded_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AggregateLogsFlushed;
+
+impl InternalEvent for AggregateLogsFlushed {
+    fn emit(self) {
+        counter!("aggregate_logs_flushes_total", 1);
+    }
+}
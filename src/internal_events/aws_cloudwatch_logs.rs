@@ -1,35 +1,104 @@
 use metrics::counter;
-use vector_common::internal_event::{
-    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
-};
+#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+pub use sink::*;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+pub use source::*;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::InternalEvent;
 
-use crate::emit;
+#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+mod sink {
+    use vector_common::internal_event::{ComponentEventsDropped, UNINTENTIONAL};
 
-#[derive(Debug)]
-pub struct AwsCloudwatchLogsMessageSizeError {
-    pub size: usize,
-    pub max_size: usize,
+    use super::*;
+    use crate::emit;
+
+    #[derive(Debug)]
+    pub struct AwsCloudwatchLogsMessageSizeError {
+        pub size: usize,
+        pub max_size: usize,
+    }
+
+    impl InternalEvent for AwsCloudwatchLogsMessageSizeError {
+        fn emit(self) {
+            let reason = "Encoded event is too long.";
+            error!(
+                message = reason,
+                size = self.size as u64,
+                max_size = self.max_size as u64,
+                error_code = "message_too_long",
+                error_type = error_type::ENCODER_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "message_too_long",
+                "error_type" => error_type::ENCODER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+        }
+    }
 }
 
-impl InternalEvent for AwsCloudwatchLogsMessageSizeError {
-    fn emit(self) {
-        let reason = "Encoded event is too long.";
-        error!(
-            message = reason,
-            size = self.size as u64,
-            max_size = self.max_size as u64,
-            error_code = "message_too_long",
-            error_type = error_type::ENCODER_FAILED,
-            stage = error_stage::PROCESSING,
-            internal_log_rate_limit = true,
-        );
-        counter!(
-            "component_errors_total", 1,
-            "error_code" => "message_too_long",
-            "error_type" => error_type::ENCODER_FAILED,
-            "stage" => error_stage::PROCESSING,
-        );
-        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+mod source {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[derive(Debug)]
+    pub struct AwsCloudwatchLogsRequestError<'a, E> {
+        pub error: &'a E,
+        pub log_group: &'a str,
+        pub retry_after: Duration,
+    }
+
+    impl<'a, E: std::fmt::Display> InternalEvent for AwsCloudwatchLogsRequestError<'a, E> {
+        fn emit(self) {
+            error!(
+                message = "Failed to fetch CloudWatch Logs events.",
+                error = %self.error,
+                log_group = %self.log_group,
+                retry_after_secs = self.retry_after.as_secs_f64(),
+                error_code = "failed_fetching_cloudwatch_logs_events",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_fetching_cloudwatch_logs_events",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct AwsCloudwatchLogsCheckpointError {
+        pub error: std::io::Error,
+        pub path: String,
+    }
+
+    impl InternalEvent for AwsCloudwatchLogsCheckpointError {
+        fn emit(self) {
+            error!(
+                message = "Could not write the CloudWatch Logs checkpoints.",
+                path = ?self.path,
+                error = %self.error,
+                error_code = "failed_writing_checkpoints",
+                error_type = error_type::IO_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_writing_checkpoints",
+                "error_type" => error_type::IO_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
     }
 }
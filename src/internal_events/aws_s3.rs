@@ -0,0 +1,142 @@
+use metrics::counter;
+#[cfg(any(feature = "sinks-aws_s3", feature = "sinks-datadog_archives"))]
+pub use sink::*;
+#[cfg(feature = "sources-aws_s3")]
+pub use source::*;
+use vector_core::internal_event::InternalEvent;
+
+#[cfg(any(feature = "sinks-aws_s3", feature = "sinks-datadog_archives"))]
+mod sink {
+    use super::*;
+
+    /// The partition an event that arrived after its time period was written to.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum LatePartition {
+        /// The partition of the time period the event occurred in.
+        Past,
+        /// The partition of the current time period, as the event was older than the lateness
+        /// window.
+        Current,
+    }
+
+    impl LatePartition {
+        const fn as_str(self) -> &'static str {
+            match self {
+                Self::Past => "past",
+                Self::Current => "current",
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3LateEvent {
+        pub partition: LatePartition,
+    }
+
+    impl InternalEvent for S3LateEvent {
+        fn emit(self) {
+            trace!(
+                message = "Partitioning late event.",
+                partition = %self.partition.as_str(),
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "late_events_total", 1,
+                "partition" => self.partition.as_str(),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "sources-aws_s3")]
+mod source {
+    use std::path::Path;
+
+    use aws_sdk_s3::error::ListObjectsV2Error;
+    use aws_smithy_client::SdkError;
+    use vector_common::internal_event::{error_stage, error_type};
+
+    use super::*;
+    use crate::sources::aws_s3::sqs::ProcessingError;
+
+    #[derive(Debug)]
+    pub struct S3ObjectListError<'a> {
+        pub bucket: &'a str,
+        pub prefix: &'a str,
+        pub error: SdkError<ListObjectsV2Error>,
+    }
+
+    impl<'a> InternalEvent for S3ObjectListError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed to list S3 objects.",
+                bucket = %self.bucket,
+                prefix = %self.prefix,
+                error = %self.error,
+                error_code = "failed_listing_s3_objects",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_listing_s3_objects",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3ObjectProcessingError<'a> {
+        pub bucket: &'a str,
+        pub key: &'a str,
+        pub error: &'a ProcessingError,
+    }
+
+    impl<'a> InternalEvent for S3ObjectProcessingError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed to process S3 object.",
+                bucket = %self.bucket,
+                key = %self.key,
+                error = %self.error,
+                error_code = "failed_processing_s3_object",
+                error_type = error_type::READER_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_processing_s3_object",
+                "error_type" => error_type::READER_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3ListStateWriteError<'a> {
+        pub error: std::io::Error,
+        pub path: &'a Path,
+    }
+
+    impl InternalEvent for S3ListStateWriteError<'_> {
+        fn emit(self) {
+            error!(
+                message = "Could not write the state of the listed S3 objects.",
+                error = %self.error,
+                path = ?self.path,
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "stage" => error_stage::RECEIVING,
+                "error_type" => error_type::WRITER_FAILED,
+            );
+            counter!("checkpoint_write_errors_total", 1);
+        }
+    }
+}
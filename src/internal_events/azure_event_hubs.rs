@@ -0,0 +1,90 @@
+#[cfg(feature = "sinks-azure_event_hubs")]
+pub use sink::*;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub use source::*;
+
+#[cfg(feature = "sources-azure_event_hubs")]
+mod source {
+    use metrics::counter;
+    use vector_common::internal_event::{error_stage, error_type};
+    use vector_core::internal_event::InternalEvent;
+
+    #[derive(Debug)]
+    pub struct AzureEventHubsReceiveError<'a> {
+        pub error: crate::Error,
+        pub partition_id: &'a str,
+    }
+
+    impl InternalEvent for AzureEventHubsReceiveError<'_> {
+        fn emit(self) {
+            error!(
+                message = "Failed to read partition.",
+                error = %self.error,
+                partition_id = %self.partition_id,
+                error_code = "failed_reading_partition",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_reading_partition",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct AzureEventHubsCheckpointStoreError {
+        pub error: azure_core::Error,
+    }
+
+    impl InternalEvent for AzureEventHubsCheckpointStoreError {
+        fn emit(self) {
+            error!(
+                message = "Failed to access the checkpoint store.",
+                error = %self.error,
+                error_code = "failed_accessing_checkpoint_store",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_accessing_checkpoint_store",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "sinks-azure_event_hubs")]
+mod sink {
+    use metrics::counter;
+    use vector_common::internal_event::{error_stage, error_type};
+    use vector_core::internal_event::InternalEvent;
+
+    #[derive(Debug)]
+    pub struct AzureEventHubsSendError<'a> {
+        pub error: &'a dyn std::error::Error,
+    }
+
+    impl InternalEvent for AzureEventHubsSendError<'_> {
+        fn emit(self) {
+            error!(
+                message = "Failed to send event.",
+                error = %self.error,
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::SENDING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::SENDING,
+            );
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use metrics::{gauge, histogram};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct BandwidthThrottled {
+    pub limit: &'static str,
+    pub delay: Duration,
+}
+
+impl InternalEvent for BandwidthThrottled {
+    fn emit(self) {
+        debug!(
+            message = "Bandwidth limit reached, delaying requests.",
+            limit = %self.limit,
+            delay_ms = %self.delay.as_millis(),
+            internal_log_rate_limit = true,
+        );
+        gauge!("bandwidth_throttled", 1.0, "limit" => self.limit);
+        histogram!(
+            "bandwidth_throttle_duration_seconds", self.delay,
+            "limit" => self.limit,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct BandwidthUnthrottled {
+    pub limit: &'static str,
+}
+
+impl InternalEvent for BandwidthUnthrottled {
+    fn emit(self) {
+        gauge!("bandwidth_throttled", 0.0, "limit" => self.limit);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::emit;
+use metrics::{counter, gauge};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct CardinalityLimitReached<'a> {
+    pub metric_name: &'a str,
+    pub series_limit: u32,
+}
+
+impl<'a> InternalEvent for CardinalityLimitReached<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Series limit reached for metric. New series of this metric will be limited.",
+            metric_name = %self.metric_name,
+            series_limit = %self.series_limit,
+            internal_log_rate_limit = true,
+        );
+        counter!("cardinality_limit_reached_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct CardinalityLimitSeriesDropped<'a> {
+    pub metric_name: &'a str,
+}
+
+impl<'a> InternalEvent for CardinalityLimitSeriesDropped<'a> {
+    fn emit(self) {
+        debug!(
+            message = "New series after hitting configured 'series_limit'; discarding event.",
+            metric_name = %self.metric_name,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "cardinality_limit_exceeded_total", 1,
+            "metric_name" => self.metric_name.to_owned(),
+            "action" => "drop_series",
+        );
+
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Series limit exceeded."
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CardinalityLimitSeriesAggregated<'a> {
+    pub metric_name: &'a str,
+}
+
+impl<'a> InternalEvent for CardinalityLimitSeriesAggregated<'a> {
+    fn emit(self) {
+        debug!(
+            message = "New series after hitting configured 'series_limit'; aggregating it into the overflow series.",
+            metric_name = %self.metric_name,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "cardinality_limit_exceeded_total", 1,
+            "metric_name" => self.metric_name.to_owned(),
+            "action" => "aggregate",
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct CardinalityLimitTagsStripped<'a> {
+    pub metric_name: &'a str,
+    pub tag_keys: &'a [String],
+}
+
+impl<'a> InternalEvent for CardinalityLimitTagsStripped<'a> {
+    fn emit(self) {
+        debug!(
+            message = "New series after hitting configured 'series_limit'; stripping tags.",
+            metric_name = %self.metric_name,
+            tag_keys = ?self.tag_keys,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "cardinality_limit_exceeded_total", 1,
+            "metric_name" => self.metric_name.to_owned(),
+            "action" => "strip_tags",
+        );
+        for tag_key in self.tag_keys {
+            counter!(
+                "cardinality_limit_stripped_tags_total", 1,
+                "metric_name" => self.metric_name.to_owned(),
+                "tag_key" => tag_key.clone(),
+            );
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CardinalityLimitTrackedSeries {
+    pub count: usize,
+}
+
+impl InternalEvent for CardinalityLimitTrackedSeries {
+    fn emit(self) {
+        gauge!("cardinality_limit_tracked_series", self.count as f64);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ClickhouseColumnConversionError<'a> {
+    pub column: &'a str,
+    pub error: String,
+}
+
+impl InternalEvent for ClickhouseColumnConversionError<'_> {
+    fn emit(self) {
+        let reason = "Failed to convert event field to column type.";
+        error!(
+            message = reason,
+            column = %self.column,
+            error = %self.error,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -1,5 +1,5 @@
 use crate::emit;
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::InternalEvent;
 
 use vector_common::internal_event::{
@@ -131,3 +131,17 @@ impl<E: std::fmt::Display> InternalEvent for EncoderWriteError<'_, E> {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct PassThroughActive {
+    pub active: bool,
+}
+
+impl InternalEvent for PassThroughActive {
+    fn emit(self) {
+        if self.active {
+            info!(message = "Relaying frames without decoding them, as all the consumers write them as they are.");
+        }
+        gauge!("pass_through_active", if self.active { 1.0 } else { 0.0 });
+    }
+}
@@ -0,0 +1,16 @@
+use crate::emit;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct DatadogAgentTracesSampledOut {
+    pub count: usize,
+}
+
+impl InternalEvent for DatadogAgentTracesSampledOut {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Trace sampled out."
+        })
+    }
+}
@@ -0,0 +1,20 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct DeadLetterEventsUntracked {
+    pub count: usize,
+    pub max_pending: usize,
+}
+
+impl InternalEvent for DeadLetterEventsUntracked {
+    fn emit(self) {
+        warn!(
+            message = "Too many events are pending; events going into the sink won't be rerouted to its dlq output if they fail.",
+            count = %self.count,
+            max_pending = %self.max_pending,
+            internal_log_rate_limit = true,
+        );
+        counter!("dlq_untracked_events_total", self.count as u64);
+    }
+}
@@ -1,7 +1,10 @@
 use crate::emit;
 use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
 
+use super::prelude::io_error_code;
+
 #[derive(Debug)]
 pub struct DedupeEventsDropped {
     pub count: usize,
@@ -16,3 +19,47 @@ impl InternalEvent for DedupeEventsDropped {
         counter!("events_discarded_total", self.count as u64); // Deprecated
     }
 }
+
+#[derive(Debug)]
+pub struct DedupeCacheHit;
+
+impl InternalEvent for DedupeCacheHit {
+    fn emit(self) {
+        counter!("dedupe_cache_hits_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct DedupeCacheMiss;
+
+impl InternalEvent for DedupeCacheMiss {
+    fn emit(self) {
+        counter!("dedupe_cache_misses_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct DedupeCacheStateError<'a> {
+    pub path: &'a std::path::Path,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for DedupeCacheStateError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to access the saved deduplication cache.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+/// The fields of an event which aren't part of the Elastic Common Schema.
+#[derive(Debug)]
+pub struct EcsUnmappedFields<'a> {
+    pub fields: &'a [String],
+    pub removed: bool,
+}
+
+impl<'a> InternalEvent for EcsUnmappedFields<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Event has fields not part of the Elastic Common Schema.",
+            fields = ?self.fields,
+            removed = %self.removed,
+            internal_log_rate_limit = true,
+        );
+        counter!("ecs_unmapped_fields_total", self.fields.len() as u64);
+    }
+}
@@ -0,0 +1,86 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+/// What became of the items of a bulk request which failed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BulkItemOutcome {
+    Retried,
+    DeadLettered,
+    Dropped,
+}
+
+impl BulkItemOutcome {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Retried => "retried",
+            Self::DeadLettered => "dead_lettered",
+            Self::Dropped => "dropped",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchBulkItemsFailed<'a> {
+    pub error_code: &'a str,
+    pub reason: &'a str,
+    pub status: u16,
+    pub outcome: BulkItemOutcome,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for ElasticsearchBulkItemsFailed<'a> {
+    fn emit(self) {
+        match self.outcome {
+            BulkItemOutcome::Retried => warn!(
+                message = "Bulk items failed; retrying.",
+                error_code = %self.error_code,
+                reason = %self.reason,
+                status = %self.status,
+                count = %self.count,
+                internal_log_rate_limit = true,
+            ),
+            _ => error!(
+                message = "Bulk items failed.",
+                error_code = %self.error_code,
+                reason = %self.reason,
+                status = %self.status,
+                count = %self.count,
+                outcome = %self.outcome.as_str(),
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::SENDING,
+                internal_log_rate_limit = true,
+            ),
+        }
+        counter!(
+            "elasticsearch_bulk_item_errors_total", self.count as u64,
+            "error_code" => self.error_code.to_owned(),
+            "status" => self.status.to_string(),
+            "outcome" => self.outcome.as_str(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchDeadLetterError {
+    pub error: String,
+    pub count: usize,
+}
+
+impl InternalEvent for ElasticsearchDeadLetterError {
+    fn emit(self) {
+        error!(
+            message = "Failed to write items to the dead letter index.",
+            error = %self.error,
+            count = %self.count,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
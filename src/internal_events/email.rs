@@ -0,0 +1,48 @@
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, INTENTIONAL, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct EmailSendError {
+    pub error: crate::Error,
+    pub count: usize,
+}
+
+impl InternalEvent for EmailSendError {
+    fn emit(self) {
+        error!(
+            message = "Failed to send email.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason: "Failed to send email.",
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct EmailEventsSuppressed {
+    pub count: usize,
+}
+
+impl InternalEvent for EmailEventsSuppressed {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Digest reached its maximum number of events.",
+        });
+    }
+}
@@ -4,7 +4,7 @@ use crate::emit;
 use metrics::{counter, histogram};
 use tokio::time::error::Elapsed;
 use vector_common::internal_event::{
-    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+    error_stage, error_type, ComponentEventsDropped, INTENTIONAL, UNINTENTIONAL,
 };
 use vector_core::internal_event::InternalEvent;
 
@@ -41,6 +41,25 @@ impl InternalEvent for ExecEventsReceived<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct ExecEventsSampledOut<'a> {
+    pub count: usize,
+    pub command: &'a str,
+}
+
+impl InternalEvent for ExecEventsSampledOut<'_> {
+    fn emit(self) {
+        counter!(
+            "exec_events_sampled_out_total", self.count as u64,
+            "command" => self.command.to_owned(),
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Sampled out."
+        });
+    }
+}
+
 #[derive(Debug)]
 pub struct ExecFailedError<'a> {
     pub command: &'a str,
@@ -149,6 +168,277 @@ impl InternalEvent for ExecCommandExecuted<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct ExecSourceExpired<'a> {
+    pub command: &'a str,
+    pub max_total_runtime: Duration,
+}
+
+impl InternalEvent for ExecSourceExpired<'_> {
+    fn emit(self) {
+        info!(
+            message = "Maximum total runtime reached, no further runs will be scheduled.",
+            command = %self.command,
+            max_total_runtime_secs = %self.max_total_runtime.as_secs(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecDecodeStalled<'a> {
+    pub command: &'a str,
+    pub stream: &'static str,
+    pub stalled_for: Duration,
+}
+
+impl InternalEvent for ExecDecodeStalled<'_> {
+    fn emit(self) {
+        error!(
+            message = "Reading command output stalled, restarting command.",
+            command = %self.command,
+            stream = %self.stream,
+            stalled_for_secs = %self.stalled_for.as_secs(),
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecHeartbeatMissed<'a> {
+    pub command: &'a str,
+    pub missed_for: Duration,
+}
+
+impl InternalEvent for ExecHeartbeatMissed<'_> {
+    fn emit(self) {
+        error!(
+            message = "Command stopped sending heartbeats, restarting command.",
+            command = %self.command,
+            missed_for_secs = %self.missed_for.as_secs(),
+            error_code = "heartbeat_missed",
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "exec_heartbeats_missed_total", 1,
+            "command" => self.command.to_owned(),
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_code" => "heartbeat_missed",
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandUnhealthy<'a> {
+    pub command: &'a str,
+    pub reason: &'a str,
+}
+
+impl InternalEvent for ExecCommandUnhealthy<'_> {
+    fn emit(self) {
+        error!(
+            message = "Command reported itself as unhealthy, restarting command.",
+            command = %self.command,
+            reason = %self.reason,
+            error_code = "command_unhealthy",
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "exec_unhealthy_reports_total", 1,
+            "command" => self.command.to_owned(),
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_code" => "command_unhealthy",
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecFramingDecoderMismatch<'a> {
+    pub command: &'a str,
+    pub framing: &'static str,
+    pub codec: &'static str,
+    pub decode_errors: usize,
+}
+
+impl InternalEvent for ExecFramingDecoderMismatch<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Command exited successfully but none of its output could be decoded. Check that the configured framing and decoding match the command's output.",
+            command = %self.command,
+            framing = %self.framing,
+            codec = %self.codec,
+            decode_errors = %self.decode_errors,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecOrphanReaped<'a> {
+    pub command: &'a str,
+    pub pid: u32,
+}
+
+impl InternalEvent for ExecOrphanReaped<'_> {
+    fn emit(self) {
+        info!(
+            message = "Terminated orphaned child left behind by a previous run.",
+            command = %self.command,
+            pid = %self.pid,
+        );
+        counter!(
+            "exec_orphans_reaped_total", 1,
+            "command" => self.command.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecChildStateError<'a> {
+    pub path: &'a std::path::Path,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecChildStateError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to access child process state.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandRestarted<'a> {
+    pub command: &'a str,
+    pub restarts: u64,
+    pub delay: Duration,
+}
+
+impl InternalEvent for ExecCommandRestarted<'_> {
+    fn emit(self) {
+        debug!(
+            message = "Restarting streaming process.",
+            command = %self.command,
+            consecutive_restarts = %self.restarts,
+            delay_millis = %self.delay.as_millis(),
+        );
+        counter!(
+            "command_restarts_total", 1,
+            "command" => self.command.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecRestartsExhausted<'a> {
+    pub command: &'a str,
+    pub restarts: u64,
+}
+
+impl InternalEvent for ExecRestartsExhausted<'_> {
+    fn emit(self) {
+        error!(
+            message = "Maximum number of consecutive restarts reached, not restarting command.",
+            command = %self.command,
+            consecutive_restarts = %self.restarts,
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecSpawnError<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecSpawnError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to start command.",
+            command = %self.command,
+            error = ?self.error,
+            error_type = error_type::COMMAND_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type::COMMAND_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecStdinWriteError<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecStdinWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to write to the standard input of command, restarting it.",
+            command = %self.command,
+            error = ?self.error,
+            error_type = error_type::IO_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type::IO_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
 pub enum ExecFailedToSignalChild {
     #[cfg(unix)]
     SignalError(nix::errno::Errno),
@@ -227,6 +517,84 @@ impl InternalEvent for ExecFailedToSignalChildError<'_> {
     }
 }
 
+pub struct ExecFailedToPauseChildError<'a> {
+    pub command: &'a tokio::process::Command,
+    pub signal: &'static str,
+    pub error: ExecFailedToSignalChild,
+}
+
+impl InternalEvent for ExecFailedToPauseChildError<'_> {
+    fn emit(self) {
+        error!(
+            message = %format!("Failed to send {} to child: {}", self.signal, self.error),
+            command = ?self.command.as_std(),
+            error_code = %self.error.to_error_code(),
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => format!("{:?}", self.command.as_std()),
+            "error_code" => self.error.to_error_code(),
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecDeliveryFailed<'a> {
+    pub command: &'a str,
+    pub count: usize,
+    pub attempts: u32,
+}
+
+impl InternalEvent for ExecDeliveryFailed<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Events were not delivered, resending.",
+            command = %self.command,
+            count = self.count,
+            attempts = self.attempts,
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecDeliveryRetriesExhausted<'a> {
+    pub command: &'a str,
+    pub count: usize,
+    pub action: &'static str,
+}
+
+impl InternalEvent for ExecDeliveryRetriesExhausted<'_> {
+    fn emit(self) {
+        error!(
+            message = "Events could not be delivered, giving up on the command.",
+            command = %self.command,
+            count = self.count,
+            action = %self.action,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        if self.action == "kill" {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: self.count,
+                reason: "Events could not be delivered, and the command was killed.",
+            });
+        }
+    }
+}
+
 pub struct ExecChannelClosedError;
 
 impl InternalEvent for ExecChannelClosedError {
@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+/// A field looked up by the `geoip` transform which doesn't hold an IP address.
+#[derive(Debug)]
+pub struct GeoipInvalidAddress<'a> {
+    pub field: &'a str,
+    pub address: &'a str,
+}
+
+impl<'a> InternalEvent for GeoipInvalidAddress<'a> {
+    fn emit(self) {
+        error!(
+            message = "Field isn't an IP address, not looking it up.",
+            field = %self.field,
+            address = %self.address,
+            error_code = "invalid_ip_address",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_ip_address",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::emit;
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct HttpSinkInvalidUri<'a> {
+    pub uri: &'a str,
+    pub error: http::uri::InvalidUri,
+}
+
+impl<'a> InternalEvent for HttpSinkInvalidUri<'a> {
+    fn emit(self) {
+        error!(
+            message = "Rendered URI is invalid.",
+            uri = %self.uri,
+            error = %self.error,
+            error_code = "invalid_uri",
+            error_type = error_type::TEMPLATE_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_uri",
+            "error_type" => error_type::TEMPLATE_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Rendered URI is invalid.",
+        });
+    }
+}
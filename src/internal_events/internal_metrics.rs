@@ -1,4 +1,4 @@
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::InternalEvent;
 
 #[derive(Debug)]
@@ -19,3 +19,29 @@ impl InternalEvent for InternalMetricsBytesReceived {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct InternalMetricsTagValuesCollapsed<'a> {
+    pub metric_name: &'a str,
+    pub tag: &'a str,
+    pub count: usize,
+}
+
+impl InternalEvent for InternalMetricsTagValuesCollapsed<'_> {
+    fn emit(self) {
+        if self.count > 0 {
+            warn!(
+                message = "Collapsed tag values over the cardinality limit.",
+                metric_name = %self.metric_name,
+                tag = %self.tag,
+                count = %self.count,
+                internal_log_rate_limit = true,
+            );
+        }
+        gauge!(
+            "internal_metrics_collapsed_series", self.count as f64,
+            "metric_name" => self.metric_name.to_owned(),
+            "tag" => self.tag.to_owned(),
+        );
+    }
+}
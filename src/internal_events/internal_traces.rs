@@ -0,0 +1,37 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct InternalTracesExportError<E> {
+    pub error: E,
+    pub count: usize,
+}
+
+impl<E: std::fmt::Display> InternalEvent for InternalTracesExportError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to export the internal traces.",
+            error = %self.error,
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("internal_traces_export_errors_total", 1);
+        counter!("internal_traces_dropped_spans_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct InternalTracesSpansDropped {
+    pub count: u64,
+}
+
+impl InternalEvent for InternalTracesSpansDropped {
+    fn emit(self) {
+        warn!(
+            message = "Dropped internal trace spans as the export queue was full.",
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("internal_traces_dropped_spans_total", self.count);
+    }
+}
@@ -111,6 +111,30 @@ impl InternalEvent for KafkaReadError {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaSchemaRegistryError {
+    pub error: crate::schema_registry::SchemaRegistryError,
+}
+
+impl InternalEvent for KafkaSchemaRegistryError {
+    fn emit(self) {
+        error!(
+            message = "Failed to resolve message schema.",
+            error = %self.error,
+            error_code = "resolving_schema",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "resolving_schema",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaStatisticsReceived<'a> {
     pub statistics: &'a rdkafka::Statistics,
@@ -0,0 +1,50 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct KubernetesEventsWatchError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KubernetesEventsWatchError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to watch Kubernetes events. Retrying.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsCheckpointError<'a> {
+    pub error: std::io::Error,
+    pub filename: &'a std::path::Path,
+}
+
+impl InternalEvent for KubernetesEventsCheckpointError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Could not write Kubernetes events checkpoint.",
+            filename = ?self.filename,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
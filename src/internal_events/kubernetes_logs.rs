@@ -164,6 +164,57 @@ impl InternalEvent for KubernetesLogsDockerFormatParseError<'_> {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct KubernetesLogsPodHintError<'a, E> {
+    pub annotation: &'static str,
+    pub pod: &'a str,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KubernetesLogsPodHintError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Ignoring invalid pod parsing hint.",
+            annotation = %self.annotation,
+            pod = %self.pod,
+            error = %self.error,
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct KubernetesLogsPodHintParseError<'a> {
+    pub format: &'static str,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl InternalEvent for KubernetesLogsPodHintParseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse message with the format hinted by its pod.",
+            format = %self.format,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!("k8s_pod_hint_parse_failures_total", 1);
+    }
+}
+
 const KUBERNETES_LIFECYCLE: &str = "kubernetes_lifecycle";
 
 #[derive(Debug)]
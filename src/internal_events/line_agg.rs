@@ -0,0 +1,46 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+/// The reason a multi-line aggregate was flushed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LineAggregationFlushReason {
+    /// A line matched the condition ending the aggregate.
+    Match,
+    /// No line was added to the aggregate before the timeout.
+    Timeout,
+    /// The lines started coming from another source, such as a rotated file.
+    Rotation,
+    /// The stream of lines ended, for example because of a shutdown.
+    Shutdown,
+}
+
+impl LineAggregationFlushReason {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Match => "match",
+            Self::Timeout => "timeout",
+            Self::Rotation => "rotation",
+            Self::Shutdown => "shutdown",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LineAggregationFlushed {
+    pub reason: LineAggregationFlushReason,
+    pub lines: usize,
+}
+
+impl InternalEvent for LineAggregationFlushed {
+    fn emit(self) {
+        trace!(
+            message = "Flushed multi-line aggregate.",
+            reason = self.reason.as_str(),
+            lines = self.lines,
+        );
+        counter!(
+            "multiline_flushed_lines_total", self.lines as u64,
+            "reason" => self.reason.as_str(),
+        );
+    }
+}
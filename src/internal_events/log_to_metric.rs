@@ -73,3 +73,35 @@ impl<'a> InternalEvent for LogToMetricParseFloatError<'a> {
         emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
     }
 }
+
+pub struct LogToMetricVrlError<'a> {
+    pub field: &'a str,
+    pub error: &'a str,
+    pub drop_event: bool,
+}
+
+impl<'a> InternalEvent for LogToMetricVrlError<'a> {
+    fn emit(self) {
+        let reason = "VRL expression execution failed.";
+        error!(
+            message = reason,
+            error = %self.error,
+            field = %self.field,
+            error_code = "vrl_execution_failed",
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "vrl_execution_failed",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+            "field" => self.field.to_string(),
+        );
+
+        if self.drop_event {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
+        }
+    }
+}
@@ -53,3 +53,18 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
                 "error_type" => "out_of_order");
     }
 }
+
+#[derive(Debug)]
+pub struct LokiReorderWindowExceeded {
+    pub count: usize,
+}
+
+impl InternalEvent for LokiReorderWindowExceeded {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "reorder_window_exceeded",
+        });
+        counter!("reorder_window_exceeded_events_total", self.count as u64);
+    }
+}
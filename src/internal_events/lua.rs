@@ -81,6 +81,32 @@ impl InternalEvent for LuaBuildError {
     }
 }
 
+#[derive(Debug)]
+pub struct LuaStateCheckpointError<'a> {
+    pub error: crate::Error,
+    pub path: &'a std::path::Path,
+}
+
+impl<'a> InternalEvent for LuaStateCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to save Lua state.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "state_checkpoint_failed",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "state_checkpoint_failed",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
 const fn mlua_error_code(err: &mlua::Error) -> &'static str {
     use mlua::Error::*;
 
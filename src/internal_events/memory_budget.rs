@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use crate::emit;
+use metrics::{counter, gauge, histogram};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct MemoryBudgetThrottled {
+    pub duration: Duration,
+}
+
+impl InternalEvent for MemoryBudgetThrottled {
+    fn emit(self) {
+        debug!(
+            message = "Memory budget used up, throttling the source.",
+            duration_ms = %self.duration.as_millis(),
+            internal_log_rate_limit = true,
+        );
+        counter!("memory_budget_throttled_total", 1);
+        histogram!("memory_budget_throttle_duration_seconds", self.duration);
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryBudgetEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for MemoryBudgetEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Memory budget exceeded.",
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryBudgetUsedBytes {
+    pub bytes: usize,
+}
+
+impl InternalEvent for MemoryBudgetUsedBytes {
+    fn emit(self) {
+        gauge!("memory_budget_used_bytes", self.bytes as f64);
+    }
+}
@@ -3,6 +3,8 @@ pub mod prelude;
 
 mod adaptive_concurrency;
 mod aggregate;
+#[cfg(feature = "transforms-aggregate_logs")]
+mod aggregate_logs;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
 mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
@@ -11,7 +13,10 @@ mod apache_metrics;
 mod api;
 #[cfg(feature = "aws-core")]
 mod aws;
-#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sinks-aws_cloudwatch_logs"
+))]
 mod aws_cloudwatch_logs;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 mod aws_ec2_metadata;
@@ -24,16 +29,35 @@ mod aws_ecs_metrics;
 mod aws_kinesis;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 mod aws_kinesis_firehose;
+#[cfg(any(
+    feature = "sinks-aws_s3",
+    feature = "sinks-datadog_archives",
+    feature = "sources-aws_s3",
+))]
+mod aws_s3;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs"
+))]
+mod azure_event_hubs;
+mod bandwidth;
 mod batch;
+#[cfg(feature = "transforms-cardinality_limit")]
+mod cardinality_limit;
+#[cfg(feature = "sinks-clickhouse")]
+mod clickhouse;
 mod codecs;
 mod common;
 mod conditions;
+#[cfg(feature = "sources-datadog_agent")]
+mod datadog_agent;
 #[cfg(feature = "sinks-datadog_metrics")]
 mod datadog_metrics;
 #[cfg(feature = "sinks-datadog_traces")]
 mod datadog_traces;
+mod dead_letter;
 #[cfg(feature = "transforms-dedupe")]
 mod dedupe;
 #[cfg(feature = "sources-demo_logs")]
@@ -42,10 +66,16 @@ mod demo_logs;
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(feature = "transforms-ecs")]
+mod ecs;
+#[cfg(feature = "sinks-elasticsearch")]
+mod elasticsearch;
+#[cfg(feature = "sinks-email")]
+mod email;
 mod encoding_transcode;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
-#[cfg(feature = "sources-exec")]
+#[cfg(any(feature = "sources-exec", feature = "sinks-exec"))]
 mod exec;
 #[cfg(any(feature = "sources-file-descriptor", feature = "sources-stdin"))]
 mod file_descriptor;
@@ -55,6 +85,8 @@ mod filter;
 mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
+#[cfg(feature = "transforms-geoip")]
+mod geoip;
 #[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
 mod grpc;
 mod heartbeat;
@@ -64,64 +96,108 @@ mod http;
 pub mod http_client;
 #[cfg(feature = "sources-utils-http-client")]
 mod http_client_source;
+#[cfg(feature = "sinks-http")]
+mod http_sink;
 #[cfg(feature = "sinks-influxdb")]
 mod influxdb;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
 mod internal_metrics;
+#[cfg(feature = "internal-telemetry-traces")]
+mod internal_traces;
 #[cfg(all(unix, feature = "sources-journald"))]
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
+mod line_agg;
 mod log_to_metric;
 mod logplex;
 #[cfg(feature = "sinks-loki")]
 mod loki;
 #[cfg(feature = "transforms-lua")]
 mod lua;
+mod memory_budget;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+mod mqtt;
+#[cfg(any(
+    feature = "sources-nats_jetstream",
+    feature = "sinks-nats",
+    feature = "sinks-nats_jetstream"
+))]
 mod nats;
+#[cfg(feature = "sources-netflow")]
+mod netflow;
+#[cfg(all(target_os = "linux", feature = "sources-network_flows"))]
+mod network_flows;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+mod ordering;
 mod parser;
+#[cfg(feature = "sources-postgres_cdc")]
+mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 mod prometheus;
+#[cfg(feature = "transforms-protect_fields")]
+mod protect_fields;
 #[cfg(feature = "sinks-pulsar")]
 mod pulsar;
-#[cfg(feature = "sources-redis")]
+mod recording;
+#[cfg(any(feature = "sources-redis", feature = "sources-redis_streams"))]
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
+#[cfg(feature = "sources-replay")]
+mod replay;
+mod runtime_options;
 mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sinks-sentry")]
+mod sentry;
+mod sink_schema;
+#[cfg(feature = "sources-snmp_trap")]
+mod snmp_trap;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
+mod stale_events;
 #[cfg(feature = "sinks-statsd")]
 mod statsd_sink;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sample")]
+mod tail_sample;
 mod tcp;
 mod template;
+mod tenant;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
 mod udp;
 mod unix;
+#[cfg(feature = "sinks-vector")]
+mod vector;
+#[cfg(feature = "transforms-wasm")]
+mod wasm;
+#[cfg(feature = "sources-webhook_receiver")]
+mod webhook_receiver;
 #[cfg(feature = "sinks-websocket")]
 mod websocket;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+mod windows_event_log;
 
 #[cfg(any(
     feature = "sources-file",
@@ -136,6 +212,8 @@ pub(crate) use mongodb_metrics::*;
 
 #[cfg(feature = "transforms-aggregate")]
 pub(crate) use self::aggregate::*;
+#[cfg(feature = "transforms-aggregate_logs")]
+pub(crate) use self::aggregate_logs::*;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
 pub(crate) use self::amqp::*;
 #[cfg(feature = "sources-apache_metrics")]
@@ -144,7 +222,10 @@ pub(crate) use self::apache_metrics::*;
 pub(crate) use self::api::*;
 #[cfg(feature = "aws-core")]
 pub(crate) use self::aws::*;
-#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sinks-aws_cloudwatch_logs"
+))]
 pub(crate) use self::aws_cloudwatch_logs::*;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub(crate) use self::aws_ec2_metadata::*;
@@ -157,13 +238,31 @@ pub(crate) use self::aws_ecs_metrics::*;
 pub(crate) use self::aws_kinesis::*;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub(crate) use self::aws_kinesis_firehose::*;
+#[cfg(any(
+    feature = "sinks-aws_s3",
+    feature = "sinks-datadog_archives",
+    feature = "sources-aws_s3",
+))]
+pub(crate) use self::aws_s3::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs"
+))]
+pub(crate) use self::azure_event_hubs::*;
+#[cfg(feature = "transforms-cardinality_limit")]
+pub(crate) use self::cardinality_limit::*;
+#[cfg(feature = "sinks-clickhouse")]
+pub(crate) use self::clickhouse::*;
 pub(crate) use self::codecs::*;
+#[cfg(feature = "sources-datadog_agent")]
+pub(crate) use self::datadog_agent::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
 #[cfg(feature = "sinks-datadog_traces")]
 pub(crate) use self::datadog_traces::*;
+pub(crate) use self::dead_letter::*;
 #[cfg(feature = "transforms-dedupe")]
 pub(crate) use self::dedupe::*;
 #[cfg(feature = "sources-demo_logs")]
@@ -172,9 +271,15 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(feature = "transforms-ecs")]
+pub(crate) use self::ecs::*;
+#[cfg(feature = "sinks-elasticsearch")]
+pub(crate) use self::elasticsearch::*;
+#[cfg(feature = "sinks-email")]
+pub(crate) use self::email::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
-#[cfg(feature = "sources-exec")]
+#[cfg(any(feature = "sources-exec", feature = "sinks-exec"))]
 pub(crate) use self::exec::*;
 #[cfg(any(
     feature = "sources-file",
@@ -190,6 +295,8 @@ pub(crate) use self::filter::*;
 pub(crate) use self::fluent::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
+#[cfg(feature = "transforms-geoip")]
+pub(crate) use self::geoip::*;
 #[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
 pub(crate) use self::grpc::*;
 #[cfg(feature = "sources-host_metrics")]
@@ -203,16 +310,22 @@ pub(crate) use self::host_metrics::*;
 pub(crate) use self::http::*;
 #[cfg(feature = "sources-utils-http-client")]
 pub(crate) use self::http_client_source::*;
+#[cfg(feature = "sinks-http")]
+pub(crate) use self::http_sink::*;
 #[cfg(feature = "sinks-influxdb")]
 pub(crate) use self::influxdb::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
 #[cfg(feature = "sources-internal_metrics")]
 pub(crate) use self::internal_metrics::*;
+#[cfg(feature = "internal-telemetry-traces")]
+pub(crate) use self::internal_traces::*;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub(crate) use self::kafka::*;
+#[cfg(feature = "sources-kubernetes_events")]
+pub(crate) use self::kubernetes_events::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
 pub(crate) use self::log_to_metric::*;
@@ -224,33 +337,59 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) use self::mqtt::*;
+#[cfg(any(
+    feature = "sources-nats_jetstream",
+    feature = "sinks-nats",
+    feature = "sinks-nats_jetstream"
+))]
 pub(crate) use self::nats::*;
+#[cfg(feature = "sources-netflow")]
+pub(crate) use self::netflow::*;
+#[cfg(all(target_os = "linux", feature = "sources-network_flows"))]
+pub(crate) use self::network_flows::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 pub(crate) use self::parser::*;
+#[cfg(feature = "sources-postgres_cdc")]
+pub(crate) use self::postgres_cdc::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 pub(crate) use self::prometheus::*;
+#[cfg(feature = "transforms-protect_fields")]
+pub(crate) use self::protect_fields::*;
 #[cfg(feature = "sinks-pulsar")]
 pub(crate) use self::pulsar::*;
-#[cfg(feature = "sources-redis")]
+pub(crate) use self::recording::*;
+#[cfg(any(feature = "sources-redis", feature = "sources-redis_streams"))]
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
 pub(crate) use self::reduce::*;
 #[cfg(feature = "transforms-remap")]
 pub(crate) use self::remap::*;
+#[cfg(feature = "sources-replay")]
+pub(crate) use self::replay::*;
+pub(crate) use self::runtime_options::*;
 #[cfg(feature = "transforms-sample")]
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sinks-sentry")]
+pub(crate) use self::sentry::*;
+pub(crate) use self::sink_schema::*;
+#[cfg(feature = "sources-snmp_trap")]
+pub(crate) use self::snmp_trap::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
+pub(crate) use self::stale_events::*;
 #[cfg(feature = "sinks-statsd")]
 pub(crate) use self::statsd_sink::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub(crate) use self::tag_cardinality_limit::*;
+#[cfg(feature = "transforms-tail_sample")]
+pub(crate) use self::tail_sample::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
 #[cfg(all(
@@ -266,15 +405,28 @@ pub(crate) use self::throttle::*;
     unix
 ))]
 pub(crate) use self::unix::*;
+#[cfg(feature = "sinks-vector")]
+pub(crate) use self::vector::*;
+#[cfg(feature = "transforms-wasm")]
+pub(crate) use self::wasm::*;
+#[cfg(feature = "sources-webhook_receiver")]
+pub(crate) use self::webhook_receiver::*;
 #[cfg(feature = "sinks-websocket")]
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+pub(crate) use self::windows_event_log::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, bandwidth::*, batch::*, common::*, conditions::*,
+    encoding_transcode::*, heartbeat::*, line_agg::*, memory_budget::*, open::*, ordering::*,
+    process::*, socket::*, tcp::*, template::*, tenant::*, udp::*,
 };
 
+// The metrics of emitted events are tagged with the `component_id`, `component_kind` and
+// `component_type` fields of the current span, so the tasks and blocking closures spawned by
+// components must run in it, such as by being instrumented with `in_current_span`.
+
 // this version won't be needed once all `InternalEvent`s implement `name()`
 #[cfg(test)]
 #[macro_export]
@@ -0,0 +1,120 @@
+#[cfg(feature = "sinks-mqtt")]
+pub use sink::*;
+#[cfg(feature = "sources-mqtt")]
+pub use source::*;
+
+#[cfg(feature = "sources-mqtt")]
+mod source {
+    use metrics::counter;
+    use vector_common::internal_event::{error_stage, error_type};
+    use vector_core::internal_event::InternalEvent;
+
+    use crate::mqtt::MqttError;
+
+    #[derive(Debug)]
+    pub struct MqttReceiveError {
+        pub error: MqttError,
+    }
+
+    impl InternalEvent for MqttReceiveError {
+        fn emit(self) {
+            error!(
+                message = "Failed to receive messages.",
+                error = %self.error,
+                error_code = "failed_receiving",
+                error_type = error_type::CONNECTION_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_receiving",
+                "error_type" => error_type::CONNECTION_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct MqttAckError {
+        pub error: MqttError,
+    }
+
+    impl InternalEvent for MqttAckError {
+        fn emit(self) {
+            error!(
+                message = "Unable to acknowledge message.",
+                error = %self.error,
+                error_code = "failed_acknowledge",
+                error_type = error_type::ACKNOWLEDGMENT_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_acknowledge",
+                "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "sinks-mqtt")]
+mod sink {
+    use metrics::counter;
+    use vector_common::internal_event::{
+        error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+    };
+    use vector_core::internal_event::InternalEvent;
+
+    use crate::{emit, mqtt::MqttError};
+
+    #[derive(Debug)]
+    pub struct MqttConnectionError {
+        pub error: MqttError,
+    }
+
+    impl InternalEvent for MqttConnectionError {
+        fn emit(self) {
+            error!(
+                message = "MQTT connection error.",
+                error = %self.error,
+                error_code = "mqtt_connection",
+                error_type = error_type::CONNECTION_FAILED,
+                stage = error_stage::SENDING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "mqtt_connection",
+                "error_type" => error_type::CONNECTION_FAILED,
+                "stage" => error_stage::SENDING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct MqttEventSendError {
+        pub error: MqttError,
+    }
+
+    impl InternalEvent for MqttEventSendError {
+        fn emit(self) {
+            let reason = "Failed to send message.";
+            error!(
+                message = reason,
+                error = %self.error,
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::SENDING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_type" => error_type::WRITER_FAILED,
+                "stage" => error_stage::SENDING,
+            );
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+        }
+    }
+}
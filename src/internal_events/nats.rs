@@ -1,39 +1,106 @@
-use std::io::Error;
+#[cfg(any(feature = "sinks-nats", feature = "sinks-nats_jetstream"))]
+pub use sink::*;
+#[cfg(feature = "sources-nats_jetstream")]
+pub use source::*;
 
-use crate::emit;
-use metrics::counter;
-use vector_common::internal_event::{
-    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
-};
-use vector_core::internal_event::InternalEvent;
+#[cfg(any(feature = "sinks-nats", feature = "sinks-nats_jetstream"))]
+mod sink {
+    use std::io::Error;
 
-use super::prelude::io_error_code;
+    use crate::emit;
+    use metrics::counter;
+    use vector_common::internal_event::{
+        error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+    };
+    use vector_core::internal_event::InternalEvent;
 
-#[derive(Debug)]
-pub struct NatsEventSendError {
-    pub error: Error,
+    use super::super::prelude::io_error_code;
+
+    #[derive(Debug)]
+    pub struct NatsEventSendError {
+        pub error: Error,
+    }
+
+    impl InternalEvent for NatsEventSendError {
+        fn emit(self) {
+            let reason = "Failed to send message.";
+            error!(
+                message = reason,
+                error = %self.error,
+                error_type = error_type::WRITER_FAILED,
+                error_code = io_error_code(&self.error),
+                stage = error_stage::SENDING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_type" => error_type::WRITER_FAILED,
+                "error_code" => io_error_code(&self.error),
+                "stage" => error_stage::SENDING,
+            );
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+
+            // deprecated
+            counter!("send_errors_total", 1);
+        }
+    }
 }
 
-impl InternalEvent for NatsEventSendError {
-    fn emit(self) {
-        let reason = "Failed to send message.";
-        error!(
-            message = reason,
-            error = %self.error,
-            error_type = error_type::WRITER_FAILED,
-            error_code = io_error_code(&self.error),
-            stage = error_stage::SENDING,
-            internal_log_rate_limit = true,
-        );
-        counter!(
-            "component_errors_total", 1,
-            "error_type" => error_type::WRITER_FAILED,
-            "error_code" => io_error_code(&self.error),
-            "stage" => error_stage::SENDING,
-        );
-        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
-
-        // deprecated
-        counter!("send_errors_total", 1);
+#[cfg(feature = "sources-nats_jetstream")]
+mod source {
+    use std::io::Error;
+
+    use metrics::counter;
+    use vector_common::internal_event::{error_stage, error_type};
+    use vector_core::internal_event::InternalEvent;
+
+    use super::super::prelude::io_error_code;
+
+    #[derive(Debug)]
+    pub struct NatsJetStreamFetchError {
+        pub error: Error,
+    }
+
+    impl InternalEvent for NatsJetStreamFetchError {
+        fn emit(self) {
+            error!(
+                message = "Failed to fetch messages.",
+                error = %self.error,
+                error_code = io_error_code(&self.error),
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => io_error_code(&self.error),
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct NatsJetStreamAckError {
+        pub error: Error,
+    }
+
+    impl InternalEvent for NatsJetStreamAckError {
+        fn emit(self) {
+            error!(
+                message = "Unable to acknowledge message.",
+                error = %self.error,
+                error_code = "failed_acknowledge",
+                error_type = error_type::ACKNOWLEDGMENT_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_acknowledge",
+                "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
     }
 }
@@ -0,0 +1,59 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+/// A datagram received by the `netflow` source which isn't a valid flow datagram.
+#[derive(Debug)]
+pub struct NetflowParseError<E> {
+    pub error: E,
+    pub peer_addr: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for NetflowParseError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse flow datagram.",
+            error = %self.error,
+            peer_addr = %self.peer_addr,
+            error_code = "invalid_flow_datagram",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_flow_datagram",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+/// A NetFlow v9 or IPFIX data set whose template wasn't received from its exporter, or expired.
+#[derive(Debug)]
+pub struct NetflowTemplateNotFound {
+    pub exporter: SocketAddr,
+    pub template_id: u16,
+}
+
+impl InternalEvent for NetflowTemplateNotFound {
+    fn emit(self) {
+        error!(
+            message = "Dropped flow records of an unknown template.",
+            exporter = %self.exporter,
+            template_id = %self.template_id,
+            error_code = "template_not_found",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "template_not_found",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
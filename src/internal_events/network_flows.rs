@@ -0,0 +1,28 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct NetworkFlowsScrapeError {
+    pub message: &'static str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for NetworkFlowsScrapeError {
+    fn emit(self) {
+        error!(
+            message = self.message,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use metrics::{gauge, histogram};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct OrderingLaneQueued {
+    pub depth: usize,
+}
+
+impl InternalEvent for OrderingLaneQueued {
+    fn emit(self) {
+        histogram!("ordering_lane_depth", self.depth as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct OrderingLanesActive {
+    pub count: usize,
+}
+
+impl InternalEvent for OrderingLanesActive {
+    fn emit(self) {
+        gauge!("ordering_lanes", self.count as f64);
+    }
+}
@@ -0,0 +1,52 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct PostgresCdcQueryError<'a> {
+    pub error: String,
+    pub slot: &'a str,
+}
+
+impl<'a> InternalEvent for PostgresCdcQueryError<'a> {
+    fn emit(self) {
+        error!(
+            message = "PostgreSQL query error.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            slot = %self.slot,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresCdcDecodeError<'a> {
+    pub error: String,
+    pub lsn: &'a str,
+}
+
+impl<'a> InternalEvent for PostgresCdcDecodeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to decode change, skipping it.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            lsn = %self.lsn,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
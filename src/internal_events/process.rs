@@ -47,6 +47,16 @@ impl InternalEvent for VectorReloaded<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct InternalLogsSuppressed;
+
+impl InternalEvent for InternalLogsSuppressed {
+    fn emit(self) {
+        // MUST not emit logs here, as this is emitted while handling a log event
+        counter!("internal_logs_suppressed_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct VectorStopped;
 
@@ -47,6 +47,33 @@ impl<'a> InternalEvent for PrometheusParseError<'a> {
     }
 }
 
+#[cfg(feature = "sources-prometheus")]
+#[derive(Debug)]
+pub struct PrometheusDiscoveryError<E> {
+    pub error: E,
+    pub mechanism: &'static str,
+}
+
+#[cfg(feature = "sources-prometheus")]
+impl<E: std::fmt::Display> InternalEvent for PrometheusDiscoveryError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to discover targets. Keeping the previously discovered targets.",
+            error = %self.error,
+            mechanism = self.mechanism,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+            "mechanism" => self.mechanism,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct PrometheusRemoteWriteParseError {
     pub error: prost::DecodeError,
@@ -71,6 +98,30 @@ impl InternalEvent for PrometheusRemoteWriteParseError {
     }
 }
 
+#[derive(Debug)]
+pub struct PrometheusRemoteWriteSamplesRejected<'a> {
+    pub status: StatusCode,
+    pub body: &'a str,
+}
+
+impl<'a> InternalEvent for PrometheusRemoteWriteSamplesRejected<'a> {
+    fn emit(self) {
+        error!(
+            message = "Some samples were rejected by the receiver, the others were written.",
+            status_code = %self.status,
+            error = %self.body,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct PrometheusServerRequestComplete {
     pub status_code: StatusCode,
@@ -0,0 +1,37 @@
+use crate::emit;
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct FieldProtectionError<'a> {
+    pub field: &'a str,
+    pub error: crate::Error,
+    pub drop_event: bool,
+}
+
+impl<'a> InternalEvent for FieldProtectionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to protect field.",
+            field = %self.field,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        if self.drop_event {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: 1,
+                reason: "Failed to protect field.",
+            });
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct RecordingWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for RecordingWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing to recording, no longer recording.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "recording_write_failed",
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "recording_write_failed",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct RecordingLimitReached<'a> {
+    pub path: &'a Path,
+    pub max_bytes: u64,
+}
+
+impl InternalEvent for RecordingLimitReached<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Recording reached its maximum size, no longer recording.",
+            path = ?self.path,
+            max_bytes = %self.max_bytes,
+        );
+    }
+}
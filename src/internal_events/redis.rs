@@ -34,3 +34,27 @@ impl InternalEvent for RedisReceiveEventError {
         );
     }
 }
+
+#[cfg(feature = "sources-redis_streams")]
+#[derive(Debug)]
+pub struct RedisStreamAckError {
+    pub error: redis::RedisError,
+}
+
+#[cfg(feature = "sources-redis_streams")]
+impl InternalEvent for RedisStreamAckError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge stream entries.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
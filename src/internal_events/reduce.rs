@@ -1,6 +1,9 @@
 use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::io_error_code;
+
 #[derive(Debug)]
 pub struct ReduceStaleEventFlushed;
 
@@ -9,3 +12,29 @@ impl InternalEvent for ReduceStaleEventFlushed {
         counter!("stale_events_flushed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct ReduceStateError<'a> {
+    pub path: &'a std::path::Path,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ReduceStateError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to access the saved state of the reduced events.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -1,5 +1,7 @@
+use std::{fmt, time::Duration};
+
 use crate::emit;
-use metrics::counter;
+use metrics::{counter, register_histogram, Histogram};
 use vector_core::internal_event::InternalEvent;
 
 use vector_common::internal_event::{
@@ -12,6 +14,10 @@ pub struct RemapMappingError {
     /// mapping. This internal event reflects that in its messaging.
     pub event_dropped: bool,
     pub error: String,
+    /// The code of the VRL error.
+    pub error_code: usize,
+    /// The function whose call failed, if the error was raised by one.
+    pub function: Option<String>,
 }
 
 impl InternalEvent for RemapMappingError {
@@ -28,6 +34,11 @@ impl InternalEvent for RemapMappingError {
             "error_type" => error_type::CONVERSION_FAILED,
             "stage" => error_stage::PROCESSING,
         );
+        counter!(
+            "remap_errors_total", 1,
+            "error_code" => format!("E{:03}", self.error_code),
+            "function" => self.function.unwrap_or_else(|| "none".to_owned()),
+        );
         if self.event_dropped {
             emit!(ComponentEventsDropped::<UNINTENTIONAL> {
                 count: 1,
@@ -52,6 +63,7 @@ impl InternalEvent for RemapMappingAbort {
             message = "Event mapping aborted.",
             internal_log_rate_limit = true
         );
+        counter!("remap_aborts_total", 1);
 
         if self.event_dropped {
             emit!(ComponentEventsDropped::<INTENTIONAL> {
@@ -61,3 +73,19 @@ impl InternalEvent for RemapMappingAbort {
         }
     }
 }
+
+vector_common::registered_event!(
+    RemapProgramExecuted => {
+        duration: Histogram = register_histogram!("remap_execution_duration_seconds"),
+    }
+
+    fn emit(&self, duration: Duration) {
+        self.duration.record(duration);
+    }
+);
+
+impl fmt::Debug for RemapProgramExecutedHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemapProgramExecutedHandle").finish()
+    }
+}
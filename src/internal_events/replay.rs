@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct RecordingReadError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for RecordingReadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed reading recording.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "recording_read_failed",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "recording_read_failed",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use metrics::counter;
+use tracing::level_filters::LevelFilter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ComponentOptionChanged<'a> {
+    pub component_id: &'a str,
+    pub option: &'a str,
+    pub previous: u64,
+    pub value: u64,
+}
+
+impl InternalEvent for ComponentOptionChanged<'_> {
+    fn emit(self) {
+        info!(
+            message = "Component option changed at runtime.",
+            component_id = %self.component_id,
+            option = %self.option,
+            previous = %self.previous,
+            value = %self.value,
+        );
+        counter!(
+            "component_option_changes_total", 1,
+            "component_id" => self.component_id.to_owned(),
+            "option" => self.option.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ComponentLogLevelChanged<'a> {
+    pub component_id: &'a str,
+    pub previous: Option<LevelFilter>,
+    pub level: Option<LevelFilter>,
+}
+
+impl InternalEvent for ComponentLogLevelChanged<'_> {
+    fn emit(self) {
+        // `None` stands for the level of the logs given on startup.
+        let describe = |level: Option<LevelFilter>| {
+            level.map_or_else(|| "default".to_owned(), |level| level.to_string())
+        };
+        info!(
+            message = "Component log level changed at runtime.",
+            component_id = %self.component_id,
+            previous = %describe(self.previous),
+            level = %describe(self.level),
+        );
+        counter!(
+            "component_option_changes_total", 1,
+            "component_id" => self.component_id.to_owned(),
+            "option" => "log_level",
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::emit;
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
 
 #[derive(Debug)]
@@ -14,3 +14,15 @@ impl InternalEvent for SampleEventDiscarded {
         })
     }
 }
+
+#[derive(Debug)]
+pub struct SampleRateUpdated {
+    pub rate: u64,
+}
+
+impl InternalEvent for SampleRateUpdated {
+    fn emit(self) {
+        trace!(message = "Sample rate updated.", rate = %self.rate);
+        gauge!("sample_rate", self.rate as f64);
+    }
+}
@@ -0,0 +1,16 @@
+use vector_common::internal_event::{ComponentEventsDropped, INTENTIONAL};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct SentryEventRateLimited;
+
+impl InternalEvent for SentryEventRateLimited {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Rate limit of the fingerprint reached.",
+        });
+    }
+}
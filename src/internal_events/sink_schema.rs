@@ -0,0 +1,33 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SinkSchemaViolation<'a, E> {
+    pub error: &'a E,
+    pub drop_event: bool,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SinkSchemaViolation<'_, E> {
+    fn emit(self) {
+        let reason = "Event violates the schema of the sink.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.drop_event {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+/// A message received by the `snmp_trap` source which isn't a valid SNMP trap.
+#[derive(Debug)]
+pub struct SnmpTrapParseError<E> {
+    pub error: E,
+    pub peer_addr: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SnmpTrapParseError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse SNMP trap.",
+            error = %self.error,
+            peer_addr = %self.peer_addr,
+            error_code = "invalid_snmp_message",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_snmp_message",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+/// A trap rejected by the `snmp_trap` source, for its community or user, or as it failed to
+/// authenticate.
+#[derive(Debug)]
+pub struct SnmpTrapRejected<E> {
+    pub error: E,
+    pub peer_addr: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SnmpTrapRejected<E> {
+    fn emit(self) {
+        error!(
+            message = "Rejected SNMP trap.",
+            error = %self.error,
+            peer_addr = %self.peer_addr,
+            error_code = "snmp_security_check_failed",
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "snmp_security_check_failed",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -7,7 +7,9 @@ pub use self::source::*;
 
 #[cfg(feature = "sinks-splunk_hec")]
 mod sink {
-    use metrics::{counter, decrement_gauge, increment_gauge};
+    use std::time::Duration;
+
+    use metrics::{counter, decrement_gauge, gauge, histogram, increment_gauge};
     use serde_json::Error;
     use vector_core::internal_event::InternalEvent;
 
@@ -168,6 +170,29 @@ mod sink {
         }
     }
 
+    pub struct SplunkIndexerAcknowledgementAckFinalized {
+        pub age: Duration,
+    }
+
+    impl InternalEvent for SplunkIndexerAcknowledgementAckFinalized {
+        fn emit(self) {
+            histogram!("splunk_ack_duration_seconds", self.age);
+        }
+    }
+
+    pub struct SplunkIndexerAcknowledgementAcksPending {
+        pub oldest_age: Duration,
+    }
+
+    impl InternalEvent for SplunkIndexerAcknowledgementAcksPending {
+        fn emit(self) {
+            gauge!(
+                "splunk_pending_acks_oldest_age_seconds",
+                self.oldest_age.as_secs_f64()
+            );
+        }
+    }
+
     pub struct SplunkEventTimestampInvalidType<'a> {
         pub r#type: &'a str,
     }
@@ -0,0 +1,20 @@
+use metrics::counter;
+use vector_common::internal_event::{ComponentEventsDropped, INTENTIONAL};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct StaleEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for StaleEventsDropped {
+    fn emit(self) {
+        counter!("dropped_stale_events_total", self.count as u64);
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Event is older than the maximum age of the sink.",
+        });
+    }
+}
@@ -0,0 +1,35 @@
+use crate::emit;
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct TailSampleGroupKept;
+
+impl InternalEvent for TailSampleGroupKept {
+    fn emit(self) {
+        counter!("tail_sample_groups_kept_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct TailSampleGroupDropped;
+
+impl InternalEvent for TailSampleGroupDropped {
+    fn emit(self) {
+        counter!("tail_sample_groups_dropped_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct TailSampleEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for TailSampleEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Group of events dropped by sampling."
+        })
+    }
+}
@@ -1,6 +1,6 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
-use metrics::counter;
+use metrics::{counter, histogram};
 use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::InternalEvent;
 
@@ -149,3 +149,59 @@ impl InternalEvent for TcpBytesReceived {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct TcpConnectionRejected {
+    pub peer_addr: SocketAddr,
+    pub limit: &'static str,
+}
+
+impl InternalEvent for TcpConnectionRejected {
+    fn emit(self) {
+        warn!(
+            message = "Connection rejected, over the limit of connections.",
+            peer_addr = %self.peer_addr,
+            limit = %self.limit,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "connections_rejected_total", 1,
+            "mode" => "tcp",
+            "limit" => self.limit,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TcpConnectionQueued {
+    pub peer_addr: SocketAddr,
+    pub limit: &'static str,
+}
+
+impl InternalEvent for TcpConnectionQueued {
+    fn emit(self) {
+        debug!(
+            message = "Connection queued, over the limit of connections.",
+            peer_addr = %self.peer_addr,
+            limit = %self.limit,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "connections_queued_total", 1,
+            "mode" => "tcp",
+            "limit" => self.limit,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TcpConnectionThrottled {
+    pub delay: Duration,
+}
+
+impl InternalEvent for TcpConnectionThrottled {
+    fn emit(self) {
+        trace!(message = "Connection throttled, over its rate.", delay = ?self.delay);
+        histogram!("connection_throttle_duration_seconds", self.delay, "mode" => "tcp");
+    }
+}
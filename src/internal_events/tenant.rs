@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use metrics::{counter, gauge, histogram};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TenantThrottled<'a> {
+    pub tenant: &'a str,
+    pub reason: &'static str,
+    pub duration: Duration,
+}
+
+impl InternalEvent for TenantThrottled<'_> {
+    fn emit(self) {
+        debug!(
+            message = "Tenant quota exceeded, throttling its sources.",
+            tenant = %self.tenant,
+            reason = %self.reason,
+            duration_ms = %self.duration.as_millis(),
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "tenant_throttled_total", 1,
+            "tenant" => self.tenant.to_owned(),
+            "reason" => self.reason,
+        );
+        histogram!(
+            "tenant_throttle_duration_seconds", self.duration,
+            "tenant" => self.tenant.to_owned(),
+            "reason" => self.reason,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantBufferedBytes<'a> {
+    pub tenant: &'a str,
+    pub bytes: usize,
+}
+
+impl InternalEvent for TenantBufferedBytes<'_> {
+    fn emit(self) {
+        gauge!(
+            "tenant_buffered_bytes", self.bytes as f64,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
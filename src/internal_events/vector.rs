@@ -0,0 +1,17 @@
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct VectorStreamQueuedRequests {
+    pub stream: &'static str,
+    pub count: usize,
+}
+
+impl InternalEvent for VectorStreamQueuedRequests {
+    fn emit(self) {
+        gauge!(
+            "stream_queued_requests", self.count as f64,
+            "stream" => self.stream,
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct WasmProcessingError {
+    pub error: String,
+    pub error_code: &'static str,
+}
+
+impl InternalEvent for WasmProcessingError {
+    fn emit(self) {
+        error!(
+            message = "Error in WebAssembly module.",
+            error = %self.error,
+            error_code = self.error_code,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => self.error_code,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Error in WebAssembly module.",
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct WasmModuleReloaded<'a> {
+    pub path: &'a std::path::Path,
+}
+
+impl<'a> InternalEvent for WasmModuleReloaded<'a> {
+    fn emit(self) {
+        info!(message = "Reloaded WebAssembly module.", path = ?self.path);
+        counter!("wasm_module_reloads_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WasmModuleReloadError<'a> {
+    pub path: &'a std::path::Path,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for WasmModuleReloadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to reload WebAssembly module, keeping the previous one.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "reload_failed",
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "reload_failed",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+/// A counter incremented by the module, through the `counter` function of the ABI.
+#[derive(Debug)]
+pub struct WasmGuestCounter {
+    pub name: String,
+    pub value: u64,
+}
+
+impl InternalEvent for WasmGuestCounter {
+    fn emit(self) {
+        counter!("wasm_guest_counter_total", self.value, "name" => self.name);
+    }
+}
+
+/// A gauge set by the module, through the `gauge` function of the ABI.
+#[derive(Debug)]
+pub struct WasmGuestGauge {
+    pub name: String,
+    pub value: f64,
+}
+
+impl InternalEvent for WasmGuestGauge {
+    fn emit(self) {
+        gauge!("wasm_guest_gauge", self.value, "name" => self.name);
+    }
+}
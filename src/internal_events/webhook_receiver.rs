@@ -0,0 +1,24 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+/// A request rejected as its signature failed the verification.
+///
+/// The rejection itself is reported as a bad request, so this only counts the failures by reason.
+#[derive(Debug)]
+pub struct WebhookSignatureVerificationFailed {
+    pub reason: &'static str,
+}
+
+impl InternalEvent for WebhookSignatureVerificationFailed {
+    fn emit(self) {
+        debug!(
+            message = "Signature verification failed.",
+            reason = %self.reason,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "webhook_signature_verification_failures_total", 1,
+            "reason" => self.reason,
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+use crate::sources::windows_event_log::ParseError;
+
+#[derive(Debug)]
+pub struct WindowsEventLogSubscribeError<'a> {
+    pub error: std::io::Error,
+    pub channel: &'a str,
+}
+
+impl InternalEvent for WindowsEventLogSubscribeError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Could not subscribe to the Windows Event Log channel.",
+            error = %self.error,
+            channel = %self.channel,
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::CONNECTION_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogReadError<'a> {
+    pub error: std::io::Error,
+    pub channel: &'a str,
+}
+
+impl InternalEvent for WindowsEventLogReadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Could not read events from the Windows Event Log channel.",
+            error = %self.error,
+            channel = %self.channel,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogParseError<'a> {
+    pub error: ParseError,
+    pub channel: &'a str,
+}
+
+impl InternalEvent for WindowsEventLogParseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Invalid event from the Windows Event Log, discarding.",
+            error = %self.error,
+            channel = %self.channel,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogCheckpointError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for WindowsEventLogCheckpointError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Could not write the Windows Event Log checkpoints.",
+            error = %self.error,
+            path = ?self.path,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::WRITER_FAILED,
+        );
+        counter!("checkpoint_write_errors_total", 1);
+    }
+}
@@ -0,0 +1,192 @@
+//! CPU time accounting of the components, for the profiling endpoint of the API.
+//!
+//! The CPU time of the threads is read when entering and exiting the spans of the components, and
+//! the time spent between the two is attributed to the innermost component, so the time of a
+//! component doesn't include the time of the components it polls inline.
+use std::{
+    cell::{Cell, RefCell},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use nix::time::{clock_gettime, ClockId};
+use tracing::{span, Subscriber};
+use tracing_core::Field;
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// Whether the CPU time of the components is being accounted, as the profiling is enabled.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The components whose CPU time is accounted, in the order their first span was created in.
+static COMPONENTS: Mutex<Vec<Arc<ComponentCpu>>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// The components whose spans are entered on this thread.
+    static ENTERED: RefCell<Vec<Arc<ComponentCpu>>> = RefCell::new(Vec::new());
+
+    /// The CPU time of this thread, in nanoseconds, when it was last attributed.
+    static LAST_CPU_TIME: Cell<u64> = Cell::new(0);
+}
+
+/// Enables or disables the accounting of the CPU time of the components.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the CPU time of the components is being accounted.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The identity of a component, from the fields of its span.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Component {
+    pub kind: String,
+    pub id: String,
+    pub component_type: String,
+}
+
+#[derive(Debug)]
+struct ComponentCpu {
+    component: Component,
+    nanos: AtomicU64,
+}
+
+/// Returns the CPU time used by each component since the start of Vector, while profiling was
+/// enabled.
+pub fn component_cpu_times() -> Vec<(Component, Duration)> {
+    COMPONENTS
+        .lock()
+        .expect("poisoned lock")
+        .iter()
+        .map(|cpu| {
+            (
+                cpu.component.clone(),
+                Duration::from_nanos(cpu.nanos.load(Ordering::Relaxed)),
+            )
+        })
+        .collect()
+}
+
+/// Returns the CPU time used by the process since its start.
+pub fn process_cpu_time() -> Duration {
+    cpu_time(ClockId::CLOCK_PROCESS_CPUTIME_ID)
+}
+
+fn cpu_time(clock: ClockId) -> Duration {
+    clock_gettime(clock).map_or(Duration::ZERO, Duration::from)
+}
+
+fn thread_cpu_nanos() -> u64 {
+    cpu_time(ClockId::CLOCK_THREAD_CPUTIME_ID).as_nanos() as u64
+}
+
+fn register(component: Component) -> Arc<ComponentCpu> {
+    let mut components = COMPONENTS.lock().expect("poisoned lock");
+    // A component keeps its time when it is reloaded, or when its buffer has a span of its own.
+    if let Some(cpu) = components.iter().find(|cpu| cpu.component == component) {
+        return Arc::clone(cpu);
+    }
+
+    let cpu = Arc::new(ComponentCpu {
+        component,
+        nanos: AtomicU64::new(0),
+    });
+    components.push(Arc::clone(&cpu));
+    cpu
+}
+
+/// Attributes the CPU time of the thread since it was last attributed to the innermost component.
+fn attribute(entered: &[Arc<ComponentCpu>]) {
+    let now = thread_cpu_nanos();
+    let last = LAST_CPU_TIME.with(|last| last.replace(now));
+    if let Some(cpu) = entered.last() {
+        if ENABLED.load(Ordering::Relaxed) {
+            cpu.nanos
+                .fetch_add(now.saturating_sub(last), Ordering::Relaxed);
+        }
+    }
+}
+
+fn component_cpu<S>(ctx: &Context<'_, S>, id: &span::Id) -> Option<Arc<ComponentCpu>>
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    let span = ctx.span(id)?;
+    let extensions = span.extensions();
+    extensions.get::<Arc<ComponentCpu>>().cloned()
+}
+
+/// Accounts the CPU time spent in the spans of the components.
+pub struct CpuLayer;
+
+impl<S> Layer<S> for CpuLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if attrs.metadata().fields().field("component_id").is_none() {
+            return;
+        }
+
+        let mut component = Component::default();
+        attrs.values().record(&mut component);
+        let span = ctx.span(id).expect("span must already exist!");
+        span.extensions_mut().insert(register(component));
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+
+        if let Some(cpu) = component_cpu(&ctx, id) {
+            ENTERED.with(|entered| {
+                let mut entered = entered.borrow_mut();
+                attribute(&entered);
+                entered.push(cpu);
+            });
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        // Still tracked once the profiling is disabled, until the entered spans are exited.
+        if ENTERED.with(|entered| entered.borrow().is_empty()) {
+            return;
+        }
+
+        if let Some(cpu) = component_cpu(&ctx, id) {
+            ENTERED.with(|entered| {
+                let mut entered = entered.borrow_mut();
+                if entered.last().map_or(false, |last| Arc::ptr_eq(last, &cpu)) {
+                    attribute(&entered);
+                    entered.pop();
+                }
+            });
+        }
+    }
+}
+
+impl Component {
+    fn record(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "component_kind" => self.kind = value,
+            "component_id" => self.id = value,
+            "component_type" => self.component_type = value,
+            _ => {}
+        }
+    }
+}
+
+impl tracing::field::Visit for Component {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
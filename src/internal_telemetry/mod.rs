@@ -2,6 +2,10 @@
 
 #[cfg(feature = "allocation-tracing")]
 pub mod allocations;
+#[cfg(all(unix, feature = "api"))]
+pub mod cpu;
+#[cfg(feature = "internal-telemetry-traces")]
+pub mod traces;
 
 pub const fn is_allocation_tracking_enabled() -> bool {
     cfg!(feature = "allocation-tracing")
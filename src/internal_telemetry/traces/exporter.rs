@@ -0,0 +1,288 @@
+//! The export of the finished spans, in batches, to an OTLP gRPC collector.
+use std::{
+    mem,
+    sync::atomic::Ordering,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use opentelemetry_proto::proto::{
+    collector::trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+    resource::v1::Resource,
+    trace::v1::{span, status, ResourceSpans, ScopeSpans, Span, Status},
+};
+use tokio::{sync::mpsc, time};
+use tonic::transport::Channel;
+use vector_common::request_metadata::TraceContext;
+use vector_core::config::TracesConfig;
+
+use super::DROPPED_SPANS;
+use crate::internal_events::{InternalTracesExportError, InternalTracesSpansDropped};
+
+/// The maximum number of spans exported at once, sooner than the next export if reached.
+const MAX_BATCH_SIZE: usize = 512;
+
+/// A value of an attribute of a span or of an event.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum AttributeValue {
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    String(String),
+}
+
+impl AttributeValue {
+    pub(super) fn into_string(self) -> String {
+        match self {
+            Self::Bool(value) => value.to_string(),
+            Self::Int(value) => value.to_string(),
+            Self::Double(value) => value.to_string(),
+            Self::String(value) => value,
+        }
+    }
+}
+
+/// The data collected for a sampled span.
+pub(super) struct SpanData {
+    pub(super) name: String,
+    pub(super) start: SystemTime,
+    pub(super) attributes: Vec<(&'static str, AttributeValue)>,
+    pub(super) events: Vec<SpanEvent>,
+    pub(super) dropped_events: u32,
+    /// Whether an error was logged within the span.
+    pub(super) error: bool,
+}
+
+pub(super) struct SpanEvent {
+    pub(super) name: String,
+    pub(super) time: SystemTime,
+    pub(super) attributes: Vec<(&'static str, AttributeValue)>,
+}
+
+/// A closed span, waiting to be exported.
+pub(super) struct FinishedSpan {
+    pub(super) context: TraceContext,
+    pub(super) parent_span_id: Option<u64>,
+    pub(super) data: Box<SpanData>,
+    pub(super) end: SystemTime,
+}
+
+/// Exports the spans received, until the exporter is replaced or stopped.
+pub(super) async fn run(config: TracesConfig, mut receiver: mpsc::Receiver<FinishedSpan>) {
+    let mut client = None;
+    let mut batch = Vec::new();
+    let mut interval = time::interval(config.export_interval);
+
+    loop {
+        tokio::select! {
+            span = receiver.recv() => match span {
+                Some(span) => {
+                    batch.push(span);
+                    if batch.len() >= MAX_BATCH_SIZE {
+                        export(&config, &mut client, &mut batch).await;
+                    }
+                }
+                None => break,
+            },
+            _ = interval.tick() => export(&config, &mut client, &mut batch).await,
+        }
+    }
+
+    // The spans received before the exporter was replaced or stopped.
+    export(&config, &mut client, &mut batch).await;
+}
+
+async fn export(
+    config: &TracesConfig,
+    client: &mut Option<TraceServiceClient<Channel>>,
+    batch: &mut Vec<FinishedSpan>,
+) {
+    let dropped = DROPPED_SPANS.swap(0, Ordering::Relaxed);
+    if dropped > 0 {
+        emit!(InternalTracesSpansDropped { count: dropped });
+    }
+    if batch.is_empty() {
+        return;
+    }
+
+    let count = batch.len();
+    let request = export_request(&config.service_name, mem::take(batch));
+    if client.is_none() {
+        match TraceServiceClient::connect(config.endpoint.clone()).await {
+            Ok(connected) => *client = Some(connected),
+            Err(error) => {
+                emit!(InternalTracesExportError { error, count });
+                return;
+            }
+        }
+    }
+    if let Some(connected) = client.as_mut() {
+        if let Err(error) = connected.export(request).await {
+            // Connected again for the next export.
+            *client = None;
+            emit!(InternalTracesExportError { error, count });
+        }
+    }
+}
+
+fn export_request(service_name: &str, spans: Vec<FinishedSpan>) -> ExportTraceServiceRequest {
+    let mut resource_attributes = vec![
+        key_value(
+            "service.name",
+            AttributeValue::String(service_name.to_owned()),
+        ),
+        key_value(
+            "service.version",
+            AttributeValue::String(crate::vector_version().to_string()),
+        ),
+    ];
+    if let Ok(hostname) = crate::get_hostname() {
+        resource_attributes.push(key_value("host.name", AttributeValue::String(hostname)));
+    }
+
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(Resource {
+                attributes: resource_attributes,
+                dropped_attributes_count: 0,
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope {
+                    name: "vector".to_owned(),
+                    version: String::new(),
+                    attributes: Vec::new(),
+                    dropped_attributes_count: 0,
+                }),
+                spans: spans.into_iter().map(Span::from).collect(),
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+impl From<FinishedSpan> for Span {
+    fn from(span: FinishedSpan) -> Self {
+        let FinishedSpan {
+            context,
+            parent_span_id,
+            data,
+            end,
+        } = span;
+        let SpanData {
+            name,
+            start,
+            attributes,
+            events,
+            dropped_events,
+            error,
+        } = *data;
+
+        Self {
+            trace_id: context.trace_id.to_be_bytes().to_vec(),
+            span_id: context.span_id.to_be_bytes().to_vec(),
+            trace_state: String::new(),
+            parent_span_id: parent_span_id
+                .map(|span_id| span_id.to_be_bytes().to_vec())
+                .unwrap_or_default(),
+            name,
+            kind: span::SpanKind::Internal as i32,
+            start_time_unix_nano: unix_nanos(start),
+            end_time_unix_nano: unix_nanos(end),
+            attributes: key_values(attributes),
+            dropped_attributes_count: 0,
+            events: events
+                .into_iter()
+                .map(|event| span::Event {
+                    time_unix_nano: unix_nanos(event.time),
+                    name: event.name,
+                    attributes: key_values(event.attributes),
+                    dropped_attributes_count: 0,
+                })
+                .collect(),
+            dropped_events_count: dropped_events,
+            links: Vec::new(),
+            dropped_links_count: 0,
+            status: Some(Status {
+                message: String::new(),
+                code: if error {
+                    status::StatusCode::Error
+                } else {
+                    status::StatusCode::Unset
+                } as i32,
+            }),
+        }
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+fn key_values(attributes: Vec<(&'static str, AttributeValue)>) -> Vec<KeyValue> {
+    attributes
+        .into_iter()
+        .map(|(key, value)| key_value(key, value))
+        .collect()
+}
+
+fn key_value(key: &str, value: AttributeValue) -> KeyValue {
+    let value = match value {
+        AttributeValue::Bool(value) => any_value::Value::BoolValue(value),
+        AttributeValue::Int(value) => any_value::Value::IntValue(value),
+        AttributeValue::Double(value) => any_value::Value::DoubleValue(value),
+        AttributeValue::String(value) => any_value::Value::StringValue(value),
+    };
+    KeyValue {
+        key: key.to_owned(),
+        value: Some(AnyValue { value: Some(value) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn converts_finished_spans() {
+        let start = UNIX_EPOCH + Duration::from_secs(1);
+        let span = Span::from(FinishedSpan {
+            context: TraceContext {
+                trace_id: 1,
+                span_id: 2,
+                sampled: true,
+            },
+            parent_span_id: Some(3),
+            data: Box::new(SpanData {
+                name: "sink.request".to_owned(),
+                start,
+                attributes: vec![(
+                    "component_id",
+                    AttributeValue::String("elasticsearch".to_owned()),
+                )],
+                events: vec![SpanEvent {
+                    name: "Retrying after error.".to_owned(),
+                    time: start + Duration::from_millis(10),
+                    attributes: Vec::new(),
+                }],
+                dropped_events: 0,
+                error: true,
+            }),
+            end: start + Duration::from_millis(20),
+        });
+
+        assert_eq!(span.trace_id, 1u128.to_be_bytes());
+        assert_eq!(span.span_id, 2u64.to_be_bytes());
+        assert_eq!(span.parent_span_id, 3u64.to_be_bytes());
+        assert_eq!(span.name, "sink.request");
+        assert_eq!(span.start_time_unix_nano, 1_000_000_000);
+        assert_eq!(span.end_time_unix_nano, 1_020_000_000);
+        assert_eq!(span.attributes[0].key, "component_id");
+        assert_eq!(span.events[0].name, "Retrying after error.");
+        assert_eq!(span.events[0].time_unix_nano, 1_010_000_000);
+        assert_eq!(span.status.unwrap().code, status::StatusCode::Error as i32);
+    }
+}
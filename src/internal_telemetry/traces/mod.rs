@@ -0,0 +1,334 @@
+//! Internal traces of the lifecycle of the requests of the sinks, exported over OTLP.
+//!
+//! The spans with an `otel.name` field are exported, as part of the trace of their closest
+//! exported ancestor, or of the trace whose context is given in their `trace_parent` field, so the
+//! spans of a request run by different tasks end up in the same trace. The other spans are only
+//! used for the `component_*` fields they add to the attributes of their exported descendants, and
+//! the events within them are attached to the closest exported span.
+//!
+//! Whether a trace is sampled is decided when its root span is created, and inherited by all of
+//! its spans, so the traces are either exported whole or not at all.
+
+mod exporter;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        RwLock,
+    },
+    time::SystemTime,
+};
+
+use rand::Rng;
+use tokio::sync::mpsc;
+use tracing::{Level, Span, Subscriber};
+use tracing_core::{span, Event, Field};
+use tracing_subscriber::{
+    layer::Context,
+    registry::{LookupSpan, Registry, SpanRef},
+    Layer,
+};
+use value::Value;
+use vector_common::request_metadata::TraceContext;
+use vector_core::config::TracesConfig;
+
+use self::exporter::{AttributeValue, FinishedSpan, SpanData, SpanEvent};
+use crate::trace::SpanFields;
+
+/// The maximum number of events attached to a span, the later ones being counted as dropped.
+const MAX_EVENTS_PER_SPAN: usize = 64;
+
+/// Whether the traces are configured, so the spans can be skipped as early as possible otherwise.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The exporter of the configured traces.
+static EXPORTER: RwLock<Option<Exporter>> = RwLock::new(None);
+
+/// The number of spans dropped since the last export, as the queue of the exporter was full.
+static DROPPED_SPANS: AtomicU64 = AtomicU64::new(0);
+
+struct Exporter {
+    config: TracesConfig,
+    sender: mpsc::Sender<FinishedSpan>,
+}
+
+/// Starts, replaces, or stops the exporter of the traces, as configured.
+///
+/// The spans queued by a replaced exporter are still exported, to its own endpoint.
+pub fn set_traces_config(config: Option<&TracesConfig>) {
+    let mut exporter = EXPORTER.write().expect("poisoned lock");
+    if exporter.as_ref().map(|exporter| &exporter.config) == config {
+        return;
+    }
+
+    *exporter = config.map(|config| {
+        let (sender, receiver) = mpsc::channel(config.max_queued_spans.get());
+        tokio::spawn(exporter::run(config.clone(), receiver));
+        Exporter {
+            config: config.clone(),
+            sender,
+        }
+    });
+    ENABLED.store(exporter.is_some(), Ordering::Release);
+}
+
+fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+fn sampling_ratio() -> f64 {
+    EXPORTER
+        .read()
+        .expect("poisoned lock")
+        .as_ref()
+        .map_or(0.0, |exporter| exporter.config.sampling_ratio)
+}
+
+/// Returns the trace context of the current span, to propagate it along with a request.
+///
+/// Returns `None` if the traces aren't configured, or if the current span isn't part of a trace.
+pub fn current_trace_context() -> Option<TraceContext> {
+    if !is_enabled() {
+        return None;
+    }
+
+    Span::current()
+        .with_subscriber(|(id, dispatch)| {
+            dispatch
+                .downcast_ref::<Registry>()?
+                .span(id)?
+                .scope()
+                .find_map(|span| trace_context(&span))
+        })
+        .flatten()
+}
+
+fn trace_context<R: for<'a> LookupSpan<'a>>(span: &SpanRef<'_, R>) -> Option<TraceContext> {
+    let extensions = span.extensions();
+    extensions.get::<SpanState>().map(|state| state.context)
+}
+
+/// The state of an exported span, in its extensions.
+struct SpanState {
+    context: TraceContext,
+    parent_span_id: Option<u64>,
+    /// The data of the span, only collected if its trace is sampled.
+    data: Option<Box<SpanData>>,
+}
+
+/// Creates the spans of the internal traces, and sends them to the exporter once they are closed.
+pub struct TracesLayer;
+
+impl<S> Layer<S> for TracesLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if !is_enabled() {
+            return;
+        }
+
+        let mut fields = Fields::default();
+        attrs.values().record(&mut fields);
+        let name = match fields.name.take() {
+            Some(name) => name,
+            None => return,
+        };
+
+        let span = ctx.span(id).expect("span must already exist!");
+        let parent = fields
+            .trace_parent
+            .as_deref()
+            .and_then(|trace_parent| trace_parent.parse::<TraceContext>().ok())
+            .or_else(|| {
+                span.scope()
+                    .skip(1)
+                    .find_map(|ancestor| trace_context(&ancestor))
+            });
+
+        let mut rng = rand::thread_rng();
+        let context = match parent {
+            Some(parent) => TraceContext {
+                span_id: rng.gen(),
+                ..parent
+            },
+            None => TraceContext {
+                trace_id: rng.gen(),
+                span_id: rng.gen(),
+                sampled: rng.gen::<f64>() < sampling_ratio(),
+            },
+        };
+
+        let data = context.sampled.then(|| {
+            // The fields of the components the span is running in.
+            for ancestor in span.scope().skip(1) {
+                if let Some(component) = ancestor.extensions().get::<SpanFields>() {
+                    for (key, value) in &component.0 {
+                        fields.attributes.push((*key, AttributeValue::from(value)));
+                    }
+                }
+            }
+
+            Box::new(SpanData {
+                name,
+                start: SystemTime::now(),
+                attributes: fields.attributes,
+                events: Vec::new(),
+                dropped_events: 0,
+                error: false,
+            })
+        });
+
+        span.extensions_mut().insert(SpanState {
+            context,
+            parent_span_id: parent.map(|parent| parent.span_id),
+            data,
+        });
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if !is_enabled() {
+            return;
+        }
+
+        if let Some(span) = ctx.span(id) {
+            if let Some(data) = span
+                .extensions_mut()
+                .get_mut::<SpanState>()
+                .and_then(|state| state.data.as_mut())
+            {
+                let mut fields = Fields::default();
+                values.record(&mut fields);
+                data.attributes.append(&mut fields.attributes);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if !is_enabled() {
+            return;
+        }
+
+        let span = match ctx.event_span(event) {
+            Some(span) => span,
+            None => return,
+        };
+        for span in span.scope() {
+            let mut extensions = span.extensions_mut();
+            let state = match extensions.get_mut::<SpanState>() {
+                Some(state) => state,
+                None => continue,
+            };
+
+            if let Some(data) = state.data.as_mut() {
+                let level = *event.metadata().level();
+                data.error |= level == Level::ERROR;
+                if data.events.len() < MAX_EVENTS_PER_SPAN {
+                    let mut fields = Fields::default();
+                    event.record(&mut fields);
+                    fields
+                        .attributes
+                        .push(("level", AttributeValue::String(level.to_string())));
+                    data.events.push(SpanEvent {
+                        name: fields
+                            .message
+                            .unwrap_or_else(|| event.metadata().name().to_owned()),
+                        time: SystemTime::now(),
+                        attributes: fields.attributes,
+                    });
+                } else {
+                    data.dropped_events += 1;
+                }
+            }
+            break;
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        if !is_enabled() {
+            return;
+        }
+
+        let span = match ctx.span(&id) {
+            Some(span) => span,
+            None => return,
+        };
+        let state = match span.extensions_mut().remove::<SpanState>() {
+            Some(state) => state,
+            None => return,
+        };
+        if let Some(data) = state.data {
+            let finished = FinishedSpan {
+                context: state.context,
+                parent_span_id: state.parent_span_id,
+                data,
+                end: SystemTime::now(),
+            };
+            let exporter = EXPORTER.read().expect("poisoned lock");
+            if let Some(exporter) = exporter.as_ref() {
+                // Dropped rather than slowing down the requests when the collector can't keep up.
+                if exporter.sender.try_send(finished).is_err() {
+                    DROPPED_SPANS.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
+
+/// The fields of a span or an event, as attributes.
+#[derive(Default)]
+struct Fields {
+    name: Option<String>,
+    trace_parent: Option<String>,
+    message: Option<String>,
+    attributes: Vec<(&'static str, AttributeValue)>,
+}
+
+impl Fields {
+    fn record(&mut self, field: &Field, value: AttributeValue) {
+        match field.name() {
+            "otel.name" => self.name = Some(value.into_string()),
+            "trace_parent" => self.trace_parent = Some(value.into_string()),
+            "message" => self.message = Some(value.into_string()),
+            name if name.starts_with("otel.") => {}
+            name => self.attributes.push((name, value)),
+        }
+    }
+}
+
+impl tracing::field::Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, AttributeValue::Double(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, AttributeValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, AttributeValue::Int(value as i64));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, AttributeValue::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, AttributeValue::String(value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record(field, AttributeValue::String(format!("{:?}", value)));
+    }
+}
+
+impl From<&Value> for AttributeValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Boolean(value) => Self::Bool(*value),
+            Value::Integer(value) => Self::Int(*value),
+            Value::Float(value) => Self::Double(value.into_inner()),
+            value => Self::String(value.to_string_lossy().into_owned()),
+        }
+    }
+}
@@ -1,7 +1,15 @@
 #![allow(missing_docs)]
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
-use rdkafka::{consumer::ConsumerContext, ClientConfig, ClientContext, Statistics};
+use once_cell::sync::Lazy;
+use rdkafka::{
+    consumer::{ConsumerContext, ConsumerGroupMetadata},
+    ClientConfig, ClientContext, Statistics,
+};
 use snafu::Snafu;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
@@ -165,3 +173,41 @@ impl ClientContext for KafkaStatisticsContext {
 }
 
 impl ConsumerContext for KafkaStatisticsContext {}
+
+/// The key, in the `vector` event metadata, of the offset of the message an event was consumed
+/// from by a kafka source whose offsets are committed by a kafka sink.
+pub(crate) const OFFSET_METADATA_KEY: &str = "kafka_offset";
+
+/// Returns the metadata of the consumer group of a kafka source, while it is running.
+pub(crate) type GroupMetadata = Arc<dyn Fn() -> Option<ConsumerGroupMetadata> + Send + Sync>;
+
+/// The consumer groups of the kafka sources whose offsets are committed by the transactions of a
+/// kafka sink, by the ID of the source.
+///
+/// The sources and the sinks they're linked to are checked when the configuration is loaded, see
+/// `Config::propagate_links`.
+static LINKED_SOURCES: Lazy<Mutex<HashMap<String, GroupMetadata>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Registers the consumer group of a kafka source whose offsets are committed by a kafka sink.
+///
+/// A source registered again, as it is reloaded, replaces its previous consumer group.
+#[cfg(feature = "sources-kafka")]
+pub(crate) fn register_consumer_group(source: &str, group_metadata: GroupMetadata) {
+    LINKED_SOURCES
+        .lock()
+        .expect("poisoned lock")
+        .insert(source.to_owned(), group_metadata);
+}
+
+/// Returns the metadata of the consumer group of a kafka source, if it is running and has joined
+/// its group.
+#[cfg(feature = "sinks-kafka")]
+pub(crate) fn consumer_group_metadata(source: &str) -> Option<ConsumerGroupMetadata> {
+    let group_metadata = LINKED_SOURCES
+        .lock()
+        .expect("poisoned lock")
+        .get(source)
+        .cloned()?;
+    group_metadata()
+}
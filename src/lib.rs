@@ -46,6 +46,7 @@ pub mod cli;
 #[allow(unreachable_pub)]
 pub mod components;
 pub mod conditions;
+pub mod convert_config;
 pub mod dns;
 #[cfg(feature = "docker")]
 pub mod docker;
@@ -64,6 +65,13 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs"
+))]
+pub(crate) mod azure_event_hubs;
+#[cfg(feature = "sources-file")]
+pub mod checkpoints;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
@@ -81,11 +89,25 @@ pub mod kafka;
 pub mod kubernetes;
 pub mod line_agg;
 pub mod list;
-#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
+pub mod memory_budget;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) mod mqtt;
+#[cfg(any(
+    feature = "sources-nats",
+    feature = "sources-nats_jetstream",
+    feature = "sinks-nats",
+    feature = "sinks-nats_jetstream"
+))]
 pub(crate) mod nats;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
+pub mod recording;
+#[cfg(feature = "sources-replay")]
+pub(crate) mod replay;
+pub mod runtime_options;
+#[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
+pub mod schema_registry;
 pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
@@ -197,3 +219,16 @@ pub fn num_threads() -> usize {
     };
     usize::from(count)
 }
+
+/// Returns the functions available to the VRL programs of components: those of the VRL standard
+/// library, unless Vector is built without the `vrl-stdlib` feature, of enrichment tables, and of
+/// Vector.
+pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
+    #[cfg(feature = "vrl-stdlib")]
+    let mut functions = vrl_stdlib::all();
+    #[cfg(not(feature = "vrl-stdlib"))]
+    let mut functions = Vec::new();
+    functions.append(&mut enrichment::vrl_functions());
+    functions.append(&mut vector_vrl_functions::vrl_functions());
+    functions
+}
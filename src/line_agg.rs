@@ -17,6 +17,12 @@ use regex::bytes::Regex;
 use tokio_util::time::delay_queue::{DelayQueue, Key};
 use vector_config::configurable_component;
 
+use crate::{
+    conditions::Condition,
+    event::{Event, LogEvent},
+    internal_events::{LineAggregationFlushReason, LineAggregationFlushed},
+};
+
 /// Mode of operation of the line aggregator.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -47,16 +53,45 @@ pub enum Mode {
     HaltWith,
 }
 
+/// Matches the lines that start or continue a message.
+#[derive(Clone, Debug)]
+pub enum Matcher {
+    /// Matches the lines with a regular expression.
+    Pattern(Regex),
+
+    /// Matches the lines with a VRL condition, run against a log event holding the line in its
+    /// message field.
+    Condition(Condition),
+}
+
+impl Matcher {
+    fn is_match(&self, line: &Bytes) -> bool {
+        match self {
+            Self::Pattern(pattern) => pattern.is_match(line.as_ref()),
+            Self::Condition(condition) => {
+                let event = Event::Log(LogEvent::from_bytes_legacy(line));
+                condition.check(event).0
+            }
+        }
+    }
+}
+
+impl From<Regex> for Matcher {
+    fn from(pattern: Regex) -> Self {
+        Self::Pattern(pattern)
+    }
+}
+
 /// Configuration of multi-line aggregation.
 #[derive(Clone, Debug)]
 pub struct Config {
-    /// Regular expression pattern that is used to match the start of a new message.
-    pub start_pattern: Regex,
+    /// Matcher that is used to match the start of a new message.
+    pub start_pattern: Matcher,
 
-    /// Regular expression pattern that is used to determine whether or not more lines should be read.
+    /// Matcher that is used to determine whether or not more lines should be read.
     ///
     /// This setting must be configured in conjunction with `mode`.
-    pub condition_pattern: Regex,
+    pub condition_pattern: Matcher,
 
     /// Aggregation mode.
     ///
@@ -73,7 +108,7 @@ impl Config {
     /// Build `Config` from legacy `file` source line aggregator configuration
     /// params.
     pub fn for_legacy(marker: Regex, timeout_ms: u64) -> Self {
-        let start_pattern = marker;
+        let start_pattern = Matcher::Pattern(marker);
         let condition_pattern = start_pattern.clone();
         let mode = Mode::HaltBefore;
         let timeout = Duration::from_millis(timeout_ms);
@@ -125,6 +160,9 @@ pub struct Logic<K, C> {
 
     /// A queue of key timeouts.
     timeouts: DelayQueue<K>,
+
+    /// Tells whether two line contexts come from the same underlying source.
+    same_source: Option<fn(&C, &C) -> bool>,
 }
 
 impl<K, C> Logic<K, C> {
@@ -134,8 +172,17 @@ impl<K, C> Logic<K, C> {
             config,
             buffers: HashMap::new(),
             timeouts: DelayQueue::new(),
+            same_source: None,
         }
     }
+
+    /// Flush the buffered lines of a key as soon as a line with a context from another source
+    /// comes in for it, such as when the file a key is read from is rotated.
+    #[must_use]
+    pub fn with_source_check(mut self, same_source: fn(&C, &C) -> bool) -> Self {
+        self.same_source = Some(same_source);
+        self
+    }
 }
 
 impl<T, K, C> LineAgg<T, K, C>
@@ -207,7 +254,8 @@ where
                             .buffers
                             .drain()
                             .map(|(src, (_, aggregate))| {
-                                let (line, context) = aggregate.merge();
+                                let (line, context) =
+                                    aggregate.flush(LineAggregationFlushReason::Shutdown);
                                 (src, line, context)
                             })
                             .collect(),
@@ -220,7 +268,8 @@ where
                     {
                         let key = expired_key.into_inner();
                         if let Some((_, aggregate)) = this.logic.buffers.remove(&key) {
-                            let (line, context) = aggregate.merge();
+                            let (line, context) =
+                                aggregate.flush(LineAggregationFlushReason::Timeout);
                             return Poll::Ready(Some((key, line, context)));
                         }
                     }
@@ -295,10 +344,27 @@ where
         line: Bytes,
         context: C,
     ) -> Option<(K, Emit<(Bytes, C)>)> {
+        // Flush the buffered data if the line comes from another source, and handle the line as
+        // the first one of the key.
+        let rotated = match (self.same_source, self.buffers.get(&src)) {
+            (Some(same_source), Some((_, buffered))) => !same_source(&buffered.context, &context),
+            _ => false,
+        };
+        if rotated {
+            let (key, buffered) = self.buffers.remove(&src).expect("checked to be buffered");
+            self.timeouts.remove(&key);
+            let flushed = buffered.flush(LineAggregationFlushReason::Rotation);
+            return Some(match self.handle_line(src.clone(), line, context) {
+                None => (src, Emit::One(flushed)),
+                Some((src, Emit::One(line))) => (src, Emit::Two(flushed, line)),
+                Some((_, Emit::Two(..))) => unreachable!("the first line emits at most one line"),
+            });
+        }
+
         // Check if we already have the buffered data for the source.
         match self.buffers.entry(src) {
             Entry::Occupied(mut entry) => {
-                let condition_matched = self.config.condition_pattern.is_match(line.as_ref());
+                let condition_matched = self.config.condition_pattern.is_match(&line);
                 let decision = match (self.config.mode, condition_matched) {
                     // All consecutive lines matching this pattern are included in
                     // the group.
@@ -329,18 +395,27 @@ where
                         let (src, (key, mut buffered)) = entry.remove_entry();
                         self.timeouts.remove(&key);
                         buffered.add_next_line(line, context);
-                        Some((src, Emit::One(buffered.merge())))
+                        Some((
+                            src,
+                            Emit::One(buffered.flush(LineAggregationFlushReason::Match)),
+                        ))
                     }
                     Decision::EndExclude => {
                         let (src, (key, buffered)) = entry.remove_entry();
                         self.timeouts.remove(&key);
-                        Some((src, Emit::Two(buffered.merge(), (line, context))))
+                        Some((
+                            src,
+                            Emit::Two(
+                                buffered.flush(LineAggregationFlushReason::Match),
+                                (line, context),
+                            ),
+                        ))
                     }
                 }
             }
             Entry::Vacant(entry) => {
                 // This line is a candidate for buffering, or passing through.
-                if self.config.start_pattern.is_match(line.as_ref()) {
+                if self.config.start_pattern.is_match(&line) {
                     // It was indeed a new line we need to filter.
                     // Set the timeout and buffer this line.
                     let key = self
@@ -375,6 +450,14 @@ impl<C> Aggregate<C> {
         self.lines.push(line);
     }
 
+    fn flush(self, reason: LineAggregationFlushReason) -> (Bytes, C) {
+        emit!(LineAggregationFlushed {
+            reason,
+            lines: self.lines.len(),
+        });
+        self.merge()
+    }
+
     fn merge(self) -> (Bytes, C) {
         let capacity = self.lines.iter().map(|line| line.len() + 1).sum::<usize>() - 1;
         let mut bytes_mut = BytesMut::with_capacity(capacity);
@@ -413,8 +496,8 @@ mod tests {
             " last part of the incomplete finishing message",
         ];
         let config = Config {
-            start_pattern: Regex::new("^[^\\s]").unwrap(),
-            condition_pattern: Regex::new("^[\\s]+").unwrap(),
+            start_pattern: Regex::new("^[^\\s]").unwrap().into(),
+            condition_pattern: Regex::new("^[\\s]+").unwrap().into(),
             mode: Mode::ContinueThrough,
             timeout: Duration::from_millis(10),
         };
@@ -447,8 +530,8 @@ mod tests {
             "last part of the incomplete finishing message \\",
         ];
         let config = Config {
-            start_pattern: Regex::new("\\\\$").unwrap(),
-            condition_pattern: Regex::new("\\\\$").unwrap(),
+            start_pattern: Regex::new("\\\\$").unwrap().into(),
+            condition_pattern: Regex::new("\\\\$").unwrap().into(),
             mode: Mode::ContinuePast,
             timeout: Duration::from_millis(10),
         };
@@ -484,8 +567,8 @@ mod tests {
             "last part of the incomplete finishing message",
         ];
         let config = Config {
-            start_pattern: Regex::new("").unwrap(),
-            condition_pattern: Regex::new("^(INFO|ERROR) ").unwrap(),
+            start_pattern: Regex::new("").unwrap().into(),
+            condition_pattern: Regex::new("^(INFO|ERROR) ").unwrap().into(),
             mode: Mode::HaltBefore,
             timeout: Duration::from_millis(10),
         };
@@ -521,8 +604,8 @@ mod tests {
             "last part of the incomplete finishing message",
         ];
         let config = Config {
-            start_pattern: Regex::new("[^;]$").unwrap(),
-            condition_pattern: Regex::new(";$").unwrap(),
+            start_pattern: Regex::new("[^;]$").unwrap().into(),
+            condition_pattern: Regex::new(";$").unwrap().into(),
             mode: Mode::HaltWith,
             timeout: Duration::from_millis(10),
         };
@@ -550,8 +633,8 @@ mod tests {
             "    at com.foo.baz(baz.java:456)",
         ];
         let config = Config {
-            start_pattern: Regex::new("^[^\\s]").unwrap(),
-            condition_pattern: Regex::new("^[\\s]+at").unwrap(),
+            start_pattern: Regex::new("^[^\\s]").unwrap().into(),
+            condition_pattern: Regex::new("^[\\s]+at").unwrap().into(),
             mode: Mode::ContinueThrough,
             timeout: Duration::from_millis(10),
         };
@@ -575,8 +658,8 @@ mod tests {
             "\tfrom foobar.rb:9:in `<main>'",
         ];
         let config = Config {
-            start_pattern: Regex::new("^[^\\s]").unwrap(),
-            condition_pattern: Regex::new("^[\\s]+from").unwrap(),
+            start_pattern: Regex::new("^[^\\s]").unwrap().into(),
+            condition_pattern: Regex::new("^[\\s]+from").unwrap().into(),
             mode: Mode::ContinueThrough,
             timeout: Duration::from_millis(10),
         };
@@ -612,8 +695,8 @@ mod tests {
             "not merged 6", // will be stashed
         ];
         let config = Config {
-            start_pattern: Regex::new("^\\s").unwrap(),
-            condition_pattern: Regex::new("^\\s").unwrap(),
+            start_pattern: Regex::new("^\\s").unwrap().into(),
+            condition_pattern: Regex::new("^\\s").unwrap().into(),
             mode: Mode::ContinueThrough,
             timeout: Duration::from_millis(10),
         };
@@ -650,8 +733,8 @@ mod tests {
             "START msg 5", // will be stashed
         ];
         let config = Config {
-            start_pattern: Regex::new("").unwrap(),
-            condition_pattern: Regex::new("^START ").unwrap(),
+            start_pattern: Regex::new("").unwrap().into(),
+            condition_pattern: Regex::new("^START ").unwrap().into(),
             mode: Mode::HaltBefore,
             timeout: Duration::from_millis(10),
         };
@@ -723,8 +806,8 @@ mod tests {
             lines.push(format!("line {}", i));
         }
         let config = Config {
-            start_pattern: Regex::new("").unwrap(),
-            condition_pattern: Regex::new("^START ").unwrap(),
+            start_pattern: Regex::new("").unwrap().into(),
+            condition_pattern: Regex::new("^START ").unwrap().into(),
             mode: Mode::HaltBefore,
             timeout: Duration::from_millis(10),
         };
@@ -757,6 +840,81 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn mode_continue_through_vrl_condition() {
+        use crate::conditions::{ConditionalConfig, VrlConfig};
+
+        let condition = |source: &str| {
+            let condition = VrlConfig {
+                source: source.to_owned(),
+                ..Default::default()
+            }
+            .build(&Default::default())
+            .unwrap();
+            Matcher::Condition(condition)
+        };
+        let lines = vec![
+            "some usual line",
+            "java.lang.Exception: failure",
+            "    at com.example.Foo.bar(Foo.java:10)",
+            "    at com.example.Foo.main(Foo.java:5)",
+            "another normal message",
+        ];
+        let config = Config {
+            start_pattern: condition(r#"!starts_with(string!(.message), " ")"#),
+            condition_pattern: condition(r#"starts_with(string!(.message), "    at ")"#),
+            mode: Mode::ContinueThrough,
+            timeout: Duration::from_millis(10),
+        };
+        let expected = vec![
+            ("some usual line", 0),
+            (
+                concat!(
+                    "java.lang.Exception: failure\n",
+                    "    at com.example.Foo.bar(Foo.java:10)\n",
+                    "    at com.example.Foo.main(Foo.java:5)"
+                ),
+                3,
+            ),
+            ("another normal message", 4),
+        ];
+        run_and_assert(&lines, config, &expected).await;
+    }
+
+    #[tokio::test]
+    async fn flushes_on_source_change() {
+        let lines = vec![
+            ("first part", 0),
+            (" second part", 1),
+            // The next lines come from a new file, once the first one was rotated.
+            (" orphan part", 100),
+            ("new first part", 101),
+            (" new second part", 102),
+        ];
+        let config = Config {
+            start_pattern: Regex::new("^[^\\s]").unwrap().into(),
+            condition_pattern: Regex::new("^[\\s]+").unwrap().into(),
+            mode: Mode::ContinueThrough,
+            timeout: Duration::from_millis(10),
+        };
+        let stream = futures::stream::iter(lines.into_iter().map(|(line, index)| {
+            (
+                "test.log".to_owned(),
+                Bytes::from_static(line.as_bytes()),
+                index,
+            )
+        }));
+        let logic = Logic::new(config).with_source_check(|a, b| a / 100 == b / 100);
+        let results = LineAgg::new(stream, logic).collect().await;
+
+        let expected = vec![
+            (concat!("first part\n", " second part"), 1),
+            (" orphan part", 100),
+            (concat!("new first part\n", " new second part"), 102),
+        ];
+        assert_results(results, &expected);
+    }
+
     // Test helpers.
 
     /// Private type alias to be more expressive in the internal implementation.
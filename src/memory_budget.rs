@@ -0,0 +1,478 @@
+//! Accounting of the memory used by the events in flight, and enforcement of the global memory
+//! budget.
+//!
+//! Components register themselves when they are built, and count the bytes they hold in the
+//! returned [`ComponentMemory`]. The sources acquire the bytes of the events they send from the
+//! budget, so that they are backpressured, or their events dropped, once it's used up. The bytes
+//! are released once the events are finalized, that is once the sinks deliver them, or once they
+//! are written to a disk buffer.
+//!
+//! Events are only accounted for while a budget is configured, as tracking their finalization
+//! isn't free.
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock, Weak,
+    },
+};
+
+use once_cell::sync::Lazy;
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+use vector_common::{
+    config::ComponentKey,
+    finalization::{AddBatchNotifier, BatchNotifier},
+};
+use vector_core::{
+    config::{MemoryBudgetConfig, MemoryBudgetPolicy},
+    event::{EventArray, EventContainer},
+    ByteSizeOf,
+};
+
+use crate::internal_events::{
+    MemoryBudgetEventsDropped, MemoryBudgetThrottled, MemoryBudgetUsedBytes,
+};
+
+/// The budget shared by the sources, if one is configured.
+static BUDGET: Lazy<RwLock<Option<Arc<Budget>>>> = Lazy::new(RwLock::default);
+
+/// The memory accounting of the components, by component.
+static COMPONENTS: Lazy<Mutex<HashMap<ComponentKey, Registered>>> = Lazy::new(Default::default);
+
+struct Registered {
+    kind: &'static str,
+    usage: Weak<Usage>,
+}
+
+struct Budget {
+    config: MemoryBudgetConfig,
+    /// The bytes that can still be used, out of `limit`.
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+    /// Held while acquiring more bytes than a semaphore hands out at once, so that two such
+    /// acquisitions can't deadlock each holding part of the bytes the other waits for.
+    chunked: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Budget {
+    fn new(config: MemoryBudgetConfig) -> Self {
+        let limit = config.max_bytes.get().min(Semaphore::MAX_PERMITS);
+        Self {
+            config,
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+            chunked: Arc::default(),
+        }
+    }
+
+    fn used_bytes(&self) -> usize {
+        self.limit - self.semaphore.available_permits()
+    }
+
+    /// Acquires `bytes` from the budget if they're available right away.
+    fn try_acquire(&self, bytes: usize) -> Option<OwnedSemaphorePermit> {
+        let mut chunks = chunks(bytes);
+        let mut permit = Arc::clone(&self.semaphore)
+            .try_acquire_many_owned(chunks.next()?)
+            .ok()?;
+        for chunk in chunks {
+            // The chunks acquired so far are released if the rest aren't available.
+            permit.merge(
+                Arc::clone(&self.semaphore)
+                    .try_acquire_many_owned(chunk)
+                    .ok()?,
+            );
+        }
+        Some(permit)
+    }
+
+    /// Acquires `bytes` from the budget, waiting for them to be released if needed.
+    async fn acquire(&self, bytes: usize) -> OwnedSemaphorePermit {
+        let _chunked = match u32::try_from(bytes) {
+            Ok(_) => None,
+            Err(_) => Some(self.chunked.lock().await),
+        };
+        let mut permit: Option<OwnedSemaphorePermit> = None;
+        for chunk in chunks(bytes) {
+            let acquired = Arc::clone(&self.semaphore)
+                .acquire_many_owned(chunk)
+                .await
+                .expect("memory budget semaphore is never closed");
+            match &mut permit {
+                Some(permit) => permit.merge(acquired),
+                None => permit = Some(acquired),
+            }
+        }
+        permit.expect("there's always a first chunk")
+    }
+}
+
+/// Splits `bytes` into the numbers of permits a semaphore hands out at once, of which there's
+/// always at least one.
+fn chunks(bytes: usize) -> impl Iterator<Item = u32> {
+    let mut remaining = Some(bytes);
+    std::iter::from_fn(move || {
+        let bytes = remaining?;
+        let chunk = u32::try_from(bytes).unwrap_or(u32::MAX);
+        remaining = Some(bytes - chunk as usize).filter(|rest| *rest > 0);
+        Some(chunk)
+    })
+}
+
+/// Sets the memory budget shared by the sources.
+///
+/// The bytes in use are kept if the size of the budget didn't change, such as when reloading.
+pub fn set_memory_budget(config: Option<MemoryBudgetConfig>) {
+    let mut budget = BUDGET.write().expect("memory budget lock poisoned");
+    *budget = updated_budget(budget.take(), config);
+}
+
+fn updated_budget(
+    current: Option<Arc<Budget>>,
+    config: Option<MemoryBudgetConfig>,
+) -> Option<Arc<Budget>> {
+    match (current, config) {
+        (Some(current), Some(config)) if current.config.max_bytes == config.max_bytes => {
+            Some(Arc::new(Budget {
+                config,
+                semaphore: Arc::clone(&current.semaphore),
+                limit: current.limit,
+                chunked: Arc::clone(&current.chunked),
+            }))
+        }
+        // Events in flight keep the permits of the previous budget, and release them there.
+        (_, config) => config.map(|config| Arc::new(Budget::new(config))),
+    }
+}
+
+fn budget() -> Option<Arc<Budget>> {
+    BUDGET
+        .read()
+        .expect("memory budget lock poisoned")
+        .as_ref()
+        .map(Arc::clone)
+}
+
+/// What the bytes held by a component are used for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MemoryUsage {
+    /// Events sent by a source which aren't finalized yet, wherever they currently are.
+    InFlight,
+    /// Events read by a sink from its buffer which aren't finalized yet, such as those waiting in
+    /// a batch, or in a request being sent.
+    Batched,
+    /// State held by a transform, such as the metrics it aggregates.
+    State,
+}
+
+#[derive(Debug, Default)]
+struct Usage {
+    in_flight: AtomicUsize,
+    batched: AtomicUsize,
+    state: AtomicUsize,
+}
+
+impl Usage {
+    const fn get(&self, usage: MemoryUsage) -> &AtomicUsize {
+        match usage {
+            MemoryUsage::InFlight => &self.in_flight,
+            MemoryUsage::Batched => &self.batched,
+            MemoryUsage::State => &self.state,
+        }
+    }
+}
+
+/// The memory accounting of a component.
+#[derive(Clone, Debug)]
+pub struct ComponentMemory(Arc<Usage>);
+
+/// Registers the memory accounting of the component of the given kind.
+///
+/// The accounting is shared with the previous instance of the component if it's still running,
+/// such as while the configuration is reloaded. It is unregistered once the returned value, and
+/// all of its clones, are dropped.
+pub fn register(component_key: &ComponentKey, kind: &'static str) -> ComponentMemory {
+    let mut components = COMPONENTS.lock().expect("memory accounting lock poisoned");
+    if let Some(usage) = components
+        .get(component_key)
+        .and_then(|registered| registered.usage.upgrade())
+    {
+        return ComponentMemory(usage);
+    }
+
+    // Take the chance to forget the components that stopped since.
+    components.retain(|_, registered| registered.usage.strong_count() > 0);
+    let usage = Arc::default();
+    components.insert(
+        component_key.clone(),
+        Registered {
+            kind,
+            usage: Arc::downgrade(&usage),
+        },
+    );
+    ComponentMemory(usage)
+}
+
+impl ComponentMemory {
+    /// Sets the bytes used by the component for `usage`.
+    pub fn set(&self, usage: MemoryUsage, bytes: usize) {
+        self.0.get(usage).store(bytes, Ordering::Relaxed);
+    }
+
+    /// Counts the bytes of the events as used by the component for `usage`, until they are
+    /// finalized.
+    pub fn track(&self, usage: MemoryUsage, events: &mut EventArray) {
+        if budget().is_some() {
+            self.track_until_finalized(usage, events.size_of(), None, events);
+        }
+    }
+
+    /// Acquires the bytes of the events sent by a source from the budget, waiting for them to be
+    /// released by the events in flight if needed.
+    ///
+    /// Returns `false` if the events were dropped instead, according to the policy of the budget.
+    /// The bytes are released once the events are finalized.
+    pub(crate) async fn acquire(&self, events: &mut EventArray) -> bool {
+        self.acquire_from(budget(), events).await
+    }
+
+    async fn acquire_from(&self, budget: Option<Arc<Budget>>, events: &mut EventArray) -> bool {
+        let budget = match budget {
+            Some(budget) => budget,
+            None => return true,
+        };
+
+        let size = events.size_of();
+        // Events larger than the whole budget are let through once it's not used anymore.
+        let bytes = size.min(budget.limit);
+        let permit = match budget.try_acquire(bytes) {
+            Some(permit) => permit,
+            None => match budget.config.policy {
+                MemoryBudgetPolicy::Block => {
+                    let start = Instant::now();
+                    let permit = budget.acquire(bytes).await;
+                    emit!(MemoryBudgetThrottled {
+                        duration: start.elapsed(),
+                    });
+                    permit
+                }
+                MemoryBudgetPolicy::DropNewest => {
+                    emit!(MemoryBudgetEventsDropped {
+                        count: events.len(),
+                    });
+                    return false;
+                }
+            },
+        };
+
+        self.track_until_finalized(MemoryUsage::InFlight, size, Some((budget, permit)), events);
+        true
+    }
+
+    fn track_until_finalized(
+        &self,
+        usage: MemoryUsage,
+        bytes: usize,
+        acquired: Option<(Arc<Budget>, OwnedSemaphorePermit)>,
+        events: &mut EventArray,
+    ) {
+        self.0.get(usage).fetch_add(bytes, Ordering::Relaxed);
+        if let Some((budget, _)) = &acquired {
+            // The budget is shared by all of the sources, so it's reported outside of the span of
+            // this one.
+            tracing::Span::none().in_scope(|| {
+                emit!(MemoryBudgetUsedBytes {
+                    bytes: budget.used_bytes(),
+                })
+            });
+        }
+
+        let memory = Arc::clone(&self.0);
+        events.add_batch_notifier(BatchNotifier::new_with_callback(move |_| {
+            memory.get(usage).fetch_sub(bytes, Ordering::Relaxed);
+            if let Some((budget, permit)) = acquired {
+                drop(permit);
+                tracing::Span::none().in_scope(|| {
+                    emit!(MemoryBudgetUsedBytes {
+                        bytes: budget.used_bytes(),
+                    })
+                });
+            }
+        }));
+    }
+}
+
+/// The current usage of the memory budget.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BudgetUsage {
+    /// The size of the budget.
+    pub max_bytes: usize,
+    /// The bytes of the events in flight.
+    pub used_bytes: usize,
+    /// The policy applied once the budget is used up.
+    pub policy: MemoryBudgetPolicy,
+}
+
+/// Returns the current usage of the memory budget, if one is configured.
+pub fn budget_usage() -> Option<BudgetUsage> {
+    budget().map(|budget| BudgetUsage {
+        max_bytes: budget.config.max_bytes.get(),
+        used_bytes: budget.used_bytes(),
+        policy: budget.config.policy,
+    })
+}
+
+/// The bytes currently used by a running component.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComponentUsage {
+    /// The component the bytes are used by.
+    pub component_key: ComponentKey,
+    /// The kind of the component, such as `source`.
+    pub kind: &'static str,
+    /// The bytes of the events sent by the component which aren't finalized yet.
+    pub in_flight_bytes: usize,
+    /// The bytes of the events batched by the component which aren't finalized yet.
+    pub batched_bytes: usize,
+    /// The bytes of the state held by the component.
+    pub state_bytes: usize,
+}
+
+/// Returns the bytes currently used by the running components.
+pub fn component_usage() -> Vec<ComponentUsage> {
+    let components = COMPONENTS.lock().expect("memory accounting lock poisoned");
+    let mut usage = components
+        .iter()
+        .filter_map(|(component_key, registered)| {
+            let usage = registered.usage.upgrade()?;
+            Some(ComponentUsage {
+                component_key: component_key.clone(),
+                kind: registered.kind,
+                in_flight_bytes: usage.in_flight.load(Ordering::Relaxed),
+                batched_bytes: usage.batched.load(Ordering::Relaxed),
+                state_bytes: usage.state.load(Ordering::Relaxed),
+            })
+        })
+        .collect::<Vec<_>>();
+    usage.sort_by(|a, b| a.component_key.id().cmp(b.component_key.id()));
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn events(count: usize) -> EventArray {
+        let logs = (0..count)
+            .map(|i| LogEvent::from(format!("event {i}")))
+            .collect::<Vec<_>>();
+        logs.into()
+    }
+
+    fn budget_of(count: usize, policy: MemoryBudgetPolicy) -> Option<Arc<Budget>> {
+        let config = MemoryBudgetConfig {
+            max_bytes: NonZeroUsize::new(events(1).size_of() * count).unwrap(),
+            policy,
+        };
+        Some(Arc::new(Budget::new(config)))
+    }
+
+    #[tokio::test]
+    async fn blocks_once_budget_is_used_up() {
+        let memory = ComponentMemory(Arc::default());
+        let budget = budget_of(2, MemoryBudgetPolicy::Block);
+
+        let size = events(1).size_of();
+        let mut first = events(1);
+        let mut second = events(1);
+        assert!(memory.acquire_from(budget.clone(), &mut first).await);
+        assert!(memory.acquire_from(budget.clone(), &mut second).await);
+        assert_eq!(memory.0.in_flight.load(Ordering::Relaxed), size * 2);
+
+        let blocked = {
+            let memory = memory.clone();
+            let budget = budget.clone();
+            tokio::spawn(async move { memory.acquire_from(budget, &mut events(1)).await })
+        };
+        tokio::task::yield_now().await;
+        assert!(!blocked.is_finished());
+
+        // Finalizing the events releases their bytes.
+        drop(first);
+        let sent = tokio::time::timeout(std::time::Duration::from_secs(5), blocked)
+            .await
+            .expect("the blocked events should be let through")
+            .unwrap();
+        assert!(sent);
+    }
+
+    #[tokio::test]
+    async fn drops_once_budget_is_used_up() {
+        let memory = ComponentMemory(Arc::default());
+        let budget = budget_of(1, MemoryBudgetPolicy::Block);
+
+        let size = events(1).size_of();
+        let mut first = events(1);
+        assert!(memory.acquire_from(budget.clone(), &mut first).await);
+
+        // Changing the policy keeps the bytes in use.
+        let config = MemoryBudgetConfig {
+            policy: MemoryBudgetPolicy::DropNewest,
+            ..budget.as_ref().unwrap().config
+        };
+        let budget = updated_budget(budget, Some(config));
+        assert_eq!(budget.as_ref().unwrap().used_bytes(), size);
+        assert!(!memory.acquire_from(budget, &mut events(1)).await);
+
+        assert!(memory.acquire_from(None, &mut events(1)).await);
+        assert_eq!(memory.0.in_flight.load(Ordering::Relaxed), size);
+        drop(first);
+    }
+
+    #[tokio::test]
+    async fn acquires_more_bytes_than_a_semaphore_hands_out_at_once() {
+        let bytes = u32::MAX as usize + 10;
+        let budget = Budget::new(MemoryBudgetConfig {
+            max_bytes: NonZeroUsize::new(bytes * 2).unwrap(),
+            policy: MemoryBudgetPolicy::Block,
+        });
+
+        let first = budget.try_acquire(bytes).unwrap();
+        assert_eq!(budget.used_bytes(), bytes);
+        // The bytes acquired before the rest turn out to be missing are released.
+        assert!(budget.try_acquire(bytes + 1).is_none());
+        assert_eq!(budget.used_bytes(), bytes);
+
+        let second = budget.acquire(bytes).await;
+        assert_eq!(budget.used_bytes(), bytes * 2);
+        drop((first, second));
+        assert_eq!(budget.used_bytes(), 0);
+    }
+
+    #[test]
+    fn forgets_dropped_components() {
+        let key = ComponentKey::from("memory_budget_transform");
+        let memory = register(&key, "transform");
+        memory.set(MemoryUsage::State, 100);
+
+        // A new instance of the component shares the accounting of the running one.
+        let rebuilt = register(&key, "transform");
+        assert!(Arc::ptr_eq(&memory.0, &rebuilt.0));
+        let usage = component_usage()
+            .into_iter()
+            .find(|usage| usage.component_key == key)
+            .unwrap();
+        assert_eq!(usage.state_bytes, 100);
+
+        drop((memory, rebuilt));
+        assert!(!component_usage()
+            .iter()
+            .any(|usage| usage.component_key == key));
+    }
+}
@@ -0,0 +1,446 @@
+//! Functionality shared by the `mqtt` source and sink.
+//!
+//! Both MQTT 3.1.1 and MQTT 5 are supported, through the two clients of `rumqttc`. The types in
+//! this module hide which one is used behind a single interface.
+use std::{borrow::Cow, time::Duration};
+
+use bytes::Bytes;
+use rumqttc::{TlsConfiguration, Transport};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::tls::{MaybeTlsSettings, TlsEnableableConfig, TlsError};
+
+/// The number of requests, such as publishes and acknowledgements, queued for the event loop.
+const REQUEST_CAPACITY: usize = 1024;
+
+#[derive(Debug, Snafu)]
+pub(crate) enum MqttConfigError {
+    #[snafu(display("MQTT TLS Config Error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("MQTT Config Error: `user` and `password` must be set together"))]
+    IncompleteCredentials,
+    #[snafu(display(
+        "MQTT Config Error: `client_id` is required when `clean_session` is disabled"
+    ))]
+    MissingClientId,
+}
+
+#[derive(Debug, Snafu)]
+pub(crate) enum MqttError {
+    #[snafu(display("MQTT connection error: {}", source))]
+    ConnectionV3 { source: rumqttc::ConnectionError },
+    #[snafu(display("MQTT connection error: {}", source))]
+    ConnectionV5 {
+        source: rumqttc::v5::ConnectionError,
+    },
+    #[snafu(display("MQTT client error: {}", source))]
+    ClientV3 { source: rumqttc::ClientError },
+    #[snafu(display("MQTT client error: {}", source))]
+    ClientV5 { source: rumqttc::v5::ClientError },
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("The broker refused the connection: {}", reason))]
+    Refused { reason: String },
+}
+
+/// The version of the MQTT protocol to use.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum MqttProtocolVersion {
+    /// MQTT 3.1.1.
+    #[default]
+    #[serde(rename = "3.1.1")]
+    V3_1_1,
+
+    /// MQTT 5.
+    #[serde(rename = "5")]
+    V5,
+}
+
+/// The quality of service of the messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MqttQos {
+    /// QoS 0: messages are delivered at most once, and are lost if the connection drops.
+    AtMostOnce,
+
+    /// QoS 1: messages are acknowledged by their receiver, and delivered again until they are.
+    #[default]
+    AtLeastOnce,
+}
+
+impl From<MqttQos> for rumqttc::QoS {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => Self::AtMostOnce,
+            MqttQos::AtLeastOnce => Self::AtLeastOnce,
+        }
+    }
+}
+
+impl From<MqttQos> for rumqttc::v5::mqttbytes::QoS {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => Self::AtMostOnce,
+            MqttQos::AtLeastOnce => Self::AtLeastOnce,
+        }
+    }
+}
+
+/// Connection options for MQTT brokers.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub(crate) struct MqttConnectionConfig {
+    /// The host name, or IP address, of the MQTT broker.
+    #[configurable(metadata(docs::examples = "mqtt.example.com"))]
+    #[configurable(metadata(docs::examples = "127.0.0.1"))]
+    pub(crate) host: String,
+
+    /// The port of the MQTT broker.
+    #[serde(default = "default_port")]
+    pub(crate) port: u16,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) protocol_version: MqttProtocolVersion,
+
+    /// The client identifier of the connection.
+    ///
+    /// The identifier must be unique among the clients of the broker. By default, a random
+    /// identifier is generated each time Vector connects, so it is required to resume a persistent
+    /// session.
+    #[configurable(metadata(docs::examples = "vector-gateway-1"))]
+    pub(crate) client_id: Option<String>,
+
+    /// The user name to authenticate with.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub(crate) user: Option<String>,
+
+    /// The password to authenticate with.
+    #[configurable(metadata(docs::examples = "${MQTT_PASSWORD}"))]
+    pub(crate) password: Option<SensitiveString>,
+
+    /// The interval at which the broker is pinged when the connection is idle, in seconds.
+    #[serde(default = "default_keep_alive_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(crate) keep_alive_secs: u16,
+
+    /// Whether to start a new session each time Vector connects.
+    ///
+    /// When disabled, the broker keeps the session of Vector while it is disconnected, including
+    /// its subscriptions and the QoS 1 messages not yet acknowledged, and Vector resumes it when it
+    /// connects again. This requires `client_id` to be set.
+    #[serde(default = "crate::serde::default_true")]
+    pub(crate) clean_session: bool,
+
+    #[configurable(derived)]
+    pub(crate) tls: Option<TlsEnableableConfig>,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive_secs() -> u16 {
+    60
+}
+
+impl MqttConnectionConfig {
+    /// Creates the client of the broker, along with the event loop driving its connection.
+    ///
+    /// The connection is only established once the event loop is polled, and it is established
+    /// again by polling the event loop after a connection error.
+    pub(crate) fn client(
+        &self,
+        manual_acks: bool,
+    ) -> Result<(MqttClient, MqttEventLoop), MqttConfigError> {
+        let client_id = match &self.client_id {
+            Some(client_id) => client_id.clone(),
+            None if self.clean_session => format!("vector-{}", uuid::Uuid::new_v4()),
+            None => return Err(MqttConfigError::MissingClientId),
+        };
+        let credentials = match (&self.user, &self.password) {
+            (Some(user), Some(password)) => Some((user.clone(), password.inner().to_owned())),
+            (None, None) => None,
+            _ => return Err(MqttConfigError::IncompleteCredentials),
+        };
+        let transport = self.transport()?;
+        let keep_alive = Duration::from_secs(self.keep_alive_secs.into());
+
+        Ok(match self.protocol_version {
+            MqttProtocolVersion::V3_1_1 => {
+                let mut options = rumqttc::MqttOptions::new(client_id, &self.host, self.port);
+                options
+                    .set_keep_alive(keep_alive)
+                    .set_clean_session(self.clean_session)
+                    .set_manual_acks(manual_acks)
+                    .set_transport(transport);
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                let (client, eventloop) = rumqttc::AsyncClient::new(options, REQUEST_CAPACITY);
+                (MqttClient::V3(client), MqttEventLoop::V3(eventloop))
+            }
+            MqttProtocolVersion::V5 => {
+                let mut options = rumqttc::v5::MqttOptions::new(client_id, &self.host, self.port);
+                options
+                    .set_keep_alive(keep_alive)
+                    .set_clean_start(self.clean_session)
+                    .set_manual_acks(manual_acks)
+                    .set_transport(transport);
+                if !self.clean_session {
+                    // Unlike MQTT 3.1.1, sessions end with the connection unless they are given an
+                    // expiry interval, and this one means that the session never expires.
+                    options.set_session_expiry_interval(Some(u32::MAX));
+                }
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                let (client, eventloop) = rumqttc::v5::AsyncClient::new(options, REQUEST_CAPACITY);
+                (MqttClient::V5(client), MqttEventLoop::V5(eventloop))
+            }
+        })
+    }
+
+    /// Checks that the broker accepts connections, with a new client and a clean session.
+    pub(crate) async fn healthcheck(&self) -> crate::Result<()> {
+        let mut connection = self.clone();
+        // Connecting with the identifier of the component would take over its session.
+        connection.client_id = None;
+        connection.clean_session = true;
+        let (client, mut eventloop) = connection.client(false)?;
+
+        let result = loop {
+            match eventloop.poll().await {
+                Ok(MqttEvent::Connected { .. }) => break Ok(()),
+                Ok(_) => {}
+                Err(error) => {
+                    break Err(HealthcheckError::Refused {
+                        reason: error.to_string(),
+                    }
+                    .into())
+                }
+            }
+        };
+        let _ = client.disconnect().await;
+        result
+    }
+
+    fn transport(&self) -> Result<Transport, MqttConfigError> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        let tls = match tls.tls() {
+            Some(tls) => tls,
+            None => return Ok(Transport::Tcp),
+        };
+
+        let ca = tls.authorities_pem().flatten().collect::<Vec<u8>>();
+        let client_auth = tls.identity_pem();
+        if ca.is_empty() && client_auth.is_none() {
+            // Verify the broker against the certificates of the system.
+            return Ok(Transport::tls_with_default_config());
+        }
+        Ok(Transport::tls_with_config(TlsConfiguration::Simple {
+            ca,
+            alpn: None,
+            client_auth,
+        }))
+    }
+}
+
+/// The client of an MQTT broker, used to send requests through its event loop.
+#[derive(Clone)]
+pub(crate) enum MqttClient {
+    V3(rumqttc::AsyncClient),
+    V5(rumqttc::v5::AsyncClient),
+}
+
+impl MqttClient {
+    pub(crate) async fn subscribe(&self, topics: &[String], qos: MqttQos) -> Result<(), MqttError> {
+        for topic in topics {
+            match self {
+                Self::V3(client) => client
+                    .subscribe(topic, qos.into())
+                    .await
+                    .context(ClientV3Snafu)?,
+                Self::V5(client) => client
+                    .subscribe(topic, qos.into())
+                    .await
+                    .context(ClientV5Snafu)?,
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn publish(
+        &self,
+        topic: String,
+        qos: MqttQos,
+        retain: bool,
+        payload: Vec<u8>,
+    ) -> Result<(), MqttError> {
+        match self {
+            Self::V3(client) => client
+                .publish(topic, qos.into(), retain, payload)
+                .await
+                .context(ClientV3Snafu),
+            Self::V5(client) => client
+                .publish(topic, qos.into(), retain, payload)
+                .await
+                .context(ClientV5Snafu),
+        }
+    }
+
+    /// Acknowledges a message received with manual acknowledgements enabled.
+    ///
+    /// This doesn't wait for the event loop to take the request, so that it can be called from the
+    /// task polling the event loop.
+    pub(crate) fn try_ack(&self, publish: &MqttPublish) -> Result<(), MqttError> {
+        match (self, publish) {
+            (Self::V3(client), MqttPublish::V3(publish)) => {
+                client.try_ack(publish).context(ClientV3Snafu)
+            }
+            (Self::V5(client), MqttPublish::V5(publish)) => {
+                client.try_ack(publish).context(ClientV5Snafu)
+            }
+            _ => unreachable!("messages are received with the version of their client"),
+        }
+    }
+
+    pub(crate) async fn disconnect(&self) -> Result<(), MqttError> {
+        match self {
+            Self::V3(client) => client.disconnect().await.context(ClientV3Snafu),
+            Self::V5(client) => client.disconnect().await.context(ClientV5Snafu),
+        }
+    }
+}
+
+/// The event loop driving the connection to an MQTT broker.
+pub(crate) enum MqttEventLoop {
+    V3(rumqttc::EventLoop),
+    V5(rumqttc::v5::EventLoop),
+}
+
+/// What happened on the connection, as far as the MQTT components are concerned.
+pub(crate) enum MqttEvent {
+    /// The connection was established, resuming the previous session if the broker kept it.
+    Connected {
+        session_present: bool,
+    },
+    /// A message was received on one of the subscriptions.
+    Publish(MqttPublish),
+    /// The client disconnected from the broker.
+    Disconnected,
+    Other,
+}
+
+impl MqttEventLoop {
+    pub(crate) async fn poll(&mut self) -> Result<MqttEvent, MqttError> {
+        use rumqttc::v5::mqttbytes::v5::Packet as PacketV5;
+
+        Ok(match self {
+            Self::V3(eventloop) => match eventloop.poll().await.context(ConnectionV3Snafu)? {
+                rumqttc::Event::Incoming(rumqttc::Packet::ConnAck(connack)) => {
+                    MqttEvent::Connected {
+                        session_present: connack.session_present,
+                    }
+                }
+                rumqttc::Event::Incoming(rumqttc::Packet::Publish(publish)) => {
+                    MqttEvent::Publish(MqttPublish::V3(publish))
+                }
+                rumqttc::Event::Outgoing(rumqttc::Outgoing::Disconnect) => MqttEvent::Disconnected,
+                _ => MqttEvent::Other,
+            },
+            Self::V5(eventloop) => match eventloop.poll().await.context(ConnectionV5Snafu)? {
+                rumqttc::v5::Event::Incoming(PacketV5::ConnAck(connack)) => MqttEvent::Connected {
+                    session_present: connack.session_present,
+                },
+                rumqttc::v5::Event::Incoming(PacketV5::Publish(publish)) => {
+                    MqttEvent::Publish(MqttPublish::V5(publish))
+                }
+                rumqttc::v5::Event::Outgoing(rumqttc::Outgoing::Disconnect) => {
+                    MqttEvent::Disconnected
+                }
+                _ => MqttEvent::Other,
+            },
+        })
+    }
+}
+
+/// A message received from the broker.
+pub(crate) enum MqttPublish {
+    V3(rumqttc::Publish),
+    V5(rumqttc::v5::mqttbytes::v5::Publish),
+}
+
+impl MqttPublish {
+    pub(crate) fn topic(&self) -> Cow<'_, str> {
+        match self {
+            Self::V3(publish) => Cow::Borrowed(publish.topic.as_str()),
+            Self::V5(publish) => String::from_utf8_lossy(&publish.topic),
+        }
+    }
+
+    pub(crate) fn payload(&self) -> &Bytes {
+        match self {
+            Self::V3(publish) => &publish.payload,
+            Self::V5(publish) => &publish.payload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_config(s: &str) -> MqttConnectionConfig {
+        toml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parses_protocol_version() {
+        let config = parse_config(r#"host = "localhost""#);
+        assert_eq!(config.protocol_version, MqttProtocolVersion::V3_1_1);
+        assert_eq!(config.port, 1883);
+
+        let config = parse_config(
+            r#"
+            host = "localhost"
+            protocol_version = "5"
+            "#,
+        );
+        assert_eq!(config.protocol_version, MqttProtocolVersion::V5);
+    }
+
+    #[test]
+    fn persistent_session_requires_client_id() {
+        let config = parse_config(
+            r#"
+            host = "localhost"
+            clean_session = false
+            "#,
+        );
+        assert!(matches!(
+            config.client(false),
+            Err(MqttConfigError::MissingClientId)
+        ));
+    }
+
+    #[test]
+    fn credentials_must_be_complete() {
+        let config = parse_config(
+            r#"
+            host = "localhost"
+            user = "vector"
+            "#,
+        );
+        assert!(matches!(
+            config.client(false),
+            Err(MqttConfigError::IncompleteCredentials)
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use nkeys::error::Error as NKeysError;
 use snafu::{ResultExt, Snafu};
 use vector_common::sensitive_string::SensitiveString;
@@ -114,42 +116,115 @@ pub(crate) struct NatsAuthNKey {
     pub(crate) seed: String,
 }
 
+/// The connection options of a NATS client.
+///
+/// Core NATS is used through the asynchronous client, while JetStream is only supported by the
+/// blocking one, whose options are built the same way.
+pub(crate) trait NatsOptions: Sized {
+    fn new() -> Self;
+
+    fn with_user_pass(user: &str, password: &str) -> Self;
+
+    fn with_credentials(path: &str) -> Self;
+
+    fn with_nkey(nkey: &str, sign: impl Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static) -> Self;
+
+    fn with_token(token: &str) -> Self;
+
+    fn with_name(self, name: &str) -> Self;
+
+    fn reconnect_buffer_size(self, size: usize) -> Self;
+
+    fn tls_required(self, tls_required: bool) -> Self;
+
+    fn add_root_certificate(self, path: &Path) -> Self;
+
+    fn client_cert(self, cert: &Path, key: &Path) -> Self;
+}
+
+macro_rules! impl_nats_options {
+    ($options:ty) => {
+        impl NatsOptions for $options {
+            fn new() -> Self {
+                <$options>::new()
+            }
+
+            fn with_user_pass(user: &str, password: &str) -> Self {
+                <$options>::with_user_pass(user, password)
+            }
+
+            fn with_credentials(path: &str) -> Self {
+                <$options>::with_credentials(path)
+            }
+
+            fn with_nkey(
+                nkey: &str,
+                sign: impl Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static,
+            ) -> Self {
+                <$options>::with_nkey(nkey, sign)
+            }
+
+            fn with_token(token: &str) -> Self {
+                <$options>::with_token(token)
+            }
+
+            fn with_name(self, name: &str) -> Self {
+                <$options>::with_name(self, name)
+            }
+
+            fn reconnect_buffer_size(self, size: usize) -> Self {
+                <$options>::reconnect_buffer_size(self, size)
+            }
+
+            fn tls_required(self, tls_required: bool) -> Self {
+                <$options>::tls_required(self, tls_required)
+            }
+
+            fn add_root_certificate(self, path: &Path) -> Self {
+                <$options>::add_root_certificate(self, path)
+            }
+
+            fn client_cert(self, cert: &Path, key: &Path) -> Self {
+                <$options>::client_cert(self, cert, key)
+            }
+        }
+    };
+}
+
+impl_nats_options!(nats::asynk::Options);
+#[cfg(any(feature = "sources-nats_jetstream", feature = "sinks-nats_jetstream"))]
+impl_nats_options!(nats::Options);
+
 impl NatsAuthConfig {
-    pub(crate) fn to_nats_options(&self) -> Result<nats::asynk::Options, NatsConfigError> {
+    pub(crate) fn to_nats_options<O: NatsOptions>(&self) -> Result<O, NatsConfigError> {
         match self {
-            NatsAuthConfig::UserPassword { user_password } => {
-                Ok(nats::asynk::Options::with_user_pass(
-                    user_password.user.as_str(),
-                    user_password.password.inner(),
-                ))
+            NatsAuthConfig::UserPassword { user_password } => Ok(O::with_user_pass(
+                user_password.user.as_str(),
+                user_password.password.inner(),
+            )),
+            NatsAuthConfig::CredentialsFile { credentials_file } => {
+                Ok(O::with_credentials(&credentials_file.path))
             }
-            NatsAuthConfig::CredentialsFile { credentials_file } => Ok(
-                nats::asynk::Options::with_credentials(&credentials_file.path),
-            ),
             NatsAuthConfig::Nkey { nkey } => nkeys::KeyPair::from_seed(&nkey.seed)
                 .context(AuthConfigSnafu)
                 .map(|kp| {
                     // The following unwrap is safe because the only way the sign method can fail is if
                     // keypair does not contain a seed. We are constructing the keypair from a seed in
                     // the preceding line.
-                    nats::asynk::Options::with_nkey(&nkey.nkey, move |nonce| {
-                        kp.sign(nonce).unwrap()
-                    })
+                    O::with_nkey(&nkey.nkey, move |nonce| kp.sign(nonce).unwrap())
                 }),
-            NatsAuthConfig::Token { token } => {
-                Ok(nats::asynk::Options::with_token(token.value.inner()))
-            }
+            NatsAuthConfig::Token { token } => Ok(O::with_token(token.value.inner())),
         }
     }
 }
 
-pub(crate) fn from_tls_auth_config(
+pub(crate) fn from_tls_auth_config<O: NatsOptions>(
     connection_name: &str,
     auth_config: &Option<NatsAuthConfig>,
     tls_config: &Option<TlsEnableableConfig>,
-) -> Result<nats::asynk::Options, NatsConfigError> {
+) -> Result<O, NatsConfigError> {
     let nats_options = match &auth_config {
-        None => nats::asynk::Options::new(),
+        None => O::new(),
         Some(auth) => auth.to_nats_options()?,
     };
 
@@ -184,6 +259,19 @@ pub(crate) fn from_tls_auth_config(
     }
 }
 
+/// Connects to the NATS server at `url` and returns its JetStream context.
+///
+/// The JetStream client is blocking, so it's only used from blocking tasks.
+#[cfg(any(feature = "sources-nats_jetstream", feature = "sinks-nats_jetstream"))]
+pub(crate) async fn connect_jetstream(
+    options: nats::Options,
+    url: String,
+) -> std::io::Result<nats::jetstream::JetStream> {
+    tokio::task::spawn_blocking(move || options.connect(url.as_str()).map(nats::jetstream::new))
+        .await
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, error))?
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
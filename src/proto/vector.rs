@@ -5,3 +5,36 @@ tonic::include_proto!("vector");
 
 pub use vector_client::VectorClient as Client;
 pub use vector_server::{Vector as Service, VectorServer as Server};
+
+impl PushEventsRequest {
+    /// Encodes the request and compresses it with zstd.
+    pub fn compress_zstd(&self) -> std::io::Result<PushCompressedEventsRequest> {
+        let encoded = prost::Message::encode_to_vec(self);
+        Ok(PushCompressedEventsRequest {
+            compression: Compression::Zstd.into(),
+            events: zstd::encode_all(encoded.as_slice(), 0)?,
+        })
+    }
+}
+
+impl PushCompressedEventsRequest {
+    /// Decompresses and decodes the request holding the events.
+    pub fn decompress(self) -> Result<PushEventsRequest, tonic::Status> {
+        let encoded = match Compression::from_i32(self.compression) {
+            Some(Compression::None) => self.events,
+            Some(Compression::Zstd) => {
+                zstd::decode_all(self.events.as_slice()).map_err(|error| {
+                    tonic::Status::invalid_argument(format!("Invalid zstd payload: {}", error))
+                })?
+            }
+            None => {
+                return Err(tonic::Status::unimplemented(format!(
+                    "Unsupported compression: {}",
+                    self.compression
+                )))
+            }
+        };
+        prost::Message::decode(encoded.as_slice())
+            .map_err(|error| tonic::Status::invalid_argument(format!("Invalid events: {}", error)))
+    }
+}
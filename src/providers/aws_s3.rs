@@ -0,0 +1,161 @@
+use aws_sdk_s3::Client as S3Client;
+use vector_config::{component::GenerateConfig, configurable_component};
+
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::s3::S3ClientBuilder,
+    config::{provider::ProviderConfig, ProxyConfig},
+    signal,
+    tls::TlsConfig,
+};
+
+use super::{BuildResult, Fetch, Fetched, SignatureConfig};
+
+/// Configuration for the `aws_s3` provider.
+#[configurable_component(provider("aws_s3"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AwsS3Config {
+    /// The name of the bucket holding the configuration.
+    #[configurable(metadata(docs::examples = "my-bucket"))]
+    bucket: String,
+
+    /// The key of the object holding the configuration.
+    #[configurable(metadata(docs::examples = "vector/vector.toml"))]
+    key: String,
+
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    /// How often to poll the provider, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    signature: Option<SignatureConfig>,
+
+    /// The user-defined metadata key of the object holding the signature of the configuration, if
+    /// it's verified.
+    #[serde(default = "default_signature_metadata_key")]
+    signature_metadata_key: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls_options: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_signature_metadata_key() -> String {
+    "vector-signature".to_owned()
+}
+
+impl GenerateConfig for AwsS3Config {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            bucket: String::from("my-bucket"),
+            key: String::from("vector/vector.toml"),
+            region: RegionOrEndpoint::with_region(String::from("us-east-1")),
+            auth: AwsAuthentication::default(),
+            poll_interval_secs: default_poll_interval_secs(),
+            signature: None,
+            signature_metadata_key: default_signature_metadata_key(),
+            tls_options: None,
+            proxy: ProxyConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+/// Fetches the configuration from an S3 object.
+struct S3Fetcher {
+    client: S3Client,
+    bucket: String,
+    key: String,
+    signature_metadata_key: String,
+}
+
+#[async_trait::async_trait]
+impl Fetch for S3Fetcher {
+    async fn fetch(&self) -> Result<Fetched, String> {
+        info!(
+            message = "Attempting to retrieve configuration.",
+            bucket = %self.bucket,
+            key = %self.key
+        );
+
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .send()
+            .await
+            .map_err(|error| format!("Could not get the configuration object: {}", error))?;
+
+        let signature = object
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(&self.signature_metadata_key))
+            .cloned();
+        let config = object
+            .body
+            .collect()
+            .await
+            .map_err(|error| format!("Could not read the configuration object: {}", error))?
+            .into_bytes();
+
+        Ok(Fetched { config, signature })
+    }
+}
+
+#[async_trait::async_trait]
+impl ProviderConfig for AwsS3Config {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> BuildResult {
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|error| vec![format!("Invalid endpoint: {}", error)])?;
+        let proxy = ProxyConfig::from_env().merge(&self.proxy);
+        let client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            endpoint,
+            &proxy,
+            &self.tls_options,
+            false,
+        )
+        .await
+        .map_err(|error| vec![format!("Unable to create AWS client: {}", error)])?;
+
+        let fetcher = S3Fetcher {
+            client,
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            signature_metadata_key: self.signature_metadata_key.clone(),
+        };
+
+        // Poll for changes to remote configuration.
+        super::build(
+            fetcher,
+            self.signature.clone(),
+            self.poll_interval_secs,
+            signal_handler,
+        )
+        .await
+    }
+}
@@ -0,0 +1,228 @@
+use bytes::Bytes;
+use http::StatusCode;
+use hyper::Body;
+use url::Url;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::{component::GenerateConfig, configurable_component};
+
+use crate::{
+    config::{provider::ProviderConfig, ProxyConfig},
+    http::HttpClient,
+    signal,
+    tls::{TlsConfig, TlsSettings},
+};
+
+use super::{BuildResult, Fetch, Fetched, SignatureConfig};
+
+/// Configuration for the `consul` provider.
+#[configurable_component(provider("consul"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ConsulConfig {
+    /// The address of the Consul agent.
+    #[serde(default = "default_address")]
+    address: String,
+
+    /// The key of the configuration in the key/value store.
+    #[configurable(metadata(docs::examples = "vector/config"))]
+    key: String,
+
+    /// The datacenter to read the configuration from.
+    ///
+    /// Defaults to the datacenter of the agent.
+    #[serde(default)]
+    datacenter: Option<String>,
+
+    /// The ACL token to read the configuration with.
+    #[serde(default)]
+    token: Option<SensitiveString>,
+
+    /// How often to poll the provider, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    signature: Option<SignatureConfig>,
+
+    /// The key of the signature of the configuration in the key/value store, if it's verified.
+    ///
+    /// Defaults to the key of the configuration, suffixed with `.sig`.
+    #[serde(default)]
+    signature_key: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+}
+
+fn default_address() -> String {
+    String::from("http://127.0.0.1:8500")
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+impl GenerateConfig for ConsulConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: default_address(),
+            key: String::from("vector/config"),
+            datacenter: None,
+            token: None,
+            poll_interval_secs: default_poll_interval_secs(),
+            signature: None,
+            signature_key: None,
+            tls: None,
+            proxy: ProxyConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+/// Fetches the configuration from the key/value store of Consul.
+struct ConsulFetcher {
+    http_client: HttpClient,
+    config_url: Url,
+    /// The URL of the signature, if it's verified.
+    signature_url: Option<Url>,
+    token: Option<SensitiveString>,
+}
+
+impl ConsulFetcher {
+    /// Reads the raw value of a key, as the API otherwise encodes it in base64.
+    async fn get(&self, url: &Url) -> Result<Bytes, String> {
+        let mut builder = http::request::Builder::new().uri(url.as_str());
+        if let Some(token) = &self.token {
+            builder = builder.header("X-Consul-Token", token.inner());
+        }
+        let request = builder
+            .body(Body::empty())
+            .map_err(|error| format!("Couldn't create HTTP request: {}", error))?;
+
+        let response = self
+            .http_client
+            .send(request)
+            .await
+            .map_err(|error| format!("HTTP error: {}", error))?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Err(format!("The key of {:?} doesn't exist.", url.path())),
+            status if !status.is_success() => {
+                Err(format!("Unexpected response status: {}", status))
+            }
+            _ => hyper::body::to_bytes(response.into_body())
+                .await
+                .map_err(|error| format!("Error interpreting response: {}", error)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Fetch for ConsulFetcher {
+    async fn fetch(&self) -> Result<Fetched, String> {
+        info!(
+            message = "Attempting to retrieve configuration.",
+            url = ?self.config_url.as_str()
+        );
+
+        let config = self.get(&self.config_url).await?;
+        let signature = match &self.signature_url {
+            Some(url) => {
+                let signature = self.get(url).await?;
+                Some(String::from_utf8_lossy(&signature).into_owned())
+            }
+            None => None,
+        };
+
+        Ok(Fetched { config, signature })
+    }
+}
+
+/// Returns the URL reading the raw value of a key.
+fn key_url(address: &str, key: &str, datacenter: Option<&str>) -> Result<Url, String> {
+    let mut url = Url::parse(address)
+        .and_then(|address| address.join(&format!("v1/kv/{}", key.trim_start_matches('/'))))
+        .map_err(|error| format!("Invalid address of the `consul` provider: {}", error))?;
+    url.set_query(Some("raw"));
+    if let Some(datacenter) = datacenter {
+        url.query_pairs_mut().append_pair("dc", datacenter);
+    }
+    Ok(url)
+}
+
+#[async_trait::async_trait]
+impl ProviderConfig for ConsulConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> BuildResult {
+        let datacenter = self.datacenter.as_deref();
+        let config_url =
+            key_url(&self.address, &self.key, datacenter).map_err(|error| vec![error])?;
+        let signature_url = match &self.signature {
+            Some(_) => {
+                let signature_key = self
+                    .signature_key
+                    .clone()
+                    .unwrap_or_else(|| format!("{}.sig", self.key));
+                Some(
+                    key_url(&self.address, &signature_key, datacenter)
+                        .map_err(|error| vec![error])?,
+                )
+            }
+            None => None,
+        };
+
+        let tls_settings = TlsSettings::from_options(&self.tls)
+            .map_err(|_| vec!["Invalid TLS options".to_owned()])?;
+        let proxy = ProxyConfig::from_env().merge(&self.proxy);
+        let http_client = HttpClient::<Body>::new(tls_settings, &proxy)
+            .map_err(|_| vec!["Invalid TLS settings".to_owned()])?;
+
+        let fetcher = ConsulFetcher {
+            http_client,
+            config_url,
+            signature_url,
+            token: self.token.clone(),
+        };
+
+        // Poll for changes to remote configuration.
+        super::build(
+            fetcher,
+            self.signature.clone(),
+            self.poll_interval_secs,
+            signal_handler,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_key_urls() {
+        assert_eq!(
+            key_url("http://127.0.0.1:8500", "vector/config", None)
+                .unwrap()
+                .as_str(),
+            "http://127.0.0.1:8500/v1/kv/vector/config?raw"
+        );
+        assert_eq!(
+            key_url(
+                "https://consul.example.com/",
+                "/vector/config.sig",
+                Some("eu-west")
+            )
+            .unwrap()
+            .as_str(),
+            "https://consul.example.com/v1/kv/vector/config.sig?raw&dc=eu-west"
+        );
+    }
+}
@@ -1,20 +1,16 @@
-use async_stream::stream;
-use bytes::Buf;
-use futures::Stream;
 use hyper::Body;
 use indexmap::IndexMap;
-use tokio::time;
 use url::Url;
 use vector_config::configurable_component;
 
 use crate::{
-    config::{self, provider::ProviderConfig, ProxyConfig},
+    config::{provider::ProviderConfig, ProxyConfig},
     http::HttpClient,
     signal,
     tls::{TlsConfig, TlsSettings},
 };
 
-use super::BuildResult;
+use super::{BuildResult, Fetch, Fetched, SignatureConfig};
 
 /// Request settings.
 #[configurable_component]
@@ -56,6 +52,12 @@ pub struct HttpConfig {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     proxy: ProxyConfig,
+
+    #[configurable(derived)]
+    signature: Option<SignatureConfig>,
+
+    /// The response header holding the signature of the configuration, if it's verified.
+    signature_header: String,
 }
 
 impl Default for HttpConfig {
@@ -66,109 +68,90 @@ impl Default for HttpConfig {
             poll_interval_secs: 30,
             tls_options: None,
             proxy: Default::default(),
+            signature: None,
+            signature_header: "X-Vector-Signature".to_owned(),
         }
     }
 }
 
-/// Makes an HTTP request to the provided endpoint, returning the String body.
-async fn http_request(
-    url: &Url,
-    tls_options: &Option<TlsConfig>,
-    headers: &IndexMap<String, String>,
-    proxy: &ProxyConfig,
-) -> Result<bytes::Bytes, &'static str> {
-    let tls_settings = TlsSettings::from_options(tls_options).map_err(|_| "Invalid TLS options")?;
-    let http_client =
-        HttpClient::<Body>::new(tls_settings, proxy).map_err(|_| "Invalid TLS settings")?;
-
-    // Build HTTP request.
-    let mut builder = http::request::Builder::new().uri(url.to_string());
-
-    // Augment with headers. These may be required e.g. for authentication to
-    // private endpoints.
-    for (header, value) in headers.iter() {
-        builder = builder.header(header.as_str(), value.as_str());
-    }
+/// Fetches the configuration from an HTTP endpoint.
+struct HttpFetcher {
+    http_client: HttpClient,
+    url: Url,
+    headers: IndexMap<String, String>,
+    signature_header: String,
+}
 
-    let request = builder
-        .body(Body::empty())
-        .map_err(|_| "Couldn't create HTTP request")?;
+impl HttpFetcher {
+    /// Makes an HTTP request to the provided endpoint, returning the body along with its
+    /// signature.
+    async fn http_request(&self) -> Result<Fetched, &'static str> {
+        let url = &self.url;
 
-    info!(
-        message = "Attempting to retrieve configuration.",
-        url = ?url.as_str()
-    );
+        // Build HTTP request.
+        let mut builder = http::request::Builder::new().uri(url.to_string());
 
-    let response = http_client.send(request).await.map_err(|err| {
-        let message = "HTTP error";
-        error!(
-            message = ?message,
-            error = ?err,
-            url = ?url.as_str());
-        message
-    })?;
+        // Augment with headers. These may be required e.g. for authentication to
+        // private endpoints.
+        for (header, value) in self.headers.iter() {
+            builder = builder.header(header.as_str(), value.as_str());
+        }
 
-    info!(message = "Response received.", url = ?url.as_str());
+        let request = builder
+            .body(Body::empty())
+            .map_err(|_| "Couldn't create HTTP request")?;
 
-    hyper::body::to_bytes(response.into_body())
-        .await
-        .map_err(|err| {
-            let message = "Error interpreting response.";
-            let cause = err.into_cause();
-            error!(
-                    message = ?message,
-                    error = ?cause);
+        info!(
+            message = "Attempting to retrieve configuration.",
+            url = ?url.as_str()
+        );
 
+        let response = self.http_client.send(request).await.map_err(|err| {
+            let message = "HTTP error";
+            error!(
+                message = ?message,
+                error = ?err,
+                url = ?url.as_str());
             message
-        })
-}
+        })?;
 
-/// Calls `http_request`, serializing the result to a `ConfigBuilder`.
-async fn http_request_to_config_builder(
-    url: &Url,
-    tls_options: &Option<TlsConfig>,
-    headers: &IndexMap<String, String>,
-    proxy: &ProxyConfig,
-) -> BuildResult {
-    let config_str = http_request(url, tls_options, headers, proxy)
-        .await
-        .map_err(|e| vec![e.to_owned()])?;
+        info!(message = "Response received.", url = ?url.as_str());
 
-    let (config_builder, warnings) =
-        config::load(config_str.chunk(), crate::config::format::Format::Toml)?;
+        if !response.status().is_success() {
+            let message = "Unexpected response status.";
+            error!(
+                message = ?message,
+                status = %response.status(),
+                url = ?url.as_str());
+            return Err(message);
+        }
 
-    for warning in warnings.into_iter() {
-        warn!("{}", warning);
+        let signature = response
+            .headers()
+            .get(self.signature_header.as_str())
+            .and_then(|signature| signature.to_str().ok())
+            .map(ToOwned::to_owned);
+
+        let config = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|err| {
+                let message = "Error interpreting response.";
+                let cause = err.into_cause();
+                error!(
+                        message = ?message,
+                        error = ?cause);
+
+                message
+            })?;
+
+        Ok(Fetched { config, signature })
     }
-
-    Ok(config_builder)
 }
 
-/// Polls the HTTP endpoint after/every `poll_interval_secs`, returning a stream of `ConfigBuilder`.
-fn poll_http(
-    poll_interval_secs: u64,
-    url: Url,
-    tls_options: Option<TlsConfig>,
-    headers: IndexMap<String, String>,
-    proxy: ProxyConfig,
-) -> impl Stream<Item = signal::SignalTo> {
-    let duration = time::Duration::from_secs(poll_interval_secs);
-    let mut interval = time::interval_at(time::Instant::now() + duration, duration);
-
-    stream! {
-        loop {
-            interval.tick().await;
-
-            match http_request_to_config_builder(&url, &tls_options, &headers, &proxy).await {
-                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
-                Err(_) => {},
-            };
-
-            info!(
-                message = "HTTP provider is waiting.",
-                poll_interval_secs = ?poll_interval_secs,
-                url = ?url.as_str());
-        }
+#[async_trait::async_trait]
+impl Fetch for HttpFetcher {
+    async fn fetch(&self) -> Result<Fetched, String> {
+        self.http_request().await.map_err(ToOwned::to_owned)
     }
 }
 
@@ -181,23 +164,27 @@ impl ProviderConfig for HttpConfig {
             .ok_or_else(|| vec!["URL is required for the `http` provider.".to_owned()])?;
 
         let tls_options = self.tls_options.take();
-        let poll_interval_secs = self.poll_interval_secs;
-        let request = self.request.clone();
-
+        let tls_settings = TlsSettings::from_options(&tls_options)
+            .map_err(|_| vec!["Invalid TLS options".to_owned()])?;
         let proxy = ProxyConfig::from_env().merge(&self.proxy);
-        let config_builder =
-            http_request_to_config_builder(&url, &tls_options, &request.headers, &proxy).await?;
+        let http_client = HttpClient::<Body>::new(tls_settings, &proxy)
+            .map_err(|_| vec!["Invalid TLS settings".to_owned()])?;
 
-        // Poll for changes to remote configuration.
-        signal_handler.add(poll_http(
-            poll_interval_secs,
+        let fetcher = HttpFetcher {
+            http_client,
             url,
-            tls_options,
-            request.headers.clone(),
-            proxy.clone(),
-        ));
+            headers: self.request.headers.clone(),
+            signature_header: self.signature_header.clone(),
+        };
 
-        Ok(config_builder)
+        // Poll for changes to remote configuration.
+        super::build(
+            fetcher,
+            self.signature.clone(),
+            self.poll_interval_secs,
+            signal_handler,
+        )
+        .await
     }
 }
 
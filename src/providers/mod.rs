@@ -1,13 +1,24 @@
 #![allow(missing_docs)]
+use async_stream::stream;
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
 use enum_dispatch::enum_dispatch;
+use futures::Stream;
+use tokio::time;
 use vector_config::{configurable_component, NamedComponent};
 
 use crate::{
-    config::{ConfigBuilder, ProviderConfig},
+    config::{self, ConfigBuilder, ProviderConfig},
     signal,
 };
 
+#[cfg(feature = "providers-aws_s3")]
+pub mod aws_s3;
+pub mod consul;
 pub mod http;
+mod signature;
+
+pub use signature::SignatureConfig;
 
 pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 
@@ -17,6 +28,13 @@ pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 #[serde(tag = "type", rename_all = "snake_case")]
 #[enum_dispatch(ProviderConfig)]
 pub enum Providers {
+    /// AWS S3.
+    #[cfg(feature = "providers-aws_s3")]
+    AwsS3(aws_s3::AwsS3Config),
+
+    /// Consul.
+    Consul(consul::ConsulConfig),
+
     /// HTTP.
     Http(http::HttpConfig),
 }
@@ -25,7 +43,96 @@ pub enum Providers {
 impl NamedComponent for Providers {
     fn get_component_name(&self) -> &'static str {
         match self {
+            #[cfg(feature = "providers-aws_s3")]
+            Self::AwsS3(config) => config.get_component_name(),
+            Self::Consul(config) => config.get_component_name(),
             Self::Http(config) => config.get_component_name(),
         }
     }
 }
+
+/// A configuration fetched by a provider, along with its signature, if any.
+struct Fetched {
+    config: Bytes,
+    signature: Option<String>,
+}
+
+/// Fetches the configuration of a provider.
+#[async_trait]
+trait Fetch: Send + Sync + 'static {
+    async fn fetch(&self) -> Result<Fetched, String>;
+}
+
+/// Verifies the signature of a fetched configuration, if required, then loads it.
+fn load(fetched: &Fetched, signature: Option<&SignatureConfig>) -> BuildResult {
+    if let Some(signature) = signature {
+        signature
+            .verify(&fetched.config, fetched.signature.as_deref())
+            .map_err(|error| vec![error])?;
+    }
+
+    let (config_builder, warnings) =
+        config::load(fetched.config.chunk(), crate::config::format::Format::Toml)?;
+
+    for warning in warnings.into_iter() {
+        warn!("{}", warning);
+    }
+
+    Ok(config_builder)
+}
+
+/// Fetches and loads the configuration of a provider, then polls the provider for its changes
+/// every `poll_interval_secs`.
+///
+/// The configuration is only reloaded once it changed, and its new signature is verified.
+async fn build<F: Fetch>(
+    fetcher: F,
+    signature: Option<SignatureConfig>,
+    poll_interval_secs: u64,
+    signal_handler: &mut signal::SignalHandler,
+) -> BuildResult {
+    let fetched = fetcher.fetch().await.map_err(|error| vec![error])?;
+    let config_builder = load(&fetched, signature.as_ref())?;
+
+    signal_handler.add(poll(fetcher, signature, poll_interval_secs, fetched.config));
+
+    Ok(config_builder)
+}
+
+fn poll<F: Fetch>(
+    fetcher: F,
+    signature: Option<SignatureConfig>,
+    poll_interval_secs: u64,
+    mut loaded: Bytes,
+) -> impl Stream<Item = signal::SignalTo> {
+    let duration = time::Duration::from_secs(poll_interval_secs);
+    let mut interval = time::interval_at(time::Instant::now() + duration, duration);
+
+    stream! {
+        loop {
+            interval.tick().await;
+
+            match fetcher.fetch().await {
+                Ok(fetched) if fetched.config == loaded => {
+                    debug!(message = "Configuration is unchanged.");
+                }
+                Ok(fetched) => match load(&fetched, signature.as_ref()) {
+                    Ok(config_builder) => {
+                        loaded = fetched.config;
+                        yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                    }
+                    Err(errors) => {
+                        for error in errors {
+                            error!(message = "Could not load the fetched configuration.", %error);
+                        }
+                    }
+                },
+                Err(error) => error!(message = "Could not fetch the configuration.", %error),
+            }
+
+            info!(
+                message = "Provider is waiting.",
+                poll_interval_secs = ?poll_interval_secs);
+        }
+    }
+}
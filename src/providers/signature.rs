@@ -0,0 +1,74 @@
+use openssl::{hash::MessageDigest, memcmp, pkey::PKey, sign::Signer};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+/// Signature verification settings.
+///
+/// The signature of a configuration is the hex-encoded HMAC-SHA256 of its content, optionally
+/// prefixed with `sha256=`. The configurations which aren't signed, or whose signature doesn't
+/// match, are rejected.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureConfig {
+    /// The secret key the configurations are signed with.
+    secret: SensitiveString,
+}
+
+impl SignatureConfig {
+    /// Verifies the signature of a fetched configuration.
+    pub(super) fn verify(&self, config: &[u8], signature: Option<&str>) -> Result<(), String> {
+        let signature = signature
+            .ok_or_else(|| "The configuration isn't signed.".to_owned())?
+            .trim();
+        let signature = signature
+            .strip_prefix("sha256=")
+            .unwrap_or(signature)
+            .to_ascii_lowercase();
+
+        let expected = hmac_sha256(self.secret.inner().as_bytes(), config)
+            .map_err(|error| format!("Could not compute the signature: {}", error))?;
+        // The signatures are compared in constant time, not to leak how much of it matches.
+        if expected.len() == signature.len()
+            && memcmp::eq(expected.as_bytes(), signature.as_bytes())
+        {
+            Ok(())
+        } else {
+            Err("The signature of the configuration doesn't match.".to_owned())
+        }
+    }
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of `data`.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<String, openssl::error::ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(data)?;
+    Ok(signer
+        .sign_to_vec()?
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_signatures() {
+        let signature = SignatureConfig {
+            secret: "key".to_owned().into(),
+        };
+        let config = b"The quick brown fox jumps over the lazy dog";
+        let expected = "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+
+        assert!(signature.verify(config, Some(expected)).is_ok());
+        assert!(signature
+            .verify(config, Some(&format!("sha256={}", expected.to_uppercase())))
+            .is_ok());
+        assert!(signature.verify(b"[sources]", Some(expected)).is_err());
+        assert!(signature.verify(config, Some("f7bc83")).is_err());
+        assert!(signature.verify(config, None).is_err());
+    }
+}
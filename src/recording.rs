@@ -0,0 +1,332 @@
+//! Recording of the input of sources, to replay it with `vector replay`.
+//!
+//! The decoders built while a source is being built or run, within [`with_recorder`], record each
+//! frame they read, before decoding it, along with the time it was read, in the recording of that
+//! source.
+//!
+//! A recording starts with a line holding the version of its format, followed by a line holding
+//! its [`RecordingHeader`] in JSON. Each frame is then written as the time it was read, in
+//! nanoseconds since the Unix epoch as a signed 64-bit integer, followed by its length as an
+//! unsigned 32-bit integer, both in little-endian order, and by the frame itself.
+use std::{
+    fs::{File, OpenOptions},
+    future::Future,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use codecs::decoding::DeserializerConfig;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use vector_config::configurable_component;
+
+use crate::{
+    config::ComponentKey,
+    internal_events::{RecordingLimitReached, RecordingWriteError},
+};
+
+/// The first line of recordings, identifying the version of their format.
+const MAGIC: &str = "vector-recording 1";
+
+/// How often the recorded frames are flushed to the recording, at most.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The size of the time and length preceding each recorded frame.
+const FRAME_PREFIX_LEN: u64 = 12;
+
+tokio::task_local! {
+    static RECORDER: Option<Arc<Recorder>>;
+}
+
+/// Recording options.
+///
+/// Records the frames read by the source, before they are decoded, along with the time they were
+/// read, so that its input can be fed back through a topology with `vector replay`. Only the
+/// sources decoding their input with a `decoding` option are recorded.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RecordConfig {
+    /// The file the frames are recorded to.
+    ///
+    /// If the file already holds a recording, the frames are appended to it.
+    #[configurable(metadata(docs::examples = "/var/lib/vector/in.recording"))]
+    pub path: PathBuf,
+
+    /// The maximum size of the recording, in bytes.
+    ///
+    /// Once reached, the frames read are no longer recorded. The size of the recording is not
+    /// limited by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::type_unit = "bytes", docs::examples = 1073741824))]
+    pub max_bytes: Option<u64>,
+}
+
+/// The header of a recording.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecordingHeader {
+    /// The ID of the recorded source.
+    pub source: String,
+
+    /// The type of the recorded source.
+    pub source_type: String,
+
+    /// The decoding of the recorded frames, if known.
+    pub decoding: Option<DeserializerConfig>,
+}
+
+/// The recording of a source.
+pub struct Recorder {
+    path: PathBuf,
+    max_bytes: Option<u64>,
+    state: Mutex<RecorderState>,
+}
+
+struct RecorderState {
+    writer: BufWriter<File>,
+    size: u64,
+    /// The header, until it is written along with the first frame.
+    header: Option<RecordingHeader>,
+    flushed_at: Instant,
+    /// Whether frames are no longer recorded, as the recording is full or failed to be written.
+    stopped: bool,
+}
+
+impl Recorder {
+    /// Opens the recording of the source `key`, of type `source_type`.
+    pub fn open(key: &ComponentKey, source_type: &str, config: &RecordConfig) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)?;
+        let size = file.metadata()?.len();
+        let header = (size == 0).then(|| RecordingHeader {
+            source: key.id().to_owned(),
+            source_type: source_type.to_owned(),
+            decoding: None,
+        });
+
+        Ok(Self {
+            path: config.path.clone(),
+            max_bytes: config.max_bytes,
+            state: Mutex::new(RecorderState {
+                writer: BufWriter::new(file),
+                size,
+                header,
+                flushed_at: Instant::now(),
+                stopped: false,
+            }),
+        })
+    }
+
+    /// Sets the decoding of the recorded frames, if the recording doesn't have one yet.
+    pub fn set_decoding(&self, decoding: &DeserializerConfig) {
+        let mut state = self.state.lock().expect("Recorder mutex is poisoned");
+        if let Some(header) = &mut state.header {
+            header.decoding.get_or_insert_with(|| decoding.clone());
+        }
+    }
+
+    /// Records `frame`, read now.
+    pub fn record(&self, frame: &[u8]) {
+        let mut state = self.state.lock().expect("Recorder mutex is poisoned");
+        if state.stopped {
+            return;
+        }
+        let len = match u32::try_from(frame.len()) {
+            Ok(len) => len,
+            // Frames this large can't be recorded.
+            Err(_) => return,
+        };
+        if let Some(max_bytes) = self.max_bytes {
+            if state.size + FRAME_PREFIX_LEN + u64::from(len) > max_bytes {
+                state.stopped = true;
+                emit!(RecordingLimitReached {
+                    path: &self.path,
+                    max_bytes,
+                });
+                return;
+            }
+        }
+
+        if let Err(error) = state.write(frame, len) {
+            state.stopped = true;
+            emit!(RecordingWriteError {
+                error,
+                path: &self.path,
+            });
+        }
+    }
+}
+
+impl RecorderState {
+    fn write(&mut self, frame: &[u8], len: u32) -> io::Result<()> {
+        if let Some(header) = self.header.take() {
+            let header = serde_json::to_string(&header)?;
+            writeln!(self.writer, "{}\n{}", MAGIC, header)?;
+            self.size += (MAGIC.len() + header.len() + 2) as u64;
+        }
+
+        let timestamp = Utc::now().timestamp_nanos();
+        self.writer.write_all(&timestamp.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(frame)?;
+        self.size += FRAME_PREFIX_LEN + u64::from(len);
+
+        if self.flushed_at.elapsed() >= FLUSH_INTERVAL {
+            self.writer.flush()?;
+            self.flushed_at = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+/// Runs `future`, which builds or runs a source, recording the frames read by the decoders it
+/// builds with `recorder`.
+pub async fn with_recorder<F: Future>(recorder: Option<Arc<Recorder>>, future: F) -> F::Output {
+    RECORDER.scope(recorder, future).await
+}
+
+/// Returns the recorder of the source currently being built or run, if it is recorded.
+pub(crate) fn current() -> Option<Arc<Recorder>> {
+    RECORDER.try_with(Clone::clone).ok().flatten()
+}
+
+/// A reader of the frames of a recording.
+pub struct RecordingReader<R> {
+    reader: BufReader<R>,
+    header: RecordingHeader,
+}
+
+impl RecordingReader<tokio::fs::File> {
+    /// Opens the recording at `path`, reading its header.
+    pub async fn open(path: &Path) -> io::Result<Self> {
+        Self::new(tokio::fs::File::open(path).await?).await
+    }
+}
+
+impl<R: AsyncRead + Unpin> RecordingReader<R> {
+    /// Reads the header of the recording read from `reader`.
+    pub async fn new(reader: R) -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        if line.trim_end() != MAGIC {
+            return Err(invalid_data(
+                "not a recording, or of an unsupported version",
+            ));
+        }
+        line.clear();
+        reader.read_line(&mut line).await?;
+        let header = serde_json::from_str(&line)
+            .map_err(|error| invalid_data(&format!("invalid header: {}", error)))?;
+
+        Ok(Self { reader, header })
+    }
+
+    /// The header of the recording.
+    pub const fn header(&self) -> &RecordingHeader {
+        &self.header
+    }
+
+    /// Reads the next frame, along with the time it was read, or `None` at the end of the
+    /// recording.
+    ///
+    /// A frame cut short, such as the last one of a recording still being written, ends the
+    /// recording.
+    pub async fn next_frame(&mut self) -> io::Result<Option<(DateTime<Utc>, Bytes)>> {
+        let mut prefix = [0; FRAME_PREFIX_LEN as usize];
+        match self.reader.read_exact(&mut prefix).await {
+            Ok(_) => {}
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+        let (timestamp, len) = prefix.split_at(8);
+        let timestamp = i64::from_le_bytes(timestamp.try_into().expect("8 bytes"));
+        let len = u32::from_le_bytes(len.try_into().expect("4 bytes"));
+
+        let mut frame = vec![0; len as usize];
+        match self.reader.read_exact(&mut frame).await {
+            Ok(_) => Ok(Some((Utc.timestamp_nanos(timestamp), frame.into()))),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn records_and_reads_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = RecordConfig {
+            path: dir.path().join("in.recording"),
+            max_bytes: Some(150),
+        };
+        let key = ComponentKey::from("in");
+
+        let recorder = Recorder::open(&key, "socket", &config).unwrap();
+        recorder.set_decoding(&DeserializerConfig::Bytes);
+        recorder.record(b"foo");
+        recorder.record(b"");
+        drop(recorder);
+
+        // Reopening the recording appends to it, up to its maximum size.
+        let recorder = Recorder::open(&key, "socket", &config).unwrap();
+        recorder.record(b"bar");
+        recorder.record(&[b'x'; 100]);
+        recorder.record(b"baz");
+        drop(recorder);
+
+        let mut reader = RecordingReader::open(&config.path).await.unwrap();
+        assert_eq!(reader.header().source, "in");
+        assert_eq!(reader.header().source_type, "socket");
+        assert!(matches!(
+            reader.header().decoding,
+            Some(DeserializerConfig::Bytes)
+        ));
+        let mut frames = Vec::new();
+        let mut last = Utc.timestamp_nanos(0);
+        while let Some((timestamp, frame)) = reader.next_frame().await.unwrap() {
+            assert!(timestamp >= last);
+            last = timestamp;
+            frames.push(frame);
+        }
+        assert_eq!(frames, ["foo", "", "bar"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_other_files() {
+        let error = RecordingReader::new(&b"foo\nbar\n"[..])
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn ends_at_truncated_frames() {
+        let mut recording = format!(
+            "{}\n{{\"source\":\"in\",\"source_type\":\"socket\",\"decoding\":null}}\n",
+            MAGIC
+        )
+        .into_bytes();
+        recording.extend_from_slice(&0i64.to_le_bytes());
+        recording.extend_from_slice(&5u32.to_le_bytes());
+        recording.extend_from_slice(b"fo");
+
+        let mut reader = RecordingReader::new(&recording[..]).await.unwrap();
+        assert!(reader.header().decoding.is_none());
+        assert!(reader.next_frame().await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,348 @@
+//! The `vector replay` command, feeding the input recorded from sources back through a topology.
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use clap::Parser;
+use codecs::decoding::DeserializerConfig;
+
+use crate::{
+    config::{self, ComponentKey, ConfigBuilder, SourceConfig, SourceOuter},
+    recording::RecordingReader,
+    signal::{SignalRx, SignalTo},
+    sources::replay::ReplayConfig,
+    topology,
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The recording of a source to replay, as `SOURCE=PATH`.
+    ///
+    /// The source is replaced by the replay of its recording. The sources without a recording
+    /// are removed, along with the transforms and sinks left without inputs.
+    #[arg(id = "recording", short, long, required = true)]
+    recordings: Vec<Recording>,
+
+    /// How many times faster than they were recorded the frames are replayed, or `0` to replay
+    /// them as quickly as possible.
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[arg(
+        id = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        value_delimiter(',')
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+/// The recording of a source.
+#[derive(Clone, Debug)]
+struct Recording {
+    source: ComponentKey,
+    path: PathBuf,
+}
+
+impl FromStr for Recording {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((source, path)) if !source.is_empty() && !path.is_empty() => Ok(Self {
+                source: ComponentKey::from(source),
+                path: PathBuf::from(path),
+            }),
+            _ => Err(format!("expected `SOURCE=PATH`, got {:?}", s)),
+        }
+    }
+}
+
+pub(crate) async fn cmd(opts: &Opts, mut signal_rx: SignalRx) -> exitcode::ExitCode {
+    if !(opts.speed >= 0.0 && opts.speed.is_finite()) {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("The speed must be a positive number, or zero.");
+        }
+        return exitcode::USAGE;
+    }
+
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+    if let Err(errors) = config::init_log_schema(&paths, true) {
+        print_errors(errors);
+        return exitcode::CONFIG;
+    }
+    let mut builder = match config::load_builder_from_paths(&paths) {
+        Ok((builder, _)) => builder,
+        Err(errors) => {
+            print_errors(errors);
+            return exitcode::CONFIG;
+        }
+    };
+
+    let mut replays = HashMap::new();
+    for recording in &opts.recordings {
+        let source = match builder.sources.get(&recording.source) {
+            Some(source) => source,
+            None => {
+                print_errors([format!("No source {:?} to replay.", recording.source.id())]);
+                return exitcode::CONFIG;
+            }
+        };
+        let decoding = match replay_decoding(source, &recording.path).await {
+            Ok(decoding) => decoding,
+            Err(error) => {
+                print_errors([error]);
+                return exitcode::NOINPUT;
+            }
+        };
+        replays.insert(
+            recording.source.clone(),
+            ReplayConfig::new(recording.path.clone(), opts.speed, decoding),
+        );
+    }
+    replace_sources(&mut builder, replays);
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => {
+            print_errors(errors);
+            return exitcode::CONFIG;
+        }
+    };
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+    let (topology, (_, mut crash_rx)) = match topology::start_validated(config, diff, pieces).await
+    {
+        Some(started) => started,
+        None => return exitcode::CONFIG,
+    };
+
+    let mut exit_status = exitcode::OK;
+    tokio::select! {
+        _ = topology.sources_finished() => info!("Finished replaying."),
+        Some(()) = crash_rx.recv() => exit_status = exitcode::SOFTWARE,
+        Ok(SignalTo::Shutdown | SignalTo::Drain(_) | SignalTo::Quit) = signal_rx.recv() => {},
+    }
+    topology.stop().await;
+
+    exit_status
+}
+
+/// Returns the decoding of the replayed frames: the one of the source they replace, falling back
+/// to the one recorded.
+async fn replay_decoding(source: &SourceOuter, path: &Path) -> Result<DeserializerConfig, String> {
+    let reader = RecordingReader::open(path)
+        .await
+        .map_err(|error| format!("Failed to open recording {:?}: {}", path, error))?;
+    Ok(source
+        .inner
+        .pass_through_decoding()
+        .or_else(|| reader.header().decoding.clone())
+        .unwrap_or(DeserializerConfig::Bytes))
+}
+
+/// Replaces the sources with the replays of their recordings, removing the sources without one
+/// and the transforms and sinks no longer having inputs.
+fn replace_sources(builder: &mut ConfigBuilder, mut replays: HashMap<ComponentKey, ReplayConfig>) {
+    builder.sources = std::mem::take(&mut builder.sources)
+        .into_iter()
+        .filter_map(|(key, source)| {
+            replays.remove(&key).map(|replay| {
+                let mut replacement = SourceOuter::new(replay);
+                replacement.tenant = source.tenant;
+                replacement.internal_log_rate_limit_secs = source.internal_log_rate_limit_secs;
+                (key, replacement)
+            })
+        })
+        .collect();
+
+    // Removing a component can leave consumers without inputs, so repeat until no more are.
+    loop {
+        let keys = builder
+            .sources
+            .keys()
+            .chain(builder.transforms.keys())
+            .map(|key| key.id().to_owned())
+            .collect::<HashSet<_>>();
+        let exists = |input: &String| {
+            input.contains('*')
+                || keys.contains(input.as_str())
+                || input
+                    .rsplit_once('.')
+                    .map_or(false, |(component, _port)| keys.contains(component))
+        };
+
+        let mut removed = Vec::new();
+        for (key, transform) in &mut builder.transforms {
+            transform.inputs = transform
+                .inputs
+                .iter()
+                .filter(|i| exists(i))
+                .cloned()
+                .collect();
+            if transform.inputs.is_empty() {
+                removed.push(key.clone());
+            }
+        }
+        for (key, sink) in &mut builder.sinks {
+            sink.inputs = sink.inputs.iter().filter(|i| exists(i)).cloned().collect();
+            if sink.inputs.is_empty() {
+                removed.push(key.clone());
+            }
+        }
+        if removed.is_empty() {
+            break;
+        }
+        for key in removed {
+            builder.transforms.remove(&key);
+            builder.sinks.remove(&key);
+        }
+    }
+}
+
+fn print_errors<I: IntoIterator<Item = String>>(errors: I) {
+    #[allow(clippy::print_stderr)]
+    for error in errors {
+        eprintln!("{}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use vector_config::NamedComponent;
+
+    use super::*;
+    use crate::config::Format;
+
+    #[test]
+    fn parses_recordings() {
+        let recording = Recording::from_str("in=/tmp/in.recording").unwrap();
+        assert_eq!(recording.source, ComponentKey::from("in"));
+        assert_eq!(recording.path, PathBuf::from("/tmp/in.recording"));
+        assert!(Recording::from_str("/tmp/in.recording").is_err());
+    }
+
+    #[cfg(all(
+        feature = "sources-demo_logs",
+        feature = "transforms-remap",
+        feature = "sinks-blackhole"
+    ))]
+    #[test]
+    fn replaces_sources() {
+        let mut builder: ConfigBuilder = config::format::deserialize(
+            indoc! {r#"
+                [sources.in]
+                type = "demo_logs"
+                format = "json"
+
+                [sources.other]
+                type = "demo_logs"
+                format = "json"
+
+                [transforms.both]
+                type = "remap"
+                inputs = ["in", "other"]
+                source = "."
+
+                [transforms.only_other]
+                type = "remap"
+                inputs = ["other"]
+                source = "."
+
+                [sinks.out]
+                type = "blackhole"
+                inputs = ["both", "only_other"]
+
+                [sinks.other_out]
+                type = "blackhole"
+                inputs = ["only_other"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+        let replays = HashMap::from([(
+            ComponentKey::from("in"),
+            ReplayConfig::new("in.recording".into(), 1.0, DeserializerConfig::Bytes),
+        )]);
+
+        replace_sources(&mut builder, replays);
+
+        assert_eq!(
+            builder.sources.keys().collect::<Vec<_>>(),
+            [&ComponentKey::from("in")]
+        );
+        assert_eq!(builder.sources[0].inner.get_component_name(), "replay");
+        assert_eq!(
+            builder.transforms[&ComponentKey::from("both")].inputs,
+            vec!["in"]
+        );
+        assert!(!builder
+            .transforms
+            .contains_key(&ComponentKey::from("only_other")));
+        assert_eq!(
+            builder.sinks[&ComponentKey::from("out")].inputs,
+            vec!["both"]
+        );
+        assert!(!builder.sinks.contains_key(&ComponentKey::from("other_out")));
+    }
+}
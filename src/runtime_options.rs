@@ -0,0 +1,328 @@
+//! Component options that can be changed at runtime, without reloading the configuration.
+//!
+//! Components register the few options they allow to be changed when they are built, and read
+//! their current value from the returned [`RuntimeOption`] while running. Only the options
+//! registered here can be changed, such as through the `setComponentOption` mutation of the API.
+//!
+//! The level of the internal logs of any component can be changed as well, overriding the level
+//! given on startup for the events emitted within the spans of the component.
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, RwLock, Weak,
+    },
+};
+
+use once_cell::sync::Lazy;
+use snafu::Snafu;
+use tracing::level_filters::LevelFilter;
+use vector_common::config::ComponentKey;
+
+use crate::internal_events::{ComponentLogLevelChanged, ComponentOptionChanged};
+
+/// The options currently registered, by component.
+static OPTIONS: Lazy<Mutex<HashMap<ComponentKey, HashMap<&'static str, Registered>>>> =
+    Lazy::new(Default::default);
+
+struct Registered {
+    value: Weak<AtomicU64>,
+    min: u64,
+    max: u64,
+}
+
+/// An option of a running component, which can be changed at runtime.
+#[derive(Clone, Debug)]
+pub struct RuntimeOption(Arc<AtomicU64>);
+
+impl RuntimeOption {
+    /// Returns the current value of the option.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Errors changing the value of an option.
+#[derive(Debug, Snafu)]
+pub enum SetOptionError {
+    /// The component isn't running, or has no such option.
+    #[snafu(display(
+        "Component {:?} has no option {:?} that can be changed at runtime",
+        component_id,
+        option
+    ))]
+    UnknownOption {
+        /// The identifier of the component.
+        component_id: String,
+        /// The name of the option.
+        option: String,
+    },
+
+    /// The value is outside of the range allowed for the option.
+    #[snafu(display("Option {:?} must be between {} and {}", option, min, max))]
+    OutOfRange {
+        /// The name of the option.
+        option: String,
+        /// The smallest value allowed.
+        min: u64,
+        /// The largest value allowed.
+        max: u64,
+    },
+}
+
+/// The current value of a registered option.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptionValue {
+    /// The component the option belongs to.
+    pub component_key: ComponentKey,
+    /// The name of the option.
+    pub option: &'static str,
+    /// The current value of the option.
+    pub value: u64,
+}
+
+fn options() -> MutexGuard<'static, HashMap<ComponentKey, HashMap<&'static str, Registered>>> {
+    OPTIONS.lock().expect("runtime options lock poisoned")
+}
+
+/// Registers the option `name` of the component, which can then be changed to values between `min`
+/// and `max`.
+///
+/// The option replaces the one registered by a previous instance of the component, such as before
+/// the configuration was reloaded. It is unregistered once the returned value, and all its clones,
+/// are dropped.
+pub fn register(
+    component_key: &ComponentKey,
+    name: &'static str,
+    value: u64,
+    min: u64,
+    max: u64,
+) -> RuntimeOption {
+    let option = Arc::new(AtomicU64::new(value));
+    register_shared(component_key, name, &option, min, max);
+    RuntimeOption(option)
+}
+
+/// Registers the option `name` of the component, held by `option`, which can then be changed to
+/// values between `min` and `max`.
+///
+/// This is for options whose value is read elsewhere than through a [`RuntimeOption`]. The option
+/// is unregistered once all the references to `option` are dropped.
+pub fn register_shared(
+    component_key: &ComponentKey,
+    name: &'static str,
+    option: &Arc<AtomicU64>,
+    min: u64,
+    max: u64,
+) {
+    let mut options = options();
+    // Take the chance to forget the options of the components that stopped since.
+    options.retain(|_, component| {
+        component.retain(|_, registered| registered.value.strong_count() > 0);
+        !component.is_empty()
+    });
+    options.entry(component_key.clone()).or_default().insert(
+        name,
+        Registered {
+            value: Arc::downgrade(option),
+            min,
+            max,
+        },
+    );
+}
+
+/// Changes the value of the option `name` of a running component, returning its previous value.
+///
+/// # Errors
+///
+/// If the component has no such option registered, or if the value is out of the range allowed for
+/// the option, an error variant will be returned.
+pub fn set(component_key: &ComponentKey, name: &str, value: u64) -> Result<u64, SetOptionError> {
+    let options = options();
+    let (option, registered) = options
+        .get(component_key)
+        .and_then(|component| component.get_key_value(name))
+        .ok_or_else(|| SetOptionError::UnknownOption {
+            component_id: component_key.id().to_owned(),
+            option: name.to_owned(),
+        })?;
+    let current = registered
+        .value
+        .upgrade()
+        .ok_or_else(|| SetOptionError::UnknownOption {
+            component_id: component_key.id().to_owned(),
+            option: name.to_owned(),
+        })?;
+    if value < registered.min || value > registered.max {
+        return Err(SetOptionError::OutOfRange {
+            option: name.to_owned(),
+            min: registered.min,
+            max: registered.max,
+        });
+    }
+
+    let previous = current.swap(value, Ordering::Relaxed);
+    emit!(ComponentOptionChanged {
+        component_id: component_key.id(),
+        option: *option,
+        previous,
+        value,
+    });
+    Ok(previous)
+}
+
+/// Returns the current values of the options of the running components.
+pub fn values() -> Vec<OptionValue> {
+    let options = options();
+    let mut values = options
+        .iter()
+        .flat_map(|(component_key, component)| {
+            component.iter().filter_map(|(option, registered)| {
+                Some(OptionValue {
+                    component_key: component_key.clone(),
+                    option: *option,
+                    value: registered.value.upgrade()?.load(Ordering::Relaxed),
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    values.sort_by(|a, b| {
+        a.component_key
+            .id()
+            .cmp(b.component_key.id())
+            .then_with(|| a.option.cmp(b.option))
+    });
+    values
+}
+
+/// The log levels of the components, by component ID.
+static LOG_LEVELS: Lazy<RwLock<HashMap<String, LevelFilter>>> = Lazy::new(Default::default);
+
+/// Whether any component has a log level of its own, so that the log filters only look up the
+/// component of the events when needed.
+static HAS_LOG_LEVELS: AtomicBool = AtomicBool::new(false);
+
+/// Sets the level of the internal logs of the component, or resets it to the level given on
+/// startup when `None`, returning its previous level.
+///
+/// The level applies to the component as long as Vector runs, including once the configuration is
+/// reloaded.
+pub fn set_log_level(
+    component_key: &ComponentKey,
+    level: Option<LevelFilter>,
+) -> Option<LevelFilter> {
+    let previous = {
+        let mut levels = LOG_LEVELS.write().expect("log levels lock poisoned");
+        let previous = match level {
+            Some(level) => levels.insert(component_key.id().to_owned(), level),
+            None => levels.remove(component_key.id()),
+        };
+        HAS_LOG_LEVELS.store(!levels.is_empty(), Ordering::Relaxed);
+        previous
+    };
+
+    // The filters decide again which logs are enabled, as they may have been disabled entirely.
+    tracing::callsite::rebuild_interest_cache();
+    emit!(ComponentLogLevelChanged {
+        component_id: component_key.id(),
+        previous,
+        level,
+    });
+    previous
+}
+
+/// Returns the log levels of the components that have one of their own.
+pub fn log_levels() -> Vec<(ComponentKey, LevelFilter)> {
+    let mut levels = LOG_LEVELS
+        .read()
+        .expect("log levels lock poisoned")
+        .iter()
+        .map(|(id, level)| (ComponentKey::from(id.as_str()), *level))
+        .collect::<Vec<_>>();
+    levels.sort_by(|a, b| a.0.id().cmp(b.0.id()));
+    levels
+}
+
+/// Returns whether any component has a log level of its own.
+pub(crate) fn has_log_levels() -> bool {
+    HAS_LOG_LEVELS.load(Ordering::Relaxed)
+}
+
+/// Returns the log level of the component with the given ID, if it has one of its own.
+pub(crate) fn log_level(component_id: &str) -> Option<LevelFilter> {
+    LOG_LEVELS
+        .read()
+        .expect("log levels lock poisoned")
+        .get(component_id)
+        .copied()
+}
+
+/// Returns the most verbose of the log levels of the components.
+pub(crate) fn max_log_level() -> Option<LevelFilter> {
+    LOG_LEVELS
+        .read()
+        .expect("log levels lock poisoned")
+        .values()
+        .max()
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_registered_options() {
+        let key = ComponentKey::from("runtime_options_changes");
+        let option = register(&key, "rate", 10, 1, 100);
+
+        assert_eq!(set(&key, "rate", 20).unwrap(), 10);
+        assert_eq!(option.get(), 20);
+        assert!(values().contains(&OptionValue {
+            component_key: key.clone(),
+            option: "rate",
+            value: 20,
+        }));
+
+        assert!(matches!(
+            set(&key, "rate", 0),
+            Err(SetOptionError::OutOfRange {
+                min: 1,
+                max: 100,
+                ..
+            })
+        ));
+        assert!(matches!(
+            set(&key, "threshold", 20),
+            Err(SetOptionError::UnknownOption { .. })
+        ));
+        assert_eq!(option.get(), 20);
+    }
+
+    #[test]
+    fn forgets_dropped_options() {
+        let key = ComponentKey::from("runtime_options_drops");
+        drop(register(&key, "rate", 10, 1, 100));
+
+        assert!(matches!(
+            set(&key, "rate", 20),
+            Err(SetOptionError::UnknownOption { .. })
+        ));
+    }
+
+    #[test]
+    fn sets_log_levels() {
+        let key = ComponentKey::from("runtime_options_log_level");
+        assert_eq!(set_log_level(&key, Some(LevelFilter::DEBUG)), None);
+        assert_eq!(log_level(key.id()), Some(LevelFilter::DEBUG));
+        assert!(has_log_levels());
+        assert!(log_levels().contains(&(key.clone(), LevelFilter::DEBUG)));
+
+        assert_eq!(
+            set_log_level(&key, Some(LevelFilter::WARN)),
+            Some(LevelFilter::DEBUG)
+        );
+        assert_eq!(set_log_level(&key, None), Some(LevelFilter::WARN));
+        assert_eq!(log_level(key.id()), None);
+    }
+}
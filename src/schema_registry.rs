@@ -0,0 +1,175 @@
+//! A client for the [Confluent Schema Registry][schema_registry] REST API.
+//!
+//! Only the subset of the API needed to produce and consume messages in the Confluent wire format
+//! is supported: looking up a schema by its ID, and registering a schema under a subject. Listing
+//! the subjects is also used to check that the registry can be reached.
+//!
+//! [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/develop/api.html
+#![allow(missing_docs)]
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use http::{Request, StatusCode};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use crate::{
+    config::ProxyConfig,
+    http::{Auth, HttpClient, HttpError},
+    tls::{TlsConfig, TlsSettings},
+};
+
+const CONTENT_TYPE: &str = "application/vnd.schemaregistry.v1+json";
+
+#[derive(Debug, Snafu)]
+pub enum SchemaRegistryError {
+    #[snafu(display("Failed to build schema registry request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Schema registry request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read schema registry response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Schema registry responded with {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid schema registry response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("Message is not in the Confluent wire format"))]
+    MissingSchemaId,
+    #[snafu(display("Invalid Avro schema with ID {}: {}", id, source))]
+    InvalidSchema { id: u32, source: apache_avro::Error },
+}
+
+/// Configuration for looking up and registering schemas in a Confluent Schema Registry.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    /// The subject to register the schema under.
+    ///
+    /// Only used when producing messages. If not set, it defaults to `<topic>-value`, following
+    /// the default `TopicNameStrategy`, which requires the topic to not be templated.
+    #[configurable(metadata(docs::examples = "logs-value"))]
+    pub subject: Option<String>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+#[derive(Deserialize)]
+struct SchemaResponse {
+    schema: String,
+}
+
+#[derive(Serialize)]
+struct RegisterRequest<'a> {
+    schema: &'a str,
+}
+
+#[derive(Deserialize)]
+struct RegisterResponse {
+    id: u32,
+}
+
+/// Schema registry client, caching schemas by ID.
+#[derive(Clone)]
+pub struct SchemaRegistryClient {
+    client: HttpClient,
+    url: String,
+    auth: Option<Auth>,
+    schemas: Arc<Mutex<HashMap<u32, apache_avro::Schema>>>,
+}
+
+impl SchemaRegistryClient {
+    pub fn new(config: &SchemaRegistryConfig, proxy: &ProxyConfig) -> crate::Result<Self> {
+        let tls_settings = TlsSettings::from_options(&config.tls)?;
+        let client = HttpClient::new(tls_settings, proxy)?;
+
+        Ok(Self {
+            client,
+            url: config.url.trim_end_matches('/').to_owned(),
+            auth: config.auth.clone(),
+            schemas: Arc::default(),
+        })
+    }
+
+    /// Returns the schema with the given ID, fetching it from the registry if it isn't cached.
+    pub async fn schema_by_id(&self, id: u32) -> Result<apache_avro::Schema, SchemaRegistryError> {
+        if let Some(schema) = self.schemas.lock().expect("poisoned lock").get(&id) {
+            return Ok(schema.clone());
+        }
+
+        let request = Request::get(format!("{}/schemas/ids/{}", self.url, id))
+            .header("Accept", CONTENT_TYPE)
+            .body(Body::empty())
+            .context(BuildRequestSnafu)?;
+        let response: SchemaResponse = self.send(request).await?;
+
+        let schema =
+            apache_avro::Schema::parse_str(&response.schema).context(InvalidSchemaSnafu { id })?;
+        self.schemas
+            .lock()
+            .expect("poisoned lock")
+            .insert(id, schema.clone());
+        Ok(schema)
+    }
+
+    /// Registers the schema under the given subject, returning its ID.
+    ///
+    /// Registering a schema that is already registered under the subject returns the existing ID.
+    pub async fn register(&self, subject: &str, schema: &str) -> Result<u32, SchemaRegistryError> {
+        let body = serde_json::to_vec(&RegisterRequest { schema }).context(InvalidResponseSnafu)?;
+        let request = Request::post(format!("{}/subjects/{}/versions", self.url, subject))
+            .header("Accept", CONTENT_TYPE)
+            .header("Content-Type", CONTENT_TYPE)
+            .body(Body::from(body))
+            .context(BuildRequestSnafu)?;
+        let response: RegisterResponse = self.send(request).await?;
+        Ok(response.id)
+    }
+
+    /// Checks that the registry can be reached, and accepts the credentials, by listing the
+    /// subjects.
+    pub async fn healthcheck(&self) -> Result<(), SchemaRegistryError> {
+        let request = Request::get(format!("{}/subjects", self.url))
+            .header("Accept", CONTENT_TYPE)
+            .body(Body::empty())
+            .context(BuildRequestSnafu)?;
+        let _subjects: Vec<String> = self.send(request).await?;
+        Ok(())
+    }
+
+    async fn send<T: for<'de> Deserialize<'de>>(
+        &self,
+        mut request: Request<Body>,
+    ) -> Result<T, SchemaRegistryError> {
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+
+        if !status.is_success() {
+            return Err(SchemaRegistryError::UnexpectedStatus {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+
+        serde_json::from_slice(&body).context(InvalidResponseSnafu)
+    }
+}
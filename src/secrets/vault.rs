@@ -0,0 +1,443 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use futures::executor;
+use http::{Method, Request};
+use hyper::Body;
+use serde::Deserialize;
+use tokio::time;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::{component::GenerateConfig, configurable_component};
+
+use crate::{
+    config::{ProxyConfig, SecretBackend},
+    http::HttpClient,
+    signal,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `vault` secrets backend.
+#[configurable_component(secrets("vault"))]
+#[derive(Clone, Debug)]
+pub struct VaultBackend {
+    /// The address of the Vault server.
+    #[configurable(metadata(docs::examples = "https://vault.example.com:8200"))]
+    pub address: String,
+
+    #[configurable(derived)]
+    pub auth: VaultAuth,
+
+    /// The [namespace][namespace] to use, for Vault Enterprise.
+    ///
+    /// [namespace]: https://developer.hashicorp.com/vault/docs/enterprise/namespaces
+    #[configurable(metadata(docs::examples = "team-a"))]
+    pub namespace: Option<String>,
+
+    /// The path at which the secrets engine is mounted.
+    #[serde(default = "default_mount")]
+    #[configurable(metadata(docs::examples = "secret", docs::examples = "database"))]
+    pub mount: String,
+
+    /// The path of the secret, relative to the mount of the secrets engine.
+    ///
+    /// The key of each secret placeholder is the name of a field of the secret stored at this path.
+    /// For example, `SECRET[<backend_name>.kafka_password]` is replaced by the `kafka_password`
+    /// field of the secret.
+    #[configurable(metadata(
+        docs::examples = "vector/production",
+        docs::examples = "creds/vector"
+    ))]
+    pub path: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub engine: VaultEngine,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub renewal: VaultRenewalConfig,
+
+    /// The timeout, in seconds, to wait for Vault to respond.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout: u64,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub proxy: ProxyConfig,
+
+    /// How long until the secrets must be retrieved again, as determined by the last retrieval.
+    #[serde(skip)]
+    refresh_after: Option<Duration>,
+}
+
+/// Configuration of the authentication method used to log in to Vault.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+#[configurable(metadata(docs::enum_tag_description = "The authentication method to use."))]
+pub enum VaultAuth {
+    /// Authenticate with a Vault token.
+    ///
+    /// The token must be valid whenever the secrets are retrieved, including when they are
+    /// refreshed.
+    Token {
+        /// The Vault token.
+        #[configurable(metadata(docs::examples = "${VAULT_TOKEN}"))]
+        token: SensitiveString,
+    },
+
+    /// Authenticate with the [AppRole][approle] auth method.
+    ///
+    /// Vector logs in every time the secrets are retrieved, so the resulting token only needs to
+    /// be valid for as long as it takes to read the secret.
+    ///
+    /// [approle]: https://developer.hashicorp.com/vault/docs/auth/approle
+    #[serde(rename = "approle")]
+    AppRole {
+        /// The role ID of the AppRole.
+        role_id: String,
+
+        /// The secret ID of the AppRole.
+        #[configurable(metadata(docs::examples = "${VAULT_SECRET_ID}"))]
+        secret_id: SensitiveString,
+
+        /// The path at which the AppRole auth method is mounted.
+        #[serde(default = "default_approle_mount")]
+        mount: String,
+    },
+}
+
+/// The kind of secrets engine the secret is read from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultEngine {
+    /// Version 2 of the [KV][kv] secrets engine.
+    ///
+    /// The latest version of the secret is read from `<mount>/data/<path>`.
+    ///
+    /// [kv]: https://developer.hashicorp.com/vault/docs/secrets/kv/kv-v2
+    #[default]
+    Kv2,
+
+    /// Any other secrets engine, such as version 1 of the KV secrets engine or the database
+    /// secrets engine.
+    ///
+    /// The secret is read from `<mount>/<path>`.
+    Generic,
+}
+
+/// Configuration for refreshing the secrets.
+///
+/// Refreshing the secrets reloads the configuration, the same way a `SIGHUP` signal does.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VaultRenewalConfig {
+    /// Whether to refresh the secrets before their lease expires.
+    #[serde(default = "crate::serde::default_true")]
+    pub enabled: bool,
+
+    /// The fraction of the lease duration after which the secrets are refreshed.
+    ///
+    /// Must be greater than `0` and at most `1`.
+    #[serde(default = "default_lease_fraction")]
+    pub lease_fraction: f64,
+
+    /// The interval, in seconds, at which to refresh secrets that don't have a lease.
+    ///
+    /// Secrets of the KV secrets engine usually don't have a lease. By default, they are only
+    /// retrieved again when the configuration is reloaded.
+    #[configurable(metadata(docs::examples = 3600))]
+    pub interval_secs: Option<u64>,
+}
+
+impl Default for VaultRenewalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lease_fraction: default_lease_fraction(),
+            interval_secs: None,
+        }
+    }
+}
+
+impl VaultRenewalConfig {
+    /// Returns how long until secrets with the given lease duration, in seconds, must be refreshed.
+    fn refresh_after(&self, lease_duration: u64) -> Option<Duration> {
+        if !self.enabled {
+            return None;
+        }
+
+        if lease_duration > 0 {
+            let refresh_after =
+                Duration::from_secs_f64(lease_duration as f64 * self.lease_fraction);
+            // Avoid reloading in a tight loop when leases are very short.
+            Some(refresh_after.max(Duration::from_secs(1)))
+        } else {
+            self.interval_secs.map(Duration::from_secs)
+        }
+    }
+}
+
+impl GenerateConfig for VaultBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(VaultBackend {
+            address: String::from("https://127.0.0.1:8200"),
+            auth: VaultAuth::Token {
+                token: String::from("${VAULT_TOKEN}").into(),
+            },
+            namespace: None,
+            mount: default_mount(),
+            path: String::from("vector"),
+            engine: VaultEngine::default(),
+            renewal: VaultRenewalConfig::default(),
+            timeout: default_timeout_secs(),
+            tls: None,
+            proxy: ProxyConfig::default(),
+            refresh_after: None,
+        })
+        .unwrap()
+    }
+}
+
+fn default_mount() -> String {
+    String::from("secret")
+}
+
+fn default_approle_mount() -> String {
+    String::from("approle")
+}
+
+const fn default_lease_fraction() -> f64 {
+    0.75
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultResponse {
+    data: Option<serde_json::Value>,
+    auth: Option<VaultAuthResponse>,
+    #[serde(default)]
+    lease_duration: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultAuthResponse {
+    client_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultErrorResponse {
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KvV2Data {
+    data: HashMap<String, serde_json::Value>,
+}
+
+/// A secret read from Vault.
+#[derive(Debug)]
+struct VaultSecret {
+    fields: HashMap<String, serde_json::Value>,
+    lease_duration: u64,
+}
+
+impl SecretBackend for VaultBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        if !(self.renewal.lease_fraction > 0.0 && self.renewal.lease_fraction <= 1.0) {
+            return Err("`renewal.lease_fraction` must be greater than 0 and at most 1".into());
+        }
+
+        let secret = executor::block_on(async {
+            tokio::select! {
+                biased;
+                Ok(signal::SignalTo::Shutdown | signal::SignalTo::Drain(_) | signal::SignalTo::Quit) = signal_rx.recv() => {
+                    Err("Secret retrieval was interrupted.".into())
+                }
+                _ = time::sleep(Duration::from_secs(self.timeout)) => {
+                    Err("Vault request timed-out".into())
+                }
+                secret = self.read_secret() => secret,
+            }
+        })?;
+
+        self.refresh_after = self.renewal.refresh_after(secret.lease_duration);
+        secret_values(secret.fields, secret_keys)
+    }
+
+    fn refresh_after(&self) -> Option<Duration> {
+        self.refresh_after
+    }
+}
+
+impl VaultBackend {
+    fn request(&self, method: Method, path: &str) -> http::request::Builder {
+        let builder = Request::builder().method(method).uri(format!(
+            "{}/v1/{}",
+            self.address.trim_end_matches('/'),
+            path
+        ));
+        match &self.namespace {
+            Some(namespace) => builder.header("X-Vault-Namespace", namespace),
+            None => builder,
+        }
+    }
+
+    async fn token(&self, client: &HttpClient) -> crate::Result<String> {
+        match &self.auth {
+            VaultAuth::Token { token } => Ok(token.inner().to_owned()),
+            VaultAuth::AppRole {
+                role_id,
+                secret_id,
+                mount,
+            } => {
+                let body = serde_json::json!({
+                    "role_id": role_id,
+                    "secret_id": secret_id.inner(),
+                });
+                let request = self
+                    .request(
+                        Method::POST,
+                        &format!("auth/{}/login", mount.trim_matches('/')),
+                    )
+                    .body(Body::from(serde_json::to_vec(&body)?))?;
+
+                let response = send(client, request).await?;
+                Ok(response
+                    .auth
+                    .ok_or("Vault login response didn't contain a token")?
+                    .client_token)
+            }
+        }
+    }
+
+    async fn read_secret(&self) -> crate::Result<VaultSecret> {
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let proxy = ProxyConfig::from_env().merge(&self.proxy);
+        let client = HttpClient::new(tls_settings, &proxy)?;
+
+        let token = self.token(&client).await?;
+
+        let mount = self.mount.trim_matches('/');
+        let path = self.path.trim_matches('/');
+        let path = match self.engine {
+            VaultEngine::Kv2 => format!("{}/data/{}", mount, path),
+            VaultEngine::Generic => format!("{}/{}", mount, path),
+        };
+        let request = self
+            .request(Method::GET, &path)
+            .header("X-Vault-Token", token)
+            .body(Body::empty())?;
+
+        let response = send(&client, request).await?;
+        let data = response
+            .data
+            .ok_or_else(|| format!("Vault response for '{}' didn't contain any data", path))?;
+        let fields = match self.engine {
+            VaultEngine::Kv2 => serde_json::from_value::<KvV2Data>(data)?.data,
+            VaultEngine::Generic => serde_json::from_value(data)?,
+        };
+
+        Ok(VaultSecret {
+            fields,
+            lease_duration: response.lease_duration,
+        })
+    }
+}
+
+async fn send(client: &HttpClient, request: Request<Body>) -> crate::Result<VaultResponse> {
+    let response = client.send(request).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+
+    if !status.is_success() {
+        let errors = serde_json::from_slice::<VaultErrorResponse>(&body)
+            .map(|response| response.errors.join(", "))
+            .unwrap_or_default();
+        return Err(format!("Vault responded with {}: {}", status, errors).into());
+    }
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Picks the requested keys out of the fields of the secret.
+///
+/// Fields that aren't strings are replaced by their JSON representation.
+fn secret_values(
+    mut fields: HashMap<String, serde_json::Value>,
+    secret_keys: HashSet<String>,
+) -> crate::Result<HashMap<String, String>> {
+    let mut secrets = HashMap::new();
+    for k in secret_keys.into_iter() {
+        let value = match fields.remove(&k) {
+            None | Some(serde_json::Value::Null) => {
+                return Err(format!("secret for key '{}' was not retrieved", k).into());
+            }
+            Some(serde_json::Value::String(value)) => value,
+            Some(value) => value.to_string(),
+        };
+        if value.is_empty() {
+            return Err(format!("secret for key '{}' was empty", k).into());
+        }
+        secrets.insert(k, value);
+    }
+    Ok(secrets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VaultBackend>();
+    }
+
+    #[test]
+    fn picks_secret_values() {
+        let fields: HashMap<String, serde_json::Value> = serde_json::from_str(
+            r#"{"password": "hunter2", "port": 9092, "empty": "", "unset": null}"#,
+        )
+        .unwrap();
+        let keys = |keys: &[&str]| keys.iter().map(|key| (*key).to_owned()).collect();
+
+        let secrets = secret_values(fields.clone(), keys(&["password", "port"])).unwrap();
+        assert_eq!(secrets["password"], "hunter2");
+        assert_eq!(secrets["port"], "9092");
+
+        for key in ["empty", "unset", "missing"] {
+            assert!(secret_values(fields.clone(), keys(&[key])).is_err());
+        }
+    }
+
+    #[test]
+    fn refreshes_before_lease_expiry() {
+        let mut renewal = VaultRenewalConfig::default();
+        assert_eq!(renewal.refresh_after(3600), Some(Duration::from_secs(2700)));
+        assert_eq!(renewal.refresh_after(1), Some(Duration::from_secs(1)));
+        assert_eq!(renewal.refresh_after(0), None);
+
+        renewal.interval_secs = Some(600);
+        assert_eq!(renewal.refresh_after(0), Some(Duration::from_secs(600)));
+
+        renewal.enabled = false;
+        assert_eq!(renewal.refresh_after(3600), None);
+    }
+}
@@ -1,9 +1,11 @@
 #![allow(missing_docs)]
 
-use tokio::{runtime::Runtime, sync::broadcast};
+use std::{future::Future, time::Duration};
+
+use tokio::{runtime::Runtime, sync::broadcast, task::JoinHandle};
 use tokio_stream::{Stream, StreamExt};
 
-use super::config::ConfigBuilder;
+use super::config::{ComponentKey, ConfigBuilder};
 
 pub type ShutdownTx = broadcast::Sender<()>;
 pub type SignalTx = broadcast::Sender<SignalTo>;
@@ -17,10 +19,18 @@ pub enum SignalTo {
     ReloadFromConfigBuilder(ConfigBuilder),
     /// Signal to reload config from the filesystem.
     ReloadFromDisk,
+    /// Signal to reload the given enrichment tables, leaving the rest of the config as is.
+    ReloadEnrichmentTables(Vec<ComponentKey>),
     /// Signal to shutdown process.
     Shutdown,
+    /// Signal to drain the topology, then shutdown process. The sources stop accepting new
+    /// events, and the events in flight are flushed by the sinks within the given deadline, or
+    /// the graceful shutdown limit if there is none.
+    Drain(Option<Duration>),
     /// Shutdown process immediately.
     Quit,
+    /// Signal to dump the failed requests recorded by the request logs of sinks.
+    DumpRequestLogs,
 }
 
 /// Convenience struct for app setup handling.
@@ -44,6 +54,8 @@ impl SignalPair {
 pub struct SignalHandler {
     tx: SignalTx,
     shutdown_txs: Vec<ShutdownTx>,
+    /// The pending reload refreshing the retrieved secrets, replaced every time they're retrieved.
+    secrets_refresh: Option<JoinHandle<()>>,
 }
 
 impl SignalHandler {
@@ -54,6 +66,7 @@ impl SignalHandler {
         let handler = Self {
             tx,
             shutdown_txs: vec![],
+            secrets_refresh: None,
         };
 
         (handler, rx)
@@ -133,6 +146,28 @@ impl SignalHandler {
             let _ = shutdown_tx.send(());
         }
     }
+
+    /// Spawns a task sending the signal `refresh` resolves to, in place of the refresh of the
+    /// secrets scheduled before, if any.
+    pub fn schedule_secrets_refresh<F>(&mut self, refresh: F)
+    where
+        F: Future<Output = SignalTo> + Send + 'static,
+    {
+        self.cancel_secrets_refresh();
+        let tx = self.tx.clone();
+        self.secrets_refresh = Some(tokio::spawn(async move {
+            if tx.send(refresh.await).is_err() {
+                error!(message = "Couldn't send signal.");
+            }
+        }));
+    }
+
+    /// Cancels the refresh of the secrets scheduled before, if any.
+    pub fn cancel_secrets_refresh(&mut self) {
+        if let Some(refresh) = self.secrets_refresh.take() {
+            refresh.abort();
+        }
+    }
 }
 
 /// Signals from OS/user.
@@ -147,6 +182,10 @@ fn os_signals(runtime: &Runtime) -> impl Stream<Item = SignalTo> {
             signal(SignalKind::terminate()).expect("Failed to set up SIGTERM handler.");
         let mut sigquit = signal(SignalKind::quit()).expect("Failed to set up SIGQUIT handler.");
         let mut sighup = signal(SignalKind::hangup()).expect("Failed to set up SIGHUP handler.");
+        let mut sigusr1 =
+            signal(SignalKind::user_defined1()).expect("Failed to set up SIGUSR1 handler.");
+        let mut sigusr2 =
+            signal(SignalKind::user_defined2()).expect("Failed to set up SIGUSR2 handler.");
 
         async_stream::stream! {
             loop {
@@ -167,6 +206,14 @@ fn os_signals(runtime: &Runtime) -> impl Stream<Item = SignalTo> {
                         info!(message = "Signal received.", signal = "SIGHUP");
                         SignalTo::ReloadFromDisk
                     },
+                    _ = sigusr1.recv() => {
+                        info!(message = "Signal received.", signal = "SIGUSR1");
+                        SignalTo::DumpRequestLogs
+                    },
+                    _ = sigusr2.recv() => {
+                        info!(message = "Signal received.", signal = "SIGUSR2");
+                        SignalTo::Drain(None)
+                    },
                 };
                 yield signal;
             }
@@ -186,3 +233,28 @@ fn os_signals(_: &Runtime) -> impl Stream<Item = SignalTo> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replaces_the_scheduled_secrets_refresh() {
+        let (mut handler, mut receiver) = SignalHandler::new();
+        let (sender, refreshed) = tokio::sync::oneshot::channel::<()>();
+        handler.schedule_secrets_refresh(async move {
+            let _ = refreshed.await;
+            SignalTo::Shutdown
+        });
+        handler.schedule_secrets_refresh(async { SignalTo::ReloadFromDisk });
+
+        // The refresh scheduled first was cancelled.
+        let _ = sender.send(());
+        assert!(matches!(
+            receiver.recv().await,
+            Ok(SignalTo::ReloadFromDisk)
+        ));
+        tokio::task::yield_now().await;
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use arrow_flight::Criteria;
+use http::{header::HeaderName, HeaderMap, HeaderValue};
+use indexmap::IndexMap;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    service::{ArrowFlightResponse, ArrowFlightService},
+    sink::ArrowFlightSink,
+    ArrowFlightSinkError,
+};
+use crate::{
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext,
+        SinkHealthcheckOptions,
+    },
+    http::Auth,
+    sinks::{
+        util::{
+            partitioner::KeyPartitioner, retries::RetryLogic, BatchConfig, ServiceBuilderExt,
+            SinkBatchSettings, TowerRequestConfig,
+        },
+        vector::{new_client, with_default_scheme},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ArrowFlightDefaultBatchSettings;
+
+impl SinkBatchSettings for ArrowFlightDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(100_000);
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `arrow_flight` sink.
+#[configurable_component(sink("arrow_flight"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ArrowFlightSinkConfig {
+    /// The gRPC endpoint of the Arrow Flight server to send events to.
+    ///
+    /// Both IP address and hostname are accepted formats.
+    ///
+    /// The endpoint _must_ include a port.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "http://localhost:8815"))]
+    #[configurable(metadata(docs::examples = "https://flight-server:443"))]
+    endpoint: String,
+
+    /// The path of the Flight descriptor the record batches are sent with, such as the name of a
+    /// table.
+    ///
+    /// The events are batched by path.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "{{ application }}"))]
+    path: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    schema: SchemaMode,
+
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    compression: bool,
+
+    /// A list of custom headers to add to each request, such as the database expected by the
+    /// server.
+    ///
+    /// Headers are sent as gRPC metadata.
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP request header and its value."
+    ))]
+    #[configurable(metadata(docs::examples = "example_headers()"))]
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<ArrowFlightDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// How the schema of the record batches is determined.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaMode {
+    /// The schema is inferred from the events of each batch.
+    ///
+    /// Each top-level field of the events is a nullable column, whose type is inferred from its
+    /// values. The objects and arrays are encoded as JSON strings, and the columns holding values
+    /// of different types are strings.
+    #[default]
+    Infer,
+
+    /// The schema is received from the server with a `GetSchema` request for the path.
+    ///
+    /// The columns of the record batches are cast to the types of the schema. The fields missing
+    /// from the schema are left out, and the columns missing from the events are null.
+    ///
+    /// The schema is received again after a request fails, in case it changed. The events which
+    /// can't be cast to the schema are rejected.
+    Server,
+}
+
+fn example_headers() -> IndexMap<String, String> {
+    IndexMap::from([("database".to_owned(), "vector".to_owned())])
+}
+
+impl GenerateConfig for ArrowFlightSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:8815"
+            path = "logs""#,
+        )
+        .unwrap()
+    }
+}
+
+impl ArrowFlightSinkConfig {
+    /// Builds the headers sent with each request, including the authentication ones.
+    fn build_headers(&self) -> crate::Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        if let Some(auth) = &self.auth {
+            auth.apply_headers_map(&mut headers);
+        }
+        Ok(headers)
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for ArrowFlightSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let headers = self.build_headers()?;
+
+        let client = new_client(&tls, cx.proxy())?;
+
+        let healthcheck_uri = cx
+            .healthcheck
+            .uri
+            .clone()
+            .map(|uri| uri.uri)
+            .unwrap_or_else(|| uri.clone());
+        let healthcheck_service = ArrowFlightService::new(
+            client.clone(),
+            healthcheck_uri,
+            headers.clone(),
+            false,
+            self.schema,
+        );
+        let healthcheck = healthcheck(healthcheck_service, cx.healthcheck);
+
+        let service = ArrowFlightService::new(client, uri, headers, self.compression, self.schema);
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let service = ServiceBuilder::new()
+            .settings(request_settings, ArrowFlightRetryLogic)
+            .service(service);
+
+        let sink = ArrowFlightSink {
+            batch_settings,
+            partitioner: KeyPartitioner::new(self.path.clone()),
+            service,
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(healthcheck),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the server accepts requests, by listing its flights.
+async fn healthcheck(
+    mut service: ArrowFlightService,
+    options: SinkHealthcheckOptions,
+) -> crate::Result<()> {
+    if !options.enabled {
+        return Ok(());
+    }
+
+    match service.client.list_flights(Criteria::default()).await {
+        Ok(_) => Ok(()),
+        // The server is reachable, but doesn't list its flights.
+        Err(source) if source.code() == tonic::Code::Unimplemented => Ok(()),
+        Err(source) => Err(Box::new(ArrowFlightSinkError::Request { source })),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArrowFlightRetryLogic;
+
+impl RetryLogic for ArrowFlightRetryLogic {
+    type Error = ArrowFlightSinkError;
+    type Response = ArrowFlightResponse;
+
+    fn is_retriable_error(&self, err: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match err {
+            ArrowFlightSinkError::Request { source } => !matches!(
+                source.code(),
+                // List taken from
+                //
+                // <https://github.com/grpc/grpc/blob/ed1b20777c69bd47e730a63271eafc1b299f6ca0/doc/statuscodes.md>
+                NotFound
+                    | InvalidArgument
+                    | AlreadyExists
+                    | PermissionDenied
+                    | OutOfRange
+                    | Unimplemented
+                    | Unauthenticated
+            ),
+            ArrowFlightSinkError::Schema { .. } | ArrowFlightSinkError::Conform { .. } => false,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use arrow::{
+    array::new_null_array,
+    compute::cast,
+    datatypes::SchemaRef,
+    error::ArrowError,
+    record_batch::{RecordBatch, RecordBatchOptions},
+};
+
+/// Conforms the record batch to the schema, by casting its columns to the types of the schema.
+///
+/// The columns missing from the record batch are null, and the ones missing from the schema are
+/// left out.
+pub(super) fn conform_record_batch(
+    batch: &RecordBatch,
+    schema: SchemaRef,
+) -> Result<RecordBatch, ArrowError> {
+    let batch_schema = batch.schema();
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch_schema.index_of(field.name()) {
+            Ok(index) => cast(batch.column(index), field.data_type()),
+            Err(_) => Ok(new_null_array(field.data_type(), batch.num_rows())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    RecordBatch::try_new_with_options(
+        schema,
+        columns,
+        &RecordBatchOptions::new().with_row_count(Some(batch.num_rows())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Array, UInt16Array},
+        datatypes::{DataType, Field, Schema},
+    };
+
+    use super::*;
+    use crate::{
+        event::{LogEvent, Value},
+        sinks::util::record_batch::build_record_batch,
+    };
+
+    fn log(fields: Vec<(&str, Value)>) -> LogEvent {
+        let mut log = LogEvent::default();
+        for (name, value) in fields {
+            log.insert(name, value);
+        }
+        log
+    }
+
+    #[test]
+    fn conforms_to_the_schema() {
+        let events = vec![log(vec![
+            ("message", Value::from("first")),
+            ("status", Value::from("404")),
+            ("extra", Value::from(true)),
+        ])];
+        let batch = build_record_batch(&events).unwrap();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("message", DataType::Utf8, false),
+            Field::new("status", DataType::UInt16, true),
+            Field::new("host", DataType::Utf8, true),
+        ]));
+        let batch = conform_record_batch(&batch, Arc::clone(&schema)).unwrap();
+
+        assert_eq!(batch.schema(), schema);
+        let status = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<UInt16Array>()
+            .unwrap();
+        assert_eq!(status.value(0), 404);
+        assert!(batch.column(2).is_null(0));
+    }
+
+    #[test]
+    fn conforming_fails_on_missing_required_columns() {
+        let events = vec![log(vec![("message", Value::from("first"))])];
+        let batch = build_record_batch(&events).unwrap();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("host", DataType::Utf8, false)]));
+        assert!(conform_record_batch(&batch, schema).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! The `arrow_flight` sink.
+//!
+//! Batches log events into Arrow record batches and sends them to an Arrow Flight server with
+//! `DoPut` requests.
+use arrow::error::ArrowError;
+use snafu::Snafu;
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::ArrowFlightSinkConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum ArrowFlightSinkError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+
+    #[snafu(display("Invalid schema received from the server: {}", source))]
+    Schema { source: ArrowError },
+
+    #[snafu(display("Failed to conform the record batch to the server schema: {}", source))]
+    Conform { source: ArrowError },
+}
@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    iter,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    ipc::writer::IpcWriteOptions,
+    record_batch::RecordBatch,
+};
+use arrow_flight::{
+    flight_service_client::FlightServiceClient, utils::flight_data_from_arrow_batch, FlightData,
+    FlightDescriptor, SchemaAsIpc,
+};
+use futures::{future::BoxFuture, stream};
+use http::{HeaderMap, Uri};
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use snafu::ResultExt;
+use tonic::body::BoxBody;
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+
+use super::{
+    config::SchemaMode, encoder::conform_record_batch, ArrowFlightSinkError, ConformSnafu,
+    RequestSnafu, SchemaSnafu,
+};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::SocksConnector,
+    internal_events::EndpointBytesSent,
+    sinks::util::uri,
+    Error,
+};
+
+#[derive(Clone, Debug)]
+pub struct ArrowFlightService {
+    pub client: FlightServiceClient<HyperSvc>,
+    pub schema_mode: SchemaMode,
+    /// The schemas received from the server, by path.
+    schemas: Arc<Mutex<HashMap<String, SchemaRef>>>,
+    pub protocol: String,
+    pub endpoint: String,
+}
+
+pub struct ArrowFlightResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for ArrowFlightResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.events_count, self.events_byte_size)
+    }
+}
+
+/// The record batch of a batch of events, sent with the path of its Flight descriptor.
+#[derive(Clone)]
+pub struct ArrowFlightRequest {
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+    pub path: String,
+    pub batch: RecordBatch,
+}
+
+impl Finalizable for ArrowFlightRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for ArrowFlightRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+impl ArrowFlightService {
+    pub fn new(
+        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>,
+        uri: Uri,
+        headers: HeaderMap,
+        compression: bool,
+        schema_mode: SchemaMode,
+    ) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        let mut client = FlightServiceClient::new(HyperSvc {
+            uri,
+            headers: Arc::new(headers),
+            client: hyper_client,
+        });
+
+        if compression {
+            client = client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
+        }
+        Self {
+            client,
+            schema_mode,
+            schemas: Arc::default(),
+            protocol,
+            endpoint,
+        }
+    }
+
+    /// Gets the schema of the path from the server, unless it was already received.
+    async fn schema(&mut self, path: &str) -> Result<SchemaRef, ArrowFlightSinkError> {
+        let cached = self.schemas.lock().unwrap().get(path).cloned();
+        if let Some(schema) = cached {
+            return Ok(schema);
+        }
+
+        let descriptor = FlightDescriptor::new_path(vec![path.to_owned()]);
+        let result = self
+            .client
+            .get_schema(descriptor)
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+        let schema = Arc::new(Schema::try_from(&result).context(SchemaSnafu)?);
+
+        self.schemas
+            .lock()
+            .unwrap()
+            .insert(path.to_owned(), Arc::clone(&schema));
+        Ok(schema)
+    }
+
+    async fn put(
+        &mut self,
+        path: String,
+        batch: RecordBatch,
+    ) -> Result<usize, ArrowFlightSinkError> {
+        let batch = match self.schema_mode {
+            SchemaMode::Infer => batch,
+            SchemaMode::Server => {
+                let schema = self.schema(&path).await?;
+                conform_record_batch(&batch, schema).context(ConformSnafu)?
+            }
+        };
+
+        let options = IpcWriteOptions::default();
+        let mut schema = FlightData::from(SchemaAsIpc::new(&batch.schema(), &options));
+        schema.flight_descriptor = Some(FlightDescriptor::new_path(vec![path]));
+        let (dictionaries, data) = flight_data_from_arrow_batch(&batch, &options);
+
+        let messages = iter::once(schema)
+            .chain(dictionaries)
+            .chain(iter::once(data))
+            .collect::<Vec<_>>();
+        let byte_size = messages
+            .iter()
+            .map(|message| message.data_header.len() + message.data_body.len())
+            .sum();
+
+        let mut results = self
+            .client
+            .do_put(stream::iter(messages))
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+        while results.message().await.context(RequestSnafu)?.is_some() {}
+
+        Ok(byte_size)
+    }
+}
+
+impl Service<ArrowFlightRequest> for ArrowFlightService {
+    type Response = ArrowFlightResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness check of the client is done through the `do_put()` call happening inside
+        // `call()`, like in the `vector` sink.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: ArrowFlightRequest) -> Self::Future {
+        let mut service = self.clone();
+        let events_count = request.get_metadata().event_count();
+        let events_byte_size = request.get_metadata().events_byte_size();
+
+        let future = async move {
+            let path = request.path.clone();
+            match service.put(request.path, request.batch).await {
+                Ok(byte_size) => {
+                    emit!(EndpointBytesSent {
+                        byte_size,
+                        protocol: &service.protocol,
+                        endpoint: &service.endpoint,
+                    });
+                    Ok(ArrowFlightResponse {
+                        events_count,
+                        events_byte_size,
+                    })
+                }
+                Err(error) => {
+                    // The schema may have changed on the server, so it is received again when the
+                    // request is retried.
+                    service.schemas.lock().unwrap().remove(&path);
+                    Err(Error::from(error))
+                }
+            }
+        };
+
+        Box::pin(future)
+    }
+}
+
+/// Sends the gRPC requests to the configured endpoint, with the configured headers.
+#[derive(Clone, Debug)]
+pub struct HyperSvc {
+    uri: Uri,
+    headers: Arc<HeaderMap>,
+    client: hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>,
+}
+
+impl Service<hyper::Request<BoxBody>> for HyperSvc {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+        let uri = Uri::builder()
+            .scheme(self.uri.scheme().unwrap().clone())
+            .authority(self.uri.authority().unwrap().clone())
+            .path_and_query(req.uri().path_and_query().unwrap().clone())
+            .build()
+            .unwrap();
+
+        *req.uri_mut() = uri;
+        for (name, value) in self.headers.iter() {
+            req.headers_mut().insert(name, value.clone());
+        }
+
+        Box::pin(self.client.request(req))
+    }
+}
@@ -0,0 +1,91 @@
+use std::{fmt, num::NonZeroUsize};
+
+use arrow::array::Array;
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::stream::{BatcherSettings, DriverResponse};
+
+use super::service::ArrowFlightRequest;
+use crate::{
+    event::{Event, EventStatus, Finalizable},
+    internal_events::SinkRequestBuildError,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, partitioner::KeyPartitioner,
+        record_batch::build_record_batch, SinkBuilderExt, StreamSink,
+    },
+};
+
+pub struct ArrowFlightSink<S> {
+    pub batch_settings: BatcherSettings,
+    pub partitioner: KeyPartitioner,
+    pub service: S,
+}
+
+impl<S> ArrowFlightSink<S>
+where
+    S: Service<ArrowFlightRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(path, mut events)| async move {
+                // We don't need to emit an error here if the event is dropped since this will occur
+                // if the template couldn't be rendered during the partitioning. A
+                // `TemplateRenderingError` is already emitted when that occurs.
+                let path = path?;
+
+                let finalizers = events.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&events);
+                let logs = events
+                    .into_iter()
+                    .filter_map(Event::try_into_log)
+                    .collect::<Vec<_>>();
+
+                match build_record_batch(&logs) {
+                    Ok(batch) => {
+                        // The events may not have any field, so that the record batch has no
+                        // columns.
+                        let byte_size = batch
+                            .columns()
+                            .iter()
+                            .map(|column| column.get_array_memory_size())
+                            .sum::<usize>();
+                        let bytes_len = NonZeroUsize::new(byte_size.max(1))
+                            .expect("request size should never be zero");
+
+                        Some(ArrowFlightRequest {
+                            finalizers,
+                            metadata: builder.with_request_size(bytes_len),
+                            path,
+                            batch,
+                        })
+                    }
+                    Err(error) => {
+                        finalizers.update_status(EventStatus::Rejected);
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for ArrowFlightSink<S>
+where
+    S: Service<ArrowFlightRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
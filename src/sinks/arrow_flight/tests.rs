@@ -0,0 +1,132 @@
+use std::{collections::HashMap, sync::Arc};
+
+use arrow::{
+    array::{Array, StringArray},
+    datatypes::Schema,
+    record_batch::RecordBatch,
+};
+use arrow_flight::{utils::flight_data_to_arrow_batch, FlightData, FlightDescriptor};
+use bytes::{Buf, Bytes};
+use futures::StreamExt;
+use http::request::Parts;
+use prost::Message;
+use vector_core::event::{BatchNotifier, BatchStatus};
+
+use super::*;
+use crate::{
+    config::{SinkConfig as _, SinkContext},
+    sinks::util::test::build_test_server_generic,
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr, random_lines_with_stream,
+    },
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<ArrowFlightSinkConfig>();
+}
+
+fn config(addr: std::net::SocketAddr) -> ArrowFlightSinkConfig {
+    toml::from_str(&format!(
+        r#"
+        endpoint = "http://{}/"
+        path = "logs"
+
+        [headers]
+        database = "vector"
+        "#,
+        addr
+    ))
+    .unwrap()
+}
+
+/// Decodes the `FlightData` messages of a gRPC request body.
+fn decode_messages(mut body: Bytes) -> Vec<FlightData> {
+    let mut messages = Vec::new();
+    while body.has_remaining() {
+        // One byte for the compression flag, then four bytes for the length.
+        assert_eq!(body.get_u8(), 0);
+        let len = body.get_u32() as usize;
+        messages.push(FlightData::decode(body.split_to(len)).unwrap());
+    }
+    messages
+}
+
+#[tokio::test]
+async fn puts_record_batches() {
+    let in_addr = next_addr();
+    let cx = SinkContext::new_test();
+    let (sink, _) = config(in_addr).build(cx).await.unwrap();
+    let (rx, trigger, server) = build_test_server_generic(in_addr, || {
+        hyper::Response::builder()
+            .header("grpc-status", "0") // OK
+            .header("content-type", "application/grpc")
+            .body(tonic::body::empty_body())
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (input_lines, events) = random_lines_with_stream(8, 5, Some(batch));
+
+    run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = rx.collect::<Vec<(Parts, Bytes)>>().await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = requests.into_iter().next().unwrap();
+    assert_eq!(
+        parts.uri.path(),
+        "/arrow.flight.protocol.FlightService/DoPut"
+    );
+    assert_eq!(
+        parts.headers.get("database").unwrap().to_str().unwrap(),
+        "vector"
+    );
+
+    let messages = decode_messages(body);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[0].flight_descriptor,
+        Some(FlightDescriptor::new_path(vec!["logs".to_owned()]))
+    );
+    let schema = Arc::new(Schema::try_from(&messages[0]).unwrap());
+    let batch: RecordBatch =
+        flight_data_to_arrow_batch(&messages[1], Arc::clone(&schema), &HashMap::new()).unwrap();
+
+    let messages = batch
+        .column(schema.index_of("message").unwrap())
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    let output_lines = (0..messages.len())
+        .map(|index| messages.value(index).to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(input_lines, output_lines);
+}
+
+#[tokio::test]
+async fn rejects_on_permanent_error() {
+    let in_addr = next_addr();
+    let cx = SinkContext::new_test();
+    let (sink, _) = config(in_addr).build(cx).await.unwrap();
+    let (_rx, trigger, server) = build_test_server_generic(in_addr, move || {
+        hyper::Response::builder()
+            .header("grpc-status", "3") // invalid argument
+            .header("content-type", "application/grpc")
+            .body(tonic::body::empty_body())
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (_, events) = random_lines_with_stream(8, 5, Some(batch));
+
+    sink.run(events).await.expect("Running sink failed");
+
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
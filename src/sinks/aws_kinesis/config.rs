@@ -12,7 +12,10 @@ use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
     codecs::{Encoder, EncodingConfig},
     config::AcknowledgementsConfig,
-    sinks::util::{retries::RetryLogic, Compression, ServiceBuilderExt, TowerRequestConfig},
+    sinks::util::{
+        retries::RetryLogic, service::OrderedLanes, Compression, ServiceBuilderExt,
+        TowerRequestConfig,
+    },
     tls::TlsConfig,
 };
 
@@ -51,6 +54,16 @@ pub struct KinesisSinkBaseConfig {
     #[serde(default)]
     pub request: TowerRequestConfig,
 
+    /// The log field name whose value the events are kept in order by.
+    ///
+    /// The events with the same value are batched together, and their batches are sent one at a
+    /// time, in the order they're received in, so that the retries of a batch can't reorder it
+    /// with the next ones. This limits the throughput of each value to one request in flight, and
+    /// the events missing the field are sent without any ordering.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "user_id"))]
+    pub preserve_order_by: Option<String>,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
@@ -117,12 +130,25 @@ where
         _phantom: PhantomData,
     };
 
-    let sink = KinesisSink {
-        batch_settings,
-        service,
-        request_builder,
-        partition_key_field,
-        _phantom: PhantomData,
+    let preserve_order_by = config.preserve_order_by.clone();
+    let sink = if preserve_order_by.is_some() {
+        VectorSink::from_event_streamsink(KinesisSink {
+            batch_settings,
+            service: OrderedLanes::new(service),
+            request_builder,
+            partition_key_field,
+            preserve_order_by,
+            _phantom: PhantomData,
+        })
+    } else {
+        VectorSink::from_event_streamsink(KinesisSink {
+            batch_settings,
+            service,
+            request_builder,
+            partition_key_field,
+            preserve_order_by,
+            _phantom: PhantomData,
+        })
     };
-    Ok(VectorSink::from_event_streamsink(sink))
+    Ok(sink)
 }
@@ -54,6 +54,7 @@ async fn firehose_put_records() {
             retry_attempts: Some(0),
             ..Default::default()
         },
+        preserve_order_by: None,
         tls: None,
         auth: Default::default(),
         acknowledgements: Default::default(),
@@ -31,6 +31,7 @@ async fn check_batch_size() {
         encoding: JsonSerializerConfig::default().into(),
         compression: Compression::None,
         request: Default::default(),
+        preserve_order_by: None,
         tls: None,
         auth: Default::default(),
         acknowledgements: Default::default(),
@@ -60,6 +61,7 @@ async fn check_batch_events() {
         encoding: JsonSerializerConfig::default().into(),
         compression: Compression::None,
         request: Default::default(),
+        preserve_order_by: None,
         tls: None,
         auth: Default::default(),
         acknowledgements: Default::default(),
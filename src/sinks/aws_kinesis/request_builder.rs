@@ -27,6 +27,7 @@ pub struct KinesisRequestBuilder<R> {
 pub struct KinesisMetadata {
     pub finalizers: EventFinalizers,
     pub partition_key: String,
+    pub lane: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -100,6 +101,7 @@ where
         let kinesis_metadata = KinesisMetadata {
             finalizers: processed_event.event.take_finalizers(),
             partition_key: processed_event.metadata.partition_key,
+            lane: processed_event.metadata.lane,
         };
         let event = Event::from(processed_event.event);
         let builder = RequestMetadataBuilder::from_events(&event);
@@ -120,6 +122,7 @@ where
         KinesisRequest {
             key: KinesisKey {
                 partition_key: kinesis_metadata.partition_key.clone(),
+                lane: kinesis_metadata.lane,
             },
             record,
             finalizers: kinesis_metadata.finalizers,
@@ -16,7 +16,11 @@ use vector_core::{
 use crate::{
     event::{Event, LogEvent},
     internal_events::{AwsKinesisStreamNoPartitionKeyError, SinkRequestBuildError},
-    sinks::util::{processed_event::ProcessedEvent, SinkBuilderExt, StreamSink},
+    sinks::util::{
+        processed_event::ProcessedEvent,
+        service::{lane, Laned},
+        SinkBuilderExt, StreamSink,
+    },
 };
 
 use super::{
@@ -29,6 +33,8 @@ pub type KinesisProcessedEvent = ProcessedEvent<LogEvent, KinesisKey>;
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct KinesisKey {
     pub partition_key: String,
+    /// The lane the events are sent in order with, if any.
+    pub lane: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -37,6 +43,7 @@ pub struct KinesisSink<S, R> {
     pub service: S,
     pub request_builder: KinesisRequestBuilder<R>,
     pub partition_key_field: Option<String>,
+    pub preserve_order_by: Option<String>,
     pub _phantom: PhantomData<R>,
 }
 
@@ -52,12 +59,13 @@ where
         let request_builder_concurrency_limit = NonZeroUsize::new(50);
 
         let partition_key_field = self.partition_key_field.clone();
+        let preserve_order_by = self.preserve_order_by.clone();
 
         input
             .filter_map(|event| {
                 // Panic: This sink only accepts Logs, so this should never panic
                 let log = event.into_log();
-                let processed = process_log(log, &partition_key_field);
+                let processed = process_log(log, &partition_key_field, &preserve_order_by);
 
                 future::ready(processed)
             })
@@ -107,14 +115,16 @@ where
 }
 
 /// Returns a `KinesisProcessedEvent` containing the unmodified log event + metadata consisting of
-/// the partition key. The partition key is either generated from the provided partition_key_field
-/// or is generated randomly.
+/// the partition key and the lane. The partition key is either generated from the provided
+/// partition_key_field or is generated randomly, and the lane is given by the preserve_order_by
+/// field, if any.
 ///
 /// If the provided partition_key_field was not found in the log, `Error` `EventsDropped` internal
 /// events are emitted and None is returned.
 pub(crate) fn process_log(
     log: LogEvent,
     partition_key_field: &Option<String>,
+    preserve_order_by: &Option<String>,
 ) -> Option<KinesisProcessedEvent> {
     let partition_key = if let Some(partition_key_field) = partition_key_field {
         if let Some(v) = log.get(partition_key_field.as_str()) {
@@ -133,10 +143,16 @@ pub(crate) fn process_log(
     } else {
         partition_key.into_owned()
     };
+    let lane = preserve_order_by
+        .as_ref()
+        .and_then(|field| lane(&log, field));
 
     Some(KinesisProcessedEvent {
         event: log,
-        metadata: KinesisKey { partition_key },
+        metadata: KinesisKey {
+            partition_key,
+            lane,
+        },
     })
 }
 
@@ -164,9 +180,7 @@ where
 {
     fn clone(&self) -> Self {
         Self {
-            key: KinesisKey {
-                partition_key: self.key.partition_key.clone(),
-            },
+            key: self.key.clone(),
             events: self.events.to_vec(),
             metadata: self.metadata,
         }
@@ -182,6 +196,15 @@ where
     }
 }
 
+impl<R> Laned for BatchKinesisRequest<R>
+where
+    R: Record + Clone,
+{
+    fn lane(&self) -> Option<u64> {
+        self.key.lane
+    }
+}
+
 impl<R> MetaDescriptive for BatchKinesisRequest<R>
 where
     R: Record + Clone,
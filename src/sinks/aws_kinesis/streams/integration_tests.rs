@@ -39,6 +39,7 @@ fn kinesis_address() -> String {
 //        encoding: TextSerializerConfig::default().into(),
 //        compression: Compression::None,
 //        request: Default::default(),
+//        preserve_order_by: None,
 //        tls: Default::default(),
 //        auth: Default::default(),
 //        acknowledgements: Default::default(),
@@ -95,6 +96,7 @@ async fn kinesis_put_records_without_partition_key() {
         encoding: TextSerializerConfig::default().into(),
         compression: Compression::None,
         request: Default::default(),
+        preserve_order_by: None,
         tls: Default::default(),
         auth: Default::default(),
         acknowledgements: Default::default(),
@@ -1,10 +1,7 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 use aws_sdk_s3::Client as S3Client;
-use codecs::{
-    encoding::{Framer, FramingConfig},
-    TextSerializerConfig,
-};
+use codecs::{encoding::FramingConfig, TextSerializerConfig};
 use tower::ServiceBuilder;
 use vector_config::configurable_component;
 use vector_core::sink::VectorSink;
@@ -12,7 +9,7 @@ use vector_core::sink::VectorSink;
 use super::sink::S3RequestOptions;
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::EncodingConfigWithFraming,
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
         SinkContext,
@@ -26,6 +23,7 @@ use crate::{
             sink::S3Sink,
         },
         util::{
+            parquet::{BatchSerializerConfig, ObjectEncoder},
             BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
             TowerRequestConfig,
         },
@@ -51,6 +49,10 @@ pub struct S3SinkConfig {
     /// Prefixes are useful for partitioning objects, such as by creating an object key that
     /// stores objects under a particular "directory". If using a prefix for this purpose, it must end
     /// in `/` to act as a directory path. A trailing `/` is **not** automatically added.
+    ///
+    /// Time specifiers, such as `%F` or `%H`, are rendered using the timestamp of the event, or the
+    /// current time if the event has none, so events are written to the partition of the time
+    /// period they belong to. See `late_event_window_secs` for how late events are handled.
     #[serde(default = "default_key_prefix")]
     #[configurable(metadata(docs::templateable))]
     #[configurable(metadata(docs::examples = "date=%F/hour=%H"))]
@@ -58,6 +60,16 @@ pub struct S3SinkConfig {
     #[configurable(metadata(docs::examples = "application_id={{ application_id }}/date=%F"))]
     pub key_prefix: String,
 
+    /// The maximum age of an event, compared to the current time, for it to still be written to
+    /// the partition of its own time period.
+    ///
+    /// Events older than this are written to the partition of the current time period instead.
+    /// By default, events are always written to the partition of their own time period, however
+    /// late they arrive.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 3600))]
+    pub late_event_window_secs: Option<u64>,
+
     /// The timestamp format for the time component of the object key.
     ///
     /// By default, object keys are appended with a timestamp that reflects when the objects are
@@ -103,6 +115,9 @@ pub struct S3SinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     /// Compression configuration.
     ///
     /// All compression algorithms use the default compression level unless otherwise specified.
@@ -150,12 +165,14 @@ impl GenerateConfig for S3SinkConfig {
         toml::Value::try_from(Self {
             bucket: "".to_owned(),
             key_prefix: default_key_prefix(),
+            late_event_window_secs: None,
             filename_time_format: default_filename_time_format(),
             filename_append_uuid: true,
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -206,20 +223,25 @@ impl S3SinkConfig {
             .cloned()
             .map(|ssekms_key_id| Template::try_from(ssekms_key_id.as_str()))
             .transpose()?;
-        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
+        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id)
+            .with_late_event_window(self.late_event_window_secs.map(Duration::from_secs));
 
-        let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = ObjectEncoder::new(&self.encoding, self.batch_encoding.as_ref())?;
+        let mut api_options = self.options.clone();
+        if self.batch_encoding.is_some() {
+            api_options
+                .content_type
+                .get_or_insert_with(|| encoder.content_type().to_owned());
+        }
 
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
-            api_options: self.options.clone(),
+            api_options,
             filename_extension: self.filename_extension.clone(),
             filename_time_format: self.filename_time_format.clone(),
             filename_append_uuid: self.filename_append_uuid,
-            encoder: (transformer, encoder),
-            compression: self.compression,
+            compression: encoder.compression(self.compression),
+            encoder,
         };
 
         let sink = S3Sink::new(service, request_options, partitioner, batch_settings);
@@ -386,12 +386,14 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
     S3SinkConfig {
         bucket: bucket.to_string(),
         key_prefix: random_string(10) + "/date=%F",
+        late_event_window_secs: None,
         filename_time_format: default_filename_time_format(),
         filename_append_uuid: true,
         filename_extension: None,
         options: S3Options::default(),
         region: RegionOrEndpoint::with_both("minio", s3_address()),
         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+        batch_encoding: None,
         compression: Compression::None,
         batch,
         request: TowerRequestConfig::default(),
@@ -1,22 +1,24 @@
 use std::sync::Arc;
 
 use azure_storage_blobs::prelude::*;
-use codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+use codecs::{JsonSerializerConfig, NewlineDelimitedEncoderConfig};
 use tower::ServiceBuilder;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 use super::request_builder::AzureBlobRequestOptions;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::EncodingConfigWithFraming,
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
             self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
         },
         util::{
-            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression, ServiceBuilderExt, TowerRequestConfig,
+            parquet::{BatchSerializerConfig, ObjectEncoder},
+            partitioner::KeyPartitioner,
+            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
+            TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -118,6 +120,9 @@ pub struct AzureBlobSinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -154,6 +159,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -217,16 +223,14 @@ impl AzureBlobSinkConfig {
             .blob_append_uuid
             .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
 
-        let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = ObjectEncoder::new(&self.encoding, self.batch_encoding.as_ref())?;
 
         let request_options = AzureBlobRequestOptions {
             container_name: self.container_name.clone(),
             blob_time_format,
             blob_append_uuid,
-            encoder: (transformer, encoder),
-            compression: self.compression,
+            compression: encoder.compression(self.compression),
+            encoder,
         };
 
         let sink = AzureBlobSink::new(
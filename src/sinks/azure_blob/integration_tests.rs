@@ -227,6 +227,7 @@ impl AzureBlobSinkConfig {
                 blob_time_format: None,
                 blob_append_uuid: None,
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                batch_encoding: None,
                 compression: Compression::None,
                 batch: Default::default(),
                 request: TowerRequestConfig::default(),
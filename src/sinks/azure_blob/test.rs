@@ -10,7 +10,11 @@ use super::config::AzureBlobSinkConfig;
 use super::request_builder::AzureBlobRequestOptions;
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
-use crate::sinks::util::{request_builder::RequestBuilder, Compression};
+use crate::sinks::util::{
+    parquet::{BatchSerializerConfig, ObjectEncoder},
+    request_builder::RequestBuilder,
+    Compression,
+};
 use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
 
 fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
@@ -23,6 +27,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
         encoding,
+        batch_encoding: None,
         compression: Compression::gzip_default(),
         batch: Default::default(),
         request: Default::default(),
@@ -58,13 +63,13 @@ fn azure_blob_build_request_without_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
             ),
-        ),
+        )),
         compression,
     };
 
@@ -103,13 +108,13 @@ fn azure_blob_build_request_with_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
             ),
-        ),
+        )),
         compression,
     };
     let (metadata, request_metadata_builder, _events) =
@@ -147,13 +152,13 @@ fn azure_blob_build_request_with_time_format() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
             ),
-        ),
+        )),
         compression,
     };
 
@@ -195,13 +200,13 @@ fn azure_blob_build_request_with_uuid() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
             ),
-        ),
+        )),
         compression,
     };
 
@@ -216,3 +221,43 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[test]
+fn azure_blob_build_request_with_parquet() {
+    let log = Event::Log(LogEvent::from("test message"));
+    let sink_config = AzureBlobSinkConfig {
+        blob_prefix: "blob".try_into().unwrap(),
+        container_name: String::from("logs"),
+        blob_time_format: Some(String::from("")),
+        blob_append_uuid: Some(false),
+        batch_encoding: Some(BatchSerializerConfig::Parquet(Default::default())),
+        ..default_config((None::<FramingConfig>, TextSerializerConfig::default()).into())
+    };
+
+    let key = sink_config
+        .key_partitioner()
+        .unwrap()
+        .partition(&log)
+        .expect("key wasn't provided");
+
+    let encoder =
+        ObjectEncoder::new(&sink_config.encoding, sink_config.batch_encoding.as_ref()).unwrap();
+    let request_options = AzureBlobRequestOptions {
+        container_name: sink_config.container_name.clone(),
+        blob_time_format: String::from(""),
+        blob_append_uuid: false,
+        compression: encoder.compression(sink_config.compression),
+        encoder,
+    };
+    let (metadata, request_metadata_builder, events) =
+        request_options.split_input((key, vec![log]));
+
+    let payload = request_options.encode_events(events).unwrap();
+    let request_metadata = request_metadata_builder.build(&payload);
+    let request = request_options.build_request(metadata, request_metadata, payload);
+
+    assert_eq!(request.metadata.partition_key, "blob.parquet".to_string());
+    assert_eq!(request.content_encoding, None);
+    assert_eq!(request.content_type, "application/vnd.apache.parquet");
+    assert_eq!(&request.blob_data[..4], b"PAR1");
+}
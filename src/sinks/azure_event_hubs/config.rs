@@ -0,0 +1,86 @@
+//! Configuration functionality for the `Azure Event Hubs` sink.
+use crate::{
+    azure_event_hubs::{EventHub, EventHubsConnectionConfig},
+    codecs::EncodingConfig,
+    config::{DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{Healthcheck, VectorSink},
+    template::Template,
+};
+use futures::FutureExt;
+use vector_config::configurable_component;
+use vector_core::config::AcknowledgementsConfig;
+
+use super::sink::EventHubsSink;
+
+/// Configuration for the `azure_event_hubs` sink.
+#[configurable_component(sink("azure_event_hubs"))]
+#[derive(Clone, Debug)]
+pub struct AzureEventHubsSinkConfig {
+    #[serde(flatten)]
+    pub(crate) connection: EventHubsConnectionConfig,
+
+    /// The partition to send all the events to.
+    ///
+    /// By default, events are distributed between the partitions by Event Hubs.
+    #[configurable(metadata(docs::examples = "0"))]
+    pub(crate) partition_id: Option<String>,
+
+    /// The partition key of the events.
+    ///
+    /// Events with the same partition key are sent to the same partition, in order. Can't be set
+    /// along with `partition_id`.
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    pub(crate) partition_key: Option<Template>,
+
+    #[configurable(derived)]
+    pub(crate) encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(crate) acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureEventHubsSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"connection_string = "Endpoint=sb://my-namespace.servicebus.windows.net/;SharedAccessKeyName=my-policy;SharedAccessKey=keybase64encoded"
+            event_hub_name = "my-event-hub"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for AzureEventHubsSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = EventHubsSink::new(self.clone())?;
+        let hc = healthcheck(self.connection.event_hub()?).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), hc))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+async fn healthcheck(event_hub: EventHub) -> crate::Result<()> {
+    let container_id = format!("vector-{}", uuid::Uuid::new_v4());
+    let (mut connection, mut session) = event_hub.connect(&container_id).await?;
+    let _ = session.end().await;
+    let _ = connection.close().await;
+    Ok(())
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureEventHubsSinkConfig>();
+}
@@ -0,0 +1,16 @@
+//! `Azure Event Hubs` sink.
+//! Sends events to an event hub over AMQP 1.0.
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use config::AzureEventHubsSinkConfig;
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`partition_id` and `partition_key` can't be set at the same time"))]
+    PartitionIdAndKey,
+}
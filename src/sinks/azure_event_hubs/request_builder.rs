@@ -0,0 +1,72 @@
+//! Request builder for the `Azure Event Hubs` sink.
+//! Responsible for taking the event (which includes the rendered partition key) and turning it
+//! into the raw bytes and other data needed to send the request to Event Hubs.
+use crate::{
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
+        RequestBuilder,
+    },
+};
+use bytes::Bytes;
+use std::io;
+use vector_common::{
+    finalization::{EventFinalizers, Finalizable},
+    request_metadata::RequestMetadata,
+};
+
+use super::{encoder::EventHubsEncoder, service::EventHubsRequest, sink::EventHubsEvent};
+
+pub(super) struct EventHubsMetadata {
+    partition_key: Option<String>,
+    finalizers: EventFinalizers,
+}
+
+pub(super) struct EventHubsRequestBuilder {
+    pub(super) encoder: EventHubsEncoder,
+}
+
+impl RequestBuilder<EventHubsEvent> for EventHubsRequestBuilder {
+    type Metadata = EventHubsMetadata;
+    type Events = Event;
+    type Encoder = EventHubsEncoder;
+    type Payload = Bytes;
+    type Request = EventHubsRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut input: EventHubsEvent,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let builder = RequestMetadataBuilder::from_events(&input);
+
+        let metadata = EventHubsMetadata {
+            partition_key: input.partition_key,
+            finalizers: input.event.take_finalizers(),
+        };
+
+        (metadata, builder, input.event)
+    }
+
+    fn build_request(
+        &self,
+        event_hubs_metadata: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        EventHubsRequest {
+            body: payload.into_payload(),
+            partition_key: event_hubs_metadata.partition_key,
+            finalizers: event_hubs_metadata.finalizers,
+            metadata,
+        }
+    }
+}
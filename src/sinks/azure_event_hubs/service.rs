@@ -0,0 +1,198 @@
+//! The main tower service that takes the request created by the request builder
+//! and sends it to Event Hubs.
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use fe2o3_amqp::{
+    connection::ConnectionHandle,
+    link::{SendError, SenderAttachError},
+    session::SessionHandle,
+    types::{
+        messaging::{annotations::OwnedKey, Data, Message, MessageAnnotations, Outcome},
+        primitives::{Binary, OrderedMap, Symbol, Value},
+    },
+    Sender,
+};
+use futures::future::BoxFuture;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tower::Service;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_core::stream::DriverResponse;
+
+use crate::{
+    azure_event_hubs::{EventHub, EventHubsError},
+    internal_events::AzureEventHubsSendError,
+};
+
+const PARTITION_KEY_ANNOTATION: &str = "x-opt-partition-key";
+
+/// The request contains the data to send to Event Hubs together with the partition key of the
+/// event.
+pub(super) struct EventHubsRequest {
+    pub(super) body: Bytes,
+    pub(super) partition_key: Option<String>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for EventHubsRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for EventHubsRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+/// A successful response from Event Hubs.
+pub(super) struct EventHubsResponse {
+    byte_size: usize,
+}
+
+impl DriverResponse for EventHubsResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(1, self.byte_size)
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum EventHubsSinkError {
+    #[snafu(display("Failed to connect to Event Hubs: {}", source))]
+    Connect { source: EventHubsError },
+
+    #[snafu(display("Failed to attach the sender link: {}", source))]
+    Attach { source: SenderAttachError },
+
+    #[snafu(display("Failed to send the event: {}", source))]
+    Send { source: SendError },
+
+    #[snafu(display("The event was not accepted: {}", outcome))]
+    NotAccepted { outcome: String },
+}
+
+/// The link events are sent through, along with the connection and session it was attached on.
+struct Link {
+    _connection: ConnectionHandle<()>,
+    _session: SessionHandle<()>,
+    sender: Sender,
+}
+
+/// The tower service that handles the actual sending of events to Event Hubs.
+///
+/// The link is attached on the first request, and attached again on the request following a
+/// failure, so that the sink recovers from dropped connections.
+#[derive(Clone)]
+pub(super) struct EventHubsService {
+    event_hub: EventHub,
+    address: String,
+    link: Arc<Mutex<Option<Link>>>,
+}
+
+impl EventHubsService {
+    pub(super) fn new(event_hub: EventHub, partition_id: Option<String>) -> Self {
+        let address = match partition_id {
+            Some(partition_id) => format!("{}/Partitions/{}", event_hub.name, partition_id),
+            None => event_hub.name.clone(),
+        };
+        Self {
+            event_hub,
+            address,
+            link: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+async fn attach(event_hub: &EventHub, address: &str) -> Result<Link, EventHubsSinkError> {
+    let container_id = format!("vector-{}", uuid::Uuid::new_v4());
+    let (connection, mut session) = event_hub
+        .connect(&container_id)
+        .await
+        .context(ConnectSnafu)?;
+    let sender = Sender::attach(&mut session, format!("{}-sender", container_id), address)
+        .await
+        .context(AttachSnafu)?;
+    Ok(Link {
+        _connection: connection,
+        _session: session,
+        sender,
+    })
+}
+
+fn message(body: Bytes, partition_key: Option<String>) -> Message<Data> {
+    let mut message = Message::builder().data(Binary::from(body.to_vec())).build();
+    if let Some(partition_key) = partition_key {
+        let mut annotations = OrderedMap::new();
+        annotations.insert(
+            OwnedKey::Symbol(Symbol::from(PARTITION_KEY_ANNOTATION)),
+            Value::String(partition_key),
+        );
+        message.message_annotations = Some(MessageAnnotations(annotations));
+    }
+    message
+}
+
+impl Service<EventHubsRequest> for EventHubsService {
+    type Response = EventHubsResponse;
+
+    type Error = EventHubsSinkError;
+
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: EventHubsRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let byte_size = req.body.len();
+            let mut link = this.link.lock().await;
+            if link.is_none() {
+                match attach(&this.event_hub, &this.address).await {
+                    Ok(attached) => *link = Some(attached),
+                    Err(error) => {
+                        emit!(AzureEventHubsSendError { error: &error });
+                        return Err(error);
+                    }
+                }
+            }
+
+            let sender = &mut link.as_mut().expect("link is attached").sender;
+            let result = match sender.send(message(req.body, req.partition_key)).await {
+                Ok(Outcome::Accepted(_)) => Ok(EventHubsResponse { byte_size }),
+                Ok(outcome) => Err(EventHubsSinkError::NotAccepted {
+                    outcome: format!("{:?}", outcome),
+                }),
+                Err(source) => {
+                    // The link is attached again on the next request.
+                    *link = None;
+                    Err(EventHubsSinkError::Send { source })
+                }
+            };
+            if let Err(error) = &result {
+                emit!(AzureEventHubsSendError { error });
+            }
+            result
+        })
+    }
+}
@@ -0,0 +1,129 @@
+//! The sink for the `Azure Event Hubs` sink that wires together the main stream that takes the
+//! event and sends it to Event Hubs.
+use crate::{
+    codecs::Transformer, event::Event, internal_events::TemplateRenderingError,
+    sinks::util::builder::SinkBuilderExt, template::Template,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use futures_util::stream::BoxStream;
+use tower::ServiceBuilder;
+use vector_buffers::EventCount;
+use vector_core::{sink::StreamSink, ByteSizeOf, EstimatedJsonEncodedSizeOf};
+
+use super::{
+    config::AzureEventHubsSinkConfig, encoder::EventHubsEncoder,
+    request_builder::EventHubsRequestBuilder, service::EventHubsService, BuildError,
+};
+
+/// Stores the event together with its rendered partition key.
+/// This event needs to be created prior to building the request so we can filter out
+/// any events that error whilst rendering the template.
+pub(super) struct EventHubsEvent {
+    pub(super) event: Event,
+    pub(super) partition_key: Option<String>,
+}
+
+impl EventCount for EventHubsEvent {
+    fn event_count(&self) -> usize {
+        // An EventHubsEvent represents one event.
+        1
+    }
+}
+
+impl ByteSizeOf for EventHubsEvent {
+    fn allocated_bytes(&self) -> usize {
+        self.event.size_of()
+    }
+}
+
+impl EstimatedJsonEncodedSizeOf for EventHubsEvent {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        self.event.estimated_json_encoded_size_of()
+    }
+}
+
+pub(super) struct EventHubsSink {
+    service: EventHubsService,
+    partition_key: Option<Template>,
+    transformer: Transformer,
+    encoder: crate::codecs::Encoder<()>,
+}
+
+impl EventHubsSink {
+    pub(super) fn new(config: AzureEventHubsSinkConfig) -> crate::Result<Self> {
+        if config.partition_id.is_some() && config.partition_key.is_some() {
+            return Err(BuildError::PartitionIdAndKey.into());
+        }
+
+        let event_hub = config.connection.event_hub()?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build()?;
+        let encoder = crate::codecs::Encoder::<()>::new(serializer);
+
+        Ok(EventHubsSink {
+            service: EventHubsService::new(event_hub, config.partition_id),
+            partition_key: config.partition_key,
+            transformer,
+            encoder,
+        })
+    }
+
+    /// Renders the partition key of an event.
+    /// Returns None if there is an error whilst rendering.
+    fn make_event_hubs_event(&self, event: Event) -> Option<EventHubsEvent> {
+        let partition_key = match &self.partition_key {
+            None => None,
+            Some(key) => Some(
+                key.render_string(&event)
+                    .map_err(|missing_keys| {
+                        emit!(TemplateRenderingError {
+                            error: missing_keys,
+                            field: Some("partition_key"),
+                            drop_event: true,
+                        })
+                    })
+                    .ok()?,
+            ),
+        };
+
+        Some(EventHubsEvent {
+            event,
+            partition_key,
+        })
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request_builder = EventHubsRequestBuilder {
+            encoder: EventHubsEncoder {
+                encoder: self.encoder.clone(),
+                transformer: self.transformer.clone(),
+            },
+        };
+        let service = ServiceBuilder::new().service(self.service.clone());
+
+        input
+            .filter_map(|event| std::future::ready(self.make_event_hubs_event(event)))
+            .request_builder(None, request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(e) => {
+                        error!("Failed to build Event Hubs request: {:?}.", e);
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(service)
+            .protocol("amqp_1_0")
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for EventHubsSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
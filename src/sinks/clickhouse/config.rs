@@ -14,7 +14,10 @@ use crate::{
     tls::TlsConfig,
 };
 
-use super::http_sink::build_http_sink;
+use super::{
+    http_sink::build_http_sink,
+    native_sink::{build_native_sink, NativeProtocolConfig},
+};
 
 /// Configuration for the `clickhouse` sink.
 #[configurable_component(sink("clickhouse"))]
@@ -22,8 +25,11 @@ use super::http_sink::build_http_sink;
 #[serde(deny_unknown_fields)]
 pub struct ClickhouseConfig {
     /// The endpoint of the ClickHouse server.
+    ///
+    /// When using the native protocol, the endpoint must use the `tcp` scheme.
     #[serde(alias = "host")]
     #[configurable(metadata(docs::examples = "http://localhost:8123"))]
+    #[configurable(metadata(docs::examples = "tcp://localhost:9000"))]
     pub endpoint: UriSerde,
 
     /// The table that data will be inserted into.
@@ -42,6 +48,9 @@ pub struct ClickhouseConfig {
     #[serde(default)]
     pub date_time_best_effort: bool,
 
+    #[configurable(derived)]
+    pub native: Option<NativeProtocolConfig>,
+
     #[configurable(derived)]
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -81,9 +90,10 @@ impl_generate_config_from_default!(ClickhouseConfig);
 #[async_trait::async_trait]
 impl SinkConfig for ClickhouseConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        // later we can build different sink(http, native) here
-        // according to the clickhouseConfig
-        build_http_sink(self, cx).await
+        match &self.native {
+            Some(native) => build_native_sink(self, native),
+            None => build_http_sink(self, cx).await,
+        }
     }
 
     fn input(&self) -> Input {
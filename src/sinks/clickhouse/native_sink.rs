@@ -0,0 +1,841 @@
+//! Sending data to ClickHouse over its native TCP protocol.
+//!
+//! Events are converted into columnar blocks, according to the configured column types, which are
+//! then inserted by [`klickhouse`][klickhouse].
+//!
+//! [klickhouse]: https://docs.rs/klickhouse
+use std::{
+    borrow::Cow,
+    num::NonZeroUsize,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt};
+use indexmap::IndexMap;
+use klickhouse::{
+    Client, ClientOptions, DateTime, KlickhouseError as NativeError, Row, Type as NativeType, Tz,
+    Value as NativeValue,
+};
+use tokio::sync::Mutex;
+use tower::{Service, ServiceBuilder};
+use url::Url;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_config::configurable_component;
+use vector_core::{
+    event::{Event, LogEvent, Value},
+    sink::StreamSink,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::ClickhouseConfig;
+use crate::{
+    codecs::Transformer,
+    http::{Auth, MaybeAuth},
+    internal_events::ClickhouseColumnConversionError,
+    sinks::{
+        util::{
+            builder::SinkBuilderExt, metadata::RequestMetadataBuilder, retries::RetryLogic,
+            service::Svc, ServiceBuilderExt, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+};
+
+/// Configuration for sending data over the ClickHouse native protocol.
+///
+/// When set, the `endpoint` must use the `tcp` scheme, such as `tcp://localhost:9000`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NativeProtocolConfig {
+    /// The columns of the table that data is inserted into.
+    ///
+    /// Each column is filled with the value of the event field of the same name, unless a `field`
+    /// is specified. Columns with a `Nullable` type are set to `NULL` when the field is missing,
+    /// and other columns are set to the default value of their type.
+    #[configurable(metadata(docs::additional_props_description = "A column of the table."))]
+    pub columns: IndexMap<String, NativeColumnConfig>,
+}
+
+/// A column of the table that data is inserted into.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NativeColumnConfig {
+    /// The ClickHouse type of the column.
+    ///
+    /// The supported types are `String`, `Int8`, `Int16`, `Int32`, `Int64`, `UInt8`, `UInt16`,
+    /// `UInt32`, `UInt64`, `Float32`, `Float64`, `DateTime`, and `DateTime64(precision)`, along
+    /// with `Nullable` versions of all of them.
+    #[serde(rename = "type")]
+    #[configurable(metadata(docs::examples = "Nullable(String)"))]
+    #[configurable(metadata(docs::examples = "DateTime64(3)"))]
+    pub column_type: String,
+
+    /// The event field used to fill the column.
+    ///
+    /// Defaults to the name of the column.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub field: Option<String>,
+}
+
+/// The ClickHouse types that event fields can be converted to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ScalarType {
+    String,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    DateTime,
+    DateTime64(usize),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct ColumnType {
+    scalar: ScalarType,
+    nullable: bool,
+}
+
+impl FromStr for ScalarType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let scalar = match s {
+            "String" => Self::String,
+            "Int8" => Self::Int8,
+            "Int16" => Self::Int16,
+            "Int32" => Self::Int32,
+            "Int64" => Self::Int64,
+            "UInt8" => Self::UInt8,
+            "UInt16" => Self::UInt16,
+            "UInt32" => Self::UInt32,
+            "UInt64" => Self::UInt64,
+            "Float32" => Self::Float32,
+            "Float64" => Self::Float64,
+            "DateTime" => Self::DateTime,
+            _ => {
+                let precision = s
+                    .strip_prefix("DateTime64(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .ok_or_else(|| format!("Unsupported column type {:?}.", s))?;
+                match precision.trim().parse() {
+                    Ok(precision) if precision <= 9 => Self::DateTime64(precision),
+                    _ => return Err(format!("Invalid precision in column type {:?}.", s)),
+                }
+            }
+        };
+        Ok(scalar)
+    }
+}
+
+impl FromStr for ColumnType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s
+            .strip_prefix("Nullable(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            Some(inner) => Ok(Self {
+                scalar: inner.trim().parse()?,
+                nullable: true,
+            }),
+            None => Ok(Self {
+                scalar: s.parse()?,
+                nullable: false,
+            }),
+        }
+    }
+}
+
+fn integer<T: TryFrom<i64>>(value: &Value) -> Result<T, String> {
+    let integer = match value {
+        Value::Integer(integer) => *integer,
+        Value::Boolean(boolean) => i64::from(*boolean),
+        value => return Err(format!("Expected an integer, found {}.", value.kind_str())),
+    };
+    T::try_from(integer).map_err(|_| format!("Integer {} is out of range.", integer))
+}
+
+fn float(value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Integer(integer) => Ok(*integer as f64),
+        Value::Float(float) => Ok(float.into_inner()),
+        value => Err(format!("Expected a float, found {}.", value.kind_str())),
+    }
+}
+
+fn string(value: &Value) -> String {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Object(_) | Value::Array(_) => {
+            serde_json::to_string(value).expect("values are valid JSON")
+        }
+        value => value.to_string_lossy().into_owned(),
+    }
+}
+
+/// Returns the timestamp as a number of the given fractions of a second since the Unix epoch.
+fn timestamp(value: &Value, fractions_per_second: i64) -> Result<i64, String> {
+    let (seconds, nanos) = match value {
+        Value::Timestamp(timestamp) => (timestamp.timestamp(), timestamp.timestamp_subsec_nanos()),
+        Value::Integer(seconds) => (*seconds, 0),
+        value => return Err(format!("Expected a timestamp, found {}.", value.kind_str())),
+    };
+
+    seconds
+        .checked_mul(fractions_per_second)
+        .and_then(|fractions| {
+            fractions.checked_add(i64::from(nanos) * fractions_per_second / 1_000_000_000)
+        })
+        .ok_or_else(|| format!("Timestamp {} is out of range.", seconds))
+}
+
+/// Returns the timestamp as an unsigned number of the given fractions of a second since the Unix
+/// epoch, as timestamps before it can't be stored.
+fn unsigned_timestamp<T: TryFrom<i64>>(
+    value: &Value,
+    fractions_per_second: i64,
+) -> Result<T, String> {
+    let fractions = timestamp(value, fractions_per_second)?;
+    T::try_from(fractions).map_err(|_| format!("Timestamp {} is out of range.", fractions))
+}
+
+impl ScalarType {
+    /// Converts the value of an event field to this type.
+    fn convert(self, value: &Value) -> Result<NativeValue, String> {
+        let native = match self {
+            Self::String => NativeValue::String(string(value)),
+            Self::Int8 => NativeValue::Int8(integer(value)?),
+            Self::Int16 => NativeValue::Int16(integer(value)?),
+            Self::Int32 => NativeValue::Int32(integer(value)?),
+            Self::Int64 => NativeValue::Int64(integer(value)?),
+            Self::UInt8 => NativeValue::UInt8(integer(value)?),
+            Self::UInt16 => NativeValue::UInt16(integer(value)?),
+            Self::UInt32 => NativeValue::UInt32(integer(value)?),
+            Self::UInt64 => NativeValue::UInt64(integer(value)?),
+            Self::Float32 => NativeValue::Float32((float(value)? as f32).to_bits()),
+            Self::Float64 => NativeValue::Float64(float(value)?.to_bits()),
+            Self::DateTime => {
+                NativeValue::DateTime(DateTime(Tz::UTC, unsigned_timestamp(value, 1)?))
+            }
+            Self::DateTime64(precision) => {
+                let fractions = 10_i64.pow(precision as u32);
+                NativeValue::DateTime64(Tz::UTC, precision, unsigned_timestamp(value, fractions)?)
+            }
+        };
+        Ok(native)
+    }
+
+    /// Returns the default value of this type, used to fill the columns of missing fields.
+    fn default_value(self) -> NativeValue {
+        match self {
+            Self::String => NativeValue::String(String::new()),
+            Self::Int8 => NativeValue::Int8(0),
+            Self::Int16 => NativeValue::Int16(0),
+            Self::Int32 => NativeValue::Int32(0),
+            Self::Int64 => NativeValue::Int64(0),
+            Self::UInt8 => NativeValue::UInt8(0),
+            Self::UInt16 => NativeValue::UInt16(0),
+            Self::UInt32 => NativeValue::UInt32(0),
+            Self::UInt64 => NativeValue::UInt64(0),
+            Self::Float32 => NativeValue::Float32(0_f32.to_bits()),
+            Self::Float64 => NativeValue::Float64(0_f64.to_bits()),
+            Self::DateTime => NativeValue::DateTime(DateTime(Tz::UTC, 0)),
+            Self::DateTime64(precision) => NativeValue::DateTime64(Tz::UTC, precision, 0),
+        }
+    }
+}
+
+impl ColumnType {
+    /// Converts the value of an event field to the type of the column.
+    fn convert(self, value: Option<&Value>) -> Result<NativeValue, String> {
+        match value.filter(|value| !matches!(value, Value::Null)) {
+            Some(value) => self.scalar.convert(value),
+            None if self.nullable => Ok(NativeValue::Null),
+            None => Ok(self.scalar.default_value()),
+        }
+    }
+}
+
+/// A column, along with the event field used to fill it.
+#[derive(Clone, Debug)]
+struct NativeColumn {
+    name: String,
+    field: String,
+    column_type: ColumnType,
+}
+
+#[derive(Clone)]
+struct NativeRow(Vec<NativeValue>);
+
+/// A row being inserted, along with the columns of its values.
+struct InsertedRow {
+    columns: Arc<Vec<NativeColumn>>,
+    values: Vec<NativeValue>,
+}
+
+/// Sets the time zone of a timestamp to the one of its column, which ClickHouse requires to match.
+///
+/// The timestamps are counted from the Unix epoch, so they don't depend on the time zone.
+fn with_time_zone(value: NativeValue, column_type: Option<&NativeType>) -> NativeValue {
+    let column_type = match column_type {
+        Some(NativeType::Nullable(inner)) => Some(inner.as_ref()),
+        column_type => column_type,
+    };
+    match (value, column_type) {
+        (NativeValue::DateTime(DateTime(_, seconds)), Some(NativeType::DateTime(tz))) => {
+            NativeValue::DateTime(DateTime(*tz, seconds))
+        }
+        (NativeValue::DateTime64(_, precision, fractions), Some(NativeType::DateTime64(_, tz))) => {
+            NativeValue::DateTime64(*tz, precision, fractions)
+        }
+        (value, _) => value,
+    }
+}
+
+impl Row for InsertedRow {
+    const COLUMN_COUNT: Option<usize> = None;
+
+    fn column_names() -> Option<Vec<Cow<'static, str>>> {
+        None
+    }
+
+    fn deserialize_row(_map: Vec<(&str, &NativeType, NativeValue)>) -> klickhouse::Result<Self> {
+        Err(NativeError::DeserializeError(
+            "Rows are only inserted.".to_owned(),
+        ))
+    }
+
+    /// Returns the values of the row, given the types of the columns sent by the server, which
+    /// are in the order of the columns of the insert query.
+    fn serialize_row(
+        self,
+        type_hints: &[&NativeType],
+    ) -> klickhouse::Result<Vec<(Cow<'static, str>, NativeValue)>> {
+        Ok(self
+            .columns
+            .iter()
+            .zip(self.values)
+            .enumerate()
+            .map(|(index, (column, value))| {
+                (
+                    Cow::Owned(column.name.clone()),
+                    with_time_zone(value, type_hints.get(index).copied()),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Builds rows from events, according to the columns of the table.
+struct RowEncoder {
+    columns: Arc<Vec<NativeColumn>>,
+    transformer: Transformer,
+}
+
+impl RowEncoder {
+    fn encode(&self, log: &LogEvent) -> Result<NativeRow, (&str, String)> {
+        self.columns
+            .iter()
+            .map(|column| {
+                column
+                    .column_type
+                    .convert(log.get(column.field.as_str()))
+                    .map_err(|error| (column.name.as_str(), error))
+            })
+            .collect::<Result<_, _>>()
+            .map(NativeRow)
+    }
+}
+
+#[derive(Clone)]
+struct NativeRequest {
+    rows: Arc<Vec<NativeRow>>,
+    finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl Finalizable for NativeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for NativeRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+struct NativeResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for NativeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+}
+
+/// Connects to the server, with the address and the credentials of the sink.
+#[derive(Clone, Debug)]
+struct NativeConnector {
+    address: String,
+    options: ClientOptions,
+}
+
+impl NativeConnector {
+    async fn connect(&self) -> Result<Client, NativeError> {
+        Client::connect(self.address.as_str(), self.options.clone()).await
+    }
+}
+
+#[derive(Clone)]
+struct NativeService {
+    connector: NativeConnector,
+    /// The connection shared by the requests, which is replaced once it's closed.
+    client: Arc<Mutex<Option<Client>>>,
+    query: String,
+    columns: Arc<Vec<NativeColumn>>,
+}
+
+impl NativeService {
+    async fn client(&self) -> Result<Client, NativeError> {
+        let mut client = self.client.lock().await;
+        match client.as_ref() {
+            Some(client) if !client.is_closed() => Ok(client.clone()),
+            _ => {
+                let connected = self.connector.connect().await?;
+                *client = Some(connected.clone());
+                Ok(connected)
+            }
+        }
+    }
+}
+
+impl Service<NativeRequest> for NativeService {
+    type Response = NativeResponse;
+    type Error = NativeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: NativeRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let rows = request
+                .rows
+                .iter()
+                .map(|row| InsertedRow {
+                    columns: Arc::clone(&service.columns),
+                    values: row.0.clone(),
+                })
+                .collect::<Vec<_>>();
+
+            let client = service.client().await?;
+            client
+                .insert_native_block(service.query.as_str(), rows)
+                .await?;
+
+            Ok(NativeResponse {
+                metadata: request.metadata,
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct NativeRetryLogic;
+
+impl RetryLogic for NativeRetryLogic {
+    type Error = NativeError;
+    type Response = NativeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        // Errors reported by the server, such as type mismatches, fail the same way when retried.
+        matches!(error, NativeError::Io(_) | NativeError::ProtocolError(_))
+    }
+}
+
+struct NativeSink {
+    encoder: RowEncoder,
+    batch_settings: BatcherSettings,
+    service: Svc<NativeService, NativeRetryLogic>,
+}
+
+impl NativeSink {
+    /// Builds a request from the rows of the events that could be converted.
+    fn build_request(&self, events: Vec<Event>) -> Option<NativeRequest> {
+        let mut rows = Vec::with_capacity(events.len());
+        let mut finalizers = EventFinalizers::default();
+        let mut events_byte_size = 0;
+        let mut json_byte_size = 0;
+
+        for mut event in events {
+            let event_finalizers = event.take_finalizers();
+            self.encoder.transformer.transform(&mut event);
+            let log = event.into_log();
+
+            match self.encoder.encode(&log) {
+                Ok(row) => {
+                    events_byte_size += log.size_of();
+                    json_byte_size += log.estimated_json_encoded_size_of();
+                    finalizers.merge(event_finalizers);
+                    rows.push(row);
+                }
+                Err((column, error)) => {
+                    emit!(ClickhouseColumnConversionError { column, error });
+                    event_finalizers.update_status(EventStatus::Rejected);
+                }
+            }
+        }
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        let metadata = RequestMetadataBuilder::new(rows.len(), events_byte_size, json_byte_size)
+            .with_request_size(NonZeroUsize::new(json_byte_size.max(1)).expect("can't be zero"));
+
+        Some(NativeRequest {
+            rows: Arc::new(rows),
+            finalizers,
+            metadata,
+        })
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = self.service.clone();
+
+        input
+            .batched(self.batch_settings.into_byte_size_config())
+            .filter_map(|events| std::future::ready(self.build_request(events)))
+            .into_driver(service)
+            .protocol("tcp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for NativeSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+fn native_connector(cfg: &ClickhouseConfig) -> crate::Result<NativeConnector> {
+    if cfg.tls.is_some() {
+        return Err("TLS isn't supported with the native protocol.".into());
+    }
+
+    let url = Url::parse(&cfg.endpoint.uri.to_string())?;
+    if url.scheme() != "tcp" {
+        return Err(format!(
+            "The endpoint must use the `tcp` scheme with the native protocol, found {:?}.",
+            url.scheme()
+        )
+        .into());
+    }
+
+    let host = url
+        .host_str()
+        .ok_or("The endpoint must include a host with the native protocol.")?;
+    let address = format!("{}:{}", host, url.port().unwrap_or(9000));
+
+    let mut options = ClientOptions {
+        default_database: cfg.database.clone().unwrap_or_else(|| "default".to_owned()),
+        ..Default::default()
+    };
+    match cfg.auth.choose_one(&cfg.endpoint.auth)? {
+        Some(Auth::Basic { user, password }) => {
+            options.username = user;
+            options.password = password.inner().to_owned();
+        }
+        Some(Auth::Bearer { .. }) => {
+            return Err("Bearer authentication isn't supported with the native protocol.".into());
+        }
+        Some(Auth::OAuth2(_)) => {
+            return Err("OAuth2 authentication isn't supported with the native protocol.".into());
+        }
+        None => {}
+    }
+
+    Ok(NativeConnector { address, options })
+}
+
+pub(super) fn build_native_sink(
+    cfg: &ClickhouseConfig,
+    native: &NativeProtocolConfig,
+) -> crate::Result<(VectorSink, Healthcheck)> {
+    if native.columns.is_empty() {
+        return Err("`native.columns` must include at least one column.".into());
+    }
+
+    let columns = native
+        .columns
+        .iter()
+        .map(|(name, column)| {
+            let column_type = column
+                .column_type
+                .parse()
+                .map_err(|error| format!("Invalid type for column {:?}: {}", name, error))?;
+
+            Ok(NativeColumn {
+                name: name.clone(),
+                field: column.field.clone().unwrap_or_else(|| name.clone()),
+                column_type,
+            })
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    let columns = Arc::new(columns);
+
+    let connector = native_connector(cfg)?;
+    let table = match &cfg.database {
+        Some(database) => format!(
+            "{}.{}",
+            quote_identifier(database),
+            quote_identifier(&cfg.table)
+        ),
+        None => quote_identifier(&cfg.table),
+    };
+
+    let request = cfg.request.unwrap_with(&TowerRequestConfig::default());
+    let service = ServiceBuilder::new()
+        .settings(request, NativeRetryLogic)
+        .service(NativeService {
+            connector: connector.clone(),
+            client: Arc::new(Mutex::new(None)),
+            query: format!(
+                "INSERT INTO {} ({}) FORMAT native",
+                table,
+                columns
+                    .iter()
+                    .map(|column| quote_identifier(&column.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            columns: Arc::clone(&columns),
+        });
+
+    let sink = NativeSink {
+        encoder: RowEncoder {
+            columns,
+            transformer: cfg.encoding.clone(),
+        },
+        batch_settings: cfg.batch.into_batcher_settings()?,
+        service,
+    };
+
+    let healthcheck = async move {
+        let client = connector.connect().await?;
+        client.execute("SELECT 1").await?;
+        Ok(())
+    }
+    .boxed();
+
+    Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("`{}`", identifier.replace('\\', "\\\\").replace('`', "\\`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn parse_column_types() {
+        let column_type = |s: &str| s.parse::<ColumnType>();
+
+        assert_eq!(
+            column_type("String"),
+            Ok(ColumnType {
+                scalar: ScalarType::String,
+                nullable: false
+            })
+        );
+        assert_eq!(
+            column_type("Nullable(UInt16)"),
+            Ok(ColumnType {
+                scalar: ScalarType::UInt16,
+                nullable: true
+            })
+        );
+        assert_eq!(
+            column_type("DateTime64(3)"),
+            Ok(ColumnType {
+                scalar: ScalarType::DateTime64(3),
+                nullable: false
+            })
+        );
+
+        assert_eq!(
+            column_type("Nullable(DateTime64(3))"),
+            Ok(ColumnType {
+                scalar: ScalarType::DateTime64(3),
+                nullable: true
+            })
+        );
+
+        assert!(column_type("DateTime64(10)").is_err());
+        assert!(column_type("Array(String)").is_err());
+    }
+
+    #[test]
+    fn convert_values() {
+        let column_type = |s: &str| s.parse::<ColumnType>().unwrap();
+
+        assert_eq!(
+            column_type("Int8").convert(Some(&Value::from(-3))),
+            Ok(NativeValue::Int8(-3))
+        );
+        assert!(column_type("UInt8")
+            .convert(Some(&Value::from(-3)))
+            .is_err());
+        assert!(column_type("Int64")
+            .convert(Some(&Value::from("3")))
+            .is_err());
+        assert_eq!(
+            column_type("Float64").convert(Some(&Value::from(2))),
+            Ok(NativeValue::Float64(2.0_f64.to_bits()))
+        );
+        assert_eq!(
+            column_type("UInt32").convert(None),
+            Ok(NativeValue::UInt32(0))
+        );
+        assert_eq!(
+            column_type("Nullable(UInt32)").convert(Some(&Value::Null)),
+            Ok(NativeValue::Null)
+        );
+        assert_eq!(
+            column_type("Nullable(String)").convert(Some(&Value::from("foo"))),
+            Ok(NativeValue::String("foo".to_owned()))
+        );
+
+        let timestamp = chrono::Utc.timestamp_opt(1672531200, 123_456_789).unwrap();
+        assert_eq!(
+            column_type("DateTime64(3)").convert(Some(&Value::from(timestamp))),
+            Ok(NativeValue::DateTime64(Tz::UTC, 3, 1672531200123))
+        );
+        assert_eq!(
+            column_type("Nullable(DateTime64(3))").convert(None),
+            Ok(NativeValue::Null)
+        );
+        assert_eq!(
+            column_type("DateTime").convert(Some(&Value::from(timestamp))),
+            Ok(NativeValue::DateTime(DateTime(Tz::UTC, 1672531200)))
+        );
+        assert!(column_type("DateTime")
+            .convert(Some(&Value::from(-1)))
+            .is_err());
+    }
+
+    #[test]
+    fn encode_rows() {
+        let encoder = RowEncoder {
+            columns: Arc::new(vec![
+                NativeColumn {
+                    name: "message".to_owned(),
+                    field: "message".to_owned(),
+                    column_type: "String".parse().unwrap(),
+                },
+                NativeColumn {
+                    name: "code".to_owned(),
+                    field: "status.code".to_owned(),
+                    column_type: "Nullable(UInt16)".parse().unwrap(),
+                },
+            ]),
+            transformer: Default::default(),
+        };
+
+        let mut log = LogEvent::from("hello");
+        log.insert("status.code", 200);
+        let row = encoder.encode(&log).unwrap();
+        assert_eq!(
+            row.0,
+            vec![
+                NativeValue::String("hello".to_owned()),
+                NativeValue::UInt16(200)
+            ]
+        );
+
+        log.insert("status.code", "ok");
+        let (column, _) = encoder.encode(&log).unwrap_err();
+        assert_eq!(column, "code");
+    }
+
+    #[test]
+    fn serialize_rows() {
+        let row = InsertedRow {
+            columns: Arc::new(vec![
+                NativeColumn {
+                    name: "message".to_owned(),
+                    field: "message".to_owned(),
+                    column_type: "String".parse().unwrap(),
+                },
+                NativeColumn {
+                    name: "timestamp".to_owned(),
+                    field: "timestamp".to_owned(),
+                    column_type: "Nullable(DateTime64(3))".parse().unwrap(),
+                },
+            ]),
+            values: vec![
+                NativeValue::String("foo".to_owned()),
+                NativeValue::DateTime64(Tz::UTC, 3, 1672531200123),
+            ],
+        };
+
+        let timestamp_type =
+            NativeType::Nullable(Box::new(NativeType::DateTime64(3, Tz::Europe__Paris)));
+        assert_eq!(
+            row.serialize_row(&[&NativeType::String, &timestamp_type])
+                .unwrap(),
+            vec![
+                (
+                    Cow::Borrowed("message"),
+                    NativeValue::String("foo".to_owned())
+                ),
+                (
+                    Cow::Borrowed("timestamp"),
+                    NativeValue::DateTime64(Tz::Europe__Paris, 3, 1672531200123)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn quote_identifiers() {
+        assert_eq!(quote_identifier("logs"), "`logs`");
+        assert_eq!(quote_identifier("my`logs"), "`my\\`logs`");
+    }
+}
@@ -1,5 +1,5 @@
 use codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{Framer, FramingConfig, SerializerConfig},
     JsonSerializerConfig,
 };
 use futures::{future, FutureExt};
@@ -98,6 +98,10 @@ impl SinkConfig for ConsoleSinkConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn pass_through_serializer(&self) -> Option<&SerializerConfig> {
+        self.encoding.pass_through_serializer()
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     io::Write,
     sync::{Arc, Mutex},
 };
@@ -157,6 +158,7 @@ impl ApmStatsSender {
             finalizers: EventFinalizers::default(),
             uncompressed_size,
             content_type: "application/msgpack".to_string(),
+            headers: BTreeMap::new(),
         };
 
         let mut compressor = Compressor::from(self.compression);
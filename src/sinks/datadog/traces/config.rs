@@ -5,6 +5,7 @@ use indoc::indoc;
 use snafu::ResultExt;
 use tokio::sync::oneshot::{channel, Sender};
 use tower::ServiceBuilder;
+use tracing::Instrument;
 use vector_config::configurable_component;
 use vector_core::config::{proxy::ProxyConfig, AcknowledgementsConfig};
 
@@ -83,19 +84,22 @@ impl GenerateConfig for DatadogTracesConfig {
     }
 }
 
-/// Datadog traces API has two routes: one for traces and another one for stats.
+/// Datadog traces API has two routes: one for traces and another one for stats. The profiles
+/// passed through are sent to the profiling intake.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DatadogTracesEndpoint {
     Traces,
     #[allow(dead_code)] // This will be used when APM stats will be generated
     APMStats,
+    Profiles,
 }
 
-/// Store traces & APM stats endpoints actual URIs.
+/// Store traces, APM stats & profiles endpoints actual URIs.
 #[derive(Clone)]
 pub struct DatadogTracesEndpointConfiguration {
     traces_endpoint: Uri,
     stats_endpoint: Uri,
+    profiles_endpoint: Uri,
 }
 
 impl DatadogTracesEndpointConfiguration {
@@ -103,6 +107,7 @@ impl DatadogTracesEndpointConfiguration {
         match endpoint {
             DatadogTracesEndpoint::Traces => self.traces_endpoint.clone(),
             DatadogTracesEndpoint::APMStats => self.stats_endpoint.clone(),
+            DatadogTracesEndpoint::Profiles => self.profiles_endpoint.clone(),
         }
     }
 }
@@ -121,10 +126,18 @@ impl DatadogTracesConfig {
         let base_uri = self.get_base_uri();
         let traces_endpoint = build_uri(&base_uri, "/api/v0.2/traces")?;
         let stats_endpoint = build_uri(&base_uri, "/api/v0.2/stats")?;
+        // Profiles have an intake of their own, unless a custom endpoint is used for all of them.
+        let profiles_base_uri = self
+            .dd_common
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://intake.profile.{}", self.dd_common.site));
+        let profiles_endpoint = build_uri(&profiles_base_uri, "/api/v2/profile")?;
 
         Ok(DatadogTracesEndpointConfiguration {
             traces_endpoint,
             stats_endpoint,
+            profiles_endpoint,
         })
     }
 
@@ -177,13 +190,16 @@ impl DatadogTracesConfig {
         // Send the APM stats payloads independently of the sink framework.
         // This is necessary to comply with what the APM stats backend of Datadog expects with
         // respect to receiving stats payloads.
-        tokio::spawn(flush_apm_stats_thread(
-            tripwire,
-            client,
-            compression,
-            endpoints,
-            Arc::clone(&apm_stats_aggregator),
-        ));
+        tokio::spawn(
+            flush_apm_stats_thread(
+                tripwire,
+                client,
+                compression,
+                endpoints,
+                Arc::clone(&apm_stats_aggregator),
+            )
+            .in_current_span(),
+        );
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
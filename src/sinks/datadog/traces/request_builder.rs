@@ -19,6 +19,7 @@ use super::{
     sink::PartitionKey,
 };
 use crate::{
+    common::datadog::{PROFILE_HEADERS_KEY, PROFILE_KEY},
     event::{Event, TraceEvent, Value},
     sinks::util::{
         metadata::RequestMetadataBuilder, Compression, Compressor, IncrementalRequestBuilder,
@@ -92,6 +93,8 @@ pub struct DDTracesMetadata {
     pub finalizers: EventFinalizers,
     pub uncompressed_size: usize,
     pub content_type: String,
+    /// Headers sent along with the payload, on top of the ones of every request.
+    pub headers: BTreeMap<String, String>,
 }
 
 impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequestBuilder {
@@ -105,6 +108,14 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
         input: (PartitionKey, Vec<Event>),
     ) -> Vec<Result<(Self::Metadata, Self::Payload), Self::Error>> {
         let (key, events) = input;
+        if key.profile {
+            return events
+                .into_iter()
+                .filter_map(|e| e.try_into_trace())
+                .map(|profile| self.encode_profile(&key, profile))
+                .collect();
+        }
+
         let mut results = Vec::new();
         let n = events.len();
         let trace_events = events
@@ -131,6 +142,7 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
                         finalizers: processed.take_finalizers(),
                         uncompressed_size,
                         content_type: "application/x-protobuf".to_string(),
+                        headers: BTreeMap::new(),
                     };
 
                     let mut compressor = Compressor::from(self.compression);
@@ -167,12 +179,61 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
     }
 
     fn build_request(&mut self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
-        build_request(
-            metadata,
-            payload,
-            self.compression,
-            &self.endpoint_configuration,
-        )
+        // The profiles are sent as they were received, along with their own content encoding.
+        let compression = if metadata.0.endpoint == DatadogTracesEndpoint::Profiles {
+            Compression::None
+        } else {
+            self.compression
+        };
+        build_request(metadata, payload, compression, &self.endpoint_configuration)
+    }
+}
+
+impl DatadogTracesRequestBuilder {
+    /// Builds the request forwarding a profile passed through by the `datadog_agent` source.
+    fn encode_profile(
+        &self,
+        key: &PartitionKey,
+        mut profile: TraceEvent,
+    ) -> Result<((DDTracesMetadata, RequestMetadata), Bytes), RequestBuilderError> {
+        let finalizers = profile.take_finalizers();
+        let (mut fields, _) = profile.into_parts();
+        let payload = match fields.remove(PROFILE_KEY) {
+            Some(Value::Bytes(payload)) if !payload.is_empty() => payload,
+            _ => {
+                return Err(RequestBuilderError::FailedToBuild {
+                    message: "Profile has no payload.",
+                    reason: format!("the `{}` field must hold bytes", PROFILE_KEY),
+                    dropped_events: 1,
+                })
+            }
+        };
+        let mut headers = match fields.remove(PROFILE_HEADERS_KEY) {
+            Some(Value::Object(headers)) => headers
+                .into_iter()
+                .map(|(name, value)| (name, value.to_string_lossy().into_owned()))
+                .collect::<BTreeMap<_, _>>(),
+            _ => BTreeMap::new(),
+        };
+        let content_type = headers
+            .remove("Content-Type")
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+
+        let size = payload.len();
+        let metadata = DDTracesMetadata {
+            api_key: key
+                .api_key
+                .clone()
+                .unwrap_or_else(|| Arc::clone(&self.api_key)),
+            endpoint: DatadogTracesEndpoint::Profiles,
+            finalizers,
+            uncompressed_size: size,
+            content_type,
+            headers,
+        };
+        let request_metadata = RequestMetadataBuilder::new(1, size, size)
+            .with_request_size(NonZeroUsize::new(size).expect("payload is not empty"));
+        Ok(((metadata, request_metadata), payload))
     }
 }
 
@@ -200,6 +261,7 @@ pub fn build_request(
     if let Some(ce) = compression.content_encoding() {
         headers.insert("Content-Encoding".to_string(), ce.to_string());
     }
+    headers.extend(ddtraces_metadata.headers);
     TraceApiRequest {
         body: payload,
         headers,
@@ -17,6 +17,7 @@ use vector_core::{
 
 use super::service::TraceApiRequest;
 use crate::{
+    common::datadog::PROFILE_KEY,
     internal_events::DatadogTracesEncodingError,
     sinks::{datadog::traces::request_builder::DatadogTracesRequestBuilder, util::SinkBuilderExt},
 };
@@ -34,6 +35,9 @@ pub(crate) struct PartitionKey {
     // trace-agent config directly: https://github.com/DataDog/datadog-agent/blob/0f73a78/pkg/trace/config/config.go#L293-L294
     pub(crate) target_tps: Option<i64>,
     pub(crate) error_tps: Option<i64>,
+    // Whether the events are profiles passed through by the `datadog_agent` source, which are
+    // sent on their own to the profiling intake.
+    pub(crate) profile: bool,
 }
 
 impl Partitioner for EventPartitioner {
@@ -48,6 +52,15 @@ impl Partitioner for EventPartitioner {
             Event::Log(_) => {
                 panic!("unexpected log");
             }
+            Event::Trace(t) if t.contains(PROFILE_KEY) => PartitionKey {
+                api_key: item.metadata().datadog_api_key(),
+                env: None,
+                hostname: None,
+                agent_version: None,
+                target_tps: None,
+                error_tps: None,
+                profile: true,
+            },
             Event::Trace(t) => PartitionKey {
                 api_key: item.metadata().datadog_api_key(),
                 env: t.get("env").map(|s| s.to_string_lossy().into_owned()),
@@ -63,6 +76,7 @@ impl Partitioner for EventPartitioner {
                 error_tps: t
                     .get("error_tps")
                     .and_then(|tps| tps.as_integer().map(Into::into)),
+                profile: false,
             },
         }
     }
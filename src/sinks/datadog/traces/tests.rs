@@ -13,6 +13,7 @@ use vector_core::event::{BatchNotifier, BatchStatus, Event};
 use super::{apm_stats::StatsPayload, dd_proto, ddsketch_full, DatadogTracesConfig};
 
 use crate::{
+    common::datadog::{PROFILE_HEADERS_KEY, PROFILE_KEY},
     config::SinkConfig,
     event::{TraceEvent, Value},
     sinks::util::test::{build_test_server_status, load_sink},
@@ -125,6 +126,38 @@ fn validate_simple_span(span: dd_proto::Span, resource: String) {
     assert_eq!(span.metrics["a_metric"], 0.577);
 }
 
+#[tokio::test]
+async fn forwards_profiles() {
+    let body = Bytes::from("--profile\r\n\r\n{}\r\n--profile--\r\n");
+    let mut t = TraceEvent::default();
+    t.insert(PROFILE_KEY, body.clone());
+    t.insert(
+        PROFILE_HEADERS_KEY,
+        Value::Object(BTreeMap::from([
+            (
+                "Content-Type".to_string(),
+                Value::from("multipart/form-data; boundary=profile"),
+            ),
+            ("Content-Encoding".to_string(), Value::from("gzip")),
+            ("DD-EVP-ORIGIN".to_string(), Value::from("agent")),
+        ])),
+    );
+    t.metadata_mut().set_datadog_api_key(Arc::from("a_key"));
+    let events = vec![Event::Trace(t)];
+    let rx = start_test(BatchStatus::Delivered, StatusCode::OK, events).await;
+
+    let (parts, received) = rx.take(1).collect::<Vec<_>>().await.pop().unwrap();
+    assert_eq!(parts.uri.path(), "/api/v2/profile");
+    assert_eq!(
+        parts.headers.get("Content-Type").unwrap(),
+        "multipart/form-data; boundary=profile"
+    );
+    assert_eq!(parts.headers.get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(parts.headers.get("DD-EVP-ORIGIN").unwrap(), "agent");
+    assert_eq!(parts.headers.get("DD-API-KEY").unwrap(), "a_key");
+    assert_eq!(received, body);
+}
+
 #[tokio::test]
 async fn smoke() {
     let mut t = simple_trace_event("a_resource".to_string());
@@ -313,3 +346,82 @@ async fn multiple_traces() {
     assert_eq!(cgs_trace_1.resource, "trace_1");
     assert_eq!(cgs_trace_1.service, "a_service");
 }
+
+#[cfg(feature = "sources-opentelemetry")]
+#[tokio::test]
+async fn sends_otlp_traces() {
+    use opentelemetry_proto::proto::{
+        common::v1::{any_value, AnyValue, KeyValue},
+        resource::v1::Resource,
+        trace::v1::{span::SpanKind, ResourceSpans, ScopeSpans, Span},
+    };
+
+    let attribute = |key: &str, value: &str| KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    };
+    let span = |span_id: u8, parent_span_id: Vec<u8>, kind: SpanKind| Span {
+        trace_id: vec![1; 16],
+        span_id: vec![0, 0, 0, 0, 0, 0, 0, span_id],
+        parent_span_id,
+        name: "GET /users".into(),
+        kind: kind as i32,
+        start_time_unix_nano: 1_431_648_000_000_001,
+        end_time_unix_nano: 1_431_648_001_000_001,
+        ..Default::default()
+    };
+    let events = ResourceSpans {
+        resource: Some(Resource {
+            attributes: vec![
+                attribute("service.name", "a_service"),
+                attribute("deployment.environment", "an_env"),
+                attribute("host.name", "a_host"),
+            ],
+            dropped_attributes_count: 0,
+        }),
+        scope_spans: vec![ScopeSpans {
+            scope: None,
+            spans: vec![
+                span(1, vec![], SpanKind::Server),
+                span(2, vec![0, 0, 0, 0, 0, 0, 0, 1], SpanKind::Client),
+            ],
+            schema_url: String::new(),
+        }],
+        schema_url: String::new(),
+    }
+    .into_trace_events()
+    .collect();
+    let rx = start_test(BatchStatus::Delivered, StatusCode::OK, events).await;
+
+    let mut output = rx.take(2).collect::<Vec<_>>().await;
+    let (_, stats_body) = output.pop().unwrap();
+    let (_, trace_body) = output.pop().unwrap();
+
+    let mut decoded_payload = dd_proto::TracePayload::decode(trace_body).unwrap();
+    assert_eq!(decoded_payload.host_name, "a_host");
+    assert_eq!(decoded_payload.env, "an_env");
+    let mut tracer_payload = decoded_payload.tracer_payloads.pop().unwrap();
+    let chunk = tracer_payload.chunks.pop().unwrap();
+    assert_eq!(chunk.priority, 1);
+    assert_eq!(chunk.spans.len(), 2);
+    assert_eq!(chunk.spans[0].service, "a_service");
+    assert_eq!(chunk.spans[0].r#type, "web");
+    assert_eq!(chunk.spans[0].metrics["_top_level"], 1.0);
+    assert_eq!(chunk.spans[1].parent_id, 1);
+    assert!(!chunk.spans[1].metrics.contains_key("_top_level"));
+
+    // Only the top-level span is counted in the APM stats.
+    let mut sp: StatsPayload = rmp_serde::from_slice(&stats_body).unwrap();
+    let mut csp = sp.stats.pop().unwrap();
+    assert_eq!(csp.hostname, "a_host");
+    let mut csb = csp.stats.pop().unwrap();
+    assert_eq!(csb.stats.len(), 1);
+    let cgs = csb.stats.pop().unwrap();
+    assert_eq!(cgs.hits, 1);
+    assert_eq!(cgs.top_level_hits, 1);
+    assert_eq!(cgs.duration, 1_000_000_000);
+    assert_eq!(cgs.resource, "GET /users");
+    assert_eq!(cgs.service, "a_service");
+}
@@ -0,0 +1,496 @@
+//! Handling of the items of bulk requests which failed while the request as a whole succeeded.
+//!
+//! Items which failed with a retriable error are sent again on their own, while the others are
+//! either written to the dead letter index or dropped. Every item is finalized on its own, so that
+//! only the events of the items which were dropped are rejected.
+use std::{cmp, collections::HashMap, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use http::Response;
+use lookup::event_path;
+use tower::{Service, ServiceExt};
+use vector_common::request_metadata::RequestMetadata;
+use vector_core::ByteSizeOf;
+
+use super::{
+    encoder::ProcessedEvent,
+    request_builder::ElasticsearchRequestBuilder,
+    retry::{EsErrorDetails, EsIndexResult, EsResultResponse},
+    service::{get_event_status, ElasticsearchRequest},
+    BulkAction,
+};
+use crate::{
+    event::{DeliveryFailure, EventFinalizers, EventStatus, LogEvent},
+    internal_events::{
+        BulkItemOutcome, ElasticsearchBulkItemsFailed, ElasticsearchDeadLetterError,
+        TemplateRenderingError,
+    },
+    sinks::util::RequestBuilder,
+    template::Template,
+};
+
+pub struct BulkItemHandler {
+    pub retry_partial: bool,
+    pub retry_attempts: usize,
+    pub retry_initial_backoff: Duration,
+    pub retry_max_duration: Duration,
+    pub dead_letter_index: Option<Template>,
+    pub request_builder: ElasticsearchRequestBuilder,
+}
+
+/// An item which failed with an error which is not retried.
+struct FailedItem {
+    event: ProcessedEvent,
+    finalizers: EventFinalizers,
+    status: u16,
+    error: Option<EsErrorDetails>,
+}
+
+impl BulkItemHandler {
+    /// Sends `request`, and then sends again the items which failed with a retriable error until
+    /// they all succeed or retries are exhausted, returning the last response and the status of the
+    /// events of the request.
+    pub async fn send<S>(
+        &self,
+        service: &mut S,
+        mut request: ElasticsearchRequest,
+    ) -> crate::Result<(Response<Bytes>, EventStatus)>
+    where
+        S: Service<ElasticsearchRequest, Response = Response<Bytes>, Error = crate::Error>,
+    {
+        let mut attempts = 0;
+        let mut backoff = (Duration::ZERO, self.retry_initial_backoff);
+        loop {
+            let events = std::mem::take(&mut request.original_events);
+            let item_finalizers = std::mem::take(&mut request.item_finalizers);
+            let metadata = request.metadata;
+            let response = service.ready().await?.call(request).await?;
+
+            let event_status = get_event_status(&response);
+            if !response.status().is_success() || event_status != EventStatus::Rejected {
+                return Ok((response, event_status));
+            }
+
+            let parsed = EsResultResponse::parse(&String::from_utf8_lossy(response.body()));
+            let items = match parsed {
+                Ok(resp) => resp.items,
+                Err(_) => return Ok((response, EventStatus::Rejected)),
+            };
+
+            // Items can only be matched with the events they were sent for if there are as many,
+            // otherwise all of them are rejected.
+            if item_finalizers.len() != items.len() {
+                return Ok((response, EventStatus::Rejected));
+            }
+            let mut events = if events.len() == items.len() {
+                events.into_iter().map(Some).collect()
+            } else {
+                vec![None; items.len()]
+            };
+
+            let can_retry = self.retry_partial && attempts < self.retry_attempts;
+            let mut outcomes = HashMap::<_, (usize, String)>::new();
+            let mut retried = Vec::new();
+            let mut failed = Vec::new();
+            for ((item, event), finalizers) in
+                items.iter().zip(events.iter_mut()).zip(item_finalizers)
+            {
+                let result = item.result();
+                if result.is_success() {
+                    continue;
+                }
+
+                let outcome = match event.take() {
+                    Some(event) if can_retry && result.is_retriable() => {
+                        retried.push((event, finalizers));
+                        BulkItemOutcome::Retried
+                    }
+                    Some(event) if self.dead_letter_index.is_some() => {
+                        finalizers.record_failure(&item_failure(result, attempts));
+                        failed.push(FailedItem {
+                            event,
+                            finalizers,
+                            status: result.status.unwrap_or_default(),
+                            error: result.error.clone(),
+                        });
+                        BulkItemOutcome::DeadLettered
+                    }
+                    _ => {
+                        finalizers.record_failure(&item_failure(result, attempts));
+                        finalizers.update_status(EventStatus::Rejected);
+                        BulkItemOutcome::Dropped
+                    }
+                };
+
+                let (error_code, reason) = match &result.error {
+                    Some(error) => (error.err_type.clone(), error.reason.as_str()),
+                    None => ("unknown".to_owned(), ""),
+                };
+                let status = result.status.unwrap_or_default();
+                let entry = outcomes
+                    .entry((error_code, status, outcome))
+                    .or_insert_with(|| (0, reason.to_owned()));
+                entry.0 += 1;
+            }
+
+            for ((error_code, status, outcome), (count, reason)) in outcomes {
+                emit!(ElasticsearchBulkItemsFailed {
+                    error_code: &error_code,
+                    reason: &reason,
+                    status,
+                    outcome,
+                    count,
+                });
+            }
+
+            if !failed.is_empty() {
+                self.send_dead_letters(service, failed, metadata).await;
+            }
+
+            // The events of the items which failed were finalized on their own.
+            if retried.is_empty() {
+                return Ok((response, EventStatus::Delivered));
+            }
+
+            tokio::time::sleep(backoff.1).await;
+            backoff = (
+                backoff.1,
+                cmp::min(backoff.0 + backoff.1, self.retry_max_duration),
+            );
+            attempts += 1;
+
+            let (retried, finalizers): (Vec<_>, Vec<_>) = retried.into_iter().unzip();
+            let mut next = build_request(&self.request_builder, retried.clone(), metadata)?;
+            next.original_events = retried;
+            next.item_finalizers = finalizers;
+            request = next;
+        }
+    }
+
+    /// Writes the items which failed with an error which is not retried to the dead letter index,
+    /// rejecting the events of those which couldn't be written.
+    async fn send_dead_letters<S>(
+        &self,
+        service: &mut S,
+        failed: Vec<FailedItem>,
+        metadata: RequestMetadata,
+    ) where
+        S: Service<ElasticsearchRequest, Response = Response<Bytes>, Error = crate::Error>,
+    {
+        let template = self
+            .dead_letter_index
+            .as_ref()
+            .expect("dead letter index must be set");
+
+        let count = failed.len();
+        let mut events = Vec::with_capacity(count);
+        let mut finalizers = EventFinalizers::default();
+        for mut item in failed {
+            let item_finalizers = std::mem::take(&mut item.finalizers);
+            match dead_letter(item, template) {
+                Some(event) => {
+                    events.push(event);
+                    finalizers.merge(item_finalizers);
+                }
+                None => item_finalizers.update_status(EventStatus::Rejected),
+            }
+        }
+        if events.is_empty() {
+            return;
+        }
+
+        // The documents of the dead letter index are built from scratch, so the transformer of the
+        // sink must not apply to them.
+        let mut request_builder = self.request_builder.clone();
+        request_builder.encoder.transformer = Default::default();
+
+        let result = match build_request(&request_builder, events, metadata) {
+            Ok(request) => match service.ready().await {
+                Ok(service) => service.call(request).await,
+                Err(error) => Err(error),
+            },
+            Err(error) => Err(error),
+        };
+        match result {
+            Ok(response) if get_event_status(&response) == EventStatus::Delivered => {}
+            Ok(response) => {
+                emit!(ElasticsearchDeadLetterError {
+                    error: format!(
+                        "{}: {}",
+                        response.status(),
+                        String::from_utf8_lossy(response.body())
+                    ),
+                    count,
+                });
+                finalizers.update_status(EventStatus::Rejected);
+            }
+            Err(error) => {
+                emit!(ElasticsearchDeadLetterError {
+                    error: error.to_string(),
+                    count,
+                });
+                finalizers.update_status(EventStatus::Rejected);
+            }
+        }
+    }
+}
+
+/// Describes the failure of an item, after `retries` retries, for its events.
+fn item_failure(result: &EsIndexResult, retries: usize) -> DeliveryFailure {
+    DeliveryFailure {
+        status_code: result.status,
+        error_class: result
+            .error
+            .as_ref()
+            .map_or_else(|| "unknown".to_owned(), |error| error.err_type.clone()),
+        attempts: retries + 1,
+    }
+}
+
+/// Builds the document written to the dead letter index for a failed item.
+fn dead_letter(item: FailedItem, template: &Template) -> Option<ProcessedEvent> {
+    let mut log = LogEvent::default();
+    log.insert(event_path!("@timestamp"), Utc::now());
+    log.insert("status", i64::from(item.status));
+    if let Some(error) = item.error {
+        log.insert("error.type", error.err_type);
+        log.insert("error.reason", error.reason);
+    }
+    log.insert(
+        "document",
+        serde_json::to_string(&item.event.log).unwrap_or_default(),
+    );
+    log.insert("index", item.event.index);
+
+    let index = template
+        .render_string(&log)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("dead_letter_index"),
+                drop_event: true,
+            });
+        })
+        .ok()?;
+
+    Some(ProcessedEvent {
+        index,
+        bulk_action: BulkAction::Create,
+        log,
+        id: None,
+    })
+}
+
+fn build_request(
+    request_builder: &ElasticsearchRequestBuilder,
+    events: Vec<ProcessedEvent>,
+    metadata: RequestMetadata,
+) -> crate::Result<ElasticsearchRequest> {
+    let batch_size = events.len();
+    let events_byte_size = events.iter().map(|event| event.log.size_of()).sum();
+    let payload = request_builder.encode_events(events)?;
+
+    Ok(ElasticsearchRequest {
+        payload: payload.into_payload(),
+        finalizers: Default::default(),
+        item_finalizers: Vec::new(),
+        batch_size,
+        events_byte_size,
+        metadata,
+        original_events: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{
+        event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Finalizable},
+        sinks::{elasticsearch::ElasticsearchEncoder, util::Compression},
+    };
+
+    fn handler(retry_partial: bool, dead_letter_index: Option<&str>) -> BulkItemHandler {
+        BulkItemHandler {
+            retry_partial,
+            retry_attempts: 3,
+            retry_initial_backoff: Duration::from_millis(1),
+            retry_max_duration: Duration::from_millis(1),
+            dead_letter_index: dead_letter_index.map(|index| Template::try_from(index).unwrap()),
+            request_builder: ElasticsearchRequestBuilder {
+                compression: Compression::None,
+                encoder: ElasticsearchEncoder {
+                    suppress_type_name: true,
+                    ..Default::default()
+                },
+                keep_events: true,
+            },
+        }
+    }
+
+    /// Builds a request for `messages`, along with the receivers of the status of each of them.
+    fn request(
+        handler: &BulkItemHandler,
+        messages: &[&str],
+    ) -> (ElasticsearchRequest, Vec<BatchStatusReceiver>) {
+        let (mut events, receivers): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .map(|message| {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                let event = ProcessedEvent {
+                    index: "logs".into(),
+                    bulk_action: BulkAction::Create,
+                    log: LogEvent::from(*message).with_batch_notifier(&batch),
+                    id: None,
+                };
+                (event, receiver)
+            })
+            .unzip();
+        let item_finalizers = events
+            .iter_mut()
+            .map(Finalizable::take_finalizers)
+            .collect();
+        let mut request =
+            build_request(&handler.request_builder, events.clone(), Default::default()).unwrap();
+        request.original_events = events;
+        request.item_finalizers = item_finalizers;
+        (request, receivers)
+    }
+
+    async fn statuses(receivers: Vec<BatchStatusReceiver>) -> Vec<BatchStatus> {
+        futures::future::join_all(receivers).await
+    }
+
+    /// A service answering requests with `responses` in order, and recording their payloads.
+    fn service(
+        responses: Vec<&'static str>,
+    ) -> (
+        impl Service<ElasticsearchRequest, Response = Response<Bytes>, Error = crate::Error>,
+        Arc<Mutex<Vec<String>>>,
+    ) {
+        let payloads = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(responses.into_iter()));
+        let service = {
+            let payloads = Arc::clone(&payloads);
+            tower::service_fn(move |request: ElasticsearchRequest| {
+                payloads
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8(request.payload.to_vec()).unwrap());
+                let body = responses
+                    .lock()
+                    .unwrap()
+                    .next()
+                    .expect("unexpected request");
+                futures::future::ok::<_, crate::Error>(Response::new(Bytes::from(body)))
+            })
+        };
+        (service, payloads)
+    }
+
+    #[tokio::test]
+    async fn retries_only_retriable_items() {
+        let handler = handler(true, None);
+        let (mut service, payloads) = service(vec![
+            r#"{"errors":true,"items":[
+                {"create":{"status":201}},
+                {"create":{"status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected"}}},
+                {"create":{"status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}
+            ]}"#,
+            r#"{"errors":false,"items":[{"create":{"status":201}}]}"#,
+        ]);
+
+        let (request, receivers) = request(&handler, &["first", "second", "third"]);
+        let (_, event_status) = handler.send(&mut service, request).await.unwrap();
+
+        // Only the event of the item which failed to be parsed is rejected.
+        assert_eq!(event_status, EventStatus::Delivered);
+        assert_eq!(
+            statuses(receivers).await,
+            [
+                BatchStatus::Delivered,
+                BatchStatus::Delivered,
+                BatchStatus::Rejected
+            ]
+        );
+        let payloads = payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 2);
+        assert!(payloads[1].contains("second"));
+        assert!(!payloads[1].contains("first"));
+        assert!(!payloads[1].contains("third"));
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_items_without_retry_partial() {
+        let handler = handler(false, None);
+        let (mut service, payloads) = service(vec![
+            r#"{"errors":true,"items":[{"create":{"status":429}}]}"#,
+        ]);
+
+        let (request, receivers) = request(&handler, &["first"]);
+        let (_, event_status) = handler.send(&mut service, request).await.unwrap();
+
+        assert_eq!(event_status, EventStatus::Delivered);
+        assert_eq!(statuses(receivers).await, [BatchStatus::Rejected]);
+        assert_eq!(payloads.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stops_retrying_after_retry_attempts() {
+        let handler = handler(true, None);
+        let failure = r#"{"errors":true,"items":[{"create":{"status":503}}]}"#;
+        let (mut service, payloads) = service(vec![failure; 4]);
+
+        let (request, mut receivers) = request(&handler, &["first"]);
+        let (_, event_status) = handler.send(&mut service, request).await.unwrap();
+
+        assert_eq!(event_status, EventStatus::Delivered);
+        assert_eq!(
+            receivers.remove(0).outcome().await,
+            (
+                BatchStatus::Rejected,
+                Some(DeliveryFailure {
+                    status_code: Some(503),
+                    error_class: "unknown".to_owned(),
+                    attempts: 4,
+                })
+            )
+        );
+        assert_eq!(payloads.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn writes_failed_items_to_dead_letter_index() {
+        let handler = handler(true, Some("{{ index }}-failed"));
+        let (mut service, payloads) = service(vec![
+            r#"{"errors":true,"items":[
+                {"create":{"status":201}},
+                {"create":{"status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}
+            ]}"#,
+            r#"{"errors":false,"items":[{"create":{"status":201}}]}"#,
+        ]);
+
+        let (request, receivers) = request(&handler, &["first", "second"]);
+        let (_, event_status) = handler.send(&mut service, request).await.unwrap();
+
+        assert_eq!(event_status, EventStatus::Delivered);
+        assert_eq!(
+            statuses(receivers).await,
+            [BatchStatus::Delivered, BatchStatus::Delivered]
+        );
+        let payloads = payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 2);
+        let mut lines = payloads[1].lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            r#"{"create":{"_index":"logs-failed"}}"#
+        );
+        let document = serde_json::from_str::<serde_json::Value>(lines.next().unwrap()).unwrap();
+        assert_eq!(document["index"], "logs");
+        assert_eq!(document["status"], 400);
+        assert_eq!(document["error"]["type"], "mapper_parsing_exception");
+        assert!(document["document"].as_str().unwrap().contains("second"));
+    }
+}
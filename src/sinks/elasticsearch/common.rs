@@ -20,7 +20,7 @@ use crate::{
         elasticsearch::{
             ElasticsearchAuth, ElasticsearchCommonMode, ElasticsearchConfig, ParseError,
         },
-        util::{http::RequestConfig, TowerRequestConfig, UriSerde},
+        util::{http::RequestConfig, Compression, TowerRequestConfig, UriSerde},
         HealthcheckError,
     },
     tls::TlsSettings,
@@ -61,6 +61,16 @@ impl ElasticsearchCommon {
             .into());
         }
 
+        if matches!(
+            config.compression,
+            Compression::Zstd(_) | Compression::Snappy
+        ) {
+            return Err(ParseError::UnsupportedCompression {
+                compression: config.compression,
+            }
+            .into());
+        }
+
         let authorization = match &config.auth {
             Some(ElasticsearchAuth::Basic { user, password }) => Some(Auth::Basic {
                 user: user.clone(),
@@ -187,6 +197,7 @@ impl ElasticsearchCommon {
                 doc_type,
                 suppress_type_name,
             },
+            keep_events: config.request_retry_partial || config.dead_letter_index.is_some(),
         };
 
         Ok(Self {
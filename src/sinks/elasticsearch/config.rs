@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     convert::TryFrom,
+    sync::Arc,
 };
 
 use futures::{FutureExt, TryFutureExt};
@@ -15,6 +16,7 @@ use crate::{
     internal_events::TemplateRenderingError,
     sinks::{
         elasticsearch::{
+            bulk_items::BulkItemHandler,
             health::ElasticsearchHealthLogic,
             retry::ElasticsearchRetryLogic,
             service::{ElasticsearchService, HttpRequestBuilder},
@@ -90,13 +92,36 @@ pub struct ElasticsearchConfig {
     )]
     pub suppress_type_name: bool,
 
-    /// Whether or not to retry successful requests containing partial failures.
+    /// Whether or not to retry the documents of successful requests which failed with a retriable
+    /// error.
+    ///
+    /// Only the documents which failed are sent again, up to `request.retry_attempts` times.
+    /// Documents are retried when they fail with back pressure or server errors, or while their
+    /// index is blocked or closed, which happens while the write index of a data stream or alias
+    /// is rolled over by ILM, or while a disk watermark is exceeded.
     ///
     /// To avoid duplicates in Elasticsearch, please use option `id_key`.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
     pub request_retry_partial: bool,
 
+    /// The index to write documents to when they fail with an error which is not retried.
+    ///
+    /// Instead of being dropped, the failed documents are written to this index with the `create`
+    /// action, as new documents holding the failed document as a JSON string in the `document`
+    /// field, along with the index it was sent to in the `index` field, the status and error
+    /// returned by Elasticsearch in the `status`, `error.type`, and `error.reason` fields, and the
+    /// time of the failure in the `@timestamp` field.
+    ///
+    /// The index is rendered against these new documents, so it can refer to the index the
+    /// document was sent to, as in `{{ index }}-failed`. Documents which cannot be written to this
+    /// index are dropped.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "{{ index }}-failed"))]
+    #[configurable(metadata(docs::examples = "dead-letters"))]
+    pub dead_letter_index: Option<Template>,
+
     /// The name of the event key that should map to Elasticsearch’s [`_id` field][es_id].
     ///
     /// By default, the `_id` field is not set, which allows Elasticsearch to set this
@@ -205,6 +230,7 @@ impl Default for ElasticsearchConfig {
             api_version: Default::default(),
             suppress_type_name: false,
             request_retry_partial: false,
+            dead_letter_index: None,
             id_key: None,
             pipeline: None,
             mode: Default::default(),
@@ -480,6 +506,15 @@ impl SinkConfig for ElasticsearchConfig {
 
         let health_config = self.endpoint_health.clone().unwrap_or_default();
 
+        let bulk_items = Arc::new(BulkItemHandler {
+            retry_partial: self.request_retry_partial,
+            retry_attempts: request_limits.retry_attempts,
+            retry_initial_backoff: request_limits.retry_initial_backoff_secs,
+            retry_max_duration: request_limits.retry_max_duration_secs,
+            dead_letter_index: self.dead_letter_index.clone(),
+            request_builder: common.request_builder.clone(),
+        });
+
         let services = commons
             .iter()
             .cloned()
@@ -487,16 +522,18 @@ impl SinkConfig for ElasticsearchConfig {
                 let endpoint = common.base_url.clone();
 
                 let http_request_builder = HttpRequestBuilder::new(&common, self);
-                let service = ElasticsearchService::new(client.clone(), http_request_builder);
+                let service = ElasticsearchService::new(
+                    client.clone(),
+                    http_request_builder,
+                    Arc::clone(&bulk_items),
+                );
 
                 (endpoint, service)
             })
             .collect::<Vec<_>>();
 
         let service = request_limits.distributed_service(
-            ElasticsearchRetryLogic {
-                retry_partial: self.request_retry_partial,
-            },
+            ElasticsearchRetryLogic,
             services,
             health_config,
             ElasticsearchHealthLogic,
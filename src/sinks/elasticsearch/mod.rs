@@ -1,3 +1,4 @@
+mod bulk_items;
 mod common;
 mod config;
 mod encoder;
@@ -28,6 +29,7 @@ use crate::aws::AwsAuthentication;
 use crate::{
     event::{EventRef, LogEvent},
     internal_events::TemplateRenderingError,
+    sinks::util::Compression,
     template::{Template, TemplateParseError},
 };
 
@@ -218,4 +220,9 @@ pub enum ParseError {
         "`endpoint` and `endpoints` options are mutually exclusive. Please use `endpoints` option."
     ))]
     EndpointsExclusive,
+    #[snafu(display(
+        "Elasticsearch doesn't support {} compressed requests, only gzip or zlib ones",
+        compression
+    ))]
+    UnsupportedCompression { compression: Compression },
 }
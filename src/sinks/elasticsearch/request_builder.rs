@@ -20,12 +20,16 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: ElasticsearchEncoder,
+    /// Whether to keep the events in the requests, so that failed items can be sent again.
+    pub keep_events: bool,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
+    item_finalizers: Vec<EventFinalizers>,
     batch_size: usize,
     events_byte_size: usize,
+    original_events: Vec<ProcessedEvent>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -56,10 +60,23 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
 
+        // The finalizers are taken first, so that the kept events don't hold them. Those of each
+        // event are also kept apart, so that the items which fail can be finalized on their own.
+        let item_finalizers = events
+            .iter_mut()
+            .map(Finalizable::take_finalizers)
+            .collect::<Vec<_>>();
+        let finalizers = item_finalizers.iter().cloned().collect();
         let es_metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            finalizers,
+            item_finalizers,
             batch_size: events.len(),
             events_byte_size,
+            original_events: if self.keep_events {
+                events.clone()
+            } else {
+                Vec::new()
+            },
         };
         (es_metadata, metadata_builder, events)
     }
@@ -73,9 +90,11 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
         ElasticsearchRequest {
             payload: payload.into_payload(),
             finalizers: es_metadata.finalizers,
+            item_finalizers: es_metadata.item_finalizers,
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
+            original_events: es_metadata.original_events,
         }
     }
 }
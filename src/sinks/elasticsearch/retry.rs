@@ -2,6 +2,7 @@ use http::StatusCode;
 use serde::Deserialize;
 
 use crate::{
+    event::EventStatus,
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
@@ -9,13 +10,19 @@ use crate::{
     },
 };
 
+/// The types of errors of bulk items which are expected to clear up without changing the items.
+///
+/// Index blocks are set while a disk watermark is exceeded or by ILM, and indices are closed by
+/// ILM, while the write index of a data stream or alias is being rolled over.
+const RETRIABLE_ERROR_TYPES: [&str; 2] = ["cluster_block_exception", "index_closed_exception"];
+
 #[derive(Deserialize, Debug)]
-struct EsResultResponse {
-    items: Vec<EsResultItem>,
+pub(super) struct EsResultResponse {
+    pub(super) items: Vec<EsResultItem>,
 }
 
 impl EsResultResponse {
-    fn parse(body: &str) -> Result<Self, String> {
+    pub(super) fn parse(body: &str) -> Result<Self, String> {
         serde_json::from_str::<EsResultResponse>(body).map_err(|json_error| {
             format!(
                 "some messages failed, could not parse response, error: {}",
@@ -24,20 +31,9 @@ impl EsResultResponse {
         })
     }
 
-    /// Returns iterator over status codes for items and optional error details.
-    fn iter_status(&self) -> impl Iterator<Item = (StatusCode, Option<&EsErrorDetails>)> {
-        self.items.iter().filter_map(|item| {
-            item.result()
-                .status
-                .and_then(|status| StatusCode::from_u16(status).ok())
-                .map(|status| (status, item.result().error.as_ref()))
-        })
-    }
-
     /// Selects the first error since logging all errors would be quite verbose and many are duplicates.
-    /// If partial retry is enabled and we don't retry, this is because there is no retriable error in the
-    /// response, thus all errors are equally interesting so logging the first is sufficient.
-    /// When partial retry is disabled, we don't retry on any error.
+    /// The items which failed with a retriable error have already been retried by the service, so
+    /// all remaining errors are equally interesting and logging the first is sufficient.
     fn get_error_reason(&self, body: &str) -> String {
         match self
             .items
@@ -51,7 +47,7 @@ impl EsResultResponse {
 }
 
 #[derive(Deserialize, Debug)]
-enum EsResultItem {
+pub(super) enum EsResultItem {
     #[serde(rename = "index")]
     Index(EsIndexResult),
     #[serde(rename = "create")]
@@ -60,7 +56,7 @@ enum EsResultItem {
 
 impl EsResultItem {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    fn result(&self) -> &EsIndexResult {
+    pub(super) fn result(&self) -> &EsIndexResult {
         match self {
             EsResultItem::Index(r) => r,
             EsResultItem::Create(r) => r,
@@ -69,22 +65,49 @@ impl EsResultItem {
 }
 
 #[derive(Deserialize, Debug)]
-struct EsIndexResult {
-    status: Option<u16>,
-    error: Option<EsErrorDetails>,
+pub(super) struct EsIndexResult {
+    pub(super) status: Option<u16>,
+    pub(super) error: Option<EsErrorDetails>,
 }
 
-#[derive(Deserialize, Debug)]
-struct EsErrorDetails {
-    reason: String,
+impl EsIndexResult {
+    pub(super) fn is_success(&self) -> bool {
+        self.error.is_none()
+            && self
+                .status
+                .and_then(|status| StatusCode::from_u16(status).ok())
+                .map_or(true, |status| status.is_success())
+    }
+
+    /// Whether the item failed with an error which is worth retrying: back pressure, server errors,
+    /// and the errors of index blocks and rollovers.
+    pub(super) fn is_retriable(&self) -> bool {
+        let retriable_status = self
+            .status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .map_or(false, |status| {
+                status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            });
+        let retriable_error = self.error.as_ref().map_or(false, |error| {
+            RETRIABLE_ERROR_TYPES.contains(&error.err_type.as_str())
+        });
+        retriable_status || retriable_error
+    }
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub(super) struct EsErrorDetails {
+    pub(super) reason: String,
     #[serde(rename = "type")]
-    err_type: String,
+    pub(super) err_type: String,
 }
 
+/// Retries requests which failed as a whole.
+///
+/// The items of bulk requests which failed are retried, or written to the dead letter index, by
+/// the service itself, so that only those items are sent again.
 #[derive(Clone)]
-pub struct ElasticsearchRetryLogic {
-    pub retry_partial: bool,
-}
+pub struct ElasticsearchRetryLogic;
 
 impl RetryLogic for ElasticsearchRetryLogic {
     type Error = HttpError;
@@ -115,44 +138,23 @@ impl RetryLogic for ElasticsearchRetryLogic {
                 RetryAction::DontRetry(format!("client-side error, {}: {}", status, body).into())
             }
             _ if status.is_success() => {
-                let body = String::from_utf8_lossy(response.http_response.body());
+                if response.event_status == EventStatus::Delivered {
+                    return RetryAction::Successful;
+                }
 
-                if body.contains("\"errors\":true") {
-                    match EsResultResponse::parse(&body) {
-                        Ok(resp) => {
-                            if self.retry_partial {
-                                // We will retry if there exists at least one item that
-                                // failed with a retriable error.
-                                // Those are backpressure and server errors.
-                                if let Some((status, error)) =
-                                    resp.iter_status().find(|(status, _)| {
-                                        *status == StatusCode::TOO_MANY_REQUESTS
-                                            || status.is_server_error()
-                                    })
-                                {
-                                    let msg = if let Some(error) = error {
-                                        format!(
-                                            "partial error, status: {}, error type: {}, reason: {}",
-                                            status, error.err_type, error.reason
-                                        )
-                                    } else {
-                                        format!("partial error, status: {}", status)
-                                    };
-                                    return RetryAction::Retry(msg.into());
-                                }
-                            }
-
-                            RetryAction::DontRetry(resp.get_error_reason(&body).into())
-                        }
-                        Err(msg) => RetryAction::DontRetry(msg.into()),
-                    }
-                } else {
-                    RetryAction::Successful
+                let body = String::from_utf8_lossy(response.http_response.body());
+                match EsResultResponse::parse(&body) {
+                    Ok(resp) => RetryAction::DontRetry(resp.get_error_reason(&body).into()),
+                    Err(msg) => RetryAction::DontRetry(msg.into()),
                 }
             }
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn status_code(&self, response: &ElasticsearchResponse) -> Option<u16> {
+        Some(response.http_response.status().as_u16())
+    }
 }
 
 #[cfg(test)]
@@ -171,9 +173,7 @@ mod tests {
             .status(StatusCode::OK)
             .body(Bytes::from(json))
             .unwrap();
-        let logic = ElasticsearchRetryLogic {
-            retry_partial: false,
-        };
+        let logic = ElasticsearchRetryLogic;
         assert!(matches!(
             logic.should_retry_response(&ElasticsearchResponse {
                 http_response: response,
@@ -186,26 +186,66 @@ mod tests {
     }
 
     #[test]
-    fn handles_partial_error_response() {
+    fn does_not_retry_partial_error_response() {
+        // Items which failed with a retriable error are retried by the service, so the whole
+        // request is never retried.
         let json = "{\"took\":34,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-asjkf1234\",\"_type\":\"log_lines\",\"_id\":\"4Z3QLYEBT52RtoOEKz2H\",\"status\":429}}]}";
         let response = Response::builder()
             .status(StatusCode::OK)
             .body(Bytes::from(json))
             .unwrap();
-        let logic = ElasticsearchRetryLogic {
-            retry_partial: true,
-        };
+        let logic = ElasticsearchRetryLogic;
         assert!(matches!(
             logic.should_retry_response(&ElasticsearchResponse {
                 http_response: response,
-                event_status: EventStatus::Errored,
+                event_status: EventStatus::Rejected,
                 batch_size: 1,
                 events_byte_size: 1,
             }),
-            RetryAction::Retry(_)
+            RetryAction::DontRetry(_)
         ));
     }
 
+    #[test]
+    fn handles_dead_lettered_error_response() {
+        // All failed items were written to the dead letter index.
+        let json = "{\"took\":34,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-asjkf1234\",\"_type\":\"log_lines\",\"_id\":\"4Z3QLYEBT52RtoOEKz2H\",\"status\":400}}]}";
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(json))
+            .unwrap();
+        let logic = ElasticsearchRetryLogic;
+        assert!(matches!(
+            logic.should_retry_response(&ElasticsearchResponse {
+                http_response: response,
+                event_status: EventStatus::Delivered,
+                batch_size: 1,
+                events_byte_size: 1,
+            }),
+            RetryAction::Successful
+        ));
+    }
+
+    #[test]
+    fn classifies_retriable_items() {
+        let json = r#"{"took":3,"errors":true,"items":[
+            {"create":{"_index":"logs","status":201}},
+            {"create":{"_index":"logs","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}},
+            {"create":{"_index":"logs","status":403,"error":{"type":"cluster_block_exception","reason":"index [logs] blocked by: [FORBIDDEN/8/index write (api)];"}}},
+            {"create":{"_index":"logs","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse field [host]"}}}
+        ]}"#;
+        let resp = EsResultResponse::parse(json).unwrap();
+        let results = resp
+            .items
+            .iter()
+            .map(|item| (item.result().is_success(), item.result().is_retriable()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![(true, false), (false, true), (false, true), (false, false)]
+        );
+    }
+
     #[test]
     fn get_index_error_reason() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
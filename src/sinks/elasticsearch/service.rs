@@ -10,11 +10,12 @@ use bytes::Bytes;
 use futures::future::BoxFuture;
 use http::{Response, Uri};
 use hyper::{service::Service, Body, Request};
-use tower::ServiceExt;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse, ByteSizeOf};
 
-use crate::sinks::elasticsearch::sign_request;
+use crate::sinks::elasticsearch::{
+    bulk_items::BulkItemHandler, encoder::ProcessedEvent, sign_request,
+};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::{Auth, HttpClient},
@@ -30,14 +31,22 @@ use super::{ElasticsearchCommon, ElasticsearchConfig};
 pub struct ElasticsearchRequest {
     pub payload: Bytes,
     pub finalizers: EventFinalizers,
+    /// The finalizers of each of the events of the request, in order, with which the items which
+    /// fail are finalized on their own.
+    pub item_finalizers: Vec<EventFinalizers>,
     pub batch_size: usize,
     pub events_byte_size: usize,
     pub metadata: RequestMetadata,
+    /// The events of the request, kept when failed items are retried or written to the dead
+    /// letter index.
+    pub original_events: Vec<ProcessedEvent>,
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
     fn allocated_bytes(&self) -> usize {
-        self.payload.allocated_bytes() + self.finalizers.allocated_bytes()
+        self.payload.allocated_bytes()
+            + self.finalizers.allocated_bytes()
+            + self.original_events.allocated_bytes()
     }
 }
 
@@ -65,12 +74,14 @@ pub struct ElasticsearchService {
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    bulk_items: Arc<BulkItemHandler>,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        bulk_items: Arc<BulkItemHandler>,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -79,7 +90,10 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            bulk_items,
+        }
     }
 }
 
@@ -172,13 +186,12 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let bulk_items = Arc::clone(&self.bulk_items);
         Box::pin(async move {
-            http_service.ready().await?;
             let batch_size = req.batch_size;
             let events_byte_size = req.events_byte_size;
-            let http_response = http_service.call(req).await?;
+            let (http_response, event_status) = bulk_items.send(&mut http_service, req).await?;
 
-            let event_status = get_event_status(&http_response);
             Ok(ElasticsearchResponse {
                 event_status,
                 http_response,
@@ -189,7 +202,7 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     }
 }
 
-fn get_event_status(response: &Response<Bytes>) -> EventStatus {
+pub(super) fn get_event_status(response: &Response<Bytes>) -> EventStatus {
     let status = response.status();
     if status.is_success() {
         let body = String::from_utf8_lossy(response.body());
@@ -0,0 +1,301 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use futures::FutureExt;
+use lettre::{
+    message::Mailbox,
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Tls, TlsParameters},
+    },
+    AsyncSmtpTransport, Tokio1Executor,
+};
+use serde_with::serde_as;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::sink::EmailSink;
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{Healthcheck, VectorSink},
+    template::Template,
+};
+
+/// Configuration for the `email` sink.
+#[serde_as]
+#[configurable_component(sink("email"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EmailSinkConfig {
+    /// The host of the SMTP server.
+    #[configurable(metadata(docs::examples = "smtp.example.com"))]
+    pub host: String,
+
+    /// The port of the SMTP server.
+    ///
+    /// Defaults to `465` with the `tls` mode, `587` with the `starttls` mode, and `25` otherwise.
+    #[configurable(metadata(docs::examples = 587))]
+    pub port: Option<u16>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: EmailTlsConfig,
+
+    #[configurable(derived)]
+    pub auth: Option<EmailAuth>,
+
+    /// The amount of time to wait for the SMTP server to respond.
+    #[serde(default = "default_timeout")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "timeout_secs")]
+    #[configurable(metadata(docs::examples = 30))]
+    pub timeout: Duration,
+
+    /// The sender of the emails.
+    #[configurable(metadata(docs::examples = "Vector <vector@example.com>"))]
+    pub from: String,
+
+    /// The recipients of the emails.
+    ///
+    /// The events rendering to the same recipients are sent together, in the same digests.
+    #[configurable(metadata(docs::examples = "oncall@example.com"))]
+    #[configurable(metadata(docs::examples = "{{ team }}@example.com"))]
+    pub to: Vec<Template>,
+
+    /// The subject of the emails.
+    ///
+    /// The subject of a digest is the one of its first event, followed by the number of the other
+    /// events in the digest.
+    #[configurable(metadata(docs::examples = "[{{ level }}] {{ service }} alert"))]
+    pub subject: Template,
+
+    /// The body of the emails, in plain text.
+    ///
+    /// The body of a digest is the body of each of its events, one after the other.
+    #[configurable(metadata(docs::examples = "{{ message }}"))]
+    pub body: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub digest: DigestConfig,
+
+    #[configurable(derived)]
+    pub rate_limit: Option<RateLimitConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// TLS configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EmailTlsConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: EmailTlsMode,
+
+    /// Whether to verify the certificate of the SMTP server.
+    ///
+    /// Only disable this for testing, as the credentials could be sent to anyone otherwise.
+    #[serde(default = "crate::serde::default_true")]
+    pub verify_certificate: bool,
+}
+
+impl Default for EmailTlsConfig {
+    fn default() -> Self {
+        Self {
+            mode: EmailTlsMode::default(),
+            verify_certificate: true,
+        }
+    }
+}
+
+/// How the connection to the SMTP server is secured.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailTlsMode {
+    /// The connection isn't encrypted.
+    None,
+
+    /// The connection is upgraded to TLS with the `STARTTLS` command, which the server must
+    /// support.
+    #[default]
+    Starttls,
+
+    /// The connection is encrypted with TLS from the start, also known as implicit TLS or SMTPS.
+    Tls,
+}
+
+/// The credentials to authenticate to the SMTP server with.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EmailAuth {
+    /// The username.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub username: String,
+
+    /// The password.
+    #[configurable(metadata(docs::examples = "${SMTP_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+/// How the events are batched into digests.
+///
+/// Once the first event of a digest is received, the events sent to the same recipients are
+/// collected for the duration of the window, then sent in a single email.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DigestConfig {
+    /// The amount of time to collect the events of a digest for.
+    ///
+    /// Set this to `0` to send an email for each event.
+    #[serde(default = "default_window")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "window_secs")]
+    #[configurable(metadata(docs::examples = 300))]
+    pub window: Duration,
+
+    /// The maximum number of events to include in a digest.
+    ///
+    /// The events in excess are only counted at the end of the digest, and dropped.
+    #[serde(default = "default_max_events")]
+    #[configurable(metadata(docs::examples = 20))]
+    pub max_events: NonZeroUsize,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            window: default_window(),
+            max_events: default_max_events(),
+        }
+    }
+}
+
+/// The maximum rate of the emails sent to each recipient.
+///
+/// While a recipient reached the limit, its digests keep collecting events, then are sent
+/// once the rate allows it again.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// The maximum number of emails to send to a recipient in each period.
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_emails: NonZeroUsize,
+
+    /// The duration of the period.
+    #[serde(default = "default_period")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "period_secs")]
+    #[configurable(metadata(docs::examples = 3600))]
+    pub period: Duration,
+}
+
+const fn default_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+const fn default_window() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_max_events() -> NonZeroUsize {
+    NonZeroUsize::new(100).expect("not zero")
+}
+
+const fn default_period() -> Duration {
+    Duration::from_secs(3600)
+}
+
+impl GenerateConfig for EmailSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"host = "smtp.example.com"
+            from = "vector@example.com"
+            to = ["oncall@example.com"]
+            subject = "{{ service }} alert"
+            body = "{{ message }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for EmailSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let transport = self.build_transport()?;
+        let from = self
+            .from
+            .parse::<Mailbox>()
+            .map_err(|error| format!("Invalid sender {:?}: {}", self.from, error))?;
+
+        let healthcheck = healthcheck(transport.clone()).boxed();
+        let sink = EmailSink::new(self, transport, from);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl EmailSinkConfig {
+    fn build_transport(&self) -> crate::Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let parameters = || {
+            TlsParameters::builder(self.host.clone())
+                .dangerous_accept_invalid_certs(!self.tls.verify_certificate)
+                .build()
+        };
+        let (tls, default_port) = match self.tls.mode {
+            EmailTlsMode::None => (Tls::None, 25),
+            EmailTlsMode::Starttls => (Tls::Required(parameters()?), 587),
+            EmailTlsMode::Tls => (Tls::Wrapper(parameters()?), 465),
+        };
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+            .port(self.port.unwrap_or(default_port))
+            .tls(tls)
+            .timeout(Some(self.timeout));
+        if let Some(auth) = &self.auth {
+            builder = builder.credentials(Credentials::new(
+                auth.username.clone(),
+                auth.password.inner().to_owned(),
+            ));
+        }
+        Ok(builder.build())
+    }
+}
+
+async fn healthcheck(transport: AsyncSmtpTransport<Tokio1Executor>) -> crate::Result<()> {
+    if transport.test_connection().await? {
+        Ok(())
+    } else {
+        Err("The SMTP server didn't accept the connection.".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EmailSinkConfig>();
+    }
+}
@@ -0,0 +1,8 @@
+//! The `email` sink.
+//!
+//! Sends events by email through an SMTP server, batched into digests per recipients and rate
+//! limited per recipient, so it can be used for alerting without flooding the inboxes.
+mod config;
+mod sink;
+
+pub use self::config::EmailSinkConfig;
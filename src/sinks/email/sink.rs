@@ -0,0 +1,422 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write as _,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use tokio::time::Instant;
+use vector_core::{
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+        Registered,
+    },
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::config::{DigestConfig, EmailSinkConfig, RateLimitConfig};
+use crate::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{EmailEventsSuppressed, EmailSendError, TemplateRenderingError},
+    sinks::util::StreamSink,
+    template::Template,
+};
+
+/// How often the pending digests are checked for being due.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The separator between the bodies of the events of a digest.
+const BODY_SEPARATOR: &str = "\n\n---\n\n";
+
+pub(super) struct EmailSink {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Template>,
+    subject: Template,
+    body: Template,
+    digests: Digests,
+    rate_limiter: Option<RateLimiter>,
+    bytes_sent: Registered<BytesSent>,
+    events_sent: Registered<EventsSent>,
+}
+
+impl EmailSink {
+    pub(super) fn new(
+        config: &EmailSinkConfig,
+        transport: AsyncSmtpTransport<Tokio1Executor>,
+        from: Mailbox,
+    ) -> Self {
+        Self {
+            transport,
+            from,
+            to: config.to.clone(),
+            subject: config.subject.clone(),
+            body: config.body.clone(),
+            digests: Digests::new(config.digest.clone()),
+            rate_limiter: config.rate_limit.as_ref().map(RateLimiter::new),
+            bytes_sent: register!(BytesSent::from(Protocol("smtp".into()))),
+            events_sent: register!(EventsSent::from(Output(None))),
+        }
+    }
+
+    fn process_event(&mut self, mut event: Event) {
+        let finalizers = event.take_finalizers();
+        let byte_size = event.estimated_json_encoded_size_of();
+
+        match self.render(&event) {
+            Some((recipients, subject, body)) => {
+                let entry = Entry {
+                    subject,
+                    body,
+                    byte_size,
+                    finalizers,
+                };
+                self.digests.add(recipients, entry, Instant::now());
+            }
+            None => finalizers.update_status(EventStatus::Errored),
+        }
+    }
+
+    /// Renders the recipients, subject and body of the email of `event`.
+    fn render(&self, event: &Event) -> Option<(Vec<String>, String, String)> {
+        let mut recipients = self
+            .to
+            .iter()
+            .map(|template| render_template(template, event, "to"))
+            .collect::<Option<Vec<_>>>()?;
+        recipients.sort();
+        recipients.dedup();
+
+        let subject = render_template(&self.subject, event, "subject")?;
+        let body = render_template(&self.body, event, "body")?;
+        Some((recipients, subject, body))
+    }
+
+    /// Sends the digests which are due, or all of them if `force` is set.
+    async fn flush(&mut self, force: bool) {
+        let now = Instant::now();
+        for recipients in self.digests.due(now, force) {
+            // Checked one digest at a time, as the digests sent to overlapping recipients count
+            // towards the same limits.
+            let throttled = !force
+                && self.rate_limiter.as_mut().map_or(false, |rate_limiter| {
+                    !recipients
+                        .iter()
+                        .all(|recipient| rate_limiter.allows(recipient, now))
+                });
+            if throttled {
+                continue;
+            }
+            if let Some(digest) = self.digests.pending.remove(&recipients) {
+                self.send(recipients, digest).await;
+            }
+        }
+    }
+
+    async fn send(&mut self, recipients: Vec<String>, digest: Digest) {
+        let result = match build_message(&self.from, &recipients, &digest) {
+            Ok(message) => {
+                let byte_size = message.formatted().len();
+                self.transport
+                    .send(message)
+                    .await
+                    .map(|_| byte_size)
+                    .map_err(Into::into)
+            }
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(byte_size) => {
+                digest.finalizers.update_status(EventStatus::Delivered);
+                self.bytes_sent.emit(ByteSize(byte_size));
+                self.events_sent
+                    .emit(CountByteSize(digest.bodies.len(), digest.byte_size));
+                if digest.suppressed() > 0 {
+                    emit!(EmailEventsSuppressed {
+                        count: digest.suppressed(),
+                    });
+                }
+
+                if let Some(rate_limiter) = self.rate_limiter.as_mut() {
+                    let now = Instant::now();
+                    for recipient in &recipients {
+                        rate_limiter.record(recipient, now);
+                    }
+                }
+            }
+            Err(error) => {
+                digest.finalizers.update_status(EventStatus::Errored);
+                emit!(EmailSendError {
+                    error,
+                    count: digest.events,
+                });
+            }
+        }
+    }
+}
+
+fn render_template(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+fn build_message(from: &Mailbox, recipients: &[String], digest: &Digest) -> crate::Result<Message> {
+    let mut builder = Message::builder()
+        .from(from.clone())
+        .subject(digest.subject())
+        .header(ContentType::TEXT_PLAIN);
+    for recipient in recipients {
+        let mailbox = recipient
+            .parse::<Mailbox>()
+            .map_err(|error| format!("Invalid recipient {:?}: {}", recipient, error))?;
+        builder = builder.to(mailbox);
+    }
+    Ok(builder.body(digest.body())?)
+}
+
+#[async_trait]
+impl StreamSink<Event> for EmailSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            tokio::select! {
+                event = input.next() => match event {
+                    Some(event) => {
+                        self.process_event(event);
+                        // Sends the email right away if the digests aren't windowed.
+                        self.flush(false).await;
+                    }
+                    None => break,
+                },
+                _ = interval.tick() => self.flush(false).await,
+            }
+        }
+
+        // Don't leave any event behind when stopping, whatever the window and the rate limit.
+        self.flush(true).await;
+        Ok(())
+    }
+}
+
+/// The rendered email of an event.
+struct Entry {
+    subject: String,
+    body: String,
+    byte_size: usize,
+    finalizers: EventFinalizers,
+}
+
+/// The events sent to the same recipients, to be emailed together.
+#[derive(Debug)]
+struct Digest {
+    started_at: Instant,
+    subject: String,
+    bodies: Vec<String>,
+    /// The number of events in the digest, including the suppressed ones.
+    events: usize,
+    byte_size: usize,
+    finalizers: EventFinalizers,
+}
+
+impl Digest {
+    fn new(entry: Entry, now: Instant) -> Self {
+        Self {
+            started_at: now,
+            subject: entry.subject,
+            bodies: vec![entry.body],
+            events: 1,
+            byte_size: entry.byte_size,
+            finalizers: entry.finalizers,
+        }
+    }
+
+    fn add(&mut self, entry: Entry, max_events: usize) {
+        self.events += 1;
+        self.finalizers.merge(entry.finalizers);
+        // The events in excess are acknowledged along with the digest, as they are accounted for.
+        if self.bodies.len() < max_events {
+            self.byte_size += entry.byte_size;
+            self.bodies.push(entry.body);
+        }
+    }
+
+    fn suppressed(&self) -> usize {
+        self.events - self.bodies.len()
+    }
+
+    fn subject(&self) -> String {
+        match self.events - 1 {
+            0 => self.subject.clone(),
+            others => format!("{} (+{} more)", self.subject, others),
+        }
+    }
+
+    fn body(&self) -> String {
+        let mut body = self.bodies.join(BODY_SEPARATOR);
+        if self.suppressed() > 0 {
+            let _ = write!(
+                body,
+                "{}{} more events were left out of this digest.",
+                BODY_SEPARATOR,
+                self.suppressed()
+            );
+        }
+        body
+    }
+}
+
+/// The pending digests, by recipients.
+struct Digests {
+    config: DigestConfig,
+    pending: HashMap<Vec<String>, Digest>,
+}
+
+impl Digests {
+    fn new(config: DigestConfig) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, recipients: Vec<String>, entry: Entry, now: Instant) {
+        match self.pending.get_mut(&recipients) {
+            Some(digest) => digest.add(entry, self.config.max_events.get()),
+            None => {
+                self.pending.insert(recipients, Digest::new(entry, now));
+            }
+        }
+    }
+
+    /// Returns the recipients of the digests whose window elapsed, or of all of them if `force`
+    /// is set.
+    fn due(&self, now: Instant, force: bool) -> Vec<Vec<String>> {
+        self.pending
+            .iter()
+            .filter(|(_, digest)| {
+                force || now.duration_since(digest.started_at) >= self.config.window
+            })
+            .map(|(recipients, _)| recipients.clone())
+            .collect()
+    }
+}
+
+/// Tracks the emails sent to each recipient over the last period.
+struct RateLimiter {
+    max_emails: usize,
+    period: Duration,
+    sent: HashMap<String, VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            max_emails: config.max_emails.get(),
+            period: config.period,
+            sent: HashMap::new(),
+        }
+    }
+
+    /// Returns whether another email can be sent to `recipient`.
+    fn allows(&mut self, recipient: &str, now: Instant) -> bool {
+        match self.sent.get_mut(recipient) {
+            Some(sent) => {
+                while sent
+                    .front()
+                    .map_or(false, |sent_at| now.duration_since(*sent_at) >= self.period)
+                {
+                    sent.pop_front();
+                }
+                sent.len() < self.max_emails
+            }
+            None => true,
+        }
+    }
+
+    fn record(&mut self, recipient: &str, now: Instant) {
+        self.sent
+            .entry(recipient.to_owned())
+            .or_default()
+            .push_back(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    fn entry(subject: &str, body: &str) -> Entry {
+        Entry {
+            subject: subject.to_owned(),
+            body: body.to_owned(),
+            byte_size: body.len(),
+            finalizers: EventFinalizers::default(),
+        }
+    }
+
+    #[test]
+    fn batches_events_into_digests() {
+        let mut digests = Digests::new(DigestConfig {
+            window: Duration::from_secs(60),
+            max_events: NonZeroUsize::new(2).unwrap(),
+        });
+        let start = Instant::now();
+        let oncall = vec!["oncall@example.com".to_owned()];
+        let team = vec!["team@example.com".to_owned()];
+
+        digests.add(oncall.clone(), entry("Disk full", "/var is full"), start);
+        digests.add(oncall.clone(), entry("Disk full", "/tmp is full"), start);
+        digests.add(oncall.clone(), entry("Disk full", "/home is full"), start);
+        digests.add(
+            team,
+            entry("Deploy failed", "api"),
+            start + Duration::from_secs(30),
+        );
+
+        assert!(digests.due(start, false).is_empty());
+        assert_eq!(digests.due(start, true).len(), 2);
+
+        let due = digests.due(start + Duration::from_secs(60), false);
+        assert_eq!(due, vec![oncall.clone()]);
+        let digest = &digests.pending[&oncall];
+        assert_eq!(digest.subject(), "Disk full (+2 more)");
+        assert_eq!(
+            digest.body(),
+            "/var is full\n\n---\n\n/tmp is full\n\n---\n\n1 more events were left out of this digest."
+        );
+        assert_eq!(digest.suppressed(), 1);
+    }
+
+    #[test]
+    fn limits_the_rate_of_each_recipient() {
+        let mut rate_limiter = RateLimiter::new(&RateLimitConfig {
+            max_emails: NonZeroUsize::new(2).unwrap(),
+            period: Duration::from_secs(60),
+        });
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            assert!(rate_limiter.allows("oncall@example.com", start));
+            rate_limiter.record("oncall@example.com", start);
+        }
+        assert!(!rate_limiter.allows("oncall@example.com", start));
+        assert!(rate_limiter.allows("team@example.com", start));
+        assert!(rate_limiter.allows("oncall@example.com", start + Duration::from_secs(60)));
+    }
+}
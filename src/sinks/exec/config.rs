@@ -0,0 +1,110 @@
+use std::{path::PathBuf, time::Duration};
+
+use codecs::{
+    encoding::{Framer, FramingConfig},
+    JsonSerializerConfig,
+};
+use futures::{future, FutureExt};
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{exec::sink::ExecSink, Healthcheck, VectorSink},
+};
+
+/// Configuration for the `exec` sink.
+#[configurable_component(sink("exec"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExecSinkConfig {
+    /// The command to run, plus any arguments required.
+    ///
+    /// The command is started when the sink starts, and encoded events are written to its
+    /// standard input. If the command exits, it is started again after `respawn_interval_secs`.
+    #[configurable(metadata(docs::examples = "tee", docs::examples = "/var/log/vector.out"))]
+    pub command: Vec<String>,
+
+    /// The directory in which to run the command.
+    pub working_directory: Option<PathBuf>,
+
+    /// The amount of time, in seconds, to wait before restarting the command after it exits.
+    #[serde(default = "default_respawn_interval_secs")]
+    pub respawn_interval_secs: u64,
+
+    #[serde(flatten)]
+    pub encoding: EncodingConfigWithFraming,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_respawn_interval_secs() -> u64 {
+    5
+}
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum ExecSinkConfigError {
+    #[snafu(display("A non-empty list for command must be provided"))]
+    CommandEmpty,
+}
+
+impl GenerateConfig for ExecSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            command: vec!["cat".to_owned()],
+            working_directory: None,
+            respawn_interval_secs: default_respawn_interval_secs(),
+            encoding: (None::<FramingConfig>, JsonSerializerConfig::default()).into(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for ExecSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.command.is_empty() {
+            return Err(ExecSinkConfigError::CommandEmpty.into());
+        }
+
+        let transformer = self.encoding.transformer();
+        let (framer, serializer) = self.encoding.build(SinkType::StreamBased)?;
+        let encoder = Encoder::<Framer>::new(framer, serializer);
+
+        let sink = VectorSink::from_event_streamsink(ExecSink {
+            command: self.command.clone(),
+            working_directory: self.working_directory.clone(),
+            respawn_interval: Duration::from_secs(self.respawn_interval_secs),
+            transformer,
+            encoder,
+        });
+
+        Ok((sink, future::ok(()).boxed()))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().1.input_type())
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExecSinkConfig>();
+    }
+}
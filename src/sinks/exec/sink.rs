@@ -0,0 +1,253 @@
+use std::{io, path::PathBuf, process::ExitStatus, time::Duration};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use codecs::encoding::Framer;
+use futures::{stream::BoxStream, StreamExt};
+use tokio::{
+    io::AsyncWriteExt,
+    process::{Child, ChildStdin, Command},
+    time::{sleep, Instant},
+};
+use tokio_util::codec::Encoder as _;
+use vector_core::{
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    event::{Event, EventStatus, Finalizable},
+    internal_events::{
+        ExecCommandExecuted, ExecCommandRestarted, ExecSpawnError, ExecStdinWriteError,
+    },
+    sinks::util::StreamSink,
+};
+
+pub struct ExecSink {
+    pub command: Vec<String>,
+    pub working_directory: Option<PathBuf>,
+    pub respawn_interval: Duration,
+    pub transformer: Transformer,
+    pub encoder: Encoder<Framer>,
+}
+
+/// A spawned instance of the command, along with the pipe to its standard input.
+struct RunningCommand {
+    child: Child,
+    stdin: ChildStdin,
+    started: Instant,
+}
+
+enum Next {
+    Event(Option<Event>),
+    Exited(io::Result<ExitStatus>),
+}
+
+impl ExecSink {
+    fn spawn(&self) -> io::Result<RunningCommand> {
+        let mut command = Command::new(&self.command[0]);
+        command.args(&self.command[1..]);
+        command.kill_on_drop(true);
+
+        if let Some(current_dir) = &self.working_directory {
+            command.current_dir(current_dir);
+        }
+
+        command.stdin(std::process::Stdio::piped());
+        command.stdout(std::process::Stdio::null());
+        command.stderr(std::process::Stdio::null());
+
+        let mut child = command.spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+
+        Ok(RunningCommand {
+            child,
+            stdin,
+            started: Instant::now(),
+        })
+    }
+
+    /// Spawns the command, retrying after the respawn interval until it succeeds.
+    async fn spawn_with_retry(&self, command_str: &str) -> RunningCommand {
+        loop {
+            match self.spawn() {
+                Ok(running) => return running,
+                Err(error) => {
+                    emit!(ExecSpawnError {
+                        command: command_str,
+                        error,
+                    });
+                    sleep(self.respawn_interval).await;
+                }
+            }
+        }
+    }
+
+    /// Records the exit of a previous instance of the command, and starts a new one.
+    async fn respawn(
+        &self,
+        command_str: &str,
+        started: Instant,
+        exit_status: io::Result<ExitStatus>,
+        restarts: &mut u64,
+    ) -> RunningCommand {
+        emit!(ExecCommandExecuted {
+            command: command_str,
+            exit_status: exit_status.ok().and_then(|status| status.code()),
+            exec_duration: started.elapsed(),
+        });
+
+        sleep(self.respawn_interval).await;
+        *restarts += 1;
+        emit!(ExecCommandRestarted {
+            command: command_str,
+            restarts: *restarts,
+            delay: self.respawn_interval,
+        });
+
+        self.spawn_with_retry(command_str).await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for ExecSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let command_str = self.command.join(" ");
+        let bytes_sent = register!(BytesSent::from(Protocol("exec".into())));
+        let events_sent = register!(EventsSent::from(Output(None)));
+
+        let mut restarts = 0;
+        let mut running = self.spawn_with_retry(&command_str).await;
+
+        loop {
+            // Watch for the command exiting while waiting for events, so it can be restarted
+            // right away rather than on the next write.
+            let next = tokio::select! {
+                event = input.next() => Next::Event(event),
+                exit_status = running.child.wait() => Next::Exited(exit_status),
+            };
+
+            let mut event = match next {
+                Next::Event(Some(event)) => event,
+                Next::Event(None) => break,
+                Next::Exited(exit_status) => {
+                    running = self
+                        .respawn(&command_str, running.started, exit_status, &mut restarts)
+                        .await;
+                    continue;
+                }
+            };
+
+            let event_byte_size = event.estimated_json_encoded_size_of();
+            self.transformer.transform(&mut event);
+
+            let finalizers = event.take_finalizers();
+            let mut bytes = BytesMut::new();
+            self.encoder.encode(event, &mut bytes).map_err(|_| {
+                // Error is handled by `Encoder`.
+                finalizers.update_status(EventStatus::Errored);
+            })?;
+
+            // Writes only complete once the command has read enough of its input, so a command that
+            // can't keep up applies backpressure to the rest of the topology.
+            while let Err(error) = running.stdin.write_all(&bytes).await {
+                emit!(ExecStdinWriteError {
+                    command: &command_str,
+                    error,
+                });
+                _ = running.child.start_kill();
+                let exit_status = running.child.wait().await;
+                running = self
+                    .respawn(&command_str, running.started, exit_status, &mut restarts)
+                    .await;
+            }
+
+            finalizers.update_status(EventStatus::Delivered);
+            events_sent.emit(CountByteSize(1, event_byte_size));
+            bytes_sent.emit(ByteSize(bytes.len()));
+        }
+
+        // Closing standard input signals the command that no more events are coming.
+        let RunningCommand {
+            mut child,
+            stdin,
+            started,
+        } = running;
+        drop(stdin);
+        let exit_status = child.wait().await;
+        emit!(ExecCommandExecuted {
+            command: &command_str,
+            exit_status: exit_status.ok().and_then(|status| status.code()),
+            exec_duration: started.elapsed(),
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use codecs::{JsonSerializerConfig, NewlineDelimitedEncoder};
+    use futures_util::stream;
+    use vector_core::sink::VectorSink;
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        test_util::components::{run_and_assert_sink_compliance, SINK_TAGS},
+    };
+
+    fn exec_sink(command: Vec<String>) -> ExecSink {
+        ExecSink {
+            command,
+            working_directory: None,
+            respawn_interval: Duration::from_millis(10),
+            transformer: Default::default(),
+            encoder: Encoder::<Framer>::new(
+                NewlineDelimitedEncoder::new().into(),
+                JsonSerializerConfig::default().build().into(),
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn component_spec_compliance() {
+        let sink = exec_sink(vec!["cat".to_owned()]);
+
+        run_and_assert_sink_compliance(
+            VectorSink::from_event_streamsink(sink),
+            stream::iter(vec![Event::Log(LogEvent::from("foo"))]),
+            &SINK_TAGS,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn writes_encoded_events_to_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.log");
+        let sink = exec_sink(vec![
+            "sh".to_owned(),
+            "-c".to_owned(),
+            format!("cat > {}", path.display()),
+        ]);
+
+        let events = vec![
+            Event::Log(LogEvent::from("first")),
+            Event::Log(LogEvent::from("second")),
+        ];
+        VectorSink::from_event_streamsink(sink)
+            .run_events(events)
+            .await
+            .unwrap();
+
+        let output = std::fs::read_to_string(&path).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("first"));
+        assert!(lines[1].contains("second"));
+    }
+}
@@ -1,11 +1,16 @@
 use std::convert::TryFrom;
-use std::time::{Duration, Instant};
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant, SystemTime};
 
 use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
 use codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{Framer, FramingConfig, SerializerConfig},
     TextSerializerConfig,
 };
 use futures::{
@@ -17,8 +22,11 @@ use serde_with::serde_as;
 use tokio::{
     fs::{self, File},
     io::AsyncWriteExt,
+    sync::mpsc,
 };
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::codec::Encoder as _;
+use tracing::Instrument;
 use vector_config::configurable_component;
 use vector_core::{
     internal_event::{CountByteSize, EventsSent, InternalEventHandle as _, Output, Registered},
@@ -28,13 +36,14 @@ use vector_core::{
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     expiring_hash_map::ExpiringHashMap,
     internal_events::{FileBytesSent, FileIoError, FileOpen, TemplateRenderingError},
     sinks::util::StreamSink,
     template::Template,
 };
 mod bytes_path;
+mod rotation;
 
 use bytes_path::BytesPath;
 
@@ -73,6 +82,38 @@ pub struct FileSinkConfig {
     )]
     pub compression: Compression,
 
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub rotation: RotationConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub retention: RetentionConfig,
+
+    /// The interval at which the written data is flushed and synced to disk.
+    ///
+    /// When set, the events are only acknowledged once they are synced to disk. Otherwise, the data
+    /// is only synced when the files are closed, and the events are acknowledged once written.
+    #[serde(default)]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(rename = "fsync_interval_secs")]
+    #[configurable(metadata(docs::examples = 1))]
+    pub fsync_interval: Option<Duration>,
+
+    /// The number of writers the files are written with, concurrently.
+    ///
+    /// The files are partitioned across the writers by path, for the events written to a path
+    /// to stay in order, so that a file on a slow disk doesn't hold back the writes to the others.
+    /// There's a single writer by default.
+    #[configurable(metadata(docs::examples = 4))]
+    pub writers: Option<NonZeroUsize>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -89,6 +130,10 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Default::default(),
+            rotation: Default::default(),
+            retention: Default::default(),
+            fsync_interval: None,
+            writers: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -99,9 +144,11 @@ const fn default_idle_timeout() -> Duration {
     Duration::from_secs(30)
 }
 
+/// The number of events queued for each writer, when there's more than one.
+const WRITER_QUEUE_SIZE: usize = 100;
+
 /// Compression configuration.
 // TODO: Why doesn't this already use `crate::sinks::util::Compression`
-// `crate::sinks::util::Compression` doesn't support zstd yet
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -121,6 +168,58 @@ pub enum Compression {
     None,
 }
 
+/// Rotation configuration.
+///
+/// Once the file written to is too large or too old, it's rotated: it's renamed by appending the
+/// time of the rotation to its path, and a new file is started at the same path. The file is
+/// checked whenever an event is written to it.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// The size of the file after which it's rotated.
+    ///
+    /// If the file is compressed, this is the size of the data written to it before compression.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 104857600))]
+    pub max_bytes: Option<NonZeroU64>,
+
+    /// The age of the file after which it's rotated.
+    #[serde(default)]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(rename = "max_age_secs")]
+    #[configurable(metadata(docs::examples = 3600))]
+    pub max_age: Option<Duration>,
+
+    /// The compression of the rotated files, appending the extension of the compression format to
+    /// their path.
+    ///
+    /// This can't be used if the file is already compressed while it's written.
+    #[serde(default)]
+    pub compression: Compression,
+}
+
+/// Retention configuration.
+///
+/// The rotated files which are in excess or too old are deleted whenever a file is rotated.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RetentionConfig {
+    /// The maximum number of rotated files kept for each path, the oldest being deleted first.
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_files: Option<NonZeroUsize>,
+
+    /// The age of the rotated files after which they are deleted.
+    #[serde(default)]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(rename = "max_age_secs")]
+    #[configurable(metadata(docs::examples = 604800))]
+    pub max_age: Option<Duration>,
+}
+
 enum OutFile {
     Regular(File),
     Gzip(GzipEncoder<File>),
@@ -152,6 +251,14 @@ impl OutFile {
         }
     }
 
+    async fn flush(&mut self) -> Result<(), std::io::Error> {
+        match self {
+            OutFile::Regular(file) => file.flush().await,
+            OutFile::Gzip(gzip) => gzip.flush().await,
+            OutFile::Zstd(zstd) => zstd.flush().await,
+        }
+    }
+
     async fn write_all(&mut self, src: &[u8]) -> Result<(), std::io::Error> {
         match self {
             OutFile::Regular(file) => file.write_all(src).await,
@@ -168,12 +275,60 @@ impl OutFile {
     }
 }
 
+/// A file open for writing, along with the state of its rotation.
+struct OpenFile {
+    file: OutFile,
+    /// The number of bytes written to the file, before compression.
+    size: u64,
+    /// When the file was created, or opened if its creation time isn't known.
+    created_at: SystemTime,
+    /// The finalizers of the events written since the file was last synced, if it's synced
+    /// periodically.
+    pending: EventFinalizers,
+    /// The encoder of the events written to the file, which knows whether the file was started,
+    /// such as with the header of the CSV codec.
+    encoder: Encoder<Framer>,
+}
+
+impl OpenFile {
+    /// Flushes and syncs the file, then acknowledges the events written to it.
+    async fn sync(&mut self) -> Result<(), std::io::Error> {
+        let result = match self.file.flush().await {
+            Ok(()) => self.file.sync_all().await,
+            Err(error) => Err(error),
+        };
+        self.finalize(&result);
+        result
+    }
+
+    async fn close(&mut self) -> Result<(), std::io::Error> {
+        let result = self.file.close().await;
+        self.finalize(&result);
+        result
+    }
+
+    fn finalize(&mut self, result: &Result<(), std::io::Error>) {
+        let status = match result {
+            Ok(()) => EventStatus::Delivered,
+            Err(_) => EventStatus::Errored,
+        };
+        std::mem::take(&mut self.pending).update_status(status);
+    }
+
+    fn age(&self) -> Duration {
+        self.created_at.elapsed().unwrap_or_default()
+    }
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for FileSinkConfig {
     async fn build(
         &self,
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if self.rotation.compression != Compression::None && self.compression != Compression::None {
+            return Err("`rotation.compression` can't be used along with `compression`.".into());
+        }
         let sink = FileSink::new(self)?;
         Ok((
             super::VectorSink::from_event_streamsink(sink),
@@ -182,12 +337,21 @@ impl SinkConfig for FileSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        Input::new(self.encoding.config().1.input_type() & (DataType::Log | DataType::Metric))
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn pass_through_serializer(&self) -> Option<&SerializerConfig> {
+        // The path of each event would be rendered from fields the relayed frames don't have.
+        if self.path.is_dynamic() {
+            None
+        } else {
+            self.encoding.pass_through_serializer()
+        }
+    }
 }
 
 pub struct FileSink {
@@ -195,9 +359,17 @@ pub struct FileSink {
     transformer: Transformer,
     encoder: Encoder<Framer>,
     idle_timeout: Duration,
-    files: ExpiringHashMap<Bytes, OutFile>,
+    files: ExpiringHashMap<Bytes, OpenFile>,
     compression: Compression,
+    rotation: RotationConfig,
+    retention: RetentionConfig,
+    fsync_interval: Option<Duration>,
+    writers: usize,
     events_sent: Registered<EventsSent>,
+    /// The number of files open across the writers, of which this one last reported
+    /// `reported_open_files`.
+    open_files: Arc<AtomicUsize>,
+    reported_open_files: usize,
 }
 
 impl FileSink {
@@ -213,10 +385,49 @@ impl FileSink {
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            rotation: config.rotation.clone(),
+            retention: config.retention.clone(),
+            fsync_interval: config.fsync_interval,
+            writers: config.writers.map_or(1, NonZeroUsize::get),
             events_sent: register!(EventsSent::from(Output(None))),
+            open_files: Arc::default(),
+            reported_open_files: 0,
         })
     }
 
+    /// Creates another writer of the files of the sink.
+    fn writer(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            transformer: self.transformer.clone(),
+            encoder: self.encoder.clone(),
+            idle_timeout: self.idle_timeout,
+            files: ExpiringHashMap::default(),
+            compression: self.compression,
+            rotation: self.rotation.clone(),
+            retention: self.retention.clone(),
+            fsync_interval: self.fsync_interval,
+            writers: 1,
+            events_sent: register!(EventsSent::from(Output(None))),
+            open_files: Arc::clone(&self.open_files),
+            reported_open_files: 0,
+        }
+    }
+
+    /// Reports the number of files open across the writers, given this one has `count` of them
+    /// open.
+    fn emit_open_files(&mut self, count: usize) {
+        let total = if count >= self.reported_open_files {
+            let added = count - self.reported_open_files;
+            self.open_files.fetch_add(added, Ordering::Relaxed) + added
+        } else {
+            let removed = self.reported_open_files - count;
+            self.open_files.fetch_sub(removed, Ordering::Relaxed) - removed
+        };
+        self.reported_open_files = count;
+        emit!(FileOpen { count: total });
+    }
+
     /// Uses pass the `event` to `self.path` template to obtain the file path
     /// to store the event as.
     fn partition_event(&mut self, event: &Event) -> Option<bytes::Bytes> {
@@ -242,6 +453,9 @@ impl FileSink {
     }
 
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> crate::Result<()> {
+        // The interval is only ticking if the files are synced periodically.
+        let mut sync_interval =
+            tokio::time::interval(self.fsync_interval.unwrap_or(Duration::from_secs(1)));
         loop {
             tokio::select! {
                 event = input.next() => {
@@ -267,9 +481,7 @@ impl FileSink {
                                 }
                             }
 
-                            emit!(FileOpen {
-                                count: 0
-                            });
+                            self.emit_open_files(0);
 
                             break;
                         }
@@ -293,8 +505,20 @@ impl FileSink {
                                 });
                             }
                             drop(expired_file); // ignore close error
-                            emit!(FileOpen {
-                                count: self.files.len()
+                            self.emit_open_files(self.files.len());
+                        }
+                    }
+                }
+                _ = sync_interval.tick(), if self.fsync_interval.is_some() => {
+                    for (path, file) in self.files.iter_mut() {
+                        let dropped_events = file.pending.len();
+                        if let Err(error) = file.sync().await {
+                            emit!(FileIoError {
+                                error,
+                                code: "failed_syncing_file",
+                                message: "Failed to sync the file.",
+                                path,
+                                dropped_events,
                             });
                         }
                     }
@@ -318,6 +542,12 @@ impl FileSink {
             }
         };
 
+        if self.files.get(&path).map_or(false, |file| {
+            rotation::is_due(&self.rotation, file.size, file.age())
+        }) {
+            self.rotate(&path).await;
+        }
+
         let next_deadline = self.deadline_at();
         trace!(message = "Computed next deadline.", next_deadline = ?next_deadline, path = ?path);
 
@@ -326,7 +556,7 @@ impl FileSink {
             file
         } else {
             trace!(message = "Opening new file.", ?path);
-            let file = match open_file(BytesPath::new(path.clone())).await {
+            let file = match self.open(&path).await {
                 Ok(file) => file,
                 Err(error) => {
                     // We couldn't open the file for this event.
@@ -344,21 +574,23 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
-
-            self.files.insert_at(path.clone(), outfile, next_deadline);
-            emit!(FileOpen {
-                count: self.files.len()
-            });
+            self.files.insert_at(path.clone(), file, next_deadline);
+            self.emit_open_files(self.files.len());
             self.files.get_mut(&path).unwrap()
         };
 
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(&mut file.file, event, &self.transformer, &mut file.encoder).await
+        {
             Ok(byte_size) => {
-                finalizers.update_status(EventStatus::Delivered);
+                file.size += byte_size as u64;
+                if self.fsync_interval.is_some() {
+                    file.pending.merge(finalizers);
+                } else {
+                    finalizers.update_status(EventStatus::Delivered);
+                }
                 self.events_sent.emit(CountByteSize(1, event_size));
                 emit!(FileBytesSent {
                     byte_size,
@@ -379,6 +611,77 @@ impl FileSink {
     }
 }
 
+impl FileSink {
+    /// Opens the file at `path`, rotating it first if it's already due for rotation.
+    async fn open(&self, path: &Bytes) -> std::io::Result<OpenFile> {
+        let mut file = open_file(BytesPath::new(path.clone())).await?;
+        let mut metadata = file.metadata().await?;
+        let age = |metadata: &std::fs::Metadata| {
+            metadata
+                .created()
+                .ok()
+                .and_then(|created| created.elapsed().ok())
+                .unwrap_or_default()
+        };
+        if metadata.len() > 0 && rotation::is_due(&self.rotation, metadata.len(), age(&metadata)) {
+            drop(file);
+            self.rotate_path(path).await;
+            file = open_file(BytesPath::new(path.clone())).await?;
+            metadata = file.metadata().await?;
+        }
+
+        let mut encoder = self.encoder.clone();
+        if metadata.len() > 0 {
+            // Appending to an existing file.
+            encoder.set_output_started();
+        }
+        Ok(OpenFile {
+            file: OutFile::new(file, self.compression),
+            size: metadata.len(),
+            created_at: SystemTime::now() - age(&metadata),
+            pending: EventFinalizers::default(),
+            encoder,
+        })
+    }
+
+    /// Closes the file at `path`, then rotates it.
+    async fn rotate(&mut self, path: &Bytes) {
+        if let Some((mut file, _)) = self.files.remove(path) {
+            if let Err(error) = file.close().await {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_closing_file",
+                    message: "Failed to close file.",
+                    path,
+                    dropped_events: 0,
+                });
+            }
+            self.emit_open_files(self.files.len());
+        }
+        self.rotate_path(path).await;
+    }
+
+    async fn rotate_path(&self, path: &Bytes) {
+        debug!(message = "Rotating file.", ?path);
+        let bytes_path = BytesPath::new(path.clone());
+        if let Err(error) = rotation::rotate(
+            bytes_path.as_ref(),
+            self.rotation.compression,
+            &self.retention,
+        )
+        .await
+        {
+            emit!(FileIoError {
+                error,
+                code: "failed_rotating_file",
+                message: "Failed to rotate the file.",
+                path,
+                dropped_events: 0,
+            });
+        }
+    }
+}
+
 async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
     let parent = path.as_ref().parent();
 
@@ -411,10 +714,43 @@ async fn write_event_to_file(
 
 #[async_trait]
 impl StreamSink<Event> for FileSink {
-    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        FileSink::run(&mut self, input)
-            .await
-            .expect("file sink error");
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        if self.writers == 1 {
+            FileSink::run(&mut self, input)
+                .await
+                .expect("file sink error");
+            return Ok(());
+        }
+
+        let (senders, writers): (Vec<_>, Vec<_>) = (0..self.writers)
+            .map(|_| {
+                let (tx, rx) = mpsc::channel(WRITER_QUEUE_SIZE);
+                let mut writer = self.writer();
+                let handle = tokio::spawn(
+                    async move { writer.run(ReceiverStream::new(rx).boxed()).await }
+                        .in_current_span(),
+                );
+                (tx, handle)
+            })
+            .unzip();
+
+        while let Some(event) = input.next().await {
+            // The events whose path can't be rendered are dropped by any writer.
+            let index = self.path.render(&event).map_or(0, |path| {
+                (seahash::hash(&path) % senders.len() as u64) as usize
+            });
+            if senders[index].send(event).await.is_err() {
+                error!(message = "File writer stopped unexpectedly.");
+                return Err(());
+            }
+        }
+
+        drop(senders);
+        for writer in future::join_all(writers).await {
+            writer
+                .expect("file writer panicked")
+                .expect("file sink error");
+        }
         Ok(())
     }
 }
@@ -423,6 +759,7 @@ impl StreamSink<Event> for FileSink {
 mod tests {
     use std::convert::TryInto;
 
+    use codecs::encoding::{CsvSerializerConfig, CsvSerializerOptions};
     use futures::{stream, SinkExt};
     use similar_asserts::assert_eq;
     use vector_core::{event::LogEvent, sink::VectorSink};
@@ -451,6 +788,10 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: Default::default(),
+            retention: Default::default(),
+            fsync_interval: None,
+            writers: None,
             acknowledgements: Default::default(),
         };
 
@@ -473,6 +814,10 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Gzip,
+            rotation: Default::default(),
+            retention: Default::default(),
+            fsync_interval: None,
+            writers: None,
             acknowledgements: Default::default(),
         };
 
@@ -495,6 +840,10 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Zstd,
+            rotation: Default::default(),
+            retention: Default::default(),
+            fsync_interval: None,
+            writers: None,
             acknowledgements: Default::default(),
         };
 
@@ -510,6 +859,15 @@ mod tests {
 
     #[tokio::test]
     async fn many_partitions() {
+        test_many_partitions(None).await;
+    }
+
+    #[tokio::test]
+    async fn many_partitions_many_writers() {
+        test_many_partitions(NonZeroUsize::new(3)).await;
+    }
+
+    async fn test_many_partitions(writers: Option<NonZeroUsize>) {
         let directory = temp_dir();
 
         let mut template = directory.to_string_lossy().to_string();
@@ -522,6 +880,10 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: Default::default(),
+            retention: Default::default(),
+            fsync_interval: None,
+            writers,
             acknowledgements: Default::default(),
         };
 
@@ -599,6 +961,10 @@ mod tests {
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: Default::default(),
+            retention: Default::default(),
+            fsync_interval: None,
+            writers: None,
             acknowledgements: Default::default(),
         };
 
@@ -642,6 +1008,80 @@ mod tests {
         sink_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn rotation_by_size() {
+        let directory = temp_dir();
+        let path = directory.join("vector.log");
+
+        let config = FileSinkConfig {
+            path: path.to_string_lossy().as_ref().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            compression: Compression::None,
+            rotation: RotationConfig {
+                max_bytes: NonZeroU64::new(100),
+                ..Default::default()
+            },
+            retention: Default::default(),
+            fsync_interval: None,
+            writers: None,
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _) = random_lines_with_stream(10, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        // The lines are 65 bytes long, so the files are rotated once they hold two lines.
+        let mut files = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files.len(), 5);
+        // The file being written to sorts before the rotated files.
+        files.rotate_left(1);
+        let output = files
+            .into_iter()
+            .flat_map(lines_from_file)
+            .collect::<Vec<_>>();
+        assert_eq!(input, output);
+    }
+
+    #[tokio::test]
+    async fn csv_header_once_per_file() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (
+                None::<FramingConfig>,
+                CsvSerializerConfig::new(CsvSerializerOptions {
+                    fields: vec!["message".to_string().try_into().unwrap()],
+                    header: true,
+                    ..Default::default()
+                }),
+            )
+                .into(),
+            compression: Compression::None,
+            rotation: Default::default(),
+            retention: Default::default(),
+            fsync_interval: None,
+            writers: None,
+            acknowledgements: Default::default(),
+        };
+
+        run_assert_log_sink(config.clone(), vec!["a".to_string(), "b".to_string()]).await;
+        // Appending to the file doesn't write the header again.
+        run_assert_log_sink(config, vec!["c".to_string()]).await;
+
+        assert_eq!(
+            lines_from_file(template),
+            ["message", "a", "b", "c"].map(String::from)
+        );
+    }
+
     async fn run_assert_log_sink(config: FileSinkConfig, events: Vec<String>) {
         run_assert_sink(
             config,
@@ -0,0 +1,172 @@
+//! Rotation of the files written by the sink, and retention of the rotated files.
+//!
+//! A rotated file is renamed by appending the time of the rotation to its path, so the rotated
+//! files of a path sort in the order they were rotated in, and are recognized by their name.
+use std::{
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use tokio::{
+    fs::{self, File},
+    io::AsyncReadExt,
+};
+
+use super::{Compression, OutFile, RetentionConfig};
+
+/// The format of the time appended to the path of the rotated files.
+const ROTATION_TIME_FORMAT: &str = "%Y%m%dT%H%M%S%.6f";
+
+/// Renames the file at `path` as a rotated file, compresses it, then deletes the rotated files of
+/// the same path which aren't retained anymore.
+pub(super) async fn rotate(
+    path: &Path,
+    compression: Compression,
+    retention: &RetentionConfig,
+) -> io::Result<()> {
+    let rotated = with_suffix(path, &Utc::now().format(ROTATION_TIME_FORMAT).to_string());
+    fs::rename(path, &rotated).await?;
+    compress(&rotated, compression).await?;
+    apply_retention(path, retention).await
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(suffix);
+    path.into()
+}
+
+/// Replaces the file at `path` with its compressed copy.
+async fn compress(path: &Path, compression: Compression) -> io::Result<()> {
+    let extension = match compression {
+        Compression::Gzip => "gz",
+        Compression::Zstd => "zst",
+        Compression::None => return Ok(()),
+    };
+
+    let mut source = File::open(path).await?;
+    let mut compressed = OutFile::new(
+        File::create(with_suffix(path, extension)).await?,
+        compression,
+    );
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = source.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        compressed.write_all(&buffer[..read]).await?;
+    }
+    compressed.close().await?;
+
+    fs::remove_file(path).await
+}
+
+/// Deletes the rotated files of `path` which are too old, or in excess.
+async fn apply_retention(path: &Path, retention: &RetentionConfig) -> io::Result<()> {
+    if retention.max_files.is_none() && retention.max_age.is_none() {
+        return Ok(());
+    }
+
+    let mut rotated = rotated_files(path).await?;
+    // Newest first.
+    rotated.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let now = Utc::now();
+    let max_age = retention
+        .max_age
+        .and_then(|max_age| chrono::Duration::from_std(max_age).ok());
+    for (index, (file, rotated_at)) in rotated.into_iter().enumerate() {
+        let in_excess = retention
+            .max_files
+            .map_or(false, |max_files| index >= max_files.get());
+        let too_old = max_age.map_or(false, |max_age| now - rotated_at > max_age);
+        if in_excess || too_old {
+            debug!(message = "Deleting rotated file.", path = ?file);
+            fs::remove_file(&file).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the rotated files of `path`, along with the time they were rotated at.
+async fn rotated_files(path: &Path) -> io::Result<Vec<(PathBuf, DateTime<Utc>)>> {
+    let (directory, file_name) = match (path.parent(), path.file_name()) {
+        (Some(directory), Some(file_name)) => (directory, file_name.to_string_lossy()),
+        _ => return Ok(Vec::new()),
+    };
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+    let prefix = format!("{}.", file_name);
+
+    let mut rotated = Vec::new();
+    let mut entries = fs::read_dir(directory).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name();
+        let rotated_at = name
+            .to_string_lossy()
+            .strip_prefix(&prefix)
+            .and_then(parse_rotation_time);
+        if let Some(rotated_at) = rotated_at {
+            rotated.push((entry.path(), rotated_at));
+        }
+    }
+    Ok(rotated)
+}
+
+fn parse_rotation_time(suffix: &str) -> Option<DateTime<Utc>> {
+    let time = suffix
+        .strip_suffix(".gz")
+        .or_else(|| suffix.strip_suffix(".zst"))
+        .unwrap_or(suffix);
+    NaiveDateTime::parse_from_str(time, ROTATION_TIME_FORMAT)
+        .ok()
+        .map(|time| DateTime::from_utc(time, Utc))
+}
+
+/// Returns whether a file of the given size and age is due for rotation.
+pub(super) fn is_due(config: &super::RotationConfig, size: u64, age: Duration) -> bool {
+    config
+        .max_bytes
+        .map_or(false, |max_bytes| size >= max_bytes.get())
+        || config.max_age.map_or(false, |max_age| age >= max_age)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[tokio::test]
+    async fn rotates_and_retains_files() {
+        let directory = temp_dir();
+        fs::create_dir_all(&directory).await.unwrap();
+        let path = directory.join("vector.log");
+        let retention = RetentionConfig {
+            max_files: NonZeroUsize::new(2),
+            max_age: None,
+        };
+
+        for _ in 0..3 {
+            fs::write(&path, "line\n").await.unwrap();
+            rotate(&path, Compression::Gzip, &retention).await.unwrap();
+        }
+
+        let rotated = rotated_files(&path).await.unwrap();
+        assert_eq!(rotated.len(), 2);
+        assert!(rotated
+            .iter()
+            .all(|(file, _)| file.extension() == Some("gz".as_ref())));
+        assert!(!path.exists());
+    }
+}
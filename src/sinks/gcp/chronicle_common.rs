@@ -0,0 +1,718 @@
+//! Common pieces of the sinks sending data to the Google Chronicle [ingestion API][api].
+//!
+//! The events are batched by log type, and each batch is split into requests whose encoded body
+//! fits within the size limit of the API.
+//!
+//! [api]: https://cloud.google.com/chronicle/docs/reference/ingestion-api
+use std::{fmt, io, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, BoxStream};
+use futures_util::{future::BoxFuture, task::Poll, StreamExt};
+use http::{header::HeaderValue, Request, StatusCode, Uri};
+use hyper::Body;
+use serde_json::json;
+use snafu::Snafu;
+use tokio_util::codec::Encoder as _;
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_config::configurable_component;
+use vector_core::{
+    event::{Event, EventFinalizers, Finalizable},
+    partition::Partitioner,
+    sink::StreamSink,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{self, Transformer},
+    gcp::GcpAuthenticator,
+    http::HttpClient,
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
+    sinks::{
+        gcs_common::{config::healthcheck_response, service::GcsResponse},
+        util::{
+            encoding::Encoder, metadata::RequestMetadataBuilder, request_builder::EncodeResult,
+            retries::RetryLogic, Compression, RequestBuilder, SinkBuilderExt,
+        },
+        Healthcheck,
+    },
+    template::{Template, TemplateParseError},
+};
+
+/// The maximum size of the body of a request accepted by the ingestion API.
+pub const MAX_REQUEST_BYTES: usize = 1_000_000;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum GcsHealthcheckError {
+    #[snafu(display("log_type template parse error: {}", source))]
+    LogTypeTemplate { source: TemplateParseError },
+
+    #[snafu(display("Endpoint not found"))]
+    NotFound,
+}
+
+/// Google Chronicle regions.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Region {
+    /// EU region.
+    Eu,
+
+    /// US region.
+    Us,
+
+    /// APAC region.
+    Asia,
+
+    /// London, United Kingdom.
+    #[serde(rename = "europe-west2")]
+    EuropeWest2,
+
+    /// Frankfurt, Germany.
+    #[serde(rename = "europe-west3")]
+    EuropeWest3,
+
+    /// Zurich, Switzerland.
+    #[serde(rename = "europe-west6")]
+    EuropeWest6,
+
+    /// Tel Aviv, Israel.
+    #[serde(rename = "me-west1")]
+    MeWest1,
+
+    /// Mumbai, India.
+    #[serde(rename = "asia-south1")]
+    AsiaSouth1,
+
+    /// Tokyo, Japan.
+    #[serde(rename = "asia-northeast1")]
+    AsiaNortheast1,
+
+    /// Sydney, Australia.
+    #[serde(rename = "australia-southeast1")]
+    AustraliaSoutheast1,
+
+    /// Toronto, Canada.
+    #[serde(rename = "northamerica-northeast2")]
+    NorthamericaNortheast2,
+}
+
+impl Region {
+    /// Each region has a its own endpoint.
+    const fn endpoint(self) -> &'static str {
+        match self {
+            Region::Eu => "https://europe-malachiteingestion-pa.googleapis.com",
+            Region::Us => "https://malachiteingestion-pa.googleapis.com",
+            Region::Asia => "https://asia-southeast1-malachiteingestion-pa.googleapis.com",
+            Region::EuropeWest2 => "https://europe-west2-malachiteingestion-pa.googleapis.com",
+            Region::EuropeWest3 => "https://europe-west3-malachiteingestion-pa.googleapis.com",
+            Region::EuropeWest6 => "https://europe-west6-malachiteingestion-pa.googleapis.com",
+            Region::MeWest1 => "https://me-west1-malachiteingestion-pa.googleapis.com",
+            Region::AsiaSouth1 => "https://asia-south1-malachiteingestion-pa.googleapis.com",
+            Region::AsiaNortheast1 => {
+                "https://asia-northeast1-malachiteingestion-pa.googleapis.com"
+            }
+            Region::AustraliaSoutheast1 => {
+                "https://australia-southeast1-malachiteingestion-pa.googleapis.com"
+            }
+            Region::NorthamericaNortheast2 => {
+                "https://northamerica-northeast2-malachiteingestion-pa.googleapis.com"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ChronicleError {
+    #[snafu(display("Region or endpoint not defined"))]
+    RegionOrEndpoint,
+    #[snafu(display("You can only specify one of region or endpoint"))]
+    BothRegionAndEndpoint,
+}
+
+/// Builds the URL of `path`, at the custom `endpoint` or at the endpoint of `region`.
+pub fn endpoint_url(
+    endpoint: Option<&str>,
+    region: Option<Region>,
+    path: &str,
+) -> Result<String, ChronicleError> {
+    Ok(format!(
+        "{}/{}",
+        match (endpoint, region) {
+            (Some(endpoint), None) => endpoint.trim_end_matches('/'),
+            (None, Some(region)) => region.endpoint(),
+            (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
+            (None, None) => return Err(ChronicleError::RegionOrEndpoint),
+        },
+        path
+    ))
+}
+
+pub fn build_healthcheck(
+    client: HttpClient,
+    base_url: &str,
+    auth: GcpAuthenticator,
+) -> crate::Result<Healthcheck> {
+    let uri = base_url.parse::<Uri>()?;
+
+    let healthcheck = async move {
+        let mut request = http::Request::get(&uri).body(Body::empty())?;
+        auth.apply(&mut request);
+
+        let response = client.send(request).await?;
+        healthcheck_response(response, GcsHealthcheckError::NotFound.into())
+    };
+
+    Ok(Box::pin(healthcheck))
+}
+
+/// Partitions the events by log type, if the API takes one.
+pub struct LogTypePartitioner(Option<Template>);
+
+impl LogTypePartitioner {
+    pub const fn new(log_type: Option<Template>) -> Self {
+        Self(log_type)
+    }
+}
+
+impl Partitioner for LogTypePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match &self.0 {
+            None => Some(String::new()),
+            Some(log_type) => log_type
+                .render_string(item)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("log_type"),
+                        drop_event: true,
+                    });
+                })
+                .ok(),
+        }
+    }
+}
+
+/// The API the events are sent to.
+#[derive(Clone, Debug)]
+pub enum ChronicleApi {
+    /// Unstructured log entries, whose text is encoded with the given encoder, sent along with
+    /// their log type.
+    Unstructured(codecs::Encoder<()>),
+
+    /// Events of the Unified Data Model, sent as they are.
+    Udm,
+}
+
+impl ChronicleApi {
+    /// The path of the endpoint the events are sent to.
+    pub const fn path(&self) -> &'static str {
+        match self {
+            ChronicleApi::Unstructured(_) => "v2/unstructuredlogentries:batchCreate",
+            ChronicleApi::Udm => "v2/udmevents:batchCreate",
+        }
+    }
+}
+
+/// Events of a log type that fit in a single request, encoded into entries.
+pub struct ChronicleBatch {
+    log_type: String,
+    entries: Vec<Vec<u8>>,
+    finalizers: EventFinalizers,
+    event_count: usize,
+    events_byte_size: usize,
+    events_json_encoded_byte_size: usize,
+}
+
+impl ChronicleBatch {
+    fn new(log_type: String) -> Self {
+        Self {
+            log_type,
+            entries: Vec::new(),
+            finalizers: EventFinalizers::default(),
+            event_count: 0,
+            events_byte_size: 0,
+            events_json_encoded_byte_size: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChronicleEncoder {
+    customer_id: String,
+    api: ChronicleApi,
+    transformer: Transformer,
+}
+
+impl ChronicleEncoder {
+    pub const fn new(customer_id: String, api: ChronicleApi, transformer: Transformer) -> Self {
+        Self {
+            customer_id,
+            api,
+            transformer,
+        }
+    }
+
+    /// Encodes the events of a log type into batches, each of which fits in a request of at most
+    /// `MAX_REQUEST_BYTES`. An event too large to fit in a request is sent in a request of its
+    /// own, which the API rejects.
+    fn split(&self, log_type: String, events: Vec<Event>) -> Vec<ChronicleBatch> {
+        let mut codec = match &self.api {
+            ChronicleApi::Unstructured(encoder) => Some(encoder.clone()),
+            ChronicleApi::Udm => None,
+        };
+        let mut envelope = Vec::new();
+        self.write_envelope_start(&log_type, &mut envelope)
+            .expect("Writing to a Vec never fails");
+        // The envelope is closed with `]}`.
+        let envelope_size = envelope.len() + 2;
+
+        let mut batches = Vec::new();
+        let mut batch = ChronicleBatch::new(log_type.clone());
+        let mut size = envelope_size;
+        for mut event in events {
+            let finalizers = event.take_finalizers();
+            let events_byte_size = event.size_of();
+            let events_json_encoded_byte_size = event.estimated_json_encoded_size_of();
+
+            if let Some(entry) = self.encode_entry(codec.as_mut(), event) {
+                // The entries are separated by commas.
+                if !batch.entries.is_empty() && size + 1 + entry.len() > MAX_REQUEST_BYTES {
+                    batches.push(std::mem::replace(
+                        &mut batch,
+                        ChronicleBatch::new(log_type.clone()),
+                    ));
+                    size = envelope_size;
+                }
+                if !batch.entries.is_empty() {
+                    size += 1;
+                }
+                size += entry.len();
+                batch.entries.push(entry);
+            }
+
+            batch.finalizers.merge(finalizers);
+            batch.event_count += 1;
+            batch.events_byte_size += events_byte_size;
+            batch.events_json_encoded_byte_size += events_json_encoded_byte_size;
+        }
+        if !batch.entries.is_empty() {
+            batches.push(batch);
+        }
+        batches
+    }
+
+    fn encode_entry(
+        &self,
+        codec: Option<&mut codecs::Encoder<()>>,
+        mut event: Event,
+    ) -> Option<Vec<u8>> {
+        match codec {
+            Some(codec) => {
+                let timestamp = event
+                    .as_log()
+                    .get_timestamp()
+                    .and_then(|ts| ts.as_timestamp())
+                    .cloned();
+                let mut bytes = BytesMut::new();
+                self.transformer.transform(&mut event);
+                codec.encode(event, &mut bytes).ok()?;
+
+                let mut value = json!({
+                    "log_text": String::from_utf8_lossy(&bytes),
+                });
+
+                if let Some(ts) = timestamp {
+                    value.as_object_mut().unwrap().insert(
+                        "ts_rfc3339".to_string(),
+                        ts.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+                            .into(),
+                    );
+                }
+
+                serde_json::to_vec(&value).ok()
+            }
+            None => {
+                self.transformer.transform(&mut event);
+                serde_json::to_vec(event.as_log()).ok()
+            }
+        }
+    }
+
+    /// Writes the start of the body of a request, up to the opening of the array of entries.
+    fn write_envelope_start(&self, log_type: &str, writer: &mut dyn io::Write) -> io::Result<()> {
+        writer.write_all(b"{\"customer_id\":")?;
+        serde_json::to_writer(&mut *writer, &self.customer_id)?;
+        match self.api {
+            ChronicleApi::Unstructured(_) => {
+                writer.write_all(b",\"log_type\":")?;
+                serde_json::to_writer(&mut *writer, log_type)?;
+                writer.write_all(b",\"entries\":[")
+            }
+            ChronicleApi::Udm => writer.write_all(b",\"events\":["),
+        }
+    }
+}
+
+impl Encoder<ChronicleBatch> for ChronicleEncoder {
+    fn encode_input(&self, input: ChronicleBatch, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let mut body = Vec::new();
+        self.write_envelope_start(&input.log_type, &mut body)?;
+        for (i, entry) in input.entries.iter().enumerate() {
+            if i > 0 {
+                body.push(b',');
+            }
+            body.extend_from_slice(entry);
+        }
+        body.extend_from_slice(b"]}");
+
+        writer.write_all(&body)?;
+        Ok(body.len())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChronicleRequest {
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl Finalizable for ChronicleRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for ChronicleRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub struct ChronicleRequestPayload {
+    bytes: Bytes,
+}
+
+impl From<Bytes> for ChronicleRequestPayload {
+    fn from(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+}
+
+impl AsRef<[u8]> for ChronicleRequestPayload {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+}
+
+// Settings required to produce a request that do not change per
+// request. All possible values are pre-computed for direct use in
+// producing a request.
+#[derive(Clone, Debug)]
+pub struct RequestSettings {
+    encoder: ChronicleEncoder,
+}
+
+impl RequestSettings {
+    pub const fn new(encoder: ChronicleEncoder) -> Self {
+        Self { encoder }
+    }
+}
+
+impl RequestBuilder<ChronicleBatch> for RequestSettings {
+    type Metadata = EventFinalizers;
+    type Events = ChronicleBatch;
+    type Encoder = ChronicleEncoder;
+    type Payload = ChronicleRequestPayload;
+    type Request = ChronicleRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut input: ChronicleBatch,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let finalizers = std::mem::take(&mut input.finalizers);
+        let builder = RequestMetadataBuilder::new(
+            input.event_count,
+            input.events_byte_size,
+            input.events_json_encoded_byte_size,
+        );
+        (finalizers, builder, input)
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        ChronicleRequest {
+            body: payload.into_payload().bytes,
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChronicleService {
+    client: HttpClient,
+    base_url: String,
+    creds: GcpAuthenticator,
+}
+
+impl ChronicleService {
+    pub const fn new(client: HttpClient, base_url: String, creds: GcpAuthenticator) -> Self {
+        Self {
+            client,
+            base_url,
+            creds,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ChronicleResponseError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: crate::http::HttpError },
+}
+
+impl Service<ChronicleRequest> for ChronicleService {
+    type Response = GcsResponse;
+    type Error = ChronicleResponseError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: ChronicleRequest) -> Self::Future {
+        let mut builder = Request::post(&self.base_url);
+        let headers = builder.headers_mut().unwrap();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_str("application/json").unwrap(),
+        );
+        headers.insert(
+            "content-length",
+            HeaderValue::from_str(&request.body.len().to_string()).unwrap(),
+        );
+
+        let metadata = request.get_metadata();
+
+        let mut http_request = builder.body(Body::from(request.body)).unwrap();
+        self.creds.apply(&mut http_request);
+
+        let mut client = self.client.clone();
+        Box::pin(async move {
+            match client.call(http_request).await {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_success() {
+                        Ok(GcsResponse {
+                            inner: response,
+                            metadata,
+                        })
+                    } else {
+                        Err(ChronicleResponseError::ServerError { code: status })
+                    }
+                }
+                Err(error) => Err(ChronicleResponseError::HttpError { error }),
+            }
+        })
+    }
+}
+
+/// Retries the requests that were throttled or failed on the side of the server.
+#[derive(Clone, Debug)]
+pub struct ChronicleRetryLogic;
+
+impl RetryLogic for ChronicleRetryLogic {
+    type Error = ChronicleResponseError;
+    type Response = GcsResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            ChronicleResponseError::ServerError { code } => {
+                *code == StatusCode::TOO_MANY_REQUESTS || code.is_server_error()
+            }
+            ChronicleResponseError::HttpError { error } => error.is_retriable(),
+        }
+    }
+}
+
+pub struct ChronicleSink<Svc> {
+    service: Svc,
+    request_builder: RequestSettings,
+    partitioner: LogTypePartitioner,
+    batcher_settings: BatcherSettings,
+}
+
+impl<Svc> ChronicleSink<Svc> {
+    pub const fn new(
+        service: Svc,
+        request_builder: RequestSettings,
+        partitioner: LogTypePartitioner,
+        batcher_settings: BatcherSettings,
+    ) -> Self {
+        Self {
+            service,
+            request_builder,
+            partitioner,
+            batcher_settings,
+        }
+    }
+}
+
+impl<Svc> ChronicleSink<Svc>
+where
+    Svc: Service<ChronicleRequest> + Send + 'static,
+    Svc::Future: Send + 'static,
+    Svc::Response: DriverResponse + Send + 'static,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let partitioner = self.partitioner;
+        let settings = self.batcher_settings;
+
+        let builder_limit = NonZeroUsize::new(64);
+        let request_builder = self.request_builder;
+        let encoder = request_builder.encoder.clone();
+
+        input
+            .batched_partitioned(partitioner, settings)
+            .filter_map(|(key, batch)| async move {
+                // A `TemplateRenderingError` will have been emitted by `LogTypePartitioner` if the
+                // key here is `None`, thus no further `EventsDropped` event needs emitting at this
+                // stage.
+                key.map(move |k| (k, batch))
+            })
+            .flat_map(move |(log_type, events)| stream::iter(encoder.split(log_type, events)))
+            .request_builder(builder_limit, request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("http")
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<Svc> StreamSink<Event> for ChronicleSink<Svc>
+where
+    Svc: Service<ChronicleRequest> + Send + 'static,
+    Svc::Future: Send + 'static,
+    Svc::Response: DriverResponse + Send + 'static,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::codecs::TextSerializerConfig;
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn encoder(api: ChronicleApi) -> ChronicleEncoder {
+        ChronicleEncoder::new("customer".to_owned(), api, Transformer::default())
+    }
+
+    fn unstructured() -> ChronicleApi {
+        ChronicleApi::Unstructured(codecs::Encoder::<()>::new(
+            TextSerializerConfig::default().build().into(),
+        ))
+    }
+
+    fn encode(encoder: &ChronicleEncoder, batch: ChronicleBatch) -> serde_json::Value {
+        let mut body = Vec::new();
+        encoder.encode_input(batch, &mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[test]
+    fn encodes_unstructured_entries() {
+        let encoder = encoder(unstructured());
+        let mut event = LogEvent::from("hello");
+        event.remove_timestamp();
+        let mut batches = encoder.split("WINDOWS_DNS".to_owned(), vec![event.into()]);
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            encode(&encoder, batches.remove(0)),
+            json!({
+                "customer_id": "customer",
+                "log_type": "WINDOWS_DNS",
+                "entries": [{"log_text": "hello"}],
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_udm_events() {
+        let encoder = encoder(ChronicleApi::Udm);
+        let mut event = LogEvent::default();
+        event.insert("metadata.event_type", "NETWORK_CONNECTION");
+        let mut batches = encoder.split(String::new(), vec![event.into()]);
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            encode(&encoder, batches.remove(0)),
+            json!({
+                "customer_id": "customer",
+                "events": [{"metadata": {"event_type": "NETWORK_CONNECTION"}}],
+            })
+        );
+    }
+
+    #[test]
+    fn splits_batches_into_requests_within_the_size_limit() {
+        let encoder = encoder(unstructured());
+        let message = "x".repeat(100_000);
+        let events = (0..25)
+            .map(|_| LogEvent::from(message.as_str()).into())
+            .collect();
+        let batches = encoder.split("WINDOWS_DNS".to_owned(), events);
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(
+            batches.iter().map(|batch| batch.event_count).sum::<usize>(),
+            25
+        );
+        for batch in batches {
+            let mut body = Vec::new();
+            encoder.encode_input(batch, &mut body).unwrap();
+            assert!(body.len() <= MAX_REQUEST_BYTES);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! This sink sends events of the Unified Data Model to Google Chronicles UDM events endpoint.
+//! See <https://cloud.google.com/chronicle/docs/reference/ingestion-api#udmevents>
+//! for more information.
+use goauth::scopes::Scope;
+use indoc::indoc;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{AcknowledgementsConfig, Input},
+    sink::VectorSink,
+};
+
+use super::chronicle_common::{
+    build_healthcheck, endpoint_url, ChronicleApi, ChronicleEncoder, ChronicleError,
+    ChronicleRetryLogic, ChronicleService, ChronicleSink, LogTypePartitioner, Region,
+    RequestSettings,
+};
+use crate::{
+    codecs::Transformer,
+    config::{GenerateConfig, SinkConfig, SinkContext},
+    gcp::{GcpAuthConfig, GcpAuthenticator},
+    http::HttpClient,
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChronicleUdmDefaultBatchSettings;
+
+// Chronicle Ingestion API has a 1MB limit[1] for UDM events. Batches whose encoded events exceed
+// the limit are split into several requests.
+//
+// [1]: https://cloud.google.com/chronicle/docs/reference/ingestion-api#udmevents
+impl SinkBatchSettings for ChronicleUdmDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 15.0;
+}
+
+/// Configuration for the `gcp_chronicle_udm` sink.
+#[configurable_component(sink("gcp_chronicle_udm"))]
+#[derive(Clone, Debug)]
+pub struct ChronicleUdmConfig {
+    /// The endpoint to send data to.
+    #[configurable(metadata(
+        docs::examples = "127.0.0.1:8080",
+        docs::examples = "example.com:12345"
+    ))]
+    pub endpoint: Option<String>,
+
+    /// The GCP region to use.
+    #[configurable(derived)]
+    pub region: Option<Region>,
+
+    /// The Unique identifier (UUID) corresponding to the Chronicle instance.
+    #[configurable(validation(format = "uuid"))]
+    #[configurable(metadata(docs::examples = "c8c65bfa-5f2c-42d4-9189-64bb7b939f2c"))]
+    pub customer_id: String,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<ChronicleUdmDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for ChronicleUdmConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            credentials_path = "/path/to/credentials.json"
+            customer_id = "customer_id"
+            region = "us"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for ChronicleUdmConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let creds = self.auth.build(Scope::MalachiteIngestion).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = self.create_endpoint(ChronicleApi::Udm.path())?;
+
+        // For the healthcheck we see if we can fetch the list of available log types.
+        let healthcheck_endpoint = self.create_endpoint("v2/logtypes")?;
+
+        let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
+        creds.spawn_regenerate_token();
+        let sink = self.build_sink(client, endpoint, creds)?;
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl ChronicleUdmConfig {
+    fn build_sink(
+        &self,
+        client: HttpClient,
+        base_url: String,
+        creds: GcpAuthenticator,
+    ) -> crate::Result<VectorSink> {
+        use crate::sinks::util::service::ServiceBuilderExt;
+
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            rate_limit_num: Some(1000),
+            ..Default::default()
+        });
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        // UDM events carry their own log type, so they are sent in batches of any log type.
+        let partitioner = LogTypePartitioner::new(None);
+
+        let svc = ServiceBuilder::new()
+            .settings(request, ChronicleRetryLogic)
+            .service(ChronicleService::new(client, base_url, creds));
+
+        let encoder = ChronicleEncoder::new(
+            self.customer_id.clone(),
+            ChronicleApi::Udm,
+            self.encoding.clone(),
+        );
+        let request_settings = RequestSettings::new(encoder);
+
+        let sink = ChronicleSink::new(svc, request_settings, partitioner, batch_settings);
+
+        Ok(VectorSink::from_event_streamsink(sink))
+    }
+
+    fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
+        endpoint_url(self.endpoint.as_deref(), self.region, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChronicleUdmConfig>();
+    }
+
+    #[test]
+    fn uses_the_regional_endpoint() {
+        let config: ChronicleUdmConfig = toml::from_str(indoc! {r#"
+            customer_id = "customer_id"
+            region = "europe-west2"
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            config.create_endpoint("v2/udmevents:batchCreate").unwrap(),
+            "https://europe-west2-malachiteingestion-pa.googleapis.com/v2/udmevents:batchCreate"
+        );
+    }
+}
@@ -1,26 +1,21 @@
 //! This sink sends data to Google Chronicles unstructured log entries endpoint.
 //! See <https://cloud.google.com/chronicle/docs/reference/ingestion-api#unstructuredlogentries>
 //! for more information.
-use bytes::{Bytes, BytesMut};
-use futures_util::{future::BoxFuture, task::Poll};
 use goauth::scopes::Scope;
-use http::{header::HeaderValue, Request, StatusCode, Uri};
-use hyper::Body;
 use indoc::indoc;
-use serde_json::json;
-use snafu::Snafu;
-use std::io;
-use tokio_util::codec::Encoder as _;
-use tower::{Service, ServiceBuilder};
+use tower::ServiceBuilder;
 use value::Kind;
-use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_config::configurable_component;
 use vector_core::{
     config::{AcknowledgementsConfig, Input},
-    event::{Event, EventFinalizers, Finalizable},
     sink::VectorSink,
 };
 
+use super::chronicle_common::{
+    build_healthcheck, endpoint_url, ChronicleApi, ChronicleEncoder, ChronicleError,
+    ChronicleRetryLogic, ChronicleService, ChronicleSink, LogTypePartitioner, Region,
+    RequestSettings,
+};
 use crate::{
     codecs::{self, EncodingConfig},
     config::{GenerateConfig, SinkConfig, SinkContext},
@@ -28,66 +23,20 @@ use crate::{
     http::HttpClient,
     schema,
     sinks::{
-        gcs_common::{
-            config::{healthcheck_response, GcsRetryLogic},
-            service::GcsResponse,
-            sink::GcsSink,
-        },
-        util::{
-            encoding::{as_tracked_write, Encoder},
-            metadata::RequestMetadataBuilder,
-            partitioner::KeyPartitioner,
-            request_builder::EncodeResult,
-            BatchConfig, Compression, RequestBuilder, SinkBatchSettings, TowerRequestConfig,
-        },
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
         Healthcheck,
     },
-    template::{Template, TemplateParseError},
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
-#[derive(Debug, Snafu)]
-#[snafu(visibility(pub))]
-pub enum GcsHealthcheckError {
-    #[snafu(display("log_type template parse error: {}", source))]
-    LogTypeTemplate { source: TemplateParseError },
-
-    #[snafu(display("Endpoint not found"))]
-    NotFound,
-}
-
-/// Google Chronicle regions.
-#[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Region {
-    /// EU region.
-    Eu,
-
-    /// US region.
-    Us,
-
-    /// APAC region.
-    Asia,
-}
-
-impl Region {
-    /// Each region has a its own endpoint.
-    const fn endpoint(self) -> &'static str {
-        match self {
-            Region::Eu => "https://europe-malachiteingestion-pa.googleapis.com",
-            Region::Us => "https://malachiteingestion-pa.googleapis.com",
-            Region::Asia => "https://asia-southeast1-malachiteingestion-pa.googleapis.com",
-        }
-    }
-}
-
 #[derive(Clone, Copy, Debug, Default)]
 pub struct ChronicleUnstructuredDefaultBatchSettings;
 
 // Chronicle Ingestion API has a 1MB limit[1] for unstructured log entries. We're also using a
 // conservatively low batch timeout to ensure events make it to Chronicle in a timely fashion, but
-// high enough that it allows for reasonable batching.
+// high enough that it allows for reasonable batching. Batches whose encoded entries exceed the
+// limit are split into several requests.
 //
 // [1]: https://cloud.google.com/chronicle/docs/reference/ingestion-api#unstructuredlogentries
 impl SinkBatchSettings for ChronicleUnstructuredDefaultBatchSettings {
@@ -163,32 +112,6 @@ impl GenerateConfig for ChronicleUnstructuredConfig {
     }
 }
 
-pub fn build_healthcheck(
-    client: HttpClient,
-    base_url: &str,
-    auth: GcpAuthenticator,
-) -> crate::Result<Healthcheck> {
-    let uri = base_url.parse::<Uri>()?;
-
-    let healthcheck = async move {
-        let mut request = http::Request::get(&uri).body(Body::empty())?;
-        auth.apply(&mut request);
-
-        let response = client.send(request).await?;
-        healthcheck_response(response, GcsHealthcheckError::NotFound.into())
-    };
-
-    Ok(Box::pin(healthcheck))
-}
-
-#[derive(Debug, Snafu)]
-pub enum ChronicleError {
-    #[snafu(display("Region or endpoint not defined"))]
-    RegionOrEndpoint,
-    #[snafu(display("You can only specify one of region or endpoint"))]
-    BothRegionAndEndpoint,
-}
-
 #[async_trait::async_trait]
 impl SinkConfig for ChronicleUnstructuredConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
@@ -197,14 +120,15 @@ impl SinkConfig for ChronicleUnstructuredConfig {
         let tls = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls, cx.proxy())?;
 
-        let endpoint = self.create_endpoint("v2/unstructuredlogentries:batchCreate")?;
+        let api = self.api()?;
+        let endpoint = self.create_endpoint(api.path())?;
 
         // For the healthcheck we see if we can fetch the list of available log types.
         let healthcheck_endpoint = self.create_endpoint("v2/logtypes")?;
 
         let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
         creds.spawn_regenerate_token();
-        let sink = self.build_sink(client, endpoint, creds)?;
+        let sink = self.build_sink(client, endpoint, creds, api)?;
 
         Ok((sink, healthcheck))
     }
@@ -227,6 +151,7 @@ impl ChronicleUnstructuredConfig {
         client: HttpClient,
         base_url: String,
         creds: GcpAuthenticator,
+        api: ChronicleApi,
     ) -> crate::Result<VectorSink> {
         use crate::sinks::util::service::ServiceBuilderExt;
 
@@ -237,261 +162,30 @@ impl ChronicleUnstructuredConfig {
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
-        let partitioner = self.key_partitioner()?;
+        let partitioner = LogTypePartitioner::new(Some(self.log_type.clone()));
 
         let svc = ServiceBuilder::new()
-            .settings(request, GcsRetryLogic)
+            .settings(request, ChronicleRetryLogic)
             .service(ChronicleService::new(client, base_url, creds));
 
-        let request_settings = RequestSettings::new(self)?;
+        let encoder =
+            ChronicleEncoder::new(self.customer_id.clone(), api, self.encoding.transformer());
+        let request_settings = RequestSettings::new(encoder);
 
-        let sink = GcsSink::new(svc, request_settings, partitioner, batch_settings, "http");
+        let sink = ChronicleSink::new(svc, request_settings, partitioner, batch_settings);
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
-    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
-        Ok(KeyPartitioner::new(self.log_type.clone()))
+    fn api(&self) -> crate::Result<ChronicleApi> {
+        let serializer = self.encoding.config().build()?;
+        Ok(ChronicleApi::Unstructured(codecs::Encoder::<()>::new(
+            serializer,
+        )))
     }
 
     fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
-        Ok(format!(
-            "{}/{}",
-            match (&self.endpoint, self.region) {
-                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
-                (None, Some(region)) => region.endpoint(),
-                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
-                (None, None) => return Err(ChronicleError::RegionOrEndpoint),
-            },
-            path
-        ))
-    }
-}
-
-#[derive(Clone, Debug)]
-pub struct ChronicleRequest {
-    pub body: Bytes,
-    pub finalizers: EventFinalizers,
-    metadata: RequestMetadata,
-}
-
-impl Finalizable for ChronicleRequest {
-    fn take_finalizers(&mut self) -> EventFinalizers {
-        std::mem::take(&mut self.finalizers)
-    }
-}
-
-impl MetaDescriptive for ChronicleRequest {
-    fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
-    }
-}
-
-#[derive(Clone, Debug)]
-struct ChronicleEncoder {
-    customer_id: String,
-    encoder: codecs::Encoder<()>,
-    transformer: codecs::Transformer,
-}
-
-impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
-    fn encode_input(
-        &self,
-        input: (String, Vec<Event>),
-        writer: &mut dyn io::Write,
-    ) -> io::Result<usize> {
-        let (partition_key, events) = input;
-        let mut encoder = self.encoder.clone();
-        let events = events
-            .into_iter()
-            .filter_map(|mut event| {
-                let timestamp = event
-                    .as_log()
-                    .get_timestamp()
-                    .and_then(|ts| ts.as_timestamp())
-                    .cloned();
-                let mut bytes = BytesMut::new();
-                self.transformer.transform(&mut event);
-                encoder.encode(event, &mut bytes).ok()?;
-
-                let mut value = json!({
-                    "log_text": String::from_utf8_lossy(&bytes),
-                });
-
-                if let Some(ts) = timestamp {
-                    value.as_object_mut().unwrap().insert(
-                        "ts_rfc3339".to_string(),
-                        ts.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
-                            .into(),
-                    );
-                }
-
-                Some(value)
-            })
-            .collect::<Vec<_>>();
-
-        let json = json!({
-            "customer_id": self.customer_id,
-            "log_type": partition_key,
-            "entries": events,
-        });
-
-        let size = as_tracked_write::<_, _, io::Error>(writer, &json, |writer, json| {
-            serde_json::to_writer(writer, json)?;
-            Ok(())
-        })?;
-
-        Ok(size)
-    }
-}
-
-// Settings required to produce a request that do not change per
-// request. All possible values are pre-computed for direct use in
-// producing a request.
-#[derive(Clone, Debug)]
-struct RequestSettings {
-    encoder: ChronicleEncoder,
-}
-
-struct ChronicleRequestPayload {
-    bytes: Bytes,
-}
-
-impl From<Bytes> for ChronicleRequestPayload {
-    fn from(bytes: Bytes) -> Self {
-        Self { bytes }
-    }
-}
-
-impl AsRef<[u8]> for ChronicleRequestPayload {
-    fn as_ref(&self) -> &[u8] {
-        self.bytes.as_ref()
-    }
-}
-
-impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
-    type Metadata = EventFinalizers;
-    type Events = (String, Vec<Event>);
-    type Encoder = ChronicleEncoder;
-    type Payload = ChronicleRequestPayload;
-    type Request = ChronicleRequest;
-    type Error = io::Error;
-
-    fn compression(&self) -> Compression {
-        Compression::None
-    }
-
-    fn encoder(&self) -> &Self::Encoder {
-        &self.encoder
-    }
-
-    fn split_input(
-        &self,
-        input: (String, Vec<Event>),
-    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
-        let (partition_key, mut events) = input;
-        let finalizers = events.take_finalizers();
-
-        let builder = RequestMetadataBuilder::from_events(&events);
-        (finalizers, builder, (partition_key, events))
-    }
-
-    fn build_request(
-        &self,
-        finalizers: Self::Metadata,
-        metadata: RequestMetadata,
-        payload: EncodeResult<Self::Payload>,
-    ) -> Self::Request {
-        ChronicleRequest {
-            body: payload.into_payload().bytes,
-            finalizers,
-            metadata,
-        }
-    }
-}
-
-impl RequestSettings {
-    fn new(config: &ChronicleUnstructuredConfig) -> crate::Result<Self> {
-        let transformer = config.encoding.transformer();
-        let serializer = config.encoding.config().build()?;
-        let encoder = crate::codecs::Encoder::<()>::new(serializer);
-        let encoder = ChronicleEncoder {
-            customer_id: config.customer_id.clone(),
-            encoder,
-            transformer,
-        };
-        Ok(Self { encoder })
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct ChronicleService {
-    client: HttpClient,
-    base_url: String,
-    creds: GcpAuthenticator,
-}
-
-impl ChronicleService {
-    pub const fn new(client: HttpClient, base_url: String, creds: GcpAuthenticator) -> Self {
-        Self {
-            client,
-            base_url,
-            creds,
-        }
-    }
-}
-
-#[derive(Debug, Snafu)]
-pub enum ChronicleResponseError {
-    #[snafu(display("Server responded with an error: {}", code))]
-    ServerError { code: StatusCode },
-    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
-    HttpError { error: crate::http::HttpError },
-}
-
-impl Service<ChronicleRequest> for ChronicleService {
-    type Response = GcsResponse;
-    type Error = ChronicleResponseError;
-    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
-
-    fn poll_ready(&mut self, _: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
-    }
-
-    fn call(&mut self, request: ChronicleRequest) -> Self::Future {
-        let mut builder = Request::post(&self.base_url);
-        let headers = builder.headers_mut().unwrap();
-        headers.insert(
-            "content-type",
-            HeaderValue::from_str("application/json").unwrap(),
-        );
-        headers.insert(
-            "content-length",
-            HeaderValue::from_str(&request.body.len().to_string()).unwrap(),
-        );
-
-        let metadata = request.get_metadata();
-
-        let mut http_request = builder.body(Body::from(request.body)).unwrap();
-        self.creds.apply(&mut http_request);
-
-        let mut client = self.client.clone();
-        Box::pin(async move {
-            match client.call(http_request).await {
-                Ok(response) => {
-                    let status = response.status();
-                    if status.is_success() {
-                        Ok(GcsResponse {
-                            inner: response,
-                            metadata,
-                        })
-                    } else {
-                        Err(ChronicleResponseError::ServerError { code: status })
-                    }
-                }
-                Err(error) => Err(ChronicleResponseError::HttpError { error }),
-            }
-        })
+        endpoint_url(self.endpoint.as_deref(), self.region, path)
     }
 }
 
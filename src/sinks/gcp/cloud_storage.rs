@@ -2,7 +2,6 @@ use std::{collections::HashMap, convert::TryFrom, io};
 
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::encoding::Framer;
 use http::header::{HeaderName, HeaderValue};
 use http::Uri;
 use indoc::indoc;
@@ -16,7 +15,7 @@ use vector_core::event::{EventFinalizers, Finalizable};
 
 use crate::sinks::util::metadata::RequestMetadataBuilder;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::EncodingConfigWithFraming,
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
@@ -31,7 +30,10 @@ use crate::{
             sink::GcsSink,
         },
         util::{
-            batch::BatchConfig, partitioner::KeyPartitioner, request_builder::EncodeResult,
+            batch::BatchConfig,
+            parquet::{BatchSerializerConfig, ObjectEncoder},
+            partitioner::KeyPartitioner,
+            request_builder::EncodeResult,
             BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder, ServiceBuilderExt,
             TowerRequestConfig,
         },
@@ -136,6 +138,9 @@ pub struct GcsSinkConfig {
     #[serde(flatten)]
     encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     compression: Compression,
@@ -179,6 +184,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         filename_append_uuid: true,
         filename_extension: Default::default(),
         encoding,
+        batch_encoding: None,
         compression: Compression::gzip_default(),
         batch: Default::default(),
         request: Default::default(),
@@ -278,14 +284,14 @@ struct RequestSettings {
     extension: String,
     time_format: String,
     append_uuid: bool,
-    encoder: (Transformer, Encoder<Framer>),
+    encoder: ObjectEncoder,
     compression: Compression,
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
     type Metadata = (String, EventFinalizers);
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = ObjectEncoder;
     type Payload = Bytes;
     type Request = GcsRequest;
     type Error = io::Error;
@@ -349,15 +355,13 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 
 impl RequestSettings {
     fn new(config: &GcsSinkConfig) -> crate::Result<Self> {
-        let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = ObjectEncoder::new(&config.encoding, config.batch_encoding.as_ref())?;
+        let compression = encoder.compression(config.compression);
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
         let content_type = HeaderValue::from_str(encoder.content_type()).unwrap();
-        let content_encoding = config
-            .compression
+        let content_encoding = compression
             .content_encoding()
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
@@ -375,7 +379,7 @@ impl RequestSettings {
         let extension = config
             .filename_extension
             .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+            .unwrap_or_else(|| encoder.extension(compression).into());
         let time_format = config.filename_time_format.clone();
         let append_uuid = config.filename_append_uuid;
         Ok(Self {
@@ -387,8 +391,8 @@ impl RequestSettings {
             extension,
             time_format,
             append_uuid,
-            compression: config.compression,
-            encoder: (transformer, encoder),
+            compression,
+            encoder,
         })
     }
 }
@@ -510,4 +514,30 @@ mod tests {
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.key, "key/date.log.gz".to_string());
     }
+
+    #[test]
+    fn gcs_build_parquet_request() {
+        let sink_config = GcsSinkConfig {
+            key_prefix: Some("key/".into()),
+            filename_time_format: "date".into(),
+            filename_append_uuid: false,
+            batch_encoding: Some(BatchSerializerConfig::Parquet(Default::default())),
+            ..default_config((None::<FramingConfig>, TextSerializerConfig::default()).into())
+        };
+        let log = LogEvent::from("simple message").into();
+        let request_settings = request_settings(&sink_config);
+        let (metadata, metadata_request_builder, events) =
+            request_settings.split_input(("key/".into(), vec![log]));
+        let payload = request_settings.encode_events(events).unwrap();
+        let request_metadata = metadata_request_builder.build(&payload);
+        let req = request_settings.build_request(metadata, request_metadata, payload);
+
+        assert_eq!(req.key, "key/date.parquet".to_string());
+        assert_eq!(
+            req.settings.content_type,
+            HeaderValue::from_static("application/vnd.apache.parquet")
+        );
+        assert_eq!(req.settings.content_encoding, None);
+        assert_eq!(&req.body[..4], b"PAR1");
+    }
 }
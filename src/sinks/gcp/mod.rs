@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
 
+pub mod chronicle_common;
+pub mod chronicle_udm;
 pub mod chronicle_unstructured;
 pub mod cloud_storage;
 pub mod pubsub;
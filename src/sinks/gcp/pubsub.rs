@@ -15,14 +15,18 @@ use crate::{
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope, PUBSUB_URL},
     http::HttpClient,
+    internal_events::TemplateRenderingError,
     sinks::{
         gcs_common::config::healthcheck_response,
         util::{
-            http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
-            BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
+            http::{HttpEventEncoder, HttpSink, PartitionHttpSink},
+            service::lane,
+            BatchConfig, BoxedRawValue, Concurrency, JsonArrayBuffer, PartitionBuffer,
+            PartitionInnerBuffer, SinkBatchSettings, TowerRequestConfig, TowerRequestSettings,
         },
         Healthcheck, UriParseSnafu, VectorSink,
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
@@ -32,6 +36,14 @@ enum HealthcheckError {
     TopicNotFound,
 }
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "`request.concurrency` must be 1 when `ordering_key` is set, so that ordered messages are not reordered"
+    ))]
+    OrderedConcurrency,
+}
+
 // 10MB maximum message size: https://cloud.google.com/pubsub/quotas#resource_limits
 const MAX_BATCH_PAYLOAD_SIZE: usize = 10_000_000;
 
@@ -68,6 +80,32 @@ pub struct PubsubConfig {
     #[configurable(metadata(docs::examples = "https://us-central1-pubsub.googleapis.com"))]
     pub endpoint: String,
 
+    /// The ordering key to attach to each message.
+    ///
+    /// Messages with the same ordering key are delivered to subscribers that have message
+    /// ordering enabled in the order they are published. To preserve that order, requests are
+    /// sent one at a time, and a request that fails is retried before any following request is
+    /// sent. Events for which the key cannot be rendered are dropped.
+    ///
+    /// Messages with an ordering key must be published to a [regional endpoint][regional_endpoints].
+    ///
+    /// [regional_endpoints]: https://cloud.google.com/pubsub/docs/reference/service_apis_overview#pubsub_endpoints
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    #[configurable(metadata(docs::examples = "{{ customer_id }}-{{ region }}"))]
+    pub ordering_key: Option<Template>,
+
+    /// The log field name whose value the events are kept in order by.
+    ///
+    /// The events with the same value are batched together, and their batches are sent one at a
+    /// time, in the order they're received in, so that the retries of a batch can't reorder it
+    /// with the next ones. Unlike `ordering_key`, the batches of different values are still sent
+    /// concurrently, but the throughput of each value is limited to one request in flight. The
+    /// events missing the field are kept in order with each other.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "user_id"))]
+    pub preserve_order_by: Option<String>,
+
     #[serde(default, flatten)]
     pub auth: GcpAuthConfig,
 
@@ -99,6 +137,28 @@ fn default_endpoint() -> String {
     PUBSUB_URL.to_string()
 }
 
+impl PubsubConfig {
+    fn request_settings(&self) -> crate::Result<TowerRequestSettings> {
+        if self.ordering_key.is_none() {
+            return Ok(self.request.unwrap_with(&Default::default()));
+        }
+
+        // Requests must be sent one at a time, and retried before any following request is sent,
+        // for the messages of each ordering key to be published in order.
+        match self.request.concurrency {
+            Concurrency::None | Concurrency::Fixed(1) => {
+                Ok(self.request.unwrap_with(&TowerRequestConfig {
+                    concurrency: Concurrency::Fixed(1),
+                    ..Default::default()
+                }))
+            }
+            Concurrency::Fixed(_) | Concurrency::Adaptive => {
+                Err(BuildError::OrderedConcurrency.into())
+            }
+        }
+    }
+}
+
 impl GenerateConfig for PubsubConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(indoc! {r#"
@@ -113,27 +173,32 @@ impl GenerateConfig for PubsubConfig {
 #[async_trait::async_trait]
 impl SinkConfig for PubsubConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let request_settings = self.request_settings()?;
         let sink = PubsubSink::from_config(self).await?;
         let batch_settings = self
             .batch
             .validate()?
             .limit_max_bytes(MAX_BATCH_PAYLOAD_SIZE)?
             .into_batch_settings()?;
-        let request_settings = self.request.unwrap_with(&Default::default());
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls_settings, cx.proxy())?;
 
         let healthcheck = healthcheck(client.clone(), sink.uri("")?, sink.auth.clone()).boxed();
         sink.auth.spawn_regenerate_token();
 
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             sink,
-            JsonArrayBuffer::new(batch_settings.size),
+            PartitionBuffer::new(JsonArrayBuffer::new(batch_settings.size)),
             request_settings,
             batch_settings.timeout,
             client,
-        )
-        .sink_map_err(|error| error!(message = "Fatal gcp_pubsub sink error.", %error));
+        );
+        let sink = match self.preserve_order_by {
+            Some(_) => sink.ordered(),
+            None => sink,
+        };
+        let sink =
+            sink.sink_map_err(|error| error!(message = "Fatal gcp_pubsub sink error.", %error));
 
         Ok((VectorSink::from_event_sink(sink), healthcheck))
     }
@@ -150,6 +215,8 @@ impl SinkConfig for PubsubConfig {
 struct PubsubSink {
     auth: GcpAuthenticator,
     uri_base: String,
+    ordering_key: Option<Template>,
+    preserve_order_by: Option<String>,
     transformer: Transformer,
     encoder: Encoder<()>,
 }
@@ -171,6 +238,8 @@ impl PubsubSink {
         Ok(Self {
             auth,
             uri_base,
+            ordering_key: config.ordering_key.clone(),
+            preserve_order_by: config.preserve_order_by.clone(),
             transformer,
             encoder,
         })
@@ -185,36 +254,68 @@ impl PubsubSink {
 }
 
 struct PubSubSinkEventEncoder {
+    ordering_key: Option<Template>,
+    preserve_order_by: Option<String>,
     transformer: Transformer,
     encoder: Encoder<()>,
 }
 
-impl HttpEventEncoder<Value> for PubSubSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<Value> {
+impl HttpEventEncoder<PartitionInnerBuffer<Value, Option<u64>>> for PubSubSinkEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<Value, Option<u64>>> {
+        let ordering_key = self
+            .ordering_key
+            .as_ref()
+            .map(|ordering_key| {
+                ordering_key.render_string(&event).map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("ordering_key"),
+                        drop_event: true,
+                    });
+                })
+            })
+            .transpose()
+            .ok()?;
+        let lane = self
+            .preserve_order_by
+            .as_ref()
+            .zip(event.maybe_as_log())
+            .and_then(|(field, log)| lane(log, field));
+
         self.transformer.transform(&mut event);
         let mut bytes = BytesMut::new();
         // Errors are handled by `Encoder`.
         self.encoder.encode(event, &mut bytes).ok()?;
         // Each event needs to be base64 encoded, and put into a JSON object
         // as the `data` item.
-        Some(json!({ "data": BASE64_STANDARD.encode(&bytes) }))
+        let mut message = json!({ "data": BASE64_STANDARD.encode(&bytes) });
+        if let Some(ordering_key) = ordering_key {
+            message["orderingKey"] = ordering_key.into();
+        }
+        Some(PartitionInnerBuffer::new(message, lane))
     }
 }
 
 #[async_trait::async_trait]
 impl HttpSink for PubsubSink {
-    type Input = Value;
-    type Output = Vec<BoxedRawValue>;
+    type Input = PartitionInnerBuffer<Value, Option<u64>>;
+    type Output = PartitionInnerBuffer<Vec<BoxedRawValue>, Option<u64>>;
     type Encoder = PubSubSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         PubSubSinkEventEncoder {
+            ordering_key: self.ordering_key.clone(),
+            preserve_order_by: self.preserve_order_by.clone(),
             transformer: self.transformer.clone(),
             encoder: self.encoder.clone(),
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Bytes>> {
+        let (events, _) = output.into_parts();
         let body = json!({ "messages": events });
         let body = crate::serde::json::to_bytes(&body).unwrap().freeze();
 
@@ -241,6 +342,7 @@ mod tests {
     use indoc::indoc;
 
     use super::*;
+    use crate::event::LogEvent;
 
     #[test]
     fn generate_config() {
@@ -259,6 +361,87 @@ mod tests {
             panic!("config.build failed to error");
         }
     }
+
+    fn ordered_config(request: &str) -> PubsubConfig {
+        toml::from_str(&format!(
+            indoc! {r#"
+                project = "project"
+                topic = "topic"
+                encoding.codec = "json"
+                ordering_key = "{{{{ key }}}}"
+                {}
+            "#},
+            request
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn ordering_key_limits_concurrency() {
+        let settings = ordered_config("").request_settings().unwrap();
+        assert_eq!(settings.concurrency, Some(1));
+
+        ordered_config("request.concurrency = 4")
+            .request_settings()
+            .expect_err("concurrency above 1 should be rejected");
+        ordered_config(r#"request.concurrency = "adaptive""#)
+            .request_settings()
+            .expect_err("adaptive concurrency should be rejected");
+    }
+
+    #[test]
+    fn encodes_ordering_key() {
+        let config = ordered_config("");
+        let mut encoder = PubSubSinkEventEncoder {
+            ordering_key: config.ordering_key.clone(),
+            preserve_order_by: None,
+            transformer: config.encoding.transformer(),
+            encoder: Encoder::<()>::new(config.encoding.build().unwrap()),
+        };
+
+        let mut log = LogEvent::from("hello");
+        log.insert("key", "first");
+        let (message, _) = encoder.encode_event(log.into()).unwrap().into_parts();
+        assert_eq!(message["orderingKey"], "first");
+        assert!(message["data"].is_string());
+
+        // Events without a key to order them by are dropped.
+        assert!(encoder
+            .encode_event(LogEvent::from("hello").into())
+            .is_none());
+    }
+
+    #[test]
+    fn partitions_by_preserve_order_by() {
+        let config: PubsubConfig = toml::from_str(indoc! {r#"
+                project = "project"
+                topic = "topic"
+                encoding.codec = "json"
+                preserve_order_by = "user"
+            "#})
+        .unwrap();
+        let mut encoder = PubSubSinkEventEncoder {
+            ordering_key: None,
+            preserve_order_by: config.preserve_order_by.clone(),
+            transformer: config.encoding.transformer(),
+            encoder: Encoder::<()>::new(config.encoding.build().unwrap()),
+        };
+        let mut encode = |user: Option<&str>| {
+            let mut log = LogEvent::from("hello");
+            if let Some(user) = user {
+                log.insert("user", user);
+            }
+            let (message, lane) = encoder.encode_event(log.into()).unwrap().into_parts();
+            assert!(message.get("orderingKey").is_none());
+            lane
+        };
+
+        let first = encode(Some("first"));
+        assert!(first.is_some());
+        assert_eq!(encode(Some("first")), first);
+        assert_ne!(encode(Some("second")), first);
+        assert_eq!(encode(None), None);
+    }
 }
 
 #[cfg(all(test, feature = "gcp-integration-tests"))]
@@ -284,6 +467,8 @@ mod integration_tests {
             project: PROJECT.into(),
             topic: topic.into(),
             endpoint: gcp::PUBSUB_ADDRESS.clone(),
+            ordering_key: None,
+            preserve_order_by: None,
             auth: GcpAuthConfig {
                 skip_authentication: true,
                 ..Default::default()
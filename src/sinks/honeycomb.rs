@@ -1,6 +1,9 @@
+use std::{collections::BTreeMap, time::Duration};
+
 use bytes::Bytes;
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use serde_json::json;
 use value::Kind;
 use vector_common::sensitive_string::SensitiveString;
@@ -9,21 +12,29 @@ use vector_config::configurable_component;
 use crate::{
     codecs::Transformer,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, Value},
-    http::HttpClient,
+    event::{Event, LogEvent, Value},
+    http::{HttpClient, HttpError},
+    internal_events::TemplateRenderingError,
     schema,
     sinks::util::{
-        http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
-        BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
+        http::{retry_after, HttpEventEncoder, HttpRetryLogic, HttpSink, PartitionHttpSink},
+        retries::{RetryAction, RetryLogic},
+        BatchConfig, BoxedRawValue, JsonArrayBuffer, PartitionBuffer, PartitionInnerBuffer,
+        SinkBatchSettings, TowerRequestConfig,
     },
+    template::Template,
 };
 
 /// Configuration for the `honeycomb` sink.
 #[configurable_component(sink("honeycomb"))]
 #[derive(Clone, Debug)]
 pub struct HoneycombConfig {
-    // This endpoint is not user-configurable and only exists for testing purposes
-    #[serde(skip, default = "default_endpoint")]
+    /// The base URL of the Honeycomb API.
+    ///
+    /// This can be changed to send the events to another region of Honeycomb, or to any other
+    /// service implementing its batched events API.
+    #[serde(default = "default_endpoint")]
+    #[configurable(metadata(docs::examples = "https://api.eu1.honeycomb.io"))]
     endpoint: String,
 
     /// The team key that will be used to authenticate against Honeycomb.
@@ -32,10 +43,16 @@ pub struct HoneycombConfig {
     api_key: SensitiveString,
 
     /// The dataset to which logs are sent.
+    ///
+    /// The events are batched separately for each dataset they resolve to, and the ones whose
+    /// dataset can't be rendered are dropped.
     #[configurable(metadata(docs::examples = "my-honeycomb-dataset"))]
-    // TODO: we probably want to make this a template
-    // but this limits us in how we can do our healthcheck.
-    dataset: String,
+    #[configurable(metadata(docs::examples = "{{ service }}-{{ environment }}"))]
+    dataset: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    flatten: FlattenConfig,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -61,8 +78,106 @@ pub struct HoneycombConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// Encodes the datasets as a segment of the path of the URI, leaving the unreserved characters.
+const DATASET_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
 fn default_endpoint() -> String {
-    "https://api.honeycomb.io/1/batch".to_string()
+    "https://api.honeycomb.io".to_string()
+}
+
+/// Controls how the nested fields of the events are flattened into the attributes of the
+/// Honeycomb events.
+///
+/// By default, all the nested fields are flattened, each one into an attribute named after its
+/// path.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct FlattenConfig {
+    /// The separator joining the keys of the nested objects into the names of the attributes.
+    ///
+    /// The separator is escaped with a backslash in the keys containing it.
+    #[serde(default = "default_separator")]
+    #[derivative(Default(value = "default_separator()"))]
+    #[configurable(validation(length(min = 1)))]
+    #[configurable(metadata(docs::examples = "_"))]
+    separator: String,
+
+    /// The depth up to which the nested objects are flattened, the top-level fields being at a
+    /// depth of `1`.
+    ///
+    /// The deeper objects are sent as JSON, which Honeycomb unpacks into attributes if the dataset
+    /// is configured to. By default, the objects are flattened whatever their depth.
+    #[configurable(metadata(docs::examples = 1))]
+    #[configurable(metadata(docs::examples = 3))]
+    max_depth: Option<usize>,
+
+    /// Whether to flatten the arrays, into an attribute for each of their elements, suffixed with
+    /// its index.
+    ///
+    /// If disabled, the arrays are sent as JSON arrays.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    arrays: bool,
+}
+
+fn default_separator() -> String {
+    ".".to_string()
+}
+
+impl FlattenConfig {
+    /// Returns the attributes of a log event.
+    ///
+    /// A log event whose root isn't an object only has a `message` attribute.
+    fn attributes<'a>(&self, log: &'a LogEvent) -> BTreeMap<String, &'a Value> {
+        let mut attributes = BTreeMap::new();
+        match log.as_map() {
+            Some(fields) => {
+                for (key, value) in fields {
+                    self.flatten(self.escape(key), value, 1, &mut attributes);
+                }
+            }
+            None => {
+                attributes.insert("message".to_string(), log.value());
+            }
+        }
+        attributes
+    }
+
+    fn flatten<'a>(
+        &self,
+        path: String,
+        value: &'a Value,
+        depth: usize,
+        attributes: &mut BTreeMap<String, &'a Value>,
+    ) {
+        let nested = self.max_depth.map_or(true, |max_depth| depth < max_depth);
+        match value {
+            Value::Object(fields) if nested && !fields.is_empty() => {
+                for (key, value) in fields {
+                    let path = format!("{}{}{}", path, self.separator, self.escape(key));
+                    self.flatten(path, value, depth + 1, attributes);
+                }
+            }
+            Value::Array(values) if nested && self.arrays && !values.is_empty() => {
+                for (index, value) in values.iter().enumerate() {
+                    self.flatten(format!("{}[{}]", path, index), value, depth + 1, attributes);
+                }
+            }
+            _ => {
+                attributes.insert(path, value);
+            }
+        }
+    }
+
+    fn escape(&self, key: &str) -> String {
+        key.replace(&self.separator, &format!("\\{}", self.separator))
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -93,13 +208,14 @@ impl SinkConfig for HoneycombConfig {
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batch_settings()?;
 
-        let buffer = JsonArrayBuffer::new(batch_settings.size);
+        let buffer = PartitionBuffer::new(JsonArrayBuffer::new(batch_settings.size));
 
         let client = HttpClient::new(None, cx.proxy())?;
 
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::with_retry_logic(
             self.clone(),
             buffer,
+            HoneycombRetryLogic,
             request_settings,
             batch_settings.timeout,
             client.clone(),
@@ -124,11 +240,28 @@ impl SinkConfig for HoneycombConfig {
 }
 
 pub struct HoneycombEventEncoder {
+    dataset: Template,
+    flatten: FlattenConfig,
     transformer: Transformer,
 }
 
-impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<serde_json::Value> {
+impl HttpEventEncoder<PartitionInnerBuffer<serde_json::Value, String>> for HoneycombEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<serde_json::Value, String>> {
+        let dataset = self
+            .dataset
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("dataset"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
         self.transformer.transform(&mut event);
         let mut log = event.into_log();
 
@@ -140,27 +273,33 @@ impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
 
         let data = json!({
             "time": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
-            "data": log.convert_to_fields(),
+            "data": self.flatten.attributes(&log),
         });
 
-        Some(data)
+        Some(PartitionInnerBuffer::new(data, dataset))
     }
 }
 
 #[async_trait::async_trait]
 impl HttpSink for HoneycombConfig {
-    type Input = serde_json::Value;
-    type Output = Vec<BoxedRawValue>;
+    type Input = PartitionInnerBuffer<serde_json::Value, String>;
+    type Output = PartitionInnerBuffer<Vec<BoxedRawValue>, String>;
     type Encoder = HoneycombEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HoneycombEventEncoder {
+            dataset: self.dataset.clone(),
+            flatten: self.flatten.clone(),
             transformer: self.encoding.clone(),
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
-        let uri = self.build_uri();
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Bytes>> {
+        let (events, dataset) = output.into_parts();
+        let uri = self.build_uri(&format!(
+            "1/batch/{}",
+            utf8_percent_encode(&dataset, DATASET_ENCODE_SET)
+        ))?;
         let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.inner());
         let body = crate::serde::json::to_bytes(&events).unwrap().freeze();
 
@@ -169,25 +308,47 @@ impl HttpSink for HoneycombConfig {
 }
 
 impl HoneycombConfig {
-    fn build_uri(&self) -> Uri {
-        let uri = format!("{}/{}", self.endpoint, self.dataset);
+    fn build_uri(&self, path: &str) -> crate::Result<Uri> {
+        let uri = format!("{}/{}", self.endpoint.trim_end_matches('/'), path);
 
-        uri.parse::<Uri>().expect("This should be a valid uri")
+        uri.parse::<Uri>().map_err(Into::into)
+    }
+}
+
+/// Retries the requests like any HTTP sink, waiting for as long as the rate limited responses
+/// ask to.
+#[derive(Clone, Debug, Default)]
+struct HoneycombRetryLogic;
+
+impl RetryLogic for HoneycombRetryLogic {
+    type Error = HttpError;
+    type Response = http::Response<Bytes>;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        HttpRetryLogic.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        HttpRetryLogic.should_retry_response(response)
+    }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(response.headers())
     }
 }
 
 async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Result<()> {
-    let req = config
-        .build_request(Vec::new())
-        .await?
-        .map(hyper::Body::from);
+    // The datasets may be templated, so only the team key is checked.
+    let req = Request::get(config.build_uri("1/auth")?)
+        .header("X-Honeycomb-Team", config.api_key.inner())
+        .body(hyper::Body::empty())?;
 
     let res = client.send(req).await?;
 
     let status = res.status();
     let body = hyper::body::to_bytes(res.into_body()).await?;
 
-    if status == StatusCode::BAD_REQUEST {
+    if status.is_success() {
         Ok(())
     } else if status == StatusCode::UNAUTHORIZED {
         let json: serde_json::Value = serde_json::from_slice(&body[..])?;
@@ -227,13 +388,125 @@ mod test {
         },
     };
 
-    use super::HoneycombConfig;
+    use super::*;
+
+    fn nested_log() -> LogEvent {
+        LogEvent::try_from(json!({
+            "a": {
+                "b": { "c": 1 },
+                "d.e": true,
+            },
+            "list": [1, { "x": "y" }],
+            "empty": {},
+        }))
+        .unwrap()
+    }
+
+    fn flatten(flatten: &FlattenConfig, log: &LogEvent) -> serde_json::Value {
+        serde_json::to_value(flatten.attributes(log)).unwrap()
+    }
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<HoneycombConfig>();
     }
 
+    #[test]
+    fn flattens_all_fields_by_default() {
+        let log = nested_log();
+
+        assert_eq!(
+            flatten(&FlattenConfig::default(), &log),
+            serde_json::to_value(log.convert_to_fields()).unwrap()
+        );
+        assert_eq!(
+            flatten(
+                &FlattenConfig::default(),
+                &LogEvent::from_str_legacy("hello")
+            ),
+            json!({ "message": "hello" })
+        );
+    }
+
+    #[test]
+    fn flattens_with_controls() {
+        let log = nested_log();
+
+        let config = FlattenConfig {
+            separator: "_".to_string(),
+            max_depth: Some(2),
+            arrays: false,
+        };
+        assert_eq!(
+            flatten(&config, &log),
+            json!({
+                "a_b": { "c": 1 },
+                "a_d.e": true,
+                "list": [1, { "x": "y" }],
+                "empty": {},
+            })
+        );
+
+        let config = FlattenConfig {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            flatten(&config, &log),
+            json!({
+                "a": { "b": { "c": 1 }, "d.e": true },
+                "list": [1, { "x": "y" }],
+                "empty": {},
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn batches_by_dataset() {
+        let config = HoneycombConfig::generate_config().to_string();
+        let mut config = HoneycombConfig::deserialize(toml::de::ValueDeserializer::new(&config))
+            .expect("config should be valid");
+        config.dataset = Template::try_from("{{ service }} logs").unwrap();
+        let mut encoder = config.build_encoder();
+
+        let mut log = LogEvent::from("simple message");
+        log.insert("service", "api/v1");
+        let (_, dataset) = encoder.encode_event(log.into()).unwrap().into_parts();
+        assert_eq!(dataset, "api/v1 logs");
+        assert!(encoder
+            .encode_event(LogEvent::from("simple message").into())
+            .is_none());
+
+        let request = config
+            .build_request(PartitionInnerBuffer::new(
+                Vec::<BoxedRawValue>::new(),
+                dataset,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            request.uri(),
+            "https://api.honeycomb.io/1/batch/api%2Fv1%20logs"
+        );
+    }
+
+    #[test]
+    fn retries_after_rate_limit() {
+        let response = http::Response::builder()
+            .status(429)
+            .header("Retry-After", "5")
+            .body(Bytes::new())
+            .unwrap();
+
+        assert!(HoneycombRetryLogic
+            .should_retry_response(&response)
+            .is_retryable());
+        assert_eq!(
+            HoneycombRetryLogic.retry_after(&response),
+            Some(Duration::from_secs(5))
+        );
+    }
+
     #[tokio::test]
     async fn component_spec_compliance() {
         let mock_endpoint = spawn_blackhole_http_server(always_200_response).await;
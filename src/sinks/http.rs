@@ -2,7 +2,6 @@ use std::io::Write;
 
 use bytes::{BufMut, Bytes, BytesMut};
 use codecs::encoding::{CharacterDelimitedEncoder, Framer, Serializer};
-use flate2::write::{GzEncoder, ZlibEncoder};
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{HeaderName, HeaderValue, AUTHORIZATION},
@@ -10,6 +9,8 @@ use http::{
 };
 use hyper::Body;
 use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use tokio_util::codec::Encoder as _;
 use vector_config::configurable_component;
 
@@ -19,16 +20,21 @@ use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     http::{Auth, HttpClient, MaybeAuth},
+    internal_events::{HttpSinkInvalidUri, TemplateRenderingError},
     register_validatable_component,
     sinks::util::{
         self,
-        http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{HttpEventEncoder, PartitionHttpSink, RequestConfig},
+        BatchConfig, Buffer, Compression, Compressor, PartitionBuffer, PartitionInnerBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
+/// The placeholder of the body template replaced by the encoded batch of events.
+static BODY_PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*events\s*\}\}").unwrap());
+
 /// Configuration for the `http` sink.
 #[configurable_component(sink("http"))]
 #[derive(Clone, Debug)]
@@ -37,8 +43,15 @@ pub struct HttpSinkConfig {
     /// The full URI to make HTTP requests to.
     ///
     /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    ///
+    /// The URI can be templated with the fields of the events, in which case the events are batched
+    /// separately for each URI they render. As `%` starts a time format specifier in templates,
+    /// percent-encoded characters must be written with `%%`, such as `%%20` for a space.
     #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/endpoint"))]
-    pub uri: UriSerde,
+    #[configurable(metadata(
+        docs::examples = "https://api.example.com/tenants/{{ tenant_id }}/logs"
+    ))]
+    pub uri: Template,
 
     /// The HTTP method to use when making the request.
     #[serde(default)]
@@ -79,6 +92,18 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub payload_suffix: String,
 
+    /// A template wrapping the encoded batch of events in the request body.
+    ///
+    /// The batch is inserted in place of `{{ events }}`, which must appear exactly once. The rest
+    /// of the template can refer to the fields of the events, in which case the events are batched
+    /// separately for each body they render.
+    ///
+    /// This is applied after the `payload_prefix` and `payload_suffix`, and before compression.
+    #[configurable(metadata(
+        docs::examples = "{\"tenant\":\"{{ tenant_id }}\",\"logs\":{{ events }}}"
+    ))]
+    pub body_template: Option<String>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
@@ -168,11 +193,12 @@ impl HttpSinkConfig {
 }
 
 struct HttpSink {
-    pub uri: UriSerde,
+    pub uri: Template,
     pub method: HttpMethod,
     pub auth: Option<Auth>,
     pub payload_prefix: String,
     pub payload_suffix: String,
+    pub body_template: Option<BodyTemplate>,
     pub compression: Compression,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
@@ -195,6 +221,7 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
         encoder,
         payload_prefix: Default::default(),
         payload_suffix: Default::default(),
+        body_template: Default::default(),
         batch: Default::default(),
         tower: Default::default(),
         headers: Default::default(),
@@ -225,11 +252,28 @@ impl SinkConfig for HttpSinkConfig {
 
         let (payload_prefix, payload_suffix) =
             validate_payload_wrapper(&self.payload_prefix, &self.payload_suffix, &encoder)?;
+        let body_template = self
+            .body_template
+            .as_deref()
+            .map(BodyTemplate::new)
+            .transpose()?;
+
+        // Credentials can only be taken from a URI which doesn't depend on the events, the ones of
+        // a templated URI are checked when the requests are built.
+        let auth = if self.uri.is_dynamic() {
+            self.auth.clone()
+        } else {
+            let uri = self.uri.get_ref().parse::<UriSerde>()?;
+            self.auth.choose_one(&uri.auth)?
+        };
+        if let Some(auth) = &auth {
+            auth.start(&client).await?;
+        }
 
         let sink = HttpSink {
-            uri: self.uri.with_default_parts(),
+            uri: self.uri.clone(),
             method: self.method,
-            auth: self.auth.choose_one(&self.uri.auth)?,
+            auth,
             compression: self.compression,
             transformer: self.encoding.transformer(),
             encoder,
@@ -238,14 +282,15 @@ impl SinkConfig for HttpSinkConfig {
             headers,
             payload_prefix,
             payload_suffix,
+            body_template,
         };
 
         let request = sink.tower.unwrap_with(&TowerRequestConfig::default());
 
         let batch = sink.batch.into_batch_settings()?;
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
             request,
             batch.timeout,
             client,
@@ -272,7 +317,7 @@ impl ValidatableComponent for HttpSinkConfig {
         use std::str::FromStr;
 
         let config = Self {
-            uri: UriSerde::from_str("http://127.0.0.1:9000/endpoint")
+            uri: Template::try_from("http://127.0.0.1:9000/endpoint")
                 .expect("should never fail to parse"),
             method: HttpMethod::Post,
             encoding: EncodingConfigWithFraming::new(
@@ -289,11 +334,13 @@ impl ValidatableComponent for HttpSinkConfig {
             acknowledgements: AcknowledgementsConfig::default(),
             payload_prefix: String::new(),
             payload_suffix: String::new(),
+            body_template: None,
         };
 
+        let uri = Uri::from_str(config.uri.get_ref()).expect("should never fail to parse");
         let external_resource = ExternalResource::new(
             ResourceDirection::Push,
-            HttpResourceConfig::from_parts(config.uri.uri.clone(), Some(config.method.into())),
+            HttpResourceConfig::from_parts(uri, Some(config.method.into())),
             config.encoding.clone(),
         );
 
@@ -303,38 +350,121 @@ impl ValidatableComponent for HttpSinkConfig {
 
 register_validatable_component!(HttpSinkConfig);
 
+/// The parts of the body template around the encoded batch of events.
+#[derive(Clone, Debug)]
+struct BodyTemplate {
+    prefix: Template,
+    suffix: Template,
+}
+
+impl BodyTemplate {
+    fn new(template: &str) -> crate::Result<Self> {
+        let mut placeholders = BODY_PLACEHOLDER.find_iter(template);
+        match (placeholders.next(), placeholders.next()) {
+            (Some(placeholder), None) => Ok(Self {
+                prefix: Template::try_from(&template[..placeholder.start()])?,
+                suffix: Template::try_from(&template[placeholder.end()..])?,
+            }),
+            _ => Err("The body template must contain `{{ events }}` exactly once.".into()),
+        }
+    }
+}
+
+/// The batch of events is split by the rendered URI and body template.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PartitionKey {
+    uri: String,
+    body: Option<(String, String)>,
+}
+
 pub struct HttpSinkEventEncoder {
     encoder: Encoder<Framer>,
     transformer: Transformer,
+    uri: Template,
+    body_template: Option<BodyTemplate>,
+}
+
+impl HttpSinkEventEncoder {
+    fn render_key(
+        &self,
+        event: &Event,
+    ) -> Result<PartitionKey, (&'static str, crate::template::TemplateRenderingError)> {
+        let uri = self.uri.render_string(event).map_err(|e| ("uri", e))?;
+        let body = self
+            .body_template
+            .as_ref()
+            .map(|body| {
+                Ok((
+                    body.prefix.render_string(event)?,
+                    body.suffix.render_string(event)?,
+                ))
+            })
+            .transpose()
+            .map_err(|e| ("body_template", e))?;
+        Ok(PartitionKey { uri, body })
+    }
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, PartitionKey>> for HttpSinkEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<BytesMut, PartitionKey>> {
+        let key = self
+            .render_key(&event)
+            .map_err(|(field, error)| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        // A static URI is validated when the sink is built.
+        if self.uri.is_dynamic() {
+            if let Err(error) = key.uri.parse::<Uri>() {
+                emit!(HttpSinkInvalidUri {
+                    uri: &key.uri,
+                    error
+                });
+                return None;
+            }
+        }
+
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
         self.encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, key))
     }
 }
 
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = PartitionInnerBuffer<BytesMut, PartitionKey>;
+    type Output = PartitionInnerBuffer<BytesMut, PartitionKey>;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HttpSinkEventEncoder {
             encoder: self.encoder.clone(),
             transformer: self.transformer.clone(),
+            uri: self.uri.clone(),
+            body_template: self.body_template.clone(),
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (mut body, key) = output.into_parts();
         let method: Method = self.method.into();
-        let uri: Uri = self.uri.uri.clone();
+        let uri = key.uri.parse::<UriSerde>()?.with_default_parts();
+        let auth = if self.uri.is_dynamic() {
+            self.auth.choose_one(&uri.auth)?
+        } else {
+            self.auth.clone()
+        };
 
         let content_type = {
             use Framer::*;
@@ -369,30 +499,27 @@ impl util::http::HttpSink for HttpSink {
             }
         };
 
-        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some((prefix, suffix)) = &key.body {
+            let events = body.split();
+            body.put(prefix.as_bytes());
+            body.unsplit(events);
+            body.put(suffix.as_bytes());
+        }
+
+        let mut builder = Request::builder().method(method).uri(uri.uri);
 
         if let Some(content_type) = content_type {
             builder = builder.header("Content-Type", content_type);
         }
 
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
+        if let Some(content_encoding) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
 
-                let buffer = BytesMut::new();
-                let mut w = GzEncoder::new(buffer.writer(), level.as_flate2());
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
-            }
-            Compression::Zlib(level) => {
-                builder = builder.header("Content-Encoding", "deflate");
-
-                let buffer = BytesMut::new();
-                let mut w = ZlibEncoder::new(buffer.writer(), level.as_flate2());
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
-            }
-            Compression::None => {}
+            let mut compressor = Compressor::with_capacity(self.compression, body.len());
+            compressor
+                .write_all(&body)
+                .expect("Writing to Vec can't fail");
+            body = compressor.finish().expect("Writing to Vec can't fail");
         }
 
         let headers = builder
@@ -405,7 +532,7 @@ impl util::http::HttpSink for HttpSink {
 
         let mut request = builder.body(body.freeze()).unwrap();
 
-        if let Some(auth) = &self.auth {
+        if let Some(auth) = &auth {
             auth.apply(&mut request);
         }
 
@@ -511,7 +638,7 @@ mod tests {
 
         let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
@@ -528,7 +655,7 @@ mod tests {
                 .into(),
         );
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -612,6 +739,83 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn http_validates_body_template() {
+        assert!(BodyTemplate::new(r#"{"logs":{{events}}}"#).is_ok());
+        assert!(BodyTemplate::new(r#"{"logs":[]}"#).is_err());
+        assert!(BodyTemplate::new(r#"{"logs":{{ events }},"more":{{ events }}}"#).is_err());
+    }
+
+    #[test]
+    fn http_encode_event_partitions_by_template() {
+        let mut sink =
+            default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
+        sink.uri = Template::try_from("http://example.com/{{ tenant }}/logs").unwrap();
+        sink.body_template = Some(BodyTemplate::new("{{ tenant }}: {{ events }}").unwrap());
+        let mut encoder = sink.build_encoder();
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("tenant", "a");
+        let (_, key) = encoder.encode_event(log.into()).unwrap().into_parts();
+        assert_eq!(
+            key,
+            PartitionKey {
+                uri: "http://example.com/a/logs".to_owned(),
+                body: Some(("a: ".to_owned(), String::new())),
+            }
+        );
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("tenant", "not a valid path");
+        assert!(encoder.encode_event(log.into()).is_none());
+        assert!(encoder
+            .encode_event(LogEvent::from("hello world").into())
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn http_templated_uri_and_body() {
+        let in_addr = next_addr();
+
+        let config = r#"
+        uri = "http://$IN_ADDR/tenants/{{ tenant }}/logs"
+        encoding.codec = "json"
+        body_template = '{"tenant":"{{ tenant }}","logs":{{ events }}}'
+    "#
+        .replace("$IN_ADDR", &in_addr.to_string());
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (rx, trigger, server) = build_test_server(in_addr);
+        tokio::spawn(server);
+
+        let events = ["a", "b", "a"].into_iter().map(|tenant| {
+            let mut log = LogEvent::from(format!("hello {}", tenant));
+            log.insert("tenant", tenant);
+            Event::Log(log)
+        });
+        components::run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS)
+            .await;
+        drop(trigger);
+
+        let mut requests = rx
+            .map(|(parts, body)| {
+                let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                (parts.uri.path().to_owned(), body)
+            })
+            .collect::<Vec<_>>()
+            .await;
+        requests.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].0, "/tenants/a/logs");
+        assert_eq!(requests[0].1["tenant"], "a");
+        assert_eq!(requests[0].1["logs"].as_array().unwrap().len(), 2);
+        assert_eq!(requests[1].0, "/tenants/b/logs");
+        assert_eq!(requests[1].1["tenant"], "b");
+        assert_eq!(requests[1].1["logs"][0]["message"], "hello b");
+    }
+
     // TODO: Fix failure on GH Actions using macos-latest image.
     #[cfg(not(target_os = "macos"))]
     #[tokio::test]
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, time::Duration};
 
-use codecs::JsonSerializerConfig;
+use codecs::{encoding::SerializerConfig, JsonSerializerConfig};
 use futures::FutureExt;
 use rdkafka::ClientConfig;
 use serde_with::serde_as;
@@ -10,8 +10,12 @@ use vector_core::schema::Requirement;
 
 use crate::{
     codecs::EncodingConfig,
-    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
+        SinkContext,
+    },
     kafka::{KafkaAuthConfig, KafkaCompression},
+    schema_registry::{SchemaRegistryClient, SchemaRegistryConfig},
     serde::json::to_string,
     sinks::{
         kafka::sink::{healthcheck, KafkaSink},
@@ -110,6 +114,75 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<String>,
 
+    /// The log field name whose value the events are kept in order by.
+    ///
+    /// The events with the same value are produced one at a time, in the order they're received
+    /// in, so that the retries of an event can't reorder it with the next ones. This limits the
+    /// throughput of each value to one message in flight, and the events missing the field are
+    /// produced without any ordering. The transactions of a `transactional_id` are always produced
+    /// one at a time.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "user_id"))]
+    pub preserve_order_by: Option<String>,
+
+    /// Whether the producer is idempotent, so that the messages it retries are written exactly
+    /// once, and in order, to their partition.
+    ///
+    /// Always enabled when a `transactional_id` is set.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub idempotent: bool,
+
+    /// The transactional ID of the producer, which produces the events of each batch in a
+    /// transaction when set.
+    ///
+    /// The events of a batch are only acknowledged once its transaction is committed, and the
+    /// transaction is aborted if any of them fails to be produced, so the consumers reading with
+    /// `isolation.level` set to `read_committed` only see the batches which were fully produced.
+    /// When Vector restarts, the transaction left open by its previous run is aborted, so the
+    /// events delivered again by sources supporting end-to-end acknowledgements aren't
+    /// duplicated.
+    ///
+    /// The ID must be unique to each instance of Vector, and stay the same across its restarts.
+    /// The batches are bounded by the `batch` options.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "vector-aggregator-0"))]
+    pub transactional_id: Option<String>,
+
+    /// The maximum amount of time a transaction can be open, in milliseconds, before the broker
+    /// aborts it.
+    ///
+    /// Must be longer than `batch.timeout_secs`.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_timeout_ms")]
+    #[configurable(metadata(docs::examples = 60000))]
+    #[configurable(metadata(docs::advanced))]
+    pub transaction_timeout_ms: Duration,
+
+    /// The ID of a `kafka` source whose consumer offsets are committed in the transactions of the
+    /// sink, for the events it consumed.
+    ///
+    /// The offsets are committed to the consumer group of the source along with the messages they
+    /// were transformed into, so each message consumed by the source is produced exactly once,
+    /// even when Vector restarts. The source then leaves committing its offsets to the sink. It
+    /// must use a consumer group and have end-to-end acknowledgements enabled, which is checked
+    /// when the configuration is loaded. The events which weren't consumed by the source are
+    /// produced without committing any offset.
+    ///
+    /// Requires a `transactional_id`.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "kafka_in"))]
+    pub transactional_source: Option<String>,
+
+    /// Produces messages in the Confluent wire format, registering the Avro schema in a schema
+    /// registry.
+    ///
+    /// Requires the `avro` codec. The schema is registered when the sink starts, and the ID it is
+    /// registered with prefixes every message.
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub schema_registry: Option<SchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -127,6 +200,10 @@ const fn default_message_timeout_ms() -> Duration {
     Duration::from_millis(300000) // default in librdkafka
 }
 
+const fn default_transaction_timeout_ms() -> Duration {
+    Duration::from_millis(60000) // default in librdkafka
+}
+
 fn example_librdkafka_options() -> HashMap<String, String> {
     HashMap::<_, _>::from_iter(
         [
@@ -169,6 +246,16 @@ impl KafkaSinkConfig {
                         &self.message_timeout_ms.as_millis().to_string(),
                     );
 
+                if self.idempotent || self.transactional_id.is_some() {
+                    client_config.set("enable.idempotence", "true");
+                }
+                if let Some(transactional_id) = &self.transactional_id {
+                    client_config.set("transactional.id", transactional_id).set(
+                        "transaction.timeout.ms",
+                        &self.transaction_timeout_ms.as_millis().to_string(),
+                    );
+                }
+
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                     // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -244,6 +331,35 @@ impl KafkaSinkConfig {
     }
 }
 
+impl KafkaSinkConfig {
+    /// Registers the Avro schema of the encoding in the schema registry, returning its ID.
+    async fn register_schema(
+        &self,
+        registry: &SchemaRegistryConfig,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<u32> {
+        let schema = match self.encoding.config() {
+            SerializerConfig::Avro { avro } => &avro.schema,
+            _ => return Err("The `schema_registry` option requires the `avro` codec.".into()),
+        };
+
+        let subject = match &registry.subject {
+            Some(subject) => subject.clone(),
+            None if !self.topic.is_dynamic() => format!("{}-value", self.topic.get_ref()),
+            None => {
+                return Err(
+                    "A `schema_registry.subject` must be set when the topic is templated.".into(),
+                )
+            }
+        };
+
+        let client = SchemaRegistryClient::new(registry, proxy)?;
+        let id = client.register(&subject, schema).await?;
+        debug!(message = "Registered Avro schema.", %subject, %id);
+        Ok(id)
+    }
+}
+
 impl GenerateConfig for KafkaSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -258,6 +374,12 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            preserve_order_by: None,
+            idempotent: false,
+            transactional_id: None,
+            transaction_timeout_ms: default_transaction_timeout_ms(),
+            transactional_source: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -266,8 +388,12 @@ impl GenerateConfig for KafkaSinkConfig {
 
 #[async_trait::async_trait]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone())?;
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let schema_id = match &self.schema_registry {
+            Some(registry) => Some(self.register_schema(registry, cx.proxy()).await?),
+            None => None,
+        };
+        let sink = KafkaSink::new(self.clone())?.with_schema_id(schema_id);
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
@@ -282,6 +408,10 @@ impl SinkConfig for KafkaSinkConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn linked_source(&self) -> Option<&str> {
+        self.transactional_source.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -292,4 +422,70 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn transactional_producer_options() {
+        let config: KafkaSinkConfig = toml::from_str(indoc::indoc! {r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transactional_id = "vector-0"
+            transaction_timeout_ms = 30000
+        "#})
+        .unwrap();
+
+        let producer = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(producer.get("enable.idempotence"), Some("true"));
+        assert_eq!(producer.get("transactional.id"), Some("vector-0"));
+        assert_eq!(producer.get("transaction.timeout.ms"), Some("30000"));
+
+        let consumer = config.to_rdkafka(KafkaRole::Consumer).unwrap();
+        assert_eq!(consumer.get("transactional.id"), None);
+    }
+
+    #[test]
+    fn transactional_source_requires_transactional_id() {
+        let config: KafkaSinkConfig = toml::from_str(indoc::indoc! {r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transactional_source = "kafka_in"
+        "#})
+        .unwrap();
+
+        let error = KafkaSink::new(config).err().unwrap();
+        assert!(error.to_string().contains("requires a `transactional_id`"));
+    }
+
+    #[tokio::test]
+    async fn schema_registry_requires_avro_and_subject() {
+        let config: KafkaSinkConfig = toml::from_str(indoc::indoc! {r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            schema_registry.url = "http://localhost:8081"
+        "#})
+        .unwrap();
+        let registry = config.schema_registry.clone().unwrap();
+        let error = config
+            .register_schema(&registry, &ProxyConfig::default())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("`avro` codec"));
+
+        let config: KafkaSinkConfig = toml::from_str(indoc::indoc! {r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs-{{ service }}"
+            encoding.codec = "avro"
+            encoding.avro.schema = '{ "type": "record", "name": "log", "fields": [] }'
+            schema_registry.url = "http://localhost:8081"
+        "#})
+        .unwrap();
+        let registry = config.schema_registry.clone().unwrap();
+        let error = config
+            .register_schema(&registry, &ProxyConfig::default())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("`schema_registry.subject`"));
+    }
 }
@@ -1,6 +1,8 @@
 use std::num::NonZeroUsize;
 
-use bytes::{Bytes, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
+use codecs::decoding::format::CONFLUENT_MAGIC_BYTE;
+use lookup::metadata_path;
 use rdkafka::message::{Header, OwnedHeaders};
 use tokio_util::codec::Encoder as _;
 
@@ -8,9 +10,10 @@ use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, Finalizable, Value},
     internal_events::{KafkaHeaderExtractionError, TemplateRenderingError},
+    kafka,
     sinks::{
-        kafka::service::{KafkaRequest, KafkaRequestMetadata},
-        util::metadata::RequestMetadataBuilder,
+        kafka::service::{KafkaRequest, KafkaRequestMetadata, SourceOffset},
+        util::{metadata::RequestMetadataBuilder, service::lane},
     },
     template::Template,
 };
@@ -18,9 +21,12 @@ use crate::{
 pub struct KafkaRequestBuilder {
     pub key_field: Option<String>,
     pub headers_key: Option<String>,
+    pub preserve_order_by: Option<String>,
     pub topic_template: Template,
     pub transformer: Transformer,
     pub encoder: Encoder<()>,
+    pub schema_id: Option<u32>,
+    pub transactional_source: Option<String>,
 }
 
 impl KafkaRequestBuilder {
@@ -45,9 +51,19 @@ impl KafkaRequestBuilder {
             timestamp_millis: get_timestamp_millis(&event),
             headers: get_headers(&event, &self.headers_key),
             topic,
+            source_offset: get_source_offset(&event, &self.transactional_source),
+            lane: self
+                .preserve_order_by
+                .as_ref()
+                .zip(event.maybe_as_log())
+                .and_then(|(field, log)| lane(log, field)),
         };
         self.transformer.transform(&mut event);
         let mut body = BytesMut::new();
+        if let Some(schema_id) = self.schema_id {
+            body.put_u8(CONFLUENT_MAGIC_BYTE);
+            body.put_u32(schema_id);
+        }
         self.encoder.encode(event, &mut body).ok()?;
         let body = body.freeze();
 
@@ -75,6 +91,24 @@ fn get_key(event: &Event, key_field: &Option<String>) -> Option<Bytes> {
     })
 }
 
+/// Returns the offset the event was consumed from, if it was consumed by the linked kafka source.
+fn get_source_offset(event: &Event, source: &Option<String>) -> Option<SourceOffset> {
+    let source = source.as_ref()?;
+    let offset = event
+        .maybe_as_log()?
+        .get(metadata_path!("vector", kafka::OFFSET_METADATA_KEY))?
+        .as_object()?;
+    if offset.get("source")?.as_bytes()? != source.as_str() {
+        return None;
+    }
+
+    Some(SourceOffset {
+        topic: String::from_utf8_lossy(offset.get("topic")?.as_bytes()?).into_owned(),
+        partition: i32::try_from(offset.get("partition")?.as_integer()?).ok()?,
+        offset: offset.get("offset")?.as_integer()?,
+    })
+}
+
 fn get_timestamp_millis(event: &Event) -> Option<i64> {
     match &event {
         Event::Log(log) => log.get_timestamp().and_then(|v| v.as_timestamp()).copied(),
@@ -143,4 +177,26 @@ mod tests {
         assert_eq!(headers.get(1).key, "b-key");
         assert_eq!(headers.get(1).value.unwrap(), "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_get_source_offset() {
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert(
+            metadata_path!("vector", kafka::OFFSET_METADATA_KEY),
+            BTreeMap::from([
+                ("source".to_owned(), Value::from("in")),
+                ("topic".to_owned(), Value::from("logs")),
+                ("partition".to_owned(), Value::from(3)),
+                ("offset".to_owned(), Value::from(42)),
+            ]),
+        );
+
+        let offset = get_source_offset(&event, &Some("in".to_owned())).unwrap();
+        assert_eq!(offset.topic, "logs");
+        assert_eq!(offset.partition, 3);
+        assert_eq!(offset.offset, 42);
+
+        assert!(get_source_offset(&event, &Some("other".to_owned())).is_none());
+        assert!(get_source_offset(&event, &None).is_none());
+    }
 }
@@ -1,12 +1,18 @@
-use std::task::{Context, Poll};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use bytes::Bytes;
-use futures::future::BoxFuture;
+use futures::future::{self, BoxFuture};
 use rdkafka::{
-    error::KafkaError,
+    error::{KafkaError, KafkaResult, RDKafkaErrorCode},
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{FutureProducer, FutureRecord, Producer},
     util::Timeout,
+    ClientConfig, Offset, TopicPartitionList,
 };
 use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
@@ -19,7 +25,8 @@ use vector_core::{
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    kafka::KafkaStatisticsContext,
+    kafka::{self, KafkaStatisticsContext},
+    sinks::{kafka::sink::create_producer, util::service::Laned},
 };
 
 pub struct KafkaRequest {
@@ -34,9 +41,20 @@ pub struct KafkaRequestMetadata {
     pub timestamp_millis: Option<i64>,
     pub headers: Option<OwnedHeaders>,
     pub topic: String,
+    pub source_offset: Option<SourceOffset>,
+    /// The lane the request is produced in order with, if any.
+    pub lane: Option<u64>,
+}
+
+/// The offset of the message an event was consumed from by the kafka source linked to the sink.
+pub struct SourceOffset {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
 }
 
 pub struct KafkaResponse {
+    event_count: usize,
     event_byte_size: usize,
 }
 
@@ -46,7 +64,7 @@ impl DriverResponse for KafkaResponse {
     }
 
     fn events_sent(&self) -> CountByteSize {
-        CountByteSize(1, self.event_byte_size)
+        CountByteSize(self.event_count, self.event_byte_size)
     }
 }
 
@@ -56,6 +74,12 @@ impl Finalizable for KafkaRequest {
     }
 }
 
+impl Laned for KafkaRequest {
+    fn lane(&self) -> Option<u64> {
+        self.metadata.lane
+    }
+}
+
 impl MetaDescriptive for KafkaRequest {
     fn get_metadata(&self) -> RequestMetadata {
         self.request_metadata
@@ -91,29 +115,318 @@ impl Service<KafkaRequest> for KafkaService {
 
         Box::pin(async move {
             let event_byte_size = request.get_metadata().events_byte_size();
+            let bytes = produce(&this.kafka_producer, request).await?;
+            this.bytes_sent.emit(ByteSize(bytes));
+            Ok(KafkaResponse {
+                event_count: 1,
+                event_byte_size,
+            })
+        })
+    }
+}
 
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
+/// The requests of a batch, produced in a single transaction.
+pub struct KafkaTransaction {
+    requests: Vec<KafkaRequest>,
+    /// The last offset consumed by the linked source from each of its partitions, by topic and
+    /// partition.
+    source_offsets: HashMap<(String, i32), i64>,
+    finalizers: EventFinalizers,
+    request_metadata: RequestMetadata,
+}
+
+impl KafkaTransaction {
+    pub fn new(mut requests: Vec<KafkaRequest>) -> Self {
+        let request_metadata =
+            RequestMetadata::from_batch(requests.iter().map(|request| request.get_metadata()));
+        let finalizers =
+            requests
+                .iter_mut()
+                .fold(EventFinalizers::default(), |mut finalizers, request| {
+                    finalizers.merge(request.take_finalizers());
+                    finalizers
+                });
+        let mut source_offsets = HashMap::new();
+        for source_offset in requests
+            .iter_mut()
+            .filter_map(|request| request.metadata.source_offset.take())
+        {
+            let offset = source_offsets
+                .entry((source_offset.topic, source_offset.partition))
+                .or_insert(source_offset.offset);
+            *offset = source_offset.offset.max(*offset);
+        }
+        Self {
+            requests,
+            source_offsets,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
+impl Finalizable for KafkaTransaction {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransaction {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.request_metadata
+    }
+}
+
+/// Produces each batch in a transaction, committed before the events of the batch are
+/// acknowledged.
+///
+/// The producer can only have one transaction open at a time, so the transactions must be run
+/// one after the other.
+///
+/// When the sink is linked to a kafka source, the offsets its events were consumed from are
+/// committed to the consumer group of the source in the transaction, so they are only consumed
+/// again if the transaction is aborted.
+///
+/// A producer fenced by another one with the same transactional ID, or failing fatally, can't be
+/// used anymore, so it's replaced by a new one built from the same configuration.
+#[derive(Clone)]
+pub struct TransactionalKafkaService {
+    kafka_producer: Arc<Mutex<FutureProducer<KafkaStatisticsContext>>>,
+    client_config: ClientConfig,
+    timeout: Duration,
+    source: Option<String>,
+    bytes_sent: Registered<BytesSent>,
+}
+
+impl TransactionalKafkaService {
+    pub(crate) fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        client_config: ClientConfig,
+        timeout: Duration,
+        source: Option<String>,
+    ) -> Self {
+        Self {
+            kafka_producer: Arc::new(Mutex::new(kafka_producer)),
+            client_config,
+            timeout,
+            source,
+            bytes_sent: register!(BytesSent::from(Protocol("kafka".into()))),
+        }
+    }
+
+    fn producer(&self) -> FutureProducer<KafkaStatisticsContext> {
+        self.kafka_producer
+            .lock()
+            .expect("kafka producer poisoned")
+            .clone()
+    }
+
+    /// Initializes the transactions of the producer, aborting the transaction left open by a
+    /// previous producer with the same transactional ID.
+    pub(crate) async fn init(&self) -> KafkaResult<()> {
+        init_transactions(&self.producer(), self.timeout).await
+    }
+
+    /// Replaces the producer by a new one, after it failed with `error`.
+    async fn recreate_producer(&self, error: &KafkaError) {
+        warn!(
+            message = "Kafka producer can't be used anymore, creating a new one.",
+            %error,
+        );
+        let producer = match create_producer(self.client_config.clone()) {
+            Ok(producer) => producer,
+            Err(error) => {
+                error!(message = "Failed to create the Kafka producer.", %error);
+                return;
             }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
+        };
+        match init_transactions(&producer, self.timeout).await {
+            Ok(()) => *self.kafka_producer.lock().expect("kafka producer poisoned") = producer,
+            // The failed producer is kept, for the next transaction to try again.
+            Err(error) => error!(message = "Failed to initialize the Kafka transactions.", %error),
+        }
+    }
+
+    async fn run(&self, transaction: KafkaTransaction) -> KafkaResult<usize> {
+        let timeout = self.timeout;
+        let producer = self.producer();
+        let committed = match producer.begin_transaction() {
+            Ok(()) => {
+                let produced = future::try_join_all(
+                    transaction
+                        .requests
+                        .into_iter()
+                        .map(|request| produce(&producer, request)),
+                )
+                .await;
+                match produced {
+                    Ok(bytes) => self
+                        .commit(&producer, transaction.source_offsets)
+                        .await
+                        .map(|_| bytes.into_iter().sum()),
+                    Err(error) => Err(error),
+                }
             }
+            Err(error) => Err(error),
+        };
 
-            // rdkafka will internally retry forever if the queue is full
-            match this.kafka_producer.send(record, Timeout::Never).await {
-                Ok((_partition, _offset)) => {
-                    this.bytes_sent.emit(ByteSize(
-                        request.body.len() + request.metadata.key.map(|x| x.len()).unwrap_or(0),
-                    ));
-                    Ok(KafkaResponse { event_byte_size })
+        if let Err(error) = &committed {
+            if is_fatal(error) {
+                self.recreate_producer(error).await;
+            } else {
+                // Nothing of a failed transaction is seen by the consumers reading the committed
+                // messages only, so its events can be sent again.
+                if let Err(error) = blocking(&producer, move |producer| {
+                    producer.abort_transaction(timeout)
+                })
+                .await
+                {
+                    error!(message = "Failed to abort the Kafka transaction.", %error);
                 }
-                Err((kafka_err, _original_record)) => Err(kafka_err),
             }
+        }
+        committed
+    }
+
+    /// Commits the open transaction, along with the offsets consumed by the linked source.
+    async fn commit(
+        &self,
+        producer: &FutureProducer<KafkaStatisticsContext>,
+        source_offsets: HashMap<(String, i32), i64>,
+    ) -> KafkaResult<()> {
+        let timeout = self.timeout;
+        if let Some(source) = self.source.as_ref().filter(|_| !source_offsets.is_empty()) {
+            let group_metadata = kafka::consumer_group_metadata(source).ok_or_else(|| {
+                error!(
+                    message = "The consumer group of the linked Kafka source is not available.",
+                    %source,
+                );
+                KafkaError::ConsumerCommit(RDKafkaErrorCode::UnknownGroup)
+            })?;
+
+            let mut offsets = TopicPartitionList::new();
+            for ((topic, partition), offset) in source_offsets {
+                // The committed offset is the one of the next message to consume.
+                offsets.add_partition_offset(&topic, partition, Offset::Offset(offset + 1))?;
+            }
+            blocking(producer, move |producer| {
+                producer.send_offsets_to_transaction(&offsets, &group_metadata, timeout)
+            })
+            .await?;
+        }
+
+        blocking(producer, move |producer| {
+            producer.commit_transaction(timeout)
+        })
+        .await
+    }
+}
+
+async fn init_transactions(
+    producer: &FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+) -> KafkaResult<()> {
+    blocking(producer, move |producer| {
+        producer.init_transactions(timeout)
+    })
+    .await
+}
+
+/// Returns whether the producer can't be used anymore after failing with `error`, as it was fenced
+/// by another producer with the same transactional ID, or failed fatally.
+fn is_fatal(error: &KafkaError) -> bool {
+    if let KafkaError::Transaction(error) = error {
+        if error.is_fatal() {
+            return true;
+        }
+    }
+    matches!(
+        error.rdkafka_error_code(),
+        Some(
+            RDKafkaErrorCode::Fatal
+                | RDKafkaErrorCode::Fenced
+                | RDKafkaErrorCode::ProducerFenced
+                | RDKafkaErrorCode::InvalidProducerEpoch
+        )
+    )
+}
+
+impl Service<KafkaTransaction> for TransactionalKafkaService {
+    type Response = KafkaResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, transaction: KafkaTransaction) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let metadata = transaction.get_metadata();
+            let bytes = this.run(transaction).await?;
+            this.bytes_sent.emit(ByteSize(bytes));
+            Ok(KafkaResponse {
+                event_count: metadata.event_count(),
+                event_byte_size: metadata.events_byte_size(),
+            })
         })
     }
 }
+
+/// Produces the message of a request, returning the number of bytes sent.
+async fn produce(
+    producer: &FutureProducer<KafkaStatisticsContext>,
+    request: KafkaRequest,
+) -> KafkaResult<usize> {
+    let mut record = FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
+    if let Some(key) = &request.metadata.key {
+        record = record.key(&key[..]);
+    }
+    if let Some(timestamp) = request.metadata.timestamp_millis {
+        record = record.timestamp(timestamp);
+    }
+    if let Some(headers) = request.metadata.headers {
+        record = record.headers(headers);
+    }
+
+    // rdkafka will internally retry forever if the queue is full
+    match producer.send(record, Timeout::Never).await {
+        Ok((_partition, _offset)) => {
+            Ok(request.body.len() + request.metadata.key.map(|x| x.len()).unwrap_or(0))
+        }
+        Err((kafka_err, _original_record)) => Err(kafka_err),
+    }
+}
+
+/// Runs a call of the producer which blocks until the broker responds, such as those of the
+/// transactions, outside of the runtime.
+async fn blocking<F>(producer: &FutureProducer<KafkaStatisticsContext>, call: F) -> KafkaResult<()>
+where
+    F: FnOnce(&FutureProducer<KafkaStatisticsContext>) -> KafkaResult<()> + Send + 'static,
+{
+    let producer = producer.clone();
+    tokio::task::spawn_blocking(move || call(&producer))
+        .await
+        .unwrap_or(Err(KafkaError::Canceled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fenced_producers_are_recreated() {
+        assert!(is_fatal(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::ProducerFenced
+        )));
+        assert!(is_fatal(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::Fenced
+        )));
+        assert!(!is_fatal(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::MessageTimedOut
+        )));
+        assert!(!is_fatal(&KafkaError::Canceled));
+    }
+}
@@ -9,6 +9,7 @@ use rdkafka::{
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
+use vector_core::stream::BatcherSettings;
 
 use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
@@ -17,10 +18,11 @@ use crate::{
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
-            config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
-            service::KafkaService,
+            config::QUEUED_MIN_MESSAGES,
+            request_builder::KafkaRequestBuilder,
+            service::{KafkaRequest, KafkaService, KafkaTransaction, TransactionalKafkaService},
         },
-        util::{builder::SinkBuilderExt, StreamSink},
+        util::{builder::SinkBuilderExt, service::OrderedLanes, StreamSink},
     },
     template::{Template, TemplateParseError},
 };
@@ -32,6 +34,12 @@ pub(super) enum BuildError {
     KafkaCreateFailed { source: KafkaError },
     #[snafu(display("invalid topic template: {}", source))]
     TopicTemplate { source: TemplateParseError },
+    #[snafu(display(
+        "the `transaction_timeout_ms` must be longer than the `batch.timeout_secs` of the transactions"
+    ))]
+    TransactionTimeout,
+    #[snafu(display("the `transactional_source` requires a `transactional_id`"))]
+    TransactionalSourceWithoutId,
 }
 
 pub struct KafkaSink {
@@ -41,6 +49,16 @@ pub struct KafkaSink {
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
+    preserve_order_by: Option<String>,
+    schema_id: Option<u32>,
+    transactions: Option<Transactions>,
+    transactional_source: Option<String>,
+}
+
+/// The transactions the batches are produced in, when the producer has a transactional ID.
+struct Transactions {
+    service: TransactionalKafkaService,
+    batch_settings: BatcherSettings,
 }
 
 pub(crate) fn create_producer(
@@ -54,41 +72,103 @@ pub(crate) fn create_producer(
 
 impl KafkaSink {
     pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+        if config.transactional_source.is_some() && config.transactional_id.is_none() {
+            return Err(Box::new(BuildError::TransactionalSourceWithoutId));
+        }
+
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
-        let producer = create_producer(producer_config)?;
+        let producer = create_producer(producer_config.clone())?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
 
+        let transactions = match config.transactional_id {
+            Some(_) => {
+                let batch_settings = config.batch.into_batcher_settings()?;
+                if batch_settings.timeout >= config.transaction_timeout_ms {
+                    return Err(Box::new(BuildError::TransactionTimeout));
+                }
+                Some(Transactions {
+                    service: TransactionalKafkaService::new(
+                        producer.clone(),
+                        producer_config,
+                        config.transaction_timeout_ms,
+                        config.transactional_source.clone(),
+                    ),
+                    batch_settings,
+                })
+            }
+            None => None,
+        };
+
         Ok(KafkaSink {
             headers_key: config.headers_key,
+            preserve_order_by: config.preserve_order_by,
             transformer,
             encoder,
             service: KafkaService::new(producer),
             topic: config.topic,
             key_field: config.key_field,
+            schema_id: None,
+            transactions,
+            transactional_source: config.transactional_source,
         })
     }
 
+    /// Prefixes every message with the given schema ID, in the Confluent wire format.
+    pub(crate) const fn with_schema_id(mut self, schema_id: Option<u32>) -> Self {
+        self.schema_id = schema_id;
+        self
+    }
+
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
-        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
+        let ordered = self.preserve_order_by.is_some();
         let mut request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
             headers_key: self.headers_key,
+            preserve_order_by: self.preserve_order_by,
             topic_template: self.topic,
             transformer: self.transformer,
             encoder: self.encoder,
+            schema_id: self.schema_id,
+            transactional_source: self.transactional_source,
         };
 
-        input
-            .filter_map(|event|
-                // request_builder is fallible but the places it can fail are emitting
-                // `Error` and `DroppedEvent` internal events appropriately so no need to here.
-                future::ready(request_builder.build_request(event)))
-            .into_driver(service)
-            .run()
-            .await
+        let requests = input.filter_map(move |event|
+            // request_builder is fallible but the places it can fail are emitting
+            // `Error` and `DroppedEvent` internal events appropriately so no need to here.
+            future::ready(request_builder.build_request(event)));
+
+        match self.transactions {
+            Some(transactions) => {
+                if let Err(error) = transactions.service.init().await {
+                    error!(message = "Failed to initialize the Kafka transactions.", %error);
+                    return Err(());
+                }
+
+                // Only one transaction can be open at a time.
+                let service = ConcurrencyLimit::new(transactions.service, 1);
+                requests
+                    .batched(
+                        transactions
+                            .batch_settings
+                            .into_item_size_config(|request: &KafkaRequest| request.body.len()),
+                    )
+                    .map(KafkaTransaction::new)
+                    .into_driver(service)
+                    .run()
+                    .await
+            }
+            None => {
+                // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
+                let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
+                if ordered {
+                    requests.into_driver(OrderedLanes::new(service)).run().await
+                } else {
+                    requests.into_driver(service).run().await
+                }
+            }
+        }
     }
 }
 
@@ -66,6 +66,12 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            preserve_order_by: None,
+            idempotent: false,
+            transactional_id: None,
+            transaction_timeout_ms: Duration::from_millis(60000),
+            transactional_source: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -121,6 +127,12 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            preserve_order_by: None,
+            idempotent: false,
+            transactional_id: None,
+            transaction_timeout_ms: Duration::from_millis(60000),
+            transactional_source: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -231,6 +243,74 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_transactional_produce() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(100);
+        let config = KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch,
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            preserve_order_by: None,
+            idempotent: false,
+            transactional_id: Some(format!("vector-{}", random_string(10))),
+            transaction_timeout_ms: Duration::from_millis(60000),
+            transactional_source: None,
+            schema_registry: None,
+            acknowledgements: Default::default(),
+        };
+
+        let num_events = 1000;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+        let sink = KafkaSink::new(config).unwrap();
+        VectorSink::from_event_streamsink(sink)
+            .run(events)
+            .await
+            .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // Only the committed messages are read.
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", kafka_address(9091).as_str());
+        client_config.set("group.id", &random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 && out.len() < input.len() {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+        assert_eq!(out, input);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -252,6 +332,12 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            preserve_order_by: None,
+            idempotent: false,
+            transactional_id: None,
+            transaction_timeout_ms: Duration::from_millis(60000),
+            transactional_source: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -48,6 +48,16 @@ impl CompressionConfigAdapter {
             CompressionConfigAdapter::Extended(_) => Some("snappy"),
         }
     }
+
+    /// Gets whether push requests are sent as Protocol Buffers, which is how Loki expects the
+    /// Snappy compressed ones.
+    pub const fn is_protobuf(self) -> bool {
+        matches!(
+            self,
+            CompressionConfigAdapter::Extended(ExtendedCompression::Snappy)
+                | CompressionConfigAdapter::Original(Compression::Snappy)
+        )
+    }
 }
 
 impl Default for CompressionConfigAdapter {
@@ -123,6 +133,9 @@ pub struct LokiConfig {
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
 
+    #[configurable(derived)]
+    pub reorder_buffer: Option<ReorderBufferConfig>,
+
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
@@ -200,6 +213,37 @@ pub enum OutOfOrderAction {
     Accept,
 }
 
+/// Configuration for reordering events before they are sent to Loki.
+///
+/// Events are held for a short time, so that events arriving slightly out of order can be sorted
+/// before being sent, instead of being handled according to `out_of_order_action`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ReorderBufferConfig {
+    /// The maximum amount of time, in milliseconds, that events are held to be reordered.
+    ///
+    /// The events of a stream are sent, sorted by their timestamp, once the oldest of them has
+    /// been held for this long. Events arriving afterwards with an earlier timestamp than the
+    /// events already sent are handled according to `out_of_order_action`.
+    #[serde(default = "default_reorder_max_delay_ms")]
+    pub max_delay_ms: u64,
+
+    /// The maximum size, in bytes, of the events held across all streams.
+    ///
+    /// When exceeded, the events of the stream held for the longest time are sent immediately.
+    #[serde(default = "default_reorder_max_bytes")]
+    pub max_bytes: usize,
+}
+
+const fn default_reorder_max_delay_ms() -> u64 {
+    1000
+}
+
+const fn default_reorder_max_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
 impl GenerateConfig for LokiConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -229,6 +273,10 @@ impl SinkConfig for LokiConfig {
             return Err("`labels` must include at least one label.".into());
         }
 
+        if let CompressionConfigAdapter::Original(Compression::Zstd(_)) = self.compression {
+            return Err("Loki doesn't support `zstd` compression.".into());
+        }
+
         for label in self.labels.keys() {
             if !valid_label_name(label) {
                 return Err(format!("Invalid label name {:?}", label.get_ref()).into());
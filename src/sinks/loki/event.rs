@@ -174,6 +174,18 @@ pub struct LokiRecord {
     pub finalizers: EventFinalizers,
 }
 
+impl LokiRecord {
+    pub fn stream_key(&self) -> StreamKey {
+        // Loki identifies a stream by its set of labels, whatever their order.
+        let mut labels = self.labels.clone();
+        labels.sort();
+        StreamKey {
+            partition: self.partition.clone(),
+            labels,
+        }
+    }
+}
+
 impl ByteSizeOf for LokiRecord {
     fn allocated_bytes(&self) -> usize {
         self.partition.allocated_bytes()
@@ -208,6 +220,13 @@ pub struct PartitionKey {
     pub tenant_id: Option<String>,
 }
 
+/// The stream of a record, made of its tenant and its labels.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct StreamKey {
+    pub partition: PartitionKey,
+    pub labels: Labels,
+}
+
 impl ByteSizeOf for PartitionKey {
     fn allocated_bytes(&self) -> usize {
         self.tenant_id
@@ -1,12 +1,13 @@
 use std::convert::TryFrom;
 use std::sync::Arc;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Duration, Utc};
+use codecs::LogfmtSerializer;
 use futures::stream;
 use lookup::owned_value_path;
+use tokio_util::codec::Encoder as _;
 use value::{kind::Collection, Kind};
-use vector_common::encode_logfmt;
 use vector_core::{
     config::LogNamespace,
     event::{BatchNotifier, BatchStatus, Event, LogEvent},
@@ -278,8 +279,11 @@ async fn logfmt() {
     let (_, outputs) = fetch_stream(stream.to_string(), "default").await;
     assert_eq!(lines.len(), outputs.len());
     for (i, output) in outputs.iter().enumerate() {
-        let expected_logfmt = encode_logfmt::encode_value(lines[i].as_log().value()).unwrap();
-        assert_eq!(output, &expected_logfmt);
+        let mut expected_logfmt = BytesMut::new();
+        LogfmtSerializer::new(Default::default())
+            .encode(lines[i].clone(), &mut expected_logfmt)
+            .unwrap();
+        assert_eq!(output.as_bytes(), &expected_logfmt[..]);
     }
 }
 
@@ -16,6 +16,7 @@ mod healthcheck;
 #[cfg(feature = "loki-integration-tests")]
 #[cfg(test)]
 mod integration_tests;
+mod reorder;
 mod service;
 mod sink;
 #[cfg(test)]
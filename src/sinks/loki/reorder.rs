@@ -0,0 +1,258 @@
+//! Reordering of records before they are sent to Loki.
+//!
+//! Records are held per stream for a bounded amount of time and memory, and released sorted by
+//! their timestamp.
+use std::{collections::HashMap, time::Duration};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use tokio::time::{sleep_until, Instant};
+use vector_core::ByteSizeOf;
+
+use super::{
+    config::ReorderBufferConfig,
+    event::{LokiRecord, StreamKey},
+};
+
+/// The records of a stream waiting to be released.
+struct PendingRecords {
+    first_received: Instant,
+    records: Vec<LokiRecord>,
+    byte_size: usize,
+}
+
+pub(super) struct ReorderBuffer {
+    max_delay: Duration,
+    max_bytes: usize,
+    byte_size: usize,
+    pending: HashMap<StreamKey, PendingRecords>,
+}
+
+impl ReorderBuffer {
+    pub(super) fn new(config: &ReorderBufferConfig) -> Self {
+        Self {
+            max_delay: Duration::from_millis(config.max_delay_ms),
+            max_bytes: config.max_bytes,
+            byte_size: 0,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Holds the record, returning the records that must be released to stay within the memory
+    /// limit.
+    fn insert(&mut self, record: LokiRecord, now: Instant) -> Vec<LokiRecord> {
+        let byte_size = record.size_of();
+        let pending = self
+            .pending
+            .entry(record.stream_key())
+            .or_insert_with(|| PendingRecords {
+                first_received: now,
+                records: Vec::new(),
+                byte_size: 0,
+            });
+        pending.records.push(record);
+        pending.byte_size += byte_size;
+        self.byte_size += byte_size;
+
+        let mut released = Vec::new();
+        while self.byte_size > self.max_bytes {
+            let oldest = self
+                .pending
+                .iter()
+                .min_by_key(|(_, pending)| pending.first_received)
+                .map(|(stream, _)| stream.clone())
+                .expect("buffer can't be empty while holding bytes");
+            self.release(&oldest, &mut released);
+        }
+        released
+    }
+
+    /// The time at which the longest held stream must be released.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.pending
+            .values()
+            .map(|pending| pending.first_received + self.max_delay)
+            .min()
+    }
+
+    /// Releases the streams that have been held for the maximum delay.
+    fn release_expired(&mut self, now: Instant) -> Vec<LokiRecord> {
+        let expired = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.first_received + self.max_delay <= now)
+            .map(|(stream, _)| stream.clone())
+            .collect::<Vec<_>>();
+
+        let mut released = Vec::new();
+        for stream in expired {
+            self.release(&stream, &mut released);
+        }
+        released
+    }
+
+    fn release_all(&mut self) -> Vec<LokiRecord> {
+        let streams = self.pending.keys().cloned().collect::<Vec<_>>();
+
+        let mut released = Vec::new();
+        for stream in streams {
+            self.release(&stream, &mut released);
+        }
+        released
+    }
+
+    fn release(&mut self, stream: &StreamKey, released: &mut Vec<LokiRecord>) {
+        if let Some(mut pending) = self.pending.remove(stream) {
+            self.byte_size -= pending.byte_size;
+            // The sort is stable, so records with the same timestamp keep their order.
+            pending.records.sort_by_key(|record| record.event.timestamp);
+            released.extend(pending.records);
+        }
+    }
+}
+
+/// Passes the records through the buffer, releasing them sorted by their timestamp.
+///
+/// Any records still held when the input ends are released.
+pub(super) fn reorder<'a>(
+    input: impl Stream<Item = LokiRecord> + Send + 'a,
+    mut buffer: ReorderBuffer,
+) -> impl Stream<Item = LokiRecord> + Send + 'a {
+    stream! {
+        tokio::pin!(input);
+        loop {
+            let deadline = buffer.next_deadline();
+            let released = tokio::select! {
+                maybe_record = input.next() => match maybe_record {
+                    Some(record) => buffer.insert(record, Instant::now()),
+                    None => break,
+                },
+                _ = sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    buffer.release_expired(Instant::now())
+                }
+            };
+
+            for record in released {
+                yield record;
+            }
+        }
+
+        for record in buffer.release_all() {
+            yield record;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::sinks::loki::event::{LokiEvent, PartitionKey};
+
+    fn record(tenant_id: &str, timestamp: i64) -> LokiRecord {
+        labeled_record(tenant_id, &[("agent", "vector")], timestamp)
+    }
+
+    fn labeled_record(tenant_id: &str, labels: &[(&str, &str)], timestamp: i64) -> LokiRecord {
+        LokiRecord {
+            partition: PartitionKey {
+                tenant_id: Some(tenant_id.to_owned()),
+            },
+            labels: labels
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+                .collect(),
+            event: LokiEvent {
+                timestamp,
+                event: Bytes::from_static(b"hello"),
+            },
+            finalizers: Default::default(),
+        }
+    }
+
+    fn timestamps(records: &[LokiRecord]) -> Vec<i64> {
+        records
+            .iter()
+            .map(|record| record.event.timestamp)
+            .collect()
+    }
+
+    fn buffer(max_bytes: usize) -> ReorderBuffer {
+        ReorderBuffer::new(&ReorderBufferConfig {
+            max_delay_ms: 1000,
+            max_bytes,
+        })
+    }
+
+    #[test]
+    fn releases_expired_streams_sorted() {
+        let mut buffer = buffer(usize::MAX);
+        let start = Instant::now();
+
+        assert!(buffer.insert(record("a", 3), start).is_empty());
+        assert!(buffer.insert(record("a", 1), start).is_empty());
+        let later = start + Duration::from_millis(500);
+        assert!(buffer.insert(record("b", 5), later).is_empty());
+        assert!(buffer.insert(record("a", 2), later).is_empty());
+
+        assert_eq!(buffer.next_deadline(), Some(start + Duration::from_secs(1)));
+        assert!(buffer
+            .release_expired(start + Duration::from_millis(999))
+            .is_empty());
+
+        let released = buffer.release_expired(start + Duration::from_secs(1));
+        assert_eq!(timestamps(&released), vec![1, 2, 3]);
+
+        assert_eq!(timestamps(&buffer.release_all()), vec![5]);
+        assert_eq!(buffer.next_deadline(), None);
+        assert_eq!(buffer.byte_size, 0);
+    }
+
+    #[test]
+    fn releases_oldest_stream_when_full() {
+        let max_bytes = record("a", 0).size_of() * 2;
+        let mut buffer = buffer(max_bytes);
+        let start = Instant::now();
+
+        assert!(buffer.insert(record("a", 2), start).is_empty());
+        let later = start + Duration::from_millis(1);
+        assert!(buffer.insert(record("b", 1), later).is_empty());
+
+        let released = buffer.insert(record("b", 0), later);
+        assert_eq!(timestamps(&released), vec![2]);
+        assert_eq!(timestamps(&buffer.release_all()), vec![0, 1]);
+    }
+
+    #[test]
+    fn holds_streams_of_a_tenant_apart() {
+        let mut buffer = buffer(usize::MAX);
+        let start = Instant::now();
+
+        let app = [("app", "api"), ("env", "prod")];
+        let worker = [("app", "worker"), ("env", "prod")];
+        assert!(buffer
+            .insert(labeled_record("a", &app, 3), start)
+            .is_empty());
+        let later = start + Duration::from_millis(500);
+        assert!(buffer
+            .insert(labeled_record("a", &worker, 1), later)
+            .is_empty());
+        // The order of the labels doesn't make another stream.
+        let reordered = [("env", "prod"), ("app", "api")];
+        assert!(buffer
+            .insert(labeled_record("a", &reordered, 2), later)
+            .is_empty());
+
+        let released = buffer.release_expired(start + Duration::from_secs(1));
+        assert_eq!(timestamps(&released), vec![2, 3]);
+        assert_eq!(timestamps(&buffer.release_all()), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn reorders_stream() {
+        let input = futures::stream::iter(vec![record("a", 2), record("a", 3), record("a", 1)]);
+        let output = reorder(input, buffer(usize::MAX)).collect::<Vec<_>>().await;
+        assert_eq!(timestamps(&output), vec![1, 2, 3]);
+    }
+}
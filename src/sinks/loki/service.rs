@@ -13,7 +13,7 @@ use vector_core::{
     stream::DriverResponse,
 };
 
-use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression};
+use crate::sinks::loki::config::CompressionConfigAdapter;
 use crate::{
     http::{Auth, HttpClient},
     sinks::util::{retries::RetryLogic, UriSerde},
@@ -119,11 +119,10 @@ impl Service<LokiRequest> for LokiService {
     }
 
     fn call(&mut self, request: LokiRequest) -> Self::Future {
-        let content_type = match request.compression {
-            CompressionConfigAdapter::Original(_) => "application/json",
-            CompressionConfigAdapter::Extended(ExtendedCompression::Snappy) => {
-                "application/x-protobuf"
-            }
+        let content_type = if request.compression.is_protobuf() {
+            "application/x-protobuf"
+        } else {
+            "application/json"
         };
         let mut req = http::Request::post(&self.endpoint.uri).header("Content-Type", content_type);
 
@@ -16,21 +16,21 @@ use vector_core::{
 };
 
 use super::{
-    config::{LokiConfig, OutOfOrderAction},
-    event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
+    config::{LokiConfig, OutOfOrderAction, ReorderBufferConfig},
+    event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey, StreamKey},
+    reorder::{reorder, ReorderBuffer},
     service::{LokiRequest, LokiRetryLogic, LokiService},
 };
 use crate::sinks::loki::event::LokiBatchEncoding;
 use crate::sinks::{
-    loki::config::{CompressionConfigAdapter, ExtendedCompression},
-    util::metadata::RequestMetadataBuilder,
+    loki::config::CompressionConfigAdapter, util::metadata::RequestMetadataBuilder,
 };
 use crate::{
     codecs::{Encoder, Transformer},
     http::{get_http_scheme_from_uri, HttpClient},
     internal_events::{
         LokiEventUnlabeled, LokiOutOfOrderEventDropped, LokiOutOfOrderEventRewritten,
-        SinkRequestBuildError, TemplateRenderingError,
+        LokiReorderWindowExceeded, SinkRequestBuildError, TemplateRenderingError,
     },
     sinks::util::{
         builder::SinkBuilderExt,
@@ -111,6 +111,8 @@ impl RequestBuilder<(PartitionKey, Vec<LokiRecord>)> for LokiRequestBuilder {
 
     fn compression(&self) -> Compression {
         match self.compression {
+            // Protocol Buffers payloads are compressed by their encoding.
+            compression if compression.is_protobuf() => Compression::None,
             CompressionConfigAdapter::Original(compression) => compression,
             CompressionConfigAdapter::Extended(_) => Compression::None,
         }
@@ -283,7 +285,7 @@ impl ByteSizeOf for FilteredRecord {
 }
 
 struct RecordFilter {
-    timestamps: HashMap<PartitionKey, i64>,
+    timestamps: HashMap<StreamKey, i64>,
     out_of_order_action: OutOfOrderAction,
 }
 
@@ -298,7 +300,8 @@ impl RecordFilter {
 
 impl RecordFilter {
     pub fn filter_record(&mut self, mut record: LokiRecord) -> Option<FilteredRecord> {
-        if let Some(latest) = self.timestamps.get_mut(&record.partition) {
+        let stream = record.stream_key();
+        if let Some(latest) = self.timestamps.get_mut(&stream) {
             if record.event.timestamp < *latest {
                 match self.out_of_order_action {
                     OutOfOrderAction::Drop => None,
@@ -313,8 +316,7 @@ impl RecordFilter {
                 Some(FilteredRecord::valid(record))
             }
         } else {
-            self.timestamps
-                .insert(record.partition.clone(), record.event.timestamp);
+            self.timestamps.insert(stream, record.event.timestamp);
             Some(FilteredRecord::valid(record))
         }
     }
@@ -325,6 +327,7 @@ pub struct LokiSink {
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
+    reorder_buffer: Option<ReorderBufferConfig>,
     service: Svc<LokiService, LokiRetryLogic>,
     protocol: &'static str,
 }
@@ -364,11 +367,10 @@ impl LokiSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
-        let batch_encoder = match config.compression {
-            CompressionConfigAdapter::Original(_) => LokiBatchEncoder(LokiBatchEncoding::Json),
-            CompressionConfigAdapter::Extended(ExtendedCompression::Snappy) => {
-                LokiBatchEncoder(LokiBatchEncoding::Protobuf)
-            }
+        let batch_encoder = if config.compression.is_protobuf() {
+            LokiBatchEncoder(LokiBatchEncoding::Protobuf)
+        } else {
+            LokiBatchEncoder(LokiBatchEncoding::Json)
         };
 
         Ok(Self {
@@ -386,6 +388,7 @@ impl LokiSink {
             },
             batch_settings: config.batch.into_batcher_settings()?,
             out_of_order_action: config.out_of_order_action,
+            reorder_buffer: config.reorder_buffer,
             service,
             protocol,
         })
@@ -404,12 +407,22 @@ impl LokiSink {
             }
         };
 
-        input
+        let records = input
             .map(|event| encoder.encode_event(event))
-            .filter_map(|event| async { event })
+            .filter_map(|event| async { event });
+
+        // With a reorder buffer, the only records left out of order are those arriving after the
+        // records they should precede were already released.
+        let reordering = self.reorder_buffer.is_some();
+        let records = match &self.reorder_buffer {
+            Some(config) => reorder(records, ReorderBuffer::new(config)).boxed(),
+            None => records.boxed(),
+        };
+
+        records
             .map(|record| filter.filter_record(record))
             .batched_partitioned(RecordPartitioner::default(), self.batch_settings)
-            .filter_map(|(partition, batch)| async {
+            .filter_map(|(partition, batch)| async move {
                 if let Some(partition) = partition {
                     let mut count: usize = 0;
                     let result = batch
@@ -426,6 +439,9 @@ impl LokiSink {
                         emit!(LokiOutOfOrderEventRewritten { count });
                     }
                     Some((partition, result))
+                } else if reordering {
+                    emit!(LokiReorderWindowExceeded { count: batch.len() });
+                    None
                 } else {
                     emit!(LokiOutOfOrderEventDropped { count: batch.len() });
                     None
@@ -473,7 +489,9 @@ mod tests {
     use futures::stream::StreamExt;
     use vector_core::event::{Event, LogEvent, Value};
 
-    use super::{EventEncoder, KeyPartitioner, RecordFilter};
+    use bytes::Bytes;
+
+    use super::{EventEncoder, KeyPartitioner, LokiEvent, LokiRecord, PartitionKey, RecordFilter};
     use crate::{
         codecs::Encoder, config::log_schema, sinks::loki::config::OutOfOrderAction,
         template::Template, test_util::random_lines,
@@ -723,4 +741,22 @@ mod tests {
         }
         assert_eq!(result.len(), 17);
     }
+
+    #[test]
+    fn filters_out_of_order_records_per_stream() {
+        let record = |app: &str, timestamp: i64| LokiRecord {
+            partition: PartitionKey { tenant_id: None },
+            labels: vec![("app".to_owned(), app.to_owned())],
+            event: LokiEvent {
+                timestamp,
+                event: Bytes::from_static(b"hello"),
+            },
+            finalizers: Default::default(),
+        };
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop);
+
+        assert!(filter.filter_record(record("api", 2)).is_some());
+        assert!(filter.filter_record(record("worker", 1)).is_some());
+        assert!(filter.filter_record(record("api", 1)).is_none());
+    }
 }
@@ -7,6 +7,8 @@ pub mod util;
 
 #[cfg(feature = "sinks-amqp")]
 pub mod amqp;
+#[cfg(feature = "sinks-arrow_flight")]
+pub mod arrow_flight;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
 pub mod aws_cloudwatch_logs;
 #[cfg(feature = "sinks-aws_cloudwatch_metrics")]
@@ -24,8 +26,14 @@ pub mod aws_sqs;
 pub mod axiom;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_blob;
-#[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sources-azure_event_hubs"
+))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
@@ -47,6 +55,10 @@ pub mod datadog;
 pub mod datadog_archives;
 #[cfg(feature = "sinks-elasticsearch")]
 pub mod elasticsearch;
+#[cfg(feature = "sinks-email")]
+pub mod email;
+#[cfg(feature = "sinks-exec")]
+pub mod exec;
 #[cfg(feature = "sinks-file")]
 pub mod file;
 #[cfg(feature = "sinks-gcp")]
@@ -67,12 +79,18 @@ pub mod kafka;
 pub mod loki;
 #[cfg(feature = "sinks-mezmo")]
 pub mod mezmo;
+#[cfg(feature = "sinks-mqtt")]
+pub mod mqtt;
 #[cfg(feature = "sinks-nats")]
 pub mod nats;
+#[cfg(feature = "sinks-nats_jetstream")]
+pub mod nats_jetstream;
 #[cfg(feature = "sinks-new_relic")]
 pub mod new_relic;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod opendal_common;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
 #[cfg(feature = "sinks-prometheus")]
@@ -88,12 +106,18 @@ pub mod redis;
 pub mod s3_common;
 #[cfg(feature = "sinks-sematext")]
 pub mod sematext;
+#[cfg(feature = "sinks-sentry")]
+pub mod sentry;
 #[cfg(feature = "sinks-socket")]
 pub mod socket;
 #[cfg(feature = "sinks-splunk_hec")]
 pub mod splunk_hec;
+#[cfg(feature = "sinks-sql")]
+pub mod sql;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
+#[cfg(feature = "sinks-syslog")]
+pub mod syslog;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
 #[cfg(feature = "sinks-webhdfs")]
@@ -143,6 +167,11 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "AMQP"))]
     Amqp(amqp::AmqpSinkConfig),
 
+    /// Deliver log events as Arrow record batches to an Arrow Flight server.
+    #[cfg(feature = "sinks-arrow_flight")]
+    #[configurable(metadata(docs::label = "Arrow Flight"))]
+    ArrowFlight(arrow_flight::ArrowFlightSinkConfig),
+
     /// Publish log events to AWS CloudWatch Logs.
     #[cfg(feature = "sinks-aws_cloudwatch_logs")]
     #[configurable(metadata(docs::label = "AWS CloudWatch Logs"))]
@@ -183,6 +212,11 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Azure Blob Storage"))]
     AzureBlob(azure_blob::AzureBlobSinkConfig),
 
+    /// Publish events to Azure Event Hubs.
+    #[cfg(feature = "sinks-azure_event_hubs")]
+    #[configurable(metadata(docs::label = "Azure Event Hubs"))]
+    AzureEventHubs(azure_event_hubs::AzureEventHubsSinkConfig),
+
     /// Publish log events to the Azure Monitor Logs service.
     #[cfg(feature = "sinks-azure_monitor_logs")]
     #[configurable(metadata(docs::label = "Azure Monitor Logs"))]
@@ -237,11 +271,26 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Elasticsearch"))]
     Elasticsearch(elasticsearch::ElasticsearchConfig),
 
+    /// Send digests of events by email.
+    #[cfg(feature = "sinks-email")]
+    #[configurable(metadata(docs::label = "Email"))]
+    Email(email::EmailSinkConfig),
+
+    /// Pipe observability events to the standard input of a long-running command.
+    #[cfg(feature = "sinks-exec")]
+    #[configurable(metadata(docs::label = "Exec"))]
+    Exec(exec::ExecSinkConfig),
+
     /// Output observability events into files.
     #[cfg(feature = "sinks-file")]
     #[configurable(metadata(docs::label = "File"))]
     File(file::FileSinkConfig),
 
+    /// Store events of the Unified Data Model in Google Chronicle.
+    #[cfg(feature = "sinks-gcp")]
+    #[configurable(metadata(docs::label = "GCP Chronicle UDM"))]
+    GcpChronicleUdm(gcp::chronicle_udm::ChronicleUdmConfig),
+
     /// Store unstructured log events in Google Chronicle.
     #[cfg(feature = "sinks-gcp")]
     #[configurable(metadata(docs::label = "GCP Chronicle Unstructured"))]
@@ -322,16 +371,31 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Loki"))]
     Loki(loki::LokiConfig),
 
+    /// Publish observability data to topics of an MQTT broker.
+    #[cfg(feature = "sinks-mqtt")]
+    #[configurable(metadata(docs::label = "MQTT"))]
+    Mqtt(mqtt::MqttSinkConfig),
+
     /// Publish observability data to subjects on the NATS messaging system.
     #[cfg(feature = "sinks-nats")]
     #[configurable(metadata(docs::label = "NATS"))]
     Nats(self::nats::NatsSinkConfig),
 
+    /// Publish observability data to the streams of the NATS JetStream persistence layer.
+    #[cfg(feature = "sinks-nats_jetstream")]
+    #[configurable(metadata(docs::label = "NATS JetStream"))]
+    NatsJetStream(self::nats_jetstream::NatsJetStreamSinkConfig),
+
     /// Deliver events to New Relic.
     #[cfg(feature = "sinks-new_relic")]
     #[configurable(metadata(docs::label = "New Relic"))]
     NewRelic(new_relic::NewRelicConfig),
 
+    /// Deliver log and metric data to an OpenTelemetry collector over OTLP/gRPC.
+    #[cfg(feature = "sinks-opentelemetry")]
+    #[configurable(metadata(docs::label = "OpenTelemetry"))]
+    Opentelemetry(opentelemetry::OpentelemetryConfig),
+
     /// Deliver log events to Papertrail from SolarWinds.
     #[cfg(feature = "sinks-papertrail")]
     #[configurable(metadata(docs::label = "Papertrail"))]
@@ -367,6 +431,11 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Sematext Metrics"))]
     SematextMetrics(sematext::metrics::SematextMetricsConfig),
 
+    /// Send error events to Sentry.
+    #[cfg(feature = "sinks-sentry")]
+    #[configurable(metadata(docs::label = "Sentry"))]
+    Sentry(sentry::SentryConfig),
+
     /// Deliver logs to a remote socket endpoint.
     #[cfg(feature = "sinks-socket")]
     #[configurable(metadata(docs::label = "Socket"))]
@@ -382,11 +451,21 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Splunk HEC Metrics"))]
     SplunkHecMetrics(splunk_hec::metrics::config::HecMetricsSinkConfig),
 
+    /// Insert log data into the tables of a PostgreSQL or MySQL database.
+    #[cfg(feature = "sinks-sql")]
+    #[configurable(metadata(docs::label = "SQL"))]
+    Sql(sql::SqlSinkConfig),
+
     /// Deliver metric data to a StatsD aggregator.
     #[cfg(feature = "sinks-statsd")]
     #[configurable(metadata(docs::label = "Statsd"))]
     Statsd(statsd::StatsdSinkConfig),
 
+    /// Deliver log events to a syslog server.
+    #[cfg(feature = "sinks-syslog")]
+    #[configurable(metadata(docs::label = "Syslog"))]
+    Syslog(syslog::SyslogSinkConfig),
+
     /// Test (adaptive concurrency).
     #[cfg(all(test, feature = "sources-demo_logs"))]
     #[configurable(metadata(docs::label = ""))]
@@ -434,6 +513,8 @@ impl NamedComponent for Sinks {
         match self {
             #[cfg(feature = "sinks-amqp")]
             Self::Amqp(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-arrow_flight")]
+            Self::ArrowFlight(config) => config.get_component_name(),
             #[cfg(feature = "sinks-aws_cloudwatch_logs")]
             Self::AwsCloudwatchLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-aws_cloudwatch_metrics")]
@@ -450,6 +531,8 @@ impl NamedComponent for Sinks {
             Self::Axiom(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_blob")]
             Self::AzureBlob(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-azure_event_hubs")]
+            Self::AzureEventHubs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_monitor_logs")]
             Self::AzureMonitorLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-blackhole")]
@@ -472,9 +555,15 @@ impl NamedComponent for Sinks {
             Self::DatadogTraces(config) => config.get_component_name(),
             #[cfg(feature = "sinks-elasticsearch")]
             Self::Elasticsearch(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-email")]
+            Self::Email(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-exec")]
+            Self::Exec(config) => config.get_component_name(),
             #[cfg(feature = "sinks-file")]
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
+            Self::GcpChronicleUdm(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-gcp")]
             Self::GcpChronicleUnstructured(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpStackdriverLogs(config) => config.get_component_name(),
@@ -506,10 +595,16 @@ impl NamedComponent for Sinks {
             Self::Logdna(config) => config.get_component_name(),
             #[cfg(feature = "sinks-loki")]
             Self::Loki(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-mqtt")]
+            Self::Mqtt(config) => config.get_component_name(),
             #[cfg(feature = "sinks-nats")]
             Self::Nats(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-nats_jetstream")]
+            Self::NatsJetStream(config) => config.get_component_name(),
             #[cfg(feature = "sinks-new_relic")]
             Self::NewRelic(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-opentelemetry")]
+            Self::Opentelemetry(config) => config.get_component_name(),
             #[cfg(feature = "sinks-papertrail")]
             Self::Papertrail(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
@@ -524,14 +619,20 @@ impl NamedComponent for Sinks {
             Self::SematextLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-sematext")]
             Self::SematextMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-sentry")]
+            Self::Sentry(config) => config.get_component_name(),
             #[cfg(feature = "sinks-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sinks-splunk_hec")]
             Self::SplunkHecLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-splunk_hec")]
             Self::SplunkHecMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-sql")]
+            Self::Sql(config) => config.get_component_name(),
             #[cfg(feature = "sinks-statsd")]
             Self::Statsd(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-syslog")]
+            Self::Syslog(config) => config.get_component_name(),
             #[cfg(all(test, feature = "sources-demo_logs"))]
             Self::TestArc(config) => config.get_component_name(),
             #[cfg(test)]
@@ -0,0 +1,220 @@
+//! `MQTT` sink.
+//! Publishes events to an MQTT 3.1.1 or MQTT 5 broker.
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Encoder as _;
+use tracing::Instrument;
+use vector_common::internal_event::{
+    ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle, Output, Protocol,
+};
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{EstimatedJsonEncodedSizeOf, Event, EventStatus, Finalizable},
+    internal_events::{MqttConnectionError, MqttEventSendError, TemplateRenderingError},
+    mqtt::{MqttClient, MqttConfigError, MqttConnectionConfig, MqttEvent, MqttEventLoop, MqttQos},
+    sinks::util::StreamSink,
+    template::{Template, TemplateParseError},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid encoding: {}", source))]
+    Encoding {
+        source: codecs::encoding::BuildError,
+    },
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("MQTT Config Error: {}", source))]
+    Config { source: MqttConfigError },
+}
+
+/// Configuration for the `mqtt` sink.
+#[configurable_component(sink("mqtt"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+
+    /// The topic to publish events to.
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "{{ host }}/logs", docs::examples = "gateway/logs"))]
+    topic: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    qos: MqttQos,
+
+    /// Whether the broker retains the last event published to the topic, for new subscribers.
+    #[serde(default)]
+    retain: bool,
+
+    #[configurable(derived)]
+    encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            port = 1883
+            topic = "vector"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self.clone())?;
+        let connection = self.connection.clone();
+        let healthcheck = async move { connection.healthcheck().await }.boxed();
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+pub struct MqttSink {
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    client: MqttClient,
+    eventloop: Option<MqttEventLoop>,
+    topic: Template,
+    qos: MqttQos,
+    retain: bool,
+}
+
+impl MqttSink {
+    fn new(config: MqttSinkConfig) -> Result<Self, BuildError> {
+        let (client, eventloop) = config.connection.client(false).context(ConfigSnafu)?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build().context(EncodingSnafu)?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(MqttSink {
+            transformer,
+            encoder,
+            client,
+            eventloop: Some(eventloop),
+            topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
+            qos: config.qos,
+            retain: config.retain,
+        })
+    }
+}
+
+/// Drives the connection, so that the published events are sent to the broker, until the client
+/// disconnects.
+async fn run_eventloop(mut eventloop: MqttEventLoop) {
+    loop {
+        match eventloop.poll().await {
+            Ok(MqttEvent::Disconnected) => break,
+            Ok(_) => {}
+            Err(error) => {
+                emit!(MqttConnectionError { error });
+                // Polling again connects again, give the broker some time first.
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let bytes_sent = register!(BytesSent::from(Protocol::TCP));
+        let events_sent = register!(EventsSent::from(Output(None)));
+        let eventloop = tokio::spawn(
+            run_eventloop(self.eventloop.take().expect("sink is only run once")).in_current_span(),
+        );
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            let topic = match self.topic.render_string(&event) {
+                Ok(topic) => topic,
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("topic"),
+                        drop_event: true,
+                    });
+                    finalizers.update_status(EventStatus::Rejected);
+                    continue;
+                }
+            };
+
+            self.transformer.transform(&mut event);
+
+            let event_byte_size = event.estimated_json_encoded_size_of();
+
+            let mut bytes = BytesMut::new();
+            if self.encoder.encode(event, &mut bytes).is_err() {
+                // Error is handled by `Encoder`.
+                finalizers.update_status(EventStatus::Rejected);
+                continue;
+            }
+            let byte_size = bytes.len();
+
+            match self
+                .client
+                .publish(topic, self.qos, self.retain, bytes.to_vec())
+                .await
+            {
+                Err(error) => {
+                    finalizers.update_status(EventStatus::Errored);
+
+                    emit!(MqttEventSendError { error });
+                }
+                Ok(_) => {
+                    finalizers.update_status(EventStatus::Delivered);
+
+                    events_sent.emit(CountByteSize(1, event_byte_size));
+                    bytes_sent.emit(ByteSize(byte_size));
+                }
+            }
+        }
+
+        // The disconnection is queued after the events published so far, so that these are sent
+        // before the event loop stops.
+        let _ = self.client.disconnect().await;
+        let _ = eventloop.await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+}
@@ -0,0 +1,351 @@
+use std::convert::TryFrom;
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use codecs::JsonSerializerConfig;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use nats::jetstream::{JetStream, PublishOptions};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Encoder as _;
+use vector_common::internal_event::{
+    ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle, Output, Protocol,
+};
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{EstimatedJsonEncodedSizeOf, Event, EventStatus, Finalizable},
+    internal_events::{NatsEventSendError, TemplateRenderingError},
+    nats::{connect_jetstream, from_tls_auth_config, NatsAuthConfig, NatsConfigError},
+    sinks::util::StreamSink,
+    template::{Template, TemplateParseError},
+    tls::TlsEnableableConfig,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid encoding: {}", source))]
+    Encoding {
+        source: codecs::encoding::BuildError,
+    },
+    #[snafu(display("invalid subject template: {}", source))]
+    SubjectTemplate { source: TemplateParseError },
+    #[snafu(display("invalid stream template: {}", source))]
+    StreamTemplate { source: TemplateParseError },
+    #[snafu(display("invalid message_id template: {}", source))]
+    MessageIdTemplate { source: TemplateParseError },
+    #[snafu(display("NATS Config Error: {}", source))]
+    Config { source: NatsConfigError },
+    #[snafu(display("NATS Connect Error: {}", source))]
+    Connect { source: std::io::Error },
+}
+
+/// Configuration for the `nats_jetstream` sink.
+#[configurable_component(sink("nats_jetstream"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamSinkConfig {
+    #[configurable(derived)]
+    encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+
+    /// A NATS [name][nats_connection_name] assigned to the NATS connection.
+    ///
+    /// [nats_connection_name]: https://docs.nats.io/using-nats/developer/connecting/name
+    #[serde(default = "default_name")]
+    #[configurable(metadata(docs::examples = "foo"))]
+    connection_name: String,
+
+    /// The NATS [subject][nats_subject] to publish messages to.
+    ///
+    /// The subject must be bound to a JetStream stream, for the messages to be stored and their
+    /// publishing acknowledged.
+    ///
+    /// [nats_subject]: https://docs.nats.io/nats-concepts/subjects
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(
+        docs::examples = "{{ host }}",
+        docs::examples = "events.{{ service }}",
+        docs::examples = "time.us.east"
+    ))]
+    subject: String,
+
+    /// The name of the [stream][nats_stream] the messages are expected to be stored in.
+    ///
+    /// The messages whose subject is bound to another stream are rejected by the server. By
+    /// default, they're stored in whichever stream their subject is bound to.
+    ///
+    /// [nats_stream]: https://docs.nats.io/nats-concepts/jetstream/streams
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "events", docs::examples = "{{ service }}"))]
+    stream: Option<String>,
+
+    /// The ID of the messages, which the server uses to [deduplicate][nats_deduplication] them.
+    ///
+    /// The messages published again with the same ID, such as when they're retried, are only
+    /// stored once within the duplicate window of their stream.
+    ///
+    /// [nats_deduplication]: https://docs.nats.io/using-nats/developer/develop_jetstream/model_deep_dive#message-deduplication
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "{{ id }}"))]
+    message_id: Option<String>,
+
+    /// The NATS [URL][nats_url] to connect to.
+    ///
+    /// The URL must take the form of `nats://server:port`.
+    /// If the port is not specified it defaults to 4222.
+    ///
+    /// [nats_url]: https://docs.nats.io/using-nats/developer/connecting#nats-url
+    #[configurable(metadata(docs::examples = "nats://demo.nats.io"))]
+    #[configurable(metadata(docs::examples = "nats://127.0.0.1:4242"))]
+    url: String,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<NatsAuthConfig>,
+}
+
+fn default_name() -> String {
+    String::from("vector")
+}
+
+impl GenerateConfig for NatsJetStreamSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            acknowledgements: Default::default(),
+            auth: None,
+            connection_name: "vector".into(),
+            encoding: JsonSerializerConfig::default().into(),
+            message_id: None,
+            stream: None,
+            subject: "events.vector".into(),
+            tls: None,
+            url: "nats://127.0.0.1:4222".into(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for NatsJetStreamSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = NatsJetStreamSink::new(self.clone()).await?;
+        let healthcheck = healthcheck(self.clone()).boxed();
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl TryFrom<&NatsJetStreamSinkConfig> for nats::Options {
+    type Error = NatsConfigError;
+
+    fn try_from(config: &NatsJetStreamSinkConfig) -> Result<Self, Self::Error> {
+        from_tls_auth_config(&config.connection_name, &config.auth, &config.tls)
+    }
+}
+
+impl NatsJetStreamSinkConfig {
+    async fn connect(&self) -> Result<JetStream, BuildError> {
+        let options: nats::Options = self.try_into().context(ConfigSnafu)?;
+
+        connect_jetstream(options, self.url.clone())
+            .await
+            .context(ConnectSnafu)
+    }
+}
+
+async fn healthcheck(config: NatsJetStreamSinkConfig) -> crate::Result<()> {
+    let jetstream = config.connect().await?;
+    // Fails unless JetStream is enabled for the account.
+    tokio::task::spawn_blocking(move || jetstream.account_info())
+        .await?
+        .map(|_| ())
+        .map_err(Into::into)
+}
+
+pub struct NatsJetStreamSink {
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    jetstream: JetStream,
+    templates: PublishTemplates,
+}
+
+/// The templates of the subject and publishing options of the events.
+struct PublishTemplates {
+    subject: Template,
+    stream: Option<Template>,
+    message_id: Option<Template>,
+}
+
+impl NatsJetStreamSink {
+    async fn new(config: NatsJetStreamSinkConfig) -> Result<Self, BuildError> {
+        let jetstream = config.connect().await?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build().context(EncodingSnafu)?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(NatsJetStreamSink {
+            jetstream,
+            transformer,
+            encoder,
+            templates: PublishTemplates {
+                subject: Template::try_from(config.subject).context(SubjectTemplateSnafu)?,
+                stream: config
+                    .stream
+                    .map(Template::try_from)
+                    .transpose()
+                    .context(StreamTemplateSnafu)?,
+                message_id: config
+                    .message_id
+                    .map(Template::try_from)
+                    .transpose()
+                    .context(MessageIdTemplateSnafu)?,
+            },
+        })
+    }
+}
+
+impl PublishTemplates {
+    /// Renders the subject and the publishing options of an event.
+    fn render(
+        &self,
+        event: &Event,
+    ) -> Result<(String, PublishOptions), TemplateRenderingError<'static>> {
+        let render = |template: &Template, field| {
+            template
+                .render_string(event)
+                .map_err(|error| TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                })
+        };
+
+        let subject = render(&self.subject, "subject")?;
+        let options = PublishOptions {
+            expected_stream: self
+                .stream
+                .as_ref()
+                .map(|stream| render(stream, "stream"))
+                .transpose()?,
+            id: self
+                .message_id
+                .as_ref()
+                .map(|message_id| render(message_id, "message_id"))
+                .transpose()?,
+            ..Default::default()
+        };
+        Ok((subject, options))
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for NatsJetStreamSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let bytes_sent = register!(BytesSent::from(Protocol::TCP));
+        let events_sent = register!(EventsSent::from(Output(None)));
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            let (subject, options) = match self.templates.render(&event) {
+                Ok(rendered) => rendered,
+                Err(error) => {
+                    emit!(error);
+                    finalizers.update_status(EventStatus::Rejected);
+                    continue;
+                }
+            };
+
+            self.transformer.transform(&mut event);
+
+            let event_byte_size = event.estimated_json_encoded_size_of();
+
+            let mut bytes = BytesMut::new();
+            if self.encoder.encode(event, &mut bytes).is_err() {
+                // Error is handled by `Encoder`.
+                finalizers.update_status(EventStatus::Rejected);
+                continue;
+            }
+
+            // The message is only acknowledged once the server stored it.
+            let jetstream = self.jetstream.clone();
+            let byte_size = bytes.len();
+            let data = bytes.freeze();
+            let published = tokio::task::spawn_blocking(move || {
+                jetstream.publish_with_options(&subject, data, &options)
+            })
+            .await
+            .expect("NATS JetStream publish task panicked");
+
+            match published {
+                Err(error) => {
+                    finalizers.update_status(EventStatus::Errored);
+
+                    emit!(NatsEventSendError { error });
+                }
+                Ok(_) => {
+                    finalizers.update_status(EventStatus::Delivered);
+
+                    events_sent.emit(CountByteSize(1, event_byte_size));
+                    bytes_sent.emit(ByteSize(byte_size));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NatsJetStreamSinkConfig>();
+    }
+
+    #[test]
+    fn renders_subject_stream_and_message_id() {
+        let templates = PublishTemplates {
+            subject: Template::try_from("events.{{ service }}").unwrap(),
+            stream: Some(Template::try_from("{{ service }}").unwrap()),
+            message_id: Some(Template::try_from("{{ id }}").unwrap()),
+        };
+
+        let mut log = LogEvent::from("message");
+        log.insert("service", "api");
+        log.insert("id", "abc");
+        let (subject, options) = templates.render(&log.clone().into()).ok().unwrap();
+        assert_eq!(subject, "events.api");
+        assert_eq!(options.expected_stream.as_deref(), Some("api"));
+        assert_eq!(options.id.as_deref(), Some("abc"));
+
+        log.remove("id");
+        let error = templates.render(&log.into()).err().unwrap();
+        assert_eq!(error.field, Some("message_id"));
+    }
+}
@@ -0,0 +1,224 @@
+use http::{header::HeaderName, HeaderMap, HeaderValue};
+use indexmap::IndexMap;
+use opentelemetry_proto::proto::collector::logs::v1::ExportLogsServiceRequest;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    encoder::OpentelemetryEncoder,
+    service::{OpentelemetryResponse, OpentelemetryService},
+    sink::OpentelemetrySink,
+    OpentelemetrySinkError,
+};
+use crate::{
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext,
+        SinkHealthcheckOptions,
+    },
+    http::Auth,
+    sinks::{
+        util::{
+            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
+            ServiceBuilderExt, TowerRequestConfig,
+        },
+        vector::{new_client, with_default_scheme},
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink("opentelemetry"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    /// The OTLP/gRPC endpoint of the OpenTelemetry collector to send events to.
+    ///
+    /// Both IP address and hostname are accepted formats.
+    ///
+    /// The endpoint _must_ include a port.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otel-collector:4317"))]
+    endpoint: String,
+
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    compression: bool,
+
+    /// A list of custom headers to add to each request, such as API keys expected by the collector.
+    ///
+    /// Headers are sent as gRPC metadata.
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP request header and its value."
+    ))]
+    #[configurable(metadata(docs::examples = "example_headers()"))]
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    /// The buckets to use for converting [distribution][dist_metric_docs] metrics into OTLP
+    /// histograms.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default = "default_histogram_buckets")]
+    buckets: Vec<f64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn example_headers() -> IndexMap<String, String> {
+    IndexMap::from([("api-key".to_owned(), "${OTEL_API_KEY}".to_owned())])
+}
+
+pub(super) fn default_histogram_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:4317""#).unwrap()
+    }
+}
+
+impl OpentelemetryConfig {
+    /// Builds the headers sent with each request, including the authentication ones.
+    fn build_headers(&self) -> crate::Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        if let Some(auth) = &self.auth {
+            auth.apply_headers_map(&mut headers);
+        }
+        Ok(headers)
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let headers = self.build_headers()?;
+
+        let client = new_client(&tls, cx.proxy())?;
+
+        let healthcheck_uri = cx
+            .healthcheck
+            .uri
+            .clone()
+            .map(|uri| uri.uri)
+            .unwrap_or_else(|| uri.clone());
+        let healthcheck_service =
+            OpentelemetryService::new(client.clone(), healthcheck_uri, headers.clone(), false);
+        let healthcheck = healthcheck(healthcheck_service, cx.healthcheck);
+
+        let service = OpentelemetryService::new(client, uri, headers, self.compression);
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpentelemetrySink {
+            batch_settings,
+            encoder: OpentelemetryEncoder::new(self.buckets.clone()),
+            service,
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(healthcheck),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the collector accepts requests, by exporting an empty batch of logs.
+async fn healthcheck(
+    mut service: OpentelemetryService,
+    options: SinkHealthcheckOptions,
+) -> crate::Result<()> {
+    if !options.enabled {
+        return Ok(());
+    }
+
+    match service
+        .logs_client
+        .export(ExportLogsServiceRequest::default())
+        .await
+    {
+        Ok(_) => Ok(()),
+        // The collector is reachable, but isn't configured to receive logs.
+        Err(source) if source.code() == tonic::Code::Unimplemented => Ok(()),
+        Err(source) => Err(Box::new(OpentelemetrySinkError::Request { source })),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, err: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match err {
+            OpentelemetrySinkError::Request { source } => matches!(
+                source.code(),
+                // List taken from
+                //
+                // <https://github.com/open-telemetry/opentelemetry-proto/blob/v0.19.0/docs/specification.md#failures>
+                Cancelled
+                    | DeadlineExceeded
+                    | ResourceExhausted
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+            ),
+        }
+    }
+}
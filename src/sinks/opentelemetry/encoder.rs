@@ -0,0 +1,924 @@
+//! Conversion of Vector events to OTLP.
+//!
+//! Logs are expected in the shape produced by the `opentelemetry` source: the known OTLP fields
+//! (`resources`, `attributes`, `trace_id`, and so on) are mapped back to the log record, while
+//! any other field is sent as an attribute.
+//!
+//! Traces are expected in the shape shared by the `opentelemetry` and `datadog_agent` sources: the
+//! OTLP fields that Datadog spans have no equivalent for are read back from the span `meta`.
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use opentelemetry_proto::{
+    convert::{
+        span_kind_name, status_code_name, ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY,
+        HOST_NAME_ATTRIBUTE, LIBRARY_NAME_KEY, LIBRARY_VERSION_KEY, OBSERVED_TIMESTAMP_KEY,
+        OTEL_TRACE_ID_KEY, RESOURCE_KEY, SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY,
+        SPAN_KIND_KEY, STATUS_CODE_KEY, STATUS_DESCRIPTION_KEY, TOP_LEVEL_KEY, TRACE_ID_KEY,
+        TRACE_RESOURCE_FIELDS,
+    },
+    proto::{
+        collector::{
+            logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+            trace::v1::ExportTraceServiceRequest,
+        },
+        common::v1::{
+            any_value::Value as PBValue, AnyValue, ArrayValue, InstrumentationScope, KeyValue,
+            KeyValueList,
+        },
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+            AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric as PBMetric,
+            NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{span::SpanKind, status::StatusCode, ResourceSpans, ScopeSpans, Span, Status},
+    },
+};
+use value::Value;
+use vector_core::{
+    config::log_schema,
+    event::{
+        metric::{samples_to_buckets, Bucket, MetricSketch, Quantile},
+        LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
+};
+
+/// The quantiles reported for sketches, which OTLP has no equivalent for.
+const SKETCH_QUANTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryEncoder {
+    /// The buckets distributions are aggregated into.
+    buckets: Vec<f64>,
+}
+
+impl OpentelemetryEncoder {
+    pub const fn new(buckets: Vec<f64>) -> Self {
+        Self { buckets }
+    }
+
+    /// Encodes the logs, grouping the records by resource.
+    pub fn encode_logs(&self, logs: impl Iterator<Item = LogEvent>) -> ExportLogsServiceRequest {
+        let now = Utc::now();
+        let mut resource_logs: Vec<ResourceLogs> = Vec::new();
+
+        for log in logs {
+            let (resource, record) = encode_log(log, now);
+            let position = resource_logs
+                .iter()
+                .position(|resource_log| resource_log.resource == resource);
+            let scope_logs = match position {
+                Some(position) => &mut resource_logs[position].scope_logs[0],
+                None => {
+                    resource_logs.push(ResourceLogs {
+                        resource,
+                        scope_logs: vec![ScopeLogs::default()],
+                        schema_url: String::new(),
+                    });
+                    &mut resource_logs.last_mut().expect("just pushed").scope_logs[0]
+                }
+            };
+            scope_logs.log_records.push(record);
+        }
+
+        ExportLogsServiceRequest { resource_logs }
+    }
+
+    /// Encodes the metrics, each of which becomes a single data point.
+    pub fn encode_metrics(
+        &self,
+        metrics: impl Iterator<Item = Metric>,
+    ) -> ExportMetricsServiceRequest {
+        let now = Utc::now();
+        let metrics = metrics
+            .map(|metric| self.encode_metric(metric, now))
+            .collect();
+
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: None,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    /// Encodes the traces, grouping the spans by resource and instrumentation scope.
+    pub fn encode_traces(
+        &self,
+        traces: impl Iterator<Item = TraceEvent>,
+    ) -> ExportTraceServiceRequest {
+        let mut resource_spans: Vec<ResourceSpans> = Vec::new();
+
+        for trace in traces {
+            let (resource, spans) = encode_trace(trace);
+            let position = resource_spans
+                .iter()
+                .position(|resource_span| resource_span.resource == resource);
+            let entry = match position {
+                Some(position) => &mut resource_spans[position],
+                None => {
+                    resource_spans.push(ResourceSpans {
+                        resource,
+                        scope_spans: Vec::new(),
+                        schema_url: String::new(),
+                    });
+                    resource_spans.last_mut().expect("just pushed")
+                }
+            };
+            for (scope, span) in spans {
+                let position = entry
+                    .scope_spans
+                    .iter()
+                    .position(|scope_span| scope_span.scope == scope);
+                match position {
+                    Some(position) => entry.scope_spans[position].spans.push(span),
+                    None => entry.scope_spans.push(ScopeSpans {
+                        scope,
+                        spans: vec![span],
+                        schema_url: String::new(),
+                    }),
+                }
+            }
+        }
+
+        ExportTraceServiceRequest { resource_spans }
+    }
+
+    fn encode_metric(&self, metric: Metric, now: DateTime<Utc>) -> PBMetric {
+        let name = match metric.namespace() {
+            Some(namespace) => format!("{}.{}", namespace, metric.name()),
+            None => metric.name().to_owned(),
+        };
+        let attributes = metric
+            .tags()
+            .map(|tags| {
+                tags.iter_single()
+                    .map(|(key, value)| key_value(key.to_owned(), Value::from(value)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let timestamp = metric.timestamp().unwrap_or(now);
+        let time_unix_nano = timestamp_nanos(timestamp);
+        // Incremental metrics are reported over their interval, when known.
+        let start_time_unix_nano = match (metric.kind(), metric.interval_ms()) {
+            (MetricKind::Incremental, Some(interval_ms)) => {
+                time_unix_nano.saturating_sub(u64::from(interval_ms.get()) * 1_000_000)
+            }
+            _ => 0,
+        };
+        let temporality = match metric.kind() {
+            MetricKind::Incremental => AggregationTemporality::Delta,
+            MetricKind::Absolute => AggregationTemporality::Cumulative,
+        } as i32;
+
+        let number_point = |value| NumberDataPoint {
+            attributes: attributes.clone(),
+            start_time_unix_nano,
+            time_unix_nano,
+            value: Some(value),
+            ..Default::default()
+        };
+        let histogram_point = |buckets: &[Bucket], count, sum, min, max| {
+            let (explicit_bounds, bucket_counts) = histogram_buckets(buckets, count);
+            HistogramDataPoint {
+                attributes: attributes.clone(),
+                start_time_unix_nano,
+                time_unix_nano,
+                count,
+                sum: Some(sum),
+                bucket_counts,
+                explicit_bounds,
+                min,
+                max,
+                ..Default::default()
+            }
+        };
+        let summary_point = |quantiles: &[Quantile], count, sum| SummaryDataPoint {
+            attributes: attributes.clone(),
+            start_time_unix_nano,
+            time_unix_nano,
+            count,
+            sum,
+            quantile_values: quantiles
+                .iter()
+                .map(|quantile| ValueAtQuantile {
+                    quantile: quantile.quantile,
+                    value: quantile.value,
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        let data = match metric.value() {
+            MetricValue::Counter { value } => Data::Sum(Sum {
+                data_points: vec![number_point(number_data_point::Value::AsDouble(*value))],
+                aggregation_temporality: temporality,
+                is_monotonic: true,
+            }),
+            MetricValue::Gauge { value } => match metric.kind() {
+                MetricKind::Absolute => Data::Gauge(Gauge {
+                    data_points: vec![number_point(number_data_point::Value::AsDouble(*value))],
+                }),
+                // An incremental gauge is a change of its value, which can go both ways.
+                MetricKind::Incremental => Data::Sum(Sum {
+                    data_points: vec![number_point(number_data_point::Value::AsDouble(*value))],
+                    aggregation_temporality: temporality,
+                    is_monotonic: false,
+                }),
+            },
+            // Sets are reported as the number of distinct values they hold.
+            MetricValue::Set { values } => Data::Gauge(Gauge {
+                data_points: vec![number_point(number_data_point::Value::AsInt(
+                    values.len() as i64
+                ))],
+            }),
+            MetricValue::Distribution { samples, .. } => {
+                let (buckets, count, sum) = samples_to_buckets(samples, &self.buckets);
+                let min = samples.iter().map(|sample| sample.value).reduce(f64::min);
+                let max = samples.iter().map(|sample| sample.value).reduce(f64::max);
+                Data::Histogram(Histogram {
+                    data_points: vec![histogram_point(&buckets, count, sum, min, max)],
+                    aggregation_temporality: temporality,
+                })
+            }
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => Data::Histogram(Histogram {
+                data_points: vec![histogram_point(buckets, *count, *sum, None, None)],
+                aggregation_temporality: temporality,
+            }),
+            MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => Data::Summary(Summary {
+                data_points: vec![summary_point(quantiles, *count, *sum)],
+            }),
+            MetricValue::Sketch { sketch } => {
+                let MetricSketch::AgentDDSketch(sketch) = sketch;
+                let quantiles = SKETCH_QUANTILES
+                    .iter()
+                    .filter_map(|quantile| {
+                        sketch.quantile(*quantile).map(|value| Quantile {
+                            quantile: *quantile,
+                            value,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                Data::Summary(Summary {
+                    data_points: vec![summary_point(
+                        &quantiles,
+                        u64::from(sketch.count()),
+                        sketch.sum().unwrap_or_default(),
+                    )],
+                })
+            }
+        };
+
+        PBMetric {
+            name,
+            description: String::new(),
+            unit: String::new(),
+            data: Some(data),
+        }
+    }
+}
+
+/// Converts Vector buckets into OTLP explicit bounds and their counts.
+///
+/// OTLP histograms always have an overflow bucket for the values above the last bound, which
+/// holds the values not counted by any finite bucket.
+fn histogram_buckets(buckets: &[Bucket], count: u64) -> (Vec<f64>, Vec<u64>) {
+    let finite = buckets
+        .iter()
+        .filter(|bucket| bucket.upper_limit.is_finite())
+        .collect::<Vec<_>>();
+
+    let explicit_bounds = finite.iter().map(|bucket| bucket.upper_limit).collect();
+    let mut bucket_counts = finite.iter().map(|bucket| bucket.count).collect::<Vec<_>>();
+    let counted = bucket_counts.iter().sum::<u64>();
+    bucket_counts.push(count.saturating_sub(counted));
+
+    (explicit_bounds, bucket_counts)
+}
+
+fn encode_log(mut log: LogEvent, now: DateTime<Utc>) -> (Option<Resource>, LogRecord) {
+    let mut record = LogRecord {
+        observed_time_unix_nano: timestamp_nanos(now),
+        ..Default::default()
+    };
+
+    if log.as_map().is_none() {
+        // Logs that aren't objects only have a body.
+        record.body = Some(any_value(log.into_parts().0));
+        return (None, record);
+    }
+
+    if let Some(Value::Timestamp(timestamp)) = log.remove_timestamp() {
+        record.time_unix_nano = timestamp_nanos(timestamp);
+    }
+    record.body = log
+        .message_path()
+        .and_then(|path| log.remove(path.as_str()))
+        .map(any_value);
+
+    let mut fields = match log.into_parts().0 {
+        Value::Object(fields) => fields,
+        _ => unreachable!("checked to be an object"),
+    };
+
+    if let Some(Value::Timestamp(timestamp)) = fields.remove(OBSERVED_TIMESTAMP_KEY) {
+        record.observed_time_unix_nano = timestamp_nanos(timestamp);
+    }
+    if let Some(Value::Bytes(severity_text)) = fields.remove(SEVERITY_TEXT_KEY) {
+        record.severity_text = String::from_utf8_lossy(&severity_text).into_owned();
+    }
+    if let Some(Value::Integer(severity_number)) = fields.remove(SEVERITY_NUMBER_KEY) {
+        record.severity_number = i32::try_from(severity_number).unwrap_or_default();
+    }
+    if let Some(Value::Integer(flags)) = fields.remove(FLAGS_KEY) {
+        record.flags = u32::try_from(flags).unwrap_or_default();
+    }
+    if let Some(Value::Integer(count)) = fields.remove(DROPPED_ATTRIBUTES_COUNT_KEY) {
+        record.dropped_attributes_count = u32::try_from(count).unwrap_or_default();
+    }
+    for (key, id) in [
+        (TRACE_ID_KEY, &mut record.trace_id),
+        (SPAN_ID_KEY, &mut record.span_id),
+    ] {
+        // Ids that aren't hex encoded are kept as attributes, rather than being lost.
+        let decoded = match fields.get(key) {
+            Some(Value::Bytes(hex_id)) => hex::decode(hex_id).ok(),
+            _ => None,
+        };
+        if let Some(decoded) = decoded {
+            *id = decoded;
+            fields.remove(key);
+        }
+    }
+
+    let resource = match fields.remove(RESOURCE_KEY) {
+        Some(Value::Object(resources)) => Some(Resource {
+            attributes: key_values(resources),
+            dropped_attributes_count: 0,
+        }),
+        Some(resources) => {
+            fields.insert(RESOURCE_KEY.to_owned(), resources);
+            None
+        }
+        None => None,
+    };
+
+    let mut attributes = match fields.remove(ATTRIBUTES_KEY) {
+        Some(Value::Object(attributes)) => attributes,
+        Some(attributes) => BTreeMap::from([(ATTRIBUTES_KEY.to_owned(), attributes)]),
+        None => BTreeMap::new(),
+    };
+    // Any other field is sent as an attribute, so that nothing is lost.
+    attributes.extend(fields);
+    record.attributes = key_values(attributes);
+
+    (resource, record)
+}
+
+fn encode_trace(
+    trace: TraceEvent,
+) -> (Option<Resource>, Vec<(Option<InstrumentationScope>, Span)>) {
+    let mut fields = trace.into_parts().0;
+    let spans = match fields.remove("spans") {
+        Some(Value::Array(spans)) => spans,
+        _ => Vec::new(),
+    };
+
+    let mut resources = match fields.remove(RESOURCE_KEY) {
+        Some(Value::Object(resources)) => resources,
+        _ => BTreeMap::new(),
+    };
+    // Traces that weren't received over OTLP only have their resource in the Datadog fields.
+    let host = fields
+        .remove(log_schema().host_key())
+        .map(|host| (HOST_NAME_ATTRIBUTE, host));
+    let service = spans
+        .iter()
+        .find_map(|span| {
+            span.as_object()
+                .and_then(|span| span.get("service"))
+                .cloned()
+        })
+        .map(|service| ("service.name", service));
+    let known_fields = TRACE_RESOURCE_FIELDS
+        .iter()
+        .filter_map(|(field, attribute)| fields.remove(*field).map(|value| (*attribute, value)));
+    for (attribute, value) in known_fields.chain(host).chain(service) {
+        resources.entry(attribute.to_owned()).or_insert(value);
+    }
+    let resource = (!resources.is_empty()).then(|| Resource {
+        attributes: key_values(resources),
+        dropped_attributes_count: 0,
+    });
+
+    let spans = spans
+        .into_iter()
+        .filter_map(|span| match span {
+            Value::Object(span) => Some(encode_span(span)),
+            _ => None,
+        })
+        .collect();
+
+    (resource, spans)
+}
+
+fn encode_span(span: BTreeMap<String, Value>) -> (Option<InstrumentationScope>, Span) {
+    let integer = |key: &str| match span.get(key) {
+        Some(Value::Integer(value)) => *value,
+        _ => 0,
+    };
+    let object = |key: &str| match span.get(key) {
+        Some(Value::Object(fields)) => fields.clone(),
+        _ => BTreeMap::new(),
+    };
+    let mut meta = object("meta");
+
+    // The full trace id is only known for spans received over OTLP, the others have the lower
+    // 64 bits of it.
+    let trace_id = match meta.remove(OTEL_TRACE_ID_KEY) {
+        Some(Value::Bytes(hex_id)) => hex::decode(hex_id).ok().filter(|id| id.len() == 16),
+        _ => None,
+    }
+    .unwrap_or_else(|| [[0; 8], integer("trace_id").to_be_bytes()].concat());
+    let parent_span_id = match integer("parent_id") {
+        0 => Vec::new(),
+        id => id.to_be_bytes().to_vec(),
+    };
+    let start_time_unix_nano = match span.get("start") {
+        Some(Value::Timestamp(timestamp)) => timestamp_nanos(*timestamp),
+        _ => 0,
+    };
+    let duration = u64::try_from(integer("duration")).unwrap_or_default();
+
+    let kind = match take_string(&mut meta, SPAN_KIND_KEY) {
+        Some(kind) => [
+            SpanKind::Internal,
+            SpanKind::Server,
+            SpanKind::Client,
+            SpanKind::Producer,
+            SpanKind::Consumer,
+        ]
+        .into_iter()
+        .find(|candidate| span_kind_name(*candidate) == kind)
+        .unwrap_or(SpanKind::Unspecified),
+        None => SpanKind::Unspecified,
+    };
+    let code = take_string(&mut meta, STATUS_CODE_KEY)
+        .and_then(|code| {
+            [StatusCode::Unset, StatusCode::Ok, StatusCode::Error]
+                .into_iter()
+                .find(|candidate| status_code_name(*candidate) == code)
+        })
+        .unwrap_or(if integer("error") == 0 {
+            StatusCode::Unset
+        } else {
+            StatusCode::Error
+        });
+    let status = Status {
+        message: take_string(&mut meta, STATUS_DESCRIPTION_KEY).unwrap_or_default(),
+        code: code as i32,
+    };
+
+    let scope_name = take_string(&mut meta, LIBRARY_NAME_KEY);
+    let scope_version = take_string(&mut meta, LIBRARY_VERSION_KEY);
+    let scope = (scope_name.is_some() || scope_version.is_some()).then(|| InstrumentationScope {
+        name: scope_name.unwrap_or_default(),
+        version: scope_version.unwrap_or_default(),
+        ..Default::default()
+    });
+
+    // The resource is the most specific description of the operation for Datadog spans.
+    let name = ["resource", "name"]
+        .into_iter()
+        .filter_map(|key| span.get(key))
+        .map(|name| name.to_string_lossy().into_owned())
+        .find(|name| !name.is_empty())
+        .unwrap_or_default();
+
+    // Datadog marks the top-level spans, which OTLP tells apart from their parent.
+    let mut metrics = object("metrics");
+    metrics.remove(TOP_LEVEL_KEY);
+    let mut attributes = meta;
+    attributes.extend(metrics);
+
+    let encoded = Span {
+        trace_id,
+        span_id: integer("span_id").to_be_bytes().to_vec(),
+        parent_span_id,
+        name,
+        kind: kind as i32,
+        start_time_unix_nano,
+        end_time_unix_nano: start_time_unix_nano.saturating_add(duration),
+        attributes: key_values(attributes),
+        status: Some(status),
+        ..Default::default()
+    };
+
+    (scope, encoded)
+}
+
+fn take_string(fields: &mut BTreeMap<String, Value>, key: &str) -> Option<String> {
+    fields
+        .remove(key)
+        .map(|value| value.to_string_lossy().into_owned())
+}
+
+fn timestamp_nanos(timestamp: DateTime<Utc>) -> u64 {
+    u64::try_from(timestamp.timestamp_nanos()).unwrap_or_default()
+}
+
+fn key_value(key: String, value: Value) -> KeyValue {
+    KeyValue {
+        key,
+        value: Some(any_value(value)),
+    }
+}
+
+fn key_values(fields: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    fields
+        .into_iter()
+        .map(|(key, value)| key_value(key, value))
+        .collect()
+}
+
+fn any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => PBValue::StringValue(String::from_utf8_lossy(&bytes).into_owned()),
+        Value::Regex(regex) => PBValue::StringValue(regex.to_string()),
+        Value::Integer(value) => PBValue::IntValue(value),
+        Value::Float(value) => PBValue::DoubleValue(value.into_inner()),
+        Value::Boolean(value) => PBValue::BoolValue(value),
+        Value::Timestamp(timestamp) => {
+            PBValue::StringValue(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        Value::Object(fields) => PBValue::KvlistValue(KeyValueList {
+            values: key_values(fields),
+        }),
+        Value::Array(values) => PBValue::ArrayValue(ArrayValue {
+            values: values.into_iter().map(any_value).collect(),
+        }),
+        Value::Null => return AnyValue { value: None },
+    };
+
+    AnyValue { value: Some(value) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use chrono::TimeZone;
+    use ordered_float::NotNan;
+    use vector_core::event::metric::Sample;
+
+    use super::*;
+
+    fn encoder() -> OpentelemetryEncoder {
+        OpentelemetryEncoder::new(vec![1.0, 2.0])
+    }
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(PBValue::StringValue(value.to_owned())),
+        })
+    }
+
+    fn encode_metric(metric: Metric) -> Data {
+        let request = encoder().encode_metrics(std::iter::once(metric));
+        request.resource_metrics[0].scope_metrics[0].metrics[0]
+            .data
+            .clone()
+            .unwrap()
+    }
+
+    #[test]
+    fn encodes_logs_grouped_by_resource() {
+        let timestamp = Utc.timestamp_nanos(1_000);
+        let mut first = LogEvent::from("first");
+        first.insert("timestamp", timestamp);
+        first.insert("resources.\"service.name\"", "api");
+        first.insert("attributes.user", "alice");
+        first.insert("trace_id", "0123456789abcdef0123456789abcdef");
+        first.insert("span_id", "not-hex");
+        first.insert("severity_number", 9);
+        first.insert("host", "localhost");
+
+        let mut second = LogEvent::from("second");
+        second.insert("resources.\"service.name\"", "api");
+
+        let third = LogEvent::from("third");
+
+        let request = encoder().encode_logs(vec![first, second, third].into_iter());
+        assert_eq!(request.resource_logs.len(), 2);
+
+        let api = &request.resource_logs[0];
+        assert_eq!(
+            api.resource.as_ref().unwrap().attributes,
+            vec![KeyValue {
+                key: "service.name".to_owned(),
+                value: string_value("api"),
+            }]
+        );
+        let records = &api.scope_logs[0].log_records;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].body, string_value("first"));
+        assert_eq!(records[0].time_unix_nano, 1_000);
+        assert_eq!(records[0].severity_number, 9);
+        assert_eq!(records[0].trace_id.len(), 16);
+        assert!(records[0].span_id.is_empty());
+        assert_eq!(
+            records[0]
+                .attributes
+                .iter()
+                .map(|attribute| attribute.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["host", "span_id", "user"]
+        );
+        assert_eq!(records[1].body, string_value("second"));
+
+        assert_eq!(request.resource_logs[1].resource, None);
+    }
+
+    #[test]
+    fn encodes_traces() {
+        let span = |span_id: i64, meta: Value| {
+            Value::from(BTreeMap::from([
+                ("service".to_owned(), Value::from("api")),
+                ("name".to_owned(), Value::from("http.request")),
+                ("resource".to_owned(), Value::from("GET /users")),
+                ("trace_id".to_owned(), Value::from(10_i64)),
+                ("span_id".to_owned(), Value::from(span_id)),
+                ("parent_id".to_owned(), Value::from(0_i64)),
+                ("start".to_owned(), Value::from(Utc.timestamp_nanos(1_000))),
+                ("duration".to_owned(), Value::from(500_i64)),
+                ("error".to_owned(), Value::from(1_i64)),
+                ("meta".to_owned(), meta),
+                (
+                    "metrics".to_owned(),
+                    Value::from(BTreeMap::from([
+                        (
+                            "_top_level".to_owned(),
+                            Value::Float(NotNan::new(1.0).unwrap()),
+                        ),
+                        (
+                            "http.status_code".to_owned(),
+                            Value::Float(NotNan::new(500.0).unwrap()),
+                        ),
+                    ])),
+                ),
+            ]))
+        };
+        let mut trace = TraceEvent::default();
+        trace.insert("env", "prod");
+        trace.insert(
+            "spans",
+            vec![
+                span(
+                    1,
+                    Value::from(BTreeMap::from([
+                        (
+                            "otel.trace_id".to_owned(),
+                            Value::from("4ac52aadf321c2e531db005df08792f5"),
+                        ),
+                        ("otel.library.name".to_owned(), Value::from("tracer")),
+                        ("otel.status_code".to_owned(), Value::from("Ok")),
+                        ("span.kind".to_owned(), Value::from("server")),
+                        ("user".to_owned(), Value::from("alice")),
+                    ])),
+                ),
+                span(2, Value::from(BTreeMap::<String, Value>::new())),
+            ],
+        );
+
+        let request = encoder().encode_traces(std::iter::once(trace));
+        assert_eq!(request.resource_spans.len(), 1);
+
+        let resource_spans = &request.resource_spans[0];
+        assert_eq!(
+            resource_spans.resource.as_ref().unwrap().attributes,
+            vec![
+                KeyValue {
+                    key: "deployment.environment".to_owned(),
+                    value: string_value("prod"),
+                },
+                KeyValue {
+                    key: "service.name".to_owned(),
+                    value: string_value("api"),
+                },
+            ]
+        );
+        let scopes = &resource_spans.scope_spans;
+        assert_eq!(scopes.len(), 2);
+        assert_eq!(scopes[0].scope.as_ref().unwrap().name, "tracer");
+        assert_eq!(scopes[1].scope, None);
+
+        let otlp = &scopes[0].spans[0];
+        assert_eq!(
+            otlp.trace_id,
+            hex::decode("4ac52aadf321c2e531db005df08792f5").unwrap()
+        );
+        assert_eq!(otlp.span_id, 1_i64.to_be_bytes().to_vec());
+        assert!(otlp.parent_span_id.is_empty());
+        assert_eq!(otlp.name, "GET /users");
+        assert_eq!(otlp.kind, SpanKind::Server as i32);
+        assert_eq!(otlp.start_time_unix_nano, 1_000);
+        assert_eq!(otlp.end_time_unix_nano, 1_500);
+        assert_eq!(otlp.status.as_ref().unwrap().code, StatusCode::Ok as i32);
+        assert_eq!(
+            otlp.attributes
+                .iter()
+                .map(|attribute| attribute.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["http.status_code", "user"]
+        );
+
+        let datadog = &scopes[1].spans[0];
+        assert_eq!(datadog.trace_id, [[0; 8], 10_i64.to_be_bytes()].concat());
+        assert_eq!(datadog.kind, SpanKind::Unspecified as i32);
+        assert_eq!(
+            datadog.status.as_ref().unwrap().code,
+            StatusCode::Error as i32
+        );
+    }
+
+    #[test]
+    fn encodes_counters_as_sums() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        )
+        .with_namespace(Some("http"))
+        .with_tags(Some(
+            [("method".to_owned(), "GET".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+
+        let request = encoder().encode_metrics(std::iter::once(metric));
+        let metric = &request.resource_metrics[0].scope_metrics[0].metrics[0];
+        assert_eq!(metric.name, "http.requests");
+        match metric.data.as_ref().unwrap() {
+            Data::Sum(sum) => {
+                assert!(sum.is_monotonic);
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::Delta as i32
+                );
+                assert_eq!(
+                    sum.data_points[0].value,
+                    Some(number_data_point::Value::AsDouble(2.0))
+                );
+                assert_eq!(sum.data_points[0].attributes[0].key, "method");
+            }
+            data => panic!("unexpected data: {:?}", data),
+        }
+    }
+
+    #[test]
+    fn encodes_gauges_and_sets() {
+        let gauge = Metric::new(
+            "memory",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        );
+        assert!(matches!(encode_metric(gauge), Data::Gauge(_)));
+
+        let gauge = Metric::new(
+            "memory",
+            MetricKind::Incremental,
+            MetricValue::Gauge { value: -1.5 },
+        );
+        assert!(matches!(encode_metric(gauge), Data::Sum(sum) if !sum.is_monotonic));
+
+        let set = Metric::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: BTreeSet::from(["alice".to_owned(), "bob".to_owned()]),
+            },
+        );
+        match encode_metric(set) {
+            Data::Gauge(gauge) => assert_eq!(
+                gauge.data_points[0].value,
+                Some(number_data_point::Value::AsInt(2))
+            ),
+            data => panic!("unexpected data: {:?}", data),
+        }
+    }
+
+    #[test]
+    fn encodes_histograms() {
+        let distribution = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vec![
+                    Sample {
+                        value: 0.5,
+                        rate: 1,
+                    },
+                    Sample {
+                        value: 1.5,
+                        rate: 2,
+                    },
+                    Sample {
+                        value: 3.0,
+                        rate: 1,
+                    },
+                ],
+                statistic: vector_core::event::StatisticKind::Histogram,
+            },
+        );
+        match encode_metric(distribution) {
+            Data::Histogram(histogram) => {
+                let point = &histogram.data_points[0];
+                assert_eq!(point.explicit_bounds, vec![1.0, 2.0]);
+                assert_eq!(point.bucket_counts, vec![1, 2, 1]);
+                assert_eq!(point.count, 4);
+                assert_eq!(point.sum, Some(6.5));
+                assert_eq!(point.min, Some(0.5));
+                assert_eq!(point.max, Some(3.0));
+            }
+            data => panic!("unexpected data: {:?}", data),
+        }
+
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 3,
+                    },
+                    Bucket {
+                        upper_limit: f64::INFINITY,
+                        count: 1,
+                    },
+                ],
+                count: 4,
+                sum: 5.0,
+            },
+        );
+        match encode_metric(histogram) {
+            Data::Histogram(histogram) => {
+                assert_eq!(
+                    histogram.aggregation_temporality,
+                    AggregationTemporality::Cumulative as i32
+                );
+                let point = &histogram.data_points[0];
+                assert_eq!(point.explicit_bounds, vec![1.0]);
+                assert_eq!(point.bucket_counts, vec![3, 1]);
+            }
+            data => panic!("unexpected data: {:?}", data),
+        }
+    }
+
+    #[test]
+    fn encodes_summaries() {
+        let summary = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedSummary {
+                quantiles: vec![Quantile {
+                    quantile: 0.5,
+                    value: 1.0,
+                }],
+                count: 2,
+                sum: 3.0,
+            },
+        );
+        match encode_metric(summary) {
+            Data::Summary(summary) => {
+                let point = &summary.data_points[0];
+                assert_eq!(point.count, 2);
+                assert_eq!(point.sum, 3.0);
+                assert_eq!(
+                    point.quantile_values,
+                    vec![ValueAtQuantile {
+                        quantile: 0.5,
+                        value: 1.0,
+                    }]
+                );
+            }
+            data => panic!("unexpected data: {:?}", data),
+        }
+    }
+}
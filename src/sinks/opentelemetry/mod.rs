@@ -0,0 +1,21 @@
+//! The `opentelemetry` sink.
+//!
+//! Encodes logs, metrics, and traces to OTLP and exports them to an OpenTelemetry collector over gRPC.
+use snafu::Snafu;
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::OpentelemetryConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+}
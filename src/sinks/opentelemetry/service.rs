@@ -0,0 +1,216 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use http::{HeaderMap, Uri};
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+};
+use prost::Message;
+use tonic::{body::BoxBody, IntoRequest};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+
+use super::OpentelemetrySinkError;
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::SocksConnector,
+    internal_events::EndpointBytesSent,
+    sinks::util::uri,
+    Error,
+};
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryService {
+    pub logs_client: LogsServiceClient<HyperSvc>,
+    pub metrics_client: MetricsServiceClient<HyperSvc>,
+    pub traces_client: TraceServiceClient<HyperSvc>,
+    pub protocol: String,
+    pub endpoint: String,
+}
+
+pub struct OpentelemetryResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.events_count, self.events_byte_size)
+    }
+}
+
+/// The OTLP export request for a batch of events of the same type.
+#[derive(Clone, Debug)]
+pub enum OpentelemetryPayload {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl OpentelemetryPayload {
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OpentelemetryRequest {
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+    pub payload: OpentelemetryPayload,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for OpentelemetryRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+impl OpentelemetryService {
+    pub fn new(
+        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>,
+        uri: Uri,
+        headers: HeaderMap,
+        compression: bool,
+    ) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        let hyper_svc = HyperSvc {
+            uri,
+            headers: Arc::new(headers),
+            client: hyper_client,
+        };
+        let mut logs_client = LogsServiceClient::new(hyper_svc.clone());
+        let mut metrics_client = MetricsServiceClient::new(hyper_svc.clone());
+        let mut traces_client = TraceServiceClient::new(hyper_svc);
+
+        if compression {
+            logs_client = logs_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
+            metrics_client =
+                metrics_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
+            traces_client = traces_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
+        }
+        Self {
+            logs_client,
+            metrics_client,
+            traces_client,
+            protocol,
+            endpoint,
+        }
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness check of the clients is done through the `export()` call happening inside
+        // `call()`, like in the `vector` sink.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let mut service = self.clone();
+        let byte_size = request.payload.encoded_len();
+        let events_count = request.get_metadata().event_count();
+        let events_byte_size = request.get_metadata().events_byte_size();
+
+        let future = async move {
+            let result = match request.payload {
+                OpentelemetryPayload::Logs(payload) => service
+                    .logs_client
+                    .export(payload.into_request())
+                    .await
+                    .map(|_response| ()),
+                OpentelemetryPayload::Metrics(payload) => service
+                    .metrics_client
+                    .export(payload.into_request())
+                    .await
+                    .map(|_response| ()),
+                OpentelemetryPayload::Traces(payload) => service
+                    .traces_client
+                    .export(payload.into_request())
+                    .await
+                    .map(|_response| ()),
+            };
+
+            match result {
+                Ok(()) => {
+                    emit!(EndpointBytesSent {
+                        byte_size,
+                        protocol: &service.protocol,
+                        endpoint: &service.endpoint,
+                    });
+                    Ok(OpentelemetryResponse {
+                        events_count,
+                        events_byte_size,
+                    })
+                }
+                Err(source) => Err(OpentelemetrySinkError::Request { source }.into()),
+            }
+        };
+
+        Box::pin(future)
+    }
+}
+
+/// Sends the gRPC requests to the configured endpoint, with the configured headers.
+#[derive(Clone, Debug)]
+pub struct HyperSvc {
+    uri: Uri,
+    headers: Arc<HeaderMap>,
+    client: hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>,
+}
+
+impl Service<hyper::Request<BoxBody>> for HyperSvc {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+        let uri = Uri::builder()
+            .scheme(self.uri.scheme().unwrap().clone())
+            .authority(self.uri.authority().unwrap().clone())
+            .path_and_query(req.uri().path_and_query().unwrap().clone())
+            .build()
+            .unwrap();
+
+        *req.uri_mut() = uri;
+        for (name, value) in self.headers.iter() {
+            req.headers_mut().insert(name, value.clone());
+        }
+
+        Box::pin(self.client.request(req))
+    }
+}
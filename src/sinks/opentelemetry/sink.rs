@@ -0,0 +1,106 @@
+use std::{fmt, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    partition::Partitioner,
+    stream::{BatcherSettings, DriverResponse},
+};
+
+use super::{
+    encoder::OpentelemetryEncoder,
+    service::{OpentelemetryPayload, OpentelemetryRequest},
+};
+use crate::{
+    event::{Event, Finalizable},
+    sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt, StreamSink},
+};
+
+/// The OTLP signals, each of which is exported through its own service.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Signal {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+/// Partitions events by the signal they are exported as.
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => Signal::Logs,
+            Event::Metric(_) => Signal::Metrics,
+            Event::Trace(_) => Signal::Traces,
+        }
+    }
+}
+
+pub struct OpentelemetrySink<S> {
+    pub batch_settings: BatcherSettings,
+    pub encoder: OpentelemetryEncoder,
+    pub service: S,
+}
+
+impl<S> OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoder = self.encoder;
+
+        input
+            .batched_partitioned(SignalPartitioner, self.batch_settings)
+            .map(move |(signal, mut events)| {
+                let finalizers = events.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&events);
+
+                let payload = match signal {
+                    Signal::Logs => OpentelemetryPayload::Logs(
+                        encoder.encode_logs(events.into_iter().filter_map(Event::try_into_log)),
+                    ),
+                    Signal::Metrics => OpentelemetryPayload::Metrics(
+                        encoder
+                            .encode_metrics(events.into_iter().filter_map(Event::try_into_metric)),
+                    ),
+                    Signal::Traces => OpentelemetryPayload::Traces(
+                        encoder.encode_traces(events.into_iter().filter_map(Event::try_into_trace)),
+                    ),
+                };
+
+                // Batches are never empty, so requests always contain at least one resource.
+                let bytes_len = NonZeroUsize::new(payload.encoded_len())
+                    .expect("payload should never be zero length");
+
+                OpentelemetryRequest {
+                    finalizers,
+                    metadata: builder.with_request_size(bytes_len),
+                    payload,
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
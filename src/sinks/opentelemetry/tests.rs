@@ -0,0 +1,201 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{channel::mpsc, stream, StreamExt};
+use http::request::Parts;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+    metrics::v1::ExportMetricsServiceRequest,
+    trace::v1::ExportTraceServiceRequest,
+};
+use prost::Message;
+use vector_core::event::{
+    BatchNotifier, BatchStatus, Metric, MetricKind, MetricValue, TraceEvent, Value,
+};
+
+use super::*;
+use crate::{
+    config::{SinkConfig as _, SinkContext},
+    event::Event,
+    sinks::util::test::build_test_server_generic,
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr, random_lines_with_stream,
+    },
+};
+
+// one byte for the compression flag plus four bytes for the length
+const GRPC_HEADER_SIZE: usize = 5;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpentelemetryConfig>();
+}
+
+fn config(addr: std::net::SocketAddr) -> OpentelemetryConfig {
+    toml::from_str(&format!(
+        r#"
+        endpoint = "http://{}/"
+
+        [headers]
+        api-key = "secret"
+        "#,
+        addr
+    ))
+    .unwrap()
+}
+
+fn ok_response() -> hyper::Response<hyper::Body> {
+    hyper::Response::builder()
+        .header("grpc-status", "0") // OK
+        .header("content-type", "application/grpc")
+        .body(hyper::Body::from(encode_body(ExportLogsServiceResponse {})))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn exports_logs_metrics_and_traces() {
+    let in_addr = next_addr();
+    let cx = SinkContext::new_test();
+    let (sink, _) = config(in_addr).build(cx).await.unwrap();
+    let (rx, trigger, server) = build_test_server_generic(in_addr, ok_response);
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (input_lines, logs) = random_lines_with_stream(8, 5, Some(batch));
+    let metric = Event::from(Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    ));
+    let mut trace = TraceEvent::default();
+    trace.insert(
+        "spans",
+        vec![Value::from(std::collections::BTreeMap::from([(
+            "name".to_owned(),
+            Value::from("request"),
+        )]))],
+    );
+    let events = logs.chain(stream::iter(vec![metric.into(), trace.into()]));
+
+    run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = rx.collect::<Vec<(Parts, Bytes)>>().await;
+    assert_eq!(requests.len(), 3);
+
+    let mut output_lines = Vec::new();
+    let mut metric_names = Vec::new();
+    let mut span_names = Vec::new();
+    for (parts, body) in requests {
+        assert_eq!(
+            parts.headers.get("api-key").unwrap().to_str().unwrap(),
+            "secret"
+        );
+        let body = body.slice(GRPC_HEADER_SIZE..);
+
+        match parts.uri.path() {
+            "/opentelemetry.proto.collector.logs.v1.LogsService/Export" => {
+                let request = ExportLogsServiceRequest::decode(body).unwrap();
+                for record in request
+                    .resource_logs
+                    .into_iter()
+                    .flat_map(|resource_logs| resource_logs.scope_logs)
+                    .flat_map(|scope_logs| scope_logs.log_records)
+                {
+                    match record.body.and_then(|body| body.value) {
+                        Some(
+                            opentelemetry_proto::proto::common::v1::any_value::Value::StringValue(
+                                line,
+                            ),
+                        ) => output_lines.push(line),
+                        body => panic!("unexpected body: {:?}", body),
+                    }
+                }
+            }
+            "/opentelemetry.proto.collector.metrics.v1.MetricsService/Export" => {
+                let request = ExportMetricsServiceRequest::decode(body).unwrap();
+                metric_names.extend(
+                    request
+                        .resource_metrics
+                        .into_iter()
+                        .flat_map(|resource_metrics| resource_metrics.scope_metrics)
+                        .flat_map(|scope_metrics| scope_metrics.metrics)
+                        .map(|metric| metric.name),
+                );
+            }
+            "/opentelemetry.proto.collector.trace.v1.TraceService/Export" => {
+                let request = ExportTraceServiceRequest::decode(body).unwrap();
+                span_names.extend(
+                    request
+                        .resource_spans
+                        .into_iter()
+                        .flat_map(|resource_spans| resource_spans.scope_spans)
+                        .flat_map(|scope_spans| scope_spans.spans)
+                        .map(|span| span.name),
+                );
+            }
+            path => panic!("unexpected path: {}", path),
+        }
+    }
+
+    assert_eq!(input_lines, output_lines);
+    assert_eq!(metric_names, vec!["requests".to_owned()]);
+    assert_eq!(span_names, vec!["request".to_owned()]);
+}
+
+#[tokio::test]
+async fn rejects_on_permanent_error() {
+    let in_addr = next_addr();
+    let cx = SinkContext::new_test();
+    let (sink, _) = config(in_addr).build(cx).await.unwrap();
+    let (_rx, trigger, server) = build_test_server_generic(in_addr, move || {
+        hyper::Response::builder()
+            .header("grpc-status", "3") // invalid argument
+            .header("content-type", "application/grpc")
+            .body(tonic::body::empty_body())
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (_, events) = random_lines_with_stream(8, 5, Some(batch));
+
+    sink.run(events).await.expect("Running sink failed");
+
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+// taken from <https://github.com/hyperium/tonic/blob/5aa8ae1fec27377cd4c2a41d309945d7e38087d0/examples/src/grpc-web/client.rs#L45-L75>
+fn encode_body<T>(msg: T) -> Bytes
+where
+    T: prost::Message,
+{
+    let mut buf = BytesMut::with_capacity(1024);
+
+    // first skip past the header
+    // cannot write it yet since we don't know the size of the
+    // encoded message
+    buf.reserve(GRPC_HEADER_SIZE);
+    unsafe {
+        buf.advance_mut(GRPC_HEADER_SIZE);
+    }
+
+    // write the message
+    msg.encode(&mut buf).unwrap();
+
+    // now we know the size of encoded message and can write the
+    // header
+    let len = buf.len() - GRPC_HEADER_SIZE;
+    {
+        let mut buf = &mut buf[..GRPC_HEADER_SIZE];
+
+        // compression flag, 0 means "no compression"
+        buf.put_u8(0);
+
+        buf.put_u32(len as u32);
+    }
+
+    buf.split_to(len + GRPC_HEADER_SIZE).freeze()
+}
@@ -316,8 +316,22 @@ impl StringCollector {
 
 type Labels = Vec<proto::Label>;
 
+/// The base units of the Prometheus [naming conventions][naming], sent as the unit of the metrics
+/// whose name ends with them.
+///
+/// [naming]: https://prometheus.io/docs/practices/naming/#base-units
+const BASE_UNITS: [&str; 9] = [
+    "seconds", "bytes", "ratio", "meters", "volts", "amperes", "joules", "grams", "celsius",
+];
+
+#[derive(Default)]
+struct Series {
+    samples: Vec<proto::Sample>,
+    exemplars: Vec<proto::Exemplar>,
+}
+
 pub(super) struct TimeSeries {
-    buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    buffer: IndexMap<Labels, Series>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
 }
@@ -354,6 +368,45 @@ impl TimeSeries {
             .timestamp
             .get_or_insert_with(|| Utc::now().timestamp_millis())
     }
+
+    /// Attaches an exemplar with the given labels to the sample of a counter or a gauge, which
+    /// must have been encoded already.
+    pub(super) fn encode_exemplar(
+        &mut self,
+        default_namespace: Option<&str>,
+        metric: &Metric,
+        exemplar_tags: &MetricTags,
+    ) {
+        let value = match (metric.kind(), metric.value()) {
+            (MetricKind::Absolute, MetricValue::Counter { value })
+            | (MetricKind::Absolute, MetricValue::Gauge { value }) => *value,
+            _ => return,
+        };
+        let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+        let timestamp = metric
+            .timestamp()
+            .map(|t| t.timestamp_millis())
+            .unwrap_or_else(|| self.default_timestamp());
+
+        let mut labels = exemplar_tags
+            .iter_single()
+            .map(|(name, value)| proto::Label {
+                name: name.into(),
+                value: value.into(),
+            })
+            .collect::<Labels>();
+        labels.sort();
+
+        self.buffer
+            .entry(Self::make_labels(metric.tags(), &name, "", None))
+            .or_default()
+            .exemplars
+            .push(proto::Exemplar {
+                labels,
+                value,
+                timestamp,
+            });
+    }
 }
 
 impl MetricCollector for TimeSeries {
@@ -368,15 +421,15 @@ impl MetricCollector for TimeSeries {
     }
 
     fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
-        if !self.metadata.contains_key(name) {
+        if !self.metadata.contains_key(fullname) {
             let r#type = prometheus_metric_type(value);
             let metadata = proto::MetricMetadata {
                 r#type: r#type as i32,
                 metric_family_name: fullname.into(),
                 help: name.into(),
-                unit: String::new(),
+                unit: metric_unit(fullname).into(),
             };
-            self.metadata.insert(name.into(), metadata);
+            self.metadata.insert(fullname.into(), metadata);
         }
     }
 
@@ -393,6 +446,7 @@ impl MetricCollector for TimeSeries {
         self.buffer
             .entry(Self::make_labels(tags, name, suffix, extra))
             .or_default()
+            .samples
             .push(proto::Sample { value, timestamp });
     }
 
@@ -400,7 +454,18 @@ impl MetricCollector for TimeSeries {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, mut series)| {
+                // Receivers reject the samples of a series that are older than its latest one, or
+                // that have the same timestamp, so the samples of a batch are sent in order.
+                series.samples.sort_by_key(|sample| sample.timestamp);
+                series.samples.dedup_by_key(|sample| sample.timestamp);
+                series.exemplars.sort_by_key(|exemplar| exemplar.timestamp);
+                proto::TimeSeries {
+                    labels,
+                    samples: series.samples,
+                    exemplars: series.exemplars,
+                }
+            })
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -414,6 +479,17 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+fn metric_unit(fullname: &str) -> &'static str {
+    let name = fullname.strip_suffix("_total").unwrap_or(fullname);
+    BASE_UNITS
+        .into_iter()
+        .find(|unit| {
+            name.strip_suffix(unit)
+                .map_or(false, |prefix| prefix.ends_with('_'))
+        })
+        .unwrap_or_default()
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -490,6 +566,7 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            exemplars: vec![],
                         },
                     )*
                 ],
@@ -965,4 +1042,81 @@ mod tests {
             "#}
         );
     }
+
+    #[test]
+    fn infers_metadata_units() {
+        assert_eq!(metric_unit("http_request_duration_seconds"), "seconds");
+        assert_eq!(metric_unit("vector_sent_bytes_total"), "bytes");
+        assert_eq!(metric_unit("vector_hits"), "");
+        assert_eq!(metric_unit("megabytes"), "");
+
+        let metric = Metric::new(
+            "sent_bytes_total".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let encoded = encode_one::<TimeSeries>(Some("vector"), &[], &[], &metric);
+        assert_eq!(
+            encoded.metadata[0].metric_family_name,
+            "vector_sent_bytes_total"
+        );
+        assert_eq!(encoded.metadata[0].unit, "bytes");
+    }
+
+    #[test]
+    fn sorts_samples_request() {
+        let gauge = |value: f64, millis: i64| {
+            Metric::new(
+                "temperature".to_owned(),
+                MetricKind::Absolute,
+                MetricValue::Gauge { value },
+            )
+            .with_timestamp(Some(Utc.timestamp_millis_opt(millis).unwrap()))
+        };
+
+        let mut series = TimeSeries::new();
+        for metric in [gauge(2.0, 2000), gauge(1.0, 1000), gauge(3.0, 2000)] {
+            series.encode_metric(None, &[], &[], &metric);
+        }
+        let encoded = series.finish();
+
+        let samples = &encoded.timeseries[0].samples;
+        assert_eq!(
+            samples.iter().map(|s| s.timestamp).collect::<Vec<_>>(),
+            vec![1000, 2000]
+        );
+    }
+
+    #[test]
+    fn encodes_exemplar_request() {
+        let metric = Metric::new(
+            "hits".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 10.0 },
+        )
+        .with_tags(Some(tags()))
+        .with_timestamp(Some(timestamp()));
+
+        let mut series = TimeSeries::new();
+        series.encode_metric(Some("vector"), &[], &[], &metric);
+        series.encode_exemplar(
+            Some("vector"),
+            &metric,
+            &metric_tags!("trace_id" => "0af7651916cd43dd"),
+        );
+        let encoded = series.finish();
+
+        assert_eq!(encoded.timeseries.len(), 1);
+        assert_eq!(
+            encoded.timeseries[0].exemplars,
+            vec![proto::Exemplar {
+                labels: vec![proto::Label {
+                    name: "trace_id".into(),
+                    value: "0af7651916cd43dd".into(),
+                }],
+                value: 10.0,
+                timestamp: 1612325106789,
+            }]
+        );
+    }
 }
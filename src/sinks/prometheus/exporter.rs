@@ -57,6 +57,8 @@ const LOCK_FAILED: &str = "Prometheus exporter data lock is poisoned";
 enum BuildError {
     #[snafu(display("Flush period for sets must be greater or equal to {} secs", min))]
     FlushPeriodTooShort { min: u64 },
+    #[snafu(display("OAuth2 authentication can't be used to authorize scrapes"))]
+    OAuth2Unsupported,
 }
 
 /// Configuration for the `prometheus_exporter` sink.
@@ -198,6 +200,10 @@ impl SinkConfig for PrometheusExporterConfig {
 
         validate_quantiles(&self.quantiles)?;
 
+        if let Some(Auth::OAuth2(_)) = self.auth {
+            return Err(Box::new(BuildError::OAuth2Unsupported));
+        }
+
         let sink = PrometheusExporter::new(self.clone());
         let healthcheck = future::ok(()).boxed();
 
@@ -364,6 +370,8 @@ fn authorized(req: &Request<Body>, auth: &Option<Auth>) -> bool {
                 Auth::Bearer { token } => {
                     HeaderValue::from_str(format!("Bearer {}", token.inner()).as_str())
                 }
+                // Rejected when the sink is built, as there is no token to compare with.
+                Auth::OAuth2(_) => return false,
             };
 
             if let Ok(encoded_credentials) = encoded_credentials {
@@ -8,7 +8,7 @@ pub(crate) mod remote_write;
 
 use vector_config::configurable_component;
 
-use crate::aws::AwsAuthentication;
+use crate::{aws::AwsAuthentication, http::OAuth2Config};
 
 /// Authentication strategies.
 #[configurable_component]
@@ -33,6 +33,13 @@ pub enum PrometheusRemoteWriteAuth {
         token: SensitiveString,
     },
 
+    /// OAuth 2.0 authentication, with the client credentials grant.
+    ///
+    /// An access token is requested from the authorization server with the client credentials, and
+    /// refreshed ahead of its expiry. It's passed as a bearer token.
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2Config),
+
     /// Amazon Prometheus Service-specific authentication.
     Aws(AwsAuthentication),
 }
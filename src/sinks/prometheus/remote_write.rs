@@ -10,15 +10,17 @@ use prost::Message;
 use snafu::{ResultExt, Snafu};
 use tower::Service;
 use vector_config::configurable_component;
-use vector_core::ByteSizeOf;
+use vector_core::{event::metric::MetricTags, ByteSizeOf};
 
 use super::collector::{self, MetricCollector as _};
 use crate::{
     aws::RegionOrEndpoint,
     config::{self, AcknowledgementsConfig, Input, SinkConfig},
     event::{Event, Metric},
-    http::{Auth, HttpClient},
-    internal_events::{EndpointBytesSent, TemplateRenderingError},
+    http::{Auth, HttpClient, HttpError},
+    internal_events::{
+        EndpointBytesSent, PrometheusRemoteWriteSamplesRejected, TemplateRenderingError,
+    },
     sinks::{
         self,
         prometheus::PrometheusRemoteWriteAuth,
@@ -26,6 +28,7 @@ use crate::{
             batch::BatchConfig,
             buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer},
             http::HttpRetryLogic,
+            retries::{RetryAction, RetryLogic},
             uri, EncodedEvent, PartitionBuffer, PartitionInnerBuffer, SinkBatchSettings,
             TowerRequestConfig,
         },
@@ -53,7 +56,7 @@ enum Errors {
 
 /// Configuration for the `prometheus_remote_write` sink.
 #[configurable_component(sink("prometheus_remote_write"))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct RemoteWriteConfig {
     /// The endpoint to send data to.
@@ -88,6 +91,27 @@ pub struct RemoteWriteConfig {
     #[configurable(metadata(docs::advanced))]
     pub quantiles: Vec<f64>,
 
+    /// Whether to send the metadata of the metrics, such as their type, along with their samples.
+    #[serde(default = "crate::serde::default_true")]
+    #[configurable(metadata(docs::advanced))]
+    pub send_metadata: bool,
+
+    /// Tags to send as the labels of an [exemplar][exemplars] of the samples, rather than as labels
+    /// of their series.
+    ///
+    /// Exemplars reference the context of a sample without being part of its series, such as the
+    /// ID of the trace of the request a counter was incremented for. They are only attached to
+    /// counters and gauges: the tags are removed from the other metrics.
+    ///
+    /// Prometheus only stores exemplars when the `exemplar-storage` feature is enabled, and rejects
+    /// the exemplars whose labels are longer than 128 characters in total.
+    ///
+    /// [exemplars]: https://prometheus.io/docs/prometheus/latest/feature_flags/#exemplars-storage
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "trace_id"))]
+    #[configurable(metadata(docs::advanced))]
+    pub exemplar_tags: Vec<String>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<PrometheusRemoteWriteDefaultBatchSettings>,
@@ -125,6 +149,26 @@ pub struct RemoteWriteConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+impl Default for RemoteWriteConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: Default::default(),
+            default_namespace: None,
+            buckets: Vec::new(),
+            quantiles: Vec::new(),
+            send_metadata: true,
+            exemplar_tags: Vec::new(),
+            batch: Default::default(),
+            request: Default::default(),
+            tenant_id: None,
+            tls: None,
+            auth: None,
+            aws: None,
+            acknowledgements: Default::default(),
+        }
+    }
+}
+
 impl_generate_config_from_default!(RemoteWriteConfig);
 
 #[async_trait::async_trait]
@@ -159,6 +203,11 @@ impl SinkConfig for RemoteWriteConfig {
                 None,
                 None,
             ),
+            Some(PrometheusRemoteWriteAuth::OAuth2(oauth2)) => {
+                let auth = Auth::OAuth2(oauth2.clone());
+                auth.start(&client).await?;
+                (Some(auth), None, None)
+            }
             Some(PrometheusRemoteWriteAuth::Aws(aws_auth)) => {
                 let region = self
                     .aws
@@ -184,11 +233,14 @@ impl SinkConfig for RemoteWriteConfig {
         });
 
         let healthcheck = healthcheck(client.clone(), Arc::clone(&http_request_builder)).boxed();
+        let exemplar_tags = self.exemplar_tags.clone();
         let service = RemoteWriteService {
             default_namespace: self.default_namespace.clone(),
             client,
             buckets,
             quantiles,
+            send_metadata: self.send_metadata,
+            exemplar_tags: exemplar_tags.clone(),
             http_request_builder,
         };
 
@@ -197,10 +249,19 @@ impl SinkConfig for RemoteWriteConfig {
             let mut normalizer = MetricNormalizer::<PrometheusMetricNormalize>::default();
 
             request_settings
-                .partition_sink(HttpRetryLogic, service, buffer, batch.timeout)
+                .partition_sink(RemoteWriteRetryLogic, service, buffer, batch.timeout)
                 .with_flat_map(move |event: Event| {
                     let byte_size = event.size_of();
-                    stream::iter(normalizer.normalize(event.into_metric()).map(|event| {
+                    // The exemplar tags aren't part of the series the metric is normalized in.
+                    let mut metric = event.into_metric();
+                    let exemplar = split_exemplar_tags(&mut metric, &exemplar_tags);
+                    let normalized = normalizer.normalize(metric).map(|mut metric| {
+                        for (name, value) in exemplar.into_iter_single() {
+                            metric.replace_tag(name, value);
+                        }
+                        metric
+                    });
+                    stream::iter(normalized.map(|event| {
                         let tenant_id = tenant_id.as_ref().and_then(|template| {
                             template
                                 .render_string(&event)
@@ -242,6 +303,65 @@ struct PartitionKey {
     tenant_id: Option<String>,
 }
 
+/// Removes the exemplar tags from the metric, returning them.
+fn split_exemplar_tags(metric: &mut Metric, names: &[String]) -> MetricTags {
+    let mut exemplar = MetricTags::default();
+    for name in names {
+        if let Some(value) = metric.remove_tag(name) {
+            exemplar.replace(name.clone(), value);
+        }
+    }
+    exemplar
+}
+
+/// The errors of the samples that a receiver rejects, while writing the other samples of the
+/// request.
+///
+/// Prometheus, as well as Cortex, Mimir and Thanos, report them with a `400 Bad Request` response
+/// (`409 Conflict` for Thanos), so the rest of the batch must not be sent again: the samples would
+/// be rejected again for the same reason, forever.
+const REJECTED_SAMPLE_ERRORS: [&str; 8] = [
+    "out of order sample",
+    "out of order exemplar",
+    "duplicate sample for timestamp",
+    "duplicate exemplar",
+    "out of bounds",
+    "too old",
+    "sample-out-of-order",
+    "sample-duplicate-timestamp",
+];
+
+#[derive(Clone, Debug, Default)]
+struct RemoteWriteRetryLogic;
+
+impl RetryLogic for RemoteWriteRetryLogic {
+    type Error = HttpError;
+    type Response = hyper::Response<Bytes>;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        HttpRetryLogic.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        let status = response.status();
+        if status == http::StatusCode::BAD_REQUEST || status == http::StatusCode::CONFLICT {
+            let body = String::from_utf8_lossy(response.body());
+            let body_lowercase = body.to_lowercase();
+            if REJECTED_SAMPLE_ERRORS
+                .iter()
+                .any(|error| body_lowercase.contains(error))
+            {
+                emit!(PrometheusRemoteWriteSamplesRejected {
+                    status,
+                    body: body.trim(),
+                });
+                return RetryAction::Successful;
+            }
+        }
+        HttpRetryLogic.should_retry_response(response)
+    }
+}
+
 async fn healthcheck(
     client: HttpClient,
     http_request_builder: Arc<HttpRequestBuilder>,
@@ -273,21 +393,30 @@ struct RemoteWriteService {
     client: HttpClient,
     buckets: Vec<f64>,
     quantiles: Vec<f64>,
+    send_metadata: bool,
+    exemplar_tags: Vec<String>,
     http_request_builder: Arc<HttpRequestBuilder>,
 }
 
 impl RemoteWriteService {
     fn encode_events(&self, metrics: Vec<Metric>) -> Bytes {
         let mut time_series = collector::TimeSeries::new();
-        for metric in metrics {
+        for mut metric in metrics {
+            let exemplar = split_exemplar_tags(&mut metric, &self.exemplar_tags);
             time_series.encode_metric(
                 self.default_namespace.as_deref(),
                 &self.buckets,
                 &self.quantiles,
                 &metric,
             );
+            if !exemplar.is_empty() {
+                time_series.encode_exemplar(self.default_namespace.as_deref(), &metric, &exemplar);
+            }
+        }
+        let mut request = time_series.finish();
+        if !self.send_metadata {
+            request.metadata.clear();
         }
-        let request = time_series.finish();
 
         let mut out = BytesMut::with_capacity(request.encoded_len());
         request.encode(&mut out).expect("Out of memory");
@@ -393,6 +522,7 @@ async fn sign_request(
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
     use futures::StreamExt;
     use http::HeaderMap;
     use indoc::indoc;
@@ -556,6 +686,78 @@ mod tests {
         check_output(2, "counter-1", 26.0);
     }
 
+    #[tokio::test]
+    async fn sends_exemplars() {
+        let outputs = send_request(
+            r#"exemplar_tags = ["trace_id"]"#,
+            vec![
+                create_traced_event("counter-1", 12.0, "0af7651916cd43dd", 1),
+                create_traced_event("counter-1", 14.0, "b7ad6b7169203331", 2),
+            ],
+        )
+        .await;
+
+        assert_eq!(outputs.len(), 1);
+        let (_, req) = &outputs[0];
+        assert_eq!(req.timeseries.len(), 1);
+        assert_eq!(req.timeseries[0].labels, labels!("__name__" => "counter-1"));
+        // The counter is accumulated across the exemplars.
+        let values = req.timeseries[0]
+            .samples
+            .iter()
+            .map(|sample| sample.value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![12.0, 26.0]);
+        let exemplars = &req.timeseries[0].exemplars;
+        assert_eq!(exemplars.len(), 2);
+        assert!(exemplars.iter().any(|exemplar| exemplar.labels
+            == labels!("trace_id" => "b7ad6b7169203331")
+            && exemplar.value == 26.0));
+    }
+
+    #[tokio::test]
+    async fn skips_metadata() {
+        let outputs = send_request(
+            "send_metadata = false",
+            vec![create_event("gauge-2".into(), 32.0)],
+        )
+        .await;
+
+        assert_eq!(outputs.len(), 1);
+        let (_, req) = &outputs[0];
+        assert_eq!(req.timeseries.len(), 1);
+        assert!(req.metadata.is_empty());
+    }
+
+    #[test]
+    fn classifies_rejected_samples() {
+        let response = |status: u16, body: &'static str| {
+            hyper::Response::builder()
+                .status(status)
+                .body(Bytes::from(body))
+                .unwrap()
+        };
+
+        for (status, body) in [
+            (400, "out of order sample"),
+            (400, "failed pushing to ingester: user=anonymous: the sample has been rejected because another sample with the same timestamp, but a different value, has already been ingested (err-mimir-sample-duplicate-timestamp)"),
+            (409, "store locally for endpoint 127.0.0.1:10901: conflict: out of bounds"),
+        ] {
+            assert!(matches!(
+                RemoteWriteRetryLogic.should_retry_response(&response(status, body)),
+                RetryAction::Successful
+            ));
+        }
+        assert!(matches!(
+            RemoteWriteRetryLogic.should_retry_response(&response(400, "invalid metric name")),
+            RetryAction::DontRetry(_)
+        ));
+        assert!(matches!(
+            RemoteWriteRetryLogic.should_retry_response(&response(503, "out of order sample")),
+            RetryAction::Retry(_)
+        ));
+    }
+
     async fn send_request(
         config: &str,
         events: Vec<Event>,
@@ -609,6 +811,19 @@ mod tests {
             .into()
     }
 
+    fn create_traced_event(name: &str, value: f64, trace_id: &str, seconds: i64) -> Event {
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+        )
+        .with_tags(Some(metric_tags!("trace_id" => trace_id)))
+        .with_timestamp(Some(
+            chrono::Utc.timestamp_opt(1612325106 + seconds, 0).unwrap(),
+        ))
+        .into()
+    }
+
     fn create_inc_event(name: String, value: f64) -> Event {
         Metric::new(
             name,
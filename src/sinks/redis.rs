@@ -5,7 +5,7 @@ use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
 use redis::{aio::ConnectionManager, RedisError, RedisResult};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
-use tower::{Service, ServiceBuilder};
+use tower::Service;
 use vector_common::internal_event::{
     ByteSize, BytesSent, InternalEventHandle, Protocol, Registered,
 };
@@ -21,8 +21,7 @@ use crate::{
         batch::BatchConfig,
         retries::{RetryAction, RetryLogic},
         sink::Response,
-        BatchSink, Concurrency, EncodedEvent, EncodedLength, ServiceBuilderExt, SinkBatchSettings,
-        TowerRequestConfig, VecBuffer,
+        Concurrency, EncodedEvent, EncodedLength, SinkBatchSettings, TowerRequestConfig, VecBuffer,
     },
     template::{Template, TemplateParseError},
 };
@@ -224,11 +223,8 @@ impl RedisSinkConfig {
             bytes_sent: register!(BytesSent::from(Protocol::TCP)),
         };
 
-        let svc = ServiceBuilder::new()
-            .settings(request, RedisRetryLogic)
-            .service(redis);
-
-        let sink = BatchSink::new(svc, buffer, batch.timeout)
+        let sink = request
+            .batch_sink(RedisRetryLogic, redis, buffer, batch.timeout)
             .with_flat_map(move |event| {
                 // Errors are handled by `Encoder`.
                 stream::iter(encode_event(event, &key, &transformer, &mut encoder)).map(Ok)
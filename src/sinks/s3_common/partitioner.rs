@@ -1,6 +1,12 @@
+use std::time::Duration;
+
+use chrono::Utc;
 use vector_core::{event::Event, partition::Partitioner};
 
-use crate::{internal_events::TemplateRenderingError, template::Template};
+use crate::{
+    internal_events::{LatePartition, S3LateEvent, TemplateRenderingError},
+    template::{event_timestamp, Template, TemplateRenderingError as RenderingError},
+};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct S3PartitionKey {
@@ -9,14 +15,60 @@ pub struct S3PartitionKey {
 }
 
 /// Partitions items based on the generated key for the given event.
-pub struct S3KeyPartitioner(Template, Option<Template>);
+pub struct S3KeyPartitioner {
+    key_prefix: Template,
+    ssekms_key_id: Option<Template>,
+    late_event_window: Option<chrono::Duration>,
+}
 
 impl S3KeyPartitioner {
     pub const fn new(
         key_prefix_template: Template,
         ssekms_key_id_template: Option<Template>,
     ) -> Self {
-        Self(key_prefix_template, ssekms_key_id_template)
+        Self {
+            key_prefix: key_prefix_template,
+            ssekms_key_id: ssekms_key_id_template,
+            late_event_window: None,
+        }
+    }
+
+    /// Sets how old an event can be, compared to the current time, to still be written to the
+    /// partition of its own time period rather than the current one.
+    pub fn with_late_event_window(mut self, window: Option<Duration>) -> Self {
+        self.late_event_window = window.map(|window| {
+            chrono::Duration::from_std(window).unwrap_or_else(|_| chrono::Duration::max_value())
+        });
+        self
+    }
+
+    fn render_key_prefix(&self, item: &Event) -> Result<String, RenderingError> {
+        let now = Utc::now();
+        let timestamp = match event_timestamp(item) {
+            // Events from the future are never late.
+            Some(timestamp) if timestamp < now => timestamp,
+            _ => return self.key_prefix.render_string(item),
+        };
+
+        if self.key_prefix.render_time_period(timestamp) == self.key_prefix.render_time_period(now)
+        {
+            return self.key_prefix.render_string(item);
+        }
+
+        match self.late_event_window {
+            Some(window) if now - timestamp > window => {
+                emit!(S3LateEvent {
+                    partition: LatePartition::Current,
+                });
+                self.key_prefix.render_string_at(item, now)
+            }
+            _ => {
+                emit!(S3LateEvent {
+                    partition: LatePartition::Past,
+                });
+                self.key_prefix.render_string(item)
+            }
+        }
     }
 }
 
@@ -26,8 +78,7 @@ impl Partitioner for S3KeyPartitioner {
 
     fn partition(&self, item: &Self::Item) -> Self::Key {
         let key_prefix = self
-            .0
-            .render_string(item)
+            .render_key_prefix(item)
             .map_err(|error| {
                 emit!(TemplateRenderingError {
                     error,
@@ -37,7 +88,7 @@ impl Partitioner for S3KeyPartitioner {
             })
             .ok()?;
         let ssekms_key_id = self
-            .1
+            .ssekms_key_id
             .as_ref()
             .map(|ssekms_key_id| {
                 ssekms_key_id.render_string(item).map_err(|error| {
@@ -56,3 +107,66 @@ impl Partitioner for S3KeyPartitioner {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration as ChronoDuration};
+
+    use super::*;
+    use crate::{config::log_schema, event::LogEvent};
+
+    fn event_at(timestamp: Option<DateTime<Utc>>) -> Event {
+        let mut log = LogEvent::from("hello world");
+        match timestamp {
+            Some(timestamp) => {
+                log.insert(
+                    log_schema().timestamp_key().unwrap().to_string().as_str(),
+                    timestamp,
+                );
+            }
+            None => {
+                log.remove(log_schema().timestamp_key().unwrap().to_string().as_str());
+            }
+        }
+        log.into()
+    }
+
+    fn partition(partitioner: &S3KeyPartitioner, event: &Event) -> String {
+        partitioner.partition(event).unwrap().key_prefix
+    }
+
+    #[test]
+    fn partitions_by_event_time() {
+        let partitioner =
+            S3KeyPartitioner::new(Template::try_from("date=%F/hour=%H/").unwrap(), None);
+        let now = Utc::now();
+        let past = now - ChronoDuration::days(2);
+
+        assert_eq!(
+            partition(&partitioner, &event_at(Some(past))),
+            past.format("date=%F/hour=%H/").to_string()
+        );
+        assert_eq!(
+            partition(&partitioner, &event_at(None)),
+            now.format("date=%F/hour=%H/").to_string()
+        );
+    }
+
+    #[test]
+    fn partitions_events_older_than_window_by_current_time() {
+        let partitioner = S3KeyPartitioner::new(Template::try_from("date=%F/").unwrap(), None)
+            .with_late_event_window(Some(Duration::from_secs(3 * 86400)));
+        let now = Utc::now();
+        let late = now - ChronoDuration::days(2);
+        let too_late = now - ChronoDuration::days(4);
+
+        assert_eq!(
+            partition(&partitioner, &event_at(Some(late))),
+            late.format("date=%F/").to_string()
+        );
+        assert_eq!(
+            partition(&partitioner, &event_at(Some(too_late))),
+            now.format("date=%F/").to_string()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
+
+use futures::FutureExt;
+use http::Uri;
+use serde_with::serde_as;
+use tower::ServiceBuilder;
+use url::Url;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{
+    encoder::SentryEncoder,
+    service::{SentryResponse, SentryService},
+    sink::SentrySink,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{http::HttpStatusRetryLogic, ServiceBuilderExt, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `sentry` sink.
+#[serde_as]
+#[configurable_component(sink("sentry"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SentryConfig {
+    /// The DSN of the Sentry project to send the events to.
+    ///
+    /// It is found in the settings of the project, under **Client Keys (DSN)**.
+    #[configurable(metadata(docs::examples = "https://public_key@o0.ingest.sentry.io/0"))]
+    #[configurable(metadata(docs::examples = "${SENTRY_DSN}"))]
+    pub dsn: SensitiveString,
+
+    /// The field of the level of the events.
+    ///
+    /// The levels are mapped to the Sentry levels, such as `warn` to `warning` and `critical` to
+    /// `fatal`. The events without a level, or with an unknown one, are sent as `error`.
+    #[serde(default = "default_level_key")]
+    #[configurable(metadata(docs::examples = "severity"))]
+    pub level_key: String,
+
+    /// The field of the exception of the events.
+    ///
+    /// The exception is either an object with `type`, `value`, and `module` fields, or the message
+    /// of the exception.
+    #[serde(default = "default_exception_key")]
+    #[configurable(metadata(docs::examples = "error"))]
+    pub exception_key: String,
+
+    /// The fingerprint of the events, which Sentry groups the events into issues by.
+    ///
+    /// Each template renders a part of the fingerprint. By default, Sentry groups the events by
+    /// their exception, or by their message.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "{{ service }}"))]
+    #[configurable(metadata(docs::examples = "{{ exception.type }}"))]
+    pub fingerprint: Vec<Template>,
+
+    /// The release of the application the events come from.
+    #[configurable(metadata(docs::examples = "{{ service }}@{{ version }}"))]
+    pub release: Option<Template>,
+
+    /// The environment the events come from.
+    #[configurable(metadata(docs::examples = "production"))]
+    #[configurable(metadata(docs::examples = "{{ env }}"))]
+    pub environment: Option<Template>,
+
+    /// The tags of the events, which Sentry indexes for searching the events.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "A Sentry tag."))]
+    #[configurable(metadata(docs::examples = "tag_examples()"))]
+    pub tags: HashMap<String, Template>,
+
+    #[configurable(derived)]
+    pub rate_limit: Option<RateLimitConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The maximum rate of the events sent with each fingerprint.
+///
+/// The events in excess are dropped, and the events without a fingerprint are limited by their
+/// exception, or by their message.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// The maximum number of events to send with a fingerprint in each period.
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_events: NonZeroUsize,
+
+    /// The duration of the period.
+    #[serde(default = "default_period")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "period_secs")]
+    #[configurable(metadata(docs::examples = 60))]
+    pub period: Duration,
+}
+
+fn default_level_key() -> String {
+    "level".to_owned()
+}
+
+fn default_exception_key() -> String {
+    "exception".to_owned()
+}
+
+const fn default_period() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn tag_examples() -> HashMap<String, String> {
+    HashMap::from([
+        ("service".to_owned(), "{{ service }}".to_owned()),
+        ("region".to_owned(), "eu-west-1".to_owned()),
+    ])
+}
+
+impl GenerateConfig for SentryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"dsn = "https://public_key@o0.ingest.sentry.io/0"
+            environment = "production""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SentryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let dsn = Dsn::parse(self.dsn.inner())?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let retry_logic =
+            HttpStatusRetryLogic::new(|response: &SentryResponse| response.http_status);
+        let service = ServiceBuilder::new()
+            .settings(request_settings, retry_logic)
+            .service(SentryService::new(dsn, client));
+
+        let encoder = SentryEncoder::new(self);
+        let sink = SentrySink::new(encoder, self.rate_limit.as_ref(), service);
+
+        // Sentry has no endpoint to check a DSN with, without sending an event.
+        let healthcheck = futures::future::ok(()).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// The parts of a DSN the events are sent with.
+#[derive(Clone, Debug)]
+pub(super) struct Dsn {
+    pub(super) public_key: String,
+    pub(super) envelope_uri: Uri,
+}
+
+impl Dsn {
+    /// Parses a DSN of the form `{scheme}://{public_key}@{host}[/{path}]/{project_id}`.
+    fn parse(dsn: &str) -> crate::Result<Self> {
+        let url = Url::parse(dsn).map_err(|error| format!("Invalid DSN: {}", error))?;
+        let public_key = url.username();
+        if public_key.is_empty() {
+            return Err("Invalid DSN: missing public key".into());
+        }
+        let host = url.host_str().ok_or("Invalid DSN: missing host")?;
+        let (path, project_id) = url
+            .path()
+            .trim_end_matches('/')
+            .rsplit_once('/')
+            .filter(|(_, project_id)| !project_id.is_empty())
+            .ok_or("Invalid DSN: missing project ID")?;
+
+        let port = url
+            .port()
+            .map(|port| format!(":{}", port))
+            .unwrap_or_default();
+        let envelope_uri = format!(
+            "{}://{}{}{}/api/{}/envelope/",
+            url.scheme(),
+            host,
+            port,
+            path,
+            project_id
+        )
+        .parse::<Uri>()?;
+
+        Ok(Self {
+            public_key: public_key.to_owned(),
+            envelope_uri,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SentryConfig>();
+    }
+
+    #[test]
+    fn parses_dsn() {
+        let dsn = Dsn::parse("https://abc123@o42.ingest.sentry.io/1337").unwrap();
+        assert_eq!(dsn.public_key, "abc123");
+        assert_eq!(
+            dsn.envelope_uri,
+            "https://o42.ingest.sentry.io/api/1337/envelope/"
+        );
+
+        let dsn = Dsn::parse("http://abc123@localhost:9000/sentry/7").unwrap();
+        assert_eq!(
+            dsn.envelope_uri,
+            "http://localhost:9000/sentry/api/7/envelope/"
+        );
+
+        assert!(Dsn::parse("https://o42.ingest.sentry.io/1337").is_err());
+        assert!(Dsn::parse("https://abc123@o42.ingest.sentry.io/").is_err());
+    }
+}
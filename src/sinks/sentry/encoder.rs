@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::config::SentryConfig;
+use crate::{
+    event::{LogEvent, Value},
+    internal_events::TemplateRenderingError,
+    template::Template,
+};
+
+/// The envelope of a log event, sent to Sentry as a Sentry event.
+pub(super) struct Envelope {
+    /// What the rate of the event is limited by: its fingerprint, or else its exception or its
+    /// message.
+    pub(super) rate_limit_key: String,
+    pub(super) body: Bytes,
+}
+
+pub(super) struct SentryEncoder {
+    level_key: String,
+    exception_key: String,
+    fingerprint: Vec<Template>,
+    release: Option<Template>,
+    environment: Option<Template>,
+    tags: Vec<(String, Template)>,
+}
+
+impl SentryEncoder {
+    pub(super) fn new(config: &SentryConfig) -> Self {
+        let mut tags = config
+            .tags
+            .iter()
+            .map(|(name, template)| (name.clone(), template.clone()))
+            .collect::<Vec<_>>();
+        tags.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Self {
+            level_key: config.level_key.clone(),
+            exception_key: config.exception_key.clone(),
+            fingerprint: config.fingerprint.clone(),
+            release: config.release.clone(),
+            environment: config.environment.clone(),
+            tags,
+        }
+    }
+
+    pub(super) fn encode(&self, log: &LogEvent, now: DateTime<Utc>) -> Envelope {
+        let event_id = Uuid::new_v4().simple().to_string();
+        let timestamp = log
+            .get_timestamp()
+            .and_then(Value::as_timestamp)
+            .copied()
+            .unwrap_or(now);
+        let message = log.get_message().map(|message| message.to_string_lossy());
+        let exception = log.get(self.exception_key.as_str()).map(exception);
+
+        let mut payload = serde_json::Map::new();
+        payload.insert("event_id".to_owned(), json!(event_id));
+        payload.insert(
+            "timestamp".to_owned(),
+            json!(timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)),
+        );
+        payload.insert("platform".to_owned(), json!("other"));
+        payload.insert(
+            "level".to_owned(),
+            json!(log
+                .get(self.level_key.as_str())
+                .map_or("error", |level| sentry_level(&level.to_string_lossy()))),
+        );
+        if let Some(message) = &message {
+            payload.insert("logentry".to_owned(), json!({ "formatted": message }));
+        }
+        if let Some(exception) = &exception {
+            payload.insert("exception".to_owned(), json!({ "values": [exception] }));
+        }
+        if let Some(host) = log.get_host() {
+            payload.insert("server_name".to_owned(), json!(host.to_string_lossy()));
+        }
+        if let Some(release) = render(self.release.as_ref(), log, "release") {
+            payload.insert("release".to_owned(), json!(release));
+        }
+        if let Some(environment) = render(self.environment.as_ref(), log, "environment") {
+            payload.insert("environment".to_owned(), json!(environment));
+        }
+
+        let tags = self
+            .tags
+            .iter()
+            .filter_map(|(name, template)| {
+                render(Some(template), log, "tags").map(|value| (name.clone(), value))
+            })
+            .collect::<BTreeMap<_, _>>();
+        if !tags.is_empty() {
+            payload.insert("tags".to_owned(), json!(tags));
+        }
+
+        // The fingerprint is left to Sentry if any of its parts fails to render.
+        let fingerprint = self
+            .fingerprint
+            .iter()
+            .map(|template| render(Some(template), log, "fingerprint"))
+            .collect::<Option<Vec<_>>>()
+            .filter(|fingerprint| !fingerprint.is_empty());
+        if let Some(fingerprint) = &fingerprint {
+            payload.insert("fingerprint".to_owned(), json!(fingerprint));
+        }
+
+        let extra = self.extra(log);
+        if !extra.is_empty() {
+            payload.insert("extra".to_owned(), json!(extra));
+        }
+
+        let rate_limit_key = match (fingerprint, &exception) {
+            (Some(fingerprint), _) => fingerprint.join("\n"),
+            (None, Some(exception)) => format!(
+                "{}: {}",
+                exception["type"].as_str().unwrap_or_default(),
+                exception["value"].as_str().unwrap_or_default()
+            ),
+            (None, None) => message
+                .map(|message| message.into_owned())
+                .unwrap_or_default(),
+        };
+
+        let payload = serde_json::to_vec(&payload).expect("JSON values are always serializable");
+        let envelope_header = serde_json::to_vec(&json!({
+            "event_id": event_id,
+            "sent_at": now.to_rfc3339_opts(SecondsFormat::Micros, true),
+        }))
+        .expect("JSON values are always serializable");
+        let item_header = serde_json::to_vec(&json!({
+            "type": "event",
+            "length": payload.len(),
+            "content_type": "application/json",
+        }))
+        .expect("JSON values are always serializable");
+
+        // The header of the envelope, then the header and the payload of its only item.
+        let mut body = BytesMut::new();
+        for part in [envelope_header, item_header, payload] {
+            body.put_slice(&part);
+            body.put_u8(b'\n');
+        }
+
+        Envelope {
+            rate_limit_key,
+            body: body.freeze(),
+        }
+    }
+
+    /// Returns the fields of the event which aren't mapped to the fields of the Sentry event.
+    fn extra<'a>(&self, log: &'a LogEvent) -> BTreeMap<&'a str, &'a Value> {
+        let mapped = [
+            log.message_path(),
+            log.timestamp_path(),
+            log.host_path(),
+            Some(self.level_key.clone()),
+            Some(self.exception_key.clone()),
+        ];
+        log.as_map()
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter(|(name, _)| !mapped.iter().flatten().any(|path| path == *name))
+                    .map(|(name, value)| (name.as_str(), value))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn render(template: Option<&Template>, log: &LogEvent, field: &'static str) -> Option<String> {
+    template?
+        .render_string(log)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: false,
+            });
+        })
+        .ok()
+}
+
+/// Returns the Sentry exception of the exception field of an event.
+fn exception(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Object(fields) => {
+            let field = |name: &str| {
+                fields
+                    .get(name)
+                    .map(|value| value.to_string_lossy().into_owned())
+            };
+            let mut exception = json!({
+                "type": field("type").unwrap_or_else(|| "Error".to_owned()),
+                "value": field("value").or_else(|| field("message")).unwrap_or_default(),
+            });
+            if let Some(module) = field("module") {
+                exception["module"] = json!(module);
+            }
+            exception
+        }
+        value => json!({
+            "type": "Error",
+            "value": value.to_string_lossy(),
+        }),
+    }
+}
+
+/// Maps the level of an event to a Sentry level.
+fn sentry_level(level: &str) -> &'static str {
+    match level.to_ascii_lowercase().as_str() {
+        "fatal" | "critical" | "crit" | "emerg" | "emergency" | "alert" | "panic" => "fatal",
+        "warning" | "warn" => "warning",
+        "info" | "information" | "informational" | "notice" => "info",
+        "debug" | "trace" => "debug",
+        _ => "error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoder(config: &str) -> SentryEncoder {
+        let config: SentryConfig = toml::from_str(config).unwrap();
+        SentryEncoder::new(&config)
+    }
+
+    fn parse(envelope: &Envelope) -> Vec<serde_json::Value> {
+        std::str::from_utf8(&envelope.body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn encodes_events_in_envelopes() {
+        let encoder = encoder(indoc::indoc! {r#"
+            dsn = "https://public_key@o0.ingest.sentry.io/0"
+            fingerprint = ["{{ service }}", "{{ exception.type }}"]
+            release = "{{ service }}@1.2.3"
+            environment = "production"
+            tags.service = "{{ service }}"
+        "#});
+        let mut log = LogEvent::from("Payment failed");
+        log.insert("level", "WARN");
+        log.insert("service", "billing");
+        log.insert("order_id", 42);
+        log.insert("exception.type", "TimeoutError");
+        log.insert("exception.value", "upstream timed out");
+
+        let envelope = encoder.encode(&log, Utc::now());
+        let parts = parse(&envelope);
+        assert_eq!(parts.len(), 3);
+        let payload = &parts[2];
+        assert_eq!(parts[0]["event_id"], payload["event_id"]);
+        assert_eq!(parts[1]["type"], "event");
+        assert_eq!(
+            parts[1]["length"],
+            envelope
+                .body
+                .split(|byte| *byte == b'\n')
+                .nth(2)
+                .unwrap()
+                .len()
+        );
+
+        assert_eq!(payload["level"], "warning");
+        assert_eq!(payload["logentry"]["formatted"], "Payment failed");
+        assert_eq!(
+            payload["exception"]["values"][0],
+            json!({ "type": "TimeoutError", "value": "upstream timed out" })
+        );
+        assert_eq!(payload["fingerprint"], json!(["billing", "TimeoutError"]));
+        assert_eq!(payload["release"], "billing@1.2.3");
+        assert_eq!(payload["environment"], "production");
+        assert_eq!(payload["tags"], json!({ "service": "billing" }));
+        assert_eq!(
+            payload["extra"],
+            json!({ "order_id": 42, "service": "billing" })
+        );
+        assert_eq!(envelope.rate_limit_key, "billing\nTimeoutError");
+    }
+
+    #[test]
+    fn limits_the_events_without_fingerprint_by_exception_or_message() {
+        let encoder = encoder(r#"dsn = "https://public_key@o0.ingest.sentry.io/0""#);
+
+        let mut log = LogEvent::from("Payment failed");
+        let envelope = encoder.encode(&log, Utc::now());
+        assert_eq!(parse(&envelope)[2]["level"], "error");
+        assert!(parse(&envelope)[2].get("fingerprint").is_none());
+        assert_eq!(envelope.rate_limit_key, "Payment failed");
+
+        log.insert("exception", "connection refused");
+        let envelope = encoder.encode(&log, Utc::now());
+        assert_eq!(envelope.rate_limit_key, "Error: connection refused");
+    }
+
+    #[test]
+    fn maps_levels() {
+        assert_eq!(sentry_level("CRITICAL"), "fatal");
+        assert_eq!(sentry_level("err"), "error");
+        assert_eq!(sentry_level("notice"), "info");
+        assert_eq!(sentry_level("trace"), "debug");
+        assert_eq!(sentry_level("unknown"), "error");
+    }
+}
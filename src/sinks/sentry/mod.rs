@@ -0,0 +1,13 @@
+//! The `sentry` sink.
+//!
+//! Sends the error events of the applications to [Sentry][sentry], as Sentry events wrapped in
+//! envelopes, rate limited per fingerprint so an error repeating in a loop doesn't exhaust the quota
+//! of the project.
+//!
+//! [sentry]: https://sentry.io
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::SentryConfig;
@@ -0,0 +1,126 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::{self, BoxFuture, Ready};
+use http::Request;
+use tower::{Service, ServiceExt};
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse, ByteSizeOf};
+
+use super::config::Dsn;
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::HttpClient,
+    sinks::util::{http::HttpBatchService, sink::Response},
+};
+
+/// The envelope of an event, as Sentry only accepts a single event per envelope.
+pub(super) struct SentryRequest {
+    pub(super) body: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) request_metadata: RequestMetadata,
+}
+
+impl Finalizable for SentryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl ByteSizeOf for SentryRequest {
+    fn allocated_bytes(&self) -> usize {
+        self.body.allocated_bytes() + self.finalizers.allocated_bytes()
+    }
+}
+
+impl MetaDescriptive for SentryRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.request_metadata
+    }
+}
+
+pub(super) struct SentryResponse {
+    event_status: EventStatus,
+    pub(super) http_status: http::StatusCode,
+    event_byte_size: usize,
+}
+
+impl DriverResponse for SentryResponse {
+    fn event_status(&self) -> EventStatus {
+        self.event_status
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(1, self.event_byte_size)
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        // HttpBatchService emits EndpointBytesSent
+        None
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct SentryService {
+    batch_http_service:
+        HttpBatchService<Ready<Result<http::Request<Bytes>, crate::Error>>, SentryRequest>,
+}
+
+impl SentryService {
+    pub(super) fn new(dsn: Dsn, http_client: HttpClient) -> Self {
+        let auth = format!(
+            "Sentry sentry_version=7, sentry_client=vector/{}, sentry_key={}",
+            crate::get_version(),
+            dsn.public_key
+        );
+        let batch_http_service = HttpBatchService::new(http_client, move |req| {
+            let req: SentryRequest = req;
+
+            let request = Request::post(&dsn.envelope_uri)
+                .header("Content-Type", "application/x-sentry-envelope")
+                .header("X-Sentry-Auth", auth.as_str())
+                .header("Content-Length", req.body.len())
+                .body(req.body)
+                .map_err(Into::into);
+            future::ready(request)
+        });
+
+        Self { batch_http_service }
+    }
+}
+
+impl Service<SentryRequest> for SentryService {
+    type Response = SentryResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of Error internal event is handled upstream by the caller
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of Error internal event is handled upstream by the caller
+    fn call(&mut self, req: SentryRequest) -> Self::Future {
+        let mut http_service = self.batch_http_service.clone();
+
+        Box::pin(async move {
+            http_service.ready().await?;
+            let event_byte_size = req.get_metadata().events_byte_size();
+            let http_response = http_service.call(req).await?;
+            // Sentry responds with `429 Too Many Requests` once the quota of the project is
+            // exhausted, which is retried.
+            let event_status = if http_response.is_successful() {
+                EventStatus::Delivered
+            } else if http_response.is_transient() {
+                EventStatus::Errored
+            } else {
+                EventStatus::Rejected
+            };
+            Ok(SentryResponse {
+                event_status,
+                http_status: http_response.status(),
+                event_byte_size,
+            })
+        })
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    num::NonZeroUsize,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::{future, stream::BoxStream, StreamExt};
+use tokio::time::Instant;
+use tower::Service;
+use vector_core::stream::DriverResponse;
+
+use super::{config::RateLimitConfig, encoder::SentryEncoder, service::SentryRequest};
+use crate::{
+    event::{Event, Finalizable},
+    internal_events::SentryEventRateLimited,
+    sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt, StreamSink},
+};
+
+/// The number of fingerprints tracked by the rate limiter above which the fingerprints without
+/// events in the last period are forgotten.
+const MAX_IDLE_FINGERPRINTS: usize = 10_000;
+
+pub(super) struct SentrySink<S> {
+    encoder: SentryEncoder,
+    rate_limiter: Option<RateLimiter>,
+    service: S,
+}
+
+impl<S> SentrySink<S>
+where
+    S: Service<SentryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) fn new(
+        encoder: SentryEncoder,
+        rate_limit: Option<&RateLimitConfig>,
+        service: S,
+    ) -> Self {
+        Self {
+            encoder,
+            rate_limiter: rate_limit.map(RateLimiter::new),
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            encoder,
+            mut rate_limiter,
+            service,
+        } = *self;
+
+        input
+            .filter_map(move |event| {
+                future::ready(build_request(&encoder, rate_limiter.as_mut(), event))
+            })
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+/// Builds the request of an event, unless its fingerprint reached the rate limit.
+fn build_request(
+    encoder: &SentryEncoder,
+    rate_limiter: Option<&mut RateLimiter>,
+    mut event: Event,
+) -> Option<SentryRequest> {
+    let envelope = encoder.encode(event.as_log(), Utc::now());
+
+    if let Some(rate_limiter) = rate_limiter {
+        let now = Instant::now();
+        if !rate_limiter.allows(&envelope.rate_limit_key, now) {
+            // The event is acknowledged, as it is dropped on purpose.
+            emit!(SentryEventRateLimited);
+            return None;
+        }
+        rate_limiter.record(&envelope.rate_limit_key, now);
+    }
+
+    let metadata_builder = RequestMetadataBuilder::from_events(&event);
+    let finalizers = event.take_finalizers();
+    let request_size = NonZeroUsize::new(envelope.body.len()).expect("envelopes are never empty");
+    let request_metadata = metadata_builder.with_request_size(request_size);
+    Some(SentryRequest {
+        body: envelope.body,
+        finalizers,
+        request_metadata,
+    })
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for SentrySink<S>
+where
+    S: Service<SentryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Tracks the events sent with each fingerprint over the last period.
+struct RateLimiter {
+    max_events: usize,
+    period: Duration,
+    sent: HashMap<String, VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            max_events: config.max_events.get(),
+            period: config.period,
+            sent: HashMap::new(),
+        }
+    }
+
+    /// Returns whether another event can be sent with `fingerprint`.
+    fn allows(&mut self, fingerprint: &str, now: Instant) -> bool {
+        match self.sent.get_mut(fingerprint) {
+            Some(sent) => {
+                expire(sent, now, self.period);
+                sent.len() < self.max_events
+            }
+            None => true,
+        }
+    }
+
+    fn record(&mut self, fingerprint: &str, now: Instant) {
+        if self.sent.len() >= MAX_IDLE_FINGERPRINTS && !self.sent.contains_key(fingerprint) {
+            let period = self.period;
+            self.sent.retain(|_, sent| {
+                expire(sent, now, period);
+                !sent.is_empty()
+            });
+        }
+
+        self.sent
+            .entry(fingerprint.to_owned())
+            .or_default()
+            .push_back(now);
+    }
+}
+
+/// Forgets the events sent before the last period.
+fn expire(sent: &mut VecDeque<Instant>, now: Instant, period: Duration) {
+    while sent
+        .front()
+        .map_or(false, |sent_at| now.duration_since(*sent_at) >= period)
+    {
+        sent.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_the_rate_of_each_fingerprint() {
+        let mut rate_limiter = RateLimiter::new(&RateLimitConfig {
+            max_events: NonZeroUsize::new(2).unwrap(),
+            period: Duration::from_secs(60),
+        });
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            assert!(rate_limiter.allows("billing\nTimeoutError", start));
+            rate_limiter.record("billing\nTimeoutError", start);
+        }
+        assert!(!rate_limiter.allows("billing\nTimeoutError", start));
+        assert!(rate_limiter.allows("billing\nKeyError", start));
+        assert!(rate_limiter.allows("billing\nTimeoutError", start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn forgets_the_idle_fingerprints() {
+        let mut rate_limiter = RateLimiter::new(&RateLimitConfig {
+            max_events: NonZeroUsize::new(1).unwrap(),
+            period: Duration::from_secs(60),
+        });
+        let start = Instant::now();
+
+        for fingerprint in 0..MAX_IDLE_FINGERPRINTS {
+            rate_limiter.record(&fingerprint.to_string(), start);
+        }
+        rate_limiter.record("recent", start + Duration::from_secs(30));
+        assert_eq!(rate_limiter.sent.len(), MAX_IDLE_FINGERPRINTS + 1);
+
+        rate_limiter.record("new", start + Duration::from_secs(60));
+        assert_eq!(rate_limiter.sent.len(), 2);
+    }
+}
@@ -1,5 +1,5 @@
 use codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{Framer, FramingConfig, SerializerConfig},
     TextSerializerConfig,
 };
 use vector_config::configurable_component;
@@ -154,6 +154,15 @@ impl SinkConfig for SocketSinkConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn pass_through_serializer(&self) -> Option<&SerializerConfig> {
+        match &self.mode {
+            Mode::Tcp(TcpMode { encoding, .. }) => encoding.pass_through_serializer(),
+            Mode::Udp(UdpMode { encoding, .. }) => encoding.pass_through_serializer(),
+            #[cfg(unix)]
+            Mode::Unix(UnixMode { encoding, .. }) => encoding.pass_through_serializer(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -275,6 +284,62 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn tcp_stream_pool() {
+        trace_init();
+
+        let addr = next_addr();
+        let config: SocketSinkConfig = toml::from_str(&format!(
+            r#"
+            mode = "tcp"
+            address = "{}"
+            pool_size = 3
+            in_flight_limit = 1
+            encoding.codec = "json"
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let mut receiver = CountReceiver::receive_lines(addr);
+
+        let (mut lines, events) = random_lines_with_stream(10, 100, None);
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let context = SinkContext::new_test();
+            let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        receiver.connected().await;
+
+        // The connections are read one after the other, which must all be before stopping the
+        // receiver.
+        timeout(Duration::from_secs(10), async {
+            while receiver.count() < lines.len() {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Not all events were received");
+
+        // The events are spread across the connections, out of order.
+        let mut output = receiver
+            .await
+            .into_iter()
+            .map(|received| {
+                let json = serde_json::from_str::<Value>(&received).expect("Invalid JSON");
+                json.get("message").unwrap().as_str().unwrap().to_owned()
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        output.sort();
+        assert_eq!(lines, output);
+    }
+
     // This is a test that checks that we properly receive all events in the
     // case of a proper server side write side shutdown.
     //
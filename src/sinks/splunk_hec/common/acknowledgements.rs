@@ -2,7 +2,7 @@ use std::{
     collections::HashMap,
     num::{NonZeroU64, NonZeroU8},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use hyper::Body;
@@ -17,6 +17,7 @@ use crate::{
     http::HttpClient,
     internal_events::{
         SplunkIndexerAcknowledgementAPIError, SplunkIndexerAcknowledgementAckAdded,
+        SplunkIndexerAcknowledgementAckFinalized, SplunkIndexerAcknowledgementAcksPending,
         SplunkIndexerAcknowledgementAcksRemoved,
     },
 };
@@ -39,6 +40,15 @@ pub struct HecClientAcknowledgementsConfig {
     /// The maximum number of times an acknowledgement ID will be queried for its status.
     pub retry_limit: NonZeroU8,
 
+    /// The maximum amount of time to wait for an acknowledgement ID to be acknowledged, after
+    /// which its events are rejected.
+    ///
+    /// When unset, an acknowledgement ID is only rejected once it has been queried `retry_limit`
+    /// times.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 300))]
+    pub timeout_secs: Option<NonZeroU64>,
+
     /// The maximum number of pending acknowledgements from events sent to the Splunk HEC collector.
     ///
     /// Once reached, the sink will begin applying backpressure.
@@ -59,6 +69,7 @@ impl Default for HecClientAcknowledgementsConfig {
             indexer_acknowledgements_enabled: true,
             query_interval: NonZeroU8::new(10).unwrap(),
             retry_limit: NonZeroU8::new(30).unwrap(),
+            timeout_secs: None,
             max_pending_acks: NonZeroU64::new(1_000_000).unwrap(),
             inner: Default::default(),
         }
@@ -83,9 +94,25 @@ pub enum HecAckApiError {
     ServerSendQuery,
 }
 
+struct PendingAck {
+    retries: u8,
+    added_at: Instant,
+    ack_event_status_sender: Sender<EventStatus>,
+}
+
+impl PendingAck {
+    fn finalize(self, status: EventStatus) {
+        emit!(SplunkIndexerAcknowledgementAckFinalized {
+            age: self.added_at.elapsed(),
+        });
+        let _ = self.ack_event_status_sender.send(status);
+    }
+}
+
 struct HecAckClient {
-    acks: HashMap<u64, (u8, Sender<EventStatus>)>,
+    acks: HashMap<u64, PendingAck>,
     retry_limit: u8,
+    timeout: Option<Duration>,
     client: HttpClient,
     http_request_builder: Arc<HttpRequestBuilder>,
 }
@@ -93,12 +120,14 @@ struct HecAckClient {
 impl HecAckClient {
     fn new(
         retry_limit: u8,
+        timeout: Option<Duration>,
         client: HttpClient,
         http_request_builder: Arc<HttpRequestBuilder>,
     ) -> Self {
         Self {
             acks: HashMap::new(),
             retry_limit,
+            timeout,
             client,
             http_request_builder,
         }
@@ -106,13 +135,28 @@ impl HecAckClient {
 
     /// Adds an ack id to be queried
     fn add(&mut self, ack_id: u64, ack_event_status_sender: Sender<EventStatus>) {
-        self.acks
-            .insert(ack_id, (self.retry_limit, ack_event_status_sender));
+        self.acks.insert(
+            ack_id,
+            PendingAck {
+                retries: self.retry_limit,
+                added_at: Instant::now(),
+                ack_event_status_sender,
+            },
+        );
         emit!(SplunkIndexerAcknowledgementAckAdded);
     }
 
     /// Queries Splunk HEC with stored ack ids and finalizes events that are successfully acked
     async fn run(&mut self) {
+        emit!(SplunkIndexerAcknowledgementAcksPending {
+            oldest_age: self
+                .acks
+                .values()
+                .map(|ack| ack.added_at.elapsed())
+                .max()
+                .unwrap_or_default(),
+        });
+
         let ack_query_body = self.get_ack_query_body();
         if !ack_query_body.acks.is_empty() {
             let ack_query_response = self.send_ack_query_request(&ack_query_body).await;
@@ -162,8 +206,8 @@ impl HecAckClient {
     fn finalize_delivered_ack_ids(&mut self, ack_ids: &[u64]) {
         let mut removed_count = 0.0;
         for ack_id in ack_ids {
-            if let Some((_, ack_event_status_sender)) = self.acks.remove(ack_id) {
-                let _ = ack_event_status_sender.send(EventStatus::Delivered);
+            if let Some(ack) = self.acks.remove(ack_id) {
+                ack.finalize(EventStatus::Delivered);
                 removed_count += 1.0;
                 debug!(message = "Finalized ack id.", ?ack_id);
             }
@@ -180,10 +224,18 @@ impl HecAckClient {
         }
     }
 
-    /// Decrements retry count on all stored ack ids by 1
+    /// Decrements retry count on all stored ack ids by 1, and exhausts the retries of those which
+    /// have timed out
     fn decrement_retries(&mut self) {
-        for (retries, _) in self.acks.values_mut() {
-            *retries = retries.checked_sub(1).unwrap_or(0);
+        for ack in self.acks.values_mut() {
+            let timed_out = self
+                .timeout
+                .map_or(false, |timeout| ack.added_at.elapsed() >= timeout);
+            ack.retries = if timed_out {
+                0
+            } else {
+                ack.retries.saturating_sub(1)
+            };
         }
     }
 
@@ -193,12 +245,12 @@ impl HecAckClient {
         let expired_ack_ids = self
             .acks
             .iter()
-            .filter_map(|(ack_id, (retries, _))| (*retries == 0).then_some(*ack_id))
+            .filter_map(|(ack_id, ack)| (ack.retries == 0).then_some(*ack_id))
             .collect::<Vec<_>>();
         let mut removed_count = 0.0;
         for ack_id in expired_ack_ids {
-            if let Some((_, ack_event_status_sender)) = self.acks.remove(&ack_id) {
-                let _ = ack_event_status_sender.send(status);
+            if let Some(ack) = self.acks.remove(&ack_id) {
+                ack.finalize(status);
                 removed_count += 1.0;
             }
         }
@@ -259,6 +311,9 @@ pub async fn run_acknowledgements(
     ));
     let mut ack_client = HecAckClient::new(
         indexer_acknowledgements.retry_limit.get(),
+        indexer_acknowledgements
+            .timeout_secs
+            .map(|timeout| Duration::from_secs(timeout.get())),
         client,
         http_request_builder,
     );
@@ -283,7 +338,7 @@ pub async fn run_acknowledgements(
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use futures_util::{stream::FuturesUnordered, StreamExt};
     use tokio::sync::oneshot::{self, Receiver};
@@ -301,6 +356,10 @@ mod tests {
     };
 
     fn get_ack_client(retry_limit: u8) -> HecAckClient {
+        get_ack_client_with_timeout(retry_limit, None)
+    }
+
+    fn get_ack_client_with_timeout(retry_limit: u8, timeout: Option<Duration>) -> HecAckClient {
         let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
         let http_request_builder = HttpRequestBuilder::new(
             String::from(""),
@@ -308,7 +367,7 @@ mod tests {
             String::from(""),
             Compression::default(),
         );
-        HecAckClient::new(retry_limit, client, Arc::new(http_request_builder))
+        HecAckClient::new(retry_limit, timeout, client, Arc::new(http_request_builder))
     }
 
     fn populate_ack_client(
@@ -378,4 +437,19 @@ mod tests {
             assert_eq!(EventStatus::Rejected, status.unwrap());
         }
     }
+
+    #[tokio::test]
+    async fn test_expire_timed_out_ack_ids() {
+        let mut ack_client = get_ack_client_with_timeout(30, Some(Duration::ZERO));
+        let ack_ids = (0..100).collect::<Vec<u64>>();
+        let ack_status_rxs = populate_ack_client(&mut ack_client, &ack_ids);
+
+        ack_client.decrement_retries();
+        ack_client.expire_ack_ids_with_status(EventStatus::Rejected);
+        assert!(ack_client.get_ack_query_body().acks.is_empty());
+        let mut statuses = ack_status_rxs.into_iter().collect::<FuturesUnordered<_>>();
+        while let Some(status) = statuses.next().await {
+            assert_eq!(EventStatus::Rejected, status.unwrap());
+        }
+    }
 }
@@ -12,6 +12,7 @@ use snafu::ResultExt;
 use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::PollSemaphore;
 use tower::Service;
+use tracing::Instrument;
 use uuid::Uuid;
 use vector_common::request_metadata::MetaDescriptive;
 use vector_core::event::EventStatus;
@@ -59,12 +60,15 @@ where
         let max_pending_acks = indexer_acknowledgements.max_pending_acks.get();
         let tx = if let Some(ack_client) = ack_client {
             let (tx, rx) = mpsc::channel(128);
-            tokio::spawn(run_acknowledgements(
-                rx,
-                ack_client,
-                Arc::clone(&http_request_builder),
-                indexer_acknowledgements,
-            ));
+            tokio::spawn(
+                run_acknowledgements(
+                    rx,
+                    ack_client,
+                    Arc::clone(&http_request_builder),
+                    indexer_acknowledgements,
+                )
+                .in_current_span(),
+            );
             Some(tx)
         } else {
             None
@@ -0,0 +1,13 @@
+//! The `sql` sink.
+//!
+//! Inserts events into the tables of PostgreSQL and MySQL databases, in batches of multi-row
+//! `INSERT` statements with bound parameters, through [`sqlx`][sqlx].
+//!
+//! [sqlx]: https://docs.rs/sqlx
+mod config;
+#[cfg(all(test, feature = "sql-integration-tests"))]
+mod integration_tests;
+mod service;
+mod sink;
+
+pub use self::config::SqlSinkConfig;
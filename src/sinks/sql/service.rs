@@ -0,0 +1,224 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use sqlx::{types::Json, Database, Encode, MySql, Postgres, QueryBuilder, Type};
+use tower::Service;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_core::stream::DriverResponse;
+
+use super::config::{Dialect, SqlPool, Statement};
+use crate::sinks::util::retries::RetryLogic;
+
+/// The maximum number of parameters that can be bound to a statement, with both PostgreSQL and
+/// MySQL.
+const MAX_BIND_PARAMETERS: usize = 65_535;
+
+/// A value inserted into a column.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum SqlValue {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    Timestamp(DateTime<Utc>),
+    Json(serde_json::Value),
+}
+
+#[derive(Clone)]
+pub(super) struct SqlRequest {
+    pub(super) table: String,
+    pub(super) rows: Arc<Vec<Vec<SqlValue>>>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for SqlRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SqlRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub(super) struct SqlResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for SqlResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct SqlService {
+    pool: SqlPool,
+    dialect: Dialect,
+    statement: Arc<Statement>,
+}
+
+impl SqlService {
+    pub(super) const fn new(pool: SqlPool, dialect: Dialect, statement: Arc<Statement>) -> Self {
+        Self {
+            pool,
+            dialect,
+            statement,
+        }
+    }
+
+    /// Inserts the rows in a single transaction, with as few statements as the limit on bound
+    /// parameters allows.
+    async fn insert(&self, request: &SqlRequest) -> Result<(), sqlx::Error> {
+        let statement = &self.statement;
+        let head = format!(
+            "{} {} {} ",
+            statement.insert,
+            self.dialect.quote_table(&request.table),
+            statement.columns
+        );
+        let columns = request.rows.first().map_or(1, Vec::len).max(1);
+        let chunk_size = (MAX_BIND_PARAMETERS / columns).max(1);
+
+        match &self.pool {
+            SqlPool::Postgres(pool) => {
+                let mut transaction = pool.begin().await?;
+                for rows in request.rows.chunks(chunk_size) {
+                    let mut builder = QueryBuilder::<Postgres>::new(&head);
+                    push_values(&mut builder, rows);
+                    builder.push(&statement.on_conflict);
+                    builder.build().execute(&mut transaction).await?;
+                }
+                transaction.commit().await
+            }
+            SqlPool::MySql(pool) => {
+                let mut transaction = pool.begin().await?;
+                for rows in request.rows.chunks(chunk_size) {
+                    let mut builder = QueryBuilder::<MySql>::new(&head);
+                    push_values(&mut builder, rows);
+                    builder.push(&statement.on_conflict);
+                    builder.build().execute(&mut transaction).await?;
+                }
+                transaction.commit().await
+            }
+        }
+    }
+}
+
+/// Pushes the rows as a `VALUES` clause, binding their values as parameters.
+///
+/// `NULL`s are written as literals, as a bound `NULL` has the type of its parameter, which
+/// PostgreSQL may not be able to assign to the column.
+fn push_values<'a, DB>(builder: &mut QueryBuilder<'a, DB>, rows: &[Vec<SqlValue>])
+where
+    DB: Database,
+    bool: Encode<'a, DB> + Type<DB>,
+    i64: Encode<'a, DB> + Type<DB>,
+    f64: Encode<'a, DB> + Type<DB>,
+    String: Encode<'a, DB> + Type<DB>,
+    DateTime<Utc>: Encode<'a, DB> + Type<DB>,
+    Json<serde_json::Value>: Encode<'a, DB> + Type<DB>,
+{
+    builder.push_values(rows, |mut separated, row| {
+        for value in row {
+            match value {
+                SqlValue::Null => separated.push("NULL"),
+                SqlValue::Boolean(value) => separated.push_bind(*value),
+                SqlValue::Integer(value) => separated.push_bind(*value),
+                SqlValue::Float(value) => separated.push_bind(*value),
+                SqlValue::Text(value) => separated.push_bind(value.clone()),
+                SqlValue::Timestamp(value) => separated.push_bind(*value),
+                SqlValue::Json(value) => separated.push_bind(Json(value.clone())),
+            };
+        }
+    });
+}
+
+impl Service<SqlRequest> for SqlService {
+    type Response = SqlResponse;
+    type Error = sqlx::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SqlRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            service.insert(&request).await?;
+
+            Ok(SqlResponse {
+                metadata: request.metadata,
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct SqlRetryLogic;
+
+impl RetryLogic for SqlRetryLogic {
+    type Error = sqlx::Error;
+    type Response = SqlResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            sqlx::Error::Io(_)
+            | sqlx::Error::Protocol(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::WorkerCrashed => true,
+            // Errors reported by the database, such as constraint violations, fail the same way
+            // when retried, except for the ones caused by the state of the database or of the
+            // connection.
+            sqlx::Error::Database(error) => error.code().map_or(false, |code| is_transient(&code)),
+            _ => false,
+        }
+    }
+}
+
+/// Returns whether the SQLSTATE code is in one of the classes of transient errors: connection
+/// exceptions, transaction rollbacks (such as deadlocks), insufficient resources, and operator
+/// interventions (such as shutdowns).
+fn is_transient(sqlstate: &str) -> bool {
+    matches!(sqlstate.get(..2), Some("08" | "40" | "53" | "57"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_errors() {
+        assert!(is_transient("40001"));
+        assert!(is_transient("08006"));
+        assert!(is_transient("57P01"));
+        assert!(!is_transient("23505"));
+        assert!(!is_transient("42P01"));
+        assert!(!is_transient("4"));
+    }
+}
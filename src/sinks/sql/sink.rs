@@ -0,0 +1,246 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use futures::{stream::BoxStream, StreamExt};
+use vector_common::finalization::{EventFinalizers, EventStatus, Finalizable};
+use vector_core::{
+    event::{Event, LogEvent, Value},
+    partition::Partitioner,
+    sink::StreamSink,
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::service::{SqlRequest, SqlRetryLogic, SqlService, SqlValue};
+use crate::{
+    codecs::Transformer,
+    internal_events::TemplateRenderingError,
+    sinks::util::{builder::SinkBuilderExt, metadata::RequestMetadataBuilder, service::Svc},
+    template::{self, Template},
+};
+
+/// A column, along with the template of its values.
+#[derive(Clone, Debug)]
+pub(super) struct SqlColumn {
+    pub(super) name: String,
+    pub(super) value: Template,
+}
+
+impl SqlColumn {
+    fn value(&self, log: &LogEvent) -> Result<SqlValue, template::TemplateRenderingError> {
+        match self.value.get_field_reference() {
+            Some(field) => Ok(to_sql_value(log.get(field))),
+            None => self.value.render_string(log).map(SqlValue::Text),
+        }
+    }
+}
+
+fn to_sql_value(value: Option<&Value>) -> SqlValue {
+    match value {
+        None | Some(Value::Null) => SqlValue::Null,
+        Some(Value::Bytes(bytes)) => SqlValue::Text(String::from_utf8_lossy(bytes).into_owned()),
+        Some(Value::Integer(integer)) => SqlValue::Integer(*integer),
+        Some(Value::Float(float)) => SqlValue::Float(float.into_inner()),
+        Some(Value::Boolean(boolean)) => SqlValue::Boolean(*boolean),
+        Some(Value::Timestamp(timestamp)) => SqlValue::Timestamp(*timestamp),
+        Some(value @ (Value::Object(_) | Value::Array(_))) => {
+            SqlValue::Json(serde_json::to_value(value).expect("values are valid JSON"))
+        }
+        Some(value) => SqlValue::Text(value.to_string_lossy().into_owned()),
+    }
+}
+
+/// Partitions events by the table they're inserted into.
+struct TablePartitioner(Template);
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub(super) struct SqlSink {
+    table: Template,
+    columns: Arc<Vec<SqlColumn>>,
+    transformer: Transformer,
+    batch_settings: BatcherSettings,
+    service: Svc<SqlService, SqlRetryLogic>,
+}
+
+impl SqlSink {
+    pub(super) fn new(
+        table: Template,
+        columns: Vec<SqlColumn>,
+        transformer: Transformer,
+        batch_settings: BatcherSettings,
+        service: Svc<SqlService, SqlRetryLogic>,
+    ) -> Self {
+        Self {
+            table,
+            columns: Arc::new(columns),
+            transformer,
+            batch_settings,
+            service,
+        }
+    }
+
+    fn encode(
+        &self,
+        log: &LogEvent,
+    ) -> Result<Vec<SqlValue>, (&str, template::TemplateRenderingError)> {
+        self.columns
+            .iter()
+            .map(|column| {
+                column
+                    .value(log)
+                    .map_err(|error| (column.name.as_str(), error))
+            })
+            .collect()
+    }
+
+    /// Builds a request from the rows of the events whose values could be rendered.
+    fn build_request(&self, table: Option<String>, mut events: Vec<Event>) -> Option<SqlRequest> {
+        let table = match table {
+            Some(table) => table,
+            None => {
+                // The error is emitted by the partitioner.
+                events
+                    .take_finalizers()
+                    .update_status(EventStatus::Rejected);
+                return None;
+            }
+        };
+
+        let mut rows = Vec::with_capacity(events.len());
+        let mut finalizers = EventFinalizers::default();
+        let mut events_byte_size = 0;
+        let mut json_byte_size = 0;
+
+        for mut event in events {
+            let event_finalizers = event.take_finalizers();
+            self.transformer.transform(&mut event);
+            let log = event.into_log();
+
+            match self.encode(&log) {
+                Ok(row) => {
+                    events_byte_size += log.size_of();
+                    json_byte_size += log.estimated_json_encoded_size_of();
+                    finalizers.merge(event_finalizers);
+                    rows.push(row);
+                }
+                Err((column, error)) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(column),
+                        drop_event: true,
+                    });
+                    event_finalizers.update_status(EventStatus::Rejected);
+                }
+            }
+        }
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        let metadata = RequestMetadataBuilder::new(rows.len(), events_byte_size, json_byte_size)
+            .with_request_size(NonZeroUsize::new(json_byte_size.max(1)).expect("can't be zero"));
+
+        Some(SqlRequest {
+            table,
+            rows: Arc::new(rows),
+            finalizers,
+            metadata,
+        })
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = self.service.clone();
+        let partitioner = TablePartitioner(self.table.clone());
+
+        input
+            .batched_partitioned(partitioner, self.batch_settings)
+            .filter_map(|(table, events)| std::future::ready(self.build_request(table, events)))
+            .into_driver(service)
+            .protocol("tcp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for SqlSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use value::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(to_sql_value(None), SqlValue::Null);
+        assert_eq!(to_sql_value(Some(&Value::Null)), SqlValue::Null);
+        assert_eq!(
+            to_sql_value(Some(&Value::from("foo"))),
+            SqlValue::Text("foo".to_owned())
+        );
+        assert_eq!(to_sql_value(Some(&Value::from(3))), SqlValue::Integer(3));
+        assert_eq!(
+            to_sql_value(Some(&Value::from(true))),
+            SqlValue::Boolean(true)
+        );
+
+        let timestamp = Utc.timestamp_opt(1672531200, 0).unwrap();
+        assert_eq!(
+            to_sql_value(Some(&Value::from(timestamp))),
+            SqlValue::Timestamp(timestamp)
+        );
+        assert_eq!(
+            to_sql_value(Some(&Value::from(btreemap! { "code" => 200 }))),
+            SqlValue::Json(serde_json::json!({ "code": 200 }))
+        );
+    }
+
+    #[test]
+    fn renders_columns() {
+        let column = |value: &str| SqlColumn {
+            name: "column".to_owned(),
+            value: Template::try_from(value).unwrap(),
+        };
+
+        let mut log = LogEvent::from("hello");
+        log.insert("status.code", 200);
+
+        assert_eq!(
+            column("{{ status.code }}").value(&log),
+            Ok(SqlValue::Integer(200))
+        );
+        assert_eq!(column("{{ missing }}").value(&log), Ok(SqlValue::Null));
+        assert_eq!(
+            column("code {{ status.code }}").value(&log),
+            Ok(SqlValue::Text("code 200".to_owned()))
+        );
+        assert_eq!(
+            column("static").value(&log),
+            Ok(SqlValue::Text("static".to_owned()))
+        );
+        assert!(column("code {{ missing }}").value(&log).is_err());
+    }
+}
@@ -7,6 +7,8 @@ use std::{
 use bytes::{BufMut, BytesMut};
 use futures::{future, stream, SinkExt, TryFutureExt};
 use futures_util::FutureExt;
+use rand::{thread_rng, Rng};
+use snafu::Snafu;
 use tokio_util::codec::Encoder;
 use tower::{Service, ServiceBuilder};
 
@@ -52,6 +54,19 @@ pub struct StatsdSinkConfig {
     #[serde(flatten)]
     pub mode: Mode,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tag_format: TagFormat,
+
+    /// The rate at which incremental counters are sampled before being sent.
+    ///
+    /// Must be greater than `0` and at most `1`. Each incremental counter is sent with this
+    /// probability, and annotated with the sample rate (`|@<rate>`) so that the receiving server
+    /// can scale the value back up. This reduces traffic for very high-volume counters at the
+    /// cost of precision. When unset, every counter is sent.
+    #[configurable(metadata(docs::examples = 0.1))]
+    pub counter_sample_rate: Option<f64>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -78,6 +93,36 @@ pub enum Mode {
     Unix(UnixSinkConfig),
 }
 
+/// The format used to encode metric tags.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagFormat {
+    /// Tags are appended to the metric in the DogStatsD format.
+    ///
+    /// For example, `name:1|c|#tag:value,bare_tag`.
+    #[default]
+    Dogstatsd,
+
+    /// Tags are appended to the metric name in the InfluxDB (Telegraf) format.
+    ///
+    /// For example, `name,tag=value:1|c`. Bare tags are dropped, and only the last value of
+    /// multi-valued tags is sent.
+    Influxdb,
+
+    /// Tags are not sent.
+    None,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "`counter_sample_rate` must be greater than 0 and at most 1, got {}",
+        rate
+    ))]
+    InvalidSampleRate { rate: f64 },
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct StatsdDefaultBatchSettings;
 
@@ -88,6 +133,9 @@ impl SinkBatchSettings for StatsdDefaultBatchSettings {
 }
 
 /// UDP configuration.
+///
+/// Multiple metrics are packed into each datagram, separated by newlines, up to `batch.max_bytes`
+/// bytes. This should be set to fit the MTU of the network path to the server.
 #[configurable_component]
 #[derive(Clone, Debug)]
 pub struct StatsdUdpConfig {
@@ -111,6 +159,8 @@ impl GenerateConfig for StatsdSinkConfig {
                 batch: Default::default(),
                 udp: UdpSinkConfig::from_address(default_address().to_string()),
             }),
+            tag_format: Default::default(),
+            counter_sample_rate: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -123,16 +173,25 @@ impl SinkConfig for StatsdSinkConfig {
         &self,
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let default_namespace = self.default_namespace.clone();
-        let mut encoder = StatsdEncoder { default_namespace };
+        if let Some(rate) = self.counter_sample_rate {
+            if !(rate > 0.0 && rate <= 1.0) {
+                return Err(BuildError::InvalidSampleRate { rate }.into());
+            }
+        }
+
+        let mut encoder = StatsdEncoder {
+            default_namespace: self.default_namespace.clone(),
+            tag_format: self.tag_format,
+            counter_sample_rate: self.counter_sample_rate,
+        };
         match &self.mode {
             Mode::Tcp(config) => config.build(Default::default(), encoder),
             Mode::Udp(config) => {
                 // 1432 bytes is a recommended packet size to fit into MTU
                 // https://github.com/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets
-                // However we need to leave some space for +1 extra trailing event in the buffer.
-                // Also one might keep an eye on server side limitations, like
-                // mentioned here https://github.com/DataDog/dd-agent/issues/2638
+                // The default of 1300 bytes leaves some headroom for encapsulation, and `Buffer`
+                // never grows a packet beyond `max_bytes`. Also one might keep an eye on server
+                // side limitations, like mentioned here https://github.com/DataDog/dd-agent/issues/2638
                 let batch = config.batch.into_batch_settings()?;
                 let (service, healthcheck) = config.udp.build_service()?;
                 let service = StatsdSvc { inner: service };
@@ -186,29 +245,56 @@ fn encode_tags(tags: &MetricTags) -> String {
     parts.join(",")
 }
 
+// InfluxDB line protocol reserves these characters in tag keys and values, and StatsD reserves
+// `:` and `|`, so they are replaced rather than escaped as most servers don't support escaping.
+fn sanitize_influx_tag(value: &str) -> String {
+    value.replace([',', '=', ' ', ':', '|'], "_")
+}
+
+fn encode_influx_tags(tags: &MetricTags) -> String {
+    tags.iter_single()
+        .map(|(name, value)| {
+            format!(
+                ",{}={}",
+                sanitize_influx_tag(name),
+                sanitize_influx_tag(value)
+            )
+        })
+        .collect()
+}
+
 fn push_event<V: Display>(
     buf: &mut Vec<String>,
     metric: &Metric,
     val: V,
     metric_type: &str,
-    sample_rate: Option<u32>,
+    sample_rate: Option<f64>,
+    tag_format: TagFormat,
 ) {
-    buf.push(format!("{}:{}|{}", metric.name(), val, metric_type));
+    let name = match (tag_format, metric.tags()) {
+        (TagFormat::Influxdb, Some(tags)) => {
+            format!("{}{}", metric.name(), encode_influx_tags(tags))
+        }
+        _ => metric.name().to_owned(),
+    };
+    buf.push(format!("{}:{}|{}", name, val, metric_type));
 
     if let Some(sample_rate) = sample_rate {
-        if sample_rate != 1 {
-            buf.push(format!("@{}", 1.0 / f64::from(sample_rate)))
+        if sample_rate < 1.0 {
+            buf.push(format!("@{}", sample_rate))
         }
     };
 
-    if let Some(t) = metric.tags() {
+    if let (TagFormat::Dogstatsd, Some(t)) = (tag_format, metric.tags()) {
         buf.push(format!("#{}", encode_tags(t)));
     };
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct StatsdEncoder {
     default_namespace: Option<String>,
+    tag_format: TagFormat,
+    counter_sample_rate: Option<f64>,
 }
 
 impl Encoder<Event> for StatsdEncoder {
@@ -218,16 +304,33 @@ impl Encoder<Event> for StatsdEncoder {
         let mut buf = Vec::new();
 
         let metric = event.as_metric();
+        let tag_format = self.tag_format;
         match metric.value() {
             MetricValue::Counter { value } => {
-                push_event(&mut buf, metric, value, "c", None);
+                let sample_rate = match metric.kind() {
+                    MetricKind::Incremental => self.counter_sample_rate,
+                    MetricKind::Absolute => None,
+                };
+                if let Some(rate) = sample_rate {
+                    if thread_rng().gen::<f64>() >= rate {
+                        return Ok(());
+                    }
+                }
+                push_event(&mut buf, metric, value, "c", sample_rate, tag_format);
             }
             MetricValue::Gauge { value } => {
                 match metric.kind() {
-                    MetricKind::Incremental => {
-                        push_event(&mut buf, metric, format!("{:+}", value), "g", None)
+                    MetricKind::Incremental => push_event(
+                        &mut buf,
+                        metric,
+                        format!("{:+}", value),
+                        "g",
+                        None,
+                        tag_format,
+                    ),
+                    MetricKind::Absolute => {
+                        push_event(&mut buf, metric, value, "g", None, tag_format)
                     }
-                    MetricKind::Absolute => push_event(&mut buf, metric, value, "g", None),
                 };
             }
             MetricValue::Distribution { samples, statistic } => {
@@ -253,7 +356,8 @@ impl Encoder<Event> for StatsdEncoder {
                         metric,
                         sample.value,
                         metric_type,
-                        Some(sample.rate),
+                        Some(1.0 / f64::from(sample.rate)),
+                        tag_format,
                     );
                     let msg = encode_namespace(
                         metric.namespace().or(self.default_namespace.as_deref()),
@@ -266,7 +370,7 @@ impl Encoder<Event> for StatsdEncoder {
             }
             MetricValue::Set { values } => {
                 for val in values {
-                    push_event(&mut buf, metric, val, "s", None);
+                    push_event(&mut buf, metric, val, "s", None, tag_format);
                 }
             }
             _ => {
@@ -393,9 +497,7 @@ mod test {
         )
         .with_tags(Some(tags()));
         let event = Event::Metric(metric1.clone());
-        let mut encoder = StatsdEncoder {
-            default_namespace: None,
-        };
+        let mut encoder = StatsdEncoder::default();
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
         let metric2 = parse(from_utf8(&frame).unwrap().trim()).unwrap();
@@ -411,9 +513,7 @@ mod test {
             MetricValue::Counter { value: 1.5 },
         );
         let event = Event::Metric(metric1);
-        let mut encoder = StatsdEncoder {
-            default_namespace: None,
-        };
+        let mut encoder = StatsdEncoder::default();
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
         // The statsd parser will parse the counter as Incremental,
@@ -431,9 +531,7 @@ mod test {
         )
         .with_tags(Some(tags()));
         let event = Event::Metric(metric1.clone());
-        let mut encoder = StatsdEncoder {
-            default_namespace: None,
-        };
+        let mut encoder = StatsdEncoder::default();
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
         let metric2 = parse(from_utf8(&frame).unwrap().trim()).unwrap();
@@ -450,9 +548,7 @@ mod test {
         )
         .with_tags(Some(tags()));
         let event = Event::Metric(metric1.clone());
-        let mut encoder = StatsdEncoder {
-            default_namespace: None,
-        };
+        let mut encoder = StatsdEncoder::default();
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
         let metric2 = parse(from_utf8(&frame).unwrap().trim()).unwrap();
@@ -483,9 +579,7 @@ mod test {
         .with_tags(Some(tags()));
 
         let event = Event::Metric(metric1);
-        let mut encoder = StatsdEncoder {
-            default_namespace: None,
-        };
+        let mut encoder = StatsdEncoder::default();
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
         let metric2 = parse(from_utf8(&frame).unwrap().trim()).unwrap();
@@ -524,9 +618,7 @@ mod test {
         )
         .with_tags(Some(tags()));
         let event = Event::Metric(metric1);
-        let mut encoder = StatsdEncoder {
-            default_namespace: None,
-        };
+        let mut encoder = StatsdEncoder::default();
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
 
@@ -552,9 +644,7 @@ mod test {
         )
         .with_tags(Some(tags()));
         let event = Event::Metric(metric1.clone());
-        let mut encoder = StatsdEncoder {
-            default_namespace: None,
-        };
+        let mut encoder = StatsdEncoder::default();
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
         let metric2 = parse(from_utf8(&frame).unwrap().trim()).unwrap();
@@ -562,6 +652,98 @@ mod test {
         vector_common::assert_event_data_eq!(metric1, metric2);
     }
 
+    #[test]
+    fn test_encode_influxdb_tags() {
+        let metric = Metric::new(
+            "counter",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.5 },
+        )
+        .with_namespace(Some("vector"))
+        .with_tags(Some(metric_tags!(
+            "normal_tag" => "value",
+            "multi_value" => "true",
+            "multi_value" => "false",
+            "bare_tag" => TagValue::Bare,
+            "odd=tag" => "a,b:c",
+        )));
+        let mut encoder = StatsdEncoder {
+            tag_format: TagFormat::Influxdb,
+            ..Default::default()
+        };
+        let mut frame = BytesMut::new();
+        encoder.encode(Event::Metric(metric), &mut frame).unwrap();
+        assert_eq!(
+            frame,
+            "vector.counter,multi_value=false,normal_tag=value,odd_tag=a_b_c:1.5|c\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_no_tags() {
+        let metric = Metric::new(
+            "gauge",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        )
+        .with_tags(Some(tags()));
+        let mut encoder = StatsdEncoder {
+            tag_format: TagFormat::None,
+            ..Default::default()
+        };
+        let mut frame = BytesMut::new();
+        encoder.encode(Event::Metric(metric), &mut frame).unwrap();
+        assert_eq!(frame, "gauge:1.5|g\n");
+    }
+
+    #[test]
+    fn test_encode_sampled_counter() {
+        let mut encoder = StatsdEncoder {
+            counter_sample_rate: Some(0.5),
+            ..Default::default()
+        };
+        let mut sent = 0;
+        for _ in 0..1000 {
+            let metric = Metric::new(
+                "counter",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            );
+            let mut frame = BytesMut::new();
+            encoder.encode(Event::Metric(metric), &mut frame).unwrap();
+            if !frame.is_empty() {
+                assert_eq!(frame, "counter:1|c|@0.5\n");
+                sent += 1;
+            }
+        }
+        assert!((350..=650).contains(&sent), "sent {} counters", sent);
+
+        // Absolute counters are never sampled.
+        let metric = Metric::new(
+            "counter",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let mut frame = BytesMut::new();
+        encoder.encode(Event::Metric(metric), &mut frame).unwrap();
+        assert_eq!(frame, "counter:1|c\n");
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_sample_rate() {
+        let config = StatsdSinkConfig {
+            default_namespace: None,
+            mode: Mode::Udp(StatsdUdpConfig {
+                batch: Default::default(),
+                udp: UdpSinkConfig::from_address(default_address().to_string()),
+            }),
+            tag_format: Default::default(),
+            counter_sample_rate: Some(1.5),
+            acknowledgements: Default::default(),
+        };
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_send_to_statsd() {
         trace_init();
@@ -576,6 +758,8 @@ mod test {
                 batch,
                 udp: UdpSinkConfig::from_address(addr.to_string()),
             }),
+            tag_format: Default::default(),
+            counter_sample_rate: None,
             acknowledgements: Default::default(),
         };
 
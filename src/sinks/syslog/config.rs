@@ -0,0 +1,296 @@
+use codecs::TextSerializerConfig;
+use lookup::lookup_v2::OptionalTargetPath;
+use vector_config::configurable_component;
+
+use super::encoder::SyslogEncoder;
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{tcp::TcpSinkConfig, udp::UdpSinkConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+};
+
+/// Configuration for the `syslog` sink.
+#[configurable_component(sink("syslog"))]
+#[derive(Clone, Debug)]
+pub struct SyslogSinkConfig {
+    #[serde(flatten)]
+    pub mode: Mode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: SyslogFormat,
+
+    /// The facility of the messages.
+    ///
+    /// Both the names and the numeric codes of the facilities are accepted.
+    ///
+    /// By default, the `facility` field of the events is used, as set by the `syslog` source,
+    /// falling back to `user`.
+    #[configurable(metadata(docs::examples = "local0", docs::examples = "{{ facility }}"))]
+    pub facility: Option<Template>,
+
+    /// The severity of the messages.
+    ///
+    /// Both the names and the numeric codes of the severities are accepted.
+    ///
+    /// By default, the `severity` field of the events is used, as set by the `syslog` source,
+    /// falling back to `info`.
+    #[configurable(metadata(docs::examples = "warning", docs::examples = "{{ level }}"))]
+    pub severity: Option<Template>,
+
+    /// The hostname of the messages.
+    ///
+    /// By default, the `hostname` field of the events is used, as set by the `syslog` source,
+    /// falling back to the `host` field.
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    pub hostname: Option<Template>,
+
+    /// The application name of the messages, used as the tag of the RFC 3164 format.
+    ///
+    /// By default, the `appname` field of the events is used, as set by the `syslog` source.
+    #[configurable(metadata(docs::examples = "my-app", docs::examples = "{{ service }}"))]
+    pub app_name: Option<Template>,
+
+    /// The process ID of the messages.
+    ///
+    /// By default, the `procid` field of the events is used, as set by the `syslog` source.
+    #[configurable(metadata(docs::examples = "{{ pid }}"))]
+    pub proc_id: Option<Template>,
+
+    /// The message ID of the messages. This is only part of the RFC 5424 format.
+    ///
+    /// By default, the `msgid` field of the events is used, as set by the `syslog` source.
+    #[configurable(metadata(docs::examples = "ID47"))]
+    pub msg_id: Option<Template>,
+
+    /// The field holding the structured data of the messages, as an object of SD elements, which
+    /// are objects of parameters. This is only part of the RFC 5424 format.
+    ///
+    /// By default, the structured data is taken from the fields set by the `syslog` source: the
+    /// `structured_data` metadata with the Vector namespace, or the top-level fields named after an
+    /// SD-ID otherwise, such as `exampleSDID@32473` or `timeQuality`.
+    ///
+    /// Set this to `""` to leave out the structured data.
+    #[configurable(metadata(docs::examples = "structured_data"))]
+    pub structured_data_key: Option<OptionalTargetPath>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// Socket mode.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of socket to use."))]
+pub enum Mode {
+    /// Send over TCP.
+    Tcp(TcpMode),
+
+    /// Send over UDP.
+    Udp(UdpMode),
+}
+
+/// TCP configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct TcpMode {
+    #[serde(flatten)]
+    config: TcpSinkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    framing: SyslogFraming,
+}
+
+/// UDP configuration.
+///
+/// Each message is sent in a datagram of its own.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UdpMode {
+    #[serde(flatten)]
+    config: UdpSinkConfig,
+}
+
+/// The format of the syslog messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogFormat {
+    /// The format described in [RFC 5424][rfc5424], with structured data.
+    ///
+    /// [rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+    #[default]
+    Rfc5424,
+
+    /// The older BSD format described in [RFC 3164][rfc3164], for the receivers not supporting
+    /// RFC 5424.
+    ///
+    /// The timestamps are in UTC, and the message IDs and structured data are left out.
+    ///
+    /// [rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+    Rfc3164,
+}
+
+/// How the messages are delimited over TCP, as described in [RFC 6587][rfc6587].
+///
+/// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFraming {
+    /// Each message is prefixed with its length in bytes.
+    ///
+    /// This allows the messages to contain newlines.
+    #[default]
+    OctetCounting,
+
+    /// Each message is followed by a newline.
+    ///
+    /// The newlines within the messages are replaced by spaces.
+    Newline,
+}
+
+impl GenerateConfig for SyslogSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:514"
+            mode = "tcp"
+            encoding.codec = "text""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SyslogSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let framing = match &self.mode {
+            Mode::Tcp(mode) => Some(mode.framing),
+            Mode::Udp(_) => None,
+        };
+        let encoder = SyslogEncoder {
+            format: self.format,
+            framing,
+            facility: self.facility.clone(),
+            severity: self.severity.clone(),
+            hostname: self.hostname.clone(),
+            app_name: self.app_name.clone(),
+            proc_id: self.proc_id.clone(),
+            msg_id: self.msg_id.clone(),
+            structured_data_key: self.structured_data_key.clone(),
+            transformer,
+            encoder: Encoder::<()>::new(serializer),
+        };
+
+        match &self.mode {
+            Mode::Tcp(TcpMode { config, .. }) => config.build(Transformer::default(), encoder),
+            Mode::Udp(UdpMode { config }) => config.build(Transformer::default(), encoder),
+        }
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl SyslogSinkConfig {
+    /// Builds a TCP configuration with the default formatting of the messages.
+    pub fn from_address(address: String) -> Self {
+        Self {
+            mode: Mode::Tcp(TcpMode {
+                config: TcpSinkConfig::from_address(address),
+                framing: SyslogFraming::default(),
+            }),
+            format: SyslogFormat::default(),
+            facility: None,
+            severity: None,
+            hostname: None,
+            app_name: None,
+            proc_id: None,
+            msg_id: None,
+            structured_data_key: None,
+            encoding: TextSerializerConfig::default().into(),
+            acknowledgements: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use tokio::{io::AsyncReadExt, net::TcpListener};
+    use vector_core::event::{Event, LogEvent};
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SyslogSinkConfig>();
+    }
+
+    #[tokio::test]
+    async fn sends_octet_counted_messages() {
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+
+        let mut config = SyslogSinkConfig::from_address(addr.to_string());
+        config.app_name = Some(Template::try_from("vector").unwrap());
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let mut event = LogEvent::from("first line\nsecond line");
+        event.insert("hostname", "example.com");
+        let events = vec![Event::Log(event.clone()), Event::Log(event)];
+        let sent = tokio::spawn(run_and_assert_sink_compliance(
+            sink,
+            stream::iter(events),
+            &SINK_TAGS,
+        ));
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        for _ in 0..2 {
+            let mut length = Vec::new();
+            loop {
+                match socket.read_u8().await.unwrap() {
+                    b' ' => break,
+                    digit => length.push(digit),
+                }
+            }
+            let length = String::from_utf8(length).unwrap().parse().unwrap();
+            let mut message = vec![0; length];
+            socket.read_exact(&mut message).await.unwrap();
+
+            let message = String::from_utf8(message).unwrap();
+            assert!(message.starts_with("<14>1 "), "{}", message);
+            assert!(
+                message.ends_with(" example.com vector - - - first line\nsecond line"),
+                "{}",
+                message
+            );
+        }
+        sent.await.unwrap();
+    }
+}
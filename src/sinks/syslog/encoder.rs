@@ -0,0 +1,458 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{SecondsFormat, Utc};
+use lookup::{event_path, lookup_v2::OptionalTargetPath, metadata_path};
+use tokio_util::codec::Encoder as _;
+use value::Value;
+
+use super::config::{SyslogFormat, SyslogFraming};
+use crate::{
+    codecs::{Encoder, Transformer},
+    event::{Event, LogEvent},
+    internal_events::TemplateRenderingError,
+    template::Template,
+};
+
+/// The names of the facilities, by their numeric code.
+const FACILITIES: [&str; 24] = [
+    "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "uucp", "cron", "authpriv",
+    "ftp", "ntp", "audit", "alert", "clockd", "local0", "local1", "local2", "local3", "local4",
+    "local5", "local6", "local7",
+];
+
+/// The other names some implementations give to the facilities.
+const FACILITY_ALIASES: [(&str, u8); 3] = [("security", 13), ("console", 14), ("solaris-cron", 15)];
+
+/// The names of the severities, by their numeric code.
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// The other names some implementations give to the severities.
+const SEVERITY_ALIASES: [(&str, u8); 7] = [
+    ("emergency", 0),
+    ("panic", 0),
+    ("critical", 2),
+    ("error", 3),
+    ("warn", 4),
+    ("informational", 6),
+    ("trace", 7),
+];
+
+const DEFAULT_FACILITY: u8 = 1;
+const DEFAULT_SEVERITY: u8 = 6;
+
+/// The SD-IDs registered with IANA, which unlike the private ones don't contain an `@`.
+const REGISTERED_SD_IDS: [&str; 3] = ["timeQuality", "origin", "meta"];
+
+/// The value of the RFC 5424 header fields which aren't known.
+const NIL: &str = "-";
+
+#[derive(Clone, Debug)]
+pub(super) struct SyslogEncoder {
+    pub(super) format: SyslogFormat,
+    /// `None` for the transports delimiting the messages themselves.
+    pub(super) framing: Option<SyslogFraming>,
+    pub(super) facility: Option<Template>,
+    pub(super) severity: Option<Template>,
+    pub(super) hostname: Option<Template>,
+    pub(super) app_name: Option<Template>,
+    pub(super) proc_id: Option<Template>,
+    pub(super) msg_id: Option<Template>,
+    pub(super) structured_data_key: Option<OptionalTargetPath>,
+    pub(super) transformer: Transformer,
+    pub(super) encoder: Encoder<()>,
+}
+
+impl tokio_util::codec::Encoder<Event> for SyslogEncoder {
+    type Error = codecs::encoding::Error;
+
+    fn encode(&mut self, mut event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        // The header is built ahead of the transformer, which may remove the fields it comes from.
+        let header = match self.format {
+            SyslogFormat::Rfc5424 => self.rfc5424_header(&event),
+            SyslogFormat::Rfc3164 => self.rfc3164_header(&event),
+        };
+
+        self.transformer.transform(&mut event);
+        let mut body = BytesMut::new();
+        self.encoder.encode(event, &mut body)?;
+
+        let mut message = header.into_bytes();
+        if !body.is_empty() {
+            message.push(b' ');
+            message.extend_from_slice(&body);
+        }
+
+        match self.framing {
+            Some(SyslogFraming::OctetCounting) => {
+                buffer.put_slice(message.len().to_string().as_bytes());
+                buffer.put_u8(b' ');
+                buffer.put_slice(&message);
+            }
+            Some(SyslogFraming::Newline) => {
+                for byte in message.iter_mut().filter(|byte| **byte == b'\n') {
+                    *byte = b' ';
+                }
+                buffer.put_slice(&message);
+                buffer.put_u8(b'\n');
+            }
+            None => buffer.put_slice(&message),
+        }
+
+        Ok(())
+    }
+}
+
+impl SyslogEncoder {
+    fn rfc5424_header(&self, event: &Event) -> String {
+        let log = event.as_log();
+        let mut header = format!(
+            "<{}>1 {} {} {} {} {} ",
+            self.priority(event),
+            timestamp(log).to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            header_field(self.hostname(event), 255),
+            header_field(self.field(&self.app_name, "app_name", "appname", event), 48),
+            header_field(self.field(&self.proc_id, "proc_id", "procid", event), 128),
+            header_field(self.field(&self.msg_id, "msg_id", "msgid", event), 32),
+        );
+
+        let elements = self.structured_data(log);
+        if elements.is_empty() {
+            header.push_str(NIL);
+        }
+        for (id, params) in elements {
+            header.push('[');
+            header.push_str(id);
+            for (name, value) in params.iter().filter(|(name, _)| is_sd_name(name)) {
+                // Arrays are written as repeated parameters, which are allowed by the RFC.
+                match value {
+                    Value::Array(values) => values
+                        .iter()
+                        .for_each(|value| write_sd_param(&mut header, name, value)),
+                    value => write_sd_param(&mut header, name, value),
+                }
+            }
+            header.push(']');
+        }
+
+        header
+    }
+
+    fn rfc3164_header(&self, event: &Event) -> String {
+        let mut header = format!(
+            "<{}>{} {}",
+            self.priority(event),
+            timestamp(event.as_log()).format("%b %e %H:%M:%S"),
+            header_field(self.hostname(event), 255),
+        );
+
+        // The tag is left out when the application isn't known, which the RFC allows.
+        let app_name = self.field(&self.app_name, "app_name", "appname", event);
+        if let Some(app_name) = app_name.filter(|app_name| !app_name.is_empty()) {
+            header.push(' ');
+            header.push_str(&header_field(Some(app_name), 32));
+            if let Some(proc_id) = self.field(&self.proc_id, "proc_id", "procid", event) {
+                write!(header, "[{}]", header_field(Some(proc_id), 128))
+                    .expect("writing to a string doesn't fail");
+            }
+            header.push(':');
+        }
+
+        header
+    }
+
+    fn priority(&self, event: &Event) -> u8 {
+        let facility = self.field(&self.facility, "facility", "facility", event);
+        let facility = code(facility, &FACILITIES, &FACILITY_ALIASES, DEFAULT_FACILITY);
+        let severity = self.field(&self.severity, "severity", "severity", event);
+        let severity = code(severity, &SEVERITIES, &SEVERITY_ALIASES, DEFAULT_SEVERITY);
+        facility * 8 + severity
+    }
+
+    fn hostname(&self, event: &Event) -> Option<String> {
+        self.field(&self.hostname, "hostname", "hostname", event)
+            .or_else(|| {
+                event
+                    .as_log()
+                    .get_host()
+                    .map(|host| host.to_string_lossy().into_owned())
+            })
+    }
+
+    /// Renders the template of an option, or takes the field set by the `syslog` source when the
+    /// option isn't set.
+    fn field(
+        &self,
+        template: &Option<Template>,
+        option: &'static str,
+        field: &'static str,
+        event: &Event,
+    ) -> Option<String> {
+        match template {
+            Some(template) => template
+                .render_string(event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(option),
+                        drop_event: false,
+                    })
+                })
+                .ok(),
+            None => {
+                let log = event.as_log();
+                log.get(event_path!(field))
+                    .or_else(|| log.get(metadata_path!("syslog", field)))
+                    .map(|value| value.to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    fn structured_data<'a>(
+        &self,
+        log: &'a LogEvent,
+    ) -> Vec<(&'a str, &'a BTreeMap<String, Value>)> {
+        let elements = match &self.structured_data_key {
+            Some(key) => object_fields(key.path.as_ref().and_then(|path| log.get(path))),
+            None => match log.get(metadata_path!("syslog", "structured_data")) {
+                Some(value) => object_fields(Some(value)),
+                None => log
+                    .as_map()
+                    .into_iter()
+                    .flatten()
+                    .filter(|(id, _)| is_sd_id_field(id))
+                    .collect(),
+            },
+        };
+
+        elements
+            .into_iter()
+            .filter(|(id, _)| is_sd_name(id))
+            .filter_map(|(id, params)| Some((id.as_str(), params.as_object()?)))
+            .collect()
+    }
+}
+
+fn object_fields(value: Option<&Value>) -> Vec<(&String, &Value)> {
+    value
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn timestamp(log: &LogEvent) -> chrono::DateTime<Utc> {
+    log.get_timestamp()
+        .and_then(Value::as_timestamp)
+        .copied()
+        .unwrap_or_else(Utc::now)
+}
+
+/// Parses the name or the numeric code of a facility or a severity.
+fn code(value: Option<String>, names: &[&str], aliases: &[(&str, u8)], default: u8) -> u8 {
+    let value = match value {
+        Some(value) => value,
+        None => return default,
+    };
+    let code = value
+        .parse::<u8>()
+        .ok()
+        .filter(|code| usize::from(*code) < names.len())
+        .or_else(|| {
+            let position = names
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(&value));
+            position.map(|position| position as u8)
+        })
+        .or_else(|| {
+            let alias = aliases
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&value));
+            alias.map(|(_, code)| *code)
+        });
+    code.unwrap_or_else(|| {
+        warn!(
+            message = "Invalid syslog facility or severity, using the default.",
+            value = %value,
+            default = names[usize::from(default)],
+            internal_log_rate_limit = true,
+        );
+        default
+    })
+}
+
+/// Formats a header field, which is made of printable ASCII characters other than spaces.
+fn header_field(value: Option<String>, max_length: usize) -> String {
+    let value = value
+        .unwrap_or_default()
+        .chars()
+        .filter(char::is_ascii_graphic)
+        .take(max_length)
+        .collect::<String>();
+    if value.is_empty() {
+        NIL.to_owned()
+    } else {
+        value
+    }
+}
+
+/// Whether the top-level field looks like an SD element set by the `syslog` source.
+fn is_sd_id_field(name: &str) -> bool {
+    name.contains('@') || REGISTERED_SD_IDS.contains(&name)
+}
+
+/// Whether the name is a valid SD-ID or PARAM-NAME.
+fn is_sd_name(name: &str) -> bool {
+    (1..=32).contains(&name.len())
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_graphic() && !matches!(byte, b'=' | b']' | b'"'))
+}
+
+fn write_sd_param(header: &mut String, name: &str, value: &Value) {
+    write!(header, " {}=\"", name).expect("writing to a string doesn't fail");
+    for c in value.to_string_lossy().chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            header.push('\\');
+        }
+        header.push(c);
+    }
+    header.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use codecs::TextSerializerConfig;
+    use lookup::{owned_value_path, OwnedTargetPath};
+    use value::btreemap;
+
+    use super::*;
+
+    fn encoder(format: SyslogFormat, framing: Option<SyslogFraming>) -> SyslogEncoder {
+        SyslogEncoder {
+            format,
+            framing,
+            facility: None,
+            severity: None,
+            hostname: None,
+            app_name: None,
+            proc_id: None,
+            msg_id: None,
+            structured_data_key: None,
+            transformer: Transformer::default(),
+            encoder: Encoder::<()>::new(TextSerializerConfig::default().build().into()),
+        }
+    }
+
+    fn encode(encoder: &mut SyslogEncoder, event: LogEvent) -> String {
+        let mut buffer = BytesMut::new();
+        encoder.encode(event.into(), &mut buffer).unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    fn event() -> LogEvent {
+        let mut event = LogEvent::from("An application event log entry...");
+        event.insert(
+            "timestamp",
+            Utc.ymd(2003, 10, 11)
+                .and_hms_milli_opt(22, 14, 15, 3)
+                .expect("invalid timestamp"),
+        );
+        event.insert("hostname", "mymachine.example.com");
+        event.insert("appname", "evntslog");
+        event.insert("facility", "local4");
+        event.insert("severity", "notice");
+        event
+    }
+
+    #[cfg(feature = "sources-syslog")]
+    #[test]
+    fn round_trips_rfc5424_messages() {
+        use codecs::{decoding::format::Deserializer, SyslogDeserializer};
+        use vector_core::config::LogNamespace;
+
+        let message = concat!(
+            r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 "#,
+            r#"[examplePriority@32473 class="high"]"#,
+            r#"[exampleSDID@32473 eventID="1011" eventSource="App\]" iut="3"] "#,
+            "An application event log entry...",
+        );
+        let deserializer = SyslogDeserializer { source: None };
+        let events = deserializer
+            .parse(message.into(), LogNamespace::Legacy)
+            .unwrap();
+
+        let mut encoder = encoder(SyslogFormat::Rfc5424, None);
+        let event = events.into_iter().next().unwrap().into_log();
+        assert_eq!(encode(&mut encoder, event), message);
+    }
+
+    #[test]
+    fn encodes_rfc5424_messages() {
+        let mut encoder = encoder(SyslogFormat::Rfc5424, Some(SyslogFraming::Newline));
+        encoder.proc_id = Some(Template::try_from("{{ pid }}").unwrap());
+        encoder.structured_data_key = Some(OwnedTargetPath::event(owned_value_path!("sd")).into());
+
+        let mut event = event();
+        event.insert("pid", 42);
+        event.insert(
+            "sd",
+            btreemap! {
+                "origin" => btreemap! { "ip" => vec!["192.0.2.1", "192.0.2.2"] },
+                "invalid name" => btreemap! { "a" => "b" },
+            },
+        );
+        event.insert("message", "multiple\nlines");
+
+        assert_eq!(
+            encode(&mut encoder, event),
+            concat!(
+                "<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog 42 - ",
+                r#"[origin ip="192.0.2.1" ip="192.0.2.2"] multiple lines"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn encodes_rfc3164_messages() {
+        let mut encoder = encoder(SyslogFormat::Rfc3164, Some(SyslogFraming::OctetCounting));
+        encoder.severity = Some(Template::try_from("{{ level }}").unwrap());
+
+        let mut event = event();
+        event.insert("procid", 42);
+        event.insert("level", "error");
+        event.insert(event_path!("exampleSDID@32473"), btreemap! { "iut" => "3" });
+
+        let message = concat!(
+            "<163>Oct 11 22:14:15 mymachine.example.com evntslog[42]: ",
+            "An application event log entry...",
+        );
+        assert_eq!(
+            encode(&mut encoder, event),
+            format!("{} {}", message.len(), message)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_defaults() {
+        let mut encoder = encoder(SyslogFormat::Rfc5424, None);
+        let mut event = LogEvent::from("message");
+        event.insert(
+            "timestamp",
+            Utc.ymd(2003, 10, 11)
+                .and_hms_opt(22, 14, 15)
+                .expect("invalid timestamp"),
+        );
+        event.insert("facility", "unknown");
+        event.insert("host", "my host");
+
+        assert_eq!(
+            encode(&mut encoder, event),
+            "<14>1 2003-10-11T22:14:15Z myhost - - - - message"
+        );
+    }
+}
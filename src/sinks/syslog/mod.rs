@@ -0,0 +1,4 @@
+mod config;
+mod encoder;
+
+pub use config::SyslogSinkConfig;
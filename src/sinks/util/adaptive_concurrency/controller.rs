@@ -1,14 +1,22 @@
 use std::{
+    collections::VecDeque,
     future::Future,
     sync::{Arc, Mutex, MutexGuard},
     time::{Duration, Instant},
 };
 
+use chrono::Utc;
 use tokio::sync::OwnedSemaphorePermit;
 use tower::timeout::error::Elapsed;
 use vector_common::internal_event::{InternalEventHandle as _, Registered};
+use vector_core::stream::{dynamic_limits, DynamicLimits};
 
-use super::{instant_now, semaphore::ShrinkableSemaphore, AdaptiveConcurrencySettings};
+use super::{
+    instant_now,
+    introspection::{self, ControllerSnapshot, Decision, DecisionAction},
+    semaphore::ShrinkableSemaphore,
+    AdaptiveConcurrencySettings,
+};
 #[cfg(test)]
 use crate::test_util::stats::{TimeHistogram, TimeWeightedSum};
 use crate::{
@@ -29,6 +37,9 @@ pub(super) struct Controller<L> {
     concurrency: Option<usize>,
     settings: AdaptiveConcurrencySettings,
     logic: L,
+    /// The limits of the sink that can be changed while it runs, overriding `concurrency` or the
+    /// maximum concurrency limit.
+    limits: Option<Arc<DynamicLimits>>,
     pub(super) inner: Arc<Mutex<Inner>>,
     #[cfg(test)]
     pub(super) stats: Arc<Mutex<ControllerStatistics>>,
@@ -48,6 +59,28 @@ pub(super) struct Inner {
     current_rtt: Mean,
     had_back_pressure: bool,
     reached_limit: bool,
+    decisions: VecDeque<Decision>,
+}
+
+impl Inner {
+    pub(super) fn snapshot(
+        &self,
+        adaptive: bool,
+        settings: &AdaptiveConcurrencySettings,
+    ) -> ControllerSnapshot {
+        let past_rtt = self.past_rtt.state();
+        ControllerSnapshot {
+            adaptive,
+            concurrency_limit: self.current_limit,
+            max_concurrency_limit: settings.max_concurrency_limit,
+            in_flight: self.in_flight,
+            measured_rtt: past_rtt.map(|past_rtt| Duration::from_secs_f64(past_rtt.mean)),
+            measured_rtt_deviation: past_rtt
+                .map(|past_rtt| Duration::from_secs_f64(past_rtt.variance.sqrt())),
+            target_rtt: settings.target_rtt_secs.map(Duration::from_secs_f64),
+            decisions: self.decisions.iter().cloned().collect(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -68,22 +101,27 @@ impl<L> Controller<L> {
         // If a `concurrency` is specified, it becomes both the
         // current limit and the maximum, effectively bypassing all the
         // mechanisms. Otherwise, the current limit is set to 1 and the
-        // maximum to MAX_CONCURRENCY.
+        // maximum to `max_concurrency_limit`.
         let current_limit = concurrency.unwrap_or(1);
+        let inner = Arc::new(Mutex::new(Inner {
+            current_limit,
+            in_flight: 0,
+            past_rtt: EwmaVar::new(settings.ewma_alpha),
+            next_update: instant_now(),
+            current_rtt: Default::default(),
+            had_back_pressure: false,
+            reached_limit: false,
+            decisions: VecDeque::with_capacity(introspection::DECISION_HISTORY),
+        }));
+        introspection::register(Arc::downgrade(&inner), concurrency.is_none(), settings);
+
         Self {
             semaphore: Arc::new(ShrinkableSemaphore::new(current_limit)),
             concurrency,
             settings,
             logic,
-            inner: Arc::new(Mutex::new(Inner {
-                current_limit,
-                in_flight: 0,
-                past_rtt: EwmaVar::new(settings.ewma_alpha),
-                next_update: instant_now(),
-                current_rtt: Default::default(),
-                had_back_pressure: false,
-                reached_limit: false,
-            })),
+            limits: dynamic_limits::current(),
+            inner,
             #[cfg(test)]
             stats: Arc::new(Mutex::new(ControllerStatistics::default())),
             limit: register!(AdaptiveConcurrencyLimit),
@@ -93,6 +131,48 @@ impl<L> Controller<L> {
         }
     }
 
+    /// The maximum the concurrency limit can be raised to when it is adaptive.
+    fn max_concurrency_limit(&self) -> usize {
+        let max = self.settings.max_concurrency_limit;
+        self.limits
+            .as_ref()
+            .map_or(max, |limits| limits.concurrency(max))
+    }
+
+    /// Brings the concurrency limit in line with the limits of the sink changed while it runs: a
+    /// fixed concurrency is replaced, while an adaptive one is only lowered to its new maximum.
+    fn apply_dynamic_limits(&self, inner: &mut Inner) {
+        let limits = match &self.limits {
+            Some(limits) => limits,
+            None => return,
+        };
+        let target = match self.concurrency {
+            Some(concurrency) => limits.concurrency(concurrency),
+            None => inner.current_limit.min(self.max_concurrency_limit()),
+        }
+        .max(1);
+        if target > inner.current_limit {
+            self.semaphore.add_permits(target - inner.current_limit);
+        } else if target < inner.current_limit {
+            self.semaphore.forget_permits(inner.current_limit - target);
+        }
+        if target != inner.current_limit {
+            inner.current_limit = target;
+            let (past_rtt, past_rtt_deviation) = inner
+                .past_rtt
+                .state()
+                .map_or((0.0, 0.0), |rtt| (rtt.mean, rtt.variance.sqrt()));
+            self.limit.emit(AdaptiveConcurrencyLimitData {
+                concurrency: target as u64,
+                reached_limit: inner.reached_limit,
+                had_back_pressure: inner.had_back_pressure,
+                current_rtt: None,
+                past_rtt: Duration::from_secs_f64(past_rtt),
+                past_rtt_deviation: Duration::from_secs_f64(past_rtt_deviation),
+            });
+        }
+    }
+
     /// An estimate of current load on service managed by this controller.
     ///
     /// 0.0 is no load, while 1.0 is max load.
@@ -142,6 +222,7 @@ impl<L> Controller<L> {
         if is_back_pressure {
             inner.had_back_pressure = true;
         }
+        self.apply_dynamic_limits(&mut inner);
 
         #[cfg(test)]
         let mut stats = self.stats.lock().expect("Stats mutex is poisoned");
@@ -221,32 +302,38 @@ impl<L> Controller<L> {
     ) {
         let past_rtt_deviation = past_rtt.variance.sqrt();
         let threshold = past_rtt_deviation * self.settings.rtt_deviation_scale;
+        // A target RTT, when set, replaces the average of past RTT measurements as the reference
+        // the current RTT is compared with.
+        let reference_rtt = self.settings.target_rtt_secs.unwrap_or(past_rtt.mean);
+        let mut action = DecisionAction::Hold;
 
         // Normal quick responses trigger an increase in the
         // concurrency limit. Note that we only check this if we had
         // requests to go beyond the current limit to prevent
         // increasing the limit beyond what we have evidence for.
-        if inner.current_limit < super::MAX_CONCURRENCY
+        if inner.current_limit < self.max_concurrency_limit()
             && inner.reached_limit
             && !inner.had_back_pressure
             && current_rtt.is_some()
-            && current_rtt.unwrap() <= past_rtt.mean
+            && current_rtt.unwrap() <= reference_rtt
         {
             // Increase (additive) the current concurrency limit
             self.semaphore.add_permits(1);
             inner.current_limit += 1;
+            action = DecisionAction::Increase;
         }
         // Back pressure responses, either explicit or implicit due
         // to increasing response times, trigger a decrease in the
         // concurrency limit.
         else if inner.current_limit > 1
-            && (inner.had_back_pressure || current_rtt.unwrap_or(0.0) >= past_rtt.mean + threshold)
+            && (inner.had_back_pressure || current_rtt.unwrap_or(0.0) >= reference_rtt + threshold)
         {
             // Decrease (multiplicative) the current concurrency limit
             let to_forget = inner.current_limit
                 - (inner.current_limit as f64 * self.settings.decrease_ratio) as usize;
             self.semaphore.forget_permits(to_forget);
             inner.current_limit -= to_forget;
+            action = DecisionAction::Decrease;
         }
         self.limit.emit(AdaptiveConcurrencyLimitData {
             concurrency: inner.current_limit as u64,
@@ -256,6 +343,18 @@ impl<L> Controller<L> {
             past_rtt: Duration::from_secs_f64(past_rtt.mean),
             past_rtt_deviation: Duration::from_secs_f64(past_rtt_deviation),
         });
+
+        let decision = Decision {
+            timestamp: Utc::now(),
+            action,
+            concurrency_limit: inner.current_limit,
+            reached_limit: inner.reached_limit,
+            had_back_pressure: inner.had_back_pressure,
+            current_rtt: current_rtt.map(Duration::from_secs_f64),
+            reference_rtt: Duration::from_secs_f64(reference_rtt),
+            rtt_deviation: Duration::from_secs_f64(past_rtt_deviation),
+        };
+        introspection::push_decision(&mut inner.decisions, decision);
     }
 }
 
@@ -0,0 +1,119 @@
+//! Introspection of the adaptive concurrency controllers of running sinks.
+//!
+//! Controllers built while a sink is being built or run, within [`with_sink`], register
+//! themselves under the key of that sink, so that their current state can be queried through the
+//! API.
+use std::{
+    collections::VecDeque,
+    future::Future,
+    sync::{Mutex, Weak},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use super::{controller::Inner, AdaptiveConcurrencySettings};
+use crate::config::ComponentKey;
+
+/// The number of past decisions kept for each controller.
+pub(super) const DECISION_HISTORY: usize = 20;
+
+tokio::task_local! {
+    static SINK: ComponentKey;
+}
+
+static CONTROLLERS: Lazy<Mutex<Vec<Entry>>> = Lazy::new(Default::default);
+
+struct Entry {
+    component_key: ComponentKey,
+    adaptive: bool,
+    settings: AdaptiveConcurrencySettings,
+    inner: Weak<Mutex<Inner>>,
+}
+
+/// The adjustment made to the concurrency limit at the end of an RTT interval.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecisionAction {
+    Increase,
+    Decrease,
+    Hold,
+}
+
+#[derive(Clone, Debug)]
+pub struct Decision {
+    pub timestamp: DateTime<Utc>,
+    pub action: DecisionAction,
+    /// The concurrency limit after the decision.
+    pub concurrency_limit: usize,
+    pub reached_limit: bool,
+    pub had_back_pressure: bool,
+    pub current_rtt: Option<Duration>,
+    /// The RTT the current RTT was compared with, which is either the target RTT or the average
+    /// of past RTT measurements.
+    pub reference_rtt: Duration,
+    pub rtt_deviation: Duration,
+}
+
+/// The state of one controller. A sink has one controller per endpoint or partition it sends
+/// requests to.
+#[derive(Clone, Debug)]
+pub struct ControllerSnapshot {
+    /// Whether the concurrency limit is managed adaptively, rather than fixed by `concurrency`.
+    pub adaptive: bool,
+    pub concurrency_limit: usize,
+    pub max_concurrency_limit: usize,
+    pub in_flight: usize,
+    /// The average of past RTT measurements, if any request completed yet.
+    pub measured_rtt: Option<Duration>,
+    pub measured_rtt_deviation: Option<Duration>,
+    pub target_rtt: Option<Duration>,
+    /// The most recent decisions, oldest first.
+    pub decisions: Vec<Decision>,
+}
+
+/// Runs `future`, which builds or runs the sink `component_key`, registering the controllers it
+/// builds under that sink.
+pub async fn with_sink<F: Future>(component_key: ComponentKey, future: F) -> F::Output {
+    SINK.scope(component_key, future).await
+}
+
+/// Registers a controller under the sink currently being built or run, if any.
+pub(super) fn register(
+    inner: Weak<Mutex<Inner>>,
+    adaptive: bool,
+    settings: AdaptiveConcurrencySettings,
+) {
+    if let Ok(component_key) = SINK.try_with(Clone::clone) {
+        let mut controllers = CONTROLLERS.lock().expect("Controllers mutex is poisoned");
+        controllers.retain(|entry| entry.inner.strong_count() > 0);
+        controllers.push(Entry {
+            component_key,
+            adaptive,
+            settings,
+            inner,
+        });
+    }
+}
+
+/// Returns the state of the live controllers of the sink `component_key`.
+pub fn controllers(component_key: &ComponentKey) -> Vec<ControllerSnapshot> {
+    let mut controllers = CONTROLLERS.lock().expect("Controllers mutex is poisoned");
+    controllers.retain(|entry| entry.inner.strong_count() > 0);
+    controllers
+        .iter()
+        .filter(|entry| &entry.component_key == component_key)
+        .filter_map(|entry| {
+            let inner = entry.inner.upgrade()?;
+            let inner = inner.lock().expect("Controller mutex is poisoned");
+            Some(inner.snapshot(entry.adaptive, &entry.settings))
+        })
+        .collect()
+}
+
+pub(super) fn push_decision(decisions: &mut VecDeque<Decision>, decision: Decision) {
+    if decisions.len() == DECISION_HISTORY {
+        decisions.pop_front();
+    }
+    decisions.push_back(decision);
+}
@@ -2,6 +2,7 @@
 
 mod controller;
 mod future;
+mod introspection;
 mod layer;
 mod semaphore;
 mod service;
@@ -11,6 +12,9 @@ pub mod tests;
 
 pub(super) const MAX_CONCURRENCY: usize = 200;
 
+pub(crate) use introspection::{
+    controllers, with_sink, ControllerSnapshot, Decision, DecisionAction,
+};
 pub(crate) use layer::AdaptiveConcurrencyLimitLayer;
 pub(crate) use service::AdaptiveConcurrencyLimit;
 use vector_config::configurable_component;
@@ -61,6 +65,25 @@ pub struct AdaptiveConcurrencySettings {
     #[configurable(validation(range(min = 0.0)))]
     #[serde(default = "default_rtt_deviation_scale")]
     pub(super) rtt_deviation_scale: f64,
+
+    /// The maximum concurrency limit.
+    ///
+    /// The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+    #[configurable(validation(range(min = 1)))]
+    #[serde(default = "default_max_concurrency_limit")]
+    pub(super) max_concurrency_limit: usize,
+
+    /// A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+    ///
+    /// Valid values are greater than `0`.
+    ///
+    /// The concurrency limit is increased while the current RTT is at or below the target, and decreased once it goes
+    /// above the target by more than the scaled deviation. This is useful for services whose latency degrades slowly
+    /// under load, which the past RTT average follows without triggering a decrease.
+    #[configurable(validation(range(min = 0.0)))]
+    #[configurable(metadata(docs::examples = 0.5, docs::type_unit = "seconds"))]
+    #[serde(default)]
+    pub(super) target_rtt_secs: Option<f64>,
 }
 
 const fn default_decrease_ratio() -> f64 {
@@ -75,9 +98,13 @@ const fn default_rtt_deviation_scale() -> f64 {
     2.5
 }
 
+const fn default_max_concurrency_limit() -> usize {
+    MAX_CONCURRENCY
+}
+
 impl AdaptiveConcurrencySettings {
-    pub const fn max_concurrency() -> usize {
-        MAX_CONCURRENCY
+    pub const fn max_concurrency_limit(&self) -> usize {
+        self.max_concurrency_limit
     }
 }
 
@@ -87,6 +114,8 @@ impl Default for AdaptiveConcurrencySettings {
             decrease_ratio: default_decrease_ratio(),
             ewma_alpha: default_ewma_alpha(),
             rtt_deviation_scale: default_rtt_deviation_scale(),
+            max_concurrency_limit: default_max_concurrency_limit(),
+            target_rtt_secs: None,
         }
     }
 }
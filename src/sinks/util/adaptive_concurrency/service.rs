@@ -141,11 +141,11 @@ mod tests {
     use super::{
         super::{
             controller::{ControllerStatistics, Inner},
-            AdaptiveConcurrencyLimitLayer,
+            controllers, with_sink, AdaptiveConcurrencyLimitLayer, DecisionAction,
         },
         *,
     };
-    use crate::assert_downcast_matches;
+    use crate::{assert_downcast_matches, config::ComponentKey};
 
     #[derive(Clone, Copy, Debug, Snafu)]
     enum TestError {
@@ -178,15 +178,8 @@ mod tests {
     }
 
     impl TestService {
-        fn start() -> Self {
-            let layer = AdaptiveConcurrencyLimitLayer::new(
-                None,
-                AdaptiveConcurrencySettings {
-                    decrease_ratio: 0.5,
-                    ..Default::default()
-                },
-                TestRetryLogic,
-            );
+        fn start(settings: AdaptiveConcurrencySettings) -> Self {
+            let layer = AdaptiveConcurrencyLimitLayer::new(None, settings, TestRetryLogic);
             let (service, handle) = mock::spawn_layer(layer);
             let controller = Arc::clone(&service.get_ref().controller);
             let inner = Arc::clone(&controller.inner);
@@ -205,7 +198,25 @@ mod tests {
             F: FnOnce(Self) -> Ret,
             Ret: Future<Output = ()>,
         {
-            let svc = Self::start();
+            Self::run_with(
+                AdaptiveConcurrencySettings {
+                    decrease_ratio: 0.5,
+                    ..Default::default()
+                },
+                doit,
+            )
+            .await
+        }
+
+        async fn run_with<F, Ret>(
+            settings: AdaptiveConcurrencySettings,
+            doit: F,
+        ) -> ControllerStatistics
+        where
+            F: FnOnce(Self) -> Ret,
+            Ret: Future<Output = ()>,
+        {
+            let svc = Self::start(settings);
             //let inner = svc.inner.clone();
             let stats = Arc::clone(&svc.stats);
             pause();
@@ -346,4 +357,78 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn caps_limit_at_max_concurrency_limit() {
+        let settings = AdaptiveConcurrencySettings {
+            max_concurrency_limit: 2,
+            ..Default::default()
+        };
+        TestService::run_with(settings, |mut svc| async move {
+            let mut reqs = [None, None];
+            for &concurrent in &[1, 1, 2, 2] {
+                assert_eq!(svc.inner().current_limit, concurrent);
+                for (i, req) in reqs.iter_mut().take(concurrent).enumerate() {
+                    *req = Some(svc.send(i < concurrent - 1).await);
+                }
+                advance(Duration::from_secs(1)).await;
+                for req in reqs.iter_mut().take(concurrent) {
+                    req.take().unwrap().respond().await;
+                }
+            }
+
+            assert_eq!(svc.inner().current_limit, 2);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn holds_limit_above_target_rtt() {
+        let settings = AdaptiveConcurrencySettings {
+            target_rtt_secs: Some(0.5),
+            ..Default::default()
+        };
+        TestService::run_with(settings, |mut svc| async move {
+            // Constant responses slower than the target never increase the limit
+            for _ in 0..3 {
+                let req = svc.send(false).await;
+                advance(Duration::from_secs(1)).await;
+                req.respond().await;
+                assert_eq!(svc.inner().current_limit, 1);
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn registers_controllers_with_sink() {
+        let key = ComponentKey::from("adaptive_concurrency_sink");
+        let run_key = key.clone();
+        with_sink(
+            key.clone(),
+            TestService::run(|mut svc| async move {
+                let req = svc.send(false).await;
+                advance(Duration::from_secs(1)).await;
+                req.respond().await;
+                let req = svc.send(false).await;
+                advance(Duration::from_secs(1)).await;
+                req.respond().await;
+
+                let snapshots = controllers(&run_key);
+                assert_eq!(snapshots.len(), 1);
+                let snapshot = &snapshots[0];
+                assert!(snapshot.adaptive);
+                assert_eq!(snapshot.concurrency_limit, 2);
+                assert_eq!(snapshot.in_flight, 0);
+                assert_eq!(snapshot.measured_rtt, Some(Duration::from_secs(1)));
+                assert_eq!(snapshot.decisions.len(), 1);
+                assert_eq!(snapshot.decisions[0].action, DecisionAction::Increase);
+                assert_eq!(snapshot.decisions[0].concurrency_limit, 2);
+            }),
+        )
+        .await;
+
+        // Controllers are unregistered once dropped.
+        assert!(controllers(&key).is_empty());
+    }
 }
@@ -4,7 +4,7 @@ use derivative::Derivative;
 use serde_with::serde_as;
 use snafu::Snafu;
 use vector_config::configurable_component;
-use vector_core::stream::BatcherSettings;
+use vector_core::stream::{dynamic_limits, BatcherSettings, Limit};
 
 use super::EncodedEvent;
 use crate::{event::EventFinalizers, internal_events::LargeEventDroppedError};
@@ -174,6 +174,13 @@ impl<D: SinkBatchSettings + Clone> BatchConfig<D, Unmerged> {
     }
 }
 
+/// Makes `max` the largest value `limit` can be changed to while the sink being built runs.
+fn set_dynamic_max(limit: Limit, max: usize) {
+    if let Some(limits) = dynamic_limits::current() {
+        limits.set_max(limit, u64::try_from(max).unwrap_or(u64::MAX));
+    }
+}
+
 impl<D: SinkBatchSettings + Clone> BatchConfig<D, Merged> {
     pub const fn validate(self) -> Result<BatchConfig<D, Merged>, BatchError> {
         Ok(self)
@@ -188,14 +195,20 @@ impl<D: SinkBatchSettings + Clone> BatchConfig<D, Merged> {
         }
     }
 
-    pub const fn limit_max_bytes(self, limit: usize) -> Result<Self, BatchError> {
+    /// Checks that `max_bytes` is at most `limit`, which the limit of the sink can't be changed to
+    /// exceed while it runs either.
+    pub fn limit_max_bytes(self, limit: usize) -> Result<Self, BatchError> {
+        set_dynamic_max(Limit::BatchMaxBytes, limit);
         match self.max_bytes {
             Some(n) if n > limit => Err(BatchError::MaxBytesExceeded { limit }),
             _ => Ok(self),
         }
     }
 
-    pub const fn limit_max_events(self, limit: usize) -> Result<Self, BatchError> {
+    /// Checks that `max_events` is at most `limit`, which the limit of the sink can't be changed to
+    /// exceed while it runs either.
+    pub fn limit_max_events(self, limit: usize) -> Result<Self, BatchError> {
+        set_dynamic_max(Limit::BatchMaxEvents, limit);
         match self.max_events {
             Some(n) if n > limit => Err(BatchError::MaxEventsExceeded { limit }),
             _ => Ok(self),
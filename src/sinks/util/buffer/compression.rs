@@ -30,6 +30,18 @@ pub enum Compression {
     ///
     /// [zlib]: https://zlib.net/
     Zlib(CompressionLevel),
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd(CompressionLevel),
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// Payloads are compressed as a whole, in the raw format. Snappy doesn't have compression levels.
+    ///
+    /// [snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+    Snappy,
 }
 
 impl Compression {
@@ -53,11 +65,17 @@ impl Compression {
         Compression::Zlib(CompressionLevel::const_default())
     }
 
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd(CompressionLevel::const_default())
+    }
+
     pub const fn content_encoding(self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
             Self::Zlib(_) => Some("deflate"),
+            Self::Zstd(_) => Some("zstd"),
+            Self::Snappy => Some("snappy"),
         }
     }
 
@@ -74,13 +92,15 @@ impl Compression {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
             Self::Zlib(_) => "log.zz",
+            Self::Zstd(_) => "log.zst",
+            Self::Snappy => "log.snappy",
         }
     }
 
     pub const fn level(self) -> flate2::Compression {
         match self {
-            Self::None => flate2::Compression::none(),
-            Self::Gzip(level) | Self::Zlib(level) => level.as_flate2(),
+            Self::None | Self::Snappy => flate2::Compression::none(),
+            Self::Gzip(level) | Self::Zlib(level) | Self::Zstd(level) => level.as_flate2(),
         }
     }
 }
@@ -91,6 +111,8 @@ impl fmt::Display for Compression {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.as_flate2().level()),
             Compression::Zlib(ref level) => write!(f, "zlib({})", level.as_flate2().level()),
+            Compression::Zstd(ref level) => write!(f, "zstd({})", level.as_flate2().level()),
+            Compression::Snappy => write!(f, "snappy"),
         }
     }
 }
@@ -117,9 +139,11 @@ impl<'de> de::Deserialize<'de> for Compression {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
                     "zlib" => Ok(Compression::zlib_default()),
+                    "zstd" => Ok(Compression::zstd_default()),
+                    "snappy" => Ok(Compression::Snappy),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip" or "zlib""#,
+                        &r#""none", "gzip", "zlib", "zstd" or "snappy""#,
                     )),
                 }
             }
@@ -159,9 +183,14 @@ impl<'de> de::Deserialize<'de> for Compression {
                     },
                     "gzip" => Ok(Compression::Gzip(level.unwrap_or_default())),
                     "zlib" => Ok(Compression::Zlib(level.unwrap_or_default())),
+                    "zstd" => Ok(Compression::Zstd(level.unwrap_or_default())),
+                    "snappy" => match level {
+                        Some(_) => Err(de::Error::unknown_field("level", &[])),
+                        None => Ok(Compression::Snappy),
+                    },
                     algorithm => Err(de::Error::unknown_variant(
                         algorithm,
-                        &["none", "gzip", "zlib"],
+                        &["none", "gzip", "zlib", "zstd", "snappy"],
                     )),
                 }
             }
@@ -202,6 +231,17 @@ impl ser::Serialize for Compression {
                     serializer.serialize_str("zlib")
                 }
             }
+            Compression::Zstd(zstd_level) => {
+                if *zstd_level != default_level {
+                    let mut map = serializer.serialize_map(None)?;
+                    map.serialize_entry("algorithm", "zstd")?;
+                    map.serialize_entry("level", &zstd_level)?;
+                    map.end()
+                } else {
+                    serializer.serialize_str("zstd")
+                }
+            }
+            Compression::Snappy => serializer.serialize_str("snappy"),
         }
     }
 }
@@ -257,11 +297,24 @@ impl Configurable for Compression {
             Some("[Zlib][zlib] compression."),
             "[zlib]: https://zlib.net/",
         );
+        let zstd_string_subschema = generate_string_schema(
+            "Zstd",
+            Some("[Zstandard][zstd] compression."),
+            "[zstd]: https://facebook.github.io/zstd/",
+        );
+        let snappy_string_subschema = generate_string_schema(
+            "Snappy",
+            Some("[Snappy][snappy] compression."),
+            "Payloads are compressed as a whole, in the raw format. Snappy doesn't have compression \
+             levels.\n\n[snappy]: https://github.com/google/snappy/blob/main/docs/README.md",
+        );
 
         let mut all_string_oneof_subschema = generate_one_of_schema(&[
             none_string_subschema,
             gzip_string_subschema,
             zlib_string_subschema,
+            zstd_string_subschema,
+            snappy_string_subschema,
         ]);
         apply_base_metadata(&mut all_string_oneof_subschema, string_metadata);
 
@@ -340,6 +393,15 @@ impl CompressionLevel {
     pub const fn as_flate2(self) -> flate2::Compression {
         self.0
     }
+
+    /// Gets the Zstandard level, from 1 to 9, the `none` level being the fastest one since
+    /// Zstandard always compresses.
+    pub const fn as_zstd(self) -> i32 {
+        match self.0.level() {
+            0 => 1,
+            level => level as i32,
+        }
+    }
 }
 
 impl<'de> de::Deserialize<'de> for CompressionLevel {
@@ -493,6 +555,16 @@ mod test {
                 r#"{"algorithm": "zlib", "level": 8}"#,
                 Compression::Zlib(CompressionLevel::new(8)),
             ),
+            (
+                r#""zstd""#,
+                Compression::Zstd(CompressionLevel::const_default()),
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": "fast"}"#,
+                Compression::Zstd(CompressionLevel::fast()),
+            ),
+            (r#""snappy""#, Compression::Snappy),
+            (r#"{"algorithm": "snappy"}"#, Compression::Snappy),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -506,11 +578,15 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" or "zlib" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zlib", "zstd" or "snappy" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib`, `zstd`, `snappy` at line 1 column 20"#,
+            ),
+            (
+                r#"{"algorithm": "snappy", "level": "best"}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 40"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -548,6 +624,8 @@ mod test {
             Compression::Gzip(CompressionLevel::new(7)),
             Compression::Zlib(CompressionLevel::best()),
             Compression::Zlib(CompressionLevel::new(7)),
+            Compression::Zstd(CompressionLevel::new(7)),
+            Compression::Snappy,
         ];
 
         for v in fixtures_valid {
@@ -2,6 +2,7 @@ use std::{fmt, future::Future, hash::Hash, num::NonZeroUsize, pin::Pin, sync::Ar
 
 use futures_util::{stream::Map, Stream, StreamExt};
 use tower::Service;
+use tracing::Instrument;
 use vector_core::{
     event::{Finalizable, Metric},
     partition::Partitioner,
@@ -97,25 +98,36 @@ pub trait SinkBuilderExt: Stream {
 
         self.concurrent_map(limit, move |input| {
             let builder = Arc::clone(&builder);
-
-            Box::pin(async move {
-                // Split the input into metadata and events.
-                let (metadata, request_metadata_builder, events) = builder.split_input(input);
-
-                // Encode the events.
-                let payload = builder.encode_events(events)?;
-
-                // Note: it would be nice for the RequestMetadataBuilder to build be created from the
-                // events here, and not need to be required by split_input(). But this then requires
-                // each Event type to implement Serialize, and that causes conflicts with the Serialize
-                // implementation for EstimatedJsonEncodedSizeOf.
-
-                // Build the request metadata.
-                let request_metadata = request_metadata_builder.build(&payload);
-
-                // Now build the actual request.
-                Ok(builder.build_request(metadata, request_metadata, payload))
-            })
+            // Created here, within the span of the sink, rather than in the spawned task.
+            let span = info_span!("request_build", otel.name = "sink.request.build");
+
+            Box::pin(
+                async move {
+                    // Split the input into metadata and events.
+                    let (metadata, request_metadata_builder, events) = builder.split_input(input);
+
+                    // Encode the events.
+                    let payload = builder.encode_events(events)?;
+
+                    // Note: it would be nice for the RequestMetadataBuilder to build be created from the
+                    // events here, and not need to be required by split_input(). But this then requires
+                    // each Event type to implement Serialize, and that causes conflicts with the Serialize
+                    // implementation for EstimatedJsonEncodedSizeOf.
+
+                    // Build the request metadata.
+                    let request_metadata = request_metadata_builder.build(&payload);
+
+                    // The spans of the sending of the request are part of the same trace.
+                    #[cfg(feature = "internal-telemetry-traces")]
+                    let request_metadata = request_metadata.with_trace_context(
+                        crate::internal_telemetry::traces::current_trace_context(),
+                    );
+
+                    // Now build the actual request.
+                    Ok(builder.build_request(metadata, request_metadata, payload))
+                }
+                .instrument(span),
+            )
         })
     }
 
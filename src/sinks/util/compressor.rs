@@ -1,34 +1,47 @@
-use std::io;
+use std::{fmt, io};
 
 use bytes::{BufMut, BytesMut};
 use flate2::write::{GzEncoder, ZlibEncoder};
 
-use super::Compression;
+use super::{snappy::SnappyEncoder, Compression};
 
 enum Writer {
     Plain(bytes::buf::Writer<BytesMut>),
     Gzip(GzEncoder<bytes::buf::Writer<BytesMut>>),
     Zlib(ZlibEncoder<bytes::buf::Writer<BytesMut>>),
+    Zstd(zstd::stream::write::Encoder<'static, bytes::buf::Writer<BytesMut>>),
+    Snappy(SnappyEncoder<bytes::buf::Writer<BytesMut>>),
 }
 
 impl Writer {
+    fn new(compression: Compression, capacity: usize) -> Self {
+        let writer = BytesMut::with_capacity(capacity).writer();
+        match compression {
+            Compression::None => Writer::Plain(writer),
+            Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(writer, level.as_flate2())),
+            Compression::Zlib(level) => Writer::Zlib(ZlibEncoder::new(writer, level.as_flate2())),
+            Compression::Zstd(level) => Writer::Zstd(
+                zstd::stream::write::Encoder::new(writer, level.as_zstd())
+                    .expect("zstd encoder should not fail to initialize"),
+            ),
+            Compression::Snappy => Writer::Snappy(SnappyEncoder::new(writer)),
+        }
+    }
+
     pub fn get_ref(&self) -> &BytesMut {
         match self {
             Writer::Plain(inner) => inner.get_ref(),
             Writer::Gzip(inner) => inner.get_ref().get_ref(),
             Writer::Zlib(inner) => inner.get_ref().get_ref(),
+            Writer::Zstd(inner) => inner.get_ref().get_ref(),
+            Writer::Snappy(inner) => inner.get_ref().get_ref(),
         }
     }
 }
 
 impl From<Compression> for Writer {
     fn from(compression: Compression) -> Self {
-        let writer = BytesMut::with_capacity(1_024).writer();
-        match compression {
-            Compression::None => Writer::Plain(writer),
-            Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(writer, level.as_flate2())),
-            Compression::Zlib(level) => Writer::Zlib(ZlibEncoder::new(writer, level.as_flate2())),
-        }
+        Writer::new(compression, 1_024)
     }
 }
 
@@ -39,6 +52,8 @@ impl io::Write for Writer {
             Writer::Plain(inner_buf) => inner_buf.write(buf),
             Writer::Gzip(writer) => writer.write(buf),
             Writer::Zlib(writer) => writer.write(buf),
+            Writer::Zstd(writer) => writer.write(buf),
+            Writer::Snappy(writer) => writer.write(buf),
         }
     }
 
@@ -47,6 +62,8 @@ impl io::Write for Writer {
             Writer::Plain(writer) => writer.flush(),
             Writer::Gzip(writer) => writer.flush(),
             Writer::Zlib(writer) => writer.flush(),
+            Writer::Zstd(writer) => writer.flush(),
+            Writer::Snappy(writer) => writer.flush(),
         }
     }
 }
@@ -60,6 +77,14 @@ pub struct Compressor {
 }
 
 impl Compressor {
+    /// Creates a compressor whose underlying buffer starts with the given capacity.
+    pub fn with_capacity(compression: Compression, capacity: usize) -> Self {
+        Compressor {
+            compression,
+            inner: Writer::new(compression, capacity),
+        }
+    }
+
     /// Gets a mutable reference to the underlying buffer.
     pub fn get_ref(&self) -> &BytesMut {
         self.inner.get_ref()
@@ -88,6 +113,8 @@ impl Compressor {
             Writer::Plain(writer) => writer,
             Writer::Gzip(writer) => writer.finish()?,
             Writer::Zlib(writer) => writer.finish()?,
+            Writer::Zstd(writer) => writer.finish()?,
+            Writer::Snappy(writer) => writer.finish()?,
         }
         .into_inner();
 
@@ -112,6 +139,12 @@ impl Compressor {
             Writer::Zlib(writer) => writer
                 .finish()
                 .expect("zlib writer should not fail to finish"),
+            Writer::Zstd(writer) => writer
+                .finish()
+                .expect("zstd writer should not fail to finish"),
+            Writer::Snappy(writer) => writer
+                .finish()
+                .expect("snappy writer should not fail to finish"),
         }
         .into_inner()
     }
@@ -128,6 +161,14 @@ impl io::Write for Compressor {
     }
 }
 
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressor")
+            .field("compression", &self.compression)
+            .finish_non_exhaustive()
+    }
+}
+
 impl From<Compression> for Compressor {
     fn from(compression: Compression) -> Self {
         Compressor {
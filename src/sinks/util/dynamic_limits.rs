@@ -0,0 +1,355 @@
+//! Batch and concurrency limits of sinks that change with the time of day, or through the API.
+//!
+//! Each sink is given [`DynamicLimits`] when it is built. Its limits can be set for periods of the
+//! day with its `limit_schedule`, and overridden through the `setComponentOption` mutation of the
+//! API, with the options named after [`Limit::name`]. Setting an option to zero removes the
+//! override. Neither can exceed the largest batch limits the sink supports.
+use std::{
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use chrono::{Local, NaiveTime, Timelike, Utc};
+use vector_common::TimeZone;
+use vector_config::configurable_component;
+use vector_core::stream::{DynamicLimits, Limit};
+
+use crate::{config::ComponentKey, runtime_options};
+
+/// The largest concurrency limit that can be set while a sink runs.
+const MAX_CONCURRENCY: u64 = 10_000;
+
+/// Limits of a sink applied during a period of the day.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledLimitsConfig {
+    /// The time of day the limits start applying at, as `HH:MM` in the global `timezone`.
+    #[configurable(metadata(docs::examples = "22:00"))]
+    pub start: String,
+
+    /// The time of day the limits stop applying at, as `HH:MM` in the global `timezone`.
+    ///
+    /// This can be earlier than `start`, for limits applying overnight. If it is equal to
+    /// `start`, the limits apply all day.
+    #[configurable(metadata(docs::examples = "06:00"))]
+    pub end: String,
+
+    /// The maximum size of a batch, in bytes, replacing `batch.max_bytes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = 100000000))]
+    pub batch_max_bytes: Option<u64>,
+
+    /// The maximum number of events in a batch, replacing `batch.max_events`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = 100000))]
+    pub batch_max_events: Option<u64>,
+
+    /// The maximum age of a batch, in seconds, replacing `batch.timeout_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = 300.0))]
+    pub batch_timeout_secs: Option<f64>,
+
+    /// The concurrency limit of requests.
+    ///
+    /// This replaces `request.concurrency` when the concurrency isn't adaptive, and
+    /// `request.adaptive_concurrency.max_concurrency_limit` when it is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = 50))]
+    pub concurrency: Option<u64>,
+}
+
+/// A parsed [`ScheduledLimitsConfig`].
+#[derive(Clone, Debug)]
+struct ScheduledLimits {
+    start: NaiveTime,
+    end: NaiveTime,
+    values: Vec<(Limit, u64)>,
+}
+
+fn parse_time_of_day(time: &str) -> crate::Result<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| {
+        format!(
+            "Invalid time of day {:?} in `limit_schedule`, expected `HH:MM`.",
+            time
+        )
+        .into()
+    })
+}
+
+/// Returns the largest value `limit` can be set to for the sink with the dynamic `limits`.
+fn max_value(limits: &DynamicLimits, limit: Limit) -> u64 {
+    match limit {
+        Limit::Concurrency => MAX_CONCURRENCY,
+        _ => limits.max(limit).unwrap_or(u64::MAX),
+    }
+}
+
+impl ScheduledLimits {
+    fn new(config: &ScheduledLimitsConfig) -> crate::Result<Self> {
+        let mut values = Vec::new();
+        let mut push = |limit: Limit, value: Option<u64>| match value {
+            Some(0) => Err(format!(
+                "The `{}` limit in `limit_schedule` must be positive.",
+                limit.name()
+            )),
+            Some(value) => {
+                values.push((limit, value));
+                Ok(())
+            }
+            None => Ok(()),
+        };
+        push(Limit::BatchMaxBytes, config.batch_max_bytes)?;
+        push(Limit::BatchMaxEvents, config.batch_max_events)?;
+        push(Limit::Concurrency, config.concurrency)?;
+        if let Some(timeout) = config.batch_timeout_secs {
+            if !timeout.is_finite() || timeout <= 0.0 {
+                return Err(
+                    "The `batch_timeout_secs` limit in `limit_schedule` must be positive.".into(),
+                );
+            }
+            // Timeouts shorter than a millisecond are rounded up to one.
+            let timeout_ms = (timeout * 1000.0).ceil() as u64;
+            values.push((Limit::BatchTimeoutMs, timeout_ms));
+        }
+
+        Ok(Self {
+            start: parse_time_of_day(&config.start)?,
+            end: parse_time_of_day(&config.end)?,
+            values,
+        })
+    }
+
+    /// Checks that the limits don't exceed the largest ones supported by the sink with the dynamic
+    /// `limits`.
+    fn check(&self, limits: &DynamicLimits) -> crate::Result<()> {
+        for &(limit, value) in &self.values {
+            let max = max_value(limits, limit);
+            if value > max {
+                return Err(format!(
+                    "The `{}` limit in `limit_schedule` must be between 1 and {}.",
+                    limit.name(),
+                    max
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    fn value(&self, limit: Limit) -> Option<u64> {
+        self.values
+            .iter()
+            .find(|(scheduled, _)| *scheduled == limit)
+            .map(|(_, value)| *value)
+    }
+}
+
+fn time_of_day(timezone: TimeZone) -> NaiveTime {
+    match timezone {
+        TimeZone::Local => Local::now().time(),
+        TimeZone::Named(tz) => Utc::now().with_timezone(&tz).time(),
+    }
+}
+
+/// Sets the scheduled limits to those of the first period `time` falls in, returning its index.
+fn apply(limits: &DynamicLimits, schedule: &[ScheduledLimits], time: NaiveTime) -> Option<usize> {
+    let active = schedule
+        .iter()
+        .position(|scheduled| scheduled.contains(time));
+    for limit in Limit::ALL {
+        limits.set_scheduled(limit, active.and_then(|index| schedule[index].value(limit)));
+    }
+    active
+}
+
+async fn run_schedule(
+    limits: Weak<DynamicLimits>,
+    schedule: Vec<ScheduledLimits>,
+    timezone: TimeZone,
+    mut active: Option<usize>,
+) {
+    loop {
+        // Periods start and end on minutes, so there is nothing to do until the next one.
+        let seconds = 60 - u64::from(time_of_day(timezone).second());
+        tokio::time::sleep(Duration::from_secs(seconds)).await;
+
+        let limits = match limits.upgrade() {
+            Some(limits) => limits,
+            // The sink stopped.
+            None => break,
+        };
+        let now_active = apply(&limits, &schedule, time_of_day(timezone));
+        if now_active != active {
+            active = now_active;
+            match active.map(|index| &schedule[index]) {
+                Some(scheduled) => info!(
+                    message = "Applying scheduled limits.",
+                    start = %scheduled.start.format("%H:%M"),
+                    end = %scheduled.end.format("%H:%M"),
+                ),
+                None => info!(message = "Scheduled limits ended, applying the configured limits."),
+            }
+        }
+    }
+}
+
+/// The parsed `limit_schedule` of a sink.
+#[derive(Clone, Debug)]
+pub struct LimitSchedule(Vec<ScheduledLimits>);
+
+impl LimitSchedule {
+    pub fn new(schedule: &[ScheduledLimitsConfig]) -> crate::Result<Self> {
+        schedule
+            .iter()
+            .map(ScheduledLimits::new)
+            .collect::<crate::Result<Vec<_>>>()
+            .map(Self)
+    }
+
+    /// Applies the schedule to the dynamic `limits` of the sink `component_key` from now on, in
+    /// the time zone `timezone`, and registers their overrides as options of the sink that can be
+    /// changed at runtime.
+    ///
+    /// This is done once the sink is built, as neither can exceed the largest limits the sink set
+    /// on `limits` while it was built.
+    pub fn start(
+        self,
+        component_key: &ComponentKey,
+        limits: &Arc<DynamicLimits>,
+        timezone: TimeZone,
+    ) -> crate::Result<()> {
+        for scheduled in &self.0 {
+            scheduled.check(limits)?;
+        }
+
+        for limit in Limit::ALL {
+            runtime_options::register_shared(
+                component_key,
+                limit.name(),
+                limits.override_value(limit),
+                0,
+                max_value(limits, limit),
+            );
+        }
+
+        if !self.0.is_empty() {
+            let active = apply(limits, &self.0, time_of_day(timezone));
+            tokio::spawn(run_schedule(
+                Arc::downgrade(limits),
+                self.0,
+                timezone,
+                active,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduled(start: &str, end: &str) -> ScheduledLimitsConfig {
+        ScheduledLimitsConfig {
+            start: start.to_owned(),
+            end: end.to_owned(),
+            batch_max_bytes: Some(100_000_000),
+            batch_max_events: None,
+            batch_timeout_secs: Some(300.0),
+            concurrency: None,
+        }
+    }
+
+    fn time(time: &str) -> NaiveTime {
+        parse_time_of_day(time).unwrap()
+    }
+
+    #[test]
+    fn applies_the_limits_of_the_current_period() {
+        let schedule = [
+            ScheduledLimits::new(&scheduled("22:00", "06:00")).unwrap(),
+            ScheduledLimits::new(&ScheduledLimitsConfig {
+                concurrency: Some(2),
+                ..scheduled("12:00", "13:30")
+            })
+            .unwrap(),
+        ];
+        let limits = DynamicLimits::default();
+
+        assert_eq!(apply(&limits, &schedule, time("23:15")), Some(0));
+        assert_eq!(limits.get(Limit::BatchMaxBytes), Some(100_000_000));
+        assert_eq!(
+            limits.batch_timeout(Duration::from_secs(1)),
+            Duration::from_secs(300)
+        );
+        assert_eq!(limits.get(Limit::Concurrency), None);
+
+        assert_eq!(apply(&limits, &schedule, time("05:59")), Some(0));
+        assert_eq!(apply(&limits, &schedule, time("13:00")), Some(1));
+        assert_eq!(limits.get(Limit::Concurrency), Some(2));
+
+        assert_eq!(apply(&limits, &schedule, time("06:00")), None);
+        for limit in Limit::ALL {
+            assert_eq!(limits.get(limit), None);
+        }
+    }
+
+    #[test]
+    fn equal_start_and_end_apply_all_day() {
+        let scheduled = ScheduledLimits::new(&scheduled("00:00", "00:00")).unwrap();
+        assert!(scheduled.contains(time("00:00")));
+        assert!(scheduled.contains(time("12:34")));
+    }
+
+    #[test]
+    fn rejects_invalid_schedules() {
+        assert!(ScheduledLimits::new(&scheduled("10pm", "06:00")).is_err());
+        assert!(ScheduledLimits::new(&ScheduledLimitsConfig {
+            batch_max_events: Some(0),
+            ..scheduled("22:00", "06:00")
+        })
+        .is_err());
+        assert!(ScheduledLimits::new(&ScheduledLimitsConfig {
+            batch_timeout_secs: Some(-1.0),
+            ..scheduled("22:00", "06:00")
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_limits_above_the_maximum_of_the_sink() {
+        let limits = Arc::new(DynamicLimits::default());
+        limits.set_max(Limit::BatchMaxBytes, 1_000_000);
+        let schedule = LimitSchedule::new(&[scheduled("22:00", "06:00")]).unwrap();
+        let key = ComponentKey::from("dynamic_limits_maximum");
+        assert!(schedule.start(&key, &limits, TimeZone::Local).is_err());
+    }
+
+    #[test]
+    fn overrides_are_runtime_options() {
+        let key = ComponentKey::from("dynamic_limits_overrides");
+        let limits = Arc::new(DynamicLimits::default());
+        limits.set_max(Limit::BatchMaxEvents, 1000);
+        LimitSchedule::new(&[])
+            .unwrap()
+            .start(&key, &limits, TimeZone::Local)
+            .unwrap();
+
+        runtime_options::set(&key, "batch.max_events", 500).unwrap();
+        assert_eq!(limits.batch_max_events(10), 500);
+        assert!(runtime_options::set(&key, "request.concurrency", MAX_CONCURRENCY + 1).is_err());
+        assert!(runtime_options::set(&key, "batch.max_events", 1001).is_err());
+
+        runtime_options::set(&key, "batch.max_events", 0).unwrap();
+        assert_eq!(limits.batch_max_events(10), 10);
+    }
+}
@@ -464,6 +464,10 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn status_code(&self, response: &Self::Response) -> Option<u16> {
+        Some(response.status().as_u16())
+    }
 }
 
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
@@ -514,6 +518,10 @@ where
             _ => RetryAction::DontRetry(format!("Http status: {}", status).into()),
         }
     }
+
+    fn status_code(&self, response: &T) -> Option<u16> {
+        Some((self.func)(response).as_u16())
+    }
 }
 
 impl<F, T> Clone for HttpStatusRetryLogic<F, T>
@@ -593,6 +601,37 @@ pub fn validate_headers(
     Ok(validated_headers)
 }
 
+/// Returns how long a response asks to wait before retrying.
+///
+/// This is given by its `Retry-After` header, either in seconds or as a date, or else by the reset
+/// of its rate limit, in seconds, from its `RateLimit-Reset` header or the `reset` parameter of its
+/// `RateLimit` header.
+pub fn retry_after(headers: &http::HeaderMap) -> Option<Duration> {
+    let get = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    if let Some(value) = get(header::RETRY_AFTER.as_str()).map(str::trim) {
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(Duration::from_secs(secs));
+        }
+        if let Ok(date) = chrono::DateTime::parse_from_rfc2822(value) {
+            let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+            return Some(delay.to_std().unwrap_or_default());
+        }
+    }
+
+    get("ratelimit-reset")
+        .map(str::trim)
+        .or_else(|| {
+            get("ratelimit").and_then(|value| {
+                value
+                    .split(|c: char| c == ',' || c == ';')
+                    .find_map(|param| param.trim().strip_prefix("reset="))
+            })
+        })
+        .and_then(|secs| secs.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
 #[cfg(test)]
 mod test {
     #![allow(clippy::print_stderr)] //tests
@@ -625,6 +664,37 @@ mod test {
             .is_not_retryable());
     }
 
+    #[test]
+    fn util_http_retry_after() {
+        let headers = |name: &'static str, value: &str| {
+            let mut headers = http::HeaderMap::new();
+            headers.insert(name, HeaderValue::from_str(value).unwrap());
+            headers
+        };
+
+        assert_eq!(retry_after(&http::HeaderMap::new()), None);
+        assert_eq!(
+            retry_after(&headers("retry-after", "30")),
+            Some(Duration::from_secs(30))
+        );
+        let date = (chrono::Utc::now() + chrono::Duration::minutes(5)).to_rfc2822();
+        let delay = retry_after(&headers("retry-after", &date)).unwrap();
+        assert!(delay > Duration::from_secs(290) && delay <= Duration::from_secs(300));
+        assert_eq!(
+            retry_after(&headers("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            retry_after(&headers("ratelimit-reset", "12")),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(
+            retry_after(&headers("ratelimit", "limit=100, remaining=0, reset=7")),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(retry_after(&headers("ratelimit", "limit=100")), None);
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
@@ -3,16 +3,22 @@ pub mod batch;
 pub mod buffer;
 pub mod builder;
 pub mod compressor;
+pub mod dynamic_limits;
 pub mod encoding;
 pub mod http;
 pub mod metadata;
 pub mod normalizer;
+#[cfg(feature = "sinks-utils-parquet")]
+pub mod parquet;
 pub mod partitioner;
 pub mod processed_event;
+#[cfg(feature = "sinks-utils-arrow")]
+pub mod record_batch;
 pub mod request_builder;
 pub mod retries;
 pub mod service;
 pub mod sink;
+pub mod snappy;
 pub mod socket_bytes_sink;
 pub mod statistic;
 pub mod tcp;
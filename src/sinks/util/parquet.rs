@@ -0,0 +1,320 @@
+//! Encoding of the batches of the object store sinks as [Apache Parquet][parquet] files.
+//!
+//! [parquet]: https://parquet.apache.org/
+
+use std::io;
+
+use codecs::encoding::Framer;
+use parquet::{
+    arrow::ArrowWriter, basic::Compression as ParquetCompressionCodec,
+    file::properties::WriterProperties,
+};
+use vector_config::configurable_component;
+
+use super::{
+    encoding::{write_all, Encoder as _},
+    record_batch::build_record_batch,
+    Compression,
+};
+use crate::{
+    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    event::Event,
+};
+
+const fn default_row_group_size() -> usize {
+    // The default of the Parquet writer.
+    1024 * 1024
+}
+
+/// Batch encoding configuration.
+///
+/// When set, the events of a batch are written together as a single file, instead of being
+/// serialized one after the other with `encoding`. Only the transformations of `encoding`, such as
+/// `only_fields` and `timestamp_format`, are then applied, and `compression` is not applied, as
+/// the file format compresses its contents itself.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The codec to use for encoding batches."))]
+pub enum BatchSerializerConfig {
+    /// Encodes the batch as an [Apache Parquet][parquet] file.
+    ///
+    /// The schema of the file is derived from the events of the batch, with a column for each of
+    /// their top-level fields. The integers and floats are 64-bit, the timestamps are in
+    /// microseconds, and the other values, including the objects and arrays as JSON, are strings.
+    /// The columns holding values of different types are strings, except for the ones holding both
+    /// integers and floats, which are floats.
+    ///
+    /// [parquet]: https://parquet.apache.org/
+    Parquet(
+        /// Options for the Parquet encoder.
+        ParquetSerializerConfig,
+    ),
+}
+
+/// Options for the Parquet encoder.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ParquetSerializerConfig {
+    /// The maximum number of rows of the row groups of the file.
+    #[serde(default = "default_row_group_size")]
+    #[configurable(metadata(docs::type_unit = "rows"))]
+    pub row_group_size: usize,
+
+    /// The compression of the pages of the file.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: ParquetCompression,
+}
+
+impl Default for ParquetSerializerConfig {
+    fn default() -> Self {
+        Self {
+            row_group_size: default_row_group_size(),
+            compression: ParquetCompression::default(),
+        }
+    }
+}
+
+/// The compression of the pages of the Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[default]
+    Snappy,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+impl ParquetSerializerConfig {
+    /// Builds the Parquet serializer for this config.
+    pub fn build(&self) -> crate::Result<ParquetSerializer> {
+        if self.row_group_size == 0 {
+            return Err("`row_group_size` must be greater than zero".into());
+        }
+        let compression = match self.compression {
+            ParquetCompression::None => ParquetCompressionCodec::UNCOMPRESSED,
+            ParquetCompression::Snappy => ParquetCompressionCodec::SNAPPY,
+            ParquetCompression::Zstd => ParquetCompressionCodec::ZSTD,
+        };
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(self.row_group_size)
+            .set_compression(compression)
+            .build();
+        Ok(ParquetSerializer { properties })
+    }
+}
+
+/// Writes the log events of a batch as a Parquet file.
+#[derive(Clone, Debug)]
+pub struct ParquetSerializer {
+    properties: WriterProperties,
+}
+
+impl ParquetSerializer {
+    /// Serializes the events as a Parquet file.
+    pub fn serialize(&self, events: Vec<Event>) -> io::Result<Vec<u8>> {
+        let logs = events
+            .into_iter()
+            .filter_map(Event::try_into_log)
+            .collect::<Vec<_>>();
+        let batch = build_record_batch(&logs).map_err(invalid_data)?;
+
+        let mut file = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut file, batch.schema(), Some(self.properties.clone()))
+                .map_err(invalid_data)?;
+        writer.write(&batch).map_err(invalid_data)?;
+        writer.close().map_err(invalid_data)?;
+        Ok(file)
+    }
+}
+
+fn invalid_data(error: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Encodes the events of a batch into the contents of an object.
+#[derive(Clone)]
+pub enum ObjectEncoder {
+    /// The events are serialized one after the other, and framed.
+    Framed((Transformer, Encoder<Framer>)),
+
+    /// The events are written together as a Parquet file.
+    Parquet(Transformer, ParquetSerializer),
+}
+
+impl ObjectEncoder {
+    /// Builds the encoder of the sink, using the batch encoding if set.
+    pub fn new(
+        encoding: &EncodingConfigWithFraming,
+        batch_encoding: Option<&BatchSerializerConfig>,
+    ) -> crate::Result<Self> {
+        let transformer = encoding.transformer();
+        Ok(match batch_encoding {
+            Some(BatchSerializerConfig::Parquet(config)) => {
+                Self::Parquet(transformer, config.build()?)
+            }
+            None => {
+                let (framer, serializer) = encoding.build(SinkType::MessageBased)?;
+                Self::Framed((transformer, Encoder::<Framer>::new(framer, serializer)))
+            }
+        })
+    }
+
+    /// The content type of the objects.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Framed((_, encoder)) => encoder.content_type(),
+            Self::Parquet(..) => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// The compression applied to the objects, given the configured one.
+    ///
+    /// The files of the batch encodings are never compressed, as they compress their contents
+    /// themselves.
+    pub const fn compression(&self, compression: Compression) -> Compression {
+        match self {
+            Self::Framed(_) => compression,
+            Self::Parquet(..) => Compression::None,
+        }
+    }
+
+    /// The filename extension of the objects, given the configured compression.
+    pub const fn extension(&self, compression: Compression) -> &'static str {
+        match self {
+            Self::Framed(_) => compression.extension(),
+            Self::Parquet(..) => "parquet",
+        }
+    }
+}
+
+impl super::encoding::Encoder<Vec<Event>> for ObjectEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        match self {
+            Self::Framed(encoder) => encoder.encode_input(events, writer),
+            Self::Parquet(transformer, serializer) => {
+                let count = events.len();
+                let events = events
+                    .into_iter()
+                    .map(|mut event| {
+                        transformer.transform(&mut event);
+                        event
+                    })
+                    .collect();
+                let file = serializer.serialize(events)?;
+                write_all(writer, count, &file)?;
+                Ok(file.len())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use codecs::{encoding::FramingConfig, TextSerializerConfig};
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn events(count: usize) -> Vec<Event> {
+        (0..count)
+            .map(|index| {
+                let mut log = LogEvent::from(format!("message {}", index));
+                log.insert("index", index as i64);
+                log.into()
+            })
+            .collect()
+    }
+
+    fn encoder(config: ParquetSerializerConfig) -> ObjectEncoder {
+        ObjectEncoder::new(
+            &(None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            Some(&BatchSerializerConfig::Parquet(config)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn writes_row_groups() {
+        let encoder = encoder(ParquetSerializerConfig {
+            row_group_size: 2,
+            compression: ParquetCompression::Zstd,
+        });
+
+        let mut file = Vec::new();
+        let written = encoder.encode_input(events(5), &mut file).unwrap();
+        assert_eq!(written, file.len());
+
+        let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 5);
+        assert_eq!(metadata.num_row_groups(), 3);
+        let columns = metadata
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|column| column.name().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec!["index", "message"]);
+        assert_eq!(
+            metadata.row_group(0).column(0).compression(),
+            ParquetCompressionCodec::ZSTD
+        );
+    }
+
+    #[test]
+    fn applies_the_transformer() {
+        let encoding = EncodingConfigWithFraming::new(
+            None,
+            TextSerializerConfig::default().into(),
+            Transformer::new(None, Some(vec!["index".into()]), None).unwrap(),
+        );
+        let encoder = ObjectEncoder::new(
+            &encoding,
+            Some(&BatchSerializerConfig::Parquet(Default::default())),
+        )
+        .unwrap();
+
+        let mut file = Vec::new();
+        encoder.encode_input(events(1), &mut file).unwrap();
+
+        let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr();
+        assert_eq!(schema.num_columns(), 1);
+        assert_eq!(schema.column(0).name(), "message");
+    }
+
+    #[test]
+    fn parquet_is_not_compressed_again() {
+        let encoder = encoder(Default::default());
+        assert_eq!(
+            encoder.compression(Compression::gzip_default()),
+            Compression::None
+        );
+        assert_eq!(encoder.extension(Compression::gzip_default()), "parquet");
+    }
+
+    #[test]
+    fn rejects_empty_row_groups() {
+        let config = ParquetSerializerConfig {
+            row_group_size: 0,
+            ..Default::default()
+        };
+        assert!(config.build().is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! Building Arrow record batches from events, with a schema inferred from their fields.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use arrow::{
+    array::{
+        ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, TimestampMicrosecondArray,
+    },
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    error::ArrowError,
+    record_batch::{RecordBatch, RecordBatchOptions},
+};
+use chrono::SecondsFormat;
+use vector_core::config::log_schema;
+
+use crate::event::{LogEvent, Value};
+
+/// The type of the columns, derived from the values of the events.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ColumnKind {
+    Boolean,
+    Integer,
+    Float,
+    Timestamp,
+    String,
+}
+
+impl ColumnKind {
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Boolean(_) => Some(Self::Boolean),
+            Value::Integer(_) => Some(Self::Integer),
+            Value::Float(_) => Some(Self::Float),
+            Value::Timestamp(_) => Some(Self::Timestamp),
+            _ => Some(Self::String),
+        }
+    }
+
+    /// The kind of a column holding values of both kinds.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Integer, Self::Float) | (Self::Float, Self::Integer) => Self::Float,
+            _ => Self::String,
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Boolean => DataType::Boolean,
+            Self::Integer => DataType::Int64,
+            Self::Float => DataType::Float64,
+            Self::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into())),
+            Self::String => DataType::Utf8,
+        }
+    }
+
+    fn build_array<'a>(self, values: impl Iterator<Item = Option<&'a Value>>) -> ArrayRef {
+        match self {
+            Self::Boolean => Arc::new(
+                values
+                    .map(|value| match value {
+                        Some(Value::Boolean(boolean)) => Some(*boolean),
+                        _ => None,
+                    })
+                    .collect::<BooleanArray>(),
+            ),
+            Self::Integer => Arc::new(
+                values
+                    .map(|value| match value {
+                        Some(Value::Integer(integer)) => Some(*integer),
+                        _ => None,
+                    })
+                    .collect::<Int64Array>(),
+            ),
+            Self::Float => Arc::new(
+                values
+                    .map(|value| match value {
+                        Some(Value::Float(float)) => Some(float.into_inner()),
+                        Some(Value::Integer(integer)) => Some(*integer as f64),
+                        _ => None,
+                    })
+                    .collect::<Float64Array>(),
+            ),
+            Self::Timestamp => Arc::new(
+                values
+                    .map(|value| match value {
+                        Some(Value::Timestamp(timestamp)) => Some(timestamp.timestamp_micros()),
+                        _ => None,
+                    })
+                    .collect::<TimestampMicrosecondArray>()
+                    .with_timezone("+00:00".to_owned()),
+            ),
+            Self::String => Arc::new(
+                values
+                    .map(|value| value.and_then(to_string))
+                    .collect::<StringArray>(),
+            ),
+        }
+    }
+}
+
+fn to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Timestamp(timestamp) => Some(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        Value::Object(_) | Value::Array(_) => serde_json::to_string(value).ok(),
+        value => Some(value.to_string_lossy().into_owned()),
+    }
+}
+
+/// Gets the top-level fields of the event, which are the columns of the record batches.
+///
+/// The events whose root isn't an object, such as the ones with the Vector namespace, are sent as
+/// their message.
+fn fields(log: &LogEvent) -> Vec<(&str, &Value)> {
+    match log.value() {
+        Value::Object(map) => map
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+            .collect(),
+        value => vec![(log_schema().message_key(), value)],
+    }
+}
+
+fn field<'a>(log: &'a LogEvent, name: &str) -> Option<&'a Value> {
+    match log.value() {
+        Value::Object(map) => map.get(name),
+        value => (name == log_schema().message_key()).then_some(value),
+    }
+}
+
+/// Builds a record batch from the events, with a column for each of their top-level fields.
+///
+/// The type of the columns is inferred from their values: the integers and floats are 64-bit, the
+/// timestamps are in microseconds, and the other values, including the objects and arrays as JSON,
+/// are strings. The columns holding values of different types are strings, except for the ones
+/// holding both integers and floats, which are floats.
+pub fn build_record_batch(events: &[LogEvent]) -> Result<RecordBatch, ArrowError> {
+    let mut kinds = BTreeMap::<&str, Option<ColumnKind>>::new();
+    for log in events {
+        for (name, value) in fields(log) {
+            let kind = kinds.entry(name).or_default();
+            *kind = match (*kind, ColumnKind::of(value)) {
+                (Some(kind), Some(other)) => Some(kind.merge(other)),
+                (kind, other) => kind.or(other),
+            };
+        }
+    }
+
+    let mut schema_fields = Vec::with_capacity(kinds.len());
+    let mut columns = Vec::with_capacity(kinds.len());
+    for (name, kind) in kinds {
+        // The columns only holding nulls are sent as strings.
+        let kind = kind.unwrap_or(ColumnKind::String);
+        schema_fields.push(Field::new(name, kind.data_type(), true));
+        columns.push(kind.build_array(events.iter().map(|log| field(log, name))));
+    }
+
+    RecordBatch::try_new_with_options(
+        Arc::new(Schema::new(schema_fields)),
+        columns,
+        &RecordBatchOptions::new().with_row_count(Some(events.len())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Array;
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn log(fields: Vec<(&str, Value)>) -> LogEvent {
+        let mut log = LogEvent::default();
+        for (name, value) in fields {
+            log.insert(name, value);
+        }
+        log
+    }
+
+    #[test]
+    fn infers_the_schema() {
+        let timestamp = Utc
+            .ymd(2023, 3, 1)
+            .and_hms_milli_opt(12, 0, 0, 500)
+            .expect("invalid timestamp");
+        let events = vec![
+            log(vec![
+                ("message", Value::from("first")),
+                ("count", Value::from(1)),
+                ("ratio", Value::from(1)),
+                ("timestamp", Value::from(timestamp)),
+            ]),
+            log(vec![
+                ("message", Value::from("second")),
+                ("ratio", Value::from(0.5)),
+                ("success", Value::from(true)),
+                ("tags", Value::from(vec![Value::from("a")])),
+            ]),
+        ];
+
+        let batch = build_record_batch(&events).unwrap();
+        let schema = batch.schema();
+        let types = schema
+            .fields()
+            .iter()
+            .map(|field| (field.name().as_str(), field.data_type().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                ("count", DataType::Int64),
+                ("message", DataType::Utf8),
+                ("ratio", DataType::Float64),
+                ("success", DataType::Boolean),
+                ("tags", DataType::Utf8),
+                (
+                    "timestamp",
+                    DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+                ),
+            ]
+        );
+        assert_eq!(batch.num_rows(), 2);
+
+        let count = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(count.value(0), 1);
+        assert!(count.is_null(1));
+        let ratio = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(ratio.values(), &[1.0, 0.5]);
+        let tags = batch
+            .column(4)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(tags.value(1), r#"["a"]"#);
+    }
+
+    #[test]
+    fn mixed_types_are_strings() {
+        let events = vec![
+            log(vec![("status", Value::from(200))]),
+            log(vec![("status", Value::from("error"))]),
+        ];
+
+        let batch = build_record_batch(&events).unwrap();
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::Utf8);
+        let status = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(status.value(0), "200");
+        assert_eq!(status.value(1), "error");
+    }
+}
@@ -10,6 +10,7 @@ use std::{
 use futures::FutureExt;
 use tokio::time::{sleep, Sleep};
 use tower::{retry::Policy, timeout::error::Elapsed};
+use vector_core::stream::record_attempt;
 
 use crate::Error;
 
@@ -32,6 +33,20 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// Returns how long the response asks to wait before retrying, if it does.
+    ///
+    /// The retry then waits for at least as long, up to the maximum backoff.
+    fn retry_after(&self, _response: &Self::Response) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the status code of the response, for the protocols that have one.
+    ///
+    /// The status code of the last attempt is attached to the events which fail to be delivered.
+    fn status_code(&self, _response: &Self::Response) -> Option<u16> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -80,12 +95,33 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
         self.current_duration
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    fn delay(&self, retry_after: Option<Duration>) -> Duration {
+        match retry_after {
+            Some(retry_after) => cmp::min(cmp::max(retry_after, self.backoff()), self.max_duration),
+            None => self.backoff(),
+        }
+    }
+
+    /// Decides whether to retry after `response`, and records the attempt which ended with it.
+    fn record_response(&self, response: &L::Response) -> RetryAction {
+        let action = self.logic.should_retry_response(response);
+        let error_class = match action {
+            RetryAction::Successful => None,
+            RetryAction::Retry(_) | RetryAction::DontRetry(_) => Some("response".to_owned()),
+        };
+        record_attempt(self.logic.status_code(response), error_class);
+        action
+    }
+
+    fn build_retry(&self, retry_after: Option<Duration>) -> RetryPolicyFuture<L> {
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let delay = self.delay(retry_after);
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
-        RetryPolicyFuture { delay, policy }
+        debug!(message = "Retrying request.", delay_ms = %delay.as_millis());
+        RetryPolicyFuture {
+            delay: Box::pin(sleep(delay)),
+            policy,
+        }
     }
 }
 
@@ -100,7 +136,7 @@ where
     // driver, so only need to log here.
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
         match result {
-            Ok(response) => match self.logic.should_retry_response(response) {
+            Ok(response) => match self.record_response(response) {
                 RetryAction::Retry(reason) => {
                     if self.remaining_attempts == 0 {
                         error!(
@@ -112,7 +148,7 @@ where
                     }
 
                     warn!(message = "Retrying after response.", reason = %reason, internal_log_rate_limit = true);
-                    Some(self.build_retry())
+                    Some(self.build_retry(self.logic.retry_after(response)))
                 }
 
                 RetryAction::DontRetry(reason) => {
@@ -123,6 +159,7 @@ where
                 RetryAction::Successful => None,
             },
             Err(error) => {
+                record_attempt(None, Some(error_class(error)));
                 if self.remaining_attempts == 0 {
                     error!(message = "Retries exhausted; dropping the request.", %error, internal_log_rate_limit = true);
                     return None;
@@ -131,7 +168,7 @@ where
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
                     if self.logic.is_retriable_error(expected) {
                         warn!(message = "Retrying after error.", error = %expected, internal_log_rate_limit = true);
-                        Some(self.build_retry())
+                        Some(self.build_retry(None))
                     } else {
                         error!(
                             message = "Non-retriable error; dropping the request.",
@@ -145,7 +182,7 @@ where
                         message = "Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.",
                         internal_log_rate_limit = true
                     );
-                    Some(self.build_retry())
+                    Some(self.build_retry(None))
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -176,6 +213,16 @@ impl<L: RetryLogic> Future for RetryPolicyFuture<L> {
     }
 }
 
+/// Returns the class of `error`, which is the name of its type, or of its variant for enums.
+fn error_class(error: &Error) -> String {
+    format!("{:?}", error)
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .filter(|class| !class.is_empty())
+        .unwrap_or("unknown")
+        .to_owned()
+}
+
 impl RetryAction {
     pub const fn is_retryable(&self) -> bool {
         matches!(self, RetryAction::Retry(_))
@@ -393,6 +440,60 @@ mod tests {
         assert_eq!(Duration::from_secs(10), policy.backoff());
     }
 
+    #[test]
+    fn delay_waits_for_retry_after() {
+        let policy = FixedRetryPolicy::new(
+            10,
+            Duration::from_secs(2),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        );
+        assert_eq!(Duration::from_secs(2), policy.delay(None));
+        assert_eq!(
+            Duration::from_secs(2),
+            policy.delay(Some(Duration::from_secs(1)))
+        );
+        assert_eq!(
+            Duration::from_secs(5),
+            policy.delay(Some(Duration::from_secs(5)))
+        );
+        assert_eq!(
+            Duration::from_secs(10),
+            policy.delay(Some(Duration::from_secs(60)))
+        );
+    }
+
+    #[tokio::test]
+    async fn records_attempts() {
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        );
+
+        let ((), attempts) = vector_core::stream::track_delivery_attempts(async {
+            let retriable: crate::Error = Box::new(Error(true));
+            let fatal: crate::Error = Box::new(Error(false));
+            assert!(policy.retry(&"hello", Err(&retriable)).is_some());
+            assert!(policy.retry(&"hello", Err(&fatal)).is_none());
+        })
+        .await;
+
+        let failure = attempts.failure("request_failed");
+        assert_eq!(failure.attempts, 2);
+        assert_eq!(failure.status_code, None);
+        assert_eq!(failure.error_class, "Error");
+    }
+
+    #[test]
+    fn classifies_errors() {
+        let elapsed: crate::Error = Box::new(Elapsed::new());
+        assert_eq!(error_class(&elapsed), "Elapsed");
+        let error: crate::Error = Box::new(Error(true));
+        assert_eq!(error_class(&error), "Error");
+    }
+
     #[derive(Debug, Clone)]
     struct SvcRetryLogic;
 
@@ -12,11 +12,14 @@ use tower::{
     timeout::Timeout,
     Service, ServiceBuilder,
 };
+use vector_common::request_metadata::MetaDescriptive;
 use vector_config::configurable_component;
 
 pub use crate::sinks::util::service::{
+    bandwidth::{set_global_bandwidth_limit, BandwidthLimit, BandwidthLimitLayer},
     concurrency::{concurrency_is_none, Concurrency},
     health::{HealthConfig, HealthLogic, HealthService},
+    lanes::{lane, Laned, OrderedLanes},
     map::Map,
 };
 use crate::{
@@ -32,13 +35,19 @@ use crate::{
     },
 };
 
+mod bandwidth;
 mod concurrency;
 mod health;
+mod lanes;
 mod map;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
-pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
-pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
+pub type Svc<S, L> =
+    RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, BandwidthLimit<Timeout<S>>>, L>>;
+/// The service of the deprecated batch sinks, whose requests can't be weighed to limit the bandwidth.
+pub type LegacySvc<S, L> =
+    RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type TowerBatchedSink<S, B, RL> = BatchSink<LegacySvc<S, RL>, B>;
+pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<LegacySvc<S, RL>, B, K>;
 
 // Distributed service types
 pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
@@ -113,6 +122,14 @@ pub struct TowerRequestConfig {
     #[serde(default = "default_rate_limit_num")]
     pub rate_limit_num: Option<u64>,
 
+    /// The maximum number of bytes sent per second.
+    ///
+    /// The requests are weighed by their encoded size, and are delayed once the limit is reached.
+    /// The sinks still using the deprecated batching don't support this option, and ignore it.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[serde(default)]
+    pub rate_limit_bytes_per_sec: Option<u64>,
+
     /// The maximum number of retries to make for failed requests.
     ///
     /// The default, for all intents and purposes, represents an infinite number of retries.
@@ -174,6 +191,7 @@ impl Default for TowerRequestConfig {
             timeout_secs: default_timeout_secs(),
             rate_limit_duration_secs: default_rate_limit_duration_secs(),
             rate_limit_num: default_rate_limit_num(),
+            rate_limit_bytes_per_sec: None,
             retry_attempts: default_retry_attempts(),
             retry_max_duration_secs: default_retry_max_duration_secs(),
             retry_initial_backoff_secs: default_retry_initial_backoff_secs(),
@@ -241,6 +259,9 @@ impl TowerRequestConfig {
                 .or(defaults.rate_limit_num)
                 .or(default_rate_limit_num())
                 .unwrap(),
+            rate_limit_bytes_per_sec: self
+                .rate_limit_bytes_per_sec
+                .or(defaults.rate_limit_bytes_per_sec),
             retry_attempts: self
                 .retry_attempts
                 .or(defaults.retry_attempts)
@@ -269,6 +290,7 @@ pub struct TowerRequestSettings {
     pub timeout: Duration,
     pub rate_limit_duration: Duration,
     pub rate_limit_num: u64,
+    pub rate_limit_bytes_per_sec: Option<u64>,
     pub retry_attempts: usize,
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
@@ -304,9 +326,7 @@ impl TowerRequestSettings {
         B::Output: Send + Clone + 'static,
         K: Hash + Eq + Clone + Send + 'static,
     {
-        let service = ServiceBuilder::new()
-            .settings(self.clone(), retry_logic)
-            .service(service);
+        let service = self.legacy_service(retry_logic, service);
         PartitionBatchSink::new(service, batch, batch_timeout)
     }
 
@@ -327,12 +347,40 @@ impl TowerRequestSettings {
         B: Batch,
         B::Output: Send + Clone + 'static,
     {
-        let service = ServiceBuilder::new()
-            .settings(self.clone(), retry_logic)
-            .service(service);
+        let service = self.legacy_service(retry_logic, service);
         BatchSink::new(service, batch, batch_timeout)
     }
 
+    /// Builds the service of the deprecated batch sinks, whose requests don't report their size to
+    /// limit the bandwidth.
+    fn legacy_service<RL, S, Request>(&self, retry_logic: RL, service: S) -> LegacySvc<S, RL>
+    where
+        RL: RetryLogic<Response = S::Response>,
+        S: Service<Request> + Clone + Send + 'static,
+        S::Error: Into<crate::Error> + Send + Sync + 'static,
+        S::Response: Send,
+        S::Future: Send + 'static,
+        Request: Clone + Send + 'static,
+    {
+        if self.rate_limit_bytes_per_sec.is_some() {
+            warn!(
+                message = "The `request.rate_limit_bytes_per_sec` option isn't supported by this sink, ignoring it."
+            );
+        }
+
+        let policy = self.retry_policy(retry_logic.clone());
+        ServiceBuilder::new()
+            .rate_limit(self.rate_limit_num, self.rate_limit_duration)
+            .layer(AdaptiveConcurrencyLimitLayer::new(
+                self.concurrency,
+                self.adaptive_concurrency,
+                retry_logic,
+            ))
+            .retry(policy)
+            .timeout(self.timeout)
+            .service(service)
+    }
+
     /// Distributes requests to services [(Endpoint, service, healthcheck)]
     pub fn distributed_service<Req, RL, HL, S>(
         self,
@@ -355,7 +403,8 @@ impl TowerRequestSettings {
 
         // Build services
         let open = OpenGauge::new();
-        let max_concurrency = services.len() * AdaptiveConcurrencySettings::max_concurrency();
+        let max_concurrency =
+            services.len() * settings.adaptive_concurrency.max_concurrency_limit();
         let services = services
             .into_iter()
             .map(|(endpoint, inner)| {
@@ -406,7 +455,7 @@ where
     S::Error: Into<crate::Error> + Send + Sync + 'static,
     S::Future: Send + 'static,
     RL: RetryLogic<Response = S::Response> + Send + 'static,
-    Request: Clone + Send + 'static,
+    Request: Clone + MetaDescriptive + Send + 'static,
 {
     type Service = Svc<S, RL>;
 
@@ -423,6 +472,9 @@ where
                 self.retry_logic.clone(),
             ))
             .retry(policy)
+            .layer(BandwidthLimitLayer::new(
+                self.settings.rate_limit_bytes_per_sec,
+            ))
             .timeout(self.settings.timeout)
             .service(inner)
     }
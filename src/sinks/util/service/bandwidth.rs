@@ -0,0 +1,210 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, RwLock},
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::time::{sleep, Instant, Sleep};
+use tower::{Layer, Service};
+use vector_common::request_metadata::MetaDescriptive;
+
+use crate::internal_events::{BandwidthThrottled, BandwidthUnthrottled};
+
+/// The limiter of the bytes sent by all of the sinks together.
+static GLOBAL_LIMITER: Lazy<RwLock<Option<Arc<BandwidthLimiter>>>> = Lazy::new(RwLock::default);
+
+/// Sets the limit of the bytes per second sent by all of the sinks together.
+///
+/// The state of the limiter is kept if the limit didn't change, such as when reloading.
+pub fn set_global_bandwidth_limit(bytes_per_sec: Option<u64>) {
+    let mut global = GLOBAL_LIMITER.write().unwrap();
+    if global.as_ref().map(|limiter| limiter.bytes_per_sec) != bytes_per_sec {
+        *global = bytes_per_sec.map(|bytes_per_sec| Arc::new(BandwidthLimiter::new(bytes_per_sec)));
+    }
+}
+
+fn global_limiter() -> Option<Arc<BandwidthLimiter>> {
+    GLOBAL_LIMITER.read().unwrap().clone()
+}
+
+/// A token bucket limiting the bytes sent per second.
+///
+/// The bucket holds up to a second worth of bytes. The requests are sent as long as it isn't
+/// empty, so that a request larger than the bucket is still sent, and delays the following ones.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    bytes_per_sec: u64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    bytes: f64,
+    updated: Instant,
+}
+
+impl BandwidthLimiter {
+    fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1);
+        Self {
+            bytes_per_sec,
+            bucket: Mutex::new(Bucket {
+                bytes: bytes_per_sec as f64,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Refills the bucket, and returns how long to wait until it isn't empty anymore.
+    fn delay(&self) -> Option<Duration> {
+        let rate = self.bytes_per_sec as f64;
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.bytes = (bucket.bytes + elapsed * rate).min(rate);
+        bucket.updated = now;
+
+        (bucket.bytes < 1.0).then(|| Duration::from_secs_f64((1.0 - bucket.bytes) / rate))
+    }
+
+    fn consume(&self, bytes: usize) {
+        self.bucket.lock().unwrap().bytes -= bytes as f64;
+    }
+}
+
+/// Limits the bytes per second sent by a sink, and by all of the sinks together.
+#[derive(Clone, Debug)]
+pub struct BandwidthLimitLayer {
+    limiter: Option<Arc<BandwidthLimiter>>,
+}
+
+impl BandwidthLimitLayer {
+    pub fn new(bytes_per_sec: Option<u64>) -> Self {
+        Self {
+            limiter: bytes_per_sec
+                .map(|bytes_per_sec| Arc::new(BandwidthLimiter::new(bytes_per_sec))),
+        }
+    }
+}
+
+impl<S> Layer<S> for BandwidthLimitLayer {
+    type Service = BandwidthLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BandwidthLimit {
+            inner,
+            limiter: self.limiter.clone(),
+            sleep: None,
+            throttled: None,
+        }
+    }
+}
+
+/// A service which delays the requests while the bandwidth limit is reached.
+///
+/// The requests are weighed by their encoded size.
+pub struct BandwidthLimit<S> {
+    inner: S,
+    limiter: Option<Arc<BandwidthLimiter>>,
+    sleep: Option<Pin<Box<Sleep>>>,
+    /// The limit currently delaying the requests.
+    throttled: Option<&'static str>,
+}
+
+impl<S: Clone> Clone for BandwidthLimit<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            limiter: self.limiter.clone(),
+            sleep: None,
+            throttled: None,
+        }
+    }
+}
+
+impl<S, Req> Service<Req> for BandwidthLimit<S>
+where
+    S: Service<Req>,
+    Req: MetaDescriptive,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        loop {
+            if let Some(sleep) = self.sleep.as_mut() {
+                ready!(sleep.as_mut().poll(cx));
+                self.sleep = None;
+            }
+
+            let global = global_limiter();
+            let delay = [("sink", self.limiter.as_ref()), ("global", global.as_ref())]
+                .into_iter()
+                .filter_map(|(limit, limiter)| Some((limit, limiter?.delay()?)))
+                .max_by_key(|(_, delay)| *delay);
+
+            match delay {
+                Some((limit, delay)) => {
+                    if let Some(previous) = self.throttled.replace(limit) {
+                        if previous != limit {
+                            emit!(BandwidthUnthrottled { limit: previous });
+                        }
+                    }
+                    emit!(BandwidthThrottled { limit, delay });
+                    self.sleep = Some(Box::pin(sleep(delay)));
+                }
+                None => {
+                    if let Some(limit) = self.throttled.take() {
+                        emit!(BandwidthUnthrottled { limit });
+                    }
+                    return self.inner.poll_ready(cx);
+                }
+            }
+        }
+    }
+
+    fn call(&mut self, request: Req) -> Self::Future {
+        let bytes = request.get_metadata().request_encoded_size();
+        for limiter in self.limiter.iter().chain(global_limiter().as_ref()) {
+            limiter.consume(bytes);
+        }
+
+        self.inner.call(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_delay(limiter: &BandwidthLimiter, secs: f64) {
+        let delay = limiter.delay().expect("bucket should be empty");
+        assert!((delay.as_secs_f64() - secs).abs() < 1e-6, "{:?}", delay);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delays_until_bucket_is_refilled() {
+        let limiter = BandwidthLimiter::new(1000);
+        assert_eq!(limiter.delay(), None);
+
+        // A request larger than the bucket is sent, and delays the following ones.
+        limiter.consume(2500);
+        assert_delay(&limiter, 1.501);
+
+        tokio::time::advance(Duration::from_millis(1502)).await;
+        assert_eq!(limiter.delay(), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn holds_up_to_a_second_of_bytes() {
+        let limiter = BandwidthLimiter::new(1000);
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        limiter.consume(1000);
+        assert_delay(&limiter, 0.001);
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use tokio::sync::oneshot;
+use tower::{buffer::Buffer, Service};
+
+use crate::{
+    event::LogEvent,
+    internal_events::{OrderingLaneQueued, OrderingLanesActive},
+};
+
+/// A request which is sent in order with the other requests of its lane, if any.
+pub trait Laned {
+    /// Returns the lane of the request, if it's ordered.
+    fn lane(&self) -> Option<u64>;
+}
+
+/// Returns the lane of a log event, given by the value of its `field`.
+///
+/// The events missing the field don't have any lane.
+pub fn lane(log: &LogEvent, field: &str) -> Option<u64> {
+    log.get(field)
+        .map(|value| seahash::hash(value.to_string_lossy().as_bytes()))
+}
+
+/// The requests of a lane, each waiting for the previous one to be sent.
+struct Lane {
+    /// Resolves once the last request called with is sent.
+    last: oneshot::Receiver<()>,
+    depth: usize,
+}
+
+type Lanes = Arc<Mutex<HashMap<u64, Lane>>>;
+
+/// The maximum number of requests waiting for their turn, or to be sent by the inner service.
+const MAX_QUEUED_REQUESTS: usize = 1024;
+
+/// Sends the requests of each lane one at a time, in the order they're called with, so that the
+/// retries of a request can't reorder it with the next ones of its lane.
+///
+/// The requests of different lanes, and the ones without any lane, are still sent concurrently,
+/// up to the limits of the inner service, which is buffered for the requests to wait for their
+/// turn apart from it.
+pub struct OrderedLanes<S, Request>
+where
+    S: Service<Request>,
+{
+    inner: Buffer<S, Request>,
+    lanes: Lanes,
+}
+
+impl<S, Request> OrderedLanes<S, Request>
+where
+    S: Service<Request> + Send + 'static,
+    S::Error: Into<crate::Error> + Send + Sync,
+    S::Future: Send,
+    Request: Send + 'static,
+{
+    /// Wraps the given service, which must be called from a Tokio runtime.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner: Buffer::new(inner, MAX_QUEUED_REQUESTS),
+            lanes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<S, Request> Service<Request> for OrderedLanes<S, Request>
+where
+    S: Service<Request> + Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Into<crate::Error> + Send + Sync,
+    S::Future: Send + 'static,
+    Request: Laned + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let lane = match request.lane() {
+            Some(lane) => lane,
+            None => return Box::pin(self.inner.call(request)),
+        };
+
+        // The service which was polled ready is the one sending the request, once its turn
+        // comes.
+        let clone = self.inner.clone();
+        let mut inner = mem::replace(&mut self.inner, clone);
+        let lanes = Arc::clone(&self.lanes);
+        let (sent, last) = oneshot::channel();
+        let previous = {
+            let mut lanes = lanes.lock().expect("lanes lock poisoned");
+            match lanes.get_mut(&lane) {
+                Some(queued) => {
+                    queued.depth += 1;
+                    emit!(OrderingLaneQueued {
+                        depth: queued.depth
+                    });
+                    Some(mem::replace(&mut queued.last, last))
+                }
+                None => {
+                    lanes.insert(lane, Lane { last, depth: 1 });
+                    emit!(OrderingLaneQueued { depth: 1 });
+                    emit!(OrderingLanesActive { count: lanes.len() });
+                    None
+                }
+            }
+        };
+
+        Box::pin(async move {
+            // The previous request is either sent or dropped once its sender is.
+            if let Some(previous) = previous {
+                let _ = previous.await;
+            }
+            let result = inner.call(request).await;
+            let _ = sent.send(());
+
+            let mut lanes = lanes.lock().expect("lanes lock poisoned");
+            if let Some(queued) = lanes.get_mut(&lane) {
+                queued.depth -= 1;
+                if queued.depth == 0 {
+                    lanes.remove(&lane);
+                    emit!(OrderingLanesActive { count: lanes.len() });
+                }
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::{stream::FuturesUnordered, StreamExt};
+    use tokio::time::sleep;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+
+    struct Request {
+        lane: Option<u64>,
+        attempts: usize,
+        id: usize,
+    }
+
+    impl Laned for Request {
+        fn lane(&self) -> Option<u64> {
+            self.lane
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_lanes_in_order() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let service = {
+            let sent = Arc::clone(&sent);
+            service_fn(move |request: Request| {
+                let sent = Arc::clone(&sent);
+                async move {
+                    // The first requests take the longest, as if they were retried.
+                    for _ in 0..request.attempts {
+                        sleep(Duration::from_millis(10)).await;
+                    }
+                    sent.lock().unwrap().push((request.lane, request.id));
+                    Ok::<_, crate::Error>(())
+                }
+            })
+        };
+        let mut service = OrderedLanes::new(service);
+
+        let mut responses = FuturesUnordered::new();
+        for id in 0..4 {
+            for lane in [Some(1), Some(2), None] {
+                let request = Request {
+                    lane,
+                    attempts: 4 - id,
+                    id,
+                };
+                responses.push(service.ready().await.unwrap().call(request));
+            }
+        }
+        while let Some(response) = responses.next().await {
+            response.unwrap();
+        }
+
+        let sent = sent.lock().unwrap();
+        for lane in [Some(1), Some(2)] {
+            let ids = sent
+                .iter()
+                .filter(|(l, _)| *l == lane)
+                .map(|(_, id)| *id)
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec![0, 1, 2, 3]);
+        }
+        // The requests without any lane aren't ordered.
+        let ids = sent
+            .iter()
+            .filter(|(l, _)| l.is_none())
+            .map(|(_, id)| *id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![3, 2, 1, 0]);
+        assert!(service.lanes.lock().unwrap().is_empty());
+    }
+}
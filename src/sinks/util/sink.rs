@@ -49,8 +49,9 @@ use tokio::{
 use tower::{Service, ServiceBuilder};
 use tracing::Instrument;
 use vector_common::internal_event::{
-    CallError, CountByteSize, EventsSent, InternalEventHandle as _, Output,
+    error_type, CallError, CountByteSize, EventsSent, InternalEventHandle as _, Output,
 };
+use vector_core::stream::track_delivery_attempts;
 // === StreamSink<Event> ===
 pub use vector_core::sink::StreamSink;
 
@@ -60,7 +61,7 @@ use super::{
     service::{Map, ServiceBuilderExt},
     EncodedEvent,
 };
-use crate::event::EventStatus;
+use crate::{event::EventStatus, internal_events::OrderingLanesActive};
 
 // === BatchSink ===
 
@@ -327,6 +328,7 @@ where
 
                     if let Some(map) = this.in_flight.as_mut() {
                         map.insert(partition.clone(), future.map(|_| ()).fuse().boxed());
+                        emit!(OrderingLanesActive { count: map.len() });
                     }
 
                     batch_consumed = true;
@@ -347,6 +349,9 @@ where
                     in_flight.retain(|partition, req| {
                         partitions.contains_key(partition) || req.poll_unpin(cx).is_pending()
                     });
+                    emit!(OrderingLanesActive {
+                        count: in_flight.len()
+                    });
                 }
             }
 
@@ -441,11 +446,12 @@ where
             in_flight_requests = self.in_flight.len()
         );
         let events_sent = register!(EventsSent::from(Output(None)));
-        self.service
-            .call(items)
-            .err_into()
-            .map(move |result| {
+        track_delivery_attempts(self.service.call(items).err_into())
+            .map(move |(result, attempts)| {
                 let status = result_status(&result);
+                if status != EventStatus::Delivered {
+                    finalizers.record_failure(&attempts.failure(error_type::REQUEST_FAILED));
+                }
                 finalizers.update_status(status);
                 match status {
                     EventStatus::Delivered => {
@@ -0,0 +1,70 @@
+use std::io;
+
+/// An encoder compressing everything written to it at once, in the raw [Snappy][snappy] format,
+/// into the given writer.
+///
+/// Unlike the framed format, the raw one is what HTTP servers expect of a `snappy` content
+/// encoding, but it can't be streamed: the data is held in memory until the encoder is finished.
+///
+/// [snappy]: https://github.com/google/snappy/blob/main/format_description.txt
+pub struct SnappyEncoder<W: io::Write> {
+    writer: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: io::Write> SnappyEncoder<W> {
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer, which is only written to once finished.
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Compresses the data written so far into the underlying writer, returning it.
+    ///
+    /// # Errors
+    ///
+    /// If the data can't be compressed, or written to the underlying writer, an error is returned.
+    pub fn finish(mut self) -> io::Result<W> {
+        let compressed = snap::raw::Encoder::new().compress_vec(&self.buffer)?;
+        self.writer.write_all(&compressed)?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: io::Write> io::Write for SnappyEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn compresses_raw_snappy() {
+        let mut encoder = SnappyEncoder::new(Vec::new());
+        encoder.write_all(b"It's going down, ").unwrap();
+        encoder.write_all(b"I'm yelling timber").unwrap();
+        assert!(encoder.get_ref().is_empty());
+
+        let compressed = encoder.finish().unwrap();
+        let decompressed = snap::raw::Decoder::new()
+            .decompress_vec(&compressed)
+            .unwrap();
+        assert_eq!(decompressed, b"It's going down, I'm yelling timber");
+    }
+}
@@ -8,15 +8,20 @@ use std::{
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-use futures::{stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
+use futures::{
+    future::join_all, stream::BoxStream, task::noop_waker_ref, SinkExt, Stream, StreamExt,
+};
 use futures_util::{future::ready, stream};
 use snafu::{ResultExt, Snafu};
 use tokio::{
     io::{AsyncRead, ReadBuf},
     net::TcpStream,
+    sync::mpsc::{self, error::TrySendError},
     time::sleep,
 };
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::codec::Encoder;
+use tracing::Instrument;
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
@@ -77,6 +82,30 @@ pub struct TcpSinkConfig {
     #[configurable(metadata(docs::type_unit = "bytes"))]
     #[configurable(metadata(docs::examples = 65536))]
     send_buffer_bytes: Option<usize>,
+
+    /// The number of connections opened to the address.
+    ///
+    /// The events are spread across the connections, so they aren't sent in order anymore when
+    /// there's more than one.
+    #[configurable(validation(range(min = 1)))]
+    #[serde(default = "default_pool_size")]
+    pool_size: usize,
+
+    /// The number of events queued for a connection at most.
+    ///
+    /// Once a connection reaches its limit, the events go to the other connections, not to wait on
+    /// a slow one.
+    #[configurable(validation(range(min = 1)))]
+    #[serde(default = "default_in_flight_limit")]
+    in_flight_limit: usize,
+}
+
+const fn default_pool_size() -> usize {
+    1
+}
+
+const fn default_in_flight_limit() -> usize {
+    1_000
 }
 
 impl TcpSinkConfig {
@@ -91,16 +120,13 @@ impl TcpSinkConfig {
             keepalive,
             tls,
             send_buffer_bytes,
+            pool_size: default_pool_size(),
+            in_flight_limit: default_in_flight_limit(),
         }
     }
 
     pub const fn from_address(address: String) -> Self {
-        Self {
-            address,
-            keepalive: None,
-            tls: None,
-            send_buffer_bytes: None,
-        }
+        Self::new(address, None, None, None)
     }
 
     pub fn build(
@@ -113,7 +139,13 @@ impl TcpSinkConfig {
         let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
         let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
-        let sink = TcpSink::new(connector.clone(), transformer, encoder);
+        let sink = TcpSink::new(
+            connector.clone(),
+            transformer,
+            encoder,
+            self.pool_size.max(1),
+            self.in_flight_limit.max(1),
+        );
 
         Ok((
             VectorSink::from_event_streamsink(sink),
@@ -220,22 +252,34 @@ where
     connector: TcpConnector,
     transformer: Transformer,
     encoder: E,
+    pool_size: usize,
+    in_flight_limit: usize,
 }
 
 impl<E> TcpSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
 {
-    const fn new(connector: TcpConnector, transformer: Transformer, encoder: E) -> Self {
+    const fn new(
+        connector: TcpConnector,
+        transformer: Transformer,
+        encoder: E,
+        pool_size: usize,
+        in_flight_limit: usize,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            pool_size,
+            in_flight_limit,
         }
     }
+}
 
-    async fn connect(&self) -> BytesSink<MaybeTlsStream<TcpStream>> {
-        let stream = self.connector.connect_backoff().await;
+impl TcpConnector {
+    async fn connect_sink(&self) -> BytesSink<MaybeTlsStream<TcpStream>> {
+        let stream = self.connect_backoff().await;
         BytesSink::new(stream, Self::shutdown_check, SocketMode::Tcp)
     }
 
@@ -262,6 +306,38 @@ where
             _ => ShutdownCheck::Alive,
         }
     }
+
+    /// Sends the events over a connection, reconnecting whenever it's lost.
+    async fn send_all(&self, mut input: impl Stream<Item = EncodedEvent<Bytes>> + Unpin) {
+        // We need [Peekable](https://docs.rs/futures/0.3.6/futures/stream/struct.Peekable.html) for initiating
+        // connection only when we have something to send.
+        while let Some(item) = input.next().await {
+            let mut sink = self.connect_sink().await;
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            let mut mapped_input = stream::once(ready(item)).chain(&mut input).map(Ok);
+
+            let result = match sink.send_all(&mut mapped_input).await {
+                Ok(()) => sink.close().await,
+                Err(error) => Err(error),
+            };
+
+            // TODO we can consider retrying once in the Error case. This sink is a "best effort"
+            // delivery due to the nature of the underlying protocol.
+            // For now, if an error occurs we cannot assume that the events succeeded in delivery
+            // so we will emit `Error` / `EventsDropped` internal events regardless of if the server
+            // responded with Ok(0).
+            if let Err(error) = result {
+                if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
+                    emit!(TcpSocketConnectionShutdown {});
+                }
+                emit!(SocketSendError {
+                    mode: SocketMode::Tcp,
+                    error
+                });
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -270,8 +346,6 @@ where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + Sync + 'static,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // We need [Peekable](https://docs.rs/futures/0.3.6/futures/stream/struct.Peekable.html) for initiating
-        // connection only when we have something to send.
         let mut encoder = self.encoder.clone();
         let mut input = input.map(|mut event| {
             let byte_size = event.size_of();
@@ -292,31 +366,57 @@ where
             }
         });
 
-        while let Some(item) = input.next().await {
-            let mut sink = self.connect().await;
-            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
-
-            let mut mapped_input = stream::once(ready(item)).chain(&mut input).map(Ok);
+        if self.pool_size == 1 {
+            self.connector.send_all(input).await;
+            return Ok(());
+        }
 
-            let result = match sink.send_all(&mut mapped_input).await {
-                Ok(()) => sink.close().await,
-                Err(error) => Err(error),
-            };
+        let (senders, connections): (Vec<_>, Vec<_>) = (0..self.pool_size)
+            .map(|_| {
+                let (tx, rx) = mpsc::channel(self.in_flight_limit);
+                let connector = self.connector.clone();
+                let connection = tokio::spawn(
+                    async move { connector.send_all(ReceiverStream::new(rx)).await }
+                        .in_current_span(),
+                );
+                (tx, connection)
+            })
+            .unzip();
 
-            // TODO we can consider retrying once in the Error case. This sink is a "best effort"
-            // delivery due to the nature of the underlying protocol.
-            // For now, if an error occurs we cannot assume that the events succeeded in delivery
-            // so we will emit `Error` / `EventsDropped` internal events regardless of if the server
-            // responded with Ok(0).
-            if let Err(error) = result {
-                if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
-                    emit!(TcpSocketConnectionShutdown {});
+        let mut next = 0;
+        while let Some(item) = input.next().await {
+            // The event goes to the next connection with room for it, or waits on it if they're
+            // all at their limit.
+            let mut item = Some(item);
+            for offset in 0..senders.len() {
+                let index = (next + offset) % senders.len();
+                match senders[index].try_send(item.take().expect("event is taken once")) {
+                    Ok(()) => {
+                        next = index;
+                        break;
+                    }
+                    Err(TrySendError::Full(rejected) | TrySendError::Closed(rejected)) => {
+                        item = Some(rejected);
+                    }
                 }
-                emit!(SocketSendError {
-                    mode: SocketMode::Tcp,
-                    error
-                });
             }
+            if let Some(item) = item {
+                if senders[next].send(item).await.is_err() {
+                    error!("TCP connection stopped unexpectedly.");
+                    return Err(());
+                }
+            }
+            next = (next + 1) % senders.len();
+        }
+
+        drop(senders);
+        if join_all(connections)
+            .await
+            .into_iter()
+            .any(|result| result.is_err())
+        {
+            error!("TCP connection stopped unexpectedly.");
+            return Err(());
         }
 
         Ok(())
@@ -0,0 +1,455 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use http::Uri;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use tokio::time::{self, Instant};
+use tonic::body::BoxBody;
+use tower::Service;
+use tracing::Instrument;
+use vector_config::configurable_component;
+
+use super::{
+    config::VectorCompression,
+    service::{VectorRequest, VectorResponse, VectorService},
+    VectorSinkError,
+};
+use crate::{
+    dns, http::SocksConnector, internal_events::EndpointsActive, template::Template, Error,
+};
+
+type Client = hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>;
+
+/// Configuration of the load balancing of the requests across the downstream Vector aggregators.
+///
+/// The hostname of the address is resolved periodically, and the requests are balanced across all
+/// of its IP addresses. The aggregators whose requests keep failing are ejected for a while.
+///
+/// When TLS is enabled, the certificates of the aggregators are verified against their IP
+/// addresses, so `tls.verify_hostname` must be disabled unless the certificates include them.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LoadBalancingConfig {
+    /// The interval between the resolutions of the hostname of the address.
+    #[serde(default = "default_refresh_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    refresh_interval_secs: u64,
+
+    /// The key to balance the events by.
+    ///
+    /// The events with the same key are sent to the same aggregator, as long as it is available.
+    /// The keys are spread with consistent hashing, so that only the keys of an aggregator which is
+    /// added, removed, or ejected move to other aggregators.
+    ///
+    /// If not set, the requests are sent to the aggregators in turn.
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    pub key: Option<Template>,
+
+    /// The number of consecutive failed requests after which an aggregator is ejected.
+    #[serde(default = "default_max_failures")]
+    max_failures: usize,
+
+    /// The duration an ejected aggregator is left out of the load balancing.
+    ///
+    /// The aggregator is then sent requests again, and ejected again at its next failure.
+    #[serde(default = "default_ejection_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ejection_secs: u64,
+}
+
+const fn default_refresh_interval_secs() -> u64 {
+    30
+}
+
+const fn default_max_failures() -> usize {
+    5
+}
+
+const fn default_ejection_secs() -> u64 {
+    30
+}
+
+impl LoadBalancingConfig {
+    /// Discovers the aggregators of the address, and keeps rediscovering them in the background.
+    pub async fn build(
+        &self,
+        client: Client,
+        uri: Uri,
+        compression: VectorCompression,
+    ) -> crate::Result<BalancedService> {
+        let host = uri.host().ok_or(VectorSinkError::NoHost)?.to_owned();
+        let port = uri.port_u16().unwrap_or(match uri.scheme_str() {
+            Some("https") => 443,
+            _ => 80,
+        });
+        let discovery = Discovery {
+            host,
+            port,
+            uri,
+            client,
+            compression,
+        };
+
+        let service = BalancedService {
+            endpoints: Arc::default(),
+            next: Arc::default(),
+            max_failures: self.max_failures.max(1),
+            ejection: Duration::from_secs(self.ejection_secs),
+        };
+        discovery.refresh(&service.endpoints).await;
+
+        let refresh_interval = Duration::from_secs(self.refresh_interval_secs.max(1));
+        tokio::spawn(
+            discovery
+                .run(Arc::downgrade(&service.endpoints), refresh_interval)
+                .in_current_span(),
+        );
+
+        Ok(service)
+    }
+}
+
+/// A discovered aggregator.
+#[derive(Clone)]
+struct Endpoint {
+    address: SocketAddr,
+    service: VectorService,
+    health: Arc<Mutex<Health>>,
+}
+
+#[derive(Default)]
+struct Health {
+    /// The number of consecutive failed requests.
+    failures: usize,
+    ejected_until: Option<Instant>,
+}
+
+impl Endpoint {
+    fn is_ejected(&self, now: Instant) -> bool {
+        let health = self.health.lock().unwrap();
+        health.ejected_until.map_or(false, |until| now < until)
+    }
+
+    /// Ejects the aggregator once too many of its requests failed in a row.
+    fn record(
+        &self,
+        result: &Result<VectorResponse, Error>,
+        max_failures: usize,
+        ejection: Duration,
+    ) {
+        let mut health = self.health.lock().unwrap();
+        match result {
+            Ok(_) => {
+                if health.failures >= max_failures {
+                    info!(message = "Aggregator is healthy.", endpoint = %self.address);
+                }
+                *health = Health::default();
+            }
+            Err(error) if is_aggregator_failure(error) => {
+                health.failures += 1;
+                if health.failures >= max_failures {
+                    warn!(
+                        message = "Aggregator is ejected.",
+                        endpoint = %self.address,
+                        failures = health.failures,
+                    );
+                    health.ejected_until = Some(Instant::now() + ejection);
+                }
+            }
+            Err(_) => (),
+        }
+    }
+}
+
+/// Whether the request failed because of the aggregator rather than because of the request itself.
+fn is_aggregator_failure(error: &Error) -> bool {
+    use tonic::Code::*;
+
+    match error.downcast_ref::<VectorSinkError>() {
+        Some(VectorSinkError::Request { source }) => matches!(
+            source.code(),
+            Unknown | DeadlineExceeded | ResourceExhausted | Internal | Unavailable
+        ),
+        _ => false,
+    }
+}
+
+/// Resolves the hostname of the address into the aggregators.
+struct Discovery {
+    host: String,
+    port: u16,
+    uri: Uri,
+    client: Client,
+    compression: VectorCompression,
+}
+
+impl Discovery {
+    async fn run(self, endpoints: Weak<Mutex<Vec<Endpoint>>>, refresh_interval: Duration) {
+        let mut interval = time::interval_at(Instant::now() + refresh_interval, refresh_interval);
+        loop {
+            interval.tick().await;
+            // The sink stopped.
+            let endpoints = match endpoints.upgrade() {
+                Some(endpoints) => endpoints,
+                None => break,
+            };
+            self.refresh(&endpoints).await;
+        }
+    }
+
+    /// Replaces the aggregators by the resolved ones, keeping the health of those still resolved.
+    ///
+    /// If the hostname can't be resolved, the previous aggregators are kept.
+    async fn refresh(&self, endpoints: &Mutex<Vec<Endpoint>>) {
+        let mut addresses = match dns::Resolver.lookup_ip(self.host.clone()).await {
+            Ok(ips) => ips
+                .map(|ip| SocketAddr::new(ip, self.port))
+                .collect::<Vec<_>>(),
+            Err(error) => {
+                warn!(message = "Unable to discover aggregators.", host = %self.host, %error);
+                return;
+            }
+        };
+        if addresses.is_empty() {
+            warn!(message = "No aggregators were discovered.", host = %self.host);
+            return;
+        }
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let mut endpoints = endpoints.lock().unwrap();
+        let discovered = addresses
+            .into_iter()
+            .filter_map(|address| {
+                if let Some(endpoint) = endpoints
+                    .iter()
+                    .find(|endpoint| endpoint.address == address)
+                {
+                    return Some(endpoint.clone());
+                }
+                match self.endpoint_uri(address) {
+                    Ok(uri) => {
+                        debug!(message = "Discovered aggregator.", endpoint = %address);
+                        Some(Endpoint {
+                            address,
+                            service: VectorService::new(self.client.clone(), uri, self.compression),
+                            health: Arc::default(),
+                        })
+                    }
+                    Err(error) => {
+                        warn!(message = "Invalid aggregator address.", endpoint = %address, %error);
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        emit!(EndpointsActive {
+            count: discovered.len()
+        });
+        *endpoints = discovered;
+    }
+
+    /// The URI of the address, with the IP address of the aggregator instead of the hostname.
+    fn endpoint_uri(&self, address: SocketAddr) -> crate::Result<Uri> {
+        let mut parts = self.uri.clone().into_parts();
+        parts.authority = Some(address.to_string().parse()?);
+        Ok(Uri::from_parts(parts)?)
+    }
+}
+
+/// Balances the requests across the discovered aggregators.
+#[derive(Clone)]
+pub struct BalancedService {
+    endpoints: Arc<Mutex<Vec<Endpoint>>>,
+    /// The index of the next aggregator sent a request without a key.
+    next: Arc<AtomicUsize>,
+    max_failures: usize,
+    ejection: Duration,
+}
+
+impl BalancedService {
+    /// Picks the aggregator of the key, or the next one if there is no key.
+    ///
+    /// When all of the aggregators are ejected, they are all picked from anyway.
+    fn pick(&self, key: Option<&str>) -> Option<Endpoint> {
+        let now = Instant::now();
+        let endpoints = self.endpoints.lock().unwrap();
+        let mut candidates = endpoints
+            .iter()
+            .filter(|endpoint| !endpoint.is_ejected(now))
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            candidates = endpoints.iter().collect();
+        }
+
+        match key {
+            // Rendezvous hashing: the aggregator of a key only changes when it is no longer a
+            // candidate, or when a new aggregator weighs more for the key.
+            Some(key) => candidates
+                .into_iter()
+                .max_by_key(|endpoint| weight(key, endpoint.address)),
+            None if candidates.is_empty() => None,
+            None => {
+                let index = self.next.fetch_add(1, Ordering::Relaxed) % candidates.len();
+                Some(candidates[index])
+            }
+        }
+        .cloned()
+    }
+}
+
+fn weight(key: &str, address: SocketAddr) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    address.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Service<VectorRequest> for BalancedService {
+    type Response = VectorResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The services of the aggregators are always ready, like the `VectorService`.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: VectorRequest) -> Self::Future {
+        let endpoint = self.pick(request.key.as_deref());
+        let max_failures = self.max_failures;
+        let ejection = self.ejection;
+
+        Box::pin(async move {
+            match endpoint {
+                Some(mut endpoint) => {
+                    let result = endpoint.service.call(request).await;
+                    endpoint.record(&result, max_failures, ejection);
+                    result
+                }
+                None => Err(Error::from(VectorSinkError::NoEndpoints)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::ProxyConfig, sinks::vector::new_client, tls::MaybeTlsSettings};
+
+    fn balanced_service(addresses: &[&str]) -> BalancedService {
+        let client = new_client(&MaybeTlsSettings::Raw(()), &ProxyConfig::default()).unwrap();
+        let endpoints = addresses
+            .iter()
+            .map(|address| Endpoint {
+                address: address.parse().unwrap(),
+                service: VectorService::new(
+                    client.clone(),
+                    format!("http://{}/", address).parse().unwrap(),
+                    VectorCompression::None,
+                ),
+                health: Arc::default(),
+            })
+            .collect();
+
+        BalancedService {
+            endpoints: Arc::new(Mutex::new(endpoints)),
+            next: Arc::default(),
+            max_failures: 2,
+            ejection: Duration::from_secs(30),
+        }
+    }
+
+    fn picked(service: &BalancedService, key: Option<&str>) -> SocketAddr {
+        service.pick(key).unwrap().address
+    }
+
+    fn failure() -> Result<VectorResponse, Error> {
+        Err(Error::from(VectorSinkError::Request {
+            source: tonic::Status::unavailable("down"),
+        }))
+    }
+
+    #[test]
+    fn sends_requests_without_key_in_turn() {
+        let service = balanced_service(&["10.0.0.1:6000", "10.0.0.2:6000"]);
+
+        let first = picked(&service, None);
+        let second = picked(&service, None);
+        assert_ne!(first, second);
+        assert_eq!(picked(&service, None), first);
+    }
+
+    #[test]
+    fn sends_keys_to_consistent_aggregators() {
+        let addresses = ["10.0.0.1:6000", "10.0.0.2:6000", "10.0.0.3:6000"];
+        let service = balanced_service(&addresses);
+        let keys = (0..100)
+            .map(|index| format!("key{}", index))
+            .collect::<Vec<_>>();
+        let before = keys
+            .iter()
+            .map(|key| picked(&service, Some(key)))
+            .collect::<Vec<_>>();
+        assert!(keys
+            .iter()
+            .zip(&before)
+            .all(|(key, address)| picked(&service, Some(key)) == *address));
+
+        // Only the keys of the removed aggregator move.
+        let removed: SocketAddr = addresses[0].parse().unwrap();
+        service
+            .endpoints
+            .lock()
+            .unwrap()
+            .retain(|endpoint| endpoint.address != removed);
+        for (key, address) in keys.iter().zip(&before) {
+            let after = picked(&service, Some(key));
+            if *address == removed {
+                assert_ne!(after, removed);
+            } else {
+                assert_eq!(after, *address);
+            }
+        }
+    }
+
+    #[test]
+    fn ejects_failing_aggregators() {
+        let service = balanced_service(&["10.0.0.1:6000", "10.0.0.2:6000"]);
+        let failing = service.pick(Some("key")).unwrap();
+
+        failing.record(&failure(), service.max_failures, service.ejection);
+        assert_eq!(picked(&service, Some("key")), failing.address);
+
+        failing.record(&failure(), service.max_failures, service.ejection);
+        assert_ne!(picked(&service, Some("key")), failing.address);
+        assert_ne!(picked(&service, None), failing.address);
+        assert_ne!(picked(&service, None), failing.address);
+    }
+
+    #[test]
+    fn picks_ejected_aggregators_when_all_are_ejected() {
+        let service = balanced_service(&["10.0.0.1:6000"]);
+        let failing = service.pick(None).unwrap();
+        failing.record(&failure(), service.max_failures, service.ejection);
+        failing.record(&failure(), service.max_failures, service.ejection);
+
+        assert_eq!(picked(&service, None), failing.address);
+    }
+}
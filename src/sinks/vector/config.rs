@@ -2,13 +2,16 @@ use http::Uri;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
+use serde::{Deserialize, Deserializer};
 use tonic::body::BoxBody;
 use tower::ServiceBuilder;
 use vector_config::configurable_component;
 
 use super::{
+    balance::LoadBalancingConfig,
     service::{VectorResponse, VectorService},
     sink::VectorSink,
+    streams::{Streams, StreamsConfig},
     VectorSinkError,
 };
 use crate::{
@@ -16,6 +19,7 @@ use crate::{
         AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext,
         SinkHealthcheckOptions,
     },
+    http::SocksConnector,
     proto::vector as proto,
     sinks::{
         util::{
@@ -48,14 +52,20 @@ pub struct VectorConfig {
     #[configurable(metadata(docs::examples = "https://somehost:6000"))]
     address: String,
 
-    /// Whether or not to compress requests.
-    ///
-    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
-    ///
-    /// [gzip_docs]: https://www.gzip.org/
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, deserialize_with = "bool_or_compression")]
+    pub(in crate::sinks::vector) compression: VectorCompression,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    streams: Option<StreamsConfig>,
+
+    #[configurable(derived)]
     #[configurable(metadata(docs::advanced))]
     #[serde(default)]
-    compression: bool,
+    load_balancing: Option<LoadBalancingConfig>,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -78,6 +88,50 @@ pub struct VectorConfig {
     pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
+/// The compression of the requests.
+///
+/// For backwards compatibility, `true` and `false` are also accepted, as `gzip` and `none`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorCompression {
+    /// The requests aren't compressed.
+    #[default]
+    None,
+
+    /// The requests are compressed with [`gzip`][gzip_docs] by gRPC.
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    Gzip,
+
+    /// The events of the requests are compressed with [`zstd`][zstd_docs].
+    ///
+    /// This requires that the downstream `vector` source supports `zstd` compression: the
+    /// sources of older versions of Vector reject these requests, so that they aren't retried.
+    ///
+    /// [zstd_docs]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+/// Deserializes the compression, or whether or not to compress with `gzip`.
+fn bool_or_compression<'de, D>(deserializer: D) -> Result<VectorCompression, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrCompression {
+        Bool(bool),
+        Compression(VectorCompression),
+    }
+
+    Ok(match BoolOrCompression::deserialize(deserializer)? {
+        BoolOrCompression::Bool(true) => VectorCompression::Gzip,
+        BoolOrCompression::Bool(false) => VectorCompression::None,
+        BoolOrCompression::Compression(compression) => compression,
+    })
+}
+
 impl VectorConfig {
     /// Creates a `VectorConfig` with the given address.
     pub fn from_address(addr: Uri) -> Self {
@@ -96,7 +150,9 @@ fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         version: None,
         address: address.to_owned(),
-        compression: false,
+        compression: VectorCompression::None,
+        streams: None,
+        load_balancing: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -118,25 +174,43 @@ impl SinkConfig for VectorConfig {
             .clone()
             .map(|uri| uri.uri)
             .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
+        let healthcheck_client =
+            VectorService::new(client.clone(), healthcheck_uri, VectorCompression::None);
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
-
-        let service = ServiceBuilder::new()
-            .settings(request_settings, VectorGrpcRetryLogic)
-            .service(service);
-
-        let sink = VectorSink {
-            batch_settings,
-            service,
+        let streams = Streams::new(self.streams);
+
+        let sink = match &self.load_balancing {
+            None => {
+                let service = VectorService::new(client, uri, self.compression);
+                let service = ServiceBuilder::new()
+                    .settings(request_settings, VectorGrpcRetryLogic)
+                    .service(service);
+
+                VectorSinkType::from_event_streamsink(VectorSink {
+                    batch_settings,
+                    streams,
+                    key: None,
+                    service,
+                })
+            }
+            Some(load_balancing) => {
+                let service = load_balancing.build(client, uri, self.compression).await?;
+                let service = ServiceBuilder::new()
+                    .settings(request_settings, VectorGrpcRetryLogic)
+                    .service(service);
+
+                VectorSinkType::from_event_streamsink(VectorSink {
+                    batch_settings,
+                    streams,
+                    key: load_balancing.key.clone(),
+                    service,
+                })
+            }
         };
 
-        Ok((
-            VectorSinkType::from_event_streamsink(sink),
-            Box::pin(healthcheck),
-        ))
+        Ok((sink, Box::pin(healthcheck)))
     }
 
     fn input(&self) -> Input {
@@ -204,12 +278,13 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
     }
 }
 
-fn new_client(
+pub(crate) fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
-) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
+) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>> {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
+    let http = SocksConnector::new(http, proxy_config)?;
 
     let tls = tls_connector_builder(tls_settings)?;
     let mut https = HttpsConnector::with_connector(http, tls)?;
@@ -253,6 +328,7 @@ impl RetryLogic for VectorGrpcRetryLogic {
                     | Unimplemented
                     | Unauthenticated
             ),
+            VectorSinkError::Compression { .. } => false,
             _ => true,
         }
     }
@@ -2,11 +2,14 @@ use snafu::Snafu;
 
 use vector_config::configurable_component;
 
+mod balance;
 mod config;
 mod service;
 mod sink;
+mod streams;
 
 pub use config::VectorConfig;
+pub(crate) use config::{new_client, with_default_scheme};
 
 /// Marker type for the version two of the configuration for the `vector` sink.
 #[configurable_component]
@@ -23,11 +26,17 @@ pub enum VectorSinkError {
     #[snafu(display("Request failed: {}", source))]
     Request { source: tonic::Status },
 
+    #[snafu(display("Compression failed: {}", source))]
+    Compression { source: std::io::Error },
+
     #[snafu(display("Vector source unhealthy: {:?}", status))]
     Health { status: Option<&'static str> },
 
     #[snafu(display("URL has no host."))]
     NoHost,
+
+    #[snafu(display("No aggregators were discovered."))]
+    NoEndpoints,
 }
 
 #[cfg(test)]
@@ -112,6 +121,78 @@ mod tests {
         assert_eq!(input_lines, output_lines);
     }
 
+    #[tokio::test]
+    async fn deliver_zstd_message() {
+        let num_lines = 10;
+
+        let in_addr = next_addr();
+
+        let config = format!(
+            r#"
+            address = "http://{}/"
+            compression = "zstd"
+            "#,
+            in_addr
+        );
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let cx = SinkContext::new_test();
+
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+            hyper::Response::builder()
+                .header("grpc-status", "0") // OK
+                .header("content-type", "application/grpc")
+                .body(hyper::Body::from(encode_body(proto::PushEventsResponse {})))
+                .unwrap()
+        });
+
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input_lines, events) = random_lines_with_stream(8, num_lines, Some(batch));
+
+        run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let requests = rx.collect::<Vec<(Parts, Bytes)>>().await;
+        assert_eq!(requests.len(), 1);
+        let (parts, body) = requests.into_iter().next().unwrap();
+        assert_eq!("/vector.Vector/PushCompressedEvents", parts.uri.path());
+
+        let req = proto::PushCompressedEventsRequest::decode(body.slice(GRPC_HEADER_SIZE..))
+            .unwrap()
+            .decompress()
+            .unwrap();
+        let output_lines = req
+            .events
+            .into_iter()
+            .map(|event| {
+                Event::from(event)
+                    .as_log()
+                    .get("message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(input_lines, output_lines);
+    }
+
+    #[test]
+    fn accepts_boolean_compression() {
+        let config: VectorConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:6000"
+            compression = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.compression, config::VectorCompression::Gzip);
+    }
+
     #[tokio::test]
     async fn acknowledges_error() {
         let num_lines = 10;
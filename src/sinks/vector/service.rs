@@ -1,8 +1,10 @@
-use std::task::{Context, Poll};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
 
-use futures::{future::BoxFuture, TryFutureExt};
+use futures::future::BoxFuture;
 use http::Uri;
-use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use prost::Message;
@@ -11,9 +13,10 @@ use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
-use super::VectorSinkError;
+use super::{config::VectorCompression, streams::QueuedRequest, VectorSinkError};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
+    http::SocksConnector,
     internal_events::EndpointBytesSent,
     proto::vector as proto_vector,
     sinks::util::uri,
@@ -23,6 +26,7 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct VectorService {
     pub client: proto_vector::Client<HyperSvc>,
+    pub compression: VectorCompression,
     pub protocol: String,
     pub endpoint: String,
 }
@@ -47,6 +51,10 @@ pub struct VectorRequest {
     pub finalizers: EventFinalizers,
     pub metadata: RequestMetadata,
     pub request: proto_vector::PushEventsRequest,
+    /// The load balancing key of the events.
+    pub key: Option<String>,
+    /// Counts the request as queued in its stream until it is sent, including its retries.
+    pub queued: Option<Arc<QueuedRequest>>,
 }
 
 impl Finalizable for VectorRequest {
@@ -63,9 +71,9 @@ impl MetaDescriptive for VectorRequest {
 
 impl VectorService {
     pub fn new(
-        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>,
         uri: Uri,
-        compression: bool,
+        compression: VectorCompression,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
         let mut proto_client = proto_vector::Client::new(HyperSvc {
@@ -73,15 +81,44 @@ impl VectorService {
             client: hyper_client,
         });
 
-        if compression {
+        if compression == VectorCompression::Gzip {
             proto_client = proto_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
         }
         Self {
             client: proto_client,
+            compression,
             protocol,
             endpoint,
         }
     }
+
+    /// Pushes the events, compressed with `zstd` if configured, and returns the sent byte size.
+    async fn push(
+        &mut self,
+        request: proto_vector::PushEventsRequest,
+    ) -> Result<usize, VectorSinkError> {
+        match self.compression {
+            VectorCompression::Zstd => {
+                let request = request
+                    .compress_zstd()
+                    .map_err(|source| VectorSinkError::Compression { source })?;
+                let byte_size = request.encoded_len();
+                self.client
+                    .push_compressed_events(request.into_request())
+                    .await
+                    .map_err(|source| VectorSinkError::Request { source })?;
+                Ok(byte_size)
+            }
+            VectorCompression::None | VectorCompression::Gzip => {
+                let byte_size = request.encoded_len();
+                self.client
+                    .push_events(request.into_request())
+                    .await
+                    .map_err(|source| VectorSinkError::Request { source })?;
+                Ok(byte_size)
+            }
+        }
+    }
 }
 
 impl Service<VectorRequest> for VectorService {
@@ -102,27 +139,24 @@ impl Service<VectorRequest> for VectorService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, list: VectorRequest) -> Self::Future {
         let mut service = self.clone();
-        let byte_size = list.request.encoded_len();
         let events_count = list.get_metadata().event_count();
         let events_byte_size = list.get_metadata().events_byte_size();
 
         let future = async move {
-            service
-                .client
-                .push_events(list.request.into_request())
-                .map_ok(|_response| {
+            match service.push(list.request).await {
+                Ok(byte_size) => {
                     emit!(EndpointBytesSent {
                         byte_size,
                         protocol: &service.protocol,
                         endpoint: &service.endpoint,
                     });
-                    VectorResponse {
+                    Ok(VectorResponse {
                         events_count,
                         events_byte_size,
-                    }
-                })
-                .map_err(|source| VectorSinkError::Request { source }.into())
-                .await
+                    })
+                }
+                Err(error) => Err(Error::from(error)),
+            }
         };
 
         Box::pin(future)
@@ -132,7 +166,7 @@ impl Service<VectorRequest> for VectorService {
 #[derive(Clone, Debug)]
 pub struct HyperSvc {
     uri: Uri,
-    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    client: hyper::Client<ProxyConnector<HttpsConnector<SocksConnector>>, BoxBody>,
 }
 
 impl Service<hyper::Request<BoxBody>> for HyperSvc {
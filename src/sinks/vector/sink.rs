@@ -1,4 +1,4 @@
-use std::{fmt, num::NonZeroUsize};
+use std::{fmt, num::NonZeroUsize, sync::Arc};
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
@@ -9,20 +9,39 @@ use vector_core::{
     ByteSizeOf,
 };
 
-use super::service::VectorRequest;
+use super::{
+    service::VectorRequest,
+    streams::{EventStream, StreamPartitioner, Streams},
+};
 use crate::{
     event::{proto::EventWrapper, Event, EventFinalizers, Finalizable},
+    internal_events::TemplateRenderingError,
     proto::vector as proto_vector,
     sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt, StreamSink},
+    template::Template,
 };
 
 /// Data for a single event.
-struct EventData {
+pub struct EventData {
+    pub stream: EventStream,
+    /// The load balancing key of the event.
+    pub key: Option<String>,
     byte_size: usize,
     finalizers: EventFinalizers,
     wrapper: EventWrapper,
 }
 
+impl ByteSizeOf for EventData {
+    fn size_of(&self) -> usize {
+        // The batches are limited by the encoded size of their events.
+        self.wrapper.encoded_len()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
 /// Temporary struct to collect events during batching.
 #[derive(Clone, Default)]
 struct EventCollection {
@@ -33,6 +52,8 @@ struct EventCollection {
 
 pub struct VectorSink<S> {
     pub batch_settings: BatcherSettings,
+    pub streams: Streams,
+    pub key: Option<Template>,
     pub service: S,
 }
 
@@ -44,21 +65,37 @@ where
     S::Error: fmt::Debug + Into<crate::Error> + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        input
+        let streams = self.streams;
+        let key = self.key;
+
+        let requests = input
             .map(|mut event| EventData {
+                stream: streams.stream_of(&event),
+                key: key.as_ref().and_then(|key| {
+                    key.render_string(&event)
+                        .map_err(|error| {
+                            // The event is still sent, to any of the aggregators.
+                            emit!(TemplateRenderingError {
+                                error,
+                                field: Some("load_balancing.key"),
+                                drop_event: false,
+                            });
+                        })
+                        .ok()
+                }),
                 byte_size: event.size_of(),
                 finalizers: event.take_finalizers(),
                 wrapper: EventWrapper::from(event),
             })
-            .batched(self.batch_settings.into_reducer_config(
-                |data: &EventData| data.wrapper.encoded_len(),
-                |event_collection: &mut EventCollection, item: EventData| {
+            .batched_partitioned(StreamPartitioner, self.batch_settings)
+            .map(|((stream, key), batch)| {
+                let mut event_collection = EventCollection::default();
+                for item in batch {
                     event_collection.finalizers.merge(item.finalizers);
                     event_collection.events.push(item.wrapper);
                     event_collection.events_byte_size += item.byte_size;
-                },
-            ))
-            .map(|event_collection| {
+                }
+
                 let builder = RequestMetadataBuilder::new(
                     event_collection.events.len(),
                     event_collection.events_byte_size,
@@ -73,12 +110,18 @@ where
                 let bytes_len =
                     NonZeroUsize::new(byte_size).expect("payload should never be zero length");
 
-                VectorRequest {
+                let request = VectorRequest {
                     finalizers: event_collection.finalizers,
                     metadata: builder.with_request_size(bytes_len),
                     request: encoded_events,
-                }
-            })
+                    key,
+                    queued: Some(Arc::new(streams.enqueue(stream))),
+                };
+                (stream, request)
+            });
+
+        streams
+            .prioritize(requests)
             .into_driver(self.service)
             .run()
             .await
@@ -0,0 +1,266 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, VecDeque},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use futures::{stream::Fuse, Stream, StreamExt};
+use pin_project::pin_project;
+use vector_config::configurable_component;
+use vector_core::partition::Partitioner;
+
+use super::sink::EventData;
+use crate::{event::Event, internal_events::VectorStreamQueuedRequests};
+
+/// The maximum number of requests reordered at once.
+const QUEUE_LIMIT: usize = 1024;
+
+/// Configuration of the logical streams the events are sent in.
+///
+/// The logs, metrics, and traces are batched separately, in streams of their own multiplexed over
+/// the same connection. When the requests are backpressured, the ones of the streams with a higher
+/// priority are sent first, so that metrics can be prioritized over bulk logs, for example.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamsConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    logs: StreamConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    metrics: StreamConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    traces: StreamConfig,
+}
+
+/// Configuration of a logical stream.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamConfig {
+    /// The priority of the stream.
+    ///
+    /// The requests of the streams with a higher priority are sent first.
+    #[serde(default)]
+    priority: u8,
+}
+
+/// The logical streams the events are sent in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EventStream {
+    /// All of the events, when the streams aren't configured.
+    All,
+    Logs,
+    Metrics,
+    Traces,
+}
+
+impl EventStream {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Logs => "logs",
+            Self::Metrics => "metrics",
+            Self::Traces => "traces",
+        }
+    }
+}
+
+/// The state of the logical streams: their priority and the number of their queued requests.
+#[derive(Clone, Debug)]
+pub struct Streams {
+    config: Option<StreamsConfig>,
+    queued: Arc<[AtomicUsize; 4]>,
+}
+
+impl Streams {
+    pub fn new(config: Option<StreamsConfig>) -> Self {
+        Self {
+            config,
+            queued: Arc::default(),
+        }
+    }
+
+    pub fn stream_of(&self, event: &Event) -> EventStream {
+        match (self.config, event) {
+            (None, _) => EventStream::All,
+            (Some(_), Event::Log(_)) => EventStream::Logs,
+            (Some(_), Event::Metric(_)) => EventStream::Metrics,
+            (Some(_), Event::Trace(_)) => EventStream::Traces,
+        }
+    }
+
+    fn priority(&self, stream: EventStream) -> u8 {
+        match (self.config, stream) {
+            (Some(config), EventStream::Logs) => config.logs.priority,
+            (Some(config), EventStream::Metrics) => config.metrics.priority,
+            (Some(config), EventStream::Traces) => config.traces.priority,
+            (_, _) => 0,
+        }
+    }
+
+    /// Counts a request of the stream as queued, until the returned guard is dropped once the
+    /// request is sent.
+    pub fn enqueue(&self, stream: EventStream) -> QueuedRequest {
+        let count = self.queued[stream as usize].fetch_add(1, Ordering::Relaxed) + 1;
+        emit!(VectorStreamQueuedRequests {
+            stream: stream.as_str(),
+            count,
+        });
+        QueuedRequest {
+            streams: self.clone(),
+            stream,
+        }
+    }
+
+    /// Reorders the ready requests, so that the ones of the streams with a higher priority are
+    /// sent first.
+    pub fn prioritize<St, T>(&self, input: St) -> Prioritized<St, T>
+    where
+        St: Stream<Item = (EventStream, T)>,
+    {
+        Prioritized {
+            input: input.fuse(),
+            streams: self.clone(),
+            queues: BTreeMap::new(),
+            queued: 0,
+        }
+    }
+}
+
+/// A request of a stream which is queued or in flight.
+#[derive(Debug)]
+pub struct QueuedRequest {
+    streams: Streams,
+    stream: EventStream,
+}
+
+impl Drop for QueuedRequest {
+    fn drop(&mut self) {
+        let count = self.streams.queued[self.stream as usize].fetch_sub(1, Ordering::Relaxed) - 1;
+        emit!(VectorStreamQueuedRequests {
+            stream: self.stream.as_str(),
+            count,
+        });
+    }
+}
+
+/// Partitions the events by the stream they are sent in, and by their load balancing key.
+pub struct StreamPartitioner;
+
+impl Partitioner for StreamPartitioner {
+    type Item = EventData;
+    type Key = (EventStream, Option<String>);
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        (item.stream, item.key.clone())
+    }
+}
+
+/// A stream of requests reordered by the priority of their streams.
+#[pin_project]
+pub struct Prioritized<St: Stream, T> {
+    #[pin]
+    input: Fuse<St>,
+    streams: Streams,
+    /// The queued requests, by decreasing priority of their stream.
+    queues: BTreeMap<Reverse<u8>, VecDeque<T>>,
+    queued: usize,
+}
+
+impl<St, T> Stream for Prioritized<St, T>
+where
+    St: Stream<Item = (EventStream, T)>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Take all of the ready requests, so that they can be reordered.
+        while *this.queued < QUEUE_LIMIT {
+            match this.input.as_mut().poll_next(cx) {
+                Poll::Ready(Some((stream, request))) => {
+                    let priority = this.streams.priority(stream);
+                    this.queues
+                        .entry(Reverse(priority))
+                        .or_default()
+                        .push_back(request);
+                    *this.queued += 1;
+                }
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+
+        for queue in this.queues.values_mut() {
+            if let Some(request) = queue.pop_front() {
+                *this.queued -= 1;
+                return Poll::Ready(Some(request));
+            }
+        }
+
+        if this.input.is_done() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sends_higher_priorities_first() {
+        let config: StreamsConfig = toml::from_str(
+            r#"
+            metrics.priority = 10
+            traces.priority = 5
+            "#,
+        )
+        .unwrap();
+        let streams = Streams::new(Some(config));
+
+        let requests = stream::iter(vec![
+            (EventStream::Logs, 1),
+            (EventStream::Metrics, 2),
+            (EventStream::Logs, 3),
+            (EventStream::Traces, 4),
+            (EventStream::Metrics, 5),
+        ]);
+        let sent = streams.prioritize(requests).collect::<Vec<_>>().await;
+
+        assert_eq!(sent, vec![2, 5, 4, 1, 3]);
+    }
+
+    #[test]
+    fn counts_queued_requests() {
+        let streams = Streams::new(Some(StreamsConfig::default()));
+
+        let first = streams.enqueue(EventStream::Metrics);
+        let second = streams.enqueue(EventStream::Metrics);
+        assert_eq!(
+            streams.queued[EventStream::Metrics as usize].load(Ordering::Relaxed),
+            2
+        );
+
+        drop(first);
+        drop(second);
+        assert_eq!(
+            streams.queued[EventStream::Metrics as usize].load(Ordering::Relaxed),
+            0
+        );
+    }
+}
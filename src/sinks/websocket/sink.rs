@@ -567,7 +567,8 @@ mod tests {
                                             Auth::Basic {
                                                 user: _user,
                                                 password: _password,
-                                            } => { /* Not needed for tests at the moment */ }
+                                            }
+                                            | Auth::OAuth2(_) => { /* Not needed for tests at the moment */ }
                                         }
                                     }
                                     Ok(res)
@@ -1,8 +1,9 @@
 #![allow(missing_docs)]
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, sync::Arc};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use futures::{Stream, StreamExt};
+use lookup::path;
 use metrics::{register_histogram, Histogram};
 use value::Value;
 use vector_buffers::topology::channel::{self, LimitedReceiver, LimitedSender};
@@ -10,17 +11,24 @@ use vector_buffers::topology::channel::{self, LimitedReceiver, LimitedSender};
 use vector_core::event::{into_event_stream, EventStatus};
 use vector_core::{
     config::{log_schema, Output},
-    event::{array, Event, EventArray, EventContainer, EventRef},
+    event::{array, Event, EventArray, EventContainer, EventMutRef, EventRef, LogEvent},
     internal_event::{
         self, CountByteSize, EventsSent, InternalEventHandle as _, Registered, DEFAULT_OUTPUT,
     },
     ByteSizeOf, EstimatedJsonEncodedSizeOf,
 };
 
+use crate::{
+    codecs::{is_decode_error, DECODE_ERRORS_OUTPUT},
+    memory_budget::ComponentMemory,
+};
+
 mod errors;
+mod tenant;
 
 pub use errors::{ClosedError, StreamSendError};
 use lookup::PathPrefix;
+pub use tenant::{TenantQuota, TenantRegistry};
 
 pub(crate) const CHUNK_SIZE: usize = 1000;
 
@@ -34,6 +42,9 @@ pub struct Builder {
     inner: Option<Inner>,
     named_inners: HashMap<String, Inner>,
     lag_time: Option<Histogram>,
+    origin: Option<SourceOrigin>,
+    tenant: Option<Arc<TenantQuota>>,
+    memory: Option<ComponentMemory>,
 }
 
 impl Builder {
@@ -42,23 +53,55 @@ impl Builder {
     pub fn with_buffer(self, n: usize) -> Self {
         Self {
             buf_size: n,
-            inner: self.inner,
-            named_inners: self.named_inners,
-            lag_time: self.lag_time,
+            ..self
         }
     }
 
+    /// Stamps every event sent through the outputs added after this call with the given origin
+    /// metadata.
+    pub fn with_origin(self, origin: Option<SourceOrigin>) -> Self {
+        Self { origin, ..self }
+    }
+
+    /// Counts every event sent through the outputs added after this call towards the quotas of
+    /// the given tenant, waiting for them to allow sending more events when exceeded.
+    pub fn with_tenant(self, tenant: Option<Arc<TenantQuota>>) -> Self {
+        Self { tenant, ..self }
+    }
+
+    /// Acquires the bytes of every event sent through the outputs added after this call from the
+    /// memory budget, counting them as in flight for the source until they are finalized.
+    pub fn with_memory(self, memory: Option<ComponentMemory>) -> Self {
+        Self { memory, ..self }
+    }
+
     pub fn add_output(&mut self, output: Output) -> LimitedReceiver<EventArray> {
         let lag_time = self.lag_time.clone();
+        let origin = self.origin.clone();
+        let tenant = self.tenant.clone();
+        let memory = self.memory.clone();
         match output.port {
             None => {
-                let (inner, rx) =
-                    Inner::new_with_buffer(self.buf_size, DEFAULT_OUTPUT.to_owned(), lag_time);
+                let (inner, rx) = Inner::new_with_buffer(
+                    self.buf_size,
+                    DEFAULT_OUTPUT.to_owned(),
+                    lag_time,
+                    origin,
+                    tenant,
+                    memory,
+                );
                 self.inner = Some(inner);
                 rx
             }
             Some(name) => {
-                let (inner, rx) = Inner::new_with_buffer(self.buf_size, name.clone(), lag_time);
+                let (inner, rx) = Inner::new_with_buffer(
+                    self.buf_size,
+                    name.clone(),
+                    lag_time,
+                    origin,
+                    tenant,
+                    memory,
+                );
                 self.named_inners.insert(name, inner);
                 rx
             }
@@ -88,12 +131,16 @@ impl SourceSender {
             inner: None,
             named_inners: Default::default(),
             lag_time: Some(register_histogram!(LAG_TIME_NAME)),
+            origin: None,
+            tenant: None,
+            memory: None,
         }
     }
 
     pub fn new_with_buffer(n: usize) -> (Self, LimitedReceiver<EventArray>) {
         let lag_time = Some(register_histogram!(LAG_TIME_NAME));
-        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time);
+        let (inner, rx) =
+            Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time, None, None, None);
         (
             Self {
                 inner: Some(inner),
@@ -161,7 +208,7 @@ impl SourceSender {
     ) -> impl Stream<Item = EventArray> + Unpin {
         // The lag_time parameter here will need to be filled in if this function is ever used for
         // non-test situations.
-        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None);
+        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None, None, None, None);
         let recv = recv.into_stream().map(move |mut events| {
             events.iter_events_mut().for_each(|mut event| {
                 let metadata = event.metadata_mut();
@@ -175,10 +222,29 @@ impl SourceSender {
     }
 
     pub async fn send_event(&mut self, event: impl Into<EventArray>) -> Result<(), ClosedError> {
+        let mut events = event.into();
+        if self.routes_decode_errors() {
+            if let EventArray::Logs(logs) = &mut events {
+                let (errors, rest): (Vec<LogEvent>, Vec<LogEvent>) =
+                    std::mem::take(logs).into_iter().partition(is_decode_error);
+                *logs = rest;
+                if !errors.is_empty() {
+                    self.named_inners
+                        .get_mut(DECODE_ERRORS_OUTPUT)
+                        .expect("no errors output")
+                        .send_batch(errors)
+                        .await?;
+                }
+                if logs.is_empty() {
+                    return Ok(());
+                }
+            }
+        }
+
         self.inner
             .as_mut()
             .expect("no default output")
-            .send_event(event)
+            .send_event(events)
             .await
     }
 
@@ -187,6 +253,14 @@ impl SourceSender {
         S: Stream<Item = E> + Unpin,
         E: Into<Event> + ByteSizeOf,
     {
+        if self.routes_decode_errors() {
+            let mut stream = events.ready_chunks(CHUNK_SIZE);
+            while let Some(events) = stream.next().await {
+                self.send_batch(events).await?;
+            }
+            return Ok(());
+        }
+
         self.inner
             .as_mut()
             .expect("no default output")
@@ -199,6 +273,16 @@ impl SourceSender {
         E: Into<Event> + ByteSizeOf,
         I: IntoIterator<Item = E>,
     {
+        if self.routes_decode_errors() {
+            let events = self.route_decode_errors(events).await?;
+            return self
+                .inner
+                .as_mut()
+                .expect("no default output")
+                .send_batch(events)
+                .await;
+        }
+
         self.inner
             .as_mut()
             .expect("no default output")
@@ -211,12 +295,81 @@ impl SourceSender {
         E: Into<Event> + ByteSizeOf,
         I: IntoIterator<Item = E>,
     {
+        if name != DECODE_ERRORS_OUTPUT && self.routes_decode_errors() {
+            let events = self.route_decode_errors(events).await?;
+            return self
+                .named_inners
+                .get_mut(name)
+                .expect("unknown output")
+                .send_batch(events)
+                .await;
+        }
+
         self.named_inners
             .get_mut(name)
             .expect("unknown output")
             .send_batch(events)
             .await
     }
+
+    /// Sends the events carrying a payload which failed to be decoded to the `errors` output,
+    /// returning the other events.
+    async fn route_decode_errors<I, E>(&mut self, events: I) -> Result<Vec<Event>, ClosedError>
+    where
+        E: Into<Event>,
+        I: IntoIterator<Item = E>,
+    {
+        let (errors, events): (Vec<Event>, Vec<Event>) = events
+            .into_iter()
+            .map(Into::into)
+            .partition(|event| event.maybe_as_log().map_or(false, is_decode_error));
+        if !errors.is_empty() {
+            self.named_inners
+                .get_mut(DECODE_ERRORS_OUTPUT)
+                .expect("no errors output")
+                .send_batch(errors)
+                .await?;
+        }
+        Ok(events)
+    }
+
+    /// Whether the source routes the payloads it fails to decode to the `errors` output, in which
+    /// case the events carrying them are diverted from the default output.
+    fn routes_decode_errors(&self) -> bool {
+        self.named_inners.contains_key(DECODE_ERRORS_OUTPUT)
+    }
+}
+
+/// The origin of the events sent by a source, stamped into the `vector` namespace of the event
+/// metadata so it can be used by any downstream transform or sink.
+#[derive(Clone, Debug)]
+pub struct SourceOrigin {
+    component_id: Value,
+    source_type: Value,
+}
+
+impl SourceOrigin {
+    pub fn new(component_id: &str, source_type: &'static str) -> Self {
+        Self {
+            component_id: Value::from(component_id),
+            source_type: Value::from(source_type),
+        }
+    }
+
+    /// Inserts the origin fields into the metadata of the event, leaving any field the source
+    /// already set untouched.
+    fn stamp(&self, mut event: EventMutRef<'_>, now: DateTime<Utc>) {
+        let metadata = event.metadata_mut().value_mut();
+        if metadata.get(path!("vector", "source_type")).is_none() {
+            metadata.insert(path!("vector", "source_type"), self.source_type.clone());
+        }
+        if metadata.get(path!("vector", "component_id")).is_none() {
+            metadata.insert(path!("vector", "component_id"), self.component_id.clone());
+        }
+        if metadata.get(path!("vector", "ingest_timestamp")).is_none() {
+            metadata.insert(path!("vector", "ingest_timestamp"), now);
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -225,6 +378,9 @@ struct Inner {
     output: String,
     lag_time: Option<Histogram>,
     events_sent: Registered<EventsSent>,
+    origin: Option<SourceOrigin>,
+    tenant: Option<Arc<TenantQuota>>,
+    memory: Option<ComponentMemory>,
 }
 
 impl fmt::Debug for Inner {
@@ -232,6 +388,7 @@ impl fmt::Debug for Inner {
         fmt.debug_struct("Inner")
             .field("inner", &self.inner)
             .field("output", &self.output)
+            .field("origin", &self.origin)
             // `metrics::Histogram` is missing `impl Debug`
             .finish()
     }
@@ -242,6 +399,9 @@ impl Inner {
         n: usize,
         output: String,
         lag_time: Option<Histogram>,
+        origin: Option<SourceOrigin>,
+        tenant: Option<Arc<TenantQuota>>,
+        memory: Option<ComponentMemory>,
     ) -> (Self, LimitedReceiver<EventArray>) {
         let (tx, rx) = channel::limited(n);
         (
@@ -252,12 +412,24 @@ impl Inner {
                 events_sent: register!(EventsSent::from(internal_event::Output(Some(
                     output.into()
                 )))),
+                origin,
+                tenant,
+                memory,
             },
             rx,
         )
     }
 
-    async fn send(&mut self, events: EventArray) -> Result<(), ClosedError> {
+    async fn send(&mut self, mut events: EventArray) -> Result<(), ClosedError> {
+        self.stamp_origin(&mut events);
+        if let Some(tenant) = &self.tenant {
+            tenant.acquire(&mut events).await;
+        }
+        if let Some(memory) = &self.memory {
+            if !memory.acquire(&mut events).await {
+                return Ok(());
+            }
+        }
         let reference = Utc::now().timestamp_millis();
         events
             .iter_events()
@@ -292,7 +464,16 @@ impl Inner {
     {
         let reference = Utc::now().timestamp_millis();
         let events = events.into_iter().map(Into::into);
-        for events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
+        for mut events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
+            self.stamp_origin(&mut events);
+            if let Some(tenant) = &self.tenant {
+                tenant.acquire(&mut events).await;
+            }
+            if let Some(memory) = &self.memory {
+                if !memory.acquire(&mut events).await {
+                    continue;
+                }
+            }
             events
                 .iter_events()
                 .for_each(|event| self.emit_lag_time(event, reference));
@@ -310,6 +491,15 @@ impl Inner {
         Ok(())
     }
 
+    fn stamp_origin(&self, events: &mut EventArray) {
+        if let Some(origin) = &self.origin {
+            let now = Utc::now();
+            events
+                .iter_events_mut()
+                .for_each(|event| origin.stamp(event, now));
+        }
+    }
+
     /// Calculate the difference between the reference time and the
     /// timestamp stored in the given event reference, and emit the
     /// different, as expressed in milliseconds, as a histogram.
@@ -350,7 +540,10 @@ fn get_timestamp_millis(value: &Value) -> Option<i64> {
 mod tests {
     use chrono::{DateTime, Duration};
     use rand::{thread_rng, Rng};
-    use vector_core::event::{LogEvent, Metric, MetricKind, MetricValue, TraceEvent};
+    use vector_core::{
+        config::DataType,
+        event::{LogEvent, Metric, MetricKind, MetricValue, TraceEvent},
+    };
 
     use super::*;
     use crate::metrics::{self, Controller};
@@ -438,4 +631,61 @@ mod tests {
             _ => panic!("source_lag_time_seconds has invalid type"),
         }
     }
+
+    #[tokio::test]
+    async fn stamps_origin_metadata() {
+        let mut builder =
+            SourceSender::builder().with_origin(Some(SourceOrigin::new("in", "demo_logs")));
+        let rx = builder.add_output(Output::default(DataType::all()));
+        let mut sender = builder.build();
+
+        let mut log = LogEvent::from("preset");
+        log.metadata_mut()
+            .value_mut()
+            .insert(path!("vector", "source_type"), "upstream");
+        sender
+            .send_batch(vec![Event::Log(log), Event::Log(LogEvent::from("fresh"))])
+            .await
+            .expect("Send should not fail");
+        drop(sender);
+
+        let events = rx
+            .into_stream()
+            .flat_map(into_event_stream)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events.len(), 2);
+
+        let metadata = events[0].metadata().value();
+        assert_eq!(
+            metadata.get(path!("vector", "source_type")),
+            Some(&Value::from("upstream"))
+        );
+        assert_eq!(
+            metadata.get(path!("vector", "component_id")),
+            Some(&Value::from("in"))
+        );
+
+        let metadata = events[1].metadata().value();
+        assert_eq!(
+            metadata.get(path!("vector", "source_type")),
+            Some(&Value::from("demo_logs"))
+        );
+        assert!(metadata
+            .get(path!("vector", "ingest_timestamp"))
+            .and_then(Value::as_timestamp)
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn skips_origin_metadata_when_unset() {
+        let (mut sender, mut recv) = SourceSender::new_test();
+        sender
+            .send_event(Event::Log(LogEvent::from("plain")))
+            .await
+            .expect("Send should not fail");
+
+        let event = recv.next().await.expect("event should be received");
+        assert_eq!(event.metadata().value().get(path!("vector")), None);
+    }
 }
@@ -0,0 +1,258 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    sync::Semaphore,
+    time::{sleep, Instant},
+};
+use vector_common::finalization::{AddBatchNotifier, BatchNotifier};
+use vector_core::{
+    event::{EventArray, EventContainer},
+    ByteSizeOf,
+};
+
+use crate::{
+    config::TenantConfig,
+    internal_events::{TenantBufferedBytes, TenantThrottled},
+};
+
+/// How far ahead of the configured rate the sources of a tenant may send events.
+const RATE_BURST: Duration = Duration::from_secs(1);
+
+/// The quotas of all the configured tenants.
+///
+/// Quotas are kept across topology reloads, so that the sources that weren't rebuilt keep sharing
+/// them with the ones that were.
+#[derive(Default)]
+pub struct TenantRegistry {
+    tenants: Mutex<HashMap<String, Arc<TenantQuota>>>,
+}
+
+impl TenantRegistry {
+    /// Updates the quotas of the given tenants, creating those that don't exist yet.
+    pub fn load<'a>(&self, tenants: impl IntoIterator<Item = (&'a String, &'a TenantConfig)>) {
+        let mut registry = self.tenants.lock().expect("poisoned lock");
+        for (name, config) in tenants {
+            match registry.get(name) {
+                Some(quota) => quota.update(*config),
+                None => {
+                    registry.insert(name.clone(), Arc::new(TenantQuota::new(name, *config)));
+                }
+            }
+        }
+    }
+
+    /// Gets the quota of the given tenant, if it was loaded.
+    pub fn get(&self, name: &str) -> Option<Arc<TenantQuota>> {
+        self.tenants
+            .lock()
+            .expect("poisoned lock")
+            .get(name)
+            .map(Arc::clone)
+    }
+}
+
+/// The resource quotas shared by the sources of a tenant.
+pub struct TenantQuota {
+    name: String,
+    state: Mutex<QuotaState>,
+}
+
+struct QuotaState {
+    config: TenantConfig,
+    /// The time at which all the events sent so far would have been sent at the configured rate.
+    rate_deadline: Instant,
+    /// The buffer bytes that can still be used, along with the total number of buffer bytes.
+    buffer: Option<(Arc<Semaphore>, usize)>,
+}
+
+impl TenantQuota {
+    fn new(name: &str, config: TenantConfig) -> Self {
+        Self {
+            name: name.to_owned(),
+            state: Mutex::new(QuotaState {
+                config,
+                rate_deadline: Instant::now(),
+                buffer: buffer_semaphore(config),
+            }),
+        }
+    }
+
+    fn update(&self, config: TenantConfig) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        // Events in flight keep the permits of the previous semaphore, and release them there.
+        if state.config.max_buffer_bytes != config.max_buffer_bytes {
+            state.buffer = buffer_semaphore(config);
+        }
+        state.config = config;
+    }
+
+    /// Waits until the quotas of the tenant allow sending the given events.
+    ///
+    /// The events are then counted as buffered until they are finalized, which releases their
+    /// bytes for the next events of the tenant. Waiting sources are served in order, so none of
+    /// them is starved by the others.
+    pub(super) async fn acquire(&self, events: &mut EventArray) {
+        let (rate_delay, buffer) = {
+            let mut state = self.state.lock().expect("poisoned lock");
+            let now = Instant::now();
+            let rate_delay = match state.config.max_events_per_sec {
+                Some(rate) => {
+                    let interval =
+                        Duration::from_secs_f64(events.len() as f64 / f64::from(rate.get()));
+                    state.rate_deadline = state.rate_deadline.max(now) + interval;
+                    state
+                        .rate_deadline
+                        .saturating_duration_since(now)
+                        .saturating_sub(RATE_BURST)
+                }
+                None => Duration::ZERO,
+            };
+            (rate_delay, state.buffer.clone())
+        };
+
+        if !rate_delay.is_zero() {
+            emit!(TenantThrottled {
+                tenant: &self.name,
+                reason: "events_per_sec",
+                duration: rate_delay,
+            });
+            sleep(rate_delay).await;
+        }
+
+        if let Some((semaphore, limit)) = buffer {
+            // A batch larger than the whole buffer is let through once the buffer is empty.
+            let bytes = u32::try_from(events.size_of().min(limit)).unwrap_or(u32::MAX);
+            let permit = match Arc::clone(&semaphore).try_acquire_many_owned(bytes) {
+                Ok(permit) => permit,
+                Err(_) => {
+                    let start = Instant::now();
+                    let permit = Arc::clone(&semaphore)
+                        .acquire_many_owned(bytes)
+                        .await
+                        .expect("tenant semaphore is never closed");
+                    emit!(TenantThrottled {
+                        tenant: &self.name,
+                        reason: "buffer_bytes",
+                        duration: start.elapsed(),
+                    });
+                    permit
+                }
+            };
+            // The buffered bytes are shared by all the sources of the tenant, so they're reported
+            // outside of the span of this one.
+            tracing::Span::none().in_scope(|| {
+                emit!(TenantBufferedBytes {
+                    tenant: &self.name,
+                    bytes: limit - semaphore.available_permits(),
+                })
+            });
+
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            events.add_batch_notifier(batch);
+            let name = self.name.clone();
+            tokio::spawn(async move {
+                receiver.await;
+                drop(permit);
+                emit!(TenantBufferedBytes {
+                    tenant: &name,
+                    bytes: limit - semaphore.available_permits(),
+                });
+            });
+        }
+    }
+}
+
+fn buffer_semaphore(config: TenantConfig) -> Option<(Arc<Semaphore>, usize)> {
+    config.max_buffer_bytes.map(|max_buffer_bytes| {
+        let limit = max_buffer_bytes.get().min(Semaphore::MAX_PERMITS);
+        (Arc::new(Semaphore::new(limit)), limit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU32, NonZeroUsize};
+
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn events(count: usize) -> EventArray {
+        let logs = (0..count)
+            .map(|i| LogEvent::from(format!("event {i}")))
+            .collect::<Vec<_>>();
+        logs.into()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_events_per_sec() {
+        let quota = TenantQuota::new(
+            "tenant",
+            TenantConfig {
+                max_events_per_sec: NonZeroU32::new(10),
+                max_buffer_bytes: None,
+            },
+        );
+
+        let start = Instant::now();
+        // The first second's worth of events is sent as a burst.
+        quota.acquire(&mut events(10)).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        quota.acquire(&mut events(20)).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn limits_buffer_bytes() {
+        let batch = events(1);
+        let quota = Arc::new(TenantQuota::new(
+            "tenant",
+            TenantConfig {
+                max_events_per_sec: None,
+                max_buffer_bytes: NonZeroUsize::new(batch.size_of() * 2),
+            },
+        ));
+
+        let mut first = batch.clone();
+        let mut second = batch.clone();
+        quota.acquire(&mut first).await;
+        quota.acquire(&mut second).await;
+
+        let blocked = {
+            let quota = Arc::clone(&quota);
+            tokio::spawn(async move { quota.acquire(&mut events(1)).await })
+        };
+        tokio::task::yield_now().await;
+        assert!(!blocked.is_finished());
+
+        // Finalizing the events releases their bytes.
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(5), blocked)
+            .await
+            .expect("the blocked batch should be let through")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn keeps_quotas_across_loads() {
+        let registry = TenantRegistry::default();
+        let name = "tenant".to_owned();
+        let config = TenantConfig::default();
+        registry.load([(&name, &config)]);
+        let quota = registry.get("tenant").unwrap();
+
+        let config = TenantConfig {
+            max_events_per_sec: NonZeroU32::new(5),
+            max_buffer_bytes: None,
+        };
+        registry.load([(&name, &config)]);
+        assert!(Arc::ptr_eq(&quota, &registry.get("tenant").unwrap()));
+        assert_eq!(quota.state.lock().unwrap().config, config);
+        assert!(registry.get("other").is_none());
+    }
+}
@@ -1,8 +1,8 @@
 //! `AMQP` source.
-//! Handles version AMQP 0.9.1 which is used by RabbitMQ.
+//! Handles version AMQP 0.9.1 which is used by RabbitMQ, including consuming from RabbitMQ streams.
 use crate::{
     amqp::AmqpConfig,
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{Output, SourceConfig, SourceContext},
     event::{BatchNotifier, BatchStatus},
     internal_events::{
@@ -13,13 +13,17 @@ use crate::{
     shutdown::ShutdownSignal,
     SourceSender,
 };
-use async_stream::stream;
 use bytes::Bytes;
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use codecs::decoding::{DeserializerConfig, FramingConfig};
 use futures::{FutureExt, StreamExt};
 use futures_util::Stream;
-use lapin::{acker::Acker, message::Delivery, Channel};
+use lapin::{
+    acker::Acker,
+    message::Delivery,
+    types::{AMQPValue, FieldTable},
+    Channel,
+};
 use lookup::{lookup_v2::OptionalValuePath, metadata_path, owned_value_path, path, PathPrefix};
 use snafu::Snafu;
 use std::{io::Cursor, pin::Pin};
@@ -96,9 +100,97 @@ pub struct AmqpSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     pub(crate) decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) on_decode_error: DecodeErrorPolicy,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     pub(crate) acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The maximum number of messages that are delivered without being acknowledged.
+    ///
+    /// Consuming from a RabbitMQ stream requires a prefetch count to be set. Higher values improve
+    /// throughput, at the cost of more messages being redelivered if Vector stops unexpectedly. By
+    /// default, the number of unacknowledged messages isn't limited.
+    #[configurable(metadata(docs::examples = 1000))]
+    pub(crate) prefetch_count: Option<u16>,
+
+    #[configurable(derived)]
+    pub(crate) stream_offset: Option<StreamOffset>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) on_decode_failure: DecodeFailureAction,
+}
+
+/// The position in a RabbitMQ stream to start consuming from.
+///
+/// Only supported when consuming from a stream. By default, consumption starts from the next
+/// message published to the stream.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum StreamOffset {
+    /// A named position in the stream.
+    Position(StreamPosition),
+
+    /// The offset of the first message to consume.
+    Offset(u64),
+
+    /// The time from which to consume messages.
+    ///
+    /// As streams are stored in segments, messages published slightly before this time may also be
+    /// consumed.
+    Timestamp(DateTime<Utc>),
+}
+
+/// A named position in a RabbitMQ stream.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamPosition {
+    /// The first message available in the stream.
+    First,
+
+    /// The last chunk of messages written to the stream.
+    Last,
+
+    /// The next message published to the stream.
+    Next,
+}
+
+impl StreamOffset {
+    fn to_amqp_value(&self) -> AMQPValue {
+        match self {
+            Self::Position(StreamPosition::First) => AMQPValue::LongString("first".into()),
+            Self::Position(StreamPosition::Last) => AMQPValue::LongString("last".into()),
+            Self::Position(StreamPosition::Next) => AMQPValue::LongString("next".into()),
+            Self::Offset(offset) => AMQPValue::LongLongInt(*offset as i64),
+            Self::Timestamp(timestamp) => AMQPValue::Timestamp(timestamp.timestamp() as u64),
+        }
+    }
+}
+
+/// The action taken on messages that can't be decoded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DecodeFailureAction {
+    /// Acknowledge the message, forwarding any events that could be decoded from it.
+    #[default]
+    Ack,
+
+    /// Reject the message, returning it to the queue to be delivered again.
+    ///
+    /// Messages that can never be decoded are redelivered indefinitely.
+    Requeue,
+
+    /// Reject the message without returning it to the queue.
+    ///
+    /// The message is routed to the dead letter exchange of the queue, if one is configured, and
+    /// dropped otherwise.
+    DeadLetter,
 }
 
 fn default_queue() -> String {
@@ -124,8 +216,19 @@ fn default_offset_key() -> OptionalValuePath {
 impl_generate_config_from_default!(AmqpSourceConfig);
 
 impl AmqpSourceConfig {
-    fn decoder(&self, log_namespace: LogNamespace) -> Decoder {
-        DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build()
+    fn decoder(&self, log_namespace: LogNamespace) -> crate::Result<Decoder> {
+        DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+            .with_error_policy(self.on_decode_error)
+            .build()
+    }
+
+    /// The arguments passed along when starting to consume the queue.
+    fn consume_arguments(&self) -> FieldTable {
+        let mut arguments = FieldTable::default();
+        if let Some(stream_offset) = &self.stream_offset {
+            arguments.insert("x-stream-offset".into(), stream_offset.to_amqp_value());
+        }
+        arguments
     }
 }
 
@@ -176,7 +279,11 @@ impl SourceConfig for AmqpSourceConfig {
                 None,
             );
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -205,6 +312,7 @@ pub(crate) async fn amqp_source(
     acknowledgements: bool,
 ) -> crate::Result<super::Source> {
     let config = config.clone();
+    let decoder = config.decoder(log_namespace)?;
     let (_conn, channel) = config
         .connection
         .connect()
@@ -213,6 +321,7 @@ pub(crate) async fn amqp_source(
 
     Ok(Box::pin(run_amqp_source(
         config,
+        decoder,
         shutdown,
         out,
         channel,
@@ -304,13 +413,14 @@ fn populate_event(
 /// Receives an event from `AMQP` and pushes it along the pipeline.
 async fn receive_event(
     config: &AmqpSourceConfig,
+    decoder: &Decoder,
     out: &mut SourceSender,
     log_namespace: LogNamespace,
     finalizer: Option<&UnorderedFinalizer<FinalizerEntry>>,
     msg: Delivery,
 ) -> Result<(), ()> {
     let payload = Cursor::new(Bytes::copy_from_slice(&msg.data));
-    let mut stream = FramedRead::new(payload, config.decoder(log_namespace));
+    let mut stream = FramedRead::new(payload, decoder.clone());
 
     // Extract timestamp from AMQP message
     let timestamp = msg
@@ -330,44 +440,59 @@ async fn receive_event(
     };
     let events_received = register!(EventsReceived);
 
-    let stream = stream! {
-        while let Some(result) = stream.next().await {
-            match result {
-                Ok((events, byte_size)) => {
-                    emit!(AmqpBytesReceived {
-                        byte_size,
-                        protocol: "amqp_0_9_1",
-                    });
-
-                    events_received.emit(CountByteSize(
-                        events.len(),
-                        events.estimated_json_encoded_size_of(),
-                    ));
-
-                    for mut event in events {
-                        populate_event(&mut event,
-                                       timestamp,
-                                       &keys,
-                                       log_namespace);
-
-                        yield event;
-                    }
+    // Messages are decoded in full before any of their events are sent, so that messages that
+    // can't be decoded can be handled as a whole.
+    let mut decoded = Vec::new();
+    let mut decode_failed = false;
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok((events, byte_size)) => {
+                emit!(AmqpBytesReceived {
+                    byte_size,
+                    protocol: "amqp_0_9_1",
+                });
+
+                events_received.emit(CountByteSize(
+                    events.len(),
+                    events.estimated_json_encoded_size_of(),
+                ));
+
+                for mut event in events {
+                    populate_event(&mut event, timestamp, &keys, log_namespace);
+                    decoded.push(event);
                 }
-                Err(error) => {
-                    use codecs::StreamDecodingError as _;
+            }
+            Err(error) => {
+                use codecs::StreamDecodingError as _;
 
-                    // Error is logged by `codecs::Decoder`, no further handling
-                    // is needed here.
-                    if !error.can_continue() {
-                        break;
-                    }
+                // Error is logged by `codecs::Decoder`, no further handling
+                // is needed here.
+                decode_failed = true;
+                if !error.can_continue() {
+                    break;
                 }
             }
         }
     }
-    .boxed();
 
-    finalize_event_stream(finalizer, out, stream, msg).await;
+    let requeue = match config.on_decode_failure {
+        _ if !decode_failed => None,
+        DecodeFailureAction::Ack => None,
+        DecodeFailureAction::Requeue => Some(true),
+        DecodeFailureAction::DeadLetter => Some(false),
+    };
+    match requeue {
+        Some(requeue) => {
+            let reject_options = lapin::options::BasicRejectOptions { requeue };
+            if let Err(error) = msg.acker.reject(reject_options).await {
+                emit!(AmqpRejectError { error });
+            }
+        }
+        None => {
+            let stream = futures::stream::iter(decoded).boxed();
+            finalize_event_stream(finalizer, out, stream, msg).await;
+        }
+    }
 
     Ok(())
 }
@@ -410,6 +535,7 @@ async fn finalize_event_stream(
 /// Runs the `AMQP` source involving the main loop pulling data from the server.
 async fn run_amqp_source(
     config: AmqpSourceConfig,
+    decoder: Decoder,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
     channel: Channel,
@@ -419,13 +545,22 @@ async fn run_amqp_source(
     let (finalizer, mut ack_stream) =
         UnorderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
 
+    if let Some(prefetch_count) = config.prefetch_count {
+        channel
+            .basic_qos(prefetch_count, lapin::options::BasicQosOptions::default())
+            .await
+            .map_err(|error| {
+                error!(message = "Failed to set prefetch count.", error = ?error, internal_log_rate_limit = true);
+            })?;
+    }
+
     debug!("Starting amqp source, listening to queue {}.", config.queue);
     let mut consumer = channel
         .basic_consume(
             &config.queue,
             &config.consumer,
             lapin::options::BasicConsumeOptions::default(),
-            lapin::types::FieldTable::default(),
+            config.consume_arguments(),
         )
         .await
         .map_err(|error| {
@@ -449,7 +584,7 @@ async fn run_amqp_source(
                             return Err(());
                         }
                         Ok(msg) => {
-                            receive_event(&config, &mut out, log_namespace, finalizer.as_ref(), msg).await?
+                            receive_event(&config, &decoder, &mut out, log_namespace, finalizer.as_ref(), msg).await?
                         }
                     }
                 } else {
@@ -511,6 +646,35 @@ pub mod test {
         config
     }
 
+    #[test]
+    fn parse_stream_offset() {
+        for (value, expected) in [
+            (r#""first""#, StreamOffset::Position(StreamPosition::First)),
+            ("42", StreamOffset::Offset(42)),
+            (
+                r#""2023-01-01T00:00:00Z""#,
+                StreamOffset::Timestamp(Utc.timestamp_opt(1672531200, 0).unwrap()),
+            ),
+        ] {
+            let config: AmqpSourceConfig =
+                toml::from_str(&format!("stream_offset = {}", value)).unwrap();
+            assert_eq!(config.stream_offset, Some(expected));
+        }
+
+        let config: AmqpSourceConfig = toml::from_str(r#"stream_offset = "last""#).unwrap();
+        let arguments = config.consume_arguments();
+        assert_eq!(
+            arguments
+                .inner()
+                .get(&lapin::types::ShortString::from("x-stream-offset")),
+            Some(&AMQPValue::LongString("last".into()))
+        );
+        assert!(AmqpSourceConfig::default()
+            .consume_arguments()
+            .inner()
+            .is_empty());
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = AmqpSourceConfig {
@@ -713,4 +877,91 @@ mod integration_test {
         assert!(log_ts.signed_duration_since(now) < chrono::Duration::seconds(1));
         assert_eq!(log["exchange"], exchange.into());
     }
+
+    #[tokio::test]
+    async fn amqp_source_dead_letters_undecodable_messages() {
+        let exchange = format!("test-{}-exchange", random_string(10));
+        let dead_letter_exchange = format!("test-{}-dlx", random_string(10));
+        let dead_letter_queue = format!("test-{}-dlq", random_string(10));
+
+        let mut config = make_config();
+        config.consumer = format!("test-consumer-{}", random_string(10));
+        config.queue = format!("test-{}-queue", random_string(10));
+        config.decoding = DeserializerConfig::Json {
+            json: Default::default(),
+        };
+        config.on_decode_failure = DecodeFailureAction::DeadLetter;
+        let (_conn, channel) = config.connection.connect().await.unwrap();
+
+        for exchange in [&exchange, &dead_letter_exchange] {
+            channel
+                .exchange_declare(
+                    exchange,
+                    lapin::ExchangeKind::Fanout,
+                    ExchangeDeclareOptions {
+                        auto_delete: true,
+                        ..Default::default()
+                    },
+                    lapin::types::FieldTable::default(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let mut arguments = lapin::types::FieldTable::default();
+        arguments.insert(
+            "x-dead-letter-exchange".into(),
+            lapin::types::AMQPValue::LongString(dead_letter_exchange.as_str().into()),
+        );
+        for (queue, exchange, arguments) in [
+            (&config.queue, &exchange, arguments),
+            (
+                &dead_letter_queue,
+                &dead_letter_exchange,
+                lapin::types::FieldTable::default(),
+            ),
+        ] {
+            channel
+                .queue_declare(
+                    queue,
+                    QueueDeclareOptions {
+                        auto_delete: true,
+                        ..Default::default()
+                    },
+                    arguments,
+                )
+                .await
+                .unwrap();
+            channel
+                .queue_bind(
+                    queue,
+                    exchange,
+                    "",
+                    QueueBindOptions::default(),
+                    lapin::types::FieldTable::default(),
+                )
+                .await
+                .unwrap();
+        }
+
+        send_event(&channel, &exchange, "", "not json", 0).await;
+
+        let (tx, rx) = SourceSender::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let source = amqp_source(&config, shutdown, tx, LogNamespace::Legacy, false)
+            .await
+            .unwrap();
+        let handle = tokio::spawn(source);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        drop(trigger);
+        handle.await.unwrap().unwrap();
+        assert_eq!(rx.collect::<Vec<_>>().await.len(), 0);
+
+        let dead_lettered = channel
+            .basic_get(&dead_letter_queue, BasicGetOptions::default())
+            .await
+            .unwrap()
+            .expect("message was not dead lettered");
+        assert_eq!(dead_lettered.delivery.data, b"not json");
+    }
 }
@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use lookup::owned_value_path;
+use snafu::Snafu;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    codecs::{DecodeErrorPolicy, DecodingConfig},
+    common::cloudwatch_logs::CloudwatchLogsClientBuilder,
+    config::{Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sources::aws_cloudwatch_logs::source::{Checkpoints, CloudwatchLogsSource},
+    tls::TlsConfig,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Either `log_group_names` or `log_group_name_prefix` must be set"))]
+    NoLogGroups,
+    #[snafu(display("Could not read the checkpoints in {:?}: {}", path, source))]
+    ReadCheckpoints {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Configuration for the `aws_cloudwatch_logs` source.
+#[configurable_component(source("aws_cloudwatch_logs"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsCloudwatchLogsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The names of the [log groups][log_groups] to tail.
+    ///
+    /// [log_groups]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/aws/lambda/my-function"))]
+    pub log_group_names: Vec<String>,
+
+    /// The prefix of the names of the log groups to tail.
+    ///
+    /// The log groups whose name starts with the prefix are looked up at each poll, so that the
+    /// ones created in the meantime are tailed too, along with the ones of `log_group_names`.
+    #[configurable(metadata(docs::examples = "/aws/lambda/"))]
+    pub log_group_name_prefix: Option<String>,
+
+    /// The prefix of the names of the log streams to tail, in each log group.
+    ///
+    /// By default, all the log streams of the log groups are tailed.
+    #[configurable(metadata(docs::examples = "2023/01/01/"))]
+    pub log_stream_name_prefix: Option<String>,
+
+    /// The [filter pattern][filter_pattern] the log events must match to be tailed.
+    ///
+    /// By default, all the log events are tailed.
+    ///
+    /// [filter_pattern]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/FilterAndPatternSyntax.html
+    #[configurable(metadata(docs::examples = "ERROR"))]
+    #[configurable(metadata(docs::examples = "{ $.level = \"error\" }"))]
+    pub filter_pattern: Option<String>,
+
+    /// How far back to start tailing the log groups which don't have a checkpoint yet, in
+    /// seconds.
+    ///
+    /// By default, they're tailed from the log events timestamped once Vector starts. The log
+    /// groups having a checkpoint are always tailed from it.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 3600))]
+    pub start_ago_secs: Option<u64>,
+
+    /// How long to wait between the polls of the log groups once all their log events are
+    /// tailed, in seconds.
+    ///
+    /// The polls are spaced out further while CloudWatch Logs throttles the requests, backing
+    /// off exponentially up to a minute.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(validation(range(min = 1)))]
+    pub poll_secs: u32,
+
+    /// The directory used to persist the checkpoints of the log groups.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for AwsCloudwatchLogsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        if self.log_group_names.is_empty() && self.log_group_name_prefix.is_none() {
+            return Err(BuildError::NoLogGroups.into());
+        }
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let path = data_dir.join(CHECKPOINTS_FILENAME);
+        let checkpoints = Checkpoints::load(path.clone())
+            .await
+            .map_err(|source| BuildError::ReadCheckpoints { path, source })?;
+
+        let client = self.build_client(&cx).await?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let start_ago_millis = self.start_ago_secs.unwrap_or(0).saturating_mul(1000);
+
+        Ok(Box::pin(
+            CloudwatchLogsSource {
+                client,
+                log_group_names: self.log_group_names.clone(),
+                log_group_name_prefix: self.log_group_name_prefix.clone(),
+                log_stream_name_prefix: self.log_stream_name_prefix.clone(),
+                filter_pattern: self.filter_pattern.clone(),
+                start_time: Utc::now().timestamp_millis() - start_ago_millis as i64,
+                poll_secs: self.poll_secs,
+                checkpoints,
+                decoder,
+                acknowledgements,
+                log_namespace,
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let mut schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("timestamp"))),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(
+                    "ingestion_timestamp"
+                ))),
+                &owned_value_path!("ingestion_timestamp"),
+                Kind::timestamp().or_undefined(),
+                None,
+            );
+        for field in ["log_group", "log_stream", "id"] {
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(field))),
+                &owned_value_path!(field),
+                Kind::bytes(),
+                None,
+            );
+        }
+
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl AwsCloudwatchLogsConfig {
+    async fn build_client(
+        &self,
+        cx: &SourceContext,
+    ) -> crate::Result<aws_sdk_cloudwatchlogs::Client> {
+        create_client::<CloudwatchLogsClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await
+    }
+}
+
+const CHECKPOINTS_FILENAME: &str = "checkpoints.json";
+
+const fn default_poll_secs() -> u32 {
+    10
+}
+
+impl_generate_config_from_default!(AwsCloudwatchLogsConfig);
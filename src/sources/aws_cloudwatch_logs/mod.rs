@@ -0,0 +1,4 @@
+mod config;
+mod source;
+
+pub use config::AwsCloudwatchLogsConfig;
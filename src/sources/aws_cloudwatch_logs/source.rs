@@ -0,0 +1,443 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::PathBuf,
+    time::Duration,
+};
+
+use aws_sdk_cloudwatchlogs::{model::FilteredLogEvent, Client as CloudwatchLogsClient};
+use chrono::{TimeZone, Utc};
+use lookup::path;
+use serde::{Deserialize, Serialize};
+use tokio::{fs, select, time::sleep};
+use vector_common::internal_event::{EventsReceived, Registered};
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use crate::{
+    aws::is_retriable_error,
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    internal_events::{
+        AwsCloudwatchLogsCheckpointError, AwsCloudwatchLogsRequestError, EndpointBytesReceived,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    sources::{aws_cloudwatch_logs::AwsCloudwatchLogsConfig, util},
+    SourceSender,
+};
+
+/// Where the tailing of a log group is at.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(super) struct Checkpoint {
+    /// The timestamp, in milliseconds, the log events are filtered from.
+    start_time: i64,
+    /// The token of the next page of log events, while some are left from `start_time`.
+    next_token: Option<String>,
+    /// The timestamp of the latest log event tailed from `start_time`, if any.
+    last_timestamp: Option<i64>,
+}
+
+impl Checkpoint {
+    const fn new(start_time: i64) -> Self {
+        Self {
+            start_time,
+            next_token: None,
+            last_timestamp: None,
+        }
+    }
+
+    /// Returns the checkpoint after a page of log events, given its token and latest timestamp.
+    ///
+    /// The pages are fetched from the same start time, for the tokens to stay valid, until the
+    /// last one, after which the log events are filtered from the latest one tailed.
+    fn next(&self, next_token: Option<&str>, last_timestamp: Option<i64>) -> Self {
+        let last_timestamp = self.last_timestamp.max(last_timestamp);
+        match next_token {
+            Some(next_token) => Self {
+                start_time: self.start_time,
+                next_token: Some(next_token.to_owned()),
+                last_timestamp,
+            },
+            None => Self::new(last_timestamp.map_or(self.start_time, |timestamp| timestamp + 1)),
+        }
+    }
+}
+
+/// The checkpoints of the log groups, persisted to a file as they're set.
+pub(super) struct Checkpoints {
+    path: PathBuf,
+    log_groups: HashMap<String, Checkpoint>,
+}
+
+impl Checkpoints {
+    pub(super) async fn load(path: PathBuf) -> Result<Self, io::Error> {
+        let log_groups = match fs::read(&path).await {
+            Ok(data) => serde_json::from_slice(&data)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self { path, log_groups })
+    }
+
+    fn get(&self, log_group: &str) -> Option<&Checkpoint> {
+        self.log_groups.get(log_group)
+    }
+
+    async fn set(&mut self, log_group: &str, checkpoint: Checkpoint) {
+        self.log_groups.insert(log_group.to_owned(), checkpoint);
+        if let Err(error) = self.write().await {
+            emit!(AwsCloudwatchLogsCheckpointError {
+                error,
+                path: self.path.to_string_lossy().into_owned(),
+            });
+        }
+    }
+
+    /// Writes the checkpoints to a temporary file first, for the file to never be left partly
+    /// written.
+    async fn write(&self) -> Result<(), io::Error> {
+        let data = serde_json::to_vec(&self.log_groups)?;
+        let temporary = self.path.with_extension("json.tmp");
+        fs::write(&temporary, data).await?;
+        fs::rename(&temporary, &self.path).await
+    }
+}
+
+enum PollError {
+    Request {
+        error: crate::Error,
+        log_group: String,
+        retriable: bool,
+    },
+    Closed,
+}
+
+impl PollError {
+    fn request<E>(log_group: &str, error: aws_sdk_cloudwatchlogs::types::SdkError<E>) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Self::Request {
+            retriable: is_retriable_error(&error),
+            error: error.into(),
+            log_group: log_group.to_owned(),
+        }
+    }
+}
+
+pub(super) struct CloudwatchLogsSource {
+    pub(super) client: CloudwatchLogsClient,
+    pub(super) log_group_names: Vec<String>,
+    pub(super) log_group_name_prefix: Option<String>,
+    pub(super) log_stream_name_prefix: Option<String>,
+    pub(super) filter_pattern: Option<String>,
+    /// The timestamp, in milliseconds, the log groups without a checkpoint are tailed from.
+    pub(super) start_time: i64,
+    pub(super) poll_secs: u32,
+    pub(super) checkpoints: Checkpoints,
+    pub(super) decoder: Decoder,
+    pub(super) acknowledgements: bool,
+    pub(super) log_namespace: LogNamespace,
+}
+
+impl CloudwatchLogsSource {
+    pub(super) async fn run(
+        mut self,
+        mut out: SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        let poll_interval = Duration::from_secs(self.poll_secs.into());
+        let mut backoff = fresh_backoff();
+
+        loop {
+            let result = select! {
+                _ = &mut shutdown => break,
+                result = self.poll(&mut out, &events_received) => result,
+            };
+            let delay = match result {
+                Ok(()) => {
+                    backoff.reset();
+                    poll_interval
+                }
+                Err(PollError::Request {
+                    error,
+                    log_group,
+                    retriable,
+                }) => {
+                    // The throttled requests, like the ones failing on the side of CloudWatch
+                    // Logs, are retried after exponentially growing delays, while the others
+                    // are retried at the next poll, in case they're fixed in the meantime.
+                    let delay = if retriable {
+                        backoff.next().unwrap_or(poll_interval)
+                    } else {
+                        poll_interval
+                    };
+                    emit!(AwsCloudwatchLogsRequestError {
+                        error: &error,
+                        log_group: &log_group,
+                        retry_after: delay,
+                    });
+                    delay
+                }
+                Err(PollError::Closed) => return Err(()),
+            };
+
+            select! {
+                _ = &mut shutdown => break,
+                _ = sleep(delay) => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tails each log group, until their log events are all sent.
+    async fn poll(
+        &mut self,
+        out: &mut SourceSender,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<(), PollError> {
+        for log_group in self.log_groups().await? {
+            self.tail(&log_group, out, events_received).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the names of the log groups to tail, looking up the ones matching the prefix.
+    async fn log_groups(&self) -> Result<Vec<String>, PollError> {
+        let mut log_groups = self.log_group_names.clone();
+        let prefix = match &self.log_group_name_prefix {
+            Some(prefix) => prefix,
+            None => return Ok(log_groups),
+        };
+
+        let mut next_token = None;
+        loop {
+            let output = self
+                .client
+                .describe_log_groups()
+                .log_group_name_prefix(prefix)
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(|error| PollError::request(prefix, error))?;
+            log_groups.extend(
+                output
+                    .log_groups()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|log_group| log_group.log_group_name())
+                    .map(ToOwned::to_owned),
+            );
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        let mut seen = HashSet::new();
+        log_groups.retain(|log_group| seen.insert(log_group.clone()));
+        Ok(log_groups)
+    }
+
+    /// Sends the log events of a log group, page by page, checkpointing it after each page.
+    ///
+    /// A page whose events aren't delivered is tailed again at the next poll.
+    async fn tail(
+        &mut self,
+        log_group: &str,
+        out: &mut SourceSender,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<(), PollError> {
+        let mut checkpoint = self
+            .checkpoints
+            .get(log_group)
+            .cloned()
+            .unwrap_or_else(|| Checkpoint::new(self.start_time));
+
+        loop {
+            let output = self
+                .client
+                .filter_log_events()
+                .log_group_name(log_group)
+                .set_log_stream_name_prefix(self.log_stream_name_prefix.clone())
+                .set_filter_pattern(self.filter_pattern.clone())
+                .start_time(checkpoint.start_time)
+                .set_next_token(checkpoint.next_token.clone())
+                .send()
+                .await
+                .map_err(|error| PollError::request(log_group, error))?;
+
+            let log_events = output.events().unwrap_or_default();
+            if !self
+                .send(log_group, log_events, out, events_received)
+                .await?
+            {
+                return Ok(());
+            }
+
+            let last_timestamp = log_events
+                .iter()
+                .filter_map(|log_event| log_event.timestamp())
+                .max();
+            let next = checkpoint.next(output.next_token(), last_timestamp);
+            if next != checkpoint {
+                self.checkpoints.set(log_group, next.clone()).await;
+            }
+            checkpoint = next;
+            if checkpoint.next_token.is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Sends the log events of a page, returning whether they were delivered.
+    async fn send(
+        &self,
+        log_group: &str,
+        log_events: &[FilteredLogEvent],
+        out: &mut SourceSender,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<bool, PollError> {
+        if log_events.is_empty() {
+            return Ok(true);
+        }
+
+        emit!(EndpointBytesReceived {
+            byte_size: log_events
+                .iter()
+                .map(|log_event| log_event.message().map_or(0, str::len))
+                .sum(),
+            protocol: "http",
+            endpoint: log_group,
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let mut events = Vec::with_capacity(log_events.len());
+        for log_event in log_events {
+            let timestamp = log_event
+                .timestamp()
+                .and_then(|timestamp| Utc.timestamp_millis_opt(timestamp).single());
+            // Error is logged by `crate::codecs::Decoder`, no further handling is needed here.
+            let decoded = util::decode_message(
+                self.decoder.clone(),
+                AwsCloudwatchLogsConfig::NAME,
+                log_event.message().unwrap_or_default().as_bytes(),
+                timestamp,
+                &batch,
+                self.log_namespace,
+                events_received,
+            )
+            .map(|mut event| {
+                if let Event::Log(ref mut log) = event {
+                    insert_metadata(self.log_namespace, log, log_group, log_event);
+                }
+                event
+            });
+            events.extend(decoded);
+        }
+        drop(batch);
+
+        let count = events.len();
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(PollError::Closed);
+        }
+        Ok(match receiver {
+            Some(receiver) => receiver.await == BatchStatus::Delivered,
+            None => true,
+        })
+    }
+}
+
+fn insert_metadata(
+    log_namespace: LogNamespace,
+    log: &mut LogEvent,
+    log_group: &str,
+    log_event: &FilteredLogEvent,
+) {
+    for (key, value) in [
+        ("log_group", Some(log_group)),
+        ("log_stream", log_event.log_stream_name()),
+        ("id", log_event.event_id()),
+    ] {
+        if let Some(value) = value {
+            log_namespace.insert_source_metadata(
+                AwsCloudwatchLogsConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!(key))),
+                path!(key),
+                value.to_owned(),
+            );
+        }
+    }
+    if let Some(ingestion_timestamp) = log_event
+        .ingestion_time()
+        .and_then(|timestamp| Utc.timestamp_millis_opt(timestamp).single())
+    {
+        log_namespace.insert_source_metadata(
+            AwsCloudwatchLogsConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("ingestion_timestamp"))),
+            path!("ingestion_timestamp"),
+            ingestion_timestamp,
+        );
+    }
+}
+
+const fn fresh_backoff() -> ExponentialBackoff {
+    ExponentialBackoff::from_millis(2)
+        .factor(500)
+        .max_delay(Duration::from_secs(60))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn checkpoint_follows_pages() {
+        let checkpoint = Checkpoint::new(1000);
+
+        // The pages are fetched from the same start time.
+        let checkpoint = checkpoint.next(Some("page-2"), Some(1500));
+        assert_eq!(checkpoint.start_time, 1000);
+        assert_eq!(checkpoint.next_token.as_deref(), Some("page-2"));
+        let checkpoint = checkpoint.next(Some("page-3"), None);
+        assert_eq!(checkpoint.last_timestamp, Some(1500));
+
+        // The log events are then filtered from the latest one tailed.
+        let checkpoint = checkpoint.next(None, Some(1200));
+        assert_eq!(checkpoint, Checkpoint::new(1501));
+
+        // Until some log events come in.
+        assert_eq!(checkpoint.next(None, None), Checkpoint::new(1501));
+    }
+
+    #[tokio::test]
+    async fn checkpoints_are_persisted() {
+        let data_dir = tempdir().unwrap();
+        let path = data_dir.path().join("checkpoints.json");
+
+        let mut checkpoints = Checkpoints::load(path.clone()).await.unwrap();
+        assert!(checkpoints.get("group").is_none());
+        let checkpoint = Checkpoint::new(1000).next(Some("token"), Some(1001));
+        checkpoints.set("group", checkpoint.clone()).await;
+
+        let checkpoints = Checkpoints::load(path).await.unwrap();
+        assert_eq!(checkpoints.get("group"), Some(&checkpoint));
+        assert!(checkpoints.get("other").is_none());
+    }
+
+    #[test]
+    fn backs_off_up_to_a_minute() {
+        let delays = fresh_backoff().take(10).collect::<Vec<_>>();
+        assert_eq!(delays[0], Duration::from_secs(1));
+        assert_eq!(delays[1], Duration::from_secs(2));
+        assert_eq!(delays[9], Duration::from_secs(60));
+    }
+}
@@ -12,7 +12,7 @@ use super::{
     errors::{ParseSnafu, RequestError},
     handlers,
     models::{FirehoseRequest, FirehoseResponse},
-    Compression,
+    Compression, RecordType,
 };
 use crate::{
     codecs,
@@ -21,10 +21,12 @@ use crate::{
 };
 
 /// Handles routing of incoming HTTP requests from AWS Kinesis Firehose
+#[allow(clippy::too_many_arguments)]
 pub fn firehose(
     access_keys: Vec<String>,
     store_access_key: bool,
     record_compression: Compression,
+    record_type: RecordType,
     decoder: codecs::Decoder,
     acknowledgements: bool,
     out: SourceSender,
@@ -33,6 +35,7 @@ pub fn firehose(
     let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
     let context = handlers::Context {
         compression: record_compression,
+        record_type,
         store_access_key,
         decoder,
         acknowledgements,
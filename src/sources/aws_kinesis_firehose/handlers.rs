@@ -2,7 +2,7 @@ use std::io::Read;
 
 use base64::prelude::{Engine as _, BASE64_STANDARD};
 use bytes::Bytes;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use codecs::StreamDecodingError;
 use flate2::read::MultiGzDecoder;
 use futures::StreamExt;
@@ -25,13 +25,16 @@ use warp::reject;
 
 use super::{
     errors::{ParseRecordsSnafu, RequestError},
-    models::{EncodedFirehoseRecord, FirehoseRequest, FirehoseResponse},
-    Compression,
+    models::{
+        CloudwatchLogsMessage, CloudwatchLogsMessageType, EncodedFirehoseRecord, FirehoseRequest,
+        FirehoseResponse,
+    },
+    Compression, RecordType,
 };
 use crate::{
     codecs::Decoder,
     config::log_schema,
-    event::{BatchStatus, Event},
+    event::{BatchStatus, Event, LogEvent},
     internal_events::{
         AwsKinesisFirehoseAutomaticRecordDecodeError, EventsReceived, StreamClosedError,
     },
@@ -42,6 +45,7 @@ use crate::{
 #[derive(Clone)]
 pub(super) struct Context {
     pub(super) compression: Compression,
+    pub(super) record_type: RecordType,
     pub(super) store_access_key: bool,
     pub(super) decoder: Decoder,
     pub(super) acknowledgements: bool,
@@ -68,119 +72,142 @@ pub(super) async fn firehose(
             .map_err(reject::custom)?;
         context.bytes_received.emit(ByteSize(bytes.len()));
 
-        let mut stream = FramedRead::new(bytes.as_ref(), context.decoder.clone());
-        loop {
-            match stream.next().await {
-                Some(Ok((mut events, _byte_size))) => {
-                    events_received.emit(CountByteSize(
-                        events.len(),
-                        events.estimated_json_encoded_size_of(),
-                    ));
-
-                    let (batch, receiver) = context
-                        .acknowledgements
-                        .then(|| {
-                            let (batch, receiver) = BatchNotifier::new_with_receiver();
-                            (Some(batch), Some(receiver))
-                        })
-                        .unwrap_or((None, None));
-
-                    let now = Utc::now();
-                    for event in &mut events {
-                        if let Some(batch) = &batch {
-                            event.add_batch_notifier(batch.clone());
-                        }
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_vector_metadata(
-                                log,
-                                Some(log_schema().source_type_key()),
-                                path!("source_type"),
-                                Bytes::from_static(AwsKinesisFirehoseConfig::NAME.as_bytes()),
-                            );
-                            // This handles the transition from the original timestamp logic. Originally the
-                            // `timestamp_key` was always populated by the `request.timestamp` time.
-                            match log_namespace {
-                                LogNamespace::Vector => {
-                                    log.insert(metadata_path!("vector", "ingest_timestamp"), now);
-                                    log.insert(
-                                        metadata_path!(AwsKinesisFirehoseConfig::NAME, "timestamp"),
-                                        request.timestamp,
-                                    );
-                                }
-                                LogNamespace::Legacy => {
-                                    if let Some(timestamp_key) = log_schema().timestamp_key() {
-                                        log.try_insert(
-                                            (PathPrefix::Event, timestamp_key),
-                                            request.timestamp,
+        let payloads = match context.record_type {
+            RecordType::Raw => vec![(bytes, None)],
+            RecordType::CloudwatchLogs => decode_cloudwatch_logs(&bytes)
+                .with_context(|_| ParseRecordsSnafu {
+                    request_id: request_id.clone(),
+                })
+                .map_err(reject::custom)?,
+        };
+
+        for (payload, cloudwatch) in payloads {
+            let mut stream = FramedRead::new(payload.as_ref(), context.decoder.clone());
+            loop {
+                match stream.next().await {
+                    Some(Ok((mut events, _byte_size))) => {
+                        events_received.emit(CountByteSize(
+                            events.len(),
+                            events.estimated_json_encoded_size_of(),
+                        ));
+
+                        let (batch, receiver) = context
+                            .acknowledgements
+                            .then(|| {
+                                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                                (Some(batch), Some(receiver))
+                            })
+                            .unwrap_or((None, None));
+
+                        let timestamp = cloudwatch
+                            .as_ref()
+                            .map_or(request.timestamp, |cloudwatch| cloudwatch.timestamp);
+                        let now = Utc::now();
+                        for event in &mut events {
+                            if let Some(batch) = &batch {
+                                event.add_batch_notifier(batch.clone());
+                            }
+                            if let Event::Log(ref mut log) = event {
+                                log_namespace.insert_vector_metadata(
+                                    log,
+                                    Some(log_schema().source_type_key()),
+                                    path!("source_type"),
+                                    Bytes::from_static(AwsKinesisFirehoseConfig::NAME.as_bytes()),
+                                );
+                                // This handles the transition from the original timestamp logic. Originally the
+                                // `timestamp_key` was always populated by the `request.timestamp` time.
+                                match log_namespace {
+                                    LogNamespace::Vector => {
+                                        log.insert(
+                                            metadata_path!("vector", "ingest_timestamp"),
+                                            now,
+                                        );
+                                        log.insert(
+                                            metadata_path!(
+                                                AwsKinesisFirehoseConfig::NAME,
+                                                "timestamp"
+                                            ),
+                                            timestamp,
                                         );
                                     }
+                                    LogNamespace::Legacy => {
+                                        if let Some(timestamp_key) = log_schema().timestamp_key() {
+                                            log.try_insert(
+                                                (PathPrefix::Event, timestamp_key),
+                                                timestamp,
+                                            );
+                                        }
+                                    }
+                                };
+
+                                log_namespace.insert_source_metadata(
+                                    AwsKinesisFirehoseConfig::NAME,
+                                    log,
+                                    Some(LegacyKey::InsertIfEmpty(path!("request_id"))),
+                                    path!("request_id"),
+                                    request_id.to_owned(),
+                                );
+                                log_namespace.insert_source_metadata(
+                                    AwsKinesisFirehoseConfig::NAME,
+                                    log,
+                                    Some(LegacyKey::InsertIfEmpty(path!("source_arn"))),
+                                    path!("source_arn"),
+                                    source_arn.to_owned(),
+                                );
+                                if let Some(cloudwatch) = &cloudwatch {
+                                    cloudwatch.insert(log_namespace, log);
                                 }
-                            };
 
-                            log_namespace.insert_source_metadata(
-                                AwsKinesisFirehoseConfig::NAME,
-                                log,
-                                Some(LegacyKey::InsertIfEmpty(path!("request_id"))),
-                                path!("request_id"),
-                                request_id.to_owned(),
-                            );
-                            log_namespace.insert_source_metadata(
-                                AwsKinesisFirehoseConfig::NAME,
-                                log,
-                                Some(LegacyKey::InsertIfEmpty(path!("source_arn"))),
-                                path!("source_arn"),
-                                source_arn.to_owned(),
-                            );
-
-                            if context.store_access_key {
-                                if let Some(access_key) = &request.access_key {
-                                    log.metadata_mut().secrets_mut().insert_secret(
-                                        "aws_kinesis_firehose_access_key",
-                                        access_key,
-                                    );
+                                if context.store_access_key {
+                                    if let Some(access_key) = &request.access_key {
+                                        log.metadata_mut().secrets_mut().insert_secret(
+                                            "aws_kinesis_firehose_access_key",
+                                            access_key,
+                                        );
+                                    }
                                 }
                             }
                         }
-                    }
 
-                    let count = events.len();
-                    if let Err(error) = context.out.send_batch(events).await {
-                        emit!(StreamClosedError {
-                            error: error.clone(),
-                            count,
-                        });
-                        let error = RequestError::ShuttingDown {
-                            request_id: request_id.clone(),
-                            source: error,
-                        };
-                        warp::reject::custom(error);
-                    }
+                        let count = events.len();
+                        if let Err(error) = context.out.send_batch(events).await {
+                            emit!(StreamClosedError {
+                                error: error.clone(),
+                                count,
+                            });
+                            let error = RequestError::ShuttingDown {
+                                request_id: request_id.clone(),
+                                source: error,
+                            };
+                            warp::reject::custom(error);
+                        }
 
-                    drop(batch);
-                    if let Some(receiver) = receiver {
-                        match receiver.await {
-                            BatchStatus::Delivered => Ok(()),
-                            BatchStatus::Rejected => {
-                                Err(warp::reject::custom(RequestError::DeliveryFailed {
-                                    request_id: request_id.clone(),
-                                }))
-                            }
-                            BatchStatus::Errored => {
-                                Err(warp::reject::custom(RequestError::DeliveryErrored {
-                                    request_id: request_id.clone(),
-                                }))
-                            }
-                        }?;
+                        drop(batch);
+                        if let Some(receiver) = receiver {
+                            match receiver.await {
+                                BatchStatus::Delivered => Ok(()),
+                                BatchStatus::Rejected => {
+                                    Err(warp::reject::custom(RequestError::DeliveryFailed {
+                                        request_id: request_id.clone(),
+                                    }))
+                                }
+                                BatchStatus::Errored => {
+                                    Err(warp::reject::custom(RequestError::DeliveryErrored {
+                                        request_id: request_id.clone(),
+                                    }))
+                                }
+                            }?;
+                        }
                     }
-                }
-                Some(Err(error)) => {
-                    // Error is logged by `crate::codecs::Decoder`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+                    Some(Err(error)) => {
+                        // Error is logged by `crate::codecs::Decoder`, no further
+                        // handling is needed here.
+                        if !error.can_continue() {
+                            break;
+                        }
                     }
+                    None => break,
                 }
-                None => break,
             }
         }
     }
@@ -194,6 +221,11 @@ pub(super) async fn firehose(
 
 #[derive(Debug, Snafu)]
 pub enum RecordDecodeError {
+    #[snafu(display(
+        "Could not parse record as a CloudWatch Logs subscription message: {}",
+        source
+    ))]
+    CloudwatchLogs { source: serde_json::Error },
     #[snafu(display("Could not base64 decode request data: {}", source))]
     Base64 { source: base64::DecodeError },
     #[snafu(display("Could not decompress request data as {}: {}", compression, source))]
@@ -240,6 +272,75 @@ fn decode_record(
     }
 }
 
+/// The metadata of a log event unwrapped from a CloudWatch Logs subscription message.
+struct CloudwatchLogsMetadata {
+    id: String,
+    timestamp: DateTime<Utc>,
+    owner: String,
+    log_group: String,
+    log_stream: String,
+    subscription_filters: Vec<String>,
+}
+
+impl CloudwatchLogsMetadata {
+    fn insert(&self, log_namespace: LogNamespace, log: &mut LogEvent) {
+        for (key, value) in [
+            ("log_group", &self.log_group),
+            ("log_stream", &self.log_stream),
+            ("owner", &self.owner),
+            ("id", &self.id),
+        ] {
+            log_namespace.insert_source_metadata(
+                AwsKinesisFirehoseConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!(key))),
+                path!(key),
+                value.to_owned(),
+            );
+        }
+        log_namespace.insert_source_metadata(
+            AwsKinesisFirehoseConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("subscription_filters"))),
+            path!("subscription_filters"),
+            self.subscription_filters.clone(),
+        );
+    }
+}
+
+/// Unwraps the log events of a CloudWatch Logs subscription message, along with their metadata.
+///
+/// The control messages, like the empty records, don't have any log event to unwrap.
+fn decode_cloudwatch_logs(
+    data: &[u8],
+) -> Result<Vec<(Bytes, Option<CloudwatchLogsMetadata>)>, RecordDecodeError> {
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let message: CloudwatchLogsMessage =
+        serde_json::from_slice(data).context(CloudwatchLogsSnafu)?;
+    if message.message_type == CloudwatchLogsMessageType::ControlMessage {
+        return Ok(Vec::new());
+    }
+
+    Ok(message
+        .log_events
+        .into_iter()
+        .map(|event| {
+            let metadata = CloudwatchLogsMetadata {
+                id: event.id,
+                timestamp: event.timestamp,
+                owner: message.owner.clone(),
+                log_group: message.log_group.clone(),
+                log_stream: message.log_stream.clone(),
+                subscription_filters: message.subscription_filters.clone(),
+            };
+            (Bytes::from(event.message), Some(metadata))
+        })
+        .collect())
+}
+
 fn decode_gzip(data: &[u8]) -> std::io::Result<Bytes> {
     let mut decoded = Vec::new();
 
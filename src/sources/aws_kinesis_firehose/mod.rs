@@ -4,14 +4,14 @@ use codecs::decoding::{DeserializerConfig, FramingConfig};
 use futures::FutureExt;
 use lookup::owned_value_path;
 use tracing::Span;
-use value::Kind;
+use value::{kind::Collection, Kind};
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 use warp::Filter;
 
 use crate::{
-    codecs::DecodingConfig,
+    codecs::{DecodeErrorPolicy, DecodingConfig},
     config::{
         GenerateConfig, Output, Resource, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
     },
@@ -69,6 +69,10 @@ pub struct AwsKinesisFirehoseConfig {
     #[serde(default)]
     record_compression: Compression,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    record_type: RecordType,
+
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
 
@@ -82,6 +86,10 @@ pub struct AwsKinesisFirehoseConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -123,6 +131,28 @@ pub enum Compression {
     Gzip,
 }
 
+/// Type of the records in a Firehose message.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum RecordType {
+    /// Each record is decoded into events as is.
+    #[derivative(Default)]
+    Raw,
+
+    /// Each record is an [AWS CloudWatch Logs subscription message][subscription_message], whose
+    /// log events are each decoded into events.
+    ///
+    /// The events are given the log group, log stream, owner, subscription filters, ID, and
+    /// timestamp of their log event, and the control messages CloudWatch Logs sends to check the
+    /// destination is reachable are dropped. The records are decompressed according to
+    /// `record_compression`, CloudWatch Logs compressing them with gzip.
+    ///
+    /// [subscription_message]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/SubscriptionFilters.html
+    CloudwatchLogs,
+}
+
 impl fmt::Display for Compression {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self {
@@ -138,7 +168,9 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
 
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
@@ -157,6 +189,7 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
             access_keys.map(|key| key.inner().to_string()).collect(),
             self.store_access_key,
             self.record_compression,
+            self.record_type,
             decoder,
             acknowledgements,
             cx.out,
@@ -180,9 +213,10 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
-        let schema_definition = self
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let mut schema_definition = self
             .decoding
-            .schema_definition(global_log_namespace.merge(self.log_namespace))
+            .schema_definition(log_namespace)
             .with_standard_vector_source_metadata()
             .with_source_metadata(
                 Self::NAME,
@@ -198,8 +232,32 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
                 Kind::bytes(),
                 None,
             );
+        if self.record_type == RecordType::CloudwatchLogs {
+            for field in ["log_group", "log_stream", "owner", "id"] {
+                schema_definition = schema_definition.with_source_metadata(
+                    Self::NAME,
+                    Some(LegacyKey::InsertIfEmpty(owned_value_path!(field))),
+                    &owned_value_path!(field),
+                    Kind::bytes(),
+                    None,
+                );
+            }
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(
+                    "subscription_filters"
+                ))),
+                &owned_value_path!("subscription_filters"),
+                Kind::array(Collection::empty().with_unknown(Kind::bytes())),
+                None,
+            );
+        }
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -220,8 +278,10 @@ impl GenerateConfig for AwsKinesisFirehoseConfig {
             store_access_key: false,
             tls: None,
             record_compression: Default::default(),
+            record_type: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             acknowledgements: Default::default(),
             log_namespace: None,
         })
@@ -297,6 +357,27 @@ mod tests {
         record_compression: Compression,
         delivered: bool,
         log_namespace: bool,
+    ) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
+        source_with_record_type(
+            access_key,
+            access_keys,
+            store_access_key,
+            record_compression,
+            RecordType::Raw,
+            delivered,
+            log_namespace,
+        )
+        .await
+    }
+
+    async fn source_with_record_type(
+        access_key: Option<SensitiveString>,
+        access_keys: Option<Vec<SensitiveString>>,
+        store_access_key: bool,
+        record_compression: Compression,
+        record_type: RecordType,
+        delivered: bool,
+        log_namespace: bool,
     ) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
         use EventStatus::*;
         let status = if delivered { Delivered } else { Rejected };
@@ -311,8 +392,10 @@ mod tests {
                 access_keys,
                 store_access_key,
                 record_compression,
+                record_type,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
+                on_decode_error: Default::default(),
                 acknowledgements: true.into(),
                 log_namespace: Some(log_namespace),
             }
@@ -644,6 +727,111 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn aws_kinesis_firehose_unwraps_cloudwatch_logs() {
+        const CONTROL_RECORD: &str = r#"
+            {
+                "messageType": "CONTROL_MESSAGE",
+                "owner": "CloudwatchLogs",
+                "logGroup": "",
+                "logStream": "",
+                "subscriptionFilters": [],
+                "logEvents": [
+                    {
+                        "id": "",
+                        "timestamp": 1600110003794,
+                        "message": "CWL CONTROL MESSAGE: Checking health of destination Firehose."
+                    }
+                ]
+            }
+        "#;
+
+        for log_namespace in [false, true] {
+            let (rx, addr) = source_with_record_type(
+                None,
+                None,
+                false,
+                Compression::Auto,
+                RecordType::CloudwatchLogs,
+                true,
+                log_namespace,
+            )
+            .await;
+
+            let res = spawn_send(
+                addr,
+                Utc::now(),
+                vec![RECORD.as_bytes(), CONTROL_RECORD.as_bytes()],
+                None,
+                false,
+                Compression::Gzip,
+            )
+            .await;
+
+            let events = collect_ready(rx).await;
+            let res = res.await.unwrap().unwrap();
+            assert_eq!(200, res.status().as_u16());
+
+            let record: models::CloudwatchLogsMessage = serde_json::from_str(RECORD).unwrap();
+            assert_eq!(events.len(), record.log_events.len());
+            for (event, log_event) in events.iter().zip(&record.log_events) {
+                let log = event.as_log();
+                let get = |field: &str| {
+                    if log_namespace {
+                        log.metadata()
+                            .value()
+                            .get(path!("aws_kinesis_firehose", field))
+                            .cloned()
+                    } else {
+                        log.get(field).cloned()
+                    }
+                };
+
+                if log_namespace {
+                    assert_eq!(log.value(), &vrl::value!(log_event.message.clone()));
+                } else {
+                    assert_eq!(log["message"], log_event.message.clone().into());
+                    assert_eq!(log["timestamp"], log_event.timestamp.into());
+                }
+                assert_eq!(get("log_group"), Some("/jesse/test".into()));
+                assert_eq!(get("log_stream"), Some("test".into()));
+                assert_eq!(get("owner"), Some("071959437513".into()));
+                assert_eq!(get("id"), Some(log_event.id.clone().into()));
+                assert_eq!(
+                    get("subscription_filters"),
+                    Some(vrl::value!(["Destination"]))
+                );
+                assert_eq!(get("request_id"), Some(REQUEST_ID.into()));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn aws_kinesis_firehose_rejects_invalid_cloudwatch_logs() {
+        let (_rx, addr) = source_with_record_type(
+            None,
+            None,
+            false,
+            Compression::Auto,
+            RecordType::CloudwatchLogs,
+            true,
+            false,
+        )
+        .await;
+
+        let res = send(
+            addr,
+            Utc::now(),
+            vec![&b"not a subscription message"[..]],
+            None,
+            false,
+            Compression::Gzip,
+        )
+        .await
+        .unwrap();
+        assert_eq!(400, res.status().as_u16());
+    }
+
     #[tokio::test]
     async fn aws_kinesis_firehose_forwards_events_gzip_request() {
         assert_source_compliance(&SOURCE_TAGS, async move {
@@ -0,0 +1,357 @@
+//! Consumes objects by periodically listing the objects of a bucket, for buckets without
+//! notifications set up.
+use std::{collections::HashMap, io, num::NonZeroU64, path::PathBuf, time::Duration};
+
+use aws_sdk_s3::{model::Object, Client as S3Client};
+use aws_types::region::Region;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::{fs, select, time::interval};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::sqs::ObjectReader;
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    internal_events::{S3ListStateWriteError, S3ObjectListError, S3ObjectProcessingError},
+    line_agg, SourceSender,
+};
+
+const STATE_FILENAME: &str = "list_state.json";
+const STATE_TMP_FILENAME: &str = "list_state.new.json";
+
+/// Listing configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to list objects from.
+    #[configurable(metadata(docs::examples = "my-bucket"))]
+    pub(super) bucket: String,
+
+    /// The key prefixes of the objects to read.
+    ///
+    /// By default, all the objects of the bucket are read.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "logs/"))]
+    #[configurable(metadata(docs::examples = "archive/2023/"))]
+    pub(super) prefixes: Vec<String>,
+
+    /// How long to wait between listings of the bucket, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    #[derivative(Default(value = "default_poll_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) poll_interval_secs: NonZeroU64,
+
+    /// Only objects last modified at, or after, this time are read.
+    ///
+    /// The time must be in [RFC 3339][rfc3339] format. By default, all the objects are read,
+    /// including the ones already in the bucket when the source first starts.
+    ///
+    /// [rfc3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    #[configurable(metadata(docs::examples = "2023-01-01T00:00:00Z"))]
+    pub(super) start_from: Option<String>,
+
+    /// The directory used to persist the keys, and ETags, of the objects already read.
+    ///
+    /// An object is read again if its ETag changes. By default, the global `data_dir` option is
+    /// used. Make sure the running user has write permissions to this directory.
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub(super) data_dir: Option<PathBuf>,
+}
+
+fn default_poll_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(60).unwrap()
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("Invalid start_from {:?}: {}", start_from, source))]
+    InvalidStartFrom {
+        source: chrono::ParseError,
+        start_from: String,
+    },
+    #[snafu(display("Could not read the listing state from {:?}: {}", path, source))]
+    ReadState { path: PathBuf, source: io::Error },
+}
+
+pub(super) struct Ingestor {
+    region: Region,
+    s3_client: S3Client,
+
+    multiline: Option<line_agg::Config>,
+    compression: super::Compression,
+
+    bucket: String,
+    prefixes: Vec<Option<String>>,
+    poll_interval: Duration,
+    start_from: Option<DateTime<Utc>>,
+    state: StateStore,
+}
+
+impl Ingestor {
+    pub(super) async fn new(
+        region: Region,
+        s3_client: S3Client,
+        config: Config,
+        data_dir: PathBuf,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+    ) -> Result<Ingestor, IngestorNewError> {
+        let start_from = config
+            .start_from
+            .map(|start_from| {
+                DateTime::parse_from_rfc3339(&start_from)
+                    .map(|start_from| start_from.with_timezone(&Utc))
+                    .context(InvalidStartFromSnafu { start_from })
+            })
+            .transpose()?;
+
+        let state = StateStore::load(data_dir)
+            .await
+            .map_err(|(path, source)| IngestorNewError::ReadState { path, source })?;
+
+        // Listing without a prefix reads the whole bucket.
+        let prefixes = if config.prefixes.is_empty() {
+            vec![None]
+        } else {
+            config.prefixes.into_iter().map(Some).collect()
+        };
+
+        Ok(Ingestor {
+            region,
+            s3_client,
+
+            multiline,
+            compression,
+
+            bucket: config.bucket,
+            prefixes,
+            poll_interval: Duration::from_secs(config.poll_interval_secs.get()),
+            start_from,
+            state,
+        })
+    }
+
+    pub(super) async fn run(
+        mut self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        log_namespace: LogNamespace,
+    ) -> Result<(), ()> {
+        let reader = ObjectReader::new(
+            self.s3_client.clone(),
+            self.compression,
+            self.multiline.clone(),
+            cx.do_acknowledgements(acknowledgements),
+            log_namespace,
+        );
+        let mut out = cx.out;
+        let mut shutdown = cx.shutdown;
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {},
+            }
+
+            // Listing a large bucket can take a while, so shutting down doesn't wait for it to
+            // finish.
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.run_once(&reader, &mut out) => {},
+            }
+        }
+
+        // Persist the objects read until the shutdown, so they aren't read again on restart.
+        self.state.flush().await;
+
+        Ok(())
+    }
+
+    async fn run_once(&mut self, reader: &ObjectReader, out: &mut SourceSender) {
+        for prefix in self.prefixes.clone() {
+            let mut continuation_token = None;
+            loop {
+                let result = self
+                    .s3_client
+                    .list_objects_v2()
+                    .bucket(self.bucket.clone())
+                    .set_prefix(prefix.clone())
+                    .set_continuation_token(continuation_token.take())
+                    .send()
+                    .await;
+                let output = match result {
+                    Ok(output) => output,
+                    Err(error) => {
+                        emit!(S3ObjectListError {
+                            bucket: &self.bucket,
+                            prefix: prefix.as_deref().unwrap_or_default(),
+                            error,
+                        });
+                        break;
+                    }
+                };
+
+                for object in output.contents.unwrap_or_default() {
+                    self.handle_object(reader, out, object).await;
+                }
+                self.state.flush().await;
+
+                match output.next_continuation_token {
+                    Some(token) if output.is_truncated => continuation_token = Some(token),
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    async fn handle_object(
+        &mut self,
+        reader: &ObjectReader,
+        out: &mut SourceSender,
+        object: Object,
+    ) {
+        let key = match object.key {
+            Some(key) => key,
+            None => return,
+        };
+
+        if let Some(start_from) = self.start_from {
+            let last_modified = object
+                .last_modified
+                .and_then(|ts| Utc.timestamp_opt(ts.secs(), ts.subsec_nanos()).single());
+            if matches!(last_modified, Some(last_modified) if last_modified < start_from) {
+                return;
+            }
+        }
+
+        let etag = object.e_tag.unwrap_or_default();
+        if self.state.is_read(&self.bucket, &key, &etag) {
+            return;
+        }
+
+        match reader
+            .read(out, &self.bucket, &key, self.region.as_ref())
+            .await
+        {
+            Ok(()) => self.state.set_read(&self.bucket, key, etag),
+            Err(error) => emit!(S3ObjectProcessingError {
+                bucket: &self.bucket,
+                key: &key,
+                error: &error,
+            }),
+        }
+    }
+}
+
+/// The persisted state of the source, which maps the key of each object already read to its ETag,
+/// by bucket.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ReadObjects {
+    buckets: HashMap<String, HashMap<String, String>>,
+}
+
+struct StateStore {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    objects: ReadObjects,
+    dirty: bool,
+}
+
+impl StateStore {
+    async fn load(data_dir: PathBuf) -> Result<Self, (PathBuf, io::Error)> {
+        let path = data_dir.join(STATE_FILENAME);
+        let tmp_path = data_dir.join(STATE_TMP_FILENAME);
+
+        let objects = match fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|error| {
+                (
+                    path.clone(),
+                    io::Error::new(io::ErrorKind::InvalidData, error),
+                )
+            })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => ReadObjects::default(),
+            Err(error) => return Err((path, error)),
+        };
+
+        Ok(Self {
+            path,
+            tmp_path,
+            objects,
+            dirty: false,
+        })
+    }
+
+    fn is_read(&self, bucket: &str, key: &str, etag: &str) -> bool {
+        self.objects
+            .buckets
+            .get(bucket)
+            .and_then(|objects| objects.get(key))
+            .map_or(false, |read_etag| read_etag == etag)
+    }
+
+    fn set_read(&mut self, bucket: &str, key: String, etag: String) {
+        self.objects
+            .buckets
+            .entry(bucket.to_owned())
+            .or_default()
+            .insert(key, etag);
+        self.dirty = true;
+    }
+
+    /// Writes the state if any object was read since it was last written.
+    ///
+    /// Writing the state after each object would mean rewriting the whole state over and over for
+    /// large buckets, so objects read since the last write are read again if Vector stops
+    /// abruptly.
+    async fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        match self.write().await {
+            Ok(()) => self.dirty = false,
+            Err(error) => emit!(S3ListStateWriteError {
+                error,
+                path: &self.path,
+            }),
+        }
+    }
+
+    /// Writes the state to a temporary file first, which is then renamed over the previous state
+    /// so that a valid file is always available.
+    async fn write(&self) -> io::Result<()> {
+        let bytes = serde_json::to_vec(&self.objects)?;
+        fs::write(&self.tmp_path, bytes).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn state_store_persists_read_objects() {
+        let data_dir = tempdir().unwrap();
+
+        let mut state = StateStore::load(data_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        assert!(!state.is_read("bucket", "key", "etag"));
+        state.set_read("bucket", "key".to_owned(), "etag".to_owned());
+        state.flush().await;
+
+        let state = StateStore::load(data_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        assert!(state.is_read("bucket", "key", "etag"));
+        assert!(!state.is_read("bucket", "key", "changed"));
+        assert!(!state.is_read("other", "key", "etag"));
+    }
+}
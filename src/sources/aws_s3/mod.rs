@@ -21,6 +21,7 @@ use crate::{
     tls::TlsConfig,
 };
 
+mod list;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -60,6 +61,12 @@ enum Strategy {
     /// [aws_sqs]: https://aws.amazon.com/sqs/
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes objects by periodically listing the objects of a bucket.
+    ///
+    /// This is meant for buckets without notifications set up, such as buckets of historical or
+    /// static data.
+    List,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -79,12 +86,14 @@ pub struct AwsS3Config {
     compression: Compression,
 
     /// The strategy to use to consume objects from S3.
-    #[configurable(metadata(docs::hidden))]
     strategy: Strategy,
 
     /// Configuration options for SQS.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for listing the objects of a bucket.
+    list: Option<list::Config>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -134,6 +143,13 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements, log_namespace),
             )),
+            Strategy::List => Ok(Box::pin(
+                self.create_list_ingestor(multiline_config, &cx).await?.run(
+                    cx,
+                    self.acknowledgements,
+                    log_namespace,
+                ),
+            )),
         }
     }
 
@@ -245,6 +261,53 @@ impl AwsS3Config {
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_list_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+        cx: &SourceContext,
+    ) -> crate::Result<list::Ingestor> {
+        let config = self
+            .list
+            .as_ref()
+            .ok_or(CreateListIngestorError::ConfigMissing)?;
+
+        let region = self
+            .region
+            .region()
+            .ok_or(CreateListIngestorError::RegionMissing)?;
+
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|_| CreateListIngestorError::InvalidEndpoint)?;
+
+        let s3_client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            Some(region.clone()),
+            endpoint,
+            &cx.proxy,
+            &self.tls_options,
+            false,
+        )
+        .await?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), cx.key.id())?;
+
+        let ingestor = list::Ingestor::new(
+            region,
+            s3_client,
+            config.clone(),
+            data_dir,
+            self.compression,
+            multiline,
+        )
+        .await?;
+
+        Ok(ingestor)
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -263,6 +326,16 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+#[derive(Debug, Snafu)]
+enum CreateListIngestorError {
+    #[snafu(display("Configuration for `list` required when strategy=list"))]
+    ConfigMissing,
+    #[snafu(display("Region is required"))]
+    RegionMissing,
+    #[snafu(display("Endpoint is invalid"))]
+    InvalidEndpoint,
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
@@ -421,9 +494,10 @@ mod integration_tests {
     use aws_sdk_sqs::{model::QueueAttributeName, Client as SqsClient};
     use lookup::path;
     use similar_asserts::assert_eq;
+    use tempfile::tempdir;
     use value::Value;
 
-    use super::{sqs, AwsS3Config, Compression, Strategy};
+    use super::{list, sqs, AwsS3Config, Compression, Strategy};
     use crate::{
         aws::{create_client, AwsAuthentication, RegionOrEndpoint},
         common::sqs::SqsClientBuilder,
@@ -625,9 +699,11 @@ mod integration_tests {
             None,
             None,
             Some(MultilineConfig {
-                start_pattern: "abc".to_owned(),
+                start_pattern: Some("abc".to_owned()),
+                start_condition: None,
                 mode: line_agg::Mode::HaltWith,
-                condition_pattern: "geh".to_owned(),
+                condition_pattern: Some("geh".to_owned()),
+                condition: None,
                 timeout_ms: Duration::from_millis(1000),
             }),
             logs.join("\n").into_bytes(),
@@ -676,6 +752,68 @@ mod integration_tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn s3_list_objects() {
+        trace_init();
+
+        assert_source_compliance(&SOURCE_TAGS, async move {
+            let s3 = s3_client().await;
+            let bucket = create_bucket(&s3).await;
+            let data_dir = tempdir().unwrap();
+
+            let logs: Vec<String> = random_lines(100).take(10).collect();
+            for (key, payload) in [
+                ("logs/first.log", &logs[..5]),
+                ("logs/second.log", &logs[5..]),
+            ] {
+                s3.put_object()
+                    .bucket(bucket.clone())
+                    .key(key)
+                    .body(ByteStream::from(payload.join("\n").into_bytes()))
+                    .send()
+                    .await
+                    .expect("Could not put object");
+            }
+            // Objects outside the prefixes are not read.
+            s3.put_object()
+                .bucket(bucket.clone())
+                .key("other/ignored.log")
+                .body(ByteStream::from_static(b"ignored"))
+                .send()
+                .await
+                .expect("Could not put object");
+
+            let config = AwsS3Config {
+                region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
+                strategy: Strategy::List,
+                list: Some(list::Config {
+                    bucket: bucket.clone(),
+                    prefixes: vec!["logs/".to_owned()],
+                    data_dir: Some(data_dir.path().to_path_buf()),
+                    ..Default::default()
+                }),
+                acknowledgements: true.into(),
+                ..Default::default()
+            };
+
+            let (tx, rx) = SourceSender::new_test_finalize(Delivered);
+            let cx = SourceContext::new_test(tx, None);
+            let source = config.build(cx).await.unwrap();
+            tokio::spawn(async move { source.await.unwrap() });
+
+            let events = collect_n(rx, logs.len()).await;
+            let mut messages = events
+                .iter()
+                .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            messages.sort();
+            let mut expected = logs.clone();
+            expected.sort();
+            assert_eq!(messages, expected);
+        })
+        .await;
+    }
+
     fn s3_address() -> String {
         std::env::var("S3_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
     }
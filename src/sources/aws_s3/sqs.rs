@@ -271,10 +271,7 @@ pub struct IngestorProcess {
     state: Arc<State>,
     out: SourceSender,
     shutdown: ShutdownSignal,
-    acknowledgements: bool,
-    log_namespace: LogNamespace,
-    bytes_received: Registered<BytesReceived>,
-    events_received: Registered<EventsReceived>,
+    reader: ObjectReader,
 }
 
 impl IngestorProcess {
@@ -285,14 +282,18 @@ impl IngestorProcess {
         log_namespace: LogNamespace,
         acknowledgements: bool,
     ) -> Self {
+        let reader = ObjectReader::new(
+            state.s3_client.clone(),
+            state.compression,
+            state.multiline.clone(),
+            acknowledgements,
+            log_namespace,
+        );
         Self {
             state,
             out,
             shutdown,
-            acknowledgements,
-            log_namespace,
-            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
-            events_received: register!(EventsReceived),
+            reader,
         }
     }
 
@@ -417,8 +418,7 @@ impl IngestorProcess {
 
     async fn handle_s3_event(&mut self, s3_event: S3Event) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, self.log_namespace)
-                .await?
+            self.handle_s3_event_record(record).await?
         }
         Ok(())
     }
@@ -426,7 +426,6 @@ impl IngestorProcess {
     async fn handle_s3_event_record(
         &mut self,
         s3_event: S3EventRecord,
-        log_namespace: LogNamespace,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
         if !SUPPORTED_S3_EVENT_VERSION.matches(&event_version) {
@@ -455,17 +454,96 @@ impl IngestorProcess {
             });
         }
 
+        self.reader
+            .read(
+                &mut self.out,
+                &s3_event.s3.bucket.name,
+                &s3_event.s3.object.key,
+                &s3_event.aws_region,
+            )
+            .await
+    }
+
+    async fn receive_messages(&mut self) -> Result<Vec<Message>, SdkError<ReceiveMessageError>> {
+        self.state
+            .sqs_client
+            .receive_message()
+            .queue_url(self.state.queue_url.clone())
+            .max_number_of_messages(10)
+            .visibility_timeout(self.state.visibility_timeout_secs)
+            .wait_time_seconds(self.state.poll_secs)
+            .send()
+            .map_ok(|res| res.messages.unwrap_or_default())
+            .await
+    }
+
+    async fn delete_messages(
+        &mut self,
+        entries: Vec<DeleteMessageBatchRequestEntry>,
+    ) -> Result<DeleteMessageBatchOutput, SdkError<DeleteMessageBatchError>> {
+        self.state
+            .sqs_client
+            .delete_message_batch()
+            .queue_url(self.state.queue_url.clone())
+            .set_entries(Some(entries))
+            .send()
+            .await
+    }
+}
+
+/// Reads S3 objects into log events and sends them downstream.
+#[derive(Clone)]
+pub(super) struct ObjectReader {
+    s3_client: S3Client,
+    compression: super::Compression,
+    multiline: Option<line_agg::Config>,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl ObjectReader {
+    pub(super) fn new(
+        s3_client: S3Client,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+        acknowledgements: bool,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            s3_client,
+            compression,
+            multiline,
+            acknowledgements,
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    /// Reads the object `key` of `bucket`, and sends its lines as events to `out`.
+    ///
+    /// When acknowledgements are enabled, this waits for the events to be acknowledged, so that
+    /// the object is only considered processed once they are delivered.
+    pub(super) async fn read(
+        &self,
+        out: &mut SourceSender,
+        bucket: &str,
+        key: &str,
+        region: &str,
+    ) -> Result<(), ProcessingError> {
+        let log_namespace = self.log_namespace;
         let object_result = self
-            .state
             .s3_client
             .get_object()
-            .bucket(s3_event.s3.bucket.name.clone())
-            .key(s3_event.s3.object.key.clone())
+            .bucket(bucket)
+            .key(key)
             .send()
             .await
             .context(GetObjectSnafu {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
             });
 
         let object = object_result?;
@@ -480,8 +558,8 @@ impl IngestorProcess {
 
         let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
         let object_reader = super::s3_object_decoder(
-            self.state.compression,
-            &s3_event.s3.object.key,
+            self.compression,
+            key,
             object.content_encoding.as_deref(),
             object.content_type.as_deref(),
             object.body,
@@ -519,7 +597,7 @@ impl IngestorProcess {
                 .map(|r| r.expect("validated by take_while")),
         );
 
-        let lines = match &self.state.multiline {
+        let lines = match &self.multiline {
             Some(config) => Box::new(
                 LineAgg::new(
                     lines.map(|line| ((), line, ())),
@@ -541,21 +619,21 @@ impl IngestorProcess {
                 &mut log,
                 Some(LegacyKey::Overwrite(path!("bucket"))),
                 path!("bucket"),
-                Bytes::from(s3_event.s3.bucket.name.as_bytes().to_vec()),
+                Bytes::from(bucket.as_bytes().to_vec()),
             );
             log_namespace.insert_source_metadata(
                 AwsS3Config::NAME,
                 &mut log,
                 Some(LegacyKey::Overwrite(path!("object"))),
                 path!("object"),
-                Bytes::from(s3_event.s3.object.key.as_bytes().to_vec()),
+                Bytes::from(key.as_bytes().to_vec()),
             );
             log_namespace.insert_source_metadata(
                 AwsS3Config::NAME,
                 &mut log,
                 Some(LegacyKey::Overwrite(path!("region"))),
                 path!("region"),
-                Bytes::from(s3_event.aws_region.as_bytes().to_vec()),
+                Bytes::from(region.as_bytes().to_vec()),
             );
 
             if let Some(metadata) = &metadata {
@@ -603,7 +681,7 @@ impl IngestorProcess {
             log
         });
 
-        let send_error = match self.out.send_event_stream(&mut stream).await {
+        let send_error = match out.send_event_stream(&mut stream).await {
             Ok(_) => None,
             Err(error) => {
                 let (count, _) = stream.size_hint();
@@ -623,14 +701,14 @@ impl IngestorProcess {
         if let Some(error) = read_error {
             Err(ProcessingError::ReadObject {
                 source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
             })
         } else if let Some(error) = send_error {
             Err(ProcessingError::PipelineSend {
                 source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
             })
         } else {
             match receiver {
@@ -647,32 +725,6 @@ impl IngestorProcess {
             }
         }
     }
-
-    async fn receive_messages(&mut self) -> Result<Vec<Message>, SdkError<ReceiveMessageError>> {
-        self.state
-            .sqs_client
-            .receive_message()
-            .queue_url(self.state.queue_url.clone())
-            .max_number_of_messages(10)
-            .visibility_timeout(self.state.visibility_timeout_secs)
-            .wait_time_seconds(self.state.poll_secs)
-            .send()
-            .map_ok(|res| res.messages.unwrap_or_default())
-            .await
-    }
-
-    async fn delete_messages(
-        &mut self,
-        entries: Vec<DeleteMessageBatchRequestEntry>,
-    ) -> Result<DeleteMessageBatchOutput, SdkError<DeleteMessageBatchError>> {
-        self.state
-            .sqs_client
-            .delete_message_batch()
-            .queue_url(self.state.queue_url.clone())
-            .set_entries(Some(entries))
-            .send()
-            .await
-    }
 }
 
 // https://docs.aws.amazon.com/AmazonS3/latest/userguide/how-to-enable-disable-notification-intro.html
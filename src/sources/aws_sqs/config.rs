@@ -7,7 +7,7 @@ use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 
 use crate::aws::create_client;
-use crate::codecs::DecodingConfig;
+use crate::codecs::{DecodeErrorPolicy, DecodingConfig};
 use crate::common::sqs::SqsClientBuilder;
 use crate::tls::TlsConfig;
 use crate::{
@@ -88,6 +88,10 @@ pub struct AwsSqsConfig {
     #[derivative(Default(value = "default_decoding()"))]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     pub acknowledgements: SourceAcknowledgementsConfig,
@@ -108,7 +112,9 @@ impl SourceConfig for AwsSqsConfig {
 
         let client = self.build_client(&cx).await?;
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         Ok(Box::pin(
@@ -131,9 +137,10 @@ impl SourceConfig for AwsSqsConfig {
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
         let schema_definition = self
             .decoding
-            .schema_definition(global_log_namespace.merge(self.log_namespace))
+            .schema_definition(log_namespace)
             .with_standard_vector_source_metadata()
             .with_source_metadata(
                 Self::NAME,
@@ -143,7 +150,11 @@ impl SourceConfig for AwsSqsConfig {
                 Some("timestamp"),
             );
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -0,0 +1,446 @@
+//! Checkpoints, and ownership of partitions, stored as blobs in Azure Blob Storage.
+//!
+//! The blobs are laid out the way the Azure SDKs lay them out, so Vector can share a consumer
+//! group with, or take it over from, other Event Hubs consumers using the same container.
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use azure_core::{
+    error::ErrorKind,
+    request_options::{IfMatchCondition, Metadata},
+};
+use azure_storage_blobs::prelude::*;
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
+use http::StatusCode;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+const OWNER_ID: &str = "ownerid";
+const OFFSET: &str = "offset";
+const SEQUENCE_NUMBER: &str = "sequencenumber";
+
+/// Configuration of the checkpoint store.
+///
+/// The checkpoints of the partitions, and which consumer owns each partition, are stored as blobs
+/// in an Azure Blob Storage container.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(super) struct CheckpointStoreConfig {
+    /// The Azure Blob Storage Account connection string.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    #[configurable(metadata(
+        docs::examples = "DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"
+    ))]
+    pub(super) connection_string: Option<SensitiveString>,
+
+    /// The Azure Blob Storage Account name.
+    ///
+    /// Credentials are loaded the same way as for the `azure_blob` sink. Either
+    /// `connection_string`, or this field, must be specified.
+    #[configurable(metadata(docs::examples = "mylogstorage"))]
+    pub(super) storage_account: Option<String>,
+
+    /// The Azure Blob Storage Endpoint URL.
+    ///
+    /// This may only be used with `storage_account`.
+    #[configurable(metadata(docs::examples = "https://test.blob.core.windows.net/"))]
+    pub(super) endpoint: Option<String>,
+
+    /// The name of the container to store the checkpoints in.
+    #[configurable(metadata(docs::examples = "checkpoints"))]
+    pub(super) container_name: String,
+}
+
+/// The position of a consumer in a partition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct Checkpoint {
+    pub(super) offset: String,
+    pub(super) sequence_number: i64,
+}
+
+/// The ownership of a partition by a consumer of the consumer group.
+#[derive(Clone, Debug)]
+pub(super) struct Ownership {
+    pub(super) partition_id: String,
+    /// The owner, which is empty for partitions relinquished by their last owner.
+    pub(super) owner_id: String,
+    pub(super) last_modified: DateTime<Utc>,
+    etag: Option<String>,
+}
+
+impl Ownership {
+    /// The ownership of a partition that was never claimed.
+    pub(super) fn unclaimed(partition_id: String) -> Self {
+        Self {
+            partition_id,
+            owner_id: String::new(),
+            last_modified: Utc.timestamp_opt(0, 0).unwrap(),
+            etag: None,
+        }
+    }
+}
+
+pub(super) struct CheckpointStore {
+    client: Arc<ContainerClient>,
+    prefix: String,
+}
+
+impl CheckpointStore {
+    pub(super) fn new(
+        client: Arc<ContainerClient>,
+        namespace: &str,
+        event_hub: &str,
+        consumer_group: &str,
+    ) -> Self {
+        Self {
+            client,
+            prefix: format!(
+                "{}/{}/{}",
+                namespace.to_lowercase(),
+                event_hub.to_lowercase(),
+                consumer_group.to_lowercase()
+            ),
+        }
+    }
+
+    fn ownership_prefix(&self) -> String {
+        format!("{}/ownership/", self.prefix)
+    }
+
+    fn checkpoint_prefix(&self) -> String {
+        format!("{}/checkpoint/", self.prefix)
+    }
+
+    /// Lists the ownership of all the partitions that were ever claimed.
+    pub(super) async fn list_ownership(&self) -> azure_core::Result<Vec<Ownership>> {
+        let prefix = self.ownership_prefix();
+        let mut ownerships = Vec::new();
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(prefix.clone())
+            .include_metadata(true)
+            .into_stream();
+        while let Some(page) = pages.next().await {
+            for blob in page?.blobs.blobs() {
+                let partition_id = match blob.name.strip_prefix(&prefix) {
+                    Some(partition_id) => partition_id.to_owned(),
+                    None => continue,
+                };
+                let owner_id = blob
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.get(OWNER_ID))
+                    .cloned()
+                    .unwrap_or_default();
+                let last_modified = Utc
+                    .timestamp_opt(blob.properties.last_modified.unix_timestamp(), 0)
+                    .single()
+                    .unwrap_or_else(Utc::now);
+                ownerships.push(Ownership {
+                    partition_id,
+                    owner_id,
+                    last_modified,
+                    etag: Some(blob.properties.etag.to_string()),
+                });
+            }
+        }
+        Ok(ownerships)
+    }
+
+    /// Sets the owner of a partition, given its current ownership.
+    ///
+    /// Returns `None` if the ownership changed since it was read, as another consumer claimed the
+    /// partition first.
+    pub(super) async fn claim(
+        &self,
+        ownership: &Ownership,
+        owner_id: &str,
+    ) -> azure_core::Result<Option<Ownership>> {
+        let mut metadata = Metadata::new();
+        metadata.insert(OWNER_ID, owner_id.to_owned());
+        let condition = match &ownership.etag {
+            Some(etag) => IfMatchCondition::Match(etag.clone()),
+            // Only create the blob if no other consumer created it in the meantime.
+            None => IfMatchCondition::NotMatch("*".to_owned()),
+        };
+
+        let result = self
+            .client
+            .blob_client(format!(
+                "{}{}",
+                self.ownership_prefix(),
+                ownership.partition_id
+            ))
+            .put_block_blob(Bytes::new())
+            .metadata(metadata)
+            .if_match(condition)
+            .into_future()
+            .await;
+        match result {
+            Ok(response) => Ok(Some(Ownership {
+                partition_id: ownership.partition_id.clone(),
+                owner_id: owner_id.to_owned(),
+                last_modified: Utc::now(),
+                etag: Some(response.etag.to_string()),
+            })),
+            Err(error) if is_conflict(&error) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Reads the checkpoint of a partition, if one was ever written.
+    pub(super) async fn checkpoint(
+        &self,
+        partition_id: &str,
+    ) -> azure_core::Result<Option<Checkpoint>> {
+        let name = format!("{}{}", self.checkpoint_prefix(), partition_id);
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(name.clone())
+            .include_metadata(true)
+            .into_stream();
+        while let Some(page) = pages.next().await {
+            for blob in page?.blobs.blobs() {
+                if blob.name != name {
+                    continue;
+                }
+                let metadata = match &blob.metadata {
+                    Some(metadata) => metadata,
+                    None => return Ok(None),
+                };
+                let offset = metadata.get(OFFSET).cloned();
+                let sequence_number = metadata
+                    .get(SEQUENCE_NUMBER)
+                    .and_then(|sequence_number| sequence_number.parse().ok());
+                return Ok(offset
+                    .zip(sequence_number)
+                    .map(|(offset, sequence_number)| Checkpoint {
+                        offset,
+                        sequence_number,
+                    }));
+            }
+        }
+        Ok(None)
+    }
+
+    pub(super) async fn update_checkpoint(
+        &self,
+        partition_id: &str,
+        checkpoint: &Checkpoint,
+    ) -> azure_core::Result<()> {
+        let mut metadata = Metadata::new();
+        metadata.insert(OFFSET, checkpoint.offset.clone());
+        metadata.insert(SEQUENCE_NUMBER, checkpoint.sequence_number.to_string());
+
+        self.client
+            .blob_client(format!("{}{}", self.checkpoint_prefix(), partition_id))
+            .put_block_blob(Bytes::new())
+            .metadata(metadata)
+            .into_future()
+            .await
+            .map(|_| ())
+    }
+}
+
+/// Whether a conditional write failed because the blob was changed by another consumer.
+fn is_conflict(error: &azure_core::Error) -> bool {
+    match error.kind() {
+        ErrorKind::HttpResponse { status, .. } => {
+            let status: u16 = (*status).into();
+            status == StatusCode::PRECONDITION_FAILED.as_u16()
+                || status == StatusCode::CONFLICT.as_u16()
+        }
+        _ => false,
+    }
+}
+
+/// Picks the partition to claim next, if this consumer owns fewer partitions than its share.
+///
+/// Partitions are balanced evenly between the consumers with active ownerships, where the
+/// partitions that don't divide evenly go to the consumers that claimed them first. Partitions
+/// with no active owner are claimed first, and a partition is only stolen from another consumer
+/// if it owns more than its share. A single partition is claimed per call, so that consumers
+/// starting at the same time converge rather than steal partitions back and forth.
+pub(super) fn partition_to_claim<'a>(
+    partition_ids: &'a [String],
+    ownerships: &HashMap<String, Ownership>,
+    owner_id: &str,
+    expired_before: DateTime<Utc>,
+) -> Option<&'a str> {
+    let active_owner = |partition_id: &str| {
+        ownerships
+            .get(partition_id)
+            .filter(|ownership| {
+                !ownership.owner_id.is_empty() && ownership.last_modified >= expired_before
+            })
+            .map(|ownership| ownership.owner_id.as_str())
+    };
+
+    let mut owned: HashMap<&str, Vec<&'a str>> = HashMap::new();
+    owned.insert(owner_id, Vec::new());
+    let mut unowned = Vec::new();
+    for partition_id in partition_ids {
+        match active_owner(partition_id) {
+            Some(owner) => owned.entry(owner).or_default().push(partition_id),
+            None => unowned.push(partition_id.as_str()),
+        }
+    }
+
+    let min_share = partition_ids.len() / owned.len();
+    let extra = partition_ids.len() % owned.len();
+    let mine = owned[owner_id].len();
+    let above_min = owned
+        .values()
+        .filter(|owned| owned.len() > min_share)
+        .count();
+    let wants_more = mine < min_share || (mine == min_share && above_min < extra);
+    if !wants_more {
+        return None;
+    }
+
+    // Different consumers start from different partitions, so they don't all race to claim the
+    // same one.
+    let mut hasher = DefaultHasher::new();
+    owner_id.hash(&mut hasher);
+    let pick = |candidates: &[&'a str]| candidates[hasher.finish() as usize % candidates.len()];
+
+    if !unowned.is_empty() {
+        return Some(pick(&unowned));
+    }
+
+    // Steal from the consumer owning the most partitions, if it owns more than its share.
+    let max_share = min_share + usize::from(extra > 0);
+    let (_, most) = owned
+        .iter()
+        .filter(|(owner, _)| **owner != owner_id)
+        .max_by_key(|(_, owned)| owned.len())?;
+    if most.len() > max_share || (most.len() == max_share && mine < min_share) {
+        Some(pick(most))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partitions(count: usize) -> Vec<String> {
+        (0..count).map(|id| id.to_string()).collect()
+    }
+
+    fn owned_by(
+        owners: &[(&str, &str)],
+        last_modified: DateTime<Utc>,
+    ) -> HashMap<String, Ownership> {
+        owners
+            .iter()
+            .map(|(partition_id, owner_id)| {
+                (
+                    partition_id.to_string(),
+                    Ownership {
+                        partition_id: partition_id.to_string(),
+                        owner_id: owner_id.to_string(),
+                        last_modified,
+                        etag: Some("etag".to_owned()),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn claims_unowned_partitions_first() {
+        let partition_ids = partitions(4);
+        let now = Utc::now();
+        let ownerships = owned_by(&[("0", "other"), ("1", "other")], now);
+
+        let claimed = partition_to_claim(
+            &partition_ids,
+            &ownerships,
+            "me",
+            now - chrono::Duration::seconds(60),
+        )
+        .unwrap();
+        assert!(claimed == "2" || claimed == "3");
+    }
+
+    #[test]
+    fn claims_expired_partitions() {
+        let partition_ids = partitions(2);
+        let now = Utc::now();
+        let mut ownerships = owned_by(&[("0", "other")], now);
+        ownerships.extend(owned_by(
+            &[("1", "gone")],
+            now - chrono::Duration::minutes(5),
+        ));
+
+        assert_eq!(
+            partition_to_claim(
+                &partition_ids,
+                &ownerships,
+                "me",
+                now - chrono::Duration::seconds(60)
+            ),
+            Some("1")
+        );
+    }
+
+    #[test]
+    fn steals_only_above_share() {
+        let partition_ids = partitions(4);
+        let now = Utc::now();
+        let expired_before = now - chrono::Duration::seconds(60);
+
+        let ownerships = owned_by(
+            &[
+                ("0", "other"),
+                ("1", "other"),
+                ("2", "other"),
+                ("3", "other"),
+            ],
+            now,
+        );
+        let stolen = partition_to_claim(&partition_ids, &ownerships, "me", expired_before);
+        assert!(stolen.is_some());
+
+        let ownerships = owned_by(
+            &[("0", "other"), ("1", "other"), ("2", "me"), ("3", "me")],
+            now,
+        );
+        assert_eq!(
+            partition_to_claim(&partition_ids, &ownerships, "me", expired_before),
+            None
+        );
+    }
+
+    #[test]
+    fn claims_one_of_the_extra_partitions_once() {
+        let partition_ids = partitions(3);
+        let now = Utc::now();
+        let expired_before = now - chrono::Duration::seconds(60);
+
+        // The extra partition already went to the other consumer.
+        let ownerships = owned_by(&[("0", "other"), ("1", "other"), ("2", "me")], now);
+        assert_eq!(
+            partition_to_claim(&partition_ids, &ownerships, "me", expired_before),
+            None
+        );
+
+        // The other consumer has only its share, so the extra partition is up for grabs.
+        let ownerships = owned_by(&[("0", "other"), ("1", "me")], now);
+        assert_eq!(
+            partition_to_claim(&partition_ids, &ownerships, "me", expired_before),
+            Some("2")
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Reading of a single partition of the event hub.
+use std::{sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use fe2o3_amqp::{
+    link::receiver::CreditMode,
+    types::{
+        definitions::SenderSettleMode,
+        messaging::{annotations::OwnedKey, Body, MessageAnnotations, Source},
+        primitives::{Symbol, Value},
+    },
+    Delivery, Receiver,
+};
+use futures::StreamExt;
+use serde_amqp::{described::Described, descriptor::Descriptor};
+use tokio::{
+    select,
+    sync::oneshot,
+    time::{interval, MissedTickBehavior},
+};
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol},
+};
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use super::{
+    checkpoint::{Checkpoint, CheckpointStore},
+    AzureEventHubsSourceConfig, StartPosition,
+};
+use crate::{
+    azure_event_hubs::EventHub,
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{
+        AzureEventHubsCheckpointStoreError, AzureEventHubsReceiveError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util,
+    SourceSender,
+};
+
+const SELECTOR_FILTER: &str = "apache.org:selector-filter:string";
+const OFFSET_ANNOTATION: &str = "x-opt-offset";
+const SEQUENCE_NUMBER_ANNOTATION: &str = "x-opt-sequence-number";
+const ENQUEUED_TIME_ANNOTATION: &str = "x-opt-enqueued-time";
+const PARTITION_KEY_ANNOTATION: &str = "x-opt-partition-key";
+
+/// The event data, and Event Hubs annotations, of a received message.
+struct ReceivedEvent {
+    data: Bytes,
+    checkpoint: Option<Checkpoint>,
+    enqueued_time: Option<DateTime<Utc>>,
+    partition_key: Option<String>,
+}
+
+impl ReceivedEvent {
+    fn from(delivery: &Delivery<Body<Value>>) -> Option<Self> {
+        let data = match delivery.body() {
+            Body::Data(sections) => sections
+                .iter()
+                .flat_map(|section| section.0.iter().copied())
+                .collect::<Vec<u8>>()
+                .into(),
+            Body::Value(value) => match &value.0 {
+                Value::String(value) => Bytes::from(value.clone()),
+                Value::Binary(value) => Bytes::from(value.to_vec()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let annotations = &delivery.message().message_annotations;
+        let offset = match annotation(annotations, OFFSET_ANNOTATION) {
+            Some(Value::String(offset)) => Some(offset.clone()),
+            _ => None,
+        };
+        let sequence_number = match annotation(annotations, SEQUENCE_NUMBER_ANNOTATION) {
+            Some(Value::Long(sequence_number)) => Some(*sequence_number),
+            _ => None,
+        };
+        let enqueued_time = match annotation(annotations, ENQUEUED_TIME_ANNOTATION) {
+            Some(Value::Timestamp(timestamp)) => {
+                Utc.timestamp_millis_opt(timestamp.milliseconds()).latest()
+            }
+            _ => None,
+        };
+        let partition_key = match annotation(annotations, PARTITION_KEY_ANNOTATION) {
+            Some(Value::String(partition_key)) => Some(partition_key.clone()),
+            _ => None,
+        };
+
+        Some(Self {
+            data,
+            checkpoint: offset
+                .zip(sequence_number)
+                .map(|(offset, sequence_number)| Checkpoint {
+                    offset,
+                    sequence_number,
+                }),
+            enqueued_time,
+            partition_key,
+        })
+    }
+}
+
+fn annotation<'a>(annotations: &'a Option<MessageAnnotations>, key: &str) -> Option<&'a Value> {
+    annotations
+        .as_ref()?
+        .0
+        .get(&OwnedKey::Symbol(Symbol::from(key)))
+}
+
+/// The filter selecting the events of the partition to read, after the given position.
+fn selector(start: Option<&Checkpoint>, start_position: StartPosition) -> String {
+    let offset = match (start, start_position) {
+        (Some(checkpoint), _) => checkpoint.offset.as_str(),
+        (None, StartPosition::Earliest) => "-1",
+        (None, StartPosition::Latest) => "@latest",
+    };
+    format!("amqp.annotation.x-opt-offset > '{}'", offset)
+}
+
+/// Reads a partition until it is stopped, or fails to read it.
+pub(super) struct PartitionConsumer {
+    pub(super) event_hub: EventHub,
+    pub(super) partition_id: String,
+    pub(super) config: Arc<AzureEventHubsSourceConfig>,
+    pub(super) decoder: Decoder,
+    pub(super) store: Option<Arc<CheckpointStore>>,
+    pub(super) start: Option<Checkpoint>,
+    pub(super) acknowledgements: bool,
+    pub(super) log_namespace: LogNamespace,
+}
+
+impl PartitionConsumer {
+    /// Returns the position of the last event read, so that reading can start again from it.
+    pub(super) async fn run(
+        self,
+        mut out: SourceSender,
+        shutdown: ShutdownSignal,
+        mut stop: oneshot::Receiver<()>,
+    ) -> Option<Checkpoint> {
+        let mut position = self.start.clone();
+
+        let container_id = format!("vector-{}", uuid::Uuid::new_v4());
+        let (mut connection, mut session) = match self.event_hub.connect(&container_id).await {
+            Ok(connected) => connected,
+            Err(error) => {
+                emit!(AzureEventHubsReceiveError {
+                    error: error.into(),
+                    partition_id: &self.partition_id,
+                });
+                return position;
+            }
+        };
+
+        let address = format!(
+            "{}/ConsumerGroups/{}/Partitions/{}",
+            self.event_hub.name, self.config.consumer_group, self.partition_id
+        );
+        let source = Source::builder()
+            .address(address)
+            .add_to_filter(
+                Symbol::from(SELECTOR_FILTER),
+                Some(Described {
+                    descriptor: Descriptor::Name(Symbol::from(SELECTOR_FILTER)),
+                    value: Value::String(selector(position.as_ref(), self.config.start_position)),
+                }),
+            )
+            .build();
+        let receiver = Receiver::builder()
+            .name(format!("{}-{}", container_id, self.partition_id))
+            .source(source)
+            // Event Hubs doesn't track the settlement of deliveries, the position of the consumer
+            // is tracked through checkpoints instead.
+            .sender_settle_mode(SenderSettleMode::Settled)
+            .credit_mode(CreditMode::Auto(self.config.prefetch_count))
+            .attach(&mut session)
+            .await;
+        let mut receiver = match receiver {
+            Ok(receiver) => receiver,
+            Err(error) => {
+                emit!(AzureEventHubsReceiveError {
+                    error: error.into(),
+                    partition_id: &self.partition_id,
+                });
+                let _ = session.end().await;
+                let _ = connection.close().await;
+                return position;
+            }
+        };
+        debug!(message = "Reading partition.", partition_id = %self.partition_id);
+
+        let bytes_received = register!(BytesReceived::from(Protocol::from("amqp_1_0")));
+        let events_received = register!(EventsReceived);
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<Checkpoint>::maybe_new(self.acknowledgements, shutdown);
+        let mut checkpoint_interval = interval(Duration::from_secs(
+            self.config.checkpoint_interval_secs.get(),
+        ));
+        checkpoint_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut pending = None;
+
+        loop {
+            select! {
+                _ = &mut stop => break,
+                entry = ack_stream.next() => if let Some((status, checkpoint)) = entry {
+                    if status == BatchStatus::Delivered {
+                        position = Some(checkpoint.clone());
+                        pending = Some(checkpoint);
+                    }
+                },
+                _ = checkpoint_interval.tick() => self.write_checkpoint(&mut pending).await,
+                delivery = receiver.recv::<Body<Value>>() => {
+                    let delivery = match delivery {
+                        Ok(delivery) => delivery,
+                        Err(error) => {
+                            emit!(AzureEventHubsReceiveError {
+                                error: error.into(),
+                                partition_id: &self.partition_id,
+                            });
+                            break;
+                        }
+                    };
+                    let received = match ReceivedEvent::from(&delivery) {
+                        Some(received) => received,
+                        None => continue,
+                    };
+                    bytes_received.emit(ByteSize(received.data.len()));
+
+                    let (notifier, status_receiver) = match &finalizer {
+                        Some(_) => {
+                            let (notifier, status_receiver) = BatchNotifier::new_with_receiver();
+                            (Some(notifier), Some(status_receiver))
+                        }
+                        None => (None, None),
+                    };
+                    let events = util::decode_message(
+                        self.decoder.clone(),
+                        AzureEventHubsSourceConfig::NAME,
+                        &received.data,
+                        received.enqueued_time,
+                        &notifier,
+                        self.log_namespace,
+                        &events_received,
+                    )
+                    .map(|mut event| {
+                        if let Some(log) = event.maybe_as_log_mut() {
+                            self.insert_metadata(log, &received);
+                        }
+                        event
+                    })
+                    .collect::<Vec<_>>();
+                    drop(notifier);
+
+                    let count = events.len();
+                    if let Err(error) = out.send_batch(events).await {
+                        emit!(StreamClosedError { error, count });
+                        break;
+                    }
+                    match (received.checkpoint, &finalizer, status_receiver) {
+                        (Some(checkpoint), Some(finalizer), Some(status_receiver)) => {
+                            finalizer.add(checkpoint, status_receiver)
+                        }
+                        (Some(checkpoint), None, _) => {
+                            position = Some(checkpoint.clone());
+                            pending = Some(checkpoint);
+                        }
+                        _ => {}
+                    }
+                },
+            }
+        }
+
+        self.write_checkpoint(&mut pending).await;
+        let _ = receiver.close().await;
+        let _ = session.end().await;
+        let _ = connection.close().await;
+        debug!(message = "Stopped reading partition.", partition_id = %self.partition_id);
+
+        position
+    }
+
+    fn insert_metadata(&self, log: &mut crate::event::LogEvent, received: &ReceivedEvent) {
+        let name = AzureEventHubsSourceConfig::NAME;
+        self.log_namespace.insert_source_metadata(
+            name,
+            log,
+            Some(LegacyKey::Overwrite("partition_id")),
+            "partition_id",
+            self.partition_id.clone(),
+        );
+        if let Some(checkpoint) = &received.checkpoint {
+            self.log_namespace.insert_source_metadata(
+                name,
+                log,
+                Some(LegacyKey::Overwrite("offset")),
+                "offset",
+                checkpoint.offset.clone(),
+            );
+            self.log_namespace.insert_source_metadata(
+                name,
+                log,
+                Some(LegacyKey::Overwrite("sequence_number")),
+                "sequence_number",
+                checkpoint.sequence_number,
+            );
+        }
+        if let Some(partition_key) = &received.partition_key {
+            self.log_namespace.insert_source_metadata(
+                name,
+                log,
+                Some(LegacyKey::Overwrite("partition_key")),
+                "partition_key",
+                partition_key.clone(),
+            );
+        }
+    }
+
+    async fn write_checkpoint(&self, pending: &mut Option<Checkpoint>) {
+        let (store, checkpoint) = match (&self.store, pending.take()) {
+            (Some(store), Some(checkpoint)) => (store, checkpoint),
+            _ => return,
+        };
+        if let Err(error) = store
+            .update_checkpoint(&self.partition_id, &checkpoint)
+            .await
+        {
+            emit!(AzureEventHubsCheckpointStoreError { error });
+            // Try again with the next checkpoint, unless a newer one replaces it by then.
+            *pending = Some(checkpoint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_events_after_position() {
+        let checkpoint = Checkpoint {
+            offset: "4096".to_owned(),
+            sequence_number: 12,
+        };
+        assert_eq!(
+            selector(Some(&checkpoint), StartPosition::Earliest),
+            "amqp.annotation.x-opt-offset > '4096'"
+        );
+        assert_eq!(
+            selector(None, StartPosition::Earliest),
+            "amqp.annotation.x-opt-offset > '-1'"
+        );
+        assert_eq!(
+            selector(None, StartPosition::Latest),
+            "amqp.annotation.x-opt-offset > '@latest'"
+        );
+    }
+}
@@ -0,0 +1,489 @@
+//! The `azure_event_hubs` source.
+//!
+//! Reads the partitions of an event hub over AMQP 1.0. With a checkpoint store, the position of the
+//! source in each partition is checkpointed to Azure Blob Storage, and the partitions are balanced
+//! between all the consumers reading the event hub with the same consumer group.
+mod checkpoint;
+mod consumer;
+
+use std::{collections::HashMap, num::NonZeroU64, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::future::join_all;
+use lookup::owned_value_path;
+use tokio::{select, sync::oneshot, task::JoinHandle, time::interval};
+use tracing::Instrument;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use self::{
+    checkpoint::{
+        partition_to_claim, Checkpoint, CheckpointStore, CheckpointStoreConfig, Ownership,
+    },
+    consumer::PartitionConsumer,
+};
+use crate::{
+    azure_event_hubs::{EventHub, EventHubsConnectionConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
+    config::{GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    internal_events::AzureEventHubsCheckpointStoreError,
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sinks::azure_common,
+    SourceSender,
+};
+
+/// Configuration for the `azure_event_hubs` source.
+#[configurable_component(source("azure_event_hubs"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsSourceConfig {
+    #[serde(flatten)]
+    connection: EventHubsConnectionConfig,
+
+    /// The consumer group to read the event hub as.
+    #[serde(default = "default_consumer_group")]
+    #[configurable(metadata(docs::examples = "vector"))]
+    consumer_group: String,
+
+    /// The identifiers of the partitions to read.
+    ///
+    /// By default, all the partitions of the event hub are read.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "0"))]
+    #[configurable(metadata(docs::examples = "1"))]
+    partition_ids: Vec<String>,
+
+    #[configurable(derived)]
+    checkpoint_store: Option<CheckpointStoreConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    start_position: StartPosition,
+
+    /// The number of events requested from a partition ahead of time.
+    #[serde(default = "default_prefetch_count")]
+    prefetch_count: u32,
+
+    /// How often the position of the source in each partition is written to the checkpoint store,
+    /// in seconds.
+    #[serde(default = "default_checkpoint_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    checkpoint_interval_secs: NonZeroU64,
+
+    /// How often the ownership of the partitions is renewed, and balanced between the consumers of
+    /// the consumer group, in seconds.
+    #[serde(default = "default_load_balancing_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    load_balancing_interval_secs: NonZeroU64,
+
+    /// How long a partition stays owned by a consumer that stopped renewing its ownership, in
+    /// seconds.
+    ///
+    /// Once expired, the partition can be claimed by any other consumer of the consumer group.
+    #[serde(default = "default_ownership_expiration_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ownership_expiration_secs: NonZeroU64,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+/// Where to start reading the partitions without a checkpoint.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum StartPosition {
+    /// The oldest event retained in the partition.
+    Earliest,
+
+    /// The next event enqueued to the partition.
+    #[default]
+    Latest,
+}
+
+fn default_consumer_group() -> String {
+    "$Default".to_owned()
+}
+
+const fn default_prefetch_count() -> u32 {
+    300
+}
+
+fn default_checkpoint_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(10).unwrap()
+}
+
+fn default_load_balancing_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(10).unwrap()
+}
+
+fn default_ownership_expiration_secs() -> NonZeroU64 {
+    NonZeroU64::new(60).unwrap()
+}
+
+impl GenerateConfig for AzureEventHubsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"connection_string = "Endpoint=sb://my-namespace.servicebus.windows.net/;SharedAccessKeyName=my-policy;SharedAccessKey=keybase64encoded"
+            event_hub_name = "my-event-hub"
+            consumer_group = "$Default""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for AzureEventHubsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let event_hub = self.connection.event_hub()?;
+
+        let partition_ids = if self.partition_ids.is_empty() {
+            let container_id = format!("vector-{}", uuid::Uuid::new_v4());
+            let (mut connection, mut session) = event_hub.connect(&container_id).await?;
+            let partition_ids = event_hub.partition_ids(&mut session).await;
+            let _ = session.end().await;
+            let _ = connection.close().await;
+            partition_ids?
+        } else {
+            self.partition_ids.clone()
+        };
+
+        let store = self
+            .checkpoint_store
+            .as_ref()
+            .map(|store| {
+                azure_common::config::build_client(
+                    store
+                        .connection_string
+                        .as_ref()
+                        .map(|v| v.inner().to_string()),
+                    store.storage_account.clone(),
+                    store.container_name.clone(),
+                    store.endpoint.clone(),
+                )
+                .map(|client| {
+                    Arc::new(CheckpointStore::new(
+                        client,
+                        &event_hub.namespace,
+                        &event_hub.name,
+                        &self.consumer_group,
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let source = EventHubsSource {
+            config: Arc::new(self.clone()),
+            event_hub,
+            partition_ids,
+            store,
+            owner_id: uuid::Uuid::new_v4().to_string(),
+            decoder: DecodingConfig::new(
+                self.framing.clone(),
+                self.decoding.clone(),
+                log_namespace,
+            )
+            .with_error_policy(self.on_decode_error)
+            .build()?,
+            acknowledgements,
+            log_namespace,
+        };
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("timestamp"))),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("partition_id"))),
+                &owned_value_path!("partition_id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("offset"))),
+                &owned_value_path!("offset"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("sequence_number"))),
+                &owned_value_path!("sequence_number"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("partition_key"))),
+                &owned_value_path!("partition_key"),
+                Kind::bytes().or_undefined(),
+                None,
+            );
+
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// A partition being read.
+struct RunningConsumer {
+    stop: oneshot::Sender<()>,
+    handle: JoinHandle<Option<Checkpoint>>,
+    ownership: Option<Ownership>,
+}
+
+struct EventHubsSource {
+    config: Arc<AzureEventHubsSourceConfig>,
+    event_hub: EventHub,
+    partition_ids: Vec<String>,
+    store: Option<Arc<CheckpointStore>>,
+    /// Identifies this source in the ownership of partitions.
+    owner_id: String,
+    decoder: Decoder,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+}
+
+impl EventHubsSource {
+    async fn run(self, out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut consumers = HashMap::new();
+        // The positions of the partitions that stopped being read after an error, to read them
+        // again from there when there is no checkpoint store.
+        let mut positions = HashMap::new();
+        let mut interval = interval(Duration::from_secs(
+            self.config.load_balancing_interval_secs.get(),
+        ));
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {},
+            }
+
+            Self::reap(&mut consumers, &mut positions).await;
+            match &self.store {
+                Some(store) => self.balance(store, &mut consumers, &out, &shutdown).await,
+                None => {
+                    for partition_id in &self.partition_ids {
+                        if !consumers.contains_key(partition_id) {
+                            let start = positions.remove(partition_id);
+                            let consumer =
+                                self.start_consumer(partition_id, start, None, &out, &shutdown);
+                            consumers.insert(partition_id.clone(), consumer);
+                        }
+                    }
+                }
+            }
+        }
+
+        let store = self.store.as_ref();
+        join_all(consumers.into_values().map(|consumer| async move {
+            let _ = consumer.stop.send(());
+            let _ = consumer.handle.await;
+            // Relinquish the partition, so that other consumers can claim it right away rather
+            // than wait for its ownership to expire.
+            if let (Some(store), Some(ownership)) = (store, consumer.ownership) {
+                if let Err(error) = store.claim(&ownership, "").await {
+                    emit!(AzureEventHubsCheckpointStoreError { error });
+                }
+            }
+        }))
+        .await;
+
+        Ok(())
+    }
+
+    /// Removes the consumers that stopped reading their partition after an error.
+    async fn reap(
+        consumers: &mut HashMap<String, RunningConsumer>,
+        positions: &mut HashMap<String, Checkpoint>,
+    ) {
+        let finished = consumers
+            .iter()
+            .filter(|(_, consumer)| consumer.handle.is_finished())
+            .map(|(partition_id, _)| partition_id.clone())
+            .collect::<Vec<_>>();
+        for partition_id in finished {
+            if let Some(consumer) = consumers.remove(&partition_id) {
+                if let Ok(Some(position)) = consumer.handle.await {
+                    positions.insert(partition_id, position);
+                }
+            }
+        }
+    }
+
+    /// Renews the ownership of the partitions being read, and claims one more partition if this
+    /// source reads fewer partitions than its share.
+    async fn balance(
+        &self,
+        store: &Arc<CheckpointStore>,
+        consumers: &mut HashMap<String, RunningConsumer>,
+        out: &SourceSender,
+        shutdown: &ShutdownSignal,
+    ) {
+        let mut ownerships = match store.list_ownership().await {
+            Ok(ownerships) => ownerships
+                .into_iter()
+                .map(|ownership| (ownership.partition_id.clone(), ownership))
+                .collect::<HashMap<_, _>>(),
+            Err(error) => {
+                emit!(AzureEventHubsCheckpointStoreError { error });
+                return;
+            }
+        };
+
+        let mut lost = Vec::new();
+        for (partition_id, consumer) in consumers.iter_mut() {
+            let current = match ownerships.get(partition_id) {
+                Some(current) if current.owner_id == self.owner_id => current.clone(),
+                _ => {
+                    lost.push(partition_id.clone());
+                    continue;
+                }
+            };
+            match store.claim(&current, &self.owner_id).await {
+                Ok(Some(ownership)) => {
+                    ownerships.insert(partition_id.clone(), ownership.clone());
+                    consumer.ownership = Some(ownership);
+                }
+                Ok(None) => lost.push(partition_id.clone()),
+                // The ownership is renewed on the next round, unless it expires by then.
+                Err(error) => emit!(AzureEventHubsCheckpointStoreError { error }),
+            }
+        }
+        for partition_id in lost {
+            if let Some(consumer) = consumers.remove(&partition_id) {
+                debug!(message = "Partition claimed by another consumer.", %partition_id);
+                let _ = consumer.stop.send(());
+                let _ = consumer.handle.await;
+            }
+        }
+
+        // Partitions still owned by this source but no longer read, after an error, are claimed
+        // again like unowned ones.
+        for (partition_id, ownership) in ownerships.iter_mut() {
+            if ownership.owner_id == self.owner_id && !consumers.contains_key(partition_id) {
+                ownership.owner_id.clear();
+            }
+        }
+
+        let expired_before = Utc::now()
+            - chrono::Duration::seconds(self.config.ownership_expiration_secs.get() as i64);
+        let partition_id = match partition_to_claim(
+            &self.partition_ids,
+            &ownerships,
+            &self.owner_id,
+            expired_before,
+        ) {
+            Some(partition_id) => partition_id.to_owned(),
+            None => return,
+        };
+        let current = ownerships
+            .remove(&partition_id)
+            .unwrap_or_else(|| Ownership::unclaimed(partition_id.clone()));
+        let ownership = match store.claim(&current, &self.owner_id).await {
+            Ok(Some(ownership)) => ownership,
+            Ok(None) => return,
+            Err(error) => {
+                emit!(AzureEventHubsCheckpointStoreError { error });
+                return;
+            }
+        };
+        let start = match store.checkpoint(&partition_id).await {
+            Ok(start) => start,
+            Err(error) => {
+                // The partition is claimed again on the next round.
+                emit!(AzureEventHubsCheckpointStoreError { error });
+                return;
+            }
+        };
+
+        debug!(message = "Claimed partition.", %partition_id);
+        let consumer = self.start_consumer(&partition_id, start, Some(ownership), out, shutdown);
+        consumers.insert(partition_id, consumer);
+    }
+
+    fn start_consumer(
+        &self,
+        partition_id: &str,
+        start: Option<Checkpoint>,
+        ownership: Option<Ownership>,
+        out: &SourceSender,
+        shutdown: &ShutdownSignal,
+    ) -> RunningConsumer {
+        let (stop, stop_rx) = oneshot::channel();
+        let consumer = PartitionConsumer {
+            event_hub: self.event_hub.clone(),
+            partition_id: partition_id.to_owned(),
+            config: Arc::clone(&self.config),
+            decoder: self.decoder.clone(),
+            store: self.store.clone(),
+            start,
+            acknowledgements: self.acknowledgements,
+            log_namespace: self.log_namespace,
+        };
+        let handle = tokio::spawn(
+            consumer
+                .run(out.clone(), shutdown.clone(), stop_rx)
+                .in_current_span(),
+        );
+        RunningConsumer {
+            stop,
+            handle,
+            ownership,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSourceConfig>();
+    }
+}
@@ -5,6 +5,8 @@ mod tests;
 
 pub mod logs;
 pub mod metrics;
+mod profiles;
+mod sampling;
 pub mod traces;
 
 #[allow(warnings, clippy::pedantic, clippy::nursery)]
@@ -37,8 +39,9 @@ use vector_core::config::{LegacyKey, LogNamespace};
 use vector_core::event::{BatchNotifier, BatchStatus};
 use warp::{filters::BoxedFilter, reject::Rejection, reply::Response, Filter, Reply};
 
+use self::sampling::TraceSamplingConfig;
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{
         log_schema, DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig,
         SourceConfig, SourceContext,
@@ -88,6 +91,15 @@ pub struct DatadogAgentConfig {
     #[serde(default = "crate::serde::default_false")]
     disable_traces: bool,
 
+    /// If this is set to `true`, profiles are not accepted by the component.
+    ///
+    /// Profiles are passed through as trace events holding the payload the Datadog Agent sent, to
+    /// be forwarded as is to Datadog by the `datadog_traces` sink. They're sent to the `traces`
+    /// output when `multiple_outputs` is set, but aren't sampled by `trace_sampling`.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default = "crate::serde::default_false")]
+    disable_profiles: bool,
+
     /// If this is set to `true` logs, metrics, and traces are sent to different outputs.
     ///
     ///
@@ -98,6 +110,9 @@ pub struct DatadogAgentConfig {
     #[serde(default = "crate::serde::default_false")]
     multiple_outputs: bool,
 
+    #[configurable(derived)]
+    trace_sampling: Option<TraceSamplingConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -114,6 +129,10 @@ pub struct DatadogAgentConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -127,11 +146,14 @@ impl GenerateConfig for DatadogAgentConfig {
             store_api_key: true,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             acknowledgements: SourceAcknowledgementsConfig::default(),
             disable_logs: false,
             disable_metrics: false,
             disable_traces: false,
+            disable_profiles: false,
             multiple_outputs: false,
+            trace_sampling: None,
             log_namespace: Some(false),
         })
         .unwrap()
@@ -158,7 +180,9 @@ impl SourceConfig for DatadogAgentConfig {
             .clone();
 
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
 
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
         let source = DatadogAgentSource::new(
@@ -202,9 +226,10 @@ impl SourceConfig for DatadogAgentConfig {
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
         let definition = self
             .decoding
-            .schema_definition(global_log_namespace.merge(self.log_namespace))
+            .schema_definition(log_namespace)
             .with_source_metadata(
                 Self::NAME,
                 Some(LegacyKey::InsertIfEmpty(owned_value_path!("status"))),
@@ -249,7 +274,7 @@ impl SourceConfig for DatadogAgentConfig {
             )
             .with_standard_vector_source_metadata();
 
-        if self.multiple_outputs {
+        let mut outputs = if self.multiple_outputs {
             vec![
                 Output::default(DataType::Metric).with_port(METRICS),
                 Output::default(DataType::Log)
@@ -259,7 +284,9 @@ impl SourceConfig for DatadogAgentConfig {
             ]
         } else {
             vec![Output::default(DataType::all()).with_schema_definition(definition)]
-        }
+        };
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -368,17 +395,36 @@ impl DatadogAgentSource {
         });
 
         if !config.disable_traces {
+            let sampler = config
+                .trace_sampling
+                .as_ref()
+                .map(TraceSamplingConfig::build)
+                .transpose()?
+                .map(Arc::new);
             let trace_filter = traces::build_warp_filter(
                 acknowledgements,
                 config.multiple_outputs,
                 out.clone(),
                 self.clone(),
+                sampler,
             );
             filters = filters
                 .map(|f| f.or(trace_filter.clone()).unify().boxed())
                 .or(Some(trace_filter));
         }
 
+        if !config.disable_profiles {
+            let profile_filter = profiles::build_warp_filter(
+                acknowledgements,
+                config.multiple_outputs,
+                out.clone(),
+                self.clone(),
+            );
+            filters = filters
+                .map(|f| f.or(profile_filter.clone()).unify().boxed())
+                .or(Some(profile_filter));
+        }
+
         if !config.disable_metrics {
             let metrics_filter = metrics::build_warp_filter(
                 acknowledgements,
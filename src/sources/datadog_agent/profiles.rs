@@ -0,0 +1,109 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use bytes::Bytes;
+use chrono::Utc;
+use http::{HeaderMap, StatusCode};
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_core::EstimatedJsonEncodedSizeOf;
+use warp::{filters::BoxedFilter, path, path::FullPath, reply::Response, Filter};
+
+use crate::{
+    common::datadog::{PROFILE_FORWARDED_HEADERS, PROFILE_HEADERS_KEY, PROFILE_KEY},
+    event::{Event, TraceEvent, Value},
+    internal_events::HttpBytesReceived,
+    sources::{
+        datadog_agent::{handle_request, ApiKeyQueryParams, DatadogAgentSource},
+        util::ErrorMessage,
+    },
+    SourceSender,
+};
+
+/// Accepts the profiles of the Datadog Agent, which are passed through as trace events holding
+/// the payload of their request, without being decoded, for the `datadog_traces` sink to forward
+/// them to the profiling intake.
+pub(crate) fn build_warp_filter(
+    acknowledgements: bool,
+    multiple_outputs: bool,
+    out: SourceSender,
+    source: DatadogAgentSource,
+) -> BoxedFilter<(Response,)> {
+    warp::post()
+        .and(path!("api" / "v2" / "profile" / ..))
+        .and(warp::path::full())
+        .and(warp::header::headers_cloned())
+        .and(warp::header::optional::<String>("dd-api-key"))
+        .and(warp::query::<ApiKeyQueryParams>())
+        .and(warp::body::bytes())
+        .and_then(
+            move |path: FullPath,
+                  headers: HeaderMap,
+                  api_token: Option<String>,
+                  query_params: ApiKeyQueryParams,
+                  body: Bytes| {
+                emit!(HttpBytesReceived {
+                    byte_size: body.len(),
+                    http_path: path.as_str(),
+                    protocol: source.protocol,
+                });
+                let api_key = source.api_key_extractor.extract(
+                    path.as_str(),
+                    api_token,
+                    query_params.dd_api_key,
+                );
+                let events = handle_profile(body, &headers, api_key, &source);
+                let output = multiple_outputs.then_some(super::TRACES);
+                handle_request(events, acknowledgements, out.clone(), output)
+            },
+        )
+        .boxed()
+}
+
+fn handle_profile(
+    body: Bytes,
+    headers: &HeaderMap,
+    api_key: Option<Arc<str>>,
+    source: &DatadogAgentSource,
+) -> Result<Vec<Event>, ErrorMessage> {
+    if body.is_empty() {
+        return Err(ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            "Empty profile payload".into(),
+        ));
+    }
+
+    // The payload is multipart, so it can only be forwarded along with the boundary given by its
+    // content type.
+    let forwarded = PROFILE_FORWARDED_HEADERS
+        .iter()
+        .filter_map(|name| {
+            let value = headers.get(*name)?.to_str().ok()?;
+            Some(((*name).to_owned(), Value::from(value.to_owned())))
+        })
+        .collect::<BTreeMap<_, _>>();
+    if !forwarded.contains_key("Content-Type") {
+        return Err(ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            "Missing profile content type".into(),
+        ));
+    }
+
+    let mut trace_event = TraceEvent::default();
+    if let Some(k) = &api_key {
+        trace_event
+            .metadata_mut()
+            .set_datadog_api_key(Arc::clone(k));
+    }
+    trace_event.insert(PROFILE_KEY, body);
+    trace_event.insert(PROFILE_HEADERS_KEY, Value::Object(forwarded));
+    trace_event.insert(
+        source.log_schema_source_type_key,
+        Bytes::from("datadog_agent"),
+    );
+    trace_event.insert("timestamp", Utc::now());
+
+    source.events_received.emit(CountByteSize(
+        1,
+        trace_event.estimated_json_encoded_size_of(),
+    ));
+    Ok(vec![Event::Trace(trace_event)])
+}
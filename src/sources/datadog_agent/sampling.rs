@@ -0,0 +1,400 @@
+//! Head-based sampling of the traces received from the Datadog Agent.
+//!
+//! Sampling decisions are derived from the trace ID the same way the Datadog Agent derives them, so
+//! that all the chunks of a trace, even when received by different Vector instances, are either
+//! kept or dropped together.
+use ordered_float::NotNan;
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    event::{Event, TraceEvent, Value},
+    internal_events::DatadogAgentTracesSampledOut,
+};
+
+/// The multiplier used by the Datadog Agent to spread trace IDs before comparing them to the rate.
+const KNUTH_FACTOR: u64 = 1111111111111111111;
+
+/// The sampling priority of traces the user explicitly decided to keep.
+const PRIORITY_USER_KEEP: i64 = 2;
+
+/// The metric of the root span holding the rate the trace was sampled at, the `datadog_traces`
+/// sink weights the trace by its inverse when computing APM stats.
+const SAMPLE_RATE_METRIC: &str = "_sample_rate";
+
+#[derive(Debug, Snafu)]
+enum SamplingError {
+    #[snafu(display("Sampling rate {} is not between 0 and 1", rate))]
+    InvalidRate { rate: f64 },
+}
+
+/// Head-based sampling of the received traces.
+///
+/// Traces are sampled before being sent downstream, so that Vector can act as a sampling tier in
+/// front of the `datadog_traces` sink. Traces the user explicitly decided to keep, with a sampling
+/// priority of `2`, are always kept.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TraceSamplingConfig {
+    /// The rate at which traces matching none of the rules are kept, between `0` and `1`.
+    #[serde(default = "default_rate")]
+    default_rate: f64,
+
+    /// The sampling rules.
+    ///
+    /// The rate of a trace is the one of the first rule the trace matches.
+    #[serde(default)]
+    rules: Vec<TraceSamplingRule>,
+}
+
+/// A rule setting the sampling rate of the traces it matches.
+///
+/// A trace matches the rule when it matches all of the `service`, `env`, and `condition` options
+/// that are set.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TraceSamplingRule {
+    /// The service of the root span of the matching traces.
+    #[configurable(metadata(docs::examples = "checkout"))]
+    service: Option<String>,
+
+    /// The environment of the matching traces.
+    #[configurable(metadata(docs::examples = "production"))]
+    env: Option<String>,
+
+    /// A condition the matching traces meet.
+    ///
+    /// The spans of the trace are available in its `spans` field.
+    condition: Option<AnyCondition>,
+
+    /// The rate at which the matching traces are kept, between `0` and `1`.
+    #[configurable(metadata(docs::examples = 0.1))]
+    rate: f64,
+}
+
+const fn default_rate() -> f64 {
+    1.0
+}
+
+struct Rule {
+    service: Option<String>,
+    env: Option<String>,
+    condition: Option<Condition>,
+    rate: f64,
+}
+
+/// Samples traces according to a [`TraceSamplingConfig`].
+pub(crate) struct TraceSampler {
+    default_rate: f64,
+    rules: Vec<Rule>,
+}
+
+fn validate_rate(rate: f64) -> crate::Result<f64> {
+    if (0.0..=1.0).contains(&rate) {
+        Ok(rate)
+    } else {
+        Err(SamplingError::InvalidRate { rate }.into())
+    }
+}
+
+impl TraceSamplingConfig {
+    pub(crate) fn build(&self) -> crate::Result<TraceSampler> {
+        let rules = self
+            .rules
+            .iter()
+            .map(|rule| {
+                Ok(Rule {
+                    service: rule.service.clone(),
+                    env: rule.env.clone(),
+                    // Sources have no access to enrichment tables.
+                    condition: rule
+                        .condition
+                        .as_ref()
+                        .map(|condition| condition.build(&Default::default()))
+                        .transpose()?,
+                    rate: validate_rate(rule.rate)?,
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(TraceSampler {
+            default_rate: validate_rate(self.default_rate)?,
+            rules,
+        })
+    }
+}
+
+impl TraceSampler {
+    /// Keeps the sampled traces, recording the rate they were sampled at in their root span.
+    pub(crate) fn sample(&self, events: Vec<Event>) -> Vec<Event> {
+        let count = events.len();
+        let kept = events
+            .into_iter()
+            .filter_map(|event| self.sample_event(event))
+            .collect::<Vec<_>>();
+
+        if kept.len() < count {
+            emit!(DatadogAgentTracesSampledOut {
+                count: count - kept.len()
+            });
+        }
+        kept
+    }
+
+    fn sample_event(&self, event: Event) -> Option<Event> {
+        let (rate, mut event) = self.rate(event);
+        let trace = event.as_mut_trace();
+
+        let user_keep = matches!(
+            trace.get("priority"),
+            Some(Value::Integer(priority)) if *priority >= PRIORITY_USER_KEEP
+        );
+        // Chunks the Agent already dropped are only forwarded for APM stats, which account for
+        // their own weight.
+        let dropped = matches!(trace.get("dropped"), Some(Value::Boolean(true)));
+        if user_keep || dropped || rate >= 1.0 {
+            return Some(event);
+        }
+
+        match trace_id(trace) {
+            Some(trace_id) if sampled(trace_id, rate) => {
+                record_rate(trace, rate);
+                Some(event)
+            }
+            // Traces without an ID can't be sampled consistently, they are kept.
+            None => Some(event),
+            Some(_) => None,
+        }
+    }
+
+    fn rate(&self, mut event: Event) -> (f64, Event) {
+        let trace = event.as_trace();
+        let service = root_span(trace).and_then(|span| span_string(span, "service"));
+        let env = match trace.get("env") {
+            Some(Value::Bytes(env)) => Some(String::from_utf8_lossy(env).into_owned()),
+            _ => None,
+        };
+
+        for rule in &self.rules {
+            if (rule.service.is_some() && rule.service != service)
+                || (rule.env.is_some() && rule.env != env)
+            {
+                continue;
+            }
+            if let Some(condition) = &rule.condition {
+                let (matched, checked) = condition.check(event);
+                event = checked;
+                if !matched {
+                    continue;
+                }
+            }
+            return (rule.rate, event);
+        }
+        (self.default_rate, event)
+    }
+}
+
+/// Whether the Datadog Agent would keep the trace when sampling it at the given rate.
+fn sampled(trace_id: u64, rate: f64) -> bool {
+    rate >= 1.0 || trace_id.wrapping_mul(KNUTH_FACTOR) < (rate * u64::MAX as f64) as u64
+}
+
+fn trace_id(trace: &TraceEvent) -> Option<u64> {
+    // Trace IDs are stored as `i64`, even though they are `u64` in the payloads.
+    match trace.get("trace_id") {
+        Some(Value::Integer(trace_id)) => Some(*trace_id as u64),
+        _ => match spans(trace)?.first()? {
+            Value::Object(span) => match span.get("trace_id") {
+                Some(Value::Integer(trace_id)) => Some(*trace_id as u64),
+                _ => None,
+            },
+            _ => None,
+        },
+    }
+}
+
+fn spans(trace: &TraceEvent) -> Option<&Vec<Value>> {
+    match trace.get("spans") {
+        Some(Value::Array(spans)) => Some(spans),
+        _ => None,
+    }
+}
+
+fn is_root(span: &Value) -> bool {
+    matches!(span, Value::Object(span) if matches!(span.get("parent_id"), Some(Value::Integer(0))))
+}
+
+/// The span without a parent, or the first span of chunks missing the root span.
+fn root_span(trace: &TraceEvent) -> Option<&Value> {
+    let spans = spans(trace)?;
+    spans.iter().find(|span| is_root(span)).or(spans.first())
+}
+
+fn span_string(span: &Value, field: &str) -> Option<String> {
+    match span {
+        Value::Object(span) => match span.get(field) {
+            Some(Value::Bytes(value)) => Some(String::from_utf8_lossy(value).into_owned()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Combines the sampling rate with the one the trace was already sampled at, by the tracer.
+fn record_rate(trace: &mut TraceEvent, rate: f64) {
+    let spans = match trace.get_mut("spans") {
+        Some(Value::Array(spans)) => spans,
+        _ => return,
+    };
+    let index = match spans.iter().position(is_root) {
+        Some(index) => index,
+        None if !spans.is_empty() => 0,
+        None => return,
+    };
+    if let Value::Object(span) = &mut spans[index] {
+        let metrics = span
+            .entry("metrics".to_owned())
+            .or_insert_with(|| Value::Object(Default::default()));
+        if let Value::Object(metrics) = metrics {
+            let previous = match metrics.get(SAMPLE_RATE_METRIC) {
+                Some(Value::Float(previous)) => previous.into_inner(),
+                _ => 1.0,
+            };
+            if let Ok(combined) = NotNan::new(previous * rate) {
+                metrics.insert(SAMPLE_RATE_METRIC.to_owned(), Value::Float(combined));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn trace(trace_id: i64, service: &str, env: &str, priority: i64) -> Event {
+        let mut span = BTreeMap::new();
+        span.insert("service".to_owned(), Value::from(service));
+        span.insert("trace_id".to_owned(), Value::from(trace_id));
+        span.insert("parent_id".to_owned(), Value::from(0));
+        span.insert(
+            "metrics".to_owned(),
+            Value::Object(BTreeMap::from([(
+                SAMPLE_RATE_METRIC.to_owned(),
+                Value::from(NotNan::new(0.5).unwrap()),
+            )])),
+        );
+
+        let mut trace = TraceEvent::default();
+        trace.insert("env", env);
+        trace.insert("priority", priority);
+        trace.insert("spans", vec![Value::from(span)]);
+        Event::Trace(trace)
+    }
+
+    fn sampler(toml: &str) -> TraceSampler {
+        toml::from_str::<TraceSamplingConfig>(toml)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn root_sample_rate(event: &Event) -> Option<f64> {
+        match root_span(event.as_trace())? {
+            Value::Object(span) => match span.get("metrics")? {
+                Value::Object(metrics) => match metrics.get(SAMPLE_RATE_METRIC)? {
+                    Value::Float(rate) => Some(rate.into_inner()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn matches_agent_decisions() {
+        // `1 * KNUTH_FACTOR` is below half of the ID space, `9 * KNUTH_FACTOR` is above it.
+        assert!(sampled(1, 0.5));
+        assert!(!sampled(9, 0.5));
+        assert!(sampled(9, 1.0));
+        assert!(!sampled(1, 0.0));
+    }
+
+    #[test]
+    fn applies_first_matching_rule() {
+        let sampler = sampler(
+            r#"
+            default_rate = 1.0
+
+            [[rules]]
+            service = "checkout"
+            env = "production"
+            rate = 0.0
+
+            [[rules]]
+            service = "checkout"
+            rate = 0.5
+            "#,
+        );
+
+        let kept = sampler.sample(vec![
+            trace(1, "checkout", "production", 1),
+            trace(1, "checkout", "staging", 1),
+            trace(9, "checkout", "staging", 1),
+            trace(9, "cart", "production", 1),
+        ]);
+
+        assert_eq!(kept.len(), 2);
+        assert_eq!(root_sample_rate(&kept[0]), Some(0.25));
+        assert_eq!(root_sample_rate(&kept[1]), Some(0.5));
+    }
+
+    #[test]
+    fn keeps_user_decisions() {
+        let sampler = sampler("default_rate = 0.0");
+
+        let kept = sampler.sample(vec![
+            trace(1, "checkout", "production", 1),
+            trace(1, "checkout", "production", PRIORITY_USER_KEEP),
+        ]);
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(root_sample_rate(&kept[0]), Some(0.5));
+    }
+
+    #[test]
+    fn applies_conditions() {
+        let sampler = sampler(
+            r#"
+            [[rules]]
+            condition = 'length!(.spans) > 1'
+            rate = 0.0
+            "#,
+        );
+
+        let mut large = trace(1, "checkout", "production", 1);
+        let spans = match large.as_trace().get("spans") {
+            Some(Value::Array(spans)) => spans.clone(),
+            _ => unreachable!(),
+        };
+        large
+            .as_mut_trace()
+            .insert("spans", [spans.clone(), spans].concat());
+
+        let kept = sampler.sample(vec![large, trace(1, "checkout", "production", 1)]);
+        assert_eq!(kept.len(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_rates() {
+        assert!(toml::from_str::<TraceSamplingConfig>("default_rate = 1.5")
+            .unwrap()
+            .build()
+            .is_err());
+    }
+}
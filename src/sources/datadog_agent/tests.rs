@@ -29,7 +29,9 @@ use vrl::prelude::Collection;
 
 use crate::schema::Definition;
 use crate::{
-    common::datadog::{DatadogMetricType, DatadogPoint, DatadogSeriesMetric},
+    common::datadog::{
+        DatadogMetricType, DatadogPoint, DatadogSeriesMetric, PROFILE_HEADERS_KEY, PROFILE_KEY,
+    },
     config::{log_schema, SourceConfig, SourceContext},
     event::{
         into_event_stream,
@@ -1040,6 +1042,52 @@ async fn decode_sketches() {
     .await;
 }
 
+#[tokio::test]
+async fn passes_profiles_through() {
+    assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+        let (rx, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "dd-api-key",
+            "12345678abcdefgh12345678abcdefgh".parse().unwrap(),
+        );
+        headers.insert(
+            "Content-Type",
+            "multipart/form-data; boundary=profile".parse().unwrap(),
+        );
+        headers.insert("DD-EVP-ORIGIN", "agent".parse().unwrap());
+
+        let body = "--profile\r\nContent-Disposition: form-data; name=\"event\"\r\n\r\n{}\r\n--profile--\r\n";
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    200,
+                    send_with_path(addr, body, headers, "/api/v2/profile").await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let profile = events[0].as_trace();
+        assert_eq!(profile.as_map()[PROFILE_KEY], Value::from(body));
+        let forwarded = profile.as_map()[PROFILE_HEADERS_KEY].as_object().unwrap();
+        assert_eq!(
+            forwarded["Content-Type"],
+            "multipart/form-data; boundary=profile".into()
+        );
+        assert_eq!(forwarded["DD-EVP-ORIGIN"], "agent".into());
+        assert!(!forwarded.contains_key("Content-Encoding"));
+        assert_eq!(
+            &events[0].metadata().datadog_api_key().as_ref().unwrap()[..],
+            "12345678abcdefgh12345678abcdefgh"
+        );
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn decode_traces() {
     assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
@@ -1598,7 +1646,9 @@ fn test_config_outputs() {
         (
             "json / single output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: DeserializerConfig::Json {
+                    json: Default::default(),
+                },
                 multiple_outputs: false,
                 want: HashMap::from([(
                     None,
@@ -1623,7 +1673,9 @@ fn test_config_outputs() {
         (
             "json / multiple output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: DeserializerConfig::Json {
+                    json: Default::default(),
+                },
                 multiple_outputs: true,
                 want: HashMap::from([
                     (
@@ -1823,11 +1875,14 @@ fn test_config_outputs() {
             store_api_key: true,
             framing: default_framing_message_based(),
             decoding,
+            on_decode_error: Default::default(),
             acknowledgements: Default::default(),
             multiple_outputs,
             disable_logs: false,
             disable_metrics: false,
             disable_traces: false,
+            disable_profiles: false,
+            trace_sampling: None,
             log_namespace: Some(false),
         };
 
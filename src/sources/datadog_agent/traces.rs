@@ -13,7 +13,10 @@ use warp::{filters::BoxedFilter, path, path::FullPath, reply::Response, Filter,
 use crate::{
     event::{Event, TraceEvent, Value},
     sources::{
-        datadog_agent::{ddtrace_proto, handle_request, ApiKeyQueryParams, DatadogAgentSource},
+        datadog_agent::{
+            ddtrace_proto, handle_request, sampling::TraceSampler, ApiKeyQueryParams,
+            DatadogAgentSource,
+        },
         util::ErrorMessage,
     },
     SourceSender,
@@ -24,8 +27,9 @@ pub(crate) fn build_warp_filter(
     multiple_outputs: bool,
     out: SourceSender,
     source: DatadogAgentSource,
+    sampler: Option<Arc<TraceSampler>>,
 ) -> BoxedFilter<(Response,)> {
-    build_trace_filter(acknowledgements, multiple_outputs, out, source)
+    build_trace_filter(acknowledgements, multiple_outputs, out, source, sampler)
         .or(build_stats_filter())
         .unify()
         .boxed()
@@ -36,6 +40,7 @@ fn build_trace_filter(
     multiple_outputs: bool,
     out: SourceSender,
     source: DatadogAgentSource,
+    sampler: Option<Arc<TraceSampler>>,
 ) -> BoxedFilter<(Response,)> {
     warp::post()
         .and(path!("api" / "v0.2" / "traces" / ..))
@@ -73,6 +78,10 @@ fn build_trace_filter(
                                 format!("Error decoding Datadog traces: {:?}", error),
                             )
                         })
+                    })
+                    .map(|events| match &sampler {
+                        Some(sampler) => sampler.sample(events),
+                        None => events,
                     });
                 let output = multiple_outputs.then_some(super::TRACES);
                 handle_request(events, acknowledgements, out.clone(), output)
@@ -18,7 +18,7 @@ use vector_config::configurable_component;
 use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{Output, SourceConfig, SourceContext},
     internal_events::{DemoLogsEventProcessed, EventsReceived, StreamClosedError},
     serde::{default_decoding, default_framing_message_based},
@@ -66,6 +66,10 @@ pub struct DemoLogsConfig {
     #[serde(default = "default_decoding")]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -198,6 +202,7 @@ impl DemoLogsConfig {
             },
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             log_namespace,
         }
     }
@@ -276,7 +281,9 @@ impl SourceConfig for DemoLogsConfig {
 
         self.format.validate()?;
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
         Ok(Box::pin(demo_logs_source(
             self.interval,
             self.count,
@@ -298,7 +305,11 @@ impl SourceConfig for DemoLogsConfig {
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata();
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -335,7 +346,8 @@ mod tests {
                 default_decoding(),
                 LogNamespace::Legacy,
             )
-            .build();
+            .build()
+            .unwrap();
             demo_logs_source(
                 config.interval,
                 config.count,
@@ -0,0 +1,192 @@
+//! Monitoring of the heartbeats of streaming children.
+//!
+//! A child is given a control channel as file descriptor 3, whose number is also set in the
+//! `VECTOR_HEARTBEAT_FD` environment variable, and writes a line to it every so often to tell that
+//! it is still alive, even when it has no output to write. A line starting with `unhealthy` reports
+//! the child as unhealthy instead, followed by the reason. A child whose heartbeats stop for longer
+//! than the timeout is considered hung, whereas a child that keeps sending heartbeats without
+//! writing any output is simply quiet.
+
+use std::io;
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader, Lines},
+    time::{self, Duration, Instant},
+};
+
+/// The file descriptor of the control channel in the child.
+#[cfg(unix)]
+pub const HEARTBEAT_FD: i32 = 3;
+
+/// The environment variable holding the file descriptor of the control channel.
+#[cfg(unix)]
+pub const HEARTBEAT_FD_ENV: &str = "VECTOR_HEARTBEAT_FD";
+
+const UNHEALTHY: &str = "unhealthy";
+
+/// Why a child is to be restarted.
+#[derive(Debug, Eq, PartialEq)]
+pub enum HeartbeatFailure {
+    /// No heartbeat was received for the given amount of time.
+    Missed(Duration),
+
+    /// The child reported itself as unhealthy, with the given reason.
+    Unhealthy(String),
+}
+
+/// Reads the heartbeats of a child from its control channel.
+pub struct Heartbeats {
+    lines: Lines<BufReader<Box<dyn AsyncRead + Send + Unpin>>>,
+    timeout: Duration,
+    last_heartbeat: Instant,
+    closed: bool,
+    #[cfg(unix)]
+    child_end: Option<std::os::unix::net::UnixStream>,
+}
+
+impl Heartbeats {
+    pub fn new(reader: impl AsyncRead + Send + Unpin + 'static, timeout: Duration) -> Self {
+        let reader: Box<dyn AsyncRead + Send + Unpin> = Box::new(reader);
+        Self {
+            lines: BufReader::new(reader).lines(),
+            timeout,
+            last_heartbeat: Instant::now(),
+            closed: false,
+            #[cfg(unix)]
+            child_end: None,
+        }
+    }
+
+    /// Creates the control channel of a child, and sets it up to be passed to the child.
+    ///
+    /// The end of the channel kept for the child must be released with [`Heartbeats::spawned`]
+    /// once the child is spawned.
+    #[cfg(unix)]
+    pub fn attach(command: &mut tokio::process::Command, timeout: Duration) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let (ours, theirs) = std::os::unix::net::UnixStream::pair()?;
+        // Duplicating the descriptor onto itself would keep it closed on exec.
+        let theirs = if theirs.as_raw_fd() == HEARTBEAT_FD {
+            theirs.try_clone()?
+        } else {
+            theirs
+        };
+        ours.set_nonblocking(true)?;
+        let ours = tokio::net::UnixStream::from_std(ours)?;
+
+        let fd = theirs.as_raw_fd();
+        command.env(HEARTBEAT_FD_ENV, HEARTBEAT_FD.to_string());
+        // SAFETY: `dup2` is async-signal-safe, and the closure doesn't allocate.
+        unsafe {
+            command.pre_exec(move || {
+                nix::unistd::dup2(fd, HEARTBEAT_FD)
+                    .map(drop)
+                    .map_err(io::Error::from)
+            });
+        }
+
+        let mut heartbeats = Self::new(ours, timeout);
+        heartbeats.child_end = Some(theirs);
+        Ok(heartbeats)
+    }
+
+    #[cfg(windows)]
+    pub fn attach(_command: &mut tokio::process::Command, _timeout: Duration) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Heartbeats are only supported on Unix.",
+        ))
+    }
+
+    /// Releases the end of the channel kept for the child, so that the channel is closed once the
+    /// child exits.
+    pub fn spawned(&mut self) {
+        #[cfg(unix)]
+        drop(self.child_end.take());
+        self.last_heartbeat = Instant::now();
+    }
+
+    /// Waits until the child misses its heartbeats, or reports itself as unhealthy.
+    ///
+    /// This is cancel safe, so that it can be polled in `select!` along with the output of the
+    /// child.
+    pub async fn failure(&mut self) -> HeartbeatFailure {
+        loop {
+            let deadline = self.last_heartbeat + self.timeout;
+            if self.closed {
+                time::sleep_until(deadline).await;
+                return HeartbeatFailure::Missed(self.last_heartbeat.elapsed());
+            }
+
+            match time::timeout_at(deadline, self.lines.next_line()).await {
+                Ok(Ok(Some(line))) => {
+                    self.last_heartbeat = Instant::now();
+                    let line = line.trim();
+                    if let Some(reason) = line.strip_prefix(UNHEALTHY) {
+                        return HeartbeatFailure::Unhealthy(
+                            reason.trim_start_matches(':').trim().to_owned(),
+                        );
+                    }
+                }
+                // Once the channel is closed, no more heartbeats can be received.
+                Ok(Ok(None) | Err(_)) => self.closed = true,
+                Err(_) => return HeartbeatFailure::Missed(self.last_heartbeat.elapsed()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn heartbeats_keep_quiet_children_alive() {
+        let (mut child, reader) = tokio::io::duplex(64);
+        let mut heartbeats = Heartbeats::new(reader, Duration::from_secs(10));
+
+        for _ in 0..3 {
+            tokio::select! {
+                failure = heartbeats.failure() => panic!("unexpected failure: {:?}", failure),
+                _ = time::sleep(Duration::from_secs(8)) => child.write_all(b"ok\n").await.unwrap(),
+            }
+        }
+
+        let failure = heartbeats.failure().await;
+        assert!(
+            matches!(failure, HeartbeatFailure::Missed(missed) if missed >= Duration::from_secs(10))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reports_unhealthy_children() {
+        let (mut child, reader) = tokio::io::duplex(64);
+        let mut heartbeats = Heartbeats::new(reader, Duration::from_secs(10));
+
+        child
+            .write_all(b"ok\nunhealthy: lost the connection\n")
+            .await
+            .unwrap();
+        assert_eq!(
+            heartbeats.failure().await,
+            HeartbeatFailure::Unhealthy("lost the connection".to_owned())
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn closed_channels_miss_heartbeats() {
+        let (child, reader) = tokio::io::duplex(64);
+        let mut heartbeats = Heartbeats::new(reader, Duration::from_secs(10));
+        drop(child);
+
+        let start = Instant::now();
+        assert!(matches!(
+            heartbeats.failure().await,
+            HeartbeatFailure::Missed(_)
+        ));
+        assert!(start.elapsed() >= Duration::from_secs(10));
+    }
+}
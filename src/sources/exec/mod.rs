@@ -2,6 +2,10 @@ use std::{
     io::{Error, ErrorKind},
     path::PathBuf,
     process::ExitStatus,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use chrono::Utc;
@@ -10,37 +14,58 @@ use codecs::{
     StreamDecodingError,
 };
 use futures::StreamExt;
+use indexmap::IndexMap;
+use ordered_float::NotNan;
 use smallvec::SmallVec;
 use snafu::Snafu;
 use tokio::{
     io::{AsyncRead, BufReader},
     process::Command,
     sync::mpsc::{channel, Sender},
+    task::JoinHandle,
     time::{self, sleep, Duration, Instant},
 };
 use tokio_stream::wrappers::IntervalStream;
 use tokio_util::codec::FramedRead;
-use value::Kind;
+use tracing::Instrument;
+use value::{Kind, Value};
 use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
 use vector_config::configurable_component;
 use vector_core::{config::LegacyKey, EstimatedJsonEncodedSizeOf};
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
-    config::{Output, SourceConfig, SourceContext},
-    event::Event,
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
+    config::{Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
     internal_events::{
-        ExecChannelClosedError, ExecCommandExecuted, ExecEventsReceived, ExecFailedError,
-        ExecFailedToSignalChild, ExecFailedToSignalChildError, ExecTimeoutError, StreamClosedError,
+        ExecChannelClosedError, ExecCommandExecuted, ExecCommandRestarted, ExecCommandUnhealthy,
+        ExecDecodeStalled, ExecDeliveryFailed, ExecDeliveryRetriesExhausted, ExecEventsReceived,
+        ExecEventsSampledOut, ExecFailedError, ExecFailedToSignalChild,
+        ExecFailedToSignalChildError, ExecFramingDecoderMismatch, ExecHeartbeatMissed,
+        ExecRestartsExhausted, ExecSourceExpired, ExecTimeoutError, StreamClosedError,
     },
-    serde::default_decoding,
+    serde::{bool_or_struct, default_decoding},
     shutdown::ShutdownSignal,
     SourceSender,
 };
 use lookup::{owned_value_path, path};
 use vector_core::config::{log_schema, LogNamespace};
 
+mod heartbeat;
+mod orphans;
+mod respawn;
+mod sampling;
 pub mod sized_bytes_codec;
+mod templating;
+mod watchdog;
+
+use heartbeat::{HeartbeatFailure, Heartbeats};
+use orphans::ChildTracker;
+use respawn::RespawnPolicy;
+use sampling::Sampler;
+pub use sampling::SamplingMode;
+use templating::{TemplateValues, Window};
+use watchdog::{DecodeProgress, ProgressReader};
 
 /// Configuration for the `exec` source.
 #[configurable_component(source("exec"))]
@@ -57,9 +82,31 @@ pub struct ExecConfig {
     pub streaming: Option<StreamingConfig>,
 
     /// The command to be run, plus any arguments required.
+    ///
+    /// The command and its arguments can contain placeholders, which are replaced on each run:
+    /// `{{ hostname }}` with the host name, and in the scheduled mode, `{{ window_start }}` and
+    /// `{{ window_end }}` with the boundaries of the window of the run, in RFC 3339, or
+    /// `{{ window_start_unix }}` and `{{ window_end_unix }}` with the same boundaries, in seconds
+    /// since the epoch.
+    ///
+    /// The window of a run starts where the window of the last successful run ended, so that an
+    /// incremental poller can fetch the records since then, and ends when the run starts. The first
+    /// window starts `exec_interval_secs` before the first run.
     #[configurable(metadata(docs::examples = "echo", docs::examples = "Hello World!"))]
+    #[configurable(metadata(
+        docs::examples = "./fetch-audit-log",
+        docs::examples = "--since={{ window_start }}"
+    ))]
     pub command: Vec<String>,
 
+    /// Environment variables to set for the command, in addition to the environment of Vector.
+    ///
+    /// The values can contain the same placeholders as the command.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "An environment variable."))]
+    #[configurable(metadata(docs::examples = "example_environment()"))]
+    pub environment: IndexMap<String, String>,
+
     /// The directory in which to run the command.
     pub working_directory: Option<PathBuf>,
 
@@ -67,6 +114,10 @@ pub struct ExecConfig {
     #[serde(default = "default_include_stderr")]
     pub include_stderr: bool,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub stderr: Option<StderrConfig>,
+
     /// The maximum buffer size allowed before a log event is generated.
     #[serde(default = "default_maximum_buffer_size")]
     pub maximum_buffer_size_bytes: usize,
@@ -78,12 +129,81 @@ pub struct ExecConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
+    /// The rate at which decoded events are emitted, expressed as `1/N`.
+    ///
+    /// For example, `sampling_rate = 10` means 1 out of every 10 events is emitted and the rest
+    /// are dropped. Dropped events are counted by the `exec_events_sampled_out_total` metric. By
+    /// default, all events are emitted.
+    #[configurable(metadata(docs::examples = 10))]
+    #[serde(default)]
+    pub sampling_rate: Option<u64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub sampling_mode: SamplingMode,
+
+    /// The amount of time, in seconds, after which reading the output of a command is considered
+    /// stalled.
+    ///
+    /// Reading is stalled when output has been read from the command that the decoder hasn't
+    /// produced an event from, and no event has been decoded for this amount of time while the
+    /// command is still running, even if more output is read. A command that is simply not writing
+    /// any output is never considered stalled. When a stall is detected, the command is killed,
+    /// and restarted according to the configured mode. By default, stalls are not detected.
+    #[configurable(metadata(docs::examples = 60))]
+    #[serde(default)]
+    pub decode_stall_timeout_secs: Option<u64>,
+
+    /// Whether or not to emit an event when each run of the command completes.
+    ///
+    /// The event is emitted regardless of whether the run produced any output, and contains the
+    /// `exit_code` of the command (if known), the number of events emitted during the run as
+    /// `event_count`, and the `duration_seconds` of the run. This makes it possible to tell a
+    /// command that ran without producing output apart from a command that didn't run at all.
+    #[serde(default)]
+    pub always_emit_completion: bool,
+
+    /// Whether or not to terminate children orphaned by a previous run of Vector.
+    ///
+    /// When enabled, the PID and start time of every spawned child is recorded in the data
+    /// directory. On startup, any recorded child that is still running, has the same start time,
+    /// and has the same command line is sent a `SIGTERM` before new children are spawned.
+    ///
+    /// PIDs are reused by the operating system, so matching a previous child is a heuristic.
+    /// Start times are only available on Linux, and orphans are never terminated on other
+    /// platforms.
+    #[serde(default)]
+    pub reap_orphans: bool,
+
+    /// The directory used to persist the state of spawned children when `reap_orphans` is enabled.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     log_namespace: Option<bool>,
 }
 
+fn example_environment() -> IndexMap<String, String> {
+    IndexMap::from([
+        ("SINCE".to_owned(), "{{ window_start_unix }}".to_owned()),
+        ("UNTIL".to_owned(), "{{ window_end_unix }}".to_owned()),
+    ])
+}
+
 /// Mode of operation for running the command.
 #[configurable_component]
 #[derive(Clone, Copy, Debug)]
@@ -106,6 +226,15 @@ pub struct ScheduledConfig {
     /// If the command takes longer than `exec_interval_secs` to run, it is killed.
     #[serde(default = "default_exec_interval_secs")]
     exec_interval_secs: u64,
+
+    /// The maximum amount of time, in seconds, that the source keeps scheduling command runs.
+    ///
+    /// Once this amount of time has elapsed since the source started, no further runs are
+    /// scheduled and the source completes. A run that is in progress when the limit is reached is
+    /// allowed to finish. By default, runs are scheduled until Vector is shut down.
+    #[configurable(metadata(docs::examples = 3600))]
+    #[serde(default)]
+    max_total_runtime_secs: Option<u64>,
 }
 
 /// Configuration options for streaming commands.
@@ -118,8 +247,124 @@ pub struct StreamingConfig {
     respawn_on_exit: bool,
 
     /// The amount of time, in seconds, before rerunning a streaming command that exited.
-    #[serde(default = "default_respawn_interval_secs")]
+    ///
+    /// When `max_backoff_secs` is set, this is the initial delay, which doubles after each
+    /// consecutive respawn.
+    #[serde(default = "default_respawn_interval_secs", alias = "backoff_secs")]
     respawn_interval_secs: u64,
+
+    /// The maximum amount of time, in seconds, before rerunning a streaming command that exited.
+    ///
+    /// When set, the delay between respawns doubles after each consecutive respawn, starting from
+    /// `respawn_interval_secs`, until it reaches this value. A run that lasts at least this long is
+    /// considered healthy, and resets the delay. By default, the delay stays constant.
+    #[configurable(metadata(docs::examples = 300))]
+    #[serde(default)]
+    max_backoff_secs: Option<u64>,
+
+    /// The maximum number of consecutive respawns of a streaming command.
+    ///
+    /// Once this number of consecutive respawns is reached, and the command exits again, it is no
+    /// longer rerun and the source completes. The count is reset by a healthy run, as described
+    /// for `max_backoff_secs`. By default, the command is respawned indefinitely.
+    #[configurable(metadata(docs::examples = 10))]
+    #[serde(default)]
+    max_restarts: Option<u64>,
+
+    /// Whether or not to randomize the delay between respawns.
+    ///
+    /// When enabled, each delay is chosen randomly between half of the computed delay and the
+    /// full delay, so that many flapping commands don't all respawn at the same time.
+    #[serde(default)]
+    jitter: bool,
+
+    /// The maximum number of times a batch of events is resent when it isn't delivered.
+    ///
+    /// Only used when acknowledgements are enabled. Batches are resent once per second, and once a
+    /// batch has been resent this many times without being delivered, the `on_delivery_failure`
+    /// action is taken.
+    #[serde(default = "default_delivery_retries")]
+    delivery_retries: u32,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_delivery_failure: DeliveryFailureAction,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    heartbeat: Option<HeartbeatConfig>,
+}
+
+/// Configuration options for the heartbeats of streaming commands.
+///
+/// The command is given a control channel as file descriptor 3, whose number is also set in the
+/// `VECTOR_HEARTBEAT_FD` environment variable. The command must write a line, such as `ok`, to
+/// the channel at least once every `timeout_secs` to tell that it isn't hung, even when it has no
+/// output to write. A line starting with `unhealthy`, optionally followed by a reason, reports the
+/// command as unhealthy. The command is killed when it misses its heartbeats or reports itself as
+/// unhealthy, and restarted according to the respawn options.
+///
+/// Heartbeats are only supported on Unix.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HeartbeatConfig {
+    /// The amount of time, in seconds, after which a command that hasn't written a heartbeat is
+    /// considered hung.
+    #[configurable(metadata(docs::examples = 30))]
+    timeout_secs: u64,
+}
+
+/// The action taken when a batch of events from a streaming command persistently fails to be
+/// delivered.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryFailureAction {
+    /// Drop the batch and kill the command.
+    ///
+    /// The command is restarted according to the respawn options.
+    #[default]
+    Kill,
+
+    /// Pause the command, and keep resending the batch until it is delivered.
+    ///
+    /// The command is sent `SIGSTOP`, and then `SIGCONT` once the batch is delivered. On Windows,
+    /// the command isn't paused, but no more of its output is read until the batch is delivered.
+    Pause,
+}
+
+impl DeliveryFailureAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Kill => "kill",
+            Self::Pause => "pause",
+        }
+    }
+}
+
+/// Configuration options for decoding the output from stderr.
+///
+/// By default, the output from stderr is framed and decoded in the same way as the output from
+/// stdout. This is only used if `include_stderr` is enabled.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StderrConfig {
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+}
+
+impl StderrConfig {
+    fn framing(&self) -> FramingConfig {
+        self.framing
+            .clone()
+            .unwrap_or_else(|| self.decoding.default_stream_framing())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Snafu)]
@@ -128,6 +373,25 @@ pub enum ExecConfigError {
     CommandEmpty,
     #[snafu(display("The maximum buffer size must be greater than zero"))]
     ZeroBuffer,
+    #[snafu(display("The sampling rate must be greater than zero"))]
+    ZeroSamplingRate,
+    #[snafu(display("The heartbeat timeout must be greater than zero"))]
+    ZeroHeartbeatTimeout,
+    #[snafu(display("Heartbeats are only supported on Unix"))]
+    HeartbeatUnsupported,
+    #[snafu(display("Invalid placeholder in {:?}: {}", value, reason))]
+    InvalidTemplate { value: String, reason: String },
+    #[snafu(display(
+        "The `{}` framing cannot be used with the `{}` codec: {}",
+        framing,
+        codec,
+        reason
+    ))]
+    IncompatibleFraming {
+        framing: &'static str,
+        codec: &'static str,
+        reason: &'static str,
+    },
 }
 
 impl Default for ExecConfig {
@@ -136,14 +400,25 @@ impl Default for ExecConfig {
             mode: Mode::Scheduled,
             scheduled: Some(ScheduledConfig {
                 exec_interval_secs: default_exec_interval_secs(),
+                max_total_runtime_secs: None,
             }),
             streaming: None,
             command: vec!["echo".to_owned(), "Hello World!".to_owned()],
+            environment: IndexMap::new(),
             working_directory: None,
             include_stderr: default_include_stderr(),
+            stderr: None,
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
+            sampling_rate: None,
+            sampling_mode: SamplingMode::default(),
+            decode_stall_timeout_secs: None,
+            always_emit_completion: false,
+            reap_orphans: false,
+            data_dir: None,
+            acknowledgements: Default::default(),
             log_namespace: None,
         }
     }
@@ -170,6 +445,10 @@ const fn default_include_stderr() -> bool {
     true
 }
 
+const fn default_delivery_retries() -> u32 {
+    3
+}
+
 fn get_hostname() -> Option<String> {
     crate::get_hostname().ok()
 }
@@ -179,6 +458,13 @@ const STDERR: &str = "stderr";
 const STREAM_KEY: &str = "stream";
 const PID_KEY: &str = "pid";
 const COMMAND_KEY: &str = "command";
+const EXIT_CODE_KEY: &str = "exit_code";
+const EVENT_COUNT_KEY: &str = "event_count";
+const DURATION_SECONDS_KEY: &str = "duration_seconds";
+const WINDOW_START_KEY: &str = "window_start";
+const WINDOW_END_KEY: &str = "window_end";
+
+const DELIVERY_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
 impl_generate_config_from_default!(ExecConfig);
 
@@ -188,11 +474,67 @@ impl ExecConfig {
             Err(ExecConfigError::CommandEmpty)
         } else if self.maximum_buffer_size_bytes == 0 {
             Err(ExecConfigError::ZeroBuffer)
+        } else if self.sampling_rate == Some(0) {
+            Err(ExecConfigError::ZeroSamplingRate)
+        } else if self.heartbeat_timeout() == Some(Duration::ZERO) {
+            Err(ExecConfigError::ZeroHeartbeatTimeout)
+        } else if cfg!(windows) && self.heartbeat_timeout().is_some() {
+            Err(ExecConfigError::HeartbeatUnsupported)
         } else {
+            let scheduled = matches!(self.mode, Mode::Scheduled);
+            for value in self.command.iter().chain(self.environment.values()) {
+                templating::validate(value, scheduled).map_err(|reason| {
+                    ExecConfigError::InvalidTemplate {
+                        value: value.clone(),
+                        reason,
+                    }
+                })?;
+            }
+
+            let stderr = self
+                .stderr
+                .as_ref()
+                .map(|stderr| (stderr.framing(), &stderr.decoding));
+            for (framing, decoding) in
+                std::iter::once((self.framing(), &self.decoding)).chain(stderr)
+            {
+                if let Some(reason) = incompatible_framing_reason(&framing, decoding) {
+                    return Err(ExecConfigError::IncompatibleFraming {
+                        framing: framing_name(&framing),
+                        codec: codec_name(decoding),
+                        reason,
+                    });
+                }
+            }
             Ok(())
         }
     }
 
+    fn sampler(&self) -> Option<Sampler> {
+        self.sampling_rate
+            .map(|rate| Sampler::new(rate, self.sampling_mode))
+    }
+
+    fn decode_stall_timeout(&self) -> Option<Duration> {
+        self.decode_stall_timeout_secs.map(Duration::from_secs)
+    }
+
+    fn heartbeat_timeout(&self) -> Option<Duration> {
+        match (&self.mode, &self.streaming) {
+            (Mode::Streaming, Some(config)) => config
+                .heartbeat
+                .as_ref()
+                .map(|heartbeat| Duration::from_secs(heartbeat.timeout_secs)),
+            _ => None,
+        }
+    }
+
+    fn framing(&self) -> FramingConfig {
+        self.framing
+            .clone()
+            .unwrap_or_else(|| self.decoding.default_stream_framing())
+    }
+
     fn command_line(&self) -> String {
         self.command.join(" ")
     }
@@ -204,6 +546,13 @@ impl ExecConfig {
         }
     }
 
+    fn max_total_runtime(&self) -> Option<Duration> {
+        self.scheduled
+            .as_ref()
+            .and_then(|config| config.max_total_runtime_secs)
+            .map(Duration::from_secs)
+    }
+
     const fn respawn_on_exit_or_default(&self) -> bool {
         match &self.streaming {
             None => default_respawn_on_exit(),
@@ -211,12 +560,78 @@ impl ExecConfig {
         }
     }
 
-    const fn respawn_interval_secs_or_default(&self) -> u64 {
+    fn delivery_policy(&self) -> (u32, DeliveryFailureAction) {
         match &self.streaming {
-            None => default_respawn_interval_secs(),
-            Some(config) => config.respawn_interval_secs,
+            None => (default_delivery_retries(), DeliveryFailureAction::default()),
+            Some(config) => (config.delivery_retries, config.on_delivery_failure),
         }
     }
+
+    fn respawn_policy(&self) -> RespawnPolicy {
+        match &self.streaming {
+            None => RespawnPolicy::new(
+                Duration::from_secs(default_respawn_interval_secs()),
+                None,
+                None,
+                false,
+            ),
+            Some(config) => RespawnPolicy::new(
+                Duration::from_secs(config.respawn_interval_secs),
+                config.max_backoff_secs.map(Duration::from_secs),
+                config.max_restarts,
+                config.jitter,
+            ),
+        }
+    }
+}
+
+/// Checks for framing and codec combinations that are known not to work on the byte stream read
+/// from a child process, returning the reason why they don't.
+///
+/// The known-bad combinations are `newline_delimited` or `character_delimited` framing with the
+/// `native` codec, as Protobuf payloads are binary and routinely contain the delimiter byte. The
+/// `bytes` framing works with any codec, in either mode, as it buffers the whole output of the
+/// command into a single frame.
+const fn incompatible_framing_reason(
+    framing: &FramingConfig,
+    decoding: &DeserializerConfig,
+) -> Option<&'static str> {
+    match (framing, decoding) {
+        (
+            FramingConfig::NewlineDelimited { .. } | FramingConfig::CharacterDelimited { .. },
+            DeserializerConfig::Native,
+        ) => Some(
+            "native events are binary and can contain the delimiter; use `length_delimited` framing instead",
+        ),
+        _ => None,
+    }
+}
+
+const fn framing_name(framing: &FramingConfig) -> &'static str {
+    match framing {
+        FramingConfig::Bytes => "bytes",
+        FramingConfig::CharacterDelimited { .. } => "character_delimited",
+        FramingConfig::ChunkedGelf { .. } => "chunked_gelf",
+        FramingConfig::LengthDelimited => "length_delimited",
+        FramingConfig::NewlineDelimited { .. } => "newline_delimited",
+        FramingConfig::OctetCounting { .. } => "octet_counting",
+        FramingConfig::VarintLengthDelimited => "varint_length_delimited",
+    }
+}
+
+const fn codec_name(decoding: &DeserializerConfig) -> &'static str {
+    match decoding {
+        DeserializerConfig::Bytes => "bytes",
+        DeserializerConfig::Csv { .. } => "csv",
+        DeserializerConfig::Json { .. } => "json",
+        DeserializerConfig::Logfmt { .. } => "logfmt",
+        #[cfg(feature = "sources-syslog")]
+        DeserializerConfig::Syslog => "syslog",
+        DeserializerConfig::Native => "native",
+        DeserializerConfig::NativeJson => "native_json",
+        DeserializerConfig::Gelf => "gelf",
+        DeserializerConfig::Protobuf { .. } => "protobuf",
+    }
 }
 
 #[async_trait::async_trait]
@@ -225,23 +640,51 @@ impl SourceConfig for ExecConfig {
         self.validate()?;
         let hostname = get_hostname();
         let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
-        let framing = self
-            .framing
-            .clone()
-            .unwrap_or_else(|| self.decoding.default_stream_framing());
         let decoder =
-            DecodingConfig::new(framing, self.decoding.clone(), LogNamespace::Legacy).build();
+            DecodingConfig::new(self.framing(), self.decoding.clone(), LogNamespace::Legacy)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
+        let stderr_decoder = self
+            .stderr
+            .as_ref()
+            .map(|stderr| {
+                DecodingConfig::new(
+                    stderr.framing(),
+                    stderr.decoding.clone(),
+                    LogNamespace::Legacy,
+                )
+                .with_error_policy(self.on_decode_error)
+                .build()
+            })
+            .transpose()?;
+
+        let tracker = if self.reap_orphans {
+            let data_dir = cx
+                .globals
+                .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+            let tracker = ChildTracker::new(&data_dir);
+            tracker.reap_orphans(&self.command);
+            Some(tracker)
+        } else {
+            None
+        };
 
         match &self.mode {
             Mode::Scheduled => {
                 let exec_interval_secs = self.exec_interval_secs_or_default();
+                let max_total_runtime = self.max_total_runtime();
 
                 Ok(Box::pin(run_scheduled(
                     self.clone(),
                     hostname,
                     exec_interval_secs,
+                    max_total_runtime,
                     decoder,
+                    stderr_decoder,
+                    tracker,
+                    acknowledgements,
                     cx.shutdown,
                     cx.out,
                     log_namespace,
@@ -249,14 +692,17 @@ impl SourceConfig for ExecConfig {
             }
             Mode::Streaming => {
                 let respawn_on_exit = self.respawn_on_exit_or_default();
-                let respawn_interval_secs = self.respawn_interval_secs_or_default();
+                let respawn_policy = self.respawn_policy();
 
                 Ok(Box::pin(run_streaming(
                     self.clone(),
                     hostname,
                     respawn_on_exit,
-                    respawn_interval_secs,
+                    respawn_policy,
                     decoder,
+                    stderr_decoder,
+                    tracker,
+                    acknowledgements,
                     cx.shutdown,
                     cx.out,
                     log_namespace,
@@ -268,9 +714,15 @@ impl SourceConfig for ExecConfig {
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         let log_namespace = global_log_namespace.merge(Some(self.log_namespace.unwrap_or(false)));
 
-        let schema_definition = self
-            .decoding
-            .schema_definition(log_namespace)
+        let mut output_type = self.decoding.output_type();
+        let mut schema_definition = self.decoding.schema_definition(log_namespace);
+        if let Some(stderr) = self.stderr.as_ref().filter(|_| self.include_stderr) {
+            output_type |= stderr.decoding.output_type();
+            schema_definition =
+                schema_definition.merge(stderr.decoding.schema_definition(log_namespace));
+        }
+
+        let schema_definition = schema_definition
             .with_standard_vector_source_metadata()
             .with_source_metadata(
                 Self::NAME,
@@ -301,38 +753,100 @@ impl SourceConfig for ExecConfig {
                 &owned_value_path!(COMMAND_KEY),
                 Kind::bytes(),
                 None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(
+                    WINDOW_START_KEY
+                ))),
+                &owned_value_path!(WINDOW_START_KEY),
+                Kind::timestamp().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(WINDOW_END_KEY))),
+                &owned_value_path!(WINDOW_END_KEY),
+                Kind::timestamp().or_undefined(),
+                None,
             );
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(output_type).with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        true
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_scheduled(
     config: ExecConfig,
     hostname: Option<String>,
     exec_interval_secs: u64,
+    max_total_runtime: Option<Duration>,
     decoder: Decoder,
+    stderr_decoder: Option<Decoder>,
+    tracker: Option<ChildTracker>,
+    acknowledgements: bool,
     shutdown: ShutdownSignal,
     out: SourceSender,
     log_namespace: LogNamespace,
 ) -> Result<(), ()> {
     debug!("Starting scheduled exec runs.");
     let schedule = Duration::from_secs(exec_interval_secs);
+    let mismatch_reported = Arc::new(AtomicBool::new(false));
 
     let mut interval = IntervalStream::new(time::interval(schedule)).take_until(shutdown.clone());
+    let mut window_start = Utc::now()
+        - chrono::Duration::from_std(schedule).unwrap_or_else(|_| chrono::Duration::zero());
+
+    // Runs are only started from the `select!` below, so a run that is in progress when the
+    // deadline passes is always allowed to complete before the source expires.
+    let expired = async move {
+        match max_total_runtime {
+            Some(max_total_runtime) => sleep(max_total_runtime).await,
+            None => futures::future::pending().await,
+        }
+    };
+    tokio::pin!(expired);
+
+    loop {
+        tokio::select! {
+            biased;
+
+            _ = &mut expired => {
+                emit!(ExecSourceExpired {
+                    command: config.command_line().as_str(),
+                    max_total_runtime: max_total_runtime.unwrap_or_default(),
+                });
+                break;
+            }
+            tick = interval.next() => if tick.is_none() {
+                break;
+            }
+        }
+
+        let window = Window {
+            start: window_start,
+            end: Utc::now(),
+        };
 
-    while interval.next().await.is_some() {
         // Wait for our task to finish, wrapping it in a timeout
         let timeout = tokio::time::timeout(
             schedule,
             run_command(
                 config.clone(),
                 hostname.clone(),
+                Some(window),
                 decoder.clone(),
+                stderr_decoder.clone(),
+                tracker.clone(),
+                Arc::clone(&mismatch_reported),
+                acknowledgements,
                 shutdown.clone(),
                 out.clone(),
                 log_namespace,
@@ -341,14 +855,18 @@ async fn run_scheduled(
         .await;
 
         match timeout {
-            Ok(output) => {
-                if let Err(command_error) = output {
+            Ok(output) => match output {
+                // The next window starts where this one ended, unless the run failed, in which case
+                // it covers this window again.
+                Ok(Some(exit_status)) if exit_status.success() => window_start = window.end,
+                Ok(_) => {}
+                Err(command_error) => {
                     emit!(ExecFailedError {
                         command: config.command_line().as_str(),
                         error: command_error,
                     });
                 }
-            }
+            },
             Err(error) => {
                 emit!(ExecTimeoutError {
                     command: config.command_line().as_str(),
@@ -368,21 +886,30 @@ async fn run_streaming(
     config: ExecConfig,
     hostname: Option<String>,
     respawn_on_exit: bool,
-    respawn_interval_secs: u64,
+    mut respawn_policy: RespawnPolicy,
     decoder: Decoder,
+    stderr_decoder: Option<Decoder>,
+    tracker: Option<ChildTracker>,
+    acknowledgements: bool,
     mut shutdown: ShutdownSignal,
     out: SourceSender,
     log_namespace: LogNamespace,
 ) -> Result<(), ()> {
-    if respawn_on_exit {
-        let duration = Duration::from_secs(respawn_interval_secs);
+    let mismatch_reported = Arc::new(AtomicBool::new(false));
 
+    if respawn_on_exit {
         // Continue to loop while not shutdown
         loop {
+            let start = Instant::now();
             let output = run_command(
                 config.clone(),
                 hostname.clone(),
+                None,
                 decoder.clone(),
+                stderr_decoder.clone(),
+                tracker.clone(),
+                Arc::clone(&mismatch_reported),
+                acknowledgements,
                 shutdown.clone(),
                 out.clone(),
                 log_namespace,
@@ -397,16 +924,37 @@ async fn run_streaming(
                 });
             }
 
+            respawn_policy.record_run(start.elapsed());
+            let delay = match respawn_policy.next_delay() {
+                Some(delay) => delay,
+                None => {
+                    emit!(ExecRestartsExhausted {
+                        command: config.command_line().as_str(),
+                        restarts: respawn_policy.respawns(),
+                    });
+                    break;
+                }
+            };
+
             tokio::select! {
                 _ = &mut shutdown => break, // will break early if a shutdown is started
-                _ = sleep(duration) => debug!("Restarting streaming process."),
+                _ = sleep(delay) => emit!(ExecCommandRestarted {
+                    command: config.command_line().as_str(),
+                    restarts: respawn_policy.respawns(),
+                    delay,
+                }),
             }
         }
     } else {
         let output = run_command(
             config.clone(),
             hostname,
+            None,
             decoder,
+            stderr_decoder,
+            tracker,
+            mismatch_reported,
+            acknowledgements,
             shutdown,
             out,
             log_namespace,
@@ -424,25 +972,45 @@ async fn run_streaming(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_command(
     config: ExecConfig,
     hostname: Option<String>,
+    window: Option<Window>,
     decoder: Decoder,
+    stderr_decoder: Option<Decoder>,
+    tracker: Option<ChildTracker>,
+    mismatch_reported: Arc<AtomicBool>,
+    acknowledgements: bool,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     log_namespace: LogNamespace,
 ) -> Result<Option<ExitStatus>, Error> {
     debug!("Starting command run.");
-    let mut command = build_command(&config);
+    let values = TemplateValues {
+        hostname: hostname.as_deref(),
+        window,
+    };
+    let mut command = build_command(&config, &values)?;
+    let mut heartbeats = config
+        .heartbeat_timeout()
+        .map(|timeout| Heartbeats::attach(&mut command, timeout))
+        .transpose()?;
 
     // Mark the start time just before spawning the process as
     // this seems to be the best approximation of exec duration
     let start = Instant::now();
 
     let mut child = command.spawn()?;
+    if let Some(heartbeats) = heartbeats.as_mut() {
+        heartbeats.spawned();
+    }
 
-    // Set up communication channels
-    let (sender, mut receiver) = channel(1024);
+    // Set up communication channels. With acknowledgements, output is only read ahead by a single
+    // batch, so that the command isn't read any further until its output is delivered.
+    let (sender, mut receiver) = channel(if acknowledgements { 1 } else { 1024 });
+    let decode_errors = Arc::new(AtomicUsize::new(0));
+    let mut readers = Vec::with_capacity(2);
 
     // Optionally include stderr
     if config.include_stderr {
@@ -453,7 +1021,16 @@ async fn run_command(
         // Create stderr async reader
         let stderr_reader = BufReader::new(stderr);
 
-        spawn_reader_thread(stderr_reader, decoder.clone(), STDERR, sender.clone());
+        let progress = Arc::new(DecodeProgress::default());
+        let handle = spawn_reader_thread(
+            stderr_reader,
+            stderr_decoder.unwrap_or_else(|| decoder.clone()),
+            STDERR,
+            sender.clone(),
+            Arc::clone(&decode_errors),
+            Arc::clone(&progress),
+        );
+        readers.push((STDERR, progress, handle));
     }
 
     let stdout = child
@@ -466,9 +1043,33 @@ async fn run_command(
 
     let pid = child.id();
 
-    spawn_reader_thread(stdout_reader, decoder.clone(), STDOUT, sender);
+    if let (Some(tracker), Some(pid)) = (&tracker, pid) {
+        tracker.track(pid);
+    }
+
+    let progress = Arc::new(DecodeProgress::default());
+    let handle = spawn_reader_thread(
+        stdout_reader,
+        decoder.clone(),
+        STDOUT,
+        sender,
+        Arc::clone(&decode_errors),
+        Arc::clone(&progress),
+    );
+    readers.push((STDOUT, progress, handle));
 
     let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+    let mut events_received = 0;
+    let mut events_emitted = 0;
+    let sampler = config.sampler();
+
+    let stall_timeout = config.decode_stall_timeout();
+    let mut watchdog = time::interval(
+        stall_timeout
+            .map(|stall_timeout| stall_timeout / 2)
+            .unwrap_or_default()
+            .max(Duration::from_millis(100)),
+    );
 
     'outer: loop {
         tokio::select! {
@@ -477,6 +1078,47 @@ async fn run_command(
                         break 'outer; // couldn't signal, exit early
                 }
             }
+            _ = watchdog.tick(), if stall_timeout.is_some() => {
+                let stall_timeout = stall_timeout.unwrap_or_default();
+                if !matches!(child.try_wait(), Ok(None)) {
+                    continue; // the child already exited, the readers will finish on their own
+                }
+                let stalled = readers.iter().find_map(|(stream, progress, _)| {
+                    progress.stalled_for(stall_timeout).map(|stalled_for| (*stream, stalled_for))
+                });
+                if let Some((stream, stalled_for)) = stalled {
+                    emit!(ExecDecodeStalled {
+                        command: config.command_line().as_str(),
+                        stream,
+                        stalled_for,
+                    });
+                    // Recycle the child. It is also killed on drop, so a failure here is harmless.
+                    readers.iter().for_each(|(_, _, handle)| handle.abort());
+                    _ = child.start_kill();
+                    break 'outer;
+                }
+            }
+            failure = heartbeat_failure(&mut heartbeats) => {
+                if !matches!(child.try_wait(), Ok(None)) {
+                    // The child already exited, and closed its control channel.
+                    heartbeats = None;
+                    continue;
+                }
+                match failure {
+                    HeartbeatFailure::Missed(missed_for) => emit!(ExecHeartbeatMissed {
+                        command: config.command_line().as_str(),
+                        missed_for,
+                    }),
+                    HeartbeatFailure::Unhealthy(reason) => emit!(ExecCommandUnhealthy {
+                        command: config.command_line().as_str(),
+                        reason: &reason,
+                    }),
+                }
+                // Recycle the child. It is also killed on drop, so a failure here is harmless.
+                readers.iter().for_each(|(_, _, handle)| handle.abort());
+                _ = child.start_kill();
+                break 'outer;
+            }
             v = receiver.recv() => {
                 match v {
                     None => break 'outer,
@@ -484,16 +1126,47 @@ async fn run_command(
                         bytes_received.emit(ByteSize(byte_size));
 
                         let count = events.len();
+                        events_received += count;
                         emit!(ExecEventsReceived {
                             count,
                             command: config.command_line().as_str(),
                             byte_size: events.estimated_json_encoded_size_of(),
                         });
 
+                        if let Some(sampler) = &sampler {
+                            events.retain(|event| sampler.should_emit(event));
+                            let sampled_out = count - events.len();
+                            if sampled_out > 0 {
+                                emit!(ExecEventsSampledOut {
+                                    count: sampled_out,
+                                    command: config.command_line().as_str(),
+                                });
+                            }
+                            if events.is_empty() {
+                                continue;
+                            }
+                        }
+                        let count = events.len();
+                        events_emitted += count;
+
                         for event in &mut events {
-                            handle_event(&config, &hostname, &Some(stream.to_string()), pid, event, log_namespace);
+                            handle_event(&config, &hostname, &Some(stream.to_string()), pid, window, event, log_namespace);
                         }
-                        if let Err(error) = out.send_batch(events).await {
+                        if acknowledgements {
+                            let delivery = send_with_acknowledgements(
+                                &config,
+                                events,
+                                &mut out,
+                                &mut child,
+                                &command,
+                                &mut shutdown,
+                            )
+                            .await;
+                            match delivery {
+                                Delivery::Delivered => {}
+                                Delivery::Failed | Delivery::Closed => break 'outer,
+                            }
+                        } else if let Err(error) = out.send_batch(events).await {
                             emit!(StreamClosedError { count, error });
                             break;
                         }
@@ -508,6 +1181,23 @@ async fn run_command(
     let result = match child.try_wait() {
         Ok(Some(exit_status)) => {
             handle_exit_status(&config, exit_status.code(), elapsed);
+
+            // A successful run that produced output, none of which could be decoded, almost
+            // always means the framing and codec don't fit the command's output.
+            let decode_errors = decode_errors.load(Ordering::Relaxed);
+            if exit_status.success()
+                && events_received == 0
+                && decode_errors > 0
+                && !mismatch_reported.swap(true, Ordering::Relaxed)
+            {
+                emit!(ExecFramingDecoderMismatch {
+                    command: config.command_line().as_str(),
+                    framing: framing_name(&config.framing()),
+                    codec: codec_name(&config.decoding),
+                    decode_errors,
+                });
+            }
+
             Ok(Some(exit_status))
         }
         Ok(None) => {
@@ -522,11 +1212,167 @@ async fn run_command(
         }
     };
 
+    if let (Some(tracker), Some(pid)) = (&tracker, pid) {
+        tracker.untrack(pid);
+    }
+
+    if config.always_emit_completion {
+        let exit_code = match &result {
+            Ok(Some(exit_status)) => exit_status.code(),
+            _ => None,
+        };
+        let mut event = completion_event(exit_code, events_emitted, elapsed);
+        handle_event(
+            &config,
+            &hostname,
+            &None,
+            pid,
+            window,
+            &mut event,
+            log_namespace,
+        );
+        if let Err(error) = out.send_event(event).await {
+            emit!(StreamClosedError { count: 1, error });
+        }
+    }
+
     debug!("Finished command run.");
 
     result
 }
 
+/// Waits for the heartbeats of the child to fail, if they are monitored.
+async fn heartbeat_failure(heartbeats: &mut Option<Heartbeats>) -> HeartbeatFailure {
+    match heartbeats {
+        Some(heartbeats) => heartbeats.failure().await,
+        None => futures::future::pending().await,
+    }
+}
+
+/// The outcome of sending a batch of events with acknowledgements enabled.
+enum Delivery {
+    /// The batch was delivered.
+    Delivered,
+
+    /// The batch persistently failed to be delivered, and the command was killed.
+    Failed,
+
+    /// The source is shutting down, or the output is closed.
+    Closed,
+}
+
+/// Sends a batch of events, waiting until it is delivered.
+///
+/// Batches that aren't delivered are resent according to the delivery policy of the command, which
+/// also decides what happens to the command once the retries are exhausted.
+async fn send_with_acknowledgements(
+    config: &ExecConfig,
+    events: SmallVec<[Event; 1]>,
+    out: &mut SourceSender,
+    child: &mut tokio::process::Child,
+    command: &tokio::process::Command,
+    shutdown: &mut ShutdownSignal,
+) -> Delivery {
+    let (delivery_retries, on_delivery_failure) = config.delivery_policy();
+    let count = events.len();
+    let mut attempts = 0;
+    let mut paused = false;
+
+    let delivery = loop {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let batch_events = events
+            .iter()
+            .cloned()
+            .map(|event| event.with_batch_notifier(&batch));
+        if let Err(error) = out.send_batch(batch_events).await {
+            emit!(StreamClosedError { count, error });
+            break Delivery::Closed;
+        }
+        drop(batch);
+
+        if receiver.await == BatchStatus::Delivered {
+            break Delivery::Delivered;
+        }
+
+        attempts += 1;
+        if attempts <= delivery_retries {
+            emit!(ExecDeliveryFailed {
+                command: config.command_line().as_str(),
+                count,
+                attempts,
+            });
+        } else {
+            match on_delivery_failure {
+                DeliveryFailureAction::Kill => {
+                    emit!(ExecDeliveryRetriesExhausted {
+                        command: config.command_line().as_str(),
+                        count,
+                        action: on_delivery_failure.as_str(),
+                    });
+                    // The child is also killed on drop, so a failure here is harmless.
+                    _ = child.start_kill();
+                    break Delivery::Failed;
+                }
+                DeliveryFailureAction::Pause if !paused => {
+                    emit!(ExecDeliveryRetriesExhausted {
+                        command: config.command_line().as_str(),
+                        count,
+                        action: on_delivery_failure.as_str(),
+                    });
+                    paused = true;
+                    pause_child(child, command, true);
+                }
+                DeliveryFailureAction::Pause => {}
+            }
+        }
+
+        tokio::select! {
+            _ = &mut *shutdown => break Delivery::Closed,
+            _ = sleep(DELIVERY_RETRY_INTERVAL) => {}
+        }
+    };
+
+    if paused {
+        pause_child(child, command, false);
+    }
+
+    delivery
+}
+
+/// Pauses the child, or resumes it once paused.
+#[cfg(unix)]
+fn pause_child(child: &tokio::process::Child, command: &tokio::process::Command, pause: bool) {
+    use crate::internal_events::ExecFailedToPauseChildError;
+
+    let signal = if pause {
+        nix::sys::signal::Signal::SIGSTOP
+    } else {
+        nix::sys::signal::Signal::SIGCONT
+    };
+
+    let error = match child.id().map(i32::try_from) {
+        Some(Ok(pid)) => nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid), signal)
+            .err()
+            .map(ExecFailedToSignalChild::SignalError),
+        Some(Err(err)) => Some(ExecFailedToSignalChild::FailedToMarshalPid(err)),
+        None => Some(ExecFailedToSignalChild::NoPid),
+    };
+
+    if let Some(error) = error {
+        emit!(ExecFailedToPauseChildError {
+            command,
+            signal: signal.as_str(),
+            error,
+        });
+    }
+}
+
+#[cfg(windows)]
+fn pause_child(_child: &tokio::process::Child, _command: &tokio::process::Command, _pause: bool) {
+    // Windows processes can't be paused, so the command is only paused by no longer reading its
+    // output.
+}
+
 fn handle_exit_status(config: &ExecConfig, exit_status: Option<i32>, exec_duration: Duration) {
     emit!(ExecCommandExecuted {
         command: config.command_line().as_str(),
@@ -591,14 +1437,25 @@ async fn shutdown_child(
     }
 }
 
-fn build_command(config: &ExecConfig) -> Command {
-    let command = &config.command[0];
+fn build_command(config: &ExecConfig, values: &TemplateValues<'_>) -> Result<Command, Error> {
+    let render = |value: &str| {
+        templating::render(value, values).map_err(|reason| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid placeholder in {:?}: {}", value, reason),
+            )
+        })
+    };
 
-    let mut command = Command::new(command);
+    let mut command = Command::new(render(&config.command[0])?);
 
-    if config.command.len() > 1 {
-        command.args(&config.command[1..]);
-    };
+    for arg in &config.command[1..] {
+        command.arg(render(arg)?);
+    }
+
+    for (key, value) in &config.environment {
+        command.env(key, render(value)?);
+    }
 
     command.kill_on_drop(true);
 
@@ -620,7 +1477,7 @@ fn build_command(config: &ExecConfig) -> Command {
     // Stdin is not needed
     command.stdin(std::process::Stdio::null());
 
-    command
+    Ok(command)
 }
 
 fn handle_event(
@@ -628,6 +1485,7 @@ fn handle_event(
     hostname: &Option<String>,
     data_stream: &Option<String>,
     pid: Option<u32>,
+    window: Option<Window>,
     event: &mut Event,
     log_namespace: LogNamespace,
 ) {
@@ -675,7 +1533,40 @@ fn handle_event(
             path!(COMMAND_KEY),
             config.command.clone(),
         );
+
+        // Add the window of the scheduled run (if needed)
+        if let Some(window) = window {
+            log_namespace.insert_source_metadata(
+                ExecConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!(WINDOW_START_KEY))),
+                path!(WINDOW_START_KEY),
+                window.start,
+            );
+            log_namespace.insert_source_metadata(
+                ExecConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!(WINDOW_END_KEY))),
+                path!(WINDOW_END_KEY),
+                window.end,
+            );
+        }
+    }
+}
+
+fn completion_event(exit_code: Option<i32>, event_count: usize, duration: Duration) -> Event {
+    let mut log = LogEvent::default();
+    if let Some(exit_code) = exit_code {
+        log.insert(EXIT_CODE_KEY, i64::from(exit_code));
     }
+    log.insert(EVENT_COUNT_KEY, event_count as i64);
+    log.insert(
+        DURATION_SECONDS_KEY,
+        NotNan::new(duration.as_secs_f64())
+            .map(Value::Float)
+            .unwrap_or(Value::Null),
+    );
+    log.into()
 }
 
 fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
@@ -683,34 +1574,44 @@ fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
     decoder: Decoder,
     origin: &'static str,
     sender: Sender<((SmallVec<[Event; 1]>, usize), &'static str)>,
-) {
+    decode_errors: Arc<AtomicUsize>,
+    progress: Arc<DecodeProgress>,
+) -> JoinHandle<()> {
     // Start the green background thread for collecting
-    drop(tokio::spawn(async move {
-        debug!("Start capturing {} command output.", origin);
-
-        let mut stream = FramedRead::new(reader, decoder);
-        while let Some(result) = stream.next().await {
-            match result {
-                Ok(next) => {
-                    if sender.send((next, origin)).await.is_err() {
-                        // If the receive half of the channel is closed, either due to close being
-                        // called or the Receiver handle dropping, the function returns an error.
-                        emit!(ExecChannelClosedError);
-                        break;
+    tokio::spawn(
+        async move {
+            debug!("Start capturing {} command output.", origin);
+
+            let reader = ProgressReader::new(reader, Arc::clone(&progress));
+            let mut stream = FramedRead::new(reader, decoder);
+            while let Some(result) = stream.next().await {
+                let buffered = !stream.read_buffer().is_empty();
+                match result {
+                    Ok(next) => {
+                        progress.events_decoded(buffered);
+                        if sender.send((next, origin)).await.is_err() {
+                            // If the receive half of the channel is closed, either due to close being
+                            // called or the Receiver handle dropping, the function returns an error.
+                            emit!(ExecChannelClosedError);
+                            break;
+                        }
                     }
-                }
-                Err(error) => {
-                    // Error is logged by `crate::codecs::Decoder`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+                    Err(error) => {
+                        progress.decode_failed(buffered);
+                        // Error is logged by `crate::codecs::Decoder`, it is only counted here to
+                        // diagnose runs which produce no events at all.
+                        decode_errors.fetch_add(1, Ordering::Relaxed);
+                        if !error.can_continue() {
+                            break;
+                        }
                     }
                 }
             }
-        }
 
-        debug!("Finished capturing {} command output.", origin);
-    }));
+            debug!("Finished capturing {} command output.", origin);
+        }
+        .in_current_span(),
+    )
 }
 
 #[cfg(test)]
@@ -723,9 +1624,9 @@ mod tests {
     use futures::task::Poll;
 
     use super::*;
-    use crate::config::log_schema;
+    use crate::{config::log_schema, event::EventStatus};
 
-    use crate::{event::LogEvent, test_util::trace_init};
+    use crate::test_util::trace_init;
 
     #[test]
     fn test_generate_config() {
@@ -745,6 +1646,7 @@ mod tests {
             &hostname,
             &data_stream,
             pid,
+            None,
             &mut event,
             LogNamespace::Legacy,
         );
@@ -764,6 +1666,30 @@ mod tests {
             .is_some());
     }
 
+    #[test]
+    fn test_scheduled_handle_event_window() {
+        let config = standard_scheduled_test_config();
+        let window = Window {
+            start: Utc::now() - chrono::Duration::seconds(60),
+            end: Utc::now(),
+        };
+
+        let mut event = LogEvent::from("hello world").into();
+        handle_event(
+            &config,
+            &None,
+            &None,
+            None,
+            Some(window),
+            &mut event,
+            LogNamespace::Legacy,
+        );
+        let log = event.as_log();
+
+        assert_eq!(log[WINDOW_START_KEY], window.start.into());
+        assert_eq!(log[WINDOW_END_KEY], window.end.into());
+    }
+
     #[test]
     fn test_scheduled_handle_event_vector_namespace() {
         let config = standard_scheduled_test_config();
@@ -779,6 +1705,7 @@ mod tests {
             &hostname,
             &data_stream,
             pid,
+            None,
             &mut event,
             LogNamespace::Vector,
         );
@@ -826,6 +1753,7 @@ mod tests {
             &hostname,
             &data_stream,
             pid,
+            None,
             &mut event,
             LogNamespace::Legacy,
         );
@@ -860,6 +1788,7 @@ mod tests {
             &hostname,
             &data_stream,
             pid,
+            None,
             &mut event,
             LogNamespace::Vector,
         );
@@ -894,6 +1823,50 @@ mod tests {
             .is_timestamp());
     }
 
+    #[test]
+    fn test_validate_incompatible_framing() {
+        let config = ExecConfig {
+            framing: Some(FramingConfig::Bytes),
+            decoding: DeserializerConfig::Json {
+                json: Default::default(),
+            },
+            ..standard_scheduled_test_config()
+        };
+        assert_eq!(config.validate(), Ok(()));
+
+        let config = ExecConfig {
+            framing: Some(FramingConfig::Bytes),
+            decoding: DeserializerConfig::Json {
+                json: Default::default(),
+            },
+            ..standard_streaming_test_config()
+        };
+        assert_eq!(config.validate(), Ok(()));
+
+        let config = ExecConfig {
+            framing: None,
+            decoding: DeserializerConfig::Native,
+            ..standard_scheduled_test_config()
+        };
+        assert_eq!(config.validate(), Ok(()));
+
+        let config = ExecConfig {
+            framing: Some(FramingConfig::NewlineDelimited {
+                newline_delimited: Default::default(),
+            }),
+            decoding: DeserializerConfig::Native,
+            ..standard_scheduled_test_config()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ExecConfigError::IncompatibleFraming {
+                framing: "newline_delimited",
+                codec: "native",
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_build_command() {
         let config = ExecConfig {
@@ -902,17 +1875,33 @@ mod tests {
             streaming: Some(StreamingConfig {
                 respawn_on_exit: default_respawn_on_exit(),
                 respawn_interval_secs: default_respawn_interval_secs(),
+                max_backoff_secs: None,
+                max_restarts: None,
+                jitter: false,
+                delivery_retries: default_delivery_retries(),
+                on_delivery_failure: DeliveryFailureAction::default(),
+                heartbeat: None,
             }),
             command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
+            environment: IndexMap::new(),
             working_directory: Some(PathBuf::from("/tmp")),
             include_stderr: default_include_stderr(),
+            stderr: None,
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
+            sampling_rate: None,
+            sampling_mode: SamplingMode::default(),
+            decode_stall_timeout_secs: None,
+            always_emit_completion: false,
+            reap_orphans: false,
+            data_dir: None,
+            acknowledgements: Default::default(),
             log_namespace: None,
         };
 
-        let command = build_command(&config);
+        let command = build_command(&config, &TemplateValues::default()).unwrap();
 
         let mut expected_command = Command::new("./runner");
         expected_command.kill_on_drop(true);
@@ -935,7 +1924,14 @@ mod tests {
         let decoder = crate::codecs::Decoder::default();
         let (sender, mut receiver) = channel(1024);
 
-        spawn_reader_thread(reader, decoder, STDOUT, sender);
+        spawn_reader_thread(
+            reader,
+            decoder,
+            STDOUT,
+            sender,
+            Default::default(),
+            Default::default(),
+        );
 
         let mut counter = 0;
         if let Some(((events, byte_size), origin)) = receiver.recv().await {
@@ -979,7 +1975,12 @@ mod tests {
             run_command(
                 config.clone(),
                 hostname,
+                None,
                 decoder,
+                None,
+                None,
+                Default::default(),
+                false,
                 shutdown,
                 tx,
                 LogNamespace::Legacy,
@@ -1014,7 +2015,12 @@ mod tests {
                     run_command(
                         config.clone(),
                         hostname,
+                        None,
                         decoder,
+                        None,
+                        None,
+                        Default::default(),
+                        false,
                         shutdown,
                         tx,
                         LogNamespace::Legacy,
@@ -1052,6 +2058,364 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_separate_stderr_decoding() {
+        trace_init();
+        let config = ExecConfig {
+            command: vec![
+                String::from("bash"),
+                String::from("-c"),
+                String::from(r#"echo '{"answer": 42}'; echo 'not json' >&2"#),
+            ],
+            decoding: DeserializerConfig::Json {
+                json: Default::default(),
+            },
+            stderr: Some(StderrConfig {
+                framing: None,
+                decoding: DeserializerConfig::Bytes,
+            }),
+            ..standard_scheduled_test_config()
+        };
+        let decoder = DecodingConfig::new(
+            config.framing(),
+            config.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build()
+        .unwrap();
+        let stderr = config.stderr.as_ref().unwrap();
+        let stderr_decoder = DecodingConfig::new(
+            stderr.framing(),
+            stderr.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build()
+        .unwrap();
+        let (tx, rx) = SourceSender::new_test();
+
+        tokio::time::timeout(
+            time::Duration::from_secs(5),
+            run_command(
+                config,
+                None,
+                None,
+                decoder,
+                Some(stderr_decoder),
+                None,
+                Default::default(),
+                false,
+                ShutdownSignal::noop(),
+                tx,
+                LogNamespace::Legacy,
+            ),
+        )
+        .await
+        .expect("command timed out")
+        .expect("command error");
+
+        let events = rx.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 2);
+
+        let stdout = events
+            .iter()
+            .map(Event::as_log)
+            .find(|log| log[STREAM_KEY] == STDOUT.into())
+            .expect("Expected an event from stdout");
+        assert_eq!(stdout["answer"], 42_i64.into());
+
+        let stderr = events
+            .iter()
+            .map(Event::as_log)
+            .find(|log| log[STREAM_KEY] == STDERR.into())
+            .expect("Expected an event from stderr");
+        assert_eq!(stderr[log_schema().message_key()], "not json".into());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_completion_event_without_output() {
+        trace_init();
+        let config = ExecConfig {
+            command: vec!["true".to_owned()],
+            always_emit_completion: true,
+            ..standard_scheduled_test_config()
+        };
+        let (tx, mut rx) = SourceSender::new_test();
+
+        tokio::time::timeout(
+            time::Duration::from_secs(5),
+            run_command(
+                config.clone(),
+                None,
+                None,
+                Default::default(),
+                None,
+                None,
+                Default::default(),
+                false,
+                ShutdownSignal::noop(),
+                tx,
+                LogNamespace::Legacy,
+            ),
+        )
+        .await
+        .expect("command timed out")
+        .expect("command error");
+
+        let event = rx.next().await.expect("Expected a completion event");
+        let log = event.as_log();
+        assert_eq!(log[EXIT_CODE_KEY], 0_i64.into());
+        assert_eq!(log[EVENT_COUNT_KEY], 0_i64.into());
+        assert!(log.get(DURATION_SECONDS_KEY).is_some());
+        assert_eq!(log[COMMAND_KEY], config.command.into());
+        assert_eq!(log[log_schema().source_type_key()], "exec".into());
+        assert!(log.get(STREAM_KEY).is_none());
+
+        assert!(rx.next().await.is_none());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_framing_decoder_mismatch_reported() {
+        trace_init();
+        // `echo` outputs plain text, which can't be decoded as JSON.
+        let config = ExecConfig {
+            decoding: DeserializerConfig::Json {
+                json: Default::default(),
+            },
+            ..standard_scheduled_test_config()
+        };
+        let decoder = DecodingConfig::new(
+            config.framing(),
+            config.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build()
+        .unwrap();
+        let mismatch_reported = Arc::new(AtomicBool::new(false));
+        let (tx, _rx) = SourceSender::new_test();
+
+        let exit_status = tokio::time::timeout(
+            time::Duration::from_secs(5),
+            run_command(
+                config,
+                None,
+                None,
+                decoder,
+                None,
+                None,
+                Arc::clone(&mismatch_reported),
+                false,
+                ShutdownSignal::noop(),
+                tx,
+                LogNamespace::Legacy,
+            ),
+        )
+        .await
+        .expect("command timed out")
+        .expect("command error");
+
+        assert_eq!(0_i32, exit_status.unwrap().code().unwrap());
+        assert!(mismatch_reported.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_scheduled_max_total_runtime() {
+        trace_init();
+        let config = ExecConfig {
+            scheduled: Some(ScheduledConfig {
+                exec_interval_secs: 1,
+                max_total_runtime_secs: Some(1),
+            }),
+            ..standard_scheduled_test_config()
+        };
+        let (tx, mut rx) = SourceSender::new_test();
+
+        let result = tokio::time::timeout(
+            time::Duration::from_secs(5),
+            run_scheduled(
+                config.clone(),
+                None,
+                config.exec_interval_secs_or_default(),
+                config.max_total_runtime(),
+                Default::default(),
+                None,
+                None,
+                false,
+                ShutdownSignal::noop(),
+                tx,
+                LogNamespace::Legacy,
+            ),
+        )
+        .await
+        .expect("source did not expire");
+        assert_eq!(result, Ok(()));
+
+        let event = rx.next().await.expect("Expected to receive an event");
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "Hello World!".into()
+        );
+        assert!(rx.next().await.is_none());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_acknowledged_events_delivered() {
+        trace_init();
+        let config = standard_scheduled_test_config();
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+
+        let task = tokio::spawn(run_command(
+            config,
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
+            Default::default(),
+            true,
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ));
+
+        let events = tokio::time::timeout(time::Duration::from_secs(5), rx.collect::<Vec<_>>())
+            .await
+            .expect("command timed out");
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "Hello World!".into()
+        );
+
+        let exit_status = task.await.unwrap().expect("command error");
+        assert_eq!(0_i32, exit_status.unwrap().code().unwrap());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_rejected_events_kill_command() {
+        trace_init();
+        let mut config = standard_streaming_test_config();
+        if let Some(streaming) = config.streaming.as_mut() {
+            streaming.delivery_retries = 0;
+            streaming.on_delivery_failure = DeliveryFailureAction::Kill;
+        }
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Rejected);
+
+        // `yes` never exits on its own, so the run only finishes once the command is killed.
+        let task = tokio::spawn(run_command(
+            config,
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
+            Default::default(),
+            true,
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ));
+
+        let events = tokio::time::timeout(time::Duration::from_secs(5), rx.collect::<Vec<_>>())
+            .await
+            .expect("command was not killed");
+        assert_eq!(events.len(), 1);
+
+        task.await.unwrap().expect("command error");
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_heartbeats_keep_quiet_command_alive() {
+        trace_init();
+        let mut config = standard_streaming_test_config();
+        config.command = vec![
+            String::from("bash"),
+            String::from("-c"),
+            String::from(
+                r#"for i in 1 2 3 4 ; do echo ok >&$VECTOR_HEARTBEAT_FD ; sleep 0.5 ; done ; echo done"#,
+            ),
+        ];
+        if let Some(streaming) = config.streaming.as_mut() {
+            streaming.heartbeat = Some(HeartbeatConfig { timeout_secs: 1 });
+        }
+        let (tx, rx) = SourceSender::new_test();
+
+        let task = tokio::spawn(run_command(
+            config,
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
+            Default::default(),
+            false,
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ));
+
+        let events = tokio::time::timeout(time::Duration::from_secs(10), rx.collect::<Vec<_>>())
+            .await
+            .expect("command timed out");
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "done".into()
+        );
+
+        let exit_status = task.await.unwrap().expect("command error");
+        assert_eq!(0_i32, exit_status.unwrap().code().unwrap());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_missed_heartbeats_kill_command() {
+        trace_init();
+        let mut config = standard_streaming_test_config();
+        config.command = vec![
+            String::from("bash"),
+            String::from("-c"),
+            String::from(r#"echo ok >&3 ; echo started ; sleep 60"#),
+        ];
+        if let Some(streaming) = config.streaming.as_mut() {
+            streaming.heartbeat = Some(HeartbeatConfig { timeout_secs: 1 });
+        }
+        let (tx, rx) = SourceSender::new_test();
+
+        // The command doesn't exit on its own for a minute, so the run only finishes quickly once
+        // the command is killed.
+        let task = tokio::spawn(run_command(
+            config,
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
+            Default::default(),
+            false,
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ));
+
+        let events = tokio::time::timeout(time::Duration::from_secs(10), rx.collect::<Vec<_>>())
+            .await
+            .expect("command was not killed");
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "started".into()
+        );
+
+        task.await.unwrap().expect("command error");
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn test_graceful_shutdown() {
@@ -1072,7 +2436,12 @@ mod tests {
         let task = tokio::spawn(run_command(
             config.clone(),
             hostname,
+            None,
             decoder,
+            None,
+            None,
+            Default::default(),
+            false,
             shutdown,
             tx,
             LogNamespace::Legacy,
@@ -1119,13 +2488,29 @@ mod tests {
             streaming: Some(StreamingConfig {
                 respawn_on_exit: default_respawn_on_exit(),
                 respawn_interval_secs: default_respawn_interval_secs(),
+                max_backoff_secs: None,
+                max_restarts: None,
+                jitter: false,
+                delivery_retries: default_delivery_retries(),
+                on_delivery_failure: DeliveryFailureAction::default(),
+                heartbeat: None,
             }),
             command: vec!["yes".to_owned()],
+            environment: IndexMap::new(),
             working_directory: None,
             include_stderr: default_include_stderr(),
+            stderr: None,
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
+            sampling_rate: None,
+            sampling_mode: SamplingMode::default(),
+            decode_stall_timeout_secs: None,
+            always_emit_completion: false,
+            reap_orphans: false,
+            data_dir: None,
+            acknowledgements: Default::default(),
             log_namespace: None,
         }
     }
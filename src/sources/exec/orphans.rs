@@ -0,0 +1,256 @@
+//! Tracking of spawned child processes across restarts of Vector.
+//!
+//! When Vector exits uncleanly, children spawned by the `exec` source can be left running and get
+//! reparented to init. To be able to clean those up, every spawned child is recorded in a small
+//! state file in the source's data directory, along with a start token that identifies that
+//! particular process instance. On startup, any recorded process that still exists and still
+//! looks like one of our children is sent a `SIGTERM` before new children are spawned.
+//!
+//! Identifying a process from a previous run is inherently heuristic, since PIDs are reused by
+//! the operating system. A recorded process is only considered an orphan when all of the following
+//! hold:
+//!
+//! - a process with the recorded PID exists,
+//! - its start time (as reported in `/proc/<pid>/stat`) matches the recorded start token,
+//! - its command line matches the configured command exactly,
+//! - and it is not a child of the current Vector process.
+//!
+//! Start times are only available on Linux, so orphans are never reaped on other platforms. Even
+//! on Linux, a PID that was reused by an identical command started within the same clock tick
+//! would be misidentified; this is considered unlikely enough to be acceptable.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal_events::{ExecChildStateError, ExecOrphanReaped};
+
+const STATE_FILENAME: &str = "children.json";
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct ChildRecord {
+    pid: u32,
+    start_token: Option<u64>,
+}
+
+/// Records the children spawned by an `exec` source in its data directory.
+#[derive(Clone, Debug)]
+pub struct ChildTracker {
+    path: PathBuf,
+    children: Arc<Mutex<Vec<ChildRecord>>>,
+}
+
+impl ChildTracker {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(STATE_FILENAME),
+            children: Arc::default(),
+        }
+    }
+
+    /// Terminates any processes recorded by a previous run that are still alive and match
+    /// `command`, then clears the recorded state.
+    pub fn reap_orphans(&self, command: &[String]) {
+        let recorded = match self.load() {
+            Ok(recorded) => recorded,
+            Err(error) => {
+                emit!(ExecChildStateError {
+                    path: &self.path,
+                    error,
+                });
+                Vec::new()
+            }
+        };
+
+        for record in recorded {
+            if is_orphan(&record, command) && terminate(record.pid) {
+                emit!(ExecOrphanReaped {
+                    command: command.join(" ").as_str(),
+                    pid: record.pid,
+                });
+            }
+        }
+
+        let mut children = self.children.lock().expect("poisoned lock");
+        children.clear();
+        self.persist(&children);
+    }
+
+    /// Records a newly spawned child.
+    pub fn track(&self, pid: u32) {
+        let mut children = self.children.lock().expect("poisoned lock");
+        children.push(ChildRecord {
+            pid,
+            start_token: start_token(pid),
+        });
+        self.persist(&children);
+    }
+
+    /// Forgets a child that has exited or been reaped.
+    pub fn untrack(&self, pid: u32) {
+        let mut children = self.children.lock().expect("poisoned lock");
+        children.retain(|record| record.pid != pid);
+        self.persist(&children);
+    }
+
+    fn load(&self) -> io::Result<Vec<ChildRecord>> {
+        match fs::read(&self.path) {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn persist(&self, children: &[ChildRecord]) {
+        // Write to a temporary file first so a crash never leaves a truncated state file behind.
+        let tmp_path = self.path.with_extension("json.tmp");
+        let result = serde_json::to_vec(children)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+            .and_then(|contents| fs::write(&tmp_path, contents))
+            .and_then(|_| fs::rename(&tmp_path, &self.path));
+
+        if let Err(error) = result {
+            emit!(ExecChildStateError {
+                path: &self.path,
+                error,
+            });
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_orphan(record: &ChildRecord, command: &[String]) -> bool {
+    let stat = match fs::read_to_string(format!("/proc/{}/stat", record.pid)) {
+        Ok(stat) => stat,
+        // The process no longer exists.
+        Err(_) => return false,
+    };
+
+    let start_token = parse_start_token(&stat);
+    if record.start_token.is_none() || start_token != record.start_token {
+        return false;
+    }
+
+    if parse_parent_pid(&stat) == Some(std::process::id()) {
+        return false;
+    }
+
+    fs::read(format!("/proc/{}/cmdline", record.pid))
+        .map(|cmdline| parse_cmdline(&cmdline) == command)
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_orphan(_record: &ChildRecord, _command: &[String]) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn start_token(pid: u32) -> Option<u64> {
+    fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|stat| parse_start_token(&stat))
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn start_token(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(unix)]
+fn terminate(pid: u32) -> bool {
+    i32::try_from(pid)
+        .ok()
+        .map(|pid| {
+            nix::sys::signal::kill(
+                nix::unistd::Pid::from_raw(pid),
+                nix::sys::signal::Signal::SIGTERM,
+            )
+            .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+const fn terminate(_pid: u32) -> bool {
+    false
+}
+
+/// Returns the fields of `/proc/<pid>/stat` that follow the command name.
+///
+/// The command name is wrapped in parentheses and may itself contain spaces and parentheses, so
+/// the fields are located relative to the last closing parenthesis.
+#[cfg(any(target_os = "linux", test))]
+fn stat_fields(stat: &str) -> impl Iterator<Item = &str> {
+    let rest = stat.rfind(')').map(|idx| &stat[idx + 1..]).unwrap_or("");
+    rest.split_whitespace()
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_parent_pid(stat: &str) -> Option<u32> {
+    // `ppid` is the 4th field, the 2nd after the command name.
+    stat_fields(stat).nth(1).and_then(|ppid| ppid.parse().ok())
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_start_token(stat: &str) -> Option<u64> {
+    // `starttime` is the 22nd field, the 20th after the command name.
+    stat_fields(stat)
+        .nth(19)
+        .and_then(|starttime| starttime.parse().ok())
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_cmdline(cmdline: &[u8]) -> Vec<String> {
+    cmdline
+        .split(|byte| *byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "4242 (my (odd) cmd) S 1 4242 4242 0 -1 4194560 120 0 0 0 0 0 0 0 20 0 1 0 987654 1000 100 18446744073709551615";
+
+    #[test]
+    fn parses_stat_fields() {
+        assert_eq!(parse_parent_pid(STAT), Some(1));
+        assert_eq!(parse_start_token(STAT), Some(987654));
+        assert_eq!(parse_start_token("garbage"), None);
+    }
+
+    #[test]
+    fn parses_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"bash\0-c\0sleep 10\0"),
+            vec!["bash".to_owned(), "-c".to_owned(), "sleep 10".to_owned()]
+        );
+    }
+
+    #[test]
+    fn tracks_children_in_state_file() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let tracker = ChildTracker::new(data_dir.path());
+
+        tracker.track(1234);
+        tracker.track(5678);
+        tracker.untrack(1234);
+
+        let recorded = tracker.load().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].pid, 5678);
+
+        // A fresh tracker picks up the state left behind, and clears it once orphans are reaped.
+        let tracker = ChildTracker::new(data_dir.path());
+        tracker.reap_orphans(&["vector-exec-test-no-such-command".to_owned()]);
+        assert!(tracker.load().unwrap().is_empty());
+    }
+}
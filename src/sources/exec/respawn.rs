@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Determines how long to wait before respawning a streaming command, and when to stop.
+///
+/// The delay starts at `interval` and doubles after each consecutive respawn, up to
+/// `max_interval`. When no maximum is set, the delay stays constant. A run that lasts at least as
+/// long as the maximum delay is considered healthy, and resets both the delay and the count of
+/// consecutive respawns.
+#[derive(Clone, Debug)]
+pub struct RespawnPolicy {
+    interval: Duration,
+    max_interval: Duration,
+    max_respawns: Option<u64>,
+    jitter: bool,
+    current: Duration,
+    respawns: u64,
+}
+
+impl RespawnPolicy {
+    pub fn new(
+        interval: Duration,
+        max_interval: Option<Duration>,
+        max_respawns: Option<u64>,
+        jitter: bool,
+    ) -> Self {
+        Self {
+            interval,
+            max_interval: max_interval.unwrap_or(interval).max(interval),
+            max_respawns,
+            jitter,
+            current: interval,
+            respawns: 0,
+        }
+    }
+
+    /// Records that a run lasted for `run_duration`, resetting the policy if the run was healthy.
+    pub fn record_run(&mut self, run_duration: Duration) {
+        if run_duration >= self.max_interval {
+            self.current = self.interval;
+            self.respawns = 0;
+        }
+    }
+
+    /// Returns the delay before the next respawn, or `None` if the maximum number of consecutive
+    /// respawns has been reached.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        if self
+            .max_respawns
+            .map_or(false, |max_respawns| self.respawns >= max_respawns)
+        {
+            return None;
+        }
+
+        let delay = self.current;
+        self.current = self.current.saturating_mul(2).min(self.max_interval);
+        self.respawns += 1;
+
+        if self.jitter {
+            // Equal jitter: wait for at least half of the delay, so respawns are spread out
+            // without ever respawning immediately.
+            let half = delay / 2;
+            let jitter_millis = rand::thread_rng().gen_range(0..=half.as_millis() as u64);
+            Some(half + Duration::from_millis(jitter_millis))
+        } else {
+            Some(delay)
+        }
+    }
+
+    /// The number of consecutive respawns so far.
+    pub const fn respawns(&self) -> u64 {
+        self.respawns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_delay_by_default() {
+        let mut policy = RespawnPolicy::new(Duration::from_secs(5), None, None, false);
+        for _ in 0..10 {
+            assert_eq!(policy.next_delay(), Some(Duration::from_secs(5)));
+        }
+    }
+
+    #[test]
+    fn exponential_delay_up_to_maximum() {
+        let mut policy = RespawnPolicy::new(
+            Duration::from_secs(1),
+            Some(Duration::from_secs(5)),
+            None,
+            false,
+        );
+        let delays = (0..5)
+            .map(|_| policy.next_delay().unwrap().as_secs())
+            .collect::<Vec<_>>();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+
+        policy.record_run(Duration::from_secs(1));
+        assert_eq!(policy.next_delay(), Some(Duration::from_secs(5)));
+
+        policy.record_run(Duration::from_secs(5));
+        assert_eq!(policy.next_delay(), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn stops_after_max_respawns() {
+        let mut policy = RespawnPolicy::new(
+            Duration::from_secs(1),
+            Some(Duration::from_secs(10)),
+            Some(2),
+            false,
+        );
+        assert!(policy.next_delay().is_some());
+        assert!(policy.next_delay().is_some());
+        assert_eq!(policy.respawns(), 2);
+        assert_eq!(policy.next_delay(), None);
+
+        policy.record_run(Duration::from_secs(10));
+        assert!(policy.next_delay().is_some());
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let mut policy = RespawnPolicy::new(Duration::from_secs(4), None, None, true);
+        for _ in 0..100 {
+            let delay = policy.next_delay().unwrap();
+            assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(4));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use rand::Rng;
+use vector_config::configurable_component;
+
+use crate::event::Event;
+
+/// The method used to select which events are emitted when sampling.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingMode {
+    /// Each event is emitted with a probability of `1/N`, independently of its contents.
+    #[default]
+    Random,
+
+    /// The message of each event is hashed to decide whether it is emitted.
+    ///
+    /// Identical messages are consistently either emitted or sampled out. The actual sampling rate
+    /// may differ from the configured one if messages are not uniformly distributed.
+    Hash,
+}
+
+/// Decides which decoded events are emitted when a sampling rate is configured.
+#[derive(Clone, Copy, Debug)]
+pub struct Sampler {
+    rate: u64,
+    mode: SamplingMode,
+}
+
+impl Sampler {
+    pub const fn new(rate: u64, mode: SamplingMode) -> Self {
+        Self { rate, mode }
+    }
+
+    pub fn should_emit(&self, event: &Event) -> bool {
+        match (self.mode, event) {
+            (SamplingMode::Hash, Event::Log(log)) => {
+                let value = log.get_message().unwrap_or_else(|| log.value());
+                seahash::hash(value.to_string_lossy().as_bytes()) % self.rate == 0
+            }
+            // Metrics and traces don't have a message to hash.
+            _ => rand::thread_rng().gen_range(0..self.rate) == 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn rate_of_one_emits_everything() {
+        for mode in [SamplingMode::Random, SamplingMode::Hash] {
+            let sampler = Sampler::new(1, mode);
+            assert!((0..100).all(|i| sampler.should_emit(&LogEvent::from(format!("{}", i)).into())));
+        }
+    }
+
+    #[test]
+    fn hash_sampling_is_deterministic() {
+        let sampler = Sampler::new(4, SamplingMode::Hash);
+        let events = (0..1000)
+            .map(|i| Event::from(LogEvent::from(format!("line {}", i))))
+            .collect::<Vec<_>>();
+
+        let first = events
+            .iter()
+            .map(|event| sampler.should_emit(event))
+            .collect::<Vec<_>>();
+        let second = events
+            .iter()
+            .map(|event| sampler.should_emit(event))
+            .collect::<Vec<_>>();
+        assert_eq!(first, second);
+
+        let emitted = first.iter().filter(|emitted| **emitted).count();
+        assert!(emitted > 150 && emitted < 350, "emitted {} events", emitted);
+    }
+}
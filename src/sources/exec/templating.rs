@@ -0,0 +1,163 @@
+//! The templating of the command and its environment with the values known when it is run.
+//!
+//! The placeholders are written `{{ <variable> }}`, with the variables being:
+//!
+//! * `hostname`: the host name of the system Vector is running on.
+//! * `window_start` and `window_end`: the boundaries of the window of a scheduled run, in RFC 3339.
+//! * `window_start_unix` and `window_end_unix`: the same boundaries, in seconds since the epoch.
+use chrono::{DateTime, SecondsFormat, Utc};
+
+const VARIABLES: &str =
+    "`hostname`, `window_start`, `window_end`, `window_start_unix`, and `window_end_unix`";
+
+/// The window of time covered by a scheduled run.
+///
+/// Each window starts where the window of the last successful run ended, so a command can fetch
+/// what happened since then without records being missed or fetched twice.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) struct Window {
+    pub(super) start: DateTime<Utc>,
+    pub(super) end: DateTime<Utc>,
+}
+
+/// The values the placeholders are replaced with.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct TemplateValues<'a> {
+    pub(super) hostname: Option<&'a str>,
+    pub(super) window: Option<Window>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Variable {
+    Hostname,
+    WindowStart,
+    WindowEnd,
+    WindowStartUnix,
+    WindowEndUnix,
+}
+
+impl Variable {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "hostname" => Some(Self::Hostname),
+            "window_start" => Some(Self::WindowStart),
+            "window_end" => Some(Self::WindowEnd),
+            "window_start_unix" => Some(Self::WindowStartUnix),
+            "window_end_unix" => Some(Self::WindowEndUnix),
+            _ => None,
+        }
+    }
+
+    const fn is_window(self) -> bool {
+        !matches!(self, Self::Hostname)
+    }
+
+    fn render(self, values: &TemplateValues<'_>, rendered: &mut String) {
+        let time = |window: Window| match self {
+            Self::WindowStart | Self::WindowStartUnix => window.start,
+            _ => window.end,
+        };
+        match (self, values.window) {
+            (Self::Hostname, _) => rendered.push_str(values.hostname.unwrap_or_default()),
+            (Self::WindowStart | Self::WindowEnd, Some(window)) => {
+                rendered.push_str(&time(window).to_rfc3339_opts(SecondsFormat::Secs, true))
+            }
+            (Self::WindowStartUnix | Self::WindowEndUnix, Some(window)) => {
+                rendered.push_str(&time(window).timestamp().to_string())
+            }
+            // Rejected by `validate` outside of the scheduled mode.
+            (_, None) => {}
+        }
+    }
+}
+
+enum Part<'a> {
+    Literal(&'a str),
+    Variable(Variable),
+}
+
+fn parse(value: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| "the placeholder isn't closed with `}}`".to_owned())?;
+        let name = rest[start + 2..start + end].trim();
+        let variable = Variable::parse(name).ok_or_else(|| {
+            format!(
+                "unknown variable {:?}, the variables are {}",
+                name, VARIABLES
+            )
+        })?;
+
+        parts.push(Part::Literal(&rest[..start]));
+        parts.push(Part::Variable(variable));
+        rest = &rest[start + end + 2..];
+    }
+    parts.push(Part::Literal(rest));
+    Ok(parts)
+}
+
+/// Checks that the placeholders of `value` are valid, and that the window is only used in the
+/// scheduled mode.
+pub(super) fn validate(value: &str, scheduled: bool) -> Result<(), String> {
+    for part in parse(value)? {
+        if let Part::Variable(variable) = part {
+            if variable.is_window() && !scheduled {
+                return Err("the window is only known in the scheduled mode".to_owned());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the placeholders of `value` with the given values.
+pub(super) fn render(value: &str, values: &TemplateValues<'_>) -> Result<String, String> {
+    let mut rendered = String::with_capacity(value.len());
+    for part in parse(value)? {
+        match part {
+            Part::Literal(literal) => rendered.push_str(literal),
+            Part::Variable(variable) => variable.render(values, &mut rendered),
+        }
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn renders_the_placeholders() {
+        let values = TemplateValues {
+            hostname: Some("host-1"),
+            window: Some(Window {
+                start: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+                end: Utc.timestamp_opt(1_700_000_060, 0).unwrap(),
+            }),
+        };
+
+        assert_eq!(
+            render("--since={{ window_start }}&until={{window_end}}", &values).unwrap(),
+            "--since=2023-11-14T22:13:20Z&until=2023-11-14T22:14:20Z"
+        );
+        assert_eq!(
+            render("{{ window_start_unix }}-{{ window_end_unix }}", &values).unwrap(),
+            "1700000000-1700000060"
+        );
+        assert_eq!(render("{{ hostname }}", &values).unwrap(), "host-1");
+        assert_eq!(render("plain", &values).unwrap(), "plain");
+    }
+
+    #[test]
+    fn validates_the_placeholders() {
+        assert!(validate("{{ hostname }}", false).is_ok());
+        assert!(validate("{{ window_start }}", true).is_ok());
+        assert!(validate("{{ window_start }}", false).is_err());
+        assert!(validate("{{ window_middle }}", true).is_err());
+        assert!(validate("{{ hostname", true).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Detection of read/decode loops that stop making progress.
+//!
+//! Each stream read from the child tracks when it last made progress, that is, when the decoder
+//! last produced events, or when bytes were read while none were waiting to be decoded. A stream
+//! is considered stalled when bytes have been read that haven't resulted in events yet, and no
+//! progress has been made for longer than the configured threshold. Reading more bytes isn't
+//! progress, as a decoder can keep buffering them without ever producing a frame. A child that is
+//! simply quiet has no such pending bytes, and is never considered stalled.
+
+use std::{
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Tracks the progress of reading and decoding a single output stream of a child.
+#[derive(Debug)]
+pub struct DecodeProgress {
+    started: Instant,
+    last_progress_millis: AtomicU64,
+    pending: AtomicBool,
+}
+
+impl Default for DecodeProgress {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            last_progress_millis: AtomicU64::new(0),
+            pending: AtomicBool::new(false),
+        }
+    }
+}
+
+impl DecodeProgress {
+    fn touch(&self) {
+        let elapsed = self.started.elapsed().as_millis() as u64;
+        self.last_progress_millis.store(elapsed, Ordering::Relaxed);
+    }
+
+    /// Records that bytes were read from the stream.
+    pub fn bytes_read(&self) {
+        // The stall starts with the first bytes waiting to be decoded.
+        if !self.pending.swap(true, Ordering::Relaxed) {
+            self.touch();
+        }
+    }
+
+    /// Records that the decoder produced events, and whether bytes are still buffered after them.
+    pub fn events_decoded(&self, buffered: bool) {
+        self.pending.store(buffered, Ordering::Relaxed);
+        self.touch();
+    }
+
+    /// Records that the decoder failed, and whether bytes are still buffered after the failure.
+    pub fn decode_failed(&self, buffered: bool) {
+        if !buffered {
+            self.pending.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns how long the stream has been stalled for, if it has been longer than `threshold`.
+    pub fn stalled_for(&self, threshold: Duration) -> Option<Duration> {
+        if !self.pending.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let last_progress =
+            Duration::from_millis(self.last_progress_millis.load(Ordering::Relaxed));
+        let stalled_for = self.started.elapsed().saturating_sub(last_progress);
+        (stalled_for > threshold).then_some(stalled_for)
+    }
+}
+
+/// Wraps a reader to record in [`DecodeProgress`] whenever bytes are read.
+pub struct ProgressReader<R> {
+    inner: R,
+    progress: Arc<DecodeProgress>,
+}
+
+impl<R> ProgressReader<R> {
+    pub const fn new(inner: R, progress: Arc<DecodeProgress>) -> Self {
+        Self { inner, progress }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ProgressReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if matches!(result, Poll::Ready(Ok(()))) && buf.filled().len() > filled {
+            self.progress.bytes_read();
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_pending_bytes_can_stall() {
+        let progress = DecodeProgress::default();
+        assert_eq!(progress.stalled_for(Duration::ZERO), None);
+
+        progress.bytes_read();
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(progress.stalled_for(Duration::ZERO).is_some());
+        assert_eq!(progress.stalled_for(Duration::from_secs(60)), None);
+
+        progress.events_decoded(false);
+        assert_eq!(progress.stalled_for(Duration::ZERO), None);
+    }
+
+    #[test]
+    fn reading_more_bytes_is_not_progress() {
+        let progress = DecodeProgress::default();
+
+        progress.bytes_read();
+        std::thread::sleep(Duration::from_millis(20));
+        progress.bytes_read();
+        progress.decode_failed(true);
+        assert!(progress.stalled_for(Duration::from_millis(10)).is_some());
+
+        progress.events_decoded(true);
+        assert_eq!(progress.stalled_for(Duration::from_millis(10)), None);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(progress.stalled_for(Duration::from_millis(10)).is_some());
+    }
+}
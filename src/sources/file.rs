@@ -698,7 +698,9 @@ fn wrap_with_line_agg(
     rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
     config: line_agg::Config,
 ) -> Box<dyn Stream<Item = Line> + Send + std::marker::Unpin + 'static> {
-    let logic = line_agg::Logic::new(config);
+    // A new file under the same name means the previous one was rotated, so whatever was
+    // aggregated from it is complete.
+    let logic = line_agg::Logic::new(config).with_source_check(same_file);
     Box::new(
         LineAgg::new(
             rx.map(|line| {
@@ -722,6 +724,10 @@ fn wrap_with_line_agg(
     )
 }
 
+fn same_file(a: &(FileFingerprint, u64, u64), b: &(FileFingerprint, u64, u64)) -> bool {
+    a.0 == b.0
+}
+
 struct EventMetadata {
     host_key: Option<OwnedValuePath>,
     hostname: Option<String>,
@@ -1792,8 +1798,10 @@ mod tests {
         let config = file::FileConfig {
             include: vec![dir.path().join("*")],
             multiline: Some(MultilineConfig {
-                start_pattern: "INFO".to_owned(),
-                condition_pattern: "INFO".to_owned(),
+                start_pattern: Some("INFO".to_owned()),
+                start_condition: None,
+                condition_pattern: Some("INFO".to_owned()),
+                condition: None,
                 mode: line_agg::Mode::HaltBefore,
                 timeout_ms: Duration::from_millis(25), // less than 50 in sleep()
             }),
@@ -1851,8 +1859,10 @@ mod tests {
         let config = file::FileConfig {
             include: vec![dir.path().join("*")],
             multiline: Some(MultilineConfig {
-                start_pattern: "INFO".to_owned(),
-                condition_pattern: "INFO".to_owned(),
+                start_pattern: Some("INFO".to_owned()),
+                start_condition: None,
+                condition_pattern: Some("INFO".to_owned()),
+                condition: None,
                 mode: line_agg::Mode::HaltBefore,
                 timeout_ms: Duration::from_millis(25), // less than 50 in sleep()
             }),
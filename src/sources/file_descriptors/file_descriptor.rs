@@ -8,6 +8,7 @@ use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
 use crate::{
+    codecs::DecodeErrorPolicy,
     config::{GenerateConfig, Output, Resource, SourceConfig, SourceContext},
     serde::default_decoding,
 };
@@ -36,6 +37,10 @@ pub struct FileDescriptorSourceConfig {
     #[serde(default = "default_decoding")]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
     /// The file descriptor number to read from.
     #[configurable(metadata(docs::examples = 10))]
     pub fd: u32,
@@ -59,6 +64,10 @@ impl FileDescriptorConfig for FileDescriptorSourceConfig {
         self.decoding.clone()
     }
 
+    fn on_decode_error(&self) -> DecodeErrorPolicy {
+        self.on_decode_error
+    }
+
     fn description(&self) -> String {
         format!("file descriptor {}", self.fd)
     }
@@ -85,7 +94,13 @@ impl SourceConfig for FileDescriptorSourceConfig {
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
 
-        outputs(log_namespace, &self.host_key, &self.decoding, Self::NAME)
+        outputs(
+            log_namespace,
+            &self.host_key,
+            &self.decoding,
+            self.on_decode_error,
+            Self::NAME,
+        )
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -127,6 +142,7 @@ mod tests {
                 host_key: Default::default(),
                 framing: None,
                 decoding: default_decoding(),
+                on_decode_error: Default::default(),
                 fd: read_fd as u32,
                 log_namespace: None,
             };
@@ -171,6 +187,7 @@ mod tests {
                 host_key: Default::default(),
                 framing: None,
                 decoding: default_decoding(),
+                on_decode_error: Default::default(),
                 fd: read_fd as u32,
                 log_namespace: Some(true),
             };
@@ -220,6 +237,7 @@ mod tests {
                 host_key: Default::default(),
                 framing: None,
                 decoding: default_decoding(),
+                on_decode_error: Default::default(),
                 fd: write_fd as u32, // intentionally giving the source a write-only fd
                 log_namespace: None,
             };
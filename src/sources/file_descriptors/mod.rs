@@ -25,7 +25,7 @@ use vector_core::{
 };
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::log_schema,
     internal_events::{EventsReceived, FileDescriptorReadError, StreamClosedError},
     shutdown::ShutdownSignal,
@@ -41,6 +41,7 @@ pub trait FileDescriptorConfig: NamedComponent {
     fn host_key(&self) -> Option<OptionalValuePath>;
     fn framing(&self) -> Option<FramingConfig>;
     fn decoding(&self) -> DeserializerConfig;
+    fn on_decode_error(&self) -> DecodeErrorPolicy;
     fn description(&self) -> String;
 
     fn source<R>(
@@ -65,7 +66,9 @@ pub trait FileDescriptorConfig: NamedComponent {
         let framing = self
             .framing()
             .unwrap_or_else(|| decoding.default_stream_framing());
-        let decoder = DecodingConfig::new(framing, decoding, log_namespace).build();
+        let decoder = DecodingConfig::new(framing, decoding, log_namespace)
+            .with_error_policy(self.on_decode_error())
+            .build()?;
 
         let (sender, receiver) = mpsc::channel(1024);
 
@@ -209,6 +212,7 @@ fn outputs(
     log_namespace: LogNamespace,
     host_key: &Option<OptionalValuePath>,
     decoding: &DeserializerConfig,
+    on_decode_error: DecodeErrorPolicy,
     source_name: &'static str,
 ) -> Vec<Output> {
     let legacy_host_key = Some(LegacyKey::InsertIfEmpty(
@@ -228,5 +232,8 @@ fn outputs(
         )
         .with_standard_vector_source_metadata();
 
-    vec![Output::default(decoding.output_type()).with_schema_definition(schema_definition)]
+    let mut outputs =
+        vec![Output::default(decoding.output_type()).with_schema_definition(schema_definition)];
+    outputs.extend(on_decode_error.output(log_namespace));
+    outputs
 }
@@ -6,6 +6,7 @@ use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
 use crate::{
+    codecs::DecodeErrorPolicy,
     config::{Output, Resource, SourceConfig, SourceContext},
     serde::default_decoding,
 };
@@ -39,6 +40,10 @@ pub struct StdinConfig {
     #[serde(default = "default_decoding")]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -58,6 +63,10 @@ impl FileDescriptorConfig for StdinConfig {
         self.decoding.clone()
     }
 
+    fn on_decode_error(&self) -> DecodeErrorPolicy {
+        self.on_decode_error
+    }
+
     fn description(&self) -> String {
         Self::NAME.to_string()
     }
@@ -70,6 +79,7 @@ impl Default for StdinConfig {
             host_key: Default::default(),
             framing: None,
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             log_namespace: None,
         }
     }
@@ -92,7 +102,13 @@ impl SourceConfig for StdinConfig {
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
 
-        outputs(log_namespace, &self.host_key, &self.decoding, Self::NAME)
+        outputs(
+            log_namespace,
+            &self.host_key,
+            &self.decoding,
+            self.on_decode_error,
+            Self::NAME,
+        )
     }
 
     fn resources(&self) -> Vec<Resource> {
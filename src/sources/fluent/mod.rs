@@ -19,7 +19,9 @@ use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 use vector_core::schema::Definition;
 
-use super::util::net::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker};
+use super::util::net::{
+    SocketListenAddr, TcpConnectionLimits, TcpSource, TcpSourceAck, TcpSourceAcker,
+};
 use crate::{
     config::{
         log_schema, DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig,
@@ -107,7 +109,8 @@ impl SourceConfig for FluentConfig {
             None,
             cx,
             self.acknowledgements,
-            self.connection_limit,
+            TcpConnectionLimits::new(self.connection_limit),
+            false,
             FluentConfig::NAME,
             log_namespace,
         )
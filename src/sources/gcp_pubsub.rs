@@ -20,6 +20,7 @@ use tonic::{
     transport::{Certificate, ClientTlsConfig, Endpoint, Identity},
     Code, Request, Status,
 };
+use tracing::Instrument;
 use value::{kind::Collection, Kind};
 use vector_common::internal_event::{
     ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol, Registered,
@@ -29,7 +30,7 @@ use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{DataType, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
     event::{BatchNotifier, BatchStatus, Event, MaybeAsLogMut, Value},
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope, PUBSUB_URL},
@@ -208,6 +209,10 @@ pub struct PubsubConfig {
     #[derivative(Default(value = "default_decoding()"))]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     pub acknowledgements: SourceAcknowledgementsConfig,
@@ -304,7 +309,8 @@ impl SourceConfig for PubsubConfig {
                 self.decoding.clone(),
                 log_namespace,
             )
-            .build(),
+            .with_error_policy(self.on_decode_error)
+            .build()?,
             acknowledgements: cx.do_acknowledgements(self.acknowledgements),
             shutdown: cx.shutdown,
             out: cx.out,
@@ -350,7 +356,10 @@ impl SourceConfig for PubsubConfig {
                 None,
             );
 
-        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(DataType::Log).with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -433,7 +442,7 @@ impl PubsubSource {
         // when it has an idle interval it will mark itself as not
         // busy.
         let busy_flag = Arc::new(AtomicBool::new(false));
-        let task = tokio::spawn(self.clone().run(Arc::clone(&busy_flag)));
+        let task = tokio::spawn(self.clone().run(Arc::clone(&busy_flag)).in_current_span());
         tasks.push(Task { task, busy_flag });
     }
 
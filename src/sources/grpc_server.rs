@@ -0,0 +1,720 @@
+//! The `grpc_server` source. See [GrpcServerConfig].
+//!
+//! Serves a single method of a user-defined gRPC service, whose messages are described by a
+//! compiled file descriptor set, and decodes the messages of the requests into events.
+use std::{convert::Infallible, net::SocketAddr, path::PathBuf, sync::Arc, task::Poll};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chrono::Utc;
+use codecs::{
+    decoding::{Deserializer, Framer},
+    BytesDecoder, ProtobufDeserializer, ProtobufSerializer,
+};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
+use http::{Request, Response};
+use hyper::Body;
+use prost_reflect::{DescriptorPool, MethodDescriptor};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::{Decoder as _, Encoder as _};
+use tonic::{
+    body::BoxBody,
+    codec::{Codec, CompressionEncoding, DecodeBuf, EncodeBuf},
+    server::{
+        ClientStreamingService, Grpc, ServerStreamingService, StreamingService, UnaryService,
+    },
+    Status, Streaming,
+};
+use value::Kind;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    schema::Definition,
+};
+
+use crate::{
+    codecs::Decoder,
+    config::{
+        DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
+    internal_events::{EventsReceived, StreamClosedError},
+    serde::bool_or_struct,
+    sources::{util::grpc::run_grpc_server_with_service, Source},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed reading the file descriptor set {:?}: {}", path, source))]
+    ReadDescriptorSet {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed parsing the file descriptor set: {}", source))]
+    ParseDescriptorSet {
+        source: prost_reflect::DescriptorError,
+    },
+    #[snafu(display(
+        "Invalid method {:?}, it must be formatted as `package.Service/Method`",
+        method
+    ))]
+    InvalidMethod { method: String },
+    #[snafu(display("The file descriptor set doesn't define the service {:?}", service))]
+    UnknownService { service: String },
+    #[snafu(display("The service {:?} has no method {:?}", service, method))]
+    UnknownMethod { service: String, method: String },
+    #[snafu(display("Invalid response: {}", source))]
+    Response { source: crate::Error },
+}
+
+/// Configuration for the `grpc_server` source.
+#[configurable_component(source("grpc_server"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcServerConfig {
+    /// The socket address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    #[configurable(metadata(docs::examples = "0.0.0.0:50051"))]
+    address: SocketAddr,
+
+    /// The path to the compiled file descriptor set of the service.
+    ///
+    /// The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+    #[configurable(metadata(docs::examples = "/etc/vector/protobuf_descriptor_set.desc"))]
+    desc_file: PathBuf,
+
+    /// The method to serve, formatted as `package.Service/Method`.
+    ///
+    /// Unary and streaming methods are supported. Each message of a request becomes an event, and
+    /// requests for any other method are answered with the `UNIMPLEMENTED` status.
+    #[configurable(metadata(docs::examples = "telemetry.Collector/Publish"))]
+    method: String,
+
+    /// The fields of the response message returned once the events of a request are accepted.
+    ///
+    /// For streaming methods, a response is returned for each message of the request, unless the
+    /// method only streams requests. Fields that aren't set have their default value.
+    #[configurable(metadata(docs::examples = "response_example()"))]
+    #[serde(default)]
+    response: Option<toml::Value>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+fn response_example() -> toml::Value {
+    toml::from_str("accepted = true").unwrap()
+}
+
+impl GenerateConfig for GrpcServerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            address = "0.0.0.0:50051"
+            desc_file = "/etc/vector/protobuf_descriptor_set.desc"
+            method = "telemetry.Collector/Publish""#,
+        )
+        .unwrap()
+    }
+}
+
+impl GrpcServerConfig {
+    /// Looks up the served method in the file descriptor set.
+    fn method_descriptor(&self) -> Result<MethodDescriptor, BuildError> {
+        let (service, method) = match self.method.split_once('/') {
+            Some((service, method)) if !service.is_empty() && !method.is_empty() => {
+                (service, method)
+            }
+            _ => {
+                return Err(BuildError::InvalidMethod {
+                    method: self.method.clone(),
+                })
+            }
+        };
+
+        let bytes = std::fs::read(&self.desc_file).context(ReadDescriptorSetSnafu {
+            path: self.desc_file.clone(),
+        })?;
+        let pool = DescriptorPool::decode(bytes.as_slice()).context(ParseDescriptorSetSnafu)?;
+        let service_descriptor =
+            pool.get_service_by_name(service)
+                .ok_or_else(|| BuildError::UnknownService {
+                    service: service.to_owned(),
+                })?;
+        let method_descriptor = service_descriptor
+            .methods()
+            .find(|descriptor| descriptor.name() == method)
+            .ok_or_else(|| BuildError::UnknownMethod {
+                service: service.to_owned(),
+                method: method.to_owned(),
+            })?;
+        Ok(method_descriptor)
+    }
+
+    /// Encodes the configured response, which is the same for every request.
+    fn encode_response(&self, method: &MethodDescriptor) -> crate::Result<Bytes> {
+        let log = match &self.response {
+            Some(response) => LogEvent::try_from(serde_json::to_value(response)?)?,
+            None => LogEvent::default(),
+        };
+        let mut serializer = ProtobufSerializer::new(method.output());
+        let mut bytes = BytesMut::new();
+        serializer.encode(log.into(), &mut bytes)?;
+        Ok(bytes.freeze())
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for GrpcServerConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let method = self.method_descriptor()?;
+        let response = self.encode_response(&method).context(ResponseSnafu)?;
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+
+        let decoder = Decoder::new(
+            Framer::Bytes(BytesDecoder::new()),
+            Deserializer::Protobuf(ProtobufDeserializer::new(method.input())),
+        )
+        .with_log_namespace(log_namespace);
+        let handler = Handler {
+            decoder,
+            response,
+            pipeline: cx.out,
+            acknowledgements,
+            log_namespace,
+            events_received: register!(EventsReceived),
+        };
+        let service = MethodService {
+            path: format!("/{}/{}", method.parent_service().full_name(), method.name()).into(),
+            kind: MethodKind::from(&method),
+            handler,
+        };
+
+        let source = run_grpc_server_with_service(self.address, tls_settings, service, cx.shutdown)
+            .map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            });
+
+        Ok(Box::pin(source))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition = match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        Kind::any().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => {
+                Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+        .with_standard_vector_source_metadata();
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// The shape of the served method, that is whether its requests and responses are streamed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MethodKind {
+    Unary,
+    ClientStreaming,
+    ServerStreaming,
+    Streaming,
+}
+
+impl From<&MethodDescriptor> for MethodKind {
+    fn from(method: &MethodDescriptor) -> Self {
+        match (method.is_client_streaming(), method.is_server_streaming()) {
+            (false, false) => Self::Unary,
+            (true, false) => Self::ClientStreaming,
+            (false, true) => Self::ServerStreaming,
+            (true, true) => Self::Streaming,
+        }
+    }
+}
+
+/// Passes the messages through as bytes, which are decoded by the [`Handler`] instead.
+#[derive(Clone, Copy, Debug, Default)]
+struct BytesCodec;
+
+impl Codec for BytesCodec {
+    type Encode = Bytes;
+    type Decode = Bytes;
+    type Encoder = Self;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        Self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        Self
+    }
+}
+
+impl tonic::codec::Encoder for BytesCodec {
+    type Item = Bytes;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put(item);
+        Ok(())
+    }
+}
+
+impl tonic::codec::Decoder for BytesCodec {
+    type Item = Bytes;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining())))
+    }
+}
+
+/// Turns the messages of the requests into events.
+#[derive(Clone)]
+struct Handler {
+    decoder: Decoder,
+    response: Bytes,
+    pipeline: SourceSender,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    events_received: Registered<EventsReceived>,
+}
+
+impl Handler {
+    /// Sends the events decoded from the message, then waits for them to be delivered if
+    /// acknowledgements are enabled.
+    async fn handle(&self, message: Bytes) -> Result<(), Status> {
+        let mut decoder = self.decoder.clone();
+        let (events, byte_size) = decoder
+            .decode_eof(&mut BytesMut::from(message.as_ref()))
+            // Errors are reported by the decoder.
+            .map_err(|error| Status::invalid_argument(error.to_string()))?
+            .unwrap_or_default();
+        let mut events = events.into_vec();
+
+        let now = Utc::now();
+        for event in &mut events {
+            if let Event::Log(log) = event {
+                self.log_namespace.insert_standard_vector_source_metadata(
+                    log,
+                    GrpcServerConfig::NAME,
+                    now,
+                );
+            }
+        }
+
+        let count = events.len();
+        self.events_received.emit(CountByteSize(count, byte_size));
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        self.pipeline
+            .clone()
+            .send_batch(events)
+            .await
+            .map_err(|error| {
+                let message = error.to_string();
+                emit!(StreamClosedError { error, count });
+                Status::unavailable(message)
+            })?;
+
+        let status = match receiver {
+            Some(receiver) => receiver.await,
+            None => BatchStatus::Delivered,
+        };
+        match status {
+            BatchStatus::Errored => Err(Status::internal("Delivery error")),
+            BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+            BatchStatus::Delivered => Ok(()),
+        }
+    }
+
+    /// Handles the messages of a streamed request, as they arrive.
+    async fn handle_stream(&self, mut messages: Streaming<Bytes>) -> Result<(), Status> {
+        while let Some(message) = messages.message().await? {
+            self.handle(message).await?;
+        }
+        Ok(())
+    }
+}
+
+impl UnaryService<Bytes> for Handler {
+    type Response = Bytes;
+    type Future = BoxFuture<'static, Result<tonic::Response<Bytes>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Bytes>) -> Self::Future {
+        let handler = self.clone();
+        async move {
+            handler.handle(request.into_inner()).await?;
+            Ok(tonic::Response::new(handler.response.clone()))
+        }
+        .boxed()
+    }
+}
+
+impl ClientStreamingService<Bytes> for Handler {
+    type Response = Bytes;
+    type Future = BoxFuture<'static, Result<tonic::Response<Bytes>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<Bytes>>) -> Self::Future {
+        let handler = self.clone();
+        async move {
+            handler.handle_stream(request.into_inner()).await?;
+            Ok(tonic::Response::new(handler.response.clone()))
+        }
+        .boxed()
+    }
+}
+
+impl ServerStreamingService<Bytes> for Handler {
+    type Response = Bytes;
+    type ResponseStream = BoxStream<'static, Result<Bytes, Status>>;
+    type Future = BoxFuture<'static, Result<tonic::Response<Self::ResponseStream>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Bytes>) -> Self::Future {
+        let handler = self.clone();
+        async move {
+            handler.handle(request.into_inner()).await?;
+            let response =
+                futures::stream::once(futures::future::ok::<_, Status>(handler.response.clone()));
+            Ok(tonic::Response::new(response.boxed()))
+        }
+        .boxed()
+    }
+}
+
+impl StreamingService<Bytes> for Handler {
+    type Response = Bytes;
+    type ResponseStream = BoxStream<'static, Result<Bytes, Status>>;
+    type Future = BoxFuture<'static, Result<tonic::Response<Self::ResponseStream>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<Bytes>>) -> Self::Future {
+        let handler = self.clone();
+        // Every message of the request is answered once its events are accepted.
+        let responses = request
+            .into_inner()
+            .then(move |message| {
+                let handler = handler.clone();
+                async move {
+                    handler.handle(message?).await?;
+                    Ok::<_, Status>(handler.response.clone())
+                }
+            })
+            .boxed();
+        futures::future::ok(tonic::Response::new(responses)).boxed()
+    }
+}
+
+/// Routes the requests for the served method to the [`Handler`].
+#[derive(Clone)]
+struct MethodService {
+    path: Arc<str>,
+    kind: MethodKind,
+    handler: Handler,
+}
+
+impl tower::Service<Request<Body>> for MethodService {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        if request.uri().path() != &*self.path {
+            return futures::future::ok(
+                Status::unimplemented(format!("Unknown method {}", request.uri().path())).to_http(),
+            )
+            .boxed();
+        }
+
+        let handler = self.handler.clone();
+        let kind = self.kind;
+        async move {
+            let mut grpc = Grpc::new(BytesCodec).accept_compressed(CompressionEncoding::Gzip);
+            let response = match kind {
+                MethodKind::Unary => grpc.unary(handler, request).await,
+                MethodKind::ClientStreaming => grpc.client_streaming(handler, request).await,
+                MethodKind::ServerStreaming => grpc.server_streaming(handler, request).await,
+                MethodKind::Streaming => grpc.streaming(handler, request).await,
+            };
+            Ok(response)
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use prost_reflect::prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto,
+    };
+    use tonic::transport::Channel;
+    use vector_core::event::EventStatus;
+
+    use super::*;
+    use crate::test_util::{
+        self,
+        components::{assert_source_compliance, SOURCE_TAGS},
+    };
+
+    fn field(name: &str, number: i32, r#type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            json_name: Some(name.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn method(name: &str, client_streaming: bool, server_streaming: bool) -> MethodDescriptorProto {
+        MethodDescriptorProto {
+            name: Some(name.to_owned()),
+            input_type: Some(".test.Event".to_owned()),
+            output_type: Some(".test.Ack".to_owned()),
+            client_streaming: Some(client_streaming),
+            server_streaming: Some(server_streaming),
+            ..Default::default()
+        }
+    }
+
+    /// Writes the file descriptor set of the following definitions, returning its path:
+    ///
+    /// ```protobuf
+    /// syntax = "proto3";
+    /// package test;
+    ///
+    /// message Event { string message = 1; int64 count = 2; }
+    /// message Ack { bool accepted = 1; }
+    ///
+    /// service Collector {
+    ///   rpc Publish(Event) returns (Ack);
+    ///   rpc PublishStream(stream Event) returns (Ack);
+    /// }
+    /// ```
+    fn descriptor_set_file() -> PathBuf {
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("test.proto".to_owned()),
+                package: Some("test".to_owned()),
+                message_type: vec![
+                    DescriptorProto {
+                        name: Some("Event".to_owned()),
+                        field: vec![
+                            field("message", 1, Type::String),
+                            field("count", 2, Type::Int64),
+                        ],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("Ack".to_owned()),
+                        field: vec![field("accepted", 1, Type::Bool)],
+                        ..Default::default()
+                    },
+                ],
+                service: vec![ServiceDescriptorProto {
+                    name: Some("Collector".to_owned()),
+                    method: vec![
+                        method("Publish", false, false),
+                        method("PublishStream", true, false),
+                    ],
+                    ..Default::default()
+                }],
+                syntax: Some("proto3".to_owned()),
+                ..Default::default()
+            }],
+        };
+
+        let path = test_util::temp_file();
+        std::fs::write(&path, set.encode_to_vec()).unwrap();
+        path
+    }
+
+    fn config(address: SocketAddr, method: &str) -> GrpcServerConfig {
+        GrpcServerConfig {
+            address,
+            desc_file: descriptor_set_file(),
+            method: method.to_owned(),
+            response: Some(response_example()),
+            tls: None,
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        }
+    }
+
+    fn event_message(message: &str, count: i64) -> Bytes {
+        // Field 1 is a length-delimited string, field 2 a varint.
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(0x0a);
+        bytes.put_u8(message.len() as u8);
+        bytes.put_slice(message.as_bytes());
+        bytes.put_u8(0x10);
+        bytes.put_u8(count as u8);
+        bytes.freeze()
+    }
+
+    async fn client(address: SocketAddr) -> tonic::client::Grpc<Channel> {
+        let channel = Channel::from_shared(format!("http://{}", address))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        tonic::client::Grpc::new(channel)
+    }
+
+    #[test]
+    fn generate_config() {
+        test_util::test_generate_config::<GrpcServerConfig>();
+    }
+
+    #[test]
+    fn resolves_methods() {
+        let address = test_util::next_addr();
+
+        assert_eq!(
+            MethodKind::from(
+                &config(address, "test.Collector/PublishStream")
+                    .method_descriptor()
+                    .unwrap()
+            ),
+            MethodKind::ClientStreaming
+        );
+        assert!(matches!(
+            config(address, "test.Collector.Publish").method_descriptor(),
+            Err(BuildError::InvalidMethod { .. })
+        ));
+        assert!(matches!(
+            config(address, "test.Missing/Publish").method_descriptor(),
+            Err(BuildError::UnknownService { .. })
+        ));
+        assert!(matches!(
+            config(address, "test.Collector/Missing").method_descriptor(),
+            Err(BuildError::UnknownMethod { .. })
+        ));
+
+        let mut invalid_response = config(address, "test.Collector/Publish");
+        invalid_response.response = Some(toml::from_str("accepted = 3").unwrap());
+        let method = invalid_response.method_descriptor().unwrap();
+        assert!(invalid_response.encode_response(&method).is_err());
+    }
+
+    #[tokio::test]
+    async fn receives_unary_requests() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let address = test_util::next_addr();
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = config(address, "test.Collector/Publish")
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            test_util::wait_for_tcp(address).await;
+
+            let mut client = client(address).await;
+            client.ready().await.unwrap();
+            let response = client
+                .unary(
+                    tonic::Request::new(event_message("hello", 3)),
+                    "/test.Collector/Publish".parse().unwrap(),
+                    BytesCodec,
+                )
+                .await
+                .unwrap();
+            // The `accepted = true` response.
+            assert_eq!(response.into_inner(), Bytes::from_static(b"\x08\x01"));
+
+            client.ready().await.unwrap();
+            let status = client
+                .unary(
+                    tonic::Request::new(event_message("hello", 3)),
+                    "/test.Collector/Other".parse().unwrap(),
+                    BytesCodec,
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), tonic::Code::Unimplemented);
+
+            let events = test_util::collect_ready(rx).await;
+            assert_eq!(events.len(), 1);
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "hello".into());
+            assert_eq!(log["count"], 3.into());
+            assert_eq!(log["source_type"], GrpcServerConfig::NAME.into());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn receives_streamed_requests() {
+        let address = test_util::next_addr();
+        let (tx, rx) = SourceSender::new_test();
+        let source = config(address, "test.Collector/PublishStream")
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        test_util::wait_for_tcp(address).await;
+
+        let mut client = client(address).await;
+        client.ready().await.unwrap();
+        let messages = futures::stream::iter([event_message("a", 1), event_message("b", 2)]);
+        client
+            .client_streaming(
+                tonic::Request::new(messages),
+                "/test.Collector/PublishStream".parse().unwrap(),
+                BytesCodec,
+            )
+            .await
+            .unwrap();
+
+        let events = test_util::collect_ready(rx).await;
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()["message"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["a".into(), "b".into()]);
+    }
+}
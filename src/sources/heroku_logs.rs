@@ -24,7 +24,7 @@ use vector_core::{
 };
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{
         log_schema, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig, SourceConfig,
         SourceContext,
@@ -69,6 +69,10 @@ pub struct LogplexConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -143,6 +147,7 @@ impl Default for LogplexConfig {
             auth: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             acknowledgements: SourceAcknowledgementsConfig::default(),
             log_namespace: None,
         }
@@ -161,7 +166,9 @@ impl SourceConfig for LogplexConfig {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
 
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
@@ -184,8 +191,12 @@ impl SourceConfig for LogplexConfig {
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         // There is a global and per-source `log_namespace` config.
         // The source config overrides the global setting and is merged here.
-        let schema_def = self.schema_definition(global_log_namespace.merge(self.log_namespace));
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_def)]
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_def = self.schema_definition(log_namespace);
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_def)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -443,6 +454,7 @@ mod tests {
                 auth,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
+                on_decode_error: Default::default(),
                 acknowledgements: acknowledgements.into(),
                 log_namespace: None,
             }
@@ -34,6 +34,8 @@ mod disk;
 mod filesystem;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod process;
 
 /// Collector types.
 #[serde_as]
@@ -66,6 +68,11 @@ pub enum Collector {
 
     /// Metrics related to network utilization.
     Network,
+
+    /// Metrics related to individual processes.
+    ///
+    /// Only available on Linux, and not enabled by default.
+    Process,
 }
 
 /// Filtering configuration.
@@ -97,7 +104,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors, except `process`.
     #[configurable(metadata(docs::examples = "example_collectors()"))]
     #[derivative(Default(value = "default_collectors()"))]
     #[serde(default = "default_collectors")]
@@ -124,6 +131,10 @@ pub struct HostMetricsConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub process: ProcessConfig,
 }
 
 /// Options for the cgroups (controller groups) metrics collector.
@@ -160,6 +171,35 @@ pub struct CGroupsConfig {
     base_dir: Option<PathBuf>,
 }
 
+/// Options for the process metrics collector.
+///
+/// This collector is only available on Linux systems.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(default)]
+pub struct ProcessConfig {
+    /// Regular expressions matched against the names of the processes to report metrics for.
+    ///
+    /// The name of a process is the name of its executable, truncated to 15 characters.
+    ///
+    /// Processes are reported if their name matches any of `names`, or if their command line
+    /// matches any of `cmdlines`. If neither is set, all processes are reported.
+    #[configurable(metadata(docs::examples = "^nginx$"))]
+    names: Vec<String>,
+
+    /// Regular expressions matched against the command lines of the processes to report metrics
+    /// for.
+    ///
+    /// The command line of a process is its arguments, separated by spaces.
+    #[configurable(metadata(docs::examples = "java .*kafka"))]
+    cmdlines: Vec<String>,
+
+    /// Base procfs directory, for testing use only
+    #[serde(skip_serializing)]
+    #[configurable(metadata(docs::hidden))]
+    procfs_root: Option<PathBuf>,
+}
+
 const fn default_scrape_interval() -> Duration {
     Duration::from_secs(15)
 }
@@ -168,7 +208,7 @@ pub fn default_namespace() -> Option<String> {
     Some(String::from("host"))
 }
 
-const fn example_collectors() -> [&'static str; 8] {
+const fn example_collectors() -> [&'static str; 9] {
     [
         "cgroups",
         "cpu",
@@ -178,6 +218,7 @@ const fn example_collectors() -> [&'static str; 8] {
         "host",
         "memory",
         "network",
+        "process",
     ]
 }
 
@@ -258,8 +299,14 @@ impl SourceConfig for HostMetricsConfig {
             if self.cgroups.is_some() || self.has_collector(Collector::CGroups) {
                 return Err("CGroups collector is only available on Linux systems".into());
             }
+            if self.has_collector(Collector::Process) {
+                return Err("Process collector is only available on Linux systems".into());
+            }
         }
 
+        #[cfg(target_os = "linux")]
+        process::ProcessFilter::new(&self.process)?;
+
         let mut config = self.clone();
         config.namespace = config.namespace.filter(|namespace| !namespace.is_empty());
 
@@ -314,6 +361,8 @@ pub struct HostMetrics {
     config: HostMetricsConfig,
     #[cfg(target_os = "linux")]
     root_cgroup: Option<cgroups::CGroupRoot>,
+    #[cfg(target_os = "linux")]
+    process_filter: process::ProcessFilter,
     events_received: Registered<EventsReceived>,
 }
 
@@ -330,9 +379,12 @@ impl HostMetrics {
     pub fn new(config: HostMetricsConfig) -> Self {
         let cgroups = config.cgroups.clone().unwrap_or_default();
         let root_cgroup = cgroups::CGroupRoot::new(&cgroups);
+        let process_filter = process::ProcessFilter::new(&config.process)
+            .expect("process patterns are validated when the source is built");
         Self {
             config,
             root_cgroup,
+            process_filter,
             events_received: register!(EventsReceived),
         }
     }
@@ -370,6 +422,10 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Process) {
+            self.process_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         self.events_received.emit(CountByteSize(
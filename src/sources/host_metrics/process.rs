@@ -0,0 +1,526 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use nix::unistd::{self, SysconfVar, Uid, User};
+use regex::Regex;
+use snafu::{OptionExt, ResultExt, Snafu};
+use tokio::fs;
+use vector_core::metric_tags;
+
+use super::{filter_result_sync, HostMetrics, MetricsBuffer, ProcessConfig};
+
+const MODE: &str = "mode";
+const CPU_SECS_TOTAL: &str = "process_cpu_seconds_total";
+
+/// The number of clock ticks per second used when it can't be queried, which is the value Linux
+/// uses on all architectures.
+const DEFAULT_CLOCK_TICKS: f64 = 100.0;
+const KILOBYTES: u64 = 1024;
+
+#[derive(Debug, Snafu)]
+enum ProcessError {
+    #[snafu(display("Could not list processes in {:?}.", path))]
+    Listing { path: PathBuf, source: io::Error },
+    #[snafu(display("Could not parse process data file {:?}.", filename))]
+    Parsing { filename: PathBuf },
+}
+
+type ProcessResult<T> = Result<T, ProcessError>;
+
+/// The compiled patterns selecting the processes to report metrics for.
+#[derive(Clone, Debug)]
+pub(super) struct ProcessFilter {
+    names: Vec<Regex>,
+    cmdlines: Vec<Regex>,
+}
+
+impl ProcessFilter {
+    pub(super) fn new(config: &ProcessConfig) -> Result<Self, regex::Error> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| Regex::new(pattern))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            names: compile(&config.names)?,
+            cmdlines: compile(&config.cmdlines)?,
+        })
+    }
+
+    /// Command lines are only read when they are matched against.
+    fn needs_cmdline(&self) -> bool {
+        !self.cmdlines.is_empty()
+    }
+
+    fn matches(&self, name: &str, cmdline: Option<&str>) -> bool {
+        (self.names.is_empty() && self.cmdlines.is_empty())
+            || self.names.iter().any(|pattern| pattern.is_match(name))
+            || cmdline.map_or(false, |cmdline| {
+                self.cmdlines
+                    .iter()
+                    .any(|pattern| pattern.is_match(cmdline))
+            })
+    }
+}
+
+impl HostMetrics {
+    pub(super) async fn process_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "process";
+        let root = procfs_root(&self.config.process);
+        let pids = match filter_result_sync(list_pids(&root).await, "Failed to list processes.") {
+            Some(pids) => pids,
+            None => return,
+        };
+
+        let clock_ticks = clock_ticks();
+        let mut users = HashMap::new();
+
+        for pid in pids {
+            match filter_result_sync(
+                self.load_process(&root, pid).await,
+                "Failed to load process statistics.",
+            ) {
+                Some(Some(process)) => {
+                    let user = users
+                        .entry(process.status.uid)
+                        .or_insert_with(|| user_name(process.status.uid))
+                        .clone();
+                    process.emit(output, user, clock_ticks);
+                }
+                // The process was filtered out, or exited while it was being read.
+                _ => continue,
+            }
+        }
+    }
+
+    /// Load the statistics of the process, if it matches the filter. Returns `Ok(None)` if it
+    /// doesn't match, or if it exited before its data files could be read.
+    async fn load_process(&self, root: &Path, pid: u32) -> ProcessResult<Option<Process>> {
+        let dir = root.join(pid.to_string());
+
+        let filename = dir.join("stat");
+        let stat = match fs::read_to_string(&filename).await {
+            Ok(text) => parse_stat(&text).context(ParsingSnafu { filename })?,
+            Err(_) => return Ok(None),
+        };
+
+        let cmdline = if self.process_filter.needs_cmdline() {
+            match fs::read(dir.join("cmdline")).await {
+                Ok(cmdline) => Some(parse_cmdline(&cmdline)),
+                Err(_) => return Ok(None),
+            }
+        } else {
+            None
+        };
+        if !self.process_filter.matches(&stat.name, cmdline.as_deref()) {
+            return Ok(None);
+        }
+
+        let filename = dir.join("status");
+        let status = match fs::read_to_string(&filename).await {
+            Ok(text) => parse_status(&text).context(ParsingSnafu { filename })?,
+            Err(_) => return Ok(None),
+        };
+
+        // Listing the descriptors of the processes of other users requires privileges, so its
+        // metric is only reported when that is possible.
+        let open_fds = count_entries(&dir.join("fd")).await.ok();
+
+        Ok(Some(Process {
+            pid,
+            stat,
+            status,
+            open_fds,
+        }))
+    }
+}
+
+struct Process {
+    pid: u32,
+    stat: Stat,
+    status: Status,
+    open_fds: Option<usize>,
+}
+
+impl Process {
+    fn emit(self, output: &mut MetricsBuffer, user: String, clock_ticks: f64) {
+        let tags = metric_tags! {
+            "pid" => self.pid.to_string(),
+            "name" => self.stat.name,
+            "user" => user,
+        };
+        let mode_tags = |mode: &str| {
+            let mut tags = tags.clone();
+            tags.replace(MODE.into(), mode.to_owned());
+            tags
+        };
+
+        output.counter(
+            CPU_SECS_TOTAL,
+            self.stat.utime as f64 / clock_ticks,
+            mode_tags("user"),
+        );
+        output.counter(
+            CPU_SECS_TOTAL,
+            self.stat.stime as f64 / clock_ticks,
+            mode_tags("system"),
+        );
+        output.gauge(
+            "process_threads",
+            self.stat.num_threads as f64,
+            tags.clone(),
+        );
+        // Kernel threads have no memory of their own, so their status has no memory fields.
+        if let Some(rss) = self.status.rss_bytes {
+            output.gauge("process_memory_rss_bytes", rss as f64, tags.clone());
+        }
+        if let Some(size) = self.status.virtual_bytes {
+            output.gauge("process_memory_virtual_bytes", size as f64, tags.clone());
+        }
+        if let Some(open_fds) = self.open_fds {
+            output.gauge("process_open_fds", open_fds as f64, tags);
+        }
+    }
+}
+
+/// The fields of `/proc/<pid>/stat` that metrics are reported for.
+#[derive(Debug, PartialEq)]
+struct Stat {
+    name: String,
+    utime: u64,
+    stime: u64,
+    num_threads: u64,
+}
+
+/// Parse `/proc/<pid>/stat`, as documented in `proc(5)`.
+fn parse_stat(text: &str) -> Option<Stat> {
+    // The name is enclosed in parentheses, and may itself contain spaces and parentheses.
+    let start = text.find('(')?;
+    let end = text.rfind(')')?;
+    let name = text.get(start + 1..end)?.to_owned();
+
+    // The fields after the name start with the state, which is the third field of the file.
+    let fields = text.get(end + 1..)?.split_whitespace().collect::<Vec<_>>();
+    let field = |number: usize| fields.get(number - 3)?.parse().ok();
+
+    Some(Stat {
+        name,
+        utime: field(14)?,
+        stime: field(15)?,
+        num_threads: field(20)?,
+    })
+}
+
+/// The fields of `/proc/<pid>/status` that metrics are reported for.
+#[derive(Debug, PartialEq)]
+struct Status {
+    uid: u32,
+    rss_bytes: Option<u64>,
+    virtual_bytes: Option<u64>,
+}
+
+/// Parse `/proc/<pid>/status`, as documented in `proc(5)`.
+fn parse_status(text: &str) -> Option<Status> {
+    let mut uid = None;
+    let mut rss_bytes = None;
+    let mut virtual_bytes = None;
+
+    for line in text.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            // The real UID comes first, and memory sizes are in kilobytes.
+            let value = value.split_whitespace().next();
+            let kilobytes = || value?.parse::<u64>().ok().map(|size| size * KILOBYTES);
+            match key {
+                "Uid" => uid = value.and_then(|uid| uid.parse().ok()),
+                "VmRSS" => rss_bytes = kilobytes(),
+                "VmSize" => virtual_bytes = kilobytes(),
+                _ => (),
+            }
+        }
+    }
+
+    Some(Status {
+        uid: uid?,
+        rss_bytes,
+        virtual_bytes,
+    })
+}
+
+/// Parse `/proc/<pid>/cmdline`, whose arguments are separated by NUL characters, into the
+/// arguments separated by spaces.
+fn parse_cmdline(cmdline: &[u8]) -> String {
+    cmdline
+        .split(|&byte| byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn procfs_root(config: &ProcessConfig) -> PathBuf {
+    config
+        .procfs_root
+        .clone()
+        .or_else(|| std::env::var_os("PROCFS_ROOT").map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("/proc"))
+}
+
+async fn list_pids(root: &Path) -> ProcessResult<Vec<u32>> {
+    let mut entries = fs::read_dir(root)
+        .await
+        .with_context(|_| ListingSnafu { path: root })?;
+    let mut pids = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .with_context(|_| ListingSnafu { path: root })?
+    {
+        if let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            pids.push(pid);
+        }
+    }
+    pids.sort_unstable();
+    Ok(pids)
+}
+
+async fn count_entries(path: &Path) -> io::Result<usize> {
+    let mut entries = fs::read_dir(path).await?;
+    let mut count = 0;
+    while entries.next_entry().await?.is_some() {
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn clock_ticks() -> f64 {
+    match unistd::sysconf(SysconfVar::CLK_TCK) {
+        Ok(Some(ticks)) if ticks > 0 => ticks as f64,
+        _ => DEFAULT_CLOCK_TICKS,
+    }
+}
+
+/// Look up the name of the user, falling back to the UID for users without one.
+fn user_name(uid: u32) -> String {
+    match User::from_uid(Uid::from_raw(uid)) {
+        Ok(Some(user)) => user.name,
+        _ => uid.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use similar_asserts::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{
+        super::{
+            tests::{all_counters, all_gauges, count_name, count_tag},
+            HostMetrics, HostMetricsConfig, ProcessConfig,
+        },
+        parse_cmdline, parse_stat, parse_status, MetricsBuffer, ProcessFilter, Stat, Status,
+    };
+
+    #[test]
+    fn parses_stat() {
+        assert_eq!(
+            parse_stat(
+                "1234 (tmux: server) S 1 1234 1234 0 -1 4194368 1690 0 0 0 \
+                 25 17 0 0 20 0 3 0 4127 10457088 1142 18446744073709551615"
+            ),
+            Some(Stat {
+                name: "tmux: server".into(),
+                utime: 25,
+                stime: 17,
+                num_threads: 3,
+            })
+        );
+        assert_eq!(
+            parse_stat("42 (a) b)) R 1 42 42 0 -1 0 0 0 0 0 1 2 0 0 20 0 1 0"),
+            Some(Stat {
+                name: "a) b)".into(),
+                utime: 1,
+                stime: 2,
+                num_threads: 1,
+            })
+        );
+        assert_eq!(parse_stat("42 (truncated) R 1 42"), None);
+    }
+
+    #[test]
+    fn parses_status() {
+        assert_eq!(
+            parse_status(
+                "Name:\tnginx\nUmask:\t0022\nState:\tS (sleeping)\n\
+                 Uid:\t101\t101\t101\t101\nGid:\t101\t101\t101\t101\n\
+                 VmSize:\t   10212 kB\nVmRSS:\t    2660 kB\nThreads:\t1\n"
+            ),
+            Some(Status {
+                uid: 101,
+                rss_bytes: Some(2660 * 1024),
+                virtual_bytes: Some(10212 * 1024),
+            })
+        );
+        assert_eq!(
+            parse_status("Name:\tkthreadd\nUid:\t0\t0\t0\t0\nThreads:\t1\n"),
+            Some(Status {
+                uid: 0,
+                rss_bytes: None,
+                virtual_bytes: None,
+            })
+        );
+        assert_eq!(parse_status("Name:\tkthreadd\n"), None);
+    }
+
+    #[test]
+    fn parses_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"java\0-jar\0kafka.jar\0"),
+            "java -jar kafka.jar"
+        );
+        assert_eq!(parse_cmdline(b""), "");
+    }
+
+    #[test]
+    fn filters_processes() {
+        let filter = |names: &[&str], cmdlines: &[&str]| {
+            ProcessFilter::new(&ProcessConfig {
+                names: names.iter().map(|name| name.to_string()).collect(),
+                cmdlines: cmdlines.iter().map(|name| name.to_string()).collect(),
+                procfs_root: None,
+            })
+            .unwrap()
+        };
+
+        assert!(filter(&[], &[]).matches("anything", None));
+
+        let names = filter(&["^nginx$"], &[]);
+        assert!(names.matches("nginx", None));
+        assert!(!names.matches("nginx-debug", None));
+
+        let both = filter(&["^nginx$"], &["kafka"]);
+        assert!(both.needs_cmdline());
+        assert!(both.matches("nginx", Some("nginx: worker process")));
+        assert!(both.matches("java", Some("java -jar kafka.jar")));
+        assert!(!both.matches("java", Some("java -jar zookeeper.jar")));
+
+        assert!(ProcessFilter::new(&ProcessConfig {
+            names: vec!["(".into()],
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        let config: HostMetricsConfig = toml::from_str(r#"collectors = ["process"]"#).unwrap();
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(config).process_metrics(&mut buffer).await;
+        let metrics = buffer.metrics;
+
+        assert!(!metrics.is_empty());
+        assert_eq!(count_tag(&metrics, "pid"), metrics.len());
+        assert_eq!(count_tag(&metrics, "name"), metrics.len());
+        assert_eq!(count_tag(&metrics, "user"), metrics.len());
+        assert_ne!(count_name(&metrics, "process_cpu_seconds_total"), 0);
+        assert_ne!(count_name(&metrics, "process_threads"), 0);
+    }
+
+    #[tokio::test]
+    async fn reads_fake_procfs() {
+        let procfs = FakeProcfs::new();
+        procfs.process(
+            1,
+            "1 (init) S 0 1 1 0 -1 0 0 0 0 0 300 200 0 0 20 0 1 0",
+            "Uid:\t0\t0\t0\t0\nVmSize:\t2048 kB\nVmRSS:\t1024 kB\n",
+            b"/sbin/init\0",
+            3,
+        );
+        procfs.process(
+            2,
+            "2 (kthreadd) S 0 0 0 0 -1 0 0 0 0 0 0 10 0 0 20 0 1 0",
+            "Uid:\t0\t0\t0\t0\n",
+            b"",
+            0,
+        );
+        procfs.process(
+            300,
+            "300 (java) S 1 300 300 0 -1 0 0 0 0 0 100 50 0 0 20 0 42 0",
+            "Uid:\t0\t0\t0\t0\nVmSize:\t4096 kB\nVmRSS:\t3072 kB\n",
+            b"java\0-jar\0kafka.jar\0",
+            7,
+        );
+        fs::write(procfs.0.path().join("meminfo"), "MemTotal: 1 kB\n").unwrap();
+        fs::create_dir(procfs.0.path().join("self")).unwrap();
+
+        let metrics = procfs.metrics(&[], &[]).await;
+        assert_eq!(metrics.len(), 16);
+        assert_eq!(count_name(&metrics, "process_cpu_seconds_total"), 6);
+        assert_eq!(count_name(&metrics, "process_memory_rss_bytes"), 2);
+        assert_eq!(count_name(&metrics, "process_open_fds"), 3);
+
+        let java = procfs.metrics(&["^init$"], &["kafka"]).await;
+        assert_eq!(java.len(), 12);
+        assert!(all_counters(&java[..2]));
+        assert!(all_gauges(&java[2..6]));
+
+        let threads = java
+            .iter()
+            .find(|metric| {
+                metric.name() == "process_threads" && metric.tag_value("pid").unwrap() == "300"
+            })
+            .unwrap();
+        assert_eq!(threads.tag_value("name").unwrap(), "java");
+        assert_eq!(
+            threads.value(),
+            &vector_core::event::MetricValue::Gauge { value: 42.0 }
+        );
+    }
+
+    struct FakeProcfs(TempDir);
+
+    impl FakeProcfs {
+        fn new() -> Self {
+            Self(tempfile::tempdir().unwrap())
+        }
+
+        fn process(&self, pid: u32, stat: &str, status: &str, cmdline: &[u8], fds: usize) {
+            let dir = self.0.path().join(pid.to_string());
+            fs::create_dir_all(dir.join("fd")).unwrap();
+            fs::write(dir.join("stat"), stat).unwrap();
+            fs::write(dir.join("status"), status).unwrap();
+            fs::write(dir.join("cmdline"), cmdline).unwrap();
+            for fd in 0..fds {
+                fs::write(dir.join("fd").join(fd.to_string()), "").unwrap();
+            }
+        }
+
+        async fn metrics(
+            &self,
+            names: &[&str],
+            cmdlines: &[&str],
+        ) -> Vec<vector_core::event::Metric> {
+            let config = HostMetricsConfig {
+                process: ProcessConfig {
+                    names: names.iter().map(|name| name.to_string()).collect(),
+                    cmdlines: cmdlines.iter().map(|name| name.to_string()).collect(),
+                    procfs_root: Some(self.0.path().into()),
+                },
+                ..Default::default()
+            };
+            let mut buffer = MetricsBuffer::new(None);
+            HostMetrics::new(config).process_metrics(&mut buffer).await;
+            buffer.metrics
+        }
+    }
+}
@@ -11,9 +11,9 @@ use std::{collections::HashMap, time::Duration};
 use tokio_util::codec::Decoder as _;
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext},
-    http::Auth,
+    http::{Auth, HttpClient},
     register_validatable_component,
     serde::{default_decoding, default_framing_message_based},
     sources,
@@ -72,6 +72,10 @@ pub struct HttpClientConfig {
     #[serde(default = "default_decoding")]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
     /// Framing to use in the decoding.
     #[configurable(derived)]
     #[serde(default = "default_framing_message_based")]
@@ -150,6 +154,7 @@ impl Default for HttpClientConfig {
             query: HashMap::new(),
             interval: default_interval(),
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             framing: default_framing_message_based(),
             headers: HashMap::new(),
             method: default_http_method(),
@@ -175,10 +180,15 @@ impl SourceConfig for HttpClientConfig {
 
         let tls = TlsSettings::from_options(&self.tls)?;
 
+        if let Some(auth) = &self.auth {
+            auth.start(&HttpClient::new(tls.clone(), &cx.proxy)?)
+                .await?;
+        }
+
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         // build the decoder
-        let decoder = self.get_decoding_config(Some(log_namespace)).build();
+        let decoder = self.get_decoding_config(Some(log_namespace)).build()?;
 
         let content_type = self.decoding.content_type(&self.framing).to_string();
 
@@ -212,7 +222,11 @@ impl SourceConfig for HttpClientConfig {
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata();
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -227,7 +241,9 @@ impl ValidatableComponent for HttpClientConfig {
         let config = Self {
             endpoint: uri.to_string(),
             interval: Duration::from_secs(1),
-            decoding: DeserializerConfig::Json,
+            decoding: DeserializerConfig::Json {
+                json: Default::default(),
+            },
             ..Default::default()
         };
 
@@ -251,6 +267,7 @@ impl HttpClientConfig {
             log_namespace.unwrap_or_else(|| self.log_namespace.unwrap_or(false).into());
 
         DecodingConfig::new(framing, decoding, log_namespace)
+            .with_error_policy(self.on_decode_error)
     }
 }
 
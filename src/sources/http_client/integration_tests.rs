@@ -55,6 +55,7 @@ async fn invalid_endpoint() {
         interval: INTERVAL,
         query: HashMap::new(),
         decoding: default_decoding(),
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -73,6 +74,7 @@ async fn collected_logs_bytes() {
         interval: INTERVAL,
         query: HashMap::new(),
         decoding: DeserializerConfig::Bytes,
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -96,7 +98,10 @@ async fn collected_logs_json() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -121,6 +126,7 @@ async fn collected_metrics_native_json() {
         interval: INTERVAL,
         query: HashMap::new(),
         decoding: DeserializerConfig::NativeJson,
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -150,6 +156,7 @@ async fn collected_trace_native_json() {
         interval: INTERVAL,
         query: HashMap::new(),
         decoding: DeserializerConfig::NativeJson,
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -173,7 +180,10 @@ async fn unauthorized_no_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -191,7 +201,10 @@ async fn unauthorized_wrong_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -212,7 +225,10 @@ async fn authorized() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -233,7 +249,9 @@ async fn tls_invalid_ca() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -254,7 +272,9 @@ async fn tls_valid() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -276,7 +296,10 @@ async fn shutdown() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
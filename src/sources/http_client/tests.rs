@@ -51,6 +51,7 @@ async fn bytes_decoding() {
         interval: INTERVAL,
         query: HashMap::new(),
         decoding: default_decoding(),
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -78,7 +79,10 @@ async fn json_decoding_newline_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: FramingConfig::NewlineDelimited {
             newline_delimited: NewlineDelimitedDecoderOptions::default(),
         },
@@ -108,7 +112,10 @@ async fn json_decoding_character_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: FramingConfig::CharacterDelimited {
             character_delimited: CharacterDelimitedDecoderOptions {
                 delimiter: b',',
@@ -146,7 +153,10 @@ async fn request_query_applied() {
                 vec!["val1".to_string(), "val2".to_string()],
             ),
         ]),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerConfig::Json {
+            json: Default::default(),
+        },
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -209,6 +219,7 @@ async fn headers_applied() {
         interval: INTERVAL,
         query: HashMap::new(),
         decoding: default_decoding(),
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::from([(
             "f00".to_string(),
@@ -240,6 +251,7 @@ async fn accept_header_override() {
         interval: INTERVAL,
         query: HashMap::new(),
         decoding: DeserializerConfig::Bytes,
+        on_decode_error: Default::default(),
         framing: default_framing_message_based(),
         headers: HashMap::from([("ACCEPT".to_string(), vec!["application/json".to_string()])]),
         method: HttpMethod::Get,
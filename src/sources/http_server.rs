@@ -20,7 +20,7 @@ use vector_core::{
 use warp::http::{HeaderMap, HeaderValue};
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     components::validation::*,
     config::{
         GenerateConfig, Output, Resource, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
@@ -30,7 +30,7 @@ use crate::{
     serde::{bool_or_struct, default_decoding},
     sources::util::{
         http::{add_query_parameters, HttpMethod},
-        Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        DecompressionConfig, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsEnableableConfig,
 };
@@ -64,6 +64,10 @@ impl SourceConfig for HttpConfig {
     fn can_acknowledge(&self) -> bool {
         self.0.can_acknowledge()
     }
+
+    fn pass_through_decoding(&self) -> Option<DeserializerConfig> {
+        self.0.pass_through_decoding()
+    }
 }
 
 /// Configuration for the `http_server` source.
@@ -136,6 +140,14 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     decoding: Option<DeserializerConfig>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    decompression: DecompressionConfig,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -200,11 +212,11 @@ impl SimpleHttpConfig {
                 ),
                 Encoding::Json => (
                     BytesDecoderConfig::new().into(),
-                    JsonDeserializerConfig::new().into(),
+                    JsonDeserializerConfig::default().into(),
                 ),
                 Encoding::Ndjson => (
                     NewlineDelimitedDecoderConfig::new().into(),
-                    JsonDeserializerConfig::new().into(),
+                    JsonDeserializerConfig::default().into(),
                 ),
                 Encoding::Binary => (
                     BytesDecoderConfig::new().into(),
@@ -224,7 +236,44 @@ impl SimpleHttpConfig {
             framing,
             decoding,
             self.log_namespace.unwrap_or(false).into(),
-        ))
+        )
+        .with_error_policy(self.on_decode_error))
+    }
+
+    /// Builds the source handling the requests, for the sources extending this one.
+    pub(crate) fn http_source(&self, cx: &SourceContext) -> crate::Result<SimpleHttpSource> {
+        let decoder = self
+            .get_decoding_config()?
+            .with_pass_through(cx.pass_through)
+            .build()?;
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        Ok(SimpleHttpSource {
+            headers: remove_duplicates(self.headers.clone(), "headers"),
+            query_parameters: remove_duplicates(self.query_parameters.clone(), "query_parameters"),
+            path_key: self.path_key.clone(),
+            decoder,
+            decompression: self.decompression.clone(),
+            log_namespace,
+        })
+    }
+
+    /// Serves `source` with the server options of this configuration.
+    pub(crate) fn serve(
+        &self,
+        source: impl HttpSource,
+        cx: SourceContext,
+    ) -> crate::Result<super::Source> {
+        source.run(
+            self.address,
+            self.path.as_str(),
+            self.method,
+            self.strict_path,
+            &self.tls,
+            &self.auth,
+            cx,
+            self.acknowledgements,
+        )
     }
 }
 
@@ -243,6 +292,8 @@ impl Default for SimpleHttpConfig {
             strict_path: true,
             framing: None,
             decoding: Some(default_decoding()),
+            on_decode_error: DecodeErrorPolicy::default(),
+            decompression: DecompressionConfig::default(),
             acknowledgements: SourceAcknowledgementsConfig::default(),
             log_namespace: None,
         }
@@ -254,7 +305,9 @@ impl_generate_config_from_default!(SimpleHttpConfig);
 impl ValidatableComponent for SimpleHttpConfig {
     fn validation_configuration() -> ValidationConfiguration {
         let config = Self {
-            decoding: Some(DeserializerConfig::Json),
+            decoding: Some(DeserializerConfig::Json {
+                json: Default::default(),
+            }),
             ..Default::default()
         };
 
@@ -311,26 +364,8 @@ fn remove_duplicates(mut list: Vec<String>, list_name: &str) -> Vec<String> {
 #[async_trait::async_trait]
 impl SourceConfig for SimpleHttpConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let decoder = self.get_decoding_config()?.build();
-        let log_namespace = cx.log_namespace(self.log_namespace);
-
-        let source = SimpleHttpSource {
-            headers: remove_duplicates(self.headers.clone(), "headers"),
-            query_parameters: remove_duplicates(self.query_parameters.clone(), "query_parameters"),
-            path_key: self.path_key.clone(),
-            decoder,
-            log_namespace,
-        };
-        source.run(
-            self.address,
-            self.path.as_str(),
-            self.method,
-            self.strict_path,
-            &self.tls,
-            &self.auth,
-            cx,
-            self.acknowledgements,
-        )
+        let source = self.http_source(&cx)?;
+        self.serve(source, cx)
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -340,13 +375,15 @@ impl SourceConfig for SimpleHttpConfig {
 
         let schema_definition = self.schema_definition(log_namespace);
 
-        vec![Output::default(
+        let mut outputs = vec![Output::default(
             self.decoding
                 .as_ref()
                 .map(|d| d.output_type())
                 .unwrap_or(DataType::Log),
         )
-        .with_schema_definition(schema_definition)]
+        .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -356,14 +393,21 @@ impl SourceConfig for SimpleHttpConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn pass_through_decoding(&self) -> Option<DeserializerConfig> {
+        self.get_decoding_config()
+            .ok()
+            .map(|decoding| decoding.config().clone())
+    }
 }
 
 #[derive(Clone)]
-struct SimpleHttpSource {
+pub(crate) struct SimpleHttpSource {
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: OptionalValuePath,
     decoder: Decoder,
+    decompression: DecompressionConfig,
     log_namespace: LogNamespace,
 }
 
@@ -376,6 +420,10 @@ impl HttpSource for SimpleHttpSource {
         headers_config: &HeaderMap,
         query_parameters: &HashMap<String, String>,
     ) {
+        if self.decoder.is_pass_through() {
+            return;
+        }
+
         for event in events.iter_mut() {
             let log = event.as_mut_log();
 
@@ -453,6 +501,10 @@ impl HttpSource for SimpleHttpSource {
 
         Ok(events)
     }
+
+    fn decompression(&self) -> &DecompressionConfig {
+        &self.decompression
+    }
 }
 
 #[cfg(test)]
@@ -534,6 +586,8 @@ mod tests {
                 method,
                 framing,
                 decoding,
+                on_decode_error: Default::default(),
+                decompression: Default::default(),
                 acknowledgements: acknowledgements.into(),
                 log_namespace: None,
             }
@@ -757,7 +811,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -807,7 +861,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -850,7 +904,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -899,7 +953,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -983,7 +1037,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -1024,7 +1078,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -1103,7 +1157,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -1147,7 +1201,7 @@ mod tests {
                 EventStatus::Delivered,
                 true,
                 None,
-                Some(JsonDeserializerConfig::new().into()),
+                Some(JsonDeserializerConfig::default().into()),
             )
             .await;
 
@@ -1216,7 +1270,7 @@ mod tests {
             EventStatus::Delivered,
             true,
             None,
-            Some(JsonDeserializerConfig::new().into()),
+            Some(JsonDeserializerConfig::default().into()),
         )
         .await;
 
@@ -1,6 +1,8 @@
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 use futures::StreamExt;
+use indexmap::IndexMap;
 use serde_with::serde_as;
 use tokio::time;
 use tokio_stream::wrappers::IntervalStream;
@@ -11,7 +13,11 @@ use vector_core::EstimatedJsonEncodedSizeOf;
 
 use crate::{
     config::{log_schema, DataType, Output, SourceConfig, SourceContext},
-    internal_events::{EventsReceived, InternalMetricsBytesReceived, StreamClosedError},
+    event::{metric::MetricSeries, Metric},
+    internal_events::{
+        EventsReceived, InternalMetricsBytesReceived, InternalMetricsTagValuesCollapsed,
+        StreamClosedError,
+    },
     metrics::Controller,
     shutdown::ShutdownSignal,
     SourceSender,
@@ -34,6 +40,23 @@ pub struct InternalMetricsConfig {
     /// Overrides the default namespace for the metrics emitted by the source.
     #[serde(default = "default_namespace")]
     pub namespace: String,
+
+    /// The bucket upper limits of internal histograms, by metric name.
+    ///
+    /// By default, histograms have buckets with upper limits that are powers of two, from `2^-6`
+    /// to `2^12`. A final bucket without upper limit is always added.
+    ///
+    /// This only applies to histograms created after the source is started. As histograms are
+    /// shared by the whole Vector process, this should only be set on one `internal_metrics`
+    /// source.
+    #[configurable(metadata(
+        docs::additional_props_description = "The bucket upper limits of the histogram."
+    ))]
+    #[configurable(metadata(docs::examples = "example_histogram_buckets()", docs::advanced))]
+    pub histogram_buckets: HashMap<String, Vec<f64>>,
+
+    #[configurable(derived)]
+    pub cardinality_limit: Option<CardinalityLimitConfig>,
 }
 
 impl Default for InternalMetricsConfig {
@@ -42,10 +65,50 @@ impl Default for InternalMetricsConfig {
             scrape_interval_secs: default_scrape_interval(),
             tags: TagsConfig::default(),
             namespace: default_namespace(),
+            histogram_buckets: HashMap::new(),
+            cardinality_limit: None,
         }
     }
 }
 
+fn example_histogram_buckets() -> HashMap<String, Vec<f64>> {
+    HashMap::from([(
+        "command_execution_duration_seconds".to_owned(),
+        vec![0.1, 0.5, 1.0, 5.0, 10.0, 60.0],
+    )])
+}
+
+/// Limits the number of distinct values of the tags of each internal metric.
+///
+/// This guards against the high cardinality of tags like the `command` tag of the metrics of the
+/// `exec` source. Once a tag of a metric has reached the limit, its new values are replaced with
+/// `overflow_value`, and the metrics whose series become identical are aggregated together. The
+/// number of series collapsed at each scrape is reported by the `internal_metrics_collapsed_series`
+/// gauge.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CardinalityLimitConfig {
+    /// The maximum number of distinct values of a tag, for each metric.
+    #[configurable(metadata(docs::examples = 100))]
+    pub value_limit: usize,
+
+    /// The tags whose values are limited.
+    ///
+    /// By default, the values of all tags are limited.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "command"))]
+    pub tags: Vec<String>,
+
+    /// The value replacing the values of a tag over the limit.
+    #[serde(default = "default_overflow_value")]
+    pub overflow_value: String,
+}
+
+fn default_overflow_value() -> String {
+    "other".to_owned()
+}
+
 /// Tag configuration for the `internal_metrics` source.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -118,12 +181,20 @@ impl SourceConfig for InternalMetricsConfig {
             .as_deref()
             .and_then(|tag| (!tag.is_empty()).then(|| tag.to_owned()));
 
+        let controller = Controller::get()?;
+        if !self.histogram_buckets.is_empty() {
+            controller.set_histogram_buckets(self.histogram_buckets.clone());
+        }
+
+        let limiter = self.cardinality_limit.clone().map(CardinalityLimiter::new);
+
         Ok(Box::pin(
             InternalMetrics {
                 namespace,
                 host_key,
                 pid_key,
-                controller: Controller::get()?,
+                limiter,
+                controller,
                 interval,
                 out: cx.out,
                 shutdown: cx.shutdown,
@@ -145,6 +216,7 @@ struct InternalMetrics<'a> {
     namespace: String,
     host_key: Option<String>,
     pid_key: Option<String>,
+    limiter: Option<CardinalityLimiter>,
     controller: &'a Controller,
     interval: time::Duration,
     out: SourceSender,
@@ -160,7 +232,10 @@ impl<'a> InternalMetrics<'a> {
             let hostname = crate::get_hostname();
             let pid = std::process::id().to_string();
 
-            let metrics = self.controller.capture_metrics();
+            let mut metrics = self.controller.capture_metrics();
+            if let Some(limiter) = &mut self.limiter {
+                metrics = limiter.limit(metrics);
+            }
             let count = metrics.len();
             let byte_size = metrics.estimated_json_encoded_size_of();
 
@@ -195,6 +270,101 @@ impl<'a> InternalMetrics<'a> {
     }
 }
 
+struct CardinalityLimiter {
+    config: CardinalityLimitConfig,
+    /// The values admitted so far for each tag of each metric, by metric name and tag.
+    values: HashMap<(String, String), HashSet<String>>,
+    /// The tags of the metrics that had series collapsed at the previous scrape.
+    collapsed: HashSet<(String, String)>,
+}
+
+impl CardinalityLimiter {
+    fn new(config: CardinalityLimitConfig) -> Self {
+        Self {
+            config,
+            values: HashMap::new(),
+            collapsed: HashSet::new(),
+        }
+    }
+
+    fn limits_tag(&self, tag: &str) -> bool {
+        self.config.tags.is_empty() || self.config.tags.iter().any(|limited| limited == tag)
+    }
+
+    fn limit(&mut self, metrics: Vec<Metric>) -> Vec<Metric> {
+        let mut collapsed = HashMap::<(String, String), usize>::new();
+        let mut series = IndexMap::<MetricSeries, Metric>::with_capacity(metrics.len());
+        let mut unmerged = Vec::new();
+
+        for mut metric in metrics {
+            let mut overflowing = Vec::new();
+            for (tag, value) in metric
+                .tags()
+                .into_iter()
+                .flat_map(|tags| tags.iter_single())
+            {
+                if !self.limits_tag(tag) || value == self.config.overflow_value {
+                    continue;
+                }
+                let values = self
+                    .values
+                    .entry((metric.name().to_owned(), tag.to_owned()))
+                    .or_default();
+                if values.contains(value) {
+                    continue;
+                }
+                if values.len() < self.config.value_limit {
+                    values.insert(value.to_owned());
+                } else {
+                    overflowing.push(tag.to_owned());
+                }
+            }
+
+            if overflowing.is_empty() {
+                series.insert(metric.series().clone(), metric);
+                continue;
+            }
+
+            for tag in overflowing {
+                *collapsed
+                    .entry((metric.name().to_owned(), tag.clone()))
+                    .or_default() += 1;
+                metric.replace_tag(tag, self.config.overflow_value.clone());
+            }
+            match series.get_mut(metric.series()) {
+                Some(existing) => {
+                    if !existing.update(&metric) {
+                        unmerged.push(metric);
+                    }
+                }
+                None => {
+                    series.insert(metric.series().clone(), metric);
+                }
+            }
+        }
+
+        for (metric_name, tag) in &self.collapsed {
+            if !collapsed.contains_key(&(metric_name.clone(), tag.clone())) {
+                emit!(InternalMetricsTagValuesCollapsed {
+                    metric_name,
+                    tag,
+                    count: 0,
+                });
+            }
+        }
+        for ((metric_name, tag), count) in &collapsed {
+            emit!(InternalMetricsTagValuesCollapsed {
+                metric_name,
+                tag,
+                count: *count,
+            });
+        }
+        self.collapsed = collapsed.into_keys().collect();
+
+        series.into_values().chain(unmerged).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -348,4 +518,110 @@ mod tests {
 
         assert_eq!(event.as_metric().namespace(), Some(namespace));
     }
+
+    fn counter(name: &str, value: f64, command: &str) -> Metric {
+        Metric::new(
+            name,
+            vector_core::event::MetricKind::Absolute,
+            MetricValue::Counter { value },
+        )
+        .with_tags(Some(metric_tags!("command" => command, "host" => "foo")))
+    }
+
+    #[test]
+    fn limits_tag_cardinality() {
+        let mut limiter = CardinalityLimiter::new(CardinalityLimitConfig {
+            value_limit: 2,
+            tags: vec!["command".to_owned()],
+            overflow_value: default_overflow_value(),
+        });
+
+        let metrics = limiter.limit(vec![
+            counter("commands_total", 1.0, "a"),
+            counter("commands_total", 2.0, "b"),
+            counter("commands_total", 3.0, "c"),
+            counter("commands_total", 4.0, "d"),
+            counter("other_total", 5.0, "d"),
+        ]);
+        let values = metrics
+            .iter()
+            .map(|metric| {
+                (
+                    metric.name(),
+                    metric.tag_value("command").unwrap(),
+                    metric.value().clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                (
+                    "commands_total",
+                    "a".to_owned(),
+                    MetricValue::Counter { value: 1.0 }
+                ),
+                (
+                    "commands_total",
+                    "b".to_owned(),
+                    MetricValue::Counter { value: 2.0 }
+                ),
+                (
+                    "commands_total",
+                    "other".to_owned(),
+                    MetricValue::Counter { value: 7.0 }
+                ),
+                (
+                    "other_total",
+                    "d".to_owned(),
+                    MetricValue::Counter { value: 5.0 }
+                ),
+            ]
+        );
+
+        // The admitted values are kept across scrapes.
+        let metrics = limiter.limit(vec![
+            counter("commands_total", 1.0, "d"),
+            counter("commands_total", 1.0, "b"),
+        ]);
+        assert_eq!(metrics[0].tag_value("command").unwrap(), "other");
+        assert_eq!(metrics[1].tag_value("command").unwrap(), "b");
+        assert_eq!(metrics[0].tag_value("host").unwrap(), "foo");
+    }
+
+    #[tokio::test]
+    async fn sets_histogram_buckets() {
+        test_util::trace_init();
+
+        let config = InternalMetricsConfig {
+            histogram_buckets: HashMap::from([(
+                "internal_metrics_test_histogram".to_owned(),
+                vec![1.0, 10.0],
+            )]),
+            ..Default::default()
+        };
+        let (tx, _rx) = SourceSender::new_test();
+        let _source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        histogram!("internal_metrics_test_histogram", 5.0);
+
+        let metric = Controller::get()
+            .expect("no controller")
+            .capture_metrics()
+            .into_iter()
+            .find(|metric| metric.name() == "internal_metrics_test_histogram")
+            .unwrap();
+        match metric.value() {
+            MetricValue::AggregatedHistogram { buckets, .. } => {
+                let limits = buckets
+                    .iter()
+                    .map(|bucket| (bucket.upper_limit, bucket.count))
+                    .collect::<Vec<_>>();
+                assert_eq!(limits, vec![(1.0, 0), (10.0, 1), (f64::INFINITY, 0)]);
+            }
+            _ => panic!("wrong type"),
+        }
+    }
 }
@@ -28,6 +28,7 @@ use tokio::{
     time::sleep,
 };
 use tokio_util::codec::FramedRead;
+use tracing::Instrument;
 use value::{kind::Collection, Kind, Value};
 use vector_common::{
     finalizer::OrderedFinalizer,
@@ -64,6 +65,8 @@ const CURSOR: &str = "__CURSOR";
 const HOSTNAME: &str = "_HOSTNAME";
 const MESSAGE: &str = "MESSAGE";
 const SYSTEMD_UNIT: &str = "_SYSTEMD_UNIT";
+const SYSLOG_IDENTIFIER: &str = "SYSLOG_IDENTIFIER";
+const PRIORITY: &str = "PRIORITY";
 const SOURCE_TIMESTAMP: &str = "_SOURCE_REALTIME_TIMESTAMP";
 const RECEIVED_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
 
@@ -88,6 +91,11 @@ enum BuildError {
         value,
     ))]
     DuplicatedMatches { field: String, value: String },
+    #[snafu(display(
+        "The PRIORITY range {:?} is invalid, expected a range such as \"0..3\" or \"emerg..err\".",
+        range,
+    ))]
+    InvalidPriorityRange { range: String },
 }
 
 type Matches = HashMap<String, HashSet<String>>;
@@ -122,11 +130,22 @@ pub struct JournaldConfig {
     #[configurable(metadata(docs::examples = "badservice", docs::examples = "sysinit.target"))]
     pub exclude_units: Vec<String>,
 
+    /// A list of syslog identifiers to exclude from monitoring.
+    ///
+    /// Entries whose `SYSLOG_IDENTIFIER` field matches any of these values are excluded from this
+    /// source.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "kernel", docs::examples = "CRON"))]
+    pub exclude_syslog_identifiers: Vec<String>,
+
     /// A list of sets of field/value pairs to monitor.
     ///
     /// If empty or not present, all journal fields are accepted.
     ///
     /// If `include_units` is specified, it is merged into this list.
+    ///
+    /// Values of the `PRIORITY` field may be given as an inclusive range of priorities, such as
+    /// `0..3` or `emerg..err`.
     #[serde(default)]
     #[configurable(metadata(
         docs::additional_props_description = "The set of field values to match in journal entries that are to be included."
@@ -137,7 +156,11 @@ pub struct JournaldConfig {
     /// A list of sets of field/value pairs that, if any are present in a journal entry,
     /// excludes the entry from this source.
     ///
-    /// If `exclude_units` is specified, it is merged into this list.
+    /// If `exclude_units` or `exclude_syslog_identifiers` are specified, they are merged into this
+    /// list.
+    ///
+    /// Values of the `PRIORITY` field may be given as an inclusive range of priorities, such as
+    /// `0..3` or `emerg..err`.
     #[serde(default)]
     #[configurable(metadata(
         docs::additional_props_description = "The set of field values to match in journal entries that are to be excluded."
@@ -203,18 +226,29 @@ fn matches_examples() -> HashMap<String, Vec<String>> {
                 vec!["sshd.service".to_owned(), "ntpd.service".to_owned()],
             ),
             ("_TRANSPORT".to_owned(), vec!["kernel".to_owned()]),
+            ("PRIORITY".to_owned(), vec!["0..3".to_owned()]),
         ]
         .into_iter(),
     )
 }
 
 impl JournaldConfig {
-    fn merged_include_matches(&self) -> Matches {
-        Self::merge_units(&self.include_matches, &self.include_units)
+    fn merged_include_matches(&self) -> Result<Matches, BuildError> {
+        let mut matches = Self::merge_units(&self.include_matches, &self.include_units);
+        expand_priority_ranges(&mut matches)?;
+        Ok(matches)
     }
 
-    fn merged_exclude_matches(&self) -> Matches {
-        Self::merge_units(&self.exclude_matches, &self.exclude_units)
+    fn merged_exclude_matches(&self) -> Result<Matches, BuildError> {
+        let mut matches = Self::merge_units(&self.exclude_matches, &self.exclude_units);
+        if !self.exclude_syslog_identifiers.is_empty() {
+            matches
+                .entry(String::from(SYSLOG_IDENTIFIER))
+                .or_default()
+                .extend(self.exclude_syslog_identifiers.iter().cloned());
+        }
+        expand_priority_ranges(&mut matches)?;
+        Ok(matches)
     }
 
     fn merge_units(matches: &Matches, units: &[String]) -> Matches {
@@ -282,6 +316,7 @@ impl Default for JournaldConfig {
             current_boot_only: true,
             include_units: vec![],
             exclude_units: vec![],
+            exclude_syslog_identifiers: vec![],
             include_matches: Default::default(),
             exclude_matches: Default::default(),
             data_dir: None,
@@ -320,8 +355,8 @@ impl SourceConfig for JournaldConfig {
             return Err(BuildError::DuplicatedUnit { unit }.into());
         }
 
-        let include_matches = self.merged_include_matches();
-        let exclude_matches = self.merged_exclude_matches();
+        let include_matches = self.merged_include_matches()?;
+        let exclude_matches = self.merged_exclude_matches()?;
 
         if let Some((field, value)) = find_duplicate_match(&include_matches, &exclude_matches) {
             return Err(BuildError::DuplicatedMatches { field, value }.into());
@@ -823,23 +858,60 @@ fn decode_array_as_bytes(array: &[JsonValue]) -> Option<JsonValue> {
         .map(|array| String::from_utf8_lossy(&array).into())
 }
 
+/// The syslog priority names, indexed by their numeric value.
+const PRIORITY_NAMES: [&str; 8] = [
+    "EMERG", "ALERT", "CRIT", "ERR", "WARNING", "NOTICE", "INFO", "DEBUG",
+];
+
 fn remap_priority(priority: &mut JsonValue) {
     if let Some(num) = priority.as_str().and_then(|s| usize::from_str(s).ok()) {
-        let text = match num {
-            0 => "EMERG",
-            1 => "ALERT",
-            2 => "CRIT",
-            3 => "ERR",
-            4 => "WARNING",
-            5 => "NOTICE",
-            6 => "INFO",
-            7 => "DEBUG",
-            _ => "UNKNOWN",
-        };
+        let text = PRIORITY_NAMES.get(num).copied().unwrap_or("UNKNOWN");
         *priority = JsonValue::String(text.into());
     }
 }
 
+fn parse_priority(priority: &str) -> Option<usize> {
+    let priority = priority.trim();
+    match usize::from_str(priority) {
+        Ok(num) => (num < PRIORITY_NAMES.len()).then_some(num),
+        Err(_) => PRIORITY_NAMES
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(priority)),
+    }
+}
+
+/// Replaces any ranges in the `PRIORITY` matches, such as `0..3` or `emerg..err`, with each of the
+/// priorities they contain, in both their numeric and named forms, so that they match records
+/// whether or not `remap_priority` is enabled.
+fn expand_priority_ranges(matches: &mut Matches) -> Result<(), BuildError> {
+    let values = match matches.get_mut(PRIORITY) {
+        Some(values) => values,
+        None => return Ok(()),
+    };
+
+    let ranges = values
+        .iter()
+        .filter(|value| value.contains(".."))
+        .cloned()
+        .collect::<Vec<_>>();
+    for range in ranges {
+        values.remove(&range);
+        let (start, end) = range
+            .split_once("..")
+            .and_then(|(start, end)| Some((parse_priority(start)?, parse_priority(end)?)))
+            .filter(|(start, end)| start <= end)
+            .ok_or_else(|| BuildError::InvalidPriorityRange {
+                range: range.clone(),
+            })?;
+        for num in start..=end {
+            values.insert(num.to_string());
+            values.insert(PRIORITY_NAMES[num].to_owned());
+        }
+    }
+
+    Ok(())
+}
+
 fn filter_matches(record: &Record, includes: &Matches, excludes: &Matches) -> bool {
     match (includes.is_empty(), excludes.is_empty()) {
         (true, true) => false,
@@ -888,13 +960,16 @@ impl Finalizer {
     ) -> Self {
         if acknowledgements {
             let (finalizer, mut ack_stream) = OrderedFinalizer::new(shutdown);
-            tokio::spawn(async move {
-                while let Some((status, cursor)) = ack_stream.next().await {
-                    if status == BatchStatus::Delivered {
-                        checkpointer.lock().await.set(cursor).await;
+            tokio::spawn(
+                async move {
+                    while let Some((status, cursor)) = ack_stream.next().await {
+                        if status == BatchStatus::Delivered {
+                            checkpointer.lock().await.set(cursor).await;
+                        }
                     }
                 }
-            });
+                .in_current_span(),
+            );
             Self::Async(finalizer)
         } else {
             Self::Sync(checkpointer)
@@ -1216,6 +1291,63 @@ mod tests {
         assert_eq!(timestamp(&received[4]), value_ts(1578529839, 140006000));
     }
 
+    #[tokio::test]
+    async fn includes_priority_range() {
+        let mut matches = create_matches(vec![("PRIORITY", "crit..3")]);
+        expand_priority_ranges(&mut matches).unwrap();
+        let received = run_journal(matches, HashMap::new(), None).await;
+        assert_eq!(received.len(), 3);
+        assert_eq!(timestamp(&received[0]), value_ts(1578529839, 140004000));
+        assert_eq!(timestamp(&received[1]), value_ts(1578529839, 140005000));
+        assert_eq!(timestamp(&received[2]), value_ts(1578529839, 140005000));
+    }
+
+    #[test]
+    fn expands_priority_ranges() {
+        let mut matches = create_matches(vec![("PRIORITY", "0..1"), ("PRIORITY", "DEBUG")]);
+        expand_priority_ranges(&mut matches).unwrap();
+        assert_eq!(
+            matches[PRIORITY],
+            HashSet::from_iter(["0", "1", "EMERG", "ALERT", "DEBUG"].map(String::from))
+        );
+
+        for range in ["3..0", "0..8", "err..bogus", "..3"] {
+            let mut matches = create_matches(vec![("PRIORITY", range)]);
+            assert!(matches!(
+                expand_priority_ranges(&mut matches),
+                Err(BuildError::InvalidPriorityRange { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn merges_excluded_syslog_identifiers() {
+        let config = JournaldConfig {
+            exclude_units: vec!["unit".into()],
+            exclude_syslog_identifiers: vec!["kernel".into(), "CRON".into()],
+            ..Default::default()
+        };
+        let matches = config.merged_exclude_matches().unwrap();
+        assert_eq!(
+            matches[SYSLOG_IDENTIFIER],
+            HashSet::from_iter(["kernel", "CRON"].map(String::from))
+        );
+        assert_eq!(
+            matches[SYSTEMD_UNIT],
+            HashSet::from_iter(["unit.service".to_owned()])
+        );
+    }
+
+    #[tokio::test]
+    async fn excludes_syslog_identifiers() {
+        let matches = create_matches(vec![(SYSLOG_IDENTIFIER, "kernel")]);
+        let received = run_journal(HashMap::new(), matches, None).await;
+        assert_eq!(received.len(), 7);
+        assert!(received
+            .iter()
+            .all(|event| message(event) != Value::Bytes("audit log".into())));
+    }
+
     #[tokio::test]
     async fn handles_checkpoint() {
         let received = run_with_units(&[], &[], Some("1")).await;
@@ -9,18 +9,23 @@ use async_stream::stream;
 use bytes::Bytes;
 use chrono::{DateTime, TimeZone, Utc};
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
-    StreamDecodingError,
+    decoding::{
+        format::{self, confluent_schema_id},
+        Deserializer, DeserializerConfig, FramingConfig,
+    },
+    AvroDeserializer, StreamDecodingError,
 };
 use futures::{Stream, StreamExt};
-use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
+use lookup::{lookup_v2::OptionalValuePath, metadata_path, owned_value_path, path, OwnedValuePath};
 use once_cell::sync::OnceCell;
 use rdkafka::{
-    consumer::{CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
+    consumer::{BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
+    error::RDKafkaErrorCode,
     message::{BorrowedMessage, Headers as _, Message},
-    ClientConfig, ClientContext, Statistics,
+    ClientConfig, ClientContext, Offset, Statistics, TopicPartitionList,
 };
 use serde_with::serde_as;
+use smallvec::SmallVec;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 
@@ -33,16 +38,18 @@ use vector_core::{
 };
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{
-        log_schema, LogSchema, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        log_schema, ComponentKey, LogSchema, Output, ProxyConfig, SourceAcknowledgementsConfig,
+        SourceConfig, SourceContext,
     },
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaSchemaRegistryError, StreamClosedError,
     },
     kafka,
+    schema_registry::{SchemaRegistryClient, SchemaRegistryConfig, SchemaRegistryError},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     SourceSender,
@@ -54,6 +61,40 @@ enum BuildError {
     KafkaCreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     KafkaSubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not assign Kafka partitions: {}", source))]
+    KafkaAssignError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not look up Kafka offsets for timestamps: {}", source))]
+    KafkaOffsetsForTimesError { source: rdkafka::error::KafkaError },
+    #[snafu(display(
+        "A `group_id` is required unless partitions are assigned with `assignments`"
+    ))]
+    MissingGroupId,
+    #[snafu(display(
+        "Only one of `offset` and `timestamp_ms` can be set, for partition {} of topic {:?}",
+        partition,
+        topic
+    ))]
+    ConflictingAssignmentStart { topic: String, partition: u32 },
+    #[snafu(display(
+        "Acknowledgements are required when the offsets are committed by a `kafka` sink"
+    ))]
+    LinkedWithoutAcknowledgements,
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("Could not fetch the metadata of Kafka topic {:?}: {}", topic, source))]
+    FetchMetadata {
+        topic: String,
+        source: rdkafka::error::KafkaError,
+    },
+    #[snafu(display("Kafka topic {:?} is not available: {}", topic, code))]
+    TopicUnavailable {
+        topic: String,
+        code: RDKafkaErrorCode,
+    },
+    #[snafu(display("Kafka topic {:?} has no partition {}", topic, partition))]
+    MissingPartition { topic: String, partition: u32 },
 }
 
 /// Metrics configuration.
@@ -83,6 +124,9 @@ pub struct KafkaSourceConfig {
     /// The Kafka topics names to read events from.
     ///
     /// Regular expression syntax is supported if the topic begins with `^`.
+    ///
+    /// Ignored when `assignments` are set.
+    #[serde(default)]
     #[configurable(metadata(
         docs::examples = "^(prefix1|prefix2)-.+",
         docs::examples = "topic-1",
@@ -91,9 +135,22 @@ pub struct KafkaSourceConfig {
     topics: Vec<String>,
 
     /// The consumer group name to be used to consume events from Kafka.
+    ///
+    /// Required unless `assignments` are set.
+    #[serde(default)]
     #[configurable(metadata(docs::examples = "consumer-group-name"))]
     group_id: String,
 
+    /// Explicit partitions to consume, bypassing consumer groups.
+    ///
+    /// When set, `topics` is ignored, and only the listed partitions are consumed, starting from
+    /// the given offsets or timestamps. No consumer group is joined, and no offsets are committed,
+    /// so consumption starts from the same positions every time Vector starts. If `group_id` is
+    /// set, it is only passed along to the client.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    assignments: Vec<PartitionAssignment>,
+
     /// If offsets for consumer group do not exist, set them using this strategy.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for the `auto.offset.reset` option for further clarification.
@@ -197,6 +254,22 @@ pub struct KafkaSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
+    /// Decodes messages in the Confluent wire format, using Avro schemas from a schema registry.
+    ///
+    /// When set, the schema ID that prefixes each message is used to fetch the schema the message
+    /// is decoded with, and `decoding` is ignored. Schemas are cached once fetched.
+    ///
+    /// The messages whose schema can't be resolved, such as while the registry is unreachable, are
+    /// handled according to `on_decode_error`. With `drop`, they're read again a second later, so
+    /// that neither their offsets nor the ones of the messages after them are committed in the
+    /// meantime. Otherwise they're forwarded or routed as is.
+    #[configurable(derived)]
+    schema_registry: Option<SchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -211,10 +284,42 @@ pub struct KafkaSourceConfig {
     metrics: Metrics,
 }
 
+/// A partition to consume, and where to start consuming it from.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct PartitionAssignment {
+    /// The topic of the partition.
+    #[configurable(metadata(docs::examples = "topic-1"))]
+    topic: String,
+
+    /// The partition number.
+    #[configurable(metadata(docs::examples = 0))]
+    partition: u32,
+
+    /// The offset of the first message to consume.
+    ///
+    /// If neither `offset` nor `timestamp_ms` is set, the partition is consumed from the beginning.
+    #[configurable(metadata(docs::examples = 1024))]
+    offset: Option<u64>,
+
+    /// The timestamp, in milliseconds since the Unix epoch, from which to consume.
+    ///
+    /// Consumption starts from the first message with a timestamp greater than or equal to this
+    /// one, or from the end of the partition if there is no such message.
+    #[configurable(metadata(docs::examples = 1672531200000_u64))]
+    timestamp_ms: Option<u64>,
+}
+
 impl KafkaSourceConfig {
     fn keys(&self) -> Keys {
         Keys::from(log_schema(), self)
     }
+
+    /// Whether partitions are assigned by joining a consumer group, which offsets are committed to.
+    fn uses_consumer_group(&self) -> bool {
+        self.assignments.is_empty()
+    }
 }
 
 const fn default_session_timeout_ms() -> Duration {
@@ -287,15 +392,28 @@ impl SourceConfig for KafkaSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        let consumer = create_consumer(self)?;
+        let consumer = create_consumer(self, cx.linked)?;
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
+        let schema_registry = self
+            .schema_registry
+            .as_ref()
+            .map(|config| SchemaRegistryClient::new(config, &cx.proxy))
+            .transpose()?;
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        if cx.linked && !acknowledgements {
+            return Err(BuildError::LinkedWithoutAcknowledgements.into());
+        }
 
         Ok(Box::pin(kafka_source(
             self.clone(),
+            cx.key,
+            cx.linked,
             consumer,
             decoder,
+            schema_registry,
             cx.shutdown,
             cx.out,
             acknowledgements,
@@ -307,8 +425,16 @@ impl SourceConfig for KafkaSourceConfig {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
         let keys = self.keys();
 
-        let schema_definition = self
-            .decoding
+        // Avro records decode to the same shape of events as JSON objects.
+        let decoding = if self.schema_registry.is_some() {
+            DeserializerConfig::Json {
+                json: Default::default(),
+            }
+        } else {
+            self.decoding.clone()
+        };
+
+        let schema_definition = decoding
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata()
             .with_source_metadata(
@@ -354,24 +480,142 @@ impl SourceConfig for KafkaSourceConfig {
                 None,
             );
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(decoding.output_type()).with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    async fn healthcheck(&self, proxy: &ProxyConfig) -> Option<crate::Result<()>> {
+        Some(healthcheck(self.clone(), proxy).await)
+    }
+
+    fn check_linked(&self) -> Result<(), String> {
+        if self.uses_consumer_group() {
+            Ok(())
+        } else {
+            Err(
+                "the source doesn't use a consumer group, as its partitions are assigned"
+                    .to_owned(),
+            )
+        }
+    }
 }
 
+/// Checks that the brokers can be reached with the configured credentials, that the topics to read
+/// from exist, along with the assigned partitions, and that the schema registry can be reached.
+///
+/// A separate client is used, which doesn't join the consumer group so as not to disturb the
+/// consumers of the group that are running.
+async fn healthcheck(config: KafkaSourceConfig, proxy: &ProxyConfig) -> crate::Result<()> {
+    if let Some(registry) = &config.schema_registry {
+        SchemaRegistryClient::new(registry, proxy)?
+            .healthcheck()
+            .await?;
+    }
+
+    let mut client_config = ClientConfig::new();
+    client_config
+        .set("bootstrap.servers", &config.bootstrap_servers)
+        .set(
+            "socket.timeout.ms",
+            &config.socket_timeout_ms.as_millis().to_string(),
+        )
+        .set("client.id", "vector");
+    config.auth.apply(&mut client_config)?;
+    if let Some(librdkafka_options) = &config.librdkafka_options {
+        for (key, value) in librdkafka_options {
+            client_config.set(key.as_str(), value.as_str());
+        }
+    }
+    let consumer: BaseConsumer = client_config.create().context(KafkaCreateSnafu)?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut topics: Vec<(&str, Vec<u32>)> = Vec::new();
+        if config.uses_consumer_group() {
+            // Topics given as patterns can't be looked up.
+            topics.extend(
+                config
+                    .topics
+                    .iter()
+                    .filter(|topic| !topic.starts_with('^'))
+                    .map(|topic| (topic.as_str(), Vec::new())),
+            );
+        } else {
+            for assignment in &config.assignments {
+                match topics
+                    .iter_mut()
+                    .find(|(topic, _)| *topic == assignment.topic)
+                {
+                    Some((_, partitions)) => partitions.push(assignment.partition),
+                    None => topics.push((assignment.topic.as_str(), vec![assignment.partition])),
+                }
+            }
+        }
+
+        for (topic, partitions) in topics {
+            let metadata = consumer
+                .fetch_metadata(Some(topic), Duration::from_secs(3))
+                .context(FetchMetadataSnafu { topic })?;
+            for found in metadata.topics() {
+                if let Some(error) = found.error() {
+                    return Err(HealthcheckError::TopicUnavailable {
+                        topic: topic.to_owned(),
+                        code: error.into(),
+                    });
+                }
+                if let Some(partition) = partitions.iter().find(|partition| {
+                    !found
+                        .partitions()
+                        .iter()
+                        .any(|metadata| metadata.id() == **partition as i32)
+                }) {
+                    return Err(HealthcheckError::MissingPartition {
+                        topic: topic.to_owned(),
+                        partition: *partition,
+                    });
+                }
+            }
+        }
+        Ok(())
+    })
+    .await??;
+    Ok(())
+}
+
+/// How long to wait before reading a message whose schema couldn't be resolved again.
+const SCHEMA_RETRY_DELAY: Duration = Duration::from_secs(1);
+
 async fn kafka_source(
     config: KafkaSourceConfig,
+    key: ComponentKey,
+    linked: bool,
     consumer: StreamConsumer<CustomContext>,
     decoder: Decoder,
+    schema_registry: Option<SchemaRegistryClient>,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
     log_namespace: LogNamespace,
 ) -> Result<(), ()> {
     let consumer = Arc::new(consumer);
+    if linked {
+        let consumer = Arc::downgrade(&consumer);
+        kafka::register_consumer_group(
+            key.id(),
+            Arc::new(move || {
+                consumer
+                    .upgrade()
+                    .and_then(|consumer| consumer.group_metadata())
+            }),
+        );
+    }
+    // The offsets of a linked source are committed by the sink instead.
+    let store_offsets = config.uses_consumer_group() && !linked;
     let (finalizer, mut ack_stream) =
         OrderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
     let finalizer = finalizer.map(Arc::new);
@@ -389,7 +633,7 @@ async fn kafka_source(
         tokio::select! {
             _ = &mut shutdown => break,
             entry = ack_stream.next() => if let Some((status, entry)) = entry {
-                if status == BatchStatus::Delivered {
+                if status == BatchStatus::Delivered && store_offsets {
                     if let Err(error) =
                         consumer.store_offset(&entry.topic, entry.partition, entry.offset)
                     {
@@ -408,7 +652,52 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
-                    parse_message(msg, decoder.clone(), config.keys(), &finalizer, &mut out, &consumer, log_namespace).await;
+                    let decoder = match &schema_registry {
+                        Some(registry) => match schema_registry_decoder(
+                            registry,
+                            &msg,
+                            &config.framing,
+                            config.on_decode_error,
+                            log_namespace,
+                        ).await {
+                            Ok(decoder) => decoder,
+                            Err(error) => {
+                                let reason = error.to_string();
+                                emit!(KafkaSchemaRegistryError { error });
+                                if config.on_decode_error != DecodeErrorPolicy::Drop {
+                                    unresolved_schema_decoder(
+                                        &config.framing,
+                                        reason,
+                                        config.on_decode_error,
+                                        log_namespace,
+                                    )
+                                } else {
+                                    // Reading the message again later keeps its offset, and the
+                                    // ones after it, from being committed.
+                                    tokio::select! {
+                                        _ = &mut shutdown => break,
+                                        _ = tokio::time::sleep(SCHEMA_RETRY_DELAY) => {}
+                                    }
+                                    if let Err(error) = consumer.seek(
+                                        msg.topic(),
+                                        msg.partition(),
+                                        Offset::Offset(msg.offset()),
+                                        SCHEMA_RETRY_DELAY,
+                                    ) {
+                                        emit!(KafkaReadError { error });
+                                    }
+                                    continue;
+                                }
+                            }
+                        },
+                        None => decoder.clone(),
+                    };
+
+                    let mut keys = config.keys();
+                    if linked {
+                        keys.linked_source = Some(key.id());
+                    }
+                    parse_message(msg, decoder, keys, &finalizer, &mut out, &consumer, store_offsets, log_namespace).await;
                 }
             },
         }
@@ -416,14 +705,65 @@ async fn kafka_source(
 
     // Since commits are async internally, we try one last sync commit inside the interval
     // in case there have been acks.
-    if let Ok(current_assignment) = consumer.assignment() {
-        // not logging on error because it will error if there are no offsets stored for a partition,
-        // and this is best-effort cleanup anyway
-        let _ = consumer.commit(&current_assignment, CommitMode::Sync);
+    if store_offsets {
+        if let Ok(current_assignment) = consumer.assignment() {
+            // not logging on error because it will error if there are no offsets stored for a partition,
+            // and this is best-effort cleanup anyway
+            let _ = consumer.commit(&current_assignment, CommitMode::Sync);
+        }
     }
     Ok(())
 }
 
+/// Builds a decoder for a message in the Confluent wire format, using the Avro schema referenced by
+/// the schema ID that prefixes the message.
+async fn schema_registry_decoder(
+    registry: &SchemaRegistryClient,
+    msg: &BorrowedMessage<'_>,
+    framing: &FramingConfig,
+    error_policy: DecodeErrorPolicy,
+    log_namespace: LogNamespace,
+) -> Result<Decoder, SchemaRegistryError> {
+    let id = msg
+        .payload()
+        .and_then(confluent_schema_id)
+        .ok_or(SchemaRegistryError::MissingSchemaId)?;
+    let schema = registry.schema_by_id(id).await?;
+    let deserializer = Deserializer::Boxed(Box::new(AvroDeserializer::new(schema, true)));
+
+    Ok(Decoder::new(framing.build(), deserializer)
+        .with_log_namespace(log_namespace)
+        .with_error_policy(error_policy))
+}
+
+/// Builds a decoder for a message whose schema couldn't be resolved, failing to parse its frames for
+/// them to be forwarded or routed as is, with the `reason` the schema couldn't be resolved.
+fn unresolved_schema_decoder(
+    framing: &FramingConfig,
+    reason: String,
+    error_policy: DecodeErrorPolicy,
+    log_namespace: LogNamespace,
+) -> Decoder {
+    let deserializer = Deserializer::Boxed(Box::new(UnresolvedSchema(reason)));
+    Decoder::new(framing.build(), deserializer)
+        .with_log_namespace(log_namespace)
+        .with_error_policy(error_policy)
+}
+
+/// A deserializer failing to parse any payload, as its schema couldn't be resolved.
+#[derive(Clone, Debug)]
+struct UnresolvedSchema(String);
+
+impl format::Deserializer for UnresolvedSchema {
+    fn parse(
+        &self,
+        _bytes: Bytes,
+        _log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        Err(self.0.clone().into())
+    }
+}
+
 async fn parse_message(
     msg: BorrowedMessage<'_>,
     decoder: Decoder,
@@ -431,6 +771,7 @@ async fn parse_message(
     finalizer: &Option<Arc<OrderedFinalizer<FinalizerEntry>>>,
     out: &mut SourceSender,
     consumer: &Arc<StreamConsumer<CustomContext>>,
+    store_offsets: bool,
     log_namespace: LogNamespace,
 ) {
     if let Some((count, mut stream)) = parse_stream(&msg, decoder, keys, log_namespace) {
@@ -454,13 +795,14 @@ async fn parse_message(
                 Err(error) => {
                     emit!(StreamClosedError { error, count });
                 }
-                Ok(_) => {
+                Ok(_) if store_offsets => {
                     if let Err(error) =
                         consumer.store_offset(msg.topic(), msg.partition(), msg.offset())
                     {
                         emit!(KafkaOffsetUpdateError { error });
                     }
                 }
+                Ok(_) => {}
             },
         }
     }
@@ -518,6 +860,8 @@ struct Keys<'a> {
     partition: &'a Option<OwnedValuePath>,
     offset: &'a Option<OwnedValuePath>,
     headers: &'a Option<OwnedValuePath>,
+    /// The ID of the source, when a kafka sink commits its offsets in its transactions.
+    linked_source: Option<&'a str>,
 }
 
 impl<'a> Keys<'a> {
@@ -529,6 +873,7 @@ impl<'a> Keys<'a> {
             partition: &config.partition_key.path,
             offset: &config.offset_key.path,
             headers: &config.headers_key.path,
+            linked_source: None,
         }
     }
 }
@@ -643,6 +988,18 @@ impl ReceivedMessage {
                 path!("headers"),
                 self.headers.clone(),
             );
+
+            if let Some(source) = keys.linked_source {
+                log.insert(
+                    metadata_path!("vector", kafka::OFFSET_METADATA_KEY),
+                    BTreeMap::from([
+                        ("source".to_owned(), Value::from(source)),
+                        ("topic".to_owned(), Value::from(self.topic.clone())),
+                        ("partition".to_owned(), Value::from(self.partition)),
+                        ("offset".to_owned(), Value::from(self.offset)),
+                    ]),
+                );
+            }
         }
     }
 }
@@ -664,10 +1021,19 @@ impl<'a> From<BorrowedMessage<'a>> for FinalizerEntry {
     }
 }
 
-fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<CustomContext>> {
+fn create_consumer(
+    config: &KafkaSourceConfig,
+    linked: bool,
+) -> crate::Result<StreamConsumer<CustomContext>> {
+    if config.uses_consumer_group() && config.group_id.is_empty() {
+        return Err(BuildError::MissingGroupId.into());
+    }
+
     let mut client_config = ClientConfig::new();
+    if !config.group_id.is_empty() {
+        client_config.set("group.id", &config.group_id);
+    }
     client_config
-        .set("group.id", &config.group_id)
         .set("bootstrap.servers", &config.bootstrap_servers)
         .set("auto.offset.reset", &config.auto_offset_reset)
         .set(
@@ -683,7 +1049,14 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<C
             &config.fetch_wait_max_ms.as_millis().to_string(),
         )
         .set("enable.partition.eof", "false")
-        .set("enable.auto.commit", "true")
+        .set(
+            "enable.auto.commit",
+            if config.uses_consumer_group() {
+                "true"
+            } else {
+                "false"
+            },
+        )
         .set(
             "auto.commit.interval.ms",
             &config.commit_interval_ms.as_millis().to_string(),
@@ -700,17 +1073,72 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<C
         }
     }
 
+    // The offsets of a linked source are only committed in the transactions of the sink, with the
+    // messages produced from them.
+    if linked {
+        client_config.set("enable.auto.commit", "false");
+    }
+
     let consumer = client_config
         .create_with_context::<_, StreamConsumer<_>>(CustomContext::new(
             config.metrics.topic_lag_metric,
         ))
         .context(KafkaCreateSnafu)?;
-    let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
-    consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+
+    if config.uses_consumer_group() {
+        let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
+        consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+    } else {
+        assign_partitions(&consumer, config)?;
+    }
 
     Ok(consumer)
 }
 
+/// Assigns the consumer the partitions listed in `assignments`, starting from their configured
+/// positions.
+///
+/// Starting positions given as timestamps are resolved to offsets by the brokers.
+fn assign_partitions(
+    consumer: &StreamConsumer<CustomContext>,
+    config: &KafkaSourceConfig,
+) -> crate::Result<()> {
+    let mut offsets = TopicPartitionList::new();
+    let mut timestamps = TopicPartitionList::new();
+
+    for assignment in &config.assignments {
+        let (list, offset) = match (assignment.offset, assignment.timestamp_ms) {
+            (Some(_), Some(_)) => {
+                return Err(BuildError::ConflictingAssignmentStart {
+                    topic: assignment.topic.clone(),
+                    partition: assignment.partition,
+                }
+                .into())
+            }
+            (Some(offset), None) => (&mut offsets, Offset::Offset(offset as i64)),
+            (None, Some(timestamp)) => (&mut timestamps, Offset::Offset(timestamp as i64)),
+            (None, None) => (&mut offsets, Offset::Beginning),
+        };
+        list.add_partition_offset(&assignment.topic, assignment.partition as i32, offset)
+            .context(KafkaAssignSnafu)?;
+    }
+
+    if timestamps.count() > 0 {
+        let resolved = consumer
+            .offsets_for_times(timestamps, config.socket_timeout_ms)
+            .context(KafkaOffsetsForTimesSnafu)?;
+        for element in resolved.elements() {
+            offsets
+                .add_partition_offset(element.topic(), element.partition(), element.offset())
+                .context(KafkaAssignSnafu)?;
+        }
+    }
+
+    consumer.assign(&offsets).context(KafkaAssignSnafu)?;
+
+    Ok(())
+}
+
 #[derive(Default)]
 struct CustomContext {
     stats: kafka::KafkaStatisticsContext,
@@ -748,6 +1176,7 @@ mod test {
     use vector_core::schema::Definition;
 
     use super::*;
+    use crate::event::LogEvent;
 
     pub fn kafka_host() -> String {
         std::env::var("KAFKA_HOST").unwrap_or_else(|_| "localhost".into())
@@ -762,6 +1191,24 @@ mod test {
         crate::test_util::test_generate_config::<KafkaSourceConfig>();
     }
 
+    #[test]
+    fn forwards_messages_with_unresolved_schemas_as_is() {
+        use tokio_util::codec::Decoder as _;
+
+        let mut decoder = unresolved_schema_decoder(
+            &default_framing_message_based(),
+            SchemaRegistryError::MissingSchemaId.to_string(),
+            DecodeErrorPolicy::ForwardRaw,
+            LogNamespace::Legacy,
+        );
+        let mut payload = bytes::BytesMut::from("not avro");
+        let (events, _) = decoder.decode_eof(&mut payload).unwrap().unwrap();
+
+        let log = events[0].as_log();
+        assert!(crate::codecs::is_decode_error(log));
+        assert_eq!(log[log_schema().message_key()], "not avro".into());
+    }
+
     pub(super) fn make_config(
         topic: &str,
         group: &str,
@@ -871,7 +1318,7 @@ mod test {
     #[tokio::test]
     async fn consumer_create_ok() {
         let config = make_config("topic", "group", LogNamespace::Legacy);
-        assert!(create_consumer(&config).is_ok());
+        assert!(create_consumer(&config, false).is_ok());
     }
 
     #[tokio::test]
@@ -880,7 +1327,97 @@ mod test {
             auto_offset_reset: "incorrect-auto-offset-reset".to_string(),
             ..make_config("topic", "group", LogNamespace::Legacy)
         };
-        assert!(create_consumer(&config).is_err());
+        assert!(create_consumer(&config, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn consumer_create_requires_group_id() {
+        let config = make_config("topic", "", LogNamespace::Legacy);
+        assert!(create_consumer(&config, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn consumer_create_with_assignments() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9091"
+
+            [[assignments]]
+            topic = "topic"
+            partition = 0
+
+            [[assignments]]
+            topic = "topic"
+            partition = 1
+            offset = 42
+            "#,
+        )
+        .unwrap();
+        assert!(!config.uses_consumer_group());
+
+        let consumer = create_consumer(&config, false).unwrap();
+        let assignment = consumer.assignment().unwrap();
+        assert_eq!(assignment.count(), 2);
+        assert_eq!(
+            assignment.find_partition("topic", 1).unwrap().offset(),
+            Offset::Offset(42)
+        );
+    }
+
+    #[tokio::test]
+    async fn consumer_create_conflicting_assignment_start() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9091"
+
+            [[assignments]]
+            topic = "topic"
+            partition = 0
+            offset = 42
+            timestamp_ms = 1672531200000
+            "#,
+        )
+        .unwrap();
+        assert!(create_consumer(&config, false).is_err());
+    }
+
+    #[test]
+    fn tags_the_events_of_a_linked_source_with_their_offset() {
+        let config = make_config("topic", "group", LogNamespace::Legacy);
+        let message = ReceivedMessage {
+            timestamp: None,
+            key: Value::Null,
+            headers: BTreeMap::new(),
+            topic: "topic".to_owned(),
+            partition: 2,
+            offset: 42,
+        };
+
+        let mut event = Event::Log(LogEvent::from("message"));
+        message.apply(&config.keys(), &mut event, LogNamespace::Legacy);
+        assert_eq!(
+            event
+                .as_log()
+                .get(metadata_path!("vector", kafka::OFFSET_METADATA_KEY)),
+            None
+        );
+
+        let mut keys = config.keys();
+        keys.linked_source = Some("in");
+        let mut event = Event::Log(LogEvent::from("message"));
+        message.apply(&keys, &mut event, LogNamespace::Legacy);
+        assert_eq!(
+            event
+                .as_log()
+                .get(metadata_path!("vector", kafka::OFFSET_METADATA_KEY))
+                .unwrap(),
+            &Value::from(BTreeMap::from([
+                ("source".to_owned(), Value::from("in")),
+                ("topic".to_owned(), Value::from("topic")),
+                ("partition".to_owned(), Value::from(2)),
+                ("offset".to_owned(), Value::from(42)),
+            ]))
+        );
     }
 }
 
@@ -1132,19 +1669,23 @@ mod integration_test {
         log_namespace: LogNamespace,
     ) -> (Trigger, Tripwire) {
         let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
-        let consumer = create_consumer(&config).unwrap();
+        let consumer = create_consumer(&config, false).unwrap();
 
         let decoder = DecodingConfig::new(
             config.framing.clone(),
             config.decoding.clone(),
             log_namespace,
         )
-        .build();
+        .build()
+        .unwrap();
 
         tokio::spawn(kafka_source(
             config,
+            ComponentKey::from("kafka"),
+            false,
             consumer,
             decoder,
+            None,
             shutdown,
             tx,
             acknowledgements,
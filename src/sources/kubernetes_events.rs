@@ -0,0 +1,613 @@
+//! The `kubernetes_events` source. See [KubernetesEventsConfig].
+//!
+//! Watches the Events API, and remembers the resource versions of the events it emitted, so the
+//! events listed again when the watch is restarted, or when Vector is, aren't emitted twice.
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::PathBuf,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::{stream, StreamExt};
+use k8s_openapi::api::core::v1::Event as KubeEvent;
+use kube::{
+    api::{Api, ListParams},
+    config::{self, KubeConfigOptions},
+    runtime::watcher,
+    Client, Config as ClientConfig,
+};
+use lookup::{owned_value_path, path};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use value::{kind::Collection, Kind};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{DataType, Output, SourceConfig, SourceContext},
+    internal_events::{
+        EventsReceived, KubernetesEventsCheckpointError, KubernetesEventsWatchError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const CHECKPOINT_FILENAME: &str = "checkpoints.json";
+
+/// How often the checkpoints are written, if they changed.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait before watching again after the watch failed.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Configuration for the `kubernetes_events` source.
+#[configurable_component(source("kubernetes_events"))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesEventsConfig {
+    /// The [Namespaces][namespaces] to watch the events of.
+    ///
+    /// If empty, the events of all Namespaces are watched.
+    ///
+    /// [namespaces]: https://kubernetes.io/docs/concepts/overview/working-with-objects/namespaces/
+    #[configurable(metadata(docs::examples = "default"))]
+    #[configurable(metadata(docs::examples = "kube-system"))]
+    namespaces: Vec<String>,
+
+    /// Specifies the [field selector][field_selector] to filter the events with.
+    ///
+    /// [field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+    #[configurable(metadata(docs::examples = "type=Warning"))]
+    #[configurable(metadata(docs::examples = "involvedObject.kind=Pod,reason!=Pulled"))]
+    field_selector: String,
+
+    /// Specifies the [label selector][label_selector] to filter the events with.
+    ///
+    /// [label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+    #[configurable(metadata(docs::examples = "my_custom_label!=my_value"))]
+    label_selector: String,
+
+    /// Ignore events last observed more than the specified number of seconds ago.
+    ///
+    /// The events that occurred before Vector started are listed when the watch starts, so this
+    /// limits how far back they are emitted from the first time the source runs.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 3600))]
+    ignore_older_secs: Option<u64>,
+
+    /// The directory used to persist the versions of the events that were emitted.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[configurable(metadata(docs::examples = "/var/local/lib/vector/"))]
+    data_dir: Option<PathBuf>,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    kube_config_file: Option<PathBuf>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+impl_generate_config_from_default!(KubernetesEventsConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for KubernetesEventsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpoints = Checkpoints::load(data_dir.join(CHECKPOINT_FILENAME))?;
+
+        let watcher = EventWatcher {
+            checkpoints,
+            ignore_older: self
+                .ignore_older_secs
+                .map(|secs| chrono::Duration::seconds(secs as i64)),
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+            out: cx.out,
+        };
+        let params = ListParams {
+            field_selector: non_empty(&self.field_selector),
+            label_selector: non_empty(&self.label_selector),
+            ..Default::default()
+        };
+        let apis = if self.namespaces.is_empty() {
+            vec![(None, Api::all(client))]
+        } else {
+            self.namespaces
+                .iter()
+                .map(|namespace| {
+                    (
+                        Some(namespace.clone()),
+                        Api::namespaced(client.clone(), namespace),
+                    )
+                })
+                .collect()
+        };
+
+        Ok(Box::pin(watcher.run(apis, params, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition = match log_namespace {
+            LogNamespace::Legacy => Definition::empty_legacy_namespace(),
+            LogNamespace::Vector => Definition::new_with_default_metadata(
+                Kind::object(Collection::empty()),
+                [log_namespace],
+            ),
+        }
+        .with_event_field(
+            &owned_value_path!("metadata"),
+            Kind::object(Collection::any()),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("involvedObject"),
+            Kind::object(Collection::any()),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("reason"),
+            Kind::bytes().or_undefined(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("message"),
+            Kind::bytes().or_undefined(),
+            Some("message"),
+        )
+        .with_event_field(
+            &owned_value_path!("type"),
+            Kind::bytes().or_undefined(),
+            None,
+        )
+        .unknown_fields(Kind::json())
+        .with_source_metadata(
+            Self::NAME,
+            log_schema()
+                .timestamp_key()
+                .cloned()
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("timestamp"),
+            Kind::timestamp().or_undefined(),
+            Some("timestamp"),
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn non_empty(selector: &str) -> Option<String> {
+    (!selector.is_empty()).then(|| selector.to_owned())
+}
+
+/// The version of an event that was emitted.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Seen {
+    namespace: String,
+    resource_version: String,
+}
+
+/// The versions of the events that were emitted, by their UID.
+struct Checkpoints {
+    filename: PathBuf,
+    seen: HashMap<String, Seen>,
+    dirty: bool,
+}
+
+impl Checkpoints {
+    fn load(filename: PathBuf) -> io::Result<Self> {
+        let seen = match std::fs::read(&filename) {
+            Ok(data) => serde_json::from_slice(&data)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            filename,
+            seen,
+            dirty: false,
+        })
+    }
+
+    /// Records the version of the event, returning whether it wasn't seen before.
+    fn observe(&mut self, event: &KubeEvent) -> bool {
+        let (uid, resource_version) = match (&event.metadata.uid, &event.metadata.resource_version)
+        {
+            (Some(uid), Some(resource_version)) => (uid, resource_version),
+            // Events without a version can't be told apart, so they are always emitted.
+            _ => return true,
+        };
+        let seen = Seen {
+            namespace: event.metadata.namespace.clone().unwrap_or_default(),
+            resource_version: resource_version.clone(),
+        };
+
+        if self.seen.get(uid) == Some(&seen) {
+            false
+        } else {
+            self.seen.insert(uid.clone(), seen);
+            self.dirty = true;
+            true
+        }
+    }
+
+    fn forget(&mut self, event: &KubeEvent) {
+        if let Some(uid) = &event.metadata.uid {
+            self.dirty |= self.seen.remove(uid).is_some();
+        }
+    }
+
+    /// Forgets the events of the namespace, or of all namespaces, that are no longer listed,
+    /// since they were deleted while they weren't watched.
+    fn retain_listed(&mut self, namespace: Option<&str>, events: &[KubeEvent]) {
+        let listed = events
+            .iter()
+            .filter_map(|event| event.metadata.uid.as_deref())
+            .collect::<HashSet<_>>();
+        let count = self.seen.len();
+        self.seen.retain(|uid, seen| {
+            namespace.map_or(false, |namespace| namespace != seen.namespace)
+                || listed.contains(uid.as_str())
+        });
+        self.dirty |= self.seen.len() != count;
+    }
+
+    fn persist(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        match self.write() {
+            Ok(()) => self.dirty = false,
+            Err(error) => emit!(KubernetesEventsCheckpointError {
+                error,
+                filename: &self.filename,
+            }),
+        }
+    }
+
+    /// Writes the checkpoints to a temporary file first, so they can't be left half-written.
+    fn write(&self) -> io::Result<()> {
+        let temporary = self.filename.with_extension("json.tmp");
+        std::fs::write(&temporary, serde_json::to_vec(&self.seen)?)?;
+        std::fs::rename(&temporary, &self.filename)
+    }
+}
+
+struct EventWatcher {
+    checkpoints: Checkpoints,
+    ignore_older: Option<chrono::Duration>,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+}
+
+impl EventWatcher {
+    async fn run(
+        mut self,
+        apis: Vec<(Option<String>, Api<KubeEvent>)>,
+        params: ListParams,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let mut watches = stream::select_all(apis.into_iter().map(|(namespace, api)| {
+            watcher(api, params.clone())
+                .map(move |result| (namespace.clone(), result))
+                .boxed()
+        }));
+        let mut checkpoint_interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+
+        let result = loop {
+            tokio::select! {
+                _ = &mut shutdown => break Ok(()),
+                _ = checkpoint_interval.tick() => self.checkpoints.persist(),
+                item = watches.next() => match item {
+                    Some((namespace, Ok(event))) => {
+                        if let Err(error) = self.handle(namespace.as_deref(), event).await {
+                            break Err(error);
+                        }
+                    }
+                    Some((_, Err(error))) => {
+                        emit!(KubernetesEventsWatchError { error });
+                        tokio::select! {
+                            _ = &mut shutdown => break Ok(()),
+                            _ = tokio::time::sleep(RETRY_DELAY) => {},
+                        }
+                    }
+                    // A watcher stream never ends.
+                    None => break Ok(()),
+                },
+            }
+        };
+
+        self.checkpoints.persist();
+        result
+    }
+
+    async fn handle(
+        &mut self,
+        namespace: Option<&str>,
+        event: watcher::Event<KubeEvent>,
+    ) -> Result<(), ()> {
+        let objects = match event {
+            watcher::Event::Applied(object) => vec![object],
+            watcher::Event::Deleted(object) => {
+                self.checkpoints.forget(&object);
+                return Ok(());
+            }
+            watcher::Event::Restarted(objects) => {
+                self.checkpoints.retain_listed(namespace, &objects);
+                objects
+            }
+        };
+
+        let now = Utc::now();
+        let mut events = Vec::new();
+        for object in objects {
+            if self.checkpoints.observe(&object) {
+                events.extend(self.to_event(&object, now));
+            }
+        }
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let count = events.len();
+        self.events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+        self.out.send_batch(events).await.map_err(|error| {
+            emit!(StreamClosedError { error, count });
+        })
+    }
+
+    /// Converts the event to a log event, unless it's older than `ignore_older_secs`.
+    fn to_event(&self, object: &KubeEvent, now: DateTime<Utc>) -> Option<Event> {
+        let timestamp = event_timestamp(object);
+        if let (Some(ignore_older), Some(timestamp)) = (self.ignore_older, timestamp) {
+            if now - timestamp > ignore_older {
+                return None;
+            }
+        }
+
+        let mut json = serde_json::to_value(object).ok()?;
+        // The fields managed by each client are internal to the API server.
+        if let Some(metadata) = json.get_mut("metadata").and_then(JsonValue::as_object_mut) {
+            metadata.remove("managedFields");
+        }
+        let value = Value::from(json);
+        self.bytes_received
+            .emit(ByteSize(value.estimated_json_encoded_size_of()));
+
+        let mut log = LogEvent::from(value);
+        if let Some(timestamp) = timestamp {
+            self.log_namespace.insert_source_metadata(
+                KubernetesEventsConfig::NAME,
+                &mut log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                timestamp,
+            );
+        }
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            KubernetesEventsConfig::NAME,
+            now,
+        );
+
+        Some(log.into())
+    }
+}
+
+/// Returns the last time the event was observed.
+fn event_timestamp(event: &KubeEvent) -> Option<DateTime<Utc>> {
+    event
+        .series
+        .as_ref()
+        .and_then(|series| series.last_observed_time.as_ref())
+        .map(|time| time.0)
+        .or_else(|| event.last_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| event.event_time.as_ref().map(|time| time.0))
+        .or_else(|| event.first_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| {
+            event
+                .metadata
+                .creation_timestamp
+                .as_ref()
+                .map(|time| time.0)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use k8s_openapi::api::core::v1::ObjectReference;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ManagedFieldsEntry, ObjectMeta, Time};
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesEventsConfig>();
+    }
+
+    fn kube_event(uid: &str, namespace: &str, resource_version: &str) -> KubeEvent {
+        KubeEvent {
+            metadata: ObjectMeta {
+                name: Some(format!("{}.17a2b3c4d5e6f789", uid)),
+                namespace: Some(namespace.to_owned()),
+                uid: Some(uid.to_owned()),
+                resource_version: Some(resource_version.to_owned()),
+                managed_fields: Some(vec![ManagedFieldsEntry {
+                    manager: Some("kubelet".to_owned()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            involved_object: ObjectReference {
+                kind: Some("Pod".to_owned()),
+                name: Some("web-0".to_owned()),
+                namespace: Some(namespace.to_owned()),
+                ..Default::default()
+            },
+            reason: Some("BackOff".to_owned()),
+            message: Some("Back-off restarting failed container".to_owned()),
+            type_: Some("Warning".to_owned()),
+            count: Some(3),
+            last_timestamp: Some(Time(Utc.timestamp_opt(1672531200, 0).unwrap())),
+            ..Default::default()
+        }
+    }
+
+    fn event_watcher(ignore_older: Option<chrono::Duration>) -> EventWatcher {
+        let (out, _) = SourceSender::new_test();
+        EventWatcher {
+            checkpoints: Checkpoints {
+                filename: PathBuf::from("/nonexistent/checkpoints.json"),
+                seen: HashMap::new(),
+                dirty: false,
+            },
+            ignore_older,
+            log_namespace: LogNamespace::Legacy,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+            out,
+        }
+    }
+
+    #[test]
+    fn deduplicates_events() {
+        let mut checkpoints = event_watcher(None).checkpoints;
+
+        assert!(checkpoints.observe(&kube_event("a", "default", "1")));
+        assert!(checkpoints.dirty);
+        assert!(!checkpoints.observe(&kube_event("a", "default", "1")));
+        // The event was updated, such as when it occurred again.
+        assert!(checkpoints.observe(&kube_event("a", "default", "2")));
+        assert!(checkpoints.observe(&kube_event("b", "kube-system", "1")));
+
+        checkpoints.forget(&kube_event("a", "default", "2"));
+        assert!(checkpoints.observe(&kube_event("a", "default", "2")));
+
+        let mut unversioned = kube_event("c", "default", "1");
+        unversioned.metadata.resource_version = None;
+        assert!(checkpoints.observe(&unversioned));
+        assert!(checkpoints.observe(&unversioned));
+    }
+
+    #[test]
+    fn forgets_events_no_longer_listed() {
+        let mut checkpoints = event_watcher(None).checkpoints;
+        for (uid, namespace) in [("a", "default"), ("b", "default"), ("c", "kube-system")] {
+            checkpoints.observe(&kube_event(uid, namespace, "1"));
+        }
+
+        checkpoints.retain_listed(Some("default"), &[kube_event("b", "default", "1")]);
+        let mut uids = checkpoints.seen.keys().cloned().collect::<Vec<_>>();
+        uids.sort();
+        assert_eq!(uids, vec!["b", "c"]);
+
+        checkpoints.retain_listed(None, &[kube_event("c", "kube-system", "1")]);
+        assert_eq!(checkpoints.seen.keys().collect::<Vec<_>>(), vec!["c"]);
+    }
+
+    #[test]
+    fn persists_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let filename = dir.path().join(CHECKPOINT_FILENAME);
+
+        let mut checkpoints = Checkpoints::load(filename.clone()).unwrap();
+        assert!(checkpoints.seen.is_empty());
+        checkpoints.observe(&kube_event("a", "default", "7"));
+        checkpoints.persist();
+        assert!(!checkpoints.dirty);
+
+        let mut checkpoints = Checkpoints::load(filename).unwrap();
+        assert!(!checkpoints.observe(&kube_event("a", "default", "7")));
+        assert!(checkpoints.observe(&kube_event("a", "default", "8")));
+    }
+
+    #[test]
+    fn converts_events() {
+        let now = Utc.timestamp_opt(1672531260, 0).unwrap();
+        let event = event_watcher(None)
+            .to_event(&kube_event("a", "default", "1"), now)
+            .unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["reason"], "BackOff".into());
+        assert_eq!(
+            log["message"],
+            "Back-off restarting failed container".into()
+        );
+        assert_eq!(log["type"], "Warning".into());
+        assert_eq!(log["count"], 3.into());
+        assert_eq!(log["involvedObject.name"], "web-0".into());
+        assert_eq!(log["metadata.uid"], "a".into());
+        assert!(log.get("metadata.managedFields").is_none());
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            KubernetesEventsConfig::NAME.into()
+        );
+        assert_eq!(
+            log.get((
+                lookup::PathPrefix::Event,
+                log_schema().timestamp_key().unwrap()
+            )),
+            Some(&Utc.timestamp_opt(1672531200, 0).unwrap().into())
+        );
+    }
+
+    #[test]
+    fn ignores_old_events() {
+        let watcher = event_watcher(Some(chrono::Duration::seconds(60)));
+        let event = kube_event("a", "default", "1");
+
+        let recent = Utc.timestamp_opt(1672531230, 0).unwrap();
+        assert!(watcher.to_event(&event, recent).is_some());
+        let later = Utc.timestamp_opt(1672531300, 0).unwrap();
+        assert!(watcher.to_event(&event, later).is_none());
+    }
+}
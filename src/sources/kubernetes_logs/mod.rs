@@ -30,6 +30,7 @@ use kube::{
 use lifecycle::Lifecycle;
 use lookup::{lookup_v2::OptionalTargetPath, owned_value_path, path, OwnedTargetPath};
 use serde_with::serde_as;
+use tracing::Instrument;
 use value::{kind::Collection, Kind};
 use vector_common::{
     internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol},
@@ -66,6 +67,7 @@ mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
+mod pod_hints;
 mod pod_metadata_annotator;
 mod transform_utils;
 mod util;
@@ -73,7 +75,9 @@ mod util;
 use self::namespace_metadata_annotator::NamespaceMetadataAnnotator;
 use self::node_metadata_annotator::NodeMetadataAnnotator;
 use self::parser::Parser;
+use self::pod_hints::PodHintsApplier;
 use self::pod_metadata_annotator::PodMetadataAnnotator;
+use self::transform_utils::optional::Optional;
 
 /// The key we use for `file` field.
 const FILE_KEY: &str = "file";
@@ -155,6 +159,9 @@ pub struct Config {
     #[configurable(derived)]
     node_annotation_fields: node_metadata_annotator::FieldsSpec,
 
+    #[configurable(derived)]
+    pod_hints: pod_hints::HintsConfig,
+
     /// A list of glob patterns to exclude from reading the files.
     #[configurable(metadata(docs::examples = "**/exclude/**"))]
     exclude_paths_glob_patterns: Vec<PathBuf>,
@@ -261,6 +268,7 @@ impl Default for Config {
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
             node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
+            pod_hints: pod_hints::HintsConfig::default(),
             exclude_paths_glob_patterns: default_path_exclusion(),
             read_from: default_read_from(),
             ignore_older_secs: None,
@@ -506,6 +514,7 @@ struct Source {
     pod_fields_spec: pod_metadata_annotator::FieldsSpec,
     namespace_fields_spec: namespace_metadata_annotator::FieldsSpec,
     node_field_spec: node_metadata_annotator::FieldsSpec,
+    pod_hints: pod_hints::HintsConfig,
     field_selector: String,
     label_selector: String,
     namespace_label_selector: String,
@@ -582,6 +591,7 @@ impl Source {
             pod_fields_spec: config.pod_annotation_fields.clone(),
             namespace_fields_spec: config.namespace_annotation_fields.clone(),
             node_field_spec: config.node_annotation_fields.clone(),
+            pod_hints: config.pod_hints.clone(),
             field_selector,
             label_selector,
             namespace_label_selector,
@@ -612,6 +622,7 @@ impl Source {
             pod_fields_spec,
             namespace_fields_spec,
             node_field_spec,
+            pod_hints,
             field_selector,
             label_selector,
             namespace_label_selector,
@@ -644,12 +655,10 @@ impl Source {
         let pod_state = pod_store_w.as_reader();
         let pod_cacher = MetaCache::new();
 
-        reflectors.push(tokio::spawn(custom_reflector(
-            pod_store_w,
-            pod_cacher,
-            pod_watcher,
-            delay_deletion,
-        )));
+        reflectors.push(tokio::spawn(
+            custom_reflector(pod_store_w, pod_cacher, pod_watcher, delay_deletion)
+                .in_current_span(),
+        ));
 
         // -----------------------------------------------------------------
 
@@ -665,12 +674,9 @@ impl Source {
         let ns_state = ns_store_w.as_reader();
         let ns_cacher = MetaCache::new();
 
-        reflectors.push(tokio::spawn(custom_reflector(
-            ns_store_w,
-            ns_cacher,
-            ns_watcher,
-            delay_deletion,
-        )));
+        reflectors.push(tokio::spawn(
+            custom_reflector(ns_store_w, ns_cacher, ns_watcher, delay_deletion).in_current_span(),
+        ));
 
         // -----------------------------------------------------------------
 
@@ -686,15 +692,16 @@ impl Source {
         let node_state = node_store_w.as_reader();
         let node_cacher = MetaCache::new();
 
-        reflectors.push(tokio::spawn(custom_reflector(
-            node_store_w,
-            node_cacher,
-            node_watcher,
-            delay_deletion,
-        )));
+        reflectors.push(tokio::spawn(
+            custom_reflector(node_store_w, node_cacher, node_watcher, delay_deletion)
+                .in_current_span(),
+        ));
 
         let paths_provider =
             K8sPathsProvider::new(pod_state.clone(), ns_state.clone(), exclude_paths);
+        let pod_hints_applier = pod_hints
+            .enabled
+            .then(|| PodHintsApplier::new(pod_state.clone(), &pod_hints, log_namespace));
         let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec, log_namespace);
         let ns_annotator =
             NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec, log_namespace);
@@ -820,7 +827,8 @@ impl Source {
         });
         let (events_count, _) = events.size_hint();
 
-        let mut stream = partial_events_merger.transform(Box::pin(events));
+        let stream = partial_events_merger.transform(Box::pin(events));
+        let mut stream = Box::new(Optional(pod_hints_applier)).transform(stream);
         let event_processing_loop = out.send_event_stream(&mut stream);
 
         let mut lifecycle = Lifecycle::new();
@@ -0,0 +1,619 @@
+//! Applies the parsing hints Pods opt into with annotations.
+
+#![deny(missing_docs)]
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use bytes::BytesMut;
+use futures::{stream, Stream, StreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use kube::runtime::reflector::{store::Store, ObjectRef};
+use lookup::path;
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use serde_with::serde_as;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::{path_helpers::parse_log_file_path, Config, FILE_KEY};
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    internal_events::{KubernetesLogsPodHintError, KubernetesLogsPodHintParseError},
+    transforms::TaskTransform,
+};
+
+/// The annotation selecting the format the messages of a Pod are parsed from.
+const PARSE_ANNOTATION_KEY: &str = "vector.dev/parse";
+
+/// The annotation with the pattern matching the first line of the multiline messages of a Pod.
+const MULTILINE_PATTERN_ANNOTATION_KEY: &str = "vector.dev/multiline-pattern";
+
+/// Configuration for the parsing hints Pods opt into with annotations.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct HintsConfig {
+    /// Whether or not to apply the parsing hints in the annotations of Pods.
+    ///
+    /// The `vector.dev/parse` annotation selects the format the messages of the Pod are parsed
+    /// from, and only supports `json`. The fields of JSON objects replace the message.
+    ///
+    /// The `vector.dev/multiline-pattern` annotation is a regular expression matching the first
+    /// line of each message of the Pod. Lines that don't match it are appended to the previous
+    /// message, separated by a newline.
+    pub enabled: bool,
+
+    /// The maximum amount of time to wait for the next line of a multiline message, in
+    /// milliseconds.
+    ///
+    /// Once this timeout is reached, the buffered message is emitted.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub multiline_timeout_ms: Duration,
+}
+
+impl Default for HintsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            multiline_timeout_ms: Duration::from_millis(1_000),
+        }
+    }
+}
+
+/// The formats messages can be parsed from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Json,
+}
+
+impl Format {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "json" => Ok(Self::Json),
+            other => Err(format!("Unsupported format {:?}.", other)),
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+        }
+    }
+}
+
+/// The hints of a Pod.
+#[derive(Debug, Default)]
+struct Hints {
+    format: Option<Format>,
+    multiline_start: Option<Regex>,
+}
+
+impl Hints {
+    /// Builds the hints from the values of the annotations, ignoring the invalid ones.
+    fn new(pod: &str, parse: Option<&str>, multiline_pattern: Option<&str>) -> Self {
+        let format = parse.and_then(|parse| {
+            Format::parse(parse)
+                .map_err(|error| {
+                    emit!(KubernetesLogsPodHintError {
+                        annotation: PARSE_ANNOTATION_KEY,
+                        pod,
+                        error,
+                    })
+                })
+                .ok()
+        });
+        let multiline_start = multiline_pattern.and_then(|pattern| {
+            Regex::new(pattern)
+                .map_err(|error| {
+                    emit!(KubernetesLogsPodHintError {
+                        annotation: MULTILINE_PATTERN_ANNOTATION_KEY,
+                        pod,
+                        error,
+                    })
+                })
+                .ok()
+        });
+
+        Self {
+            format,
+            multiline_start,
+        }
+    }
+}
+
+/// The hints of a Pod, along with the annotations they were built from.
+struct CachedHints {
+    annotations: (Option<String>, Option<String>),
+    hints: Arc<Hints>,
+}
+
+/// Looks up the hints of the Pods of log files, only building them again when the annotations
+/// of a Pod change.
+///
+/// The hints are cached by Pod, rather than by log file, so that there's at most one entry for
+/// each of the known Pods.
+struct HintsResolver {
+    pods_state_reader: Store<Pod>,
+    cache: HashMap<ObjectRef<Pod>, CachedHints>,
+}
+
+impl HintsResolver {
+    fn resolve(&mut self, file: &str) -> Arc<Hints> {
+        let file_info = match parse_log_file_path(file) {
+            Some(file_info) => file_info,
+            None => return Arc::default(),
+        };
+        let pod = ObjectRef::<Pod>::new(file_info.pod_name).within(file_info.pod_namespace);
+        let annotations = match self.annotations(&pod) {
+            Some(annotations) => annotations,
+            None => {
+                self.cache.remove(&pod);
+                return Arc::default();
+            }
+        };
+
+        match self.cache.get(&pod) {
+            Some(cached) if cached.annotations == annotations => Arc::clone(&cached.hints),
+            _ => {
+                let hints = Arc::new(Hints::new(
+                    file_info.pod_name,
+                    annotations.0.as_deref(),
+                    annotations.1.as_deref(),
+                ));
+                self.cache.insert(
+                    pod,
+                    CachedHints {
+                        annotations,
+                        hints: Arc::clone(&hints),
+                    },
+                );
+                hints
+            }
+        }
+    }
+
+    /// Returns the values of the hint annotations of the Pod, or `None` if the Pod is unknown.
+    fn annotations(&self, pod: &ObjectRef<Pod>) -> Option<(Option<String>, Option<String>)> {
+        let resource = self.pods_state_reader.get(pod)?;
+        let annotations = resource.metadata.annotations.as_ref();
+        let get = |key: &str| annotations.and_then(|annotations| annotations.get(key).cloned());
+
+        Some((
+            get(PARSE_ANNOTATION_KEY),
+            get(MULTILINE_PATTERN_ANNOTATION_KEY),
+        ))
+    }
+
+    /// Forgets the hints of the Pods which were deleted, even if none of their files are read
+    /// anymore.
+    fn evict_deleted_pods(&mut self) {
+        let pods_state_reader = &self.pods_state_reader;
+        self.cache
+            .retain(|pod, _| pods_state_reader.get(pod).is_some());
+    }
+}
+
+/// A multiline message waiting for its next lines.
+struct Pending {
+    event: Event,
+    hints: Arc<Hints>,
+    updated: Instant,
+}
+
+/// Applies the hints of the Pods to their events.
+pub struct PodHintsApplier {
+    resolver: HintsResolver,
+    multiline_timeout: Duration,
+    log_namespace: LogNamespace,
+    pending: HashMap<String, Pending>,
+}
+
+impl PodHintsApplier {
+    /// Create a new [`PodHintsApplier`].
+    pub fn new(
+        pods_state_reader: Store<Pod>,
+        config: &HintsConfig,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            resolver: HintsResolver {
+                pods_state_reader,
+                cache: HashMap::new(),
+            },
+            multiline_timeout: config.multiline_timeout_ms,
+            log_namespace,
+            pending: HashMap::new(),
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        let file = match event_file(event.as_log(), self.log_namespace) {
+            Some(file) => file,
+            None => {
+                output.push(event);
+                return;
+            }
+        };
+        let hints = self.resolver.resolve(&file);
+        let starts_message = hints.multiline_start.as_ref().map(|multiline_start| {
+            message(event.as_log(), self.log_namespace)
+                .map_or(true, |message| multiline_start.is_match(&message))
+        });
+
+        match starts_message {
+            Some(starts_message) => match self.pending.entry(file) {
+                Entry::Occupied(mut entry) if !starts_message => {
+                    let pending = entry.get_mut();
+                    append(&mut pending.event, event, self.log_namespace);
+                    pending.updated = Instant::now();
+                }
+                Entry::Occupied(mut entry) => {
+                    let previous = std::mem::replace(entry.get_mut(), Pending::new(event, hints));
+                    output.push(previous.finish(self.log_namespace));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Pending::new(event, hints));
+                }
+            },
+            None => {
+                // The annotation may have been removed while a message was pending.
+                if let Some(previous) = self.pending.remove(&file) {
+                    output.push(previous.finish(self.log_namespace));
+                }
+                output.push(apply_format(event, hints.format, self.log_namespace));
+            }
+        }
+    }
+
+    /// Flushes the multiline messages that timed out, and forgets the hints of the Pods that
+    /// were removed.
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let timed_out = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.updated.elapsed() >= self.multiline_timeout)
+            .map(|(file, _)| file.clone())
+            .collect::<Vec<_>>();
+        for file in timed_out {
+            if let Some(pending) = self.pending.remove(&file) {
+                output.push(pending.finish(self.log_namespace));
+            }
+        }
+
+        self.resolver.evict_deleted_pods();
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        let log_namespace = self.log_namespace;
+        output.extend(
+            self.pending
+                .drain()
+                .map(|(_, pending)| pending.finish(log_namespace)),
+        );
+    }
+}
+
+impl Pending {
+    fn new(event: Event, hints: Arc<Hints>) -> Self {
+        Self {
+            event,
+            hints,
+            updated: Instant::now(),
+        }
+    }
+
+    fn finish(self, log_namespace: LogNamespace) -> Event {
+        apply_format(self.event, self.hints.format, log_namespace)
+    }
+}
+
+impl TaskTransform<Event> for PodHintsApplier {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut flush_stream =
+            tokio::time::interval(me.multiline_timeout.max(Duration::from_millis(1)));
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                      me.flush_into(&mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.transform_one(&mut output, event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+fn message_field(log_namespace: LogNamespace) -> &'static str {
+    match log_namespace {
+        LogNamespace::Vector => ".",
+        LogNamespace::Legacy => log_schema().message_key(),
+    }
+}
+
+fn event_file(log: &LogEvent, log_namespace: LogNamespace) -> Option<String> {
+    let file = match log_namespace {
+        LogNamespace::Vector => log.metadata().value().get(path!(Config::NAME, FILE_KEY)),
+        LogNamespace::Legacy => log.get(path!(FILE_KEY)),
+    };
+    file.map(|file| file.to_string_lossy().into_owned())
+}
+
+fn message(log: &LogEvent, log_namespace: LogNamespace) -> Option<String> {
+    match log.get(message_field(log_namespace)) {
+        Some(Value::Bytes(bytes)) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        _ => None,
+    }
+}
+
+/// Appends the message of the event to the pending message, separated by a newline.
+fn append(pending: &mut Event, event: Event, log_namespace: LogNamespace) {
+    let field = message_field(log_namespace);
+    let mut log = event.into_log();
+    let line = log.remove(field);
+    let (_, metadata) = log.into_parts();
+
+    let pending = pending.as_mut_log();
+    if let (Some(Value::Bytes(message)), Some(Value::Bytes(line))) = (pending.get_mut(field), line)
+    {
+        let mut joined = BytesMut::with_capacity(message.len() + 1 + line.len());
+        joined.extend_from_slice(message);
+        joined.extend_from_slice(b"\n");
+        joined.extend_from_slice(&line);
+        *message = joined.freeze();
+    }
+    pending.metadata_mut().merge(metadata);
+}
+
+/// Replaces the message of the event with the fields parsed from it, leaving the event as-is if
+/// the message can't be parsed.
+fn apply_format(mut event: Event, format: Option<Format>, log_namespace: LogNamespace) -> Event {
+    let format = match format {
+        Some(format) => format,
+        None => return event,
+    };
+
+    let log = event.as_mut_log();
+    let field = message_field(log_namespace);
+    let parsed = match log.get(field) {
+        Some(Value::Bytes(bytes)) => match format {
+            Format::Json => serde_json::from_slice::<JsonValue>(bytes),
+        },
+        _ => return event,
+    };
+
+    match parsed {
+        Ok(JsonValue::Object(object)) => match log_namespace {
+            LogNamespace::Vector => *log.value_mut() = Value::from(JsonValue::Object(object)),
+            LogNamespace::Legacy => {
+                log.remove(field);
+                if let Some(map) = log.as_map_mut() {
+                    for (key, value) in object {
+                        map.insert(key, Value::from(value));
+                    }
+                }
+            }
+        },
+        // Messages that aren't objects, such as the lines of a Pod that only logs some of its
+        // messages as JSON, are left as-is.
+        Ok(_) => (),
+        Err(error) => emit!(KubernetesLogsPodHintParseError {
+            format: format.name(),
+            error: &error,
+        }),
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use futures::channel::mpsc;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use kube::runtime::reflector::store::Writer;
+    use kube::runtime::watcher;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    const FILE: &str =
+        "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/sandbox0-container0-name/1.log";
+
+    fn pod(annotations: &[(&str, &str)]) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("sandbox0-name".to_owned()),
+                namespace: Some("sandbox0-ns".to_owned()),
+                uid: Some("sandbox0-uid".to_owned()),
+                annotations: Some(
+                    annotations
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                ),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        }
+    }
+
+    fn pods_state(annotations: &[(&str, &str)]) -> (Writer<Pod>, Store<Pod>) {
+        let mut writer = Writer::default();
+        writer.apply_watcher_event(&watcher::Event::Applied(pod(annotations)));
+        let store = writer.as_reader();
+        (writer, store)
+    }
+
+    fn event(message: &str) -> Event {
+        event_in(FILE, message)
+    }
+
+    fn event_in(file: &str, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert(FILE_KEY, file);
+        log.into()
+    }
+
+    fn applier(annotations: &[(&str, &str)]) -> (Writer<Pod>, PodHintsApplier) {
+        let (writer, store) = pods_state(annotations);
+        let applier = PodHintsApplier::new(store, &HintsConfig::default(), LogNamespace::Legacy);
+        (writer, applier)
+    }
+
+    fn messages(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| message(event.as_log(), LogNamespace::Legacy).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn leaves_events_without_hints_as_is() {
+        let (_writer, mut applier) = applier(&[]);
+        let mut output = Vec::new();
+        applier.transform_one(&mut output, event(r#"{"level":"info"}"#));
+
+        assert_eq!(messages(&output), vec![r#"{"level":"info"}"#]);
+    }
+
+    #[test]
+    fn parses_json_messages() {
+        let (_writer, mut applier) = applier(&[(PARSE_ANNOTATION_KEY, "json")]);
+        let mut output = Vec::new();
+        applier.transform_one(
+            &mut output,
+            event(r#"{"level":"info","message":"started","port":8080}"#),
+        );
+        applier.transform_one(&mut output, event("not json"));
+        applier.transform_one(&mut output, event("[1, 2]"));
+
+        let log = output[0].as_log();
+        assert_eq!(log["message"], "started".into());
+        assert_eq!(log["level"], "info".into());
+        assert_eq!(log["port"], 8080.into());
+        assert_eq!(log[FILE_KEY], FILE.into());
+        assert_eq!(messages(&output[1..]), vec!["not json", "[1, 2]"]);
+    }
+
+    #[test]
+    fn parses_json_messages_in_vector_namespace() {
+        let mut log = LogEvent::from(Value::from(r#"{"level":"info"}"#));
+        log.metadata_mut()
+            .value_mut()
+            .insert(path!(Config::NAME, FILE_KEY), FILE);
+        let event = apply_format(log.into(), Some(Format::Json), LogNamespace::Vector);
+
+        let mut expected = BTreeMap::new();
+        expected.insert("level".to_owned(), Value::from("info"));
+        assert_eq!(event.as_log().value(), &Value::Object(expected));
+        assert_eq!(
+            event_file(event.as_log(), LogNamespace::Vector),
+            Some(FILE.to_owned())
+        );
+    }
+
+    #[test]
+    fn merges_multiline_messages() {
+        let (_writer, mut applier) = applier(&[
+            (MULTILINE_PATTERN_ANNOTATION_KEY, r"^\d{4}-"),
+            (PARSE_ANNOTATION_KEY, "csv"),
+        ]);
+        let mut output = Vec::new();
+        for line in [
+            "  continuation without a start",
+            "2023-01-01 Exception in thread main",
+            "    at com.example.Main.main(Main.java:5)",
+            "    at java.base/java.lang.Thread.run(Thread.java:833)",
+            "2023-01-01 Recovered",
+        ] {
+            applier.transform_one(&mut output, event(line));
+        }
+        assert_eq!(
+            messages(&output),
+            vec![
+                "  continuation without a start",
+                "2023-01-01 Exception in thread main\n    at com.example.Main.main(Main.java:5)\n    at java.base/java.lang.Thread.run(Thread.java:833)",
+            ]
+        );
+
+        output.clear();
+        applier.flush_all_into(&mut output);
+        assert_eq!(messages(&output), vec!["2023-01-01 Recovered"]);
+    }
+
+    #[test]
+    fn forgets_hints_of_deleted_pods() {
+        let annotations = [(PARSE_ANNOTATION_KEY, "json")];
+        let (mut writer, mut applier) = applier(&annotations);
+        let mut output = Vec::new();
+        applier.transform_one(&mut output, event("{}"));
+        // The rotated files of a Pod share its hints.
+        applier.transform_one(&mut output, event_in(&FILE.replace("1.log", "2.log"), "{}"));
+        assert_eq!(applier.resolver.cache.len(), 1);
+
+        writer.apply_watcher_event(&watcher::Event::Deleted(pod(&annotations)));
+        applier.flush_into(&mut output);
+        assert!(applier.resolver.cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn flushes_multiline_messages_on_timeout() {
+        let (_writer, store) = pods_state(&[(MULTILINE_PATTERN_ANNOTATION_KEY, "^[^ ]")]);
+        let config = HintsConfig {
+            multiline_timeout_ms: Duration::from_millis(50),
+            ..HintsConfig::default()
+        };
+        let applier = Box::new(PodHintsApplier::new(store, &config, LogNamespace::Legacy));
+
+        let (mut tx, rx) = mpsc::channel(10);
+        let mut output = applier.transform(Box::pin(rx));
+        tx.try_send(event("first")).unwrap();
+        tx.try_send(event("  second")).unwrap();
+
+        let flushed = tokio::time::timeout(Duration::from_secs(5), output.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(messages(&[flushed]), vec!["first\n  second"]);
+
+        drop(tx);
+        assert!(output.next().await.is_none());
+    }
+}
@@ -22,7 +22,9 @@ use vector_core::{
     schema::Definition,
 };
 
-use super::util::net::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker};
+use super::util::net::{
+    SocketListenAddr, TcpConnectionLimits, TcpSource, TcpSourceAck, TcpSourceAcker,
+};
 use crate::{
     config::{
         log_schema, DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig,
@@ -160,7 +162,8 @@ impl SourceConfig for LogstashConfig {
             None,
             cx,
             self.acknowledgements,
-            self.connection_limit,
+            TcpConnectionLimits::new(self.connection_limit),
+            false,
             LogstashConfig::NAME,
             log_namespace,
         )
@@ -6,6 +6,8 @@ use snafu::Snafu;
 pub mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+pub mod aws_cloudwatch_logs;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
@@ -14,6 +16,8 @@ pub mod aws_kinesis_firehose;
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog_agent;
 #[cfg(feature = "sources-demo_logs")]
@@ -37,6 +41,8 @@ pub mod file_descriptors;
 pub mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-grpc_server")]
+pub mod grpc_server;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -53,24 +59,42 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+pub mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
 pub mod logstash;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mqtt")]
+pub mod mqtt;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
+#[cfg(feature = "sources-nats_jetstream")]
+pub mod nats_jetstream;
+#[cfg(feature = "sources-netflow")]
+pub mod netflow;
+#[cfg(all(target_os = "linux", feature = "sources-network_flows"))]
+pub mod network_flows;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
 #[cfg(feature = "sources-opentelemetry")]
 pub mod opentelemetry;
+#[cfg(feature = "sources-postgres_cdc")]
+pub mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
 #[cfg(feature = "sources-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-redis_streams")]
+pub mod redis_streams;
+#[cfg(feature = "sources-replay")]
+pub mod replay;
+#[cfg(feature = "sources-snmp_trap")]
+pub mod snmp_trap;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -81,6 +105,10 @@ pub mod statsd;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-webhook_receiver")]
+pub mod webhook_receiver;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+pub mod windows_event_log;
 
 pub mod util;
 
@@ -117,6 +145,11 @@ pub enum Sources {
     #[configurable(metadata(docs::label = "Apache Metrics"))]
     ApacheMetrics(apache_metrics::ApacheMetricsConfig),
 
+    /// Collect logs from AWS CloudWatch Logs.
+    #[cfg(feature = "sources-aws_cloudwatch_logs")]
+    #[configurable(metadata(docs::label = "AWS CloudWatch Logs"))]
+    AwsCloudwatchLogs(aws_cloudwatch_logs::AwsCloudwatchLogsConfig),
+
     /// Collect Docker container stats for tasks running in AWS ECS and AWS Fargate.
     #[cfg(feature = "sources-aws_ecs_metrics")]
     #[configurable(metadata(docs::label = "AWS ECS Metrics"))]
@@ -137,6 +170,11 @@ pub enum Sources {
     #[configurable(metadata(docs::label = "AWS SQS"))]
     AwsSqs(aws_sqs::AwsSqsConfig),
 
+    /// Collect events from Azure Event Hubs.
+    #[cfg(feature = "sources-azure_event_hubs")]
+    #[configurable(metadata(docs::label = "Azure Event Hubs"))]
+    AzureEventHubs(azure_event_hubs::AzureEventHubsSourceConfig),
+
     /// Receive logs, metrics, and traces collected by a Datadog Agent.
     #[cfg(feature = "sources-datadog_agent")]
     #[configurable(metadata(docs::label = "Datadog Agent"))]
@@ -187,6 +225,11 @@ pub enum Sources {
     #[configurable(metadata(docs::label = "GCP Pub/Sub"))]
     GcpPubsub(gcp_pubsub::PubsubConfig),
 
+    /// Serve a user-defined gRPC method, and decode the Protocol Buffers messages of its requests into events.
+    #[cfg(feature = "sources-grpc_server")]
+    #[configurable(metadata(docs::label = "gRPC Server"))]
+    GrpcServer(grpc_server::GrpcServerConfig),
+
     /// Collect logs from Heroku's Logplex, the router responsible for receiving logs from your Heroku apps.
     #[cfg(feature = "sources-heroku_logs")]
     #[configurable(metadata(docs::label = "Heroku Logplex"))]
@@ -233,6 +276,11 @@ pub enum Sources {
     #[configurable(metadata(docs::label = "Kafka"))]
     Kafka(kafka::KafkaSourceConfig),
 
+    /// Collect events from the Kubernetes API.
+    #[cfg(feature = "sources-kubernetes_events")]
+    #[configurable(metadata(docs::label = "Kubernetes Events"))]
+    KubernetesEvents(kubernetes_events::KubernetesEventsConfig),
+
     /// Collect Pod logs from Kubernetes Nodes.
     #[cfg(feature = "sources-kubernetes_logs")]
     #[configurable(metadata(docs::label = "Kubernetes Logs"))]
@@ -248,11 +296,31 @@ pub enum Sources {
     #[configurable(metadata(docs::label = "MongoDB Metrics"))]
     MongodbMetrics(mongodb_metrics::MongoDbMetricsConfig),
 
+    /// Collect events from topics of an MQTT broker.
+    #[cfg(feature = "sources-mqtt")]
+    #[configurable(metadata(docs::label = "MQTT"))]
+    Mqtt(mqtt::MqttSourceConfig),
+
     /// Read observability data from subjects on the NATS messaging system.
     #[cfg(all(feature = "sources-nats"))]
     #[configurable(metadata(docs::label = "NATS"))]
     Nats(nats::NatsSourceConfig),
 
+    /// Consume streams of the NATS JetStream persistence layer with durable consumers.
+    #[cfg(feature = "sources-nats_jetstream")]
+    #[configurable(metadata(docs::label = "NATS JetStream"))]
+    NatsJetStream(nats_jetstream::NatsJetStreamSourceConfig),
+
+    /// Collect NetFlow, IPFIX and sFlow flows.
+    #[cfg(feature = "sources-netflow")]
+    #[configurable(metadata(docs::label = "NetFlow"))]
+    Netflow(netflow::NetflowConfig),
+
+    /// Collect summaries of the network connections of the host.
+    #[cfg(all(target_os = "linux", feature = "sources-network_flows"))]
+    #[configurable(metadata(docs::label = "Network Flows"))]
+    NetworkFlows(network_flows::NetworkFlowsConfig),
+
     /// Collect metrics from NGINX.
     #[cfg(feature = "sources-nginx_metrics")]
     #[configurable(metadata(docs::label = "NGINX"))]
@@ -263,6 +331,11 @@ pub enum Sources {
     #[configurable(metadata(docs::label = "OpenTelemetry"))]
     Opentelemetry(opentelemetry::OpentelemetryConfig),
 
+    /// Collect the changes made to PostgreSQL tables, from a logical replication slot.
+    #[cfg(feature = "sources-postgres_cdc")]
+    #[configurable(metadata(docs::label = "PostgreSQL CDC"))]
+    PostgresCdc(postgres_cdc::PostgresCdcConfig),
+
     /// Collect metrics from the PostgreSQL database.
     #[cfg(feature = "sources-postgresql_metrics")]
     #[configurable(metadata(docs::label = "PostgreSQL Metrics"))]
@@ -283,6 +356,16 @@ pub enum Sources {
     #[configurable(metadata(docs::label = "Redis"))]
     Redis(redis::RedisSourceConfig),
 
+    /// Collect observability data from a Redis stream, as part of a consumer group.
+    #[cfg(feature = "sources-redis_streams")]
+    #[configurable(metadata(docs::label = "Redis Streams"))]
+    RedisStreams(redis_streams::RedisStreamsSourceConfig),
+
+    /// Replay the raw input recorded from a source.
+    #[cfg(feature = "sources-replay")]
+    #[configurable(metadata(docs::label = "Replay"))]
+    Replay(replay::ReplayConfig),
+
     /// Test (backpressure).
     #[cfg(test)]
     TestBackpressure(crate::test_util::mock::sources::BackpressureSourceConfig),
@@ -303,6 +386,11 @@ pub enum Sources {
     #[cfg(test)]
     TestTripwire(crate::test_util::mock::sources::TripwireSourceConfig),
 
+    /// Collect SNMP traps.
+    #[cfg(feature = "sources-snmp_trap")]
+    #[configurable(metadata(docs::label = "SNMP Trap"))]
+    SnmpTrap(snmp_trap::SnmpTrapConfig),
+
     /// Collect logs over a socket.
     #[cfg(feature = "sources-socket")]
     #[configurable(metadata(docs::label = "Socket"))]
@@ -338,6 +426,16 @@ pub enum Sources {
     #[cfg(feature = "sources-vector")]
     #[configurable(metadata(docs::label = "Vector"))]
     Vector(vector::VectorConfig),
+
+    /// Receive webhooks over HTTP, verifying their HMAC signatures.
+    #[cfg(feature = "sources-webhook_receiver")]
+    #[configurable(metadata(docs::label = "Webhook Receiver"))]
+    WebhookReceiver(webhook_receiver::WebhookReceiverConfig),
+
+    /// Collect events from the Windows Event Log.
+    #[cfg(all(windows, feature = "sources-windows_event_log"))]
+    #[configurable(metadata(docs::label = "Windows Event Log"))]
+    WindowsEventLog(windows_event_log::WindowsEventLogConfig),
 }
 
 // TODO: Use `enum_dispatch` here
@@ -348,6 +446,8 @@ impl NamedComponent for Sources {
             Self::Amqp(config) => config.get_component_name(),
             #[cfg(feature = "sources-apache_metrics")]
             Self::ApacheMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-aws_cloudwatch_logs")]
+            Self::AwsCloudwatchLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_ecs_metrics")]
             Self::AwsEcsMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_kinesis_firehose")]
@@ -356,6 +456,8 @@ impl NamedComponent for Sources {
             Self::AwsS3(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_sqs")]
             Self::AwsSqs(config) => config.get_component_name(),
+            #[cfg(feature = "sources-azure_event_hubs")]
+            Self::AzureEventHubs(config) => config.get_component_name(),
             #[cfg(feature = "sources-datadog_agent")]
             Self::DatadogAgent(config) => config.get_component_name(),
             #[cfg(feature = "sources-demo_logs")]
@@ -376,6 +478,8 @@ impl NamedComponent for Sources {
             Self::Fluent(config) => config.get_component_name(),
             #[cfg(feature = "sources-gcp_pubsub")]
             Self::GcpPubsub(config) => config.get_component_name(),
+            #[cfg(feature = "sources-grpc_server")]
+            Self::GrpcServer(config) => config.get_component_name(),
             #[cfg(feature = "sources-heroku_logs")]
             Self::HerokuLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-host_metrics")]
@@ -394,18 +498,30 @@ impl NamedComponent for Sources {
             Self::Journald(config) => config.get_component_name(),
             #[cfg(feature = "sources-kafka")]
             Self::Kafka(config) => config.get_component_name(),
+            #[cfg(feature = "sources-kubernetes_events")]
+            Self::KubernetesEvents(config) => config.get_component_name(),
             #[cfg(feature = "sources-kubernetes_logs")]
             Self::KubernetesLogs(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-logstash"))]
             Self::Logstash(config) => config.get_component_name(),
             #[cfg(feature = "sources-mongodb_metrics")]
             Self::MongodbMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-mqtt")]
+            Self::Mqtt(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-nats"))]
             Self::Nats(config) => config.get_component_name(),
+            #[cfg(feature = "sources-nats_jetstream")]
+            Self::NatsJetStream(config) => config.get_component_name(),
+            #[cfg(feature = "sources-netflow")]
+            Self::Netflow(config) => config.get_component_name(),
+            #[cfg(all(target_os = "linux", feature = "sources-network_flows"))]
+            Self::NetworkFlows(config) => config.get_component_name(),
             #[cfg(feature = "sources-nginx_metrics")]
             Self::NginxMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-opentelemetry")]
             Self::Opentelemetry(config) => config.get_component_name(),
+            #[cfg(feature = "sources-postgres_cdc")]
+            Self::PostgresCdc(config) => config.get_component_name(),
             #[cfg(feature = "sources-postgresql_metrics")]
             Self::PostgresqlMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-prometheus")]
@@ -414,6 +530,10 @@ impl NamedComponent for Sources {
             Self::PrometheusRemoteWrite(config) => config.get_component_name(),
             #[cfg(feature = "sources-redis")]
             Self::Redis(config) => config.get_component_name(),
+            #[cfg(feature = "sources-redis_streams")]
+            Self::RedisStreams(config) => config.get_component_name(),
+            #[cfg(feature = "sources-replay")]
+            Self::Replay(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestBackpressure(config) => config.get_component_name(),
             #[cfg(test)]
@@ -424,6 +544,8 @@ impl NamedComponent for Sources {
             Self::TestPanic(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestTripwire(config) => config.get_component_name(),
+            #[cfg(feature = "sources-snmp_trap")]
+            Self::SnmpTrap(config) => config.get_component_name(),
             #[cfg(feature = "sources-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sources-splunk_hec")]
@@ -438,6 +560,10 @@ impl NamedComponent for Sources {
             Self::UnitTestStream(config) => config.get_component_name(),
             #[cfg(feature = "sources-vector")]
             Self::Vector(config) => config.get_component_name(),
+            #[cfg(feature = "sources-webhook_receiver")]
+            Self::WebhookReceiver(config) => config.get_component_name(),
+            #[cfg(all(windows, feature = "sources-windows_event_log"))]
+            Self::WindowsEventLog(config) => config.get_component_name(),
         }
     }
 }
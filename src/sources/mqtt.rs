@@ -0,0 +1,384 @@
+//! `MQTT` source.
+//! Subscribes to topics of an MQTT 3.1.1 or MQTT 5 broker.
+use std::time::Duration;
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::{FutureExt, StreamExt};
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use value::Kind;
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    },
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
+    config::{
+        GenerateConfig, Output, ProxyConfig, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{MqttAckError, MqttReceiveError, StreamClosedError},
+    mqtt::{
+        MqttClient, MqttConfigError, MqttConnectionConfig, MqttEvent, MqttEventLoop, MqttPublish,
+        MqttQos,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// How long to wait before connecting again after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("MQTT Config Error: {}", source))]
+    Config { source: MqttConfigError },
+    #[snafu(display("At least one topic must be subscribed to"))]
+    NoTopics,
+}
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source("mqtt"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+
+    /// The topics to subscribe to.
+    ///
+    /// Topic filters can use the `+` wildcard to match a single level of the topic, and the `#`
+    /// wildcard to match all the remaining levels.
+    #[configurable(metadata(docs::examples = "sensors/+/temperature"))]
+    #[configurable(metadata(docs::examples = "gateway/#"))]
+    topics: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    qos: MqttQos,
+
+    /// Overrides the name of the log field used to add the topic to each event.
+    ///
+    /// The value is the topic the message was published to.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[configurable(metadata(docs::examples = "topic"))]
+    topic_key: OptionalValuePath,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            port = 1883
+            topics = ["vector/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        // Only QoS 1 messages are acknowledged, QoS 0 messages are lost when Vector stops anyway.
+        let acknowledgements =
+            cx.do_acknowledgements(self.acknowledgements) && self.qos == MqttQos::AtLeastOnce;
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics.into());
+        }
+        let (client, eventloop) = self
+            .connection
+            .client(acknowledgements)
+            .context(ConfigSnafu)?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
+
+        Ok(Box::pin(mqtt_source(
+            self.clone(),
+            client,
+            eventloop,
+            decoder,
+            log_namespace,
+            acknowledgements,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                MqttSourceConfig::NAME,
+                self.topic_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            );
+
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+
+    async fn healthcheck(&self, _proxy: &ProxyConfig) -> Option<crate::Result<()>> {
+        Some(self.connection.healthcheck().await)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn mqtt_source(
+    config: MqttSourceConfig,
+    client: MqttClient,
+    mut eventloop: MqttEventLoop,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    // MQTT requires messages to be acknowledged in the order they were received.
+    let (finalizer, mut ack_stream) =
+        OrderedFinalizer::<MqttPublish>::maybe_new(acknowledgements, shutdown.clone());
+    let mut shutdown = shutdown.fuse();
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => if let Some((status, publish)) = entry {
+                // Messages failing to be delivered are left unacknowledged, so that the broker
+                // delivers them again when the session is resumed.
+                if status != BatchStatus::Errored {
+                    if let Err(error) = client.try_ack(&publish) {
+                        emit!(MqttAckError { error });
+                    }
+                }
+            },
+            event = eventloop.poll() => match event {
+                Ok(MqttEvent::Connected { session_present }) => {
+                    debug!(message = "Connected to the MQTT broker.", session_present);
+                    // The subscriptions are kept along with the session, so they only need to
+                    // be made again for new sessions.
+                    if !session_present {
+                        if let Err(error) = client.subscribe(&config.topics, config.qos).await {
+                            emit!(MqttReceiveError { error });
+                        }
+                    }
+                }
+                Ok(MqttEvent::Publish(publish)) => {
+                    bytes_received.emit(ByteSize(publish.payload().len()));
+
+                    let (batch, receiver) = match &finalizer {
+                        Some(_) => {
+                            let (batch, receiver) = BatchNotifier::new_with_receiver();
+                            (Some(batch), Some(receiver))
+                        }
+                        None => (None, None),
+                    };
+                    let events = decode_publish(&config, &decoder, &publish, log_namespace, &batch)
+                        .await;
+                    drop(batch);
+
+                    let count = events.len();
+                    events_received.emit(CountByteSize(
+                        count,
+                        events.estimated_json_encoded_size_of(),
+                    ));
+                    if let Err(error) = out.send_batch(events).await {
+                        emit!(StreamClosedError { error, count });
+                        break;
+                    }
+
+                    // Without acknowledgements, the client acknowledges messages itself.
+                    if let (Some(finalizer), Some(receiver)) = (&finalizer, receiver) {
+                        finalizer.add(publish, receiver);
+                    }
+                }
+                Ok(MqttEvent::Other) => {}
+                Err(error) => {
+                    emit!(MqttReceiveError { error });
+                    // Polling again connects again, give the broker some time first.
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            },
+        }
+    }
+
+    if let Err(error) = client.disconnect().await {
+        debug!(message = "Failed to disconnect from the MQTT broker.", %error);
+    }
+    Ok(())
+}
+
+async fn decode_publish(
+    config: &MqttSourceConfig,
+    decoder: &Decoder,
+    publish: &MqttPublish,
+    log_namespace: LogNamespace,
+    batch: &Option<BatchNotifier>,
+) -> Vec<Event> {
+    let topic = publish.topic();
+    let now = Utc::now();
+    let mut decoded = Vec::new();
+
+    let mut stream = FramedRead::new(publish.payload().as_ref(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                for mut event in events {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            MqttSourceConfig::NAME,
+                            now,
+                        );
+                        log_namespace.insert_source_metadata(
+                            MqttSourceConfig::NAME,
+                            log,
+                            config.topic_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                            "topic",
+                            topic.as_ref(),
+                        );
+                    }
+                    decoded.push(event.with_batch_notifier_option(batch));
+                }
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::OwnedTargetPath;
+    use value::kind::Collection;
+    use vector_core::schema::Definition;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    fn config(log_namespace: Option<bool>) -> MqttSourceConfig {
+        let mut config: MqttSourceConfig = toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = ["vector/#"]
+            "#,
+        )
+        .unwrap();
+        config.log_namespace = log_namespace;
+        config
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let definition = config(Some(true)).outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(OwnedTargetPath::event_root(), "message")
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                )
+                .with_metadata_field(&owned_value_path!("mqtt", "topic"), Kind::bytes(), None);
+
+        assert_eq!(definition, expected_definition);
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let definition = config(None).outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let expected_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_event_field(
+            &owned_value_path!("message"),
+            Kind::bytes(),
+            Some("message"),
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_event_field(&owned_value_path!("source_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("topic"), Kind::bytes(), None);
+
+        assert_eq!(definition, expected_definition);
+    }
+}
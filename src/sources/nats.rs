@@ -15,7 +15,7 @@ use vector_core::{
 };
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{GenerateConfig, Output, SourceConfig, SourceContext},
     event::Event,
     internal_events::StreamClosedError,
@@ -91,6 +91,10 @@ pub struct NatsSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
     /// The `NATS` subject key.
     #[serde(default = "default_subject_key_field")]
     subject_key_field: OptionalValuePath,
@@ -118,7 +122,9 @@ impl SourceConfig for NatsSourceConfig {
         let log_namespace = cx.log_namespace(self.log_namespace);
         let (connection, subscription) = create_subscription(self).await?;
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
 
         Ok(Box::pin(nats_source(
             self.clone(),
@@ -150,7 +156,11 @@ impl SourceConfig for NatsSourceConfig {
                 None,
             );
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -366,7 +376,8 @@ mod integration_tests {
                 conf.decoding.clone(),
                 LogNamespace::Legacy,
             )
-            .build();
+            .build()
+            .unwrap();
             tokio::spawn(nats_source(
                 conf.clone(),
                 nc,
@@ -400,6 +411,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: None,
             log_namespace: None,
@@ -427,6 +439,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -459,6 +472,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -491,6 +505,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -522,6 +537,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -553,6 +569,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -585,6 +602,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -617,6 +635,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -650,6 +669,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: None,
             auth: None,
             log_namespace: None,
@@ -677,6 +697,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -712,6 +733,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -745,6 +767,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -782,6 +805,7 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -0,0 +1,500 @@
+use std::{io, time::Duration};
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::StreamExt;
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use nats::jetstream::{
+    AckKind, AckPolicy, ConsumerConfig, JetStream, PullSubscribeOptions, PullSubscription,
+};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use value::Kind;
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    },
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
+    config::{GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsJetStreamAckError, NatsJetStreamFetchError, StreamClosedError},
+    nats::{connect_jetstream, from_tls_auth_config, NatsAuthConfig, NatsConfigError},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::TlsEnableableConfig,
+    SourceSender,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("NATS Config Error: {}", source))]
+    Config { source: NatsConfigError },
+    #[snafu(display("NATS Connect Error: {}", source))]
+    Connect { source: io::Error },
+    #[snafu(display("NATS JetStream Consumer Error: {}", source))]
+    Consumer { source: io::Error },
+    #[snafu(display("NATS Subscribe Error: {}", source))]
+    Subscribe { source: io::Error },
+}
+
+type Finalizer = UnorderedFinalizer<Vec<nats::Message>>;
+
+/// How long a fetch waits for the messages of its batch, before returning the ones received.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Configuration for the `nats_jetstream` source.
+#[configurable_component(source("nats_jetstream"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamSourceConfig {
+    /// The NATS URL to connect to.
+    ///
+    /// The URL takes the form of `nats://server:port`.
+    /// If the port is not specified it defaults to 4222.
+    #[configurable(metadata(docs::examples = "nats://demo.nats.io"))]
+    #[configurable(metadata(docs::examples = "nats://127.0.0.1:4242"))]
+    url: String,
+
+    /// A [name][nats_connection_name] assigned to the NATS connection.
+    ///
+    /// [nats_connection_name]: https://docs.nats.io/using-nats/developer/connecting/name
+    #[serde(default = "default_name")]
+    #[derivative(Default(value = "default_name()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    connection_name: String,
+
+    /// The name of the JetStream [stream][nats_stream] to consume.
+    ///
+    /// [nats_stream]: https://docs.nats.io/nats-concepts/jetstream/streams
+    #[configurable(metadata(docs::examples = "events"))]
+    stream: String,
+
+    /// The NATS [subject][nats_subject] of the messages of the stream to consume.
+    ///
+    /// [nats_subject]: https://docs.nats.io/nats-concepts/subjects
+    #[configurable(metadata(docs::examples = "events.>"))]
+    #[configurable(metadata(docs::examples = "events.*.east"))]
+    subject: String,
+
+    /// The name of the durable pull [consumer][nats_consumer] to consume the stream with.
+    ///
+    /// The consumer is created, filtering the messages of the stream by `subject`, if it doesn't
+    /// exist yet. It keeps track of the messages acknowledged across restarts, and is shared by
+    /// all the instances consuming from it.
+    ///
+    /// [nats_consumer]: https://docs.nats.io/nats-concepts/jetstream/consumers
+    #[configurable(metadata(docs::examples = "vector"))]
+    durable_name: String,
+
+    /// How long the server waits for a message to be acknowledged before redelivering it, in
+    /// seconds.
+    ///
+    /// When acknowledgements are enabled, the messages are only acknowledged once their events
+    /// are delivered by all the connected sinks, so this must leave time for them to be. This is
+    /// only used to create the consumer.
+    #[serde(default = "default_ack_wait_secs")]
+    #[derivative(Default(value = "default_ack_wait_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ack_wait_secs: u64,
+
+    /// The maximum number of messages fetched at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    #[configurable(validation(range(min = 1)))]
+    batch_size: usize,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<NatsAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The `NATS` subject key.
+    #[serde(default = "default_subject_key_field")]
+    #[derivative(Default(value = "default_subject_key_field()"))]
+    subject_key_field: OptionalValuePath,
+}
+
+fn default_name() -> String {
+    String::from("vector")
+}
+
+const fn default_ack_wait_secs() -> u64 {
+    30
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+fn default_subject_key_field() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("subject"))
+}
+
+impl GenerateConfig for NatsJetStreamSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            durable_name = "vector"
+            stream = "events"
+            subject = "events.>"
+            url = "nats://127.0.0.1:4222""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for NatsJetStreamSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let subscription = self.subscribe().await?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+
+        Ok(Box::pin(nats_jetstream_source(
+            self.clone(),
+            subscription,
+            decoder,
+            log_namespace,
+            acknowledgements,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let legacy_subject_key_field = self
+            .subject_key_field
+            .clone()
+            .path
+            .map(LegacyKey::InsertIfEmpty);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                NatsJetStreamSourceConfig::NAME,
+                legacy_subject_key_field,
+                &owned_value_path!("subject"),
+                Kind::bytes(),
+                None,
+            );
+
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl TryFrom<&NatsJetStreamSourceConfig> for nats::Options {
+    type Error = NatsConfigError;
+
+    fn try_from(config: &NatsJetStreamSourceConfig) -> Result<Self, Self::Error> {
+        from_tls_auth_config(&config.connection_name, &config.auth, &config.tls)
+    }
+}
+
+impl NatsJetStreamSourceConfig {
+    async fn connect(&self) -> Result<JetStream, BuildError> {
+        let options: nats::Options = self.try_into().context(ConfigSnafu)?;
+        connect_jetstream(options, self.url.clone())
+            .await
+            .context(ConnectSnafu)
+    }
+
+    fn consumer_config(&self) -> ConsumerConfig {
+        ConsumerConfig {
+            durable_name: Some(self.durable_name.clone()),
+            ack_policy: AckPolicy::Explicit,
+            ack_wait: Duration::from_secs(self.ack_wait_secs),
+            filter_subject: self.subject.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates the durable consumer if it doesn't exist yet, and subscribes to it.
+    async fn subscribe(&self) -> Result<PullSubscription, BuildError> {
+        let jetstream = self.connect().await?;
+        let config = self.clone();
+        tokio::task::spawn_blocking(move || {
+            if jetstream
+                .consumer_info(&config.stream, &config.durable_name)
+                .is_err()
+            {
+                jetstream
+                    .add_consumer(&config.stream, config.consumer_config())
+                    .context(ConsumerSnafu)?;
+            }
+
+            let options = PullSubscribeOptions::new()
+                .bind_stream(config.stream.clone())
+                .durable_name(config.durable_name.clone());
+            jetstream
+                .pull_subscribe_with_options(&config.subject, &options)
+                .context(SubscribeSnafu)
+        })
+        .await
+        .expect("NATS JetStream subscribe task panicked")
+    }
+}
+
+/// Fetches the next batch of messages, which is empty if none were received before the timeout.
+async fn fetch(
+    subscription: PullSubscription,
+    batch_size: usize,
+) -> io::Result<Vec<nats::Message>> {
+    tokio::task::spawn_blocking(move || {
+        match subscription.timeout_fetch(batch_size, FETCH_TIMEOUT) {
+            Ok(batch) => Ok(batch.filter_map(Result::ok).collect()),
+            Err(error) if error.kind() == io::ErrorKind::TimedOut => Ok(Vec::new()),
+            Err(error) => Err(error),
+        }
+    })
+    .await
+    .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
+}
+
+/// Returns how to acknowledge the messages whose events ended up with the given status.
+///
+/// The messages whose events errored are redelivered, while the ones whose events were rejected
+/// are never redelivered, as they'd be rejected again.
+const fn ack_kind(status: BatchStatus) -> AckKind {
+    match status {
+        BatchStatus::Delivered => AckKind::Ack,
+        BatchStatus::Errored => AckKind::Nak,
+        BatchStatus::Rejected => AckKind::Term,
+    }
+}
+
+async fn acknowledge(messages: Vec<nats::Message>, status: BatchStatus) {
+    tokio::task::spawn_blocking(move || {
+        for message in messages {
+            if let Err(error) = message.ack_kind(ack_kind(status)) {
+                emit!(NatsJetStreamAckError { error });
+            }
+        }
+    })
+    .await
+    .expect("NATS JetStream acknowledgement task panicked");
+}
+
+async fn nats_jetstream_source(
+    config: NatsJetStreamSourceConfig,
+    subscription: PullSubscription,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    let (finalizer, mut ack_stream) = Finalizer::maybe_new(acknowledgements, shutdown.clone());
+    let mut pending_acks = 0_usize;
+    // The fetch runs across the iterations, for its messages not to be dropped while the
+    // acknowledgements are handled.
+    let mut fetching = Box::pin(fetch(subscription.clone(), config.batch_size));
+
+    loop {
+        tokio::select! {
+            biased;
+            entry = ack_stream.next() => if let Some((status, messages)) = entry {
+                pending_acks -= 1;
+                acknowledge(messages, status).await;
+            },
+            _ = &mut shutdown, if pending_acks == 0 => break,
+            messages = &mut fetching => {
+                fetching = Box::pin(fetch(subscription.clone(), config.batch_size));
+                let messages = match messages {
+                    Ok(messages) if messages.is_empty() => continue,
+                    Ok(messages) => messages,
+                    Err(error) => {
+                        emit!(NatsJetStreamFetchError { error });
+                        continue;
+                    }
+                };
+
+                let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+                let mut events = Vec::new();
+                let now = Utc::now();
+                for message in &messages {
+                    bytes_received.emit(ByteSize(message.data.len()));
+                    let mut stream = FramedRead::new(message.data.as_ref(), decoder.clone());
+                    while let Some(next) = stream.next().await {
+                        match next {
+                            Ok((decoded, _byte_size)) => {
+                                events.extend(decoded.into_iter().map(|mut event| {
+                                    if let Event::Log(ref mut log) = event {
+                                        log_namespace.insert_standard_vector_source_metadata(
+                                            log,
+                                            NatsJetStreamSourceConfig::NAME,
+                                            now,
+                                        );
+
+                                        let legacy_subject_key_field = config
+                                            .subject_key_field
+                                            .path
+                                            .as_ref()
+                                            .map(LegacyKey::InsertIfEmpty);
+                                        log_namespace.insert_source_metadata(
+                                            NatsJetStreamSourceConfig::NAME,
+                                            log,
+                                            legacy_subject_key_field,
+                                            "subject",
+                                            message.subject.as_str(),
+                                        )
+                                    }
+                                    event.with_batch_notifier_option(&batch)
+                                }));
+                            }
+                            Err(error) => {
+                                // Error is logged by `crate::codecs`, no further
+                                // handling is needed here.
+                                if !error.can_continue() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                drop(batch);
+
+                let count = events.len();
+                events_received.emit(CountByteSize(count, events.estimated_json_encoded_size_of()));
+                out.send_batch(events).await.map_err(|error| {
+                    emit!(StreamClosedError { error, count });
+                })?;
+
+                match (&finalizer, receiver) {
+                    (Some(finalizer), Some(receiver)) => {
+                        finalizer.add(messages, receiver);
+                        pending_acks += 1;
+                    }
+                    _ => acknowledge(messages, BatchStatus::Delivered).await,
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::{owned_value_path, OwnedTargetPath};
+    use value::Kind;
+    use vector_core::schema::Definition;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NatsJetStreamSourceConfig>();
+    }
+
+    #[test]
+    fn builds_durable_consumer_config() {
+        let config: NatsJetStreamSourceConfig = toml::from_str(
+            r#"
+            durable_name = "vector"
+            stream = "events"
+            subject = "events.>"
+            url = "nats://127.0.0.1:4222"
+            ack_wait_secs = 120
+            "#,
+        )
+        .unwrap();
+        let consumer = config.consumer_config();
+
+        assert_eq!(consumer.durable_name.as_deref(), Some("vector"));
+        assert!(matches!(consumer.ack_policy, AckPolicy::Explicit));
+        assert_eq!(consumer.ack_wait, Duration::from_secs(120));
+        assert_eq!(consumer.filter_subject, "events.>");
+        assert_eq!(config.batch_size, 100);
+    }
+
+    #[test]
+    fn acknowledges_by_status() {
+        assert!(matches!(ack_kind(BatchStatus::Delivered), AckKind::Ack));
+        assert!(matches!(ack_kind(BatchStatus::Errored), AckKind::Nak));
+        assert!(matches!(ack_kind(BatchStatus::Rejected), AckKind::Term));
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = NatsJetStreamSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definition = config.outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(OwnedTargetPath::event_root(), "message")
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("nats_jetstream", "subject"),
+                    Kind::bytes(),
+                    None,
+                );
+
+        assert_eq!(definition, expected_definition);
+    }
+}
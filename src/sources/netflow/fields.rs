@@ -0,0 +1,364 @@
+//! The information elements of the flow records, as assigned by the [IANA IPFIX registry][iana],
+//! which NetFlow v9 shares the first identifiers of.
+//!
+//! The fields of the events are named after the elements, in snake case, so that the flows of all
+//! the protocols have the same fields.
+//!
+//! [iana]: https://www.iana.org/assignments/ipfix/ipfix.xhtml
+use std::borrow::Cow;
+
+use chrono::{DateTime, TimeZone, Utc};
+use value::Value;
+
+/// How the contents of a field are decoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum FieldType {
+    Unsigned,
+    Ipv4,
+    Ipv6,
+    Mac,
+    Text,
+    /// Seconds since the Unix epoch.
+    Seconds,
+    /// Milliseconds since the Unix epoch.
+    Milliseconds,
+    /// An NTP timestamp.
+    Ntp,
+    /// Milliseconds since the start of the exporter, which are converted to the absolute time of
+    /// the field with the given name.
+    SysUpTime(&'static str),
+}
+
+use FieldType::*;
+
+/// The known information elements, sorted by identifier.
+const ELEMENTS: &[(u16, &str, FieldType)] = &[
+    (1, "octet_delta_count", Unsigned),
+    (2, "packet_delta_count", Unsigned),
+    (3, "delta_flow_count", Unsigned),
+    (4, "protocol_identifier", Unsigned),
+    (5, "ip_class_of_service", Unsigned),
+    (6, "tcp_control_bits", Unsigned),
+    (7, "source_transport_port", Unsigned),
+    (8, "source_ipv4_address", Ipv4),
+    (9, "source_ipv4_prefix_length", Unsigned),
+    (10, "ingress_interface", Unsigned),
+    (11, "destination_transport_port", Unsigned),
+    (12, "destination_ipv4_address", Ipv4),
+    (13, "destination_ipv4_prefix_length", Unsigned),
+    (14, "egress_interface", Unsigned),
+    (15, "ip_next_hop_ipv4_address", Ipv4),
+    (16, "bgp_source_as_number", Unsigned),
+    (17, "bgp_destination_as_number", Unsigned),
+    (18, "bgp_next_hop_ipv4_address", Ipv4),
+    (19, "post_mcast_packet_delta_count", Unsigned),
+    (20, "post_mcast_octet_delta_count", Unsigned),
+    (
+        21,
+        "flow_end_sys_up_time",
+        SysUpTime("flow_end_milliseconds"),
+    ),
+    (
+        22,
+        "flow_start_sys_up_time",
+        SysUpTime("flow_start_milliseconds"),
+    ),
+    (23, "post_octet_delta_count", Unsigned),
+    (24, "post_packet_delta_count", Unsigned),
+    (25, "minimum_ip_total_length", Unsigned),
+    (26, "maximum_ip_total_length", Unsigned),
+    (27, "source_ipv6_address", Ipv6),
+    (28, "destination_ipv6_address", Ipv6),
+    (29, "source_ipv6_prefix_length", Unsigned),
+    (30, "destination_ipv6_prefix_length", Unsigned),
+    (31, "flow_label_ipv6", Unsigned),
+    (32, "icmp_type_code_ipv4", Unsigned),
+    (33, "igmp_type", Unsigned),
+    (34, "sampling_interval", Unsigned),
+    (35, "sampling_algorithm", Unsigned),
+    (36, "flow_active_timeout", Unsigned),
+    (37, "flow_idle_timeout", Unsigned),
+    (38, "engine_type", Unsigned),
+    (39, "engine_id", Unsigned),
+    (40, "exported_octet_total_count", Unsigned),
+    (41, "exported_message_total_count", Unsigned),
+    (42, "exported_flow_record_total_count", Unsigned),
+    (44, "source_ipv4_prefix", Ipv4),
+    (45, "destination_ipv4_prefix", Ipv4),
+    (46, "mpls_top_label_type", Unsigned),
+    (47, "mpls_top_label_ipv4_address", Ipv4),
+    (48, "sampler_id", Unsigned),
+    (49, "sampler_mode", Unsigned),
+    (50, "sampler_random_interval", Unsigned),
+    (52, "minimum_ttl", Unsigned),
+    (53, "maximum_ttl", Unsigned),
+    (54, "fragment_identification", Unsigned),
+    (55, "post_ip_class_of_service", Unsigned),
+    (56, "source_mac_address", Mac),
+    (57, "post_destination_mac_address", Mac),
+    (58, "vlan_id", Unsigned),
+    (59, "post_vlan_id", Unsigned),
+    (60, "ip_version", Unsigned),
+    (61, "flow_direction", Unsigned),
+    (62, "ip_next_hop_ipv6_address", Ipv6),
+    (63, "bgp_next_hop_ipv6_address", Ipv6),
+    (64, "ipv6_extension_headers", Unsigned),
+    (80, "destination_mac_address", Mac),
+    (81, "post_source_mac_address", Mac),
+    (82, "interface_name", Text),
+    (83, "interface_description", Text),
+    (84, "sampler_name", Text),
+    (85, "octet_total_count", Unsigned),
+    (86, "packet_total_count", Unsigned),
+    (88, "fragment_offset", Unsigned),
+    (89, "forwarding_status", Unsigned),
+    (94, "application_description", Text),
+    (96, "application_name", Text),
+    (98, "post_ip_diff_serv_code_point", Unsigned),
+    (99, "multicast_replication_factor", Unsigned),
+    (128, "bgp_next_adjacent_as_number", Unsigned),
+    (129, "bgp_prev_adjacent_as_number", Unsigned),
+    (130, "exporter_ipv4_address", Ipv4),
+    (131, "exporter_ipv6_address", Ipv6),
+    (136, "flow_end_reason", Unsigned),
+    (138, "observation_point_id", Unsigned),
+    (139, "icmp_type_code_ipv6", Unsigned),
+    (144, "exporting_process_id", Unsigned),
+    (148, "flow_id", Unsigned),
+    (149, "observation_domain_id", Unsigned),
+    (150, "flow_start_seconds", Seconds),
+    (151, "flow_end_seconds", Seconds),
+    (152, "flow_start_milliseconds", Milliseconds),
+    (153, "flow_end_milliseconds", Milliseconds),
+    (154, "flow_start_microseconds", Ntp),
+    (155, "flow_end_microseconds", Ntp),
+    (156, "flow_start_nanoseconds", Ntp),
+    (157, "flow_end_nanoseconds", Ntp),
+    (160, "system_init_time_milliseconds", Milliseconds),
+    (161, "flow_duration_milliseconds", Unsigned),
+    (176, "icmp_type_ipv4", Unsigned),
+    (177, "icmp_code_ipv4", Unsigned),
+    (178, "icmp_type_ipv6", Unsigned),
+    (179, "icmp_code_ipv6", Unsigned),
+    (180, "udp_source_port", Unsigned),
+    (181, "udp_destination_port", Unsigned),
+    (182, "tcp_source_port", Unsigned),
+    (183, "tcp_destination_port", Unsigned),
+    (192, "ip_ttl", Unsigned),
+    (195, "ip_diff_serv_code_point", Unsigned),
+    (225, "post_nat_source_ipv4_address", Ipv4),
+    (226, "post_nat_destination_ipv4_address", Ipv4),
+    (227, "post_napt_source_transport_port", Unsigned),
+    (228, "post_napt_destination_transport_port", Unsigned),
+    (233, "firewall_event", Unsigned),
+    (234, "ingress_vrf_id", Unsigned),
+    (235, "egress_vrf_id", Unsigned),
+    (243, "dot1q_vlan_id", Unsigned),
+    (256, "ethernet_type", Unsigned),
+    (281, "post_nat_source_ipv6_address", Ipv6),
+    (282, "post_nat_destination_ipv6_address", Ipv6),
+    (302, "selector_id", Unsigned),
+    (305, "sampling_packet_interval", Unsigned),
+    (306, "sampling_packet_space", Unsigned),
+    (312, "data_link_frame_size", Unsigned),
+    (352, "layer2_octet_delta_count", Unsigned),
+];
+
+/// The names of the scope fields of the NetFlow v9 options templates, which have their own
+/// identifiers.
+const V9_SCOPES: &[&str] = &["system", "interface", "line_card", "cache", "template"];
+
+/// The seconds between the NTP and the Unix epochs.
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+
+/// The time of the start of an exporter, which the `SysUpTime` fields are relative to.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct TimeBase {
+    /// The uptime of the exporter when the datagram was exported.
+    pub(super) uptime_millis: u32,
+    /// The time the datagram was exported.
+    pub(super) unix_millis: i64,
+}
+
+impl TimeBase {
+    pub(super) fn timestamp(&self, uptime_millis: u32) -> Option<DateTime<Utc>> {
+        // The uptime wraps around after 49.7 days.
+        let elapsed = self.uptime_millis.wrapping_sub(uptime_millis);
+        millis_to_timestamp(self.unix_millis - i64::from(elapsed))
+    }
+}
+
+/// Decodes a field of a flow record, returning its name and value.
+pub(super) fn decode_field(
+    id: u16,
+    enterprise: Option<u32>,
+    bytes: &[u8],
+    time_base: Option<&TimeBase>,
+) -> (Cow<'static, str>, Value) {
+    if let Some(enterprise) = enterprise {
+        return (
+            format!("enterprise_{}_{}", enterprise, id).into(),
+            decode_value(Unsigned, bytes),
+        );
+    }
+
+    match ELEMENTS.binary_search_by_key(&id, |(id, _, _)| *id) {
+        Ok(index) => {
+            let (_, name, field_type) = ELEMENTS[index];
+            match (field_type, time_base, unsigned(bytes)) {
+                (SysUpTime(absolute), Some(time_base), Some(uptime)) => {
+                    let value = time_base
+                        .timestamp(uptime as u32)
+                        .map_or(Value::Null, Value::Timestamp);
+                    (absolute.into(), value)
+                }
+                _ => (name.into(), decode_value(field_type, bytes)),
+            }
+        }
+        Err(_) => (
+            format!("field_{}", id).into(),
+            decode_value(Unsigned, bytes),
+        ),
+    }
+}
+
+/// Decodes a scope field of a NetFlow v9 options record.
+pub(super) fn decode_v9_scope(id: u16, bytes: &[u8]) -> (Cow<'static, str>, Value) {
+    let name = match V9_SCOPES.get(usize::from(id).wrapping_sub(1)) {
+        Some(scope) => format!("scope_{}", scope),
+        None => format!("scope_{}", id),
+    };
+    (name.into(), decode_value(Unsigned, bytes))
+}
+
+pub(super) fn decode_value(field_type: FieldType, bytes: &[u8]) -> Value {
+    match (field_type, bytes.len()) {
+        (Ipv4, 4) => <[u8; 4]>::try_from(bytes)
+            .map(|address| std::net::Ipv4Addr::from(address).to_string().into())
+            .unwrap_or(Value::Null),
+        (Ipv6, 16) => <[u8; 16]>::try_from(bytes)
+            .map(|address| std::net::Ipv6Addr::from(address).to_string().into())
+            .unwrap_or(Value::Null),
+        (Mac, 6) => mac_address(bytes).into(),
+        (Text, _) => String::from_utf8_lossy(bytes)
+            .trim_end_matches('\0')
+            .to_owned()
+            .into(),
+        (Seconds, 4) => unsigned(bytes)
+            .and_then(|seconds| Utc.timestamp_opt(seconds as i64, 0).single())
+            .map_or(Value::Null, Value::Timestamp),
+        (Milliseconds, 8) => unsigned(bytes)
+            .and_then(|millis| millis_to_timestamp(millis as i64))
+            .map_or(Value::Null, Value::Timestamp),
+        (Ntp, 8) => {
+            let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+            let nanos = (u64::from(fraction) * 1_000_000_000) >> 32;
+            Utc.timestamp_opt(i64::from(seconds) - NTP_UNIX_OFFSET, nanos as u32)
+                .single()
+                .map_or(Value::Null, Value::Timestamp)
+        }
+        // The fields of unexpected lengths, and the unknown ones, are numbers if they fit in one.
+        _ => match unsigned(bytes) {
+            Some(value) => unsigned_value(value),
+            None => hex::encode(bytes).into(),
+        },
+    }
+}
+
+/// Converts an unsigned integer, keeping the ones beyond the range of integers as strings.
+pub(super) fn unsigned_value(value: u64) -> Value {
+    match i64::try_from(value) {
+        Ok(value) => Value::from(value),
+        Err(_) => Value::from(value.to_string()),
+    }
+}
+
+pub(super) fn mac_address(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+pub(super) fn millis_to_timestamp(millis: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_millis_opt(millis).single()
+}
+
+fn unsigned(bytes: &[u8]) -> Option<u64> {
+    (!bytes.is_empty() && bytes.len() <= 8).then(|| {
+        bytes
+            .iter()
+            .fold(0, |value: u64, byte| (value << 8) | u64::from(*byte))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements_are_sorted() {
+        assert!(ELEMENTS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn decodes_fields() {
+        assert_eq!(
+            decode_field(8, None, &[192, 0, 2, 1], None),
+            ("source_ipv4_address".into(), Value::from("192.0.2.1"))
+        );
+        assert_eq!(
+            decode_field(2, None, &[0, 0, 1, 0], None),
+            ("packet_delta_count".into(), Value::from(256))
+        );
+        assert_eq!(
+            decode_field(56, None, &[0, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e], None),
+            (
+                "source_mac_address".into(),
+                Value::from("00:1a:2b:3c:4d:5e")
+            )
+        );
+        assert_eq!(
+            decode_field(1000, None, &[7], None),
+            ("field_1000".into(), Value::from(7))
+        );
+        assert_eq!(
+            decode_field(1, Some(9), &[1, 2, 3, 4, 5, 6, 7, 8, 9], None),
+            ("enterprise_9_1".into(), Value::from("010203040506070809"))
+        );
+    }
+
+    #[test]
+    fn decodes_timestamps() {
+        let time_base = TimeBase {
+            uptime_millis: 10_000,
+            unix_millis: 1_700_000_000_000,
+        };
+        assert_eq!(
+            decode_field(22, None, &[0, 0, 0x1f, 0x40], Some(&time_base)),
+            (
+                "flow_start_milliseconds".into(),
+                Value::Timestamp(Utc.timestamp_millis_opt(1_699_999_998_000).unwrap())
+            )
+        );
+        assert_eq!(
+            decode_field(22, None, &[0, 0, 0x1f, 0x40], None),
+            ("flow_start_sys_up_time".into(), Value::from(8000))
+        );
+        assert_eq!(
+            decode_field(150, None, &[0x65, 0x53, 0xf1, 0x00], None),
+            (
+                "flow_start_seconds".into(),
+                Value::Timestamp(Utc.timestamp_opt(1_700_000_000, 0).unwrap())
+            )
+        );
+        // 2023-11-14T22:13:20.5Z as an NTP timestamp.
+        let ntp = (((1_700_000_000 + NTP_UNIX_OFFSET) as u64) << 32) | 0x8000_0000;
+        assert_eq!(
+            decode_field(154, None, &ntp.to_be_bytes(), None).1,
+            Value::Timestamp(Utc.timestamp_opt(1_700_000_000, 500_000_000).unwrap())
+        );
+    }
+}
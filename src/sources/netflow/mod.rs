@@ -0,0 +1,456 @@
+//! The `netflow` source.
+//!
+//! Listens for the NetFlow v5, NetFlow v9, IPFIX and sFlow v5 datagrams of flow exporters, and
+//! emits each of their flow records as a log event. The fields of the records are named after the
+//! [IPFIX information elements][iana], whatever the protocol they were exported with.
+//!
+//! [iana]: https://www.iana.org/assignments/ipfix/ipfix.xhtml
+use std::{collections::BTreeMap, net::SocketAddr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use listenfd::ListenFd;
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use serde_with::serde_as;
+use snafu::Snafu;
+use value::{kind::Collection, Kind, Value};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{log_schema, DataType, Output, SourceConfig, SourceContext},
+    event::LogEvent,
+    internal_events::{
+        EventsReceived, NetflowParseError, SocketBindError, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::net::{try_bind_udp_socket, SocketListenAddr},
+    udp, SourceSender,
+};
+
+mod fields;
+mod reader;
+mod sflow;
+mod template;
+mod v5;
+
+use self::{reader::Reader, template::TemplateCache};
+
+/// The largest payload of a UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+/// The fields of a flow record.
+type Record = BTreeMap<String, Value>;
+
+/// Configuration for the `netflow` source.
+#[serde_as]
+#[configurable_component(source("netflow"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    /// The UDP address to listen for flows on.
+    #[serde(default = "default_address")]
+    #[configurable(metadata(docs::examples = "0.0.0.0:2055"))]
+    #[configurable(metadata(docs::examples = "systemd"))]
+    #[configurable(metadata(docs::examples = "systemd#3"))]
+    pub address: SocketListenAddr,
+
+    /// The flow protocols to accept.
+    ///
+    /// The datagrams of the other protocols are rejected.
+    #[serde(default = "default_protocols")]
+    pub protocols: Vec<FlowProtocol>,
+
+    /// How long the NetFlow v9 and IPFIX templates are kept after they were last received.
+    ///
+    /// Exporters periodically resend their templates. The records whose template is unknown, or
+    /// expired, are dropped.
+    #[serde(default = "default_template_timeout_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub template_timeout_secs: Duration,
+
+    /// The size of the receive buffer used for the listening socket.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub receive_buffer_bytes: Option<usize>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// A flow protocol.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowProtocol {
+    /// NetFlow version 5.
+    NetflowV5,
+
+    /// NetFlow version 9.
+    NetflowV9,
+
+    /// IPFIX, also known as NetFlow version 10.
+    Ipfix,
+
+    /// sFlow version 5.
+    Sflow,
+}
+
+impl FlowProtocol {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::NetflowV5 => "netflow_v5",
+            Self::NetflowV9 => "netflow_v9",
+            Self::Ipfix => "ipfix",
+            Self::Sflow => "sflow",
+        }
+    }
+}
+
+fn default_address() -> SocketListenAddr {
+    SocketListenAddr::SocketAddr(SocketAddr::from(([0, 0, 0, 0], 2055)))
+}
+
+fn default_protocols() -> Vec<FlowProtocol> {
+    vec![
+        FlowProtocol::NetflowV5,
+        FlowProtocol::NetflowV9,
+        FlowProtocol::Ipfix,
+        FlowProtocol::Sflow,
+    ]
+}
+
+const fn default_template_timeout_secs() -> Duration {
+    Duration::from_secs(1800)
+}
+
+impl Default for NetflowConfig {
+    fn default() -> Self {
+        Self {
+            address: default_address(),
+            protocols: default_protocols(),
+            template_timeout_secs: default_template_timeout_secs(),
+            receive_buffer_bytes: None,
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(NetflowConfig);
+
+impl NetflowConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .with_event_field(&owned_value_path!("flow_protocol"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("record_type"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("sequence_number"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("export_time"),
+                Kind::timestamp().or_undefined(),
+                None,
+            )
+            .unknown_fields(Kind::any())
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                NetflowConfig::NAME,
+                parse_value_path(log_schema().host_key())
+                    .ok()
+                    .map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some("host"),
+            )
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.protocols.is_empty() {
+            return Err("At least one flow protocol must be accepted.".into());
+        }
+
+        let source = NetflowSource {
+            address: self.address,
+            receive_buffer_bytes: self.receive_buffer_bytes,
+            protocols: self.protocols.clone(),
+            templates: TemplateCache::new(self.template_timeout_secs),
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+enum DecodeError {
+    #[snafu(display("The datagram is truncated."))]
+    Truncated,
+    #[snafu(display("Unsupported flow protocol version {}.", version))]
+    UnsupportedVersion { version: u16 },
+    #[snafu(display("The {} protocol isn't accepted.", protocol))]
+    DisabledProtocol { protocol: &'static str },
+    #[snafu(display("Invalid length."))]
+    InvalidLength,
+    #[snafu(display("Invalid template {}.", id))]
+    InvalidTemplate { id: u16 },
+    #[snafu(display("Invalid agent address type {}.", address_type))]
+    InvalidAddressType { address_type: u32 },
+}
+
+struct NetflowSource {
+    address: SocketListenAddr,
+    receive_buffer_bytes: Option<usize>,
+    protocols: Vec<FlowProtocol>,
+    templates: TemplateCache,
+    log_namespace: LogNamespace,
+}
+
+impl NetflowSource {
+    async fn run(mut self, mut out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let socket = try_bind_udp_socket(self.address, ListenFd::from_env())
+            .await
+            .map_err(|error| {
+                emit!(SocketBindError {
+                    mode: SocketMode::Udp,
+                    error,
+                })
+            })?;
+
+        if let Some(receive_buffer_bytes) = self.receive_buffer_bytes {
+            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+            }
+        }
+
+        let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+        let events_received = register!(EventsReceived);
+
+        info!(message = "Listening.", address = %self.address);
+
+        let mut buffer = vec![0; MAX_DATAGRAM_SIZE];
+        loop {
+            let (size, peer_addr) = tokio::select! {
+                received = socket.recv_from(&mut buffer) => match received {
+                    Ok(received) => received,
+                    Err(error) => {
+                        return Err(emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error
+                        }));
+                    }
+                },
+                _ = &mut shutdown => return Ok(()),
+            };
+            bytes_received.emit(ByteSize(size));
+
+            let records = match self.decode(&buffer[..size], peer_addr) {
+                Ok(records) => records,
+                Err(error) => {
+                    emit!(NetflowParseError { error, peer_addr });
+                    continue;
+                }
+            };
+            if records.is_empty() {
+                // The datagram only had templates, or records of unknown templates.
+                continue;
+            }
+
+            let now = Utc::now();
+            let events = records
+                .into_iter()
+                .map(|record| self.create_log_event(record, peer_addr, now))
+                .collect::<Vec<_>>();
+            let count = events.len();
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+    }
+
+    /// Decodes the flow records of a datagram, whose protocol is told by its version.
+    fn decode(
+        &mut self,
+        datagram: &[u8],
+        exporter: SocketAddr,
+    ) -> Result<Vec<Record>, DecodeError> {
+        let mut reader = Reader::new(datagram);
+        let protocol = match reader.u16()? {
+            5 => FlowProtocol::NetflowV5,
+            9 => FlowProtocol::NetflowV9,
+            10 => FlowProtocol::Ipfix,
+            // The version of sFlow is a 32-bit integer.
+            0 => match reader.u16()? {
+                5 => FlowProtocol::Sflow,
+                version => return Err(DecodeError::UnsupportedVersion { version }),
+            },
+            version => return Err(DecodeError::UnsupportedVersion { version }),
+        };
+        if !self.protocols.contains(&protocol) {
+            return Err(DecodeError::DisabledProtocol {
+                protocol: protocol.as_str(),
+            });
+        }
+
+        match protocol {
+            FlowProtocol::NetflowV5 => v5::decode(reader),
+            FlowProtocol::NetflowV9 => template::decode_v9(reader, exporter, &mut self.templates),
+            FlowProtocol::Ipfix => template::decode_ipfix(reader, exporter, &mut self.templates),
+            FlowProtocol::Sflow => sflow::decode(reader),
+        }
+    }
+
+    fn create_log_event(
+        &self,
+        record: Record,
+        peer_addr: SocketAddr,
+        now: DateTime<Utc>,
+    ) -> LogEvent {
+        let mut log = LogEvent::from(record);
+        self.log_namespace.insert_source_metadata(
+            NetflowConfig::NAME,
+            &mut log,
+            parse_value_path(log_schema().host_key())
+                .ok()
+                .as_ref()
+                .map(LegacyKey::InsertIfEmpty),
+            path!("host"),
+            peer_addr.ip().to_string(),
+        );
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            NetflowConfig::NAME,
+            now,
+        );
+
+        log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::UdpSocket;
+
+    use super::*;
+    use crate::test_util::{collect_n, next_addr};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    async fn start_source(
+        config: NetflowConfig,
+    ) -> impl futures::Stream<Item = crate::event::Event> {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        rx
+    }
+
+    #[tokio::test]
+    async fn receives_flows() {
+        let address = next_addr();
+        let rx = start_source(NetflowConfig {
+            address: address.into(),
+            ..Default::default()
+        })
+        .await;
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        socket
+            .send_to(&v5::tests::datagram(), address)
+            .await
+            .unwrap();
+        // The records of an unknown template are dropped.
+        let datagram = template::tests::v9_datagram(&[template::tests::v9_data_set()]);
+        socket.send_to(&datagram, address).await.unwrap();
+        let datagram = template::tests::v9_datagram(&[
+            template::tests::v9_template_set(),
+            template::tests::v9_data_set(),
+        ]);
+        socket.send_to(&datagram, address).await.unwrap();
+        socket
+            .send_to(&sflow::tests::datagram(), address)
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 4).await;
+        let protocols = events
+            .iter()
+            .map(|event| event.as_log()["flow_protocol"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            protocols,
+            vec![
+                Value::from("netflow_v5"),
+                Value::from("netflow_v9"),
+                Value::from("netflow_v9"),
+                Value::from("sflow"),
+            ]
+        );
+        let log = events[1].as_log();
+        assert_eq!(log["octet_delta_count"], Value::from(1500));
+        assert_eq!(log[log_schema().host_key()], Value::from("127.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn rejects_disabled_protocols() {
+        let address = next_addr();
+        let rx = start_source(NetflowConfig {
+            address: address.into(),
+            protocols: vec![FlowProtocol::Sflow],
+            ..Default::default()
+        })
+        .await;
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        socket
+            .send_to(&v5::tests::datagram(), address)
+            .await
+            .unwrap();
+        socket
+            .send_to(&sflow::tests::datagram(), address)
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(events[0].as_log()["flow_protocol"], Value::from("sflow"));
+    }
+}
@@ -0,0 +1,68 @@
+//! Reading of the big-endian values the flow datagrams are made of.
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::DecodeError;
+
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub(super) const fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub(super) fn bytes(&mut self, length: usize) -> Result<&'a [u8], DecodeError> {
+        if length > self.bytes.len() {
+            return Err(DecodeError::Truncated);
+        }
+        let (bytes, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Splits off a reader of the next bytes.
+    pub(super) fn split(&mut self, length: usize) -> Result<Reader<'a>, DecodeError> {
+        self.bytes(length).map(Reader::new)
+    }
+
+    pub(super) fn skip(&mut self, length: usize) -> Result<(), DecodeError> {
+        self.bytes(length).map(|_| ())
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, DecodeError> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub(super) fn ipv4(&mut self) -> Result<Ipv4Addr, DecodeError> {
+        self.array::<4>().map(Ipv4Addr::from)
+    }
+
+    pub(super) fn ipv6(&mut self) -> Result<Ipv6Addr, DecodeError> {
+        self.array::<16>().map(Ipv6Addr::from)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let bytes = self.bytes(N)?;
+        let mut array = [0; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+}
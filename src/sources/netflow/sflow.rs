@@ -0,0 +1,375 @@
+//! Decoding of the flow samples of sFlow v5 datagrams, as defined by the [sFlow version 5][sflow]
+//! specification.
+//!
+//! The headers of the sampled packets are decoded into the same fields as the flow records of
+//! NetFlow and IPFIX. The counter samples are skipped.
+//!
+//! [sflow]: https://sflow.org/sflow_version_5.txt
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use value::Value;
+
+use super::{
+    fields::{mac_address, unsigned_value},
+    reader::Reader,
+    DecodeError, Record,
+};
+
+const FLOW_SAMPLE: u32 = 1;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+
+const RAW_PACKET_HEADER: u32 = 1;
+const ETHERNET_FRAME: u32 = 2;
+const IPV4_DATA: u32 = 3;
+const IPV6_DATA: u32 = 4;
+const EXTENDED_SWITCH: u32 = 1001;
+
+const HEADER_ETHERNET: u32 = 1;
+const HEADER_IPV4: u32 = 11;
+const HEADER_IPV6: u32 = 12;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+
+const PROTOCOL_TCP: u8 = 6;
+const PROTOCOL_UDP: u8 = 17;
+
+/// Decodes the flow samples of an sFlow datagram, whose version was already read.
+pub(super) fn decode(mut reader: Reader) -> Result<Vec<Record>, DecodeError> {
+    let agent_address = match reader.u32()? {
+        1 => reader.ipv4()?.to_string(),
+        2 => reader.ipv6()?.to_string(),
+        address_type => return Err(DecodeError::InvalidAddressType { address_type }),
+    };
+    let sub_agent_id = reader.u32()?;
+    let sequence_number = reader.u32()?;
+    let _uptime = reader.u32()?;
+    let samples = reader.u32()?;
+
+    let mut header = Record::new();
+    header.insert("flow_protocol".to_owned(), "sflow".into());
+    header.insert("agent_address".to_owned(), agent_address.into());
+    header.insert(
+        "sub_agent_id".to_owned(),
+        Value::from(i64::from(sub_agent_id)),
+    );
+    header.insert(
+        "sequence_number".to_owned(),
+        Value::from(i64::from(sequence_number)),
+    );
+    header.insert("record_type".to_owned(), "flow".into());
+
+    let mut records = Vec::new();
+    for _ in 0..samples {
+        let format = reader.u32()?;
+        let length = reader.u32()?;
+        let sample = reader.split(length as usize)?;
+        match format {
+            FLOW_SAMPLE => records.push(decode_flow_sample(sample, &header, false)?),
+            EXPANDED_FLOW_SAMPLE => records.push(decode_flow_sample(sample, &header, true)?),
+            _ => {}
+        }
+    }
+    Ok(records)
+}
+
+fn decode_flow_sample(
+    mut sample: Reader,
+    header: &Record,
+    expanded: bool,
+) -> Result<Record, DecodeError> {
+    let mut record = header.clone();
+
+    let _sample_sequence_number = sample.u32()?;
+    if expanded {
+        sample.skip(8)?;
+    } else {
+        sample.skip(4)?;
+    }
+    insert(&mut record, "sampling_rate", sample.u32()?.into());
+    insert(&mut record, "sample_pool", sample.u32()?.into());
+    insert(&mut record, "drops", sample.u32()?.into());
+
+    // The interfaces are given with their format, which is zero for the index of a single one.
+    let (input, output) = if expanded {
+        let input = (sample.u32()?, sample.u32()?);
+        let output = (sample.u32()?, sample.u32()?);
+        (input, output)
+    } else {
+        let input = sample.u32()?;
+        let output = sample.u32()?;
+        (
+            (input >> 30, input & 0x3fff_ffff),
+            (output >> 30, output & 0x3fff_ffff),
+        )
+    };
+    if input.0 == 0 {
+        insert(&mut record, "ingress_interface", input.1.into());
+    }
+    if output.0 == 0 {
+        insert(&mut record, "egress_interface", output.1.into());
+    }
+
+    let count = sample.u32()?;
+    for _ in 0..count {
+        let format = sample.u32()?;
+        let length = sample.u32()?;
+        let mut data = sample.split(length as usize)?;
+        match format {
+            RAW_PACKET_HEADER => {
+                let protocol = data.u32()?;
+                let frame_length = data.u32()?;
+                let _stripped = data.u32()?;
+                let header_length = data.u32()?;
+                let bytes = data.bytes(header_length as usize)?;
+                insert(&mut record, "data_link_frame_size", frame_length.into());
+                match protocol {
+                    HEADER_ETHERNET => decode_ethernet(bytes, &mut record),
+                    HEADER_IPV4 => decode_ipv4(bytes, &mut record),
+                    HEADER_IPV6 => decode_ipv6(bytes, &mut record),
+                    _ => {}
+                }
+            }
+            ETHERNET_FRAME => {
+                let _length = data.u32()?;
+                // The addresses are padded to four bytes.
+                let source = data.bytes(8)?;
+                let destination = data.bytes(8)?;
+                let ethernet_type = data.u32()?;
+                record.insert(
+                    "source_mac_address".to_owned(),
+                    mac_address(&source[..6]).into(),
+                );
+                record.insert(
+                    "destination_mac_address".to_owned(),
+                    mac_address(&destination[..6]).into(),
+                );
+                insert(&mut record, "ethernet_type", ethernet_type.into());
+            }
+            IPV4_DATA | IPV6_DATA => {
+                let _length = data.u32()?;
+                let protocol = data.u32()?;
+                let (source, destination) = if format == IPV4_DATA {
+                    (data.ipv4()?.to_string(), data.ipv4()?.to_string())
+                } else {
+                    (data.ipv6()?.to_string(), data.ipv6()?.to_string())
+                };
+                let version = if format == IPV4_DATA { "ipv4" } else { "ipv6" };
+                record.insert(format!("source_{}_address", version), source.into());
+                record.insert(
+                    format!("destination_{}_address", version),
+                    destination.into(),
+                );
+                insert(&mut record, "protocol_identifier", protocol.into());
+                insert(&mut record, "source_transport_port", data.u32()?.into());
+                insert(
+                    &mut record,
+                    "destination_transport_port",
+                    data.u32()?.into(),
+                );
+                insert(&mut record, "tcp_control_bits", data.u32()?.into());
+                insert(&mut record, "ip_class_of_service", data.u32()?.into());
+            }
+            EXTENDED_SWITCH => {
+                insert(&mut record, "vlan_id", data.u32()?.into());
+                let _source_priority = data.u32()?;
+                insert(&mut record, "post_vlan_id", data.u32()?.into());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(record)
+}
+
+fn insert(record: &mut Record, name: &str, value: u64) {
+    record.insert(name.to_owned(), unsigned_value(value));
+}
+
+/// Decodes the header of a sampled Ethernet frame, and the headers of the packet it carries.
+fn decode_ethernet(bytes: &[u8], record: &mut Record) {
+    if bytes.len() < 14 {
+        return;
+    }
+    record.insert(
+        "destination_mac_address".to_owned(),
+        mac_address(&bytes[0..6]).into(),
+    );
+    record.insert(
+        "source_mac_address".to_owned(),
+        mac_address(&bytes[6..12]).into(),
+    );
+
+    let mut ethernet_type = u16::from_be_bytes([bytes[12], bytes[13]]);
+    let mut offset = 14;
+    if ethernet_type == ETHERTYPE_VLAN && bytes.len() >= 18 {
+        let tag = u16::from_be_bytes([bytes[14], bytes[15]]);
+        insert(record, "vlan_id", u64::from(tag & 0x0fff));
+        ethernet_type = u16::from_be_bytes([bytes[16], bytes[17]]);
+        offset = 18;
+    }
+    insert(record, "ethernet_type", ethernet_type.into());
+
+    match ethernet_type {
+        ETHERTYPE_IPV4 => decode_ipv4(&bytes[offset..], record),
+        ETHERTYPE_IPV6 => decode_ipv6(&bytes[offset..], record),
+        _ => {}
+    }
+}
+
+fn decode_ipv4(bytes: &[u8], record: &mut Record) {
+    if bytes.len() < 20 || bytes[0] >> 4 != 4 {
+        return;
+    }
+    let header_length = usize::from(bytes[0] & 0x0f) * 4;
+    insert(record, "ip_version", 4);
+    insert(record, "ip_class_of_service", bytes[1].into());
+    insert(record, "ip_ttl", bytes[8].into());
+    insert(record, "protocol_identifier", bytes[9].into());
+    let source = Ipv4Addr::new(bytes[12], bytes[13], bytes[14], bytes[15]);
+    let destination = Ipv4Addr::new(bytes[16], bytes[17], bytes[18], bytes[19]);
+    record.insert("source_ipv4_address".to_owned(), source.to_string().into());
+    record.insert(
+        "destination_ipv4_address".to_owned(),
+        destination.to_string().into(),
+    );
+
+    if let Some(transport) = bytes.get(header_length..) {
+        decode_transport(bytes[9], transport, record);
+    }
+}
+
+fn decode_ipv6(bytes: &[u8], record: &mut Record) {
+    if bytes.len() < 40 || bytes[0] >> 4 != 6 {
+        return;
+    }
+    insert(record, "ip_version", 6);
+    let traffic_class = ((bytes[0] & 0x0f) << 4) | (bytes[1] >> 4);
+    insert(record, "ip_class_of_service", traffic_class.into());
+    insert(record, "protocol_identifier", bytes[6].into());
+    insert(record, "ip_ttl", bytes[7].into());
+    let address = |range: std::ops::Range<usize>| {
+        <[u8; 16]>::try_from(&bytes[range])
+            .map(|address| Ipv6Addr::from(address).to_string())
+            .unwrap_or_default()
+    };
+    record.insert("source_ipv6_address".to_owned(), address(8..24).into());
+    record.insert(
+        "destination_ipv6_address".to_owned(),
+        address(24..40).into(),
+    );
+
+    // The extension headers are not followed.
+    decode_transport(bytes[6], &bytes[40..], record);
+}
+
+fn decode_transport(protocol: u8, bytes: &[u8], record: &mut Record) {
+    if (protocol != PROTOCOL_TCP && protocol != PROTOCOL_UDP) || bytes.len() < 4 {
+        return;
+    }
+    insert(
+        record,
+        "source_transport_port",
+        u16::from_be_bytes([bytes[0], bytes[1]]).into(),
+    );
+    insert(
+        record,
+        "destination_transport_port",
+        u16::from_be_bytes([bytes[2], bytes[3]]).into(),
+    );
+    if protocol == PROTOCOL_TCP && bytes.len() >= 14 {
+        insert(record, "tcp_control_bits", bytes[13].into());
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// A sampled Ethernet frame carrying a TCP segment, in a VLAN.
+    fn frame() -> Vec<u8> {
+        let mut frame = vec![0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e];
+        frame.extend([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5f]);
+        frame.extend(ETHERTYPE_VLAN.to_be_bytes());
+        frame.extend(100_u16.to_be_bytes());
+        frame.extend(ETHERTYPE_IPV4.to_be_bytes());
+        // The IPv4 header.
+        frame.extend([0x45, 0x10, 0x00, 0x3c, 0, 0, 0, 0, 64, PROTOCOL_TCP, 0, 0]);
+        frame.extend([192, 0, 2, 1, 198, 51, 100, 2]);
+        // The TCP header.
+        frame.extend(51_000_u16.to_be_bytes());
+        frame.extend(443_u16.to_be_bytes());
+        frame.extend([0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x12]);
+        frame
+    }
+
+    /// Encodes an sFlow datagram with a single flow sample.
+    pub(in crate::sources::netflow) fn datagram() -> Vec<u8> {
+        let frame = frame();
+        let mut header = Vec::new();
+        for value in [HEADER_ETHERNET, 1514, 4, frame.len() as u32] {
+            header.extend(value.to_be_bytes());
+        }
+        header.extend(&frame);
+        header.extend(vec![0; (4 - frame.len() % 4) % 4]);
+
+        let mut sample = Vec::new();
+        // The sequence number, the source, the sampling rate, the sample pool, the drops, the
+        // input and output interfaces, and the number of records.
+        for value in [1_u32, 3, 512, 51_200, 0, 3, 4, 1] {
+            sample.extend(value.to_be_bytes());
+        }
+        sample.extend(RAW_PACKET_HEADER.to_be_bytes());
+        sample.extend((header.len() as u32).to_be_bytes());
+        sample.extend(header);
+
+        let mut datagram = Vec::new();
+        for value in [5_u32, 1] {
+            datagram.extend(value.to_be_bytes());
+        }
+        datagram.extend([192, 0, 2, 254]);
+        // The sub-agent, the sequence number, the uptime, and the number of samples.
+        for value in [0_u32, 9, 10_000, 1] {
+            datagram.extend(value.to_be_bytes());
+        }
+        datagram.extend(FLOW_SAMPLE.to_be_bytes());
+        datagram.extend((sample.len() as u32).to_be_bytes());
+        datagram.extend(sample);
+        datagram
+    }
+
+    #[test]
+    fn decodes_flow_samples() {
+        let datagram = datagram();
+        let mut reader = Reader::new(&datagram);
+        assert_eq!(reader.u32(), Ok(5));
+        let records = decode(reader).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record["flow_protocol"], Value::from("sflow"));
+        assert_eq!(record["agent_address"], Value::from("192.0.2.254"));
+        assert_eq!(record["sequence_number"], Value::from(9));
+        assert_eq!(record["sampling_rate"], Value::from(512));
+        assert_eq!(record["ingress_interface"], Value::from(3));
+        assert_eq!(record["egress_interface"], Value::from(4));
+        assert_eq!(record["data_link_frame_size"], Value::from(1514));
+        assert_eq!(
+            record["source_mac_address"],
+            Value::from("00:1a:2b:3c:4d:5f")
+        );
+        assert_eq!(record["vlan_id"], Value::from(100));
+        assert_eq!(record["ethernet_type"], Value::from(0x0800));
+        assert_eq!(record["source_ipv4_address"], Value::from("192.0.2.1"));
+        assert_eq!(
+            record["destination_ipv4_address"],
+            Value::from("198.51.100.2")
+        );
+        assert_eq!(record["protocol_identifier"], Value::from(6));
+        assert_eq!(record["ip_ttl"], Value::from(64));
+        assert_eq!(record["source_transport_port"], Value::from(51_000));
+        assert_eq!(record["destination_transport_port"], Value::from(443));
+        assert_eq!(record["tcp_control_bits"], Value::from(0x12));
+    }
+}
@@ -0,0 +1,548 @@
+//! Decoding of NetFlow v9 and IPFIX datagrams, whose records are described by the templates the
+//! exporters send beforehand.
+//!
+//! The templates are cached per exporter and observation domain, until they are updated, withdrawn
+//! or expire, as exporters periodically resend them over UDP.
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use value::Value;
+
+use super::{
+    fields::{decode_field, decode_v9_scope, millis_to_timestamp, TimeBase},
+    reader::Reader,
+    DecodeError, Record,
+};
+use crate::internal_events::NetflowTemplateNotFound;
+
+const V9_TEMPLATE_SET: u16 = 0;
+const V9_OPTIONS_TEMPLATE_SET: u16 = 1;
+const IPFIX_TEMPLATE_SET: u16 = 2;
+const IPFIX_OPTIONS_TEMPLATE_SET: u16 = 3;
+/// The first identifier of the data sets, which is that of the template describing them.
+const MIN_DATA_SET: u16 = 256;
+
+/// The length of the IPFIX fields which are preceded by their length.
+const VARIABLE_LENGTH: u16 = 0xffff;
+const ENTERPRISE_BIT: u16 = 0x8000;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct TemplateKey {
+    exporter: SocketAddr,
+    version: u16,
+    observation_domain_id: u32,
+    template_id: u16,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct FieldSpec {
+    id: u16,
+    enterprise: Option<u32>,
+    length: u16,
+}
+
+#[derive(Clone, Debug)]
+struct Template {
+    /// The scope fields of the NetFlow v9 options templates, whose identifiers are not those of
+    /// information elements. The scope fields of IPFIX are part of `fields`.
+    scopes: Vec<FieldSpec>,
+    fields: Vec<FieldSpec>,
+    options: bool,
+    received: Instant,
+}
+
+impl Template {
+    fn new(scopes: Vec<FieldSpec>, fields: Vec<FieldSpec>, options: bool) -> Self {
+        Self {
+            scopes,
+            fields,
+            options,
+            received: Instant::now(),
+        }
+    }
+
+    /// The minimum length of a record, with the variable length fields being at least a byte long.
+    fn min_length(&self) -> usize {
+        self.scopes
+            .iter()
+            .chain(&self.fields)
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => usize::from(length),
+            })
+            .sum()
+    }
+}
+
+/// The templates received from the exporters.
+pub(super) struct TemplateCache {
+    templates: HashMap<TemplateKey, Template>,
+    timeout: Duration,
+    last_purge: Instant,
+}
+
+impl TemplateCache {
+    pub(super) fn new(timeout: Duration) -> Self {
+        Self {
+            templates: HashMap::new(),
+            timeout,
+            last_purge: Instant::now(),
+        }
+    }
+
+    fn insert(&mut self, key: TemplateKey, template: Template) -> Result<(), DecodeError> {
+        if template.min_length() == 0 {
+            return Err(DecodeError::InvalidTemplate {
+                id: key.template_id,
+            });
+        }
+        self.templates.insert(key, template);
+        Ok(())
+    }
+
+    fn get(&self, key: &TemplateKey) -> Option<&Template> {
+        self.templates
+            .get(key)
+            .filter(|template| template.received.elapsed() < self.timeout)
+    }
+
+    /// Removes the expired templates, as often as they expire.
+    fn purge(&mut self) {
+        if self.last_purge.elapsed() < self.timeout {
+            return;
+        }
+        let timeout = self.timeout;
+        self.templates
+            .retain(|_, template| template.received.elapsed() < timeout);
+        self.last_purge = Instant::now();
+    }
+}
+
+/// Decodes the records of a NetFlow v9 datagram, whose version was already read.
+pub(super) fn decode_v9(
+    mut reader: Reader,
+    exporter: SocketAddr,
+    cache: &mut TemplateCache,
+) -> Result<Vec<Record>, DecodeError> {
+    let _count = reader.u16()?;
+    let uptime_millis = reader.u32()?;
+    let unix_seconds = reader.u32()?;
+    let sequence_number = reader.u32()?;
+    let source_id = reader.u32()?;
+
+    let time_base = TimeBase {
+        uptime_millis,
+        unix_millis: i64::from(unix_seconds) * 1000,
+    };
+    let header = header("netflow_v9", sequence_number, unix_seconds, source_id);
+    let key = |template_id| TemplateKey {
+        exporter,
+        version: 9,
+        observation_domain_id: source_id,
+        template_id,
+    };
+
+    cache.purge();
+    let mut records = Vec::new();
+    while reader.remaining() >= 4 {
+        let set_id = reader.u16()?;
+        let mut set = next_set(&mut reader)?;
+        match set_id {
+            V9_TEMPLATE_SET => {
+                while set.remaining() >= 4 {
+                    let template_id = set.u16()?;
+                    let count = set.u16()?;
+                    let fields = read_field_specs(&mut set, count, false)?;
+                    cache.insert(key(template_id), Template::new(Vec::new(), fields, false))?;
+                }
+            }
+            V9_OPTIONS_TEMPLATE_SET => {
+                // The set is padded to four bytes.
+                while set.remaining() >= 6 {
+                    let template_id = set.u16()?;
+                    let scope_length = set.u16()?;
+                    let options_length = set.u16()?;
+                    let scopes = read_field_specs(&mut set, scope_length / 4, false)?;
+                    let fields = read_field_specs(&mut set, options_length / 4, false)?;
+                    cache.insert(key(template_id), Template::new(scopes, fields, true))?;
+                }
+            }
+            template_id if template_id >= MIN_DATA_SET => match cache.get(&key(template_id)) {
+                Some(template) => {
+                    decode_records(set, template, &header, Some(&time_base), &mut records)?
+                }
+                None => emit!(NetflowTemplateNotFound {
+                    exporter,
+                    template_id,
+                }),
+            },
+            // The reserved sets are skipped.
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+/// Decodes the records of an IPFIX message, whose version was already read.
+pub(super) fn decode_ipfix(
+    mut reader: Reader,
+    exporter: SocketAddr,
+    cache: &mut TemplateCache,
+) -> Result<Vec<Record>, DecodeError> {
+    let length = reader.u16()?;
+    let export_time = reader.u32()?;
+    let sequence_number = reader.u32()?;
+    let observation_domain_id = reader.u32()?;
+    // The length of the message includes its 16 bytes header.
+    let body = usize::from(length)
+        .checked_sub(16)
+        .ok_or(DecodeError::InvalidLength)?;
+    let mut reader = reader.split(body)?;
+
+    let header = header("ipfix", sequence_number, export_time, observation_domain_id);
+    let key = |template_id| TemplateKey {
+        exporter,
+        version: 10,
+        observation_domain_id,
+        template_id,
+    };
+
+    cache.purge();
+    let mut records = Vec::new();
+    while reader.remaining() >= 4 {
+        let set_id = reader.u16()?;
+        let mut set = next_set(&mut reader)?;
+        match set_id {
+            IPFIX_TEMPLATE_SET | IPFIX_OPTIONS_TEMPLATE_SET => {
+                let options = set_id == IPFIX_OPTIONS_TEMPLATE_SET;
+                while set.remaining() >= 4 {
+                    let template_id = set.u16()?;
+                    let count = set.u16()?;
+                    if count == 0 {
+                        withdraw(cache, key(template_id));
+                        continue;
+                    }
+                    if options {
+                        // The scope fields are decoded like the other fields.
+                        let _scope_count = set.u16()?;
+                    }
+                    let fields = read_field_specs(&mut set, count, true)?;
+                    cache.insert(key(template_id), Template::new(Vec::new(), fields, options))?;
+                }
+            }
+            template_id if template_id >= MIN_DATA_SET => match cache.get(&key(template_id)) {
+                Some(template) => decode_records(set, template, &header, None, &mut records)?,
+                None => emit!(NetflowTemplateNotFound {
+                    exporter,
+                    template_id,
+                }),
+            },
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+/// Withdraws a template, or all the templates of the observation domain when the identifier of
+/// the template sets is withdrawn.
+fn withdraw(cache: &mut TemplateCache, key: TemplateKey) {
+    if key.template_id == IPFIX_TEMPLATE_SET || key.template_id == IPFIX_OPTIONS_TEMPLATE_SET {
+        cache.templates.retain(|other, _| {
+            other.exporter != key.exporter
+                || other.version != key.version
+                || other.observation_domain_id != key.observation_domain_id
+        });
+    } else {
+        cache.templates.remove(&key);
+    }
+}
+
+fn header(protocol: &str, sequence_number: u32, export_time: u32, domain: u32) -> Record {
+    let mut header = Record::new();
+    header.insert("flow_protocol".to_owned(), protocol.into());
+    header.insert(
+        "sequence_number".to_owned(),
+        Value::from(i64::from(sequence_number)),
+    );
+    if let Some(export_time) = millis_to_timestamp(i64::from(export_time) * 1000) {
+        header.insert("export_time".to_owned(), Value::Timestamp(export_time));
+    }
+    header.insert(
+        "observation_domain_id".to_owned(),
+        Value::from(i64::from(domain)),
+    );
+    header
+}
+
+/// Splits off the contents of the next set, whose identifier was already read.
+fn next_set<'a>(reader: &mut Reader<'a>) -> Result<Reader<'a>, DecodeError> {
+    let length = usize::from(reader.u16()?);
+    let contents = length.checked_sub(4).ok_or(DecodeError::InvalidLength)?;
+    reader.split(contents)
+}
+
+fn read_field_specs(
+    set: &mut Reader,
+    count: u16,
+    ipfix: bool,
+) -> Result<Vec<FieldSpec>, DecodeError> {
+    (0..count)
+        .map(|_| {
+            let id = set.u16()?;
+            let length = set.u16()?;
+            if ipfix && id & ENTERPRISE_BIT != 0 {
+                Ok(FieldSpec {
+                    id: id & !ENTERPRISE_BIT,
+                    enterprise: Some(set.u32()?),
+                    length,
+                })
+            } else {
+                Ok(FieldSpec {
+                    id,
+                    enterprise: None,
+                    length,
+                })
+            }
+        })
+        .collect()
+}
+
+fn decode_records(
+    mut set: Reader,
+    template: &Template,
+    header: &Record,
+    time_base: Option<&TimeBase>,
+    records: &mut Vec<Record>,
+) -> Result<(), DecodeError> {
+    let min_length = template.min_length();
+    // The set may be padded after the last record.
+    while set.remaining() >= min_length && !set.is_empty() {
+        let mut record = header.clone();
+        record.insert(
+            "record_type".to_owned(),
+            if template.options { "options" } else { "flow" }.into(),
+        );
+
+        for scope in &template.scopes {
+            let bytes = set.bytes(usize::from(scope.length))?;
+            let (name, value) = decode_v9_scope(scope.id, bytes);
+            record.insert(name.into_owned(), value);
+        }
+        for field in &template.fields {
+            let length = match field.length {
+                VARIABLE_LENGTH => match set.u8()? {
+                    255 => set.u16()?,
+                    length => u16::from(length),
+                },
+                length => length,
+            };
+            let bytes = set.bytes(usize::from(length))?;
+            let (name, value) = decode_field(field.id, field.enterprise, bytes, time_base);
+            record.insert(name.into_owned(), value);
+        }
+
+        records.push(record);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    pub(in crate::sources::netflow) fn set(id: u16, contents: &[u8]) -> Vec<u8> {
+        let mut set = id.to_be_bytes().to_vec();
+        set.extend((contents.len() as u16 + 4).to_be_bytes());
+        set.extend(contents);
+        set
+    }
+
+    /// The fields of the test template: the source and destination addresses, the bytes, and the
+    /// start of the flows.
+    fn template_fields() -> Vec<u8> {
+        let mut fields = Vec::new();
+        for (id, length) in [(8_u16, 4_u16), (12, 4), (1, 4), (22, 4)] {
+            fields.extend(id.to_be_bytes());
+            fields.extend(length.to_be_bytes());
+        }
+        fields
+    }
+
+    fn data_record(bytes: u32) -> Vec<u8> {
+        let mut record = vec![192, 0, 2, 1, 198, 51, 100, 2];
+        record.extend(bytes.to_be_bytes());
+        record.extend(8_000_u32.to_be_bytes());
+        record
+    }
+
+    /// Encodes a NetFlow v9 datagram with the given sets.
+    pub(in crate::sources::netflow) fn v9_datagram(sets: &[Vec<u8>]) -> Vec<u8> {
+        let mut datagram = 9_u16.to_be_bytes().to_vec();
+        datagram.extend((sets.len() as u16).to_be_bytes());
+        datagram.extend(10_000_u32.to_be_bytes());
+        datagram.extend(1_700_000_000_u32.to_be_bytes());
+        datagram.extend(7_u32.to_be_bytes());
+        datagram.extend(1_u32.to_be_bytes());
+        datagram.extend(sets.concat());
+        datagram
+    }
+
+    pub(in crate::sources::netflow) fn v9_template_set() -> Vec<u8> {
+        let mut template = 256_u16.to_be_bytes().to_vec();
+        template.extend(4_u16.to_be_bytes());
+        template.extend(template_fields());
+        set(V9_TEMPLATE_SET, &template)
+    }
+
+    pub(in crate::sources::netflow) fn v9_data_set() -> Vec<u8> {
+        // Two records, and the padding to four bytes.
+        let mut records = [data_record(1500), data_record(3000)].concat();
+        records.extend([0, 0, 0]);
+        set(256, &records)
+    }
+
+    fn exporter() -> SocketAddr {
+        SocketAddr::from(([192, 0, 2, 254], 2055))
+    }
+
+    fn decode_v9_datagram(datagram: &[u8], cache: &mut TemplateCache) -> Vec<Record> {
+        let mut reader = Reader::new(datagram);
+        assert_eq!(reader.u16(), Ok(9));
+        decode_v9(reader, exporter(), cache).unwrap()
+    }
+
+    #[test]
+    fn decodes_v9_records() {
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let datagram = v9_datagram(&[v9_template_set(), v9_data_set()]);
+        let records = decode_v9_datagram(&datagram, &mut cache);
+
+        assert_eq!(records.len(), 2);
+        let record = &records[0];
+        assert_eq!(record["flow_protocol"], Value::from("netflow_v9"));
+        assert_eq!(record["record_type"], Value::from("flow"));
+        assert_eq!(record["sequence_number"], Value::from(7));
+        assert_eq!(record["observation_domain_id"], Value::from(1));
+        assert_eq!(record["source_ipv4_address"], Value::from("192.0.2.1"));
+        assert_eq!(
+            record["destination_ipv4_address"],
+            Value::from("198.51.100.2")
+        );
+        assert_eq!(record["octet_delta_count"], Value::from(1500));
+        assert_eq!(
+            record["flow_start_milliseconds"],
+            Value::Timestamp(Utc.timestamp_millis_opt(1_699_999_998_000).unwrap())
+        );
+        assert_eq!(records[1]["octet_delta_count"], Value::from(3000));
+    }
+
+    #[test]
+    fn caches_templates_per_exporter() {
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        // The data set is dropped until its template is received.
+        let data = v9_datagram(&[v9_data_set()]);
+        assert!(decode_v9_datagram(&data, &mut cache).is_empty());
+
+        decode_v9_datagram(&v9_datagram(&[v9_template_set()]), &mut cache);
+        assert_eq!(decode_v9_datagram(&data, &mut cache).len(), 2);
+
+        // The templates of an exporter don't apply to the others.
+        let mut reader = Reader::new(&data);
+        reader.u16().unwrap();
+        let other = SocketAddr::from(([192, 0, 2, 253], 2055));
+        assert!(decode_v9(reader, other, &mut cache).unwrap().is_empty());
+    }
+
+    #[test]
+    fn expires_templates() {
+        let mut cache = TemplateCache::new(Duration::ZERO);
+        let datagram = v9_datagram(&[v9_template_set(), v9_data_set()]);
+        assert!(decode_v9_datagram(&datagram, &mut cache).is_empty());
+
+        cache.purge();
+        assert!(cache.templates.is_empty());
+    }
+
+    #[test]
+    fn decodes_v9_options() {
+        let mut template = 257_u16.to_be_bytes().to_vec();
+        template.extend(4_u16.to_be_bytes());
+        template.extend(4_u16.to_be_bytes());
+        // The interface scope, and the sampling interval.
+        template.extend([0, 2, 0, 4, 0, 34, 0, 4]);
+        template.extend([0, 0]);
+        let mut record = 3_u32.to_be_bytes().to_vec();
+        record.extend(1000_u32.to_be_bytes());
+
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let datagram = v9_datagram(&[set(V9_OPTIONS_TEMPLATE_SET, &template), set(257, &record)]);
+        let records = decode_v9_datagram(&datagram, &mut cache);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["record_type"], Value::from("options"));
+        assert_eq!(records[0]["scope_interface"], Value::from(3));
+        assert_eq!(records[0]["sampling_interval"], Value::from(1000));
+    }
+
+    pub(in crate::sources::netflow) fn ipfix_message(sets: &[Vec<u8>]) -> Vec<u8> {
+        let sets = sets.concat();
+        let mut message = 10_u16.to_be_bytes().to_vec();
+        message.extend((sets.len() as u16 + 16).to_be_bytes());
+        message.extend(1_700_000_000_u32.to_be_bytes());
+        message.extend(3_u32.to_be_bytes());
+        message.extend(5_u32.to_be_bytes());
+        message.extend(sets);
+        message
+    }
+
+    #[test]
+    fn decodes_ipfix_records() {
+        let mut template = 300_u16.to_be_bytes().to_vec();
+        template.extend(3_u16.to_be_bytes());
+        // The source address, an enterprise field, and the variable length application name.
+        template.extend([0, 8, 0, 4]);
+        template.extend((ENTERPRISE_BIT | 1).to_be_bytes());
+        template.extend(2_u16.to_be_bytes());
+        template.extend(9_u32.to_be_bytes());
+        template.extend([0, 96]);
+        template.extend(VARIABLE_LENGTH.to_be_bytes());
+
+        let mut record = vec![192, 0, 2, 1, 0, 42];
+        record.push(5);
+        record.extend(b"https");
+
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let message = ipfix_message(&[set(IPFIX_TEMPLATE_SET, &template), set(300, &record)]);
+        let mut reader = Reader::new(&message);
+        assert_eq!(reader.u16(), Ok(10));
+        let records = decode_ipfix(reader, exporter(), &mut cache).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record["flow_protocol"], Value::from("ipfix"));
+        assert_eq!(record["observation_domain_id"], Value::from(5));
+        assert_eq!(
+            record["export_time"],
+            Value::Timestamp(Utc.timestamp_opt(1_700_000_000, 0).unwrap())
+        );
+        assert_eq!(record["source_ipv4_address"], Value::from("192.0.2.1"));
+        assert_eq!(record["enterprise_9_1"], Value::from(42));
+        assert_eq!(record["application_name"], Value::from("https"));
+
+        // The template is withdrawn.
+        let mut withdrawal = 300_u16.to_be_bytes().to_vec();
+        withdrawal.extend(0_u16.to_be_bytes());
+        let message = ipfix_message(&[set(IPFIX_TEMPLATE_SET, &withdrawal)]);
+        let mut reader = Reader::new(&message);
+        reader.u16().unwrap();
+        decode_ipfix(reader, exporter(), &mut cache).unwrap();
+        assert!(cache.templates.is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! Decoding of NetFlow v5 datagrams, whose records have a fixed format.
+use value::Value;
+
+use super::{
+    fields::{millis_to_timestamp, unsigned_value, TimeBase},
+    reader::Reader,
+    DecodeError, Record,
+};
+
+const RECORD_LENGTH: usize = 48;
+
+/// Decodes the records of a NetFlow v5 datagram, whose version was already read.
+pub(super) fn decode(mut reader: Reader) -> Result<Vec<Record>, DecodeError> {
+    let count = reader.u16()?;
+    let uptime_millis = reader.u32()?;
+    let unix_seconds = reader.u32()?;
+    let unix_nanos = reader.u32()?;
+    let sequence_number = reader.u32()?;
+    let engine_type = reader.u8()?;
+    let engine_id = reader.u8()?;
+    // The two upper bits are the sampling mode.
+    let sampling_interval = reader.u16()? & 0x3fff;
+
+    if reader.remaining() < usize::from(count) * RECORD_LENGTH {
+        return Err(DecodeError::Truncated);
+    }
+
+    let time_base = TimeBase {
+        uptime_millis,
+        unix_millis: i64::from(unix_seconds) * 1000 + i64::from(unix_nanos / 1_000_000),
+    };
+    let mut header = Record::new();
+    header.insert("flow_protocol".to_owned(), "netflow_v5".into());
+    header.insert(
+        "sequence_number".to_owned(),
+        Value::from(i64::from(sequence_number)),
+    );
+    if let Some(export_time) = millis_to_timestamp(time_base.unix_millis) {
+        header.insert("export_time".to_owned(), Value::Timestamp(export_time));
+    }
+    header.insert(
+        "engine_type".to_owned(),
+        Value::from(i64::from(engine_type)),
+    );
+    header.insert("engine_id".to_owned(), Value::from(i64::from(engine_id)));
+    header.insert(
+        "sampling_interval".to_owned(),
+        Value::from(i64::from(sampling_interval)),
+    );
+
+    let mut records = Vec::with_capacity(usize::from(count));
+    for _ in 0..count {
+        let mut record = header.clone();
+        let mut insert = |name: &str, value: Value| {
+            record.insert(name.to_owned(), value);
+        };
+        let unsigned = |value: u32| unsigned_value(u64::from(value));
+
+        insert("source_ipv4_address", reader.ipv4()?.to_string().into());
+        insert(
+            "destination_ipv4_address",
+            reader.ipv4()?.to_string().into(),
+        );
+        insert(
+            "ip_next_hop_ipv4_address",
+            reader.ipv4()?.to_string().into(),
+        );
+        insert("ingress_interface", unsigned(reader.u16()?.into()));
+        insert("egress_interface", unsigned(reader.u16()?.into()));
+        insert("packet_delta_count", unsigned(reader.u32()?));
+        insert("octet_delta_count", unsigned(reader.u32()?));
+        let first = reader.u32()?;
+        let last = reader.u32()?;
+        if let Some(start) = time_base.timestamp(first) {
+            insert("flow_start_milliseconds", Value::Timestamp(start));
+        }
+        if let Some(end) = time_base.timestamp(last) {
+            insert("flow_end_milliseconds", Value::Timestamp(end));
+        }
+        insert("source_transport_port", unsigned(reader.u16()?.into()));
+        insert("destination_transport_port", unsigned(reader.u16()?.into()));
+        reader.skip(1)?;
+        insert("tcp_control_bits", unsigned(reader.u8()?.into()));
+        insert("protocol_identifier", unsigned(reader.u8()?.into()));
+        insert("ip_class_of_service", unsigned(reader.u8()?.into()));
+        insert("bgp_source_as_number", unsigned(reader.u16()?.into()));
+        insert("bgp_destination_as_number", unsigned(reader.u16()?.into()));
+        insert("source_ipv4_prefix_length", unsigned(reader.u8()?.into()));
+        insert(
+            "destination_ipv4_prefix_length",
+            unsigned(reader.u8()?.into()),
+        );
+        reader.skip(2)?;
+
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    /// Encodes a NetFlow v5 datagram with a single TCP flow.
+    pub(in crate::sources::netflow) fn datagram() -> Vec<u8> {
+        let mut datagram = Vec::new();
+        datagram.extend(5_u16.to_be_bytes());
+        datagram.extend(1_u16.to_be_bytes());
+        datagram.extend(10_000_u32.to_be_bytes());
+        datagram.extend(1_700_000_000_u32.to_be_bytes());
+        datagram.extend(0_u32.to_be_bytes());
+        datagram.extend(42_u32.to_be_bytes());
+        datagram.extend([0, 1]);
+        datagram.extend((0x4000_u16 | 100).to_be_bytes());
+
+        datagram.extend([192, 0, 2, 1]);
+        datagram.extend([198, 51, 100, 2]);
+        datagram.extend([192, 0, 2, 254]);
+        datagram.extend(3_u16.to_be_bytes());
+        datagram.extend(4_u16.to_be_bytes());
+        datagram.extend(10_u32.to_be_bytes());
+        datagram.extend(1500_u32.to_be_bytes());
+        datagram.extend(8_000_u32.to_be_bytes());
+        datagram.extend(9_500_u32.to_be_bytes());
+        datagram.extend(51_000_u16.to_be_bytes());
+        datagram.extend(443_u16.to_be_bytes());
+        datagram.extend([0, 0x1b, 6, 0]);
+        datagram.extend(64_500_u16.to_be_bytes());
+        datagram.extend(64_501_u16.to_be_bytes());
+        datagram.extend([24, 16, 0, 0]);
+        datagram
+    }
+
+    #[test]
+    fn decodes_records() {
+        let datagram = datagram();
+        let mut reader = Reader::new(&datagram);
+        assert_eq!(reader.u16(), Ok(5));
+        let records = decode(reader).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record["flow_protocol"], Value::from("netflow_v5"));
+        assert_eq!(record["sequence_number"], Value::from(42));
+        assert_eq!(record["sampling_interval"], Value::from(100));
+        assert_eq!(record["source_ipv4_address"], Value::from("192.0.2.1"));
+        assert_eq!(
+            record["destination_ipv4_address"],
+            Value::from("198.51.100.2")
+        );
+        assert_eq!(record["ingress_interface"], Value::from(3));
+        assert_eq!(record["packet_delta_count"], Value::from(10));
+        assert_eq!(record["octet_delta_count"], Value::from(1500));
+        assert_eq!(
+            record["flow_start_milliseconds"],
+            Value::Timestamp(Utc.timestamp_millis_opt(1_699_999_998_000).unwrap())
+        );
+        assert_eq!(
+            record["flow_end_milliseconds"],
+            Value::Timestamp(Utc.timestamp_millis_opt(1_699_999_999_500).unwrap())
+        );
+        assert_eq!(record["source_transport_port"], Value::from(51_000));
+        assert_eq!(record["destination_transport_port"], Value::from(443));
+        assert_eq!(record["tcp_control_bits"], Value::from(0x1b));
+        assert_eq!(record["protocol_identifier"], Value::from(6));
+        assert_eq!(record["bgp_destination_as_number"], Value::from(64_501));
+        assert_eq!(record["destination_ipv4_prefix_length"], Value::from(16));
+    }
+
+    #[test]
+    fn rejects_truncated_datagrams() {
+        let datagram = datagram();
+        let mut reader = Reader::new(&datagram[..datagram.len() - 1]);
+        reader.u16().unwrap();
+        assert_eq!(decode(reader), Err(DecodeError::Truncated));
+    }
+}
@@ -0,0 +1,309 @@
+//! The network flows of the host, read either from the `sock_diag` netlink interface or by polling
+//! the connection tables of procfs.
+use std::{
+    collections::HashMap,
+    fs, io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::Path,
+};
+
+use vector_config::configurable_component;
+
+/// The TCP state of a socket marking it as listening for connections, which is not a flow.
+pub const TCP_LISTEN: u8 = 10;
+
+/// Transport protocols.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowProtocol {
+    /// Transmission Control Protocol.
+    Tcp,
+
+    /// User Datagram Protocol.
+    ///
+    /// Only connected UDP sockets are reported, and their byte and packet counters are not
+    /// available.
+    Udp,
+}
+
+impl FlowProtocol {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Udp => "udp",
+        }
+    }
+}
+
+/// The local and remote endpoints identifying a flow.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FlowKey {
+    pub protocol: FlowProtocol,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+}
+
+/// The byte and packet counters of a flow since it was opened.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FlowCounters {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Process {
+    pub pid: u32,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Flow {
+    pub key: FlowKey,
+    pub state: u8,
+    pub uid: u32,
+    pub inode: u64,
+    pub send_queue: u64,
+    pub receive_queue: u64,
+    /// Only available for TCP flows read through `sock_diag`.
+    pub counters: Option<FlowCounters>,
+    pub process: Option<Process>,
+}
+
+impl Flow {
+    /// Returns the name of the state of the flow, as used by the kernel.
+    pub const fn state_name(&self) -> Option<&'static str> {
+        match self.key.protocol {
+            FlowProtocol::Tcp => tcp_state_name(self.state),
+            // UDP sockets only use the established and close states, for connected and
+            // unconnected sockets respectively.
+            FlowProtocol::Udp => match self.state {
+                1 => Some("established"),
+                _ => None,
+            },
+        }
+    }
+}
+
+const fn tcp_state_name(state: u8) -> Option<&'static str> {
+    match state {
+        1 => Some("established"),
+        2 => Some("syn_sent"),
+        3 => Some("syn_recv"),
+        4 => Some("fin_wait1"),
+        5 => Some("fin_wait2"),
+        6 => Some("time_wait"),
+        7 => Some("close"),
+        8 => Some("close_wait"),
+        9 => Some("last_ack"),
+        10 => Some("listen"),
+        11 => Some("closing"),
+        _ => None,
+    }
+}
+
+/// Whether a socket is a flow, that is whether it is connected to a remote endpoint.
+pub fn is_flow(protocol: FlowProtocol, state: u8, remote: &SocketAddr) -> bool {
+    match protocol {
+        FlowProtocol::Tcp => state != TCP_LISTEN,
+        FlowProtocol::Udp => remote.port() != 0,
+    }
+}
+
+/// Reads the flows of `protocol` from the connection tables of procfs, `net/tcp` and `net/tcp6`
+/// or `net/udp` and `net/udp6`. The tables do not hold byte or packet counters.
+pub fn read_procfs_flows(procfs_root: &Path, protocol: FlowProtocol) -> io::Result<Vec<Flow>> {
+    let mut flows = Vec::new();
+    for suffix in ["", "6"] {
+        let path = procfs_root
+            .join("net")
+            .join(format!("{}{}", protocol.as_str(), suffix));
+        match fs::read_to_string(&path) {
+            Ok(contents) => flows.extend(parse_procfs_table(&contents, protocol)),
+            // IPv6 may be disabled.
+            Err(error) if suffix == "6" && error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(flows)
+}
+
+/// Parses a connection table of procfs, skipping its header and any malformed line.
+pub fn parse_procfs_table(contents: &str, protocol: FlowProtocol) -> Vec<Flow> {
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| parse_procfs_line(line, protocol))
+        .filter(|flow| is_flow(protocol, flow.state, &flow.key.remote))
+        .collect()
+}
+
+fn parse_procfs_line(line: &str, protocol: FlowProtocol) -> Option<Flow> {
+    let mut fields = line.split_whitespace();
+    let local = parse_procfs_address(fields.nth(1)?)?;
+    let remote = parse_procfs_address(fields.next()?)?;
+    let state = u8::from_str_radix(fields.next()?, 16).ok()?;
+    let (send_queue, receive_queue) = fields.next()?.split_once(':')?;
+    let send_queue = u64::from_str_radix(send_queue, 16).ok()?;
+    let receive_queue = u64::from_str_radix(receive_queue, 16).ok()?;
+    let uid = fields.nth(2)?.parse().ok()?;
+    let inode = fields.nth(1)?.parse().ok()?;
+
+    Some(Flow {
+        key: FlowKey {
+            protocol,
+            local,
+            remote,
+        },
+        state,
+        uid,
+        inode,
+        send_queue,
+        receive_queue,
+        counters: None,
+        process: None,
+    })
+}
+
+/// Parses an address of a connection table, such as `0100007F:0050`. The address is printed as
+/// 32 bit words in host byte order, while the port is printed as a number.
+fn parse_procfs_address(address: &str) -> Option<SocketAddr> {
+    let (ip, port) = address.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut octets = [0; 16];
+    if ip.len() != 8 && ip.len() != 32 {
+        return None;
+    }
+    for (i, chunk) in ip.as_bytes().chunks(8).enumerate() {
+        let word = u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        octets[i * 4..i * 4 + 4].copy_from_slice(&word.to_ne_bytes());
+    }
+
+    let ip = if ip.len() == 8 {
+        IpAddr::V4(Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]))
+    } else {
+        IpAddr::V6(Ipv6Addr::from(octets))
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Maps the inode of each socket opened by a process to that process, by reading the file
+/// descriptors of all processes. Processes which exit while being read, or whose file
+/// descriptors cannot be read, are skipped.
+pub fn socket_processes(procfs_root: &Path) -> io::Result<HashMap<u64, Process>> {
+    let mut processes = HashMap::new();
+    for entry in fs::read_dir(procfs_root)? {
+        let entry = entry?;
+        let pid = match entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            Some(pid) => pid,
+            None => continue,
+        };
+
+        let fds = match fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(_) => continue,
+        };
+        let mut name = None;
+        for fd in fds.flatten() {
+            let inode = match fs::read_link(fd.path())
+                .ok()
+                .and_then(|target| parse_socket_inode(target.to_str()?))
+            {
+                Some(inode) => inode,
+                None => continue,
+            };
+
+            let name = name
+                .get_or_insert_with(|| {
+                    fs::read_to_string(entry.path().join("comm"))
+                        .map(|comm| comm.trim_end().to_owned())
+                        .unwrap_or_default()
+                })
+                .clone();
+            processes.entry(inode).or_insert(Process { pid, name });
+        }
+    }
+    Ok(processes)
+}
+
+/// Parses the target of a file descriptor link, which is `socket:[<inode>]` for sockets.
+fn parse_socket_inode(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    const TCP_TABLE: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21962 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:A4B6 0100007F:1F90 01 0000002A:00000000 00:00000000 00000000  1000        0 37645 1 0000000000000000 20 4 30 10 -1
+   2: garbage
+";
+
+    const TCP6_TABLE: &str = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:C350 00000000000000000000000001000000:01BB 06 00000000:00000010 03:00000B4B 00000000     0        0 0 3 0000000000000000
+";
+
+    #[test]
+    fn parses_procfs_tables() {
+        let flows = parse_procfs_table(TCP_TABLE, FlowProtocol::Tcp);
+        assert_eq!(
+            flows,
+            vec![Flow {
+                key: FlowKey {
+                    protocol: FlowProtocol::Tcp,
+                    local: "127.0.0.1:42166".parse().unwrap(),
+                    remote: "127.0.0.1:8080".parse().unwrap(),
+                },
+                state: 1,
+                uid: 1000,
+                inode: 37645,
+                send_queue: 42,
+                receive_queue: 0,
+                counters: None,
+                process: None,
+            }]
+        );
+        assert_eq!(flows[0].state_name(), Some("established"));
+
+        let flows = parse_procfs_table(TCP6_TABLE, FlowProtocol::Tcp);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].key.local, "[::1]:50000".parse().unwrap());
+        assert_eq!(flows[0].key.remote, "[::1]:443".parse().unwrap());
+        assert_eq!(flows[0].receive_queue, 16);
+        assert_eq!(flows[0].state_name(), Some("time_wait"));
+    }
+
+    #[test]
+    fn skips_unconnected_udp_sockets() {
+        let table = "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  100: 00000000:0044 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 17880 2 0000000000000000 0
+  200: 0100007F:D431 0100007F:0035 01 00000000:00000000 00:00000000 00000000   101        0 40213 2 0000000000000000 0
+";
+        let flows = parse_procfs_table(table, FlowProtocol::Udp);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].key.remote, "127.0.0.1:53".parse().unwrap());
+        assert_eq!(flows[0].inode, 40213);
+    }
+
+    #[test]
+    fn parses_socket_inodes() {
+        assert_eq!(parse_socket_inode("socket:[37645]"), Some(37645));
+        assert_eq!(parse_socket_inode("pipe:[37645]"), None);
+        assert_eq!(parse_socket_inode("/dev/null"), None);
+    }
+}
@@ -0,0 +1,643 @@
+//! The `network_flows` source.
+//!
+//! Periodically collects a summary of each network connection of the host, including the process
+//! which opened it and, for TCP, the bytes and packets it transferred, and emits them as log or
+//! metric events.
+use std::{collections::BTreeMap, io, path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use futures::StreamExt;
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use serde_with::serde_as;
+use snafu::Snafu;
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use tracing::Span;
+use value::{kind::Collection, Kind, Value};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{log_schema, DataType, Output, SourceConfig, SourceContext},
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event, LogEvent,
+    },
+    internal_events::{EventsReceived, NetworkFlowsScrapeError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod flows;
+mod sock_diag;
+
+pub use self::flows::FlowProtocol;
+use self::{
+    flows::{read_procfs_flows, socket_processes, Flow},
+    sock_diag::SockDiag,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one protocol must be given."))]
+    NoProtocols,
+    #[snafu(display("Could not open a sock_diag netlink socket: {}", source))]
+    OpenSockDiag { source: io::Error },
+}
+
+/// How network flows are collected.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowCollector {
+    /// Use `sock_diag` if a netlink socket can be opened, and fall back to `procfs` otherwise.
+    #[default]
+    Auto,
+
+    /// Read flows through the `sock_diag` netlink interface of the kernel.
+    ///
+    /// This provides the byte and packet counters of TCP flows.
+    SockDiag,
+
+    /// Poll the connection tables of procfs, such as `/proc/net/tcp`.
+    ///
+    /// The connection tables do not provide byte or packet counters.
+    Procfs,
+}
+
+/// The type of events emitted for network flows.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowEventType {
+    /// Emit a log event for each flow.
+    #[default]
+    Log,
+
+    /// Emit metrics for each flow, tagged with its endpoints and process.
+    Metric,
+}
+
+/// Configuration for the `network_flows` source.
+#[serde_as]
+#[configurable_component(source("network_flows"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkFlowsConfig {
+    /// The interval between flow collections, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(default = "default_scrape_interval")]
+    pub scrape_interval_secs: Duration,
+
+    /// The transport protocols to collect flows for.
+    #[serde(default = "default_protocols")]
+    pub protocols: Vec<FlowProtocol>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub collector: FlowCollector,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub event_type: FlowEventType,
+
+    /// Whether to resolve the process which opened each flow.
+    ///
+    /// Resolving processes requires reading the file descriptors of all processes, which can be
+    /// expensive on hosts running many processes, and requires Vector to run as root, or with the
+    /// `CAP_SYS_PTRACE` capability, to resolve the processes of other users.
+    #[serde(default = "crate::serde::default_true")]
+    pub include_processes: bool,
+
+    /// Overrides the default namespace for the metrics emitted by the source.
+    ///
+    /// Only used when `event_type` is `metric`.
+    #[serde(default = "default_namespace")]
+    pub namespace: Option<String>,
+
+    /// The root of the procfs filesystem.
+    ///
+    /// Defaults to the `PROCFS_ROOT` environment variable, or `/proc` if it is unset. Set this to
+    /// the procfs of the host when running Vector in a container.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/host/proc"))]
+    pub procfs_root: Option<PathBuf>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+const fn default_scrape_interval() -> Duration {
+    Duration::from_secs(15)
+}
+
+fn default_protocols() -> Vec<FlowProtocol> {
+    vec![FlowProtocol::Tcp, FlowProtocol::Udp]
+}
+
+fn default_namespace() -> Option<String> {
+    Some(String::from("network_flows"))
+}
+
+impl Default for NetworkFlowsConfig {
+    fn default() -> Self {
+        Self {
+            scrape_interval_secs: default_scrape_interval(),
+            protocols: default_protocols(),
+            collector: FlowCollector::default(),
+            event_type: FlowEventType::default(),
+            include_processes: true,
+            namespace: default_namespace(),
+            procfs_root: None,
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(NetworkFlowsConfig);
+
+impl NetworkFlowsConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .with_event_field(&owned_value_path!("protocol"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("state"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(&owned_value_path!("local_address"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("local_port"), Kind::integer(), None)
+            .with_event_field(&owned_value_path!("remote_address"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("remote_port"), Kind::integer(), None)
+            .with_event_field(&owned_value_path!("uid"), Kind::integer(), None)
+            .with_event_field(&owned_value_path!("send_queue"), Kind::integer(), None)
+            .with_event_field(&owned_value_path!("receive_queue"), Kind::integer(), None)
+            .with_event_field(
+                &owned_value_path!("bytes_sent"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("bytes_received"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("packets_sent"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("packets_received"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("process_id"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("process_name"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                NetworkFlowsConfig::NAME,
+                parse_value_path(log_schema().host_key())
+                    .ok()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("host"),
+                Kind::bytes().or_undefined(),
+                Some("host"),
+            )
+    }
+
+    fn procfs_root(&self) -> PathBuf {
+        self.procfs_root
+            .clone()
+            .or_else(|| std::env::var_os("PROCFS_ROOT").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("/proc"))
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for NetworkFlowsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.protocols.is_empty() {
+            return Err(BuildError::NoProtocols.into());
+        }
+
+        let sock_diag = match self.collector {
+            FlowCollector::Auto => match SockDiag::new() {
+                Ok(sock_diag) => Some(sock_diag),
+                Err(error) => {
+                    warn!(
+                        message = "Could not open a sock_diag netlink socket, falling back to procfs.",
+                        %error,
+                    );
+                    None
+                }
+            },
+            FlowCollector::SockDiag => {
+                Some(SockDiag::new().map_err(|source| BuildError::OpenSockDiag { source })?)
+            }
+            FlowCollector::Procfs => None,
+        };
+
+        let reader = FlowReader {
+            procfs_root: self.procfs_root(),
+            protocols: self.protocols.clone(),
+            include_processes: self.include_processes,
+            sock_diag,
+        };
+        let source = NetworkFlowsSource {
+            scrape_interval: self.scrape_interval_secs,
+            event_type: self.event_type,
+            namespace: self
+                .namespace
+                .clone()
+                .filter(|namespace| !namespace.is_empty()),
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+
+        Ok(Box::pin(source.run(reader, cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        match self.event_type {
+            FlowEventType::Log => {
+                let schema_definition =
+                    self.schema_definition(global_log_namespace.merge(self.log_namespace));
+                vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+            }
+            FlowEventType::Metric => vec![Output::default(DataType::Metric)],
+        }
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Reads the flows of the host, which is blocking.
+struct FlowReader {
+    procfs_root: PathBuf,
+    protocols: Vec<FlowProtocol>,
+    include_processes: bool,
+    sock_diag: Option<SockDiag>,
+}
+
+impl FlowReader {
+    fn read(&mut self) -> Vec<Flow> {
+        let mut flows = Vec::new();
+        for &protocol in &self.protocols {
+            let result = match &mut self.sock_diag {
+                Some(sock_diag) => sock_diag.flows(protocol),
+                None => read_procfs_flows(&self.procfs_root, protocol),
+            };
+            match result {
+                Ok(protocol_flows) => flows.extend(protocol_flows),
+                Err(error) => emit!(NetworkFlowsScrapeError {
+                    message: "Failed to read network flows.",
+                    error,
+                }),
+            }
+        }
+
+        if self.include_processes && !flows.is_empty() {
+            match socket_processes(&self.procfs_root) {
+                Ok(mut processes) => {
+                    for flow in &mut flows {
+                        flow.process = processes.remove(&flow.inode);
+                    }
+                }
+                Err(error) => emit!(NetworkFlowsScrapeError {
+                    message: "Failed to read the sockets of processes.",
+                    error,
+                }),
+            }
+        }
+
+        flows
+    }
+}
+
+struct NetworkFlowsSource {
+    scrape_interval: Duration,
+    event_type: FlowEventType,
+    namespace: Option<String>,
+    log_namespace: LogNamespace,
+}
+
+impl NetworkFlowsSource {
+    async fn run(
+        self,
+        mut reader: FlowReader,
+        mut out: SourceSender,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let mut interval =
+            IntervalStream::new(time::interval(self.scrape_interval)).take_until(shutdown);
+        let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+        let events_received = register!(EventsReceived);
+        let host = crate::get_hostname().ok();
+
+        while interval.next().await.is_some() {
+            let span = Span::current();
+            let (returned, flows) = tokio::task::spawn_blocking(move || {
+                let flows = span.in_scope(|| reader.read());
+                (reader, flows)
+            })
+            .await
+            .expect("Reading network flows panicked.");
+            reader = returned;
+
+            bytes_received.emit(ByteSize(0));
+            let events = match self.event_type {
+                FlowEventType::Log => flows
+                    .iter()
+                    .map(|flow| Event::from(self.create_log_event(flow, host.as_deref())))
+                    .collect::<Vec<_>>(),
+                FlowEventType::Metric => flows
+                    .iter()
+                    .flat_map(|flow| self.create_metrics(flow, host.as_deref()))
+                    .map(Event::from)
+                    .collect(),
+            };
+
+            let count = events.len();
+            if count == 0 {
+                continue;
+            }
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn create_log_event(&self, flow: &Flow, host: Option<&str>) -> LogEvent {
+        let mut log = LogEvent::from(flow_fields(flow));
+
+        if let Some(host) = host {
+            self.log_namespace.insert_source_metadata(
+                NetworkFlowsConfig::NAME,
+                &mut log,
+                parse_value_path(log_schema().host_key())
+                    .ok()
+                    .as_ref()
+                    .map(LegacyKey::Overwrite),
+                path!("host"),
+                host.to_owned(),
+            );
+        }
+
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            NetworkFlowsConfig::NAME,
+            Utc::now(),
+        );
+
+        log
+    }
+
+    /// Creates the metrics of a flow, which are absolute as they are read from the kernel.
+    fn create_metrics(&self, flow: &Flow, host: Option<&str>) -> Vec<Metric> {
+        let mut tags = MetricTags::default();
+        tags.replace("protocol".into(), flow.key.protocol.as_str().to_owned());
+        tags.replace("local_address".into(), flow.key.local.ip().to_string());
+        tags.replace("local_port".into(), flow.key.local.port().to_string());
+        tags.replace("remote_address".into(), flow.key.remote.ip().to_string());
+        tags.replace("remote_port".into(), flow.key.remote.port().to_string());
+        if let Some(state) = flow.state_name() {
+            tags.replace("state".into(), state.to_owned());
+        }
+        if let Some(process) = &flow.process {
+            tags.replace("process_id".into(), process.pid.to_string());
+            tags.replace("process_name".into(), process.name.clone());
+        }
+        if let Some(host) = host {
+            tags.replace("host".into(), host.to_owned());
+        }
+
+        let timestamp = Utc::now();
+        let metric = |name: &str, value: MetricValue| {
+            Metric::new(name, MetricKind::Absolute, value)
+                .with_namespace(self.namespace.clone())
+                .with_tags(Some(tags.clone()))
+                .with_timestamp(Some(timestamp))
+        };
+
+        let mut metrics = vec![
+            metric(
+                "send_queue_bytes",
+                MetricValue::Gauge {
+                    value: flow.send_queue as f64,
+                },
+            ),
+            metric(
+                "receive_queue_bytes",
+                MetricValue::Gauge {
+                    value: flow.receive_queue as f64,
+                },
+            ),
+        ];
+        if let Some(counters) = flow.counters {
+            for (name, value) in [
+                ("bytes_sent_total", counters.bytes_sent),
+                ("bytes_received_total", counters.bytes_received),
+                ("packets_sent_total", counters.packets_sent),
+                ("packets_received_total", counters.packets_received),
+            ] {
+                metrics.push(metric(
+                    name,
+                    MetricValue::Counter {
+                        value: value as f64,
+                    },
+                ));
+            }
+        }
+        metrics
+    }
+}
+
+fn flow_fields(flow: &Flow) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+    fields.insert("protocol".into(), flow.key.protocol.as_str().into());
+    if let Some(state) = flow.state_name() {
+        fields.insert("state".into(), state.into());
+    }
+    fields.insert(
+        "local_address".into(),
+        flow.key.local.ip().to_string().into(),
+    );
+    fields.insert("local_port".into(), i64::from(flow.key.local.port()).into());
+    fields.insert(
+        "remote_address".into(),
+        flow.key.remote.ip().to_string().into(),
+    );
+    fields.insert(
+        "remote_port".into(),
+        i64::from(flow.key.remote.port()).into(),
+    );
+    fields.insert("uid".into(), i64::from(flow.uid).into());
+    fields.insert("send_queue".into(), (flow.send_queue as i64).into());
+    fields.insert("receive_queue".into(), (flow.receive_queue as i64).into());
+
+    if let Some(counters) = flow.counters {
+        fields.insert("bytes_sent".into(), (counters.bytes_sent as i64).into());
+        fields.insert(
+            "bytes_received".into(),
+            (counters.bytes_received as i64).into(),
+        );
+        fields.insert("packets_sent".into(), (counters.packets_sent as i64).into());
+        fields.insert(
+            "packets_received".into(),
+            (counters.packets_received as i64).into(),
+        );
+    }
+
+    if let Some(process) = &flow.process {
+        fields.insert("process_id".into(), i64::from(process.pid).into());
+        fields.insert("process_name".into(), process.name.clone().into());
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        flows::{FlowCounters, FlowKey, Process},
+        *,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetworkFlowsConfig>();
+    }
+
+    fn source(event_type: FlowEventType, log_namespace: LogNamespace) -> NetworkFlowsSource {
+        NetworkFlowsSource {
+            scrape_interval: default_scrape_interval(),
+            event_type,
+            namespace: default_namespace(),
+            log_namespace,
+        }
+    }
+
+    fn flow() -> Flow {
+        Flow {
+            key: FlowKey {
+                protocol: FlowProtocol::Tcp,
+                local: "10.0.0.1:42166".parse().unwrap(),
+                remote: "10.0.0.2:443".parse().unwrap(),
+            },
+            state: 1,
+            uid: 1000,
+            inode: 37645,
+            send_queue: 0,
+            receive_queue: 16,
+            counters: Some(FlowCounters {
+                bytes_sent: 1234,
+                bytes_received: 5678,
+                packets_sent: 12,
+                packets_received: 34,
+            }),
+            process: Some(Process {
+                pid: 4321,
+                name: "curl".into(),
+            }),
+        }
+    }
+
+    #[test]
+    fn creates_legacy_log_event() {
+        let log = source(FlowEventType::Log, LogNamespace::Legacy)
+            .create_log_event(&flow(), Some("vector-host"));
+
+        assert_eq!(log["protocol"], "tcp".into());
+        assert_eq!(log["state"], "established".into());
+        assert_eq!(log["local_address"], "10.0.0.1".into());
+        assert_eq!(log["remote_port"], 443_i64.into());
+        assert_eq!(log["bytes_received"], 5678_i64.into());
+        assert_eq!(log["process_name"], "curl".into());
+        assert_eq!(log[log_schema().host_key()], "vector-host".into());
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            NetworkFlowsConfig::NAME.into()
+        );
+    }
+
+    #[test]
+    fn creates_vector_log_event() {
+        let log = source(FlowEventType::Log, LogNamespace::Vector)
+            .create_log_event(&flow(), Some("vector-host"));
+
+        assert_eq!(log["process_id"], 4321_i64.into());
+        assert!(log.get(log_schema().host_key()).is_none());
+        assert_eq!(
+            log.metadata()
+                .value()
+                .get(path!(NetworkFlowsConfig::NAME, "host")),
+            Some(&Value::from("vector-host"))
+        );
+    }
+
+    #[test]
+    fn creates_metrics() {
+        let metrics = source(FlowEventType::Metric, LogNamespace::Legacy)
+            .create_metrics(&flow(), Some("vector-host"));
+
+        let names = metrics.iter().map(Metric::name).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "send_queue_bytes",
+                "receive_queue_bytes",
+                "bytes_sent_total",
+                "bytes_received_total",
+                "packets_sent_total",
+                "packets_received_total",
+            ]
+        );
+
+        let bytes_sent = &metrics[2];
+        assert_eq!(bytes_sent.namespace(), Some("network_flows"));
+        assert_eq!(bytes_sent.value(), &MetricValue::Counter { value: 1234.0 });
+        let tags = bytes_sent.tags().unwrap();
+        assert_eq!(tags.get("remote_address"), Some("10.0.0.2"));
+        assert_eq!(tags.get("process_name"), Some("curl"));
+        assert_eq!(tags.get("state"), Some("established"));
+    }
+
+    #[test]
+    fn procfs_flows_only_have_queue_metrics() {
+        let mut flow = flow();
+        flow.counters = None;
+        flow.process = None;
+        let metrics =
+            source(FlowEventType::Metric, LogNamespace::Legacy).create_metrics(&flow, None);
+
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0].tags().unwrap().get("process_name"), None);
+    }
+}
@@ -0,0 +1,366 @@
+//! Reads the sockets of the host through the [`sock_diag`][sock_diag] netlink interface, which
+//! exposes the byte and packet counters of TCP sockets kept by the kernel.
+//!
+//! [sock_diag]: https://man7.org/linux/man-pages/man7/sock_diag.7.html
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    os::unix::io::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+use nix::sys::socket::{
+    recv, sendto, socket, AddressFamily, MsgFlags, NetlinkAddr, SockFlag, SockProtocol, SockType,
+};
+
+use super::flows::{is_flow, Flow, FlowCounters, FlowKey, FlowProtocol, TCP_LISTEN};
+
+const NLMSG_HEADER_LEN: usize = 16;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLM_F_REQUEST: u16 = 0x01;
+const NLM_F_DUMP: u16 = 0x300;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+
+/// The length of `struct inet_diag_req_v2`.
+const REQUEST_LEN: usize = 56;
+/// The length of `struct inet_diag_msg`.
+const MESSAGE_LEN: usize = 72;
+/// The attribute holding the `struct tcp_info` of a TCP socket.
+const INET_DIAG_INFO: u16 = 2;
+
+/// The offsets of the counters in `struct tcp_info`. `tcpi_segs_in` was added in Linux 4.2, and
+/// older kernels return a shorter structure from which counters are not read.
+const TCPI_BYTES_ACKED: usize = 120;
+const TCPI_BYTES_RECEIVED: usize = 128;
+const TCPI_SEGS_OUT: usize = 136;
+const TCPI_SEGS_IN: usize = 140;
+const TCP_INFO_MIN_LEN: usize = 144;
+
+const RECEIVE_BUFFER_LEN: usize = 32 * 1024;
+
+/// A netlink socket used to dump the sockets of the host.
+pub struct SockDiag {
+    fd: OwnedFd,
+    sequence: u32,
+}
+
+impl SockDiag {
+    pub fn new() -> io::Result<Self> {
+        let fd = socket(
+            AddressFamily::Netlink,
+            SockType::Datagram,
+            SockFlag::SOCK_CLOEXEC,
+            SockProtocol::NetlinkSockDiag,
+        )?;
+        // SAFETY: the file descriptor was just opened and is not owned by anything else.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        Ok(Self { fd, sequence: 0 })
+    }
+
+    /// Dumps the IPv4 and IPv6 flows of `protocol`.
+    pub fn flows(&mut self, protocol: FlowProtocol) -> io::Result<Vec<Flow>> {
+        let mut flows = Vec::new();
+        for family in [AF_INET, AF_INET6] {
+            self.sequence = self.sequence.wrapping_add(1);
+            let request = dump_request(family, protocol, self.sequence);
+            sendto(
+                self.fd.as_raw_fd(),
+                &request,
+                &NetlinkAddr::new(0, 0),
+                MsgFlags::empty(),
+            )?;
+
+            let mut buffer = vec![0; RECEIVE_BUFFER_LEN];
+            loop {
+                let len = recv(self.fd.as_raw_fd(), &mut buffer, MsgFlags::empty())?;
+                if parse_messages(&buffer[..len], protocol, &mut flows)? {
+                    break;
+                }
+            }
+        }
+        Ok(flows)
+    }
+}
+
+/// Builds a `SOCK_DIAG_BY_FAMILY` dump request for all sockets of `family` and `protocol` which
+/// are not listening, asking for the TCP information of TCP sockets.
+fn dump_request(family: u8, protocol: FlowProtocol, sequence: u32) -> Vec<u8> {
+    let (protocol, extensions, states) = match protocol {
+        FlowProtocol::Tcp => (
+            IPPROTO_TCP,
+            1 << (INET_DIAG_INFO - 1),
+            !(1_u32 << TCP_LISTEN),
+        ),
+        FlowProtocol::Udp => (IPPROTO_UDP, 0, u32::MAX),
+    };
+
+    let mut request = Vec::with_capacity(NLMSG_HEADER_LEN + REQUEST_LEN);
+    request.extend_from_slice(&((NLMSG_HEADER_LEN + REQUEST_LEN) as u32).to_ne_bytes());
+    request.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    request.extend_from_slice(&(NLM_F_REQUEST | NLM_F_DUMP).to_ne_bytes());
+    request.extend_from_slice(&sequence.to_ne_bytes());
+    request.extend_from_slice(&0_u32.to_ne_bytes());
+
+    request.extend_from_slice(&[family, protocol, extensions, 0]);
+    request.extend_from_slice(&states.to_ne_bytes());
+    // An empty socket ID matches all sockets.
+    request.resize(NLMSG_HEADER_LEN + REQUEST_LEN, 0);
+    request
+}
+
+/// Parses the netlink messages of one datagram into `flows`, returning whether the dump is done.
+fn parse_messages(
+    mut buffer: &[u8],
+    protocol: FlowProtocol,
+    flows: &mut Vec<Flow>,
+) -> io::Result<bool> {
+    while buffer.len() >= NLMSG_HEADER_LEN {
+        let len = read_u32(buffer, 0) as usize;
+        let kind = read_u16(buffer, 4);
+        if len < NLMSG_HEADER_LEN || len > buffer.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated netlink message",
+            ));
+        }
+        let payload = &buffer[NLMSG_HEADER_LEN..len];
+
+        match kind {
+            NLMSG_DONE => return Ok(true),
+            NLMSG_ERROR => {
+                let code = if payload.len() >= 4 {
+                    i32::from_ne_bytes(payload[..4].try_into().unwrap())
+                } else {
+                    0
+                };
+                if code != 0 {
+                    return Err(io::Error::from_raw_os_error(-code));
+                }
+            }
+            SOCK_DIAG_BY_FAMILY => {
+                if let Some(flow) = parse_flow(payload, protocol) {
+                    flows.push(flow);
+                }
+            }
+            _ => {}
+        }
+
+        buffer = &buffer[align(len).min(buffer.len())..];
+    }
+    Ok(false)
+}
+
+/// Parses a `struct inet_diag_msg`, followed by its attributes.
+fn parse_flow(payload: &[u8], protocol: FlowProtocol) -> Option<Flow> {
+    if payload.len() < MESSAGE_LEN {
+        return None;
+    }
+
+    let family = payload[0];
+    let state = payload[1];
+    let local_port = u16::from_be_bytes([payload[4], payload[5]]);
+    let remote_port = u16::from_be_bytes([payload[6], payload[7]]);
+    let local = SocketAddr::new(parse_address(family, &payload[8..24])?, local_port);
+    let remote = SocketAddr::new(parse_address(family, &payload[24..40])?, remote_port);
+    if !is_flow(protocol, state, &remote) {
+        return None;
+    }
+
+    let counters = attributes(&payload[MESSAGE_LEN..])
+        .find(|(kind, _)| *kind == INET_DIAG_INFO)
+        .and_then(|(_, tcp_info)| parse_counters(tcp_info));
+
+    Some(Flow {
+        key: FlowKey {
+            protocol,
+            local,
+            remote,
+        },
+        state,
+        receive_queue: read_u32(payload, 56).into(),
+        send_queue: read_u32(payload, 60).into(),
+        uid: read_u32(payload, 64),
+        inode: read_u32(payload, 68).into(),
+        counters,
+        process: None,
+    })
+}
+
+fn parse_address(family: u8, bytes: &[u8]) -> Option<IpAddr> {
+    match family {
+        AF_INET => Some(IpAddr::V4(Ipv4Addr::new(
+            bytes[0], bytes[1], bytes[2], bytes[3],
+        ))),
+        AF_INET6 => Some(IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(bytes).ok()?,
+        ))),
+        _ => None,
+    }
+}
+
+fn parse_counters(tcp_info: &[u8]) -> Option<FlowCounters> {
+    (tcp_info.len() >= TCP_INFO_MIN_LEN).then(|| FlowCounters {
+        bytes_sent: read_u64(tcp_info, TCPI_BYTES_ACKED),
+        bytes_received: read_u64(tcp_info, TCPI_BYTES_RECEIVED),
+        packets_sent: read_u32(tcp_info, TCPI_SEGS_OUT).into(),
+        packets_received: read_u32(tcp_info, TCPI_SEGS_IN).into(),
+    })
+}
+
+/// Iterates over the `struct rtattr` attributes of a message, as pairs of types and payloads.
+fn attributes(mut buffer: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    std::iter::from_fn(move || {
+        if buffer.len() < 4 {
+            return None;
+        }
+        let len = read_u16(buffer, 0) as usize;
+        let kind = read_u16(buffer, 2);
+        if len < 4 || len > buffer.len() {
+            return None;
+        }
+        let payload = &buffer[4..len];
+        buffer = &buffer[align(len).min(buffer.len())..];
+        Some((kind, payload))
+    })
+}
+
+const fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(buffer: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes(buffer[offset..offset + 2].try_into().unwrap())
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(buffer[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(buffer: &[u8], offset: usize) -> u64 {
+    u64::from_ne_bytes(buffer[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    fn message(kind: u16, payload: &[u8]) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(&((NLMSG_HEADER_LEN + payload.len()) as u32).to_ne_bytes());
+        message.extend_from_slice(&kind.to_ne_bytes());
+        message.extend_from_slice(&[0; 10]);
+        message.extend_from_slice(payload);
+        message.resize(align(message.len()), 0);
+        message
+    }
+
+    fn inet_diag_msg(state: u8, remote_port: u16, tcp_info: Option<&[u8]>) -> Vec<u8> {
+        let mut payload = vec![AF_INET, state, 0, 0];
+        payload.extend_from_slice(&42166_u16.to_be_bytes());
+        payload.extend_from_slice(&remote_port.to_be_bytes());
+        payload.extend_from_slice(&[127, 0, 0, 1]);
+        payload.extend_from_slice(&[0; 12]);
+        payload.extend_from_slice(&[10, 0, 0, 2]);
+        payload.extend_from_slice(&[0; 12]);
+        // The interface and cookie of the socket.
+        payload.extend_from_slice(&[0; 12]);
+        for value in [0_u32, 3, 7, 1000, 37645] {
+            payload.extend_from_slice(&value.to_ne_bytes());
+        }
+
+        if let Some(tcp_info) = tcp_info {
+            payload.extend_from_slice(&((4 + tcp_info.len()) as u16).to_ne_bytes());
+            payload.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+            payload.extend_from_slice(tcp_info);
+        }
+        payload
+    }
+
+    fn tcp_info() -> Vec<u8> {
+        let mut tcp_info = vec![0; TCP_INFO_MIN_LEN + 8];
+        tcp_info[TCPI_BYTES_ACKED..TCPI_BYTES_ACKED + 8].copy_from_slice(&1234_u64.to_ne_bytes());
+        tcp_info[TCPI_BYTES_RECEIVED..TCPI_BYTES_RECEIVED + 8]
+            .copy_from_slice(&5678_u64.to_ne_bytes());
+        tcp_info[TCPI_SEGS_OUT..TCPI_SEGS_OUT + 4].copy_from_slice(&12_u32.to_ne_bytes());
+        tcp_info[TCPI_SEGS_IN..TCPI_SEGS_IN + 4].copy_from_slice(&34_u32.to_ne_bytes());
+        tcp_info
+    }
+
+    #[test]
+    fn builds_dump_request() {
+        let request = dump_request(AF_INET6, FlowProtocol::Tcp, 7);
+        assert_eq!(request.len(), NLMSG_HEADER_LEN + REQUEST_LEN);
+        assert_eq!(read_u32(&request, 0) as usize, request.len());
+        assert_eq!(read_u16(&request, 4), SOCK_DIAG_BY_FAMILY);
+        assert_eq!(read_u32(&request, 8), 7);
+        assert_eq!(&request[16..20], &[AF_INET6, IPPROTO_TCP, 1 << 1, 0]);
+        assert_eq!(read_u32(&request, 20) & (1 << TCP_LISTEN), 0);
+    }
+
+    #[test]
+    fn parses_tcp_flows() {
+        let mut buffer = message(
+            SOCK_DIAG_BY_FAMILY,
+            &inet_diag_msg(1, 443, Some(&tcp_info())),
+        );
+        // Listening sockets are not flows.
+        buffer.extend(message(
+            SOCK_DIAG_BY_FAMILY,
+            &inet_diag_msg(TCP_LISTEN, 0, None),
+        ));
+
+        let mut flows = Vec::new();
+        assert!(!parse_messages(&buffer, FlowProtocol::Tcp, &mut flows).unwrap());
+        assert_eq!(
+            flows,
+            vec![Flow {
+                key: FlowKey {
+                    protocol: FlowProtocol::Tcp,
+                    local: "127.0.0.1:42166".parse().unwrap(),
+                    remote: "10.0.0.2:443".parse().unwrap(),
+                },
+                state: 1,
+                uid: 1000,
+                inode: 37645,
+                send_queue: 7,
+                receive_queue: 3,
+                counters: Some(FlowCounters {
+                    bytes_sent: 1234,
+                    bytes_received: 5678,
+                    packets_sent: 12,
+                    packets_received: 34,
+                }),
+                process: None,
+            }]
+        );
+
+        assert!(
+            parse_messages(&message(NLMSG_DONE, &[0; 4]), FlowProtocol::Tcp, &mut flows).unwrap()
+        );
+    }
+
+    #[test]
+    fn skips_short_tcp_info() {
+        let buffer = message(
+            SOCK_DIAG_BY_FAMILY,
+            &inet_diag_msg(1, 443, Some(&[0; TCPI_BYTES_ACKED])),
+        );
+        let mut flows = Vec::new();
+        parse_messages(&buffer, FlowProtocol::Tcp, &mut flows).unwrap();
+        assert_eq!(flows[0].counters, None);
+    }
+
+    #[test]
+    fn returns_netlink_errors() {
+        let buffer = message(NLMSG_ERROR, &(-libc::EPERM).to_ne_bytes());
+        let error = parse_messages(&buffer, FlowProtocol::Udp, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.raw_os_error(), Some(libc::EPERM));
+    }
+}
@@ -1,6 +1,11 @@
 use futures::TryFutureExt;
-use opentelemetry_proto::proto::collector::logs::v1::{
-    logs_service_server::LogsService, ExportLogsServiceRequest, ExportLogsServiceResponse,
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{
+        logs_service_server::LogsService, ExportLogsServiceRequest, ExportLogsServiceResponse,
+    },
+    trace::v1::{
+        trace_service_server::TraceService, ExportTraceServiceRequest, ExportTraceServiceResponse,
+    },
 };
 use tonic::{Request, Response, Status};
 use vector_common::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
@@ -12,7 +17,7 @@ use vector_core::{
 
 use crate::{
     internal_events::{EventsReceived, StreamClosedError},
-    sources::opentelemetry::LOGS,
+    sources::opentelemetry::{LOGS, TRACES},
     SourceSender,
 };
 
@@ -30,13 +35,38 @@ impl LogsService for Service {
         &self,
         request: Request<ExportLogsServiceRequest>,
     ) -> Result<Response<ExportLogsServiceResponse>, Status> {
-        let mut events: Vec<Event> = request
+        let events = request
             .into_inner()
             .resource_logs
             .into_iter()
             .flat_map(|v| v.into_event_iter(self.log_namespace))
             .collect();
 
+        self.handle_events(events, LOGS).await?;
+        Ok(Response::new(ExportLogsServiceResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportTraceServiceRequest>,
+    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+        let events = request
+            .into_inner()
+            .resource_spans
+            .into_iter()
+            .flat_map(|v| v.into_trace_events())
+            .collect();
+
+        self.handle_events(events, TRACES).await?;
+        Ok(Response::new(ExportTraceServiceResponse {}))
+    }
+}
+
+impl Service {
+    async fn handle_events(&self, mut events: Vec<Event>, output: &str) -> Result<(), Status> {
         let count = events.len();
         let byte_size = events.estimated_json_encoded_size_of();
         self.events_received.emit(CountByteSize(count, byte_size));
@@ -45,15 +75,14 @@ impl LogsService for Service {
 
         self.pipeline
             .clone()
-            .send_batch_named(LOGS, events)
+            .send_batch_named(output, events)
             .map_err(|error| {
                 let message = error.to_string();
                 emit!(StreamClosedError { error, count });
                 Status::unavailable(message)
             })
             .and_then(|_| handle_batch_status(receiver))
-            .await?;
-        Ok(Response::new(ExportLogsServiceResponse {}))
+            .await
     }
 }
 
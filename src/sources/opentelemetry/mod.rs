@@ -17,7 +17,11 @@ use opentelemetry_proto::convert::{
     SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY, TRACE_ID_KEY,
 };
 
-use opentelemetry_proto::proto::collector::logs::v1::logs_service_server::LogsServiceServer;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::logs_service_server::LogsServiceServer,
+    trace::v1::trace_service_server::TraceServiceServer,
+};
+use tonic::transport::server::Routes;
 use value::{kind::Collection, Kind};
 use vector_common::internal_event::{BytesReceived, EventsReceived, Protocol};
 use vector_config::configurable_component;
@@ -36,11 +40,12 @@ use crate::{
         SourceContext,
     },
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{util::grpc::run_grpc_server_with_routes, Source},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
 pub const LOGS: &str = "logs";
+pub const TRACES: &str = "traces";
 
 /// Configuration for the `opentelemetry` source.
 #[configurable_component(source("opentelemetry"))]
@@ -131,17 +136,24 @@ impl SourceConfig for OpentelemetryConfig {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         let grpc_tls_settings = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
-        let grpc_service = LogsServiceServer::new(Service {
+        let grpc_service = Service {
             pipeline: cx.out.clone(),
             acknowledgements,
             log_namespace,
             events_received: events_received.clone(),
-        })
-        .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
-        let grpc_source = run_grpc_server(
+        };
+        let grpc_routes = Routes::new(
+            LogsServiceServer::new(grpc_service.clone())
+                .accept_compressed(tonic::codec::CompressionEncoding::Gzip),
+        )
+        .add_service(
+            TraceServiceServer::new(grpc_service)
+                .accept_compressed(tonic::codec::CompressionEncoding::Gzip),
+        );
+        let grpc_source = run_grpc_server_with_routes(
             self.grpc.address,
             grpc_tls_settings,
-            grpc_service,
+            grpc_routes,
             cx.shutdown.clone(),
         )
         .map_err(|error| {
@@ -254,9 +266,12 @@ impl SourceConfig for OpentelemetryConfig {
             }
         };
 
-        vec![Output::default(DataType::Log)
-            .with_port(LOGS)
-            .with_schema_definition(schema_definition)]
+        vec![
+            Output::default(DataType::Log)
+                .with_port(LOGS)
+                .with_schema_definition(schema_definition),
+            Output::default(DataType::Trace).with_port(TRACES),
+        ]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -3,10 +3,14 @@ use futures::Stream;
 use futures_util::StreamExt;
 use lookup::path;
 use opentelemetry_proto::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
-    common::v1::{any_value, AnyValue, KeyValue},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    },
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
     resource::v1::Resource as OtelResource,
+    trace::v1::{span::SpanKind, status::StatusCode, ResourceSpans, ScopeSpans, Span, Status},
 };
 use similar_asserts::assert_eq;
 use std::collections::BTreeMap;
@@ -16,7 +20,7 @@ use vector_core::config::LogNamespace;
 use crate::{
     config::{SourceConfig, SourceContext},
     event::{into_event_stream, Event, EventStatus, LogEvent, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -278,6 +282,152 @@ async fn receive_grpc_logs_legacy_namespace() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_grpc_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+            },
+            acknowledgements: Default::default(),
+            log_namespace: Default::default(),
+        };
+
+        let (mut sender, _recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let traces_output = sender
+            .add_outputs(EventStatus::Delivered, TRACES.to_string())
+            .flat_map(into_event_stream);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let span = |span_id: &str, trace_id: &str, kind: SpanKind, code: StatusCode| Span {
+            trace_id: str_into_hex_bytes(trace_id),
+            span_id: str_into_hex_bytes(span_id),
+            name: "GET /users".into(),
+            kind: kind as i32,
+            start_time_unix_nano: 1_000,
+            end_time_unix_nano: 1_500,
+            attributes: vec![KeyValue {
+                key: "http.status_code".into(),
+                value: Some(AnyValue {
+                    value: Some(any_value::Value::IntValue(500)),
+                }),
+            }],
+            status: Some(Status {
+                message: String::new(),
+                code: code as i32,
+            }),
+            ..Default::default()
+        };
+        let req = Request::new(ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(OtelResource {
+                    attributes: vec![KeyValue {
+                        key: "service.name".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("api".into())),
+                        }),
+                    }],
+                    dropped_attributes_count: 0,
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: Some(InstrumentationScope {
+                        name: "tracer".into(),
+                        ..Default::default()
+                    }),
+                    spans: vec![
+                        span(
+                            "0000000000000001",
+                            "4ac52aadf321c2e531db005df08792f5",
+                            SpanKind::Server,
+                            StatusCode::Error,
+                        ),
+                        Span {
+                            parent_span_id: str_into_hex_bytes("0000000000000001"),
+                            ..span(
+                                "0000000000000002",
+                                "4ac52aadf321c2e531db005df08792f5",
+                                SpanKind::Client,
+                                StatusCode::Ok,
+                            )
+                        },
+                        span(
+                            "0000000000000003",
+                            "0000000000000000000000000000000a",
+                            SpanKind::Internal,
+                            StatusCode::Unset,
+                        ),
+                    ],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        let _ = client.export(req).await;
+        let output = test_util::collect_ready(traces_output).await;
+
+        // Spans are grouped by trace id.
+        assert_eq!(output.len(), 2);
+        let trace = output[0].as_trace();
+        assert_eq!(trace.get("trace_id"), Some(&Value::from(10_i64)));
+        assert_eq!(
+            trace.get("source_type"),
+            Some(&Value::from("opentelemetry"))
+        );
+
+        let trace = output[1].as_trace();
+        assert_eq!(
+            trace.get("resources"),
+            Some(&vrl::value!({"service.name": "api"}))
+        );
+        let spans = trace.get("spans").unwrap().as_array().unwrap();
+        assert_eq!(spans.len(), 2);
+        let span = spans[0].as_object().unwrap();
+        assert_eq!(span["service"], Value::from("api"));
+        assert_eq!(span["resource"], Value::from("GET /users"));
+        assert_eq!(span["type"], Value::from("web"));
+        assert_eq!(span["span_id"], Value::from(1_i64));
+        assert_eq!(span["start"], Value::from(Utc.timestamp_nanos(1_000)));
+        assert_eq!(span["duration"], Value::from(500_i64));
+        assert_eq!(span["error"], Value::from(1_i64));
+        assert_eq!(
+            span["meta"],
+            vrl::value!({
+                "otel.library.name": "tracer",
+                "otel.status_code": "Error",
+                "otel.trace_id": "4ac52aadf321c2e531db005df08792f5",
+                "span.kind": "server",
+            })
+        );
+        assert_eq!(
+            span["metrics"],
+            vrl::value!({"_top_level": 1.0, "http.status_code": 500.0})
+        );
+
+        // Only the spans whose parent isn't in the trace are top-level.
+        let span = spans[1].as_object().unwrap();
+        assert_eq!(span["parent_id"], Value::from(1_i64));
+        assert_eq!(span["error"], Value::from(0_i64));
+        assert_eq!(span["metrics"], vrl::value!({"http.status_code": 500.0}));
+    })
+    .await;
+}
+
 pub(super) fn new_source(
     status: EventStatus,
 ) -> (
@@ -0,0 +1,423 @@
+//! Decoding of the messages of the `pgoutput` plugin, the one built into PostgreSQL.
+//!
+//! See <https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html>.
+use std::collections::{BTreeMap, HashMap};
+
+use bytes::Buf;
+use chrono::{DateTime, TimeZone, Utc};
+use ordered_float::NotNan;
+use vector_core::event::Value;
+
+use super::{Change, DecodeError, Message, Operation};
+
+/// The PostgreSQL epoch, 2000-01-01, in seconds since the Unix epoch.
+const POSTGRES_EPOCH_SECS: i64 = 946_684_800;
+
+/// The type OIDs of the columns whose text representation is converted.
+const BOOL_OID: u32 = 16;
+const INT8_OID: u32 = 20;
+const INT2_OID: u32 = 21;
+const INT4_OID: u32 = 23;
+const JSON_OID: u32 = 114;
+const FLOAT4_OID: u32 = 700;
+const FLOAT8_OID: u32 = 701;
+const JSONB_OID: u32 = 3802;
+
+struct Column {
+    name: String,
+    type_oid: u32,
+}
+
+struct Relation {
+    schema: String,
+    table: String,
+    columns: Vec<Column>,
+}
+
+/// Decodes `pgoutput` messages, keeping track of the relations their changes refer to.
+#[derive(Default)]
+pub(super) struct PgOutputDecoder {
+    relations: HashMap<u32, Relation>,
+}
+
+impl PgOutputDecoder {
+    pub(super) fn decode(&mut self, mut data: &[u8]) -> Result<Option<Message>, DecodeError> {
+        let buf = &mut data;
+        Ok(match get_u8(buf)? {
+            b'B' => {
+                let _final_lsn = get_i64(buf)?;
+                let timestamp = get_timestamp(buf)?;
+                let xid = get_i32(buf)? as u32;
+                Some(Message::Begin {
+                    xid: Some(xid.into()),
+                    timestamp,
+                })
+            }
+            b'C' => Some(Message::Commit),
+            b'R' => {
+                let oid = get_i32(buf)? as u32;
+                let schema = get_str(buf)?;
+                let table = get_str(buf)?;
+                let _replica_identity = get_u8(buf)?;
+                let count = get_i16(buf)?;
+                let columns = (0..count)
+                    .map(|_| {
+                        let _flags = get_u8(buf)?;
+                        let name = get_str(buf)?;
+                        let type_oid = get_i32(buf)? as u32;
+                        let _type_modifier = get_i32(buf)?;
+                        Ok(Column { name, type_oid })
+                    })
+                    .collect::<Result<_, DecodeError>>()?;
+                self.relations.insert(
+                    oid,
+                    Relation {
+                        schema,
+                        table,
+                        columns,
+                    },
+                );
+                None
+            }
+            b'I' => {
+                let relation = self.relation(buf)?;
+                expect_tag(buf, b'N')?;
+                let after = get_tuple(buf, relation)?;
+                Some(Message::Changes(vec![relation.change(
+                    Operation::Insert,
+                    None,
+                    Some(after),
+                )]))
+            }
+            b'U' => {
+                let relation = self.relation(buf)?;
+                // The old values are only sent when the replica identity includes the changed
+                // columns, as the key (`K`) or the whole row (`O`).
+                let before = match get_u8(buf)? {
+                    b'K' | b'O' => {
+                        let before = get_tuple(buf, relation)?;
+                        expect_tag(buf, b'N')?;
+                        Some(before)
+                    }
+                    b'N' => None,
+                    tag => return Err(DecodeError::UnexpectedTag { tag: tag as char }),
+                };
+                let after = get_tuple(buf, relation)?;
+                Some(Message::Changes(vec![relation.change(
+                    Operation::Update,
+                    before,
+                    Some(after),
+                )]))
+            }
+            b'D' => {
+                let relation = self.relation(buf)?;
+                match get_u8(buf)? {
+                    b'K' | b'O' => {}
+                    tag => return Err(DecodeError::UnexpectedTag { tag: tag as char }),
+                }
+                let before = get_tuple(buf, relation)?;
+                Some(Message::Changes(vec![relation.change(
+                    Operation::Delete,
+                    Some(before),
+                    None,
+                )]))
+            }
+            b'T' => {
+                let count = get_i32(buf)?;
+                let _options = get_u8(buf)?;
+                let changes = (0..count)
+                    .map(|_| {
+                        let relation = self.relation(buf)?;
+                        Ok(relation.change(Operation::Truncate, None, None))
+                    })
+                    .collect::<Result<_, DecodeError>>()?;
+                Some(Message::Changes(changes))
+            }
+            // Origin, type and logical decoding messages carry nothing to emit.
+            b'O' | b'Y' | b'M' => None,
+            tag => return Err(DecodeError::UnexpectedTag { tag: tag as char }),
+        })
+    }
+
+    fn relation(&self, buf: &mut &[u8]) -> Result<&Relation, DecodeError> {
+        let oid = get_i32(buf)? as u32;
+        self.relations
+            .get(&oid)
+            .ok_or(DecodeError::UnknownRelation { oid })
+    }
+}
+
+impl Relation {
+    fn change(
+        &self,
+        operation: Operation,
+        before: Option<BTreeMap<String, Value>>,
+        after: Option<BTreeMap<String, Value>>,
+    ) -> Change {
+        Change {
+            operation,
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            before,
+            after,
+        }
+    }
+}
+
+fn get_tuple(buf: &mut &[u8], relation: &Relation) -> Result<BTreeMap<String, Value>, DecodeError> {
+    let count = get_i16(buf)? as usize;
+    let mut values = BTreeMap::new();
+    for index in 0..count {
+        let column = relation
+            .columns
+            .get(index)
+            .ok_or(DecodeError::UnexpectedEnd)?;
+        match get_u8(buf)? {
+            b'n' => {
+                values.insert(column.name.clone(), Value::Null);
+            }
+            // Unchanged TOASTed values aren't sent, so they're left out.
+            b'u' => {}
+            b't' => {
+                let len = get_i32(buf)? as usize;
+                let text = get_bytes(buf, len)?;
+                let text = String::from_utf8_lossy(text);
+                values.insert(column.name.clone(), text_to_value(column.type_oid, &text));
+            }
+            tag => return Err(DecodeError::UnexpectedTag { tag: tag as char }),
+        }
+    }
+    Ok(values)
+}
+
+/// Converts the text representation of a column to a value of the matching type, falling back to
+/// the text itself.
+fn text_to_value(type_oid: u32, text: &str) -> Value {
+    let value = match type_oid {
+        BOOL_OID => match text {
+            "t" => Some(Value::Boolean(true)),
+            "f" => Some(Value::Boolean(false)),
+            _ => None,
+        },
+        INT2_OID | INT4_OID | INT8_OID => text.parse::<i64>().ok().map(Value::Integer),
+        FLOAT4_OID | FLOAT8_OID => text
+            .parse::<f64>()
+            .ok()
+            .and_then(|value| NotNan::new(value).ok())
+            .map(Value::Float),
+        JSON_OID | JSONB_OID => serde_json::from_str::<serde_json::Value>(text)
+            .ok()
+            .map(Value::from),
+        _ => None,
+    };
+    value.unwrap_or_else(|| Value::from(text))
+}
+
+fn expect_tag(buf: &mut &[u8], expected: u8) -> Result<(), DecodeError> {
+    match get_u8(buf)? {
+        tag if tag == expected => Ok(()),
+        tag => Err(DecodeError::UnexpectedTag { tag: tag as char }),
+    }
+}
+
+fn get_timestamp(buf: &mut &[u8]) -> Result<Option<DateTime<Utc>>, DecodeError> {
+    let micros = get_i64(buf)?;
+    Ok(Utc
+        .timestamp_opt(
+            POSTGRES_EPOCH_SECS + micros.div_euclid(1_000_000),
+            (micros.rem_euclid(1_000_000) * 1000) as u32,
+        )
+        .single())
+}
+
+fn get_str(buf: &mut &[u8]) -> Result<String, DecodeError> {
+    let len = buf
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or(DecodeError::UnexpectedEnd)?;
+    let string = String::from_utf8_lossy(&buf[..len]).into_owned();
+    buf.advance(len + 1);
+    Ok(string)
+}
+
+fn get_bytes<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodeError> {
+    if buf.len() < len {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(bytes)
+}
+
+macro_rules! get_number {
+    ($name:ident, $type:ty, $get:ident) => {
+        fn $name(buf: &mut &[u8]) -> Result<$type, DecodeError> {
+            if buf.remaining() < std::mem::size_of::<$type>() {
+                return Err(DecodeError::UnexpectedEnd);
+            }
+            Ok(buf.$get())
+        }
+    };
+}
+
+get_number!(get_u8, u8, get_u8);
+get_number!(get_i16, i16, get_i16);
+get_number!(get_i32, i32, get_i32);
+get_number!(get_i64, i64, get_i64);
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+    use value::btreemap;
+
+    use super::*;
+
+    fn relation() -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.put_u8(b'R');
+        buf.put_i32(16384);
+        buf.put_slice(b"public\0users\0");
+        buf.put_u8(b'd');
+        buf.put_i16(3);
+        for (name, type_oid) in [("id", INT4_OID), ("name", 25), ("active", BOOL_OID)] {
+            buf.put_u8(1);
+            buf.put_slice(name.as_bytes());
+            buf.put_u8(0);
+            buf.put_i32(type_oid as i32);
+            buf.put_i32(-1);
+        }
+        buf
+    }
+
+    fn put_tuple(buf: &mut Vec<u8>, values: &[Option<&str>]) {
+        buf.put_i16(values.len() as i16);
+        for value in values {
+            match value {
+                Some(value) => {
+                    buf.put_u8(b't');
+                    buf.put_i32(value.len() as i32);
+                    buf.put_slice(value.as_bytes());
+                }
+                None => buf.put_u8(b'n'),
+            }
+        }
+    }
+
+    fn decoder() -> PgOutputDecoder {
+        let mut decoder = PgOutputDecoder::default();
+        assert!(decoder.decode(&relation()).unwrap().is_none());
+        decoder
+    }
+
+    fn changes(message: Option<Message>) -> Vec<Change> {
+        match message {
+            Some(Message::Changes(changes)) => changes,
+            _ => panic!("expected changes"),
+        }
+    }
+
+    #[test]
+    fn decodes_begin() {
+        let mut buf = vec![b'B'];
+        buf.put_i64(0x16B_3748);
+        // One second after the PostgreSQL epoch.
+        buf.put_i64(1_000_000);
+        buf.put_i32(742);
+
+        match PgOutputDecoder::default().decode(&buf).unwrap() {
+            Some(Message::Begin { xid, timestamp }) => {
+                assert_eq!(xid, Some(742));
+                assert_eq!(
+                    timestamp,
+                    Utc.timestamp_opt(POSTGRES_EPOCH_SECS + 1, 0).single()
+                );
+            }
+            _ => panic!("expected a begin message"),
+        }
+    }
+
+    #[test]
+    fn decodes_insert() {
+        let mut buf = vec![b'I'];
+        buf.put_i32(16384);
+        buf.put_u8(b'N');
+        put_tuple(&mut buf, &[Some("1"), Some("ada"), None]);
+
+        let changes = changes(decoder().decode(&buf).unwrap());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].operation, Operation::Insert);
+        assert_eq!(changes[0].schema, "public");
+        assert_eq!(changes[0].table, "users");
+        assert_eq!(changes[0].before, None);
+        assert_eq!(
+            changes[0].after,
+            Some(btreemap! {
+                "id" => 1,
+                "name" => "ada",
+                "active" => Value::Null,
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_update_with_old_values() {
+        let mut buf = vec![b'U'];
+        buf.put_i32(16384);
+        buf.put_u8(b'O');
+        put_tuple(&mut buf, &[Some("1"), Some("ada"), Some("f")]);
+        buf.put_u8(b'N');
+        put_tuple(&mut buf, &[Some("1"), Some("ada"), Some("t")]);
+
+        let changes = changes(decoder().decode(&buf).unwrap());
+        assert_eq!(changes[0].operation, Operation::Update);
+        assert_eq!(
+            changes[0].before.as_ref().unwrap()["active"],
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            changes[0].after.as_ref().unwrap()["active"],
+            Value::Boolean(true)
+        );
+    }
+
+    #[test]
+    fn decodes_delete_and_truncate() {
+        let mut buf = vec![b'D'];
+        buf.put_i32(16384);
+        buf.put_u8(b'K');
+        put_tuple(&mut buf, &[Some("1"), None, None]);
+
+        let changes = changes(decoder().decode(&buf).unwrap());
+        assert_eq!(changes[0].operation, Operation::Delete);
+        assert_eq!(changes[0].before.as_ref().unwrap()["id"], Value::Integer(1));
+        assert_eq!(changes[0].after, None);
+
+        let mut buf = vec![b'T'];
+        buf.put_i32(1);
+        buf.put_u8(0);
+        buf.put_i32(16384);
+
+        let changes = self::changes(decoder().decode(&buf).unwrap());
+        assert_eq!(changes[0].operation, Operation::Truncate);
+        assert_eq!(changes[0].table, "users");
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        let mut buf = vec![b'I'];
+        buf.put_i32(1);
+        assert!(matches!(
+            decoder().decode(&buf),
+            Err(DecodeError::UnknownRelation { oid: 1 })
+        ));
+
+        assert!(matches!(
+            decoder().decode(&[b'I', 0, 0]),
+            Err(DecodeError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            decoder().decode(b"Z"),
+            Err(DecodeError::UnexpectedTag { tag: 'Z' })
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+//! Decoding of the messages of the `wal2json` plugin, using its `format-version` 2, where each
+//! change is a JSON object of its own.
+//!
+//! See <https://github.com/eulerto/wal2json>.
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use snafu::ResultExt;
+use vector_core::event::Value;
+
+use super::{Change, DecodeError, JsonSnafu, Message, Operation};
+
+#[derive(Deserialize)]
+struct Wal2JsonMessage {
+    action: String,
+    #[serde(default)]
+    xid: Option<i64>,
+    #[serde(default)]
+    timestamp: Option<String>,
+    #[serde(default)]
+    schema: String,
+    #[serde(default)]
+    table: String,
+    #[serde(default)]
+    columns: Vec<Wal2JsonColumn>,
+    #[serde(default)]
+    identity: Vec<Wal2JsonColumn>,
+}
+
+#[derive(Deserialize)]
+struct Wal2JsonColumn {
+    name: String,
+    value: serde_json::Value,
+}
+
+pub(super) fn decode(data: &str) -> Result<Option<Message>, DecodeError> {
+    let message: Wal2JsonMessage = serde_json::from_str(data).context(JsonSnafu)?;
+    let operation = match message.action.as_str() {
+        "B" => {
+            return Ok(Some(Message::Begin {
+                xid: message.xid,
+                timestamp: message.timestamp.as_deref().and_then(parse_timestamp),
+            }))
+        }
+        "C" => return Ok(Some(Message::Commit)),
+        "I" => Operation::Insert,
+        "U" => Operation::Update,
+        "D" => Operation::Delete,
+        "T" => Operation::Truncate,
+        // Logical decoding messages carry no change.
+        "M" => return Ok(None),
+        action => {
+            return Err(DecodeError::UnexpectedTag {
+                tag: action.chars().next().unwrap_or_default(),
+            })
+        }
+    };
+
+    // The old values are in `identity`, which only has the columns of the replica identity.
+    let before = match operation {
+        Operation::Update | Operation::Delete if !message.identity.is_empty() => {
+            Some(columns_to_values(message.identity))
+        }
+        _ => None,
+    };
+    let after = match operation {
+        Operation::Insert | Operation::Update => Some(columns_to_values(message.columns)),
+        _ => None,
+    };
+
+    Ok(Some(Message::Changes(vec![Change {
+        operation,
+        schema: message.schema,
+        table: message.table,
+        before,
+        after,
+    }])))
+}
+
+fn columns_to_values(columns: Vec<Wal2JsonColumn>) -> BTreeMap<String, Value> {
+    columns
+        .into_iter()
+        .map(|column| (column.name, Value::from(column.value)))
+        .collect()
+}
+
+/// Parses the timestamps of the plugin, such as `2023-04-01 12:30:00.123456+00`.
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f%#z")
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use value::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn decodes_transactions() {
+        match decode(r#"{"action":"B","xid":742,"timestamp":"2023-04-01 12:30:00.5+00"}"#) {
+            Ok(Some(Message::Begin { xid, timestamp })) => {
+                assert_eq!(xid, Some(742));
+                assert_eq!(
+                    timestamp,
+                    Utc.timestamp_opt(1_680_352_200, 500_000_000).single()
+                );
+            }
+            _ => panic!("expected a begin message"),
+        }
+        assert!(matches!(
+            decode(r#"{"action":"C","xid":742}"#),
+            Ok(Some(Message::Commit))
+        ));
+    }
+
+    #[test]
+    fn decodes_changes() {
+        let message = decode(
+            r#"{"action":"U","schema":"public","table":"users",
+                "columns":[{"name":"id","type":"integer","value":1},{"name":"active","type":"boolean","value":true}],
+                "identity":[{"name":"id","type":"integer","value":1}]}"#,
+        )
+        .unwrap();
+        let change = match message {
+            Some(Message::Changes(mut changes)) => changes.remove(0),
+            _ => panic!("expected changes"),
+        };
+        assert_eq!(change.operation, Operation::Update);
+        assert_eq!(change.schema, "public");
+        assert_eq!(change.table, "users");
+        assert_eq!(change.before, Some(btreemap! { "id" => 1 }));
+        assert_eq!(
+            change.after,
+            Some(btreemap! { "id" => 1, "active" => true })
+        );
+
+        let message = decode(
+            r#"{"action":"D","schema":"public","table":"users","identity":[{"name":"id","type":"integer","value":1}]}"#,
+        )
+        .unwrap();
+        let change = match message {
+            Some(Message::Changes(mut changes)) => changes.remove(0),
+            _ => panic!("expected changes"),
+        };
+        assert_eq!(change.operation, Operation::Delete);
+        assert_eq!(change.after, None);
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        assert!(matches!(decode("not json"), Err(DecodeError::Json { .. })));
+        assert!(matches!(
+            decode(r#"{"action":"X"}"#),
+            Err(DecodeError::UnexpectedTag { tag: 'X' })
+        ));
+    }
+}
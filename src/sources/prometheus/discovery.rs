@@ -0,0 +1,694 @@
+//! Discovery of the targets of the `prometheus_scrape` source.
+//!
+//! Targets are discovered as sets of labels, like in Prometheus: `__address__` is the `host:port`
+//! of the target, and the discovery mechanisms add `__meta_*` labels describing where the target
+//! was discovered. The targets are then filtered by the relabeling rules, and the labels that
+//! don't start with `__` are added as tags to the metrics scraped from the target.
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use futures::{stream, StreamExt};
+use http::Uri;
+use regex::Regex;
+use serde::Deserialize;
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use tokio_stream::wrappers::IntervalStream;
+use trust_dns_resolver::{error::ResolveError, proto::rr::RecordType, TokioAsyncResolver};
+use vector_common::shutdown::ShutdownSignal;
+use vector_config::configurable_component;
+
+use crate::{internal_events::PrometheusDiscoveryError, sources::util::http_client::build_url};
+
+const ADDRESS_LABEL: &str = "__address__";
+const SCHEME_LABEL: &str = "__scheme__";
+const METRICS_PATH_LABEL: &str = "__metrics_path__";
+
+type Labels = BTreeMap<String, String>;
+
+#[derive(Debug, Snafu)]
+pub(super) enum BuildError {
+    #[snafu(display("invalid relabel regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display(
+        "`port` must be set to discover targets with {:?} records",
+        record_type
+    ))]
+    MissingPort { record_type: DnsRecordType },
+    #[snafu(display("invalid file pattern {:?}: {}", pattern, source))]
+    InvalidPattern {
+        pattern: String,
+        source: glob::PatternError,
+    },
+    #[snafu(display("failed to create DNS resolver: {}", source))]
+    Resolver { source: ResolveError },
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum DiscoveryError {
+    #[snafu(display("DNS lookup of {:?} failed: {}", name, source))]
+    Lookup { name: String, source: ResolveError },
+    #[snafu(display("failed to read {:?}: {}", path, source))]
+    ReadFile { path: PathBuf, source: io::Error },
+    #[snafu(display("failed to parse {:?}: {}", path, source))]
+    ParseFile {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// Configuration for discovering the endpoints to scrape.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(super) struct DiscoveryConfig {
+    /// Discovers targets from DNS records, like the `dns_sd_configs` of Prometheus.
+    #[serde(default)]
+    dns: Vec<DnsDiscoveryConfig>,
+
+    /// Discovers targets from JSON files, in the format of the `file_sd_configs` of Prometheus.
+    #[serde(default)]
+    files: Vec<FileDiscoveryConfig>,
+
+    /// The scheme of the discovered endpoints, unless set by their `__scheme__` label.
+    #[serde(default = "default_scheme")]
+    #[configurable(metadata(docs::examples = "https"))]
+    scheme: String,
+
+    /// The path of the discovered endpoints, unless set by their `__metrics_path__` label.
+    #[serde(default = "default_metrics_path")]
+    #[configurable(metadata(docs::examples = "/federate"))]
+    path: String,
+
+    /// Rules filtering the discovered targets, applied in order.
+    ///
+    /// Only the targets kept by all the rules are scraped.
+    #[serde(default)]
+    relabel: Vec<RelabelConfig>,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            dns: Vec::new(),
+            files: Vec::new(),
+            scheme: default_scheme(),
+            path: default_metrics_path(),
+            relabel: Vec::new(),
+        }
+    }
+}
+
+fn default_scheme() -> String {
+    "http".to_owned()
+}
+
+fn default_metrics_path() -> String {
+    "/metrics".to_owned()
+}
+
+/// Configuration for discovering targets from DNS records.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(super) struct DnsDiscoveryConfig {
+    /// The names to query.
+    #[configurable(metadata(docs::examples = "_metrics._tcp.example.com"))]
+    names: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    record_type: DnsRecordType,
+
+    /// The port of the targets discovered with `A` and `AAAA` records.
+    #[configurable(metadata(docs::examples = 9100))]
+    port: Option<u16>,
+
+    /// The interval between queries, in seconds.
+    #[serde(default = "default_dns_refresh_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "refresh_interval_secs")]
+    refresh_interval: Duration,
+}
+
+const fn default_dns_refresh_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+/// The type of DNS records to query.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum DnsRecordType {
+    /// `SRV` records, providing both the host and the port of the targets.
+    #[default]
+    Srv,
+
+    /// `A` records, providing the IPv4 address of the targets.
+    A,
+
+    /// `AAAA` records, providing the IPv6 address of the targets.
+    Aaaa,
+}
+
+/// Configuration for discovering targets from files.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(super) struct FileDiscoveryConfig {
+    /// Glob patterns of the files to read the targets from.
+    ///
+    /// Each file contains a JSON array of groups of targets sharing labels, such as
+    /// `[{"targets": ["localhost:9100"], "labels": {"env": "prod"}}]`.
+    #[configurable(metadata(docs::examples = "/etc/vector/targets/*.json"))]
+    paths: Vec<String>,
+
+    /// The interval between reads of the files, in seconds.
+    #[serde(default = "default_file_refresh_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "refresh_interval_secs")]
+    refresh_interval: Duration,
+}
+
+const fn default_file_refresh_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// A rule filtering the discovered targets by their labels.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(super) struct RelabelConfig {
+    /// The labels whose values are joined with the `separator`, and matched against the `regex`.
+    ///
+    /// Missing labels have an empty value.
+    #[configurable(metadata(docs::examples = "__meta_dns_name"))]
+    source_labels: Vec<String>,
+
+    /// The separator of the joined values of the `source_labels`.
+    #[serde(default = "default_separator")]
+    separator: String,
+
+    /// The regular expression the joined values are matched against.
+    ///
+    /// The whole value must match.
+    #[serde(default = "default_regex")]
+    #[configurable(metadata(docs::examples = "node-exporter-.*"))]
+    regex: String,
+
+    #[configurable(derived)]
+    action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+/// What to do with the targets matching a relabeling rule.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum RelabelAction {
+    /// Only keep the targets matching the rule.
+    Keep,
+
+    /// Drop the targets matching the rule.
+    Drop,
+}
+
+struct Relabel {
+    source_labels: Vec<String>,
+    separator: String,
+    regex: Regex,
+    action: RelabelAction,
+}
+
+impl Relabel {
+    fn keeps(&self, labels: &Labels) -> bool {
+        let value = self
+            .source_labels
+            .iter()
+            .map(|label| labels.get(label).map(String::as_str).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(&self.separator);
+        let matches = self.regex.is_match(&value);
+        match self.action {
+            RelabelAction::Keep => matches,
+            RelabelAction::Drop => !matches,
+        }
+    }
+}
+
+enum Provider {
+    Dns {
+        resolver: TokioAsyncResolver,
+        names: Vec<String>,
+        record_type: DnsRecordType,
+        port: Option<u16>,
+    },
+    Files {
+        patterns: Vec<String>,
+    },
+}
+
+impl Provider {
+    const fn mechanism(&self) -> &'static str {
+        match self {
+            Self::Dns { .. } => "dns",
+            Self::Files { .. } => "file",
+        }
+    }
+
+    async fn discover(&self) -> Result<Vec<Labels>, DiscoveryError> {
+        match self {
+            Self::Dns {
+                resolver,
+                names,
+                record_type,
+                port,
+            } => {
+                let mut targets = Vec::new();
+                for name in names {
+                    targets.extend(lookup(resolver, name, *record_type, *port).await?);
+                }
+                Ok(targets)
+            }
+            Self::Files { patterns } => {
+                let mut targets = Vec::new();
+                for pattern in patterns {
+                    // The patterns were validated when building the source.
+                    for path in glob::glob(pattern).into_iter().flatten().flatten() {
+                        targets.extend(read_file(path).await?);
+                    }
+                }
+                Ok(targets)
+            }
+        }
+    }
+}
+
+async fn lookup(
+    resolver: &TokioAsyncResolver,
+    name: &str,
+    record_type: DnsRecordType,
+    port: Option<u16>,
+) -> Result<Vec<Labels>, DiscoveryError> {
+    let target = |address: String| {
+        Labels::from([
+            (ADDRESS_LABEL.to_owned(), address),
+            ("__meta_dns_name".to_owned(), name.to_owned()),
+        ])
+    };
+
+    match (record_type, port) {
+        (DnsRecordType::Srv, _) => {
+            let records = resolver
+                .srv_lookup(name)
+                .await
+                .context(LookupSnafu { name })?;
+            Ok(records
+                .iter()
+                .map(|record| {
+                    let host = record.target().to_utf8();
+                    let host = host.trim_end_matches('.');
+                    let mut labels = target(format!("{}:{}", host, record.port()));
+                    labels.insert("__meta_dns_srv_record_target".to_owned(), host.to_owned());
+                    labels.insert(
+                        "__meta_dns_srv_record_port".to_owned(),
+                        record.port().to_string(),
+                    );
+                    labels
+                })
+                .collect())
+        }
+        (record_type, Some(port)) => {
+            let record_type = match record_type {
+                DnsRecordType::Aaaa => RecordType::AAAA,
+                _ => RecordType::A,
+            };
+            let records = resolver
+                .lookup(name, record_type)
+                .await
+                .context(LookupSnafu { name })?;
+            Ok(records
+                .iter()
+                .filter_map(|record| record.to_ip_addr())
+                .map(|ip| target(SocketAddr::new(ip, port).to_string()))
+                .collect())
+        }
+        // The port is required for these records when building the source.
+        (_, None) => Ok(Vec::new()),
+    }
+}
+
+#[derive(Deserialize)]
+struct FileTargets {
+    #[serde(default)]
+    targets: Vec<String>,
+    #[serde(default)]
+    labels: Labels,
+}
+
+async fn read_file(path: PathBuf) -> Result<Vec<Labels>, DiscoveryError> {
+    let data = tokio::fs::read(&path)
+        .await
+        .with_context(|_| ReadFileSnafu { path: path.clone() })?;
+    let groups: Vec<FileTargets> =
+        serde_json::from_slice(&data).with_context(|_| ParseFileSnafu { path: path.clone() })?;
+
+    let filepath = path.to_string_lossy();
+    Ok(groups
+        .into_iter()
+        .flat_map(|group| {
+            let filepath = filepath.clone();
+            group.targets.into_iter().map(move |address| {
+                let mut labels = group.labels.clone();
+                labels.insert(ADDRESS_LABEL.to_owned(), address);
+                labels.insert("__meta_filepath".to_owned(), filepath.to_string());
+                labels
+            })
+        })
+        .collect())
+}
+
+/// The endpoints currently discovered, with the tags of their metrics.
+#[derive(Clone, Default)]
+pub(super) struct Targets(Arc<RwLock<HashMap<Uri, Labels>>>);
+
+impl Targets {
+    pub(super) fn urls(&self) -> Vec<Uri> {
+        self.0
+            .read()
+            .expect("poisoned lock")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    pub(super) fn tags(&self, url: &Uri) -> Option<Labels> {
+        self.0.read().expect("poisoned lock").get(url).cloned()
+    }
+
+    fn replace(&self, targets: HashMap<Uri, Labels>) {
+        *self.0.write().expect("poisoned lock") = targets;
+    }
+}
+
+pub(super) struct Discovery {
+    providers: Vec<(Provider, Duration)>,
+    relabel: Vec<Relabel>,
+    scheme: String,
+    path: String,
+    query: HashMap<String, Vec<String>>,
+}
+
+impl DiscoveryConfig {
+    pub(super) fn is_empty(&self) -> bool {
+        self.dns.is_empty() && self.files.is_empty()
+    }
+
+    pub(super) fn build(
+        &self,
+        query: &HashMap<String, Vec<String>>,
+    ) -> Result<Discovery, BuildError> {
+        let mut providers = Vec::new();
+        if !self.dns.is_empty() {
+            let resolver = TokioAsyncResolver::tokio_from_system_conf().context(ResolverSnafu)?;
+            for config in &self.dns {
+                if config.record_type != DnsRecordType::Srv && config.port.is_none() {
+                    return Err(BuildError::MissingPort {
+                        record_type: config.record_type,
+                    });
+                }
+                let provider = Provider::Dns {
+                    resolver: resolver.clone(),
+                    names: config.names.clone(),
+                    record_type: config.record_type,
+                    port: config.port,
+                };
+                providers.push((provider, config.refresh_interval));
+            }
+        }
+        for config in &self.files {
+            for pattern in &config.paths {
+                glob::Pattern::new(pattern).with_context(|_| InvalidPatternSnafu {
+                    pattern: pattern.clone(),
+                })?;
+            }
+            let provider = Provider::Files {
+                patterns: config.paths.clone(),
+            };
+            providers.push((provider, config.refresh_interval));
+        }
+
+        let relabel = self
+            .relabel
+            .iter()
+            .map(|config| {
+                Ok(Relabel {
+                    source_labels: config.source_labels.clone(),
+                    separator: config.separator.clone(),
+                    regex: Regex::new(&format!("^(?:{})$", config.regex)).with_context(|_| {
+                        InvalidRegexSnafu {
+                            regex: config.regex.clone(),
+                        }
+                    })?,
+                    action: config.action,
+                })
+            })
+            .collect::<Result<_, BuildError>>()?;
+
+        Ok(Discovery {
+            providers,
+            relabel,
+            scheme: self.scheme.clone(),
+            path: self.path.clone(),
+            query: query.clone(),
+        })
+    }
+}
+
+impl Discovery {
+    /// Refreshes the targets of each provider at its interval, until shutdown.
+    pub(super) async fn run(self, targets: Targets, shutdown: ShutdownSignal) {
+        let mut ticks = stream::select_all(self.providers.iter().enumerate().map(
+            |(index, (_, interval))| {
+                IntervalStream::new(tokio::time::interval(*interval)).map(move |_| index)
+            },
+        ))
+        .take_until(shutdown);
+
+        // The targets of a provider are kept when it fails, until it succeeds again.
+        let mut discovered = vec![Vec::new(); self.providers.len()];
+        while let Some(index) = ticks.next().await {
+            let provider = &self.providers[index].0;
+            match provider.discover().await {
+                Ok(labels) => {
+                    discovered[index] = labels;
+                    targets.replace(self.targets(discovered.iter().flatten()));
+                }
+                Err(error) => emit!(PrometheusDiscoveryError {
+                    error,
+                    mechanism: provider.mechanism(),
+                }),
+            }
+        }
+    }
+
+    /// Filters the discovered targets, returning their endpoints with their tags.
+    fn targets<'a>(&self, discovered: impl Iterator<Item = &'a Labels>) -> HashMap<Uri, Labels> {
+        let mut targets = HashMap::new();
+        for labels in discovered {
+            if !self.relabel.iter().all(|relabel| relabel.keeps(labels)) {
+                continue;
+            }
+            let address = match labels.get(ADDRESS_LABEL) {
+                Some(address) => address,
+                None => continue,
+            };
+            let scheme = labels.get(SCHEME_LABEL).unwrap_or(&self.scheme);
+            let path = labels.get(METRICS_PATH_LABEL).unwrap_or(&self.path);
+            let url = match format!("{}://{}{}", scheme, address, path).parse::<Uri>() {
+                Ok(url) => build_url(&url, &self.query),
+                Err(error) => {
+                    warn!(
+                        message = "Ignoring discovered target with an invalid address.",
+                        %address,
+                        %error,
+                    );
+                    continue;
+                }
+            };
+
+            let tags = labels
+                .iter()
+                .filter(|(name, _)| !name.starts_with("__"))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+            targets.insert(url, tags);
+        }
+        targets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    fn labels(labels: &[(&str, &str)]) -> Labels {
+        labels
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn discovery(config: &str) -> Discovery {
+        toml::from_str::<DiscoveryConfig>(config)
+            .unwrap()
+            .build(&HashMap::new())
+            .unwrap()
+    }
+
+    #[test]
+    fn builds_targets() {
+        let discovery = discovery(
+            r#"
+            path = "/federate"
+            "#,
+        );
+
+        let discovered = [
+            labels(&[("__address__", "10.0.0.1:9100"), ("env", "prod")]),
+            labels(&[
+                ("__address__", "10.0.0.2:9100"),
+                ("__scheme__", "https"),
+                ("__metrics_path__", "/metrics"),
+                ("__meta_filepath", "targets.json"),
+            ]),
+            labels(&[("env", "prod")]),
+        ];
+        let targets = discovery.targets(discovered.iter());
+
+        assert_eq!(targets.len(), 2);
+        assert_eq!(
+            targets[&"http://10.0.0.1:9100/federate".parse::<Uri>().unwrap()],
+            labels(&[("env", "prod")])
+        );
+        assert_eq!(
+            targets[&"https://10.0.0.2:9100/metrics".parse::<Uri>().unwrap()],
+            Labels::new()
+        );
+    }
+
+    #[test]
+    fn filters_targets() {
+        let discovery = discovery(
+            r#"
+            [[relabel]]
+            source_labels = ["env", "__meta_dns_name"]
+            regex = "prod;.*"
+            action = "keep"
+
+            [[relabel]]
+            source_labels = ["__address__"]
+            regex = "10\\.0\\.0\\.3:.*"
+            action = "drop"
+            "#,
+        );
+
+        let discovered = [
+            labels(&[
+                ("__address__", "10.0.0.1:9100"),
+                ("__meta_dns_name", "a"),
+                ("env", "prod"),
+            ]),
+            labels(&[("__address__", "10.0.0.2:9100"), ("env", "dev")]),
+            labels(&[("__address__", "10.0.0.3:9100"), ("env", "prod")]),
+            labels(&[("__address__", "10.0.0.4:9100"), ("env", "prod-eu")]),
+        ];
+        let targets = discovery.targets(discovered.iter());
+
+        let mut urls = targets.keys().map(ToString::to_string).collect::<Vec<_>>();
+        urls.sort();
+        assert_eq!(
+            urls,
+            vec![
+                "http://10.0.0.1:9100/metrics",
+                "http://10.0.0.4:9100/metrics"
+            ]
+        );
+    }
+
+    #[test]
+    fn requires_port_for_address_records() {
+        let config: DiscoveryConfig = toml::from_str(
+            r#"
+            [[dns]]
+            names = ["nodes.example.com"]
+            record_type = "a"
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config.build(&HashMap::new()),
+            Err(BuildError::MissingPort { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_target_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("targets.json");
+        std::fs::write(
+            &path,
+            r#"[
+                {"targets": ["10.0.0.1:9100", "10.0.0.2:9100"], "labels": {"env": "prod"}},
+                {"targets": ["10.0.0.3:9100"]}
+            ]"#,
+        )
+        .unwrap();
+
+        let targets = read_file(path.clone()).await.unwrap();
+        let filepath = path.to_string_lossy();
+        assert_eq!(
+            targets,
+            vec![
+                labels(&[
+                    ("__address__", "10.0.0.1:9100"),
+                    ("__meta_filepath", &filepath),
+                    ("env", "prod"),
+                ]),
+                labels(&[
+                    ("__address__", "10.0.0.2:9100"),
+                    ("__meta_filepath", &filepath),
+                    ("env", "prod"),
+                ]),
+                labels(&[
+                    ("__address__", "10.0.0.3:9100"),
+                    ("__meta_filepath", &filepath),
+                ]),
+            ]
+        );
+
+        std::fs::write(&path, "{").unwrap();
+        assert!(matches!(
+            read_file(path).await,
+            Err(DiscoveryError::ParseFile { .. })
+        ));
+    }
+}
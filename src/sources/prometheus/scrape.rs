@@ -1,19 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 
 use bytes::Bytes;
-use futures_util::FutureExt;
+use futures_util::{future, FutureExt};
 use http::{response::Parts, Uri};
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
 use vector_core::{config::LogNamespace, event::Event};
 
-use super::parser;
+use super::{
+    discovery::{DiscoveryConfig, Targets},
+    parser,
+};
 use crate::sources::util::http::HttpMethod;
 use crate::{
     config::{self, GenerateConfig, Output, SourceConfig, SourceContext},
-    http::Auth,
+    http::{Auth, HttpClient},
     internal_events::PrometheusParseError,
     sources::{
         self,
@@ -38,6 +41,8 @@ static NOT_FOUND_NO_PATH: &str = "No path is set on the endpoint and we got a 40
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
     BothEndpointsAndHosts,
+    #[snafu(display("Either `endpoints` or `discovery` must be set"))]
+    NoEndpoints,
 }
 
 /// Configuration for the `prometheus_scrape` source.
@@ -47,9 +52,16 @@ enum ConfigError {
 pub struct PrometheusScrapeConfig {
     /// Endpoints to scrape metrics from.
     #[configurable(metadata(docs::examples = "http://localhost:9090/metrics"))]
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
 
+    /// Discovers endpoints to scrape metrics from, in addition to the `endpoints`.
+    ///
+    /// The discovered endpoints are refreshed without reloading the configuration.
+    #[configurable(derived)]
+    #[serde(default)]
+    discovery: DiscoveryConfig,
+
     /// The interval between scrapes, in seconds.
     #[serde(default = "default_interval")]
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
@@ -109,6 +121,7 @@ impl GenerateConfig for PrometheusScrapeConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
+            discovery: DiscoveryConfig::default(),
             interval: default_interval(),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -124,6 +137,9 @@ impl GenerateConfig for PrometheusScrapeConfig {
 #[async_trait::async_trait]
 impl SourceConfig for PrometheusScrapeConfig {
     async fn build(&self, cx: SourceContext) -> Result<sources::Source> {
+        if self.endpoints.is_empty() && self.discovery.is_empty() {
+            return Err(ConfigError::NoEndpoints.into());
+        }
         let urls = self
             .endpoints
             .iter()
@@ -132,10 +148,18 @@ impl SourceConfig for PrometheusScrapeConfig {
             .collect::<std::result::Result<Vec<Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
 
+        if let Some(auth) = &self.auth {
+            auth.start(&HttpClient::new(tls.clone(), &cx.proxy)?)
+                .await?;
+        }
+        let discovery = self.discovery.build(&self.query)?;
+        let targets = Targets::default();
+
         let builder = PrometheusScrapeBuilder {
             honor_labels: self.honor_labels,
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
+            targets: targets.clone(),
         };
 
         let inputs = GenericHttpClientInputs {
@@ -146,10 +170,14 @@ impl SourceConfig for PrometheusScrapeConfig {
             auth: self.auth.clone(),
             tls,
             proxy: cx.proxy.clone(),
-            shutdown: cx.shutdown,
+            shutdown: cx.shutdown.clone(),
         };
 
-        Ok(call(inputs, builder, cx.out, HttpMethod::Get).boxed())
+        let discovery = discovery.run(targets, cx.shutdown);
+        let scrape = call(inputs, builder, cx.out, HttpMethod::Get);
+        Ok(future::join(scrape, discovery)
+            .map(|(result, ())| result)
+            .boxed())
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -187,11 +215,23 @@ struct PrometheusScrapeBuilder {
     honor_labels: bool,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
+    targets: Targets,
 }
 
 impl HttpClientBuilder for PrometheusScrapeBuilder {
     type Context = PrometheusScrapeContext;
 
+    /// Adds the discovered endpoints to the configured ones.
+    fn urls(&self, urls: &[Uri]) -> Vec<Uri> {
+        let mut urls = urls.to_vec();
+        for url in self.targets.urls() {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        urls
+    }
+
     /// Expands the context with the instance info and endpoint info for the current request.
     fn build(&self, url: &Uri) -> Self::Context {
         let instance_info = self.instance_tag.as_ref().map(|tag| {
@@ -218,6 +258,8 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
         PrometheusScrapeContext {
             instance_info,
             endpoint_info,
+            target_tags: self.targets.tags(url).unwrap_or_default(),
+            honor_labels: self.honor_labels,
         }
     }
 }
@@ -226,6 +268,9 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
 struct PrometheusScrapeContext {
     instance_info: Option<InstanceInfo>,
     endpoint_info: Option<EndpointInfo>,
+    /// The tags of the discovered endpoint.
+    target_tags: BTreeMap<String, String>,
+    honor_labels: bool,
 }
 
 impl HttpClientContext for PrometheusScrapeContext {
@@ -266,6 +311,18 @@ impl HttpClientContext for PrometheusScrapeContext {
                     }
                 }
             }
+            for (tag, value) in &self.target_tags {
+                match (self.honor_labels, metric.tag_value(tag)) {
+                    (false, Some(old_value)) => {
+                        metric.replace_tag(format!("exported_{}", tag), old_value);
+                        metric.replace_tag(tag.clone(), value.clone());
+                    }
+                    (true, Some(_)) => {}
+                    (_, None) => {
+                        metric.replace_tag(tag.clone(), value.clone());
+                    }
+                }
+            }
         }
     }
 
@@ -345,6 +402,7 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            discovery: Default::default(),
             interval: Duration::from_secs(1),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -363,6 +421,63 @@ mod test {
         assert!(!events.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_prometheus_discovered_endpoints() {
+        let in_addr = next_addr();
+
+        let dummy_endpoint = warp::path!("metrics").map(|| {
+            r#"
+                    promhttp_metric_handler_requests_total{code="200"} 100 1612411516789
+                    "#
+        });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+        wait_for_tcp(in_addr).await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("targets.json");
+        std::fs::write(
+            &path,
+            serde_json::json!([
+                {"targets": [in_addr.to_string()], "labels": {"env": "prod"}},
+                {"targets": ["127.0.0.1:1"], "labels": {"env": "dev"}},
+            ])
+            .to_string(),
+        )
+        .unwrap();
+
+        let config: PrometheusScrapeConfig = toml::from_str(&format!(
+            r#"
+            scrape_interval_secs = 1
+            instance_tag = "instance"
+
+            [[discovery.files]]
+            paths = ["{}"]
+
+            [[discovery.relabel]]
+            source_labels = ["env"]
+            regex = "prod"
+            action = "keep"
+            "#,
+            dir.path().join("*.json").display()
+        ))
+        .unwrap();
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+        assert!(!events.is_empty());
+
+        for event in events {
+            let metric = event.into_metric();
+            assert_eq!(metric.tag_value("env"), Some(String::from("prod")));
+            assert_eq!(metric.tag_value("instance"), Some(in_addr.to_string()));
+        }
+    }
+
     #[tokio::test]
     async fn test_prometheus_honor_labels() {
         let in_addr = next_addr();
@@ -378,6 +493,7 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            discovery: Default::default(),
             interval: Duration::from_secs(1),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -429,6 +545,7 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            discovery: Default::default(),
             interval: Duration::from_secs(1),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -494,6 +611,7 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            discovery: Default::default(),
             interval: Duration::from_secs(1),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -549,6 +667,7 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics?key1=val1", in_addr)],
+            discovery: Default::default(),
             interval: Duration::from_secs(1),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -658,6 +777,7 @@ mod test {
             "in",
             PrometheusScrapeConfig {
                 endpoints: vec![format!("http://{}", in_addr)],
+                discovery: Default::default(),
                 instance_tag: None,
                 endpoint_tag: None,
                 honor_labels: false,
@@ -747,6 +867,7 @@ mod integration_tests {
     async fn scrapes_metrics() {
         let config = PrometheusScrapeConfig {
             endpoints: vec!["http://prometheus:9090/metrics".into()],
+            discovery: Default::default(),
             interval: Duration::from_secs(1),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
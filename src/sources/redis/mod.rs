@@ -19,7 +19,7 @@ use vector_core::{
 };
 
 use crate::{
-    codecs::{Decoder, DecodingConfig},
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
     config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext},
     event::Event,
     internal_events::{EventsReceived, StreamClosedError},
@@ -133,6 +133,10 @@ pub struct RedisSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -168,7 +172,9 @@ impl SourceConfig for RedisSourceConfig {
         let client = redis::Client::open(self.url.as_str()).context(ClientSnafu {})?;
         let connection_info = ConnectionInfo::from(client.get_connection_info());
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
 
         let bytes_received = register!(BytesReceived::from(Protocol::from(
             connection_info.protocol
@@ -215,7 +221,11 @@ impl SourceConfig for RedisSourceConfig {
             )
             .with_standard_vector_source_metadata();
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -344,6 +354,7 @@ mod integration_test {
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -376,6 +387,7 @@ mod integration_test {
             redis_key: Some(OptionalValuePath::from(owned_value_path!("remapped_key"))),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             log_namespace: Some(true),
         };
 
@@ -417,6 +429,7 @@ mod integration_test {
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -441,6 +454,7 @@ mod integration_test {
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: Default::default(),
             log_namespace: Some(false),
         };
 
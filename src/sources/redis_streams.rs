@@ -0,0 +1,755 @@
+//! The `redis_streams` source. See [RedisStreamsSourceConfig].
+//!
+//! Consumes a Redis stream as part of a consumer group, acknowledging entries once their events
+//! are delivered, and claiming the entries left pending by consumers that stopped.
+use std::{io::Cursor, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::{
+    decoding::{DeserializerConfig, FramingConfig},
+    StreamDecodingError,
+};
+use futures::{FutureExt, StreamExt};
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamPendingCountReply, StreamReadOptions, StreamReadReply},
+    AsyncCommands, ErrorKind, RedisError, RedisResult,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::time::Instant;
+use tokio_util::codec::FramedRead;
+use tracing::Instrument;
+use value::Kind;
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+    },
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
+    config::{
+        log_schema, GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        EventsReceived, RedisReceiveEventError, RedisStreamAckError, StreamClosedError,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// How long a read waits for new entries, which bounds how late pending entries are claimed.
+const READ_BLOCK_MS: usize = 1000;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to build redis client: {}", source))]
+    Client { source: RedisError },
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group {:?}: {}", group, source))]
+    CreateGroup { group: String, source: RedisError },
+}
+
+/// Configuration for the `redis_streams` source.
+#[configurable_component(source("redis_streams"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct RedisStreamsSourceConfig {
+    /// The Redis URL to connect to.
+    ///
+    /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    url: String,
+
+    /// The key of the stream to read entries from.
+    #[configurable(metadata(docs::examples = "vector"))]
+    stream: String,
+
+    /// The consumer group to read the stream as.
+    ///
+    /// The group is created if it doesn't exist yet.
+    #[configurable(metadata(docs::examples = "vector"))]
+    group: String,
+
+    /// The name of the consumer within the group.
+    ///
+    /// Each Vector instance reading the stream in the same group must use a different name. By
+    /// default, the hostname is used.
+    #[configurable(metadata(docs::examples = "vector-0"))]
+    consumer: Option<String>,
+
+    /// The ID of the entry after which the group starts reading, when it's created.
+    ///
+    /// By default, only the entries added after the group is created are read. Use `0` to read the
+    /// whole stream.
+    #[serde(default = "default_start_id")]
+    #[derivative(Default(value = "default_start_id()"))]
+    #[configurable(metadata(docs::examples = "0"))]
+    start_id: String,
+
+    /// The field of the entries to decode into events.
+    ///
+    /// Entries without this field are acknowledged and skipped.
+    #[serde(default = "default_field")]
+    #[derivative(Default(value = "default_field()"))]
+    field: String,
+
+    /// The maximum number of entries read at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    #[configurable(metadata(docs::type_unit = "entries"))]
+    batch_size: usize,
+
+    /// The time after which the entries delivered to a consumer, but not acknowledged, are claimed
+    /// by this consumer.
+    ///
+    /// This is how pending entries are recovered from consumers that stopped, and retried when
+    /// their events couldn't be delivered. It must be longer than the time events take to be
+    /// delivered, or entries are read again while their events are still being delivered.
+    #[serde(default = "default_claim_min_idle_secs")]
+    #[derivative(Default(value = "default_claim_min_idle_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    claim_min_idle_secs: u64,
+
+    /// The interval between checks for pending entries to claim.
+    #[serde(default = "default_claim_interval_secs")]
+    #[derivative(Default(value = "default_claim_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    claim_interval_secs: u64,
+
+    /// Sets the name of the log field to use to add the key of the stream to each event.
+    ///
+    /// By default, this is not set and the field is not automatically added.
+    #[configurable(metadata(docs::examples = "redis_key"))]
+    redis_key: Option<OptionalValuePath>,
+
+    /// Sets the name of the log field to use to add the ID of the entry to each event.
+    ///
+    /// By default, this is not set and the field is not automatically added.
+    #[configurable(metadata(docs::examples = "redis_id"))]
+    id_key: Option<OptionalValuePath>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_start_id() -> String {
+    "$".to_owned()
+}
+
+fn default_field() -> String {
+    "message".to_owned()
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+const fn default_claim_min_idle_secs() -> u64 {
+    60
+}
+
+const fn default_claim_interval_secs() -> u64 {
+    30
+}
+
+impl GenerateConfig for RedisStreamsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            stream = "vector"
+            group = "vector"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for RedisStreamsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+
+        if self.stream.is_empty() {
+            return Err("`stream` cannot be empty.".into());
+        }
+        if self.group.is_empty() {
+            return Err("`group` cannot be empty.".into());
+        }
+        let consumer = match &self.consumer {
+            Some(consumer) => consumer.clone(),
+            None => crate::get_hostname()?,
+        };
+
+        let client = redis::Client::open(self.url.as_str()).context(ClientSnafu {})?;
+        let protocol = match client.get_connection_info().addr {
+            redis::ConnectionAddr::Unix(_) => "uds",
+            _ => "tcp",
+        };
+        // Reads block their connection, so acknowledgements and claims use a separate one.
+        let read_conn = client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+        let mut conn = client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+        create_group(&mut conn, &self.stream, &self.group, &self.start_id)
+            .await
+            .context(CreateGroupSnafu {
+                group: self.group.clone(),
+            })?;
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .build()?;
+
+        let consumer = StreamConsumer {
+            read_conn,
+            conn,
+            stream: self.stream.clone(),
+            group: self.group.clone(),
+            consumer,
+            field: self.field.clone(),
+            batch_size: self.batch_size,
+            claim_min_idle: Duration::from_secs(self.claim_min_idle_secs),
+            redis_key: self.redis_key.clone().and_then(|k| k.path),
+            id_key: self.id_key.clone().and_then(|k| k.path),
+            decoder,
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::from(protocol))),
+            events_received: register!(EventsReceived),
+            out: cx.out,
+        };
+
+        Ok(Box::pin(consumer.run(
+            Duration::from_secs(self.claim_interval_secs),
+            acknowledgements,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let redis_key_path = self
+            .redis_key
+            .clone()
+            .and_then(|k| k.path)
+            .map(LegacyKey::InsertIfEmpty);
+        let id_key_path = self
+            .id_key
+            .clone()
+            .and_then(|k| k.path)
+            .map(LegacyKey::InsertIfEmpty);
+
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                Self::NAME,
+                redis_key_path,
+                &owned_value_path!("key"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                id_key_path,
+                &owned_value_path!("id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_standard_vector_source_metadata();
+
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Creates the consumer group, unless it already exists.
+async fn create_group(
+    conn: &mut ConnectionManager,
+    stream: &str,
+    group: &str,
+    start_id: &str,
+) -> RedisResult<()> {
+    match conn
+        .xgroup_create_mkstream::<_, _, _, ()>(stream, group, start_id)
+        .await
+    {
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        result => result,
+    }
+}
+
+/// The IDs of the entries whose events were sent in a batch.
+#[derive(Debug)]
+struct FinalizerEntry {
+    ids: Vec<String>,
+}
+
+struct StreamConsumer {
+    read_conn: ConnectionManager,
+    conn: ConnectionManager,
+    stream: String,
+    group: String,
+    consumer: String,
+    field: String,
+    batch_size: usize,
+    claim_min_idle: Duration,
+    redis_key: Option<OwnedValuePath>,
+    id_key: Option<OwnedValuePath>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+}
+
+impl StreamConsumer {
+    async fn run(
+        mut self,
+        claim_interval: Duration,
+        acknowledgements: bool,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let (finalizer, mut ack_stream) =
+            UnorderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
+
+        // Acknowledgements are handled on their own, as cancelling a read would leave the entries
+        // it was delivered pending until they're claimed.
+        let mut conn = self.conn.clone();
+        let stream = self.stream.clone();
+        let group = self.group.clone();
+        tokio::spawn(
+            async move {
+                while let Some((status, entry)) = ack_stream.next().await {
+                    // Entries whose events weren't delivered are left pending, to be claimed again.
+                    if status == BatchStatus::Delivered {
+                        ack(&mut conn, &stream, &group, &entry.ids).await;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+
+        let mut shutdown = shutdown.fuse();
+        let mut next_claim = Instant::now();
+        // Entries that were delivered to this consumer before it restarted are read first.
+        let mut last_id = "0";
+        let mut retry: u32 = 0;
+        loop {
+            // Reads block for a bounded time, so claims are checked for between them.
+            if Instant::now() >= next_claim {
+                next_claim = Instant::now() + claim_interval;
+                match self.claim().await {
+                    Ok(entries) => self.handle_entries(entries, finalizer.as_ref()).await?,
+                    Err(error) => emit!(RedisReceiveEventError::from(error)),
+                }
+                continue;
+            }
+
+            let result = tokio::select! {
+                _ = &mut shutdown => break,
+                result = self.read(last_id) => result,
+            };
+
+            match result {
+                Ok(entries) if entries.is_empty() => {
+                    retry = 0;
+                    // Once this consumer's pending entries are read, only new entries are.
+                    last_id = ">";
+                }
+                Ok(entries) => {
+                    retry = 0;
+                    self.handle_entries(entries, finalizer.as_ref()).await?;
+                }
+                Err(error) => {
+                    let kind = error.kind();
+                    emit!(RedisReceiveEventError::from(error));
+                    if kind == ErrorKind::IoError {
+                        retry += 1;
+                        backoff_exponential(retry).await;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn read(&mut self, last_id: &str) -> RedisResult<Vec<StreamId>> {
+        let options = StreamReadOptions::default()
+            .group(&self.group, &self.consumer)
+            .count(self.batch_size)
+            .block(READ_BLOCK_MS);
+        let reply: StreamReadReply = self
+            .read_conn
+            .xread_options(&[&self.stream], &[last_id], &options)
+            .await?;
+        Ok(reply
+            .keys
+            .into_iter()
+            .flat_map(|stream_key| stream_key.ids)
+            .collect())
+    }
+
+    /// Claims the entries that have been pending for longer than `claim_min_idle`.
+    async fn claim(&mut self) -> RedisResult<Vec<StreamId>> {
+        let min_idle_ms = self.claim_min_idle.as_millis() as usize;
+        let pending: StreamPendingCountReply = self
+            .conn
+            .xpending_count(&self.stream, &self.group, "-", "+", self.batch_size)
+            .await?;
+        let ids = pending
+            .ids
+            .into_iter()
+            .filter(|pending| pending.last_delivered_ms >= min_idle_ms)
+            .map(|pending| pending.id)
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // The entries that were claimed by another consumer in the meantime are left out.
+        let reply: redis::streams::StreamClaimReply = self
+            .conn
+            .xclaim(
+                &self.stream,
+                &self.group,
+                &self.consumer,
+                min_idle_ms,
+                ids.as_slice(),
+            )
+            .await?;
+        debug!(
+            message = "Claimed pending entries.",
+            count = reply.ids.len()
+        );
+        Ok(reply.ids)
+    }
+
+    async fn handle_entries(
+        &mut self,
+        entries: Vec<StreamId>,
+        finalizer: Option<&UnorderedFinalizer<FinalizerEntry>>,
+    ) -> Result<(), ()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let mut ids = Vec::with_capacity(entries.len());
+        let mut events = Vec::new();
+        for entry in entries {
+            match entry.map.get(&self.field).map(payload) {
+                Some(Ok(payload)) => {
+                    self.bytes_received.emit(ByteSize(payload.len()));
+                    self.decode(payload, &entry.id, now, &mut events).await;
+                }
+                Some(Err(error)) => {
+                    emit!(RedisReceiveEventError::from(error));
+                }
+                None => {
+                    warn!(
+                        message = "Stream entry has no field to decode, skipping it.",
+                        id = %entry.id,
+                        field = %self.field,
+                        internal_log_rate_limit = true,
+                    );
+                }
+            }
+            ids.push(entry.id);
+        }
+
+        let count = events.len();
+        match finalizer {
+            Some(finalizer) => {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                let events = events
+                    .into_iter()
+                    .map(|event| event.with_batch_notifier(&batch));
+                if let Err(error) = self.out.send_batch(events).await {
+                    emit!(StreamClosedError { error, count });
+                    return Err(());
+                }
+                finalizer.add(FinalizerEntry { ids }, receiver);
+            }
+            None => {
+                if let Err(error) = self.out.send_batch(events).await {
+                    emit!(StreamClosedError { error, count });
+                    return Err(());
+                }
+                ack(&mut self.conn, &self.stream, &self.group, &ids).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn decode(
+        &self,
+        payload: Bytes,
+        id: &str,
+        now: chrono::DateTime<Utc>,
+        decoded: &mut Vec<Event>,
+    ) {
+        let mut stream = FramedRead::new(Cursor::new(payload), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    self.events_received.emit(CountByteSize(
+                        events.len(),
+                        events.estimated_json_encoded_size_of(),
+                    ));
+
+                    decoded.extend(events.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                RedisStreamsSourceConfig::NAME,
+                                now,
+                            );
+                            self.log_namespace.insert_source_metadata(
+                                RedisStreamsSourceConfig::NAME,
+                                log,
+                                self.redis_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                                path!("key"),
+                                self.stream.as_str(),
+                            );
+                            self.log_namespace.insert_source_metadata(
+                                RedisStreamsSourceConfig::NAME,
+                                log,
+                                self.id_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                                path!("id"),
+                                id,
+                            );
+                        }
+                        event
+                    }));
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn payload(value: &redis::Value) -> RedisResult<Bytes> {
+    redis::from_redis_value::<Vec<u8>>(value).map(Bytes::from)
+}
+
+async fn ack(conn: &mut ConnectionManager, stream: &str, group: &str, ids: &[String]) {
+    if ids.is_empty() {
+        return;
+    }
+    if let Err(error) = conn.xack::<_, _, _, ()>(stream, group, ids).await {
+        emit!(RedisStreamAckError { error });
+    }
+}
+
+async fn backoff_exponential(exp: u32) {
+    let ms = if exp <= 4 { 2_u64.pow(exp + 5) } else { 1000 };
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisStreamsSourceConfig>();
+    }
+
+    #[test]
+    fn defaults() {
+        let config: RedisStreamsSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            stream = "vector"
+            group = "vector"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.start_id, "$");
+        assert_eq!(config.field, "message");
+        assert_eq!(config.batch_size, 100);
+        assert_eq!(config.claim_min_idle_secs, 60);
+        assert!(config.consumer.is_none());
+    }
+}
+
+#[cfg(all(test, feature = "redis-integration-tests"))]
+mod integration_test {
+    use redis::streams::StreamPendingReply;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        test_util::{
+            collect_n,
+            components::{run_and_assert_source_compliance_n, SOURCE_TAGS},
+            random_string,
+        },
+        SourceSender,
+    };
+
+    const REDIS_SERVER: &str = "redis://redis:6379/0";
+
+    fn config(stream: &str, group: &str) -> RedisStreamsSourceConfig {
+        RedisStreamsSourceConfig {
+            url: REDIS_SERVER.to_owned(),
+            stream: stream.to_owned(),
+            group: group.to_owned(),
+            consumer: Some("vector".to_owned()),
+            start_id: "0".to_owned(),
+            redis_key: Some(OptionalValuePath::from(owned_value_path!("redis_key"))),
+            id_key: Some(OptionalValuePath::from(owned_value_path!("redis_id"))),
+            log_namespace: Some(false),
+            ..Default::default()
+        }
+    }
+
+    async fn add_entries(conn: &mut ConnectionManager, stream: &str, count: usize) -> Vec<String> {
+        let mut ids = Vec::new();
+        for i in 0..count {
+            let id: String = conn
+                .xadd(stream, "*", &[("message", i.to_string())])
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+        ids
+    }
+
+    async fn pending_count(conn: &mut ConnectionManager, stream: &str, group: &str) -> usize {
+        match conn.xpending(stream, group).await.unwrap() {
+            StreamPendingReply::Data(data) => data.count,
+            StreamPendingReply::Empty => 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_streams_source_consume_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let stream = format!("test-stream-{}", random_string(10));
+        let ids = add_entries(&mut conn, &stream, 3).await;
+
+        let events =
+            run_and_assert_source_compliance_n(config(&stream, "group"), 3, &SOURCE_TAGS).await;
+
+        for (i, event) in events.iter().enumerate() {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], i.to_string().into());
+            assert_eq!(log["redis_key"], stream.clone().into());
+            assert_eq!(log["redis_id"], ids[i].clone().into());
+            assert_eq!(
+                log[log_schema().source_type_key()],
+                RedisStreamsSourceConfig::NAME.into()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_streams_source_acknowledges_delivered_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let stream = format!("test-stream-{}", random_string(10));
+        add_entries(&mut conn, &stream, 2).await;
+
+        let (tx, rx) = SourceSender::new_test_finalize(vector_core::event::EventStatus::Delivered);
+        let source = config(&stream, "group")
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 2).await;
+        assert_eq!(events.len(), 2);
+
+        // Give the acknowledgements time to be sent.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(pending_count(&mut conn, &stream, "group").await, 0);
+    }
+
+    #[tokio::test]
+    async fn redis_streams_source_claims_pending_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let stream = format!("test-stream-{}", random_string(10));
+        create_group(&mut conn, &stream, "group", "0")
+            .await
+            .unwrap();
+        add_entries(&mut conn, &stream, 2).await;
+
+        // Another consumer reads the entries, but never acknowledges them.
+        let options = StreamReadOptions::default().group("group", "stopped");
+        let _: StreamReadReply = conn
+            .xread_options(&[&stream], &[">"], &options)
+            .await
+            .unwrap();
+        assert_eq!(pending_count(&mut conn, &stream, "group").await, 2);
+
+        let mut config = config(&stream, "group");
+        config.claim_min_idle_secs = 0;
+        config.claim_interval_secs = 1;
+        let events = run_and_assert_source_compliance_n(config, 2, &SOURCE_TAGS).await;
+
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "0".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "1".into());
+    }
+}
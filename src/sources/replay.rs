@@ -0,0 +1,313 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use codecs::{
+    decoding::{DeserializerConfig, FramingConfig},
+    StreamDecodingError,
+};
+use futures::StreamExt;
+use tokio::time::{self, Instant};
+use tokio_util::codec::FramedRead;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+
+use crate::{
+    codecs::{DecodeErrorPolicy, Decoder, DecodingConfig},
+    config::{GenerateConfig, Output, SourceConfig, SourceContext},
+    internal_events::{EventsReceived, RecordingReadError, StreamClosedError},
+    recording::RecordingReader,
+    serde::default_decoding,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `replay` source.
+#[configurable_component(source("replay"))]
+#[derive(Clone, Debug)]
+pub struct ReplayConfig {
+    /// The recording to replay, as written by the `record` option of a source.
+    #[configurable(metadata(docs::examples = "/var/lib/vector/in.recording"))]
+    pub path: PathBuf,
+
+    /// How many times faster than they were recorded the frames are replayed.
+    ///
+    /// The frames are replayed at the pace they were recorded at by default. To replay them as
+    /// quickly as possible, set `speed` to `0`.
+    #[serde(default = "default_speed")]
+    #[configurable(metadata(docs::examples = 10.0))]
+    pub speed: f64,
+
+    /// The decoding of the recorded frames.
+    ///
+    /// The `vector replay` command sets it to the decoding of the recorded source.
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    pub log_namespace: Option<bool>,
+}
+
+const fn default_speed() -> f64 {
+    1.0
+}
+
+impl ReplayConfig {
+    /// Creates the configuration replaying the recording at `path`, decoded with `decoding`.
+    pub const fn new(path: PathBuf, speed: f64, decoding: DeserializerConfig) -> Self {
+        Self {
+            path,
+            speed,
+            decoding,
+            on_decode_error: DecodeErrorPolicy::Drop,
+            log_namespace: None,
+        }
+    }
+}
+
+impl GenerateConfig for ReplayConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::new(
+            PathBuf::from("/var/lib/vector/in.recording"),
+            default_speed(),
+            default_decoding(),
+        ))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for ReplayConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if !(self.speed >= 0.0 && self.speed.is_finite()) {
+            return Err("`speed` must be a positive number, or zero".into());
+        }
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        // The frames were recorded after being framed, so each is decoded as a whole.
+        let decoder =
+            DecodingConfig::new(FramingConfig::Bytes, self.decoding.clone(), log_namespace)
+                .with_error_policy(self.on_decode_error)
+                .with_pass_through(cx.pass_through)
+                .build()?;
+        Ok(Box::pin(replay_source(
+            self.path.clone(),
+            self.speed,
+            decoder,
+            cx.shutdown,
+            cx.out,
+            log_namespace,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata();
+
+        let mut outputs =
+            vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error.output(log_namespace));
+        outputs
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+
+    fn pass_through_decoding(&self) -> Option<DeserializerConfig> {
+        Some(self.decoding.clone())
+    }
+}
+
+async fn replay_source(
+    path: PathBuf,
+    speed: f64,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let mut reader = RecordingReader::open(&path)
+        .await
+        .map_err(|error| emit!(RecordingReadError { error, path: &path }))?;
+
+    let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+    let events_received = register!(EventsReceived);
+
+    let started_at = Instant::now();
+    let mut first_timestamp: Option<DateTime<Utc>> = None;
+    loop {
+        let next = tokio::select! {
+            _ = &mut shutdown => break,
+            next = reader.next_frame() => next,
+        };
+        let (timestamp, frame) = match next {
+            Ok(Some(record)) => record,
+            Ok(None) => break,
+            Err(error) => {
+                emit!(RecordingReadError { error, path: &path });
+                return Err(());
+            }
+        };
+
+        // Replay the frame as long after the first one as it was recorded, divided by the speed.
+        if speed > 0.0 {
+            let first_timestamp = *first_timestamp.get_or_insert(timestamp);
+            let offset = (timestamp - first_timestamp)
+                .to_std()
+                .unwrap_or_default()
+                .div_f64(speed);
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = time::sleep_until(started_at + offset) => {}
+            }
+        }
+        bytes_received.emit(ByteSize(frame.len()));
+
+        let mut stream = FramedRead::new(frame.as_ref(), decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    let count = events.len();
+                    let byte_size = events.estimated_json_encoded_size_of();
+                    events_received.emit(CountByteSize(count, byte_size));
+
+                    let events = events.into_iter().map(|mut event| {
+                        // The frames relayed as they are have no metadata.
+                        if !decoder.is_pass_through() {
+                            log_namespace.insert_standard_vector_source_metadata(
+                                event.as_mut_log(),
+                                ReplayConfig::NAME,
+                                timestamp,
+                            );
+                        }
+                        event
+                    });
+                    out.send_batch(events).await.map_err(|error| {
+                        emit!(StreamClosedError { error, count });
+                    })?;
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::Stream;
+
+    use super::*;
+    use crate::{
+        config::{log_schema, ComponentKey},
+        event::{Event, Value},
+        recording::{RecordConfig, Recorder},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ReplayConfig>();
+    }
+
+    fn record(path: PathBuf, frames: &[&str], interval: Duration) {
+        let recorder = Recorder::open(
+            &ComponentKey::from("in"),
+            "socket",
+            &RecordConfig {
+                path,
+                max_bytes: None,
+            },
+        )
+        .unwrap();
+        for (index, frame) in frames.iter().enumerate() {
+            if index > 0 {
+                std::thread::sleep(interval);
+            }
+            recorder.record(frame.as_bytes());
+        }
+    }
+
+    async fn replay(path: PathBuf, speed: f64) -> impl Stream<Item = Event> {
+        let (tx, rx) = SourceSender::new_test();
+        let decoder = DecodingConfig::new(
+            FramingConfig::Bytes,
+            default_decoding(),
+            LogNamespace::Legacy,
+        )
+        .build()
+        .unwrap();
+        replay_source(
+            path,
+            speed,
+            decoder,
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        )
+        .await
+        .unwrap();
+        rx
+    }
+
+    #[tokio::test]
+    async fn replays_frames_with_their_timestamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("in.recording");
+        record(path.clone(), &["foo", "bar"], Duration::from_millis(10));
+
+        let events = replay(path, 0.0).await.collect::<Vec<_>>().await;
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["foo", "bar"]);
+
+        let timestamps = events
+            .iter()
+            .map(|event| {
+                *event
+                    .as_log()
+                    .get_timestamp()
+                    .and_then(Value::as_timestamp)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(timestamps[1] - timestamps[0] >= chrono::Duration::milliseconds(10));
+    }
+
+    #[tokio::test]
+    async fn replays_at_speed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("in.recording");
+        record(path.clone(), &["foo", "bar"], Duration::from_millis(200));
+
+        let started_at = std::time::Instant::now();
+        let events = replay(path, 2.0).await.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 2);
+        assert!(started_at.elapsed() >= Duration::from_millis(100));
+    }
+}
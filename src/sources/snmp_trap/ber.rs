@@ -0,0 +1,305 @@
+//! The subset of the Basic Encoding Rules of ASN.1 used by SNMP messages.
+use std::ops::Range;
+
+use snafu::Snafu;
+
+pub(super) const INTEGER: u8 = 0x02;
+pub(super) const OCTET_STRING: u8 = 0x04;
+pub(super) const NULL: u8 = 0x05;
+pub(super) const OBJECT_IDENTIFIER: u8 = 0x06;
+pub(super) const SEQUENCE: u8 = 0x30;
+pub(super) const IP_ADDRESS: u8 = 0x40;
+pub(super) const COUNTER32: u8 = 0x41;
+pub(super) const GAUGE32: u8 = 0x42;
+pub(super) const TIMETICKS: u8 = 0x43;
+pub(super) const OPAQUE: u8 = 0x44;
+pub(super) const COUNTER64: u8 = 0x46;
+pub(super) const NO_SUCH_OBJECT: u8 = 0x80;
+pub(super) const NO_SUCH_INSTANCE: u8 = 0x81;
+pub(super) const END_OF_MIB_VIEW: u8 = 0x82;
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub(super) enum DecodeError {
+    #[snafu(display("The message is truncated."))]
+    Truncated,
+    #[snafu(display("Expected tag {:#04x}, found {:#04x}.", expected, found))]
+    UnexpectedTag { expected: u8, found: u8 },
+    #[snafu(display("Unsupported length encoding."))]
+    UnsupportedLength,
+    #[snafu(display("Integer out of range."))]
+    IntegerOverflow,
+    #[snafu(display("Invalid object identifier."))]
+    InvalidObjectIdentifier,
+}
+
+/// Reads the values encoded in a part of a message.
+///
+/// The ranges of the values are those within the whole message, so that the parts covered by the
+/// authentication of SNMPv3 messages can be located.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Reader<'a> {
+    message: &'a [u8],
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(message: &'a [u8]) -> Self {
+        Self {
+            message,
+            position: 0,
+            end: message.len(),
+        }
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.position >= self.end
+    }
+
+    /// Returns the position of the next value in the message.
+    pub(super) const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the tag of the next value without reading it.
+    pub(super) fn peek_tag(&self) -> Result<u8, DecodeError> {
+        self.message
+            .get(self.position)
+            .filter(|_| !self.is_empty())
+            .copied()
+            .ok_or(DecodeError::Truncated)
+    }
+
+    /// Reads the next value, returning its tag and the range of its contents.
+    pub(super) fn read(&mut self) -> Result<(u8, Range<usize>), DecodeError> {
+        let tag = self.peek_tag()?;
+        let mut position = self.position + 1;
+
+        let first = *self.byte(position)?;
+        position += 1;
+        let length = if first < 0x80 {
+            first as usize
+        } else {
+            let octets = (first & 0x7f) as usize;
+            if octets == 0 || octets > 4 {
+                return Err(DecodeError::UnsupportedLength);
+            }
+            let mut length = 0;
+            for _ in 0..octets {
+                length = (length << 8) | *self.byte(position)? as usize;
+                position += 1;
+            }
+            length
+        };
+
+        let contents = position..position + length;
+        if contents.end > self.end {
+            return Err(DecodeError::Truncated);
+        }
+        self.position = contents.end;
+        Ok((tag, contents))
+    }
+
+    /// Reads the next value, which must have the given tag, returning the range of its contents.
+    pub(super) fn expect(&mut self, expected: u8) -> Result<Range<usize>, DecodeError> {
+        let (found, contents) = self.read()?;
+        if found == expected {
+            Ok(contents)
+        } else {
+            Err(DecodeError::UnexpectedTag { expected, found })
+        }
+    }
+
+    /// Reads a constructed value with the given tag, returning a reader of its contents.
+    pub(super) fn constructed(&mut self, tag: u8) -> Result<Reader<'a>, DecodeError> {
+        let contents = self.expect(tag)?;
+        Ok(self.within(contents))
+    }
+
+    /// Returns a reader of the given range of the message.
+    pub(super) const fn within(&self, contents: Range<usize>) -> Reader<'a> {
+        Reader {
+            message: self.message,
+            position: contents.start,
+            end: contents.end,
+        }
+    }
+
+    pub(super) fn bytes(&self, contents: Range<usize>) -> &'a [u8] {
+        &self.message[contents]
+    }
+
+    pub(super) fn integer(&mut self) -> Result<i64, DecodeError> {
+        let contents = self.expect(INTEGER)?;
+        decode_integer(self.bytes(contents))
+    }
+
+    pub(super) fn octet_string(&mut self) -> Result<&'a [u8], DecodeError> {
+        let contents = self.expect(OCTET_STRING)?;
+        Ok(self.bytes(contents))
+    }
+
+    pub(super) fn object_identifier(&mut self) -> Result<Vec<u32>, DecodeError> {
+        let contents = self.expect(OBJECT_IDENTIFIER)?;
+        decode_object_identifier(self.bytes(contents))
+    }
+
+    fn byte(&self, position: usize) -> Result<&'a u8, DecodeError> {
+        if position < self.end {
+            self.message.get(position).ok_or(DecodeError::Truncated)
+        } else {
+            Err(DecodeError::Truncated)
+        }
+    }
+}
+
+/// Decodes a two's complement integer.
+pub(super) fn decode_integer(bytes: &[u8]) -> Result<i64, DecodeError> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        return Err(DecodeError::IntegerOverflow);
+    }
+    let negative = bytes[0] & 0x80 != 0;
+    let value = bytes
+        .iter()
+        .fold(if negative { -1 } else { 0 }, |value: i64, byte| {
+            (value << 8) | i64::from(*byte)
+        });
+    Ok(value)
+}
+
+/// Decodes an unsigned integer, such as a counter, which may have a leading zero byte.
+pub(super) fn decode_unsigned(bytes: &[u8]) -> Result<u64, DecodeError> {
+    let bytes = match bytes {
+        [0, rest @ ..] => rest,
+        bytes => bytes,
+    };
+    if bytes.len() > 8 {
+        return Err(DecodeError::IntegerOverflow);
+    }
+    Ok(bytes
+        .iter()
+        .fold(0, |value: u64, byte| (value << 8) | u64::from(*byte)))
+}
+
+pub(super) fn decode_object_identifier(bytes: &[u8]) -> Result<Vec<u32>, DecodeError> {
+    let mut subidentifiers = Vec::new();
+    let mut value: u32 = 0;
+    for (index, byte) in bytes.iter().enumerate() {
+        value = value
+            .checked_mul(128)
+            .ok_or(DecodeError::InvalidObjectIdentifier)?
+            | u32::from(byte & 0x7f);
+        if byte & 0x80 != 0 {
+            if index == bytes.len() - 1 {
+                return Err(DecodeError::InvalidObjectIdentifier);
+            }
+            continue;
+        }
+
+        if subidentifiers.is_empty() {
+            // The first two subidentifiers are encoded together.
+            let first = (value / 40).min(2);
+            subidentifiers.push(first);
+            subidentifiers.push(value - first * 40);
+        } else {
+            subidentifiers.push(value);
+        }
+        value = 0;
+    }
+
+    if subidentifiers.is_empty() {
+        return Err(DecodeError::InvalidObjectIdentifier);
+    }
+    Ok(subidentifiers)
+}
+
+/// Encodes a value with the given tag and contents.
+pub(super) fn encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+    let length = contents.len();
+    if length < 0x80 {
+        encoded.push(length as u8);
+    } else {
+        let octets = length
+            .to_be_bytes()
+            .iter()
+            .skip_while(|byte| **byte == 0)
+            .copied()
+            .collect::<Vec<_>>();
+        encoded.push(0x80 | octets.len() as u8);
+        encoded.extend(octets);
+    }
+    encoded.extend_from_slice(contents);
+    encoded
+}
+
+pub(super) fn encode_integer(value: i64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    // The shortest two's complement encoding, dropping the redundant leading bytes.
+    let mut start = 0;
+    while start < 7
+        && ((bytes[start] == 0 && bytes[start + 1] & 0x80 == 0)
+            || (bytes[start] == 0xff && bytes[start + 1] & 0x80 != 0))
+    {
+        start += 1;
+    }
+    encode(INTEGER, &bytes[start..])
+}
+
+#[cfg(test)]
+pub(super) fn encode_object_identifier(subidentifiers: &[u32]) -> Vec<u8> {
+    let mut contents = Vec::new();
+    let first = subidentifiers[0] * 40 + subidentifiers.get(1).copied().unwrap_or_default();
+    for value in std::iter::once(first).chain(subidentifiers.iter().skip(2).copied()) {
+        let mut bytes = vec![(value & 0x7f) as u8];
+        let mut rest = value >> 7;
+        while rest > 0 {
+            bytes.push((rest & 0x7f) as u8 | 0x80);
+            rest >>= 7;
+        }
+        contents.extend(bytes.into_iter().rev());
+    }
+    encode(OBJECT_IDENTIFIER, &contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_values() {
+        let mut message = encode_integer(-129);
+        message.extend(encode_integer(300));
+        message.extend(encode_object_identifier(&[1, 3, 6, 1, 4, 1, 2021, 251]));
+        let long = vec![b'a'; 300];
+        message.extend(encode(OCTET_STRING, &long));
+        let message = encode(SEQUENCE, &message);
+
+        let mut reader = Reader::new(&message).constructed(SEQUENCE).unwrap();
+        assert_eq!(reader.integer(), Ok(-129));
+        assert_eq!(reader.integer(), Ok(300));
+        assert_eq!(
+            reader.object_identifier(),
+            Ok(vec![1, 3, 6, 1, 4, 1, 2021, 251])
+        );
+        assert_eq!(reader.octet_string(), Ok(&long[..]));
+        assert!(reader.is_empty());
+        assert_eq!(reader.integer(), Err(DecodeError::Truncated));
+    }
+
+    #[test]
+    fn rejects_truncated_values() {
+        let message = encode(OCTET_STRING, b"public");
+        let mut reader = Reader::new(&message[..message.len() - 1]);
+        assert_eq!(reader.octet_string(), Err(DecodeError::Truncated));
+    }
+
+    #[test]
+    fn decodes_unsigned_values() {
+        assert_eq!(
+            decode_unsigned(&[0, 0xff, 0xff, 0xff, 0xff]),
+            Ok(u32::MAX.into())
+        );
+        assert_eq!(decode_unsigned(&[0x01, 0x00]), Ok(256));
+    }
+}
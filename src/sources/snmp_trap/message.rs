@@ -0,0 +1,628 @@
+//! Decoding of the SNMP messages carrying traps, as defined in [RFC 3416][rfc3416] and, for
+//! SNMPv3, [RFC 3412][rfc3412].
+//!
+//! [rfc3412]: https://datatracker.ietf.org/doc/html/rfc3412
+//! [rfc3416]: https://datatracker.ietf.org/doc/html/rfc3416
+use std::{collections::HashMap, net::Ipv4Addr};
+
+use openssl::error::ErrorStack;
+use snafu::Snafu;
+
+use super::{
+    ber::{self, DecodeError, Reader},
+    usm::{UserKeys, AUTHENTICATION_LENGTH},
+};
+
+const TRAP_V1_PDU: u8 = 0xa4;
+const INFORM_REQUEST_PDU: u8 = 0xa6;
+const TRAP_V2_PDU: u8 = 0xa7;
+const RESPONSE_PDU: u8 = 0xa2;
+
+const VERSION_1: i64 = 0;
+const VERSION_2C: i64 = 1;
+const VERSION_3: i64 = 3;
+
+const USER_BASED_SECURITY_MODEL: i64 = 3;
+const AUTHENTICATION_FLAG: u8 = 0x01;
+const PRIVACY_FLAG: u8 = 0x02;
+
+/// `sysUpTime.0`, the first variable binding of SNMPv2 traps.
+const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+/// `snmpTrapOID.0`, the second variable binding of SNMPv2 traps.
+const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+/// `snmpTraps`, the parent of the generic traps of SNMPv1.
+const SNMP_TRAPS: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 5];
+
+#[derive(Debug, Snafu)]
+pub(super) enum TrapError {
+    #[snafu(display("Invalid message: {}", source))]
+    Decode { source: DecodeError },
+    #[snafu(display("Unsupported SNMP version {}.", version))]
+    UnsupportedVersion { version: i64 },
+    #[snafu(display("Unsupported PDU type {:#04x}.", tag))]
+    UnsupportedPdu { tag: u8 },
+    #[snafu(display("Unsupported security model {}.", model))]
+    UnsupportedSecurityModel { model: i64 },
+    #[snafu(display("Unknown community."))]
+    UnknownCommunity,
+    #[snafu(display("Unknown user {:?}.", user))]
+    UnknownUser { user: String },
+    #[snafu(display("The message is below the security level of user {:?}.", user))]
+    SecurityLevel { user: String },
+    #[snafu(display("Authentication failed for user {:?}.", user))]
+    Authentication { user: String },
+    #[snafu(display("Decryption failed for user {:?}.", user))]
+    Decryption { user: String },
+    #[snafu(display("Cryptographic error: {}", source))]
+    Crypto { source: ErrorStack },
+}
+
+impl TrapError {
+    /// Whether the message was rejected by the security checks, rather than invalid.
+    pub(super) const fn is_rejected(&self) -> bool {
+        matches!(
+            self,
+            Self::UnknownCommunity
+                | Self::UnknownUser { .. }
+                | Self::SecurityLevel { .. }
+                | Self::Authentication { .. }
+                | Self::Decryption { .. }
+        )
+    }
+}
+
+impl From<DecodeError> for TrapError {
+    fn from(source: DecodeError) -> Self {
+        Self::Decode { source }
+    }
+}
+
+impl From<ErrorStack> for TrapError {
+    fn from(source: ErrorStack) -> Self {
+        Self::Crypto { source }
+    }
+}
+
+/// The communities and users the traps are accepted from.
+pub(super) struct Security {
+    /// The accepted communities, or `None` to accept any.
+    pub(super) communities: Option<Vec<String>>,
+    pub(super) users: HashMap<Vec<u8>, UserKeys>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Version {
+    V1,
+    V2c,
+    V3,
+}
+
+impl Version {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::V1 => "1",
+            Self::V2c => "2c",
+            Self::V3 => "3",
+        }
+    }
+}
+
+/// The value of a variable binding.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum VarValue {
+    Integer(i64),
+    /// The counters, gauges and time ticks.
+    Unsigned(u64),
+    /// The octet strings, and the opaque values.
+    OctetString(Vec<u8>),
+    ObjectIdentifier(Vec<u32>),
+    IpAddress(Ipv4Addr),
+    /// The null values, and the exceptions such as `noSuchObject`.
+    Null,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Credentials {
+    Community(String),
+    User {
+        name: String,
+        engine_id: Vec<u8>,
+        context_name: String,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Trap {
+    pub(super) version: Version,
+    pub(super) credentials: Credentials,
+    /// Whether the trap is an inform request, which is acknowledged.
+    pub(super) inform: bool,
+    /// The time since the agent started, in hundredths of a second.
+    pub(super) uptime: Option<u64>,
+    pub(super) trap_oid: Option<Vec<u32>>,
+    /// The address of the agent, which SNMPv1 traps carry.
+    pub(super) agent_address: Option<Ipv4Addr>,
+    pub(super) varbinds: Vec<(Vec<u32>, VarValue)>,
+    request_id: i64,
+    /// The encoded variable bindings, which are echoed in the responses to inform requests.
+    encoded_varbinds: Vec<u8>,
+}
+
+impl Trap {
+    /// Returns the response acknowledging an SNMPv2c inform request.
+    ///
+    /// The inform requests of SNMPv3 are not acknowledged, as their responses must be sent by an
+    /// authoritative engine.
+    pub(super) fn response(&self) -> Option<Vec<u8>> {
+        let community = match (&self.credentials, self.inform) {
+            (Credentials::Community(community), true) => community,
+            _ => return None,
+        };
+
+        let mut pdu = ber::encode_integer(self.request_id);
+        // The error status and index.
+        pdu.extend(ber::encode_integer(0));
+        pdu.extend(ber::encode_integer(0));
+        pdu.extend_from_slice(&self.encoded_varbinds);
+
+        let mut message = ber::encode_integer(VERSION_2C);
+        message.extend(ber::encode(ber::OCTET_STRING, community.as_bytes()));
+        message.extend(ber::encode(RESPONSE_PDU, &pdu));
+        Some(ber::encode(ber::SEQUENCE, &message))
+    }
+}
+
+/// Decodes a message, checking its community or user.
+pub(super) fn decode(message: &[u8], security: &Security) -> Result<Trap, TrapError> {
+    let mut reader = Reader::new(message).constructed(ber::SEQUENCE)?;
+    let version = reader.integer()?;
+    let version = match version {
+        VERSION_1 => Version::V1,
+        VERSION_2C => Version::V2c,
+        VERSION_3 => return decode_v3(message, reader, security),
+        version => return Err(TrapError::UnsupportedVersion { version }),
+    };
+
+    let community = String::from_utf8_lossy(reader.octet_string()?).into_owned();
+    if let Some(communities) = &security.communities {
+        if !communities.iter().any(|accepted| {
+            accepted.len() == community.len()
+                && openssl::memcmp::eq(accepted.as_bytes(), community.as_bytes())
+        }) {
+            return Err(TrapError::UnknownCommunity);
+        }
+    }
+
+    decode_pdu(reader, version, Credentials::Community(community))
+}
+
+fn decode_v3(message: &[u8], mut reader: Reader, security: &Security) -> Result<Trap, TrapError> {
+    let mut header = reader.constructed(ber::SEQUENCE)?;
+    let _message_id = header.integer()?;
+    let _max_size = header.integer()?;
+    let flags = header.octet_string()?.first().copied().unwrap_or_default();
+    let model = header.integer()?;
+    if model != USER_BASED_SECURITY_MODEL {
+        return Err(TrapError::UnsupportedSecurityModel { model });
+    }
+    let authenticated = flags & AUTHENTICATION_FLAG != 0;
+    let private = flags & PRIVACY_FLAG != 0;
+
+    let parameters = reader.expect(ber::OCTET_STRING)?;
+    let mut parameters = reader.within(parameters).constructed(ber::SEQUENCE)?;
+    let engine_id = parameters.octet_string()?;
+    let engine_boots = parameters.integer()?;
+    let engine_time = parameters.integer()?;
+    let name = parameters.octet_string()?;
+    let authentication = parameters.expect(ber::OCTET_STRING)?;
+    let salt = parameters.octet_string()?;
+
+    let user_name = String::from_utf8_lossy(name).into_owned();
+    let user = match security.users.get(name) {
+        Some(user) => user,
+        None => return Err(TrapError::UnknownUser { user: user_name }),
+    };
+    // Messages with a lower security level than the one of the user are rejected, so that the
+    // traps of users with keys can't be forged.
+    if (user.authenticates() && !authenticated)
+        || (user.encrypts() && !private)
+        || (private && !authenticated)
+    {
+        return Err(TrapError::SecurityLevel { user: user_name });
+    }
+
+    if authenticated {
+        if authentication.len() != AUTHENTICATION_LENGTH {
+            return Err(TrapError::Authentication { user: user_name });
+        }
+        let mut zeroed = message.to_vec();
+        zeroed[authentication.clone()].fill(0);
+        if !user.authenticate(engine_id, &zeroed, &message[authentication])? {
+            return Err(TrapError::Authentication { user: user_name });
+        }
+    }
+
+    let credentials = |context_name: &[u8]| Credentials::User {
+        name: user_name.clone(),
+        engine_id: engine_id.to_vec(),
+        context_name: String::from_utf8_lossy(context_name).into_owned(),
+    };
+    if private {
+        let encrypted = reader.octet_string()?;
+        let decrypted = user
+            .decrypt(
+                engine_id,
+                u32::try_from(engine_boots).unwrap_or_default(),
+                u32::try_from(engine_time).unwrap_or_default(),
+                salt,
+                encrypted,
+            )?
+            .ok_or_else(|| TrapError::Decryption {
+                user: user_name.clone(),
+            })?;
+
+        // The decrypted scoped PDU is followed by the padding of the cipher.
+        let mut scoped = Reader::new(&decrypted)
+            .constructed(ber::SEQUENCE)
+            .map_err(|_| TrapError::Decryption {
+                user: user_name.clone(),
+            })?;
+        let _context_engine_id = scoped.octet_string()?;
+        let context_name = scoped.octet_string()?;
+        decode_pdu(scoped, Version::V3, credentials(context_name))
+    } else {
+        let mut scoped = reader.constructed(ber::SEQUENCE)?;
+        let _context_engine_id = scoped.octet_string()?;
+        let context_name = scoped.octet_string()?;
+        decode_pdu(scoped, Version::V3, credentials(context_name))
+    }
+}
+
+fn decode_pdu(
+    mut reader: Reader,
+    version: Version,
+    credentials: Credentials,
+) -> Result<Trap, TrapError> {
+    match reader.peek_tag()? {
+        TRAP_V1_PDU => {
+            let mut pdu = reader.constructed(TRAP_V1_PDU)?;
+            let enterprise = pdu.object_identifier()?;
+            let agent_address = pdu.expect(ber::IP_ADDRESS)?;
+            let agent_address = <[u8; 4]>::try_from(pdu.bytes(agent_address))
+                .ok()
+                .map(Ipv4Addr::from);
+            let generic = pdu.integer()?;
+            let specific = pdu.integer()?;
+            let timestamp = pdu.expect(ber::TIMETICKS)?;
+            let timestamp = ber::decode_unsigned(pdu.bytes(timestamp))?;
+            let varbinds = decode_varbinds(pdu.constructed(ber::SEQUENCE)?)?;
+
+            // The SNMPv1 traps are translated to SNMPv2 traps as per section 3.1 of RFC 3584.
+            let trap_oid = match (u32::try_from(generic), u32::try_from(specific)) {
+                (Ok(generic @ 0..=5), _) => [SNMP_TRAPS, &[generic + 1][..]].concat(),
+                (_, Ok(specific)) => [&enterprise[..], &[0, specific]].concat(),
+                _ => enterprise,
+            };
+
+            Ok(Trap {
+                version,
+                credentials,
+                inform: false,
+                uptime: Some(timestamp),
+                trap_oid: Some(trap_oid),
+                agent_address,
+                varbinds,
+                request_id: 0,
+                encoded_varbinds: Vec::new(),
+            })
+        }
+        tag @ (TRAP_V2_PDU | INFORM_REQUEST_PDU) => {
+            let mut pdu = reader.constructed(tag)?;
+            let request_id = pdu.integer()?;
+            let _error_status = pdu.integer()?;
+            let _error_index = pdu.integer()?;
+
+            let start = pdu.position();
+            let contents = pdu.expect(ber::SEQUENCE)?;
+            let encoded_varbinds = pdu.bytes(start..contents.end).to_vec();
+            let mut varbinds = decode_varbinds(pdu.within(contents))?;
+
+            let mut uptime = None;
+            let mut trap_oid = None;
+            varbinds.retain(|(oid, value)| match (&oid[..], value) {
+                (SYS_UP_TIME, VarValue::Unsigned(ticks)) if uptime.is_none() => {
+                    uptime = Some(*ticks);
+                    false
+                }
+                (SNMP_TRAP_OID, VarValue::ObjectIdentifier(oid)) if trap_oid.is_none() => {
+                    trap_oid = Some(oid.clone());
+                    false
+                }
+                _ => true,
+            });
+
+            Ok(Trap {
+                version,
+                credentials,
+                inform: tag == INFORM_REQUEST_PDU,
+                uptime,
+                trap_oid,
+                agent_address: None,
+                varbinds,
+                request_id,
+                encoded_varbinds,
+            })
+        }
+        tag => Err(TrapError::UnsupportedPdu { tag }),
+    }
+}
+
+fn decode_varbinds(mut reader: Reader) -> Result<Vec<(Vec<u32>, VarValue)>, DecodeError> {
+    let mut varbinds = Vec::new();
+    while !reader.is_empty() {
+        let mut varbind = reader.constructed(ber::SEQUENCE)?;
+        let oid = varbind.object_identifier()?;
+        let (tag, contents) = varbind.read()?;
+        let bytes = varbind.bytes(contents);
+        let value = match tag {
+            ber::INTEGER => VarValue::Integer(ber::decode_integer(bytes)?),
+            ber::COUNTER32 | ber::GAUGE32 | ber::TIMETICKS | ber::COUNTER64 => {
+                VarValue::Unsigned(ber::decode_unsigned(bytes)?)
+            }
+            ber::OBJECT_IDENTIFIER => {
+                VarValue::ObjectIdentifier(ber::decode_object_identifier(bytes)?)
+            }
+            ber::IP_ADDRESS => match <[u8; 4]>::try_from(bytes) {
+                Ok(address) => VarValue::IpAddress(Ipv4Addr::from(address)),
+                Err(_) => VarValue::OctetString(bytes.to_vec()),
+            },
+            ber::NULL | ber::NO_SUCH_OBJECT | ber::NO_SUCH_INSTANCE | ber::END_OF_MIB_VIEW => {
+                VarValue::Null
+            }
+            ber::OCTET_STRING | ber::OPAQUE => VarValue::OctetString(bytes.to_vec()),
+            // The types unknown to SNMPv2 are kept as their bytes, like the opaque values.
+            _ => VarValue::OctetString(bytes.to_vec()),
+        };
+        varbinds.push((oid, value));
+    }
+    Ok(varbinds)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use openssl::symm::{Cipher, Crypter, Mode};
+
+    use super::*;
+    use crate::sources::snmp_trap::usm::{
+        hmac, localize_key, password_to_key, AuthenticationProtocol, PrivacyProtocol, SnmpUser,
+    };
+
+    pub(in crate::sources::snmp_trap) fn varbind(oid: &[u32], value: Vec<u8>) -> Vec<u8> {
+        let mut varbind = ber::encode_object_identifier(oid);
+        varbind.extend(value);
+        ber::encode(ber::SEQUENCE, &varbind)
+    }
+
+    /// Encodes an SNMPv2 trap, or inform request, PDU.
+    pub(in crate::sources::snmp_trap) fn trap_pdu(tag: u8, varbinds: &[Vec<u8>]) -> Vec<u8> {
+        let mut pdu = ber::encode_integer(42);
+        pdu.extend(ber::encode_integer(0));
+        pdu.extend(ber::encode_integer(0));
+        pdu.extend(ber::encode(ber::SEQUENCE, &varbinds.concat()));
+        ber::encode(tag, &pdu)
+    }
+
+    pub(in crate::sources::snmp_trap) fn link_down_varbinds() -> Vec<Vec<u8>> {
+        vec![
+            varbind(SYS_UP_TIME, ber::encode(ber::TIMETICKS, &[0x01, 0x00])),
+            varbind(
+                SNMP_TRAP_OID,
+                ber::encode_object_identifier(&[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+            ),
+            varbind(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 7], ber::encode_integer(7)),
+            varbind(
+                &[1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 7],
+                ber::encode(ber::OCTET_STRING, b"eth0"),
+            ),
+        ]
+    }
+
+    pub(in crate::sources::snmp_trap) fn v2c_message(community: &str, pdu: Vec<u8>) -> Vec<u8> {
+        let mut message = ber::encode_integer(VERSION_2C);
+        message.extend(ber::encode(ber::OCTET_STRING, community.as_bytes()));
+        message.extend(pdu);
+        ber::encode(ber::SEQUENCE, &message)
+    }
+
+    fn security(communities: Option<Vec<String>>, users: &[SnmpUser]) -> Security {
+        Security {
+            communities,
+            users: users
+                .iter()
+                .map(|user| (user.name.clone().into_bytes(), UserKeys::new(user).unwrap()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn decodes_v2c_traps() {
+        let message = v2c_message("public", trap_pdu(TRAP_V2_PDU, &link_down_varbinds()));
+        let trap = decode(&message, &security(None, &[])).unwrap();
+
+        assert_eq!(trap.version, Version::V2c);
+        assert_eq!(
+            trap.credentials,
+            Credentials::Community("public".to_owned())
+        );
+        assert!(!trap.inform);
+        assert_eq!(trap.uptime, Some(256));
+        assert_eq!(trap.trap_oid, Some(vec![1, 3, 6, 1, 6, 3, 1, 1, 5, 3]));
+        assert_eq!(
+            trap.varbinds,
+            vec![
+                (vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 7], VarValue::Integer(7)),
+                (
+                    vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 7],
+                    VarValue::OctetString(b"eth0".to_vec())
+                ),
+            ]
+        );
+        assert_eq!(trap.response(), None);
+    }
+
+    #[test]
+    fn rejects_unknown_communities() {
+        let message = v2c_message("private", trap_pdu(TRAP_V2_PDU, &link_down_varbinds()));
+        let error = decode(&message, &security(Some(vec!["public".to_owned()]), &[])).unwrap_err();
+        assert!(matches!(error, TrapError::UnknownCommunity));
+        assert!(error.is_rejected());
+    }
+
+    #[test]
+    fn responds_to_informs() {
+        let varbinds = link_down_varbinds();
+        let message = v2c_message("public", trap_pdu(INFORM_REQUEST_PDU, &varbinds));
+        let trap = decode(&message, &security(None, &[])).unwrap();
+        assert!(trap.inform);
+
+        let mut response = trap_pdu(RESPONSE_PDU, &varbinds);
+        response = v2c_message("public", response);
+        assert_eq!(trap.response(), Some(response));
+    }
+
+    #[test]
+    fn translates_v1_traps() {
+        let mut pdu = ber::encode_object_identifier(&[1, 3, 6, 1, 4, 1, 99999]);
+        pdu.extend(ber::encode(ber::IP_ADDRESS, &[192, 0, 2, 1]));
+        pdu.extend(ber::encode_integer(6));
+        pdu.extend(ber::encode_integer(17));
+        pdu.extend(ber::encode(ber::TIMETICKS, &[0x10]));
+        pdu.extend(ber::encode(ber::SEQUENCE, &[]));
+        let mut message = ber::encode_integer(VERSION_1);
+        message.extend(ber::encode(ber::OCTET_STRING, b"public"));
+        message.extend(ber::encode(TRAP_V1_PDU, &pdu));
+        let message = ber::encode(ber::SEQUENCE, &message);
+
+        let trap = decode(&message, &security(None, &[])).unwrap();
+        assert_eq!(trap.version, Version::V1);
+        assert_eq!(trap.agent_address, Some(Ipv4Addr::new(192, 0, 2, 1)));
+        assert_eq!(trap.uptime, Some(16));
+        assert_eq!(trap.trap_oid, Some(vec![1, 3, 6, 1, 4, 1, 99999, 0, 17]));
+        assert!(trap.varbinds.is_empty());
+    }
+
+    fn user() -> SnmpUser {
+        SnmpUser {
+            name: "vector".to_owned(),
+            authentication_protocol: Some(AuthenticationProtocol::Sha),
+            authentication_passphrase: Some("authentication".to_owned().into()),
+            privacy_protocol: Some(PrivacyProtocol::Aes),
+            privacy_passphrase: Some("privacypassphrase".to_owned().into()),
+        }
+    }
+
+    /// Encodes an authenticated and encrypted SNMPv3 message, as an agent of the given user would.
+    fn v3_message(user: &SnmpUser, authentication_passphrase: &str, pdu: Vec<u8>) -> Vec<u8> {
+        let engine_id = b"\x80\x00\x1f\x88\x04vector";
+        let (boots, time) = (3_u32, 1234_u32);
+        let salt = [1, 2, 3, 4, 5, 6, 7, 8];
+        let protocol = AuthenticationProtocol::Sha;
+
+        let mut scoped = ber::encode(ber::OCTET_STRING, engine_id);
+        scoped.extend(ber::encode(ber::OCTET_STRING, b"context"));
+        scoped.extend(pdu);
+        let scoped = ber::encode(ber::SEQUENCE, &scoped);
+
+        let privacy_passphrase = user.privacy_passphrase.as_ref().unwrap().inner();
+        let key = localize_key(
+            protocol,
+            &password_to_key(protocol, privacy_passphrase).unwrap(),
+            engine_id,
+        )
+        .unwrap();
+        let mut iv = boots.to_be_bytes().to_vec();
+        iv.extend(time.to_be_bytes());
+        iv.extend(salt);
+        let cipher = Cipher::aes_128_cfb128();
+        let mut crypter = Crypter::new(cipher, Mode::Encrypt, &key[..16], Some(&iv)).unwrap();
+        let mut encrypted = vec![0; scoped.len() + cipher.block_size()];
+        let mut length = crypter.update(&scoped, &mut encrypted).unwrap();
+        length += crypter.finalize(&mut encrypted[length..]).unwrap();
+        encrypted.truncate(length);
+
+        let build = |authentication: &[u8]| {
+            let mut parameters = ber::encode(ber::OCTET_STRING, engine_id);
+            parameters.extend(ber::encode_integer(boots.into()));
+            parameters.extend(ber::encode_integer(time.into()));
+            parameters.extend(ber::encode(ber::OCTET_STRING, user.name.as_bytes()));
+            parameters.extend(ber::encode(ber::OCTET_STRING, authentication));
+            parameters.extend(ber::encode(ber::OCTET_STRING, &salt));
+
+            let mut header = ber::encode_integer(7);
+            header.extend(ber::encode_integer(65507));
+            header.extend(ber::encode(
+                ber::OCTET_STRING,
+                &[AUTHENTICATION_FLAG | PRIVACY_FLAG],
+            ));
+            header.extend(ber::encode_integer(USER_BASED_SECURITY_MODEL));
+
+            let mut message = ber::encode_integer(VERSION_3);
+            message.extend(ber::encode(ber::SEQUENCE, &header));
+            message.extend(ber::encode(
+                ber::OCTET_STRING,
+                &ber::encode(ber::SEQUENCE, &parameters),
+            ));
+            message.extend(ber::encode(ber::OCTET_STRING, &encrypted));
+            ber::encode(ber::SEQUENCE, &message)
+        };
+
+        let zeroed = build(&[0; AUTHENTICATION_LENGTH]);
+        let key = localize_key(
+            protocol,
+            &password_to_key(protocol, authentication_passphrase).unwrap(),
+            engine_id,
+        )
+        .unwrap();
+        let mac = hmac(protocol, &key, &zeroed).unwrap();
+        build(&mac[..AUTHENTICATION_LENGTH])
+    }
+
+    #[test]
+    fn decodes_v3_traps() {
+        let user = user();
+        let message = v3_message(
+            &user,
+            "authentication",
+            trap_pdu(TRAP_V2_PDU, &link_down_varbinds()),
+        );
+        let trap = decode(&message, &security(None, &[user])).unwrap();
+
+        assert_eq!(trap.version, Version::V3);
+        assert_eq!(
+            trap.credentials,
+            Credentials::User {
+                name: "vector".to_owned(),
+                engine_id: b"\x80\x00\x1f\x88\x04vector".to_vec(),
+                context_name: "context".to_owned(),
+            }
+        );
+        assert_eq!(trap.trap_oid, Some(vec![1, 3, 6, 1, 6, 3, 1, 1, 5, 3]));
+        assert_eq!(trap.varbinds.len(), 2);
+    }
+
+    #[test]
+    fn rejects_v3_traps_with_other_keys() {
+        let user = user();
+        let message = v3_message(
+            &user,
+            "not the passphrase",
+            trap_pdu(TRAP_V2_PDU, &link_down_varbinds()),
+        );
+
+        let error = decode(&message, &security(None, &[user.clone()])).unwrap_err();
+        assert!(matches!(error, TrapError::Authentication { .. }));
+
+        let error = decode(&message, &security(None, &[])).unwrap_err();
+        assert!(matches!(error, TrapError::UnknownUser { .. }));
+    }
+}
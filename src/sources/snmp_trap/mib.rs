@@ -0,0 +1,419 @@
+//! Resolution of object identifiers to the symbolic names defined in MIB files.
+//!
+//! Only the object identifiers assigned by the definitions of the MIB files are read, which is all
+//! it takes to name the traps and the variables they carry: the rest of the modules, such as the
+//! syntax of the objects, is skipped over.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The nodes of the tree of object identifiers defined in the base modules, which the MIB files
+/// of the devices build upon without them having to be provided.
+const BASE_NODES: &[(&str, &[u32])] = &[
+    ("ccitt", &[0]),
+    ("zeroDotZero", &[0, 0]),
+    ("iso", &[1]),
+    ("org", &[1, 3]),
+    ("dod", &[1, 3, 6]),
+    ("internet", &[1, 3, 6, 1]),
+    ("directory", &[1, 3, 6, 1, 1]),
+    ("mgmt", &[1, 3, 6, 1, 2]),
+    ("mib-2", &[1, 3, 6, 1, 2, 1]),
+    ("system", &[1, 3, 6, 1, 2, 1, 1]),
+    ("sysDescr", &[1, 3, 6, 1, 2, 1, 1, 1]),
+    ("sysObjectID", &[1, 3, 6, 1, 2, 1, 1, 2]),
+    ("sysUpTime", &[1, 3, 6, 1, 2, 1, 1, 3]),
+    ("sysContact", &[1, 3, 6, 1, 2, 1, 1, 4]),
+    ("sysName", &[1, 3, 6, 1, 2, 1, 1, 5]),
+    ("sysLocation", &[1, 3, 6, 1, 2, 1, 1, 6]),
+    ("interfaces", &[1, 3, 6, 1, 2, 1, 2]),
+    ("transmission", &[1, 3, 6, 1, 2, 1, 10]),
+    ("experimental", &[1, 3, 6, 1, 3]),
+    ("private", &[1, 3, 6, 1, 4]),
+    ("enterprises", &[1, 3, 6, 1, 4, 1]),
+    ("security", &[1, 3, 6, 1, 5]),
+    ("snmpV2", &[1, 3, 6, 1, 6]),
+    ("snmpDomains", &[1, 3, 6, 1, 6, 1]),
+    ("snmpProxys", &[1, 3, 6, 1, 6, 2]),
+    ("snmpModules", &[1, 3, 6, 1, 6, 3]),
+    ("snmpMIB", &[1, 3, 6, 1, 6, 3, 1]),
+    ("snmpMIBObjects", &[1, 3, 6, 1, 6, 3, 1, 1]),
+    ("snmpTrap", &[1, 3, 6, 1, 6, 3, 1, 1, 4]),
+    ("snmpTrapOID", &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1]),
+    ("snmpTrapEnterprise", &[1, 3, 6, 1, 6, 3, 1, 1, 4, 3]),
+    ("snmpTraps", &[1, 3, 6, 1, 6, 3, 1, 1, 5]),
+    ("coldStart", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 1]),
+    ("warmStart", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 2]),
+    ("linkDown", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+    ("linkUp", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 4]),
+    ("authenticationFailure", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 5]),
+    ("egpNeighborLoss", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 6]),
+    ("joint-iso-ccitt", &[2]),
+];
+
+/// The macros whose definitions are assigned an object identifier.
+const MACROS: &[&str] = &[
+    "AGENT-CAPABILITIES",
+    "MODULE-COMPLIANCE",
+    "MODULE-IDENTITY",
+    "NOTIFICATION-GROUP",
+    "NOTIFICATION-TYPE",
+    "OBJECT-GROUP",
+    "OBJECT-IDENTITY",
+    "OBJECT-TYPE",
+    "TRAP-TYPE",
+];
+
+/// The names of the object identifiers.
+#[derive(Debug)]
+pub(super) struct Mib {
+    names: BTreeMap<Vec<u32>, String>,
+}
+
+/// A definition read from a MIB file, whose object identifier is relative to another one.
+#[derive(Debug, PartialEq)]
+struct Definition {
+    name: String,
+    parent: Option<String>,
+    subidentifiers: Vec<u32>,
+}
+
+impl Mib {
+    /// Loads the MIB files at the given paths, which are either files or directories of files.
+    pub(super) fn load(paths: &[PathBuf]) -> crate::Result<Self> {
+        let mut definitions = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                let mut files = fs::read_dir(path)?
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                files.sort();
+                for file in files.iter().filter(|file| file.is_file()) {
+                    definitions.extend(read_definitions(file)?);
+                }
+            } else {
+                definitions.extend(read_definitions(path)?);
+            }
+        }
+
+        let (mib, unresolved) = Self::from_definitions(definitions);
+        if !unresolved.is_empty() {
+            warn!(
+                message = "Some MIB definitions couldn't be resolved, as they're defined relative to objects of MIB files that weren't loaded.",
+                count = unresolved.len(),
+                definitions = ?unresolved.iter().take(10).collect::<Vec<_>>(),
+            );
+        }
+        Ok(mib)
+    }
+
+    /// Resolves the definitions relative to each other and to the base nodes, returning the names
+    /// of the definitions that couldn't be resolved.
+    fn from_definitions(mut definitions: Vec<Definition>) -> (Self, Vec<String>) {
+        let mut oids = HashMap::new();
+        let mut names = BTreeMap::new();
+        for (name, oid) in BASE_NODES {
+            oids.insert((*name).to_owned(), oid.to_vec());
+            names.insert(oid.to_vec(), (*name).to_owned());
+        }
+
+        // The definitions can refer to those defined after them, or in other files.
+        loop {
+            let count = definitions.len();
+            definitions.retain(|definition| {
+                let parent = match &definition.parent {
+                    Some(parent) => match oids.get(parent) {
+                        Some(oid) => oid.clone(),
+                        None => return true,
+                    },
+                    None => Vec::new(),
+                };
+                let mut oid = parent;
+                oid.extend_from_slice(&definition.subidentifiers);
+                // The objects named more than once are named after their last definition.
+                names.insert(oid.clone(), definition.name.clone());
+                oids.insert(definition.name.clone(), oid);
+                false
+            });
+            if definitions.is_empty() || definitions.len() == count {
+                break;
+            }
+        }
+
+        let unresolved = definitions
+            .into_iter()
+            .map(|definition| definition.name)
+            .collect();
+        (Self { names }, unresolved)
+    }
+
+    /// Returns the name of an object identifier, with the subidentifiers beyond the closest named
+    /// object, such as the index of a table row, appended to it.
+    pub(super) fn name(&self, oid: &[u32]) -> Option<String> {
+        (1..=oid.len()).rev().find_map(|length| {
+            self.names.get(&oid[..length]).map(|name| {
+                let mut name = name.clone();
+                for subidentifier in &oid[length..] {
+                    name.push('.');
+                    name.push_str(&subidentifier.to_string());
+                }
+                name
+            })
+        })
+    }
+}
+
+fn read_definitions(path: &Path) -> crate::Result<Vec<Definition>> {
+    let text =
+        fs::read(path).map_err(|error| format!("Could not read MIB file {:?}: {}", path, error))?;
+    Ok(parse_definitions(&String::from_utf8_lossy(&text)))
+}
+
+/// Splits a MIB module into its identifiers, numbers, and punctuation, leaving out the comments
+/// and the quoted strings, such as the descriptions of the definitions.
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '"' {
+            rest = rest[1..].find('"').map_or("", |end| &rest[end + 2..]);
+        } else if let Some(comment) = rest.strip_prefix("--") {
+            // Comments end with the line, or with another `--`.
+            let end = comment
+                .find(|c| c == '\n')
+                .into_iter()
+                .chain(comment.find("--").map(|end| end + 1))
+                .min();
+            rest = end.map_or("", |end| &comment[end + 1..]);
+        } else if let Some(assignment) = rest.strip_prefix("::=") {
+            tokens.push("::=");
+            rest = assignment;
+        } else if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(&rest[..end]);
+            rest = &rest[end..];
+        } else {
+            tokens.push(&rest[..c.len_utf8()]);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    tokens
+}
+
+/// Reads the definitions assigned an object identifier from a MIB module.
+fn parse_definitions(text: &str) -> Vec<Definition> {
+    let tokens = tokenize(text);
+    let mut definitions = Vec::new();
+
+    // The definition being read, and its enterprise for SMIv1 traps.
+    let mut current: Option<&str> = None;
+    let mut enterprise: Option<&str> = None;
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        let next = tokens.get(index + 1).copied();
+
+        if starts_lowercase(token)
+            && (next.map_or(false, |next| MACROS.contains(&next))
+                || (next == Some("OBJECT") && tokens.get(index + 2) == Some(&"IDENTIFIER")))
+        {
+            current = Some(token);
+            enterprise = None;
+        } else if token == "ENTERPRISE" {
+            enterprise = next;
+        } else if token == "::=" {
+            match (current.take(), next) {
+                (Some(name), Some("{")) => {
+                    let end = tokens[index..]
+                        .iter()
+                        .position(|token| *token == "}")
+                        .map_or(tokens.len(), |end| index + end);
+                    if let Some(definition) = parse_value(name, &tokens[index + 2..end]) {
+                        definitions.push(definition);
+                    }
+                    index = end;
+                }
+                // SMIv1 traps are numbered within their enterprise.
+                (Some(name), Some(number)) => {
+                    if let (Some(enterprise), Ok(number)) = (enterprise, number.parse()) {
+                        definitions.push(Definition {
+                            name: name.to_owned(),
+                            parent: Some(enterprise.to_owned()),
+                            subidentifiers: vec![0, number],
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+        index += 1;
+    }
+
+    definitions
+}
+
+/// Parses an object identifier value, such as `{ ifEntry 1 }` or `{ iso org(3) dod(6) }`.
+fn parse_value(name: &str, components: &[&str]) -> Option<Definition> {
+    let mut parent = None;
+    let mut subidentifiers = Vec::new();
+    let mut index = 0;
+    while index < components.len() {
+        let component = components[index];
+        if let Ok(number) = component.parse() {
+            subidentifiers.push(number);
+        } else if components.get(index + 1) == Some(&"(") {
+            // A named number, whose name is only informative.
+            subidentifiers.push(components.get(index + 2)?.parse().ok()?);
+            index += 3;
+        } else if index == 0 {
+            parent = Some(component.to_owned());
+        } else {
+            return None;
+        }
+        index += 1;
+    }
+
+    Some(Definition {
+        name: name.to_owned(),
+        parent,
+        subidentifiers,
+    })
+}
+
+fn starts_lowercase(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .map_or(false, |c| c.is_ascii_lowercase())
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    pub(in crate::sources::snmp_trap) const IF_MIB: &str = r#"
+IF-MIB DEFINITIONS ::= BEGIN
+
+IMPORTS
+    MODULE-IDENTITY, OBJECT-TYPE, Counter32, Gauge32, Counter64,
+    Integer32, TimeTicks, mib-2, NOTIFICATION-TYPE    FROM SNMPv2-SMI;
+
+ifMIB MODULE-IDENTITY
+    LAST-UPDATED "200006140000Z"
+    DESCRIPTION
+            "The MIB module to describe generic objects for network
+            interface sub-layers -- this isn't a comment ::= { nothing 1 }"
+    ::= { mib-2 31 }
+
+-- the interfaces table ::= { nothing 2 }
+
+ifTable OBJECT-TYPE
+    SYNTAX      SEQUENCE OF IfEntry
+    MAX-ACCESS  not-accessible
+    STATUS      current
+    ::= { interfaces 2 }
+
+ifEntry OBJECT-TYPE
+    SYNTAX      IfEntry
+    INDEX   { ifIndex }
+    ::= { ifTable 1 }
+
+IfEntry ::=
+    SEQUENCE {
+        ifIndex                 InterfaceIndex,
+        ifSpecific              OBJECT IDENTIFIER
+    }
+
+ifIndex OBJECT-TYPE
+    SYNTAX      InterfaceIndex
+    ::= { ifEntry 1 } -- trailing comment
+
+ifAdminStatus OBJECT-TYPE
+    SYNTAX  INTEGER { up(1), down(2), testing(3) }
+    DEFVAL { up }
+    ::= { ifEntry 7 }
+
+linkDown NOTIFICATION-TYPE
+    OBJECTS { ifIndex, ifAdminStatus, ifOperStatus }
+    ::= { snmpTraps 3 }
+
+ifConformance OBJECT IDENTIFIER ::= { ifMIB 2 }
+
+acme OBJECT IDENTIFIER ::= { iso org(3) dod(6) internet(1) private(4) enterprises(1) 99999 }
+
+acmeOverheated TRAP-TYPE
+    ENTERPRISE acme
+    VARIABLES { ifIndex }
+    ::= 7
+
+orphan OBJECT IDENTIFIER ::= { unknownParent 1 }
+
+END
+"#;
+
+    #[test]
+    fn parses_definitions() {
+        let definitions = parse_definitions(IF_MIB);
+        let names = definitions
+            .iter()
+            .map(|definition| definition.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "ifMIB",
+                "ifTable",
+                "ifEntry",
+                "ifIndex",
+                "ifAdminStatus",
+                "linkDown",
+                "ifConformance",
+                "acme",
+                "acmeOverheated",
+                "orphan"
+            ]
+        );
+        assert_eq!(
+            definitions[7],
+            Definition {
+                name: "acme".to_owned(),
+                parent: Some("iso".to_owned()),
+                subidentifiers: vec![3, 6, 1, 4, 1, 99999],
+            }
+        );
+    }
+
+    #[test]
+    fn resolves_names() {
+        let (mib, unresolved) = Mib::from_definitions(parse_definitions(IF_MIB));
+        assert_eq!(unresolved, ["orphan"]);
+
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 7, 12]).as_deref(),
+            Some("ifAdminStatus.12")
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 2, 1, 31, 2]).as_deref(),
+            Some("ifConformance")
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 4, 1, 99999, 0, 7]).as_deref(),
+            Some("acmeOverheated")
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]).as_deref(),
+            Some("linkDown")
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 4, 1, 8]).as_deref(),
+            Some("enterprises.8")
+        );
+        assert_eq!(mib.name(&[3, 1]), None);
+    }
+}
@@ -0,0 +1,512 @@
+//! The `snmp_trap` source.
+//!
+//! Listens for the SNMPv1, SNMPv2c and SNMPv3 traps of network devices, resolves their object
+//! identifiers to names with the MIB files given, and emits each trap as a log event with its
+//! variable bindings as an object.
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+use chrono::Utc;
+use listenfd::ListenFd;
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use value::{kind::Collection, Kind, Value};
+use vector_common::{
+    internal_event::{ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol},
+    sensitive_string::SensitiveString,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{log_schema, DataType, Output, SourceConfig, SourceContext},
+    event::LogEvent,
+    internal_events::{
+        EventsReceived, SnmpTrapParseError, SnmpTrapRejected, SocketBindError, SocketMode,
+        SocketReceiveError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::net::{try_bind_udp_socket, SocketListenAddr},
+    udp, SourceSender,
+};
+
+mod ber;
+mod message;
+mod mib;
+mod usm;
+
+pub use self::usm::{AuthenticationProtocol, PrivacyProtocol, SnmpUser};
+use self::{
+    message::{Credentials, Security, Trap, VarValue},
+    mib::Mib,
+    usm::UserKeys,
+};
+
+/// The largest SNMP message which fits in a UDP datagram.
+const MAX_MESSAGE_SIZE: usize = 65_507;
+
+/// Configuration for the `snmp_trap` source.
+#[configurable_component(source("snmp_trap"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpTrapConfig {
+    /// The UDP address to listen for traps on.
+    #[serde(default = "default_address")]
+    #[configurable(metadata(docs::examples = "0.0.0.0:162"))]
+    #[configurable(metadata(docs::examples = "systemd"))]
+    #[configurable(metadata(docs::examples = "systemd#3"))]
+    pub address: SocketListenAddr,
+
+    /// The communities the SNMPv1 and SNMPv2c traps are accepted from.
+    ///
+    /// By default, the traps of any community are accepted.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "public"))]
+    pub communities: Vec<SensitiveString>,
+
+    /// The users the SNMPv3 traps are accepted from.
+    ///
+    /// The SNMPv3 traps of other users are rejected, as are those sent with a lower security
+    /// level than the one of their user.
+    #[serde(default)]
+    pub users: Vec<SnmpUser>,
+
+    /// The MIB files, or directories of MIB files, to resolve the object identifiers with.
+    ///
+    /// The object identifiers of the base SMI and SNMPv2 MIB modules, such as `sysUpTime` and the
+    /// generic traps, are resolved without any MIB files. The object identifiers that can't be
+    /// resolved are kept in their numeric form.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/usr/share/snmp/mibs"))]
+    pub mib_paths: Vec<PathBuf>,
+
+    /// The size of the receive buffer used for the listening socket.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub receive_buffer_bytes: Option<usize>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_address() -> SocketListenAddr {
+    SocketListenAddr::SocketAddr(SocketAddr::from(([0, 0, 0, 0], 162)))
+}
+
+impl Default for SnmpTrapConfig {
+    fn default() -> Self {
+        Self {
+            address: default_address(),
+            communities: Vec::new(),
+            users: Vec::new(),
+            mib_paths: Vec::new(),
+            receive_buffer_bytes: None,
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(SnmpTrapConfig);
+
+impl SnmpTrapConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .with_event_field(&owned_value_path!("version"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("community"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("user"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("engine_id"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("context_name"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(&owned_value_path!("inform"), Kind::boolean(), None)
+            .with_event_field(
+                &owned_value_path!("uptime"),
+                Kind::integer().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("trap_oid"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("trap_name"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("agent_address"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("varbinds"),
+                Kind::object(Collection::from_unknown(Kind::any())),
+                None,
+            )
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                SnmpTrapConfig::NAME,
+                parse_value_path(log_schema().host_key())
+                    .ok()
+                    .map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some("host"),
+            )
+    }
+
+    fn security(&self) -> crate::Result<Security> {
+        let communities = (!self.communities.is_empty()).then(|| {
+            self.communities
+                .iter()
+                .map(|community| community.inner().to_owned())
+                .collect()
+        });
+
+        let mut users = HashMap::new();
+        for user in &self.users {
+            let keys = UserKeys::new(user)?;
+            if users.insert(user.name.clone().into_bytes(), keys).is_some() {
+                return Err(format!("The SNMP user {:?} is configured twice.", user.name).into());
+            }
+        }
+
+        Ok(Security { communities, users })
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SnmpTrapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let source = SnmpTrapSource {
+            address: self.address,
+            receive_buffer_bytes: self.receive_buffer_bytes,
+            security: self.security()?,
+            mib: Mib::load(&self.mib_paths)?,
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct SnmpTrapSource {
+    address: SocketListenAddr,
+    receive_buffer_bytes: Option<usize>,
+    security: Security,
+    mib: Mib,
+    log_namespace: LogNamespace,
+}
+
+impl SnmpTrapSource {
+    async fn run(self, mut out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let socket = try_bind_udp_socket(self.address, ListenFd::from_env())
+            .await
+            .map_err(|error| {
+                emit!(SocketBindError {
+                    mode: SocketMode::Udp,
+                    error,
+                })
+            })?;
+
+        if let Some(receive_buffer_bytes) = self.receive_buffer_bytes {
+            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+            }
+        }
+
+        let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+        let events_received = register!(EventsReceived);
+
+        info!(message = "Listening.", address = %self.address);
+
+        let mut buffer = vec![0; MAX_MESSAGE_SIZE];
+        loop {
+            let (size, peer_addr) = tokio::select! {
+                received = socket.recv_from(&mut buffer) => match received {
+                    Ok(received) => received,
+                    Err(error) => {
+                        return Err(emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error
+                        }));
+                    }
+                },
+                _ = &mut shutdown => return Ok(()),
+            };
+            bytes_received.emit(ByteSize(size));
+
+            let trap = match message::decode(&buffer[..size], &self.security) {
+                Ok(trap) => trap,
+                Err(error) if error.is_rejected() => {
+                    emit!(SnmpTrapRejected { error, peer_addr });
+                    continue;
+                }
+                Err(error) => {
+                    emit!(SnmpTrapParseError { error, peer_addr });
+                    continue;
+                }
+            };
+
+            if let Some(response) = trap.response() {
+                if let Err(error) = socket.send_to(&response, peer_addr).await {
+                    warn!(
+                        message = "Failed to acknowledge SNMP inform request.",
+                        %error,
+                        %peer_addr,
+                        internal_log_rate_limit = true,
+                    );
+                }
+            }
+
+            let log = self.create_log_event(&trap, peer_addr);
+            events_received.emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+            if let Err(error) = out.send_event(log).await {
+                emit!(StreamClosedError { error, count: 1 });
+                return Err(());
+            }
+        }
+    }
+
+    fn create_log_event(&self, trap: &Trap, peer_addr: SocketAddr) -> LogEvent {
+        let mut fields = BTreeMap::new();
+        fields.insert("version".to_owned(), trap.version.as_str().into());
+        match &trap.credentials {
+            Credentials::Community(community) => {
+                fields.insert("community".to_owned(), community.as_str().into());
+            }
+            Credentials::User {
+                name,
+                engine_id,
+                context_name,
+            } => {
+                fields.insert("user".to_owned(), name.as_str().into());
+                fields.insert("engine_id".to_owned(), hex::encode(engine_id).into());
+                fields.insert("context_name".to_owned(), context_name.as_str().into());
+            }
+        }
+        fields.insert("inform".to_owned(), trap.inform.into());
+        if let Some(uptime) = trap.uptime {
+            fields.insert("uptime".to_owned(), unsigned_value(uptime));
+        }
+        if let Some(trap_oid) = &trap.trap_oid {
+            fields.insert("trap_oid".to_owned(), numeric_oid(trap_oid).into());
+            if let Some(name) = self.mib.name(trap_oid) {
+                fields.insert("trap_name".to_owned(), name.into());
+            }
+        }
+        if let Some(agent_address) = trap.agent_address {
+            fields.insert("agent_address".to_owned(), agent_address.to_string().into());
+        }
+
+        let varbinds = trap
+            .varbinds
+            .iter()
+            .map(|(oid, value)| {
+                let name = self.mib.name(oid).unwrap_or_else(|| numeric_oid(oid));
+                (name, self.varbind_value(value))
+            })
+            .collect::<BTreeMap<_, _>>();
+        fields.insert("varbinds".to_owned(), Value::Object(varbinds));
+
+        let mut log = LogEvent::from(fields);
+        self.log_namespace.insert_source_metadata(
+            SnmpTrapConfig::NAME,
+            &mut log,
+            parse_value_path(log_schema().host_key())
+                .ok()
+                .as_ref()
+                .map(LegacyKey::InsertIfEmpty),
+            path!("host"),
+            peer_addr.ip().to_string(),
+        );
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            SnmpTrapConfig::NAME,
+            Utc::now(),
+        );
+
+        log
+    }
+
+    fn varbind_value(&self, value: &VarValue) -> Value {
+        match value {
+            VarValue::Integer(value) => Value::from(*value),
+            VarValue::Unsigned(value) => unsigned_value(*value),
+            VarValue::OctetString(bytes) => octet_string_value(bytes),
+            VarValue::ObjectIdentifier(oid) => self
+                .mib
+                .name(oid)
+                .unwrap_or_else(|| numeric_oid(oid))
+                .into(),
+            VarValue::IpAddress(address) => Ipv4Addr::to_string(address).into(),
+            VarValue::Null => Value::Null,
+        }
+    }
+}
+
+/// Converts a counter, keeping the 64-bit counters beyond the range of integers as strings.
+fn unsigned_value(value: u64) -> Value {
+    match i64::try_from(value) {
+        Ok(value) => Value::from(value),
+        Err(_) => Value::from(value.to_string()),
+    }
+}
+
+/// Converts an octet string, which is either text, or binary data such as a MAC address, which is
+/// then formatted as colon-separated hexadecimal bytes.
+fn octet_string_value(bytes: &[u8]) -> Value {
+    match std::str::from_utf8(bytes) {
+        Ok(text)
+            if !text
+                .chars()
+                .any(|character| character.is_control() && !character.is_whitespace()) =>
+        {
+            Value::from(text)
+        }
+        _ => Value::from(
+            bytes
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(":"),
+        ),
+    }
+}
+
+fn numeric_oid(oid: &[u32]) -> String {
+    oid.iter().map(u32::to_string).collect::<Vec<_>>().join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{net::UdpSocket, time::timeout};
+
+    use super::{message::tests::*, mib::tests::IF_MIB, *};
+    use crate::test_util::{collect_n, next_addr};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpTrapConfig>();
+    }
+
+    #[test]
+    fn converts_octet_strings() {
+        assert_eq!(octet_string_value(b"eth0"), Value::from("eth0"));
+        assert_eq!(
+            octet_string_value(&[0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]),
+            Value::from("00:1a:2b:3c:4d:5e")
+        );
+    }
+
+    async fn start_source(
+        config: SnmpTrapConfig,
+    ) -> impl futures::Stream<Item = crate::event::Event> {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        rx
+    }
+
+    #[tokio::test]
+    async fn receives_traps() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("IF-MIB.txt"), IF_MIB).unwrap();
+        let address = next_addr();
+        let rx = start_source(SnmpTrapConfig {
+            address: address.into(),
+            communities: vec!["public".to_owned().into()],
+            mib_paths: vec![directory.path().to_owned()],
+            ..Default::default()
+        })
+        .await;
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        // A trap of another community, which is rejected.
+        let message = v2c_message("private", trap_pdu(0xa7, &link_down_varbinds()));
+        socket.send_to(&message, address).await.unwrap();
+        let message = v2c_message("public", trap_pdu(0xa7, &link_down_varbinds()));
+        socket.send_to(&message, address).await.unwrap();
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log["version"], Value::from("2c"));
+        assert_eq!(log["community"], Value::from("public"));
+        assert_eq!(log["inform"], Value::from(false));
+        assert_eq!(log["uptime"], Value::from(256));
+        assert_eq!(log["trap_oid"], Value::from("1.3.6.1.6.3.1.1.5.3"));
+        assert_eq!(log["trap_name"], Value::from("linkDown"));
+        assert_eq!(log[log_schema().host_key()], Value::from("127.0.0.1"));
+
+        let varbinds = log["varbinds"].as_object().unwrap();
+        assert_eq!(varbinds["ifIndex.7"], Value::from(7));
+        // `ifDescr` isn't defined in the MIB file.
+        assert_eq!(varbinds["ifEntry.2.7"], Value::from("eth0"));
+    }
+
+    #[tokio::test]
+    async fn acknowledges_informs() {
+        let address = next_addr();
+        let rx = start_source(SnmpTrapConfig {
+            address: address.into(),
+            ..Default::default()
+        })
+        .await;
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let message = v2c_message("public", trap_pdu(0xa6, &link_down_varbinds()));
+        socket.send_to(&message, address).await.unwrap();
+
+        let mut response = vec![0; MAX_MESSAGE_SIZE];
+        let (size, _) = timeout(Duration::from_secs(5), socket.recv_from(&mut response))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            &response[..size],
+            &v2c_message("public", trap_pdu(0xa2, &link_down_varbinds()))[..]
+        );
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(events[0].as_log()["inform"], Value::from(true));
+    }
+}
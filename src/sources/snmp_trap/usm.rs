@@ -0,0 +1,295 @@
+//! The User-based Security Model of SNMPv3, as defined in [RFC 3414][rfc3414] and
+//! [RFC 3826][rfc3826].
+//!
+//! [rfc3414]: https://datatracker.ietf.org/doc/html/rfc3414
+//! [rfc3826]: https://datatracker.ietf.org/doc/html/rfc3826
+use openssl::{
+    error::ErrorStack,
+    hash::{Hasher, MessageDigest},
+    pkey::PKey,
+    sign::Signer,
+    symm::{Cipher, Crypter, Mode},
+};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+/// The length of the authentication parameters of the messages, which hold the truncated HMAC.
+pub(super) const AUTHENTICATION_LENGTH: usize = 12;
+
+/// How many bytes of the passphrase are hashed to derive the keys.
+const PASSPHRASE_HASH_LENGTH: usize = 1024 * 1024;
+
+/// The authentication protocol of an SNMPv3 user.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthenticationProtocol {
+    /// HMAC-MD5-96.
+    Md5,
+
+    /// HMAC-SHA-96.
+    Sha,
+}
+
+impl AuthenticationProtocol {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Md5 => MessageDigest::md5(),
+            Self::Sha => MessageDigest::sha1(),
+        }
+    }
+}
+
+/// The privacy protocol of an SNMPv3 user.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyProtocol {
+    /// CBC-DES.
+    Des,
+
+    /// CFB128-AES-128.
+    Aes,
+}
+
+/// An SNMPv3 user whose traps are accepted.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpUser {
+    /// The name of the user.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub name: String,
+
+    #[configurable(derived)]
+    pub authentication_protocol: Option<AuthenticationProtocol>,
+
+    /// The passphrase the authentication key of the user is derived from.
+    ///
+    /// Required when `authentication_protocol` is set.
+    #[configurable(metadata(docs::examples = "${SNMP_AUTH_PASSPHRASE}"))]
+    pub authentication_passphrase: Option<SensitiveString>,
+
+    #[configurable(derived)]
+    pub privacy_protocol: Option<PrivacyProtocol>,
+
+    /// The passphrase the privacy key of the user is derived from.
+    ///
+    /// Required when `privacy_protocol` is set.
+    #[configurable(metadata(docs::examples = "${SNMP_PRIVACY_PASSPHRASE}"))]
+    pub privacy_passphrase: Option<SensitiveString>,
+}
+
+/// The keys of a user, derived from their passphrases, before they are localized to the engine
+/// sending the traps.
+pub(super) struct UserKeys {
+    authentication: Option<(AuthenticationProtocol, Vec<u8>)>,
+    privacy: Option<(PrivacyProtocol, Vec<u8>)>,
+}
+
+impl UserKeys {
+    pub(super) fn new(user: &SnmpUser) -> crate::Result<Self> {
+        let authentication = match (
+            user.authentication_protocol,
+            &user.authentication_passphrase,
+        ) {
+            (Some(protocol), Some(passphrase)) => {
+                Some((protocol, password_to_key(protocol, passphrase.inner())?))
+            }
+            (Some(_), None) => {
+                return Err(format!(
+                    "The SNMP user {:?} has no `authentication_passphrase`.",
+                    user.name
+                )
+                .into())
+            }
+            (None, _) => None,
+        };
+
+        let privacy = match (
+            user.privacy_protocol,
+            &user.privacy_passphrase,
+            &authentication,
+        ) {
+            (Some(protocol), Some(passphrase), Some((authentication, _))) => {
+                // The privacy key is derived with the hash of the authentication protocol.
+                Some((
+                    protocol,
+                    password_to_key(*authentication, passphrase.inner())?,
+                ))
+            }
+            (Some(_), _, None) => {
+                return Err(format!(
+                    "The SNMP user {:?} needs an `authentication_protocol` for privacy.",
+                    user.name
+                )
+                .into())
+            }
+            (Some(_), None, _) => {
+                return Err(
+                    format!("The SNMP user {:?} has no `privacy_passphrase`.", user.name).into(),
+                )
+            }
+            (None, _, _) => None,
+        };
+
+        Ok(Self {
+            authentication,
+            privacy,
+        })
+    }
+
+    pub(super) const fn authenticates(&self) -> bool {
+        self.authentication.is_some()
+    }
+
+    pub(super) const fn encrypts(&self) -> bool {
+        self.privacy.is_some()
+    }
+
+    /// Checks the authentication parameters of a message, which are zeroed in it.
+    pub(super) fn authenticate(
+        &self,
+        engine_id: &[u8],
+        zeroed_message: &[u8],
+        parameters: &[u8],
+    ) -> Result<bool, ErrorStack> {
+        let (protocol, key) = match &self.authentication {
+            Some(authentication) => authentication,
+            None => return Ok(false),
+        };
+        if parameters.len() != AUTHENTICATION_LENGTH {
+            return Ok(false);
+        }
+
+        let key = localize_key(*protocol, key, engine_id)?;
+        let mac = hmac(*protocol, &key, zeroed_message)?;
+        Ok(openssl::memcmp::eq(
+            &mac[..AUTHENTICATION_LENGTH],
+            parameters,
+        ))
+    }
+
+    /// Decrypts the scoped PDU of a message.
+    pub(super) fn decrypt(
+        &self,
+        engine_id: &[u8],
+        engine_boots: u32,
+        engine_time: u32,
+        salt: &[u8],
+        encrypted: &[u8],
+    ) -> Result<Option<Vec<u8>>, ErrorStack> {
+        let ((protocol, key), (authentication, _)) = match (&self.privacy, &self.authentication) {
+            (Some(privacy), Some(authentication)) => (privacy, authentication),
+            _ => return Ok(None),
+        };
+        if salt.len() != 8 {
+            return Ok(None);
+        }
+        let key = localize_key(*authentication, key, engine_id)?;
+
+        let (cipher, key, iv) = match protocol {
+            PrivacyProtocol::Des => {
+                if key.len() < 16 || encrypted.len() % 8 != 0 {
+                    return Ok(None);
+                }
+                let iv = key[8..16]
+                    .iter()
+                    .zip(salt)
+                    .map(|(pre_iv, salt)| pre_iv ^ salt)
+                    .collect::<Vec<_>>();
+                (Cipher::des_cbc(), &key[..8], iv)
+            }
+            PrivacyProtocol::Aes => {
+                let mut iv = Vec::with_capacity(16);
+                iv.extend_from_slice(&engine_boots.to_be_bytes());
+                iv.extend_from_slice(&engine_time.to_be_bytes());
+                iv.extend_from_slice(salt);
+                (Cipher::aes_128_cfb128(), &key[..16], iv)
+            }
+        };
+
+        let mut crypter = Crypter::new(cipher, Mode::Decrypt, key, Some(&iv))?;
+        crypter.pad(false);
+        let mut decrypted = vec![0; encrypted.len() + cipher.block_size()];
+        let mut length = crypter.update(encrypted, &mut decrypted)?;
+        length += crypter.finalize(&mut decrypted[length..])?;
+        decrypted.truncate(length);
+        Ok(Some(decrypted))
+    }
+}
+
+/// Derives a key from a passphrase, by hashing a megabyte of the repeated passphrase.
+pub(super) fn password_to_key(
+    protocol: AuthenticationProtocol,
+    passphrase: &str,
+) -> crate::Result<Vec<u8>> {
+    if passphrase.len() < 8 {
+        return Err("SNMP passphrases must be at least 8 characters long.".into());
+    }
+
+    let mut hasher = Hasher::new(protocol.digest())?;
+    let repeated = passphrase
+        .as_bytes()
+        .iter()
+        .cycle()
+        .take(PASSPHRASE_HASH_LENGTH)
+        .copied()
+        .collect::<Vec<_>>();
+    hasher.update(&repeated)?;
+    Ok(hasher.finish()?.to_vec())
+}
+
+/// Localizes a key to the engine it's used with.
+pub(super) fn localize_key(
+    protocol: AuthenticationProtocol,
+    key: &[u8],
+    engine_id: &[u8],
+) -> Result<Vec<u8>, ErrorStack> {
+    let mut hasher = Hasher::new(protocol.digest())?;
+    hasher.update(key)?;
+    hasher.update(engine_id)?;
+    hasher.update(key)?;
+    Ok(hasher.finish()?.to_vec())
+}
+
+pub(super) fn hmac(
+    protocol: AuthenticationProtocol,
+    key: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>, ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(protocol.digest(), &key)?;
+    signer.update(message)?;
+    signer.sign_to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    // The sample keys of appendix A.3 of RFC 3414.
+    #[test]
+    fn derives_md5_keys() {
+        let key = password_to_key(AuthenticationProtocol::Md5, "maplesyrup").unwrap();
+        assert_eq!(hex(&key), "9faf3283884e92834ebc9847d8edd963");
+
+        let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+        let key = localize_key(AuthenticationProtocol::Md5, &key, &engine_id).unwrap();
+        assert_eq!(hex(&key), "526f5eed9fcce26f8964c2930787d82b");
+    }
+
+    #[test]
+    fn derives_sha_keys() {
+        let key = password_to_key(AuthenticationProtocol::Sha, "maplesyrup").unwrap();
+        assert_eq!(hex(&key), "9fb5cc0381497b3793528939ff788d5d79145211");
+
+        let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+        let key = localize_key(AuthenticationProtocol::Sha, &key, &engine_id).unwrap();
+        assert_eq!(hex(&key), "6695febc9288e36282235fc7151f128497b38f3f");
+    }
+}
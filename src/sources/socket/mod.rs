@@ -12,7 +12,7 @@ use vector_core::config::{log_schema, LegacyKey, LogNamespace};
 #[cfg(unix)]
 use crate::serde::default_framing_message_based;
 use crate::{
-    codecs::DecodingConfig,
+    codecs::{DecodeErrorPolicy, DecodingConfig},
     config::{GenerateConfig, Output, Resource, SourceConfig, SourceContext},
     sources::util::net::TcpSource,
     tls::MaybeTlsSettings,
@@ -69,6 +69,17 @@ impl SocketConfig {
         }
     }
 
+    fn on_decode_error(&self) -> DecodeErrorPolicy {
+        match &self.mode {
+            Mode::Tcp(config) => config.on_decode_error(),
+            Mode::Udp(config) => config.on_decode_error(),
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => config.on_decode_error(),
+            #[cfg(unix)]
+            Mode::UnixStream(config) => config.on_decode_error(),
+        }
+    }
+
     fn log_namespace(&self) -> LogNamespace {
         match &self.mode {
             Mode::Tcp(config) => config.log_namespace.unwrap_or(false).into(),
@@ -131,7 +142,10 @@ impl SourceConfig for SocketConfig {
                 };
 
                 let log_namespace = cx.log_namespace(config.log_namespace);
-                let decoder = DecodingConfig::new(framing, decoding, log_namespace).build();
+                let decoder = DecodingConfig::new(framing, decoding, log_namespace)
+                    .with_error_policy(config.on_decode_error())
+                    .with_pass_through(cx.pass_through)
+                    .build()?;
 
                 let tcp = tcp::RawTcpSource::new(config.clone(), decoder, log_namespace);
                 let tls_config = config.tls().as_ref().map(|tls| tls.tls_config.clone());
@@ -151,7 +165,8 @@ impl SourceConfig for SocketConfig {
                     config.max_connection_duration_secs(),
                     cx,
                     false.into(),
-                    config.connection_limit,
+                    config.connection_limits(),
+                    config.proxy_protocol(),
                     SocketConfig::NAME,
                     log_namespace,
                 )
@@ -163,7 +178,9 @@ impl SourceConfig for SocketConfig {
                     config.decoding().clone(),
                     log_namespace,
                 )
-                .build();
+                .with_error_policy(config.on_decode_error())
+                .with_pass_through(cx.pass_through)
+                .build()?;
                 Ok(udp::udp(
                     config,
                     decoder,
@@ -183,7 +200,9 @@ impl SourceConfig for SocketConfig {
                     config.decoding.clone(),
                     log_namespace,
                 )
-                .build();
+                .with_error_policy(config.on_decode_error())
+                .with_pass_through(cx.pass_through)
+                .build()?;
 
                 unix::unix_datagram(config, decoder, cx.shutdown, cx.out, log_namespace)
             }
@@ -209,7 +228,10 @@ impl SourceConfig for SocketConfig {
                 };
 
                 let log_namespace = cx.log_namespace(config.log_namespace);
-                let decoder = DecodingConfig::new(framing, decoding, log_namespace).build();
+                let decoder = DecodingConfig::new(framing, decoding, log_namespace)
+                    .with_error_policy(config.on_decode_error())
+                    .with_pass_through(cx.pass_through)
+                    .build()?;
 
                 unix::unix_stream(config, decoder, cx.shutdown, cx.out, log_namespace)
             }
@@ -308,8 +330,10 @@ impl SourceConfig for SocketConfig {
             }
         };
 
-        vec![Output::default(self.decoding().output_type())
-            .with_schema_definition(schema_definition)]
+        let mut outputs = vec![Output::default(self.decoding().output_type())
+            .with_schema_definition(schema_definition)];
+        outputs.extend(self.on_decode_error().output(log_namespace));
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -326,6 +350,10 @@ impl SourceConfig for SocketConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn pass_through_decoding(&self) -> Option<DeserializerConfig> {
+        Some(self.decoding())
+    }
 }
 
 pub(crate) fn default_host_key() -> OptionalValuePath {
@@ -386,7 +414,7 @@ mod test {
         event::{Event, LogEvent},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
-        sources::util::net::SocketListenAddr,
+        sources::util::net::{ConnectionLimitPolicy, SocketListenAddr},
         test_util::{
             collect_n, collect_n_limited,
             components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
@@ -427,6 +455,36 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn tcp_it_includes_proxied_host() {
+        use tokio::io::AsyncWriteExt;
+
+        let (tx, mut rx) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_proxy_protocol(true);
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\ntest\n")
+            .await
+            .unwrap();
+        stream.shutdown().await.unwrap();
+
+        let event = rx.next().await.unwrap();
+
+        assert_eq!(event.as_log()["message"], "test".into());
+        assert_eq!(event.as_log()["host"], "192.0.2.1".into());
+        assert_eq!(event.as_log()["port"], 56324.into());
+    }
+
     #[tokio::test]
     async fn tcp_it_includes_vector_namespaced_fields() {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
@@ -855,6 +913,44 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn tcp_rejects_connections_over_limit_per_ip() {
+        let (tx, _) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let mut source_config = TcpConfig::from_address(addr.into());
+        source_config
+            .set_connection_limit_per_ip(Some(1))
+            .set_connection_limit_policy(ConnectionLimitPolicy::Reject);
+        let source_task = SocketConfig::from(source_config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+
+        // Spawn the source task and wait until we're sure it's listening:
+        drop(tokio::spawn(source_task));
+        wait_for_tcp(addr).await;
+        // Leaves time for the connection checking the source is listening to be closed.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let _first: TcpStream = TcpStream::connect(addr)
+            .await
+            .expect("stream should be able to connect");
+        // Leaves time for the first connection to be counted.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut second: TcpStream = TcpStream::connect(addr)
+            .await
+            .expect("stream should be able to connect");
+
+        let mut buffer = [0u8; 10];
+        match timeout(Duration::from_secs(1), second.read(&mut buffer)).await {
+            // read resulting with 0 bytes -> the connection was closed
+            Ok(Ok(0)) => {}
+            Ok(Err(error)) if error.kind() == std::io::ErrorKind::ConnectionReset => {}
+            result => panic!("the connection wasn't rejected: {:?}", result),
+        }
+    }
+
     //////// UDP TESTS ////////
     fn send_lines_udp(addr: SocketAddr, lines: impl IntoIterator<Item = String>) -> SocketAddr {
         let bind = next_addr();
@@ -951,6 +1047,8 @@ mod test {
                 acknowledgements: false,
                 schema: Default::default(),
                 schema_definitions: HashMap::default(),
+                pass_through: false,
+                linked: false,
             })
             .await
             .unwrap();
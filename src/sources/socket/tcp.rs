@@ -9,10 +9,12 @@ use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 
 use crate::{
-    codecs::Decoder,
+    codecs::{DecodeErrorPolicy, Decoder},
     event::Event,
     serde::default_decoding,
-    sources::util::net::{SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::net::{
+        ConnectionLimitPolicy, SocketListenAddr, TcpConnectionLimits, TcpNullAcker, TcpSource,
+    },
     tcp::TcpKeepaliveConfig,
     tls::TlsSourceConfig,
 };
@@ -84,6 +86,38 @@ pub struct TcpConfig {
     #[configurable(metadata(docs::type_unit = "connections"))]
     pub connection_limit: Option<u32>,
 
+    /// The maximum number of TCP connections that are allowed at any given time from a single IP
+    /// address.
+    ///
+    /// With `proxy_protocol`, the address of the client is used rather than the one of the load
+    /// balancer.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    #[configurable(validation(range(min = 1)))]
+    connection_limit_per_ip: Option<u32>,
+
+    /// What to do with the connections over `connection_limit` or `connection_limit_per_ip`.
+    #[serde(default)]
+    connection_limit_policy: ConnectionLimitPolicy,
+
+    /// The maximum rate at which the data of each connection is read.
+    ///
+    /// The connections sending data faster aren't read from until they're back under the rate,
+    /// slowing their client down through TCP backpressure.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(validation(range(min = 1)))]
+    max_connection_bytes_per_sec: Option<u64>,
+
+    /// Whether the connections start with a PROXY protocol header.
+    ///
+    /// Load balancers such as HAProxy or AWS Network Load Balancers send the header ahead of the
+    /// connections they proxy, to pass on the address of the client. The address of the client
+    /// is then used as the peer address of the events, instead of the address of the load balancer.
+    ///
+    /// Both versions of the protocol are supported. When enabled, connections without the header
+    /// are closed. With TLS, the header is expected ahead of the TLS handshake.
+    #[serde(default)]
+    proxy_protocol: bool,
+
     #[configurable(derived)]
     framing: Option<FramingConfig>,
 
@@ -91,6 +125,10 @@ pub struct TcpConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -119,7 +157,12 @@ impl TcpConfig {
             max_connection_duration_secs: None,
             framing: None,
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_limit_policy: ConnectionLimitPolicy::default(),
+            max_connection_bytes_per_sec: None,
+            proxy_protocol: false,
             log_namespace: None,
         }
     }
@@ -144,6 +187,10 @@ impl TcpConfig {
         &self.decoding
     }
 
+    pub const fn on_decode_error(&self) -> DecodeErrorPolicy {
+        self.on_decode_error
+    }
+
     pub const fn address(&self) -> SocketListenAddr {
         self.address
     }
@@ -168,11 +215,39 @@ impl TcpConfig {
         self.max_connection_duration_secs
     }
 
+    pub const fn proxy_protocol(&self) -> bool {
+        self.proxy_protocol
+    }
+
+    pub const fn connection_limits(&self) -> TcpConnectionLimits {
+        TcpConnectionLimits {
+            max_connections: self.connection_limit,
+            max_connections_per_ip: self.connection_limit_per_ip,
+            max_bytes_per_sec: self.max_connection_bytes_per_sec,
+            policy: self.connection_limit_policy,
+        }
+    }
+
     pub fn set_max_connection_duration_secs(&mut self, val: Option<u64>) -> &mut Self {
         self.max_connection_duration_secs = val;
         self
     }
 
+    pub fn set_proxy_protocol(&mut self, val: bool) -> &mut Self {
+        self.proxy_protocol = val;
+        self
+    }
+
+    pub fn set_connection_limit_per_ip(&mut self, val: Option<u32>) -> &mut Self {
+        self.connection_limit_per_ip = val;
+        self
+    }
+
+    pub fn set_connection_limit_policy(&mut self, val: ConnectionLimitPolicy) -> &mut Self {
+        self.connection_limit_policy = val;
+        self
+    }
+
     pub fn set_max_length(&mut self, val: Option<usize>) -> &mut Self {
         self.max_length = val;
         self
@@ -236,6 +311,10 @@ impl TcpSource for RawTcpSource {
 
         for event in events {
             if let Event::Log(ref mut log) = event {
+                if log.metadata().is_pass_through() {
+                    continue;
+                }
+
                 self.log_namespace.insert_standard_vector_source_metadata(
                     log,
                     SocketConfig::NAME,
@@ -16,7 +16,7 @@ use vector_core::{
 };
 
 use crate::{
-    codecs::Decoder,
+    codecs::{DecodeErrorPolicy, Decoder},
     config::log_schema,
     event::Event,
     internal_events::{
@@ -81,6 +81,10 @@ pub struct UdpConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_decode_error: DecodeErrorPolicy,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -116,6 +120,10 @@ impl UdpConfig {
         &self.decoding
     }
 
+    pub(super) const fn on_decode_error(&self) -> DecodeErrorPolicy {
+        self.on_decode_error
+    }
+
     pub(super) const fn address(&self) -> SocketListenAddr {
         self.address
     }
@@ -129,6 +137,7 @@ impl UdpConfig {
             receive_buffer_bytes: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             log_namespace: None,
         }
     }
@@ -240,6 +249,10 @@ pub(super) fn udp(
 
                                 for event in &mut events {
                                     if let Event::Log(ref mut log) = event {
+                                        if log.metadata().is_pass_through() {
+                                            continue;
+                                        }
+
                                         log_namespace.insert_standard_vector_source_metadata(
                                             log,
                                             SocketConfig::NAME,
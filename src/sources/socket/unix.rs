@@ -9,7 +9,7 @@ use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 
 use crate::{
-    codecs::Decoder,
+    codecs::{DecodeErrorPolicy, Decoder},
     event::Event,
     serde::default_decoding,
     sources::{
@@ -71,6 +71,10 @@ pub struct UnixConfig {
     #[serde(default = "default_decoding")]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_decode_error: DecodeErrorPolicy,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -86,6 +90,7 @@ impl UnixConfig {
             host_key: default_host_key(),
             framing: None,
             decoding: default_decoding(),
+            on_decode_error: DecodeErrorPolicy::default(),
             log_namespace: None,
         }
     }
@@ -94,6 +99,10 @@ impl UnixConfig {
         &self.decoding
     }
 
+    pub const fn on_decode_error(&self) -> DecodeErrorPolicy {
+        self.on_decode_error
+    }
+
     pub const fn host_key(&self) -> &OptionalValuePath {
         &self.host_key
     }
@@ -111,6 +120,9 @@ fn handle_events(
 
     for event in events {
         let log = event.as_mut_log();
+        if log.metadata().is_pass_through() {
+            continue;
+        }
 
         log_namespace.insert_standard_vector_source_metadata(log, SocketConfig::NAME, now);
 
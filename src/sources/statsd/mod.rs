@@ -18,7 +18,9 @@ use vector_config::configurable_component;
 use vector_core::EstimatedJsonEncodedSizeOf;
 
 use self::parser::ParseError;
-use super::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource};
+use super::util::net::{
+    try_bind_udp_socket, SocketListenAddr, TcpConnectionLimits, TcpNullAcker, TcpSource,
+};
 use crate::{
     codecs::Decoder,
     config::{self, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
@@ -164,7 +166,8 @@ impl SourceConfig for StatsdConfig {
                     None,
                     cx,
                     false.into(),
-                    config.connection_limit,
+                    TcpConnectionLimits::new(config.connection_limit),
+                    false,
                     StatsdConfig::NAME,
                     LogNamespace::Legacy,
                 )
@@ -28,7 +28,10 @@ use crate::{
     internal_events::StreamClosedError,
     internal_events::{SocketBindError, SocketMode, SocketReceiveError},
     shutdown::ShutdownSignal,
-    sources::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::net::{
+        try_bind_udp_socket, ConnectionLimitPolicy, SocketListenAddr, TcpConnectionLimits,
+        TcpNullAcker, TcpSource,
+    },
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsSourceConfig},
     udp, SourceSender,
@@ -89,6 +92,37 @@ pub enum Mode {
 
         /// The maximum number of TCP connections that are allowed at any given time.
         connection_limit: Option<u32>,
+
+        /// The maximum number of TCP connections that are allowed at any given time from a single
+        /// IP address.
+        ///
+        /// With `proxy_protocol`, the address of the client is used rather than the one of the
+        /// load balancer.
+        #[configurable(validation(range(min = 1)))]
+        connection_limit_per_ip: Option<u32>,
+
+        /// What to do with the connections over `connection_limit` or `connection_limit_per_ip`.
+        #[serde(default)]
+        connection_limit_policy: ConnectionLimitPolicy,
+
+        /// The maximum rate at which the data of each connection is read.
+        ///
+        /// The connections sending data faster aren't read from until they're back under the rate,
+        /// slowing their client down through TCP backpressure.
+        #[configurable(metadata(docs::type_unit = "bytes"))]
+        #[configurable(validation(range(min = 1)))]
+        max_connection_bytes_per_sec: Option<u64>,
+
+        /// Whether the connections start with a PROXY protocol header.
+        ///
+        /// Load balancers such as HAProxy or AWS Network Load Balancers send the header ahead of the
+        /// connections they proxy, to pass on the address of the client. The address of the client
+        /// is then used as the peer address of the events, instead of the address of the load balancer.
+        ///
+        /// Both versions of the protocol are supported. When enabled, connections without the header
+        /// are closed. With TLS, the header is expected ahead of the TLS handshake.
+        #[serde(default)]
+        proxy_protocol: bool,
     },
 
     /// Listen on UDP.
@@ -141,6 +175,10 @@ impl Default for SyslogConfig {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                connection_limit_per_ip: None,
+                connection_limit_policy: ConnectionLimitPolicy::default(),
+                max_connection_bytes_per_sec: None,
+                proxy_protocol: false,
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
@@ -171,6 +209,10 @@ impl SourceConfig for SyslogConfig {
                 tls,
                 receive_buffer_bytes,
                 connection_limit,
+                connection_limit_per_ip,
+                connection_limit_policy,
+                max_connection_bytes_per_sec,
+                proxy_protocol,
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
@@ -194,7 +236,13 @@ impl SourceConfig for SyslogConfig {
                     None,
                     cx,
                     false.into(),
-                    connection_limit,
+                    TcpConnectionLimits {
+                        max_connections: connection_limit,
+                        max_connections_per_ip: connection_limit_per_ip,
+                        max_bytes_per_sec: max_connection_bytes_per_sec,
+                        policy: connection_limit_policy,
+                    },
+                    proxy_protocol,
                     SyslogConfig::NAME,
                     log_namespace,
                 )
@@ -1078,6 +1126,10 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                connection_limit_per_ip: None,
+                connection_limit_policy: ConnectionLimitPolicy::default(),
+                max_connection_bytes_per_sec: None,
+                proxy_protocol: false,
             });
 
             let key = ComponentKey::from("in");
@@ -1221,6 +1273,10 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                connection_limit_per_ip: None,
+                connection_limit_policy: ConnectionLimitPolicy::default(),
+                max_connection_bytes_per_sec: None,
+                proxy_protocol: false,
             });
 
             let key = ComponentKey::from("in");
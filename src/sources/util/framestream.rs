@@ -564,17 +564,20 @@ fn spawn_event_handling_tasks(
 ) -> JoinHandle<()> {
     wait_for_task_quota(&active_task_nums, max_frame_handling_tasks);
 
-    tokio::spawn(async move {
-        future::ready({
-            if let Some(evt) = event_handler.handle_event(received_from, event_data) {
-                if event_sink.send_event(evt).await.is_err() {
-                    error!("Encountered error while sending event.");
+    tokio::spawn(
+        async move {
+            future::ready({
+                if let Some(evt) = event_handler.handle_event(received_from, event_data) {
+                    if event_sink.send_event(evt).await.is_err() {
+                        error!("Encountered error while sending event.");
+                    }
                 }
-            }
-            active_task_nums.fetch_sub(1, Ordering::AcqRel);
-        })
-        .await;
-    })
+                active_task_nums.fetch_sub(1, Ordering::AcqRel);
+            })
+            .await;
+        }
+        .in_current_span(),
+    )
 }
 
 fn wait_for_task_quota(active_task_nums: &Arc<AtomicU32>, max_tasks: u32) {
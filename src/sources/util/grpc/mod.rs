@@ -8,7 +8,7 @@ use hyper::Body;
 use std::{convert::Infallible, net::SocketAddr};
 use tonic::{
     body::BoxBody,
-    transport::server::{NamedService, Server},
+    transport::server::{NamedService, Routes, Server},
 };
 use tower::Service;
 use tracing::{Instrument, Span};
@@ -58,3 +58,75 @@ where
 
     Ok(())
 }
+
+/// Runs a gRPC server serving several services, routed by their names.
+pub async fn run_grpc_server_with_routes(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    routes: Routes,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let span = Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream();
+
+    info!(%address, "Building gRPC server.");
+
+    Server::builder()
+        .trace_fn(move |_| span.clone())
+        // See `run_grpc_server` for why decompression and metrics are handled by a single layer.
+        .layer(DecompressionAndMetricsLayer::default())
+        .add_routes(routes)
+        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .in_current_span()
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
+
+/// Runs a gRPC server with a service that routes the requests itself.
+///
+/// This is used for services whose methods are only known at runtime, which `tonic` can't route as
+/// they don't implement `NamedService`.
+#[cfg(feature = "sources-grpc_server")]
+pub async fn run_grpc_server_with_service<S>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    service: S,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    let span = Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream();
+
+    info!(%address, "Building gRPC server.");
+
+    // See `run_grpc_server` for why decompression and metrics are handled by a single layer.
+    let service = tower::Layer::layer(&DecompressionAndMetricsLayer::default(), service);
+    let make_service = hyper::service::make_service_fn(move |_| {
+        let service = service.clone();
+        async move { Ok::<_, Infallible>(service) }
+    });
+
+    hyper::Server::builder(hyper::server::accept::from_stream(stream))
+        .http2_only(true)
+        .serve(make_service)
+        .with_graceful_shutdown(shutdown.map(|token| tx.send(token).unwrap()))
+        .instrument(span)
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
@@ -3,48 +3,170 @@ use std::io::Read;
 use bytes::{Buf, Bytes};
 use flate2::read::{MultiGzDecoder, ZlibDecoder};
 use snap::raw::Decoder as SnappyDecoder;
+use vector_config::configurable_component;
 use warp::http::StatusCode;
 
 use super::error::ErrorMessage;
 use crate::internal_events::HttpDecompressError;
 
-pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMessage> {
-    if let Some(encodings) = header {
-        for encoding in encodings.rsplit(',').map(str::trim) {
-            body = match encoding {
-                "identity" => body,
-                "gzip" => {
-                    let mut decoded = Vec::new();
-                    MultiGzDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
-                }
-                "deflate" => {
-                    let mut decoded = Vec::new();
-                    ZlibDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
-                }
-                "snappy" => SnappyDecoder::new()
-                    .decompress_vec(&body)
-                    .map_err(|error| handle_decode_error(encoding, error))?
-                    .into(),
-                "zstd" => zstd::decode_all(body.reader())
-                    .map_err(|error| handle_decode_error(encoding, error))?
-                    .into(),
-                encoding => {
-                    return Err(ErrorMessage::new(
-                        StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                        format!("Unsupported encoding {}", encoding),
-                    ))
-                }
+/// A content encoding that request bodies can be compressed with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentEncoding {
+    /// No compression.
+    Identity,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Deflate][deflate] compression, in the zlib format.
+    ///
+    /// [deflate]: https://en.wikipedia.org/wiki/DEFLATE
+    Deflate,
+
+    /// [Snappy][snappy] compression, in the raw format.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    Snappy,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+impl ContentEncoding {
+    fn from_header_value(value: &str) -> Option<Self> {
+        match value {
+            "identity" => Some(Self::Identity),
+            "gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "snappy" => Some(Self::Snappy),
+            "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration for decompressing request bodies, according to their `Content-Encoding` header.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DecompressionConfig {
+    /// The content encodings that request bodies are accepted with.
+    ///
+    /// Requests with any other encoding are rejected with a `415 Unsupported Media Type` response.
+    /// By default, all supported encodings are accepted.
+    #[configurable(metadata(docs::examples = "gzip", docs::examples = "zstd"))]
+    pub allowed_encodings: Option<Vec<ContentEncoding>>,
+
+    /// The maximum size, in bytes, of a request body once decompressed.
+    ///
+    /// Requests whose body decompresses to more than this are rejected with a
+    /// `413 Payload Too Large` response, without being decompressed any further. By default, the
+    /// size of decompressed bodies isn't limited.
+    #[configurable(metadata(docs::examples = 10485760))]
+    pub max_decompressed_size_bytes: Option<usize>,
+}
+
+static DEFAULT_DECOMPRESSION: DecompressionConfig = DecompressionConfig {
+    allowed_encodings: None,
+    max_decompressed_size_bytes: None,
+};
+
+impl DecompressionConfig {
+    /// Returns the default configuration, which accepts all encodings without limiting the size of
+    /// decompressed bodies.
+    pub fn default_ref() -> &'static Self {
+        &DEFAULT_DECOMPRESSION
+    }
+
+    fn is_allowed(&self, encoding: ContentEncoding) -> bool {
+        self.allowed_encodings
+            .as_ref()
+            .map_or(true, |allowed| allowed.contains(&encoding))
+    }
+
+    /// Decompresses the body, applying the encodings listed in the header in reverse order.
+    pub fn decode(&self, header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMessage> {
+        if let Some(encodings) = header {
+            for encoding in encodings.rsplit(',').map(str::trim) {
+                let content_encoding = ContentEncoding::from_header_value(encoding)
+                    .filter(|content_encoding| self.is_allowed(*content_encoding))
+                    .ok_or_else(|| {
+                        ErrorMessage::new(
+                            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                            format!("Unsupported encoding {}", encoding),
+                        )
+                    })?;
+
+                body = match content_encoding {
+                    ContentEncoding::Identity => body,
+                    ContentEncoding::Gzip => {
+                        self.read_to_end(encoding, MultiGzDecoder::new(body.reader()))?
+                    }
+                    ContentEncoding::Deflate => {
+                        self.read_to_end(encoding, ZlibDecoder::new(body.reader()))?
+                    }
+                    ContentEncoding::Snappy => {
+                        // The decompressed length is stored up front, so it can be checked without
+                        // decompressing anything.
+                        let len = snap::raw::decompress_len(&body)
+                            .map_err(|error| handle_decode_error(encoding, error))?;
+                        self.check_size(len)?;
+                        SnappyDecoder::new()
+                            .decompress_vec(&body)
+                            .map_err(|error| handle_decode_error(encoding, error))?
+                            .into()
+                    }
+                    ContentEncoding::Zstd => {
+                        let decoder = zstd::stream::read::Decoder::new(body.reader())
+                            .map_err(|error| handle_decode_error(encoding, error))?;
+                        self.read_to_end(encoding, decoder)?
+                    }
+                };
             }
         }
+
+        Ok(body)
+    }
+
+    /// Reads the decompressed body, stopping as soon as it exceeds the maximum size.
+    fn read_to_end(&self, encoding: &str, reader: impl Read) -> Result<Bytes, ErrorMessage> {
+        // Reading a single byte past the maximum size is enough to tell that it was exceeded.
+        let limit = self
+            .max_decompressed_size_bytes
+            .map_or(u64::MAX, |max_size| (max_size as u64).saturating_add(1));
+
+        let mut decoded = Vec::new();
+        reader
+            .take(limit)
+            .read_to_end(&mut decoded)
+            .map_err(|error| handle_decode_error(encoding, error))?;
+        self.check_size(decoded.len())?;
+
+        Ok(decoded.into())
+    }
+
+    fn check_size(&self, size: usize) -> Result<(), ErrorMessage> {
+        match self.max_decompressed_size_bytes {
+            Some(max_size) if size > max_size => Err(ErrorMessage::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!(
+                    "Decompressed payload exceeds the maximum size of {} bytes.",
+                    max_size
+                ),
+            )),
+            _ => Ok(()),
+        }
     }
+}
 
-    Ok(body)
+pub fn decode(header: &Option<String>, body: Bytes) -> Result<Bytes, ErrorMessage> {
+    DecompressionConfig::default_ref().decode(header, body)
 }
 
 fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMessage {
@@ -57,3 +179,64 @@ fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMe
         format!("Failed decompressing payload with {} decoder.", encoding),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    fn gzip(data: &[u8]) -> Bytes {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap().into()
+    }
+
+    #[test]
+    fn decodes_allowed_encodings() {
+        let body = Bytes::from(zstd::encode_all(&gzip(b"hello")[..], 0).unwrap());
+        let header = Some("gzip, zstd".to_owned());
+
+        assert_eq!(decode(&header, body.clone()).unwrap(), "hello");
+
+        let config = DecompressionConfig {
+            allowed_encodings: Some(vec![ContentEncoding::Gzip]),
+            ..Default::default()
+        };
+        let error = config.decode(&header, body).unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn limits_decompressed_size() {
+        let data = vec![b'a'; 1024];
+        let config = DecompressionConfig {
+            max_decompressed_size_bytes: Some(1024),
+            ..Default::default()
+        };
+
+        for (encoding, body) in [
+            ("gzip", gzip(&data)),
+            ("zstd", zstd::encode_all(&data[..], 0).unwrap().into()),
+            (
+                "snappy",
+                snap::raw::Encoder::new()
+                    .compress_vec(&data)
+                    .unwrap()
+                    .into(),
+            ),
+        ] {
+            let header = Some(encoding.to_owned());
+            assert_eq!(config.decode(&header, body.clone()).unwrap().len(), 1024);
+
+            let config = DecompressionConfig {
+                max_decompressed_size_bytes: Some(1023),
+                ..Default::default()
+            };
+            let error = config.decode(&header, body).unwrap_err();
+            assert_eq!(error.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+        }
+    }
+}
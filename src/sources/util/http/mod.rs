@@ -17,7 +17,7 @@ mod query;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use auth::{HttpSourceAuth, HttpSourceAuthConfig};
 #[cfg(feature = "sources-utils-http-encoding")]
-pub use encoding::decode;
+pub use encoding::{decode, ContentEncoding, DecompressionConfig};
 #[cfg(feature = "sources-utils-http-error")]
 pub use error::ErrorMessage;
 pub use method::HttpMethod;
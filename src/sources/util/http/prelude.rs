@@ -31,7 +31,7 @@ use crate::{
 
 use super::{
     auth::{HttpSourceAuth, HttpSourceAuthConfig},
-    encoding::decode,
+    encoding::DecompressionConfig,
     error::ErrorMessage,
 };
 
@@ -49,6 +49,17 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     ) {
     }
 
+    /// Verifies a request before its body is decompressed and decoded, such as by checking the
+    /// signature of the body, so that the requests failing the verification are rejected.
+    fn verify_request(&self, _headers: &HeaderMap, _body: &Bytes) -> Result<(), ErrorMessage> {
+        Ok(())
+    }
+
+    /// The options used to decompress request bodies, according to their `Content-Encoding`.
+    fn decompression(&self) -> &DecompressionConfig {
+        DecompressionConfig::default_ref()
+    }
+
     fn build_events(
         &self,
         body: Bytes,
@@ -130,7 +141,8 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
                         let events = auth
                             .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
+                            .and_then(|()| self.verify_request(&headers, &body))
+                            .and_then(|()| self.decompression().decode(&encoding_header, body))
                             .and_then(|body| {
                                 self.build_events(body, &headers, &query_parameters, path.as_str())
                             })
@@ -57,6 +57,12 @@ pub(crate) trait HttpClientBuilder {
 
     /// Called before the HTTP request is made to build out the context.
     fn build(&self, url: &Uri) -> Self::Context;
+
+    /// (Optional) Called at each interval to get the URLs to call, which are the configured URLs by
+    /// default.
+    fn urls(&self, urls: &[Uri]) -> Vec<Uri> {
+        urls.to_vec()
+    }
 }
 
 /// Methods that allow context-specific behavior during the scraping procedure.
@@ -113,9 +119,10 @@ pub(crate) async fn call<
     mut out: SourceSender,
     http_method: HttpMethod,
 ) -> Result<(), ()> {
+    let urls_builder = context_builder.clone();
     let mut stream = IntervalStream::new(tokio::time::interval(inputs.interval))
         .take_until(inputs.shutdown)
-        .map(move |_| stream::iter(inputs.urls.clone()))
+        .map(move |_| stream::iter(urls_builder.urls(&inputs.urls)))
         .flatten()
         .map(move |url| {
             // Building the HttpClient should not fail as it is just setting up the client with the
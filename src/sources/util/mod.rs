@@ -4,7 +4,11 @@ mod body_decoding;
 mod encoding_config;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(any(
+    feature = "sources-grpc_server",
+    feature = "sources-vector",
+    feature = "sources-opentelemetry"
+))]
 pub mod grpc;
 #[cfg(any(
     feature = "sources-utils-http-auth",
@@ -16,7 +20,11 @@ pub mod grpc;
 pub mod http;
 #[cfg(any(feature = "sources-http_client", feature = "sources-prometheus"))]
 pub mod http_client;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-aws_sqs",
+    feature = "sources-azure_event_hubs",
+    feature = "sources-gcp_pubsub"
+))]
 mod message_decoding;
 pub mod multiline_config;
 #[cfg(any(feature = "sources-utils-net-tcp", feature = "sources-utils-net-udp"))]
@@ -55,13 +63,19 @@ pub use self::http::add_query_parameters;
     feature = "sources-utils-http-encoding"
 ))]
 pub use self::http::decode;
+#[cfg(feature = "sources-utils-http-encoding")]
+pub use self::http::DecompressionConfig;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
 pub use self::http::HttpSource;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-aws_sqs",
+    feature = "sources-azure_event_hubs",
+    feature = "sources-gcp_pubsub"
+))]
 pub use self::message_decoding::decode_message;
 
 /// Extract a tag and it's value from input string delimited by a colon character.
@@ -5,7 +5,10 @@ use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
 
-use crate::line_agg;
+use crate::{
+    conditions::{ConditionalConfig, VrlConfig},
+    line_agg::{self, Matcher},
+};
 
 /// Configuration of multi-line aggregation.
 #[serde_as]
@@ -14,24 +17,56 @@ use crate::line_agg;
 #[serde(deny_unknown_fields)]
 pub struct MultilineConfig {
     /// Regular expression pattern that is used to match the start of a new message.
+    ///
+    /// Exactly one of `start_pattern` and `start_condition` must be set.
     #[configurable(metadata(docs::examples = "^[\\s]+"))]
     #[configurable(metadata(docs::examples = "\\\\$"))]
     #[configurable(metadata(docs::examples = "^(INFO|ERROR) "))]
     #[configurable(metadata(docs::examples = ";$"))]
-    pub start_pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_pattern: Option<String>,
+
+    /// [VRL][vrl] condition that is used to match the start of a new message.
+    ///
+    /// The condition is run against a log event holding the line in its `message` field.
+    ///
+    /// Exactly one of `start_pattern` and `start_condition` must be set.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(docs::examples = "!starts_with(string!(.message), \" \")"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_condition: Option<String>,
 
     /// Regular expression pattern that is used to determine whether or not more lines should be read.
     ///
-    /// This setting must be configured in conjunction with `mode`.
+    /// This setting must be configured in conjunction with `mode`. Exactly one of
+    /// `condition_pattern` and `condition` must be set.
     #[configurable(metadata(docs::examples = "^[\\s]+"))]
     #[configurable(metadata(docs::examples = "\\\\$"))]
     #[configurable(metadata(docs::examples = "^(INFO|ERROR) "))]
     #[configurable(metadata(docs::examples = ";$"))]
-    pub condition_pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition_pattern: Option<String>,
+
+    /// [VRL][vrl] condition that is used to determine whether or not more lines should be read.
+    ///
+    /// The condition is run against a log event holding the line in its `message` field. This is
+    /// useful to join lines, such as the ones of a stack trace, that are hard to match with a
+    /// regular expression.
+    ///
+    /// This setting must be configured in conjunction with `mode`. Exactly one of
+    /// `condition_pattern` and `condition` must be set.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = "match(string!(.message), r'^\\s+at ') || starts_with(string!(.message), \"Caused by:\")"
+    ))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
 
     /// Aggregation mode.
     ///
-    /// This setting must be configured in conjunction with `condition_pattern`.
+    /// This setting must be configured in conjunction with `condition_pattern` or `condition`.
     #[configurable(derived)]
     pub mode: line_agg::Mode,
 
@@ -50,15 +85,46 @@ impl TryFrom<&MultilineConfig> for line_agg::Config {
     fn try_from(config: &MultilineConfig) -> Result<Self, Self::Error> {
         let MultilineConfig {
             start_pattern,
+            start_condition,
             condition_pattern,
+            condition,
             mode,
             timeout_ms,
         } = config;
 
-        let start_pattern = Regex::new(start_pattern)
-            .with_context(|_| InvalidMultilineStartPatternSnafu { start_pattern })?;
-        let condition_pattern = Regex::new(condition_pattern)
-            .with_context(|_| InvalidMultilineConditionPatternSnafu { condition_pattern })?;
+        let start_pattern = match (start_pattern, start_condition) {
+            (Some(start_pattern), None) => Matcher::Pattern(
+                Regex::new(start_pattern)
+                    .with_context(|_| InvalidMultilineStartPatternSnafu { start_pattern })?,
+            ),
+            (None, Some(start_condition)) => Matcher::Condition(
+                build_condition(start_condition)
+                    .with_context(|_| InvalidMultilineStartConditionSnafu { start_condition })?,
+            ),
+            _ => {
+                return Err(Error::AmbiguousMultilineMatcher {
+                    pattern: "start_pattern",
+                    condition: "start_condition",
+                })
+            }
+        };
+        let condition_pattern = match (condition_pattern, condition) {
+            (Some(condition_pattern), None) => {
+                Matcher::Pattern(Regex::new(condition_pattern).with_context(|_| {
+                    InvalidMultilineConditionPatternSnafu { condition_pattern }
+                })?)
+            }
+            (None, Some(condition)) => Matcher::Condition(
+                build_condition(condition)
+                    .with_context(|_| InvalidMultilineConditionSnafu { condition })?,
+            ),
+            _ => {
+                return Err(Error::AmbiguousMultilineMatcher {
+                    pattern: "condition_pattern",
+                    condition: "condition",
+                })
+            }
+        };
         let timeout = *timeout_ms;
 
         Ok(Self {
@@ -70,6 +136,14 @@ impl TryFrom<&MultilineConfig> for line_agg::Config {
     }
 }
 
+fn build_condition(source: &str) -> crate::Result<crate::conditions::Condition> {
+    VrlConfig {
+        source: source.to_owned(),
+        ..Default::default()
+    }
+    .build(&Default::default())
+}
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display(
@@ -90,4 +164,23 @@ pub enum Error {
         condition_pattern: String,
         source: regex::Error,
     },
+    #[snafu(display(
+        "unable to build multiline start condition from {:?}: {}",
+        start_condition,
+        source
+    ))]
+    InvalidMultilineStartCondition {
+        start_condition: String,
+        source: crate::Error,
+    },
+    #[snafu(display("unable to build multiline condition from {:?}: {}", condition, source))]
+    InvalidMultilineCondition {
+        condition: String,
+        source: crate::Error,
+    },
+    #[snafu(display("exactly one of `{}` and `{}` must be set", pattern, condition))]
+    AmbiguousMultilineMatcher {
+        pattern: &'static str,
+        condition: &'static str,
+    },
 }
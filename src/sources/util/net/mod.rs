@@ -11,7 +11,10 @@ use vector_config::configurable_component;
 use crate::config::{Protocol, Resource};
 
 #[cfg(feature = "sources-utils-net-tcp")]
-pub use self::tcp::{TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker};
+pub use self::tcp::{
+    ConnectionLimitPolicy, TcpConnectionLimits, TcpNullAcker, TcpSource, TcpSourceAck,
+    TcpSourceAcker,
+};
 #[cfg(feature = "sources-utils-net-udp")]
 pub use self::udp::try_bind_udp_socket;
 
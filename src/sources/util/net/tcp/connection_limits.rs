@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Notify;
+use vector_config::configurable_component;
+
+/// What to do with the connections over the limits on the number of connections.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionLimitPolicy {
+    /// Queue the connections until others are closed.
+    ///
+    /// The connections over `connection_limit` are left in the backlog of the listener, and
+    /// those over `connection_limit_per_ip` are accepted but not read from.
+    #[default]
+    Queue,
+
+    /// Close the connections right away.
+    Reject,
+}
+
+/// The limits on the connections of a TCP source, so that a single client can't starve the others.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TcpConnectionLimits {
+    /// The maximum number of connections open at once.
+    pub max_connections: Option<u32>,
+
+    /// The maximum number of connections open at once from a single IP address.
+    pub max_connections_per_ip: Option<u32>,
+
+    /// The maximum rate at which the data of each connection is read, in bytes per second.
+    pub max_bytes_per_sec: Option<u64>,
+
+    /// What to do with the connections over `max_connections` or `max_connections_per_ip`.
+    pub policy: ConnectionLimitPolicy,
+}
+
+impl TcpConnectionLimits {
+    /// Limits the number of connections open at once, queueing the connections over the limit.
+    pub fn new(max_connections: Option<u32>) -> Self {
+        Self {
+            max_connections,
+            ..Default::default()
+        }
+    }
+}
+
+/// The connections open from each IP address.
+pub(super) struct IpConnections {
+    max: usize,
+    open: Mutex<HashMap<IpAddr, usize>>,
+    closed: Notify,
+}
+
+impl IpConnections {
+    pub(super) fn new(max: u32) -> Arc<Self> {
+        Arc::new(Self {
+            max: max as usize,
+            open: Mutex::new(HashMap::new()),
+            closed: Notify::new(),
+        })
+    }
+
+    /// Opens a connection from an IP address, unless the maximum are already open from it.
+    pub(super) fn try_open(self: &Arc<Self>, ip: IpAddr) -> Option<IpConnection> {
+        let mut open = self.open.lock().expect("connections lock poisoned");
+        let count = open.entry(ip).or_insert(0);
+        if *count >= self.max {
+            return None;
+        }
+
+        *count += 1;
+        Some(IpConnection {
+            ip,
+            connections: Arc::clone(self),
+        })
+    }
+
+    /// Opens a connection from an IP address once less than the maximum are open from it.
+    pub(super) async fn open(self: &Arc<Self>, ip: IpAddr) -> IpConnection {
+        loop {
+            // Registered before trying, so that a connection closed in between isn't missed.
+            let closed = self.closed.notified();
+            if let Some(connection) = self.try_open(ip) {
+                return connection;
+            }
+            closed.await;
+        }
+    }
+}
+
+/// A connection counted against the maximum of its IP address until it's dropped.
+pub(super) struct IpConnection {
+    ip: IpAddr,
+    connections: Arc<IpConnections>,
+}
+
+impl Drop for IpConnection {
+    fn drop(&mut self) {
+        if let Ok(mut open) = self.connections.open.lock() {
+            if let Some(count) = open.get_mut(&self.ip) {
+                *count -= 1;
+                if *count == 0 {
+                    open.remove(&self.ip);
+                }
+            }
+        }
+        self.connections.closed.notify_waiters();
+    }
+}
+
+/// Limits the rate at which the data of a connection is read, as a token bucket holding up to a
+/// second of data.
+pub(super) struct ByteRateLimiter {
+    bytes_per_sec: f64,
+    available: f64,
+    updated: Instant,
+}
+
+impl ByteRateLimiter {
+    pub(super) fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: bytes_per_sec as f64,
+            available: bytes_per_sec as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Records that some bytes were read, returning how long to wait before reading more, if the
+    /// connection is over its rate.
+    pub(super) fn consume(&mut self, bytes: usize, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.updated = now;
+        self.available =
+            (self.available + elapsed * self.bytes_per_sec).min(self.bytes_per_sec) - bytes as f64;
+
+        (self.available < 0.0)
+            .then(|| Duration::from_secs_f64(-self.available / self.bytes_per_sec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn limits_connections_per_ip() {
+        let connections = IpConnections::new(2);
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+        let first = connections.try_open(ip).unwrap();
+        let _second = connections.try_open(ip).unwrap();
+        assert!(connections.try_open(ip).is_none());
+        let _other = connections.try_open(other).unwrap();
+
+        let queued = connections.open(ip);
+        tokio::pin!(queued);
+        assert!(queued.as_mut().now_or_never().is_none());
+
+        drop(first);
+        let _third = queued.await;
+        assert!(connections.try_open(ip).is_none());
+    }
+
+    #[test]
+    fn limits_byte_rate() {
+        let start = Instant::now();
+        let mut limiter = ByteRateLimiter {
+            bytes_per_sec: 1000.0,
+            available: 1000.0,
+            updated: start,
+        };
+
+        assert_eq!(limiter.consume(600, start), None);
+        assert_eq!(
+            limiter.consume(900, start),
+            Some(Duration::from_millis(500))
+        );
+
+        // Half a second later, the deficit was made up for.
+        let later = start + Duration::from_millis(500);
+        assert_eq!(limiter.consume(0, later), None);
+
+        // The unused rate doesn't accumulate beyond a second of data.
+        let much_later = later + Duration::from_secs(10);
+        assert_eq!(
+            limiter.consume(1500, much_later),
+            Some(Duration::from_millis(500))
+        );
+    }
+}
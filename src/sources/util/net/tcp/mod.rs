@@ -1,6 +1,14 @@
+mod connection_limits;
 mod request_limiter;
 
-use std::{collections::BTreeMap, io, mem::drop, net::SocketAddr, time::Duration};
+use std::{
+    collections::BTreeMap,
+    io,
+    mem::drop,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use bytes::Bytes;
 use codecs::StreamDecodingError;
@@ -13,6 +21,7 @@ use socket2::SockRef;
 use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpStream},
+    sync::Semaphore,
     time::sleep,
 };
 use tokio_util::codec::{Decoder, FramedRead};
@@ -23,7 +32,11 @@ use vector_core::{
     EstimatedJsonEncodedSizeOf,
 };
 
-use self::request_limiter::RequestLimiter;
+pub use self::connection_limits::{ConnectionLimitPolicy, TcpConnectionLimits};
+use self::{
+    connection_limits::{ByteRateLimiter, IpConnections},
+    request_limiter::RequestLimiter,
+};
 use super::SocketListenAddr;
 use crate::{
     codecs::ReadyFrames,
@@ -31,7 +44,8 @@ use crate::{
     event::{BatchNotifier, BatchStatus, Event},
     internal_events::{
         ConnectionOpen, DecoderFramingError, OpenGauge, SocketBindError, SocketEventsReceived,
-        SocketMode, SocketReceiveError, StreamClosedError, TcpBytesReceived, TcpSendAckError,
+        SocketMode, SocketReceiveError, StreamClosedError, TcpBytesReceived, TcpConnectionQueued,
+        TcpConnectionRejected, TcpConnectionThrottled, TcpSendAckError,
         TcpSocketTlsConnectionError,
     },
     shutdown::ShutdownSignal,
@@ -116,7 +130,8 @@ where
         max_connection_duration_secs: Option<u64>,
         cx: SourceContext,
         acknowledgements: SourceAcknowledgementsConfig,
-        max_connections: Option<u32>,
+        connection_limits: TcpConnectionLimits,
+        proxy_protocol: bool,
         source_name: &'static str,
         log_namespace: LogNamespace,
     ) -> crate::Result<crate::sources::Source> {
@@ -126,6 +141,7 @@ where
             let listenfd = ListenFd::from_env();
             let listener = try_bind_tcp_listener(addr, listenfd, &tls)
                 .await
+                .map(|listener| listener.with_proxy_protocol(proxy_protocol))
                 .map_err(|error| {
                     emit!(SocketBindError {
                         mode: SocketMode::Tcp,
@@ -154,8 +170,23 @@ where
             let request_limiter =
                 RequestLimiter::new(MAX_IN_FLIGHT_EVENTS_TARGET, crate::num_threads());
 
+            // Queued connections are left in the backlog of the listener, while rejecting them
+            // requires accepting them first.
+            let (accept_limit, connection_semaphore) = match connection_limits.policy {
+                ConnectionLimitPolicy::Queue => (connection_limits.max_connections, None),
+                ConnectionLimitPolicy::Reject => (
+                    None,
+                    connection_limits
+                        .max_connections
+                        .map(|max| Arc::new(Semaphore::new(max as usize))),
+                ),
+            };
+            let ip_connections = connection_limits
+                .max_connections_per_ip
+                .map(IpConnections::new);
+
             listener
-                .accept_stream_limited(max_connections)
+                .accept_stream_limited(accept_limit)
                 .take_until(shutdown_clone)
                 .for_each(move |(connection, tcp_connection_permit)| {
                     let shutdown_signal = cx.shutdown.clone();
@@ -165,6 +196,8 @@ where
                     let connection_gauge = connection_gauge.clone();
                     let request_limiter = request_limiter.clone();
                     let tls_client_metadata_key = tls_client_metadata_key.clone();
+                    let connection_semaphore = connection_semaphore.clone();
+                    let ip_connections = ip_connections.clone();
 
                     async move {
                         let socket = match connection {
@@ -179,6 +212,21 @@ where
                         };
 
                         let peer_addr = socket.peer_addr();
+
+                        let tcp_connection_permit = match connection_semaphore {
+                            Some(semaphore) => match semaphore.try_acquire_owned() {
+                                Ok(permit) => Some(permit),
+                                Err(_) => {
+                                    emit!(TcpConnectionRejected {
+                                        peer_addr,
+                                        limit: "connection_limit",
+                                    });
+                                    return;
+                                }
+                            },
+                            None => tcp_connection_permit,
+                        };
+
                         let span = info_span!("connection", %peer_addr);
 
                         let tripwire = tripwire
@@ -208,6 +256,8 @@ where
                                 out,
                                 acknowledgements,
                                 request_limiter,
+                                connection_limits,
+                                ip_connections,
                                 tls_client_metadata_key.clone(),
                                 source_name,
                                 log_namespace,
@@ -242,6 +292,8 @@ async fn handle_stream<T>(
     mut out: SourceSender,
     acknowledgements: bool,
     request_limiter: RequestLimiter,
+    connection_limits: TcpConnectionLimits,
+    ip_connections: Option<Arc<IpConnections>>,
     tls_client_metadata_key: Option<OwnedValuePath>,
     source_name: &'static str,
     log_namespace: LogNamespace,
@@ -260,6 +312,34 @@ async fn handle_stream<T>(
             return;
         }
     };
+    // With the PROXY protocol, this is the address of the client rather than the load balancer.
+    let peer_addr = socket.peer_addr();
+
+    // The connection counts against the limit of its IP address until it's closed.
+    let _ip_connection = match &ip_connections {
+        Some(ip_connections) => match ip_connections.try_open(peer_addr.ip()) {
+            Some(connection) => Some(connection),
+            None if connection_limits.policy == ConnectionLimitPolicy::Reject => {
+                emit!(TcpConnectionRejected {
+                    peer_addr,
+                    limit: "connection_limit_per_ip",
+                });
+                return;
+            }
+            None => {
+                emit!(TcpConnectionQueued {
+                    peer_addr,
+                    limit: "connection_limit_per_ip",
+                });
+                tokio::select! {
+                    connection = ip_connections.open(peer_addr.ip()) => Some(connection),
+                    _ = &mut tripwire => return,
+                    _ = &mut shutdown_signal => return,
+                }
+            }
+        },
+        None => None,
+    };
 
     if let Some(keepalive) = keepalive {
         if let Err(error) = socket.set_keepalive(keepalive) {
@@ -296,7 +376,27 @@ async fn handle_stream<T>(
 
     tokio::pin!(connection_close_timeout);
 
+    // Not reading from the connection while it's over its rate slows the client down through TCP
+    // backpressure.
+    let mut byte_rate_limiter = connection_limits
+        .max_bytes_per_sec
+        .map(ByteRateLimiter::new);
+    let mut throttle = None;
+
     loop {
+        if let Some(delay) = throttle.take() {
+            emit!(TcpConnectionThrottled { delay });
+            tokio::select! {
+                _ = &mut tripwire => break,
+                _ = &mut shutdown_signal => {
+                    if close_socket(reader.get_ref().get_ref().get_ref()) {
+                        break;
+                    }
+                },
+                _ = sleep(delay) => {},
+            }
+        }
+
         let mut permit = tokio::select! {
             _ = &mut tripwire => break,
             Some(_) = &mut connection_close_timeout  => {
@@ -334,7 +434,11 @@ async fn handle_stream<T>(
             }
             res = reader.next() => {
                 match res {
-                    Some(Ok((frames, _byte_size))) => {
+                    Some(Ok((frames, byte_size))) => {
+                        throttle = byte_rate_limiter
+                            .as_mut()
+                            .and_then(|limiter| limiter.consume(byte_size, Instant::now()));
+
                         let _num_frames = frames.len();
                         let acker = source.build_acker(&frames);
                         let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
@@ -367,6 +471,9 @@ async fn handle_stream<T>(
                             metadata.insert("subject".to_string(), certificate_metadata.subject().into());
                             for event in &mut events {
                                 let log = event.as_mut_log();
+                                if log.metadata().is_pass_through() {
+                                    continue;
+                                }
 
                                 log_namespace.insert_source_metadata(
                                     source_name,
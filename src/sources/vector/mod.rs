@@ -42,18 +42,9 @@ struct Service {
     log_namespace: LogNamespace,
 }
 
-#[tonic::async_trait]
-impl proto::Service for Service {
-    async fn push_events(
-        &self,
-        request: Request<proto::PushEventsRequest>,
-    ) -> Result<Response<proto::PushEventsResponse>, Status> {
-        let mut events: Vec<Event> = request
-            .into_inner()
-            .events
-            .into_iter()
-            .map(Event::from)
-            .collect();
+impl Service {
+    async fn handle_events(&self, request: proto::PushEventsRequest) -> Result<(), Status> {
+        let mut events: Vec<Event> = request.events.into_iter().map(Event::from).collect();
 
         let now = Utc::now();
         for event in &mut events {
@@ -82,6 +73,26 @@ impl proto::Service for Service {
                 Status::unavailable(message)
             })
             .and_then(|_| handle_batch_status(receiver))
+            .await
+    }
+}
+
+#[tonic::async_trait]
+impl proto::Service for Service {
+    async fn push_events(
+        &self,
+        request: Request<proto::PushEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        self.handle_events(request.into_inner()).await?;
+
+        Ok(Response::new(proto::PushEventsResponse {}))
+    }
+
+    async fn push_compressed_events(
+        &self,
+        request: Request<proto::PushCompressedEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        self.handle_events(request.into_inner().decompress()?)
             .await?;
 
         Ok(Response::new(proto::PushEventsResponse {}))
@@ -0,0 +1,186 @@
+//! The `webhook_receiver` source.
+//!
+//! An `http_server` source that verifies the HMAC signatures of the requests, as sent by the
+//! providers of webhooks, and rejects the requests failing the verification before decoding them.
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use warp::http::{HeaderMap, StatusCode};
+
+use crate::{
+    config::{GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::Event,
+    internal_events::WebhookSignatureVerificationFailed,
+    sources::{
+        http_server::{SimpleHttpConfig, SimpleHttpSource},
+        util::{DecompressionConfig, ErrorMessage, HttpSource},
+    },
+};
+
+mod signature;
+
+use self::signature::{SignatureConfig, SignatureVerifier};
+
+/// Configuration for the `webhook_receiver` source.
+#[configurable_component(source("webhook_receiver"))]
+#[derive(Clone, Debug)]
+pub struct WebhookReceiverConfig {
+    #[serde(flatten)]
+    pub http: SimpleHttpConfig,
+
+    #[configurable(derived)]
+    pub signature: SignatureConfig,
+}
+
+impl GenerateConfig for WebhookReceiverConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "0.0.0.0:8080"
+            signature.secret = "${WEBHOOK_SECRET}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for WebhookReceiverConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let source = WebhookReceiverSource {
+            inner: self.http.http_source(&cx)?,
+            verifier: SignatureVerifier::new(&self.signature)?,
+        };
+        self.http.serve(source, cx)
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        self.http.outputs(global_log_namespace)
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.http.resources()
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        self.http.can_acknowledge()
+    }
+}
+
+#[derive(Clone)]
+struct WebhookReceiverSource {
+    inner: SimpleHttpSource,
+    verifier: SignatureVerifier,
+}
+
+impl HttpSource for WebhookReceiverSource {
+    fn enrich_events(
+        &self,
+        events: &mut [Event],
+        request_path: &str,
+        headers_config: &HeaderMap,
+        query_parameters: &HashMap<String, String>,
+    ) {
+        self.inner
+            .enrich_events(events, request_path, headers_config, query_parameters);
+    }
+
+    fn verify_request(&self, headers: &HeaderMap, body: &Bytes) -> Result<(), ErrorMessage> {
+        self.verifier.verify(headers, body).map_err(|error| {
+            emit!(WebhookSignatureVerificationFailed {
+                reason: error.reason(),
+            });
+            ErrorMessage::new(StatusCode::UNAUTHORIZED, "Invalid signature".to_owned())
+        })
+    }
+
+    fn decompression(&self) -> &DecompressionConfig {
+        self.inner.decompression()
+    }
+
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: &HeaderMap,
+        query_parameters: &HashMap<String, String>,
+        path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        self.inner
+            .build_events(body, header_map, query_parameters, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use futures::Stream;
+    use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{collect_n, next_addr, wait_for_tcp},
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebhookReceiverConfig>();
+    }
+
+    async fn source() -> (impl Stream<Item = Event>, SocketAddr) {
+        let address = next_addr();
+        let config: WebhookReceiverConfig = toml::from_str(&format!(
+            r#"address = "{}"
+            decoding.codec = "json"
+            signature.secret = "secret""#,
+            address
+        ))
+        .unwrap();
+
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move {
+            config.build(context).await.unwrap().await.unwrap();
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn send(address: SocketAddr, body: &str, signature: &str) -> reqwest::StatusCode {
+        reqwest::Client::new()
+            .post(format!("http://{}/", address))
+            .header("X-Hub-Signature-256", signature)
+            .body(body.to_owned())
+            .send()
+            .await
+            .unwrap()
+            .status()
+    }
+
+    fn sign(body: &str) -> String {
+        let key = PKey::hmac(b"secret").unwrap();
+        let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+        signer.update(body.as_bytes()).unwrap();
+        format!("sha256={}", hex::encode(signer.sign_to_vec().unwrap()))
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_signatures() {
+        let (recv, address) = source().await;
+        let body = r#"{"action":"opened"}"#;
+
+        assert_eq!(
+            send(address, body, &sign("{}")).await,
+            reqwest::StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send(address, body, &sign(body)).await,
+            reqwest::StatusCode::OK
+        );
+
+        let events = collect_n(recv, 1).await;
+        assert_eq!(events[0].as_log()["action"], "opened".into());
+    }
+}
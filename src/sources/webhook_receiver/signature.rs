@@ -0,0 +1,406 @@
+//! Verification of the HMAC signatures of the webhooks, after the conventions of their providers.
+use std::time::Duration;
+
+use base64::Engine as _;
+use bytes::Bytes;
+use chrono::Utc;
+use openssl::{hash::MessageDigest, memcmp, pkey::PKey, sign::Signer};
+use serde_with::serde_as;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use warp::http::{header::HeaderName, HeaderMap};
+
+/// The header holding the timestamp of the requests signed by Slack.
+const SLACK_TIMESTAMP_HEADER: &str = "x-slack-request-timestamp";
+
+/// Signature verification configuration.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: SignatureFormat,
+
+    /// The header holding the signature.
+    ///
+    /// Defaults to `X-Hub-Signature-256` with the `plain` format, `Stripe-Signature` with the
+    /// `stripe` format, and `X-Slack-Signature` with the `slack` format.
+    #[configurable(metadata(docs::examples = "X-Hub-Signature-256"))]
+    #[configurable(metadata(docs::examples = "X-Shopify-Hmac-Sha256"))]
+    pub header: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub algorithm: SignatureAlgorithm,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+
+    /// The prefix of the signature in the header, with the `plain` format.
+    ///
+    /// Defaults to the name of the algorithm followed by `=`, such as `sha256=`, as sent by GitHub.
+    /// Set this to `""` for the providers sending the signature alone.
+    #[configurable(metadata(docs::examples = "sha256="))]
+    pub prefix: Option<String>,
+
+    /// The secret shared with the provider, used as the key of the HMAC.
+    ///
+    /// This is usually taken from a secrets backend, such as with `SECRET[backend.webhook_secret]`.
+    #[configurable(metadata(docs::examples = "${WEBHOOK_SECRET}"))]
+    #[configurable(metadata(docs::examples = "SECRET[backend.webhook_secret]"))]
+    pub secret: SensitiveString,
+
+    /// The maximum difference between the signed timestamp of the requests and the current time,
+    /// with the `stripe` and `slack` formats.
+    ///
+    /// The requests signed longer ago are rejected, so that they can't be replayed.
+    #[serde(default = "default_timestamp_tolerance")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "timestamp_tolerance_secs")]
+    #[configurable(metadata(docs::examples = 300))]
+    pub timestamp_tolerance: Duration,
+}
+
+const fn default_timestamp_tolerance() -> Duration {
+    Duration::from_secs(300)
+}
+
+/// The format of the signatures.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureFormat {
+    /// The header holds the signature of the body, after an optional prefix.
+    ///
+    /// This is the format of GitHub, with `X-Hub-Signature-256: sha256=<signature>`, and of most
+    /// providers.
+    #[default]
+    Plain,
+
+    /// The header holds a timestamp and the signatures of the timestamp and the body.
+    ///
+    /// This is the format of Stripe, with `Stripe-Signature: t=<timestamp>,v1=<signature>`.
+    Stripe,
+
+    /// The header holds the signature of the timestamp of the `X-Slack-Request-Timestamp` header
+    /// and the body.
+    ///
+    /// This is the format of Slack, with `X-Slack-Signature: v0=<signature>`.
+    Slack,
+}
+
+impl SignatureFormat {
+    const fn default_header(self) -> &'static str {
+        match self {
+            Self::Plain => "x-hub-signature-256",
+            Self::Stripe => "stripe-signature",
+            Self::Slack => "x-slack-signature",
+        }
+    }
+}
+
+/// The hash function of the HMAC.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureAlgorithm {
+    /// HMAC-SHA1, only used by older providers.
+    Sha1,
+
+    /// HMAC-SHA256.
+    #[default]
+    Sha256,
+
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+impl SignatureAlgorithm {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Sha1 => MessageDigest::sha1(),
+            Self::Sha256 => MessageDigest::sha256(),
+            Self::Sha512 => MessageDigest::sha512(),
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+        }
+    }
+}
+
+/// The encoding of the signatures, with the `plain` format.
+///
+/// The `stripe` and `slack` formats always encode the signatures in hexadecimal.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureEncoding {
+    /// Hexadecimal.
+    #[default]
+    Hex,
+
+    /// Base64, as sent by Shopify for example.
+    Base64,
+}
+
+/// Why the signature of a request was rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum VerificationError {
+    MissingSignature,
+    MalformedSignature,
+    ExpiredTimestamp,
+    InvalidSignature,
+}
+
+impl VerificationError {
+    pub(super) const fn reason(self) -> &'static str {
+        match self {
+            Self::MissingSignature => "missing_signature",
+            Self::MalformedSignature => "malformed_signature",
+            Self::ExpiredTimestamp => "expired_timestamp",
+            Self::InvalidSignature => "invalid_signature",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct SignatureVerifier {
+    format: SignatureFormat,
+    header: HeaderName,
+    algorithm: SignatureAlgorithm,
+    encoding: SignatureEncoding,
+    prefix: String,
+    secret: SensitiveString,
+    timestamp_tolerance: Duration,
+}
+
+impl SignatureVerifier {
+    pub(super) fn new(config: &SignatureConfig) -> crate::Result<Self> {
+        let header = config
+            .header
+            .as_deref()
+            .unwrap_or_else(|| config.format.default_header());
+        let header = HeaderName::try_from(header)
+            .map_err(|error| format!("Invalid signature header {:?}: {}", header, error))?;
+        let prefix = config
+            .prefix
+            .clone()
+            .unwrap_or_else(|| format!("{}=", config.algorithm.name()));
+
+        Ok(Self {
+            format: config.format,
+            header,
+            algorithm: config.algorithm,
+            encoding: config.encoding,
+            prefix,
+            secret: config.secret.clone(),
+            timestamp_tolerance: config.timestamp_tolerance,
+        })
+    }
+
+    pub(super) fn verify(
+        &self,
+        headers: &HeaderMap,
+        body: &Bytes,
+    ) -> Result<(), VerificationError> {
+        self.verify_at(headers, body, Utc::now().timestamp())
+    }
+
+    fn verify_at(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), VerificationError> {
+        let value = header_str(headers, &self.header)?;
+
+        match self.format {
+            SignatureFormat::Plain => {
+                let signature = value
+                    .strip_prefix(self.prefix.as_str())
+                    .ok_or(VerificationError::MalformedSignature)?;
+                let signature = match self.encoding {
+                    SignatureEncoding::Hex => hex::decode(signature).ok(),
+                    SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                        .decode(signature)
+                        .ok(),
+                }
+                .ok_or(VerificationError::MalformedSignature)?;
+                self.check(&signature, &[body])
+            }
+            SignatureFormat::Stripe => {
+                let mut timestamp = None;
+                let mut signatures = Vec::new();
+                for pair in value.split(',') {
+                    match pair.trim().split_once('=') {
+                        Some(("t", value)) => timestamp = Some(value),
+                        Some(("v1", value)) => signatures.push(value),
+                        _ => (),
+                    }
+                }
+                let timestamp = timestamp.ok_or(VerificationError::MalformedSignature)?;
+                self.check_timestamp(timestamp, now)?;
+
+                let signed: [&[u8]; 3] = [timestamp.as_bytes(), b".", body];
+                signatures
+                    .into_iter()
+                    .filter_map(|signature| hex::decode(signature).ok())
+                    .find_map(|signature| self.check(&signature, &signed).ok())
+                    .ok_or(VerificationError::InvalidSignature)
+            }
+            SignatureFormat::Slack => {
+                let timestamp =
+                    header_str(headers, &HeaderName::from_static(SLACK_TIMESTAMP_HEADER))?;
+                self.check_timestamp(timestamp, now)?;
+
+                let signature = value
+                    .strip_prefix("v0=")
+                    .and_then(|signature| hex::decode(signature).ok())
+                    .ok_or(VerificationError::MalformedSignature)?;
+                self.check(&signature, &[b"v0:", timestamp.as_bytes(), b":", body])
+            }
+        }
+    }
+
+    fn check_timestamp(&self, timestamp: &str, now: i64) -> Result<(), VerificationError> {
+        let timestamp = timestamp
+            .parse::<i64>()
+            .map_err(|_| VerificationError::MalformedSignature)?;
+        if now.abs_diff(timestamp) > self.timestamp_tolerance.as_secs() {
+            return Err(VerificationError::ExpiredTimestamp);
+        }
+        Ok(())
+    }
+
+    /// Checks that `signature` is the HMAC of the concatenation of `parts`.
+    fn check(&self, signature: &[u8], parts: &[&[u8]]) -> Result<(), VerificationError> {
+        let expected = self
+            .hmac(parts)
+            .map_err(|_| VerificationError::InvalidSignature)?;
+        // Compared in constant time, so that the signature can't be guessed from the timing.
+        if signature.len() == expected.len() && memcmp::eq(signature, &expected) {
+            Ok(())
+        } else {
+            Err(VerificationError::InvalidSignature)
+        }
+    }
+
+    fn hmac(&self, parts: &[&[u8]]) -> Result<Vec<u8>, openssl::error::ErrorStack> {
+        let key = PKey::hmac(self.secret.inner().as_bytes())?;
+        let mut signer = Signer::new(self.algorithm.digest(), &key)?;
+        for part in parts {
+            signer.update(part)?;
+        }
+        signer.sign_to_vec()
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Result<&'a str, VerificationError> {
+    headers
+        .get(name)
+        .ok_or(VerificationError::MissingSignature)?
+        .to_str()
+        .map_err(|_| VerificationError::MalformedSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use warp::http::HeaderValue;
+
+    use super::*;
+
+    const SECRET: &str = "It's a Secret to Everybody";
+    const BODY: &[u8] = b"Hello, World!";
+
+    fn verifier(format: SignatureFormat) -> SignatureVerifier {
+        SignatureVerifier::new(&SignatureConfig {
+            format,
+            header: None,
+            algorithm: SignatureAlgorithm::Sha256,
+            encoding: SignatureEncoding::Hex,
+            prefix: None,
+            secret: SECRET.to_owned().into(),
+            timestamp_tolerance: default_timestamp_tolerance(),
+        })
+        .unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_static(name),
+                    HeaderValue::from_str(value).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verifies_plain_signatures() {
+        let verifier = verifier(SignatureFormat::Plain);
+        // The example of the GitHub documentation.
+        let signature =
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17".to_owned();
+
+        let valid = headers(&[("x-hub-signature-256", signature)]);
+        assert_eq!(verifier.verify_at(&valid, BODY, 0), Ok(()));
+        assert_eq!(
+            verifier.verify_at(&valid, b"Hello, Mallory!", 0),
+            Err(VerificationError::InvalidSignature)
+        );
+        assert_eq!(
+            verifier.verify_at(&HeaderMap::new(), BODY, 0),
+            Err(VerificationError::MissingSignature)
+        );
+        assert_eq!(
+            verifier.verify_at(
+                &headers(&[("x-hub-signature-256", "sha256=zz".to_owned())]),
+                BODY,
+                0
+            ),
+            Err(VerificationError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn verifies_stripe_signatures() {
+        let verifier = verifier(SignatureFormat::Stripe);
+        let signature = hex::encode(verifier.hmac(&[b"1700000000.", BODY]).unwrap());
+        let valid = headers(&[(
+            "stripe-signature",
+            format!("t=1700000000,v1=00,v1={}", signature),
+        )]);
+
+        assert_eq!(verifier.verify_at(&valid, BODY, 1_700_000_100), Ok(()));
+        assert_eq!(
+            verifier.verify_at(&valid, BODY, 1_700_001_000),
+            Err(VerificationError::ExpiredTimestamp)
+        );
+    }
+
+    #[test]
+    fn verifies_slack_signatures() {
+        let verifier = verifier(SignatureFormat::Slack);
+        let signature = hex::encode(verifier.hmac(&[b"v0:1700000000:", BODY]).unwrap());
+        let valid = headers(&[
+            ("x-slack-signature", format!("v0={}", signature)),
+            ("x-slack-request-timestamp", "1700000000".to_owned()),
+        ]);
+
+        assert_eq!(verifier.verify_at(&valid, BODY, 1_700_000_000), Ok(()));
+        assert_eq!(
+            verifier.verify_at(&valid, b"tampered", 1_700_000_000),
+            Err(VerificationError::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,571 @@
+//! The `windows_event_log` source.
+//!
+//! Reads events from channels of the Windows Event Log, and emits them as structured log events.
+use std::{collections::HashMap, io, path::PathBuf, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use futures::{future::join_all, poll, task::Poll, StreamExt};
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::{fs, sync::Mutex, time::sleep};
+use tracing::Instrument;
+use value::{kind::Collection, Kind};
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol},
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{
+        log_schema, DataType, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, LogEvent},
+    internal_events::{
+        EventsReceived, StreamClosedError, WindowsEventLogCheckpointError,
+        WindowsEventLogParseError, WindowsEventLogReadError, WindowsEventLogSubscribeError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod parser;
+mod subscription;
+
+pub use self::parser::ParseError;
+use self::{
+    parser::ParsedEvent,
+    subscription::{EventBatch, Subscription},
+};
+
+const CHECKPOINTS_FILENAME: &str = "checkpoints.json";
+const CHECKPOINTS_TMP_FILENAME: &str = "checkpoints.new.json";
+
+/// How long to wait for new events to be logged before checking for shutdown.
+const WAIT_DURATION: Duration = Duration::from_millis(500);
+const BACKOFF_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one channel must be given."))]
+    NoChannels,
+    #[snafu(display("Could not read the checkpoints from {:?}: {}", path, source))]
+    ReadCheckpoints { path: PathBuf, source: io::Error },
+}
+
+/// Configuration for the `windows_event_log` source.
+#[configurable_component(source("windows_event_log"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowsEventLogConfig {
+    /// The channels to read events from.
+    #[serde(default = "default_channels")]
+    #[configurable(metadata(
+        docs::examples = "System",
+        docs::examples = "Application",
+        docs::examples = "Microsoft-Windows-Sysmon/Operational"
+    ))]
+    pub channels: Vec<String>,
+
+    /// An XPath query selecting the events to read from each channel.
+    ///
+    /// By default, all events are read.
+    #[serde(default = "default_query")]
+    #[configurable(metadata(
+        docs::examples = "*[System[(Level=1 or Level=2 or Level=3)]]",
+        docs::examples = "*[System[Provider[@Name='Service Control Manager'] and (EventID=7036)]]"
+    ))]
+    pub query: String,
+
+    /// Whether to read the events already logged to the channels when there is no checkpoint for
+    /// them.
+    ///
+    /// By default, only the events logged after Vector starts are read.
+    #[serde(default)]
+    pub read_existing_events: bool,
+
+    /// Whether to render the message of events from the metadata of their provider.
+    ///
+    /// Rendering messages is relatively expensive, and is not possible for events whose provider
+    /// is not installed on the host, such as forwarded events.
+    #[serde(default = "crate::serde::default_true")]
+    pub render_message: bool,
+
+    /// The maximum number of events read from a channel at once.
+    ///
+    /// The bookmark of a channel is checkpointed at the end of each batch.
+    #[serde(default = "default_batch_size")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub batch_size: usize,
+
+    /// The directory used to persist the bookmarks of the channels.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "C:\\ProgramData\\vector"))]
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_channels() -> Vec<String> {
+    vec!["System".to_owned(), "Application".to_owned()]
+}
+
+fn default_query() -> String {
+    "*".to_owned()
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+impl Default for WindowsEventLogConfig {
+    fn default() -> Self {
+        Self {
+            channels: default_channels(),
+            query: default_query(),
+            read_existing_events: false,
+            render_message: true,
+            batch_size: default_batch_size(),
+            data_dir: None,
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(WindowsEventLogConfig);
+
+impl WindowsEventLogConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(
+            Kind::object(Collection::empty().with_unknown(Kind::any())),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            WindowsEventLogConfig::NAME,
+            log_schema()
+                .timestamp_key()
+                .and_then(|key| parse_value_path(key).ok())
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("timestamp"),
+            Kind::timestamp().or_undefined(),
+            Some("timestamp"),
+        )
+        .with_source_metadata(
+            WindowsEventLogConfig::NAME,
+            parse_value_path(log_schema().host_key())
+                .ok()
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("host"),
+            Kind::bytes().or_undefined(),
+            Some("host"),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for WindowsEventLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.channels.is_empty() {
+            return Err(BuildError::NoChannels.into());
+        }
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpointer = Checkpointer::load(data_dir)
+            .await
+            .map_err(|(path, source)| BuildError::ReadCheckpoints { path, source })?;
+
+        let source = WindowsEventLogSource {
+            config: self.clone(),
+            checkpointer: Arc::new(Mutex::new(checkpointer)),
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+struct WindowsEventLogSource {
+    config: WindowsEventLogConfig,
+    checkpointer: Arc<Mutex<Checkpointer>>,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+}
+
+impl WindowsEventLogSource {
+    async fn run(self, out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let source = Arc::new(self);
+        let channels = source.config.channels.iter().map(|channel| {
+            Arc::clone(&source).run_channel(channel.clone(), out.clone(), shutdown.clone())
+        });
+        join_all(channels).await;
+
+        Ok(())
+    }
+
+    /// Reads the events of one channel until shutdown, resubscribing to it on errors.
+    async fn run_channel(
+        self: Arc<Self>,
+        channel: String,
+        mut out: SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) {
+        let bytes_received = register!(BytesReceived::from(Protocol::from("windows_event_log")));
+        let events_received = register!(EventsReceived);
+
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<String>::maybe_new(self.acknowledgements, shutdown.clone());
+        if finalizer.is_some() {
+            let checkpointer = Arc::clone(&self.checkpointer);
+            let channel = channel.clone();
+            tokio::spawn(
+                async move {
+                    while let Some((status, bookmark)) = ack_stream.next().await {
+                        if status == BatchStatus::Delivered {
+                            checkpointer.lock().await.set(&channel, bookmark).await;
+                        }
+                    }
+                }
+                .in_current_span(),
+            );
+        }
+
+        loop {
+            if matches!(poll!(&mut shutdown), Poll::Ready(_)) {
+                break;
+            }
+
+            let bookmark = self.checkpointer.lock().await.get(&channel);
+            let config = self.config.clone();
+            let subscribe_channel = channel.clone();
+            let subscription = tokio::task::spawn_blocking(move || {
+                Subscription::new(
+                    &subscribe_channel,
+                    &config.query,
+                    bookmark.as_deref(),
+                    config.read_existing_events,
+                    config.render_message,
+                )
+            })
+            .await
+            .expect("Subscribing to the channel panicked.");
+
+            let mut subscription = match subscription {
+                Ok(subscription) => subscription,
+                Err(error) => {
+                    emit!(WindowsEventLogSubscribeError {
+                        error,
+                        channel: &channel,
+                    });
+                    tokio::select! {
+                        _ = &mut shutdown => break,
+                        _ = sleep(BACKOFF_DURATION) => continue,
+                    }
+                }
+            };
+
+            loop {
+                if matches!(poll!(&mut shutdown), Poll::Ready(_)) {
+                    return;
+                }
+
+                let batch_size = self.config.batch_size;
+                let (returned, result) = tokio::task::spawn_blocking(move || {
+                    let result = subscription.next_batch(batch_size, WAIT_DURATION);
+                    (subscription, result)
+                })
+                .await
+                .expect("Reading from the channel panicked.");
+                subscription = returned;
+
+                let batch = match result {
+                    Ok(Some(batch)) => batch,
+                    Ok(None) => continue,
+                    Err(error) => {
+                        emit!(WindowsEventLogReadError {
+                            error,
+                            channel: &channel,
+                        });
+                        break;
+                    }
+                };
+
+                bytes_received.emit(ByteSize(batch.byte_size));
+
+                let EventBatch {
+                    events, bookmark, ..
+                } = batch;
+                let (batch_notifier, receiver) =
+                    BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+                let events = events
+                    .into_iter()
+                    .filter_map(|event| match event {
+                        Ok(event) => Some(
+                            create_log_event(event, self.log_namespace)
+                                .with_batch_notifier_option(&batch_notifier),
+                        ),
+                        Err(error) => {
+                            emit!(WindowsEventLogParseError {
+                                error,
+                                channel: &channel,
+                            });
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                drop(batch_notifier);
+
+                let count = events.len();
+                if count > 0 {
+                    events_received.emit(CountByteSize(
+                        count,
+                        events.estimated_json_encoded_size_of(),
+                    ));
+
+                    if let Err(error) = out.send_batch(events).await {
+                        emit!(StreamClosedError { error, count });
+                        return;
+                    }
+                }
+
+                match (&finalizer, receiver) {
+                    (Some(finalizer), Some(receiver)) => finalizer.add(bookmark, receiver),
+                    _ => self.checkpointer.lock().await.set(&channel, bookmark).await,
+                }
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = sleep(BACKOFF_DURATION) => (),
+            }
+        }
+    }
+}
+
+fn create_log_event(event: ParsedEvent, log_namespace: LogNamespace) -> LogEvent {
+    let ParsedEvent {
+        timestamp,
+        computer,
+        mut fields,
+    } = event;
+
+    let message = fields.remove("message");
+    let mut log = LogEvent::from(fields);
+    if let Some(message) = message {
+        match log_namespace {
+            LogNamespace::Vector => log.insert("message", message),
+            LogNamespace::Legacy => log.insert(log_schema().message_key(), message),
+        };
+    }
+
+    if let Some(timestamp) = timestamp {
+        log_namespace.insert_source_metadata(
+            WindowsEventLogConfig::NAME,
+            &mut log,
+            log_schema()
+                .timestamp_key()
+                .and_then(|key| parse_value_path(key).ok())
+                .as_ref()
+                .map(LegacyKey::Overwrite),
+            path!("timestamp"),
+            timestamp,
+        );
+    }
+
+    if let Some(computer) = computer {
+        log_namespace.insert_source_metadata(
+            WindowsEventLogConfig::NAME,
+            &mut log,
+            parse_value_path(log_schema().host_key())
+                .ok()
+                .as_ref()
+                .map(LegacyKey::Overwrite),
+            path!("host"),
+            computer,
+        );
+    }
+
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        WindowsEventLogConfig::NAME,
+        Utc::now(),
+    );
+
+    log
+}
+
+/// The persisted checkpoints of the source, which map each channel to a bookmark rendered as XML.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Checkpoints {
+    bookmarks: HashMap<String, String>,
+}
+
+struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    checkpoints: Checkpoints,
+}
+
+impl Checkpointer {
+    async fn load(data_dir: PathBuf) -> Result<Self, (PathBuf, io::Error)> {
+        let path = data_dir.join(CHECKPOINTS_FILENAME);
+        let tmp_path = data_dir.join(CHECKPOINTS_TMP_FILENAME);
+
+        let checkpoints = match fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|error| {
+                (
+                    path.clone(),
+                    io::Error::new(io::ErrorKind::InvalidData, error),
+                )
+            })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Checkpoints::default(),
+            Err(error) => return Err((path, error)),
+        };
+
+        Ok(Self {
+            path,
+            tmp_path,
+            checkpoints,
+        })
+    }
+
+    fn get(&self, channel: &str) -> Option<String> {
+        self.checkpoints.bookmarks.get(channel).cloned()
+    }
+
+    async fn set(&mut self, channel: &str, bookmark: String) {
+        self.checkpoints
+            .bookmarks
+            .insert(channel.to_owned(), bookmark);
+        if let Err(error) = self.write().await {
+            emit!(WindowsEventLogCheckpointError {
+                error,
+                path: &self.path,
+            });
+        }
+    }
+
+    /// Writes the checkpoints to a temporary file first, which is then renamed over the previous
+    /// checkpoints so that a valid file is always available.
+    async fn write(&self) -> io::Result<()> {
+        let bytes = serde_json::to_vec(&self.checkpoints)?;
+        fs::write(&self.tmp_path, bytes).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+    use value::Value;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsEventLogConfig>();
+    }
+
+    fn parsed_event() -> ParsedEvent {
+        ParsedEvent {
+            timestamp: Some(Utc.ymd(2023, 3, 1).and_hms(12, 0, 0)),
+            computer: Some("WIN-HOST".into()),
+            fields: [
+                ("message".to_owned(), Value::from("Service started.")),
+                ("event_id".to_owned(), Value::from(7036_i64)),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn creates_legacy_log_event() {
+        let log = create_log_event(parsed_event(), LogNamespace::Legacy);
+
+        assert_eq!(log[log_schema().message_key()], "Service started.".into());
+        assert_eq!(log["event_id"], 7036_i64.into());
+        assert_eq!(log[log_schema().host_key()], "WIN-HOST".into());
+        assert_eq!(
+            log[log_schema().timestamp_key().unwrap()],
+            Utc.ymd(2023, 3, 1).and_hms(12, 0, 0).into()
+        );
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            WindowsEventLogConfig::NAME.into()
+        );
+    }
+
+    #[test]
+    fn creates_vector_log_event() {
+        let log = create_log_event(parsed_event(), LogNamespace::Vector);
+
+        assert_eq!(log["message"], "Service started.".into());
+        assert_eq!(log["event_id"], 7036_i64.into());
+        let metadata = log.metadata().value();
+        assert_eq!(
+            metadata.get(path!(WindowsEventLogConfig::NAME, "host")),
+            Some(&Value::from("WIN-HOST"))
+        );
+        assert_eq!(
+            metadata.get(path!(WindowsEventLogConfig::NAME, "timestamp")),
+            Some(&Value::from(Utc.ymd(2023, 3, 1).and_hms(12, 0, 0)))
+        );
+        assert_eq!(
+            metadata.get(path!("vector", "source_type")),
+            Some(&Value::from(WindowsEventLogConfig::NAME))
+        );
+    }
+
+    #[tokio::test]
+    async fn checkpointer_persists_bookmarks() {
+        let data_dir = tempdir().unwrap();
+        let bookmark = r#"<BookmarkList><Bookmark Channel="System" RecordId="42" IsCurrent="true"/></BookmarkList>"#;
+
+        let mut checkpointer = Checkpointer::load(data_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        assert_eq!(checkpointer.get("System"), None);
+        checkpointer.set("System", bookmark.to_owned()).await;
+
+        let checkpointer = Checkpointer::load(data_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        assert_eq!(checkpointer.get("System").as_deref(), Some(bookmark));
+        assert_eq!(checkpointer.get("Application"), None);
+    }
+}
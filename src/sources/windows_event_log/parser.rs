@@ -0,0 +1,332 @@
+//! Parsing of events rendered as XML by the Windows Event Log API.
+//!
+//! Events are rendered following the [event schema][schema], which is flattened here into the
+//! fields of the emitted log events.
+//!
+//! [schema]: https://learn.microsoft.com/en-us/windows/win32/wes/eventschema-schema
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+use snafu::{OptionExt, ResultExt, Snafu};
+use value::Value;
+
+#[derive(Debug, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Invalid event XML: {}", source))]
+    InvalidXml { source: roxmltree::Error },
+    #[snafu(display("Event XML is missing the `{}` element", element))]
+    MissingElement { element: &'static str },
+}
+
+/// The fields of an event extracted from its `System` element, along with its data.
+#[derive(Debug, PartialEq)]
+pub struct ParsedEvent {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub computer: Option<String>,
+    pub fields: BTreeMap<String, Value>,
+}
+
+/// Returns the name of the given event level, as displayed by the Event Viewer.
+pub const fn level_name(level: i64) -> Option<&'static str> {
+    match level {
+        0 => Some("LogAlways"),
+        1 => Some("Critical"),
+        2 => Some("Error"),
+        3 => Some("Warning"),
+        4 => Some("Information"),
+        5 => Some("Verbose"),
+        _ => None,
+    }
+}
+
+/// Parses an event rendered as XML.
+///
+/// The message of the event is formatted by `format_message`, which is given the name of the
+/// provider of the event, as publisher metadata is needed to format it.
+pub fn parse_event<F>(xml: &str, format_message: F) -> Result<ParsedEvent, ParseError>
+where
+    F: FnOnce(Option<&str>) -> Option<String>,
+{
+    let document = Document::parse(xml).context(InvalidXmlSnafu)?;
+    let event = document.root_element();
+    let system = child(event, "System").context(MissingElementSnafu { element: "System" })?;
+
+    let mut fields = BTreeMap::new();
+    let mut timestamp = None;
+    let mut computer = None;
+
+    for node in system.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "Provider" => {
+                insert_attribute(&mut fields, "provider_name", node, "Name");
+                insert_attribute(&mut fields, "provider_guid", node, "Guid");
+                insert_attribute(&mut fields, "event_source_name", node, "EventSourceName");
+            }
+            "EventID" => {
+                insert_integer(&mut fields, "event_id", node.text());
+                if let Some(qualifiers) = node.attribute("Qualifiers") {
+                    insert_integer(&mut fields, "qualifiers", Some(qualifiers));
+                }
+            }
+            "Version" => insert_integer(&mut fields, "version", node.text()),
+            "Level" => {
+                let level = node.text().and_then(|text| text.trim().parse::<i64>().ok());
+                if let Some(level) = level {
+                    fields.insert("level".into(), level.into());
+                    if let Some(name) = level_name(level) {
+                        fields.insert("level_name".into(), name.into());
+                    }
+                }
+            }
+            "Task" => insert_integer(&mut fields, "task", node.text()),
+            "Opcode" => insert_integer(&mut fields, "opcode", node.text()),
+            "Keywords" => insert_text(&mut fields, "keywords", node.text()),
+            "TimeCreated" => {
+                timestamp = node
+                    .attribute("SystemTime")
+                    .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                    .map(|time| time.with_timezone(&Utc));
+            }
+            "EventRecordID" => insert_integer(&mut fields, "record_id", node.text()),
+            "Correlation" => {
+                insert_attribute(&mut fields, "activity_id", node, "ActivityID");
+                insert_attribute(
+                    &mut fields,
+                    "related_activity_id",
+                    node,
+                    "RelatedActivityID",
+                );
+            }
+            "Execution" => {
+                if let Some(process_id) = node.attribute("ProcessID") {
+                    insert_integer(&mut fields, "process_id", Some(process_id));
+                }
+                if let Some(thread_id) = node.attribute("ThreadID") {
+                    insert_integer(&mut fields, "thread_id", Some(thread_id));
+                }
+            }
+            "Channel" => insert_text(&mut fields, "channel", node.text()),
+            "Computer" => computer = node.text().map(|text| text.trim().to_owned()),
+            "Security" => insert_attribute(&mut fields, "user_id", node, "UserID"),
+            _ => {}
+        }
+    }
+
+    let provider_name = fields
+        .get("provider_name")
+        .and_then(Value::as_str)
+        .map(|name| name.into_owned());
+    if let Some(message) = format_message(provider_name.as_deref()) {
+        fields.insert("message".into(), message.trim_end().into());
+    }
+
+    if let Some(event_data) = child(event, "EventData") {
+        fields.insert("event_data".into(), parse_event_data(event_data));
+    }
+
+    if let Some(user_data) = child(event, "UserData") {
+        if let Some(node) = user_data.children().find(Node::is_element) {
+            fields.insert("user_data".into(), parse_element(node));
+        }
+    }
+
+    Ok(ParsedEvent {
+        timestamp,
+        computer,
+        fields,
+    })
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn insert_text(fields: &mut BTreeMap<String, Value>, key: &str, text: Option<&str>) {
+    if let Some(text) = text.map(str::trim).filter(|text| !text.is_empty()) {
+        fields.insert(key.into(), text.into());
+    }
+}
+
+fn insert_integer(fields: &mut BTreeMap<String, Value>, key: &str, text: Option<&str>) {
+    if let Some(num) = text.and_then(|text| text.trim().parse::<i64>().ok()) {
+        fields.insert(key.into(), num.into());
+    }
+}
+
+fn insert_attribute(fields: &mut BTreeMap<String, Value>, key: &str, node: Node, attribute: &str) {
+    insert_text(fields, key, node.attribute(attribute));
+}
+
+/// `EventData` is rendered as an object when all of its `Data` elements are named, as is the case
+/// for events logged with a manifest, and as an array of values otherwise, as is the case for
+/// events logged by classic event sources.
+fn parse_event_data(node: Node) -> Value {
+    let data = node
+        .children()
+        .filter(|child| child.is_element() && child.tag_name().name() == "Data")
+        .collect::<Vec<_>>();
+
+    if !data.is_empty() && data.iter().all(|data| data.attribute("Name").is_some()) {
+        Value::Object(
+            data.into_iter()
+                .map(|data| {
+                    let name = data.attribute("Name").unwrap_or_default();
+                    (name.into(), data.text().unwrap_or_default().into())
+                })
+                .collect(),
+        )
+    } else {
+        Value::Array(
+            data.into_iter()
+                .map(|data| data.text().unwrap_or_default().into())
+                .collect(),
+        )
+    }
+}
+
+/// `UserData` contains an arbitrary, provider defined, element which is turned into an object of
+/// its child elements, or into its text if it has none.
+fn parse_element(node: Node) -> Value {
+    let children = node.children().filter(Node::is_element).collect::<Vec<_>>();
+    if children.is_empty() {
+        return node.text().map(str::trim).unwrap_or_default().into();
+    }
+
+    Value::Object(
+        children
+            .into_iter()
+            .map(|child| (child.tag_name().name().into(), parse_element(child)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    const SERVICE_EVENT: &str = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Service Control Manager" Guid="{555908d1-a6d7-4695-8e1e-26931d2012f4}" EventSourceName="Service Control Manager"/>
+    <EventID Qualifiers="16384">7036</EventID>
+    <Version>0</Version>
+    <Level>4</Level>
+    <Task>0</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8080000000000000</Keywords>
+    <TimeCreated SystemTime="2023-03-01T12:34:56.7890123Z"/>
+    <EventRecordID>12345</EventRecordID>
+    <Correlation/>
+    <Execution ProcessID="636" ThreadID="4868"/>
+    <Channel>System</Channel>
+    <Computer>WIN-HOST</Computer>
+    <Security/>
+  </System>
+  <EventData>
+    <Data Name="param1">Windows Update</Data>
+    <Data Name="param2">running</Data>
+  </EventData>
+</Event>"#;
+
+    #[test]
+    fn parses_system_and_named_event_data() {
+        let event = parse_event(SERVICE_EVENT, |provider_name| {
+            assert_eq!(provider_name, Some("Service Control Manager"));
+            Some("The Windows Update service entered the running state.\r\n".into())
+        })
+        .unwrap();
+
+        assert_eq!(
+            event.timestamp,
+            Some(Utc.ymd(2023, 3, 1).and_hms_nano(12, 34, 56, 789_012_300))
+        );
+        assert_eq!(event.computer.as_deref(), Some("WIN-HOST"));
+
+        let mut expected = BTreeMap::new();
+        expected.insert(
+            "message".into(),
+            "The Windows Update service entered the running state.".into(),
+        );
+        expected.insert("provider_name".into(), "Service Control Manager".into());
+        expected.insert(
+            "provider_guid".into(),
+            "{555908d1-a6d7-4695-8e1e-26931d2012f4}".into(),
+        );
+        expected.insert("event_source_name".into(), "Service Control Manager".into());
+        expected.insert("event_id".into(), 7036_i64.into());
+        expected.insert("qualifiers".into(), 16384_i64.into());
+        expected.insert("version".into(), 0_i64.into());
+        expected.insert("level".into(), 4_i64.into());
+        expected.insert("level_name".into(), "Information".into());
+        expected.insert("task".into(), 0_i64.into());
+        expected.insert("opcode".into(), 0_i64.into());
+        expected.insert("keywords".into(), "0x8080000000000000".into());
+        expected.insert("record_id".into(), 12345_i64.into());
+        expected.insert("process_id".into(), 636_i64.into());
+        expected.insert("thread_id".into(), 4868_i64.into());
+        expected.insert("channel".into(), "System".into());
+        expected.insert(
+            "event_data".into(),
+            Value::Object(BTreeMap::from([
+                ("param1".into(), "Windows Update".into()),
+                ("param2".into(), "running".into()),
+            ])),
+        );
+        assert_eq!(event.fields, expected);
+    }
+
+    #[test]
+    fn parses_unnamed_event_data_and_user_data() {
+        let xml = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Application Error"/>
+    <EventID>1000</EventID>
+    <Level>2</Level>
+    <Security UserID="S-1-5-18"/>
+  </System>
+  <EventData>
+    <Data>app.exe</Data>
+    <Data>1.0.0.0</Data>
+  </EventData>
+  <UserData>
+    <LogFileCleared xmlns="http://manifests.microsoft.com/win/2004/08/windows/eventlog">
+      <SubjectUserName>admin</SubjectUserName>
+      <SubjectDomainName>CORP</SubjectDomainName>
+    </LogFileCleared>
+  </UserData>
+</Event>"#;
+        let event = parse_event(xml, |_| None).unwrap();
+
+        assert_eq!(event.timestamp, None);
+        assert_eq!(event.fields["level_name"], "Error".into());
+        assert_eq!(event.fields["user_id"], "S-1-5-18".into());
+        assert!(!event.fields.contains_key("message"));
+        assert_eq!(
+            event.fields["event_data"],
+            Value::Array(vec!["app.exe".into(), "1.0.0.0".into()])
+        );
+        assert_eq!(
+            event.fields["user_data"],
+            Value::Object(BTreeMap::from([
+                ("SubjectUserName".into(), "admin".into()),
+                ("SubjectDomainName".into(), "CORP".into()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        assert!(matches!(
+            parse_event("<Event", |_| None),
+            Err(ParseError::InvalidXml { .. })
+        ));
+        assert!(matches!(
+            parse_event("<Event></Event>", |_| None),
+            Err(ParseError::MissingElement { element: "System" })
+        ));
+    }
+}
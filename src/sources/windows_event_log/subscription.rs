@@ -0,0 +1,311 @@
+//! A pull subscription to a channel of the Windows Event Log, through the [Windows Event Log
+//! API][api].
+//!
+//! [api]: https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log
+use std::{
+    collections::HashMap, ffi::OsStr, io, iter, os::windows::ffi::OsStrExt, ptr, time::Duration,
+};
+
+use windows_sys::Win32::{
+    Foundation::{
+        CloseHandle, GetLastError, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, HANDLE,
+    },
+    System::{
+        EventLog::{
+            EvtClose, EvtCreateBookmark, EvtFormatMessage, EvtFormatMessageEvent, EvtNext,
+            EvtOpenPublisherMetadata, EvtRender, EvtRenderBookmark, EvtRenderEventXml,
+            EvtSubscribe, EvtSubscribeStartAfterBookmark, EvtSubscribeStartAtOldestRecord,
+            EvtSubscribeToFutureEvents, EvtUpdateBookmark, EVT_HANDLE,
+        },
+        Threading::{CreateEventW, ResetEvent, WaitForSingleObject},
+    },
+};
+
+use super::parser::{parse_event, ParseError, ParsedEvent};
+
+/// The events read from a channel in one call to [`Subscription::next_batch`].
+pub struct EventBatch {
+    pub events: Vec<Result<ParsedEvent, ParseError>>,
+    /// The total size of the rendered events.
+    pub byte_size: usize,
+    /// The bookmark positioned after the last event of the batch, rendered as XML.
+    pub bookmark: String,
+}
+
+/// An owned handle to a Windows Event Log object, closed on drop.
+struct EvtHandle(EVT_HANDLE);
+
+impl EvtHandle {
+    fn new(handle: EVT_HANDLE) -> io::Result<Self> {
+        if handle == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Self(handle))
+        }
+    }
+}
+
+impl Drop for EvtHandle {
+    fn drop(&mut self) {
+        // SAFETY: the handle is valid, and is not used after being closed.
+        unsafe { EvtClose(self.0) };
+    }
+}
+
+/// An owned handle to an event object, closed on drop.
+struct SignalHandle(HANDLE);
+
+impl Drop for SignalHandle {
+    fn drop(&mut self) {
+        // SAFETY: the handle is valid, and is not used after being closed.
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+pub struct Subscription {
+    // Declared before the signal so that the subscription is closed first.
+    subscription: EvtHandle,
+    bookmark: EvtHandle,
+    signal: SignalHandle,
+    render_message: bool,
+    /// The publisher metadata for each provider seen so far, or `None` if it could not be opened.
+    publishers: HashMap<String, Option<EvtHandle>>,
+}
+
+impl Subscription {
+    /// Subscribes to the events of `channel` selected by the XPath `query`. Events are read after
+    /// the given bookmark if any, or from either the oldest event of the channel or the next event
+    /// to be logged otherwise.
+    pub fn new(
+        channel: &str,
+        query: &str,
+        bookmark: Option<&str>,
+        read_existing_events: bool,
+        render_message: bool,
+    ) -> io::Result<Self> {
+        let bookmark_xml = bookmark.map(to_wide);
+        // SAFETY: the bookmark XML is a valid null terminated string, or null to create an empty
+        // bookmark.
+        let bookmark_handle = EvtHandle::new(unsafe {
+            EvtCreateBookmark(
+                bookmark_xml
+                    .as_ref()
+                    .map_or(ptr::null(), |bookmark| bookmark.as_ptr()),
+            )
+        })?;
+
+        let flags = if bookmark.is_some() {
+            EvtSubscribeStartAfterBookmark
+        } else if read_existing_events {
+            EvtSubscribeStartAtOldestRecord
+        } else {
+            EvtSubscribeToFutureEvents
+        };
+
+        // SAFETY: all arguments are null or valid. The event is manual reset and initially
+        // signaled so that existing events are read straight away.
+        let signal = unsafe { CreateEventW(ptr::null(), 1, 1, ptr::null()) };
+        if signal == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let signal = SignalHandle(signal);
+
+        let channel = to_wide(channel);
+        let query = to_wide(query);
+        // SAFETY: the strings are valid null terminated strings, and the handles are valid. In pull
+        // mode, without a callback, no context is needed.
+        let subscription = unsafe {
+            EvtSubscribe(
+                0,
+                signal.0,
+                channel.as_ptr(),
+                query.as_ptr(),
+                bookmark.map_or(0, |_| bookmark_handle.0),
+                ptr::null(),
+                None,
+                flags,
+            )
+        };
+        let subscription = EvtHandle::new(subscription)?;
+
+        Ok(Self {
+            subscription,
+            bookmark: bookmark_handle,
+            signal,
+            render_message,
+            publishers: HashMap::new(),
+        })
+    }
+
+    /// Reads up to `max` events, waiting up to `wait` for new events to be logged if there are
+    /// none. Returns `None` if no events were read.
+    pub fn next_batch(&mut self, max: usize, wait: Duration) -> io::Result<Option<EventBatch>> {
+        let mut handles = vec![0; max];
+        let mut returned = 0;
+        // SAFETY: the handles buffer holds `max` handles.
+        let ok = unsafe {
+            EvtNext(
+                self.subscription.0,
+                max as u32,
+                handles.as_mut_ptr(),
+                0,
+                0,
+                &mut returned,
+            )
+        };
+        if ok == 0 {
+            // SAFETY: no other calls were made since `EvtNext` failed.
+            let error = unsafe { GetLastError() };
+            if error != ERROR_NO_MORE_ITEMS {
+                return Err(io::Error::from_raw_os_error(error as i32));
+            }
+
+            // SAFETY: the signal is a valid event handle.
+            unsafe {
+                WaitForSingleObject(self.signal.0, wait.as_millis() as u32);
+                ResetEvent(self.signal.0);
+            }
+            return Ok(None);
+        }
+
+        // Take ownership of all the returned handles first, so that they are closed even if
+        // rendering one of them fails.
+        let handles = handles
+            .into_iter()
+            .take(returned as usize)
+            .map(EvtHandle)
+            .collect::<Vec<_>>();
+
+        let mut events = Vec::with_capacity(handles.len());
+        let mut byte_size = 0;
+        for handle in &handles {
+            let xml = render(handle.0, EvtRenderEventXml)?;
+            byte_size += xml.len();
+            events.push(parse_event(&xml, |provider_name| {
+                if self.render_message {
+                    self.format_message(handle.0, provider_name?)
+                } else {
+                    None
+                }
+            }));
+
+            // SAFETY: both handles are valid.
+            if unsafe { EvtUpdateBookmark(self.bookmark.0, handle.0) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        let bookmark = render(self.bookmark.0, EvtRenderBookmark)?;
+
+        Ok(Some(EventBatch {
+            events,
+            byte_size,
+            bookmark,
+        }))
+    }
+
+    /// Formats the message string of the event from the metadata of its publisher. Returns `None`
+    /// if the publisher has no message for the event, or if its metadata is not available, for
+    /// example when the event was forwarded from another host.
+    fn format_message(&mut self, event: EVT_HANDLE, provider_name: &str) -> Option<String> {
+        let publisher = self
+            .publishers
+            .entry(provider_name.to_owned())
+            .or_insert_with(|| {
+                let provider_name = to_wide(provider_name);
+                // SAFETY: the provider name is a valid null terminated string.
+                EvtHandle::new(unsafe {
+                    EvtOpenPublisherMetadata(0, provider_name.as_ptr(), ptr::null(), 0, 0)
+                })
+                .ok()
+            })
+            .as_ref()?;
+
+        let mut used = 0;
+        // SAFETY: a null buffer of size zero is allowed to query the required size.
+        let ok = unsafe {
+            EvtFormatMessage(
+                publisher.0,
+                event,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent,
+                0,
+                ptr::null_mut(),
+                &mut used,
+            )
+        };
+        if ok != 0 || unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+            return None;
+        }
+
+        // The buffer size is in characters.
+        let mut buffer = vec![0_u16; used as usize];
+        // SAFETY: the buffer holds `used` characters.
+        let ok = unsafe {
+            EvtFormatMessage(
+                publisher.0,
+                event,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent,
+                used,
+                buffer.as_mut_ptr(),
+                &mut used,
+            )
+        };
+        (ok != 0).then(|| from_wide(&buffer))
+    }
+}
+
+/// Renders an event or a bookmark as XML.
+fn render(handle: EVT_HANDLE, flags: u32) -> io::Result<String> {
+    let mut used = 0;
+    let mut property_count = 0;
+    // SAFETY: a null buffer of size zero is allowed to query the required size.
+    let ok = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            0,
+            ptr::null_mut(),
+            &mut used,
+            &mut property_count,
+        )
+    };
+    if ok == 0 && unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+        return Err(io::Error::last_os_error());
+    }
+
+    // The buffer size is in bytes.
+    let mut buffer = vec![0_u16; (used as usize + 1) / 2];
+    // SAFETY: the buffer holds at least `used` bytes.
+    let ok = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            used,
+            buffer.as_mut_ptr().cast(),
+            &mut used,
+            &mut property_count,
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(from_wide(&buffer))
+}
+
+fn to_wide(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(iter::once(0)).collect()
+}
+
+fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
@@ -1,4 +1,10 @@
-use std::{borrow::Cow, collections::BTreeMap, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    time::Duration,
+};
 
 use colored::{ColoredString, Colorize};
 use tokio_stream::StreamExt;
@@ -59,13 +65,25 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
             .collect()
     };
 
-    let formatter = EventFormatter::new(opts.meta, opts.format);
+    let mut writer = match EventWriter::new(opts) {
+        Ok(writer) => writer,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("[tap] Couldn't create the output file: {}", error);
+            }
+            return exitcode::CANTCREAT;
+        }
+    };
+    // The events written to a file aren't colored.
+    let formatter = EventFormatter::new(opts.meta, opts.format, opts.output.is_none());
 
+    let mut exit_status = exitcode::OK;
     loop {
         tokio::select! {
             biased;
-            Ok(SignalTo::Shutdown | SignalTo::Quit) = signal_rx.recv() => break,
-            status = run(url.clone(), opts, outputs_patterns.clone(), formatter.clone()) => {
+            Ok(SignalTo::Shutdown | SignalTo::Drain(_) | SignalTo::Quit) = signal_rx.recv() => break,
+            status = run(url.clone(), opts, outputs_patterns.clone(), formatter.clone(), &mut writer) => {
                 if status == exitcode::UNAVAILABLE || status == exitcode::TEMPFAIL && !opts.no_reconnect {
                     #[allow(clippy::print_stderr)]
                     {
@@ -73,13 +91,53 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
                     }
                     tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
                 } else {
+                    if status == exitcode::IOERR {
+                        exit_status = status;
+                    }
                     break;
                 }
             }
         }
     }
 
-    exitcode::OK
+    exit_status
+}
+
+/// Writes the observed events to the screen or to the output file, until enough of them were.
+struct EventWriter {
+    output: Box<dyn Write + Send>,
+    /// How many more events to observe before exiting, if limited.
+    remaining: Option<usize>,
+}
+
+impl EventWriter {
+    fn new(opts: &super::Opts) -> io::Result<Self> {
+        let output: Box<dyn Write + Send> = match &opts.output {
+            Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+            None => Box::new(io::stdout()),
+        };
+
+        Ok(Self {
+            output,
+            remaining: opts.count,
+        })
+    }
+
+    /// Writes an event, returning whether more events are expected.
+    fn write(&mut self, event: &str) -> io::Result<bool> {
+        if self.remaining == Some(0) {
+            return Ok(false);
+        }
+        writeln!(self.output, "{}", event)?;
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        Ok(self.remaining != Some(0))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
 }
 
 async fn run(
@@ -87,8 +145,9 @@ async fn run(
     opts: &super::Opts,
     outputs_patterns: Vec<String>,
     formatter: EventFormatter,
+    writer: &mut EventWriter,
 ) -> exitcode::ExitCode {
-    let subscription_client = match connect_subscription_client(url).await {
+    let subscription_client = match connect_subscription_client(url, opts.token.clone()).await {
         Ok(c) => c,
         Err(e) => {
             #[allow(clippy::print_stderr)]
@@ -106,34 +165,55 @@ async fn run(
             opts.format,
             opts.limit as i64,
             opts.interval as i64,
+            opts.condition.clone(),
         );
     };
 
-    // Loop over the returned results, printing out tap events.
-    #[allow(clippy::print_stdout)]
+    // Loop over the returned results, writing out tap events.
     #[allow(clippy::print_stderr)]
     loop {
         let message = stream.next().await;
         if let Some(Some(res)) = message {
             if let Some(d) = res.data {
                 for tap_event in d.output_events_by_component_id_patterns.iter() {
-                    match tap_event {
+                    let written = match tap_event {
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Log(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            writer.write(&formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()))
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Metric(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            writer.write(&formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()))
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Trace(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            writer.write(&formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()))
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::EventNotification(ev) => {
                             if !opts.quiet {
                                 eprintln!("{}", ev.message);
                             }
+                            Ok(true)
                         },
+                    };
+                    match written {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            return match writer.flush() {
+                                Ok(()) => exitcode::OK,
+                                Err(error) => {
+                                    eprintln!("[tap] Couldn't write the events: {}", error);
+                                    exitcode::IOERR
+                                }
+                            };
+                        }
+                        Err(error) => {
+                            eprintln!("[tap] Couldn't write the events: {}", error);
+                            return exitcode::IOERR;
+                        }
                     }
                 }
+                if let Err(error) = writer.flush() {
+                    eprintln!("[tap] Couldn't write the events: {}", error);
+                    return exitcode::IOERR;
+                }
             }
         } else {
             return exitcode::TEMPFAIL;
@@ -145,19 +225,21 @@ async fn run(
 struct EventFormatter {
     meta: bool,
     format: TapEncodingFormat,
+    color: bool,
     component_id_label: ColoredString,
     component_kind_label: ColoredString,
     component_type_label: ColoredString,
 }
 
 impl EventFormatter {
-    fn new(meta: bool, format: TapEncodingFormat) -> Self {
+    fn new(meta: bool, format: TapEncodingFormat, color: bool) -> Self {
         Self {
             meta,
             format,
-            component_id_label: "component_id".green(),
-            component_kind_label: "component_kind".green(),
-            component_type_label: "component_type".green(),
+            color,
+            component_id_label: paint("component_id", color),
+            component_kind_label: paint("component_kind", color),
+            component_type_label: paint("component_type", color),
         }
     }
 
@@ -173,11 +255,11 @@ impl EventFormatter {
                 TapEncodingFormat::Json => format!(
                     r#"{{"{}":"{}","{}":"{}","{}":"{}","event":{}}}"#,
                     self.component_id_label,
-                    component_id.green(),
+                    paint(component_id, self.color),
                     self.component_kind_label,
-                    component_kind.green(),
+                    paint(component_kind, self.color),
                     self.component_type_label,
-                    component_type.green(),
+                    paint(component_type, self.color),
                     event
                 )
                 .into(),
@@ -191,22 +273,22 @@ impl EventFormatter {
                         "{}{}: {}\n{}: {}\n{}: {}\n",
                         serde_yaml::to_string(&value).unwrap(),
                         self.component_id_label,
-                        component_id.green(),
+                        paint(component_id, self.color),
                         self.component_kind_label,
-                        component_kind.green(),
+                        paint(component_kind, self.color),
                         self.component_type_label,
-                        component_type.green()
+                        paint(component_type, self.color)
                     )
                     .into()
                 }
                 TapEncodingFormat::Logfmt => format!(
                     "{}={} {}={} {}={} {}",
                     self.component_id_label,
-                    component_id.green(),
+                    paint(component_id, self.color),
                     self.component_kind_label,
-                    component_kind.green(),
+                    paint(component_kind, self.color),
                     self.component_type_label,
-                    component_type.green(),
+                    paint(component_type, self.color),
                     event
                 )
                 .into(),
@@ -216,3 +298,12 @@ impl EventFormatter {
         }
     }
 }
+
+/// Colors a piece of metadata, if the events are colored.
+fn paint(value: &str, color: bool) -> ColoredString {
+    if color {
+        value.green()
+    } else {
+        value.normal()
+    }
+}
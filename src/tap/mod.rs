@@ -1,5 +1,7 @@
 mod cmd;
 
+use std::path::PathBuf;
+
 use clap::Parser;
 pub(crate) use cmd::cmd;
 use url::Url;
@@ -16,6 +18,10 @@ pub struct Opts {
     #[arg(short, long)]
     url: Option<Url>,
 
+    /// Token to access the Vector GraphQL API server with, if it requires one
+    #[arg(long, env = "VECTOR_API_TOKEN")]
+    token: Option<String>,
+
     /// Maximum number of events to sample each interval
     #[arg(default_value = "100", short = 'l', long)]
     limit: u32,
@@ -24,6 +30,14 @@ pub struct Opts {
     #[arg(default_value = "json", short = 'f', long)]
     format: TapEncodingFormat,
 
+    /// File to write the events to instead of printing them to screen
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Exit once this many events were observed
+    #[arg(long)]
+    count: Option<usize>,
+
     /// Components IDs to observe (comma-separated; accepts glob patterns)
     #[arg(value_delimiter(','))]
     component_id_patterns: Vec<String>,
@@ -36,6 +50,10 @@ pub struct Opts {
     #[arg(value_delimiter(','), long)]
     inputs_of: Vec<String>,
 
+    /// VRL boolean expression that events must match to be observed, evaluated by the Vector API server
+    #[arg(long)]
+    condition: Option<String>,
+
     /// Quiet output includes only events
     #[arg(short, long)]
     quiet: bool,
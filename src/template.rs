@@ -4,7 +4,7 @@ use std::{borrow::Cow, convert::TryFrom, fmt, hash::Hash, path::PathBuf};
 use bytes::Bytes;
 use chrono::{
     format::{strftime::StrftimeItems, Item},
-    Utc,
+    DateTime, Utc,
 };
 use lookup::lookup_v2::parse_target_path;
 use lookup::PathPrefix;
@@ -154,24 +154,63 @@ impl Template {
         if self.is_static {
             Ok(self.src.clone())
         } else {
-            self.render_event(event.into())
+            let event = event.into();
+            self.render_event(event, event_timestamp(event))
         }
     }
 
-    fn render_event(&self, event: EventRef<'_>) -> Result<String, TemplateRenderingError> {
+    /// Renders the given template with data from the event, using `timestamp` rather than the
+    /// timestamp of the event for the time format specifiers.
+    pub fn render_string_at<'a>(
+        &self,
+        event: impl Into<EventRef<'a>>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<String, TemplateRenderingError> {
+        if self.is_static {
+            Ok(self.src.clone())
+        } else {
+            self.render_event(event.into(), Some(timestamp))
+        }
+    }
+
+    /// Renders only the parts of the template containing time format specifiers with `timestamp`,
+    /// which identifies the time period that the template renders `timestamp` into.
+    ///
+    /// Returns `None` if the template has no time format specifiers.
+    pub fn render_time_period(&self, timestamp: DateTime<Utc>) -> Option<String> {
+        let mut out = None;
+        for part in &self.parts {
+            if let Part::Strftime(items) = part {
+                out.get_or_insert_with(String::new)
+                    .push_str(&timestamp.format_with_items(items.as_items()).to_string());
+            }
+        }
+        out
+    }
+
+    fn render_event(
+        &self,
+        event: EventRef<'_>,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> Result<String, TemplateRenderingError> {
         let mut missing_keys = Vec::new();
         let mut out = String::with_capacity(self.reserve_size);
         for part in &self.parts {
             match part {
                 Part::Literal(lit) => out.push_str(lit),
-                Part::Strftime(items) => out.push_str(&render_timestamp(items, event)),
+                Part::Strftime(items) => out.push_str(
+                    &timestamp
+                        .unwrap_or_else(Utc::now)
+                        .format_with_items(items.as_items())
+                        .to_string(),
+                ),
                 Part::Reference(key) => {
                     out.push_str(
                         &match event {
                             EventRef::Log(log) => log.get(&**key).map(Value::to_string_lossy),
-                            EventRef::Metric(metric) => {
-                                render_metric_field(key, metric).map(Cow::Borrowed)
-                            }
+                            EventRef::Metric(metric) => render_metric_field(key, metric)
+                                .map(Cow::Borrowed)
+                                .or_else(|| render_metric_metadata(key, metric)),
                             EventRef::Trace(trace) => {
                                 trace.get(key.as_str()).map(Value::to_string_lossy)
                             }
@@ -207,6 +246,14 @@ impl Template {
         (!parts.is_empty()).then_some(parts)
     }
 
+    /// Returns the field referenced by this template, if the template is nothing but that reference.
+    pub fn get_field_reference(&self) -> Option<&str> {
+        match self.parts.as_slice() {
+            [Part::Reference(field)] => Some(field),
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the template string.
     pub fn get_ref(&self) -> &str {
         &self.src
@@ -341,8 +388,21 @@ fn render_metric_field<'a>(key: &str, metric: &'a Metric) -> Option<&'a str> {
     }
 }
 
-fn render_timestamp(items: &ParsedStrftime, event: EventRef<'_>) -> String {
-    match event {
+fn render_metric_metadata<'a>(key: &str, metric: &'a Metric) -> Option<Cow<'a, str>> {
+    match parse_target_path(key) {
+        Ok(path) if matches!(path.prefix, PathPrefix::Metadata) => metric
+            .metadata()
+            .value()
+            .get(&path.path)
+            .map(Value::to_string_lossy),
+        _ => None,
+    }
+}
+
+/// Returns the timestamp of the event that templates render time format specifiers with, if it
+/// has one. Events without a timestamp are rendered with the current time.
+pub fn event_timestamp<'a>(event: impl Into<EventRef<'a>>) -> Option<DateTime<Utc>> {
+    match event.into() {
         EventRef::Log(log) => log_schema().timestamp_key().and_then(|timestamp_key| {
             log.get((PathPrefix::Event, timestamp_key))
                 .and_then(Value::as_timestamp)
@@ -356,15 +416,12 @@ fn render_timestamp(items: &ParsedStrftime, event: EventRef<'_>) -> String {
                 .copied()
         }),
     }
-    .unwrap_or_else(Utc::now)
-    .format_with_items(items.as_items())
-    .to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use chrono::TimeZone;
-    use lookup::metadata_path;
+    use lookup::{metadata_path, path};
     use vector_core::metric_tags;
 
     use super::*;
@@ -389,6 +446,21 @@ mod tests {
         assert_eq!(f4, None);
     }
 
+    #[test]
+    fn get_field_reference() {
+        let field = |src: &str| {
+            Template::try_from(src)
+                .unwrap()
+                .get_field_reference()
+                .map(ToOwned::to_owned)
+        };
+
+        assert_eq!(field("{{ foo }}"), Some("foo".to_owned()));
+        assert_eq!(field("{{ foo }}-{{ bar }}"), None);
+        assert_eq!(field("{{ foo }}.log"), None);
+        assert_eq!(field("nofield"), None);
+    }
+
     #[test]
     fn is_dynamic() {
         assert!(Template::try_from("/kube-demo/%F").unwrap().is_dynamic());
@@ -507,6 +579,53 @@ mod tests {
         assert_eq!(Ok(Bytes::from("abcd-2001-02-03")), template.render(&event))
     }
 
+    #[test]
+    fn render_log_at_timestamp() {
+        let ts = Utc
+            .ymd(2001, 2, 3)
+            .and_hms_opt(4, 5, 6)
+            .expect("invalid timestamp");
+        let other_ts = Utc
+            .ymd(2002, 3, 4)
+            .and_hms_opt(5, 6, 7)
+            .expect("invalid timestamp");
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("foo", "bar");
+        event.as_mut_log().insert(
+            (
+                lookup::PathPrefix::Event,
+                log_schema().timestamp_key().unwrap(),
+            ),
+            ts,
+        );
+
+        let template = Template::try_from("{{ foo }}/%F/%H").unwrap();
+
+        assert_eq!(
+            Ok("bar/2002-03-04/05".to_owned()),
+            template.render_string_at(&event, other_ts)
+        );
+        assert_eq!(Some(ts), event_timestamp(&event));
+    }
+
+    #[test]
+    fn render_time_period() {
+        let ts = Utc
+            .ymd(2001, 2, 3)
+            .and_hms_opt(4, 5, 6)
+            .expect("invalid timestamp");
+
+        let template = Template::try_from("{{ foo }}/date=%F/hour=%H").unwrap();
+        assert_eq!(
+            Some("/date=2001-02-03/hour=04".to_owned()),
+            template.render_time_period(ts)
+        );
+
+        let template = Template::try_from("{{ foo }}/static").unwrap();
+        assert_eq!(None, template.render_time_period(ts));
+    }
+
     #[test]
     fn render_log_timestamp_multiple_strftime_style() {
         let ts = Utc
@@ -662,6 +781,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_metric_metadata() {
+        let template =
+            Template::try_from("{{%vector.component_id}}-{{%vector.source_type}}").unwrap();
+        let mut metric = sample_metric();
+        let metadata = metric.metadata_mut().value_mut();
+        metadata.insert(path!("vector", "component_id"), "in");
+        metadata.insert(path!("vector", "source_type"), "statsd");
+        assert_eq!(Ok(Bytes::from("in-statsd")), template.render(&metric));
+    }
+
+    #[test]
+    fn render_metric_missing_metadata() {
+        let template = Template::try_from("{{%vector.component_id}}").unwrap();
+        assert_eq!(
+            Err(TemplateRenderingError::MissingKeys {
+                missing_keys: vec!["%vector.component_id".into()]
+            }),
+            template.render(&sample_metric())
+        );
+    }
+
     fn sample_metric() -> Metric {
         Metric::new(
             "a-counter",
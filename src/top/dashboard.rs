@@ -9,14 +9,14 @@ use crossterm::{
 };
 use num_format::{Locale, ToFormattedString};
 use number_prefix::NumberPrefix;
-use std::io::stdout;
+use std::{collections::VecDeque, io::stdout};
 use tokio::sync::oneshot;
 use tui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table, TableState, Wrap},
     Frame, Terminal,
 };
 
@@ -104,10 +104,38 @@ fn format_metric(total: i64, throughput: i64, human_metrics: bool) -> String {
     }
 }
 
+/// Bars used to render sparklines, from the lowest to the highest value
+const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Number of samples rendered in the sparklines of the components table
+const SPARKLINE_LEN: usize = 20;
+
+/// Format the most recent samples as a sparkline, scaled to the largest value shown. Returns `--`
+/// if there are no samples yet.
+fn format_sparkline(samples: &VecDeque<i64>) -> String {
+    let samples = samples
+        .iter()
+        .skip(samples.len().saturating_sub(SPARKLINE_LEN))
+        .map(|v| (*v).max(0))
+        .collect::<Vec<_>>();
+    let max = match samples.iter().max() {
+        Some(max) => *max,
+        None => return "--".into(),
+    };
+
+    samples
+        .into_iter()
+        .map(|v| match max {
+            0 => SPARKLINE_BARS[0],
+            _ => SPARKLINE_BARS[(v * (SPARKLINE_BARS.len() as i64 - 1) / max) as usize],
+        })
+        .collect()
+}
+
 const NUM_COLUMNS: usize = if is_allocation_tracking_enabled() {
-    9
+    10
 } else {
-    8
+    9
 };
 
 static HEADER: [&str; NUM_COLUMNS] = [
@@ -117,12 +145,37 @@ static HEADER: [&str; NUM_COLUMNS] = [
     "Type",
     "Events In",
     "Events Out",
+    "Out/s History",
     "Bytes",
     "Errors",
     #[cfg(feature = "allocation-tracing")]
     "Mem Usage Bytes",
 ];
 
+/// What the user selected from the dashboard, which is kept across renders.
+#[derive(Default)]
+struct View {
+    /// Index of the selected component
+    selected: usize,
+    /// Whether the detail pane of the selected component is shown
+    show_detail: bool,
+    table_state: TableState,
+}
+
+impl View {
+    fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    fn select_next(&mut self, num_components: usize) {
+        self.selected = (self.selected + 1).min(num_components.saturating_sub(1));
+    }
+
+    fn toggle_detail(&mut self) {
+        self.show_detail = !self.show_detail;
+    }
+}
+
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
     url_string: &'a str,
@@ -175,7 +228,13 @@ impl<'a> Widgets<'a> {
 
     /// Renders a components table, showing sources, transforms and sinks in tabular form, with
     /// statistics pulled from `ComponentsState`,
-    fn components_table<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, area: Rect) {
+    fn components_table<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        state: &state::State,
+        view: &mut View,
+        area: Rect,
+    ) {
         // Header columns
         let header = HEADER
             .iter()
@@ -184,7 +243,12 @@ impl<'a> Widgets<'a> {
 
         // Data columns
         let mut items = Vec::new();
-        for (_, r) in state.components.iter() {
+        let mut selected_row = None;
+        for (index, r) in state.components.values().enumerate() {
+            if index == view.selected {
+                selected_row = Some(items.len());
+            }
+
             let mut data = vec![
                 r.key.id().to_string(),
                 (!r.has_displayable_outputs())
@@ -206,6 +270,7 @@ impl<'a> Widgets<'a> {
                     r.sent_events_throughput_sec,
                     self.opts.human_metrics,
                 ),
+                format_sparkline(&r.sent_events_throughput_history),
                 format_metric(
                     r.processed_bytes_total,
                     r.processed_bytes_throughput_sec,
@@ -245,6 +310,7 @@ impl<'a> Widgets<'a> {
         let w = Table::new(items)
             .header(Row::new(header).bottom_margin(1))
             .block(Block::default().borders(Borders::ALL).title("Components"))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .column_spacing(2)
             .widths(if is_allocation_tracking_enabled() {
                 &[
@@ -254,6 +320,7 @@ impl<'a> Widgets<'a> {
                     Constraint::Percentage(10), // Type
                     Constraint::Percentage(10), // Events In
                     Constraint::Percentage(10), // Events Out
+                    Constraint::Percentage(10), // Out/s History
                     Constraint::Percentage(10), // Bytes
                     Constraint::Percentage(5),  // Errors
                     Constraint::Percentage(16), // Allocated Bytes
@@ -261,16 +328,78 @@ impl<'a> Widgets<'a> {
             } else {
                 &[
                     Constraint::Percentage(15), // ID
-                    Constraint::Percentage(15), // Output
+                    Constraint::Percentage(10), // Output
                     Constraint::Percentage(10), // Kind
                     Constraint::Percentage(10), // Type
                     Constraint::Percentage(10), // Events In
                     Constraint::Percentage(10), // Events Out
+                    Constraint::Percentage(15), // Out/s History
                     Constraint::Percentage(10), // Bytes
                     Constraint::Percentage(10), // Errors
                 ]
             });
-        f.render_widget(w, area);
+        view.table_state.select(selected_row);
+        f.render_stateful_widget(w, area, &mut view.table_state);
+    }
+
+    /// Renders the details of the selected component: the history of its sent events throughput,
+    /// and its errors broken down by error type and stage.
+    fn component_detail<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        row: &state::ComponentRow,
+        area: Rect,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Component {}", row.key.id()));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let rects = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+
+        let history = row
+            .sent_events_throughput_history
+            .iter()
+            .map(|v| (*v).max(0) as u64)
+            .collect::<Vec<_>>();
+        let w = Sparkline::default()
+            .block(Block::default().title(format!("Events Out/s, last {} samples", history.len())))
+            .style(Style::default().fg(Color::Green))
+            .data(&history);
+        f.render_widget(w, rects[0]);
+
+        let header = ["Error Type", "Stage", "Errors"]
+            .iter()
+            .map(|s| Cell::from(*s).style(Style::default().add_modifier(Modifier::BOLD)))
+            .collect::<Vec<_>>();
+        let items = row
+            .errors_by_type
+            .iter()
+            .map(|((error_type, stage), errors)| {
+                Row::new(vec![
+                    error_type.as_deref().unwrap_or("--").to_string(),
+                    stage.as_deref().unwrap_or("--").to_string(),
+                    if self.opts.human_metrics {
+                        errors.human_format()
+                    } else {
+                        errors.thousands_format()
+                    },
+                ])
+            })
+            .collect::<Vec<_>>();
+        let w = Table::new(items)
+            .header(Row::new(header))
+            .column_spacing(2)
+            .widths(&[
+                Constraint::Percentage(40), // Error Type
+                Constraint::Percentage(30), // Stage
+                Constraint::Percentage(30), // Errors
+            ]);
+        f.render_widget(w, rects[1]);
     }
 
     /// Alerts the user to resize the window to view columns
@@ -283,9 +412,11 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
-    /// Renders a box showing instructions on how to exit from `vector top`.
+    /// Renders a box showing instructions on how to navigate and exit from `vector top`.
     fn quit_box<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let text = vec![Spans::from("To quit, press ESC or 'q'")];
+        let text = vec![Spans::from(
+            "To quit, press ESC or 'q'. Select a component with ↑/↓, and press ENTER to toggle its details",
+        )];
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -299,7 +430,7 @@ impl<'a> Widgets<'a> {
     }
 
     /// Draw a single frame. Creates a layout and renders widgets into it.
-    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: state::State) {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, view: &mut View) {
         let size = f.size();
         let rects = Layout::default()
             .constraints(self.constraints.clone())
@@ -307,9 +438,22 @@ impl<'a> Widgets<'a> {
 
         self.title(f, rects[0], &state.connection_status);
 
+        // Keep the selection within the components, which may have been removed since
+        view.selected = view.selected.min(state.components.len().saturating_sub(1));
+        let selected = state.components.values().nth(view.selected);
+
         // Require a minimum of 80 chars of line width to display the table
         if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+            match selected.filter(|_| view.show_detail) {
+                Some(row) => {
+                    let areas = Layout::default()
+                        .constraints([Constraint::Min(0), Constraint::Length(12)])
+                        .split(rects[1]);
+                    self.components_table(f, state, view, areas[0]);
+                    self.component_detail(f, row, areas[1]);
+                }
+                None => self.components_table(f, state, view, rects[1]),
+            }
         } else {
             self.components_resize_window(f, rects[1]);
         }
@@ -353,16 +497,31 @@ pub async fn init_dashboard<'a>(
     terminal.clear()?;
 
     let widgets = Widgets::new(url, opts);
+    let mut view = View::default();
+    let mut current_state: Option<state::State> = None;
 
     loop {
         tokio::select! {
             Some(state) = state_rx.recv() => {
-                terminal.draw(|f| widgets.draw(f, state))?;
+                terminal.draw(|f| widgets.draw(f, &state, &mut view))?;
+                current_state = Some(state);
             },
             k = key_press_rx.recv() => {
-                if let KeyCode::Esc | KeyCode::Char('q') = k.unwrap() {
-                    let _ = key_press_kill_tx.send(());
-                    break
+                match k.unwrap() {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        let _ = key_press_kill_tx.send(());
+                        break
+                    }
+                    KeyCode::Up => view.select_previous(),
+                    KeyCode::Down => view.select_next(
+                        current_state.as_ref().map_or(0, |state| state.components.len()),
+                    ),
+                    KeyCode::Enter => view.toggle_detail(),
+                    _ => continue,
+                }
+                // Redraw right away, rather than waiting for the next update
+                if let Some(state) = &current_state {
+                    terminal.draw(|f| widgets.draw(f, state, &mut view))?;
                 }
             }
             _ = &mut shutdown_rx => {
@@ -467,6 +626,24 @@ mod tests {
         assert_eq!(N.human_format(), "1.10 T");
     }
 
+    #[test]
+    /// Sparklines should be scaled to the largest sample
+    fn format_sparkline_scaled() {
+        let samples = VecDeque::from(vec![0, 10, 35, 70]);
+
+        assert_eq!(format_sparkline(&samples), "▁▂▄█");
+    }
+
+    #[test]
+    /// Sparklines should only show the most recent samples
+    fn format_sparkline_most_recent() {
+        let samples = (0..40).collect::<VecDeque<_>>();
+
+        assert_eq!(format_sparkline(&samples).chars().count(), SPARKLINE_LEN);
+        assert_eq!(format_sparkline(&VecDeque::from(vec![0, 0])), "▁▁");
+        assert_eq!(format_sparkline(&VecDeque::new()), "--");
+    }
+
     #[test]
     /// Should format bytes
     fn format_bytes() {
@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque},
     sync::Arc,
 };
 
@@ -38,6 +38,8 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     #[cfg(feature = "allocation-tracing")]
                     allocated_bytes: 0,
                     errors: 0,
+                    errors_by_type: BTreeMap::new(),
+                    sent_events_throughput_history: VecDeque::new(),
                 }))
                 .await;
         }
@@ -263,6 +265,30 @@ async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, inte
     }
 }
 
+async fn errors_totals_by_type(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_errors_totals_by_type_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_errors_totals_by_type;
+            let _ = tx
+                .send(state::EventType::ErrorsByType(
+                    c.into_iter()
+                        .map(|c| state::ErrorsByTypeMetric {
+                            key: ComponentKey::from(c.component_id.as_str()),
+                            error_type: c.error_type,
+                            stage: c.stage,
+                            total: c.errors_total as i64,
+                        })
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(
@@ -307,7 +333,8 @@ pub fn subscribe(
         )),
         #[cfg(feature = "allocation-tracing")]
         tokio::spawn(allocated_bytes(Arc::clone(&client), tx.clone(), interval)),
-        tokio::spawn(errors_totals(Arc::clone(&client), tx, interval)),
+        tokio::spawn(errors_totals(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(errors_totals_by_type(Arc::clone(&client), tx, interval)),
     ]
 }
 
@@ -353,6 +380,8 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                         #[cfg(feature = "allocation-tracing")]
                         allocated_bytes: 0,
                         errors: 0,
+                        errors_by_type: BTreeMap::new(),
+                        sent_events_throughput_history: VecDeque::new(),
                     },
                 ))
             })
@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt::Display,
 };
 
@@ -11,6 +11,17 @@ use crate::config::ComponentKey;
 
 type IdentifiedMetric = (ComponentKey, i64);
 
+/// Number of throughput samples kept for each component, to render their recent history.
+pub const HISTORY_LEN: usize = 60;
+
+#[derive(Debug)]
+pub struct ErrorsByTypeMetric {
+    pub key: ComponentKey,
+    pub error_type: Option<String>,
+    pub stage: Option<String>,
+    pub total: i64,
+}
+
 #[derive(Debug)]
 pub struct SentEventsMetric {
     pub key: ComponentKey,
@@ -32,6 +43,8 @@ pub enum EventType {
     /// Interval + identified metric
     ProcessedBytesThroughputs(i64, Vec<IdentifiedMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    /// Errors broken down by error type and stage
+    ErrorsByType(Vec<ErrorsByTypeMetric>),
     #[cfg(feature = "allocation-tracing")]
     AllocatedBytes(Vec<IdentifiedMetric>),
     ComponentAdded(ComponentRow),
@@ -123,6 +136,10 @@ pub struct ComponentRow {
     #[cfg(feature = "allocation-tracing")]
     pub allocated_bytes: i64,
     pub errors: i64,
+    /// Errors by error type and stage
+    pub errors_by_type: BTreeMap<(Option<String>, Option<String>), i64>,
+    /// Most recent sent events throughputs, oldest first
+    pub sent_events_throughput_history: VecDeque<i64>,
 }
 
 impl ComponentRow {
@@ -132,6 +149,15 @@ impl ComponentRow {
         self.outputs.len() > 1
             || (self.outputs.len() == 1 && !self.outputs.contains_key(DEFAULT_OUTPUT))
     }
+
+    /// Records a sent events throughput sample, forgetting the oldest one once `HISTORY_LEN`
+    /// samples are kept
+    fn push_sent_events_throughput(&mut self, throughput: i64) {
+        if self.sent_events_throughput_history.len() == HISTORY_LEN {
+            self.sent_events_throughput_history.pop_front();
+        }
+        self.sent_events_throughput_history.push_back(throughput);
+    }
 }
 
 /// Takes the receiver `EventRx` channel, and returns a `StateRx` state receiver. This
@@ -144,7 +170,14 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
     tokio::spawn(async move {
         while let Some(event_type) = event_rx.recv().await {
             match event_type {
-                EventType::InitializeState(new_state) => {
+                EventType::InitializeState(mut new_state) => {
+                    // Keep the history of the components that are still running after a reconnect
+                    for (key, r) in new_state.components.iter_mut() {
+                        if let Some(previous) = state.components.remove(key) {
+                            r.sent_events_throughput_history =
+                                previous.sent_events_throughput_history;
+                        }
+                    }
                     state = new_state;
                 }
                 EventType::ReceivedEventsTotals(rows) => {
@@ -180,6 +213,7 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         if let Some(r) = state.components.get_mut(&m.key) {
                             r.sent_events_throughput_sec =
                                 (m.total as f64 * (1000.0 / interval as f64)) as i64;
+                            r.push_sent_events_throughput(r.sent_events_throughput_sec);
                             for (id, v) in m.outputs {
                                 let throughput = (v as f64 * (1000.0 / interval as f64)) as i64;
                                 r.outputs
@@ -212,6 +246,13 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         }
                     }
                 }
+                EventType::ErrorsByType(rows) => {
+                    for m in rows {
+                        if let Some(r) = state.components.get_mut(&m.key) {
+                            r.errors_by_type.insert((m.error_type, m.stage), m.total);
+                        }
+                    }
+                }
                 #[cfg(feature = "allocation-tracing")]
                 EventType::AllocatedBytes(rows) => {
                     for (key, v) in rows {
@@ -0,0 +1,93 @@
+//! Tracking of the source outputs currently blocked by backpressure.
+//!
+//! The pump of each source output marks itself as blocked while it's waiting for the downstream
+//! components to accept its events. Once the pump is blocked, the output of the source fills up,
+//! and the source itself is paused until the events are accepted again.
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::config::OutputId;
+
+/// The time since which each source output is blocked, by output.
+static OUTPUTS: Lazy<Mutex<HashMap<OutputId, Weak<Mutex<Option<Instant>>>>>> =
+    Lazy::new(Default::default);
+
+/// The backpressure state of a source output.
+#[derive(Debug)]
+pub(crate) struct Backpressure(Arc<Mutex<Option<Instant>>>);
+
+/// Registers the backpressure state of the source output.
+///
+/// The state replaces the one registered by a previous instance of the source.
+pub(crate) fn register(output: &OutputId) -> Backpressure {
+    let state = Arc::default();
+    let mut outputs = OUTPUTS.lock().expect("backpressure lock poisoned");
+    // Take the chance to forget the outputs of the sources that stopped since.
+    outputs.retain(|_, state| state.strong_count() > 0);
+    outputs.insert(output.clone(), Arc::downgrade(&state));
+    Backpressure(state)
+}
+
+impl Backpressure {
+    /// Marks the output as blocked, until the returned guard is dropped.
+    pub(crate) fn block(&self) -> Blocked<'_> {
+        *self.0.lock().expect("backpressure lock poisoned") = Some(Instant::now());
+        Blocked(self)
+    }
+}
+
+/// Clears the blocked state of an output once dropped.
+pub(crate) struct Blocked<'a>(&'a Backpressure);
+
+impl Drop for Blocked<'_> {
+    fn drop(&mut self) {
+        *self.0 .0.lock().expect("backpressure lock poisoned") = None;
+    }
+}
+
+/// Returns the source outputs which have been blocked for at least `min`, along with the time
+/// they have been blocked for.
+#[cfg(any(test, feature = "api"))]
+pub(crate) fn blocked_outputs(min: Duration) -> Vec<(OutputId, Duration)> {
+    let outputs = OUTPUTS.lock().expect("backpressure lock poisoned");
+    let mut blocked = outputs
+        .iter()
+        .filter_map(|(output, state)| {
+            let since = (*state.upgrade()?.lock().expect("backpressure lock poisoned"))?;
+            let elapsed = since.elapsed();
+            (elapsed >= min).then(|| (output.clone(), elapsed))
+        })
+        .collect::<Vec<_>>();
+    blocked.sort_by(|a, b| a.0.to_string().cmp(&b.0.to_string()));
+    blocked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_blocked_outputs() {
+        let output = OutputId::from("backpressure_source");
+        let backpressure = register(&output);
+        let is_blocked = || {
+            blocked_outputs(Duration::ZERO)
+                .iter()
+                .any(|(blocked, _)| *blocked == output)
+        };
+
+        {
+            let _blocked = backpressure.block();
+            assert!(is_blocked());
+            assert!(!blocked_outputs(Duration::from_secs(3600))
+                .iter()
+                .any(|(blocked, _)| *blocked == output));
+        }
+        assert!(!is_blocked());
+    }
+}
@@ -6,7 +6,7 @@ use std::{
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
+use futures::{future::OptionFuture, stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
 use futures_util::stream::FuturesUnordered;
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
@@ -27,15 +27,20 @@ use vector_core::{
             builder::TopologyBuilder,
             channel::{BufferReceiver, BufferSender},
         },
-        BufferType, WhenFull,
+        WhenFull,
     },
     schema::Definition,
+    stream::{with_dynamic_limits, DynamicLimits},
     EstimatedJsonEncodedSizeOf,
 };
 
 use super::{
+    backpressure,
+    dead_letter::DeadLetterTap,
     fanout::{self, Fanout},
     schema,
+    schema_enforcement::SchemaEnforcer,
+    stale_events::StaleEventsFilter,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
 };
@@ -43,12 +48,19 @@ use crate::{
     config::{
         ComponentKey, DataType, EnrichmentTableConfig, Input, Inputs, Output, OutputId,
         ProxyConfig, SinkConfig, SinkContext, SourceConfig, SourceContext, TransformContext,
-        TransformOuter,
+        TransformOuter, TransformReload, DLQ_OUTPUT, DROPPED_OUTPUT,
     },
     event::{EventArray, EventContainer},
-    internal_events::EventsReceived,
+    http::request_log::{with_request_log, RequestLog},
+    internal_events::{EventsReceived, PassThroughActive},
+    memory_budget::{self, set_memory_budget, MemoryUsage},
+    recording::{with_recorder, Recorder},
     shutdown::SourceShutdownCoordinator,
-    source_sender::CHUNK_SIZE,
+    sinks::util::{
+        adaptive_concurrency::with_sink, dynamic_limits::LimitSchedule,
+        service::set_global_bandwidth_limit,
+    },
+    source_sender::{SourceOrigin, TenantRegistry, CHUNK_SIZE},
     spawn_named,
     topology::task::TaskError,
     transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
@@ -59,6 +71,8 @@ use crate::{
 static ENRICHMENT_TABLES: Lazy<enrichment::TableRegistry> =
     Lazy::new(enrichment::TableRegistry::default);
 
+static TENANT_QUOTAS: Lazy<TenantRegistry> = Lazy::new(TenantRegistry::default);
+
 pub(crate) static SOURCE_SENDER_BUFFER_SIZE: Lazy<usize> =
     Lazy::new(|| *TRANSFORM_CONCURRENCY_LIMIT * CHUNK_SIZE);
 
@@ -76,12 +90,41 @@ pub(self) async fn load_enrichment_tables<'a>(
     config: &'a super::Config,
     diff: &'a ConfigDiff,
 ) -> (&'static enrichment::TableRegistry, Vec<String>) {
+    let errors = build_enrichment_tables(config, diff, |_| true).await;
+
+    (&ENRICHMENT_TABLES, errors)
+}
+
+/// Reloads the given enrichment tables of the running config, switching the running components
+/// over to the reloaded data.
+///
+/// The tables whose data didn't change since they were last loaded are left as is.
+pub(super) async fn reload_enrichment_tables(
+    config: &super::Config,
+    tables: &[ComponentKey],
+) -> Vec<String> {
+    let diff = ConfigDiff::new(config, config);
+    let errors = build_enrichment_tables(config, &diff, |name| tables.contains(name)).await;
+    ENRICHMENT_TABLES.finish_load();
+
+    errors
+}
+
+async fn build_enrichment_tables(
+    config: &super::Config,
+    diff: &ConfigDiff,
+    filter: impl Fn(&ComponentKey) -> bool,
+) -> Vec<String> {
     let mut enrichment_tables = HashMap::new();
 
     let mut errors = vec![];
 
     // Build enrichment tables
-    'tables: for (name, table) in config.enrichment_tables.iter() {
+    'tables: for (name, table) in config
+        .enrichment_tables
+        .iter()
+        .filter(|(name, _)| filter(name))
+    {
         let table_name = name.to_string();
         if ENRICHMENT_TABLES.needs_reload(&table_name) {
             let indexes = if !diff.enrichment_tables.is_added(name) {
@@ -125,7 +168,12 @@ pub(self) async fn load_enrichment_tables<'a>(
 
     ENRICHMENT_TABLES.load(enrichment_tables);
 
-    (&ENRICHMENT_TABLES, errors)
+    errors
+}
+
+/// Loads the resource quotas of the tenants of the given config.
+pub(super) fn load_tenant_quotas(config: &super::Config) {
+    TENANT_QUOTAS.load(&config.tenants);
 }
 
 pub struct Pieces {
@@ -138,11 +186,184 @@ pub struct Pieces {
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
 }
 
+fn transform_context(
+    key: &ComponentKey,
+    config: &super::Config,
+    enrichment_tables: &enrichment::TableRegistry,
+    merged_definition: &Definition,
+    outputs: Vec<Output>,
+    canary: bool,
+) -> TransformContext {
+    let schema_definitions = outputs
+        .into_iter()
+        .map(|output| {
+            let definition = output
+                .log_schema_definition
+                .unwrap_or_else(|| merged_definition.clone());
+            (output.port, definition)
+        })
+        .collect();
+
+    TransformContext {
+        key: Some(key.clone()),
+        globals: config.global.clone(),
+        enrichment_tables: enrichment_tables.clone(),
+        schema_definitions,
+        merged_schema_definition: merged_definition.clone(),
+        schema: config.schema,
+        canary,
+    }
+}
+
+/// Prepares the changes to transforms that can be reloaded without being rebuilt, removing them
+/// from the diff.
+///
+/// The running instance of each reloaded transform is kept, along with its inputs, outputs, and
+/// buffers. The changes are only applied with [`apply_transform_reloads`], once the reload of the
+/// topology succeeds.
+pub(super) fn reload_transforms(
+    old_config: &super::Config,
+    new_config: &super::Config,
+    diff: &mut ConfigDiff,
+) -> Vec<(ComponentKey, TransformReload)> {
+    let mut reloads = Vec::new();
+    let mut old_definition_cache = HashMap::default();
+    let mut new_definition_cache = HashMap::default();
+
+    let changed = diff
+        .transforms
+        .to_change
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    for key in changed {
+        let (old, new) = match (old_config.transform(&key), new_config.transform(&key)) {
+            (Some(old), Some(new)) if old.inputs[..] == new.inputs[..] => (old, new),
+            _ => continue,
+        };
+
+        // Downstream components are connected to the current outputs, so they must not change.
+        let old_definition =
+            schema::merged_definition(&old.inputs, old_config, &mut old_definition_cache);
+        let new_definition =
+            schema::merged_definition(&new.inputs, new_config, &mut new_definition_cache);
+        let outputs = new
+            .inner
+            .outputs(&new_definition, new_config.schema.log_namespace());
+        if old
+            .inner
+            .outputs(&old_definition, old_config.schema.log_namespace())
+            != outputs
+        {
+            continue;
+        }
+
+        let span = error_span!(
+            "transform",
+            component_kind = "transform",
+            component_id = %key.id(),
+            component_type = %new.inner.get_component_name(),
+            // maintained for compatibility
+            component_name = %key.id(),
+            internal_log_rate_secs = new.internal_log_rate_limit_secs,
+        );
+        let context = transform_context(
+            &key,
+            new_config,
+            &ENRICHMENT_TABLES,
+            &new_definition,
+            outputs,
+            false,
+        );
+        let reload = match span.in_scope(|| old.inner.reload(new.inner.as_ref(), &context)) {
+            Ok(Some(reload)) => reload,
+            Ok(None) => continue,
+            Err(error) => {
+                warn!(
+                    message = "Failed to reload transform, rebuilding it instead.",
+                    component = %key,
+                    %error
+                );
+                continue;
+            }
+        };
+
+        diff.transforms.to_change.remove(&key);
+        reloads.push((key, reload));
+    }
+
+    reloads
+}
+
+/// Applies the changes prepared by [`reload_transforms`] to the running transforms.
+///
+/// The running instance of each reloaded transform is tied to the configuration returned by its
+/// change, which replaces the one of the new configuration.
+pub(super) fn apply_transform_reloads(
+    new_config: &mut super::Config,
+    reloads: Vec<(ComponentKey, TransformReload)>,
+) {
+    for (key, reload) in reloads {
+        let transform = new_config
+            .transform_mut(&key)
+            .expect("transform must exist");
+        transform.inner = reload.apply();
+
+        info!(message = "Reloaded transform without rebuilding it.", component = %key);
+    }
+}
+
 /// Builds only the new pieces, and doesn't check their topology.
 pub async fn build_pieces(
+    config: &super::Config,
+    diff: &ConfigDiff,
+    buffers: HashMap<ComponentKey, BuiltBuffer>,
+) -> Result<Pieces, Vec<String>> {
+    apply_global_options(config);
+    build_components(config, diff, buffers, false).await
+}
+
+/// Builds only the new pieces of a canary, leaving the options shared with the running topology
+/// as they are, except for the tenant quotas the components need, which [`load_tenant_quotas`]
+/// restores if the canary fails. The transforms are built not to persist their state, which belongs
+/// to the running topology.
+pub(super) async fn build_canary_pieces(
+    config: &super::Config,
+    diff: &ConfigDiff,
+    buffers: HashMap<ComponentKey, BuiltBuffer>,
+) -> Result<Pieces, Vec<String>> {
+    load_tenant_quotas(config);
+    build_components(config, diff, buffers, true).await
+}
+
+/// Applies the global options which are shared by the whole topology.
+fn apply_global_options(config: &super::Config) {
+    // Tenant quotas are updated in place, so that the sources which aren't rebuilt pick up the
+    // new limits as well.
+    load_tenant_quotas(config);
+
+    // Likewise for the bandwidth limit shared by the sinks.
+    set_global_bandwidth_limit(config.global.egress_rate_limit_bytes_per_sec);
+
+    // And for the memory budget shared by the sources.
+    set_memory_budget(config.global.memory_budget);
+
+    // And for the exporter of the internal traces.
+    #[cfg(feature = "internal-telemetry-traces")]
+    crate::internal_telemetry::traces::set_traces_config(config.global.telemetry.traces.as_ref());
+    #[cfg(not(feature = "internal-telemetry-traces"))]
+    if config.global.telemetry.traces.is_some() {
+        warn!(
+            message = "Internal traces are configured, but Vector was built without the `internal-telemetry-traces` feature.",
+        );
+    }
+}
+
+async fn build_components(
     config: &super::Config,
     diff: &ConfigDiff,
     mut buffers: HashMap<ComponentKey, BuiltBuffer>,
+    canary: bool,
 ) -> Result<Pieces, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut outputs = HashMap::new();
@@ -174,6 +395,7 @@ pub async fn build_pieces(
             component_type = %source.inner.get_component_name(),
             // maintained for compatibility
             component_name = %key.id(),
+            internal_log_rate_secs = source.internal_log_rate_limit_secs,
         );
         let _entered_span = span.enter();
 
@@ -183,19 +405,37 @@ pub async fn build_pieces(
             key.id()
         );
 
-        let mut builder = SourceSender::builder().with_buffer(*SOURCE_SENDER_BUFFER_SIZE);
+        let origin = config
+            .schema
+            .origin_metadata
+            .then(|| SourceOrigin::new(key.id(), typetag));
+        let mut builder = SourceSender::builder()
+            .with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+            .with_origin(origin)
+            .with_tenant(
+                source
+                    .tenant
+                    .as_deref()
+                    .and_then(|tenant| TENANT_QUOTAS.get(tenant)),
+            )
+            .with_memory(Some(memory_budget::register(key, "source")));
         let mut pumps = Vec::new();
         let mut controls = HashMap::new();
         let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
 
         for output in source_outputs {
             let mut rx = builder.add_output(output.clone());
+            let backpressure = backpressure::register(&OutputId {
+                component: key.clone(),
+                port: output.port.clone(),
+            });
 
             let (mut fanout, control) = Fanout::new();
             let pump = async move {
                 debug!("Source pump starting.");
 
                 while let Some(array) = rx.next().await {
+                    let _blocked = backpressure.block();
                     fanout.send(array).await.map_err(|e| {
                         debug!("Source pump finished with an error.");
                         TaskError::wrapped(e)
@@ -215,9 +455,12 @@ pub async fn build_pieces(
                 control,
             );
 
-            let schema_definition = output
+            let mut schema_definition = output
                 .log_schema_definition
                 .unwrap_or_else(schema::Definition::default_legacy_namespace);
+            if config.schema.origin_metadata {
+                schema_definition = schema::with_origin_metadata(schema_definition);
+            }
 
             schema_definitions.insert(output.port, schema_definition);
         }
@@ -253,10 +496,30 @@ pub async fn build_pieces(
             }
             Ok(TaskOutput::Source)
         };
-        let pump = Task::new(key.clone(), typetag, pump);
+        let pump = Task::new(key.clone(), typetag, pump)
+            .with_internal_log_rate_limit(source.internal_log_rate_limit_secs);
 
         let pipeline = builder.build();
 
+        let recorder = match source
+            .record
+            .as_ref()
+            .map(|record| {
+                Recorder::open(key, typetag, record)
+                    .map(Arc::new)
+                    .map_err(|error| {
+                        format!("Failed to open recording {:?}: {}", record.path, error)
+                    })
+            })
+            .transpose()
+        {
+            Err(error) => {
+                errors.push(format!("Source \"{}\": {}", key, error));
+                continue;
+            }
+            Ok(recorder) => recorder,
+        };
+
         let (shutdown_signal, force_shutdown_tripwire) = shutdown_coordinator.register_source(key);
 
         let context = SourceContext {
@@ -268,14 +531,21 @@ pub async fn build_pieces(
             acknowledgements: source.sink_acknowledgements,
             schema_definitions,
             schema: config.schema,
+            pass_through: source.pass_through,
+            linked: source.linked,
         };
-        let source = source.inner.build(context).await;
+        if source.inner.pass_through_decoding().is_some() {
+            emit!(PassThroughActive {
+                active: source.pass_through
+            });
+        }
+        let source = with_recorder(recorder.clone(), source.inner.build(context)).await;
         let server = match source {
             Err(error) => {
                 errors.push(format!("Source \"{}\": {}", key, error));
                 continue;
             }
-            Ok(server) => server,
+            Ok(server) => with_recorder(recorder, server),
         };
 
         // Build a wrapper future that drives the actual source future, but returns early if we've
@@ -344,9 +614,9 @@ pub async fn build_pieces(
     {
         debug!(component = %key, "Building new transform.");
 
-        let mut schema_definitions = HashMap::new();
         let merged_definition =
             schema::merged_definition(&transform.inputs, config, &mut definition_cache);
+        let internal_log_rate_limit = transform.internal_log_rate_limit_secs;
 
         let span = error_span!(
             "transform",
@@ -355,26 +625,19 @@ pub async fn build_pieces(
             component_type = %transform.inner.get_component_name(),
             // maintained for compatibility
             component_name = %key.id(),
+            internal_log_rate_secs = transform.internal_log_rate_limit_secs,
         );
 
-        for output in transform
-            .inner
-            .outputs(&merged_definition, config.schema.log_namespace())
-        {
-            let definition = output
-                .log_schema_definition
-                .unwrap_or_else(|| merged_definition.clone());
-            schema_definitions.insert(output.port, definition);
-        }
-
-        let context = TransformContext {
-            key: Some(key.clone()),
-            globals: config.global.clone(),
-            enrichment_tables: enrichment_tables.clone(),
-            schema_definitions,
-            merged_schema_definition: merged_definition.clone(),
-            schema: config.schema,
-        };
+        let context = transform_context(
+            key,
+            config,
+            enrichment_tables,
+            &merged_definition,
+            transform
+                .inner
+                .outputs(&merged_definition, config.schema.log_namespace()),
+            canary,
+        );
 
         let node = TransformNode::from_parts(
             key.clone(),
@@ -405,6 +668,7 @@ pub async fn build_pieces(
             let _span = span.enter();
             build_transform(transform, node, input_rx)
         };
+        let transform_task = transform_task.with_internal_log_rate_limit(internal_log_rate_limit);
 
         outputs.extend(transform_outputs);
         tasks.insert(key.clone(), transform_task);
@@ -418,6 +682,10 @@ pub async fn build_pieces(
         debug!(component = %key, "Building new sink.");
 
         let sink_inputs = &sink.inputs;
+        let internal_log_rate_limit = sink.internal_log_rate_limit_secs;
+        let reroute_failed = sink.reroute_failed;
+        let reroute_failed_max_pending = sink.reroute_failed_max_pending;
+        let routes_dropped = sink.routes_dropped();
         let healthcheck = sink.healthcheck();
         let enable_healthcheck = healthcheck.enabled && config.healthchecks.enabled;
 
@@ -431,13 +699,54 @@ pub async fn build_pieces(
             errors.append(&mut err);
         };
 
+        let mut schema_enforcer = match sink
+            .schema
+            .as_ref()
+            .map(|schema| SchemaEnforcer::new(key.clone(), typetag, schema))
+            .transpose()
+        {
+            Err(error) => {
+                errors.push(format!("Sink \"{}\": {}", key, error));
+                continue;
+            }
+            Ok(schema_enforcer) => schema_enforcer,
+        };
+
+        let stale_events_filter = sink
+            .max_event_age_secs(&config.global)
+            .map(StaleEventsFilter::new);
+
+        let request_log = match sink
+            .request_log
+            .as_ref()
+            .map(|request_log| RequestLog::new(key.clone(), request_log))
+            .transpose()
+        {
+            Err(error) => {
+                errors.push(format!("Sink \"{}\": {}", key, error));
+                continue;
+            }
+            Ok(request_log) => request_log,
+        };
+
+        let limit_schedule = match LimitSchedule::new(&sink.limit_schedule) {
+            Err(error) => {
+                errors.push(format!("Sink \"{}\": {}", key, error));
+                continue;
+            }
+            Ok(limit_schedule) => limit_schedule,
+        };
+        let limits = Arc::new(DynamicLimits::default());
+
         let (tx, rx) = if let Some(buffer) = buffers.remove(key) {
             buffer
         } else {
-            let buffer_type = match sink.buffer.stages().first().expect("cant ever be empty") {
-                BufferType::Memory { .. } => "memory",
-                BufferType::DiskV2 { .. } => "disk",
-            };
+            let buffer_type = sink
+                .buffer
+                .stages()
+                .first()
+                .expect("cant ever be empty")
+                .metric_tag();
             let buffer_span = error_span!(
                 "sink",
                 component_kind = "sink",
@@ -445,6 +754,7 @@ pub async fn build_pieces(
                 component_type = typetag,
                 component_name = %key.id(),
                 buffer_type,
+                internal_log_rate_secs = sink.internal_log_rate_limit_secs,
             );
             let buffer = sink
                 .buffer
@@ -466,15 +776,46 @@ pub async fn build_pieces(
             schema: config.schema,
         };
 
-        let (sink, healthcheck) = match sink.inner.build(cx).await {
+        let build = with_dynamic_limits(
+            Arc::clone(&limits),
+            with_request_log(request_log.clone(), sink.inner.build(cx)),
+        );
+        let (sink, healthcheck) = match with_sink(key.clone(), build).await {
             Err(error) => {
                 errors.push(format!("Sink \"{}\": {}", key, error));
                 continue;
             }
             Ok(built) => built,
         };
+        if let Err(error) = limit_schedule.start(key, &limits, config.global.timezone()) {
+            errors.push(format!("Sink \"{}\": {}", key, error));
+            continue;
+        }
+
+        // The events the sink fails to deliver are sent to its `dlq` output, if it has one.
+        let (dead_letter_tap, forward_failed) = if reroute_failed {
+            let (fanout, control) = Fanout::new();
+            outputs.insert(OutputId::from((key, DLQ_OUTPUT.to_owned())), control);
+            let (tap, forward) =
+                DeadLetterTap::new(key.clone(), typetag, reroute_failed_max_pending, fanout);
+            (Some(tap), Some(forward))
+        } else {
+            (None, None)
+        };
+
+        // The events violating the schema of the sink are sent to its `dropped` output, if it has
+        // one.
+        let forward_dropped = match schema_enforcer.as_mut() {
+            Some(schema_enforcer) if routes_dropped => {
+                let (fanout, control) = Fanout::new();
+                outputs.insert(OutputId::from((key, DROPPED_OUTPUT.to_owned())), control);
+                Some(schema_enforcer.route_dropped(fanout))
+            }
+            _ => None,
+        };
 
         let (trigger, tripwire) = Tripwire::new();
+        let memory = memory_budget::register(key, "sink");
 
         let sink = async move {
             debug!("Sink starting.");
@@ -494,7 +835,7 @@ pub async fn build_pieces(
             let mut rx = wrap(rx);
 
             let events_received = register!(EventsReceived);
-            sink.run(
+            let run = sink.run(
                 rx.by_ref()
                     .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
                     .inspect(|events| {
@@ -503,20 +844,51 @@ pub async fn build_pieces(
                             events.estimated_json_encoded_size_of(),
                         ))
                     })
+                    .filter_map(move |events| {
+                        ready(match &stale_events_filter {
+                            Some(stale_events_filter) => stale_events_filter.filter(events),
+                            None => Some(events),
+                        })
+                    })
+                    .filter_map(move |events| {
+                        ready(match &schema_enforcer {
+                            Some(schema_enforcer) => schema_enforcer.enforce(events),
+                            None => Some(events),
+                        })
+                    })
+                    .map(move |events| match &dead_letter_tap {
+                        Some(tap) => tap.track(events),
+                        None => events,
+                    })
+                    .map(move |mut events| {
+                        memory.track(MemoryUsage::Batched, &mut events);
+                        events
+                    })
                     .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
-                debug!("Sink finished normally.");
-                TaskOutput::Sink(rx)
-            })
-            .map_err(|_| {
-                debug!("Sink finished with an error.");
-                TaskError::Opaque
-            })
+            );
+
+            // The tap and the schema enforcer are dropped along with the input stream once the
+            // sink finishes, after which the remaining events they hold are forwarded.
+            let forward = futures::future::join(
+                OptionFuture::from(forward_failed),
+                OptionFuture::from(forward_dropped),
+            );
+            let result = tokio::join!(run, forward).0;
+
+            result
+                .map(|_| {
+                    debug!("Sink finished normally.");
+                    TaskOutput::Sink(rx)
+                })
+                .map_err(|_| {
+                    debug!("Sink finished with an error.");
+                    TaskError::Opaque
+                })
         };
 
-        let task = Task::new(key.clone(), typetag, sink);
+        let sink = with_dynamic_limits(limits, with_request_log(request_log, sink));
+        let task = Task::new(key.clone(), typetag, with_sink(key.clone(), sink))
+            .with_internal_log_rate_limit(internal_log_rate_limit);
 
         let component_key = key.clone();
         let healthcheck_task = async move {
@@ -0,0 +1,309 @@
+//! Canary reloads, trying out the new and changed components of a configuration alongside the
+//! running topology before switching over to them.
+
+use std::{
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    sync::{Arc, Mutex},
+};
+
+use futures::{future, stream::FuturesUnordered, FutureExt, StreamExt};
+use tokio::{
+    task::JoinHandle,
+    time::{sleep, timeout, Duration},
+};
+use tracing::Instrument;
+use vector_buffers::{
+    topology::{
+        builder::TopologyBuilder,
+        channel::{BufferReceiverStream, BufferSender},
+    },
+    WhenFull,
+};
+
+use super::{
+    builder::{self, Pieces, TOPOLOGY_BUFFER_SIZE},
+    fanout::{ControlChannel, ControlMessage},
+    take_healthchecks,
+    task::{Task, TaskError, TaskResult},
+};
+use crate::{
+    config::{ComponentKey, Config, ConfigDiff, OutputId},
+    event::EventArray,
+};
+
+/// How long the components tried out are given to stop once the mirroring is over.
+const SHUTDOWN_LIMIT: Duration = Duration::from_secs(5);
+
+/// Tries out the new and changed transforms and sinks of `new_config` alongside the running
+/// topology, whose outputs are given, returning whether they can be switched over to.
+///
+/// The sources are left out, as are the sinks using a resource such as a port, since they would
+/// conflict with the running topology. They're only tried out by the reload itself.
+pub(super) async fn run(
+    config: &Config,
+    new_config: &Config,
+    diff: &ConfigDiff,
+    outputs: &HashMap<OutputId, ControlChannel>,
+) -> bool {
+    let diff = shadow_diff(new_config, diff);
+    if diff.transforms.to_add.is_empty() && diff.sinks.to_add.is_empty() {
+        return true;
+    }
+
+    info!("Trying out the changed components of the new configuration.");
+    let passed = try_out(new_config, &diff, outputs).await;
+    if passed {
+        info!("Changed components of the new configuration passed.");
+    } else {
+        // Building the components loaded the quotas of the new tenants.
+        builder::load_tenant_quotas(config);
+        error!("Changed components of the new configuration failed; reload aborted.");
+    }
+
+    passed
+}
+
+/// Returns the diff adding the components of `diff` to try out.
+fn shadow_diff(new_config: &Config, diff: &ConfigDiff) -> ConfigDiff {
+    let transforms = new_config
+        .transforms()
+        .map(|(key, _)| key)
+        .filter(|key| diff.transforms.contains_new(key))
+        .cloned()
+        .collect();
+    let sinks = new_config
+        .sinks()
+        .filter(|(key, sink)| diff.sinks.contains_new(key) && sink.inner.resources().is_empty())
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    // The components are all added, apart from the running ones, but the enrichment tables are
+    // the ones of the reload.
+    let mut shadow_diff = ConfigDiff::new(new_config, new_config);
+    shadow_diff.transforms.to_add = transforms;
+    shadow_diff.sinks.to_add = sinks;
+    shadow_diff.enrichment_tables.to_remove = diff.enrichment_tables.to_remove.clone();
+    shadow_diff.enrichment_tables.to_change = diff.enrichment_tables.to_change.clone();
+    shadow_diff.enrichment_tables.to_add = diff.enrichment_tables.to_add.clone();
+    shadow_diff
+}
+
+async fn try_out(
+    new_config: &Config,
+    diff: &ConfigDiff,
+    outputs: &HashMap<OutputId, ControlChannel>,
+) -> bool {
+    // The sinks are given their own memory buffers, not to contend with the running ones.
+    let mut buffers = HashMap::new();
+    for key in &diff.sinks.to_add {
+        let (tx, rx) = memory_buffer(WhenFull::Block).await;
+        buffers.insert(key.clone(), (tx, Arc::new(Mutex::new(Some(rx)))));
+    }
+
+    // The options shared with the running topology, such as the memory budget, are left as they
+    // are until the reload itself.
+    let mut pieces = match builder::build_canary_pieces(new_config, diff, buffers).await {
+        Ok(pieces) => pieces,
+        Err(errors) => {
+            for error in errors {
+                error!(message = "Configuration error.", %error);
+            }
+            return false;
+        }
+    };
+
+    if new_config.healthchecks.enabled {
+        let healthchecks = take_healthchecks(diff, &mut pieces)
+            .into_iter()
+            .map(|(_, task)| task);
+        if future::try_join_all(healthchecks).await.is_err() {
+            error!("Sinks unhealthy.");
+            return false;
+        }
+    }
+
+    if new_config.canary.mirror_secs == 0 || diff.transforms.to_add.is_empty() {
+        return true;
+    }
+    mirror(new_config, diff, pieces, outputs).await
+}
+
+/// Mirrors live traffic through the built transforms for the configured time, returning whether
+/// none of them failed or stopped in the meantime.
+///
+/// The sinks aren't run, so that the mirrored events aren't sent to their destinations on top of
+/// the ones sent by the running topology. The events output by the transforms are dropped instead.
+async fn mirror(
+    new_config: &Config,
+    diff: &ConfigDiff,
+    mut pieces: Pieces,
+    outputs: &HashMap<OutputId, ControlChannel>,
+) -> bool {
+    let ratio = new_config.canary.mirror_ratio;
+    let shadow_outputs = pieces
+        .outputs
+        .iter()
+        .flat_map(|(key, outputs)| {
+            outputs.iter().map(move |(port, control)| {
+                let id = OutputId {
+                    component: key.clone(),
+                    port: port.clone(),
+                };
+                (id, control.clone())
+            })
+        })
+        .collect::<HashMap<_, _>>();
+
+    // The fanouts of the running topology the transforms are added to, to remove them from once
+    // done.
+    let mut mirrored = Vec::new();
+    let mut samplers = Vec::new();
+    for key in &diff.transforms.to_add {
+        let (tx, inputs) = pieces.inputs.remove(key).unwrap();
+        let canary_key = canary_key(key);
+        let mut live_tx = None;
+
+        for input in inputs {
+            if let Some(output) = shadow_outputs.get(&input) {
+                let _ = output.send(ControlMessage::Add(canary_key.clone(), tx.clone()));
+            } else if let Some(output) = outputs.get(&input) {
+                // The live events go through a sampler, whose buffer drops them rather than
+                // slowing down the running topology.
+                if live_tx.is_none() {
+                    let (sample_tx, sample_rx) = memory_buffer(WhenFull::DropNewest).await;
+                    samplers.push(tokio::spawn(sample(sample_rx, tx.clone(), ratio)));
+                    live_tx = Some(sample_tx);
+                }
+                let sender = live_tx.clone().expect("sampler is spawned");
+                let _ = output.send(ControlMessage::Add(canary_key.clone(), sender));
+                mirrored.push((output.clone(), canary_key.clone()));
+            } else {
+                debug!(component = %key, input = %input, "Input isn't running, not mirroring it.");
+            }
+        }
+    }
+
+    let mut tasks = pieces
+        .tasks
+        .drain()
+        .filter(|(key, _)| diff.transforms.contains(key))
+        .map(|(key, task)| spawn(key, task))
+        .collect::<FuturesUnordered<_>>();
+
+    info!(
+        message = "Mirroring live traffic through the changed transforms.",
+        mirror_secs = new_config.canary.mirror_secs,
+        mirror_ratio = ratio,
+    );
+    let passed = tokio::select! {
+        _ = sleep(Duration::from_secs(new_config.canary.mirror_secs)) => true,
+        Some((key, result)) = tasks.next() => {
+            match result {
+                Ok(_) => error!(message = "Transform stopped while mirroring traffic.", component = %key),
+                Err(error) => error!(message = "Transform failed while mirroring traffic.", component = %key, %error),
+            }
+            false
+        }
+    };
+
+    // Disconnecting the transforms from the running topology closes their inputs, for them all to
+    // stop.
+    for (output, canary_key) in mirrored {
+        let _ = output.send(ControlMessage::Remove(canary_key));
+    }
+    drop(pieces);
+    drop(shadow_outputs);
+    let stopped = future::join(
+        future::join_all(samplers),
+        tasks.for_each(|_| future::ready(())),
+    );
+    if timeout(SHUTDOWN_LIMIT, stopped).await.is_err() {
+        warn!("Changed transforms didn't stop in time after mirroring traffic.");
+    }
+
+    passed
+}
+
+/// The key the components tried out are connected to the fanouts with, apart from the running
+/// ones.
+fn canary_key(key: &ComponentKey) -> ComponentKey {
+    ComponentKey::from(format!("{}.canary", key))
+}
+
+async fn memory_buffer(
+    when_full: WhenFull,
+) -> (BufferSender<EventArray>, BufferReceiverStream<EventArray>) {
+    let (tx, rx) = TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, when_full).await;
+    (tx, rx.into_stream())
+}
+
+/// Forwards the given ratio of the batches of events.
+async fn sample(
+    mut rx: BufferReceiverStream<EventArray>,
+    mut tx: BufferSender<EventArray>,
+    ratio: f64,
+) {
+    let mut credit = 0.0;
+    while let Some(events) = rx.next().await {
+        credit += ratio;
+        if credit >= 1.0 {
+            credit -= 1.0;
+            if tx.send(events).await.is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// Spawns the task of a transform tried out, aborted once it's dropped.
+fn spawn(
+    key: ComponentKey,
+    task: Task,
+) -> impl future::Future<Output = (ComponentKey, TaskResult)> {
+    let span = error_span!(
+        "canary",
+        component_kind = "transform",
+        component_id = %canary_key(&key),
+        component_type = %task.typetag(),
+        internal_log_rate_secs = task.internal_log_rate_limit(),
+    );
+    let handle = AbortOnDrop(tokio::spawn(
+        AssertUnwindSafe(task)
+            .catch_unwind()
+            .map(|result| {
+                result
+                    .map_err(|_| TaskError::Panicked)
+                    .and_then(|result| result)
+            })
+            .instrument(span.or_current()),
+    ));
+
+    async move {
+        let result = match handle.await {
+            Ok(result) => result,
+            Err(error) => Err(error.into()),
+        };
+        (key, result)
+    }
+}
+
+/// A handle to a spawned task, aborting it once dropped.
+struct AbortOnDrop(JoinHandle<TaskResult>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl future::Future for AbortOnDrop {
+    type Output = <JoinHandle<TaskResult> as future::Future>::Output;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
@@ -0,0 +1,395 @@
+//! Rerouting of the events a sink failed to deliver to its `dlq` output.
+//!
+//! Every event going into a sink that has `reroute_failed` enabled gets an extra finalizer, and a
+//! copy of the event is kept until the sink finalizes it. Once the status of the event is known,
+//! the copy is either discarded, if it was delivered, or annotated and sent to the `dlq` output.
+//!
+//! At most `reroute_failed_max_pending` copies are kept at once. The events going into the sink
+//! while that many are pending aren't tracked, so they can't be rerouted if they fail.
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use futures::{stream::FuturesUnordered, StreamExt};
+use lookup::{lookup_v2::ValuePath, metadata_path, path, PathPrefix};
+use tokio::{select, sync::mpsc};
+use vector_common::internal_event::{self, CountByteSize, EventsSent, InternalEventHandle as _};
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+
+use super::fanout::Fanout;
+use crate::{
+    config::{log_schema, ComponentKey, DLQ_OUTPUT},
+    event::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, DeliveryFailure, Event, EventArray,
+        EventFinalizer, EventMutRef, Finalizable,
+    },
+    internal_events::DeadLetterEventsUntracked,
+};
+
+/// Tracks the status of the events going into a sink.
+#[derive(Clone)]
+pub(super) struct DeadLetterTap {
+    pending_tx: mpsc::UnboundedSender<(Event, BatchStatusReceiver)>,
+    pending: Arc<AtomicUsize>,
+    max_pending: usize,
+}
+
+impl DeadLetterTap {
+    /// Creates a tap for the sink `key`, keeping up to `max_pending` copies of its events, along
+    /// with the future forwarding the events the sink failed to deliver to `fanout`.
+    ///
+    /// The future runs until the tap is dropped and all the events it tracked are finalized.
+    pub(super) fn new(
+        key: ComponentKey,
+        typetag: &'static str,
+        max_pending: usize,
+        fanout: Fanout,
+    ) -> (Self, impl std::future::Future<Output = ()>) {
+        let (pending_tx, pending_rx) = mpsc::unbounded_channel();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let forward = forward_failed(pending_rx, Arc::clone(&pending), fanout, key, typetag);
+        let tap = Self {
+            pending_tx,
+            pending,
+            max_pending,
+        };
+        (tap, forward)
+    }
+
+    /// Starts tracking the status of `events`, as long as fewer than `max_pending` are pending.
+    pub(super) fn track(&self, mut events: EventArray) -> EventArray {
+        let mut untracked = 0;
+        for event in events.iter_events_mut() {
+            if self.pending.load(Ordering::Relaxed) >= self.max_pending {
+                untracked += 1;
+                continue;
+            }
+            self.pending.fetch_add(1, Ordering::Relaxed);
+
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            let finalizer = EventFinalizer::new(batch);
+            let mut copy = match event {
+                EventMutRef::Log(log) => {
+                    let copy = Event::from(log.clone());
+                    log.add_finalizer(finalizer);
+                    copy
+                }
+                EventMutRef::Metric(metric) => {
+                    let copy = Event::from(metric.clone());
+                    metric.add_finalizer(finalizer);
+                    copy
+                }
+                EventMutRef::Trace(trace) => {
+                    let copy = Event::from(trace.clone());
+                    trace.add_finalizer(finalizer);
+                    copy
+                }
+            };
+            // The copy must not hold up the acknowledgement of the original event by its source.
+            drop(copy.take_finalizers());
+
+            // The forwarding task only stops once the tap is dropped, so this can't fail.
+            let _ = self.pending_tx.send((copy, receiver));
+        }
+        if untracked > 0 {
+            emit!(DeadLetterEventsUntracked {
+                count: untracked,
+                max_pending: self.max_pending,
+            });
+        }
+        events
+    }
+}
+
+async fn forward_failed(
+    mut pending_rx: mpsc::UnboundedReceiver<(Event, BatchStatusReceiver)>,
+    pending_count: Arc<AtomicUsize>,
+    mut fanout: Fanout,
+    key: ComponentKey,
+    typetag: &'static str,
+) {
+    let events_sent = register!(EventsSent::from(internal_event::Output(Some(
+        DLQ_OUTPUT.into()
+    ))));
+    let mut pending = FuturesUnordered::new();
+    let mut tap_open = true;
+
+    loop {
+        select! {
+            maybe_event = pending_rx.recv(), if tap_open => match maybe_event {
+                Some((event, receiver)) => pending.push(async move { (receiver.outcome().await, event) }),
+                None => tap_open = false,
+            },
+
+            Some(((status, failure), mut event)) = pending.next() => {
+                pending_count.fetch_sub(1, Ordering::Relaxed);
+                if status == BatchStatus::Delivered {
+                    continue;
+                }
+
+                annotate_failed(&mut event, status, failure.as_ref(), &key, typetag);
+                let events = EventArray::from(event);
+                let byte_size = events.estimated_json_encoded_size_of();
+                if let Err(error) = fanout.send(events).await {
+                    error!(message = "Failed to send events to the dlq output.", %error);
+                    break;
+                }
+                events_sent.emit(CountByteSize(1, byte_size));
+            },
+
+            else => break,
+        }
+    }
+
+    debug!("Dlq output finished.");
+}
+
+/// The fields describing the failure of an event. The status code, error class and attempts are
+/// `null` when the sink recorded no details of the failure.
+fn failed_data(
+    status: BatchStatus,
+    failure: Option<&DeliveryFailure>,
+    key: &ComponentKey,
+    typetag: &str,
+) -> serde_json::Value {
+    let status = match status {
+        BatchStatus::Delivered => "delivered",
+        BatchStatus::Errored => "errored",
+        BatchStatus::Rejected => "rejected",
+    };
+    serde_json::json!({
+        "status": status,
+        "status_code": failure.and_then(|failure| failure.status_code),
+        "error_class": failure.map(|failure| failure.error_class.as_str()),
+        "attempts": failure.map(|failure| failure.attempts),
+        "component_id": key.id(),
+        "component_type": typetag,
+        "component_kind": "sink",
+    })
+}
+
+fn annotate_failed(
+    event: &mut Event,
+    status: BatchStatus,
+    failure: Option<&DeliveryFailure>,
+    key: &ComponentKey,
+    typetag: &str,
+) {
+    let data = failed_data(status, failure, key, typetag);
+    match event {
+        Event::Log(ref mut log) => match log.namespace() {
+            LogNamespace::Legacy => {
+                log.insert(
+                    (
+                        PathPrefix::Event,
+                        log_schema().metadata_key().concat(path!("dlq")),
+                    ),
+                    data,
+                );
+            }
+            LogNamespace::Vector => {
+                log.insert(metadata_path!("vector", "dlq"), data);
+            }
+        },
+        Event::Metric(ref mut metric) => {
+            let m = log_schema().metadata_key();
+            for field in [
+                "status",
+                "status_code",
+                "error_class",
+                "attempts",
+                "component_id",
+                "component_type",
+                "component_kind",
+            ] {
+                let value = match &data[field] {
+                    serde_json::Value::String(value) => value.clone(),
+                    serde_json::Value::Number(value) => value.to_string(),
+                    _ => continue,
+                };
+                metric.replace_tag(format!("{}.dlq.{}", m, field), value);
+            }
+        }
+        Event::Trace(ref mut trace) => {
+            trace.insert(format!("{}.dlq", log_schema().metadata_key()), data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use vector_buffers::{topology::builder::TopologyBuilder, WhenFull};
+    use vector_core::{
+        event::{EventContainer, EventStatus, LogEvent, Metric, MetricKind, MetricValue},
+        fanout::ControlMessage,
+    };
+
+    use super::*;
+    use crate::topology::builder::TOPOLOGY_BUFFER_SIZE;
+
+    #[tokio::test]
+    async fn reroutes_failed_events() {
+        let (fanout, control) = Fanout::new();
+        let (tx, rx) =
+            TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
+        control
+            .send(ControlMessage::Add(ComponentKey::from("dlq_sink"), tx))
+            .unwrap();
+
+        let (tap, forward) = DeadLetterTap::new(ComponentKey::from("out"), "test", 10, fanout);
+        let forward = tokio::spawn(forward);
+
+        let events = EventArray::from(vec![
+            LogEvent::from("delivered"),
+            LogEvent::from("errored"),
+            LogEvent::from("rejected"),
+        ]);
+        let statuses = [
+            EventStatus::Delivered,
+            EventStatus::Errored,
+            EventStatus::Rejected,
+        ];
+        let failure = DeliveryFailure {
+            status_code: Some(400),
+            error_class: "response".into(),
+            attempts: 3,
+        };
+        for (mut event, status) in tap.track(events).into_events().zip(statuses) {
+            let finalizers = event.take_finalizers();
+            finalizers.record_failure(&failure);
+            finalizers.update_status(status);
+        }
+        drop(tap);
+        forward.await.unwrap();
+
+        let mut failed = rx
+            .into_stream()
+            .flat_map(|events| stream::iter(events.into_events()))
+            .collect::<Vec<_>>()
+            .await;
+        failed.sort_by_key(|event| {
+            event.as_log()[log_schema().message_key()]
+                .to_string_lossy()
+                .into_owned()
+        });
+        assert_eq!(failed.len(), 2);
+
+        let metadata_key = log_schema().metadata_key();
+        for (event, expected) in failed.iter().zip(["errored", "rejected"]) {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()].to_string_lossy(), expected);
+            assert_eq!(
+                log[format!("{}.dlq.status", metadata_key).as_str()].to_string_lossy(),
+                expected
+            );
+            assert_eq!(
+                log[format!("{}.dlq.component_id", metadata_key).as_str()].to_string_lossy(),
+                "out"
+            );
+            assert_eq!(
+                log[format!("{}.dlq.status_code", metadata_key).as_str()],
+                crate::event::Value::Integer(400)
+            );
+            assert_eq!(
+                log[format!("{}.dlq.error_class", metadata_key).as_str()].to_string_lossy(),
+                "response"
+            );
+            assert_eq!(
+                log[format!("{}.dlq.attempts", metadata_key).as_str()],
+                crate::event::Value::Integer(3)
+            );
+            assert!(log.metadata().finalizers().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn tracks_at_most_max_pending_events() {
+        let (fanout, control) = Fanout::new();
+        let (tx, rx) =
+            TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
+        control
+            .send(ControlMessage::Add(ComponentKey::from("dlq_sink"), tx))
+            .unwrap();
+
+        let (tap, forward) = DeadLetterTap::new(ComponentKey::from("out"), "test", 2, fanout);
+        let forward = tokio::spawn(forward);
+
+        let events = EventArray::from(vec![
+            LogEvent::from("first"),
+            LogEvent::from("second"),
+            LogEvent::from("third"),
+        ]);
+        let events = tap.track(events).into_events().collect::<Vec<_>>();
+        // The third event went in while two were pending, so it isn't tracked.
+        assert!(events[2].metadata().finalizers().is_empty());
+        for mut event in events {
+            event.take_finalizers().update_status(EventStatus::Rejected);
+        }
+
+        // Once finalized, the events are no longer pending.
+        while tap.pending.load(Ordering::Relaxed) > 0 {
+            tokio::task::yield_now().await;
+        }
+        let events = tap.track(EventArray::from(LogEvent::from("fourth")));
+        for mut event in events.into_events() {
+            assert!(!event.metadata().finalizers().is_empty());
+            event.take_finalizers().update_status(EventStatus::Rejected);
+        }
+        drop(tap);
+        forward.await.unwrap();
+
+        let failed = rx
+            .into_stream()
+            .flat_map(|events| stream::iter(events.into_events()))
+            .map(|event| {
+                event.as_log()[log_schema().message_key()]
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(failed, ["first", "second", "fourth"]);
+    }
+
+    #[test]
+    fn annotates_metrics_with_tags() {
+        let mut event = Event::from(Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let failure = DeliveryFailure {
+            status_code: Some(503),
+            error_class: "response".into(),
+            attempts: 5,
+        };
+        annotate_failed(
+            &mut event,
+            BatchStatus::Rejected,
+            Some(&failure),
+            &ComponentKey::from("out"),
+            "test",
+        );
+
+        let metadata_key = log_schema().metadata_key();
+        let metric = event.as_metric();
+        assert_eq!(
+            metric.tag_value(&format!("{}.dlq.status", metadata_key)),
+            Some("rejected".into())
+        );
+        assert_eq!(
+            metric.tag_value(&format!("{}.dlq.component_kind", metadata_key)),
+            Some("sink".into())
+        );
+        assert_eq!(
+            metric.tag_value(&format!("{}.dlq.status_code", metadata_key)),
+            Some("503".into())
+        );
+        assert_eq!(
+            metric.tag_value(&format!("{}.dlq.attempts", metadata_key)),
+            Some("5".into())
+        );
+    }
+}
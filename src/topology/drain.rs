@@ -0,0 +1,66 @@
+//! Progress of the graceful shutdown of the topology.
+//!
+//! While the topology is stopping, whether Vector is shutting down or was asked to drain, the
+//! components which are still running are reported here, so the API can tell how far along the
+//! drain is.
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tokio::time::{Duration, Instant};
+
+use crate::config::ComponentKey;
+
+/// The progress of the ongoing shutdown, if the topology is stopping.
+static PROGRESS: Lazy<Mutex<Option<Progress>>> = Lazy::new(Default::default);
+
+#[cfg_attr(not(feature = "api"), allow(dead_code))]
+struct Progress {
+    started_at: Instant,
+    deadline: Instant,
+    total_components: usize,
+    remaining_components: Vec<ComponentKey>,
+}
+
+/// Records the start of the shutdown of the given components, which are killed at `deadline`.
+pub(super) fn start(deadline: Instant, components: Vec<ComponentKey>) {
+    *PROGRESS.lock().expect("drain lock poisoned") = Some(Progress {
+        started_at: Instant::now(),
+        deadline,
+        total_components: components.len(),
+        remaining_components: components,
+    });
+}
+
+/// Records the components which are still running.
+pub(super) fn update(remaining_components: Vec<ComponentKey>) {
+    if let Some(progress) = PROGRESS.lock().expect("drain lock poisoned").as_mut() {
+        progress.remaining_components = remaining_components;
+    }
+}
+
+/// The progress of the shutdown of the topology.
+#[cfg(feature = "api")]
+#[derive(Debug)]
+pub(crate) struct DrainStatus {
+    pub(crate) elapsed: Duration,
+    /// The time left until the remaining components are killed, or `None` if the deadline passed.
+    pub(crate) time_remaining: Option<Duration>,
+    pub(crate) total_components: usize,
+    pub(crate) remaining_components: Vec<ComponentKey>,
+}
+
+/// Returns the progress of the shutdown of the topology, or `None` if the topology isn't stopping.
+#[cfg(feature = "api")]
+pub(crate) fn status() -> Option<DrainStatus> {
+    let progress = PROGRESS.lock().expect("drain lock poisoned");
+    progress.as_ref().map(|progress| {
+        let mut remaining_components = progress.remaining_components.clone();
+        remaining_components.sort();
+        DrainStatus {
+            elapsed: progress.started_at.elapsed(),
+            time_remaining: progress.deadline.checked_duration_since(Instant::now()),
+            total_components: progress.total_components,
+            remaining_components,
+        }
+    })
+}
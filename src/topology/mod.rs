@@ -8,11 +8,17 @@
 //! each type of component.
 
 pub(super) use vector_core::fanout;
+pub(crate) mod backpressure;
 pub mod schema;
 
 pub mod builder;
+mod canary;
+mod dead_letter;
+pub(crate) mod drain;
 mod ready_arrays;
 mod running;
+mod schema_enforcement;
+mod stale_events;
 mod task;
 
 #[cfg(test)]
@@ -27,6 +27,7 @@ use crate::{
     topology::{
         build_or_log_errors, builder,
         builder::Pieces,
+        canary, drain,
         fanout::{ControlChannel, ControlMessage},
         handle_errors, retain, take_healthchecks,
         task::TaskOutput,
@@ -34,6 +35,9 @@ use crate::{
     },
 };
 
+/// How long the components are given to shut down gracefully, unless configured otherwise.
+const DEFAULT_GRACEFUL_SHUTDOWN_LIMIT: Duration = Duration::from_secs(60);
+
 #[allow(dead_code)]
 pub struct RunningTopology {
     inputs: HashMap<ComponentKey, BufferSender<EventArray>>,
@@ -105,6 +109,12 @@ impl RunningTopology {
     /// dropped then everything from this RunningTopology instance is fully
     /// dropped.
     pub fn stop(self) -> impl Future<Output = ()> {
+        self.stop_within(DEFAULT_GRACEFUL_SHUTDOWN_LIMIT)
+    }
+
+    /// Like [`RunningTopology::stop`], except that the components which haven't finished
+    /// shutting down after `limit` are killed.
+    pub fn stop_within(self, limit: Duration) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
         // Create handy handles collections of all tasks for the subsequent
@@ -124,7 +134,8 @@ impl RunningTopology {
         }
 
         // If we reach this, we will forcefully shutdown the sources.
-        let deadline = Instant::now() + Duration::from_secs(60);
+        let deadline = Instant::now() + limit;
+        drain::start(deadline, check_handles.keys().cloned().collect());
 
         // If we reach the deadline, this future will print out which components
         // won't gracefully shutdown since we will start to forcefully shutdown
@@ -149,17 +160,25 @@ impl RunningTopology {
             );
         };
 
-        // Reports in intervals which components are still running.
-        let mut interval = interval(Duration::from_secs(5));
+        // Reports in intervals which components are still running. The progress is updated every
+        // second, but only logged every five seconds.
+        let mut interval = interval(Duration::from_secs(1));
         let reporter = async move {
+            let mut ticks = 0_u64;
             loop {
                 interval.tick().await;
+                ticks = ticks.wrapping_add(1);
 
                 // Remove all tasks that have shutdown.
                 check_handles.retain(|_key, handles| {
                     retain(handles, |handle| handle.peek().is_none());
                     !handles.is_empty()
                 });
+                drain::update(check_handles.keys().cloned().collect());
+                if ticks % 5 != 1 {
+                    continue;
+                }
+
                 let remaining_components = check_handles
                     .keys()
                     .map(|item| item.to_string())
@@ -195,6 +214,16 @@ impl RunningTopology {
         futures::future::join(source_shutdown_complete, shutdown_complete_future).map(|_| ())
     }
 
+    /// Reloads the given enrichment tables, leaving the rest of the topology as is.
+    ///
+    /// If a table can't be reloaded, its previously loaded data keeps being used.
+    pub async fn reload_enrichment_tables(&self, tables: &[ComponentKey]) {
+        debug!(message = "Reloading enrichment tables.", tables = ?tables);
+        for error in builder::reload_enrichment_tables(&self.config, tables).await {
+            error!(message = "Failed to reload enrichment table.", %error);
+        }
+    }
+
     /// Attempts to load a new configuration and update this running topology.
     ///
     /// If the new configuration was valid, and all changes were able to be made -- removing of
@@ -210,7 +239,7 @@ impl RunningTopology {
     ///
     /// If all changes from the new configuration cannot be made, and the current configuration
     /// cannot be fully restored, then `Err(())` is returned.
-    pub async fn reload_config_and_respawn(&mut self, new_config: Config) -> Result<bool, ()> {
+    pub async fn reload_config_and_respawn(&mut self, mut new_config: Config) -> Result<bool, ()> {
         info!("Reloading running topology with new configuration.");
 
         if self.config.global != new_config.global {
@@ -226,7 +255,20 @@ impl RunningTopology {
         // spawning the new version of the component.
         //
         // We also shutdown any component that is simply being removed entirely.
-        let diff = ConfigDiff::new(&self.config, &new_config);
+        //
+        // Transforms that can apply their changes while running, such as a `remap` transform whose
+        // program changed, are reloaded first so that they aren't shut down at all.
+        let mut diff = ConfigDiff::new(&self.config, &new_config);
+
+        // With canary reloads, the changed components are tried out alongside the running ones
+        // first, and the reload is aborted before anything is shut down if they fail.
+        if new_config.canary.enabled
+            && !canary::run(&self.config, &new_config, &diff, &self.outputs).await
+        {
+            return Ok(false);
+        }
+
+        let transform_reloads = builder::reload_transforms(&self.config, &new_config, &mut diff);
         let buffers = self.shutdown_diff(&diff, &new_config).await;
 
         // Gives windows some time to make available any port
@@ -251,6 +293,7 @@ impl RunningTopology {
             {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
+                builder::apply_transform_reloads(&mut new_config, transform_reloads);
                 self.config = new_config;
 
                 info!("New configuration loaded successfully.");
@@ -261,7 +304,8 @@ impl RunningTopology {
 
         // We failed to build, connect, and spawn all of the changed/new components, so we flip
         // around the configuration differential to generate all the components that we need to
-        // bring back to restore the current configuration.
+        // bring back to restore the current configuration. The transforms reloaded without being
+        // rebuilt are left out of the diff, and their changes are dropped without being applied.
         warn!("Failed to completely load new configuration. Restoring old configuration.");
 
         let diff = diff.flip();
@@ -473,6 +517,12 @@ impl RunningTopology {
             self.remove_inputs(key, diff, new_config).await;
         }
 
+        // Sinks recreate their `dlq` and `dropped` outputs when they're rebuilt, so we only remove
+        // the outputs of changed/removed sinks once every sink had a chance to detach from them.
+        for key in diff.sinks.removed_and_changed() {
+            self.remove_outputs(key);
+        }
+
         // Now that we've disconnected or temporarily detached the inputs to all changed/removed
         // sinks, we can actually wait for them to shutdown before collecting any buffers that are
         // marked for reuse.
@@ -536,7 +586,8 @@ impl RunningTopology {
             }
 
             for key in &diff.sinks.to_remove {
-                // Sinks only have inputs
+                // Sinks can have both inputs and `dlq` or `dropped` outputs
+                self.outputs_tap_metadata.remove(key);
                 self.inputs_tap_metadata.remove(key);
             }
 
@@ -554,6 +605,18 @@ impl RunningTopology {
                 }
             }
 
+            for key in diff.sinks.changed_and_added() {
+                match new_pieces.tasks.get(key) {
+                    Some(task) if new_pieces.outputs.contains_key(key) => {
+                        self.outputs_tap_metadata
+                            .insert(key.clone(), ("sink", task.typetag().to_string()));
+                    }
+                    _ => {
+                        self.outputs_tap_metadata.remove(key);
+                    }
+                }
+            }
+
             for (key, input) in &new_pieces.inputs {
                 self.inputs_tap_metadata
                     .insert(key.clone(), input.1.clone());
@@ -574,6 +637,15 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks with `dlq` or `dropped` outputs are configured last, as they can feed into
+        // transforms and other sinks as well.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                debug!(component = %key, "Configuring outputs for sink.");
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         // Now that all possible outputs are configured, we can start wiring up inputs, starting
         // with transforms.
         for key in diff.transforms.changed_and_added() {
@@ -623,6 +695,7 @@ impl RunningTopology {
 
             let mut removals = diff.sources.to_remove.clone();
             removals.extend(diff.transforms.to_remove.iter().cloned());
+            removals.extend(diff.sinks.to_remove.iter().cloned());
             self.watch
                 .0
                 .send(TapResource {
@@ -638,8 +711,7 @@ impl RunningTopology {
                         .changed_and_added()
                         .map(|key| key.to_string())
                         .collect(),
-                    // Note, only sources and transforms are relevant. Sinks do
-                    // not have outputs to tap.
+                    // Note, sinks are only relevant for their `dlq` and `dropped` outputs.
                     removals,
                 })
                 .expect("Couldn't broadcast config changes.");
@@ -827,6 +899,7 @@ impl RunningTopology {
             component_type = %task.typetag(),
             // maintained for compatibility
             component_name = %task.id(),
+            internal_log_rate_secs = task.internal_log_rate_limit(),
         );
 
         let task_span = span.or_current();
@@ -864,6 +937,7 @@ impl RunningTopology {
             component_type = %task.typetag(),
             // maintained for compatibility
             component_name = %task.id(),
+            internal_log_rate_secs = task.internal_log_rate_limit(),
         );
 
         let task_span = span.or_current();
@@ -901,6 +975,7 @@ impl RunningTopology {
             component_type = %task.typetag(),
             // maintained for compatibility
             component_name = %task.id(),
+            internal_log_rate_secs = task.internal_log_rate_limit(),
         );
 
         let task_span = span.or_current();
@@ -961,5 +1036,14 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<O
         );
     }
 
+    for sink_key in &diff.sinks.to_change {
+        changed_outputs.extend(
+            output_ids
+                .iter()
+                .filter(|id| &id.component == sink_key)
+                .cloned(),
+        );
+    }
+
     changed_outputs
 }
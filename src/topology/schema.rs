@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+
+use lookup::owned_value_path;
 use value::Kind;
 
 pub(super) use crate::schema::Definition;
@@ -103,6 +105,11 @@ pub fn merged_definition(
                 ));
             }
         }
+        // If the input is the `dlq` output of a sink, it carries the events the sink failed to
+        // deliver, so the definition of the sink inputs is passed through as-is.
+        if let Some(inputs) = config.sink_inputs(key) {
+            definition = definition.merge(merged_definition(inputs, config, cache));
+        }
     }
     definition
 }
@@ -205,6 +212,11 @@ pub(super) fn expanded_definitions(
                 // pipeline definitions.
                 definitions.append(&mut expanded_definitions);
             }
+
+        // The `dlq` output of a sink carries the events the sink received, so it expands to the
+        // pipelines feeding into the sink.
+        } else if let Some(inputs) = config.sink_inputs(key) {
+            definitions.append(&mut expanded_definitions(inputs, config, cache));
         }
     }
 
@@ -266,6 +278,13 @@ pub trait ComponentContainer {
         merged_definition: &Definition,
     ) -> Option<Vec<Output>>;
 
+    /// Gets the inputs of the sink with the given key.
+    ///
+    /// Returns None if there is no sink with the given key.
+    fn sink_inputs(&self, _key: &ComponentKey) -> Option<&[OutputId]> {
+        None
+    }
+
     /// Gets the transform output for the given port.
     ///
     /// Returns Err(()) if there is no transform with the given key
@@ -302,6 +321,27 @@ pub trait ComponentContainer {
     }
 }
 
+/// Adds the source origin fields stamped by the source sender to the metadata of the given
+/// definition, so they are known to VRL and sink schema requirements.
+pub(crate) fn with_origin_metadata(definition: Definition) -> Definition {
+    definition
+        .with_metadata_field(
+            &owned_value_path!("vector", "source_type"),
+            Kind::bytes(),
+            None,
+        )
+        .with_metadata_field(
+            &owned_value_path!("vector", "component_id"),
+            Kind::bytes(),
+            None,
+        )
+        .with_metadata_field(
+            &owned_value_path!("vector", "ingest_timestamp"),
+            Kind::timestamp(),
+            None,
+        )
+}
+
 fn get_output_for_port(outputs: Vec<Output>, port: &Option<String>) -> Option<Output> {
     outputs.into_iter().find(|output| &output.port == port)
 }
@@ -312,8 +352,24 @@ impl ComponentContainer for Config {
     }
 
     fn source_outputs(&self, key: &ComponentKey) -> Option<Vec<Output>> {
-        self.source(key)
-            .map(|source| source.inner.outputs(self.schema.log_namespace()))
+        self.source(key).map(|source| {
+            let outputs = source.inner.outputs(self.schema.log_namespace());
+            if self.schema.origin_metadata {
+                outputs
+                    .into_iter()
+                    .map(|mut output| {
+                        output.log_schema_definition = Some(with_origin_metadata(
+                            output
+                                .log_schema_definition
+                                .unwrap_or_else(Definition::default_legacy_namespace),
+                        ));
+                        output
+                    })
+                    .collect()
+            } else {
+                outputs
+            }
+        })
     }
 
     fn transform_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
@@ -331,6 +387,10 @@ impl ComponentContainer for Config {
                 .outputs(merged_definition, self.schema.log_namespace())
         })
     }
+
+    fn sink_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
+        self.sink(key).map(|sink| &sink.inputs[..])
+    }
 }
 
 #[cfg(test)]
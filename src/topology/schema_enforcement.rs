@@ -0,0 +1,563 @@
+//! Validation of the events going into a sink against the schema configured for it.
+//!
+//! The schema is a JSON Schema, compiled once when the sink is built. Only the keywords that can
+//! be checked against the fields of an event are supported, and compiling a schema using any other
+//! keyword affecting validation fails, so that no constraint is silently ignored.
+//!
+//! Events violating the schema are handled before they reach the sink, according to its
+//! `on_violation` option: they're either rejected, repaired where possible, or annotated and sent
+//! to the `dropped` output of the sink.
+use std::{collections::BTreeMap, fmt, fs};
+
+use lookup::{metadata_path, path, PathPrefix};
+use ordered_float::NotNan;
+use tokio::sync::mpsc;
+use vector_common::internal_event::{self, CountByteSize, EventsSent, InternalEventHandle as _};
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+
+use super::fanout::Fanout;
+use crate::{
+    config::{log_schema, ComponentKey, OnSchemaViolation, SinkSchemaConfig, DROPPED_OUTPUT},
+    event::{Event, EventArray, EventStatus, Finalizable, LogEvent, Value},
+    internal_events::SinkSchemaViolation,
+};
+
+/// Validates the events going into a sink, and handles the ones violating its schema.
+pub(super) struct SchemaEnforcer {
+    schema: Schema,
+    on_violation: OnSchemaViolation,
+    dropped_tx: Option<mpsc::UnboundedSender<Event>>,
+    key: ComponentKey,
+    typetag: &'static str,
+}
+
+impl SchemaEnforcer {
+    /// Compiles the schema of the sink `key`.
+    pub(super) fn new(
+        key: ComponentKey,
+        typetag: &'static str,
+        config: &SinkSchemaConfig,
+    ) -> crate::Result<Self> {
+        let definition = match (&config.path, &config.definition) {
+            (Some(path), None) => {
+                let contents = fs::read(path)
+                    .map_err(|error| format!("Could not read schema {:?}: {}", path, error))?;
+                serde_json::from_slice(&contents)
+                    .map_err(|error| format!("Invalid schema {:?}: {}", path, error))?
+            }
+            (None, Some(definition)) => serde_json::to_value(definition)?,
+            _ => {
+                return Err(
+                    "Exactly one of `schema.path` and `schema.definition` must be set".into(),
+                )
+            }
+        };
+        let schema =
+            Schema::parse(&definition).map_err(|error| format!("Invalid schema: {}", error))?;
+
+        Ok(Self {
+            schema,
+            on_violation: config.on_violation,
+            dropped_tx: None,
+            key,
+            typetag,
+        })
+    }
+
+    /// Sends the events violating the schema to `fanout`, returning the future forwarding them.
+    ///
+    /// The future runs until the enforcer is dropped and all the events it routed are sent.
+    pub(super) fn route_dropped(
+        &mut self,
+        fanout: Fanout,
+    ) -> impl std::future::Future<Output = ()> {
+        let (dropped_tx, dropped_rx) = mpsc::unbounded_channel();
+        self.dropped_tx = Some(dropped_tx);
+        forward_dropped(dropped_rx, fanout)
+    }
+
+    /// Validates `events`, returning the ones to send to the sink, if any.
+    ///
+    /// Only log events are validated.
+    pub(super) fn enforce(&self, events: EventArray) -> Option<EventArray> {
+        let logs = match events {
+            EventArray::Logs(logs) => logs,
+            events => return Some(events),
+        };
+        let logs = logs
+            .into_iter()
+            .filter_map(|log| self.enforce_one(log))
+            .collect::<Vec<_>>();
+        (!logs.is_empty()).then_some(EventArray::Logs(logs))
+    }
+
+    fn enforce_one(&self, mut log: LogEvent) -> Option<LogEvent> {
+        let coerce = self.on_violation == OnSchemaViolation::Coerce;
+        let violation = match self.schema.check(log.value_mut(), coerce) {
+            Ok(()) => return Some(log),
+            Err(violation) => violation,
+        };
+
+        match &self.dropped_tx {
+            Some(dropped_tx) => {
+                emit!(SinkSchemaViolation {
+                    error: &violation,
+                    drop_event: false,
+                });
+                self.annotate_dropped(&mut log, &violation);
+                // The forwarding task only stops once the enforcer is dropped, so this can't fail.
+                let _ = dropped_tx.send(log.into());
+            }
+            None => {
+                emit!(SinkSchemaViolation {
+                    error: &violation,
+                    drop_event: true,
+                });
+                log.take_finalizers().update_status(EventStatus::Rejected);
+            }
+        }
+        None
+    }
+
+    fn annotate_dropped(&self, log: &mut LogEvent, violation: &Violation) {
+        let data = serde_json::json!({
+            "reason": "schema_violation",
+            "message": violation.to_string(),
+            "component_id": self.key.id(),
+            "component_type": self.typetag,
+            "component_kind": "sink",
+        });
+        match log.namespace() {
+            LogNamespace::Legacy => {
+                log.insert(
+                    (
+                        PathPrefix::Event,
+                        log_schema().metadata_key().concat(path!("dropped")),
+                    ),
+                    data,
+                );
+            }
+            LogNamespace::Vector => {
+                log.insert(metadata_path!("vector", "dropped"), data);
+            }
+        }
+    }
+}
+
+async fn forward_dropped(mut dropped_rx: mpsc::UnboundedReceiver<Event>, mut fanout: Fanout) {
+    let events_sent = register!(EventsSent::from(internal_event::Output(Some(
+        DROPPED_OUTPUT.into()
+    ))));
+
+    while let Some(event) = dropped_rx.recv().await {
+        let events = EventArray::from(event);
+        let byte_size = events.estimated_json_encoded_size_of();
+        if let Err(error) = fanout.send(events).await {
+            error!(message = "Failed to send events to the dropped output.", %error);
+            break;
+        }
+        events_sent.emit(CountByteSize(1, byte_size));
+    }
+
+    debug!("Dropped output finished.");
+}
+
+/// A value of an event violating the schema.
+#[derive(Debug)]
+struct Violation {
+    path: String,
+    message: String,
+}
+
+impl Violation {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            path: String::new(),
+            message: message.into(),
+        }
+    }
+
+    /// Prefixes the path of the violating value with `segment`, as it is propagated up to the
+    /// root of the event.
+    fn within(mut self, segment: &str) -> Self {
+        self.path = if self.path.is_empty() || self.path.starts_with('[') {
+            format!("{}{}", segment, self.path)
+        } else {
+            format!("{}.{}", segment, self.path)
+        };
+        self
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{:?}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// The types of JSON Schema, and the values of events matching them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SchemaType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl SchemaType {
+    fn parse(name: &str) -> Result<Self, String> {
+        Ok(match name {
+            "null" => Self::Null,
+            "boolean" => Self::Boolean,
+            "integer" => Self::Integer,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "array" => Self::Array,
+            "object" => Self::Object,
+            _ => return Err(format!("unknown type {:?}", name)),
+        })
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+
+    /// The type of `value`, once encoded as JSON.
+    const fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Boolean(_) => Self::Boolean,
+            Value::Integer(_) => Self::Integer,
+            Value::Float(_) => Self::Number,
+            Value::Bytes(_) | Value::Timestamp(_) | Value::Regex(_) => Self::String,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Number, Value::Integer(_)) => true,
+            (Self::Integer, Value::Float(float)) => float.fract() == 0.0,
+            _ => self == Self::of(value),
+        }
+    }
+
+    /// Converts `value` to this type, if it has an unambiguous representation in it.
+    fn coerce(self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (Self::String, Value::Integer(_) | Value::Float(_) | Value::Boolean(_)) => {
+                Some(Value::from(value.to_string_lossy().into_owned()))
+            }
+            (Self::Integer, Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+                .ok()?
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .map(Value::Integer),
+            (Self::Number, Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+                .ok()?
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|float| NotNan::new(float).ok())
+                .map(Value::Float),
+            (Self::Boolean, Value::Bytes(bytes)) => match bytes.as_ref() {
+                b"true" => Some(Value::Boolean(true)),
+                b"false" => Some(Value::Boolean(false)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// A compiled JSON Schema.
+#[derive(Debug, Default)]
+struct Schema {
+    /// Whether no value is valid, as for the `false` schema.
+    never: bool,
+    types: Option<Vec<SchemaType>>,
+    properties: BTreeMap<String, Schema>,
+    required: Vec<String>,
+    additional_properties: Option<Box<Schema>>,
+    items: Option<Box<Schema>>,
+    allowed: Option<Vec<serde_json::Value>>,
+}
+
+impl Schema {
+    fn parse(schema: &serde_json::Value) -> Result<Self, String> {
+        let keywords = match schema {
+            serde_json::Value::Bool(valid) => {
+                return Ok(Self {
+                    never: !valid,
+                    ..Self::default()
+                })
+            }
+            serde_json::Value::Object(keywords) => keywords,
+            _ => return Err("a schema must be an object or a boolean".into()),
+        };
+
+        let mut parsed = Self::default();
+        for (keyword, value) in keywords {
+            match keyword.as_str() {
+                "type" => {
+                    let types = match value {
+                        serde_json::Value::String(name) => vec![SchemaType::parse(name)?],
+                        serde_json::Value::Array(names) => names
+                            .iter()
+                            .map(|name| {
+                                name.as_str()
+                                    .ok_or_else(|| "`type` must only hold strings".to_owned())
+                                    .and_then(SchemaType::parse)
+                            })
+                            .collect::<Result<_, _>>()?,
+                        _ => return Err("`type` must be a string or an array of strings".into()),
+                    };
+                    parsed.types = Some(types);
+                }
+                "properties" => {
+                    let properties = value
+                        .as_object()
+                        .ok_or_else(|| "`properties` must be an object".to_owned())?;
+                    for (name, property) in properties {
+                        let property = Self::parse(property)
+                            .map_err(|error| format!("property {:?}: {}", name, error))?;
+                        parsed.properties.insert(name.clone(), property);
+                    }
+                }
+                "required" => {
+                    parsed.required = value
+                        .as_array()
+                        .and_then(|names| {
+                            names
+                                .iter()
+                                .map(|name| name.as_str().map(ToOwned::to_owned))
+                                .collect()
+                        })
+                        .ok_or_else(|| "`required` must be an array of strings".to_owned())?;
+                }
+                "additionalProperties" => {
+                    parsed.additional_properties = Some(Box::new(Self::parse(value)?));
+                }
+                "items" => parsed.items = Some(Box::new(Self::parse(value)?)),
+                "enum" => {
+                    let allowed = value
+                        .as_array()
+                        .ok_or_else(|| "`enum` must be an array".to_owned())?;
+                    parsed.allowed = Some(allowed.clone());
+                }
+                "const" => parsed.allowed = Some(vec![value.clone()]),
+                // Annotations, which don't affect validation.
+                "$schema" | "$id" | "$comment" | "title" | "description" | "default"
+                | "examples" | "deprecated" | "readOnly" | "writeOnly" | "format" => {}
+                _ => return Err(format!("unsupported keyword {:?}", keyword)),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Checks `value` against the schema. When `coerce` is set, `value` is repaired in place where
+    /// possible, which may still leave it violating the schema.
+    fn check(&self, value: &mut Value, coerce: bool) -> Result<(), Violation> {
+        if self.never {
+            return Err(Violation::new("is not allowed"));
+        }
+
+        if let Some(types) = &self.types {
+            if !types.iter().any(|ty| ty.matches(value)) {
+                let coerced = coerce
+                    .then(|| types.iter().find_map(|ty| ty.coerce(value)))
+                    .flatten();
+                match coerced {
+                    Some(coerced) => *value = coerced,
+                    None => {
+                        let expected = types
+                            .iter()
+                            .map(|ty| ty.name())
+                            .collect::<Vec<_>>()
+                            .join(" or ");
+                        return Err(Violation::new(format!(
+                            "expected {}, found {}",
+                            expected,
+                            SchemaType::of(value).name()
+                        )));
+                    }
+                }
+            }
+        }
+
+        if let Some(allowed) = &self.allowed {
+            let json = serde_json::to_value(&*value)
+                .map_err(|error| Violation::new(format!("can't be encoded: {}", error)))?;
+            if !allowed.contains(&json) {
+                return Err(Violation::new("is not one of the allowed values"));
+            }
+        }
+
+        match value {
+            Value::Object(fields) => {
+                for name in &self.required {
+                    if !fields.contains_key(name.as_str()) {
+                        return Err(Violation::new("is required").within(name));
+                    }
+                }
+
+                let mut removed = Vec::new();
+                for (name, field) in fields.iter_mut() {
+                    let schema = match self.properties.get(name.as_str()) {
+                        Some(schema) => schema,
+                        None => match &self.additional_properties {
+                            Some(schema) => schema,
+                            None => continue,
+                        },
+                    };
+                    match schema.check(field, coerce) {
+                        Ok(()) => {}
+                        // Fields that aren't allowed at all can be repaired by removing them.
+                        Err(_) if coerce && schema.never => removed.push(name.clone()),
+                        Err(violation) => return Err(violation.within(name)),
+                    }
+                }
+                for name in removed {
+                    fields.remove(name.as_str());
+                }
+            }
+            Value::Array(values) => {
+                if let Some(items) = &self.items {
+                    for (index, item) in values.iter_mut().enumerate() {
+                        items
+                            .check(item, coerce)
+                            .map_err(|violation| violation.within(&format!("[{}]", index)))?;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(definition: serde_json::Value) -> Schema {
+        Schema::parse(&definition).unwrap()
+    }
+
+    fn order_schema() -> Schema {
+        schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer" },
+                "status": { "enum": ["open", "closed"] },
+                "items": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["id", "status"],
+            "additionalProperties": false,
+        }))
+    }
+
+    fn check(schema: &Schema, value: serde_json::Value, coerce: bool) -> Result<Value, String> {
+        let mut value = Value::from(value);
+        schema
+            .check(&mut value, coerce)
+            .map(|_| value)
+            .map_err(|violation| violation.to_string())
+    }
+
+    #[test]
+    fn validates_values() {
+        let schema = order_schema();
+
+        assert!(check(
+            &schema,
+            serde_json::json!({"id": 1, "status": "open", "items": ["a"]}),
+            false
+        )
+        .is_ok());
+        assert_eq!(
+            check(&schema, serde_json::json!({"id": 1}), false),
+            Err(r#""status": is required"#.to_owned())
+        );
+        assert_eq!(
+            check(
+                &schema,
+                serde_json::json!({"id": "1", "status": "open"}),
+                false
+            ),
+            Err(r#""id": expected integer, found string"#.to_owned())
+        );
+        assert_eq!(
+            check(
+                &schema,
+                serde_json::json!({"id": 1, "status": "pending"}),
+                false
+            ),
+            Err(r#""status": is not one of the allowed values"#.to_owned())
+        );
+        assert_eq!(
+            check(
+                &schema,
+                serde_json::json!({"id": 1, "status": "open", "items": ["a", 2]}),
+                false
+            ),
+            Err(r#""items[1]": expected string, found integer"#.to_owned())
+        );
+        assert_eq!(
+            check(
+                &schema,
+                serde_json::json!({"id": 1, "status": "open", "extra": true}),
+                false
+            ),
+            Err(r#""extra": is not allowed"#.to_owned())
+        );
+    }
+
+    #[test]
+    fn coerces_values() {
+        let schema = order_schema();
+
+        assert_eq!(
+            check(
+                &schema,
+                serde_json::json!({"id": "42", "status": "open", "items": [1], "extra": true}),
+                true
+            ),
+            Ok(Value::from(
+                serde_json::json!({"id": 42, "status": "open", "items": ["1"]})
+            ))
+        );
+        assert!(check(
+            &schema,
+            serde_json::json!({"id": "forty-two", "status": "open"}),
+            true
+        )
+        .is_err());
+        assert!(check(&schema, serde_json::json!({"status": "open"}), true).is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_keywords() {
+        assert!(Schema::parse(&serde_json::json!({"type": "string", "maxLength": 3})).is_err());
+        assert!(Schema::parse(&serde_json::json!({"type": "text"})).is_err());
+        assert!(Schema::parse(&serde_json::json!({"title": "Orders", "type": "object"})).is_ok());
+    }
+}
@@ -0,0 +1,106 @@
+//! Dropping of the events going into a sink once they're older than its maximum age.
+//!
+//! The age of an event is measured from its timestamp when it reaches the sink, so that after a
+//! long outage of the downstream service the sink doesn't spend its time catching up on events no
+//! one is going to look at. Only log and metric events with a timestamp are dropped.
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    event::{EventArray, Value},
+    internal_events::StaleEventsDropped,
+};
+
+/// Drops the events older than the maximum age of a sink.
+pub(super) struct StaleEventsFilter {
+    max_age: Duration,
+}
+
+impl StaleEventsFilter {
+    pub(super) fn new(max_age_secs: u64) -> Self {
+        Self {
+            max_age: Duration::from_std(std::time::Duration::from_secs(max_age_secs))
+                .unwrap_or_else(|_| Duration::max_value()),
+        }
+    }
+
+    /// Drops the stale `events`, returning the ones to send to the sink, if any.
+    pub(super) fn filter(&self, events: EventArray) -> Option<EventArray> {
+        self.filter_at(events, Utc::now())
+    }
+
+    fn filter_at(&self, events: EventArray, now: DateTime<Utc>) -> Option<EventArray> {
+        // No event is stale when the maximum age goes back further than timestamps can.
+        let cutoff = match now.checked_sub_signed(self.max_age) {
+            Some(cutoff) => cutoff,
+            None => return Some(events),
+        };
+        let is_stale = |timestamp: Option<&DateTime<Utc>>| {
+            timestamp.map_or(false, |timestamp| *timestamp < cutoff)
+        };
+
+        // The dropped events are acknowledged, as they're dropped on purpose.
+        let (events, count) = match events {
+            EventArray::Logs(mut logs) => {
+                let len = logs.len();
+                logs.retain(|log| !is_stale(log.get_timestamp().and_then(Value::as_timestamp)));
+                let count = len - logs.len();
+                (EventArray::Logs(logs), count)
+            }
+            EventArray::Metrics(mut metrics) => {
+                let len = metrics.len();
+                metrics.retain(|metric| !is_stale(metric.timestamp().as_ref()));
+                let count = len - metrics.len();
+                (EventArray::Metrics(metrics), count)
+            }
+            events => (events, 0),
+        };
+
+        if count > 0 {
+            emit!(StaleEventsDropped { count });
+        }
+        match &events {
+            EventArray::Logs(logs) if logs.is_empty() => None,
+            EventArray::Metrics(metrics) if metrics.is_empty() => None,
+            _ => Some(events),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{LogEvent, Metric, MetricKind, MetricValue};
+
+    use super::*;
+
+    fn log(timestamp: Option<DateTime<Utc>>) -> LogEvent {
+        let mut log = LogEvent::from("message");
+        if let Some(timestamp) = timestamp {
+            log.insert("timestamp", timestamp);
+        }
+        log
+    }
+
+    #[test]
+    fn drops_the_stale_events() {
+        let filter = StaleEventsFilter::new(3600);
+        let now = Utc::now();
+
+        let events = EventArray::from(vec![
+            log(Some(now - Duration::hours(2))),
+            log(Some(now - Duration::minutes(30))),
+            log(None),
+        ]);
+        match filter.filter_at(events, now) {
+            Some(EventArray::Logs(logs)) => assert_eq!(logs.len(), 2),
+            events => panic!("unexpected events: {:?}", events),
+        }
+
+        let events = EventArray::from(vec![Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_timestamp(Some(now - Duration::days(1)))]);
+        assert!(filter.filter_at(events, now).is_none());
+    }
+}
@@ -60,6 +60,7 @@ pub(crate) struct Task {
     inner: BoxFuture<'static, TaskResult>,
     key: ComponentKey,
     typetag: String,
+    internal_log_rate_limit: Option<u64>,
 }
 
 impl Task {
@@ -72,9 +73,16 @@ impl Task {
             inner: inner.boxed(),
             key,
             typetag: typetag.into(),
+            internal_log_rate_limit: None,
         }
     }
 
+    /// Sets the rate limit, in seconds, of the internal logs emitted by the task.
+    pub fn with_internal_log_rate_limit(mut self, limit: Option<u64>) -> Self {
+        self.internal_log_rate_limit = limit;
+        self
+    }
+
     pub fn id(&self) -> &str {
         self.key.id()
     }
@@ -82,6 +90,10 @@ impl Task {
     pub fn typetag(&self) -> &str {
         &self.typetag
     }
+
+    pub const fn internal_log_rate_limit(&self) -> Option<u64> {
+        self.internal_log_rate_limit
+    }
 }
 
 impl Future for Task {
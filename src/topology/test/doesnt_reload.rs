@@ -1,9 +1,12 @@
-use std::path::Path;
+use std::{num::NonZeroUsize, path::Path};
+
+use vector_core::config::{ComponentKey, MemoryBudgetConfig};
 
 use crate::{
     config::Config,
+    memory_budget,
     test_util::{
-        mock::{basic_sink, basic_source},
+        mock::{basic_sink, basic_sink_failing_healthcheck, basic_source},
         start_topology, trace_init,
     },
 };
@@ -32,3 +35,74 @@ async fn topology_doesnt_reload_new_data_dir() {
         Some(Path::new("/asdf").to_path_buf())
     );
 }
+
+#[tokio::test]
+async fn topology_doesnt_reload_failing_canary() {
+    trace_init();
+
+    let mut old_config = Config::builder();
+    old_config.add_source("in", basic_source().1);
+    old_config.add_sink("out", &["in"], basic_sink(1).1);
+    let mut new_config = old_config.clone();
+
+    let (mut topology, _) = start_topology(old_config.build().unwrap(), false).await;
+
+    // The healthchecks of the sinks aren't required to pass, but those of a canary are.
+    new_config.canary.enabled = true;
+    new_config.add_sink("unhealthy", &["in"], basic_sink_failing_healthcheck(1).1);
+
+    assert!(!topology
+        .reload_config_and_respawn(new_config.build().unwrap())
+        .await
+        .unwrap());
+    assert!(topology
+        .config
+        .sink(&ComponentKey::from("unhealthy"))
+        .is_none());
+
+    new_config.sinks.remove(&ComponentKey::from("unhealthy"));
+    new_config.add_sink("healthy", &["in"], basic_sink(1).1);
+
+    assert!(topology
+        .reload_config_and_respawn(new_config.build().unwrap())
+        .await
+        .unwrap());
+    assert!(topology
+        .config
+        .sink(&ComponentKey::from("healthy"))
+        .is_some());
+}
+
+#[tokio::test]
+async fn topology_doesnt_apply_memory_budget_of_failing_canary() {
+    trace_init();
+
+    let mut old_config = Config::builder();
+    old_config.add_source("in", basic_source().1);
+    old_config.add_sink("out", &["in"], basic_sink(1).1);
+    old_config.global.memory_budget = Some(MemoryBudgetConfig {
+        max_bytes: NonZeroUsize::new(1 << 20).unwrap(),
+        policy: Default::default(),
+    });
+    let mut new_config = old_config.clone();
+
+    let (mut topology, _) = start_topology(old_config.build().unwrap(), false).await;
+
+    new_config.canary.enabled = true;
+    new_config.global.memory_budget = Some(MemoryBudgetConfig {
+        max_bytes: NonZeroUsize::new(1 << 10).unwrap(),
+        policy: Default::default(),
+    });
+    new_config.add_sink("unhealthy", &["in"], basic_sink_failing_healthcheck(1).1);
+
+    assert!(!topology
+        .reload_config_and_respawn(new_config.build().unwrap())
+        .await
+        .unwrap());
+
+    // Other tests may reset the budget while this one runs, but never to the one of the canary.
+    assert_ne!(
+        memory_budget::budget_usage().map(|usage| usage.max_bytes),
+        Some(1 << 10)
+    );
+}
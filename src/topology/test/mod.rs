@@ -763,6 +763,59 @@ async fn topology_healthcheck_run_for_changes_on_reload() {
     assert!(!topology.reload_config_and_respawn(config).await.unwrap());
 }
 
+#[cfg(feature = "transforms-remap")]
+#[tokio::test]
+async fn topology_rolled_back_reload_keeps_remap_program() {
+    use crate::transforms::remap::RemapConfig;
+
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+    let config = |source: &str, unhealthy_sink: bool| {
+        let mut config = Config::builder();
+        config.add_source("in1", source1.clone());
+        config.add_transform(
+            "t1",
+            &["in1"],
+            RemapConfig {
+                source: Some(source.to_owned()),
+                ..Default::default()
+            },
+        );
+        config.add_sink("out1", &["t1"], sink1.clone());
+        if unhealthy_sink {
+            config.add_sink("out2", &["t1"], basic_sink_failing_healthcheck(10).1);
+        }
+        let mut config = config.build().unwrap();
+        config.healthchecks.require_healthy = true;
+        config
+    };
+
+    let (mut topology, _) = start_topology(config(r#".message = "old""#, false), false).await;
+    let mut out1 = out1.flat_map(into_message_stream);
+
+    // The added sink fails its healthcheck, so the reload is rolled back along with the new
+    // program.
+    assert!(!topology
+        .reload_config_and_respawn(config(r#".message = "new""#, true))
+        .await
+        .unwrap());
+    in1.send_event(LogEvent::from("this")).await.unwrap();
+    assert_eq!(out1.next().await.unwrap(), "old");
+
+    // The running transform can still be reloaded afterwards.
+    assert!(topology
+        .reload_config_and_respawn(config(r#".message = "newer""#, false))
+        .await
+        .unwrap());
+    in1.send_event(LogEvent::from("this")).await.unwrap();
+    assert_eq!(out1.next().await.unwrap(), "newer");
+
+    drop(in1);
+    topology.stop().await;
+}
+
 #[tokio::test]
 async fn topology_disk_buffer_flushes_on_idle() {
     trace_init();
@@ -786,6 +839,7 @@ async fn topology_disk_buffer_flushes_on_idle() {
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: std::num::NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::DropNewest,
+        compression: Default::default(),
     });
     config.add_sink_outer("out1", sink1_outer);
 
@@ -188,6 +188,7 @@ async fn topology_disk_buffer_conflict() {
     old_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        compression: Default::default(),
     });
 
     let mut new_config = old_config.clone();
@@ -195,6 +196,7 @@ async fn topology_disk_buffer_conflict() {
     new_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        compression: Default::default(),
     });
 
     reload_sink_test(
@@ -18,11 +18,16 @@ use tokio::sync::{
     oneshot,
 };
 use tokio_stream::wrappers::BroadcastStream;
-use tracing::{Event, Subscriber};
+use tracing::{
+    field::{Field, Visit},
+    span,
+    subscriber::Interest,
+    Event, Metadata, Subscriber,
+};
 use tracing_limit::RateLimitedLayer;
 use tracing_subscriber::{
-    filter::LevelFilter,
-    layer::{Context, SubscriberExt},
+    filter::{LevelFilter, Targets},
+    layer::{Context, Filter, SubscriberExt},
     registry::LookupSpan,
     util::SubscriberInitExt,
     Layer,
@@ -30,7 +35,7 @@ use tracing_subscriber::{
 pub use tracing_tower::{InstrumentableService, InstrumentedService};
 use value::Value;
 
-use crate::event::LogEvent;
+use crate::{event::LogEvent, internal_events::InternalLogsSuppressed, runtime_options};
 
 /// BUFFER contains all of the internal log events generated by Vector between the initialization of `tracing` and early
 /// buffering being stopped, which occurs once the topology reports as having successfully started.
@@ -58,15 +63,20 @@ fn metrics_layer_enabled() -> bool {
 }
 
 pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64) {
-    let fmt_filter = tracing_subscriber::filter::Targets::from_str(levels).expect(
-        "logging filter targets were not formatted correctly or did not specify a valid level",
-    );
+    let fmt_filter = ComponentLevels {
+        targets: Targets::from_str(levels).expect(
+            "logging filter targets were not formatted correctly or did not specify a valid level",
+        ),
+    };
 
     let metrics_layer =
         metrics_layer_enabled().then(|| MetricsLayer::new().with_filter(LevelFilter::INFO));
 
+    // Suppressed logs are only counted by the broadcast layer, as every rate limited layer sees
+    // the same events.
     let broadcast_layer = RateLimitedLayer::new(BroadcastLayer::new())
         .with_default_limit(internal_log_rate_limit)
+        .with_suppressed_callback(|| emit!(InternalLogsSuppressed))
         .with_filter(fmt_filter.clone());
 
     let subscriber = tracing_subscriber::registry()
@@ -82,6 +92,11 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
         subscriber.with(console_layer)
     };
 
+    // The spans of the internal traces are at the `info` level, whatever the level of the logs.
+    #[cfg(feature = "internal-telemetry-traces")]
+    let subscriber = subscriber
+        .with(crate::internal_telemetry::traces::TracesLayer.with_filter(LevelFilter::INFO));
+
     #[cfg(feature = "allocation-tracing")]
     let subscriber = {
         let allocation_layer = crate::internal_telemetry::allocations::AllocationLayer::new()
@@ -90,6 +105,11 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
         subscriber.with(allocation_layer)
     };
 
+    // The spans of the components are at the `error` level, as for the allocations.
+    #[cfg(all(unix, feature = "api"))]
+    let subscriber =
+        subscriber.with(crate::internal_telemetry::cpu::CpuLayer.with_filter(LevelFilter::ERROR));
+
     if json {
         let formatter = tracing_subscriber::fmt::layer().json().flatten_event(true);
 
@@ -117,6 +137,88 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
     }
 }
 
+/// Filters the logs by the levels given on startup, apart from the events emitted within the span of
+/// a component whose log level was set at runtime, which are filtered by that level instead.
+#[derive(Clone)]
+struct ComponentLevels {
+    targets: Targets,
+}
+
+/// The ID of the component a span belongs to.
+struct ComponentSpanId(String);
+
+#[derive(Default)]
+struct ComponentIdVisitor(Option<String>);
+
+impl Visit for ComponentIdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "component_id" {
+            self.0 = Some(value.to_owned());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "component_id" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl<S> Filter<S> for ComponentLevels
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn enabled(&self, metadata: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        if metadata.is_event() && runtime_options::has_log_levels() {
+            // The level of the innermost component applies.
+            let level = cx.lookup_current().and_then(|span| {
+                span.scope()
+                    .find_map(|span| {
+                        span.extensions()
+                            .get::<ComponentSpanId>()
+                            .map(|id| runtime_options::log_level(&id.0))
+                    })
+                    .flatten()
+            });
+            if let Some(level) = level {
+                return *metadata.level() <= level;
+            }
+        }
+        Filter::<S>::enabled(&self.targets, metadata, cx)
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        // The callsites disabled by the levels given on startup may be enabled for a component.
+        if runtime_options::has_log_levels() {
+            Interest::sometimes()
+        } else {
+            Filter::<S>::callsite_enabled(&self.targets, metadata)
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        let hint = Filter::<S>::max_level_hint(&self.targets)?;
+        Some(runtime_options::max_log_level().map_or(hint, |level| level.max(hint)))
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if attrs.metadata().fields().field("component_id").is_none() {
+            return;
+        }
+
+        let span = ctx.span(id).expect("span must already exist!");
+        // Each layer filtered by the levels sees the span, but only the first records it.
+        if span.extensions().get::<ComponentSpanId>().is_some() {
+            return;
+        }
+        let mut visitor = ComponentIdVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(component_id) = visitor.0 {
+            span.extensions_mut().insert(ComponentSpanId(component_id));
+        }
+    }
+}
+
 #[cfg(test)]
 pub fn reset_early_buffer() -> Option<Vec<LogEvent>> {
     get_early_buffer().replace(Vec::new())
@@ -337,7 +439,7 @@ where
 }
 
 #[derive(Default, Debug)]
-struct SpanFields(HashMap<&'static str, Value>);
+pub(crate) struct SpanFields(pub(crate) HashMap<&'static str, Value>);
 
 impl SpanFields {
     fn record(&mut self, field: &tracing_core::Field, value: impl Into<Value>) {
@@ -1,5 +1,6 @@
 use std::{
     collections::{hash_map::Entry, HashMap},
+    path::PathBuf,
     pin::Pin,
     time::Duration,
 };
@@ -7,14 +8,17 @@ use std::{
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::{config::LogNamespace, ByteSizeOf};
 
 use crate::{
     config::{DataType, Input, Output, TransformConfig, TransformContext},
     event::{metric, Event, EventMetadata},
-    internal_events::{AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed},
+    internal_events::{
+        AggregateEventRecorded, AggregateFlushed, AggregateStateError, AggregateUpdateFailed,
+    },
+    memory_budget::{self, ComponentMemory, MemoryUsage},
     schema,
-    transforms::{TaskTransform, Transform},
+    transforms::{state::StateFile, TaskTransform, Transform},
 };
 
 /// Configuration for the `aggregate` transform.
@@ -27,6 +31,23 @@ pub struct AggregateConfig {
     /// During this time frame, metrics with the same series data (name, namespace, tags, and so on) are aggregated.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+
+    /// Whether to save the aggregated metrics in the data directory, so that they're restored
+    /// after Vector restarts.
+    ///
+    /// The metrics are saved periodically, and after each flush. When the transform stops, the
+    /// metrics aggregated since the last flush are saved instead of being flushed, so that the
+    /// next run of the transform keeps aggregating them.
+    #[serde(default)]
+    pub persist: bool,
+
+    /// The directory used to save the aggregated metrics when `persist` is enabled.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub data_dir: Option<PathBuf>,
 }
 
 const fn default_interval_ms() -> u64 {
@@ -38,8 +59,23 @@ impl_generate_config_from_default!(AggregateConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "aggregate")]
 impl TransformConfig for AggregateConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Aggregate::new(self).map(Transform::event_task)
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let memory = context
+            .key
+            .as_ref()
+            .map(|key| memory_budget::register(key, "transform"));
+        let state = if self.persist && !context.canary {
+            let id = context.key.as_ref().map_or("aggregate", |key| key.id());
+            let data_dir = context
+                .globals
+                .resolve_and_make_data_subdir(self.data_dir.as_ref(), id)?;
+            Some(StateFile::new(data_dir.join(STATE_FILENAME)))
+        } else {
+            None
+        };
+        Aggregate::new(self)
+            .map(|aggregate| aggregate.with_memory(memory).with_state(state))
+            .map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -51,12 +87,22 @@ impl TransformConfig for AggregateConfig {
     }
 }
 
+const STATE_FILENAME: &str = "aggregate_state.json";
+
+/// How often the aggregated metrics are saved when `persist` is enabled, in addition to after each
+/// flush.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
 type MetricEntry = (metric::MetricData, EventMetadata);
 
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
     map: HashMap<metric::MetricSeries, MetricEntry>,
+    /// The approximate bytes of the aggregated metrics, reported as the state of the transform.
+    state_bytes: usize,
+    memory: Option<ComponentMemory>,
+    state: Option<StateFile>,
 }
 
 impl Aggregate {
@@ -64,9 +110,20 @@ impl Aggregate {
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
             map: Default::default(),
+            state_bytes: 0,
+            memory: None,
+            state: None,
         })
     }
 
+    fn with_memory(self, memory: Option<ComponentMemory>) -> Self {
+        Self { memory, ..self }
+    }
+
+    fn with_state(self, state: Option<StateFile>) -> Self {
+        Self { state, ..self }
+    }
+
     fn record(&mut self, event: Event) {
         let (series, data, metadata) = event.into_metric().into_parts();
 
@@ -83,14 +140,24 @@ impl Aggregate {
                     }
                 }
                 Entry::Vacant(entry) => {
+                    self.state_bytes += entry.key().size_of() + data.size_of();
                     entry.insert((data, metadata));
                 }
             },
             metric::MetricKind::Absolute => {
                 // Always replace/store
-                self.map.insert(series, (data, metadata));
+                let series_bytes = series.size_of();
+                let data_bytes = data.size_of();
+                match self.map.insert(series, (data, metadata)) {
+                    Some((previous, _)) => {
+                        self.state_bytes =
+                            self.state_bytes.saturating_sub(previous.size_of()) + data_bytes;
+                    }
+                    None => self.state_bytes += series_bytes + data_bytes,
+                }
             }
         };
+        self.report_state();
 
         emit!(AggregateEventRecorded);
     }
@@ -101,9 +168,59 @@ impl Aggregate {
             let metric = metric::Metric::from_parts(series, entry.0, entry.1);
             output.push(Event::Metric(metric));
         }
+        self.state_bytes = 0;
+        self.report_state();
 
         emit!(AggregateFlushed);
     }
+
+    /// Restores the metrics saved by a previous run of the transform.
+    fn load_state(&mut self) {
+        let state = match self.state.as_ref() {
+            Some(state) => state,
+            None => return,
+        };
+        match state.load::<Vec<metric::Metric>>() {
+            Ok(metrics) => {
+                for metric in metrics {
+                    let (series, data, metadata) = metric.into_parts();
+                    self.state_bytes += series.size_of() + data.size_of();
+                    self.map.insert(series, (data, metadata));
+                }
+                self.report_state();
+            }
+            Err(error) => emit!(AggregateStateError {
+                path: state.path(),
+                error
+            }),
+        }
+    }
+
+    fn save_state(&self) {
+        let state = match self.state.as_ref() {
+            Some(state) => state,
+            None => return,
+        };
+        let metrics = self
+            .map
+            .iter()
+            .map(|(series, (data, _))| {
+                metric::Metric::from_parts(series.clone(), data.clone(), EventMetadata::default())
+            })
+            .collect::<Vec<_>>();
+        if let Err(error) = state.save(&metrics) {
+            emit!(AggregateStateError {
+                path: state.path(),
+                error
+            });
+        }
+    }
+
+    fn report_state(&self) {
+        if let Some(memory) = &self.memory {
+            memory.set(MemoryUsage::State, self.state_bytes);
+        }
+    }
 }
 
 impl TaskTransform<Event> for Aggregate {
@@ -115,19 +232,34 @@ impl TaskTransform<Event> for Aggregate {
         Self: 'static,
     {
         let mut flush_stream = tokio::time::interval(self.interval);
+        let mut save_stream = tokio::time::interval(SAVE_INTERVAL);
 
         Box::pin(stream! {
+            // Held until the transform stops, so that a new instance of the transform doesn't load
+            // the state before this one saved it.
+            let _state_lock = match self.state.as_ref() {
+                Some(state) => Some(state.lock().await),
+                None => None,
+            };
+            self.load_state();
+
             let mut output = Vec::new();
             let mut done = false;
             while !done {
                 tokio::select! {
                     _ = flush_stream.tick() => {
                         self.flush_into(&mut output);
+                        self.save_state();
                     },
+                    _ = save_stream.tick(), if self.state.is_some() => self.save_state(),
                     maybe_event = input_rx.next() => {
                         match maybe_event {
                             None => {
-                                self.flush_into(&mut output);
+                                if self.state.is_some() {
+                                    self.save_state();
+                                } else {
+                                    self.flush_into(&mut output);
+                                }
                                 done = true;
                             }
                             Some(event) => self.record(event),
@@ -152,6 +284,7 @@ mod tests {
 
     use super::*;
     use crate::{
+        config::ComponentKey,
         event::{metric, Event, Metric},
         test_util::components::assert_transform_compliance,
         transforms::test::create_topology,
@@ -170,10 +303,45 @@ mod tests {
         Event::Metric(Metric::new(name, kind, value))
     }
 
+    #[test]
+    fn reports_state_bytes() {
+        let memory = memory_budget::register(&ComponentKey::from("aggregate_state"), "transform");
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            ..Default::default()
+        })
+        .unwrap()
+        .with_memory(Some(memory));
+
+        let gauge = |value| {
+            make_metric(
+                "gauge_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Gauge { value },
+            )
+        };
+        agg.record(gauge(42.0));
+        let state_bytes = agg.state_bytes;
+        assert!(state_bytes > 0);
+
+        // Replacing the value of a series doesn't count the series twice.
+        agg.record(gauge(43.0));
+        assert_eq!(agg.state_bytes, state_bytes);
+
+        agg.flush_into(&mut vec![]);
+        assert_eq!(agg.state_bytes, 0);
+        let usage = memory_budget::component_usage()
+            .into_iter()
+            .find(|usage| usage.component_key.id() == "aggregate_state")
+            .unwrap();
+        assert_eq!(usage.state_bytes, 0);
+    }
+
     #[test]
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -244,6 +412,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -309,6 +478,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -366,6 +536,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -478,6 +649,46 @@ interval_ms = 999999
         assert_eq!(2, count);
     }
 
+    #[tokio::test]
+    async fn transform_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AggregateConfig {
+            interval_ms: 999999,
+            persist: true,
+            data_dir: Some(dir.path().to_owned()),
+        };
+        let agg = config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_task();
+
+        let counter_a = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value },
+            )
+        };
+        let inputs = vec![counter_a(42.0), counter_a(43.0)];
+
+        // The aggregated metrics are saved instead of being flushed when the transform stops.
+        let out = agg
+            .transform_events(Box::pin(stream::iter(inputs)))
+            .collect::<Vec<_>>()
+            .await;
+        assert!(out.is_empty());
+
+        // The next run of the transform keeps aggregating them.
+        let state = StateFile::new(dir.path().join("aggregate").join(STATE_FILENAME));
+        let mut agg = Aggregate::new(&config).unwrap().with_state(Some(state));
+        agg.load_state();
+        agg.record(counter_a(15.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(out, vec![counter_a(100.0)]);
+    }
+
     #[tokio::test]
     async fn transform_interval() {
         let transform_config = toml::from_str::<AggregateConfig>("").unwrap();
@@ -0,0 +1,433 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    num::NonZeroU64,
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
+use ordered_float::NotNan;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{log_schema, DataType, Input, Output, TransformConfig, TransformContext},
+    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent, Value},
+    internal_events::{AggregateLogsEventRecorded, AggregateLogsFlushed},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `aggregate_logs` transform.
+#[configurable_component(transform(
+    "aggregate_logs",
+    "Summarize groups of log events over fixed time windows."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AggregateLogsConfig {
+    /// The length of each window, in milliseconds.
+    ///
+    /// At the end of every window, a single summarizing event is emitted for each group of events
+    /// received during the window.
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: NonZeroU64,
+
+    /// An ordered list of fields by which to group events.
+    ///
+    /// Each group with matching values for the specified keys is summarized independently. When no
+    /// fields are specified, all events received during a window are summarized together.
+    ///
+    /// The values of these fields are copied to the summarizing event.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "service", docs::examples = "status"))]
+    pub group_by: Vec<String>,
+
+    /// A map of numeric field names to the functions used to aggregate their values.
+    ///
+    /// In the summarizing event, each field is replaced by an object containing the result of
+    /// each function, such as `{ "sum": 12, "max": 5 }`. Values that aren't numbers are ignored.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The aggregation functions for the field."
+    ))]
+    pub aggregations: IndexMap<String, Vec<AggregationFunction>>,
+
+    /// The maximum number of messages to keep as a sample in each summarizing event.
+    ///
+    /// The first messages received during the window are kept, under the `messages` field. When
+    /// set to `0`, no messages are sampled.
+    #[serde(default = "default_max_message_samples")]
+    pub max_message_samples: usize,
+}
+
+/// Functions for aggregating numeric values.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationFunction {
+    /// The sum of all values.
+    Sum,
+
+    /// The minimum value.
+    Min,
+
+    /// The maximum value.
+    Max,
+
+    /// The arithmetic mean of all values.
+    Mean,
+}
+
+impl AggregationFunction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Mean => "mean",
+        }
+    }
+}
+
+fn default_interval_ms() -> NonZeroU64 {
+    NonZeroU64::new(10 * 1000).expect("static non-zero number")
+}
+
+const fn default_max_message_samples() -> usize {
+    5
+}
+
+impl Default for AggregateLogsConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: default_interval_ms(),
+            group_by: Vec::new(),
+            aggregations: IndexMap::new(),
+            max_message_samples: default_max_message_samples(),
+        }
+    }
+}
+
+impl_generate_config_from_default!(AggregateLogsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aggregate_logs")]
+impl TransformConfig for AggregateLogsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::event_task(AggregateLogs::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, input: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        // The shape of the summarizing events depends on the grouped and aggregated fields.
+        let schema_definition = schema::Definition::default_for_namespace(input.log_namespaces());
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+}
+
+/// The running aggregation of the values of a numeric field.
+#[derive(Debug, Default)]
+struct FieldAggregation {
+    count: u64,
+    sum: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl FieldAggregation {
+    fn record(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+
+    fn result(&self, function: AggregationFunction) -> Value {
+        let value = match function {
+            AggregationFunction::Sum => Some(self.sum),
+            AggregationFunction::Min => self.min,
+            AggregationFunction::Max => self.max,
+            AggregationFunction::Mean => (self.count > 0).then_some(self.sum / self.count as f64),
+        };
+
+        value
+            .and_then(|value| NotNan::new(value).ok())
+            .map_or(Value::Null, Value::Float)
+    }
+}
+
+/// The summary of a group of events received during the current window.
+#[derive(Debug)]
+struct Group {
+    fields: LogEvent,
+    count: u64,
+    aggregations: Vec<FieldAggregation>,
+    messages: Vec<Value>,
+}
+
+#[derive(Debug)]
+pub struct AggregateLogs {
+    interval: Duration,
+    group_by: Vec<String>,
+    aggregations: IndexMap<String, Vec<AggregationFunction>>,
+    max_message_samples: usize,
+    groups: HashMap<Discriminant, Group>,
+}
+
+impl AggregateLogs {
+    pub fn new(config: &AggregateLogsConfig) -> Self {
+        Self {
+            interval: Duration::from_millis(config.interval_ms.get()),
+            group_by: config.group_by.clone(),
+            aggregations: config.aggregations.clone(),
+            max_message_samples: config.max_message_samples,
+            groups: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, event: Event) {
+        let (value, metadata) = event.into_log().into_parts();
+        let event = LogEvent::from_parts(value, EventMetadata::default());
+        let discriminant = Discriminant::from_log_event(&event, &self.group_by);
+
+        let group = match self.groups.entry(discriminant) {
+            Entry::Occupied(entry) => {
+                let group = entry.into_mut();
+                group.fields.metadata_mut().merge(metadata);
+                group
+            }
+            Entry::Vacant(entry) => {
+                let mut fields = LogEvent::from_parts(Value::Object(Default::default()), metadata);
+                for field in &self.group_by {
+                    if let Some(value) = event.get(field.as_str()) {
+                        fields.insert(field.as_str(), value.clone());
+                    }
+                }
+
+                entry.insert(Group {
+                    fields,
+                    count: 0,
+                    aggregations: self
+                        .aggregations
+                        .keys()
+                        .map(|_| FieldAggregation::default())
+                        .collect(),
+                    messages: Vec::new(),
+                })
+            }
+        };
+
+        group.count += 1;
+
+        for (field, aggregation) in self.aggregations.keys().zip(&mut group.aggregations) {
+            match event.get(field.as_str()) {
+                Some(Value::Integer(value)) => aggregation.record(*value as f64),
+                Some(Value::Float(value)) => aggregation.record(value.into_inner()),
+                _ => {}
+            }
+        }
+
+        if group.messages.len() < self.max_message_samples {
+            if let Some(message) = event.get(log_schema().message_key()) {
+                group.messages.push(message.clone());
+            }
+        }
+
+        emit!(AggregateLogsEventRecorded);
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        if self.groups.is_empty() {
+            return;
+        }
+
+        let timestamp = Utc::now();
+        for (_, group) in self.groups.drain() {
+            let mut log = group.fields;
+
+            log.insert("count", group.count);
+            for ((field, functions), aggregation) in
+                self.aggregations.iter().zip(&group.aggregations)
+            {
+                let results = functions
+                    .iter()
+                    .map(|function| (function.as_str().to_owned(), aggregation.result(*function)))
+                    .collect::<Value>();
+                log.insert(field.as_str(), results);
+            }
+
+            if self.max_message_samples > 0 {
+                log.insert("messages", group.messages);
+            }
+
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                log.insert((lookup::PathPrefix::Event, timestamp_key), timestamp);
+            }
+
+            output.push(Event::Log(log));
+        }
+
+        emit!(AggregateLogsFlushed);
+    }
+}
+
+impl TaskTransform<Event> for AggregateLogs {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.interval);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AggregateLogsConfig>();
+    }
+
+    fn log(service: &str, duration: impl Into<Value>, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("service", service);
+        log.insert("duration", duration);
+        log.into()
+    }
+
+    fn config() -> AggregateLogsConfig {
+        toml::from_str(
+            r#"
+            group_by = ["service"]
+            max_message_samples = 2
+
+            [aggregations]
+            duration = ["sum", "min", "max", "mean"]
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rejects_empty_windows() {
+        assert!(toml::from_str::<AggregateLogsConfig>("interval_ms = 0").is_err());
+    }
+
+    #[test]
+    fn summarizes_groups() {
+        let mut aggregate = AggregateLogs::new(&config());
+
+        aggregate.record(log("api", 1, "first"));
+        aggregate.record(log("api", 2.5, "second"));
+        aggregate.record(log("api", "slow", "third"));
+        aggregate.record(log("web", 4, "fourth"));
+
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output.len(), 2);
+
+        let api = output
+            .iter()
+            .map(Event::as_log)
+            .find(|log| log["service"] == "api".into())
+            .unwrap();
+        assert_eq!(api["count"], 3.into());
+        assert_eq!(api["duration.sum"], 3.5.into());
+        assert_eq!(api["duration.min"], 1.0.into());
+        assert_eq!(api["duration.max"], 2.5.into());
+        assert_eq!(api["duration.mean"], 1.75.into());
+        assert_eq!(
+            api["messages"],
+            Value::Array(vec!["first".into(), "second".into()])
+        );
+        assert!(api.get("timestamp").is_some());
+
+        let web = output
+            .iter()
+            .map(Event::as_log)
+            .find(|log| log["service"] == "web".into())
+            .unwrap();
+        assert_eq!(web["count"], 1.into());
+        assert_eq!(web["duration.sum"], 4.0.into());
+
+        // Each window starts from scratch.
+        output.clear();
+        aggregate.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn missing_values_aggregate_to_null() {
+        let mut aggregate = AggregateLogs::new(&config());
+        aggregate.record(log("api", "n/a", "first"));
+
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        let log = output[0].as_log();
+        assert_eq!(log["duration.sum"], 0.0.into());
+        assert_eq!(log["duration.min"], Value::Null);
+        assert_eq!(log["duration.mean"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn emits_summaries_on_shutdown() {
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config()).await;
+
+            tx.send(log("api", 1, "first")).await.unwrap();
+            tx.send(log("api", 2, "second")).await.unwrap();
+            drop(tx);
+
+            let event = out.recv().await.unwrap();
+            assert_eq!(event.as_log()["count"], 2.into());
+            assert_eq!(event.as_log()["duration.sum"], 3.0.into());
+
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
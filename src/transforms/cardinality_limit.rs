@@ -0,0 +1,517 @@
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde_with::serde_as;
+use snafu::Snafu;
+use tokio::time::Instant;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{
+        metric::{MetricName, MetricTags, TagValueSet},
+        Event,
+    },
+    internal_events::{
+        CardinalityLimitReached, CardinalityLimitSeriesAggregated, CardinalityLimitSeriesDropped,
+        CardinalityLimitTagsStripped, CardinalityLimitTrackedSeries,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The tag set on the series new series are aggregated into.
+const OVERFLOW_TAG: &str = "overflow";
+
+/// Configuration for the `cardinality_limit` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "cardinality_limit",
+    "Limit the number of series of each metric as a safeguard against cardinality explosion."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CardinalityLimitConfig {
+    /// How many distinct series to accept for any given metric name.
+    ///
+    /// The series of a metric are told apart by their tags.
+    #[serde(default = "default_series_limit")]
+    pub series_limit: u32,
+
+    /// The time after which a series that was not seen again stops counting against the
+    /// `series_limit`, in seconds.
+    #[serde(default = "default_window_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    pub window_secs: Duration,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub limit_exceeded_action: LimitExceededAction,
+}
+
+/// Possible actions to take when an event arrives that would exceed the series limit of its
+/// metric.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitExceededAction {
+    /// Drop the event of the new series.
+    #[default]
+    DropSeries,
+
+    /// Replace the tags of the new series with a single `overflow` tag, aggregating all the new
+    /// series of the metric into one.
+    Aggregate,
+
+    /// Strip the tags of the new series until it matches an accepted series, starting with the tags
+    /// having the most distinct values for the metric.
+    ///
+    /// A series left without tags is accepted even though it is new.
+    StripTags,
+}
+
+const fn default_series_limit() -> u32 {
+    1000
+}
+
+const fn default_window_secs() -> Duration {
+    Duration::from_secs(3600)
+}
+
+impl GenerateConfig for CardinalityLimitConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            series_limit: default_series_limit(),
+            window_secs: default_window_secs(),
+            limit_exceeded_action: LimitExceededAction::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "cardinality_limit")]
+impl TransformConfig for CardinalityLimitConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        CardinalityLimit::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display("`series_limit`, and `window_secs` must be non-zero"))]
+    NonZero,
+}
+
+/// The series accepted for a metric.
+#[derive(Debug, Default)]
+struct AcceptedSeries {
+    /// The time each series was last seen at.
+    series: HashMap<MetricTags, Instant>,
+
+    /// The number of distinct values of each tag key among `series`, computed when tags need to
+    /// be stripped and kept until `series` change.
+    tag_cardinality: Option<HashMap<String, usize>>,
+
+    /// Whether the limit was reached, so that it is only reported once until series expire.
+    limited: bool,
+}
+
+impl AcceptedSeries {
+    fn insert(&mut self, tags: MetricTags, now: Instant) {
+        if self.series.insert(tags, now).is_none() {
+            self.tag_cardinality = None;
+        }
+    }
+
+    /// Strips tags from `tags` until they match an accepted series, or none are left, and returns
+    /// the keys of the stripped tags.
+    ///
+    /// The tags that no accepted series has are stripped first, followed by the tags with the
+    /// most distinct values.
+    fn strip_tags(&mut self, tags: &mut MetricTags) -> Vec<String> {
+        let series = &self.series;
+        let cardinality = self
+            .tag_cardinality
+            .get_or_insert_with(|| tag_cardinality(series));
+
+        let mut stripped = Vec::new();
+        while !tags.is_empty() && !series.contains_key(tags) {
+            let key = tags
+                .keys()
+                .max_by_key(|key| cardinality.get(*key).copied().unwrap_or(usize::MAX))
+                .expect("tags are not empty")
+                .to_owned();
+            tags.remove(&key);
+            stripped.push(key);
+        }
+        stripped
+    }
+}
+
+fn tag_cardinality(series: &HashMap<MetricTags, Instant>) -> HashMap<String, usize> {
+    let mut values: HashMap<&str, HashSet<&TagValueSet>> = HashMap::new();
+    for tags in series.keys() {
+        for (key, value) in tags.iter_sets() {
+            values.entry(key).or_default().insert(value);
+        }
+    }
+    values
+        .into_iter()
+        .map(|(key, values)| (key.to_owned(), values.len()))
+        .collect()
+}
+
+/// Formats the name of a metric like the series are displayed, for reporting.
+fn display_name(name: &MetricName) -> String {
+    match &name.namespace {
+        Some(namespace) => format!("{}_{}", namespace, name.name),
+        None => name.name.clone(),
+    }
+}
+
+#[derive(Debug)]
+pub struct CardinalityLimit {
+    series_limit: u32,
+    window: Duration,
+    limit_exceeded_action: LimitExceededAction,
+    metrics: HashMap<MetricName, AcceptedSeries>,
+}
+
+impl CardinalityLimit {
+    pub fn new(config: &CardinalityLimitConfig) -> crate::Result<Self> {
+        if config.series_limit == 0 || config.window_secs.is_zero() {
+            return Err(Box::new(ConfigError::NonZero));
+        }
+
+        Ok(Self {
+            series_limit: config.series_limit,
+            window: config.window_secs,
+            limit_exceeded_action: config.limit_exceeded_action,
+            metrics: HashMap::new(),
+        })
+    }
+
+    fn transform_one(&mut self, mut event: Event, now: Instant) -> Option<Event> {
+        let metric = event.as_mut_metric();
+        let name = metric.series().name();
+        if !self.metrics.contains_key(name) {
+            self.metrics.insert(name.clone(), AcceptedSeries::default());
+        }
+        let accepted = self
+            .metrics
+            .get_mut(name)
+            .expect("metric was just inserted");
+
+        let mut tags = metric.tags().cloned().unwrap_or_default();
+        if let Some(last_seen) = accepted.series.get_mut(&tags) {
+            *last_seen = now;
+            return Some(event);
+        }
+
+        let limit = self.series_limit as usize;
+        if accepted.series.len() < limit {
+            accepted.insert(tags, now);
+            if accepted.series.len() == limit && !accepted.limited {
+                accepted.limited = true;
+                emit!(CardinalityLimitReached {
+                    metric_name: &display_name(name),
+                    series_limit: self.series_limit,
+                });
+            }
+            return Some(event);
+        }
+
+        let metric_name = display_name(name);
+        match self.limit_exceeded_action {
+            LimitExceededAction::DropSeries => {
+                emit!(CardinalityLimitSeriesDropped {
+                    metric_name: &metric_name
+                });
+                return None;
+            }
+            LimitExceededAction::Aggregate => {
+                emit!(CardinalityLimitSeriesAggregated {
+                    metric_name: &metric_name
+                });
+                let overflow = MetricTags::from([(OVERFLOW_TAG.to_owned(), "true".to_owned())]);
+                accepted.insert(overflow, now);
+                metric.remove_tags();
+                metric.replace_tag(OVERFLOW_TAG.to_owned(), "true".to_owned());
+            }
+            LimitExceededAction::StripTags => {
+                let stripped = accepted.strip_tags(&mut tags);
+                emit!(CardinalityLimitTagsStripped {
+                    metric_name: &metric_name,
+                    tag_keys: &stripped,
+                });
+                accepted.insert(tags, now);
+                for key in &stripped {
+                    metric.remove_tag(key);
+                }
+            }
+        }
+        Some(event)
+    }
+
+    /// Forgets the series that were not seen within the window.
+    fn expire(&mut self, now: Instant) {
+        let window = self.window;
+        let limit = self.series_limit as usize;
+        self.metrics.retain(|_, accepted| {
+            let count = accepted.series.len();
+            accepted
+                .series
+                .retain(|_, last_seen| now.duration_since(*last_seen) < window);
+            if accepted.series.len() != count {
+                accepted.tag_cardinality = None;
+            }
+            if accepted.series.len() < limit {
+                accepted.limited = false;
+            }
+            !accepted.series.is_empty()
+        });
+
+        emit!(CardinalityLimitTrackedSeries {
+            count: self
+                .metrics
+                .values()
+                .map(|accepted| accepted.series.len())
+                .sum(),
+        });
+    }
+}
+
+impl TaskTransform<Event> for CardinalityLimit {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut inner = self;
+        // Checking a few times per window keeps expired series from counting against the limit
+        // for long.
+        let mut expire = tokio::time::interval((inner.window / 10).max(Duration::from_millis(100)));
+
+        Box::pin(stream! {
+            loop {
+                tokio::select! {
+                    maybe_event = input_rx.next() => match maybe_event {
+                        None => break,
+                        Some(event) => {
+                            if let Some(event) = inner.transform_one(event, Instant::now()) {
+                                yield event;
+                            }
+                        }
+                    },
+                    _ = expire.tick() => inner.expire(Instant::now()),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use vector_core::metric_tags;
+
+    use super::*;
+    use crate::{
+        event::{Metric, MetricKind, MetricValue},
+        test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    fn make_metric(tags: MetricTags) -> Event {
+        Event::Metric(
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(tags.as_option()),
+        )
+    }
+
+    fn make_transform(
+        series_limit: u32,
+        limit_exceeded_action: LimitExceededAction,
+    ) -> CardinalityLimit {
+        CardinalityLimit::new(&CardinalityLimitConfig {
+            series_limit,
+            window_secs: Duration::from_secs(60),
+            limit_exceeded_action,
+        })
+        .unwrap()
+    }
+
+    fn tags(event: &Event) -> MetricTags {
+        event.as_metric().tags().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CardinalityLimitConfig>();
+    }
+
+    #[test]
+    fn rejects_a_zero_limit() {
+        assert!(CardinalityLimit::new(&CardinalityLimitConfig {
+            series_limit: 0,
+            window_secs: default_window_secs(),
+            limit_exceeded_action: LimitExceededAction::DropSeries,
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn drops_new_series_above_the_limit() {
+        assert_transform_compliance(async move {
+            let config = CardinalityLimitConfig {
+                series_limit: 2,
+                window_secs: default_window_secs(),
+                limit_exceeded_action: LimitExceededAction::DropSeries,
+            };
+            let event1 = make_metric(metric_tags!("host" => "a"));
+            let event2 = make_metric(metric_tags!("host" => "b"));
+            let event3 = make_metric(metric_tags!("host" => "c"));
+
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(event1.clone()).await.unwrap();
+            tx.send(event2.clone()).await.unwrap();
+            tx.send(event3).await.unwrap();
+            // Known series are still accepted.
+            tx.send(event1.clone()).await.unwrap();
+
+            assert_eq!(out.recv().await, Some(event1.clone()));
+            assert_eq!(out.recv().await, Some(event2));
+            assert_eq!(out.recv().await, Some(event1));
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[test]
+    fn limits_each_metric_separately() {
+        let mut transform = make_transform(1, LimitExceededAction::DropSeries);
+        let now = Instant::now();
+
+        let event = make_metric(metric_tags!("host" => "a"));
+        let other = Event::Metric(
+            Metric::new(
+                "errors",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(metric_tags!("host" => "b"))),
+        );
+
+        assert!(transform.transform_one(event, now).is_some());
+        assert!(transform.transform_one(other, now).is_some());
+        assert!(transform
+            .transform_one(make_metric(metric_tags!("host" => "b")), now)
+            .is_none());
+    }
+
+    #[test]
+    fn aggregates_new_series_into_the_overflow_series() {
+        let mut transform = make_transform(1, LimitExceededAction::Aggregate);
+        let now = Instant::now();
+
+        let event = transform
+            .transform_one(make_metric(metric_tags!("host" => "a")), now)
+            .unwrap();
+        assert_eq!(tags(&event), metric_tags!("host" => "a"));
+
+        for host in ["b", "c"] {
+            let event = transform
+                .transform_one(make_metric(metric_tags!("host" => host)), now)
+                .unwrap();
+            assert_eq!(tags(&event), metric_tags!("overflow" => "true"));
+        }
+    }
+
+    #[test]
+    fn strips_the_tags_with_the_most_values() {
+        let mut transform = make_transform(3, LimitExceededAction::StripTags);
+        let now = Instant::now();
+
+        for (host, request) in [("a", "1"), ("a", "2"), ("b", "3")] {
+            transform
+                .transform_one(
+                    make_metric(metric_tags!("host" => host, "request_id" => request)),
+                    now,
+                )
+                .unwrap();
+        }
+
+        // `request_id` has more values than `host`, so it is stripped first, and stripping it is
+        // not enough to match an accepted series.
+        let event = transform
+            .transform_one(
+                make_metric(metric_tags!("host" => "b", "request_id" => "4")),
+                now,
+            )
+            .unwrap();
+        assert_eq!(tags(&event), MetricTags::default());
+
+        // Tags that no accepted series has are stripped first.
+        let event = transform
+            .transform_one(make_metric(metric_tags!("host" => "c", "user" => "x")), now)
+            .unwrap();
+        assert_eq!(tags(&event), MetricTags::default());
+
+        // The series without tags is now accepted.
+        let event = transform
+            .transform_one(make_metric(metric_tags!("host" => "c")), now)
+            .unwrap();
+        assert_eq!(tags(&event), MetricTags::default());
+    }
+
+    #[test]
+    fn expired_series_stop_counting_against_the_limit() {
+        let mut transform = make_transform(1, LimitExceededAction::DropSeries);
+        let now = Instant::now();
+
+        assert!(transform
+            .transform_one(make_metric(metric_tags!("host" => "a")), now)
+            .is_some());
+        assert!(transform
+            .transform_one(make_metric(metric_tags!("host" => "b")), now)
+            .is_none());
+
+        transform.expire(now + Duration::from_secs(30));
+        assert!(transform
+            .transform_one(make_metric(metric_tags!("host" => "b")), now)
+            .is_none());
+
+        transform.expire(now + Duration::from_secs(60));
+        assert!(transform.metrics.is_empty());
+        assert!(transform
+            .transform_one(make_metric(metric_tags!("host" => "b")), now)
+            .is_some());
+    }
+}
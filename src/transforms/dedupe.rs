@@ -1,8 +1,19 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin};
+use std::{
+    future::ready,
+    hash::Hasher,
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::Duration,
+};
 
+use async_stream::stream;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use futures::{Stream, StreamExt};
 use lru::LruCache;
+use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -11,7 +22,9 @@ use crate::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
     },
     event::{Event, Value},
-    internal_events::DedupeEventsDropped,
+    internal_events::{
+        DedupeCacheHit, DedupeCacheMiss, DedupeCacheStateError, DedupeEventsDropped,
+    },
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -65,6 +78,31 @@ pub enum FieldMatchConfig {
 pub struct CacheConfig {
     /// Number of events to cache and use for comparing incoming events to previously seen events.
     pub num_events: NonZeroUsize,
+
+    /// The number of seconds an event is remembered for, since it was last seen.
+    ///
+    /// Events seen again after that time are no longer considered duplicates. By default, events
+    /// are remembered until they are evicted from the cache to make room for newer ones.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 3600))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub time_window_secs: Option<NonZeroU64>,
+
+    /// Whether to save the cache in the data directory, so that duplicates are still dropped after
+    /// Vector restarts.
+    ///
+    /// When enabled, the cache only holds a hash of the matched fields of each event, instead of
+    /// the fields themselves. The cache is saved periodically, and when the transform stops.
+    #[serde(default)]
+    pub persist: bool,
+
+    /// The directory used to save the cache when `persist` is enabled.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub data_dir: Option<PathBuf>,
 }
 
 /// Configuration for the `dedupe` transform.
@@ -84,6 +122,9 @@ pub struct DedupeConfig {
 fn default_cache_config() -> CacheConfig {
     CacheConfig {
         num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+        time_window_secs: None,
+        persist: false,
+        data_dir: None,
     }
 }
 
@@ -128,7 +169,10 @@ impl DedupeConfig {
 
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: LruCache<CacheKey, DateTime<Utc>>,
+    time_window: Option<Duration>,
+    hash_entries: bool,
+    state_path: Option<PathBuf>,
 }
 
 impl GenerateConfig for DedupeConfig {
@@ -144,8 +188,16 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let mut dedupe = Dedupe::new(self.clone());
+        if self.cache.persist {
+            let id = context.key.as_ref().map_or("dedupe", |key| key.id());
+            let data_dir = context
+                .globals
+                .resolve_and_make_data_subdir(self.cache.data_dir.as_ref(), id)?;
+            dedupe.load_state(data_dir.join(STATE_FILENAME)).await;
+        }
+        Ok(Transform::event_task(dedupe))
     }
 
     fn input(&self) -> Input {
@@ -157,6 +209,11 @@ impl TransformConfig for DedupeConfig {
     }
 }
 
+const STATE_FILENAME: &str = "dedupe_cache.json";
+
+/// How often the cache is saved when `persist` is enabled, in addition to when the transform stops.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
 type TypeId = u8;
 
 /// A CacheEntry comes in two forms, depending on the FieldMatchConfig in use.
@@ -188,6 +245,71 @@ enum CacheEntry {
     Ignore(Vec<(String, TypeId, Bytes)>),
 }
 
+/// The key events are cached under.
+///
+/// When the cache is persisted, only a hash of the CacheEntry is kept, both to bound the size of
+/// the saved state and to avoid writing the contents of events to disk. The hash is computed with
+/// a fixed seed from the bytes of the entry, so that it stays the same across restarts and
+/// upgrades.
+#[derive(PartialEq, Eq, Hash)]
+enum CacheKey {
+    Entry(CacheEntry),
+    Hash(u64),
+}
+
+impl CacheKey {
+    fn new(entry: CacheEntry, hash_entries: bool) -> Self {
+        if hash_entries {
+            Self::Hash(entry.stable_hash())
+        } else {
+            Self::Entry(entry)
+        }
+    }
+}
+
+impl CacheEntry {
+    /// Hashes the bytes of the entry, rather than going through its `Hash` implementation, whose
+    /// output isn't guaranteed to stay the same across releases of Rust.
+    fn stable_hash(&self) -> u64 {
+        fn write_bytes(hasher: &mut seahash::SeaHasher, bytes: &[u8]) {
+            hasher.write(&(bytes.len() as u64).to_le_bytes());
+            hasher.write(bytes);
+        }
+
+        let mut hasher = seahash::SeaHasher::new();
+        match self {
+            Self::Match(fields) => {
+                hasher.write(&[0]);
+                for field in fields {
+                    match field {
+                        Some((type_id, bytes)) => {
+                            hasher.write(&[1, *type_id]);
+                            write_bytes(&mut hasher, bytes);
+                        }
+                        None => hasher.write(&[0]),
+                    }
+                }
+            }
+            Self::Ignore(fields) => {
+                hasher.write(&[1]);
+                for (name, type_id, bytes) in fields {
+                    write_bytes(&mut hasher, name.as_bytes());
+                    hasher.write(&[*type_id]);
+                    write_bytes(&mut hasher, bytes);
+                }
+            }
+        }
+        hasher.finish()
+    }
+}
+
+/// A cached hash, as saved in the data directory.
+#[derive(Debug, Deserialize, Serialize)]
+struct CacheRecord {
+    hash: u64,
+    last_seen: DateTime<Utc>,
+}
+
 /// Assigns a unique number to each of the types supported by Event::Value.
 const fn type_id_for_value(val: &Value) -> TypeId {
     match val {
@@ -210,18 +332,103 @@ impl Dedupe {
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            time_window: config
+                .cache
+                .time_window_secs
+                .map(|secs| Duration::from_secs(secs.get())),
+            hash_entries: config.cache.persist,
+            state_path: None,
         }
     }
 
     fn transform_one(&mut self, event: Event) -> Option<Event> {
-        let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        let cache_key = CacheKey::new(build_cache_entry(&event, &self.fields), self.hash_entries);
+        let now = Utc::now();
+        let duplicate = self
+            .cache
+            .put(cache_key, now)
+            .map_or(false, |last_seen| self.within_time_window(last_seen, now));
+        if duplicate {
+            emit!(DedupeCacheHit);
             emit!(DedupeEventsDropped { count: 1 });
             None
         } else {
+            emit!(DedupeCacheMiss);
             Some(event)
         }
     }
+
+    fn within_time_window(&self, last_seen: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.time_window.map_or(true, |window| {
+            // A clock going backwards keeps the event within the window.
+            (now - last_seen)
+                .to_std()
+                .map_or(true, |elapsed| elapsed < window)
+        })
+    }
+
+    /// Fills the cache with the state saved at `path` by a previous run, then saves the cache
+    /// there from now on.
+    async fn load_state(&mut self, path: PathBuf) {
+        match read_state(&path).await {
+            Ok(records) => {
+                let now = Utc::now();
+                // Records are saved from the least to the most recently seen, so that inserting
+                // them in order restores the order of the cache.
+                for record in records {
+                    if self.within_time_window(record.last_seen, now) {
+                        self.cache
+                            .put(CacheKey::Hash(record.hash), record.last_seen);
+                    }
+                }
+            }
+            Err(error) => emit!(DedupeCacheStateError { path: &path, error }),
+        }
+        self.state_path = Some(path);
+    }
+
+    async fn save_state(&self) {
+        let path = match self.state_path.as_ref() {
+            Some(path) => path,
+            None => return,
+        };
+        let records = self
+            .cache
+            .iter()
+            .rev()
+            .filter_map(|(key, last_seen)| match key {
+                CacheKey::Hash(hash) => Some(CacheRecord {
+                    hash: *hash,
+                    last_seen: *last_seen,
+                }),
+                CacheKey::Entry(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let result = match serde_json::to_vec(&records) {
+            Ok(contents) => write_state(path, contents).await,
+            Err(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+        if let Err(error) = result {
+            emit!(DedupeCacheStateError { path, error });
+        }
+    }
+}
+
+async fn read_state(path: &Path) -> io::Result<Vec<CacheRecord>> {
+    match tokio::fs::read(path).await {
+        Ok(contents) => serde_json::from_slice(&contents)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+async fn write_state(path: &Path, contents: Vec<u8>) -> io::Result<()> {
+    // Write to a temporary file first so a crash never leaves a truncated state file behind.
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(&tmp_path, path).await
 }
 
 /// Takes in an Event and returns a CacheEntry to place into the LRU cache
@@ -269,7 +476,28 @@ impl TaskTransform<Event> for Dedupe {
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        if inner.state_path.is_none() {
+            return Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))));
+        }
+
+        let mut input = task;
+        let mut save = tokio::time::interval(SAVE_INTERVAL);
+        Box::pin(stream! {
+            loop {
+                tokio::select! {
+                    maybe_event = input.next() => match maybe_event {
+                        Some(event) => {
+                            if let Some(event) = inner.transform_one(event) {
+                                yield event;
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = save.tick() => inner.save_state().await,
+                }
+            }
+            inner.save_state().await;
+        })
     }
 }
 
@@ -284,7 +512,10 @@ mod tests {
         event::{Event, LogEvent, Value},
         test_util::components::assert_transform_compliance,
         transforms::{
-            dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            dedupe::{
+                build_cache_entry, CacheConfig, CacheKey, Dedupe, DedupeConfig, FieldMatchConfig,
+                STATE_FILENAME,
+            },
             test::create_topology,
         },
     };
@@ -298,6 +529,9 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                time_window_secs: None,
+                persist: false,
+                data_dir: None,
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
         }
@@ -311,6 +545,9 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                time_window_secs: None,
+                persist: false,
+                data_dir: None,
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
         }
@@ -645,4 +882,50 @@ mod tests {
         })
         .await;
     }
+
+    #[test]
+    fn dedupe_time_window() {
+        let mut config = make_match_transform_config(5, vec!["matched".into()]);
+        config.cache.time_window_secs = std::num::NonZeroU64::new(60);
+        let mut dedupe = Dedupe::new(config);
+
+        let mut event = Event::Log(LogEvent::from("message"));
+        event.as_mut_log().insert("matched", "some value");
+
+        assert!(dedupe.transform_one(event.clone()).is_some());
+        assert!(dedupe.transform_one(event.clone()).is_none());
+
+        // Once the event hasn't been seen for longer than the window, it is let through again.
+        let key = CacheKey::new(build_cache_entry(&event, &dedupe.fields), false);
+        dedupe
+            .cache
+            .put(key, chrono::Utc::now() - chrono::Duration::minutes(2));
+        assert!(dedupe.transform_one(event.clone()).is_some());
+        assert!(dedupe.transform_one(event).is_none());
+    }
+
+    #[tokio::test]
+    async fn dedupe_persists_cache() {
+        let data_dir = crate::test_util::temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let path = data_dir.join(STATE_FILENAME);
+        let mut config = make_match_transform_config(5, vec!["matched".into()]);
+        config.cache.persist = true;
+
+        let mut event1 = Event::Log(LogEvent::from("message"));
+        event1.as_mut_log().insert("matched", "some value");
+        let mut event2 = Event::Log(LogEvent::from("message"));
+        event2.as_mut_log().insert("matched", "some value2");
+
+        let mut dedupe = Dedupe::new(config.clone());
+        dedupe.load_state(path.clone()).await;
+        assert!(dedupe.transform_one(event1.clone()).is_some());
+        dedupe.save_state().await;
+
+        // A new instance, as after a restart, still drops the events seen before.
+        let mut dedupe = Dedupe::new(config);
+        dedupe.load_state(path).await;
+        assert!(dedupe.transform_one(event1).is_none());
+        assert!(dedupe.transform_one(event2).is_some());
+    }
 }
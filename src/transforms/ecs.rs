@@ -0,0 +1,522 @@
+//! The `ecs` transform.
+//!
+//! Moves the fields set by the sources to their counterparts in the [Elastic Common Schema][ecs],
+//! so the events can be shipped to Elasticsearch and used by Elastic Security as is.
+//!
+//! [ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+use indexmap::IndexMap;
+use lookup::lookup_v2::{parse_value_path, ConfigTargetPath, OwnedSegment, OwnedTargetPath};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::EcsUnmappedFields,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The version of the Elastic Common Schema the events are normalized to.
+const ECS_VERSION: &str = "8.6.0";
+
+/// The top-level fields of the Elastic Common Schema: its base fields and field sets.
+///
+/// `kubernetes` isn't part of the schema itself, but is the field set used by the Elastic
+/// integrations for the Kubernetes metadata. Kept sorted, for the binary search.
+const ECS_FIELDS: &[&str] = &[
+    "@timestamp",
+    "agent",
+    "as",
+    "client",
+    "cloud",
+    "code_signature",
+    "container",
+    "data_stream",
+    "destination",
+    "device",
+    "dll",
+    "dns",
+    "ecs",
+    "elf",
+    "email",
+    "error",
+    "event",
+    "faas",
+    "file",
+    "geo",
+    "group",
+    "hash",
+    "host",
+    "http",
+    "interface",
+    "kubernetes",
+    "labels",
+    "log",
+    "macho",
+    "message",
+    "network",
+    "observer",
+    "orchestrator",
+    "organization",
+    "os",
+    "package",
+    "pe",
+    "process",
+    "registry",
+    "related",
+    "risk",
+    "rule",
+    "server",
+    "service",
+    "source",
+    "span",
+    "tags",
+    "threat",
+    "tls",
+    "trace",
+    "transaction",
+    "url",
+    "user",
+    "user_agent",
+    "vlan",
+    "vulnerability",
+    "x509",
+];
+
+/// The fields set by the sources, and the fields of the schema they are moved to.
+const DEFAULT_MAPPINGS: &[(&str, &str, Conversion)] = &[
+    // `syslog` and `journald`
+    ("appname", "process.name", Conversion::None),
+    ("procid", "process.pid", Conversion::Integer),
+    ("facility", "log.syslog.facility.name", Conversion::None),
+    ("severity", "log.syslog.severity.name", Conversion::None),
+    ("hostname", "host.hostname", Conversion::None),
+    ("msgid", "log.syslog.msgid", Conversion::None),
+    ("source_ip", "source.ip", Conversion::None),
+    ("level", "log.level", Conversion::None),
+    // `file` and `http_server`
+    ("file", "log.file.path", Conversion::None),
+    ("path", "url.path", Conversion::None),
+    // `docker_logs`
+    ("container_id", "container.id", Conversion::None),
+    ("container_name", "container.name", Conversion::None),
+    ("image", "container.image.name", Conversion::None),
+    ("label", "container.labels", Conversion::None),
+    // `kubernetes_logs`
+    ("kubernetes.container_id", "container.id", Conversion::None),
+    (
+        "kubernetes.container_image",
+        "container.image.name",
+        Conversion::None,
+    ),
+    (
+        "kubernetes.container_name",
+        "container.name",
+        Conversion::None,
+    ),
+    (
+        "kubernetes.pod_name",
+        "kubernetes.pod.name",
+        Conversion::None,
+    ),
+    (
+        "kubernetes.pod_namespace",
+        "kubernetes.namespace",
+        Conversion::None,
+    ),
+    ("kubernetes.pod_uid", "kubernetes.pod.uid", Conversion::None),
+    ("kubernetes.pod_ip", "kubernetes.pod.ip", Conversion::None),
+    (
+        "kubernetes.pod_node_name",
+        "kubernetes.node.name",
+        Conversion::None,
+    ),
+    (
+        "kubernetes.pod_labels",
+        "kubernetes.labels",
+        Conversion::None,
+    ),
+    (
+        "kubernetes.pod_annotations",
+        "kubernetes.annotations",
+        Conversion::None,
+    ),
+];
+
+/// Configuration for the `ecs` transform.
+#[configurable_component(transform("ecs", "Normalize log events to the Elastic Common Schema."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EcsConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: EcsMode,
+
+    /// Whether to apply the built-in mappings of the fields set by the sources.
+    ///
+    /// These cover the global log schema fields, along with the fields of the `syslog`,
+    /// `journald`, `file`, `http_server`, `docker_logs`, and `kubernetes_logs` sources.
+    #[serde(default = "crate::serde::default_true")]
+    pub default_mappings: bool,
+
+    /// A map of the fields to move, to the fields of the schema they are moved to.
+    ///
+    /// These take precedence over the built-in mappings of the same fields.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The field of the schema to move the field to."
+    ))]
+    #[configurable(metadata(docs::examples = "example_mappings()"))]
+    pub mappings: IndexMap<String, ConfigTargetPath>,
+
+    /// The field to set to the names of the top-level fields not part of the schema.
+    ///
+    /// The field isn't set on the events which only have fields of the schema.
+    #[configurable(metadata(docs::examples = "vector.unmapped_fields"))]
+    pub unmapped_fields_key: Option<ConfigTargetPath>,
+}
+
+fn example_mappings() -> IndexMap<String, String> {
+    IndexMap::from([
+        ("service".to_owned(), "service.name".to_owned()),
+        ("trace_id".to_owned(), "trace.id".to_owned()),
+    ])
+}
+
+/// How the fields not part of the schema are handled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EcsMode {
+    /// The fields not part of the schema are kept, and reported.
+    #[default]
+    Lenient,
+
+    /// The fields not part of the schema are removed, and reported.
+    ///
+    /// This includes the fields whose value couldn't be converted to the type of the field of the
+    /// schema they are mapped to.
+    Strict,
+}
+
+impl GenerateConfig for EcsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"mode = "lenient""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "ecs")]
+impl TransformConfig for EcsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Ecs::new(self)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, input: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        // The fields are moved around depending on their presence in each event.
+        let schema_definition = schema::Definition::default_for_namespace(input.log_namespaces());
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+}
+
+/// How the value of a field is converted when moved.
+#[derive(Clone, Copy, Debug)]
+enum Conversion {
+    None,
+    Integer,
+}
+
+impl Conversion {
+    fn convert(self, value: Value) -> Option<Value> {
+        match (self, value) {
+            (Self::None, value) | (Self::Integer, value @ Value::Integer(_)) => Some(value),
+            (Self::Integer, Value::Bytes(bytes)) => String::from_utf8_lossy(&bytes)
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .map(Value::from),
+            (Self::Integer, _) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Mapping {
+    from: OwnedTargetPath,
+    to: OwnedTargetPath,
+    conversion: Conversion,
+}
+
+#[derive(Clone, Debug)]
+pub struct Ecs {
+    mode: EcsMode,
+    mappings: Vec<Mapping>,
+    unmapped_fields_key: Option<OwnedTargetPath>,
+}
+
+impl Ecs {
+    pub fn new(config: &EcsConfig) -> crate::Result<Self> {
+        let mut mappings = Vec::new();
+        for (from, to) in &config.mappings {
+            let from = parse_value_path(from)
+                .map_err(|error| format!("Invalid field {:?}: {}", from, error))?;
+            mappings.push(Mapping {
+                from: OwnedTargetPath::event(from),
+                to: to.0.clone(),
+                conversion: Conversion::None,
+            });
+        }
+
+        if config.default_mappings {
+            let default_mappings = default_mappings()
+                .filter(|default_mapping| {
+                    !mappings
+                        .iter()
+                        .any(|mapping| mapping.from == default_mapping.from)
+                })
+                .collect::<Vec<_>>();
+            // Applied after the configured ones, which may move fields out of their way.
+            mappings.extend(default_mappings);
+        }
+
+        Ok(Self {
+            mode: config.mode,
+            mappings,
+            unmapped_fields_key: config.unmapped_fields_key.clone().map(|key| key.0),
+        })
+    }
+
+    fn map(&self, log: &mut LogEvent) {
+        for mapping in &self.mappings {
+            // Don't overwrite the fields already in the schema.
+            if log.contains(&mapping.to) {
+                continue;
+            }
+            let value = match log.get(&mapping.from) {
+                // Already normalized, as with `host` or `file` set to their field set.
+                Some(value) if value.is_object() && is_ecs_field(&mapping.from) => continue,
+                Some(value) => value.clone(),
+                None => continue,
+            };
+
+            // Left in place when it can't be converted, to be handled as any other field not in
+            // the schema.
+            if let Some(value) = mapping.conversion.convert(value) {
+                log.remove_prune(&mapping.from, true);
+                log.insert(&mapping.to, value);
+            }
+        }
+    }
+
+    /// Returns the names of the top-level fields not part of the schema, removing them in the
+    /// `strict` mode.
+    fn unmapped_fields(&self, log: &mut LogEvent) -> Vec<String> {
+        let fields = match log.as_map_mut() {
+            Some(fields) => fields,
+            None => return Vec::new(),
+        };
+
+        let unmapped = fields
+            .keys()
+            .filter(|field| ECS_FIELDS.binary_search(&field.as_str()).is_err())
+            .cloned()
+            .collect::<Vec<_>>();
+        if self.mode == EcsMode::Strict {
+            for field in &unmapped {
+                fields.remove(field);
+            }
+        }
+        unmapped
+    }
+}
+
+/// Returns whether `path` is a top-level field of the schema.
+fn is_ecs_field(path: &OwnedTargetPath) -> bool {
+    match path.path.segments.as_slice() {
+        [OwnedSegment::Field(field)] => ECS_FIELDS.binary_search(&field.as_str()).is_ok(),
+        _ => false,
+    }
+}
+
+fn default_mappings() -> impl Iterator<Item = Mapping> {
+    let schema = log_schema();
+    let log_schema_mappings = [
+        (schema.timestamp_key().cloned(), "@timestamp"),
+        (parse_value_path(schema.host_key()).ok(), "host.name"),
+        (
+            parse_value_path(schema.source_type_key()).ok(),
+            "event.module",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(from, to)| {
+        Some(Mapping {
+            from: OwnedTargetPath::event(from?),
+            to: OwnedTargetPath::event(parse_value_path(to).expect("valid field")),
+            conversion: Conversion::None,
+        })
+    });
+
+    let source_mappings = DEFAULT_MAPPINGS
+        .iter()
+        .map(|(from, to, conversion)| Mapping {
+            from: OwnedTargetPath::event(parse_value_path(from).expect("valid field")),
+            to: OwnedTargetPath::event(parse_value_path(to).expect("valid field")),
+            conversion: *conversion,
+        });
+
+    log_schema_mappings.chain(source_mappings)
+}
+
+impl FunctionTransform for Ecs {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let mut log = event.into_log();
+
+        self.map(&mut log);
+        log.insert("ecs.version", ECS_VERSION);
+
+        let unmapped = self.unmapped_fields(&mut log);
+        if !unmapped.is_empty() {
+            emit!(EcsUnmappedFields {
+                fields: &unmapped,
+                removed: self.mode == EcsMode::Strict,
+            });
+            if let Some(key) = &self.unmapped_fields_key {
+                log.insert(
+                    key,
+                    unmapped.into_iter().map(Value::from).collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        output.push(Event::from(log));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EcsConfig>();
+    }
+
+    #[test]
+    fn ecs_fields_are_sorted() {
+        assert!(ECS_FIELDS.windows(2).all(|fields| fields[0] < fields[1]));
+    }
+
+    fn ecs(config: &str) -> Ecs {
+        Ecs::new(&toml::from_str::<EcsConfig>(config).unwrap()).unwrap()
+    }
+
+    fn log(value: serde_json::Value) -> Event {
+        Event::from(LogEvent::try_from(value).unwrap())
+    }
+
+    #[test]
+    fn maps_syslog_fields() {
+        let mut transform = ecs("");
+        let event = log(serde_json::json!({
+            "message": "Connection refused",
+            "host": "web-01",
+            "appname": "nginx",
+            "procid": "1234",
+            "severity": "err",
+        }));
+
+        let event = transform_one(&mut transform, event).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["host.name"], "web-01".into());
+        assert_eq!(log["process.name"], "nginx".into());
+        assert_eq!(log["process.pid"], 1234.into());
+        assert_eq!(log["log.syslog.severity.name"], "err".into());
+        assert_eq!(log["ecs.version"], ECS_VERSION.into());
+        assert!(!log.contains("appname"));
+    }
+
+    #[test]
+    fn maps_kubernetes_fields() {
+        let mut transform = ecs(r#"mappings.service = "service.name""#);
+        let event = log(serde_json::json!({
+            "message": "ready",
+            "service": "api",
+            "kubernetes": {
+                "container_name": "api",
+                "pod_name": "api-7d9f",
+                "pod_namespace": "default",
+                "pod_labels": { "app": "api" },
+            },
+        }));
+
+        let event = transform_one(&mut transform, event).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["service.name"], "api".into());
+        assert_eq!(log["container.name"], "api".into());
+        assert_eq!(log["kubernetes.pod.name"], "api-7d9f".into());
+        assert_eq!(log["kubernetes.namespace"], "default".into());
+        assert_eq!(log["kubernetes.labels.app"], "api".into());
+        assert!(!log.contains("kubernetes.pod_name"));
+    }
+
+    #[test]
+    fn keeps_already_normalized_fields() {
+        let mut transform = ecs("");
+        let event = log(serde_json::json!({
+            "host": { "name": "web-01" },
+            "hostname": "web-01.example.com",
+            "file": { "name": "access.log" },
+        }));
+
+        let event = transform_one(&mut transform, event).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["host.name"], "web-01".into());
+        assert_eq!(log["host.hostname"], "web-01.example.com".into());
+        assert_eq!(log["file.name"], "access.log".into());
+        assert!(!log.contains("log.file"));
+    }
+
+    #[test]
+    fn reports_unmapped_fields() {
+        let mut transform = ecs(r#"unmapped_fields_key = "vector.unmapped_fields""#);
+        let event = log(serde_json::json!({
+            "message": "hello",
+            "custom": "value",
+        }));
+
+        let event = transform_one(&mut transform, event).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["custom"], "value".into());
+        assert_eq!(
+            log["vector.unmapped_fields"],
+            Value::from(vec![Value::from("custom")])
+        );
+    }
+
+    #[test]
+    fn removes_unmapped_fields_in_strict_mode() {
+        let mut transform = ecs(r#"mode = "strict""#);
+        let event = log(serde_json::json!({
+            "message": "hello",
+            "custom": "value",
+            "procid": "-",
+        }));
+
+        let event = transform_one(&mut transform, event).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert!(!log.contains("custom"));
+        assert!(!log.contains("procid"));
+        assert!(!log.contains("process.pid"));
+    }
+}
@@ -0,0 +1,277 @@
+//! The `geoip` transform.
+//!
+//! Looks up the IP addresses of the events in a `geoip` enrichment table, and sets the location
+//! or network data found for them on the events, without having to write any VRL.
+use std::net::IpAddr;
+
+use enrichment::{Case, Condition, IndexHandle, TableRegistry, TableSearch};
+use indexmap::IndexMap;
+use lookup::lookup_v2::{parse_value_path, ConfigTargetPath, OwnedTargetPath};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Value},
+    internal_events::GeoipInvalidAddress,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The field the `geoip` enrichment tables are searched by.
+const IP_FIELD: &str = "ip";
+
+/// Configuration for the `geoip` transform.
+#[configurable_component(transform(
+    "geoip",
+    "Enrich log events with the location or network of their IP addresses."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GeoipConfig {
+    /// The name of the `geoip` enrichment table to look up the IP addresses in.
+    ///
+    /// The table can download its database from MaxMind, and keep it up to date, with its
+    /// `download` option.
+    #[configurable(metadata(docs::examples = "geoip"))]
+    pub table: String,
+
+    /// A map of the fields holding the IP addresses to look up, to the fields to set to the data
+    /// found for them.
+    ///
+    /// The data is set as an object, such as `{ "city_name": "Boxford", "country_code": "GB", ... }`
+    /// for the city databases, or `{ "autonomous_system_number": 701, ... }` for the ASN
+    /// databases. The field isn't set for the addresses not found in the database.
+    #[configurable(metadata(
+        docs::additional_props_description = "The field to set to the data found for the IP address."
+    ))]
+    #[configurable(metadata(docs::examples = "example_fields()"))]
+    pub fields: IndexMap<String, ConfigTargetPath>,
+
+    /// The fields of the data found to set, instead of all of them.
+    #[configurable(metadata(docs::examples = "example_select()"))]
+    pub select: Option<Vec<String>>,
+}
+
+fn example_fields() -> IndexMap<String, String> {
+    IndexMap::from([
+        ("client_ip".to_owned(), "client.geo".to_owned()),
+        ("server_ip".to_owned(), "server.geo".to_owned()),
+    ])
+}
+
+fn example_select() -> Vec<String> {
+    vec!["country_code".to_owned(), "city_name".to_owned()]
+}
+
+impl GenerateConfig for GeoipConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            table = "geoip"
+            fields.client_ip = "client.geo"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "geoip")]
+impl TransformConfig for GeoipConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Geoip::new(
+            self,
+            context.enrichment_tables.clone(),
+        )?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, input: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        // The fields are only set for the addresses found in the database.
+        let schema_definition = schema::Definition::default_for_namespace(input.log_namespaces());
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Field {
+    name: String,
+    source: OwnedTargetPath,
+    target: OwnedTargetPath,
+}
+
+#[derive(Clone, Debug)]
+pub struct Geoip {
+    table: String,
+    index: IndexHandle,
+    tables: TableSearch,
+    fields: Vec<Field>,
+    select: Option<Vec<String>>,
+}
+
+impl Geoip {
+    pub fn new(config: &GeoipConfig, mut tables: TableRegistry) -> crate::Result<Self> {
+        if !tables.table_ids().contains(&config.table) {
+            return Err(format!("Enrichment table {:?} not found.", config.table).into());
+        }
+        let index = tables.add_index(&config.table, Case::Sensitive, &[IP_FIELD])?;
+
+        let mut fields = Vec::new();
+        for (source, target) in &config.fields {
+            let path = parse_value_path(source)
+                .map_err(|error| format!("Invalid field {:?}: {}", source, error))?;
+            fields.push(Field {
+                name: source.clone(),
+                source: OwnedTargetPath::event(path),
+                target: target.0.clone(),
+            });
+        }
+
+        Ok(Self {
+            table: config.table.clone(),
+            index,
+            tables: tables.as_readonly(),
+            fields,
+            select: config.select.clone(),
+        })
+    }
+}
+
+impl FunctionTransform for Geoip {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let mut log = event.into_log();
+
+        for field in &self.fields {
+            let address = match log.get(&field.source) {
+                Some(Value::Bytes(address)) => String::from_utf8_lossy(address).trim().to_owned(),
+                _ => continue,
+            };
+            if address.parse::<IpAddr>().is_err() {
+                emit!(GeoipInvalidAddress {
+                    field: &field.name,
+                    address: &address,
+                });
+                continue;
+            }
+
+            let condition = [Condition::Equals {
+                field: IP_FIELD,
+                value: address.into(),
+            }];
+            // The addresses not found, such as the private ones, are left alone.
+            if let Ok(row) = self.tables.find_table_row(
+                &self.table,
+                Case::Sensitive,
+                &condition,
+                self.select.as_deref(),
+                Some(self.index),
+            ) {
+                log.insert(&field.target, Value::Object(row));
+            }
+        }
+
+        output.push(Event::from(log));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use enrichment::Table;
+    use vector_core::event::LogEvent;
+
+    use super::*;
+    use crate::{enrichment_tables::geoip, transforms::test::transform_one};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GeoipConfig>();
+    }
+
+    fn tables() -> TableRegistry {
+        let table = geoip::Geoip::new(geoip::GeoipConfig {
+            path: "tests/data/GeoIP2-City-Test.mmdb".to_owned(),
+            locale: "en".to_owned(),
+            download: None,
+        })
+        .unwrap();
+
+        let tables = TableRegistry::default();
+        tables.load(HashMap::from([(
+            "geoip".to_owned(),
+            Box::new(table) as Box<dyn Table + Send + Sync>,
+        )]));
+        tables
+    }
+
+    #[test]
+    fn looks_up_ip_fields() {
+        let config = toml::from_str::<GeoipConfig>(
+            r#"
+            table = "geoip"
+            fields.client = "client.geo"
+            fields.server = "server.geo"
+            select = ["city_name", "country_code"]
+            "#,
+        )
+        .unwrap();
+        let tables = tables();
+        let mut transform = Geoip::new(&config, tables.clone()).unwrap();
+        tables.finish_load();
+
+        let mut log = LogEvent::from("message");
+        log.insert("client", "2.125.160.216");
+        log.insert("server", "10.1.12.1");
+        let log = transform_one(&mut transform, log.into())
+            .unwrap()
+            .into_log();
+
+        assert_eq!(log["client.geo.city_name"], Value::from("Boxford"));
+        assert_eq!(log["client.geo.country_code"], Value::from("GB"));
+        assert!(!log.contains("client.geo.timezone"));
+        assert!(!log.contains("server.geo"));
+    }
+
+    #[test]
+    fn skips_invalid_addresses() {
+        let config = toml::from_str::<GeoipConfig>(
+            r#"
+            table = "geoip"
+            fields.client = "client.geo"
+            "#,
+        )
+        .unwrap();
+        let tables = tables();
+        let mut transform = Geoip::new(&config, tables.clone()).unwrap();
+        tables.finish_load();
+
+        let mut log = LogEvent::from("message");
+        log.insert("client", "not an address");
+        let log = transform_one(&mut transform, log.into())
+            .unwrap()
+            .into_log();
+
+        assert_eq!(log["client"], Value::from("not an address"));
+        assert!(!log.contains("client.geo"));
+    }
+
+    #[test]
+    fn requires_table() {
+        let config = toml::from_str::<GeoipConfig>(
+            r#"
+            table = "missing"
+            fields.client = "client.geo"
+            "#,
+        )
+        .unwrap();
+
+        let error = Geoip::new(&config, tables()).unwrap_err();
+        assert_eq!(error.to_string(), "Enrichment table \"missing\" not found.");
+    }
+}
@@ -1,18 +1,21 @@
-use std::num::ParseFloatError;
+use std::{borrow::Cow, num::ParseFloatError};
 
 use chrono::Utc;
 use indexmap::IndexMap;
+use vector_common::TimeZone;
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::{compile_vrl, config::LogNamespace};
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
 
 use crate::{
     config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
     event::{
-        metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind, TagValue},
-        Event, Value,
+        metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, StatisticKind, TagValue},
+        Event, Value, VrlTarget,
     },
     internal_events::{
-        LogToMetricFieldNullError, LogToMetricParseFloatError, ParserMissingFieldError, DROP_EVENT,
+        LogToMetricFieldNullError, LogToMetricParseFloatError, LogToMetricVrlError,
+        ParserMissingFieldError, DROP_EVENT,
     },
     schema,
     template::{Template, TemplateRenderingError},
@@ -32,7 +35,8 @@ pub struct LogToMetricConfig {
 #[configurable_component]
 #[derive(Clone, Debug)]
 pub struct CounterConfig {
-    /// Increments the counter by the value in `field`, instead of only by `1`.
+    /// Increments the counter by the value in `field`, or computed by `value_source`, instead of
+    /// only by `1`.
     #[serde(default = "default_increment_by_value")]
     pub increment_by_value: bool,
 
@@ -41,6 +45,21 @@ pub struct CounterConfig {
     pub kind: MetricKind,
 }
 
+/// Specification of a histogram derived from a log event.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct HistogramConfig {
+    /// The upper limits of the buckets of the histogram, in ascending order.
+    ///
+    /// If set, an aggregated histogram is emitted, counting the value in the first bucket it fits
+    /// in, along with the count and the sum of the values. Values greater than the last limit are
+    /// only counted in the count and the sum. If not set, a distribution of the values is emitted
+    /// instead.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 0.005, docs::examples = 0.1, docs::examples = 1.0))]
+    pub buckets: Option<Vec<f64>>,
+}
+
 /// Specification of a metric derived from a log event.
 // TODO: While we're resolving the schema for this enum somewhat reasonably (in
 // `generate-components-docs.rb`), we have a problem where an overlapping field (overlap between two
@@ -51,11 +70,25 @@ pub struct CounterConfig {
 #[derive(Clone, Debug)]
 pub struct MetricConfig {
     /// Name of the field in the event to generate the metric.
-    pub field: Template,
+    ///
+    /// Exactly one of `field` and `value_source` must be set.
+    #[serde(default)]
+    pub field: Option<Template>,
+
+    /// A [VRL][vrl] expression computing the value of the metric from the event, instead of
+    /// reading it from `field`.
+    ///
+    /// The event can't be modified by the expression.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "to_float!(.duration_ms) / 1000"))]
+    pub value_source: Option<String>,
 
     /// Overrides the name of the counter.
     ///
-    /// If not specified, `field` is used as the name of the metric.
+    /// If not specified, `field` is used as the name of the metric. It must be specified if the
+    /// value is computed by `value_source`.
     pub name: Option<Template>,
 
     /// Sets the namespace for the metric.
@@ -72,7 +105,8 @@ pub struct MetricConfig {
 
 /// Specification of the value of a created tag.
 ///
-/// This may be a single value, a `null` for a bare tag, or an array of either.
+/// This may be a single value, a `null` for a bare tag, an array of either, or a VRL expression
+/// computing them.
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(untagged)]
@@ -82,6 +116,17 @@ pub enum TagConfig {
 
     /// An array of values to give to the same tag name.
     Multi(Vec<Option<Template>>),
+
+    /// A value computed from the event.
+    Source {
+        /// A [VRL][vrl] expression computing the value of the tag.
+        ///
+        /// If the expression returns an array, each of its elements is given to the same tag
+        /// name. A `null` gives a bare tag.
+        ///
+        /// [vrl]: https://vector.dev/docs/reference/vrl
+        source: String,
+    },
 }
 
 /// Specification of the type of an individual metric, and any associated data.
@@ -94,7 +139,7 @@ pub enum MetricTypeConfig {
     Counter(CounterConfig),
 
     /// A histogram.
-    Histogram,
+    Histogram(HistogramConfig),
 
     /// A gauge.
     Gauge,
@@ -106,12 +151,6 @@ pub enum MetricTypeConfig {
     Summary,
 }
 
-impl MetricConfig {
-    fn field(&self) -> &str {
-        self.field.get_ref()
-    }
-}
-
 const fn default_increment_by_value() -> bool {
     false
 }
@@ -123,13 +162,29 @@ const fn default_kind() -> MetricKind {
 #[derive(Debug, Clone)]
 pub struct LogToMetric {
     config: LogToMetricConfig,
+    /// The names and the compiled VRL expressions of each metric of the configuration, in order.
+    compiled: Vec<CompiledMetric>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledMetric {
+    name: Template,
+    value: ValueSource,
+    tags: IndexMap<String, Program>,
+}
+
+#[derive(Debug, Clone)]
+enum ValueSource {
+    Field(String),
+    Vrl(Program),
 }
 
 impl GenerateConfig for LogToMetricConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             metrics: vec![MetricConfig {
-                field: "field_name".try_into().expect("Fixed template"),
+                field: Some("field_name".try_into().expect("Fixed template")),
+                value_source: None,
                 name: None,
                 namespace: None,
                 tags: None,
@@ -146,8 +201,11 @@ impl GenerateConfig for LogToMetricConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "log_to_metric")]
 impl TransformConfig for LogToMetricConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(LogToMetric::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(LogToMetric::new(
+            self.clone(),
+            &context.enrichment_tables,
+        )?))
     }
 
     fn input(&self) -> Input {
@@ -164,11 +222,88 @@ impl TransformConfig for LogToMetricConfig {
 }
 
 impl LogToMetric {
-    pub const fn new(config: LogToMetricConfig) -> Self {
-        LogToMetric { config }
+    pub fn new(
+        config: LogToMetricConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let compiled = config
+            .metrics
+            .iter()
+            .map(|metric| compile_metric(metric, enrichment_tables))
+            .collect::<crate::Result<_>>()?;
+        Ok(LogToMetric { config, compiled })
     }
 }
 
+fn compile_metric(
+    config: &MetricConfig,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> crate::Result<CompiledMetric> {
+    let value = match (&config.field, &config.value_source) {
+        (Some(field), None) => ValueSource::Field(field.get_ref().to_owned()),
+        (None, Some(source)) => ValueSource::Vrl(compile_source(source, enrichment_tables)?),
+        _ => return Err("Exactly one of `field` and `value_source` must be set.".into()),
+    };
+    let name = match config.name.as_ref().or(config.field.as_ref()) {
+        Some(name) => name.clone(),
+        None => {
+            return Err("`name` must be set if the value is computed by `value_source`.".into())
+        }
+    };
+
+    if let MetricTypeConfig::Histogram(HistogramConfig {
+        buckets: Some(buckets),
+    }) = &config.metric
+    {
+        if buckets.is_empty() || buckets.windows(2).any(|limits| limits[0] >= limits[1]) {
+            return Err("The buckets of a histogram must be in ascending order.".into());
+        }
+    }
+
+    let mut tags = IndexMap::new();
+    for (tag, tag_config) in config.tags.iter().flatten() {
+        if let TagConfig::Source { source } = tag_config {
+            tags.insert(tag.clone(), compile_source(source, enrichment_tables)?);
+        }
+    }
+
+    Ok(CompiledMetric { name, value, tags })
+}
+
+fn compile_source(
+    source: &str,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> crate::Result<Program> {
+    let functions = crate::vrl_functions();
+
+    let state = vrl::state::TypeState::default();
+
+    let mut config = CompileConfig::default();
+    config.set_custom(enrichment_tables.clone());
+    config.set_read_only();
+
+    let CompilationResult {
+        program,
+        warnings,
+        config: _,
+    } = compile_vrl(source, &functions, &state, config)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+fn resolve(program: &Program, event: &Event) -> Result<Value, String> {
+    let mut target = VrlTarget::new(event.clone(), program.info(), false);
+    Runtime::default()
+        .resolve(&mut target, program, &TimeZone::default())
+        .map_err(|error| error.to_string())
+}
+
 enum TransformError {
     FieldNotFound {
         field: String,
@@ -181,6 +316,10 @@ enum TransformError {
         field: String,
         error: ParseFloatError,
     },
+    VrlError {
+        field: String,
+        error: String,
+    },
 }
 
 fn render_template(template: &Template, event: &Event) -> Result<String, TransformError> {
@@ -191,6 +330,7 @@ fn render_template(template: &Template, event: &Event) -> Result<String, Transfo
 
 fn render_tags(
     tags: &Option<IndexMap<String, TagConfig>>,
+    programs: &IndexMap<String, Program>,
     event: &Event,
 ) -> Result<Option<MetricTags>, TransformError> {
     Ok(match tags {
@@ -207,6 +347,11 @@ fn render_tags(
                             render_tag_into(event, name, template, &mut result)?;
                         }
                     }
+                    TagConfig::Source { .. } => {
+                        if let Some(program) = programs.get(name) {
+                            resolve_tag_into(event, name, program, &mut result);
+                        }
+                    }
                 }
             }
             result.as_option()
@@ -239,7 +384,41 @@ fn render_tag_into(
     Ok(())
 }
 
-fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformError> {
+fn resolve_tag_into(event: &Event, name: &str, program: &Program, result: &mut MetricTags) {
+    let to_tag_value = |value: Value| match value {
+        Value::Null => TagValue::Bare,
+        value => TagValue::Value(value.to_string_lossy().into_owned()),
+    };
+    match resolve(program, event) {
+        Ok(Value::Array(values)) => {
+            for value in values {
+                result.insert(name.to_string(), to_tag_value(value));
+            }
+        }
+        Ok(value) => result.insert(name.to_string(), to_tag_value(value)),
+        Err(error) => emit!(LogToMetricVrlError {
+            field: name,
+            error: &error,
+            drop_event: false,
+        }),
+    }
+}
+
+fn parse_float(field: &str, value: &Value) -> Result<f64, TransformError> {
+    value
+        .to_string_lossy()
+        .parse()
+        .map_err(|error| TransformError::ParseFloatError {
+            field: field.to_string(),
+            error,
+        })
+}
+
+fn to_metric(
+    config: &MetricConfig,
+    compiled: &CompiledMetric,
+    event: &Event,
+) -> Result<Metric, TransformError> {
     let log = event.as_log();
 
     let timestamp = log
@@ -249,66 +428,79 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
         .or_else(|| Some(Utc::now()));
     let metadata = event.metadata().clone();
 
-    let field = config.field();
-
-    let value = match log.get(field) {
-        None => Err(TransformError::FieldNotFound {
-            field: field.to_string(),
-        }),
-        Some(Value::Null) => Err(TransformError::FieldNull {
-            field: field.to_string(),
-        }),
-        Some(value) => Ok(value),
+    let (field, value) = match &compiled.value {
+        ValueSource::Field(field) => match log.get(field.as_str()) {
+            None => Err(TransformError::FieldNotFound {
+                field: field.to_string(),
+            }),
+            Some(Value::Null) => Err(TransformError::FieldNull {
+                field: field.to_string(),
+            }),
+            Some(value) => Ok((field.as_str(), Cow::Borrowed(value))),
+        },
+        ValueSource::Vrl(program) => {
+            let field = compiled.name.get_ref();
+            match resolve(program, event) {
+                Err(error) => Err(TransformError::VrlError {
+                    field: field.to_string(),
+                    error,
+                }),
+                Ok(Value::Null) => Err(TransformError::FieldNull {
+                    field: field.to_string(),
+                }),
+                Ok(value) => Ok((field, Cow::Owned(value))),
+            }
+        }
     }?;
 
-    let name = config.name.as_ref().unwrap_or(&config.field);
-    let name = render_template(name, event)?;
+    let name = render_template(&compiled.name, event)?;
 
     let namespace = config.namespace.as_ref();
     let namespace = namespace
         .map(|namespace| render_template(namespace, event))
         .transpose()?;
 
-    let tags = render_tags(&config.tags, event)?;
+    let tags = render_tags(&config.tags, &compiled.tags, event)?;
 
     let (kind, value) = match &config.metric {
         MetricTypeConfig::Counter(counter) => {
             let value = if counter.increment_by_value {
-                value.to_string_lossy().parse().map_err(|error| {
-                    TransformError::ParseFloatError {
-                        field: config.field.get_ref().to_owned(),
-                        error,
-                    }
-                })?
+                parse_float(field, &value)?
             } else {
                 1.0
             };
 
             (counter.kind, MetricValue::Counter { value })
         }
-        MetricTypeConfig::Histogram => {
-            let value = value.to_string_lossy().parse().map_err(|error| {
-                TransformError::ParseFloatError {
-                    field: field.to_string(),
-                    error,
+        MetricTypeConfig::Histogram(histogram) => {
+            let value = parse_float(field, &value)?;
+
+            let value = match &histogram.buckets {
+                Some(limits) => {
+                    let index = limits.iter().position(|limit| value <= *limit);
+                    MetricValue::AggregatedHistogram {
+                        buckets: limits
+                            .iter()
+                            .enumerate()
+                            .map(|(i, &upper_limit)| Bucket {
+                                upper_limit,
+                                count: u64::from(index == Some(i)),
+                            })
+                            .collect(),
+                        count: 1,
+                        sum: value,
+                    }
                 }
-            })?;
-
-            (
-                MetricKind::Incremental,
-                MetricValue::Distribution {
+                None => MetricValue::Distribution {
                     samples: vector_core::samples![value => 1],
                     statistic: StatisticKind::Histogram,
                 },
-            )
+            };
+
+            (MetricKind::Incremental, value)
         }
         MetricTypeConfig::Summary => {
-            let value = value.to_string_lossy().parse().map_err(|error| {
-                TransformError::ParseFloatError {
-                    field: field.to_string(),
-                    error,
-                }
-            })?;
+            let value = parse_float(field, &value)?;
 
             (
                 MetricKind::Incremental,
@@ -319,12 +511,7 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
             )
         }
         MetricTypeConfig::Gauge => {
-            let value = value.to_string_lossy().parse().map_err(|error| {
-                TransformError::ParseFloatError {
-                    field: field.to_string(),
-                    error,
-                }
-            })?;
+            let value = parse_float(field, &value)?;
 
             (MetricKind::Absolute, MetricValue::Gauge { value })
         }
@@ -350,8 +537,8 @@ impl FunctionTransform for LogToMetric {
         // Metrics are "all or none" for a specific log. If a single fails, none are produced.
         let mut buffer = Vec::with_capacity(self.config.metrics.len());
 
-        for config in self.config.metrics.iter() {
-            match to_metric(config, &event) {
+        for (config, compiled) in self.config.metrics.iter().zip(&self.compiled) {
+            match to_metric(config, compiled, &event) {
                 Ok(metric) => {
                     buffer.push(Event::Metric(metric));
                 }
@@ -378,6 +565,11 @@ impl FunctionTransform for LogToMetric {
                                 field: None,
                             })
                         }
+                        TransformError::VrlError { field, error } => emit!(LogToMetricVrlError {
+                            field: field.as_ref(),
+                            error: error.as_ref(),
+                            drop_event: true,
+                        }),
                     };
                     // early return to prevent the partial buffer from being sent
                     return;
@@ -966,4 +1158,94 @@ mod tests {
             .with_timestamp(Some(ts()))
         );
     }
+
+    #[tokio::test]
+    async fn response_time_histogram_buckets() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [1.0, 5.0, 10.0]
+            "#,
+        );
+
+        let event = create_event("response_time", "2.5");
+        let metadata = event.metadata().clone();
+        let metric = do_transform(config, event).await.unwrap();
+
+        assert_eq!(
+            metric.into_metric(),
+            Metric::new_with_metadata(
+                "response_time",
+                MetricKind::Incremental,
+                MetricValue::AggregatedHistogram {
+                    buckets: vector_core::buckets![1.0 => 0, 5.0 => 1, 10.0 => 0],
+                    count: 1,
+                    sum: 2.5,
+                },
+                metadata
+            )
+            .with_timestamp(Some(ts()))
+        );
+    }
+
+    #[tokio::test]
+    async fn response_time_from_vrl() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "gauge"
+            value_source = "to_float!(.response_time_ms) / 1000"
+            name = "response_time_seconds"
+            tags = {status = {source = "if to_int!(.status) < 400 { \"ok\" } else { \"error\" }"}}
+            "#,
+        );
+
+        let mut event = create_event("response_time_ms", 2500);
+        event.as_mut_log().insert("status", "200");
+        let metadata = event.metadata().clone();
+        let metric = do_transform(config, event).await.unwrap();
+
+        assert_eq!(
+            metric.into_metric(),
+            Metric::new_with_metadata(
+                "response_time_seconds",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 2.5 },
+                metadata
+            )
+            .with_tags(Some(metric_tags!("status" => "ok")))
+            .with_timestamp(Some(ts()))
+        );
+    }
+
+    #[test]
+    fn invalid_configs() {
+        let enrichment_tables = Default::default();
+        for config in [
+            // Both a field and a VRL expression.
+            r#"
+            [[metrics]]
+            type = "gauge"
+            field = "response_time"
+            value_source = ".response_time"
+            "#,
+            // A VRL expression without a name.
+            r#"
+            [[metrics]]
+            type = "gauge"
+            value_source = ".response_time"
+            "#,
+            // Unordered buckets.
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [5.0, 1.0]
+            "#,
+        ] {
+            assert!(LogToMetric::new(parse_config(config), &enrichment_tables).is_err());
+        }
+    }
 }
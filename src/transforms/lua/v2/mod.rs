@@ -1,8 +1,14 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use codecs::MetricTagValues;
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
+use value::Value;
 use vector_config::configurable_component;
 pub use vector_core::event::lua;
 use vector_core::transform::runtime_transform::{RuntimeTransform, Timer};
@@ -10,9 +16,9 @@ use vector_core::transform::runtime_transform::{RuntimeTransform, Timer};
 use crate::event::lua::event::LuaEvent;
 use crate::schema::Definition;
 use crate::{
-    config::{self, DataType, Input, Output, CONFIG_PATHS},
+    config::{self, DataType, Input, Output, TransformContext, CONFIG_PATHS},
     event::Event,
-    internal_events::{LuaBuildError, LuaGcTriggered},
+    internal_events::{LuaBuildError, LuaGcTriggered, LuaStateCheckpointError},
     schema,
     transforms::Transform,
 };
@@ -76,6 +82,14 @@ pub struct LuaConfig {
     #[serde(default)]
     timers: Vec<TimerConfig>,
 
+    /// Persists the global `state` table across restarts.
+    ///
+    /// When set, the `state` table is restored from the data directory before the `source` is
+    /// evaluated, and saved back periodically and when the transform stops. It can hold strings,
+    /// numbers, booleans, and nested tables of them. Set to `{}` to use the defaults.
+    #[configurable(derived)]
+    state: Option<StateConfig>,
+
     /// When set to `single`, metric tag values are exposed as single strings, the
     /// same as they were before this config option. Tags with multiple values show the last assigned value, and null values
     /// are ignored.
@@ -162,15 +176,59 @@ struct TimerConfig {
     /// It can produce new events using the `emit` function.
     ///
     /// This can either be inline Lua that defines a closure to use, or the name of the Lua function
-    /// to call. In both cases, the closure/function takes a single parameter, `emit`, which is a
-    /// reference to a function for emitting events.
+    /// to call. In both cases, the closure/function takes two parameters. The first parameter,
+    /// `emit`, is a reference to a function for emitting events, while the second parameter is the
+    /// `name` of the timer.
     #[configurable(metadata(docs::examples = "timer_handler"))]
     handler: String,
+
+    /// The name of the timer, passed to the handler as its second parameter.
+    ///
+    /// This allows a single handler to serve several timers, and identifies the timer in the logs.
+    #[configurable(metadata(docs::examples = "flush_sessions"))]
+    name: Option<String>,
+}
+
+/// Persistence of the `state` table.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct StateConfig {
+    /// The interval to save the `state` table, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(default = "default_checkpoint_interval")]
+    checkpoint_interval_seconds: Duration,
+
+    /// The directory used to save the `state` table.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    data_dir: Option<PathBuf>,
+}
+
+const fn default_checkpoint_interval() -> Duration {
+    Duration::from_secs(10)
 }
 
 impl LuaConfig {
-    pub fn build(&self) -> crate::Result<Transform> {
-        Lua::new(self).map(Transform::event_task)
+    pub fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let state = self
+            .state
+            .as_ref()
+            .map(|state| -> crate::Result<_> {
+                let dir = context
+                    .globals
+                    .resolve_and_make_data_subdir(state.data_dir.as_ref(), "lua")?;
+                let id = context.key.as_ref().map_or("lua", |key| key.id());
+                Ok(StateCheckpoint {
+                    path: dir.join(format!("{}.json", id)),
+                    interval: state.checkpoint_interval_seconds,
+                })
+            })
+            .transpose()?;
+        Lua::new(self, state).map(Transform::event_task)
     }
 
     pub fn input(&self) -> Input {
@@ -199,8 +257,38 @@ pub struct Lua {
     hook_init: Option<mlua::RegistryKey>,
     hook_process: mlua::RegistryKey,
     hook_shutdown: Option<mlua::RegistryKey>,
-    timers: Vec<(Timer, mlua::RegistryKey)>,
+    timers: Vec<(Timer, mlua::RegistryKey, Option<String>)>,
     multi_value_tags: bool,
+    state: Option<StateCheckpoint>,
+}
+
+/// Where and how often the `state` table is saved.
+#[derive(Clone, Debug)]
+pub struct StateCheckpoint {
+    path: PathBuf,
+    interval: Duration,
+}
+
+/// The ID of the timer saving the `state` table, which can't be the index of a configured timer.
+const CHECKPOINT_TIMER_ID: u32 = u32::MAX;
+
+fn load_state(lua: &mlua::Lua, path: &Path) -> crate::Result<()> {
+    let state = match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)?,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Value::Object(BTreeMap::new()),
+        Err(error) => return Err(error.into()),
+    };
+    lua.globals().set("state", state)?;
+    Ok(())
+}
+
+fn save_state(lua: &mlua::Lua, path: &Path) -> crate::Result<()> {
+    let state: Value = lua.globals().get("state")?;
+    // The state is written aside first, so that a partially written state is never restored.
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, serde_json::to_vec(&state)?)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
 }
 
 // Helper to create `RegistryKey` from Lua function code
@@ -211,7 +299,7 @@ fn make_registry_value(lua: &mlua::Lua, source: &str) -> mlua::Result<mlua::Regi
 }
 
 impl Lua {
-    pub fn new(config: &LuaConfig) -> crate::Result<Self> {
+    pub fn new(config: &LuaConfig, state: Option<StateCheckpoint>) -> crate::Result<Self> {
         // In order to support loading C modules in Lua, we need to create unsafe instance
         // without debug library.
         let lua = unsafe {
@@ -236,6 +324,12 @@ impl Lua {
             package.set("path", paths)?;
         }
 
+        if let Some(state) = &state {
+            load_state(&lua, &state.path).map_err(|error| {
+                format!("Cannot restore Lua state from {:?}: {}", state.path, error)
+            })?;
+        }
+
         if let Some(source) = &config.source {
             lua.load(source).eval().context(InvalidSourceSnafu)?;
         }
@@ -255,18 +349,18 @@ impl Lua {
             .transpose()
             .context(InvalidHooksShutdownSnafu)?;
 
-        for (id, timer) in config.timers.iter().enumerate() {
+        for (id, timer_config) in config.timers.iter().enumerate() {
             let handler_key = lua
-                .load(&timer.handler)
+                .load(&timer_config.handler)
                 .eval::<mlua::Function>()
                 .and_then(|f| lua.create_registry_value(f))
                 .context(InvalidTimerHandlerSnafu)?;
 
             let timer = Timer {
                 id: id as u32,
-                interval: timer.interval_seconds,
+                interval: timer_config.interval_seconds,
             };
-            timers.push((timer, handler_key));
+            timers.push((timer, handler_key, timer_config.name.clone()));
         }
 
         let multi_value_tags = config.metric_tag_values == MetricTagValues::Full;
@@ -279,9 +373,21 @@ impl Lua {
             hook_process,
             hook_shutdown,
             multi_value_tags,
+            state,
         })
     }
 
+    fn checkpoint(&self) {
+        if let Some(state) = &self.state {
+            if let Err(error) = save_state(&self.lua, &state.path) {
+                emit!(LuaStateCheckpointError {
+                    error,
+                    path: &state.path,
+                });
+            }
+        }
+    }
+
     #[cfg(test)]
     fn process(&mut self, event: Event, output: &mut Vec<Event>) -> Result<(), mlua::Error> {
         let lua = &self.lua;
@@ -404,27 +510,41 @@ impl RuntimeTransform for Lua {
             .map_err(|error| error!(%error, rate_limit = 30));
 
         self.attempt_gc();
+        self.checkpoint();
     }
 
     fn timer_handler<F>(&mut self, timer: Timer, emit_fn: F)
     where
         F: FnMut(Event),
     {
+        if timer.id == CHECKPOINT_TIMER_ID {
+            self.checkpoint();
+            return;
+        }
+
         let lua = &self.lua;
+        let (_, handler_key, name) = &self.timers[timer.id as usize];
         let _ = lua
             .scope(|scope| -> mlua::Result<()> {
-                let handler_key = &self.timers[timer.id as usize].1;
                 lua.registry_value::<mlua::Function>(handler_key)?
-                    .call(wrap_emit_fn(scope, emit_fn)?)
+                    .call((wrap_emit_fn(scope, emit_fn)?, name.as_deref()))
             })
             .context(RuntimeErrorTimerHandlerSnafu)
-            .map_err(|error| error!(%error, rate_limit = 30));
+            .map_err(|error| error!(%error, timer = ?name, rate_limit = 30));
 
         self.attempt_gc();
     }
 
     fn timers(&self) -> Vec<Timer> {
-        self.timers.iter().map(|(timer, _)| *timer).collect()
+        let checkpoint = self.state.as_ref().map(|state| Timer {
+            id: CHECKPOINT_TIMER_ID,
+            interval: state.interval,
+        });
+        self.timers
+            .iter()
+            .map(|(timer, _, _)| *timer)
+            .chain(checkpoint)
+            .collect()
     }
 }
 
@@ -456,7 +576,7 @@ mod tests {
     };
 
     fn from_config(config: &str) -> crate::Result<Box<Lua>> {
-        Lua::new(&toml::from_str(config).unwrap()).map(Box::new)
+        Lua::new(&toml::from_str(config).unwrap(), None).map(Box::new)
     }
 
     async fn run_transform<T: Future>(
@@ -954,6 +1074,68 @@ mod tests {
         .await;
     }
 
+    #[test]
+    fn lua_state_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = StateCheckpoint {
+            path: dir.path().join("lua.json"),
+            interval: Duration::from_secs(10),
+        };
+        let config: LuaConfig = toml::from_str(
+            r#"
+            source = "state.count = state.count or 0"
+            hooks.process = """function (event, emit)
+                state.count = state.count + 1
+                event.log.count = state.count
+                emit(event)
+            end
+            """
+            state = {}
+            "#,
+        )
+        .unwrap();
+
+        let mut transform = Lua::new(&config, Some(state.clone())).unwrap();
+        transform
+            .process_single(LogEvent::default().into())
+            .unwrap();
+        transform.hook_shutdown(|_| ());
+
+        let mut transform = Lua::new(&config, Some(state)).unwrap();
+        let event = transform
+            .process_single(LogEvent::default().into())
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.as_log()["count"], Value::Integer(2));
+    }
+
+    #[test]
+    fn lua_named_timers() {
+        let mut transform = from_config(
+            r#"
+            hooks.process = "function (event, emit) end"
+            timers = [
+                { interval_seconds = 1, handler = "on_timer", name = "first" },
+                { interval_seconds = 2, handler = "on_timer", name = "second" },
+            ]
+            source = """
+            function on_timer(emit, name)
+                emit({ log = { timer = name } })
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let mut timers = Vec::new();
+        for timer in transform.timers() {
+            transform.timer_handler(timer, |event| {
+                timers.push(event.as_log()["timer"].clone());
+            });
+        }
+        assert_eq!(timers, vec![Value::from("first"), Value::from("second")]);
+    }
+
     #[tokio::test]
     async fn lua_multiple_events() {
         trace_init();
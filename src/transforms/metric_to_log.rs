@@ -261,6 +261,17 @@ impl MetricToLog {
         if self.tag_values == MetricTagValues::Single {
             metric.reduce_tags_to_single();
         }
+        // JSON can't represent infinite numbers, which would turn the upper limit of the last
+        // bucket of most histograms into a `null`, so they are restored after the conversion.
+        let infinite_buckets = match metric.value() {
+            event::MetricValue::AggregatedHistogram { buckets, .. } => buckets
+                .iter()
+                .enumerate()
+                .filter(|(_, bucket)| bucket.upper_limit.is_infinite())
+                .map(|(index, bucket)| (index, bucket.upper_limit))
+                .collect(),
+            _ => Vec::new(),
+        };
         serde_json::to_value(&metric)
             .map_err(|error| emit!(MetricToLogSerializeError { error }))
             .ok()
@@ -273,6 +284,12 @@ impl MetricToLog {
                     for (key, value) in object {
                         log.insert(event_path!(&key), value);
                     }
+                    for (index, upper_limit) in infinite_buckets {
+                        log.insert(
+                            format!("aggregated_histogram.buckets[{}].upper_limit", index).as_str(),
+                            upper_limit,
+                        );
+                    }
 
                     if self.log_namespace == LogNamespace::Legacy {
                         // "Vector" namespace just leaves the `timestamp` in place.
@@ -553,6 +570,27 @@ mod tests {
         assert_eq!(log.metadata(), &metadata);
     }
 
+    #[tokio::test]
+    async fn transform_histogram_infinite_bucket() {
+        let histo = Metric::new(
+            "histo",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 10, f64::INFINITY => 20],
+                count: 30,
+                sum: 50.0,
+            },
+        )
+        .with_timestamp(Some(ts()));
+
+        let log = do_transform(histo).await.unwrap();
+
+        assert_eq!(
+            log.get("aggregated_histogram.buckets[1].upper_limit"),
+            Some(&Value::from(f64::INFINITY))
+        );
+    }
+
     #[tokio::test]
     async fn transform_summary() {
         let summary = Metric::new(
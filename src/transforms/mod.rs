@@ -6,17 +6,27 @@ use snafu::Snafu;
 
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
+#[cfg(feature = "transforms-aggregate_logs")]
+pub mod aggregate_logs;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-cardinality_limit")]
+pub mod cardinality_limit;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(feature = "transforms-ecs")]
+pub mod ecs;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
+#[cfg(feature = "transforms-geoip")]
+pub mod geoip;
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-protect_fields")]
+pub mod protect_fields;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
@@ -25,10 +35,16 @@ pub mod remap;
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(any(feature = "transforms-aggregate", feature = "transforms-reduce"))]
+pub(crate) mod state;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sample")]
+pub mod tail_sample;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-wasm")]
+pub mod wasm;
 
 pub use vector_core::transform::{
     FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
@@ -0,0 +1,237 @@
+//! The protection of the values of the fields.
+//!
+//! The protected values are tagged with the ID of the key they were protected with, so they can
+//! be decrypted, or matched against the tokens of known values, once the keys are rotated:
+//!
+//! * tokens are `tok:<key ID>:<HMAC-SHA256 of the value, in URL-safe Base64>`.
+//! * encrypted values are `enc:v1:<key ID>:<wrapped data key>:<ciphertext>`, both in Base64.
+//!
+//! Each value is encrypted with AES-256-GCM using its own random data key, which is itself
+//! encrypted, or wrapped, with the configured key. The key ID is authenticated along with both, so
+//! a value can't be passed off as being encrypted with another key.
+use std::collections::HashMap;
+
+use base64::prelude::{Engine as _, BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use openssl::{
+    error::ErrorStack,
+    hash::MessageDigest,
+    pkey::PKey,
+    rand::rand_bytes,
+    sign::Signer,
+    symm::{decrypt_aead, encrypt_aead, Cipher},
+};
+use snafu::Snafu;
+
+pub(super) const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 16;
+
+const TOKEN_PREFIX: &str = "tok";
+const ENCRYPTED_PREFIX: &str = "enc:v1";
+
+#[derive(Debug, Snafu)]
+pub(super) enum DecryptError {
+    #[snafu(display("The value isn't an encrypted value."))]
+    Malformed,
+
+    #[snafu(display("The key {:?} isn't configured.", key_id))]
+    UnknownKey { key_id: String },
+
+    #[snafu(display("The value couldn't be decrypted with the key {:?}.", key_id))]
+    Invalid { key_id: String },
+}
+
+/// Returns the token of `value`, the same for the same value and key.
+pub(super) fn tokenize(key_id: &str, key: &[u8], value: &[u8]) -> Result<String, ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(value)?;
+    let digest = signer.sign_to_vec()?;
+
+    Ok(format!(
+        "{}:{}:{}",
+        TOKEN_PREFIX,
+        key_id,
+        BASE64_URL_SAFE_NO_PAD.encode(digest)
+    ))
+}
+
+/// Encrypts `plaintext` with a new data key, wrapped with `key`.
+pub(super) fn encrypt(key_id: &str, key: &[u8], plaintext: &[u8]) -> Result<String, ErrorStack> {
+    let mut data_key = [0; KEY_LENGTH];
+    rand_bytes(&mut data_key)?;
+
+    let wrapped_data_key = seal(key, key_id.as_bytes(), &data_key)?;
+    let ciphertext = seal(&data_key, key_id.as_bytes(), plaintext)?;
+
+    Ok(format!(
+        "{}:{}:{}:{}",
+        ENCRYPTED_PREFIX,
+        key_id,
+        BASE64_STANDARD.encode(wrapped_data_key),
+        BASE64_STANDARD.encode(ciphertext)
+    ))
+}
+
+/// Decrypts a value returned by `encrypt`, with the key of the ID it is tagged with.
+pub(super) fn decrypt(
+    keys: &HashMap<String, Vec<u8>>,
+    value: &str,
+) -> Result<Vec<u8>, DecryptError> {
+    let mut parts = value
+        .strip_prefix(ENCRYPTED_PREFIX)
+        .and_then(|value| value.strip_prefix(':'))
+        .ok_or(DecryptError::Malformed)?
+        .split(':');
+    let (key_id, wrapped_data_key, ciphertext) =
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(key_id), Some(wrapped_data_key), Some(ciphertext), None) => {
+                (key_id, wrapped_data_key, ciphertext)
+            }
+            _ => return Err(DecryptError::Malformed),
+        };
+    let key = keys.get(key_id).ok_or_else(|| DecryptError::UnknownKey {
+        key_id: key_id.to_owned(),
+    })?;
+    let wrapped_data_key = BASE64_STANDARD
+        .decode(wrapped_data_key)
+        .map_err(|_| DecryptError::Malformed)?;
+    let ciphertext = BASE64_STANDARD
+        .decode(ciphertext)
+        .map_err(|_| DecryptError::Malformed)?;
+
+    let invalid = || DecryptError::Invalid {
+        key_id: key_id.to_owned(),
+    };
+    let data_key = open(key, key_id.as_bytes(), &wrapped_data_key).ok_or_else(invalid)?;
+    open(&data_key, key_id.as_bytes(), &ciphertext).ok_or_else(invalid)
+}
+
+/// Encrypts `plaintext` with AES-256-GCM, returning the nonce, the ciphertext and the tag.
+fn seal(key: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+    let mut nonce = [0; NONCE_LENGTH];
+    rand_bytes(&mut nonce)?;
+    let mut tag = [0; TAG_LENGTH];
+    let ciphertext = encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce),
+        aad,
+        plaintext,
+        &mut tag,
+    )?;
+
+    let mut sealed = Vec::with_capacity(NONCE_LENGTH + ciphertext.len() + TAG_LENGTH);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    sealed.extend_from_slice(&tag);
+    Ok(sealed)
+}
+
+/// Decrypts the output of `seal`, or returns `None` if it wasn't sealed with `key` and `aad`.
+fn open(key: &[u8], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() < NONCE_LENGTH + TAG_LENGTH {
+        return None;
+    }
+    let (nonce, rest) = sealed.split_at(NONCE_LENGTH);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LENGTH);
+    decrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(nonce),
+        aad,
+        ciphertext,
+        tag,
+    )
+    .ok()
+}
+
+/// Replaces the letters and digits of `value` with `character`, except for the first
+/// `keep_first` and the last `keep_last` of them, leaving the other characters, such as the
+/// separators, in place.
+pub(super) fn mask(value: &str, keep_first: usize, keep_last: usize, character: char) -> String {
+    let total = value.chars().filter(|c| c.is_alphanumeric()).count();
+    let mut index = 0;
+    value
+        .chars()
+        .map(|c| {
+            if !c.is_alphanumeric() {
+                return c;
+            }
+            let kept = index < keep_first || index + keep_last >= total;
+            index += 1;
+            if kept {
+                c
+            } else {
+                character
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> HashMap<String, Vec<u8>> {
+        HashMap::from([
+            ("2023".to_owned(), vec![1; KEY_LENGTH]),
+            ("2024".to_owned(), vec![2; KEY_LENGTH]),
+        ])
+    }
+
+    #[test]
+    fn tokens_are_deterministic() {
+        let token = tokenize("2023", &[1; KEY_LENGTH], b"jane@example.com").unwrap();
+        assert!(token.starts_with("tok:2023:"));
+        assert_eq!(
+            token,
+            tokenize("2023", &[1; KEY_LENGTH], b"jane@example.com").unwrap()
+        );
+        assert_ne!(
+            token,
+            tokenize("2023", &[1; KEY_LENGTH], b"john@example.com").unwrap()
+        );
+        assert_ne!(
+            token[9..],
+            tokenize("2024", &[2; KEY_LENGTH], b"jane@example.com").unwrap()[9..]
+        );
+    }
+
+    #[test]
+    fn decrypts_encrypted_values() {
+        let keys = keys();
+        let encrypted = encrypt("2024", &keys["2024"], b"123-45-6789").unwrap();
+        assert!(encrypted.starts_with("enc:v1:2024:"));
+        assert_ne!(
+            encrypted,
+            encrypt("2024", &keys["2024"], b"123-45-6789").unwrap()
+        );
+        assert_eq!(decrypt(&keys, &encrypted).unwrap(), b"123-45-6789");
+
+        // Tagged with another key.
+        let tampered = encrypted.replacen(":2024:", ":2023:", 1);
+        assert!(matches!(
+            decrypt(&keys, &tampered),
+            Err(DecryptError::Invalid { .. })
+        ));
+        assert!(matches!(
+            decrypt(&HashMap::new(), &encrypted),
+            Err(DecryptError::UnknownKey { .. })
+        ));
+        assert!(matches!(
+            decrypt(&keys, "123-45-6789"),
+            Err(DecryptError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn masks_preserving_the_format() {
+        assert_eq!(
+            mask("4111-1111-1111-1234", 0, 4, '*'),
+            "****-****-****-1234"
+        );
+        assert_eq!(mask("jane@example.com", 1, 0, '#'), "j###@#######.###");
+        assert_eq!(mask("42", 2, 2, '*'), "42");
+    }
+}
@@ -0,0 +1,391 @@
+//! The `protect_fields` transform.
+//!
+//! Tokenizes, masks, or encrypts the values of selected fields, so the personal data in the events
+//! is protected before they leave the host.
+use std::collections::HashMap;
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use indexmap::IndexMap;
+use lookup::lookup_v2::{ConfigTargetPath, OwnedTargetPath};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, LogEvent, Value},
+    internal_events::FieldProtectionError,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+mod crypto;
+
+/// Configuration for the `protect_fields` transform.
+#[configurable_component(transform(
+    "protect_fields",
+    "Tokenize, mask, or encrypt the values of fields containing personal data."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ProtectFieldsConfig {
+    /// A map of key IDs to the keys used to tokenize and encrypt the fields.
+    ///
+    /// Each key is 32 bytes, encoded in Base64, and is best retrieved from a secrets backend,
+    /// with `SECRET[<backend>.<secret>]`. The protected values are tagged with the ID of their key,
+    /// so the previous keys can be kept here to decrypt the values protected before a rotation.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "A key, encoded in Base64."))]
+    #[configurable(metadata(docs::examples = "example_keys()"))]
+    pub keys: IndexMap<String, SensitiveString>,
+
+    /// The fields to protect, and how.
+    ///
+    /// The fields are protected in order, and the fields missing from an event are skipped.
+    pub fields: Vec<FieldConfig>,
+}
+
+fn example_keys() -> IndexMap<String, String> {
+    IndexMap::from([("2024".to_owned(), "SECRET[vault.pii_key_2024]".to_owned())])
+}
+
+/// How to protect a field.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct FieldConfig {
+    /// The field to protect.
+    #[configurable(metadata(docs::examples = "user.email"))]
+    pub field: ConfigTargetPath,
+
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+/// The protection to apply to a field.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "action", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The protection to apply to the field."))]
+pub enum Action {
+    /// Replaces the value with a token, the HMAC-SHA256 of the value.
+    ///
+    /// The tokens of the same value are the same, so the events can still be correlated, but the
+    /// value can't be recovered from its token.
+    Tokenize(KeyConfig),
+
+    /// Replaces the letters and digits of the value with a character, keeping its format.
+    Mask(MaskConfig),
+
+    /// Replaces the value with its encryption with a new data key, itself encrypted with the key.
+    ///
+    /// The value is encrypted in JSON, so `decrypt` restores its type, except for the timestamps
+    /// which are restored as strings.
+    Encrypt(KeyConfig),
+
+    /// Replaces an encrypted value with its decryption, with the key whose ID it is tagged with.
+    Decrypt,
+}
+
+/// The key to protect a field with.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct KeyConfig {
+    /// The ID of the key, in `keys`.
+    #[configurable(metadata(docs::examples = "2024"))]
+    pub key_id: String,
+}
+
+/// How a field is masked.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct MaskConfig {
+    /// The number of leading letters and digits to leave unmasked.
+    #[serde(default)]
+    pub keep_first: usize,
+
+    /// The number of trailing letters and digits to leave unmasked.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 4))]
+    pub keep_last: usize,
+
+    /// The character to mask the letters and digits with.
+    #[serde(default = "default_mask_character")]
+    pub character: char,
+}
+
+const fn default_mask_character() -> char {
+    '*'
+}
+
+impl GenerateConfig for ProtectFieldsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"keys.default = "${PII_KEY}"
+
+            [[fields]]
+            field = "user.email"
+            action = "tokenize"
+            key_id = "default"
+
+            [[fields]]
+            field = "card_number"
+            action = "mask"
+            keep_last = 4"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "protect_fields")]
+impl TransformConfig for ProtectFieldsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(ProtectFields::new(self)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, input: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        // The protected fields become strings, or get their type back when decrypted.
+        let schema_definition = schema::Definition::default_for_namespace(input.log_namespaces());
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Field {
+    path: OwnedTargetPath,
+    action: Action,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProtectFields {
+    keys: HashMap<String, Vec<u8>>,
+    fields: Vec<Field>,
+}
+
+impl ProtectFields {
+    pub fn new(config: &ProtectFieldsConfig) -> crate::Result<Self> {
+        let mut keys = HashMap::new();
+        for (key_id, key) in &config.keys {
+            if key_id.contains(':') {
+                return Err(format!("Invalid key ID {:?}: it can't contain `:`.", key_id).into());
+            }
+            let key = BASE64_STANDARD
+                .decode(key.inner().trim())
+                .map_err(|error| format!("Invalid key {:?}: {}", key_id, error))?;
+            if key.len() != crypto::KEY_LENGTH {
+                return Err(format!(
+                    "Invalid key {:?}: it must be {} bytes long, not {}.",
+                    key_id,
+                    crypto::KEY_LENGTH,
+                    key.len()
+                )
+                .into());
+            }
+            keys.insert(key_id.clone(), key);
+        }
+
+        for field in &config.fields {
+            if let Action::Tokenize(key) | Action::Encrypt(key) = &field.action {
+                if !keys.contains_key(&key.key_id) {
+                    return Err(format!(
+                        "The key {:?} of the field {:?} isn't configured.",
+                        key.key_id,
+                        field.field.0.to_string()
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(Self {
+            keys,
+            fields: config
+                .fields
+                .iter()
+                .map(|field| Field {
+                    path: field.field.0.clone(),
+                    action: field.action.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Protects the fields of `log`, returning `false` if one of them couldn't be protected.
+    fn protect(&self, log: &mut LogEvent) -> bool {
+        for field in &self.fields {
+            let value = match log.get(&field.path) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            match self.apply(&field.action, value) {
+                Ok(protected) => {
+                    log.insert(&field.path, protected);
+                }
+                // The encrypted value is left as is, as it is still protected.
+                Err(error) if matches!(field.action, Action::Decrypt) => {
+                    emit!(FieldProtectionError {
+                        field: &field.path.to_string(),
+                        error,
+                        drop_event: false,
+                    });
+                }
+                Err(error) => {
+                    emit!(FieldProtectionError {
+                        field: &field.path.to_string(),
+                        error,
+                        drop_event: true,
+                    });
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn apply(&self, action: &Action, value: &Value) -> crate::Result<Value> {
+        match action {
+            Action::Tokenize(key) => {
+                let plaintext = match value {
+                    Value::Bytes(bytes) => bytes.to_vec(),
+                    value => serde_json::to_vec(value)?,
+                };
+                let token = crypto::tokenize(&key.key_id, &self.keys[&key.key_id], &plaintext)?;
+                Ok(token.into())
+            }
+            Action::Mask(mask) => Ok(crypto::mask(
+                &value.to_string_lossy(),
+                mask.keep_first,
+                mask.keep_last,
+                mask.character,
+            )
+            .into()),
+            Action::Encrypt(key) => {
+                let plaintext = serde_json::to_vec(value)?;
+                let encrypted = crypto::encrypt(&key.key_id, &self.keys[&key.key_id], &plaintext)?;
+                Ok(encrypted.into())
+            }
+            Action::Decrypt => {
+                let encrypted = value.as_str().ok_or("The value isn't a string.")?;
+                let plaintext = crypto::decrypt(&self.keys, &encrypted)?;
+                Ok(serde_json::from_slice(&plaintext)?)
+            }
+        }
+    }
+}
+
+impl FunctionTransform for ProtectFields {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let mut log = event.into_log();
+        // Dropped rather than sent with a field left unprotected.
+        if self.protect(&mut log) {
+            output.push(Event::from(log));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ProtectFieldsConfig>();
+    }
+
+    fn protect_fields(fields: &str) -> ProtectFields {
+        let config = format!(
+            r#"keys.2024 = "{}"
+            {}"#,
+            BASE64_STANDARD.encode([7; crypto::KEY_LENGTH]),
+            fields
+        );
+        ProtectFields::new(&toml::from_str(&config).unwrap()).unwrap()
+    }
+
+    fn log(value: serde_json::Value) -> Event {
+        Event::from(LogEvent::try_from(value).unwrap())
+    }
+
+    #[test]
+    fn protects_fields() {
+        let mut transform = protect_fields(
+            r#"[[fields]]
+            field = "email"
+            action = "tokenize"
+            key_id = "2024"
+
+            [[fields]]
+            field = "card_number"
+            action = "mask"
+            keep_last = 4
+
+            [[fields]]
+            field = "ssn"
+            action = "encrypt"
+            key_id = "2024""#,
+        );
+        let event = log(serde_json::json!({
+            "email": "jane@example.com",
+            "card_number": "4111 1111 1111 1234",
+            "ssn": "123-45-6789",
+        }));
+
+        let event = transform_one(&mut transform, event).unwrap();
+        let log = event.as_log();
+        let token = log["email"].to_string_lossy();
+        assert!(token.starts_with("tok:2024:"));
+        assert_eq!(log["card_number"], "**** **** **** 1234".into());
+        let encrypted = log["ssn"].to_string_lossy();
+        assert!(encrypted.starts_with("enc:v1:2024:"));
+
+        let mut decrypt = protect_fields(
+            r#"[[fields]]
+            field = "ssn"
+            action = "decrypt""#,
+        );
+        let event = transform_one(&mut decrypt, event).unwrap();
+        assert_eq!(event.as_log()["ssn"], "123-45-6789".into());
+    }
+
+    #[test]
+    fn keeps_the_type_of_encrypted_values() {
+        let mut transform = protect_fields(
+            r#"[[fields]]
+            field = "account"
+            action = "encrypt"
+            key_id = "2024"
+
+            [[fields]]
+            field = "account"
+            action = "decrypt""#,
+        );
+        let event = log(serde_json::json!({
+            "account": { "id": 42, "roles": ["admin"] },
+        }));
+
+        let event = transform_one(&mut transform, event).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["account.id"], 42.into());
+        assert_eq!(log["account.roles[0]"], "admin".into());
+    }
+
+    #[test]
+    fn requires_the_keys_of_the_fields() {
+        let config = toml::from_str::<ProtectFieldsConfig>(
+            r#"[[fields]]
+            field = "email"
+            action = "tokenize"
+            key_id = "2024""#,
+        )
+        .unwrap();
+        assert!(ProtectFields::new(&config).is_err());
+    }
+}
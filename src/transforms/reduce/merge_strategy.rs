@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
 use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
 
 use crate::event::{LogEvent, Value};
@@ -71,6 +72,12 @@ impl ReduceValueMerger for DiscardMerger {
         v.insert(k.as_str(), self.v);
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::Discard {
+            value: self.v.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -97,6 +104,12 @@ impl ReduceValueMerger for RetainMerger {
         v.insert(k.as_str(), self.v);
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::Retain {
+            value: self.v.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -137,6 +150,13 @@ impl ReduceValueMerger for ConcatMerger {
         v.insert(k.as_str(), Value::Bytes(self.v.into()));
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::Concat {
+            value: self.v.clone().freeze(),
+            join_by: self.join_by.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -164,6 +184,12 @@ impl ReduceValueMerger for ConcatArrayMerger {
         v.insert(k.as_str(), Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::ConcatArray {
+            values: self.v.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -187,6 +213,12 @@ impl ReduceValueMerger for ArrayMerger {
         v.insert(k.as_str(), Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::Array {
+            values: self.v.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -219,6 +251,12 @@ impl ReduceValueMerger for LongestArrayMerger {
         v.insert(k.as_str(), Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::LongestArray {
+            values: self.v.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -251,6 +289,12 @@ impl ReduceValueMerger for ShortestArrayMerger {
         v.insert(k.as_str(), Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::ShortestArray {
+            values: self.v.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -296,6 +340,12 @@ impl ReduceValueMerger for FlatUniqueMerger {
         v.insert(k.as_str(), Value::Array(self.v.into_iter().collect()));
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::FlatUnique {
+            values: self.v.iter().cloned().collect(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -331,6 +381,13 @@ impl ReduceValueMerger for TimestampWindowMerger {
         v.insert(k.as_str(), Value::Timestamp(self.started));
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::TimestampWindow {
+            started: self.started,
+            latest: self.latest,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -339,6 +396,28 @@ enum NumberMergerValue {
     Float(NotNan<f64>),
 }
 
+impl From<&NumberMergerValue> for NumberState {
+    fn from(v: &NumberMergerValue) -> Self {
+        match v {
+            NumberMergerValue::Int(i) => Self::Int(*i),
+            NumberMergerValue::Float(f) => Self::Float(f.into_inner()),
+        }
+    }
+}
+
+impl TryFrom<NumberState> for NumberMergerValue {
+    type Error = String;
+
+    fn try_from(v: NumberState) -> Result<Self, Self::Error> {
+        match v {
+            NumberState::Int(i) => Ok(Self::Int(i)),
+            NumberState::Float(f) => NotNan::new(f)
+                .map(Self::Float)
+                .map_err(|_| "expected number value, found: NaN".to_owned()),
+        }
+    }
+}
+
 impl From<i64> for NumberMergerValue {
     fn from(v: i64) -> Self {
         NumberMergerValue::Int(v)
@@ -394,6 +473,12 @@ impl ReduceValueMerger for AddNumbersMerger {
         };
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::AddNumbers {
+            value: (&self.v).into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -453,6 +538,12 @@ impl ReduceValueMerger for MaxNumberMerger {
         };
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::MaxNumber {
+            value: (&self.v).into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -512,11 +603,19 @@ impl ReduceValueMerger for MinNumberMerger {
         };
         Ok(())
     }
+
+    fn state(&self) -> MergerState {
+        MergerState::MinNumber {
+            value: (&self.v).into(),
+        }
+    }
 }
 
 pub trait ReduceValueMerger: std::fmt::Debug + Send + Sync {
     fn add(&mut self, v: Value) -> Result<(), String>;
     fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String>;
+    /// Returns the state of the merger, to save it in the data directory.
+    fn state(&self) -> MergerState;
 }
 
 impl From<Value> for Box<dyn ReduceValueMerger> {
@@ -535,6 +634,84 @@ impl From<Value> for Box<dyn ReduceValueMerger> {
     }
 }
 
+/// The state of a merger, as saved in the data directory.
+///
+/// Values are saved as JSON, so a timestamp kept by a strategy other than the default one for
+/// timestamps is restored as a string.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "merger", rename_all = "snake_case")]
+pub enum MergerState {
+    Discard {
+        value: Value,
+    },
+    Retain {
+        value: Value,
+    },
+    Concat {
+        value: Bytes,
+        join_by: Option<Vec<u8>>,
+    },
+    ConcatArray {
+        values: Vec<Value>,
+    },
+    Array {
+        values: Vec<Value>,
+    },
+    LongestArray {
+        values: Vec<Value>,
+    },
+    ShortestArray {
+        values: Vec<Value>,
+    },
+    FlatUnique {
+        values: Vec<Value>,
+    },
+    TimestampWindow {
+        started: DateTime<Utc>,
+        latest: DateTime<Utc>,
+    },
+    AddNumbers {
+        value: NumberState,
+    },
+    MaxNumber {
+        value: NumberState,
+    },
+    MinNumber {
+        value: NumberState,
+    },
+}
+
+/// A number held by a merger, as saved in the data directory.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberState {
+    Int(i64),
+    Float(f64),
+}
+
+/// Restores a merger from the state it saved.
+pub(crate) fn merger_from_state(state: MergerState) -> Result<Box<dyn ReduceValueMerger>, String> {
+    Ok(match state {
+        MergerState::Discard { value } => Box::new(DiscardMerger::new(value)),
+        MergerState::Retain { value } => Box::new(RetainMerger::new(value)),
+        MergerState::Concat { value, join_by } => Box::new(ConcatMerger {
+            v: BytesMut::from(&value[..]),
+            join_by,
+        }),
+        MergerState::ConcatArray { values } => Box::new(ConcatArrayMerger::new(values)),
+        MergerState::Array { values } => Box::new(ArrayMerger { v: values }),
+        MergerState::LongestArray { values } => Box::new(LongestArrayMerger::new(values)),
+        MergerState::ShortestArray { values } => Box::new(ShortestArrayMerger::new(values)),
+        MergerState::FlatUnique { values } => Box::new(FlatUniqueMerger::new(Value::Array(values))),
+        MergerState::TimestampWindow { started, latest } => {
+            Box::new(TimestampWindowMerger { started, latest })
+        }
+        MergerState::AddNumbers { value } => Box::new(AddNumbersMerger::new(value.try_into()?)),
+        MergerState::MaxNumber { value } => Box::new(MaxNumberMerger::new(value.try_into()?)),
+        MergerState::MinNumber { value } => Box::new(MinNumberMerger::new(value.try_into()?)),
+    })
+}
+
 pub(crate) fn get_value_merger(
     v: Value,
     m: &MergeStrategy,
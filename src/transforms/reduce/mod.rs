@@ -3,15 +3,18 @@ use std::{
     cmp::min,
     collections::{hash_map, HashMap},
     num::NonZeroUsize,
+    path::PathBuf,
     pin::Pin,
     time::{Duration, Instant},
 };
 
 use async_stream::stream;
+use chrono::{DateTime, Utc};
 use futures::{stream, Stream, StreamExt};
 use indexmap::IndexMap;
 use lookup::lookup_v2::parse_target_path;
 use lookup::PathPrefix;
+use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use vector_config::configurable_component;
 
@@ -19,9 +22,9 @@ use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext},
     event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
-    internal_events::ReduceStaleEventFlushed,
+    internal_events::{ReduceStaleEventFlushed, ReduceStateError},
     schema,
-    transforms::{TaskTransform, Transform},
+    transforms::{state::StateFile, TaskTransform, Transform},
 };
 
 mod merge_strategy;
@@ -102,6 +105,24 @@ pub struct ReduceConfig {
     /// If this condition resolves to `true` for an event, the previous transaction is flushed
     /// (without this event) and a new transaction is started.
     pub starts_when: Option<AnyCondition>,
+
+    /// Whether to save the events being reduced in the data directory, so that they're restored
+    /// after Vector restarts.
+    ///
+    /// The events are saved periodically. When the transform stops, they're saved instead of
+    /// being flushed, so that the next run of the transform keeps reducing them. If Vector
+    /// crashes, the events reduced since the last save are lost, and the ones flushed since then
+    /// are flushed again.
+    #[serde(default)]
+    pub persist: bool,
+
+    /// The directory used to save the events being reduced when `persist` is enabled.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub data_dir: Option<PathBuf>,
 }
 
 const fn default_expire_after_ms() -> Duration {
@@ -118,7 +139,18 @@ impl_generate_config_from_default!(ReduceConfig);
 #[typetag::serde(name = "reduce")]
 impl TransformConfig for ReduceConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Reduce::new(self, &context.enrichment_tables).map(Transform::event_task)
+        let state = if self.persist && !context.canary {
+            let id = context.key.as_ref().map_or("reduce", |key| key.id());
+            let data_dir = context
+                .globals
+                .resolve_and_make_data_subdir(self.data_dir.as_ref(), id)?;
+            Some(StateFile::new(data_dir.join(STATE_FILENAME)))
+        } else {
+            None
+        };
+        Reduce::new(self, &context.enrichment_tables)
+            .map(|reduce| reduce.with_state(state))
+            .map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -213,6 +245,11 @@ impl TransformConfig for ReduceConfig {
     }
 }
 
+const STATE_FILENAME: &str = "reduce_state.json";
+
+/// How often the events being reduced are saved when `persist` is enabled.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Debug)]
 struct ReduceState {
     events: usize,
@@ -284,6 +321,67 @@ impl ReduceState {
         self.events = 0;
         event
     }
+
+    fn to_record(&self, discriminant: &Discriminant) -> ReduceStateRecord {
+        ReduceStateRecord {
+            discriminant: discriminant.clone(),
+            events: self.events,
+            fields: self
+                .fields
+                .iter()
+                .map(|(k, v)| (k.clone(), v.state()))
+                .collect(),
+            stale_since: instant_to_utc(self.stale_since),
+            last_flushed_at: instant_to_utc(self.last_flushed_at),
+            metadata: self.metadata.value().clone(),
+        }
+    }
+
+    fn from_record(record: ReduceStateRecord) -> Result<(Discriminant, Self), String> {
+        let fields = record
+            .fields
+            .into_iter()
+            .map(|(k, v)| Ok((k, merger_from_state(v)?)))
+            .collect::<Result<_, String>>()?;
+        Ok((
+            record.discriminant,
+            Self {
+                events: record.events,
+                fields,
+                stale_since: utc_to_instant(record.stale_since),
+                last_flushed_at: utc_to_instant(record.last_flushed_at),
+                metadata: EventMetadata::default_with_value(record.metadata),
+            },
+        ))
+    }
+}
+
+/// The state of a group of events being reduced, as saved in the data directory.
+#[derive(Debug, Deserialize, Serialize)]
+struct ReduceStateRecord {
+    discriminant: Discriminant,
+    events: usize,
+    fields: HashMap<String, MergerState>,
+    stale_since: DateTime<Utc>,
+    last_flushed_at: DateTime<Utc>,
+    metadata: Value,
+}
+
+/// Converts an instant to the time it happened at, so that the time since then still counts
+/// across restarts.
+fn instant_to_utc(instant: Instant) -> DateTime<Utc> {
+    chrono::Duration::from_std(instant.elapsed())
+        .ok()
+        .and_then(|elapsed| Utc::now().checked_sub_signed(elapsed))
+        .unwrap_or_else(Utc::now)
+}
+
+fn utc_to_instant(time: DateTime<Utc>) -> Instant {
+    // A clock going backwards counts as no time having passed.
+    let elapsed = (Utc::now() - time).to_std().unwrap_or_default();
+    Instant::now()
+        .checked_sub(elapsed)
+        .unwrap_or_else(Instant::now)
 }
 
 pub struct Reduce {
@@ -295,6 +393,7 @@ pub struct Reduce {
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
     max_events: Option<usize>,
+    state: Option<StateFile>,
 }
 
 impl Reduce {
@@ -328,9 +427,58 @@ impl Reduce {
             ends_when,
             starts_when,
             max_events,
+            state: None,
         })
     }
 
+    fn with_state(self, state: Option<StateFile>) -> Self {
+        Self { state, ..self }
+    }
+
+    /// Restores the events being reduced saved by a previous run of the transform.
+    fn load_state(&mut self) {
+        let state = match self.state.as_ref() {
+            Some(state) => state,
+            None => return,
+        };
+        let records = match state.load::<Vec<ReduceStateRecord>>() {
+            Ok(records) => records,
+            Err(error) => {
+                emit!(ReduceStateError {
+                    path: state.path(),
+                    error
+                });
+                return;
+            }
+        };
+        for record in records {
+            match ReduceState::from_record(record) {
+                Ok((discriminant, reduce_state)) => {
+                    self.reduce_merge_states.insert(discriminant, reduce_state);
+                }
+                Err(error) => warn!(message = "Failed to restore reduced event.", %error),
+            }
+        }
+    }
+
+    fn save_state(&self) {
+        let state = match self.state.as_ref() {
+            Some(state) => state,
+            None => return,
+        };
+        let records = self
+            .reduce_merge_states
+            .iter()
+            .map(|(discriminant, reduce_state)| reduce_state.to_record(discriminant))
+            .collect::<Vec<_>>();
+        if let Err(error) = state.save(&records) {
+            emit!(ReduceStateError {
+                path: state.path(),
+                error
+            });
+        }
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let mut flush_discriminants = Vec::new();
         let now = Instant::now();
@@ -429,9 +577,18 @@ impl TaskTransform<Event> for Reduce {
         let poll_period = me.flush_period;
 
         let mut flush_stream = tokio::time::interval(poll_period);
+        let mut save_stream = tokio::time::interval(SAVE_INTERVAL);
 
         Box::pin(
             stream! {
+              // Held until the transform stops, so that a new instance of the transform doesn't
+              // load the state before this one saved it.
+              let _state_lock = match me.state.as_ref() {
+                  Some(state) => Some(state.lock().await),
+                  None => None,
+              };
+              me.load_state();
+
               loop {
                 let mut output = Vec::new();
                 let done = tokio::select! {
@@ -439,10 +596,18 @@ impl TaskTransform<Event> for Reduce {
                       me.flush_into(&mut output);
                       false
                     }
+                    _ = save_stream.tick(), if me.state.is_some() => {
+                      me.save_state();
+                      false
+                    }
                     maybe_event = input_rx.next() => {
                       match maybe_event {
                         None => {
-                          me.flush_all_into(&mut output);
+                          if me.state.is_some() {
+                              me.save_state();
+                          } else {
+                              me.flush_all_into(&mut output);
+                          }
                           true
                         }
                         Some(event) => {
@@ -480,6 +645,58 @@ mod test {
         crate::test_util::test_generate_config::<ReduceConfig>();
     }
 
+    #[tokio::test]
+    async fn reduce_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let reduce_config = toml::from_str::<ReduceConfig>(&format!(
+            r#"
+group_by = [ "request_id" ]
+persist = true
+data_dir = {:?}
+
+[merge_strategies]
+  message = "concat"
+"#,
+            dir.path()
+        ))
+        .unwrap();
+        let reduce = reduce_config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_task();
+
+        let event = |message: &str, counter: i64| {
+            let mut event = LogEvent::from(message);
+            event.insert("request_id", "1");
+            event.insert("counter", counter);
+            Event::from(event)
+        };
+        let inputs = vec![event("first", 1), event("second", 2)];
+
+        // The events being reduced are saved instead of being flushed when the transform stops.
+        let out = reduce
+            .transform_events(Box::pin(stream::iter(inputs)))
+            .collect::<Vec<_>>()
+            .await;
+        assert!(out.is_empty());
+
+        // The next run of the transform keeps reducing them.
+        let state = StateFile::new(dir.path().join("reduce").join(STATE_FILENAME));
+        let mut reduce = Reduce::new(&reduce_config, &Default::default())
+            .unwrap()
+            .with_state(Some(state));
+        reduce.load_state();
+        let mut out = vec![];
+        reduce.transform_one(&mut out, event("third", 3));
+        reduce.flush_all_into(&mut out);
+        assert_eq!(out.len(), 1);
+        let log = out[0].as_log();
+        assert_eq!(log["message"], "first second third".into());
+        assert_eq!(log["counter"], Value::from(6));
+        assert_eq!(log["request_id"], "1".into());
+    }
+
     #[tokio::test]
     async fn reduce_from_condition() {
         let reduce_config = toml::from_str::<ReduceConfig>(
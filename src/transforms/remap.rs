@@ -1,22 +1,27 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{
     collections::BTreeMap,
     fs::File,
     io::{self, Read},
     path::PathBuf,
+    time::Instant,
 };
 
 use codecs::MetricTagValues;
 use lookup::lookup_v2::{parse_value_path, ValuePath};
 use lookup::{metadata_path, owned_value_path, path, OwnedTargetPath, PathPrefix};
 use snafu::{ResultExt, Snafu};
+use tokio::sync::watch;
 use value::Kind;
-use vector_common::TimeZone;
+use vector_common::{
+    internal_event::{InternalEventHandle as _, Registered},
+    TimeZone,
+};
 use vector_config::configurable_component;
 use vector_core::compile_vrl;
 use vector_core::config::LogNamespace;
 use vector_core::schema::Definition;
-use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vector_vrl_functions::{library, set_semantic_meaning::MeaningList};
 use vrl::prelude::state::TypeState;
 use vrl::{
     diagnostic::{Formatter, Note},
@@ -27,9 +32,10 @@ use vrl::{
 use crate::{
     config::{
         log_schema, ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
+        TransformReload,
     },
-    event::{Event, TargetEvents, VrlTarget},
-    internal_events::{RemapMappingAbort, RemapMappingError},
+    event::{Event, EventArray, EventContainer, MetricFields, TargetEvents, VrlTarget},
+    internal_events::{RemapMappingAbort, RemapMappingError, RemapProgramExecuted},
     schema,
     transforms::{SyncTransform, Transform, TransformOutputsBuf},
     Result,
@@ -67,6 +73,21 @@ pub struct RemapConfig {
     #[configurable(metadata(docs::examples = "./my/program.vrl"))]
     pub file: Option<PathBuf>,
 
+    /// File paths to [Vector Remap Language][vrl] (VRL) libraries, defining functions that the
+    /// program can call.
+    ///
+    /// A library defines functions with `fn name(argument, ...) { ... }`, whose body is a VRL
+    /// program returning the result of the function. The body can only access the arguments of
+    /// the function, not the event. Functions can call the functions defined before them,
+    /// including those of the previous libraries.
+    ///
+    /// If a relative path is provided, its root is the current working directory.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "./common.vrl"))]
+    pub libraries: Vec<PathBuf>,
+
     /// When set to `single`, metric tag values are exposed as single strings, the
     /// same as they were before this config option. Tags with multiple values show the last assigned value, and null values
     /// are ignored.
@@ -131,8 +152,16 @@ pub struct RemapConfig {
     #[configurable(derived, metadata(docs::hidden))]
     #[serde(default)]
     pub runtime: VrlRuntime,
+
+    /// Sends new programs to the running transform, when it's reloaded without being rebuilt.
+    #[serde(skip)]
+    pub program_updates: ProgramUpdates,
 }
 
+/// The sending side of the programs run by the transform built from a configuration.
+#[derive(Clone, Debug, Default)]
+pub struct ProgramUpdates(Arc<Mutex<Option<watch::Sender<Program>>>>);
+
 impl RemapConfig {
     fn compile_vrl_program(
         &self,
@@ -159,9 +188,24 @@ impl RemapConfig {
             _ => return Err(Box::new(BuildError::SourceAndOrFile)),
         };
 
-        let mut functions = vrl_stdlib::all();
-        functions.append(&mut enrichment::vrl_functions());
-        functions.append(&mut vector_vrl_functions::vrl_functions());
+        let compile_config = || {
+            let mut config = CompileConfig::default();
+            config.set_custom(enrichment_tables.clone());
+            config.set_custom(MeaningList::default());
+            config
+        };
+
+        let mut functions = crate::vrl_functions();
+        for path in &self.libraries {
+            let library =
+                std::fs::read_to_string(path).with_context(|_| LibraryReadFailedSnafu { path })?;
+            library::load(&library, &mut functions, &compile_config).map_err(|message| {
+                BuildError::LibraryLoadFailed {
+                    path: path.clone(),
+                    message,
+                }
+            })?;
+        }
 
         let state = TypeState {
             local: Default::default(),
@@ -170,12 +214,7 @@ impl RemapConfig {
                 merged_schema_definition.metadata_kind().clone(),
             ),
         };
-        let mut config = CompileConfig::default();
-
-        config.set_custom(enrichment_tables);
-        config.set_custom(MeaningList::default());
-
-        compile_vrl(&source, &functions, &state, config)
+        compile_vrl(&source, &functions, &state, compile_config())
             .map_err(|diagnostics| {
                 Formatter::new(&source, diagnostics)
                     .colored()
@@ -201,7 +240,8 @@ impl TransformConfig for RemapConfig {
     async fn build(&self, context: &TransformContext) -> Result<Transform> {
         let (transform, warnings) = match self.runtime {
             VrlRuntime::Ast => {
-                let (remap, warnings) = Remap::new_ast(self.clone(), context)?;
+                let (mut remap, warnings) = Remap::new_ast(self.clone(), context)?;
+                remap.subscribe_program_updates(&self.program_updates);
                 (Transform::synchronous(remap), warnings)
             }
         };
@@ -316,6 +356,52 @@ impl TransformConfig for RemapConfig {
     fn enable_concurrency(&self) -> bool {
         true
     }
+
+    fn reload(
+        &self,
+        new: &dyn TransformConfig,
+        context: &TransformContext,
+    ) -> Result<Option<TransformReload>> {
+        // The running transform has stopped, so it has to be rebuilt.
+        let sender = match self.program_updates.0.lock().unwrap().clone() {
+            Some(sender) if !sender.is_closed() => sender,
+            _ => return Ok(None),
+        };
+
+        // Only a change to the program itself can be applied to the running transform, so compare
+        // everything else.
+        let mut old_fields = serde_json::to_value(&*self as &dyn TransformConfig)?;
+        let mut new_fields = serde_json::to_value(new)?;
+        let (source, file) = match (old_fields.as_object_mut(), new_fields.as_object_mut()) {
+            (Some(old), Some(new)) => {
+                old.remove("source");
+                old.remove("file");
+                (new.remove("source"), new.remove("file"))
+            }
+            _ => return Ok(None),
+        };
+        if old_fields != new_fields {
+            return Ok(None);
+        }
+
+        let mut config = self.clone();
+        config.source = source.map(serde_json::from_value).transpose()?.flatten();
+        config.file = file.map(serde_json::from_value).transpose()?.flatten();
+
+        let (program, warnings, _, _) = config.compile_vrl_program(
+            context.enrichment_tables.clone(),
+            context.merged_schema_definition.clone(),
+        )?;
+        if !warnings.is_empty() {
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        // The new configuration shares the program updates of this one, so that the running
+        // transform can be reloaded again from it.
+        Ok(Some(TransformReload::new(Box::new(config), move || {
+            let _ = sender.send(program);
+        })))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -333,6 +419,8 @@ where
     dropped_schema_definition: Arc<schema::Definition>,
     runner: Runner,
     metric_tag_values: MetricTagValues,
+    program_updates: Option<watch::Receiver<Program>>,
+    program_executed: Registered<RemapProgramExecuted>,
 }
 
 pub trait VrlRunner {
@@ -423,9 +511,18 @@ where
             dropped_schema_definition: Arc::new(dropped_schema_definition),
             runner,
             metric_tag_values: config.metric_tag_values,
+            program_updates: None,
+            program_executed: register!(RemapProgramExecuted),
         })
     }
 
+    /// Runs the programs sent through the given updates from now on, instead of the current one.
+    fn subscribe_program_updates(&mut self, updates: &ProgramUpdates) {
+        let (sender, receiver) = watch::channel(self.program.clone());
+        *updates.0.lock().unwrap() = Some(sender);
+        self.program_updates = Some(receiver);
+    }
+
     #[cfg(test)]
     const fn runner(&self) -> &Runner {
         &self.runner
@@ -490,7 +587,10 @@ where
     }
 
     fn run_vrl(&mut self, target: &mut VrlTarget) -> std::result::Result<value::Value, Terminate> {
-        self.runner.run(target, &self.program, &self.timezone)
+        let start = Instant::now();
+        let result = self.runner.run(target, &self.program, &self.timezone);
+        self.program_executed.emit(start.elapsed());
+        result
     }
 }
 
@@ -499,6 +599,50 @@ where
     Runner: VrlRunner + Clone + Send + Sync,
 {
     fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let options = self.prepare_batch(matches!(event, Event::Metric(_)));
+        self.transform_event(event, &options, output);
+    }
+
+    fn transform_all(&mut self, events: EventArray, output: &mut TransformOutputsBuf) {
+        // The program and what it requires of each event don't change within a batch, so they are
+        // only looked up once for all of its events. The runtime state of the program is already
+        // reused from one event to the next, only being cleared in between. There is no arena to
+        // reset per batch, as the values the program produces are owned by the events it outputs,
+        // so the allocations of each event are left as they are.
+        let options = self.prepare_batch(matches!(events, EventArray::Metrics(_)));
+        for event in events.into_events() {
+            self.transform_event(event, &options, output);
+        }
+    }
+}
+
+/// What running the program requires of each event of a batch.
+#[derive(Clone, Debug)]
+struct BatchOptions {
+    /// Whether the original event is kept, to be forwarded if the program fails or aborts.
+    keep_original: bool,
+    /// The fields of the metrics accessed by the program.
+    metric_fields: MetricFields,
+    /// Whether the tags of the metrics are exposed with all their values.
+    multi_value_tags: bool,
+}
+
+impl<Runner> Remap<Runner>
+where
+    Runner: VrlRunner,
+{
+    /// Switches to the latest program sent, and decides what running it requires of the events
+    /// of the next batch.
+    ///
+    /// The fields of the metrics accessed by the program are only looked up for batches of
+    /// metrics.
+    fn prepare_batch(&mut self, metrics: bool) -> BatchOptions {
+        if let Some(program_updates) = &mut self.program_updates {
+            if program_updates.has_changed().unwrap_or(false) {
+                self.program = program_updates.borrow_and_update().clone();
+            }
+        }
+
         // If a program can fail or abort at runtime and we know that we will still need to forward
         // the event in that case (either to the main output or `dropped`, depending on the
         // config), we need to clone the original event and keep it around, to allow us to discard
@@ -511,24 +655,37 @@ where
         // the event to the `dropped` output.
         let forward_on_error = !self.drop_on_error || self.reroute_dropped;
         let forward_on_abort = !self.drop_on_abort || self.reroute_dropped;
-        let original_event = if (self.program.info().fallible && forward_on_error)
-            || (self.program.info().abortable && forward_on_abort)
-        {
-            Some(event.clone())
-        } else {
-            None
-        };
-
-        let mut target = VrlTarget::new(
-            event,
-            self.program.info(),
-            match self.metric_tag_values {
+        let info = self.program.info();
+
+        BatchOptions {
+            keep_original: (info.fallible && forward_on_error)
+                || (info.abortable && forward_on_abort),
+            metric_fields: if metrics {
+                MetricFields::new(info)
+            } else {
+                MetricFields::default()
+            },
+            multi_value_tags: match self.metric_tag_values {
                 MetricTagValues::Single => false,
                 MetricTagValues::Full => true,
             },
+        }
+    }
+
+    fn transform_event(
+        &mut self,
+        event: Event,
+        options: &BatchOptions,
+        output: &mut TransformOutputsBuf,
+    ) {
+        let original_event = options.keep_original.then(|| event.clone());
+
+        let mut target = VrlTarget::new_with_metric_fields(
+            event,
+            &options.metric_fields,
+            options.multi_value_tags,
         );
         let result = self.run_vrl(&mut target);
-
         match result {
             Ok(_) => match target.into_events() {
                 TargetEvents::One(event) => {
@@ -549,8 +706,11 @@ where
                         ("abort", error, self.drop_on_abort)
                     }
                     Terminate::Error(error) => {
+                        let message = error.to_string();
                         emit!(RemapMappingError {
-                            error: error.to_string(),
+                            error_code: error.code(),
+                            function: failed_function(&message).map(ToOwned::to_owned),
+                            error: message,
                             event_dropped: self.drop_on_error,
                         });
 
@@ -573,6 +733,14 @@ where
     }
 }
 
+/// Returns the name of the function whose call raised a runtime error, given the error's message.
+fn failed_function(message: &str) -> Option<&str> {
+    message
+        .strip_prefix("function call error for \"")
+        .and_then(|rest| rest.split_once('"'))
+        .map(|(function, _)| function)
+}
+
 #[inline]
 fn push_default(
     mut event: Event,
@@ -608,6 +776,11 @@ pub enum BuildError {
     FileOpenFailed { path: PathBuf, source: io::Error },
     #[snafu(display("Could not read vrl program {:?}: {}", path, source))]
     FileReadFailed { path: PathBuf, source: io::Error },
+
+    #[snafu(display("Could not read vrl library {:?}: {}", path, source))]
+    LibraryReadFailed { path: PathBuf, source: io::Error },
+    #[snafu(display("Could not load vrl library {:?}: {}", path, message))]
+    LibraryLoadFailed { path: PathBuf, message: String },
 }
 
 #[cfg(test)]
@@ -781,6 +954,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_remap_calls_library_functions() {
+        let dir = crate::test_util::temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let first = dir.join("first.vrl");
+        let second = dir.join("second.vrl");
+        std::fs::write(
+            &first,
+            indoc! {r#"
+                # Normalizes a user name.
+                fn normalize_user(value) {
+                    downcase(strip_whitespace(string(value) ?? ""))
+                }
+            "#},
+        )
+        .unwrap();
+        std::fs::write(
+            &second,
+            indoc! {r#"
+                fn user_key(value, domain) {
+                    string!(normalize_user(value)) + "@" + string!(domain)
+                }
+            "#},
+        )
+        .unwrap();
+
+        let conf = RemapConfig {
+            source: Some(".user = user_key!(.user, \"example.com\")".to_owned()),
+            libraries: vec![first.clone(), second.clone()],
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+        let mut event = LogEvent::from("augment me");
+        event.insert("user", " Alice ");
+        let result = transform_one(&mut tform, Event::from(event)).unwrap();
+        assert_eq!(get_field_string(&result, "user"), "alice@example.com");
+
+        // Library functions are only available to the libraries following their own.
+        let conf = RemapConfig {
+            source: Some(".user = user_key!(.user, \"example.com\")".to_owned()),
+            libraries: vec![second, first],
+            ..Default::default()
+        };
+        let err = remap(conf).unwrap_err().to_string();
+        assert!(
+            err.contains("function `user_key` failed to compile"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn check_remap_emits_multiple() {
         let event = {
@@ -1501,6 +1725,148 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reloads_program_in_place() {
+        let config = RemapConfig {
+            source: Some(".foo = 1".to_owned()),
+            ..Default::default()
+        };
+        let context = TransformContext::new_test(HashMap::from([
+            (None, test_default_schema_definition()),
+            (Some(DROPPED.to_owned()), test_dropped_schema_definition()),
+        ]));
+        let (mut tform, _) = Remap::new_ast(config.clone(), &context).unwrap();
+        tform.subscribe_program_updates(&config.program_updates);
+
+        let event = transform_one(&mut tform, LogEvent::default().into()).unwrap();
+        assert_eq!(event.as_log()["foo"], 1.into());
+
+        let new = RemapConfig {
+            source: Some(".foo = 2".to_owned()),
+            ..Default::default()
+        };
+        let reload = config.reload(&new, &context).unwrap().unwrap();
+
+        // The program isn't changed until the reload is applied.
+        let event = transform_one(&mut tform, LogEvent::default().into()).unwrap();
+        assert_eq!(event.as_log()["foo"], 1.into());
+
+        let reloaded = reload.apply();
+        let event = transform_one(&mut tform, LogEvent::default().into()).unwrap();
+        assert_eq!(event.as_log()["foo"], 2.into());
+        assert_eq!(
+            serde_json::to_value(reloaded.as_ref()).unwrap(),
+            serde_json::to_value(&new as &dyn TransformConfig).unwrap()
+        );
+
+        // Other changes require the transform to be rebuilt.
+        let new = RemapConfig {
+            source: Some(".foo = 3".to_owned()),
+            drop_on_error: true,
+            ..Default::default()
+        };
+        assert!(config.reload(&new, &context).unwrap().is_none());
+
+        // So do programs that fail to compile.
+        let new = RemapConfig {
+            source: Some(".foo = ".to_owned()),
+            ..Default::default()
+        };
+        assert!(config.reload(&new, &context).is_err());
+    }
+
+    #[test]
+    fn transforms_batches_of_metrics() {
+        let conf = RemapConfig {
+            source: Some(r#".tags.name = .name"#.to_owned()),
+            drop_on_error: true,
+            drop_on_abort: false,
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        let metrics = ["foo", "bar"]
+            .into_iter()
+            .map(|name| {
+                Metric::new(
+                    name,
+                    MetricKind::Absolute,
+                    MetricValue::Counter { value: 1.0 },
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut outputs =
+            TransformOutputsBuf::new_with_capacity(vec![Output::default(DataType::all())], 2);
+
+        tform.transform_all(EventArray::from(metrics), &mut outputs);
+        let tags = outputs
+            .drain()
+            .map(|event| event.into_metric().tags().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            vec![
+                Some(metric_tags!("name" => "foo")),
+                Some(metric_tags!("name" => "bar")),
+            ]
+        );
+    }
+
+    #[test]
+    fn transforms_batches() {
+        let config = RemapConfig {
+            source: Some(".foo = to_int!(.bar)".to_owned()),
+            drop_on_error: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let context = TransformContext::new_test(HashMap::from([
+            (None, test_default_schema_definition()),
+            (Some(DROPPED.to_owned()), test_dropped_schema_definition()),
+        ]));
+        let (mut tform, _) = Remap::new_ast(config.clone(), &context).unwrap();
+        tform.subscribe_program_updates(&config.program_updates);
+
+        let batch = || {
+            let events = [1.into(), "not a number".into(), 2.into()]
+                .into_iter()
+                .map(|bar: Value| LogEvent::from(btreemap! { "bar" => bar }))
+                .collect::<Vec<_>>();
+            EventArray::from(events)
+        };
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::all()),
+                Output::default(DataType::all()).with_port(DROPPED),
+            ],
+            3,
+        );
+
+        tform.transform_all(batch(), &mut outputs);
+        let foos = outputs
+            .drain()
+            .map(|event| event.as_log()["foo"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(foos, vec![1.into(), 2.into()]);
+        let dropped = outputs.drain_named(DROPPED).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].as_log()["bar"], "not a number".into());
+
+        // A program reloaded in place is run from the next batch on.
+        let new = RemapConfig {
+            source: Some(".foo = 0".to_owned()),
+            ..config.clone()
+        };
+        config.reload(&new, &context).unwrap().unwrap().apply();
+        tform.transform_all(batch(), &mut outputs);
+        let foos = outputs
+            .drain()
+            .map(|event| event.as_log()["foo"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(foos, vec![0.into(), 0.into(), 0.into()]);
+        assert_eq!(outputs.drain_named(DROPPED).count(), 0);
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
@@ -1522,4 +1888,15 @@ mod tests {
         })
         .await
     }
+
+    #[test]
+    fn finds_failed_function() {
+        assert_eq!(
+            failed_function(
+                r#"function call error for "parse_json" at (0:20): unable to parse json: expected value at line 1 column 1"#
+            ),
+            Some("parse_json")
+        );
+        assert_eq!(failed_function("can't abort infallible function"), None);
+    }
 }
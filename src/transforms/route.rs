@@ -13,9 +13,17 @@ use crate::{
 
 pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 
+/// The number of buckets events are hashed into to pick their weighted route, which gives weights
+/// a resolution of `0.01`.
+const WEIGHT_BUCKETS: u64 = 10_000;
+
 #[derive(Clone)]
 pub struct Route {
     conditions: Vec<(String, Condition)>,
+    /// The weighted routes, with the exclusive upper bound of the range of buckets sent to each.
+    weighted: Vec<(String, u64)>,
+    weight_key: Option<String>,
+    count: u64,
 }
 
 impl Route {
@@ -25,7 +33,43 @@ impl Route {
             let condition = condition.build(&context.enrichment_tables)?;
             conditions.push((output_name.clone(), condition));
         }
-        Ok(Self { conditions })
+
+        let mut upper_bound = 0;
+        let weighted = config
+            .weighted_route
+            .iter()
+            .map(|(output_name, route)| {
+                upper_bound += route.buckets();
+                (output_name.clone(), upper_bound)
+            })
+            .collect();
+
+        Ok(Self {
+            conditions,
+            weighted,
+            weight_key: config.weight_key.clone(),
+            count: 0,
+        })
+    }
+
+    /// Picks the bucket of an event, by hashing the value of `weight_key` if the event has it, or
+    /// else by hashing the number of events seen so far, which spreads events evenly rather than
+    /// sending runs of consecutive events to the same route.
+    fn bucket(&mut self, event: &Event) -> u64 {
+        let value = self.weight_key.as_ref().and_then(|key| match event {
+            Event::Log(event) => event.get(key.as_str()).map(|v| v.to_string_lossy()),
+            Event::Trace(event) => event.get(key.as_str()).map(|v| v.to_string_lossy()),
+            Event::Metric(metric) => metric.tag_value(key).map(Into::into),
+        });
+
+        let hash = match value {
+            Some(value) => seahash::hash(value.as_bytes()),
+            None => {
+                self.count = self.count.wrapping_add(1);
+                seahash::hash(&self.count.to_le_bytes())
+            }
+        };
+        hash % WEIGHT_BUCKETS
     }
 }
 
@@ -44,8 +88,23 @@ impl SyncTransform for Route {
                 check_failed += 1;
             }
         }
-        if check_failed == self.conditions.len() {
-            output.push_named(UNMATCHED_ROUTE, event);
+
+        let mut weighted_route = None;
+        if !self.weighted.is_empty() {
+            let bucket = self.bucket(&event);
+            weighted_route = self
+                .weighted
+                .iter()
+                .find(|(_, upper_bound)| bucket < *upper_bound)
+                .map(|(output_name, _)| output_name);
+        }
+
+        match weighted_route {
+            Some(output_name) => output.push_named(output_name, event),
+            None if check_failed == self.conditions.len() => {
+                output.push_named(UNMATCHED_ROUTE, event)
+            }
+            None => {}
         }
     }
 }
@@ -67,13 +126,60 @@ pub struct RouteConfig {
     /// Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
     /// as a route name.
     #[configurable(metadata(docs::additional_props_description = "An individual route."))]
+    #[serde(default)]
     route: IndexMap<String, AnyCondition>,
+
+    /// A table of route identifiers to the share of events sent to the route.
+    ///
+    /// Weighted routes split the stream by percentage rather than by condition, which is useful to
+    /// mirror a fraction of the traffic to another pipeline. Each event is sent to at most one
+    /// weighted route, regardless of the conditional routes it matches, and events sent to neither
+    /// a conditional nor a weighted route are sent to the `<transform_name>._unmatched` output.
+    ///
+    /// The weights of all weighted routes must add up to at most `100`.
+    #[configurable(metadata(
+        docs::additional_props_description = "An individual weighted route."
+    ))]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    weighted_route: IndexMap<String, WeightedRoute>,
+
+    /// The name of the field whose value is hashed to pick the weighted route of an event.
+    ///
+    /// Events with the same value are consistently sent to the same weighted route. For metric
+    /// events, the value of the tag with this name is used instead. If left unspecified, or if
+    /// the event doesn't have the field, events are spread evenly over the weighted routes as they
+    /// arrive.
+    #[configurable(metadata(docs::examples = "user_id"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    weight_key: Option<String>,
+}
+
+/// A weighted route.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WeightedRoute {
+    /// The percentage of events sent to the route.
+    ///
+    /// For example, `weight = 5` sends 5% of events to the route. Weights have a resolution of
+    /// `0.01`.
+    #[configurable(validation(range(min = 0.0, max = 100.0)))]
+    #[configurable(metadata(docs::examples = 5.0))]
+    weight: f64,
+}
+
+impl WeightedRoute {
+    fn buckets(&self) -> u64 {
+        (self.weight.clamp(0.0, 100.0) * (WEIGHT_BUCKETS / 100) as f64).round() as u64
+    }
 }
 
 impl GenerateConfig for RouteConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             route: IndexMap::new(),
+            weighted_route: IndexMap::new(),
+            weight_key: None,
         })
         .unwrap()
     }
@@ -92,12 +198,37 @@ impl TransformConfig for RouteConfig {
     }
 
     fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
-        if self.route.contains_key(UNMATCHED_ROUTE) {
-            Err(vec![format!(
+        let mut errors = Vec::new();
+
+        if self.route.contains_key(UNMATCHED_ROUTE)
+            || self.weighted_route.contains_key(UNMATCHED_ROUTE)
+        {
+            errors.push(format!(
                 "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
-            )])
-        } else {
+            ));
+        }
+
+        for output_name in self.weighted_route.keys() {
+            if self.route.contains_key(output_name) {
+                errors.push(format!(
+                    "cannot have both a route and a weighted route named `{output_name}`"
+                ));
+            }
+        }
+
+        let buckets: u64 = self
+            .weighted_route
+            .values()
+            .map(WeightedRoute::buckets)
+            .sum();
+        if buckets > WEIGHT_BUCKETS {
+            errors.push("the weights of weighted routes cannot add up to more than 100".into());
+        }
+
+        if errors.is_empty() {
             Ok(())
+        } else {
+            Err(errors)
         }
     }
 
@@ -105,6 +236,7 @@ impl TransformConfig for RouteConfig {
         let mut result: Vec<Output> = self
             .route
             .keys()
+            .chain(self.weighted_route.keys())
             .map(|output_name| {
                 Output::default(DataType::all())
                     .with_schema_definition(merged_definition.clone())
@@ -126,13 +258,18 @@ impl TransformConfig for RouteConfig {
 
 #[cfg(test)]
 mod test {
+    use approx::assert_relative_eq;
     use indoc::indoc;
     use vector_core::transform::TransformOutputsBuf;
 
     use super::*;
     use crate::{
         config::{build_unit_tests, ConfigBuilder},
-        test_util::components::{init_test, COMPONENT_MULTIPLE_OUTPUTS_TESTS},
+        event::LogEvent,
+        test_util::{
+            components::{init_test, COMPONENT_MULTIPLE_OUTPUTS_TESTS},
+            random_lines,
+        },
     };
 
     #[test]
@@ -282,6 +419,107 @@ mod test {
         }
     }
 
+    fn weighted_outputs(config: &RouteConfig) -> TransformOutputsBuf {
+        TransformOutputsBuf::new_with_capacity(
+            config
+                .weighted_route
+                .keys()
+                .map(String::as_str)
+                .chain([UNMATCHED_ROUTE])
+                .map(|output_name| Output::default(DataType::all()).with_port(output_name))
+                .collect(),
+            1,
+        )
+    }
+
+    #[test]
+    fn route_split_by_weight() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            weighted_route.staging.weight = 5
+            weighted_route.canary.weight = 20.5
+        "#,
+        )
+        .unwrap();
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut counts = IndexMap::<&str, usize>::new();
+        for _ in 0..10000 {
+            let mut outputs = weighted_outputs(&config);
+            transform.transform(LogEvent::from("hello world").into(), &mut outputs);
+            for output_name in ["staging", "canary", UNMATCHED_ROUTE] {
+                *counts.entry(output_name).or_default() += outputs.drain_named(output_name).count();
+            }
+        }
+
+        assert_eq!(counts.values().sum::<usize>(), 10000);
+        assert_relative_eq!(counts["staging"] as f64, 500.0, epsilon = 150.0);
+        assert_relative_eq!(counts["canary"] as f64, 2050.0, epsilon = 300.0);
+        assert_relative_eq!(counts[UNMATCHED_ROUTE] as f64, 7450.0, epsilon = 400.0);
+    }
+
+    #[test]
+    fn route_split_by_weight_key() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.errors.type = "vrl"
+            route.errors.source = '.level == "error"'
+
+            weighted_route.staging.weight = 50
+            weight_key = "user_id"
+        "#,
+        )
+        .unwrap();
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        for user_id in random_lines(10).take(100) {
+            let event = Event::try_from(
+                serde_json::json!({"message": "hello world", "level": "error", "user_id": user_id}),
+            )
+            .unwrap();
+
+            let mut staged = None;
+            for _ in 0..10 {
+                let mut outputs = TransformOutputsBuf::new_with_capacity(
+                    ["errors", "staging", UNMATCHED_ROUTE]
+                        .into_iter()
+                        .map(|output_name| Output::default(DataType::all()).with_port(output_name))
+                        .collect(),
+                    1,
+                );
+                transform.transform(event.clone(), &mut outputs);
+
+                // Events matching a conditional route are still sent to their weighted route.
+                assert_eq!(outputs.drain_named("errors").count(), 1);
+                assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 0);
+                let is_staged = outputs.drain_named("staging").count() == 1;
+                assert_eq!(*staged.get_or_insert(is_staged), is_staged);
+            }
+        }
+    }
+
+    #[test]
+    fn route_validate_weights() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "vrl"
+            route.first.source = '.message == "hello world"'
+
+            weighted_route.first.weight = 50
+            weighted_route.second.weight = 60
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate(&schema::Definition::any()).unwrap_err(),
+            vec![
+                "cannot have both a route and a weighted route named `first`".to_owned(),
+                "the weights of weighted routes cannot add up to more than 100".to_owned(),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
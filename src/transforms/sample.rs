@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -5,7 +7,8 @@ use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
     event::Event,
-    internal_events::SampleEventDiscarded,
+    internal_events::{SampleEventDiscarded, SampleRateUpdated},
+    runtime_options::{self, RuntimeOption},
     schema,
     transforms::{FunctionTransform, OutputBuffer, Transform},
 };
@@ -22,8 +25,23 @@ pub struct SampleConfig {
     ///
     /// For example, `rate = 10` means 1 out of every 10 events are forwarded and the rest are
     /// dropped.
+    ///
+    /// The rate can be changed at runtime through the API, without reloading the configuration.
+    ///
+    /// When adapting to `target_events_per_sec`, this is the initial rate.
     pub rate: u64,
 
+    /// The number of events per second to forward, which the rate adapts to.
+    ///
+    /// The rate is adjusted every second, based on the smoothed number of events per second that
+    /// aren't excluded from sampling, so the rate can't be changed through the API anymore. The
+    /// effective rate is reported by the `sample_rate` metric.
+    ///
+    /// When sampling by `key_field`, the same events are only consistently sampled while the rate
+    /// doesn't change.
+    #[configurable(metadata(docs::examples = 100.0))]
+    pub target_events_per_sec: Option<f64>,
+
     /// The name of the log field whose value is hashed to determine if the event should be
     /// passed.
     ///
@@ -41,6 +59,7 @@ impl GenerateConfig for SampleConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             rate: 10,
+            target_events_per_sec: None,
             key_field: None,
             exclude: None::<AnyCondition>,
         })
@@ -52,14 +71,28 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        if matches!(self.target_events_per_sec, Some(target) if target <= 0.0) {
+            return Err("`target_events_per_sec` must be positive.".into());
+        }
+
+        // The rate of an adaptive sampler isn't up to the API.
+        let runtime_rate = context
+            .key
+            .as_ref()
+            .filter(|_| self.target_events_per_sec.is_none())
+            .map(|key| runtime_options::register(key, "rate", self.rate, 1, u64::MAX));
+        Ok(Transform::function(
+            Sample::new(
+                self.rate,
+                self.key_field.clone(),
+                self.exclude
+                    .as_ref()
+                    .map(|condition| condition.build(&context.enrichment_tables))
+                    .transpose()?,
+            )
+            .with_runtime_rate(runtime_rate)
+            .with_target_events_per_sec(self.target_events_per_sec),
+        ))
     }
 
     fn input(&self) -> Input {
@@ -72,12 +105,65 @@ impl TransformConfig for SampleConfig {
     }
 }
 
+/// How often the rate of an adaptive sampler is adjusted.
+const ADAPTIVE_WINDOW: Duration = Duration::from_secs(1);
+
+/// How much the number of events per second seen over the last window weighs in the smoothed one.
+const ADAPTIVE_SMOOTHING: f64 = 0.5;
+
+/// Adapts the rate of a sampler so that it forwards a target number of events per second.
+#[derive(Clone, Debug)]
+struct Adaptive {
+    target_events_per_sec: f64,
+    window_start: Instant,
+    window_events: u64,
+    /// The smoothed number of events per second seen by the sampler.
+    events_per_sec: Option<f64>,
+}
+
+impl Adaptive {
+    fn new(target_events_per_sec: f64, now: Instant) -> Self {
+        Self {
+            target_events_per_sec,
+            window_start: now,
+            window_events: 0,
+            events_per_sec: None,
+        }
+    }
+
+    /// Counts an event seen at `now`, returning the rate to sample at once a window elapsed.
+    fn observe(&mut self, now: Instant) -> Option<u64> {
+        self.window_events += 1;
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < ADAPTIVE_WINDOW {
+            return None;
+        }
+
+        let observed = self.window_events as f64 / elapsed.as_secs_f64();
+        let events_per_sec = match self.events_per_sec {
+            Some(previous) => previous + ADAPTIVE_SMOOTHING * (observed - previous),
+            None => observed,
+        };
+        self.events_per_sec = Some(events_per_sec);
+        self.window_start = now;
+        self.window_events = 0;
+
+        Some(
+            (events_per_sec / self.target_events_per_sec)
+                .round()
+                .max(1.0) as u64,
+        )
+    }
+}
+
 #[derive(Clone)]
 pub struct Sample {
     rate: u64,
     key_field: Option<String>,
     exclude: Option<Condition>,
     count: u64,
+    runtime_rate: Option<RuntimeOption>,
+    adaptive: Option<Adaptive>,
 }
 
 impl Sample {
@@ -87,8 +173,22 @@ impl Sample {
             key_field,
             exclude,
             count: 0,
+            runtime_rate: None,
+            adaptive: None,
         }
     }
+
+    /// Reads the rate from the given option, so that it can be changed at runtime.
+    fn with_runtime_rate(mut self, runtime_rate: Option<RuntimeOption>) -> Self {
+        self.runtime_rate = runtime_rate;
+        self
+    }
+
+    /// Adapts the rate so that the given number of events per second are forwarded, if any.
+    fn with_target_events_per_sec(mut self, target_events_per_sec: Option<f64>) -> Self {
+        self.adaptive = target_events_per_sec.map(|target| Adaptive::new(target, Instant::now()));
+        self
+    }
 }
 
 impl FunctionTransform for Sample {
@@ -107,6 +207,22 @@ impl FunctionTransform for Sample {
             }
         };
 
+        if let Some(runtime_rate) = &self.runtime_rate {
+            self.rate = runtime_rate.get();
+        }
+
+        if let Some(rate) = self
+            .adaptive
+            .as_mut()
+            .and_then(|adaptive| adaptive.observe(Instant::now()))
+        {
+            if rate != self.rate {
+                self.rate = rate;
+                self.count = 0;
+            }
+            emit!(SampleRateUpdated { rate });
+        }
+
         let value = self
             .key_field
             .as_ref()
@@ -348,6 +464,7 @@ mod tests {
         assert_transform_compliance(async move {
             let config = SampleConfig {
                 rate: 1,
+                target_events_per_sec: None,
                 key_field: None,
                 exclude: None,
             };
@@ -366,6 +483,32 @@ mod tests {
         .await
     }
 
+    #[test]
+    fn adapts_rate_to_target() {
+        let start = Instant::now();
+        let mut adaptive = Adaptive::new(10.0, start);
+
+        // 1000 events over the first second are sampled at 1/100.
+        for millis in 0..999 {
+            assert_eq!(
+                adaptive.observe(start + Duration::from_millis(millis)),
+                None
+            );
+        }
+        assert_eq!(adaptive.observe(start + Duration::from_secs(1)), Some(100));
+
+        // The rate is smoothed as the traffic drops to 200 events per second.
+        for millis in 0..199 {
+            let now = start + Duration::from_secs(1) + Duration::from_millis(millis * 5);
+            assert_eq!(adaptive.observe(now), None);
+        }
+        assert_eq!(adaptive.observe(start + Duration::from_secs(2)), Some(60));
+
+        // Never forwards more than all the events.
+        let mut adaptive = Adaptive::new(1000.0, start);
+        assert_eq!(adaptive.observe(start + Duration::from_secs(1)), Some(1));
+    }
+
     fn random_events(n: usize) -> Vec<Event> {
         random_lines(10)
             .take(n)
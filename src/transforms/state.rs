@@ -0,0 +1,106 @@
+//! Persistence of the state of stateful transforms in the data directory.
+//!
+//! When the configuration is reloaded, a changed transform is rebuilt while its previous instance
+//! may still be saving its state. Each state file is locked by the running instance of the
+//! transform, so that the new instance only loads the state once the previous one saved it.
+//!
+//! The transforms tried out by a canary don't persist their state at all, as they'd otherwise wait
+//! for the running instance to release the file for as long as the canary runs, or save a state
+//! built from the mirrored events for the reloaded instance to pick up.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, Weak},
+};
+
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// The lock of each state file, by path.
+static LOCKS: Lazy<Mutex<HashMap<PathBuf, Weak<AsyncMutex<()>>>>> = Lazy::new(Default::default);
+
+/// A file holding the state of a transform, saved as JSON.
+#[derive(Debug)]
+pub(crate) struct StateFile {
+    path: PathBuf,
+    lock: Arc<AsyncMutex<()>>,
+}
+
+impl StateFile {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        let mut locks = LOCKS.lock().expect("state locks poisoned");
+        // Take the chance to forget the files of the transforms that stopped since.
+        locks.retain(|_, lock| lock.strong_count() > 0);
+        let lock = match locks.get(&path).and_then(Weak::upgrade) {
+            Some(lock) => lock,
+            None => {
+                let lock = Arc::default();
+                locks.insert(path.clone(), Arc::downgrade(&lock));
+                lock
+            }
+        };
+        Self { path, lock }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Waits for any other instance of the transform to release the file, then holds it until the
+    /// returned guard is dropped.
+    pub(crate) async fn lock(&self) -> OwnedMutexGuard<()> {
+        Arc::clone(&self.lock).lock_owned().await
+    }
+
+    /// Reads the saved state, or returns the default state if none was saved yet.
+    pub(crate) fn load<T: DeserializeOwned + Default>(&self) -> io::Result<T> {
+        match fs::read(&self.path) {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub(crate) fn save<T: Serialize>(&self, state: &T) -> io::Result<()> {
+        // Write to a temporary file first so a crash never leaves a truncated state file behind.
+        let tmp_path = self.path.with_extension("json.tmp");
+        let contents = serde_json::to_vec(state)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn saves_and_loads_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = StateFile::new(dir.path().join("state.json"));
+        assert_eq!(file.load::<Vec<u64>>().unwrap(), Vec::<u64>::new());
+
+        file.save(&vec![1_u64, 2]).unwrap();
+        assert_eq!(file.load::<Vec<u64>>().unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn waits_for_previous_instance() {
+        let dir = tempfile::tempdir().unwrap();
+        let previous = StateFile::new(dir.path().join("state.json"));
+        let next = StateFile::new(dir.path().join("state.json"));
+
+        let guard = previous.lock().await;
+        assert!(tokio::time::timeout(Duration::from_millis(50), next.lock())
+            .await
+            .is_err());
+        drop(guard);
+        next.lock().await;
+    }
+}
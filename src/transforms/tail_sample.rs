@@ -0,0 +1,398 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    future,
+    num::{NonZeroU64, NonZeroUsize},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use tokio::time::{sleep_until, Instant};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::Event,
+    internal_events::{TailSampleEventsDropped, TailSampleGroupDropped, TailSampleGroupKept},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `tail_sample` transform.
+#[configurable_component(transform(
+    "tail_sample",
+    "Sample whole groups of events sharing a trace or correlation ID, based on conditions evaluated against the group."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TailSampleConfig {
+    /// The name of the field holding the trace or correlation ID by which events are grouped.
+    ///
+    /// Events without this field are forwarded right away.
+    #[configurable(metadata(docs::examples = "trace_id", docs::examples = "request_id"))]
+    pub group_by: String,
+
+    /// How long the events of a group are buffered, in milliseconds, starting with its first event.
+    ///
+    /// At the end of the window, the group is either kept or dropped as a whole. Events received
+    /// afterwards with the same ID start a new group.
+    #[serde(default = "default_window_ms")]
+    pub window_ms: u64,
+
+    /// A logical condition used to keep groups of events.
+    ///
+    /// A group is kept as soon as the condition matches any of its events, such as an event with
+    /// an error or with a latency above a threshold. Its buffered events are then forwarded, along
+    /// with the ones received during the rest of its window.
+    pub keep_when: AnyCondition,
+
+    /// The rate at which the groups not kept by `keep_when` are forwarded anyway, expressed as `1/N`.
+    ///
+    /// Groups are chosen by hashing their ID, so that every Vector instance makes the same decision
+    /// for the same group. When unset, these groups are dropped.
+    pub sample_rate: Option<NonZeroU64>,
+
+    /// The maximum number of events buffered for a group.
+    ///
+    /// When a group reaches it, the group is kept or dropped right away, without waiting for the end
+    /// of its window.
+    pub max_events: Option<NonZeroUsize>,
+}
+
+const fn default_window_ms() -> u64 {
+    30 * 1000
+}
+
+impl GenerateConfig for TailSampleConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            group_by: "trace_id".to_owned(),
+            window_ms: default_window_ms(),
+            keep_when: AnyCondition::String(r#".status == "error""#.to_owned()),
+            sample_rate: None,
+            max_events: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tail_sample")]
+impl TransformConfig for TailSampleConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let keep_when = self.keep_when.build(&context.enrichment_tables)?;
+        Ok(Transform::event_task(TailSample::new(self, keep_when)))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Trace)
+    }
+
+    fn outputs(&self, merged_definition: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log | DataType::Trace)
+            .with_schema_definition(merged_definition.clone())]
+    }
+}
+
+/// The state of a group of events during its window.
+enum Decision {
+    /// The group hasn't been decided yet, and its events are buffered.
+    Pending(Vec<Event>),
+    Kept,
+    Dropped,
+}
+
+pub struct TailSample {
+    group_by: String,
+    window: Duration,
+    keep_when: Condition,
+    sample_rate: Option<NonZeroU64>,
+    max_events: Option<NonZeroUsize>,
+    groups: HashMap<String, Decision>,
+    /// The IDs of the groups, in the order in which their windows end.
+    expirations: VecDeque<(Instant, String)>,
+}
+
+impl TailSample {
+    pub fn new(config: &TailSampleConfig, keep_when: Condition) -> Self {
+        Self {
+            group_by: config.group_by.clone(),
+            window: Duration::from_millis(config.window_ms),
+            keep_when,
+            sample_rate: config.sample_rate,
+            max_events: config.max_events,
+            groups: HashMap::new(),
+            expirations: VecDeque::new(),
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        let value = match &event {
+            Event::Log(event) => event.get(self.group_by.as_str()),
+            Event::Trace(event) => event.get(self.group_by.as_str()),
+            Event::Metric(_) => panic!("component can never receive metric events"),
+        };
+        let id = match value {
+            Some(value) => value.to_string_lossy().into_owned(),
+            None => {
+                output.push(event);
+                return;
+            }
+        };
+
+        let (matched, event) = self.keep_when.check(event);
+
+        if !self.groups.contains_key(&id) {
+            self.expirations
+                .push_back((Instant::now() + self.window, id.clone()));
+            self.groups
+                .insert(id.clone(), Decision::Pending(Vec::new()));
+        }
+        let decision = self.groups.get_mut(&id).expect("group was just inserted");
+
+        match decision {
+            Decision::Kept => output.push(event),
+            Decision::Dropped => emit!(TailSampleEventsDropped { count: 1 }),
+            Decision::Pending(events) => {
+                events.push(event);
+                if matched {
+                    emit!(TailSampleGroupKept);
+                    output.append(events);
+                    *decision = Decision::Kept;
+                } else if self
+                    .max_events
+                    .map_or(false, |max_events| events.len() >= max_events.get())
+                {
+                    let events = std::mem::take(events);
+                    *decision = settle(events, is_sampled(self.sample_rate, &id), output);
+                }
+            }
+        }
+    }
+
+    /// Decides the groups whose window has ended, and forgets about them.
+    fn expire_into(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        while self
+            .expirations
+            .front()
+            .map_or(false, |(deadline, _)| *deadline <= now)
+        {
+            let (_, id) = self.expirations.pop_front().expect("front was checked");
+            self.remove_into(&id, output);
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        while let Some((_, id)) = self.expirations.pop_front() {
+            self.remove_into(&id, output);
+        }
+    }
+
+    fn remove_into(&mut self, id: &str, output: &mut Vec<Event>) {
+        if let Some(Decision::Pending(events)) = self.groups.remove(id) {
+            settle(events, is_sampled(self.sample_rate, id), output);
+        }
+    }
+}
+
+/// Whether the group with the given ID is kept despite not matching the condition.
+fn is_sampled(sample_rate: Option<NonZeroU64>, id: &str) -> bool {
+    sample_rate.map_or(false, |rate| seahash::hash(id.as_bytes()) % rate.get() == 0)
+}
+
+/// Forwards or drops the buffered events of a group that didn't match the condition.
+fn settle(events: Vec<Event>, sampled: bool, output: &mut Vec<Event>) -> Decision {
+    if sampled {
+        emit!(TailSampleGroupKept);
+        output.extend(events);
+        Decision::Kept
+    } else {
+        emit!(TailSampleGroupDropped);
+        emit!(TailSampleEventsDropped {
+            count: events.len()
+        });
+        Decision::Dropped
+    }
+}
+
+async fn sleep_until_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => sleep_until(deadline).await,
+        None => future::pending().await,
+    }
+}
+
+impl TaskTransform<Event> for TailSample {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                let deadline = self.expirations.front().map(|(deadline, _)| *deadline);
+                tokio::select! {
+                    _ = sleep_until_deadline(deadline) => {
+                        self.expire_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.transform_one(&mut output, event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::{LogEvent, Value},
+        test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TailSampleConfig>();
+    }
+
+    fn config(extra: &str) -> TailSampleConfig {
+        toml::from_str(&format!(
+            r#"
+            group_by = "trace_id"
+            window_ms = 1000
+            keep_when = '.status == "error"'
+            {}
+            "#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    fn tail_sample(config: &TailSampleConfig) -> TailSample {
+        let keep_when = config
+            .keep_when
+            .build(&Default::default())
+            .expect("condition should build");
+        TailSample::new(config, keep_when)
+    }
+
+    fn log(trace_id: &str, status: &str) -> Event {
+        let mut log = LogEvent::from(format!("{} {}", trace_id, status));
+        log.insert("trace_id", trace_id);
+        log.insert("status", status);
+        log.into()
+    }
+
+    fn messages(output: &[Event]) -> Vec<Value> {
+        output
+            .iter()
+            .map(|event| event.as_log()["message"].clone())
+            .collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keeps_groups_matching_condition() {
+        let mut sample = tail_sample(&config(""));
+        let mut output = Vec::new();
+
+        sample.transform_one(&mut output, log("a", "ok"));
+        sample.transform_one(&mut output, log("b", "ok"));
+        assert!(output.is_empty());
+
+        // The whole group is forwarded as soon as one of its events matches.
+        sample.transform_one(&mut output, log("a", "error"));
+        assert_eq!(messages(&output), vec!["a ok".into(), "a error".into()]);
+        output.clear();
+
+        // Later events of a kept group are forwarded right away.
+        sample.transform_one(&mut output, log("a", "ok"));
+        assert_eq!(messages(&output), vec!["a ok".into()]);
+        output.clear();
+
+        // Events that can't be grouped are forwarded right away.
+        sample.transform_one(&mut output, LogEvent::from("no trace").into());
+        assert_eq!(messages(&output), vec!["no trace".into()]);
+        output.clear();
+
+        tokio::time::advance(Duration::from_millis(1000)).await;
+        sample.expire_into(&mut output);
+        assert!(output.is_empty());
+        assert!(sample.groups.is_empty());
+        assert!(sample.expirations.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn samples_other_groups() {
+        let mut sample = tail_sample(&config("sample_rate = 1"));
+        let mut output = Vec::new();
+
+        sample.transform_one(&mut output, log("a", "ok"));
+        sample.transform_one(&mut output, log("a", "ok"));
+        assert!(output.is_empty());
+
+        tokio::time::advance(Duration::from_millis(999)).await;
+        sample.expire_into(&mut output);
+        assert!(output.is_empty());
+
+        tokio::time::advance(Duration::from_millis(1)).await;
+        sample.expire_into(&mut output);
+        assert_eq!(messages(&output), vec!["a ok".into(), "a ok".into()]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn decides_full_groups_early() {
+        let mut sample = tail_sample(&config("max_events = 2"));
+        let mut output = Vec::new();
+
+        sample.transform_one(&mut output, log("a", "ok"));
+        sample.transform_one(&mut output, log("a", "ok"));
+        assert!(matches!(sample.groups["a"], Decision::Dropped));
+
+        // The group was already dropped, so it isn't kept by a later matching event.
+        sample.transform_one(&mut output, log("a", "error"));
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn decides_pending_groups_on_shutdown() {
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) =
+                create_topology(ReceiverStream::new(rx), config("sample_rate = 1")).await;
+
+            tx.send(log("a", "ok")).await.unwrap();
+            tx.send(log("a", "ok")).await.unwrap();
+            drop(tx);
+
+            assert_eq!(out.recv().await.unwrap().as_log()["message"], "a ok".into());
+            assert_eq!(out.recv().await.unwrap().as_log()["message"], "a ok".into());
+
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
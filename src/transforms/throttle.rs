@@ -6,13 +6,14 @@ use governor::{clock, Quota, RateLimiter};
 use serde_with::serde_as;
 use snafu::Snafu;
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::{config::LogNamespace, ByteSizeOf, EstimatedJsonEncodedSizeOf};
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext},
     event::Event,
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
+    runtime_options::{self, RuntimeOption},
     schema,
     template::Template,
     transforms::{TaskTransform, Transform},
@@ -24,11 +25,19 @@ use crate::{
 #[derive(Clone, Debug, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ThrottleConfig {
-    /// The number of events allowed for a given bucket per configured `window_secs`.
+    /// The number of events, or of bytes depending on `mode`, allowed for a given bucket per
+    /// configured `window_secs`.
     ///
     /// Each unique key has its own `threshold`.
+    ///
+    /// The threshold can be changed at runtime through the API, without reloading the
+    /// configuration. Doing so resets the buckets of all the keys.
     threshold: u32,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    mode: ThrottleMode,
+
     /// The time window in which the configured `threshold` is applied, in seconds.
     #[serde_as(as = "serde_with::DurationSeconds<f64>")]
     window_secs: Duration,
@@ -46,6 +55,39 @@ pub struct ThrottleConfig {
     exclude: Option<AnyCondition>,
 }
 
+/// How events are counted against the `threshold`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleMode {
+    /// Each event counts as one.
+    #[default]
+    Events,
+
+    /// Each event counts as its estimated size in memory, in bytes.
+    EstimatedBytes,
+
+    /// Each event counts as the estimated size of its JSON encoding, in bytes.
+    ///
+    /// This is closer to the number of bytes most sinks send for the event than
+    /// `estimated_bytes`.
+    JsonBytes,
+}
+
+impl ThrottleMode {
+    /// Returns the number of cells of the rate limiter used by `event`. Events larger than the
+    /// `threshold` never fit in the rate limiter, and so are always dropped.
+    fn cost(self, event: &Event) -> NonZeroU32 {
+        let cost = match self {
+            Self::Events => 1,
+            Self::EstimatedBytes => event.size_of(),
+            Self::JsonBytes => event.estimated_json_encoded_size_of(),
+        };
+        let cost = u32::try_from(cost).unwrap_or(u32::MAX).max(1);
+        NonZeroU32::new(cost).expect("cost is not zero")
+    }
+}
+
 impl_generate_config_from_default!(ThrottleConfig);
 
 #[async_trait::async_trait]
@@ -65,9 +107,20 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
+/// The quota allowing `threshold` events, or bytes, per `window`.
+fn quota(window: Duration, threshold: NonZeroU32) -> Option<Quota> {
+    Quota::with_period(Duration::from_secs_f64(
+        window.as_secs_f64() / f64::from(threshold.get()),
+    ))
+    .map(|quota| quota.allow_burst(threshold))
+}
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     quota: Quota,
+    threshold: NonZeroU32,
+    runtime_threshold: Option<RuntimeOption>,
+    mode: ThrottleMode,
     flush_keys_interval: Duration,
     key_field: Option<Template>,
     exclude: Option<Condition>,
@@ -91,10 +144,8 @@ where
             None => return Err(Box::new(ConfigError::NonZero)),
         };
 
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            flush_keys_interval.as_secs_f64() / f64::from(threshold.get()),
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
+        let quota = match quota(flush_keys_interval, threshold) {
+            Some(quota) => quota,
             None => return Err(Box::new(ConfigError::NonZero)),
         };
         let exclude = config
@@ -102,9 +153,21 @@ where
             .as_ref()
             .map(|condition| condition.build(&context.enrichment_tables))
             .transpose()?;
+        let runtime_threshold = context.key.as_ref().map(|key| {
+            runtime_options::register(
+                key,
+                "threshold",
+                u64::from(threshold.get()),
+                1,
+                u64::from(u32::MAX),
+            )
+        });
 
         Ok(Self {
             quota,
+            threshold,
+            runtime_threshold,
+            mode: config.mode,
             clock,
             flush_keys_interval,
             key_field: config.key_field.clone(),
@@ -127,7 +190,8 @@ where
     {
         let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
 
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
+        let mut threshold = self.threshold;
+        let mut limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
 
         Box::pin(stream! {
           loop {
@@ -138,6 +202,18 @@ where
                     match maybe_event {
                         None => true,
                         Some(event) => {
+                            let current = self
+                                .runtime_threshold
+                                .as_ref()
+                                .and_then(|option| u32::try_from(option.get()).ok())
+                                .and_then(NonZeroU32::new);
+                            if let Some(current) = current.filter(|current| *current != threshold) {
+                                threshold = current;
+                                if let Some(quota) = quota(self.flush_keys_interval, current) {
+                                    limiter = RateLimiter::dashmap_with_clock(quota, &self.clock);
+                                }
+                            }
+
                             let (throttle, event) = match self.exclude.as_ref() {
                                 Some(condition) => {
                                     let (result, event) = condition.check(event);
@@ -158,8 +234,8 @@ where
                                         .ok()
                                 });
 
-                                match limiter.check_key(&key) {
-                                    Ok(()) => {
+                                match limiter.check_key_n(&key, self.mode.cost(&event)) {
+                                    Ok(Ok(())) => {
                                         Some(event)
                                     }
                                     _ => {
@@ -406,11 +482,80 @@ key_field = "{{ bucket }}"
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
 
+    #[tokio::test]
+    async fn throttle_bytes() {
+        let clock = clock::FakeRelativeClock::default();
+        let small_log = LogEvent::from("hello");
+        let large_log = LogEvent::from("hello world, this is a much larger event");
+        let small_size = small_log.estimated_json_encoded_size_of() as u32;
+        let config = ThrottleConfig {
+            threshold: small_size * 3,
+            mode: ThrottleMode::JsonBytes,
+            window_secs: Duration::from_secs(5),
+            ..Default::default()
+        };
+
+        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
+            .map(Transform::event_task)
+            .unwrap();
+
+        let throttle = throttle.into_task();
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut out_stream = throttle.transform_events(Box::pin(rx));
+
+        // tokio interval is always immediately ready, so we poll once to make sure
+        // we trip it/set the interval in the future
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tx.send(small_log.clone().into()).await.unwrap();
+        tx.send(small_log.clone().into()).await.unwrap();
+
+        let mut count = 0_u8;
+        while count < 2 {
+            if let Some(_event) = out_stream.next().await {
+                count += 1;
+            } else {
+                panic!("Unexpectedly received None in output stream");
+            }
+        }
+        assert_eq!(2, count);
+
+        // The large event does not fit in the remaining bytes, and should be dropped
+        tx.send(large_log.clone().into()).await.unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        // While a small one still does
+        tx.send(small_log.clone().into()).await.unwrap();
+        if let Some(_event) = out_stream.next().await {
+        } else {
+            panic!("Unexpectedly received None in output stream");
+        }
+
+        clock.advance(Duration::from_secs(5));
+
+        // The rate limiter should now be refreshed and allow the large event through
+        tx.send(large_log.into()).await.unwrap();
+        if let Some(_event) = out_stream.next().await {
+        } else {
+            panic!("Unexpectedly received None in output stream");
+        }
+
+        // We should be back to pending, having nothing waiting for us
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tx.disconnect();
+
+        // And still nothing there
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
             let config = ThrottleConfig {
                 threshold: 1,
+                mode: ThrottleMode::Events,
                 window_secs: Duration::from_secs_f64(1.0),
                 key_field: None,
                 exclude: None,
@@ -0,0 +1,818 @@
+//! The `wasm` transform runs the events through a WebAssembly module.
+//!
+//! Modules implement version 1 of the ABI of the transform. They export:
+//!
+//! - `memory`, their memory.
+//! - `vector_abi_version() -> i32`, returning the version of the ABI they implement.
+//! - `vector_alloc(len: i32) -> i32`, returning a buffer of `len` bytes for the host to write to.
+//! - `vector_process(ptr: i32, len: i32) -> i32`, called for each event with the event written
+//!   to the buffer, and returning `0` on success.
+//! - Optionally, `vector_init() -> i32` and `vector_shutdown() -> i32`, called when the transform
+//!   starts and stops.
+//!
+//! They may import from the `vector` module, in which every function returns `0` on success:
+//!
+//! - `emit(ptr: i32, len: i32) -> i32`, emitting an event downstream.
+//! - `counter(name_ptr: i32, name_len: i32, value: i64) -> i32`, incrementing a counter.
+//! - `gauge(name_ptr: i32, name_len: i32, value: f64) -> i32`, setting a gauge.
+//! - `log(level: i32, ptr: i32, len: i32) -> i32`, logging a message, from `0` for errors to `4`
+//!   for traces.
+//!
+//! Events are passed in both directions as JSON, such as `{"event": {"log": {...}}, "metadata":
+//! {...}}`. The metadata can be left out of the emitted events, which then keep the metadata of
+//! the event being processed.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use value::Value;
+use vector_config::configurable_component;
+use vector_core::{
+    config::LogNamespace,
+    event::EventMetadata,
+    transform::runtime_transform::{RuntimeTransform, Timer},
+};
+use wasmtime::{
+    Caller, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, Trap,
+    TypedFunc,
+};
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    emit,
+    event::Event,
+    internal_events::{
+        WasmGuestCounter, WasmGuestGauge, WasmModuleReloadError, WasmModuleReloaded,
+        WasmProcessingError,
+    },
+    schema::{self, Definition},
+    transforms::Transform,
+};
+
+/// The version of the ABI implemented by the transform.
+const ABI_VERSION: i32 = 1;
+
+/// How often the epoch of the engine is advanced, which is the resolution of the timeouts.
+const EPOCH_TICK: Duration = Duration::from_millis(1);
+
+/// How often the module file is checked for changes, when it's watched.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configuration for the `wasm` transform.
+#[configurable_component(transform(
+    "wasm",
+    "Process events with a WebAssembly module implementing a stable ABI."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WasmConfig {
+    /// The path to the WebAssembly module, in the binary or text format.
+    ///
+    /// The module must implement version 1 of the ABI of the transform.
+    #[configurable(metadata(docs::examples = "/etc/vector/parser.wasm"))]
+    pub module: PathBuf,
+
+    /// Reloads the module when its file changes.
+    ///
+    /// The file is checked every second. The `vector_shutdown` function of the previous module is
+    /// called before the `vector_init` function of the new one. If the new module fails to load,
+    /// the previous one keeps running.
+    #[serde(default)]
+    pub watch: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub limits: LimitsConfig,
+}
+
+/// Limits on the resources used by the module.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// The amount of fuel that each call into the module can consume.
+    ///
+    /// Fuel is consumed by running the instructions of the module, about one unit per instruction.
+    /// A call running out of fuel fails, and its event is dropped.
+    #[serde(default = "default_fuel")]
+    pub fuel: u64,
+
+    /// How long each call into the module can run, in milliseconds.
+    ///
+    /// A call running longer fails, and its event is dropped.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// The maximum size of the memory of the module, in bytes.
+    #[serde(default = "default_max_memory_bytes")]
+    pub max_memory_bytes: usize,
+}
+
+const fn default_fuel() -> u64 {
+    100_000_000
+}
+
+const fn default_timeout_ms() -> u64 {
+    100
+}
+
+const fn default_max_memory_bytes() -> usize {
+    64 * 1024 * 1024
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            fuel: default_fuel(),
+            timeout_ms: default_timeout_ms(),
+            max_memory_bytes: default_max_memory_bytes(),
+        }
+    }
+}
+
+impl GenerateConfig for WasmConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            module: "/etc/vector/parser.wasm".into(),
+            watch: false,
+            limits: LimitsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "wasm")]
+impl TransformConfig for WasmConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Wasm::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, merged_definition: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        // The module can change the events in any way.
+        let definition = Definition::default_for_namespace(merged_definition.log_namespaces());
+
+        vec![Output::default(DataType::all()).with_schema_definition(definition)]
+    }
+}
+
+/// An event passed to the module.
+#[derive(Serialize)]
+struct ProcessedEvent<'a> {
+    event: &'a Event,
+    metadata: &'a Value,
+}
+
+/// An event emitted by the module.
+#[derive(Deserialize)]
+struct EmittedEvent {
+    event: Event,
+    #[serde(default)]
+    metadata: Option<Value>,
+}
+
+/// The state of the host shared with the functions imported by the module.
+struct State {
+    limits: StoreLimits,
+    /// The metadata of the event being processed, given to the events it emits.
+    metadata: EventMetadata,
+    emitted: Vec<Event>,
+}
+
+impl State {
+    fn emit(&mut self, emitted: EmittedEvent) {
+        let EmittedEvent {
+            mut event,
+            metadata,
+        } = emitted;
+        let mut event_metadata = self.metadata.clone();
+        if let Some(value) = metadata {
+            *event_metadata.value_mut() = value;
+        }
+        *event.metadata_mut() = event_metadata;
+        self.emitted.push(event);
+    }
+}
+
+/// Reads the bytes at the given location of the memory of the calling module.
+fn read(caller: &mut Caller<'_, State>, ptr: i32, len: i32) -> Option<Vec<u8>> {
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let start = usize::try_from(ptr).ok()?;
+    let end = start.checked_add(usize::try_from(len).ok()?)?;
+    memory.data(&caller).get(start..end).map(<[u8]>::to_vec)
+}
+
+fn read_string(caller: &mut Caller<'_, State>, ptr: i32, len: i32) -> Option<String> {
+    read(caller, ptr, len).and_then(|bytes| String::from_utf8(bytes).ok())
+}
+
+/// Defines the functions the modules can import.
+fn link(engine: &Engine) -> wasmtime::Result<Linker<State>> {
+    let mut linker = Linker::new(engine);
+
+    linker.func_wrap(
+        "vector",
+        "emit",
+        |mut caller: Caller<'_, State>, ptr: i32, len: i32| {
+            let emitted = read(&mut caller, ptr, len)
+                .and_then(|bytes| serde_json::from_slice::<EmittedEvent>(&bytes).ok());
+            match emitted {
+                Some(emitted) => {
+                    caller.data_mut().emit(emitted);
+                    0
+                }
+                None => -1,
+            }
+        },
+    )?;
+
+    linker.func_wrap(
+        "vector",
+        "counter",
+        |mut caller: Caller<'_, State>, ptr: i32, len: i32, value: i64| match (
+            read_string(&mut caller, ptr, len),
+            u64::try_from(value),
+        ) {
+            (Some(name), Ok(value)) => {
+                emit!(WasmGuestCounter { name, value });
+                0
+            }
+            _ => -1,
+        },
+    )?;
+
+    linker.func_wrap(
+        "vector",
+        "gauge",
+        |mut caller: Caller<'_, State>, ptr: i32, len: i32, value: f64| match read_string(
+            &mut caller,
+            ptr,
+            len,
+        ) {
+            Some(name) => {
+                emit!(WasmGuestGauge { name, value });
+                0
+            }
+            None => -1,
+        },
+    )?;
+
+    linker.func_wrap(
+        "vector",
+        "log",
+        |mut caller: Caller<'_, State>, level: i32, ptr: i32, len: i32| {
+            let message = match read_string(&mut caller, ptr, len) {
+                Some(message) => message,
+                None => return -1,
+            };
+            match level {
+                0 => error!(message = %message, internal_log_rate_limit = true),
+                1 => warn!(message = %message, internal_log_rate_limit = true),
+                2 => info!(message = %message, internal_log_rate_limit = true),
+                3 => debug!(message = %message),
+                4 => trace!(message = %message),
+                _ => return -1,
+            }
+            0
+        },
+    )?;
+
+    Ok(linker)
+}
+
+/// An instance of the module.
+struct Guest {
+    store: Store<State>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    process: TypedFunc<(i32, i32), i32>,
+    init: Option<TypedFunc<(), i32>>,
+    shutdown: Option<TypedFunc<(), i32>>,
+}
+
+impl Guest {
+    fn new(
+        engine: &Engine,
+        linker: &Linker<State>,
+        module: &Module,
+        limits: &LimitsConfig,
+    ) -> wasmtime::Result<Self> {
+        let state = State {
+            limits: StoreLimitsBuilder::new()
+                .memory_size(limits.max_memory_bytes)
+                .build(),
+            metadata: EventMetadata::default(),
+            emitted: Vec::new(),
+        };
+        let mut store = Store::new(engine, state);
+        store.limiter(|state| &mut state.limits);
+
+        refuel(&mut store, limits)?;
+        let instance = linker.instantiate(&mut store, module)?;
+
+        let version = instance
+            .get_typed_func::<(), i32>(&mut store, "vector_abi_version")?
+            .call(&mut store, ())?;
+        if version != ABI_VERSION {
+            return Err(wasmtime::Error::msg(format!(
+                "the module implements version {} of the ABI, rather than version {}",
+                version, ABI_VERSION
+            )));
+        }
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("the module doesn't export its `memory`"))?;
+        let alloc = instance.get_typed_func(&mut store, "vector_alloc")?;
+        let process = instance.get_typed_func(&mut store, "vector_process")?;
+        let init = optional_func(&instance, &mut store, "vector_init")?;
+        let shutdown = optional_func(&instance, &mut store, "vector_shutdown")?;
+
+        Ok(Self {
+            store,
+            memory,
+            alloc,
+            process,
+            init,
+            shutdown,
+        })
+    }
+
+    /// Calls a function of the module, within the limits.
+    fn call<P: wasmtime::WasmParams>(
+        &mut self,
+        func: TypedFunc<P, i32>,
+        params: P,
+        limits: &LimitsConfig,
+    ) -> wasmtime::Result<()> {
+        refuel(&mut self.store, limits)?;
+        match func.call(&mut self.store, params)? {
+            0 => Ok(()),
+            status => Err(wasmtime::Error::msg(format!(
+                "the module returned status {}",
+                status
+            ))),
+        }
+    }
+
+    /// Calls the `vector_init` or `vector_shutdown` function, returning the events it emitted.
+    fn call_hook(
+        &mut self,
+        hook: Option<TypedFunc<(), i32>>,
+        limits: &LimitsConfig,
+    ) -> wasmtime::Result<Vec<Event>> {
+        let result = match hook {
+            Some(hook) => self.call(hook, (), limits),
+            None => Ok(()),
+        };
+        let emitted = std::mem::take(&mut self.store.data_mut().emitted);
+        result.map(|()| emitted)
+    }
+
+    /// Runs the event through the module, returning the events it emitted.
+    fn process(&mut self, event: Event, limits: &LimitsConfig) -> wasmtime::Result<Vec<Event>> {
+        let input = serde_json::to_vec(&ProcessedEvent {
+            event: &event,
+            metadata: event.metadata().value(),
+        })?;
+        self.store.data_mut().metadata = event.metadata().clone();
+        drop(event);
+
+        let result = self.write_and_process(&input, limits);
+
+        let state = self.store.data_mut();
+        state.metadata = EventMetadata::default();
+        let emitted = std::mem::take(&mut state.emitted);
+        result.map(|()| emitted)
+    }
+
+    fn write_and_process(&mut self, input: &[u8], limits: &LimitsConfig) -> wasmtime::Result<()> {
+        let len = i32::try_from(input.len())?;
+        refuel(&mut self.store, limits)?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory
+            .write(&mut self.store, usize::try_from(ptr)?, input)?;
+        self.call(self.process, (ptr, len), limits)
+    }
+}
+
+fn optional_func(
+    instance: &Instance,
+    store: &mut Store<State>,
+    name: &str,
+) -> wasmtime::Result<Option<TypedFunc<(), i32>>> {
+    instance
+        .get_func(&mut *store, name)
+        .map(|func| func.typed(&*store))
+        .transpose()
+}
+
+/// Resets the fuel and the deadline of the next call into the module.
+fn refuel(store: &mut Store<State>, limits: &LimitsConfig) -> wasmtime::Result<()> {
+    let remaining = store.consume_fuel(0)?;
+    store.add_fuel(limits.fuel.saturating_sub(remaining))?;
+    store.set_epoch_deadline(limits.timeout_ms);
+    Ok(())
+}
+
+fn error_code(error: &wasmtime::Error) -> &'static str {
+    match error.downcast_ref::<Trap>() {
+        Some(Trap::OutOfFuel) => "out_of_fuel",
+        Some(Trap::Interrupt) => "timeout",
+        Some(_) => "trap",
+        None => "invalid_call",
+    }
+}
+
+/// The version of the module file, to tell when it changed.
+#[derive(Debug, PartialEq)]
+struct FileVersion {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileVersion {
+    fn of(path: &Path) -> Option<Self> {
+        fs::metadata(path).ok().map(|metadata| Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Advances the epoch of the engine, by which the calls into the module are timed out.
+struct EpochTicker {
+    stopped: Arc<AtomicBool>,
+}
+
+impl EpochTicker {
+    fn start(engine: &Engine) -> std::io::Result<Self> {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let engine = engine.clone();
+        let ticker_stopped = Arc::clone(&stopped);
+        thread::Builder::new()
+            .name("vector-wasm-epoch".to_owned())
+            .spawn(move || {
+                while !ticker_stopped.load(Ordering::Relaxed) {
+                    thread::sleep(EPOCH_TICK);
+                    engine.increment_epoch();
+                }
+            })?;
+
+        Ok(Self { stopped })
+    }
+}
+
+impl Drop for EpochTicker {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+fn load_module(engine: &Engine, path: &Path) -> crate::Result<Module> {
+    Module::from_file(engine, path).map_err(|error| {
+        format!("Could not load WebAssembly module {:?}: {:#}", path, error).into()
+    })
+}
+
+/// A transform running the events through an instance of a WebAssembly module.
+pub struct Wasm {
+    path: PathBuf,
+    watch: bool,
+    limits: LimitsConfig,
+    engine: Engine,
+    linker: Linker<State>,
+    module: Module,
+    version: Option<FileVersion>,
+    guest: Guest,
+    _ticker: EpochTicker,
+}
+
+impl Wasm {
+    pub fn new(config: &WasmConfig) -> crate::Result<Self> {
+        let mut engine_config = wasmtime::Config::new();
+        engine_config.consume_fuel(true).epoch_interruption(true);
+        let engine = Engine::new(&engine_config)
+            .map_err(|error| format!("Could not configure the WebAssembly engine: {:#}", error))?;
+        let linker = link(&engine)
+            .map_err(|error| format!("Could not define the WebAssembly ABI: {:#}", error))?;
+
+        let version = FileVersion::of(&config.module);
+        let module = load_module(&engine, &config.module)?;
+        let guest = Guest::new(&engine, &linker, &module, &config.limits).map_err(|error| {
+            format!(
+                "Could not instantiate WebAssembly module {:?}: {:#}",
+                config.module, error
+            )
+        })?;
+        let ticker = EpochTicker::start(&engine)?;
+
+        Ok(Self {
+            path: config.module.clone(),
+            watch: config.watch,
+            limits: config.limits.clone(),
+            engine,
+            linker,
+            module,
+            version,
+            guest,
+            _ticker: ticker,
+        })
+    }
+
+    /// Replaces the instance of the module after it trapped, as its state can't be relied on
+    /// anymore.
+    fn reset<F>(&mut self, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        match Guest::new(&self.engine, &self.linker, &self.module, &self.limits) {
+            Ok(guest) => {
+                self.guest = guest;
+                self.hook_init(emit_fn);
+            }
+            Err(error) => emit!(WasmProcessingError {
+                error: format!("{:#}", error),
+                error_code: "instantiation_failed",
+            }),
+        }
+    }
+
+    /// Replaces the module once its file changed.
+    fn reload<F>(&mut self, mut emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        let version = FileVersion::of(&self.path);
+        if version == self.version {
+            return;
+        }
+        // The module is only loaded once per change, even when it fails.
+        self.version = version;
+
+        let loaded = load_module(&self.engine, &self.path).and_then(|module| {
+            Guest::new(&self.engine, &self.linker, &module, &self.limits)
+                .map(|guest| (module, guest))
+                .map_err(|error| format!("{:#}", error).into())
+        });
+        match loaded {
+            Ok((module, guest)) => {
+                self.hook_shutdown(&mut emit_fn);
+                self.module = module;
+                self.guest = guest;
+                emit!(WasmModuleReloaded { path: &self.path });
+                self.hook_init(&mut emit_fn);
+            }
+            Err(error) => emit!(WasmModuleReloadError {
+                path: &self.path,
+                error: error.to_string(),
+            }),
+        }
+    }
+}
+
+impl RuntimeTransform for Wasm {
+    fn hook_process<F>(&mut self, event: Event, mut emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        match self.guest.process(event, &self.limits) {
+            Ok(events) => events.into_iter().for_each(&mut emit_fn),
+            Err(error) => {
+                emit!(WasmProcessingError {
+                    error: format!("{:#}", error),
+                    error_code: error_code(&error),
+                });
+                if error.downcast_ref::<Trap>().is_some() {
+                    self.reset(emit_fn);
+                }
+            }
+        }
+    }
+
+    fn hook_init<F>(&mut self, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        let init = self.guest.init;
+        match self.guest.call_hook(init, &self.limits) {
+            Ok(events) => events.into_iter().for_each(emit_fn),
+            Err(error) => error!(
+                message = "Error in the `vector_init` function of the WebAssembly module.",
+                error = ?error,
+                internal_log_rate_limit = true,
+            ),
+        }
+    }
+
+    fn hook_shutdown<F>(&mut self, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        let shutdown = self.guest.shutdown;
+        match self.guest.call_hook(shutdown, &self.limits) {
+            Ok(events) => events.into_iter().for_each(emit_fn),
+            Err(error) => error!(
+                message = "Error in the `vector_shutdown` function of the WebAssembly module.",
+                error = ?error,
+                internal_log_rate_limit = true,
+            ),
+        }
+    }
+
+    fn timer_handler<F>(&mut self, _timer: Timer, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        self.reload(emit_fn);
+    }
+
+    fn timers(&self) -> Vec<Timer> {
+        if self.watch {
+            vec![Timer {
+                id: 0,
+                interval: WATCH_INTERVAL,
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        test_util::{components::assert_transform_compliance, temp_file},
+        transforms::test::create_topology,
+    };
+
+    /// Emits every event twice, counting them.
+    const ECHO: &str = r#"
+        (module
+          (import "vector" "emit" (func $emit (param i32 i32) (result i32)))
+          (import "vector" "counter" (func $counter (param i32 i32 i64) (result i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "processed")
+          (func (export "vector_abi_version") (result i32) (i32.const 1))
+          (func (export "vector_alloc") (param i32) (result i32) (i32.const 1024))
+          (func (export "vector_process") (param $ptr i32) (param $len i32) (result i32)
+            (drop (call $counter (i32.const 0) (i32.const 9) (i64.const 1)))
+            (drop (call $emit (local.get $ptr) (local.get $len)))
+            (drop (call $emit (local.get $ptr) (local.get $len)))
+            (i32.const 0)))
+    "#;
+
+    /// Drops every event.
+    const DROP: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "vector_abi_version") (result i32) (i32.const 1))
+          (func (export "vector_alloc") (param i32) (result i32) (i32.const 1024))
+          (func (export "vector_process") (param i32 i32) (result i32) (i32.const 0)))
+    "#;
+
+    /// Never returns.
+    const LOOP: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "vector_abi_version") (result i32) (i32.const 1))
+          (func (export "vector_alloc") (param i32) (result i32) (i32.const 1024))
+          (func (export "vector_process") (param i32 i32) (result i32)
+            (loop $forever (br $forever))
+            (i32.const 0)))
+    "#;
+
+    fn config(module: &str, limits: LimitsConfig) -> WasmConfig {
+        let path = temp_file();
+        fs::write(&path, module).unwrap();
+        WasmConfig {
+            module: path,
+            watch: false,
+            limits,
+        }
+    }
+
+    fn log_event(message: &str) -> Event {
+        let mut log = LogEvent::default();
+        log.insert("message", message);
+        log.metadata_mut()
+            .value_mut()
+            .insert("source", Value::from("test"));
+        Event::Log(log)
+    }
+
+    fn process(wasm: &mut Wasm, event: Event) -> Vec<Event> {
+        let mut output = Vec::new();
+        wasm.hook_process(event, |event| output.push(event));
+        output
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WasmConfig>();
+    }
+
+    #[tokio::test]
+    async fn emits_events_through_topology() {
+        assert_transform_compliance(async move {
+            let config = config(ECHO, LimitsConfig::default());
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(log_event("hello")).await.unwrap();
+            for _ in 0..2 {
+                let event = out.recv().await.unwrap();
+                assert_eq!(event.as_log()["message"], "hello".into());
+                assert_eq!(event.metadata().value(), &vrl::value!({"source": "test"}));
+            }
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[test]
+    fn rejects_other_abi_versions() {
+        let module = DROP.replace("(i32.const 1))", "(i32.const 2))");
+        let error = Wasm::new(&config(&module, LimitsConfig::default()))
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("version 2 of the ABI"), "{}", error);
+    }
+
+    #[test]
+    fn limits_fuel() {
+        let mut wasm = Wasm::new(&config(
+            LOOP,
+            LimitsConfig {
+                fuel: 10_000,
+                timeout_ms: 60_000,
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+
+        // The instance is replaced after running out of fuel, so it keeps running out of fuel.
+        assert!(process(&mut wasm, log_event("first")).is_empty());
+        assert!(process(&mut wasm, log_event("second")).is_empty());
+    }
+
+    #[test]
+    fn limits_time() {
+        let mut wasm = Wasm::new(&config(
+            LOOP,
+            LimitsConfig {
+                fuel: u64::MAX,
+                timeout_ms: 10,
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+
+        let error = wasm
+            .guest
+            .process(log_event("first"), &wasm.limits)
+            .unwrap_err();
+        assert_eq!(error_code(&error), "timeout");
+    }
+
+    #[test]
+    fn reloads_changed_module() {
+        let config = WasmConfig {
+            watch: true,
+            ..config(ECHO, LimitsConfig::default())
+        };
+        let mut wasm = Wasm::new(&config).unwrap();
+        assert_eq!(wasm.timers().len(), 1);
+        assert_eq!(process(&mut wasm, log_event("first")).len(), 2);
+
+        let timer = wasm.timers()[0];
+        fs::write(&config.module, "not a module").unwrap();
+        wasm.timer_handler(timer, |_| ());
+        assert_eq!(process(&mut wasm, log_event("second")).len(), 2);
+
+        fs::write(&config.module, DROP).unwrap();
+        wasm.timer_handler(timer, |_| ());
+        assert!(process(&mut wasm, log_event("third")).is_empty());
+    }
+}
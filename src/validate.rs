@@ -1,12 +1,12 @@
 #![allow(missing_docs)]
-use std::{collections::HashMap, fmt, fs::remove_dir_all, path::PathBuf};
+use std::{collections::HashMap, fmt, fs::remove_dir_all, path::PathBuf, time::Duration};
 
 use clap::Parser;
 use colored::*;
 use exitcode::ExitCode;
 
 use crate::{
-    config::{self, Config, ConfigDiff},
+    config::{self, Config, ConfigDiff, ProxyConfig, SourceConfig},
     topology::{self, builder::Pieces},
 };
 
@@ -19,6 +19,12 @@ pub struct Opts {
     #[arg(long)]
     pub no_environment: bool,
 
+    /// Checks that sources and sinks can connect and authenticate to the systems they read
+    /// from and write to, without starting the topology. This runs the health checks of all
+    /// sinks, including those disabled by the configuration, and of the sources supporting them.
+    #[arg(long, conflicts_with = "no_environment")]
+    pub check_connectivity: bool,
+
     /// Fail validation on warnings that are probably a mistake in the configuration
     /// or are recommended to be fixed.
     #[arg(short, long)]
@@ -101,6 +107,10 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
         None => return exitcode::CONFIG,
     };
 
+    if opts.check_connectivity {
+        config.enable_healthchecks();
+    }
+
     if !opts.no_environment {
         if let Some(tmp_directory) = create_tmp_directory(&mut config, &mut fmt) {
             validated &= validate_environment(opts, &config, &mut fmt).await;
@@ -177,7 +187,11 @@ async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter)
         return false;
     };
 
-    validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await
+    let mut validated = validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await;
+    if opts.check_connectivity {
+        validated &= validate_source_healthchecks(config, fmt).await;
+    }
+    validated
 }
 
 async fn validate_components(
@@ -245,6 +259,29 @@ async fn validate_healthchecks(
     validated
 }
 
+async fn validate_source_healthchecks(config: &Config, fmt: &mut Formatter) -> bool {
+    // Like for sinks, the health checks are run in serial.
+    let mut validated = true;
+    for (id, source) in config.sources() {
+        let proxy = ProxyConfig::merge_with_env(&config.global.proxy, &source.proxy);
+        let healthcheck = source.inner.healthcheck(&proxy);
+        match tokio::time::timeout(Duration::from_secs(10), healthcheck).await {
+            Ok(None) => {}
+            Ok(Some(Ok(()))) => fmt.success(format!("Health check \"{}\"", id)),
+            Ok(Some(Err(error))) => {
+                validated = false;
+                fmt.error(format!("Health check for \"{}\" failed: {}", id, error));
+            }
+            Err(_) => {
+                validated = false;
+                fmt.error(format!("Health check for \"{}\" timed out", id));
+            }
+        }
+    }
+
+    validated
+}
+
 /// For data directory that we write to:
 /// 1. Create a tmp directory in it.
 /// 2. Change config to point to that tmp directory.
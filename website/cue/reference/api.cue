@@ -47,9 +47,135 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		profiling: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether the CPU profile of the components is exposed on the
+				`/debug/pprof/profile` endpoint. The CPU time of the components is only
+				accounted while this is enabled, as the accounting adds a small overhead.
+				Only supported on Unix.
+				"""
+		}
+		readiness: {
+			common:   false
+			required: false
+			description: """
+				Conditions under which the `/ready` endpoint reports Vector as not ready,
+				so that load balancers, such as Kubernetes services, can stop routing
+				events to an overwhelmed instance.
+				"""
+			type: object: options: {
+				max_buffer_usage_percent: {
+					common:   false
+					required: false
+					description: """
+						The maximum percentage of a buffer limited by bytes, such as a disk
+						buffer, that can be used. Not set by default, which doesn't check
+						the usage of the buffers.
+						"""
+					type: uint: {
+						default: null
+						examples: [80]
+						unit: null
+					}
+				}
+				max_source_blocked_secs: {
+					common:   false
+					required: false
+					description: """
+						The maximum amount of time a source can be paused by backpressure.
+						Not set by default, which doesn't check the backpressure of the
+						sources.
+						"""
+					type: uint: {
+						default: null
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+			}
+		}
+		tokens: {
+			common:   false
+			required: false
+			description: """
+				The tokens which can access the API, along with what each of them is
+				allowed to do. When set, the `/graphql` and `/debug/pprof/profile`
+				endpoints can only be accessed with one of the tokens, sent as a bearer
+				token in the `Authorization` header, or as the `token` field of the
+				`connection_init` payload of GraphQL subscriptions over WebSockets. The
+				`/health` and `/ready` endpoints are left open to probes. The `vector tap`
+				and `vector top` commands send the token set with `--token` or the
+				`VECTOR_API_TOKEN` environment variable.
+				"""
+			type: array: {
+				default: []
+				items: type: object: options: {
+					token: {
+						required:    true
+						description: "The token."
+						type: string: examples: ["${VECTOR_API_TOKEN}"]
+					}
+					scopes: {
+						required: true
+						description: """
+							What the token is allowed to do. Any token can check the health of
+							Vector through the `health` query.
+							"""
+						type: array: items: type: string: enum: {
+							read:   "Run queries, such as those of the components and their metrics, subscribe to the metrics, and read the CPU profile."
+							tap:    "Tap the events of the components matching `tap_components`."
+							mutate: "Run mutations, such as changing options or draining Vector."
+						}
+					}
+					tap_components: {
+						common:   false
+						required: false
+						description: """
+							The glob patterns of the components whose events the token can tap,
+							with the `tap` scope. The events emitted by the other components are
+							never sent to the token, even when the tapped patterns match them, or
+							when they flow into a component which can be tapped.
+							"""
+						type: array: {
+							default: ["*"]
+							items: type: string: examples: ["app_*"]
+						}
+					}
+				}
+			}
+		}
 	}
 
 	endpoints: {
+		"/debug/pprof/profile": {
+			GET: {
+				description: """
+					The CPU profile of the components, in the gzipped pprof format,
+					when `profiling` is enabled. The CPU time used over the number of
+					seconds set with the `seconds` query parameter, 30 by default and up
+					to 300, is attributed to the kind, the type, and the ID of the
+					components, so the profile can be viewed as a flame graph with
+					`go tool pprof -http=: http://127.0.0.1:8686/debug/pprof/profile?seconds=30`.
+					"""
+				responses: {
+					"200": {
+						description: "The profile of the requested period."
+					}
+					"401": {
+						description: "Tokens are configured, and the request doesn't have a valid one."
+					}
+					"403": {
+						description: "The token of the request doesn't have the `read` scope."
+					}
+					"404": {
+						description: "The profiling isn't enabled."
+					}
+				}
+			}
+		}
 		"/graphql": {
 			POST: {
 				description: """
@@ -63,9 +189,14 @@ api: {
 							regardless if the query was successful or not. This
 							is due to the fact that queries can partially fail.
 							Please check for the `errors` key to determine if
-							there were any errors in your query.
+							there were any errors in your query, including
+							those of the operations the token of the request
+							doesn't have the scope of.
 							"""
 					}
+					"401": {
+						description: "Tokens are configured, and the request doesn't have a valid one."
+					}
 				}
 			}
 		}
@@ -82,6 +213,24 @@ api: {
 				}
 			}
 		}
+		"/ready": {
+			GET: {
+				description: """
+					Readiness endpoint. Useful to stop routing events to
+					Vector while it's overwhelmed, according to the
+					`readiness` options. The reasons Vector isn't ready
+					are listed in the `reasons` key of the response.
+					"""
+				responses: {
+					"200": {
+						description: "Vector is running, and none of the readiness conditions hold."
+					}
+					"503": {
+						description: "Vector is shutting down or draining, or one of the readiness conditions holds."
+					}
+				}
+			}
+		}
 		"/playground": {
 			GET: {
 				description: """
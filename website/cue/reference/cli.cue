@@ -162,6 +162,12 @@ cli: {
 			type:        "integer"
 			env_var:     "VECTOR_THREADS"
 		}
+		"profile": {
+			description: env_vars.VECTOR_PROFILE.description
+			default:     env_vars.VECTOR_PROFILE.type.string.default
+			enum:        env_vars.VECTOR_PROFILE.type.string.enum
+			env_var:     "VECTOR_PROFILE"
+		}
 		"internal-log-rate-limit": {
 			_short:      "i"
 			description: env_vars.VECTOR_INTERNAL_LOG_RATE_LIMIT.description
@@ -169,11 +175,118 @@ cli: {
 			type:        "integer"
 			env_var:     "VECTOR_INTERNAL_LOG_RATE_LIMIT"
 		}
+		"graceful-shutdown-limit-secs": {
+			description: env_vars.VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS.description
+			default:     env_vars.VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS.type.uint.default
+			type:        "integer"
+			env_var:     "VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS"
+		}
 	}
 
 	options: _core_options
 
 	commands: {
+		"checkpoints export": {
+			description: """
+				Export the checkpoints of a [`file`](\(urls.vector_file_source)) source to a portable JSON
+				document, for example to migrate them to another host. Checkpoints keyed by the `device_and_inode`
+				fingerprint strategy are only meaningful on the host they were created on.
+				"""
+
+			example: "vector checkpoints export --data-dir /var/lib/vector/my_file_source --output checkpoints.json"
+
+			options: {
+				"data-dir": {
+					_short:      "d"
+					description: "The data directory of the source: its `data_dir` option, or the global `data_dir` joined with its component ID"
+					type:        "string"
+					example:     "/var/lib/vector/my_file_source"
+				}
+				"output": {
+					_short:      "o"
+					description: "The file to write the checkpoints to. Defaults to stdout."
+					type:        "string"
+					example:     "checkpoints.json"
+				}
+			}
+		}
+
+		"checkpoints import": {
+			description: """
+				Import the checkpoints previously exported with `vector checkpoints export` into the data
+				directory of a [`file`](\(urls.vector_file_source)) source. Imported checkpoints are merged with
+				the existing ones, taking precedence for the same fingerprint. Vector must be stopped while
+				importing, otherwise the imported checkpoints will be overwritten.
+				"""
+
+			example: "vector checkpoints import --data-dir /var/lib/vector/my_file_source --input checkpoints.json"
+
+			options: {
+				"data-dir": {
+					_short:      "d"
+					description: "The data directory of the source: its `data_dir` option, or the global `data_dir` joined with its component ID"
+					type:        "string"
+					example:     "/var/lib/vector/my_file_source"
+				}
+				"input": {
+					_short:      "i"
+					description: "The file to read the checkpoints from. Defaults to stdin."
+					type:        "string"
+					example:     "checkpoints.json"
+				}
+			}
+		}
+
+		"convert-config": {
+			description: """
+				Convert a Vector configuration file, or a directory of configuration files, to another format.
+				The comments of TOML and YAML configurations are carried over, except to JSON which can't
+				hold comments. Environment variables aren't interpolated. Before anything is written, each
+				converted configuration is checked to hold the same values, and to produce the same topology,
+				as the original one. Existing files are never overwritten.
+				"""
+
+			example: "vector convert-config /etc/vector/vector.toml /etc/vector/vector.yaml"
+
+			flags: _default_flags & {
+				"resolve-includes": {
+					description: "Merge the files included by the configurations into the converted configurations, instead of keeping their `include` paths"
+				}
+			}
+
+			options: {
+				"input-format": {
+					description: "The format of the configuration files to convert. Defaults to the format of the extension of each file."
+					enum: {
+						toml: "Convert TOML configurations"
+						yaml: "Convert YAML configurations"
+						json: "Convert JSON configurations"
+					}
+				}
+				"output-format": {
+					description: "The format to convert the configurations to. Defaults to the format of the extension of the output file, and is required when converting a directory."
+					enum: {
+						toml: "Convert to TOML"
+						yaml: "Convert to YAML"
+						json: "Convert to JSON"
+					}
+				}
+			}
+
+			args: {
+				input_path: {
+					description: "The configuration file to convert, or a directory of configuration files to convert recursively"
+					type:        "string"
+					required:    true
+				}
+				output_path: {
+					description: "The file to write the converted configuration to, or the directory to write the converted configuration files to"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -194,6 +307,21 @@ cli: {
 					_short:      "f"
 					description: "Whether to skip the generation of global fields"
 				}
+				"interactive": {
+					_short: "i"
+					description: """
+						Prompt for the components to generate, and for the format of the config, instead of
+						taking a pipeline expression. The generated config is commented with the options of
+						each component.
+						"""
+				}
+				"commented": {
+					description: """
+						Comment the generated config with the options of each component, along with their
+						description and default value, and whether they're required. JSON configs are never
+						commented.
+						"""
+				}
 			}
 
 			options: {
@@ -202,11 +330,19 @@ cli: {
 					type:        "string"
 					example:     "/etc/vector/my-config.toml"
 				}
+				"format": {
+					description: "The format of the generated config. Defaults to the format of the extension of `--file`, or else to TOML."
+					enum: {
+						toml: "Generate a TOML config"
+						yaml: "Generate a YAML config"
+						json: "Generate a JSON config"
+					}
+				}
 			}
 
 			args: {
 				pipeline: {
-					description: "Pipeline expression, e.g. `stdin/remap,filter/console`"
+					description: "Pipeline expression, e.g. `stdin/remap,filter/console`. Required unless `--interactive` is set."
 					type:        "string"
 				}
 			}
@@ -234,6 +370,32 @@ cli: {
 			}
 		}
 
+		"replay": {
+			description: """
+				Replay the input recorded from sources, with their [`record`](\(urls.vector_sources)) option,
+				through a topology, for example to reproduce an incident against a candidate configuration.
+				Each recorded source is replaced by the replay of its recording, decoded like the source
+				decodes its input. The sources without a recording are removed, along with the transforms
+				and sinks left without inputs. Vector exits once all the recordings are replayed.
+				"""
+
+			example: "vector replay --config candidate.toml --recording in=/var/lib/vector/in.recording --speed 10"
+
+			options: _core_config_options & {
+				"recording": {
+					_short:      "r"
+					description: "The recording of a source to replay, as `SOURCE=PATH`. Can be repeated."
+					type:        "string"
+					example:     "in=/var/lib/vector/in.recording"
+				}
+				"speed": {
+					description: "How many times faster than they were recorded the frames are replayed, or `0` to replay them as quickly as possible"
+					type:        "string"
+					default:     "1"
+				}
+			}
+		}
+
 		"test": {
 			description: """
 				Run Vector config unit tests, then exit. This command is experimental and
@@ -320,6 +482,15 @@ cli: {
 						logfmt: "Output events as logfmt"
 					}
 				}
+				"output": {
+					_short:      "o"
+					description: "File to write the events to instead of printing them to screen, such as `events.ndjson` with the `json` format. The file is created, or truncated if it exists."
+					type:        "string"
+				}
+				"count": {
+					description: "Exit once this many events were observed"
+					type:        "integer"
+				}
 				"inputs-of": {
 					description: "Components (transforms, sinks) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
@@ -328,6 +499,10 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"condition": {
+					description: "VRL boolean expression that events must match to be observed, evaluated by the Vector API server. For example, `.status >= 500`"
+					type:        "string"
+				}
 			}
 
 			args: {
@@ -395,6 +570,15 @@ cli: {
 					_short:      "d"
 					description: "Fail validation on warnings"
 				}
+				"check-connectivity": {
+					description: """
+						Checks that sources and sinks can connect and authenticate to
+						the systems they read from and write to, without starting the
+						topology. This runs the health checks of all sinks, including
+						those disabled by the configuration, and of the sources
+						supporting them
+						"""
+				}
 			}
 
 			options: {
@@ -556,7 +740,11 @@ cli: {
 			type: string: default: null
 		}
 		VECTOR_LOG: {
-			description: "Vector's log level. Each log level includes messages from higher priority levels."
+			description: """
+				Vector's log level. Each log level includes messages from higher priority levels.
+				The `setComponentLogLevel` mutation of the [GraphQL API](/docs/reference/api/)
+				overrides it for the logs of a running component.
+				"""
 			type: string: {
 				default: "INFO"
 				enum: {
@@ -592,6 +780,24 @@ cli: {
 				unit:    null
 			}
 		}
+		VECTOR_PROFILE: {
+			description: "Set the runtime profile."
+			type: string: {
+				default: "default"
+				enum: {
+					default: "Run with the default runtime settings."
+					small: """
+						Lower the memory used by Vector, for hosts with little of it. Unless set with `--threads`,
+						Vector runs at most two worker threads, which also shrinks the buffers between components.
+						The threads running blocking tasks are limited, and only the internal metrics of components
+						and buffers are recorded, along with `uptime_seconds` and `build_info`.
+
+						The profile doesn't change which components are compiled in and registered: to leave out
+						the unused ones, build Vector with only the features of the components it runs.
+						"""
+				}
+			}
+		}
 		VECTOR_WATCH_CONFIG: {
 			description: "Watch for changes in the configuration file and reload accordingly"
 			type: bool: default: false
@@ -603,6 +809,18 @@ cli: {
 				unit:    null
 			}
 		}
+		VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS: {
+			description: """
+				Set the duration in seconds to wait for the components to gracefully shut down, or drain, once Vector is
+				asked to stop. After the duration has passed, the remaining components are killed. Sending `SIGUSR2` to
+				Vector drains it the same way as `SIGTERM`, and the `drain` mutation of the API allows overriding the
+				duration.
+				"""
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
 	}
 
 	// Helpers
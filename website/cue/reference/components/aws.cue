@@ -223,7 +223,30 @@ components: _aws: {
 					body: """
 						Vector can assume an AWS IAM role via the [`auth.assume_role`](#auth.assume_role) option. This is an
 						optional setting that is helpful for a variety of use cases, such as cross
-						account access.
+						account access. The [external ID](#auth.external_id) the trust policy of the role
+						requires, if any, is given along with it.
+
+						The components assuming the same roles the same way share their credentials,
+						for them to not each request the credentials from STS, which could throttle them.
+						"""
+				},
+				{
+					title: "Chaining roles"
+					body: """
+						Further roles can be assumed in turn via the [`auth.assume_role_chain`](#auth.assume_role_chain)
+						option, each with the credentials of the previous one and its own external ID. This
+						allows reaching a role in another account which only trusts an intermediate role.
+						"""
+				},
+				{
+					title: "Web identity tokens"
+					body: """
+						A role can be assumed with a web identity token via the
+						[`auth.web_identity_token_file`](#auth.web_identity_token_file) option, along with
+						[`auth.assume_role`](#auth.assume_role). This is how the pods of EKS are given a role
+						through IAM roles for service accounts (IRSA), which the default credentials chain
+						also picks up from the `AWS_WEB_IDENTITY_TOKEN_FILE` and `AWS_ROLE_ARN`
+						environment variables.
 						"""
 				},
 			]
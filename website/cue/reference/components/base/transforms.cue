@@ -1,17 +1,31 @@
 package metadata
 
-base: components: transforms: configuration: inputs: {
-	description: """
-		A list of upstream [source][sources] or [transform][transforms] IDs.
+base: components: transforms: configuration: {
+	inputs: {
+		description: """
+			A list of upstream [source][sources] or [transform][transforms] IDs.
 
-		Wildcards (`*`) are supported.
+			Wildcards (`*`) are supported.
 
-		See [configuration][configuration] for more info.
+			See [configuration][configuration] for more info.
 
-		[sources]: https://vector.dev/docs/reference/configuration/sources/
-		[transforms]: https://vector.dev/docs/reference/configuration/transforms/
-		[configuration]: https://vector.dev/docs/reference/configuration/
-		"""
-	required: true
-	type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
+			[sources]: https://vector.dev/docs/reference/configuration/sources/
+			[transforms]: https://vector.dev/docs/reference/configuration/transforms/
+			[configuration]: https://vector.dev/docs/reference/configuration/
+			"""
+		required: true
+		type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
+	}
+	internal_log_rate_limit_secs: {
+		description: """
+			The rate limit, in seconds, of the internal logs emitted by this component.
+
+			Overrides the `--internal-log-rate-limit` option for this component. Each rate limited
+			internal log is emitted at most once per this many seconds, after which a summary of how
+			many times it was suppressed is emitted. The suppressed logs are counted in the
+			`internal_logs_suppressed_total` internal metric.
+			"""
+		required: false
+		type: uint: examples: [60]
+	}
 }
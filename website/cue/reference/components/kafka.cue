@@ -103,6 +103,9 @@ components: _kafka: {
 				- `tls.enabled` - Set to `true`.
 				- `tls.ca_file` - The certificate authority file.
 				- `tls.verify_certificate` - Set to `true`.
+
+				The `azure_event_hubs` source and sink connect to Event Hubs natively over AMQP 1.0,
+				which is available on all tiers, including the Basic tier.
 				"""
 		}
 	}
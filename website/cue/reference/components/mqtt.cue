@@ -0,0 +1,72 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		tls: {
+			enabled:                true
+			can_verify_certificate: true
+			can_verify_hostname:    true
+			enabled_default:        false
+			enabled_by_scheme:      false
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	how_it_works: {
+		protocol_versions: {
+			title: "Protocol versions"
+			body:  """
+				Both MQTT 3.1.1 and MQTT 5 are supported, with `protocol_version` choosing which one
+				is used to talk to the broker. The `mqtt` source and sink use
+				[`rumqttc`](\(urls.mqtt_rumqttc)) under the hood.
+				"""
+		}
+		persistent_sessions: {
+			title: "Persistent sessions"
+			body:  """
+				By default, Vector starts a new session each time it connects to the broker. With
+				`clean_session` disabled, and a stable `client_id`, the broker keeps the session while
+				Vector is disconnected, along with its subscriptions and the QoS 1 messages it didn't
+				acknowledge yet, and Vector resumes it when it connects again. Sessions resumed with
+				MQTT 5 never expire.
+				"""
+		}
+	}
+}
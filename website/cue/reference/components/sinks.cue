@@ -30,6 +30,13 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		limit_schedule:             base.components.sinks.configuration.limit_schedule
+		max_event_age_secs:         base.components.sinks.configuration.max_event_age_secs
+		request_log:                base.components.sinks.configuration.request_log
+		reroute_failed:             base.components.sinks.configuration.reroute_failed
+		reroute_failed_max_pending: base.components.sinks.configuration.reroute_failed_max_pending
+		schema:                     base.components.sinks.configuration.schema
+
 		if !features.auto_generated {
 			if features.acknowledgements {
 				acknowledgements: {
@@ -188,6 +195,28 @@ components: sinks: [Name=string]: {
 														[apache_avro]: https://avro.apache.org/
 														"""
 												}
+												if codec == "protobuf" {
+													protobuf: """
+														Encodes an event as a [Protocol Buffers][protobuf] message.
+
+														The message type is looked up in a compiled file descriptor set. Fields of the event that
+														the message type doesn't define aren't encoded.
+
+														[protobuf]: https://protobuf.dev/
+														"""
+												}
+												if codec == "prometheus_text" {
+													prometheus_text: """
+														Encodes a metric in the [Prometheus text exposition format][prometheus_text].
+
+														Each metric is encoded with its `# TYPE` line, so the metrics written to the same file
+														must have different names. Distributions are aggregated into histograms or summaries using
+														the default buckets and quantiles of the `prometheus_exporter` sink, and timestamps aren't
+														encoded, so the output can be read by the textfile collector of the node exporter.
+
+														[prometheus_text]: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+														"""
+												}
 											}
 										}
 									}
@@ -216,6 +245,29 @@ components: sinks: [Name=string]: {
 												}
 											}
 										}
+										if codec == "protobuf" {
+											protobuf: {
+												description:   "Protocol Buffers-specific encoder options."
+												required:      true
+												relevant_when: "codec = `protobuf`"
+												type: object: options: {
+													desc_file: {
+														description: """
+															The path to the compiled file descriptor set of the message type.
+
+															The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+															"""
+														required: true
+														type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+													}
+													message_type: {
+														description: "The fully qualified name of the message type to encode."
+														required:    true
+														type: string: examples: ["package.Message"]
+													}
+												}
+											}
+										}
 									}
 								}
 
@@ -279,6 +331,11 @@ components: sinks: [Name=string]: {
 												character_delimited: "Byte frames are delimited by a chosen character."
 												length_delimited:    "Byte frames are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 												newline_delimited:   "Byte frames are delimited by a newline character."
+												varint_length_delimited: """
+													Byte frames are prefixed by an unsigned varint indicating the length.
+
+													This is how length-delimited Protocol Buffers messages are framed.
+													"""
 											}
 										}
 									}
@@ -340,6 +397,15 @@ components: sinks: [Name=string]: {
 												required:    false
 												type: float: default: 0.7
 											}
+											max_concurrency_limit: {
+												common:      false
+												description: "The maximum concurrency limit. The adaptive request concurrency limit will not go above this bound."
+												required:    false
+												type: uint: {
+													default: 200
+													unit:    "requests"
+												}
+											}
 											rtt_deviation_scale: {
 												common: false
 												description: """
@@ -348,6 +414,16 @@ components: sinks: [Name=string]: {
 												required: false
 												type: float: default: 2.0
 											}
+											target_rtt_secs: {
+												common:      false
+												description: "A target RTT to compare the current RTT with, instead of the average of past RTT measurements. The concurrency limit is increased while the current RTT is at or below the target, and decreased once it goes above the target by more than the scaled deviation."
+												required:    false
+												type: float: {
+													default:  null
+													examples: [0.5]
+													unit:     "seconds"
+												}
+											}
 										}
 									}
 								}
@@ -365,6 +441,16 @@ components: sinks: [Name=string]: {
 										unit:    "requests"
 									}
 								}
+								rate_limit_bytes_per_sec: {
+									description: """
+										The maximum number of bytes sent per second.
+
+										The requests are weighed by their encoded size, and are delayed once the limit is reached.
+										The sinks still using the deprecated batching don't support this option, and ignore it.
+										"""
+									required: false
+									type: uint: unit: "bytes"
+								}
 								rate_limit_duration_secs: {
 									common:      true
 									description: "The time window, in seconds, used for the `rate_limit_num` option."
@@ -503,6 +589,104 @@ components: sinks: [Name=string]: {
 	}
 
 	how_it_works: {
+		dead_letter_queue: {
+			title: "Dead letter queue"
+			body: """
+				When `reroute_failed` is set to `true`, the events this sink rejects, or that still fail
+				once its retries are exhausted, are sent to an additional `dlq` output instead of being
+				dropped. For a sink component named `foo`, this output can be accessed by specifying
+				`foo.dlq` as the input to a transform or another sink, for example to persist the
+				failed events to object storage.
+
+				The events are sent in their original form, annotated with a `dlq` object under the
+				`metadata` field (or the `%vector.dlq` metadata with the `vector` log namespace, and
+				`metadata.dlq.*` tags for metrics) holding:
+
+				* `status`: `errored` for transient failures, or `rejected` for permanent ones.
+				* `status_code`: the status code returned for the last attempt, such as the HTTP status,
+				  if there is one.
+				* `error_class`: the class of the error of the last attempt, such as the variant of the
+				  error returned by the service, `response` for the responses the sink doesn't accept, or
+				  the type of error for the documents rejected by Elasticsearch.
+				* `attempts`: the number of times the delivery was attempted.
+				* `component_id`, `component_type`, and `component_kind`: the sink that failed to
+				  deliver the event.
+
+				The `status_code`, `error_class`, and `attempts` fields are `null` when the sink recorded
+				no details of the failure. At most `reroute_failed_max_pending` events are tracked at
+				once, and the events going into the sink while that many are pending are counted in the
+				`dlq_untracked_events_total` metric instead of being rerouted if they fail.
+
+				The acknowledgement of the original event to its source still reflects the failure.
+				"""
+		}
+
+		dynamic_limits: {
+			title: "Changing limits at runtime"
+			body: """
+				The batch and concurrency limits of this sink can be changed without reloading its
+				configuration, so that it can send larger batches less often at night, or back off
+				while the downstream service is overloaded:
+
+				* `limit_schedule` replaces the limits during periods of the day, in the global
+				  `timezone`. The schedule is checked at the start of every minute.
+				* The `setComponentOption` mutation of the [GraphQL API](/docs/reference/api/) overrides
+				  the limits with the options `batch.max_bytes`, `batch.max_events`, `batch.timeout_ms`
+				  and `request.concurrency`, such as from an external controller watching the load.
+				  Setting an option to `0` removes its override. The overrides take precedence over
+				  the schedule.
+
+				Neither can exceed the largest `batch.max_bytes` and `batch.max_events` the sink
+				supports, if it has any: a schedule exceeding them is rejected when the configuration
+				is loaded, and so is an override exceeding them.
+
+				Changed batch limits apply to the batches started afterwards, and a changed
+				concurrency limit applies from the next response. With adaptive concurrency,
+				`request.concurrency` caps the adaptive limit. Only sinks using the batchers and
+				request concurrency of Vector's newer sink architecture pick up these changes.
+				"""
+		}
+
+		request_log: {
+			title: "Request log"
+			body: """
+				When `request_log` is set, the last requests this sink failed to send over HTTP, or that
+				were answered with an error status, are kept in memory along with their responses, so
+				that rejections (such as `400 Bad Request` responses to malformed events) can be
+				diagnosed without capturing the traffic. They can be queried with the `failedRequests`
+				field of the sink in the [GraphQL API](/docs/reference/api/), or dumped to the logs of
+				Vector by sending it the `SIGUSR1` signal.
+
+				Sensitive headers, query parameters, and the user information of the URI are always
+				redacted, along with the headers listed in `request_log.redact_headers`. The bodies are
+				truncated to `request_log.max_body_bytes`, and the parts of them matching
+				`request_log.redact_patterns` are redacted.
+				"""
+		}
+
+		schema_enforcement: {
+			title: "Schema enforcement"
+			body: """
+				When `schema` is set, every log event going into this sink is validated against a
+				[JSON Schema](https://json-schema.org/) before it is encoded, so that only events
+				conforming to it reach the downstream service. Events violating the schema are
+				handled according to `schema.on_violation`:
+
+				* `drop`: the event is dropped, and rejected to its source.
+				* `coerce`: values of the wrong type are converted where possible, and fields not
+				  allowed by `additionalProperties` are removed. Events still violating the schema
+				  are dropped.
+				* `route_to_dropped`: the event is sent to an additional `dropped` output instead,
+				  annotated with a `dropped` object under the `metadata` field (or the
+				  `%vector.dropped` metadata with the `vector` log namespace) holding the `reason`,
+				  a `message` describing the violation, and the `component_id`, `component_type`,
+				  and `component_kind` of the sink. For a sink named `foo`, this output can be
+				  accessed by specifying `foo.dropped` as an input.
+
+				Every violation is logged and counted in `component_errors_total`.
+				"""
+		}
+
 		if features.buffer.enabled {
 			if features.send != _|_ {
 				if features.send.batch != _|_ {
@@ -656,6 +840,8 @@ components: sinks: [Name=string]: {
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		dropped_stale_events_total:           components.sources.internal_metrics.output.metrics.dropped_stale_events_total
+		dlq_untracked_events_total:           components.sources.internal_metrics.output.metrics.dlq_untracked_events_total
 		utilization:                          components.sources.internal_metrics.output.metrics.utilization
 		buffer_byte_size:                     components.sources.internal_metrics.output.metrics.buffer_byte_size
 		buffer_events:                        components.sources.internal_metrics.output.metrics.buffer_events
@@ -664,5 +850,14 @@ components: sinks: [Name=string]: {
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		buffer_drain_events_per_second:       components.sources.internal_metrics.output.metrics.buffer_drain_events_per_second
+		buffer_ingest_events_per_second:      components.sources.internal_metrics.output.metrics.buffer_ingest_events_per_second
+		buffer_oldest_event_age_seconds:      components.sources.internal_metrics.output.metrics.buffer_oldest_event_age_seconds
+		buffer_time_to_drain_seconds:         components.sources.internal_metrics.output.metrics.buffer_time_to_drain_seconds
+		buffer_compressed_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_compressed_bytes_total
+		buffer_compression_ratio:             components.sources.internal_metrics.output.metrics.buffer_compression_ratio
+		buffer_reclaimed_bytes_total:         components.sources.internal_metrics.output.metrics.buffer_reclaimed_bytes_total
+		buffer_reclaimed_data_files_total:    components.sources.internal_metrics.output.metrics.buffer_reclaimed_data_files_total
+		buffer_uncompressed_bytes_total:      components.sources.internal_metrics.output.metrics.buffer_uncompressed_bytes_total
 	}
 }
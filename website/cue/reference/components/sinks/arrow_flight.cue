@@ -0,0 +1,106 @@
+package metadata
+
+components: sinks: arrow_flight: {
+	title: "Arrow Flight"
+
+	description: """
+		Batches log events into [Apache Arrow](\(urls.arrow)) record batches and sends them to an
+		[Arrow Flight](\(urls.arrow_flight)) server, for columnar delivery to analytical stores.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   100_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.arrow_flight
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	configuration: base.components.sinks.arrow_flight.configuration
+
+	how_it_works: {
+		record_batches: {
+			title: "Record batches"
+			body: """
+				Each batch of events is sent as a single record batch in a `DoPut` request, whose Flight
+				descriptor is the rendered `path`. The top-level fields of the events are the columns of
+				the record batch, and the events whose root isn't an object have a single `message`
+				column.
+
+				By default, the schema of the record batches is inferred from the events of each batch:
+				integers and floats are 64-bit, timestamps are in microseconds in UTC, and any other
+				value is a string, with the objects and arrays encoded as JSON. With `schema` set to
+				`server`, the schema is instead received from the server with a `GetSchema` request,
+				and the columns are cast to its types.
+				"""
+		}
+
+		retries: {
+			title: "Retries"
+			body: """
+				Requests failing with a transient gRPC status, such as `UNAVAILABLE`, are retried
+				according to the `request` options. Requests failing with a status such as
+				`INVALID_ARGUMENT` or `UNAUTHENTICATED`, and the events which can't be cast to the
+				schema of the server, are rejected.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -103,5 +103,7 @@ components: sinks: aws_kinesis_firehose: components._aws & {
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		ordering_lane_depth:              components.sources.internal_metrics.output.metrics.ordering_lane_depth
+		ordering_lanes:                   components.sources.internal_metrics.output.metrics.ordering_lanes
 	}
 }
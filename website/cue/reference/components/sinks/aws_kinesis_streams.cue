@@ -148,5 +148,7 @@ components: sinks: aws_kinesis_streams: components._aws & {
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:           components.sources.internal_metrics.output.metrics.processed_events_total
+		ordering_lane_depth:              components.sources.internal_metrics.output.metrics.ordering_lane_depth
+		ordering_lanes:                   components.sources.internal_metrics.output.metrics.ordering_lanes
 	}
 }
@@ -161,6 +161,21 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		late_events: {
+			title: "Late events"
+			body:  """
+				Time specifiers in [`key_prefix`](#key_prefix), such as `%F` or `%H`, are rendered
+				with the timestamp of each event, so an event arriving late is still written to the
+				partition of the time period it belongs to. This can cause objects to be written to
+				partitions that were already considered complete by downstream consumers.
+
+				Setting [`late_event_window_secs`](#late_event_window_secs) bounds how late an event
+				can be: events older than the window are written to the partition of the current time
+				period instead. The number of late events, and the partition they were written to, is
+				reported by the `late_events_total` metric.
+				"""
+		}
+
 		object_tags_and_metadata: {
 			title: "Object Tags & metadata"
 			body:  """
@@ -222,6 +237,7 @@ components: sinks: aws_s3: components._aws & {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		late_events_total:                components.sources.internal_metrics.output.metrics.late_events_total
 		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
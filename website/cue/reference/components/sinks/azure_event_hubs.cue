@@ -0,0 +1,81 @@
+package metadata
+
+components: sinks: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: enabled: false
+			to: {
+				service: services.azure_event_hubs
+
+				interface: {
+					socket: {
+						api: {
+							title: "AMQP 1.0 protocol"
+							url:   urls.amqp_protocol
+						}
+						direction: "outgoing"
+						port:      5671
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_event_hubs.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		partitioning: {
+			title: "Partitioning"
+			body:  """
+				By default, Event Hubs distributes the events sent by Vector between the partitions of
+				the event hub. Set `partition_key` to send all the events with the same key to the same
+				partition, in the order they were sent, or `partition_id` to send all the events to a
+				single partition.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
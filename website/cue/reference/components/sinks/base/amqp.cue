@@ -67,9 +67,12 @@ base: components: sinks: amqp: configuration: {
 						[apache_avro]: https://avro.apache.org/
 						"""
 					csv: """
-						Encodes an event as a CSV message.
+						Encodes an event as a [CSV][csv] record.
 
-						This codec must be configured with fields to encode.
+						The fields encoded, and their order, are configured with `fields`, or inferred from the
+						first event encoded.
+
+						[csv]: https://datatracker.ietf.org/doc/html/rfc4180
 						"""
 					gelf: """
 						Encodes an event as a [GELF][gelf] message.
@@ -84,6 +87,9 @@ base: components: sinks: amqp: configuration: {
 					logfmt: """
 						Encodes an event as a [logfmt][logfmt] message.
 
+						Nested fields are flattened, and the strings that would be decoded as another type are
+						quoted, so that the events decoded with the `logfmt` decoder have the same fields and types.
+
 						[logfmt]: https://brandur.org/logfmt
 						"""
 					native: """
@@ -102,6 +108,24 @@ base: components: sinks: amqp: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					prometheus_text: """
+						Encodes a metric in the [Prometheus text exposition format][prometheus_text].
+
+						Each metric is encoded with its `# TYPE` line, so the metrics written to the same file
+						must have different names. Distributions are aggregated into histograms or summaries using
+						the default buckets and quantiles of the `prometheus_exporter` sink, and timestamps aren't
+						encoded, so the output can be read by the textfile collector of the node exporter.
+
+						[prometheus_text]: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+						"""
+					protobuf: """
+						Encodes an event as a [Protocol Buffers][protobuf] message.
+
+						The message type is looked up in a compiled file descriptor set. Fields of the event that
+						the message type doesn't define aren't encoded.
+
+						[protobuf]: https://protobuf.dev/
+						"""
 					raw_message: """
 						No encoding.
 
@@ -126,19 +150,86 @@ base: components: sinks: amqp: configuration: {
 			csv: {
 				description:   "The CSV Serializer Options."
 				relevant_when: "codec = \"csv\""
-				required:      true
-				type: object: options: fields: {
-					description: """
-						Configures the fields that will be encoded, as well as the order in which they
-						appear in the output.
-
-						If a field is not present in the event, the output will be an empty string.
-
-						Values of type `Array`, `Object`, and `Regex` are not supported and the
-						output will be an empty string.
-						"""
-					required: true
-					type: array: items: type: string: {}
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when writing CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether to escape quotes in fields by doubling them.
+
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when writing CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							Configures the fields that will be encoded, as well as the order in which they
+							appear in the output.
+
+							If a field is not present in the event, the output will be an empty string.
+
+							Values of type `Array`, `Object`, and `Regex` are not supported and the
+							output will be an empty string.
+
+							If no fields are configured, the top-level fields of the first event encoded are used, in
+							alphabetical order, for that event and the ones following it in the same output.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: {}
+						}
+					}
+					header: {
+						description: """
+							Whether to write a header row with the names of the fields.
+
+							The header row is written before the first event of each output: each batch of the sinks
+							sending batches of events, each file of the `file` sink, each connection of the sinks
+							streaming events, and each message of the sinks sending an event per message.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when writing CSV."
+						required:    false
+						type: uint: default: 34
+					}
+					quote_style: {
+						description: "The quoting style to use when writing CSV data."
+						required:    false
+						type: string: {
+							default: "necessary"
+							enum: {
+								always: "Always puts quotes around every field."
+								necessary: """
+									Puts quotes around fields only when necessary.
+
+									They are necessary when fields contain a quote, delimiter, or record terminator. Quotes are
+									also necessary when writing an empty record (which is indistinguishable from a record with
+									one empty field).
+									"""
+								never: "Never writes quotes, even if it produces invalid CSV data."
+								non_numeric: """
+									Puts quotes around all fields that are non-numeric.
+
+									This means that when writing a field that does not parse as a valid float or integer,
+									quotes are used even if they aren't strictly necessary.
+									"""
+							}
+						}
+					}
 				}
 			}
 			except_fields: {
@@ -146,6 +237,36 @@ base: components: sinks: amqp: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			logfmt: {
+				description:   "Logfmt-specific encoder options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: """
+							The separator joining the names of nested fields into the keys they are flattened to.
+
+							The elements of arrays are flattened to keys suffixed with their index in brackets, such
+							as `tags[0]`.
+							"""
+						required: false
+						type: string: default: "."
+					}
+					key_order: {
+						description: """
+							The keys to write first, in this order.
+
+							The other keys are written after them in alphabetical order. A key listed here also
+							brings first the keys flattened from the fields nested in it.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["time", "level"]
+						}
+					}
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -172,6 +293,27 @@ base: components: sinks: amqp: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			protobuf: {
+				description:   "Protocol Buffers-specific encoder options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to encode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -0,0 +1,464 @@
+package metadata
+
+base: components: sinks: arrow_flight: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, will wait for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	auth: {
+		description: """
+			Configuration of the authentication strategy for HTTP requests.
+
+			HTTP authentication should almost always be used with HTTPS only, as the authentication credentials are passed as an
+			HTTP header without any additional encryption beyond what is provided by the transport itself.
+			"""
+		required: false
+		type: object: options: {
+			password: {
+				description:   "The basic authentication password."
+				relevant_when: "strategy = \"basic\""
+				required:      true
+				type: string: examples: ["${PASSWORD}", "password"]
+			}
+			strategy: {
+				description: "The authentication strategy to use."
+				required:    true
+				type: string: enum: {
+					basic: """
+						Basic authentication.
+
+						The username and password are concatenated and encoded via [base64][base64].
+
+						[base64]: https://en.wikipedia.org/wiki/Base64
+						"""
+					bearer: """
+						Bearer authentication.
+
+						The bearer token value (OAuth2, JWT, etc.) is passed as-is.
+						"""
+				}
+			}
+			token: {
+				description:   "The bearer authentication token."
+				relevant_when: "strategy = \"bearer\""
+				required:      true
+				type: string: {}
+			}
+			user: {
+				description:   "The basic authentication username."
+				relevant_when: "strategy = \"basic\""
+				required:      true
+				type: string: examples: ["${USERNAME}", "username"]
+			}
+		}
+	}
+	batch: {
+		description: "Event batching behavior."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: """
+					The maximum size of a batch that will be processed by a sink.
+
+					This is based on the uncompressed size of the batched events, before they are
+					serialized / compressed.
+					"""
+				required: false
+				type: uint: {
+					default: 10000000
+					unit:    "bytes"
+				}
+			}
+			max_events: {
+				description: "The maximum size of a batch before it is flushed."
+				required:    false
+				type: uint: {
+					default: 100000
+					unit:    "events"
+				}
+			}
+			timeout_secs: {
+				description: "The maximum age of a batch before it is flushed."
+				required:    false
+				type: float: {
+					default: 1.0
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	compression: {
+		description: """
+			Whether or not to compress requests.
+
+			If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+
+			[gzip_docs]: https://www.gzip.org/
+			"""
+		required: false
+		type: bool: default: false
+	}
+	endpoint: {
+		description: """
+			The gRPC endpoint of the Arrow Flight server to send events to.
+
+			Both IP address and hostname are accepted formats.
+
+			The endpoint _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["http://localhost:8815", "https://flight-server:443"]
+	}
+	headers: {
+		description: """
+			A list of custom headers to add to each request, such as the database expected by the
+			server.
+
+			Headers are sent as gRPC metadata.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				database: "vector"
+			}]
+			options: "*": {
+				description: "An HTTP request header and its value."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	path: {
+		description: """
+			The path of the Flight descriptor the record batches are sent with, such as the name of a
+			table.
+
+			The events are batched by path.
+			"""
+		required: true
+		type: string: {
+			examples: ["logs", "{{ application }}"]
+			syntax: "template"
+		}
+	}
+	request: {
+		description: """
+			Middleware settings for outbound requests.
+
+			Various settings can be configured, such as concurrency and rate limits, timeouts, etc.
+			"""
+		required: false
+		type: object: options: {
+			adaptive_concurrency: {
+				description: """
+					Configuration of adaptive concurrency parameters.
+
+					These parameters typically do not require changes from the default, and incorrect values can lead to meta-stable or
+					unstable performance and sink behavior. Proceed with caution.
+					"""
+				required: false
+				type: object: options: {
+					decrease_ratio: {
+						description: """
+																The fraction of the current value to set the new concurrency limit when decreasing the limit.
+
+																Valid values are greater than `0` and less than `1`. Smaller values cause the algorithm to scale back rapidly
+																when latency increases.
+
+																Note that the new limit is rounded down after applying this ratio.
+																"""
+						required: false
+						type: float: default: 0.9
+					}
+					ewma_alpha: {
+						description: """
+																The weighting of new measurements compared to older measurements.
+
+																Valid values are greater than `0` and less than `1`.
+
+																ARC uses an exponentially weighted moving average (EWMA) of past RTT measurements as a reference to compare with
+																the current RTT. Smaller values cause this reference to adjust more slowly, which may be useful if a service has
+																unusually high response variability.
+																"""
+						required: false
+						type: float: default: 0.4
+					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					rtt_deviation_scale: {
+						description: """
+																Scale of RTT deviations which are not considered anomalous.
+
+																Valid values are greater than or equal to `0`, and we expect reasonable values to range from `1.0` to `3.0`.
+
+																When calculating the past RTT average, we also compute a secondary “deviation” value that indicates how variable
+																those values are. We use that deviation when comparing the past RTT average to the current measurements, so we
+																can ignore increases in RTT that are within an expected range. This factor is used to scale up the deviation to
+																an appropriate range.  Larger values cause the algorithm to ignore larger increases in the RTT.
+																"""
+						required: false
+						type: float: default: 2.5
+					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
+				}
+			}
+			concurrency: {
+				description: "Configuration for outbound request concurrency."
+				required:    false
+				type: {
+					string: {
+						default: "none"
+						enum: {
+							adaptive: """
+															Concurrency will be managed by Vector's [Adaptive Request Concurrency][arc] feature.
+
+															[arc]: https://vector.dev/docs/about/under-the-hood/networking/arc/
+															"""
+							none: """
+															A fixed concurrency of 1.
+
+															Only one request can be outstanding at any given time.
+															"""
+						}
+					}
+					uint: {}
+				}
+			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
+			rate_limit_duration_secs: {
+				description: "The time window used for the `rate_limit_num` option."
+				required:    false
+				type: uint: {
+					default: 1
+					unit:    "seconds"
+				}
+			}
+			rate_limit_num: {
+				description: "The maximum number of requests allowed within the `rate_limit_duration_secs` time window."
+				required:    false
+				type: uint: {
+					default: 9223372036854775807
+					unit:    "requests"
+				}
+			}
+			retry_attempts: {
+				description: """
+					The maximum number of retries to make for failed requests.
+
+					The default, for all intents and purposes, represents an infinite number of retries.
+					"""
+				required: false
+				type: uint: {
+					default: 9223372036854775807
+					unit:    "retries"
+				}
+			}
+			retry_initial_backoff_secs: {
+				description: """
+					The amount of time to wait before attempting the first retry for a failed request.
+
+					After the first retry has failed, the fibonacci sequence will be used to select future backoffs.
+					"""
+				required: false
+				type: uint: {
+					default: 1
+					unit:    "seconds"
+				}
+			}
+			retry_max_duration_secs: {
+				description: "The maximum amount of time to wait between retries."
+				required:    false
+				type: uint: {
+					default: 3600
+					unit:    "seconds"
+				}
+			}
+			timeout_secs: {
+				description: """
+					The time a request can take before being aborted.
+
+					It is highly recommended that you do not lower this value below the service’s internal timeout, as this could
+					create orphaned requests, pile on retries, and result in duplicate data downstream.
+					"""
+				required: false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	schema: {
+		description: "How the schema of the record batches is determined."
+		required:    false
+		type: string: {
+			default: "infer"
+			enum: {
+				infer: """
+					The schema is inferred from the events of each batch.
+
+					Each top-level field of the events is a nullable column, whose type is inferred from its
+					values. The objects and arrays are encoded as JSON strings, and the columns holding values
+					of different types are strings.
+					"""
+				server: """
+					The schema is received from the server with a `GetSchema` request for the path.
+
+					The columns of the record batches are cast to the types of the schema. The fields missing
+					from the schema are left out, and the columns missing from the events are null.
+
+					The schema is received again after a request fails, in case it changed. The events which
+					can't be cast to the schema are rejected.
+					"""
+			}
+		}
+	}
+	tls: {
+		description: "Configures the TLS options for incoming/outgoing connections."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
@@ -45,11 +45,50 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required: true
 				type: string: examples: ["arn:aws:iam::123456789098:role/my_role"]
 			}
+			assume_role_chain: {
+				description: """
+					The roles to assume in turn once `assume_role` is, each with the credentials of the
+					previous one, such as to reach a role in another account.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						assume_role: {
+							description: """
+								The ARN of an [IAM role][iam_role] to assume.
+
+								[iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
+								"""
+							required: true
+							type: string: examples: ["arn:aws:iam::210987654321:role/other_role"]
+						}
+						external_id: {
+							description: """
+								The [external ID][external_id] the trust policy of the role requires, if any.
+
+								[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+								"""
+							required: false
+							type: string: examples: ["MyExternalId"]
+						}
+					}
+				}
+			}
 			credentials_file: {
 				description: "Path to the credentials file."
 				required:    true
 				type: string: examples: ["/my/aws/credentials"]
 			}
+			external_id: {
+				description: """
+					The [external ID][external_id] the trust policy of `assume_role` requires, if any.
+
+					[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+					"""
+				required: false
+				type: string: examples: ["MyExternalId"]
+			}
 			imds: {
 				description: "Configuration for authenticating with AWS through IMDS."
 				required:    false
@@ -75,6 +114,19 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 							unit:    "seconds"
 						}
 					}
+					token_ttl_seconds: {
+						description: """
+							How long the IMDSv2 session tokens are valid for.
+
+							The metadata is fetched with IMDSv2, requesting a session token which is reused until it
+							expires.
+							"""
+						required: false
+						type: uint: {
+							default: 21600
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 			load_timeout_secs: {
@@ -106,7 +158,7 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 					The [AWS region][aws_region] to send STS requests to.
 
 					If not set, this defaults to the configured region
-					for the service itself.
+					for the service itself. The requests are sent to the regional STS endpoint.
 
 					[aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
 					"""
@@ -118,6 +170,19 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required:    true
 				type: string: examples: ["wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY"]
 			}
+			session_name: {
+				description: "The name of the session of the role assumed with the web identity token."
+				required:    false
+				type: string: {
+					default: "vector"
+					examples: ["my-session"]
+				}
+			}
+			web_identity_token_file: {
+				description: "Path to the file of the web identity token."
+				required:    true
+				type: string: examples: ["/var/run/secrets/eks.amazonaws.com/serviceaccount/token"]
+			}
 		}
 	}
 	batch: {
@@ -171,11 +236,23 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 					[gzip]: https://www.gzip.org/
 					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression.
+
+					Payloads are compressed as a whole, in the raw format. Snappy doesn't have compression levels.
+
+					[snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
 					[zlib]: https://zlib.net/
 					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
 			}
 		}
 	}
@@ -224,9 +301,12 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 						[apache_avro]: https://avro.apache.org/
 						"""
 					csv: """
-						Encodes an event as a CSV message.
+						Encodes an event as a [CSV][csv] record.
+
+						The fields encoded, and their order, are configured with `fields`, or inferred from the
+						first event encoded.
 
-						This codec must be configured with fields to encode.
+						[csv]: https://datatracker.ietf.org/doc/html/rfc4180
 						"""
 					gelf: """
 						Encodes an event as a [GELF][gelf] message.
@@ -241,6 +321,9 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 					logfmt: """
 						Encodes an event as a [logfmt][logfmt] message.
 
+						Nested fields are flattened, and the strings that would be decoded as another type are
+						quoted, so that the events decoded with the `logfmt` decoder have the same fields and types.
+
 						[logfmt]: https://brandur.org/logfmt
 						"""
 					native: """
@@ -259,6 +342,24 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					prometheus_text: """
+						Encodes a metric in the [Prometheus text exposition format][prometheus_text].
+
+						Each metric is encoded with its `# TYPE` line, so the metrics written to the same file
+						must have different names. Distributions are aggregated into histograms or summaries using
+						the default buckets and quantiles of the `prometheus_exporter` sink, and timestamps aren't
+						encoded, so the output can be read by the textfile collector of the node exporter.
+
+						[prometheus_text]: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+						"""
+					protobuf: """
+						Encodes an event as a [Protocol Buffers][protobuf] message.
+
+						The message type is looked up in a compiled file descriptor set. Fields of the event that
+						the message type doesn't define aren't encoded.
+
+						[protobuf]: https://protobuf.dev/
+						"""
 					raw_message: """
 						No encoding.
 
@@ -283,19 +384,86 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 			csv: {
 				description:   "The CSV Serializer Options."
 				relevant_when: "codec = \"csv\""
-				required:      true
-				type: object: options: fields: {
-					description: """
-						Configures the fields that will be encoded, as well as the order in which they
-						appear in the output.
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when writing CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether to escape quotes in fields by doubling them.
 
-						If a field is not present in the event, the output will be an empty string.
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when writing CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							Configures the fields that will be encoded, as well as the order in which they
+							appear in the output.
 
-						Values of type `Array`, `Object`, and `Regex` are not supported and the
-						output will be an empty string.
-						"""
-					required: true
-					type: array: items: type: string: {}
+							If a field is not present in the event, the output will be an empty string.
+
+							Values of type `Array`, `Object`, and `Regex` are not supported and the
+							output will be an empty string.
+
+							If no fields are configured, the top-level fields of the first event encoded are used, in
+							alphabetical order, for that event and the ones following it in the same output.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: {}
+						}
+					}
+					header: {
+						description: """
+							Whether to write a header row with the names of the fields.
+
+							The header row is written before the first event of each output: each batch of the sinks
+							sending batches of events, each file of the `file` sink, each connection of the sinks
+							streaming events, and each message of the sinks sending an event per message.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when writing CSV."
+						required:    false
+						type: uint: default: 34
+					}
+					quote_style: {
+						description: "The quoting style to use when writing CSV data."
+						required:    false
+						type: string: {
+							default: "necessary"
+							enum: {
+								always: "Always puts quotes around every field."
+								necessary: """
+									Puts quotes around fields only when necessary.
+
+									They are necessary when fields contain a quote, delimiter, or record terminator. Quotes are
+									also necessary when writing an empty record (which is indistinguishable from a record with
+									one empty field).
+									"""
+								never: "Never writes quotes, even if it produces invalid CSV data."
+								non_numeric: """
+									Puts quotes around all fields that are non-numeric.
+
+									This means that when writing a field that does not parse as a valid float or integer,
+									quotes are used even if they aren't strictly necessary.
+									"""
+							}
+						}
+					}
 				}
 			}
 			except_fields: {
@@ -303,6 +471,36 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			logfmt: {
+				description:   "Logfmt-specific encoder options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: """
+							The separator joining the names of nested fields into the keys they are flattened to.
+
+							The elements of arrays are flattened to keys suffixed with their index in brackets, such
+							as `tags[0]`.
+							"""
+						required: false
+						type: string: default: "."
+					}
+					key_order: {
+						description: """
+							The keys to write first, in this order.
+
+							The other keys are written after them in alphabetical order. A key listed here also
+							brings first the keys flattened from the fields nested in it.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["time", "level"]
+						}
+					}
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -329,6 +527,27 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			protobuf: {
+				description:   "Protocol Buffers-specific encoder options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to encode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -403,6 +622,15 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -417,6 +645,22 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -456,6 +700,16 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 					}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
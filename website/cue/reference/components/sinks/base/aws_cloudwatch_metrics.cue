@@ -45,11 +45,50 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 				required: true
 				type: string: examples: ["arn:aws:iam::123456789098:role/my_role"]
 			}
+			assume_role_chain: {
+				description: """
+					The roles to assume in turn once `assume_role` is, each with the credentials of the
+					previous one, such as to reach a role in another account.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						assume_role: {
+							description: """
+								The ARN of an [IAM role][iam_role] to assume.
+
+								[iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
+								"""
+							required: true
+							type: string: examples: ["arn:aws:iam::210987654321:role/other_role"]
+						}
+						external_id: {
+							description: """
+								The [external ID][external_id] the trust policy of the role requires, if any.
+
+								[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+								"""
+							required: false
+							type: string: examples: ["MyExternalId"]
+						}
+					}
+				}
+			}
 			credentials_file: {
 				description: "Path to the credentials file."
 				required:    true
 				type: string: examples: ["/my/aws/credentials"]
 			}
+			external_id: {
+				description: """
+					The [external ID][external_id] the trust policy of `assume_role` requires, if any.
+
+					[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+					"""
+				required: false
+				type: string: examples: ["MyExternalId"]
+			}
 			imds: {
 				description: "Configuration for authenticating with AWS through IMDS."
 				required:    false
@@ -75,6 +114,19 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 							unit:    "seconds"
 						}
 					}
+					token_ttl_seconds: {
+						description: """
+							How long the IMDSv2 session tokens are valid for.
+
+							The metadata is fetched with IMDSv2, requesting a session token which is reused until it
+							expires.
+							"""
+						required: false
+						type: uint: {
+							default: 21600
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 			load_timeout_secs: {
@@ -106,7 +158,7 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 					The [AWS region][aws_region] to send STS requests to.
 
 					If not set, this defaults to the configured region
-					for the service itself.
+					for the service itself. The requests are sent to the regional STS endpoint.
 
 					[aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
 					"""
@@ -118,6 +170,19 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 				required:    true
 				type: string: examples: ["wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY"]
 			}
+			session_name: {
+				description: "The name of the session of the role assumed with the web identity token."
+				required:    false
+				type: string: {
+					default: "vector"
+					examples: ["my-session"]
+				}
+			}
+			web_identity_token_file: {
+				description: "Path to the file of the web identity token."
+				required:    true
+				type: string: examples: ["/var/run/secrets/eks.amazonaws.com/serviceaccount/token"]
+			}
 		}
 	}
 	batch: {
@@ -168,11 +233,23 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 					[gzip]: https://www.gzip.org/
 					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression.
+
+					Payloads are compressed as a whole, in the raw format. Snappy doesn't have compression levels.
+
+					[snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
 					[zlib]: https://zlib.net/
 					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
 			}
 		}
 	}
@@ -244,6 +321,15 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -258,6 +344,22 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -282,6 +384,16 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 					uint: {}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
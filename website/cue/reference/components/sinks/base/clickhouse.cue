@@ -122,11 +122,23 @@ base: components: sinks: clickhouse: configuration: {
 					[gzip]: https://www.gzip.org/
 					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression.
+
+					Payloads are compressed as a whole, in the raw format. Snappy doesn't have compression levels.
+
+					[snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
 					[zlib]: https://zlib.net/
 					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
 			}
 		}
 	}
@@ -165,9 +177,59 @@ base: components: sinks: clickhouse: configuration: {
 		}
 	}
 	endpoint: {
-		description: "The endpoint of the ClickHouse server."
-		required:    true
-		type: string: examples: ["http://localhost:8123"]
+		description: """
+			The endpoint of the ClickHouse server.
+
+			When using the native protocol, the endpoint must use the `tcp` scheme.
+			"""
+		required: true
+		type: string: examples: ["http://localhost:8123", "tcp://localhost:9000"]
+	}
+	native: {
+		description: """
+			Configuration for sending data over the ClickHouse native protocol.
+
+			When set, the `endpoint` must use the `tcp` scheme, such as `tcp://localhost:9000`.
+			"""
+		required: false
+		type: object: options: {
+			columns: {
+				description: """
+					The columns of the table that data is inserted into.
+
+					Each column is filled with the value of the event field of the same name, unless a `field`
+					is specified. Columns with a `Nullable` type are set to `NULL` when the field is missing,
+					and other columns are set to the default value of their type.
+					"""
+				required: true
+				type: object: options: "*": {
+					description: "A column of the table."
+					required:    true
+					type: object: options: {
+						field: {
+							description: """
+								The event field used to fill the column.
+
+								Defaults to the name of the column.
+								"""
+							required: false
+							type: string: examples: ["message"]
+						}
+						type: {
+							description: """
+								The ClickHouse type of the column.
+
+								The supported types are `String`, `Int8`, `Int16`, `Int32`, `Int64`, `UInt8`, `UInt16`,
+								`UInt32`, `UInt64`, `Float32`, `Float64`, `DateTime`, and `DateTime64(precision)`, along
+								with `Nullable` versions of all of them.
+								"""
+							required: true
+							type: string: examples: ["Nullable(String)", "DateTime64(3)"]
+						}
+					}
+				}
+			}
+		}
 	}
 	request: {
 		description: """
@@ -211,6 +273,15 @@ base: components: sinks: clickhouse: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -225,6 +296,22 @@ base: components: sinks: clickhouse: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -249,6 +336,16 @@ base: components: sinks: clickhouse: configuration: {
 					uint: {}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
@@ -0,0 +1,210 @@
+package metadata
+
+base: components: sinks: email: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, will wait for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	auth: {
+		description: "The credentials to authenticate to the SMTP server with."
+		required:    false
+		type: object: options: {
+			password: {
+				description: "The password."
+				required:    true
+				type: string: examples: ["${SMTP_PASSWORD}"]
+			}
+			username: {
+				description: "The username."
+				required:    true
+				type: string: examples: ["vector"]
+			}
+		}
+	}
+	body: {
+		description: """
+			The body of the emails, in plain text.
+
+			The body of a digest is the body of each of its events, one after the other.
+			"""
+		required: true
+		type: string: {
+			examples: ["{{ message }}"]
+			syntax: "template"
+		}
+	}
+	digest: {
+		description: """
+			How the events are batched into digests.
+
+			Once the first event of a digest is received, the events sent to the same recipients are
+			collected for the duration of the window, then sent in a single email.
+			"""
+		required: false
+		type: object: options: {
+			max_events: {
+				description: """
+					The maximum number of events to include in a digest.
+
+					The events in excess are only counted at the end of the digest, and dropped.
+					"""
+				required: false
+				type: uint: {
+					default: 100
+					examples: [
+						20,
+					]
+				}
+			}
+			window_secs: {
+				description: """
+					The amount of time to collect the events of a digest for.
+
+					Set this to `0` to send an email for each event.
+					"""
+				required: false
+				type: uint: {
+					default: 60
+					examples: [
+						300,
+					]
+					unit: "seconds"
+				}
+			}
+		}
+	}
+	from: {
+		description: "The sender of the emails."
+		required:    true
+		type: string: examples: ["Vector <vector@example.com>"]
+	}
+	host: {
+		description: "The host of the SMTP server."
+		required:    true
+		type: string: examples: ["smtp.example.com"]
+	}
+	port: {
+		description: """
+			The port of the SMTP server.
+
+			Defaults to `465` with the `tls` mode, `587` with the `starttls` mode, and `25` otherwise.
+			"""
+		required: false
+		type: uint: examples: [587]
+	}
+	rate_limit: {
+		description: """
+			The maximum rate of the emails sent to each recipient.
+
+			While a recipient reached the limit, its digests keep collecting events, then are sent
+			once the rate allows it again.
+			"""
+		required: false
+		type: object: options: {
+			max_emails: {
+				description: "The maximum number of emails to send to a recipient in each period."
+				required:    true
+				type: uint: examples: [10]
+			}
+			period_secs: {
+				description: "The duration of the period."
+				required:    false
+				type: uint: {
+					default: 3600
+					examples: [
+						3600,
+					]
+					unit: "seconds"
+				}
+			}
+		}
+	}
+	subject: {
+		description: """
+			The subject of the emails.
+
+			The subject of a digest is the one of its first event, followed by the number of the other
+			events in the digest.
+			"""
+		required: true
+		type: string: {
+			examples: ["[{{ level }}] {{ service }} alert"]
+			syntax: "template"
+		}
+	}
+	timeout_secs: {
+		description: "The amount of time to wait for the SMTP server to respond."
+		required:    false
+		type: uint: {
+			default: 30
+			examples: [
+				30,
+			]
+			unit: "seconds"
+		}
+	}
+	tls: {
+		description: "TLS configuration."
+		required:    false
+		type: object: options: {
+			mode: {
+				description: "How the connection to the SMTP server is secured."
+				required:    false
+				type: string: {
+					default: "starttls"
+					enum: {
+						none: "The connection isn't encrypted."
+						starttls: """
+							The connection is upgraded to TLS with the `STARTTLS` command, which the server must
+							support.
+							"""
+						tls: "The connection is encrypted with TLS from the start, also known as implicit TLS or SMTPS."
+					}
+				}
+			}
+			verify_certificate: {
+				description: """
+					Whether to verify the certificate of the SMTP server.
+
+					Only disable this for testing, as the credentials could be sent to anyone otherwise.
+					"""
+				required: false
+				type: bool: default: true
+			}
+		}
+	}
+	to: {
+		description: """
+			The recipients of the emails.
+
+			The events rendering to the same recipients are sent together, in the same digests.
+			"""
+		required: true
+		type: array: items: type: string: {
+			examples: ["oncall@example.com", "{{ team }}@example.com"]
+			syntax: "template"
+		}
+	}
+}
@@ -95,7 +95,12 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 			running on. If this is not on a GCE instance, then you must define it with an API key or service account
 			credentials JSON file.
 
+			The file can also hold the credentials of an [external account][gcp_workload_identity_federation], exchanging the
+			token of another identity provider, read from a file or a URL, for a GCP one through workload identity
+			federation, so that no service account key is needed.
+
 			[gcp_service_account_credentials]: https://cloud.google.com/docs/authentication/production#manually
+			[gcp_workload_identity_federation]: https://cloud.google.com/iam/docs/workload-identity-federation
 			"""
 		required: false
 		type: string: {}
@@ -137,6 +142,30 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 		required: true
 		type: string: {}
 	}
+	impersonate_service_account: {
+		description: """
+			The email address of a [service account][gcp_impersonation] to impersonate.
+
+			The tokens of the service account are requested with the credentials otherwise in use, whose principal must be
+			granted the `roles/iam.serviceAccountTokenCreator` role on it. This can't be combined with an API key.
+
+			[gcp_impersonation]: https://cloud.google.com/iam/docs/service-account-impersonation
+			"""
+		required: false
+		type: string: examples: ["vector@my-project.iam.gserviceaccount.com"]
+	}
+	impersonation_lifetime_secs: {
+		description: """
+			How long the tokens of the impersonated service account are valid for, in seconds.
+
+			Defaults to an hour, which is the longest allowed unless an organization policy extends it, up to 12 hours.
+			"""
+		required: false
+		type: uint: {
+			examples: [3600]
+			unit:     "seconds"
+		}
+	}
 	log_id: {
 		description: """
 			The log ID to which to publish logs.
@@ -212,6 +241,15 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -226,6 +264,22 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -250,6 +304,16 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 					uint: {}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
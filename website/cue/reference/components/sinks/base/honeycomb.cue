@@ -65,9 +65,17 @@ base: components: sinks: honeycomb: configuration: {
 		}
 	}
 	dataset: {
-		description: "The dataset to which logs are sent."
-		required:    true
-		type: string: examples: ["my-honeycomb-dataset"]
+		description: """
+			The dataset to which logs are sent.
+
+			The events are batched separately for each dataset they resolve to, and the ones whose
+			dataset can't be rendered are dropped.
+			"""
+		required: true
+		type: string: {
+			examples: ["my-honeycomb-dataset", "{{ service }}-{{ environment }}"]
+			syntax: "template"
+		}
 	}
 	encoding: {
 		description: "Transformations to prepare an event for serialization."
@@ -93,6 +101,58 @@ base: components: sinks: honeycomb: configuration: {
 			}
 		}
 	}
+	endpoint: {
+		description: """
+			The base URL of the Honeycomb API.
+
+			This can be changed to send the events to another region of Honeycomb, or to any other
+			service implementing its batched events API.
+			"""
+		required: false
+		type: string: default: "https://api.honeycomb.io"
+	}
+	flatten: {
+		description: """
+			Controls how the nested fields of the events are flattened into the attributes of the
+			Honeycomb events.
+
+			By default, all the nested fields are flattened, each one into an attribute named after its
+			path.
+			"""
+		required: false
+		type: object: options: {
+			arrays: {
+				description: """
+					Whether to flatten the arrays, into an attribute for each of their elements, suffixed with
+					its index.
+
+					If disabled, the arrays are sent as JSON arrays.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			max_depth: {
+				description: """
+					The depth up to which the nested objects are flattened, the top-level fields being at a
+					depth of `1`.
+
+					The deeper objects are sent as JSON, which Honeycomb unpacks into attributes if the dataset
+					is configured to. By default, the objects are flattened whatever their depth.
+					"""
+				required: false
+				type: uint: examples: [1, 3]
+			}
+			separator: {
+				description: """
+					The separator joining the keys of the nested objects into the names of the attributes.
+
+					The separator is escaped with a backslash in the keys containing it.
+					"""
+				required: false
+				type: string: default: "."
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -135,6 +195,15 @@ base: components: sinks: honeycomb: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -149,6 +218,22 @@ base: components: sinks: honeycomb: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -173,6 +258,16 @@ base: components: sinks: honeycomb: configuration: {
 					uint: {}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
@@ -36,12 +36,43 @@ base: components: sinks: http: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			audience: {
+				description: """
+					The audience to request the access token for.
+
+					This isn't part of the OAuth 2.0 specification, but some authorization servers require it.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: string: examples: ["https://api.example.com"]
+			}
+			client_id: {
+				description:   "The client identifier."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["${CLIENT_ID}", "vector"]
+			}
+			client_secret: {
+				description:   "The client secret."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["${CLIENT_SECRET}"]
+			}
 			password: {
 				description:   "The basic authentication password."
 				relevant_when: "strategy = \"basic\""
 				required:      true
 				type: string: examples: ["${PASSWORD}", "password"]
 			}
+			scopes: {
+				description:   "The scopes to request the access token for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: examples: ["logs.write"]
+				}
+			}
 			strategy: {
 				description: "The authentication strategy to use."
 				required:    true
@@ -58,6 +89,15 @@ base: components: sinks: http: configuration: {
 
 						The bearer token value (OAuth2, JWT, etc.) is passed as-is.
 						"""
+					oauth2: """
+						OAuth 2.0 authentication, with the client credentials grant.
+
+						An access token is requested from the authorization server with the client credentials, and
+						refreshed ahead of its expiry. It's passed as a bearer token.
+
+						This is supported by the `http` and `prometheus_remote_write` sinks, and the `http_client`
+						and `prometheus_scrape` sources.
+						"""
 				}
 			}
 			token: {
@@ -66,6 +106,12 @@ base: components: sinks: http: configuration: {
 				required:      true
 				type: string: {}
 			}
+			token_url: {
+				description:   "The URL of the token endpoint of the authorization server."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["https://auth.example.com/oauth2/token"]
+			}
 			user: {
 				description:   "The basic authentication username."
 				relevant_when: "strategy = \"basic\""
@@ -106,6 +152,19 @@ base: components: sinks: http: configuration: {
 			}
 		}
 	}
+	body_template: {
+		description: """
+			A template wrapping the encoded batch of events in the request body.
+
+			The batch is inserted in place of `{{ events }}`, which must appear exactly once. The rest
+			of the template can refer to the fields of the events, in which case the events are batched
+			separately for each body they render.
+
+			This is applied after the `payload_prefix` and `payload_suffix`, and before compression.
+			"""
+		required: false
+		type: string: examples: ["{\"tenant\":\"{{ tenant_id }}\",\"logs\":{{ events }}}"]
+	}
 	compression: {
 		description: """
 			Compression configuration.
@@ -122,11 +181,23 @@ base: components: sinks: http: configuration: {
 					[gzip]: https://www.gzip.org/
 					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression.
+
+					Payloads are compressed as a whole, in the raw format. Snappy doesn't have compression levels.
+
+					[snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
 					[zlib]: https://zlib.net/
 					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
 			}
 		}
 	}
@@ -154,9 +225,12 @@ base: components: sinks: http: configuration: {
 						[apache_avro]: https://avro.apache.org/
 						"""
 					csv: """
-						Encodes an event as a CSV message.
+						Encodes an event as a [CSV][csv] record.
 
-						This codec must be configured with fields to encode.
+						The fields encoded, and their order, are configured with `fields`, or inferred from the
+						first event encoded.
+
+						[csv]: https://datatracker.ietf.org/doc/html/rfc4180
 						"""
 					gelf: """
 						Encodes an event as a [GELF][gelf] message.
@@ -171,6 +245,9 @@ base: components: sinks: http: configuration: {
 					logfmt: """
 						Encodes an event as a [logfmt][logfmt] message.
 
+						Nested fields are flattened, and the strings that would be decoded as another type are
+						quoted, so that the events decoded with the `logfmt` decoder have the same fields and types.
+
 						[logfmt]: https://brandur.org/logfmt
 						"""
 					native: """
@@ -189,6 +266,24 @@ base: components: sinks: http: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					prometheus_text: """
+						Encodes a metric in the [Prometheus text exposition format][prometheus_text].
+
+						Each metric is encoded with its `# TYPE` line, so the metrics written to the same file
+						must have different names. Distributions are aggregated into histograms or summaries using
+						the default buckets and quantiles of the `prometheus_exporter` sink, and timestamps aren't
+						encoded, so the output can be read by the textfile collector of the node exporter.
+
+						[prometheus_text]: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+						"""
+					protobuf: """
+						Encodes an event as a [Protocol Buffers][protobuf] message.
+
+						The message type is looked up in a compiled file descriptor set. Fields of the event that
+						the message type doesn't define aren't encoded.
+
+						[protobuf]: https://protobuf.dev/
+						"""
 					raw_message: """
 						No encoding.
 
@@ -213,19 +308,86 @@ base: components: sinks: http: configuration: {
 			csv: {
 				description:   "The CSV Serializer Options."
 				relevant_when: "codec = \"csv\""
-				required:      true
-				type: object: options: fields: {
-					description: """
-						Configures the fields that will be encoded, as well as the order in which they
-						appear in the output.
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when writing CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether to escape quotes in fields by doubling them.
+
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when writing CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							Configures the fields that will be encoded, as well as the order in which they
+							appear in the output.
 
-						If a field is not present in the event, the output will be an empty string.
+							If a field is not present in the event, the output will be an empty string.
 
-						Values of type `Array`, `Object`, and `Regex` are not supported and the
-						output will be an empty string.
-						"""
-					required: true
-					type: array: items: type: string: {}
+							Values of type `Array`, `Object`, and `Regex` are not supported and the
+							output will be an empty string.
+
+							If no fields are configured, the top-level fields of the first event encoded are used, in
+							alphabetical order, for that event and the ones following it in the same output.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: {}
+						}
+					}
+					header: {
+						description: """
+							Whether to write a header row with the names of the fields.
+
+							The header row is written before the first event of each output: each batch of the sinks
+							sending batches of events, each file of the `file` sink, each connection of the sinks
+							streaming events, and each message of the sinks sending an event per message.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when writing CSV."
+						required:    false
+						type: uint: default: 34
+					}
+					quote_style: {
+						description: "The quoting style to use when writing CSV data."
+						required:    false
+						type: string: {
+							default: "necessary"
+							enum: {
+								always: "Always puts quotes around every field."
+								necessary: """
+									Puts quotes around fields only when necessary.
+
+									They are necessary when fields contain a quote, delimiter, or record terminator. Quotes are
+									also necessary when writing an empty record (which is indistinguishable from a record with
+									one empty field).
+									"""
+								never: "Never writes quotes, even if it produces invalid CSV data."
+								non_numeric: """
+									Puts quotes around all fields that are non-numeric.
+
+									This means that when writing a field that does not parse as a valid float or integer,
+									quotes are used even if they aren't strictly necessary.
+									"""
+							}
+						}
+					}
 				}
 			}
 			except_fields: {
@@ -233,6 +395,36 @@ base: components: sinks: http: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			logfmt: {
+				description:   "Logfmt-specific encoder options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: """
+							The separator joining the names of nested fields into the keys they are flattened to.
+
+							The elements of arrays are flattened to keys suffixed with their index in brackets, such
+							as `tags[0]`.
+							"""
+						required: false
+						type: string: default: "."
+					}
+					key_order: {
+						description: """
+							The keys to write first, in this order.
+
+							The other keys are written after them in alphabetical order. A key listed here also
+							brings first the keys flattened from the fields nested in it.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["time", "level"]
+						}
+					}
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -259,6 +451,27 @@ base: components: sinks: http: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			protobuf: {
+				description:   "Protocol Buffers-specific encoder options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to encode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -295,6 +508,11 @@ base: components: sinks: http: configuration: {
 						The prefix is a 32-bit unsigned integer, little endian.
 						"""
 					newline_delimited: "Event data is delimited by a newline (LF) character."
+					varint_length_delimited: """
+						Event data is prefixed with its length in bytes, as an unsigned varint.
+
+						This is how length-delimited Protocol Buffers messages are framed.
+						"""
 				}
 			}
 		}
@@ -394,6 +612,15 @@ base: components: sinks: http: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -408,6 +635,22 @@ base: components: sinks: http: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -447,6 +690,16 @@ base: components: sinks: http: configuration: {
 					}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
@@ -600,8 +853,15 @@ base: components: sinks: http: configuration: {
 			The full URI to make HTTP requests to.
 
 			This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+
+			The URI can be templated with the fields of the events, in which case the events are batched
+			separately for each URI they render. As `%` starts a time format specifier in templates,
+			percent-encoded characters must be written with `%%`, such as `%%20` for a space.
 			"""
 		required: true
-		type: string: examples: ["https://10.22.212.22:9000/endpoint"]
+		type: string: {
+			examples: ["https://10.22.212.22:9000/endpoint", "https://api.example.com/tenants/{{ tenant_id }}/logs"]
+			syntax: "template"
+		}
 	}
 }
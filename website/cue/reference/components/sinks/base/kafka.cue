@@ -106,9 +106,12 @@ base: components: sinks: kafka: configuration: {
 						[apache_avro]: https://avro.apache.org/
 						"""
 					csv: """
-						Encodes an event as a CSV message.
+						Encodes an event as a [CSV][csv] record.
 
-						This codec must be configured with fields to encode.
+						The fields encoded, and their order, are configured with `fields`, or inferred from the
+						first event encoded.
+
+						[csv]: https://datatracker.ietf.org/doc/html/rfc4180
 						"""
 					gelf: """
 						Encodes an event as a [GELF][gelf] message.
@@ -123,6 +126,9 @@ base: components: sinks: kafka: configuration: {
 					logfmt: """
 						Encodes an event as a [logfmt][logfmt] message.
 
+						Nested fields are flattened, and the strings that would be decoded as another type are
+						quoted, so that the events decoded with the `logfmt` decoder have the same fields and types.
+
 						[logfmt]: https://brandur.org/logfmt
 						"""
 					native: """
@@ -141,6 +147,24 @@ base: components: sinks: kafka: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					prometheus_text: """
+						Encodes a metric in the [Prometheus text exposition format][prometheus_text].
+
+						Each metric is encoded with its `# TYPE` line, so the metrics written to the same file
+						must have different names. Distributions are aggregated into histograms or summaries using
+						the default buckets and quantiles of the `prometheus_exporter` sink, and timestamps aren't
+						encoded, so the output can be read by the textfile collector of the node exporter.
+
+						[prometheus_text]: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+						"""
+					protobuf: """
+						Encodes an event as a [Protocol Buffers][protobuf] message.
+
+						The message type is looked up in a compiled file descriptor set. Fields of the event that
+						the message type doesn't define aren't encoded.
+
+						[protobuf]: https://protobuf.dev/
+						"""
 					raw_message: """
 						No encoding.
 
@@ -165,19 +189,86 @@ base: components: sinks: kafka: configuration: {
 			csv: {
 				description:   "The CSV Serializer Options."
 				relevant_when: "codec = \"csv\""
-				required:      true
-				type: object: options: fields: {
-					description: """
-						Configures the fields that will be encoded, as well as the order in which they
-						appear in the output.
-
-						If a field is not present in the event, the output will be an empty string.
-
-						Values of type `Array`, `Object`, and `Regex` are not supported and the
-						output will be an empty string.
-						"""
-					required: true
-					type: array: items: type: string: {}
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when writing CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether to escape quotes in fields by doubling them.
+
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when writing CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							Configures the fields that will be encoded, as well as the order in which they
+							appear in the output.
+
+							If a field is not present in the event, the output will be an empty string.
+
+							Values of type `Array`, `Object`, and `Regex` are not supported and the
+							output will be an empty string.
+
+							If no fields are configured, the top-level fields of the first event encoded are used, in
+							alphabetical order, for that event and the ones following it in the same output.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: {}
+						}
+					}
+					header: {
+						description: """
+							Whether to write a header row with the names of the fields.
+
+							The header row is written before the first event of each output: each batch of the sinks
+							sending batches of events, each file of the `file` sink, each connection of the sinks
+							streaming events, and each message of the sinks sending an event per message.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when writing CSV."
+						required:    false
+						type: uint: default: 34
+					}
+					quote_style: {
+						description: "The quoting style to use when writing CSV data."
+						required:    false
+						type: string: {
+							default: "necessary"
+							enum: {
+								always: "Always puts quotes around every field."
+								necessary: """
+									Puts quotes around fields only when necessary.
+
+									They are necessary when fields contain a quote, delimiter, or record terminator. Quotes are
+									also necessary when writing an empty record (which is indistinguishable from a record with
+									one empty field).
+									"""
+								never: "Never writes quotes, even if it produces invalid CSV data."
+								non_numeric: """
+									Puts quotes around all fields that are non-numeric.
+
+									This means that when writing a field that does not parse as a valid float or integer,
+									quotes are used even if they aren't strictly necessary.
+									"""
+							}
+						}
+					}
 				}
 			}
 			except_fields: {
@@ -185,6 +276,36 @@ base: components: sinks: kafka: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			logfmt: {
+				description:   "Logfmt-specific encoder options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: """
+							The separator joining the names of nested fields into the keys they are flattened to.
+
+							The elements of arrays are flattened to keys suffixed with their index in brackets, such
+							as `tags[0]`.
+							"""
+						required: false
+						type: string: default: "."
+					}
+					key_order: {
+						description: """
+							The keys to write first, in this order.
+
+							The other keys are written after them in alphabetical order. A key listed here also
+							brings first the keys flattened from the fields nested in it.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["time", "level"]
+						}
+					}
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -211,6 +332,27 @@ base: components: sinks: kafka: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			protobuf: {
+				description:   "Protocol Buffers-specific encoder options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to encode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -230,6 +372,16 @@ base: components: sinks: kafka: configuration: {
 		required: false
 		type: string: examples: ["headers"]
 	}
+	idempotent: {
+		description: """
+			Whether the producer is idempotent, so that the messages it retries are written exactly
+			once, and in order, to their partition.
+
+			Always enabled when a `transactional_id` is set.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	key_field: {
 		description: """
 			The log field name or tags key to use for the topic key.
@@ -274,6 +426,19 @@ base: components: sinks: kafka: configuration: {
 			unit: "milliseconds"
 		}
 	}
+	preserve_order_by: {
+		description: """
+			The log field name whose value the events are kept in order by.
+
+			The events with the same value are produced one at a time, in the order they're received
+			in, so that the retries of an event can't reorder it with the next ones. This limits the
+			throughput of each value to one message in flight, and the events missing the field are
+			produced without any ordering. The transactions of a `transactional_id` are always produced
+			one at a time.
+			"""
+		required: false
+		type: string: examples: ["user_id"]
+	}
 	sasl: {
 		description: "Configuration for SASL authentication when interacting with Kafka."
 		required:    false
@@ -310,6 +475,165 @@ base: components: sinks: kafka: configuration: {
 			}
 		}
 	}
+	schema_registry: {
+		description: """
+			Produces messages in the Confluent wire format, registering the Avro schema in a schema
+			registry.
+
+			Requires the `avro` codec. The schema is registered when the sink starts, and the ID it is
+			registered with prefixes every message.
+			"""
+		required: false
+		type: object: options: {
+			auth: {
+				description: """
+					Configuration of the authentication strategy for HTTP requests.
+
+					HTTP authentication should almost always be used with HTTPS only, as the authentication credentials are passed as an
+					HTTP header without any additional encryption beyond what is provided by the transport itself.
+					"""
+				required: false
+				type: object: options: {
+					password: {
+						description:   "The basic authentication password."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${PASSWORD}", "password"]
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: enum: {
+							basic: """
+								Basic authentication.
+
+								The username and password are concatenated and encoded via [base64][base64].
+
+								[base64]: https://en.wikipedia.org/wiki/Base64
+								"""
+							bearer: """
+								Bearer authentication.
+
+								The bearer token value (OAuth2, JWT, etc.) is passed as-is.
+								"""
+						}
+					}
+					token: {
+						description:   "The bearer authentication token."
+						relevant_when: "strategy = \"bearer\""
+						required:      true
+						type: string: {}
+					}
+					user: {
+						description:   "The basic authentication username."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${USERNAME}", "username"]
+					}
+				}
+			}
+			subject: {
+				description: """
+					The subject to register the schema under.
+
+					Only used when producing messages. If not set, it defaults to `<topic>-value`, following
+					the default `TopicNameStrategy`, which requires the topic to not be templated.
+					"""
+				required: false
+				type: string: examples: ["logs-value"]
+			}
+			tls: {
+				description: "TLS configuration."
+				required:    false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+							that they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must not be expired and must be issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+							certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+							so on until the verification process reaches a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+			url: {
+				description: "The URL of the schema registry."
+				required:    true
+				type: string: examples: ["http://localhost:8081"]
+			}
+		}
+	}
 	socket_timeout_ms: {
 		description: "Default timeout, in milliseconds, for network requests."
 		required:    false
@@ -422,4 +746,53 @@ base: components: sinks: kafka: configuration: {
 			syntax: "template"
 		}
 	}
+	transaction_timeout_ms: {
+		description: """
+			The maximum amount of time a transaction can be open, in milliseconds, before the broker
+			aborts it.
+
+			Must be longer than `batch.timeout_secs`.
+			"""
+		required: false
+		type: uint: {
+			default: 60000
+			examples: [60000]
+			unit: "milliseconds"
+		}
+	}
+	transactional_id: {
+		description: """
+			The transactional ID of the producer, which produces the events of each batch in a
+			transaction when set.
+
+			The events of a batch are only acknowledged once its transaction is committed, and the
+			transaction is aborted if any of them fails to be produced, so the consumers reading with
+			`isolation.level` set to `read_committed` only see the batches which were fully produced.
+			When Vector restarts, the transaction left open by its previous run is aborted, so the
+			events delivered again by sources supporting end-to-end acknowledgements aren't
+			duplicated.
+
+			The ID must be unique to each instance of Vector, and stay the same across its restarts.
+			The batches are bounded by the `batch` options.
+			"""
+		required: false
+		type: string: examples: ["vector-aggregator-0"]
+	}
+	transactional_source: {
+		description: """
+			The ID of a `kafka` source whose consumer offsets are committed in the transactions of the
+			sink, for the events it consumed.
+
+			The offsets are committed to the consumer group of the source along with the messages they
+			were transformed into, so each message consumed by the source is produced exactly once,
+			even when Vector restarts. The source then leaves committing its offsets to the sink. It
+			must use a consumer group and have end-to-end acknowledgements enabled, which is checked
+			when the configuration is loaded. The events which weren't consumed by the source are
+			produced without committing any offset.
+
+			Requires a `transactional_id`.
+			"""
+		required: false
+		type: string: examples: ["kafka_in"]
+	}
 }
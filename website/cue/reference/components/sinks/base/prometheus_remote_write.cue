@@ -47,12 +47,73 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required:      true
 				type: string: examples: ["arn:aws:iam::123456789098:role/my_role"]
 			}
+			assume_role_chain: {
+				description: """
+					The roles to assume in turn once `assume_role` is, each with the credentials of the
+					previous one, such as to reach a role in another account.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						assume_role: {
+							description: """
+								The ARN of an [IAM role][iam_role] to assume.
+
+								[iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
+								"""
+							required: true
+							type: string: examples: ["arn:aws:iam::210987654321:role/other_role"]
+						}
+						external_id: {
+							description: """
+								The [external ID][external_id] the trust policy of the role requires, if any.
+
+								[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+								"""
+							required: false
+							type: string: examples: ["MyExternalId"]
+						}
+					}
+				}
+			}
+			audience: {
+				description: """
+					The audience to request the access token for.
+
+					This isn't part of the OAuth 2.0 specification, but some authorization servers require it.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: string: examples: ["https://api.example.com"]
+			}
+			client_id: {
+				description:   "The client identifier."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["${CLIENT_ID}", "vector"]
+			}
+			client_secret: {
+				description:   "The client secret."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["${CLIENT_SECRET}"]
+			}
 			credentials_file: {
 				description:   "Path to the credentials file."
 				relevant_when: "strategy = \"aws\""
 				required:      true
 				type: string: examples: ["/my/aws/credentials"]
 			}
+			external_id: {
+				description: """
+					The [external ID][external_id] the trust policy of `assume_role` requires, if any.
+
+					[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+					"""
+				required: false
+				type: string: examples: ["MyExternalId"]
+			}
 			imds: {
 				description:   "Configuration for authenticating with AWS through IMDS."
 				relevant_when: "strategy = \"aws\""
@@ -79,6 +140,19 @@ base: components: sinks: prometheus_remote_write: configuration: {
 							unit:    "seconds"
 						}
 					}
+					token_ttl_seconds: {
+						description: """
+							How long the IMDSv2 session tokens are valid for.
+
+							The metadata is fetched with IMDSv2, requesting a session token which is reused until it
+							expires.
+							"""
+						required: false
+						type: uint: {
+							default: 21600
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 			load_timeout_secs: {
@@ -118,7 +192,7 @@ base: components: sinks: prometheus_remote_write: configuration: {
 					The [AWS region][aws_region] to send STS requests to.
 
 					If not set, this defaults to the configured region
-					for the service itself.
+					for the service itself. The requests are sent to the regional STS endpoint.
 
 					[aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
 					"""
@@ -126,12 +200,34 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required:      false
 				type: string: examples: ["us-west-2"]
 			}
+			scopes: {
+				description:   "The scopes to request the access token for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: examples: ["logs.write"]
+				}
+			}
 			secret_access_key: {
 				description:   "The AWS secret access key."
 				relevant_when: "strategy = \"aws\""
 				required:      true
 				type: string: examples: ["wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY"]
 			}
+			session_name: {
+				description: "The name of the session of the role assumed with the web identity token."
+				required:    false
+				type: string: {
+					default: "vector"
+					examples: ["my-session"]
+				}
+			}
+			web_identity_token_file: {
+				description: "Path to the file of the web identity token."
+				required:    true
+				type: string: examples: ["/var/run/secrets/eks.amazonaws.com/serviceaccount/token"]
+			}
 			strategy: {
 				description: "The authentication strategy to use."
 				required:    true
@@ -143,6 +239,12 @@ base: components: sinks: prometheus_remote_write: configuration: {
 
 						A bearer token (OAuth2, JWT, etc) is passed as-is.
 						"""
+					oauth2: """
+						OAuth 2.0 authentication, with the client credentials grant.
+
+						An access token is requested from the authorization server with the client credentials, and
+						refreshed ahead of its expiry. It's passed as a bearer token.
+						"""
 				}
 			}
 			token: {
@@ -151,6 +253,12 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required:      true
 				type: string: {}
 			}
+			token_url: {
+				description:   "The URL of the token endpoint of the authorization server."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["https://auth.example.com/oauth2/token"]
+			}
 			user: {
 				description:   "Basic authentication username."
 				relevant_when: "strategy = \"basic\""
@@ -246,6 +354,26 @@ base: components: sinks: prometheus_remote_write: configuration: {
 		required: true
 		type: string: examples: ["https://localhost:8087/api/v1/write"]
 	}
+	exemplar_tags: {
+		description: """
+			Tags to send as the labels of an [exemplar][exemplars] of the samples, rather than as labels
+			of their series.
+
+			Exemplars reference the context of a sample without being part of its series, such as the
+			ID of the trace of the request a counter was incremented for. They are only attached to
+			counters and gauges: the tags are removed from the other metrics.
+
+			Prometheus only stores exemplars when the `exemplar-storage` feature is enabled, and rejects
+			the exemplars whose labels are longer than 128 characters in total.
+
+			[exemplars]: https://prometheus.io/docs/prometheus/latest/feature_flags/#exemplars-storage
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["trace_id"]
+		}
+	}
 	quantiles: {
 		description: """
 			Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
@@ -300,6 +428,15 @@ base: components: sinks: prometheus_remote_write: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -314,6 +451,22 @@ base: components: sinks: prometheus_remote_write: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -338,6 +491,16 @@ base: components: sinks: prometheus_remote_write: configuration: {
 					uint: {}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
@@ -401,6 +564,11 @@ base: components: sinks: prometheus_remote_write: configuration: {
 			}
 		}
 	}
+	send_metadata: {
+		description: "Whether to send the metadata of the metrics, such as their type, along with their samples."
+		required:    false
+		type: bool: default: true
+	}
 	tenant_id: {
 		description: """
 			The tenant ID to send.
@@ -75,6 +75,18 @@ base: components: sinks: statsd: configuration: {
 			}
 		}
 	}
+	counter_sample_rate: {
+		description: """
+			The rate at which incremental counters are sampled before being sent.
+
+			Must be greater than `0` and at most `1`. Each incremental counter is sent with this
+			probability, and annotated with the sample rate (`|@<rate>`) so that the receiving server
+			can scale the value back up. This reduces traffic for very high-volume counters at the
+			cost of precision. When unset, every counter is sent.
+			"""
+		required: false
+		type: float: examples: [0.1]
+	}
 	default_namespace: {
 		description: """
 			Sets the default namespace for any metrics sent.
@@ -129,6 +141,27 @@ base: components: sinks: statsd: configuration: {
 			unit: "bytes"
 		}
 	}
+	tag_format: {
+		description: "The format used to encode metric tags."
+		required:    false
+		type: string: {
+			default: "dogstatsd"
+			enum: {
+				dogstatsd: """
+					Tags are appended to the metric in the DogStatsD format.
+
+					For example, `name:1|c|#tag:value,bare_tag`.
+					"""
+				influxdb: """
+					Tags are appended to the metric name in the InfluxDB (Telegraf) format.
+
+					For example, `name,tag=value:1|c`. Bare tags are dropped, and only the last value of
+					multi-valued tags is sent.
+					"""
+				none: "Tags are not sent."
+			}
+		}
+	}
 	tls: {
 		description:   "Configures the TLS options for incoming/outgoing connections."
 		relevant_when: "mode = \"tcp\""
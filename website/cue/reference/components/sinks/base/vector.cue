@@ -72,14 +72,85 @@ base: components: sinks: vector: configuration: {
 	}
 	compression: {
 		description: """
-			Whether or not to compress requests.
+			The compression of the requests.
 
-			If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+			For backwards compatibility, `true` and `false` are also accepted, as `gzip` and `none`.
+			"""
+		required: false
+		type: string: {
+			default: "none"
+			enum: {
+				gzip: """
+					The requests are compressed with [`gzip`][gzip_docs] by gRPC.
+
+					[gzip_docs]: https://www.gzip.org/
+					"""
+				none: "The requests aren't compressed."
+				zstd: """
+					The events of the requests are compressed with [`zstd`][zstd_docs].
+
+					This requires that the downstream `vector` source supports `zstd` compression: the
+					sources of older versions of Vector reject these requests, so that they aren't retried.
+
+					[zstd_docs]: https://facebook.github.io/zstd/
+					"""
+			}
+		}
+	}
+	load_balancing: {
+		description: """
+			Configuration of the load balancing of the requests across the downstream Vector aggregators.
+
+			The hostname of the address is resolved periodically, and the requests are balanced across all
+			of its IP addresses. The aggregators whose requests keep failing are ejected for a while.
 
-			[gzip_docs]: https://www.gzip.org/
+			When TLS is enabled, the certificates of the aggregators are verified against their IP
+			addresses, so `tls.verify_hostname` must be disabled unless the certificates include them.
 			"""
 		required: false
-		type: bool: default: false
+		type: object: options: {
+			ejection_secs: {
+				description: """
+					The duration an ejected aggregator is left out of the load balancing.
+
+					The aggregator is then sent requests again, and ejected again at its next failure.
+					"""
+				required: false
+				type: uint: {
+					default: 30
+					unit:    "seconds"
+				}
+			}
+			key: {
+				description: """
+					The key to balance the events by.
+
+					The events with the same key are sent to the same aggregator, as long as it is available.
+					The keys are spread with consistent hashing, so that only the keys of an aggregator which is
+					added, removed, or ejected move to other aggregators.
+
+					If not set, the requests are sent to the aggregators in turn.
+					"""
+				required: false
+				type: string: {
+					examples: ["{{ host }}"]
+					syntax: "template"
+				}
+			}
+			max_failures: {
+				description: "The number of consecutive failed requests after which an aggregator is ejected."
+				required:    false
+				type: uint: default: 5
+			}
+			refresh_interval_secs: {
+				description: "The interval between the resolutions of the hostname of the address."
+				required:    false
+				type: uint: {
+					default: 30
+					unit:    "seconds"
+				}
+			}
+		}
 	}
 	request: {
 		description: """
@@ -123,6 +194,15 @@ base: components: sinks: vector: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -137,6 +217,22 @@ base: components: sinks: vector: configuration: {
 						required: false
 						type: float: default: 2.5
 					}
+					target_rtt_secs: {
+						description: """
+																A target RTT, in seconds, to compare the current RTT with instead of the average of past RTT measurements.
+
+																Valid values are greater than `0`. The concurrency limit is increased while the current RTT is at or below the
+																target, and decreased once it goes above the target by more than the scaled deviation.
+
+																This is useful for services whose latency degrades slowly under load, which the past RTT average follows without
+																triggering a decrease.
+																"""
+						required: false
+						type: float: {
+							examples: [0.5]
+							unit:     "seconds"
+						}
+					}
 				}
 			}
 			concurrency: {
@@ -161,6 +257,16 @@ base: components: sinks: vector: configuration: {
 					uint: {}
 				}
 			}
+			rate_limit_bytes_per_sec: {
+				description: """
+					The maximum number of bytes sent per second.
+
+					The requests are weighed by their encoded size, and are delayed once the limit is reached.
+					The sinks still using the deprecated batching don't support this option, and ignore it.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
 			rate_limit_duration_secs: {
 				description: "The time window used for the `rate_limit_num` option."
 				required:    false
@@ -224,6 +330,57 @@ base: components: sinks: vector: configuration: {
 			}
 		}
 	}
+	streams: {
+		description: """
+			Configuration of the logical streams the events are sent in.
+
+			The logs, metrics, and traces are batched separately, in streams of their own multiplexed over
+			the same connection. When the requests are backpressured, the ones of the streams with a higher
+			priority are sent first, so that metrics can be prioritized over bulk logs, for example.
+			"""
+		required: false
+		type: object: options: {
+			logs: {
+				description: "Configuration of a logical stream."
+				required:    false
+				type: object: options: priority: {
+					description: """
+						The priority of the stream.
+
+						The requests of the streams with a higher priority are sent first.
+						"""
+					required: false
+					type: uint: default: 0
+				}
+			}
+			metrics: {
+				description: "Configuration of a logical stream."
+				required:    false
+				type: object: options: priority: {
+					description: """
+						The priority of the stream.
+
+						The requests of the streams with a higher priority are sent first.
+						"""
+					required: false
+					type: uint: default: 0
+				}
+			}
+			traces: {
+				description: "Configuration of a logical stream."
+				required:    false
+				type: object: options: priority: {
+					description: """
+						The priority of the stream.
+
+						The requests of the streams with a higher priority are sent first.
+						"""
+					required: false
+					type: uint: default: 0
+				}
+			}
+		}
+	}
 	tls: {
 		description: "Configures the TLS options for incoming/outgoing connections."
 		required:    false
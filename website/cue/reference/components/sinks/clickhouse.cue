@@ -81,6 +81,22 @@ components: sinks: clickhouse: {
 		traces:  false
 	}
 
+	how_it_works: {
+		native_protocol: {
+			title: "Native Protocol"
+			body: """
+				By default, events are inserted over the HTTP interface, in the `JSONEachRow` format.
+				When `native` is configured, events are instead inserted over the native TCP interface,
+				as columnar blocks compressed with LZ4. The native protocol requires the type of each
+				column of the table to be configured under `native.columns`, so that event fields can be
+				converted to the matching ClickHouse types. Events with fields that can't be converted are
+				dropped.
+
+				TLS and bearer authentication aren't supported with the native protocol.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
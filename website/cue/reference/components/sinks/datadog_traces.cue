@@ -73,6 +73,27 @@ components: sinks: datadog_traces: {
 		traces:  true
 	}
 
+	how_it_works: {
+		otlp_traces: {
+			title: "OpenTelemetry traces"
+			body: """
+				Along with the traces of the `datadog_agent` source, the traces received by the
+				`opentelemetry` source are sent as they are converted to the Datadog span model.
+				Their top-level spans, the ones whose parent isn't part of the trace, are counted in
+				the APM stats, and their priority defaults to `1` as for the traces the Datadog Agent
+				receives over OTLP.
+				"""
+		}
+		profiles: {
+			title: "Profiles"
+			body: """
+				The profiles passed through by the `datadog_agent` source are forwarded as-is to the
+				profiling intake, one request per profile, along with the headers they were received
+				with. They aren't batched with the traces, compressed, or counted in the APM stats.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
@@ -127,8 +127,20 @@ components: sinks: elasticsearch: {
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
 
-				By default, partial failures are not retried. To enable retries, set `request_retry_partial`. Once enabled it will
-				retry whole partially failed requests. As such it is advised to use `id_key` to avoid duplicates.
+				By default, partial failures are not retried. To enable retries, set `request_retry_partial`. Once enabled,
+				the documents which failed with back pressure (`429`) or server errors, or because their index was blocked
+				or closed while ILM rolled over the write index of a data stream or alias, are sent again on their own,
+				up to `request.retry_attempts` times. Documents are sent again when a request times out or fails as a
+				whole, so it is still advised to use `id_key` to avoid duplicates.
+
+				Documents which fail with an error which is not retried, such as mapping errors, are dropped unless
+				`dead_letter_index` is set, in which case they are written to that index along with the error
+				returned by Elasticsearch, so that they can be inspected and reprocessed. Only the events of the
+				documents which are dropped are rejected, while the others are acknowledged as delivered. When
+				`reroute_failed` is set, the events of the dropped documents are sent to the `dlq` output, with the
+				status and the type of error returned by Elasticsearch as their `status_code` and `error_class`. The outcome
+				of each failed document is counted by the `elasticsearch_bulk_item_errors_total` metric, tagged by
+				error type.
 				"""
 		}
 
@@ -136,11 +148,12 @@ components: sinks: elasticsearch: {
 	}
 
 	telemetry: metrics: {
-		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
-		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
-		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
-		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
-		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
-		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+		component_sent_bytes_total:           components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:          components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total:     components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		elasticsearch_bulk_item_errors_total: components.sources.internal_metrics.output.metrics.elasticsearch_bulk_item_errors_total
+		events_discarded_total:               components.sources.internal_metrics.output.metrics.events_discarded_total
+		events_out_total:                     components.sources.internal_metrics.output.metrics.events_out_total
+		processing_errors_total:              components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
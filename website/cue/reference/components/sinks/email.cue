@@ -0,0 +1,96 @@
+package metadata
+
+components: sinks: email: {
+	title: "Email"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: enabled: false
+			tls: enabled:     false
+			to: {
+				service: {
+					name:     "SMTP"
+					thing:    "an \(name) server"
+					url:      urls.smtp
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.email.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		digests: {
+			title: "Digests"
+			body: """
+				The events are batched into digests by recipients, so that an alert firing repeatedly
+				is sent as a single email. A digest is sent once `digest.window_secs` elapsed since its
+				first event, with the subject of its first event followed by the number of the other
+				events, and the bodies of up to `digest.max_events` events. The events in excess are
+				only counted at the end of the digest.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				With `rate_limit` set, at most `rate_limit.max_emails` emails are sent to each
+				recipient in each `rate_limit.period_secs`. A digest whose recipients reached the
+				limit keeps collecting events until it can be sent. When Vector stops, the pending
+				digests are sent regardless of their window and of the rate limit.
+				"""
+		}
+		tls: {
+			title: "TLS"
+			body: """
+				By default, the connection is upgraded to TLS with the `STARTTLS` command, on port
+				587. With `tls.mode` set to `tls`, the connection is encrypted from the start, on port
+				465, as required by some providers.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
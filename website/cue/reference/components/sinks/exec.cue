@@ -0,0 +1,89 @@
+package metadata
+
+components: sinks: exec: {
+	title: "Exec"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					framing: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: {
+				service: services.exec
+				interface: stdin: {}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.exec.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		backpressure: {
+			title: "Backpressure"
+			body: """
+				Encoded events are written to a pipe connected to the standard input of the command. Once
+				the pipe is full, the sink waits for the command to read from it before accepting more
+				events, so a slow command applies backpressure to upstream components instead of events
+				being buffered without bound.
+				"""
+		}
+		restarts: {
+			title: "Restarts"
+			body: """
+				The command is started once, when the sink starts. If it exits, or its standard input can
+				no longer be written to, it is started again after
+				[`respawn_interval_secs`](#respawn_interval_secs). An event that couldn't be written is
+				written again to the new command. Events already written to the pipe but not yet read by
+				a command that exits are lost.
+
+				The standard output and standard error of the command are discarded.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		command_executed_total:             components.sources.internal_metrics.output.metrics.command_executed_total
+		command_execution_duration_seconds: components.sources.internal_metrics.output.metrics.command_execution_duration_seconds
+		command_restarts_total:             components.sources.internal_metrics.output.metrics.command_restarts_total
+		component_errors_total:             components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -34,6 +34,21 @@ components: sinks: [Name=string]: {
 						[logged](\(urls.vector_monitoring)).
 						"""
 			}
+			gcp_impersonation: {
+				title: "Workload identity federation and impersonation"
+				body:  """
+						The file of the [`credentials_path`](#credentials_path) option, or of the
+						`GOOGLE_APPLICATION_CREDENTIALS` environment variable, can hold the credentials of an
+						external account, as generated by `gcloud iam workload-identity-pools create-cred-config`.
+						Vector then reads the token of the other identity provider from the file or URL of the
+						credentials, and exchanges it with the Security Token Service for a GCP one.
+
+						Whichever credentials are found, Vector can then impersonate the service account of the
+						[`impersonate_service_account`](#impersonate_service_account) option, requesting its
+						tokens from the IAM Credentials API, so that it acts with the permissions of that service
+						account only.
+						"""
+			}
 		}
 	}
 }
@@ -0,0 +1,104 @@
+package metadata
+
+components: sinks: gcp_chronicle_udm: {
+	title: "GCP Chronicle UDM"
+
+	classes: {
+		commonly_used: true
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 15.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1000
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_chronicle
+
+				interface: {
+					socket: {
+						api: {
+							title: "GCP XML Interface"
+							url:   urls.gcp_xml_interface
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.gcp_chronicle_udm.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		udm_events: {
+			title: "UDM events"
+			body: """
+				Each log event is sent as a [UDM event](\(urls.gcp_chronicle_udm)) as it is, so it must
+				already be shaped like one, with a `metadata` object holding at least the
+				`event_timestamp` and `event_type` fields, for example with a `remap` transform.
+				Events of any log type are batched together.
+				"""
+		}
+
+		request_size: {
+			title: "Request size"
+			body: """
+				The Chronicle ingestion API rejects requests larger than 1MB. Each batch is split into
+				as many requests as needed for their encoded bodies to stay within this limit, so
+				`batch.max_bytes` can be raised without the requests being rejected. An event too
+				large to fit in a request on its own is still sent alone, and rejected.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
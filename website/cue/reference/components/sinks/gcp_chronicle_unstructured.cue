@@ -78,6 +78,23 @@ components: sinks: gcp_chronicle_unstructured: {
 	}
 
 	how_it_works: {
+		log_types: {
+			title: "Log types"
+			body: """
+				The events are batched by the log type rendered from the `log_type` template, so that
+				each request holds entries of a single log type, as required by the API.
+				"""
+		}
+
+		request_size: {
+			title: "Request size"
+			body: """
+				The Chronicle ingestion API rejects requests larger than 1MB. Each batch is split into
+				as many requests as needed for their encoded bodies to stay within this limit, so
+				`batch.max_bytes` can be raised without the requests being rejected. An event too
+				large to fit in a request on its own is still sent alone, and rejected.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -76,6 +76,29 @@ components: sinks: gcp_pubsub: {
 		traces:  false
 	}
 
+	how_it_works: {
+		message_ordering: {
+			title: "Message ordering"
+			body:  """
+				When [`ordering_key`](#ordering_key) is set, each message is published with
+				the key rendered from its event, and subscriptions with message ordering
+				enabled receive the messages of each key in the order they were published.
+
+				Publishing requests are then sent one at a time: `request.concurrency` must be
+				left unset or set to `1`. A request that fails with a retriable error is
+				retried before any following request is sent, so a retry never reorders the
+				messages of a key. A request that is rejected, or that exhausts
+				`request.retry_attempts`, is dropped and publishing resumes with the
+				following messages, as with the `resumePublish` call of the Pub/Sub client
+				libraries.
+
+				Pub/Sub's exactly-once delivery is a property of subscriptions, and not of
+				publishing: it requires no change to this sink, but retried requests may
+				still publish duplicate messages.
+				"""
+		}
+	}
+
 	permissions: iam: [
 		{
 			platform: "gcp"
@@ -99,5 +122,6 @@ components: sinks: gcp_pubsub: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+		ordering_lanes:                   components.sources.internal_metrics.output.metrics.ordering_lanes
 	}
 }
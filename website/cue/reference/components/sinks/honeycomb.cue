@@ -76,6 +76,14 @@ components: sinks: honeycomb: {
 				curl option and use the key provided with the curl example.
 				"""
 		}
+		rate_limits: {
+			title: "Rate limits"
+			body:  """
+				When Honeycomb rate limits the requests, they're retried after the delay given by the
+				`Retry-After` or `RateLimit-Reset` headers of the responses, if it's longer than the
+				backoff, up to `request.retry_max_duration_secs`.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -37,7 +37,7 @@ components: sinks: http: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "protobuf"]
 				}
 			}
 			proxy: enabled: true
@@ -34,7 +34,7 @@ components: sinks: kafka: {
 				enabled: true
 				codec: {
 					enabled: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "protobuf"]
 				}
 			}
 			request: enabled: false
@@ -84,5 +84,7 @@ components: sinks: kafka: {
 		kafka_produced_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
 		kafka_consumed_messages_total:       components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
 		kafka_consumed_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		ordering_lane_depth:                 components.sources.internal_metrics.output.metrics.ordering_lane_depth
+		ordering_lanes:                      components.sources.internal_metrics.output.metrics.ordering_lanes
 	}
 }
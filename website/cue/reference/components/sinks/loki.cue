@@ -99,6 +99,13 @@ components: sinks: loki: {
 				accepted by Loki. If no timestamp is supplied with events
 				then the Loki sink will supply its own monotonically
 				increasing timestamp.
+
+				Events that arrive slightly out of order can be sorted before being sent by
+				configuring `reorder_buffer`, which holds the events of each stream for up to
+				`reorder_buffer.max_delay_ms`. Events arriving after later events of their
+				stream were already sent are handled according to `out_of_order_action`; when
+				they are dropped, they are counted by the `reorder_window_exceeded_events_total`
+				metric.
 				"""
 		}
 
@@ -0,0 +1,83 @@
+package metadata
+
+components: sinks: nats_jetstream: {
+	title: "NATS JetStream"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.nats
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.nats_jetstream.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: components._nats.how_it_works & {
+		publish_acknowledgements: {
+			title: "Publish acknowledgements"
+			body:  """
+				Each message is published to JetStream, and its events are only marked as delivered once
+				the server acknowledges storing it. When `message_id` is set, the server drops the
+				messages published again with the same ID within the duplicate window of the stream,
+				so that the retried messages aren't stored twice.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:       components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+}
@@ -0,0 +1,137 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Sends logs, metrics, and traces to an OpenTelemetry collector, encoded as OTLP and sent over gRPC.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	configuration: base.components.sinks.opentelemetry.configuration
+
+	how_it_works: {
+		log_records: {
+			title: "Log records"
+			body: """
+				Log events are converted to OTLP log records the other way around from the
+				`opentelemetry` source: the `message` field becomes the body, the `timestamp` field the
+				time of the record, and the `resources`, `attributes`, `trace_id`, `span_id`,
+				`severity_text`, `severity_number`, `observed_timestamp`, `flags`, and
+				`dropped_attributes_count` fields their OTLP counterparts. Records are grouped by their
+				resource.
+
+				Any other field is sent as an attribute of the record, so no data is lost.
+				"""
+		}
+
+		metric_conversion: {
+			title: "Metric conversion"
+			body: """
+				Each metric is sent as a single OTLP data point, with its tags as attributes and its
+				namespace prepended to its name, separated by a `.`:
+
+				* Counters are sent as monotonic sums.
+				* Absolute gauges are sent as gauges, and incremental gauges as non-monotonic sums.
+				* Sets are sent as gauges of the number of values they hold.
+				* Distributions are aggregated into histograms with the configured `buckets`.
+				* Aggregated histograms and summaries are sent as histograms and summaries.
+				* Sketches are sent as summaries of their 50th, 75th, 90th, 95th, and 99th percentiles.
+
+				Incremental metrics are sent with the delta aggregation temporality, and absolute metrics
+				with the cumulative one.
+				"""
+		}
+
+		spans: {
+			title: "Spans"
+			body: """
+				Traces are expected in the span model shared by the `opentelemetry` and
+				`datadog_agent` sources, and are converted back to OTLP spans grouped by resource and
+				instrumentation scope. The `meta` and `metrics` of the spans become their attributes,
+				except for the OTLP fields stored in `meta` by the `opentelemetry` source. Spans that
+				weren't received over OTLP only have the lower 64 bits of their trace id set.
+				"""
+		}
+
+		retries: {
+			title: "Retries"
+			body: """
+				Requests failing with a gRPC status that the OTLP specification considers retryable,
+				such as `UNAVAILABLE` or `RESOURCE_EXHAUSTED`, are retried according to the `request`
+				options. Any other failure rejects the events.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -100,9 +100,29 @@ components: sinks: prometheus_remote_write: {
 				values for each name, Vector will only send the last value specified.
 				"""
 		}
+		metadata: {
+			title: "Metadata"
+			body: """
+				The type of each metric is sent along with its samples, unless `send_metadata` is
+				disabled. When the name of a metric ends with one of the [base units](\(urls.prometheus_base_units)),
+				such as `_seconds` or `_bytes`, optionally followed by `_total`, the unit is sent as well.
+				"""
+		}
+		rejected_samples: {
+			title: "Rejected samples"
+			body: """
+				Receivers reject the samples that are older than the latest sample of their series, or
+				that have the same timestamp with a different value, while writing the other samples of
+				the request. Such a request isn't retried, since its samples would be rejected again,
+				and the error returned by the receiver is logged instead.
+
+				The samples of each series are sent in order of their timestamps.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
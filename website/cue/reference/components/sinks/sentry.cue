@@ -0,0 +1,108 @@
+package metadata
+
+components: sinks: sentry: {
+	title: "Sentry"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["Sentry"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: {
+					name:     "Sentry"
+					thing:    "a \(name) project"
+					url:      urls.sentry
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						api: {
+							title: "Sentry envelope API"
+							url:   urls.sentry_envelopes
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.sentry.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		envelopes: {
+			title: "Envelopes"
+			body: """
+				Each log event is sent as a Sentry event, in an envelope of its own. The message of the
+				event is the message of the Sentry event, its level and its exception are read from
+				`level_key` and `exception_key`, and its host is the server name. The other fields of
+				the event are attached to the Sentry event as extra data.
+				"""
+		}
+		fingerprints: {
+			title: "Fingerprints"
+			body: """
+				Sentry groups the events into issues by their fingerprint. With `fingerprint` set, the
+				fingerprint of each event is rendered from its templates, which is left to Sentry if
+				any of them fails to render.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				With `rate_limit` set, at most `rate_limit.max_events` events are sent with each
+				fingerprint in each `rate_limit.period_secs`, so that an error logged in a loop doesn't
+				exhaust the quota of the project. The events without a fingerprint are limited by their
+				exception, or else by their message. The events in excess are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -0,0 +1,109 @@
+package metadata
+
+components: sinks: sql: {
+	title: "SQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: {
+					name:     "PostgreSQL or MySQL"
+					thing:    "a \(name) database"
+					url:      urls.postgresql
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.sql.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		batched_inserts: {
+			title: "Batched inserts"
+			body: """
+				Each batch of events is inserted with multi-row `INSERT` statements, in a single
+				transaction, with the values of the columns bound as parameters. A batch only needs
+				more than one statement when it has more values than the 65535 parameters a statement
+				can have.
+
+				Events are batched by table, so a `table` that references fields splits the events
+				into a batch per table. Events whose table or columns can't be rendered are dropped.
+				"""
+		}
+		conflicts: {
+			title: "Conflicts"
+			body: """
+				By default, a row that conflicts with an existing row, such as on a primary key, fails
+				the insert of its whole batch, which isn't retried. With `on_conflict` set to `ignore`,
+				conflicting rows are skipped, with `ON CONFLICT DO NOTHING` for PostgreSQL and
+				`INSERT IGNORE` for MySQL. With `on_conflict` set to `update`, conflicting rows update
+				the existing ones, with `ON CONFLICT (...) DO UPDATE` for PostgreSQL and
+				`ON DUPLICATE KEY UPDATE` for MySQL.
+				"""
+		}
+		retries: {
+			title: "Retries"
+			body: """
+				Inserts are retried when the connection to the database fails, and when the database
+				reports a transient error, such as a deadlock or a shutdown. Other errors reported by
+				the database, such as constraint violations or type mismatches, aren't retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
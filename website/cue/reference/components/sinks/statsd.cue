@@ -49,6 +49,37 @@ components: sinks: statsd: {
 
 	configuration: base.components.sinks.statsd.configuration
 
+	how_it_works: {
+		tag_formats: {
+			title: "Tag formats"
+			body: """
+				Plain StatsD has no notion of tags, so servers have adopted different extensions for
+				them. By default tags are sent in the DogStatsD format, which is understood by the
+				Datadog Agent and the `statsd` source. Set `tag_format` to `influxdb` to send tags in
+				the format understood by Telegraf, or to `none` for servers that don't support tags.
+				"""
+		}
+
+		packets: {
+			title: "Multi-metric packets"
+			body: """
+				When sending over UDP, multiple metrics are packed into each datagram, separated by
+				newlines, until the packet reaches `batch.max_bytes`. The default of 1300 bytes fits
+				within a typical Ethernet MTU; raise it on networks with jumbo frames, or lower it if
+				the server drops large packets.
+				"""
+		}
+
+		sampling: {
+			title: "Counter sampling"
+			body: """
+				When `counter_sample_rate` is set, incremental counters are sent with that
+				probability and annotated with the sample rate, so that the server can scale them
+				back up. Absolute counters and other metric types are always sent.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
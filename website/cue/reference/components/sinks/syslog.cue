@@ -0,0 +1,103 @@
+package metadata
+
+components: sinks: syslog: {
+	title: "Syslog"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			send_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp`"
+			}
+			keepalive: enabled: true
+			request: enabled:   false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.syslog
+
+				interface: {
+					socket: {
+						api: {
+							title: "Syslog"
+							url:   urls.syslog
+						}
+						direction: "outgoing"
+						protocols: ["tcp", "udp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.syslog.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		formats: {
+			title: "Formats"
+			body:  """
+				The messages are formatted as described in [RFC 5424](\(urls.syslog_5424)) by default,
+				or in [RFC 3164](\(urls.syslog_3164)) for the receivers not supporting it. The body of
+				the messages is the event as encoded by the `encoding` options, which is usually the
+				`message` field with the `text` codec.
+
+				The header fields are taken from the fields set by the [`syslog` source](\(urls.vector_syslog_source)),
+				so that the messages it receives are forwarded unchanged, including their structured data.
+				Each of them can be set with a template instead.
+				"""
+		}
+
+		framing: {
+			title: "Framing"
+			body:  """
+				Over TCP, the messages are delimited as described in [RFC 6587](\(urls.syslog_6587)),
+				either by prefixing them with their length, which is the default, or by following them
+				with a newline. Over UDP, each message is sent in a datagram of its own.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
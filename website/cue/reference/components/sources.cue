@@ -96,9 +96,22 @@ components: sources: [Name=string]: {
 									enum: {
 										bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (e.g. split between messages or stream segments)."
 										character_delimited: "Byte frames which are delimited by a chosen character."
+										chunked_gelf: """
+											Byte frames which are [GELF][gelf] messages sent over UDP, possibly chunked and compressed.
+
+											Each datagram holds either a whole message or a chunk of one. The chunks are reassembled
+											into their message, and the messages compressed with gzip or zlib are decompressed.
+
+											[gelf]: https://docs.graylog.org/docs/gelf
+											"""
 										length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 										newline_delimited:   "Byte frames which are delimited by a newline character."
 										octet_counting:      "Byte frames according to the [octet counting](\(urls.rfc_6587_3_4_1)) format."
+										varint_length_delimited: """
+											Byte frames which are prefixed by an unsigned varint indicating the length.
+
+											This is how length-delimited Protocol Buffers messages are framed.
+											"""
 									}
 								}
 							}
@@ -126,6 +139,45 @@ components: sources: [Name=string]: {
 									}
 								}
 							}
+							chunked_gelf: {
+								description:   "Options for the chunked GELF decoder."
+								relevant_when: "method = \"chunked_gelf\""
+								required:      false
+								type: object: options: {
+									max_length: {
+										description: """
+											The maximum length of the messages, once reassembled and decompressed.
+
+											By default, there is no maximum length enforced.
+											"""
+										required: false
+										type: uint: {}
+									}
+									pending_messages_limit: {
+										description: """
+											The maximum number of messages which are reassembled at the same time.
+
+											The chunks starting new messages are discarded once the limit is reached.
+
+											By default, there is no limit.
+											"""
+										required: false
+										type: uint: {}
+									}
+									timeout_secs: {
+										description: """
+											The time to wait for all the chunks of a message, in seconds.
+
+											The messages which are still incomplete after this time are discarded.
+											"""
+										required: false
+										type: float: {
+											default: 5.0
+											unit:    "seconds"
+										}
+									}
+								}
+							}
 							newline_delimited: {
 								description:   "Options for `newline_delimited` framing."
 								required:      false
@@ -212,6 +264,54 @@ components: sources: [Name=string]: {
 											[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 											[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 											"""
+										protobuf: """
+											Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
+
+											The message type is looked up in a compiled file descriptor set.
+
+											[protobuf]: https://protobuf.dev/
+											"""
+									}
+								}
+							}
+							json: {
+								common:        false
+								description:   "JSON-specific decoding options."
+								relevant_when: "codec = \"json\""
+								required:      false
+								type: object: options: simd: {
+									common: false
+									description: """
+										Whether to parse the JSON with SIMD instructions.
+
+										Parsing with SIMD instructions is faster on large or numerous events, at the cost of
+										copying each frame before parsing it. It falls back to the default parser on CPUs which
+										don't support the required instructions, AVX2 or SSE4.2 on x86-64 and NEON on AArch64, so
+										the events are the same whichever parser is used.
+										"""
+									required: false
+									type: bool: default: false
+								}
+							}
+							protobuf: {
+								common:        false
+								description:   "Protocol Buffers-specific decoding options."
+								relevant_when: "codec = \"protobuf\""
+								required:      true
+								type: object: options: {
+									desc_file: {
+										description: """
+											The path to the compiled file descriptor set of the message type.
+
+											The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+											"""
+										required: true
+										type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+									}
+									message_type: {
+										description: "The fully qualified name of the message type to decode."
+										required:    true
+										type: string: examples: ["package.Message"]
 									}
 								}
 							}
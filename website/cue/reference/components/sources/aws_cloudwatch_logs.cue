@@ -0,0 +1,152 @@
+package metadata
+
+components: sources: aws_cloudwatch_logs: components._aws & {
+	title: "AWS CloudWatch Logs"
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_cloudwatch_logs
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.aws_cloudwatch_logs.configuration & {
+		_aws_include: false
+	}
+
+	output: logs: record: {
+		description: "An individual CloudWatch Logs log event"
+		fields: {
+			id: {
+				description: "The ID of the log event."
+				required:    true
+				type: string: {
+					examples: ["35683658089614582423604394983260738922885519999578275840"]
+				}
+			}
+			ingestion_timestamp: {
+				description: "The time the log event was ingested by CloudWatch Logs."
+				required:    true
+				type: timestamp: {}
+			}
+			log_group: {
+				description: "The log group of the log event."
+				required:    true
+				type: string: {
+					examples: ["/aws/lambda/my-function"]
+				}
+			}
+			log_stream: {
+				description: "The log stream of the log event."
+				required:    true
+				type: string: {
+					examples: ["2023/01/01/[$LATEST]0123456789abcdef"]
+				}
+			}
+			message: {
+				description: "The message of the log event."
+				required:    true
+				type: string: {
+					examples: ["START RequestId: 8b2e2e6f-4d2e-4f5e-9b3a-1c2d3e4f5a6b Version: $LATEST"]
+					syntax: "literal"
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_cloudwatch_logs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time of the log event."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	how_it_works: {
+		tailing: {
+			title: "Tailing log groups"
+			body: """
+				The `aws_cloudwatch_logs` source polls the log groups with the
+				[FilterLogEvents](https://docs.aws.amazon.com/AmazonCloudWatchLogs/latest/APIReference/API_FilterLogEvents.html)
+				API, page by page, waiting `poll_secs` once all their log events are tailed. The log
+				groups are given by name, or looked up by prefix at each poll, and their log streams
+				can be narrowed down by prefix, as can their log events by filter pattern.
+
+				The log events are filtered by their timestamp, so the ones ingested with a timestamp
+				earlier than the latest one tailed from their log group are missed.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The position of the source in each log group, along with the token of the next page
+				of log events, is checkpointed in the data directory after each page is sent, or
+				delivered to the sinks when `acknowledgements` are enabled. A page which isn't
+				delivered is tailed again at the next poll. Once restarted, Vector resumes from the
+				checkpoints, and only the log groups tailed for the first time start from
+				`start_ago_secs`.
+				"""
+		}
+		throttling: {
+			title: "Throttling"
+			body: """
+				The requests throttled by CloudWatch Logs, like the ones failing on its side, are
+				retried after exponentially growing delays, from a second up to a minute, for the
+				source to not exceed the quotas of the account.
+				"""
+		}
+	}
+}
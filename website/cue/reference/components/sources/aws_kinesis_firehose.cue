@@ -62,11 +62,46 @@ components: sources: aws_kinesis_firehose: {
 
 	output: logs: {
 		line: {
-			description: "One event will be published per incoming AWS Kinesis Firehose record."
+			description: "One event will be published per incoming AWS Kinesis Firehose record, or per log event of the records when `record_type` is `cloudwatch_logs`."
 			fields: {
-				timestamp: fields._current_timestamp
+				timestamp: fields._current_timestamp & {
+					description: "The time the record was sent by AWS Kinesis Firehose, or the time of the log event when `record_type` is `cloudwatch_logs`."
+				}
+				id: {
+					description: "The ID of the CloudWatch Logs log event, when `record_type` is `cloudwatch_logs`."
+					required:    false
+					type: string: {
+						examples: ["35683658089614582423604394983260738922885519999578275840"]
+					}
+				}
+				log_group: {
+					description: "The log group of the CloudWatch Logs log event, when `record_type` is `cloudwatch_logs`."
+					required:    false
+					type: string: {
+						examples: ["/lambda/test"]
+					}
+				}
+				log_stream: {
+					description: "The log stream of the CloudWatch Logs log event, when `record_type` is `cloudwatch_logs`."
+					required:    false
+					type: string: {
+						examples: ["test"]
+					}
+				}
+				owner: {
+					description: "The AWS account ID of the CloudWatch Logs log event, when `record_type` is `cloudwatch_logs`."
+					required:    false
+					type: string: {
+						examples: ["111111111111"]
+					}
+				}
+				subscription_filters: {
+					description: "The subscription filters which matched the CloudWatch Logs log event, when `record_type` is `cloudwatch_logs`."
+					required:    false
+					type: array: items: type: string: examples: ["Destination"]
+				}
 				message: {
-					description: "The raw record from the incoming payload."
+					description: "The raw record from the incoming payload, or the message of the log event when `record_type` is `cloudwatch_logs`."
 					required:    true
 					type: string: {
 						examples: ["Started GET / for 127.0.0.1 at 2012-03-10 14:28:14 +0100"]
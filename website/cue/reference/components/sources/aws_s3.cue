@@ -33,8 +33,9 @@ components: sources: aws_s3: components._aws & {
 	support: {
 		requirements: [
 			"""
-				The AWS S3 source requires a SQS queue configured to receive S3
-				bucket notifications for the desired S3 buckets.
+				With the default `sqs` strategy, the AWS S3 source requires a SQS
+				queue configured to receive S3 bucket notifications for the desired
+				S3 buckets. The `list` strategy has no such requirement.
 				""",
 		]
 		warnings: []
@@ -129,6 +130,30 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+
+		listing: {
+			title: "Listing buckets without notifications"
+			body:  """
+				When the [`strategy`](#strategy) is set to `list`, the source lists the
+				objects of the [`list.bucket`](#list.bucket) bucket, under each of the
+				[`list.prefixes`](#list.prefixes), every
+				[`list.poll_interval_secs`](#list.poll_interval_secs) seconds, and reads
+				the objects it hasn't read yet.
+
+				The key and ETag of each object read are persisted in the
+				[`list.data_dir`](#list.data_dir) directory, so objects are not read
+				again when Vector restarts, unless their content changes. Objects are
+				only considered read once their events are acknowledged, when
+				acknowledgements are enabled, and objects that failed to be read are
+				read again on the next listing. The state is written after each page of
+				objects listed, so objects read since the last write are read again if
+				Vector stops abruptly.
+
+				Setting [`list.start_from`](#list.start_from) skips the objects last
+				modified before the given time, for example to only ingest recent data
+				from a large historical bucket.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -142,6 +167,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `list`"
+				},
 			]
 		},
 		{
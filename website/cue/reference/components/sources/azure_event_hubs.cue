@@ -0,0 +1,143 @@
+package metadata
+
+components: sources: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.azure_event_hubs
+				interface: {
+					socket: {
+						api: {
+							title: "AMQP 1.0 protocol"
+							url:   urls.amqp_protocol
+						}
+						direction: "outgoing"
+						port:      5671
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.azure_event_hubs.configuration
+
+	output: logs: record: {
+		description: "An individual event of the event hub."
+		fields: {
+			message: {
+				description: "The body of the event."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			offset: {
+				description: "The offset of the event in its partition."
+				required:    true
+				type: string: {
+					examples: ["4294967296"]
+					syntax: "literal"
+				}
+			}
+			partition_id: {
+				description: "The identifier of the partition the event was read from."
+				required:    true
+				type: string: {
+					examples: ["0"]
+					syntax: "literal"
+				}
+			}
+			partition_key: {
+				description: "The partition key the event was sent with, if any."
+				required:    false
+				type: string: {
+					examples: ["my-key"]
+					syntax: "literal"
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the event in its partition."
+				required:    true
+				type: int: {
+					examples: [1024]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the event was enqueued to the event hub, or the current time if it isn't known."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: {
+		consumer_groups: {
+			title: "Consumer groups"
+			body:  """
+				Every consumer group of an event hub reads all of its events, independently of the other
+				consumer groups. Vector reads the event hub as the `$Default` consumer group unless
+				`consumer_group` is set. Give Vector its own consumer group when other applications
+				already read the event hub, so that the checkpoints of each application stay separate.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body:  """
+				Without `checkpoint_store`, Vector reads every partition of the event hub, starting from
+				`start_position` each time it starts.
+
+				With `checkpoint_store`, the offset of the last event read from each partition is
+				written to an Azure Blob Storage container every `checkpoint_interval_secs`, and reading
+				starts again from it. With acknowledgements enabled, only the offsets of events
+				delivered by all the connected sinks are checkpointed. The blobs are laid out the same
+				way as the checkpoint stores of the Azure SDKs, so that Vector can take over a consumer
+				group from one of them.
+				"""
+		}
+		load_balancing: {
+			title: "Load balancing"
+			body:  """
+				With `checkpoint_store`, the partitions of the event hub are balanced between all the
+				Vector instances reading it with the same consumer group. Every
+				`load_balancing_interval_secs`, each instance renews the ownership of its partitions,
+				claims the partitions nobody owns, and claims partitions from the instances owning more
+				than their share. A partition whose owner stopped renewing its ownership for
+				`ownership_expiration_secs` can be claimed by any other instance.
+				"""
+		}
+	}
+}
@@ -40,11 +40,50 @@ base: components: sources: aws_s3: configuration: {
 				required: true
 				type: string: examples: ["arn:aws:iam::123456789098:role/my_role"]
 			}
+			assume_role_chain: {
+				description: """
+					The roles to assume in turn once `assume_role` is, each with the credentials of the
+					previous one, such as to reach a role in another account.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						assume_role: {
+							description: """
+								The ARN of an [IAM role][iam_role] to assume.
+
+								[iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
+								"""
+							required: true
+							type: string: examples: ["arn:aws:iam::210987654321:role/other_role"]
+						}
+						external_id: {
+							description: """
+								The [external ID][external_id] the trust policy of the role requires, if any.
+
+								[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+								"""
+							required: false
+							type: string: examples: ["MyExternalId"]
+						}
+					}
+				}
+			}
 			credentials_file: {
 				description: "Path to the credentials file."
 				required:    true
 				type: string: examples: ["/my/aws/credentials"]
 			}
+			external_id: {
+				description: """
+					The [external ID][external_id] the trust policy of `assume_role` requires, if any.
+
+					[external_id]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html
+					"""
+				required: false
+				type: string: examples: ["MyExternalId"]
+			}
 			imds: {
 				description: "Configuration for authenticating with AWS through IMDS."
 				required:    false
@@ -70,6 +109,19 @@ base: components: sources: aws_s3: configuration: {
 							unit:    "seconds"
 						}
 					}
+					token_ttl_seconds: {
+						description: """
+							How long the IMDSv2 session tokens are valid for.
+
+							The metadata is fetched with IMDSv2, requesting a session token which is reused until it
+							expires.
+							"""
+						required: false
+						type: uint: {
+							default: 21600
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 			load_timeout_secs: {
@@ -101,7 +153,7 @@ base: components: sources: aws_s3: configuration: {
 					The [AWS region][aws_region] to send STS requests to.
 
 					If not set, this defaults to the configured region
-					for the service itself.
+					for the service itself. The requests are sent to the regional STS endpoint.
 
 					[aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
 					"""
@@ -113,6 +165,19 @@ base: components: sources: aws_s3: configuration: {
 				required:    true
 				type: string: examples: ["wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY"]
 			}
+			session_name: {
+				description: "The name of the session of the role assumed with the web identity token."
+				required:    false
+				type: string: {
+					default: "vector"
+					examples: ["my-session"]
+				}
+			}
+			web_identity_token_file: {
+				description: "Path to the file of the web identity token."
+				required:    true
+				type: string: examples: ["/var/run/secrets/eks.amazonaws.com/serviceaccount/token"]
+			}
 		}
 	}
 	compression: {
@@ -140,6 +205,59 @@ base: components: sources: aws_s3: configuration: {
 		required:    false
 		type: string: examples: ["http://127.0.0.0:5000/path/to/service"]
 	}
+	list: {
+		description: "Configuration options for listing the objects of a bucket."
+		required:    false
+		type: object: options: {
+			bucket: {
+				description: "The name of the bucket to list objects from."
+				required:    true
+				type: string: examples: ["my-bucket"]
+			}
+			data_dir: {
+				description: """
+					The directory used to persist the keys, and ETags, of the objects already read.
+
+					An object is read again if its ETag changes. By default, the global `data_dir` option is
+					used. Make sure the running user has write permissions to this directory.
+					"""
+				required: false
+				type: string: examples: ["/var/lib/vector"]
+			}
+			poll_interval_secs: {
+				description: "How long to wait between listings of the bucket, in seconds."
+				required:    false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+			prefixes: {
+				description: """
+					The key prefixes of the objects to read.
+
+					By default, all the objects of the bucket are read.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["logs/", "archive/2023/"]
+				}
+			}
+			start_from: {
+				description: """
+					Only objects last modified at, or after, this time are read.
+
+					The time must be in [RFC 3339][rfc3339] format. By default, all the objects are read,
+					including the ones already in the bucket when the source first starts.
+
+					[rfc3339]: https://datatracker.ietf.org/doc/html/rfc3339
+					"""
+				required: false
+				type: string: examples: ["2023-01-01T00:00:00Z"]
+			}
+		}
+	}
 	multiline: {
 		description: """
 			Multiline aggregation configuration.
@@ -148,20 +266,37 @@ base: components: sources: aws_s3: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			condition: {
+				description: """
+					[VRL][vrl] condition that is used to determine whether or not more lines should be read.
+
+					The condition is run against a log event holding the line in its `message` field. This is
+					useful to join lines, such as the ones of a stack trace, that are hard to match with a
+					regular expression.
+
+					This setting must be configured in conjunction with `mode`. Exactly one of
+					`condition_pattern` and `condition` must be set.
+
+					[vrl]: https://vector.dev/docs/reference/vrl
+					"""
+				required: false
+				type: string: examples: ["match(string!(.message), r'^\\s+at ') || starts_with(string!(.message), \"Caused by:\")"]
+			}
 			condition_pattern: {
 				description: """
 					Regular expression pattern that is used to determine whether or not more lines should be read.
 
-					This setting must be configured in conjunction with `mode`.
+					This setting must be configured in conjunction with `mode`. Exactly one of
+					`condition_pattern` and `condition` must be set.
 					"""
-				required: true
+				required: false
 				type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
 			}
 			mode: {
 				description: """
 					Aggregation mode.
 
-					This setting must be configured in conjunction with `condition_pattern`.
+					This setting must be configured in conjunction with `condition_pattern` or `condition`.
 					"""
 				required: true
 				type: string: enum: {
@@ -191,9 +326,26 @@ base: components: sources: aws_s3: configuration: {
 						"""
 				}
 			}
+			start_condition: {
+				description: """
+					[VRL][vrl] condition that is used to match the start of a new message.
+
+					The condition is run against a log event holding the line in its `message` field.
+
+					Exactly one of `start_pattern` and `start_condition` must be set.
+
+					[vrl]: https://vector.dev/docs/reference/vrl
+					"""
+				required: false
+				type: string: examples: ["!starts_with(string!(.message), \" \")"]
+			}
 			start_pattern: {
-				description: "Regular expression pattern that is used to match the start of a new message."
-				required:    true
+				description: """
+					Regular expression pattern that is used to match the start of a new message.
+
+					Exactly one of `start_pattern` and `start_condition` must be set.
+					"""
+				required: false
 				type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
 			}
 			timeout_ms: {
@@ -370,6 +522,26 @@ base: components: sources: aws_s3: configuration: {
 			}
 		}
 	}
+	strategy: {
+		description: "The strategy to use to consume objects from S3."
+		required:    false
+		type: string: {
+			default: "sqs"
+			enum: {
+				list: """
+					Consumes objects by periodically listing the objects of a bucket.
+
+					This is meant for buckets without notifications set up, such as buckets of historical or
+					static data.
+					"""
+				sqs: """
+					Consumes objects by processing bucket notification events sent to an [AWS SQS queue][aws_sqs].
+
+					[aws_sqs]: https://aws.amazon.com/sqs/
+					"""
+			}
+		}
+	}
 	tls_options: {
 		description: "TLS configuration."
 		required:    false
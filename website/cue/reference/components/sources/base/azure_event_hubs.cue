@@ -0,0 +1,528 @@
+package metadata
+
+base: components: sources: azure_event_hubs: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	checkpoint_interval_secs: {
+		description: """
+			How often the position of the source in each partition is written to the checkpoint store,
+			in seconds.
+			"""
+		required: false
+		type: uint: {
+			default: 10
+			unit:    "seconds"
+		}
+	}
+	checkpoint_store: {
+		description: """
+			Configuration of the checkpoint store.
+
+			The checkpoints of the partitions, and which consumer owns each partition, are stored as blobs
+			in an Azure Blob Storage container.
+			"""
+		required: false
+		type: object: options: {
+			connection_string: {
+				description: """
+					The Azure Blob Storage Account connection string.
+
+					Either `storage_account`, or this field, must be specified.
+					"""
+				required: false
+				type: string: examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+			}
+			container_name: {
+				description: "The name of the container to store the checkpoints in."
+				required:    true
+				type: string: examples: ["checkpoints"]
+			}
+			endpoint: {
+				description: """
+					The Azure Blob Storage Endpoint URL.
+
+					This may only be used with `storage_account`.
+					"""
+				required: false
+				type: string: examples: ["https://test.blob.core.windows.net/"]
+			}
+			storage_account: {
+				description: """
+					The Azure Blob Storage Account name.
+
+					Credentials are loaded the same way as for the `azure_blob` sink. Either
+					`connection_string`, or this field, must be specified.
+					"""
+				required: false
+				type: string: examples: ["mylogstorage"]
+			}
+		}
+	}
+	connection_string: {
+		description: """
+			The connection string of a shared access policy.
+
+			The policy can either be one of the Event Hubs namespace, or one of the event hub itself, in
+			which case the connection string ends with the `EntityPath` of the event hub.
+			"""
+		required: true
+		type: string: examples: ["Endpoint=sb://my-namespace.servicebus.windows.net/;SharedAccessKeyName=my-policy;SharedAccessKey=keybase64encoded"]
+	}
+	consumer_group: {
+		description: "The consumer group to read the event hub as."
+		required:    false
+		type: string: {
+			default: "$Default"
+			examples: ["vector"]
+		}
+	}
+	decoding: {
+		description: "Configures how events are decoded from raw bytes."
+		required:    false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes: "Uses the raw bytes as-is."
+						csv: """
+							Decodes the raw bytes as [CSV][csv] records.
+
+							Each record is decoded into an event, with its columns named from the configured `fields`,
+							or from a header row.
+
+							[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+							"""
+						gelf: """
+							Decodes the raw bytes as a [GELF][gelf] message.
+
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
+						json: """
+							Decodes the raw bytes as [JSON][json].
+
+							[json]: https://www.json.org/
+							"""
+						logfmt: """
+							Decodes the raw bytes as a [logfmt][logfmt] message.
+
+							The types of unquoted values are inferred, and keys of nested fields are unflattened, so
+							that the events encoded with the `logfmt` encoder are decoded with the same fields and
+							types.
+
+							[logfmt]: https://brandur.org/logfmt
+							"""
+						native: """
+							Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						native_json: """
+							Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						protobuf: """
+							Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
+
+							The message type is looked up in a compiled file descriptor set.
+
+							[protobuf]: https://protobuf.dev/
+							"""
+						syslog: """
+							Decodes the raw bytes as a Syslog message.
+
+							Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+							[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+							[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+							[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+							"""
+					}
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when reading CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether two consecutive quotes in a quoted field stand for a quote.
+
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when reading CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							The names of the fields the columns are decoded into, in order.
+
+							The names are used as they are, as top-level fields. They take precedence over the names
+							read from the header row, and the columns past them are named from the header row, if any,
+							or dropped.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Whether the first record of each input is a header row naming the columns.
+
+							An input is a connection of the sources reading streams, and a message, request or
+							datagram of the other sources.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when reading CSV."
+						required:    false
+						type: uint: default: 34
+					}
+				}
+			}
+			json: {
+				description:   "JSON-specific decoding options."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: simd: {
+					description: """
+						Whether to parse the JSON with SIMD instructions.
+
+						Parsing with SIMD instructions is faster on large or numerous events, at the cost of
+						copying each frame before parsing it. It falls back to the default parser on CPUs which
+						don't support the required instructions, AVX2 or SSE4.2 on x86-64 and NEON on AArch64, so
+						the events are the same whichever parser is used.
+						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+			logfmt: {
+				description:   "Logfmt-specific decoding options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: "The separator joining the names of nested fields into keys."
+						required:    false
+						type: string: default: "."
+					}
+					infer_types: {
+						description: """
+							Whether to infer the types of unquoted values.
+
+							When enabled, `true` and `false` are decoded as booleans, numbers as integers or floats,
+							RFC 3339 timestamps as timestamps, and empty values as null. Quoted values are always
+							decoded as strings. When disabled, all the values are decoded as strings.
+
+							Keys without a value are decoded as `true` either way.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					unflatten: {
+						description: """
+							Whether to unflatten keys into nested fields.
+
+							When enabled, keys are split on `flatten_separator` into nested objects, and the parts
+							suffixed with an index in brackets, such as `tags[0]`, into arrays. Keys that can't be
+							split, such as ones with empty parts, are kept as they are.
+							"""
+						required: false
+						type: bool: default: true
+					}
+				}
+			}
+			protobuf: {
+				description:   "Protocol Buffers-specific decoding options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to decode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
+		}
+	}
+	event_hub_name: {
+		description: """
+			The name of the event hub.
+
+			Required unless the connection string contains the `EntityPath` of the event hub.
+			"""
+		required: false
+		type: string: examples: ["my-event-hub"]
+	}
+	framing: {
+		description: """
+			Framing configuration.
+
+			Framing deals with how events are separated when encoded in a raw byte form, where each event is
+			a "frame" that must be prefixed, or delimited, in a way that marks where an event begins and
+			ends within the byte stream.
+			"""
+		required: false
+		type: object: options: {
+			character_delimited: {
+				description:   "Options for the character delimited decoder."
+				relevant_when: "method = \"character_delimited\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The character that delimits byte sequences."
+						required:    true
+						type: uint: {}
+					}
+					max_length: {
+						description: """
+																The maximum length of the byte buffer.
+
+																This length does *not* include the trailing delimiter.
+
+																By default, there is no maximum length enforced. If events are malformed, this can lead to
+																additional resource usage as events continue to be buffered in memory, and can potentially
+																lead to memory exhaustion in extreme cases.
+
+																If there is a risk of processing malformed data, such as logs with user-controlled input,
+																consider setting the maximum length to a reasonably large value as a safety net. This
+																ensures that processing is not actually unbounded.
+																"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					max_length: {
+						description: """
+							The maximum length of the messages, once reassembled and decompressed.
+
+							By default, there is no maximum length enforced.
+							"""
+						required: false
+						type: uint: {}
+					}
+					pending_messages_limit: {
+						description: """
+							The maximum number of messages which are reassembled at the same time.
+
+							The chunks starting new messages are discarded once the limit is reached.
+
+							By default, there is no limit.
+							"""
+						required: false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: """
+							The time to wait for all the chunks of a message, in seconds.
+
+							The messages which are still incomplete after this time are discarded.
+							"""
+						required: false
+						type: float: {
+							default: 5.0
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+			method: {
+				description: "The framing method."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+						character_delimited: "Byte frames which are delimited by a chosen character."
+						chunked_gelf: """
+							Byte frames which are [GELF][gelf] messages sent over UDP, possibly chunked and compressed.
+
+							Each datagram holds either a whole message or a chunk of one. The chunks are reassembled
+							into their message, and the messages compressed with gzip or zlib are decompressed.
+
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
+						length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						newline_delimited:   "Byte frames which are delimited by a newline character."
+						octet_counting: """
+															Byte frames according to the [octet counting][octet_counting] format.
+
+															[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+															"""
+						varint_length_delimited: """
+							Byte frames which are prefixed by an unsigned varint indicating the length.
+
+							This is how length-delimited Protocol Buffers messages are framed.
+							"""
+					}
+				}
+			}
+			newline_delimited: {
+				description:   "Options for the newline delimited decoder."
+				relevant_when: "method = \"newline_delimited\""
+				required:      false
+				type: object: options: max_length: {
+					description: """
+						The maximum length of the byte buffer.
+
+						This length does *not* include the trailing delimiter.
+
+						By default, there is no maximum length enforced. If events are malformed, this can lead to
+						additional resource usage as events continue to be buffered in memory, and can potentially
+						lead to memory exhaustion in extreme cases.
+
+						If there is a risk of processing malformed data, such as logs with user-controlled input,
+						consider setting the maximum length to a reasonably large value as a safety net. This
+						ensures that processing is not actually unbounded.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+			octet_counting: {
+				description:   "Options for the octet counting decoder."
+				relevant_when: "method = \"octet_counting\""
+				required:      false
+				type: object: options: max_length: {
+					description: "The maximum length of the byte buffer."
+					required:    false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	load_balancing_interval_secs: {
+		description: """
+			How often the ownership of the partitions is renewed, and balanced between the consumers of
+			the consumer group, in seconds.
+			"""
+		required: false
+		type: uint: {
+			default: 10
+			unit:    "seconds"
+		}
+	}
+	on_decode_error: {
+		description: "What to do with the payloads failing to be decoded."
+		required:    false
+		type: string: {
+			default: "drop"
+			enum: {
+				drop: "Drop the payload, after logging the error."
+				forward_raw: """
+					Forward the payload as is, to the default output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+				route: """
+					Route the payload as is, to the `errors` output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+			}
+		}
+	}
+	ownership_expiration_secs: {
+		description: """
+			How long a partition stays owned by a consumer that stopped renewing its ownership, in
+			seconds.
+
+			Once expired, the partition can be claimed by any other consumer of the consumer group.
+			"""
+		required: false
+		type: uint: {
+			default: 60
+			unit:    "seconds"
+		}
+	}
+	partition_ids: {
+		description: """
+			The identifiers of the partitions to read.
+
+			By default, all the partitions of the event hub are read.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["0", "1"]
+		}
+	}
+	prefetch_count: {
+		description: "The number of events requested from a partition ahead of time."
+		required:    false
+		type: uint: default: 300
+	}
+	start_position: {
+		description: "Where to start reading the partitions without a checkpoint."
+		required:    false
+		type: string: {
+			default: "latest"
+			enum: {
+				earliest: "The oldest event retained in the partition."
+				latest:   "The next event enqueued to the partition."
+			}
+		}
+	}
+}
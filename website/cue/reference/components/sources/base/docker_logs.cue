@@ -88,20 +88,37 @@ base: components: sources: docker_logs: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			condition: {
+				description: """
+					[VRL][vrl] condition that is used to determine whether or not more lines should be read.
+
+					The condition is run against a log event holding the line in its `message` field. This is
+					useful to join lines, such as the ones of a stack trace, that are hard to match with a
+					regular expression.
+
+					This setting must be configured in conjunction with `mode`. Exactly one of
+					`condition_pattern` and `condition` must be set.
+
+					[vrl]: https://vector.dev/docs/reference/vrl
+					"""
+				required: false
+				type: string: examples: ["match(string!(.message), r'^\\s+at ') || starts_with(string!(.message), \"Caused by:\")"]
+			}
 			condition_pattern: {
 				description: """
 					Regular expression pattern that is used to determine whether or not more lines should be read.
 
-					This setting must be configured in conjunction with `mode`.
+					This setting must be configured in conjunction with `mode`. Exactly one of
+					`condition_pattern` and `condition` must be set.
 					"""
-				required: true
+				required: false
 				type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
 			}
 			mode: {
 				description: """
 					Aggregation mode.
 
-					This setting must be configured in conjunction with `condition_pattern`.
+					This setting must be configured in conjunction with `condition_pattern` or `condition`.
 					"""
 				required: true
 				type: string: enum: {
@@ -131,9 +148,26 @@ base: components: sources: docker_logs: configuration: {
 						"""
 				}
 			}
+			start_condition: {
+				description: """
+					[VRL][vrl] condition that is used to match the start of a new message.
+
+					The condition is run against a log event holding the line in its `message` field.
+
+					Exactly one of `start_pattern` and `start_condition` must be set.
+
+					[vrl]: https://vector.dev/docs/reference/vrl
+					"""
+				required: false
+				type: string: examples: ["!starts_with(string!(.message), \" \")"]
+			}
 			start_pattern: {
-				description: "Regular expression pattern that is used to match the start of a new message."
-				required:    true
+				description: """
+					Regular expression pattern that is used to match the start of a new message.
+
+					Exactly one of `start_pattern` and `start_condition` must be set.
+					"""
+				required: false
 				type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
 			}
 			timeout_ms: {
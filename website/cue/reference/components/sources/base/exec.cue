@@ -1,60 +1,303 @@
 package metadata
 
 base: components: sources: exec: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	always_emit_completion: {
+		description: """
+			Whether or not to emit an event when each run of the command completes.
+
+			The event is emitted regardless of whether the run produced any output, and contains the
+			`exit_code` of the command (if known), the number of events emitted during the run as
+			`event_count`, and the `duration_seconds` of the run. This makes it possible to tell a
+			command that ran without producing output apart from a command that didn't run at all.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	command: {
-		description: "The command to be run, plus any arguments required."
-		required:    true
-		type: array: items: type: string: examples: ["echo", "Hello World!"]
+		description: """
+			The command to be run, plus any arguments required.
+
+			The command and its arguments can contain placeholders, which are replaced on each run:
+			`{{ hostname }}` with the host name, and in the scheduled mode, `{{ window_start }}` and
+			`{{ window_end }}` with the boundaries of the window of the run, in RFC 3339, or
+			`{{ window_start_unix }}` and `{{ window_end_unix }}` with the same boundaries, in seconds
+			since the epoch.
+
+			The window of a run starts where the window of the last successful run ended, so that an
+			incremental poller can fetch the records since then, and ends when the run starts. The first
+			window starts `exec_interval_secs` before the first run.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["echo", "Hello World!", "./fetch-audit-log", "--since={{ window_start }}"]
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the state of spawned children when `reap_orphans` is enabled.
+
+			By default, the global `data_dir` option is used. Make sure the running user has write
+			permissions to this directory.
+			"""
+		required: false
+		type: string: examples: ["/var/lib/vector"]
+	}
+	decode_stall_timeout_secs: {
+		description: """
+			The amount of time, in seconds, after which reading the output of a command is considered
+			stalled.
+
+			Reading is stalled when output has been read from the command that the decoder hasn't
+			produced an event from, and no event has been decoded for this amount of time while the
+			command is still running, even if more output is read. A command that is simply not writing
+			any output is never considered stalled. When a stall is detected, the command is killed,
+			and restarted according to the configured mode. By default, stalls are not detected.
+			"""
+		required: false
+		type: uint: examples: [60]
 	}
 	decoding: {
 		description: "Configures how events are decoded from raw bytes."
 		required:    false
-		type: object: options: codec: {
-			description: "The codec to use for decoding events."
-			required:    false
-			type: string: {
-				default: "bytes"
-				enum: {
-					bytes: "Uses the raw bytes as-is."
-					gelf: """
-						Decodes the raw bytes as a [GELF][gelf] message.
+		type: object: options: {
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes: "Uses the raw bytes as-is."
+						csv: """
+							Decodes the raw bytes as [CSV][csv] records.
 
-						[gelf]: https://docs.graylog.org/docs/gelf
-						"""
-					json: """
-						Decodes the raw bytes as [JSON][json].
+							Each record is decoded into an event, with its columns named from the configured `fields`,
+							or from a header row.
 
-						[json]: https://www.json.org/
-						"""
-					native: """
-						Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+							[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+							"""
+						gelf: """
+							Decodes the raw bytes as a [GELF][gelf] message.
 
-						This codec is **[experimental][experimental]**.
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
+						json: """
+							Decodes the raw bytes as [JSON][json].
 
-						[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-						"""
-					native_json: """
-						Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+							[json]: https://www.json.org/
+							"""
+						logfmt: """
+							Decodes the raw bytes as a [logfmt][logfmt] message.
 
-						This codec is **[experimental][experimental]**.
+							The types of unquoted values are inferred, and keys of nested fields are unflattened, so
+							that the events encoded with the `logfmt` encoder are decoded with the same fields and
+							types.
 
-						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-						"""
-					syslog: """
-						Decodes the raw bytes as a Syslog message.
+							[logfmt]: https://brandur.org/logfmt
+							"""
+						native: """
+							Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						native_json: """
+							Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						protobuf: """
+							Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
 
-						Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
-						[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+							The message type is looked up in a compiled file descriptor set.
 
-						[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
-						[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+							[protobuf]: https://protobuf.dev/
+							"""
+						syslog: """
+							Decodes the raw bytes as a Syslog message.
+
+							Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+							[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+							[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+							[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+							"""
+					}
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when reading CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether two consecutive quotes in a quoted field stand for a quote.
+
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when reading CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							The names of the fields the columns are decoded into, in order.
+
+							The names are used as they are, as top-level fields. They take precedence over the names
+							read from the header row, and the columns past them are named from the header row, if any,
+							or dropped.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Whether the first record of each input is a header row naming the columns.
+
+							An input is a connection of the sources reading streams, and a message, request or
+							datagram of the other sources.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when reading CSV."
+						required:    false
+						type: uint: default: 34
+					}
+				}
+			}
+			json: {
+				description:   "JSON-specific decoding options."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: simd: {
+					description: """
+						Whether to parse the JSON with SIMD instructions.
+
+						Parsing with SIMD instructions is faster on large or numerous events, at the cost of
+						copying each frame before parsing it. It falls back to the default parser on CPUs which
+						don't support the required instructions, AVX2 or SSE4.2 on x86-64 and NEON on AArch64, so
+						the events are the same whichever parser is used.
 						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+			logfmt: {
+				description:   "Logfmt-specific decoding options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: "The separator joining the names of nested fields into keys."
+						required:    false
+						type: string: default: "."
+					}
+					infer_types: {
+						description: """
+							Whether to infer the types of unquoted values.
+
+							When enabled, `true` and `false` are decoded as booleans, numbers as integers or floats,
+							RFC 3339 timestamps as timestamps, and empty values as null. Quoted values are always
+							decoded as strings. When disabled, all the values are decoded as strings.
+
+							Keys without a value are decoded as `true` either way.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					unflatten: {
+						description: """
+							Whether to unflatten keys into nested fields.
+
+							When enabled, keys are split on `flatten_separator` into nested objects, and the parts
+							suffixed with an index in brackets, such as `tags[0]`, into arrays. Keys that can't be
+							split, such as ones with empty parts, are kept as they are.
+							"""
+						required: false
+						type: bool: default: true
+					}
+				}
+			}
+			protobuf: {
+				description:   "Protocol Buffers-specific decoding options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to decode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
 				}
 			}
 		}
 	}
+	environment: {
+		description: """
+			Environment variables to set for the command, in addition to the environment of Vector.
+
+			The values can contain the same placeholders as the command.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				SINCE: "{{ window_start_unix }}"
+				UNTIL: "{{ window_end_unix }}"
+			}]
+			options: "*": {
+				description: "An environment variable."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
 	framing: {
 		description: """
 			Framing configuration.
@@ -94,12 +337,59 @@ base: components: sources: exec: configuration: {
 					}
 				}
 			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					max_length: {
+						description: """
+							The maximum length of the messages, once reassembled and decompressed.
+
+							By default, there is no maximum length enforced.
+							"""
+						required: false
+						type: uint: {}
+					}
+					pending_messages_limit: {
+						description: """
+							The maximum number of messages which are reassembled at the same time.
+
+							The chunks starting new messages are discarded once the limit is reached.
+
+							By default, there is no limit.
+							"""
+						required: false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: """
+							The time to wait for all the chunks of a message, in seconds.
+
+							The messages which are still incomplete after this time are discarded.
+							"""
+						required: false
+						type: float: {
+							default: 5.0
+							unit:    "seconds"
+						}
+					}
+				}
+			}
 			method: {
 				description: "The framing method."
 				required:    true
 				type: string: enum: {
 					bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
 					character_delimited: "Byte frames which are delimited by a chosen character."
+					chunked_gelf: """
+						Byte frames which are [GELF][gelf] messages sent over UDP, possibly chunked and compressed.
+
+						Each datagram holds either a whole message or a chunk of one. The chunks are reassembled
+						into their message, and the messages compressed with gzip or zlib are decompressed.
+
+						[gelf]: https://docs.graylog.org/docs/gelf
+						"""
 					length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 					newline_delimited:   "Byte frames which are delimited by a newline character."
 					octet_counting: """
@@ -107,6 +397,11 @@ base: components: sources: exec: configuration: {
 
 						[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
 						"""
+					varint_length_delimited: """
+						Byte frames which are prefixed by an unsigned varint indicating the length.
+
+						This is how length-delimited Protocol Buffers messages are framed.
+						"""
 				}
 			}
 			newline_delimited: {
@@ -161,28 +456,382 @@ base: components: sources: exec: configuration: {
 			streaming: "The command is run until it exits, potentially being restarted."
 		}
 	}
+	on_decode_error: {
+		description: "What to do with the payloads failing to be decoded."
+		required:    false
+		type: string: {
+			default: "drop"
+			enum: {
+				drop: "Drop the payload, after logging the error."
+				forward_raw: """
+					Forward the payload as is, to the default output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+				route: """
+					Route the payload as is, to the `errors` output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+			}
+		}
+	}
+	reap_orphans: {
+		description: """
+			Whether or not to terminate children orphaned by a previous run of Vector.
+
+			When enabled, the PID and start time of every spawned child is recorded in the data
+			directory. On startup, any recorded child that is still running, has the same start time,
+			and has the same command line is sent a `SIGTERM` before new children are spawned.
+
+			PIDs are reused by the operating system, so matching a previous child is a heuristic.
+			Start times are only available on Linux, and orphans are never terminated on other
+			platforms.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	sampling_mode: {
+		description: "The method used to select which events are emitted when sampling."
+		required:    false
+		type: string: {
+			default: "random"
+			enum: {
+				hash: """
+					The message of each event is hashed to decide whether it is emitted.
+
+					Identical messages are consistently either emitted or sampled out. The actual sampling rate
+					may differ from the configured one if messages are not uniformly distributed.
+					"""
+				random: "Each event is emitted with a probability of `1/N`, independently of its contents."
+			}
+		}
+	}
+	sampling_rate: {
+		description: """
+			The rate at which decoded events are emitted, expressed as `1/N`.
+
+			For example, `sampling_rate = 10` means 1 out of every 10 events is emitted and the rest
+			are dropped. Dropped events are counted by the `exec_events_sampled_out_total` metric. By
+			default, all events are emitted.
+			"""
+		required: false
+		type: uint: examples: [10]
+	}
 	scheduled: {
 		description: "Configuration options for scheduled commands."
 		required:    false
-		type: object: options: exec_interval_secs: {
-			description: """
-				The interval, in seconds, between scheduled command runs.
-
-				If the command takes longer than `exec_interval_secs` to run, it is killed.
-				"""
-			required: false
-			type: uint: default: 60
+		type: object: options: {
+			exec_interval_secs: {
+				description: """
+					The interval, in seconds, between scheduled command runs.
+
+					If the command takes longer than `exec_interval_secs` to run, it is killed.
+					"""
+				required: false
+				type: uint: default: 60
+			}
+			max_total_runtime_secs: {
+				description: """
+					The maximum amount of time, in seconds, that the source keeps scheduling command runs.
+
+					Once this amount of time has elapsed since the source started, no further runs are
+					scheduled and the source completes. A run that is in progress when the limit is reached is
+					allowed to finish. By default, runs are scheduled until Vector is shut down.
+					"""
+				required: false
+				type: uint: examples: [3600]
+			}
+		}
+	}
+	stderr: {
+		description: """
+			Configuration options for decoding the output from stderr.
+
+			By default, the output from stderr is framed and decoded in the same way as the output from
+			stdout. This is only used if `include_stderr` is enabled.
+			"""
+		required: false
+		type: object: options: {
+			decoding: {
+				description: "Configures how events are decoded from raw bytes."
+				required:    false
+				type: object: options: {
+					codec: {
+						description: "The codec to use for decoding events."
+						required:    false
+						type: string: {
+							default: "bytes"
+							enum: {
+								bytes: "Uses the raw bytes as-is."
+								gelf: """
+									Decodes the raw bytes as a [GELF][gelf] message.
+
+									[gelf]: https://docs.graylog.org/docs/gelf
+									"""
+								json: """
+									Decodes the raw bytes as [JSON][json].
+
+									[json]: https://www.json.org/
+									"""
+								native: """
+									Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+									This codec is **[experimental][experimental]**.
+
+									[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+									[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+									"""
+								native_json: """
+									Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+									This codec is **[experimental][experimental]**.
+
+									[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+									[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+									"""
+								protobuf: """
+									Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
+
+									The message type is looked up in a compiled file descriptor set.
+
+									[protobuf]: https://protobuf.dev/
+									"""
+								syslog: """
+									Decodes the raw bytes as a Syslog message.
+
+									Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+									[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+									[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+									[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+									"""
+							}
+						}
+					}
+					protobuf: {
+						description:   "Protocol Buffers-specific decoding options."
+						relevant_when: "codec = \"protobuf\""
+						required:      true
+						type: object: options: {
+							desc_file: {
+								description: """
+									The path to the compiled file descriptor set of the message type.
+
+									The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+									"""
+								required: true
+								type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+							}
+							message_type: {
+								description: "The fully qualified name of the message type to decode."
+								required:    true
+								type: string: examples: ["package.Message"]
+							}
+						}
+					}
+				}
+			}
+			framing: {
+				description: """
+					Framing configuration.
+
+					Framing deals with how events are separated when encoded in a raw byte form, where each event is
+					a "frame" that must be prefixed, or delimited, in a way that marks where an event begins and
+					ends within the byte stream.
+					"""
+				required: false
+				type: object: options: {
+					character_delimited: {
+						description:   "Options for the character delimited decoder."
+						relevant_when: "method = \"character_delimited\""
+						required:      true
+						type: object: options: {
+							delimiter: {
+								description: "The character that delimits byte sequences."
+								required:    true
+								type: uint: {}
+							}
+							max_length: {
+								description: """
+																		The maximum length of the byte buffer.
+
+																		This length does *not* include the trailing delimiter.
+
+																		By default, there is no maximum length enforced. If events are malformed, this can lead to
+																		additional resource usage as events continue to be buffered in memory, and can potentially
+																		lead to memory exhaustion in extreme cases.
+
+																		If there is a risk of processing malformed data, such as logs with user-controlled input,
+																		consider setting the maximum length to a reasonably large value as a safety net. This
+																		ensures that processing is not actually unbounded.
+																		"""
+								required: false
+								type: uint: {}
+							}
+						}
+					}
+					method: {
+						description: "The framing method."
+						required:    true
+						type: string: enum: {
+							bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+							character_delimited: "Byte frames which are delimited by a chosen character."
+							length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+							newline_delimited:   "Byte frames which are delimited by a newline character."
+							octet_counting: """
+								Byte frames according to the [octet counting][octet_counting] format.
+
+								[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+								"""
+							varint_length_delimited: """
+								Byte frames which are prefixed by an unsigned varint indicating the length.
+
+								This is how length-delimited Protocol Buffers messages are framed.
+								"""
+						}
+					}
+					newline_delimited: {
+						description:   "Options for the newline delimited decoder."
+						relevant_when: "method = \"newline_delimited\""
+						required:      false
+						type: object: options: max_length: {
+							description: """
+								The maximum length of the byte buffer.
+
+								This length does *not* include the trailing delimiter.
+
+								By default, there is no maximum length enforced. If events are malformed, this can lead to
+								additional resource usage as events continue to be buffered in memory, and can potentially
+								lead to memory exhaustion in extreme cases.
+
+								If there is a risk of processing malformed data, such as logs with user-controlled input,
+								consider setting the maximum length to a reasonably large value as a safety net. This
+								ensures that processing is not actually unbounded.
+								"""
+							required: false
+							type: uint: {}
+						}
+					}
+					octet_counting: {
+						description:   "Options for the octet counting decoder."
+						relevant_when: "method = \"octet_counting\""
+						required:      false
+						type: object: options: max_length: {
+							description: "The maximum length of the byte buffer."
+							required:    false
+							type: uint: {}
+						}
+					}
+				}
+			}
 		}
 	}
 	streaming: {
 		description: "Configuration options for streaming commands."
 		required:    false
 		type: object: options: {
+			delivery_retries: {
+				description: """
+					The maximum number of times a batch of events is resent when it isn't delivered.
+
+					Only used when acknowledgements are enabled. Batches are resent once per second, and once a
+					batch has been resent this many times without being delivered, the `on_delivery_failure`
+					action is taken.
+					"""
+				required: false
+				type: uint: default: 3
+			}
+			heartbeat: {
+				description: """
+					Configuration options for the heartbeats of streaming commands.
+
+					The command is given a control channel as file descriptor 3, whose number is also set in the
+					`VECTOR_HEARTBEAT_FD` environment variable. The command must write a line, such as `ok`, to
+					the channel at least once every `timeout_secs` to tell that it isn't hung, even when it has no
+					output to write. A line starting with `unhealthy`, optionally followed by a reason, reports the
+					command as unhealthy. The command is killed when it misses its heartbeats or reports itself as
+					unhealthy, and restarted according to the respawn options.
+
+					Heartbeats are only supported on Unix.
+					"""
+				required: false
+				type: object: options: timeout_secs: {
+					description: """
+						The amount of time, in seconds, after which a command that hasn't written a heartbeat is
+						considered hung.
+						"""
+					required: true
+					type: uint: examples: [30]
+				}
+			}
+			jitter: {
+				description: """
+					Whether or not to randomize the delay between respawns.
+
+					When enabled, each delay is chosen randomly between half of the computed delay and the
+					full delay, so that many flapping commands don't all respawn at the same time.
+					"""
+				required: false
+				type: bool: default: false
+			}
+			max_backoff_secs: {
+				description: """
+					The maximum amount of time, in seconds, before rerunning a streaming command that exited.
+
+					When set, the delay between respawns doubles after each consecutive respawn, starting from
+					`respawn_interval_secs`, until it reaches this value. A run that lasts at least this long is
+					considered healthy, and resets the delay. By default, the delay stays constant.
+					"""
+				required: false
+				type: uint: examples: [300]
+			}
+			max_restarts: {
+				description: """
+					The maximum number of consecutive respawns of a streaming command.
+
+					Once this number of consecutive respawns is reached, and the command exits again, it is no
+					longer rerun and the source completes. The count is reset by a healthy run, as described
+					for `max_backoff_secs`. By default, the command is respawned indefinitely.
+					"""
+				required: false
+				type: uint: examples: [10]
+			}
 			respawn_interval_secs: {
-				description: "The amount of time, in seconds, before rerunning a streaming command that exited."
-				required:    false
+				description: """
+					The amount of time, in seconds, before rerunning a streaming command that exited.
+
+					When `max_backoff_secs` is set, this is the initial delay, which doubles after each
+					consecutive respawn.
+					"""
+				required: false
 				type: uint: default: 5
 			}
+			on_delivery_failure: {
+				description: """
+					The action taken when a batch of events from a streaming command persistently fails to be
+					delivered.
+					"""
+				required: false
+				type: string: {
+					default: "kill"
+					enum: {
+						kill: """
+							Drop the batch and kill the command.
+
+							The command is restarted according to the respawn options.
+							"""
+						pause: """
+							Pause the command, and keep resending the batch until it is delivered.
+
+							The command is sent `SIGSTOP`, and then `SIGCONT` once the batch is delivered. On Windows,
+							the command isn't paused, but no more of its output is read until the batch is delivered.
+							"""
+					}
+				}
+			}
 			respawn_on_exit: {
 				description: "Whether or not the command should be rerun if the command exits."
 				required:    false
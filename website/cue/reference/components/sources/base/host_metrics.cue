@@ -68,7 +68,7 @@ base: components: sources: host_metrics: configuration: {
 		description: """
 			The list of host metric collector services to use.
 
-			Defaults to all collectors.
+			Defaults to all collectors, except `process`.
 			"""
 		required: false
 		type: array: {
@@ -87,8 +87,13 @@ base: components: sources: host_metrics: configuration: {
 					load:       "Metrics related to the system load average."
 					memory:     "Metrics related to memory utilization."
 					network:    "Metrics related to network utilization."
+					process: """
+						Metrics related to individual processes.
+
+						Only available on Linux, and not enabled by default.
+						"""
 				}
-				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network", "process"]
 			}
 		}
 	}
@@ -291,6 +296,44 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	process: {
+		description: """
+			Options for the process metrics collector.
+
+			This collector is only available on Linux systems.
+			"""
+		required: false
+		type: object: options: {
+			cmdlines: {
+				description: """
+					Regular expressions matched against the command lines of the processes to report metrics
+					for.
+
+					The command line of a process is its arguments, separated by spaces.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["java .*kafka"]
+				}
+			}
+			names: {
+				description: """
+					Regular expressions matched against the names of the processes to report metrics for.
+
+					The name of a process is the name of its executable, truncated to 15 characters.
+
+					Processes are reported if their name matches any of `names`, or if their command line
+					matches any of `cmdlines`. If neither is set, all processes are reported.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["^nginx$"]
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: "The interval between metric gathering, in seconds."
 		required:    false
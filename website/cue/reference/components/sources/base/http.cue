@@ -50,46 +50,253 @@ base: components: sources: http: configuration: {
 	decoding: {
 		description: "Configures how events are decoded from raw bytes."
 		required:    false
-		type: object: options: codec: {
-			description: "The codec to use for decoding events."
-			required:    true
-			type: string: enum: {
-				bytes: "Uses the raw bytes as-is."
-				gelf: """
-					Decodes the raw bytes as a [GELF][gelf] message.
-
-					[gelf]: https://docs.graylog.org/docs/gelf
-					"""
-				json: """
-					Decodes the raw bytes as [JSON][json].
+		type: object: options: {
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    true
+				type: string: enum: {
+					bytes: "Uses the raw bytes as-is."
+					csv: """
+						Decodes the raw bytes as [CSV][csv] records.
 
-					[json]: https://www.json.org/
-					"""
-				native: """
-					Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+						Each record is decoded into an event, with its columns named from the configured `fields`,
+						or from a header row.
 
-					This codec is **[experimental][experimental]**.
+						[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+						"""
+					gelf: """
+						Decodes the raw bytes as a [GELF][gelf] message.
 
-					[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
-					[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-					"""
-				native_json: """
-					Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+						[gelf]: https://docs.graylog.org/docs/gelf
+						"""
+					json: """
+						Decodes the raw bytes as [JSON][json].
 
-					This codec is **[experimental][experimental]**.
+						[json]: https://www.json.org/
+						"""
+					logfmt: """
+						Decodes the raw bytes as a [logfmt][logfmt] message.
 
-					[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
-					[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-					"""
-				syslog: """
-					Decodes the raw bytes as a Syslog message.
+						The types of unquoted values are inferred, and keys of nested fields are unflattened, so
+						that the events encoded with the `logfmt` encoder are decoded with the same fields and
+						types.
+
+						[logfmt]: https://brandur.org/logfmt
+						"""
+					native: """
+						Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+						This codec is **[experimental][experimental]**.
+
+						[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+						"""
+					native_json: """
+						Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+						This codec is **[experimental][experimental]**.
+
+						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+						"""
+					protobuf: """
+						Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
+
+						The message type is looked up in a compiled file descriptor set.
+
+						[protobuf]: https://protobuf.dev/
+						"""
+					syslog: """
+						Decodes the raw bytes as a Syslog message.
+
+						Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+						[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+						[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+						[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+						"""
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when reading CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether two consecutive quotes in a quoted field stand for a quote.
 
-					Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
-					[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when reading CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							The names of the fields the columns are decoded into, in order.
+
+							The names are used as they are, as top-level fields. They take precedence over the names
+							read from the header row, and the columns past them are named from the header row, if any,
+							or dropped.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Whether the first record of each input is a header row naming the columns.
+
+							An input is a connection of the sources reading streams, and a message, request or
+							datagram of the other sources.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when reading CSV."
+						required:    false
+						type: uint: default: 34
+					}
+				}
+			}
+			json: {
+				description:   "JSON-specific decoding options."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: simd: {
+					description: """
+						Whether to parse the JSON with SIMD instructions.
 
-					[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
-					[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+						Parsing with SIMD instructions is faster on large or numerous events, at the cost of
+						copying each frame before parsing it. It falls back to the default parser on CPUs which
+						don't support the required instructions, AVX2 or SSE4.2 on x86-64 and NEON on AArch64, so
+						the events are the same whichever parser is used.
+						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+			logfmt: {
+				description:   "Logfmt-specific decoding options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: "The separator joining the names of nested fields into keys."
+						required:    false
+						type: string: default: "."
+					}
+					infer_types: {
+						description: """
+							Whether to infer the types of unquoted values.
+
+							When enabled, `true` and `false` are decoded as booleans, numbers as integers or floats,
+							RFC 3339 timestamps as timestamps, and empty values as null. Quoted values are always
+							decoded as strings. When disabled, all the values are decoded as strings.
+
+							Keys without a value are decoded as `true` either way.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					unflatten: {
+						description: """
+							Whether to unflatten keys into nested fields.
+
+							When enabled, keys are split on `flatten_separator` into nested objects, and the parts
+							suffixed with an index in brackets, such as `tags[0]`, into arrays. Keys that can't be
+							split, such as ones with empty parts, are kept as they are.
+							"""
+						required: false
+						type: bool: default: true
+					}
+				}
+			}
+			protobuf: {
+				description:   "Protocol Buffers-specific decoding options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to decode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
+		}
+	}
+	decompression: {
+		description: "Configuration for decompressing request bodies, according to their `Content-Encoding` header."
+		required:    false
+		type: object: options: {
+			allowed_encodings: {
+				description: """
+					The content encodings that request bodies are accepted with.
+
+					Requests with any other encoding are rejected with a `415 Unsupported Media Type` response.
+					By default, all supported encodings are accepted.
 					"""
+				required: false
+				type: array: items: type: string: {
+					enum: {
+						deflate: """
+							[Deflate][deflate] compression, in the zlib format.
+
+							[deflate]: https://en.wikipedia.org/wiki/DEFLATE
+							"""
+						gzip: """
+							[Gzip][gzip] compression.
+
+							[gzip]: https://www.gzip.org/
+							"""
+						identity: "No compression."
+						snappy: """
+							[Snappy][snappy] compression, in the raw format.
+
+							[snappy]: https://github.com/google/snappy
+							"""
+						zstd: """
+							[Zstandard][zstd] compression.
+
+							[zstd]: https://facebook.github.io/zstd/
+							"""
+					}
+					examples: ["gzip", "zstd"]
+				}
+			}
+			max_decompressed_size_bytes: {
+				description: """
+					The maximum size, in bytes, of a request body once decompressed.
+
+					Requests whose body decompresses to more than this are rejected with a
+					`413 Payload Too Large` response, without being decompressed any further. By default, the
+					size of decompressed bodies isn't limited.
+					"""
+				required: false
+				type: uint: examples: [10485760]
 			}
 		}
 	}
@@ -146,12 +353,59 @@ base: components: sources: http: configuration: {
 					}
 				}
 			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					max_length: {
+						description: """
+							The maximum length of the messages, once reassembled and decompressed.
+
+							By default, there is no maximum length enforced.
+							"""
+						required: false
+						type: uint: {}
+					}
+					pending_messages_limit: {
+						description: """
+							The maximum number of messages which are reassembled at the same time.
+
+							The chunks starting new messages are discarded once the limit is reached.
+
+							By default, there is no limit.
+							"""
+						required: false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: """
+							The time to wait for all the chunks of a message, in seconds.
+
+							The messages which are still incomplete after this time are discarded.
+							"""
+						required: false
+						type: float: {
+							default: 5.0
+							unit:    "seconds"
+						}
+					}
+				}
+			}
 			method: {
 				description: "The framing method."
 				required:    true
 				type: string: enum: {
 					bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
 					character_delimited: "Byte frames which are delimited by a chosen character."
+					chunked_gelf: """
+						Byte frames which are [GELF][gelf] messages sent over UDP, possibly chunked and compressed.
+
+						Each datagram holds either a whole message or a chunk of one. The chunks are reassembled
+						into their message, and the messages compressed with gzip or zlib are decompressed.
+
+						[gelf]: https://docs.graylog.org/docs/gelf
+						"""
 					length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 					newline_delimited:   "Byte frames which are delimited by a newline character."
 					octet_counting: """
@@ -159,6 +413,11 @@ base: components: sources: http: configuration: {
 
 						[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
 						"""
+					varint_length_delimited: """
+						Byte frames which are prefixed by an unsigned varint indicating the length.
+
+						This is how length-delimited Protocol Buffers messages are framed.
+						"""
 				}
 			}
 			newline_delimited: {
@@ -222,6 +481,28 @@ base: components: sources: http: configuration: {
 			}
 		}
 	}
+	on_decode_error: {
+		description: "What to do with the payloads failing to be decoded."
+		required:    false
+		type: string: {
+			default: "drop"
+			enum: {
+				drop: "Drop the payload, after logging the error."
+				forward_raw: """
+					Forward the payload as is, to the default output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+				route: """
+					Route the payload as is, to the `errors` output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+			}
+		}
+	}
 	path: {
 		description: "The URL path on which log event POST requests are sent."
 		required:    false
@@ -1,6 +1,65 @@
 package metadata
 
 base: components: sources: internal_metrics: configuration: {
+	cardinality_limit: {
+		description: """
+			Limits the number of distinct values of the tags of each internal metric.
+
+			This guards against the high cardinality of tags like the `command` tag of the metrics of the
+			`exec` source. Once a tag of a metric has reached the limit, its new values are replaced with
+			`overflow_value`, and the metrics whose series become identical are aggregated together. The
+			number of series collapsed at each scrape is reported by the `internal_metrics_collapsed_series`
+			gauge.
+			"""
+		required: false
+		type: object: options: {
+			overflow_value: {
+				description: "The value replacing the values of a tag over the limit."
+				required:    false
+				type: string: default: "other"
+			}
+			tags: {
+				description: """
+					The tags whose values are limited.
+
+					By default, the values of all tags are limited.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["command"]
+				}
+			}
+			value_limit: {
+				description: "The maximum number of distinct values of a tag, for each metric."
+				required:    true
+				type: uint: examples: [100]
+			}
+		}
+	}
+	histogram_buckets: {
+		description: """
+			The bucket upper limits of internal histograms, by metric name.
+
+			By default, histograms have buckets with upper limits that are powers of two, from `2^-6`
+			to `2^12`. A final bucket without upper limit is always added.
+
+			This only applies to histograms created after the source is started. As histograms are
+			shared by the whole Vector process, this should only be set on one `internal_metrics`
+			source.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				command_execution_duration_seconds: [0.1, 0.5, 1.0, 5.0, 10.0, 60.0]
+			}]
+			options: "*": {
+				description: "The bucket upper limits of the histogram."
+				required:    true
+				type: array: items: type: float: {}
+			}
+		}
+	}
 	namespace: {
 		description: "Overrides the default namespace for the metrics emitted by the source."
 		required:    false
@@ -54,11 +54,16 @@ base: components: sources: journald: configuration: {
 			A list of sets of field/value pairs that, if any are present in a journal entry,
 			excludes the entry from this source.
 
-			If `exclude_units` is specified, it is merged into this list.
+			If `exclude_units` or `exclude_syslog_identifiers` are specified, they are merged into this
+			list.
+
+			Values of the `PRIORITY` field may be given as an inclusive range of priorities, such as
+			`0..3` or `emerg..err`.
 			"""
 		required: false
 		type: object: {
 			examples: [{
+				"PRIORITY": ["0..3"]
 				"_SYSTEMD_UNIT": ["sshd.service", "ntpd.service"]
 				"_TRANSPORT": ["kernel"]
 			}]
@@ -69,6 +74,19 @@ base: components: sources: journald: configuration: {
 			}
 		}
 	}
+	exclude_syslog_identifiers: {
+		description: """
+			A list of syslog identifiers to exclude from monitoring.
+
+			Entries whose `SYSLOG_IDENTIFIER` field matches any of these values are excluded from this
+			source.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["kernel", "CRON"]
+		}
+	}
 	exclude_units: {
 		description: """
 			A list of unit names to exclude from monitoring.
@@ -89,10 +107,14 @@ base: components: sources: journald: configuration: {
 			If empty or not present, all journal fields are accepted.
 
 			If `include_units` is specified, it is merged into this list.
+
+			Values of the `PRIORITY` field may be given as an inclusive range of priorities, such as
+			`0..3` or `emerg..err`.
 			"""
 		required: false
 		type: object: {
 			examples: [{
+				"PRIORITY": ["0..3"]
 				"_SYSTEMD_UNIT": ["sshd.service", "ntpd.service"]
 				"_TRANSPORT": ["kernel"]
 			}]
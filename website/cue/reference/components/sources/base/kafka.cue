@@ -22,6 +22,51 @@ base: components: sources: kafka: configuration: {
 			type: bool: {}
 		}
 	}
+	assignments: {
+		description: """
+			Explicit partitions to consume, bypassing consumer groups.
+
+			When set, `topics` is ignored, and only the listed partitions are consumed, starting from
+			the given offsets or timestamps. No consumer group is joined, and no offsets are committed,
+			so consumption starts from the same positions every time Vector starts. If `group_id` is
+			set, it is only passed along to the client.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				offset: {
+					description: """
+						The offset of the first message to consume.
+
+						If neither `offset` nor `timestamp_ms` is set, the partition is consumed from the beginning.
+						"""
+					required: false
+					type: uint: examples: [1024]
+				}
+				partition: {
+					description: "The partition number."
+					required:    true
+					type: uint: examples: [0]
+				}
+				timestamp_ms: {
+					description: """
+						The timestamp, in milliseconds since the Unix epoch, from which to consume.
+
+						Consumption starts from the first message with a timestamp greater than or equal to this
+						one, or from the end of the partition if there is no such message.
+						"""
+					required: false
+					type: uint: examples: [1672531200000]
+				}
+				topic: {
+					description: "The topic of the partition."
+					required:    true
+					type: string: examples: ["topic-1"]
+				}
+			}
+		}
+	}
 	auto_offset_reset: {
 		description: """
 			If offsets for consumer group do not exist, set them using this strategy.
@@ -58,48 +103,203 @@ base: components: sources: kafka: configuration: {
 	decoding: {
 		description: "Configures how events are decoded from raw bytes."
 		required:    false
-		type: object: options: codec: {
-			description: "The codec to use for decoding events."
-			required:    false
-			type: string: {
-				default: "bytes"
-				enum: {
-					bytes: "Uses the raw bytes as-is."
-					gelf: """
-						Decodes the raw bytes as a [GELF][gelf] message.
-
-						[gelf]: https://docs.graylog.org/docs/gelf
-						"""
-					json: """
-						Decodes the raw bytes as [JSON][json].
+		type: object: options: {
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes: "Uses the raw bytes as-is."
+						csv: """
+							Decodes the raw bytes as [CSV][csv] records.
+
+							Each record is decoded into an event, with its columns named from the configured `fields`,
+							or from a header row.
+
+							[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+							"""
+						gelf: """
+							Decodes the raw bytes as a [GELF][gelf] message.
+
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
+						json: """
+							Decodes the raw bytes as [JSON][json].
+
+							[json]: https://www.json.org/
+							"""
+						logfmt: """
+							Decodes the raw bytes as a [logfmt][logfmt] message.
+
+							The types of unquoted values are inferred, and keys of nested fields are unflattened, so
+							that the events encoded with the `logfmt` encoder are decoded with the same fields and
+							types.
+
+							[logfmt]: https://brandur.org/logfmt
+							"""
+						native: """
+							Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						native_json: """
+							Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						protobuf: """
+							Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
+
+							The message type is looked up in a compiled file descriptor set.
+
+							[protobuf]: https://protobuf.dev/
+							"""
+						syslog: """
+							Decodes the raw bytes as a Syslog message.
+
+							Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+							[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+							[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+							[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+							"""
+					}
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when reading CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether two consecutive quotes in a quoted field stand for a quote.
 
-						[json]: https://www.json.org/
-						"""
-					native: """
-						Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when reading CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							The names of the fields the columns are decoded into, in order.
 
-						This codec is **[experimental][experimental]**.
+							The names are used as they are, as top-level fields. They take precedence over the names
+							read from the header row, and the columns past them are named from the header row, if any,
+							or dropped.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Whether the first record of each input is a header row naming the columns.
+
+							An input is a connection of the sources reading streams, and a message, request or
+							datagram of the other sources.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when reading CSV."
+						required:    false
+						type: uint: default: 34
+					}
+				}
+			}
+			json: {
+				description:   "JSON-specific decoding options."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: simd: {
+					description: """
+						Whether to parse the JSON with SIMD instructions.
 
-						[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+						Parsing with SIMD instructions is faster on large or numerous events, at the cost of
+						copying each frame before parsing it. It falls back to the default parser on CPUs which
+						don't support the required instructions, AVX2 or SSE4.2 on x86-64 and NEON on AArch64, so
+						the events are the same whichever parser is used.
 						"""
-					native_json: """
-						Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+					required: false
+					type: bool: default: false
+				}
+			}
+			logfmt: {
+				description:   "Logfmt-specific decoding options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: "The separator joining the names of nested fields into keys."
+						required:    false
+						type: string: default: "."
+					}
+					infer_types: {
+						description: """
+							Whether to infer the types of unquoted values.
 
-						This codec is **[experimental][experimental]**.
+							When enabled, `true` and `false` are decoded as booleans, numbers as integers or floats,
+							RFC 3339 timestamps as timestamps, and empty values as null. Quoted values are always
+							decoded as strings. When disabled, all the values are decoded as strings.
 
-						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-						"""
-					syslog: """
-						Decodes the raw bytes as a Syslog message.
+							Keys without a value are decoded as `true` either way.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					unflatten: {
+						description: """
+							Whether to unflatten keys into nested fields.
 
-						Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
-						[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+							When enabled, keys are split on `flatten_separator` into nested objects, and the parts
+							suffixed with an index in brackets, such as `tags[0]`, into arrays. Keys that can't be
+							split, such as ones with empty parts, are kept as they are.
+							"""
+						required: false
+						type: bool: default: true
+					}
+				}
+			}
+			protobuf: {
+				description:   "Protocol Buffers-specific decoding options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
 
-						[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
-						[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
-						"""
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to decode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
 				}
 			}
 		}
@@ -152,6 +352,45 @@ base: components: sources: kafka: configuration: {
 					}
 				}
 			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					max_length: {
+						description: """
+							The maximum length of the messages, once reassembled and decompressed.
+
+							By default, there is no maximum length enforced.
+							"""
+						required: false
+						type: uint: {}
+					}
+					pending_messages_limit: {
+						description: """
+							The maximum number of messages which are reassembled at the same time.
+
+							The chunks starting new messages are discarded once the limit is reached.
+
+							By default, there is no limit.
+							"""
+						required: false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: """
+							The time to wait for all the chunks of a message, in seconds.
+
+							The messages which are still incomplete after this time are discarded.
+							"""
+						required: false
+						type: float: {
+							default: 5.0
+							unit:    "seconds"
+						}
+					}
+				}
+			}
 			method: {
 				description: "The framing method."
 				required:    false
@@ -160,6 +399,14 @@ base: components: sources: kafka: configuration: {
 					enum: {
 						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
 						character_delimited: "Byte frames which are delimited by a chosen character."
+						chunked_gelf: """
+							Byte frames which are [GELF][gelf] messages sent over UDP, possibly chunked and compressed.
+
+							Each datagram holds either a whole message or a chunk of one. The chunks are reassembled
+							into their message, and the messages compressed with gzip or zlib are decompressed.
+
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
 						length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 						newline_delimited:   "Byte frames which are delimited by a newline character."
 						octet_counting: """
@@ -167,6 +414,11 @@ base: components: sources: kafka: configuration: {
 
 															[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
 															"""
+						varint_length_delimited: """
+							Byte frames which are prefixed by an unsigned varint indicating the length.
+
+							This is how length-delimited Protocol Buffers messages are framed.
+							"""
 					}
 				}
 			}
@@ -205,9 +457,16 @@ base: components: sources: kafka: configuration: {
 		}
 	}
 	group_id: {
-		description: "The consumer group name to be used to consume events from Kafka."
-		required:    true
-		type: string: examples: ["consumer-group-name"]
+		description: """
+			The consumer group name to be used to consume events from Kafka.
+
+			Required unless `assignments` are set.
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["consumer-group-name"]
+		}
 	}
 	headers_key: {
 		description: """
@@ -282,6 +541,28 @@ base: components: sources: kafka: configuration: {
 			]
 		}
 	}
+	on_decode_error: {
+		description: "What to do with the payloads failing to be decoded."
+		required:    false
+		type: string: {
+			default: "drop"
+			enum: {
+				drop: "Drop the payload, after logging the error."
+				forward_raw: """
+					Forward the payload as is, to the default output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+				route: """
+					Route the payload as is, to the `errors` output.
+
+					The payload is the message of a log event, with the error in its `%vector.decode_error`
+					metadata field.
+					"""
+			}
+		}
+	}
 	partition_key: {
 		description: """
 			Overrides the name of the log field used to add the partition to each event.
@@ -332,6 +613,169 @@ base: components: sources: kafka: configuration: {
 			}
 		}
 	}
+	schema_registry: {
+		description: """
+			Decodes messages in the Confluent wire format, using Avro schemas from a schema registry.
+
+			When set, the schema ID that prefixes each message is used to fetch the schema the message
+			is decoded with, and `decoding` is ignored. Schemas are cached once fetched.
+
+			The messages whose schema can't be resolved, such as while the registry is unreachable, are
+			handled according to `on_decode_error`. With `drop`, they're read again a second later, so
+			that neither their offsets nor the ones of the messages after them are committed in the
+			meantime. Otherwise they're forwarded or routed as is.
+			"""
+		required: false
+		type: object: options: {
+			auth: {
+				description: """
+					Configuration of the authentication strategy for HTTP requests.
+
+					HTTP authentication should almost always be used with HTTPS only, as the authentication credentials are passed as an
+					HTTP header without any additional encryption beyond what is provided by the transport itself.
+					"""
+				required: false
+				type: object: options: {
+					password: {
+						description:   "The basic authentication password."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${PASSWORD}", "password"]
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: enum: {
+							basic: """
+								Basic authentication.
+
+								The username and password are concatenated and encoded via [base64][base64].
+
+								[base64]: https://en.wikipedia.org/wiki/Base64
+								"""
+							bearer: """
+								Bearer authentication.
+
+								The bearer token value (OAuth2, JWT, etc.) is passed as-is.
+								"""
+						}
+					}
+					token: {
+						description:   "The bearer authentication token."
+						relevant_when: "strategy = \"bearer\""
+						required:      true
+						type: string: {}
+					}
+					user: {
+						description:   "The basic authentication username."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${USERNAME}", "username"]
+					}
+				}
+			}
+			subject: {
+				description: """
+					The subject to register the schema under.
+
+					Only used when producing messages. If not set, it defaults to `<topic>-value`, following
+					the default `TopicNameStrategy`, which requires the topic to not be templated.
+					"""
+				required: false
+				type: string: examples: ["logs-value"]
+			}
+			tls: {
+				description: "TLS configuration."
+				required:    false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+							that they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must not be expired and must be issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+							certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+							so on until the verification process reaches a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+			url: {
+				description: "The URL of the schema registry."
+				required:    true
+				type: string: examples: ["http://localhost:8081"]
+			}
+		}
+	}
 	session_timeout_ms: {
 		description: "The Kafka session timeout."
 		required:    false
@@ -466,8 +910,13 @@ base: components: sources: kafka: configuration: {
 			The Kafka topics names to read events from.
 
 			Regular expression syntax is supported if the topic begins with `^`.
+
+			Ignored when `assignments` are set.
 			"""
-		required: true
-		type: array: items: type: string: examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+		}
 	}
 }
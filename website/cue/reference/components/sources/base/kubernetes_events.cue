@@ -0,0 +1,76 @@
+package metadata
+
+base: components: sources: kubernetes_events: configuration: {
+	data_dir: {
+		description: """
+			The directory used to persist the versions of the events that were emitted.
+
+			By default, the global `data_dir` option is used. Make sure the running user has write
+			permissions to this directory.
+			"""
+		required: false
+		type: string: examples: ["/var/local/lib/vector/"]
+	}
+	field_selector: {
+		description: """
+			Specifies the [field selector][field_selector] to filter the events with.
+
+			[field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["type=Warning", "involvedObject.kind=Pod,reason!=Pulled"]
+		}
+	}
+	ignore_older_secs: {
+		description: """
+			Ignore events last observed more than the specified number of seconds ago.
+
+			The events that occurred before Vector started are listed when the watch starts, so this
+			limits how far back they are emitted from the first time the source runs.
+			"""
+		required: false
+		type: uint: {
+			examples: [3600]
+			unit: "seconds"
+		}
+	}
+	kube_config_file: {
+		description: """
+			Optional path to a readable [kubeconfig][kubeconfig] file.
+
+			If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+			[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+			"""
+		required: false
+		type: string: examples: ["/path/to/.kube/config"]
+	}
+	label_selector: {
+		description: """
+			Specifies the [label selector][label_selector] to filter the events with.
+
+			[label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["my_custom_label!=my_value"]
+		}
+	}
+	namespaces: {
+		description: """
+			The [Namespaces][namespaces] to watch the events of.
+
+			If empty, the events of all Namespaces are watched.
+
+			[namespaces]: https://kubernetes.io/docs/concepts/overview/working-with-objects/namespaces/
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["default", "kube-system"]
+		}
+	}
+}
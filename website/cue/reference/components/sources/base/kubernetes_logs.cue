@@ -371,6 +371,39 @@ base: components: sources: kubernetes_logs: configuration: {
 			}
 		}
 	}
+	pod_hints: {
+		description: "Configuration for the parsing hints Pods opt into with annotations."
+		required:    false
+		type: object: options: {
+			enabled: {
+				description: """
+					Whether or not to apply the parsing hints in the annotations of Pods.
+
+					The `vector.dev/parse` annotation selects the format the messages of the Pod are parsed
+					from, and only supports `json`. The fields of JSON objects replace the message.
+
+					The `vector.dev/multiline-pattern` annotation is a regular expression matching the first
+					line of each message of the Pod. Lines that don't match it are appended to the previous
+					message, separated by a newline.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			multiline_timeout_ms: {
+				description: """
+					The maximum amount of time to wait for the next line of a multiline message, in
+					milliseconds.
+
+					Once this timeout is reached, the buffered message is emitted.
+					"""
+				required: false
+				type: uint: {
+					default: 1000
+					unit:    "milliseconds"
+				}
+			}
+		}
+	}
 	read_from: {
 		description: "File position to use when reading a new file."
 		required:    false
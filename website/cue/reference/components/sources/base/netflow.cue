@@ -0,0 +1,47 @@
+package metadata
+
+base: components: sources: netflow: configuration: {
+	address: {
+		description: "The UDP address to listen for flows on."
+		required:    false
+		type: string: {
+			default: "0.0.0.0:2055"
+			examples: ["0.0.0.0:2055", "systemd", "systemd#3"]
+		}
+	}
+	protocols: {
+		description: """
+			The flow protocols to accept.
+
+			The datagrams of the other protocols are rejected.
+			"""
+		required: false
+		type: array: {
+			default: ["netflow_v5", "netflow_v9", "ipfix", "sflow"]
+			items: type: string: enum: {
+				ipfix:      "IPFIX, also known as NetFlow version 10."
+				netflow_v5: "NetFlow version 5."
+				netflow_v9: "NetFlow version 9."
+				sflow:      "sFlow version 5."
+			}
+		}
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for the listening socket."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+	template_timeout_secs: {
+		description: """
+			How long the NetFlow v9 and IPFIX templates are kept after they were last received.
+
+			Exporters periodically resend their templates. The records whose template is unknown, or
+			expired, are dropped.
+			"""
+		required: false
+		type: uint: {
+			default: 1800
+			unit:    "seconds"
+		}
+	}
+}
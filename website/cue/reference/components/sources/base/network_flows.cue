@@ -0,0 +1,89 @@
+package metadata
+
+base: components: sources: network_flows: configuration: {
+	collector: {
+		description: "How network flows are collected."
+		required:    false
+		type: string: {
+			default: "auto"
+			enum: {
+				auto: "Use `sock_diag` if a netlink socket can be opened, and fall back to `procfs` otherwise."
+				procfs: """
+					Poll the connection tables of procfs, such as `/proc/net/tcp`.
+
+					The connection tables do not provide byte or packet counters.
+					"""
+				sock_diag: """
+					Read flows through the `sock_diag` netlink interface of the kernel.
+
+					This provides the byte and packet counters of TCP flows.
+					"""
+			}
+		}
+	}
+	event_type: {
+		description: "The type of events emitted for network flows."
+		required:    false
+		type: string: {
+			default: "log"
+			enum: {
+				log:    "Emit a log event for each flow."
+				metric: "Emit metrics for each flow, tagged with its endpoints and process."
+			}
+		}
+	}
+	include_processes: {
+		description: """
+			Whether to resolve the process which opened each flow.
+
+			Resolving processes requires reading the file descriptors of all processes, which can be
+			expensive on hosts running many processes, and requires Vector to run as root, or with the
+			`CAP_SYS_PTRACE` capability, to resolve the processes of other users.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	namespace: {
+		description: """
+			Overrides the default namespace for the metrics emitted by the source.
+
+			Only used when `event_type` is `metric`.
+			"""
+		required: false
+		type: string: default: "network_flows"
+	}
+	procfs_root: {
+		description: """
+			The root of the procfs filesystem.
+
+			Defaults to the `PROCFS_ROOT` environment variable, or `/proc` if it is unset. Set this to
+			the procfs of the host when running Vector in a container.
+			"""
+		required: false
+		type: string: examples: ["/host/proc"]
+	}
+	protocols: {
+		description: "The transport protocols to collect flows for."
+		required:    false
+		type: array: {
+			default: ["tcp", "udp"]
+			items: type: string: enum: {
+				tcp: "Transmission Control Protocol."
+				udp: """
+					User Datagram Protocol.
+
+					Only connected UDP sockets are reported, and their byte and packet counters are not
+					available.
+					"""
+			}
+		}
+	}
+	scrape_interval_secs: {
+		description: "The interval between flow collections, in seconds."
+		required:    false
+		type: uint: {
+			default: 15
+			unit:    "seconds"
+		}
+	}
+}
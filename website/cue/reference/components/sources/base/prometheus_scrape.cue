@@ -10,12 +10,43 @@ base: components: sources: prometheus_scrape: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			audience: {
+				description: """
+					The audience to request the access token for.
+
+					This isn't part of the OAuth 2.0 specification, but some authorization servers require it.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: string: examples: ["https://api.example.com"]
+			}
+			client_id: {
+				description:   "The client identifier."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["${CLIENT_ID}", "vector"]
+			}
+			client_secret: {
+				description:   "The client secret."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["${CLIENT_SECRET}"]
+			}
 			password: {
 				description:   "The basic authentication password."
 				relevant_when: "strategy = \"basic\""
 				required:      true
 				type: string: examples: ["${PASSWORD}", "password"]
 			}
+			scopes: {
+				description:   "The scopes to request the access token for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: examples: ["logs.write"]
+				}
+			}
 			strategy: {
 				description: "The authentication strategy to use."
 				required:    true
@@ -32,6 +63,15 @@ base: components: sources: prometheus_scrape: configuration: {
 
 						The bearer token value (OAuth2, JWT, etc.) is passed as-is.
 						"""
+					oauth2: """
+						OAuth 2.0 authentication, with the client credentials grant.
+
+						An access token is requested from the authorization server with the client credentials, and
+						refreshed ahead of its expiry. It's passed as a bearer token.
+
+						This is supported by the `http` and `prometheus_remote_write` sinks, and the `http_client`
+						and `prometheus_scrape` sources.
+						"""
 				}
 			}
 			token: {
@@ -40,6 +80,12 @@ base: components: sources: prometheus_scrape: configuration: {
 				required:      true
 				type: string: {}
 			}
+			token_url: {
+				description:   "The URL of the token endpoint of the authorization server."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: examples: ["https://auth.example.com/oauth2/token"]
+			}
 			user: {
 				description:   "The basic authentication username."
 				relevant_when: "strategy = \"basic\""
@@ -48,6 +94,145 @@ base: components: sources: prometheus_scrape: configuration: {
 			}
 		}
 	}
+	discovery: {
+		description: """
+			Discovers endpoints to scrape metrics from, in addition to the `endpoints`.
+
+			The discovered endpoints are refreshed without reloading the configuration.
+			"""
+		required: false
+		type: object: options: {
+			dns: {
+				description: "Discovers targets from DNS records, like the `dns_sd_configs` of Prometheus."
+				required:    false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						names: {
+							description: "The names to query."
+							required:    true
+							type: array: items: type: string: examples: ["_metrics._tcp.example.com"]
+						}
+						port: {
+							description: "The port of the targets discovered with `A` and `AAAA` records."
+							required:    false
+							type: uint: examples: [9100]
+						}
+						record_type: {
+							description: "The type of DNS records to query."
+							required:    false
+							type: string: {
+								default: "srv"
+								enum: {
+									a:    "`A` records, providing the IPv4 address of the targets."
+									aaaa: "`AAAA` records, providing the IPv6 address of the targets."
+									srv:  "`SRV` records, providing both the host and the port of the targets."
+								}
+							}
+						}
+						refresh_interval_secs: {
+							description: "The interval between queries, in seconds."
+							required:    false
+							type: uint: {
+								default: 30
+								unit:    "seconds"
+							}
+						}
+					}
+				}
+			}
+			files: {
+				description: "Discovers targets from JSON files, in the format of the `file_sd_configs` of Prometheus."
+				required:    false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						paths: {
+							description: """
+								Glob patterns of the files to read the targets from.
+
+								Each file contains a JSON array of groups of targets sharing labels, such as
+								`[{"targets": ["localhost:9100"], "labels": {"env": "prod"}}]`.
+								"""
+							required: true
+							type: array: items: type: string: examples: ["/etc/vector/targets/*.json"]
+						}
+						refresh_interval_secs: {
+							description: "The interval between reads of the files, in seconds."
+							required:    false
+							type: uint: {
+								default: 60
+								unit:    "seconds"
+							}
+						}
+					}
+				}
+			}
+			path: {
+				description: "The path of the discovered endpoints, unless set by their `__metrics_path__` label."
+				required:    false
+				type: string: {
+					default: "/metrics"
+					examples: ["/federate"]
+				}
+			}
+			relabel: {
+				description: """
+					Rules filtering the discovered targets, applied in order.
+
+					Only the targets kept by all the rules are scraped.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						action: {
+							description: "What to do with the targets matching a relabeling rule."
+							required:    true
+							type: string: enum: {
+								drop: "Drop the targets matching the rule."
+								keep: "Only keep the targets matching the rule."
+							}
+						}
+						regex: {
+							description: """
+								The regular expression the joined values are matched against.
+
+								The whole value must match.
+								"""
+							required: false
+							type: string: {
+								default: "(.*)"
+								examples: ["node-exporter-.*"]
+							}
+						}
+						separator: {
+							description: "The separator of the joined values of the `source_labels`."
+							required:    false
+							type: string: default: ";"
+						}
+						source_labels: {
+							description: """
+								The labels whose values are joined with the `separator`, and matched against the `regex`.
+
+								Missing labels have an empty value.
+								"""
+							required: true
+							type: array: items: type: string: examples: ["__meta_dns_name"]
+						}
+					}
+				}
+			}
+			scheme: {
+				description: "The scheme of the discovered endpoints, unless set by their `__scheme__` label."
+				required:    false
+				type: string: {
+					default: "http"
+					examples: ["https"]
+				}
+			}
+		}
+	}
 	endpoint_tag: {
 		description: """
 			The tag name added to each event representing the scraped instance's endpoint.
@@ -59,8 +244,11 @@ base: components: sources: prometheus_scrape: configuration: {
 	}
 	endpoints: {
 		description: "Endpoints to scrape metrics from."
-		required:    true
-		type: array: items: type: string: examples: ["http://localhost:9090/metrics"]
+		required:    false
+		type: array: {
+			default: []
+			items: type: string: examples: ["http://localhost:9090/metrics"]
+		}
 	}
 	honor_labels: {
 		description: """
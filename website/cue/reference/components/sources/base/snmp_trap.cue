@@ -0,0 +1,96 @@
+package metadata
+
+base: components: sources: snmp_trap: configuration: {
+	address: {
+		description: "The UDP address to listen for traps on."
+		required:    false
+		type: string: {
+			default: "0.0.0.0:162"
+			examples: ["0.0.0.0:162", "systemd", "systemd#3"]
+		}
+	}
+	communities: {
+		description: """
+			The communities the SNMPv1 and SNMPv2c traps are accepted from.
+
+			By default, the traps of any community are accepted.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["public"]
+		}
+	}
+	mib_paths: {
+		description: """
+			The MIB files, or directories of MIB files, to resolve the object identifiers with.
+
+			The object identifiers of the base SMI and SNMPv2 MIB modules, such as `sysUpTime` and the
+			generic traps, are resolved without any MIB files. The object identifiers that can't be
+			resolved are kept in their numeric form.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["/usr/share/snmp/mibs"]
+		}
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for the listening socket."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+	users: {
+		description: """
+			The users the SNMPv3 traps are accepted from.
+
+			The SNMPv3 traps of other users are rejected, as are those sent with a lower security
+			level than the one of their user.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				authentication_passphrase: {
+					description: """
+						The passphrase the authentication key of the user is derived from.
+
+						Required when `authentication_protocol` is set.
+						"""
+					required: false
+					type: string: examples: ["${SNMP_AUTH_PASSPHRASE}"]
+				}
+				authentication_protocol: {
+					description: "The authentication protocol of an SNMPv3 user."
+					required:    false
+					type: string: enum: {
+						md5: "HMAC-MD5-96."
+						sha: "HMAC-SHA-96."
+					}
+				}
+				name: {
+					description: "The name of the user."
+					required:    true
+					type: string: examples: ["vector"]
+				}
+				privacy_passphrase: {
+					description: """
+						The passphrase the privacy key of the user is derived from.
+
+						Required when `privacy_protocol` is set.
+						"""
+					required: false
+					type: string: examples: ["${SNMP_PRIVACY_PASSPHRASE}"]
+				}
+				privacy_protocol: {
+					description: "The privacy protocol of an SNMPv3 user."
+					required:    false
+					type: string: enum: {
+						aes: "CFB128-AES-128."
+						des: "CBC-DES."
+					}
+				}
+			}
+		}
+	}
+}
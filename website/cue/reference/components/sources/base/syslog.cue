@@ -18,6 +18,35 @@ base: components: sources: syslog: configuration: {
 		required:      false
 		type: uint: {}
 	}
+	connection_limit_per_ip: {
+		description: """
+			The maximum number of TCP connections that are allowed at any given time from a single IP
+			address.
+
+			With `proxy_protocol`, the address of the client is used rather than the one of the load
+			balancer.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: {}
+	}
+	connection_limit_policy: {
+		description:   "What to do with the connections over `connection_limit` or `connection_limit_per_ip`."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: string: {
+			default: "queue"
+			enum: {
+				queue: """
+					Queue the connections until others are closed.
+
+					The connections over `connection_limit` are left in the backlog of the listener, and
+					those over `connection_limit_per_ip` are accepted but not read from.
+					"""
+				reject: "Close the connections right away."
+			}
+		}
+	}
 	host_key: {
 		description: """
 			Overrides the name of the log field used to add the peer host to each event.
@@ -42,6 +71,17 @@ base: components: sources: syslog: configuration: {
 			type: uint: unit: "seconds"
 		}
 	}
+	max_connection_bytes_per_sec: {
+		description: """
+			The maximum rate at which the data of each connection is read.
+
+			The connections sending data faster aren't read from until they're back under the rate,
+			slowing their client down through TCP backpressure.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: unit: "bytes"
+	}
 	max_length: {
 		description: """
 			The maximum buffer size of incoming messages, in bytes.
@@ -73,6 +113,21 @@ base: components: sources: syslog: configuration: {
 		required:      true
 		type: string: examples: ["/path/to/socket"]
 	}
+	proxy_protocol: {
+		description: """
+			Whether the connections start with a PROXY protocol header.
+
+			Load balancers such as HAProxy or AWS Network Load Balancers send the header ahead of the
+			connections they proxy, to pass on the address of the client. The address of the client
+			is then used as the peer address of the events, instead of the address of the load balancer.
+
+			Both versions of the protocol are supported. When enabled, connections without the header
+			are closed. With TLS, the header is expected ahead of the TLS handshake.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: bool: default: false
+	}
 	receive_buffer_bytes: {
 		description: """
 			The size of the receive buffer used for each connection.
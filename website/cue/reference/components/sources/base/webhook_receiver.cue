@@ -0,0 +1,724 @@
+package metadata
+
+base: components: sources: webhook_receiver: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: """
+			The socket address to listen for connections on.
+
+			It _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["0.0.0.0:80", "localhost:80"]
+	}
+	auth: {
+		description: "HTTP Basic authentication configuration."
+		required:    false
+		type: object: options: {
+			password: {
+				description: "The password for basic authentication."
+				required:    true
+				type: string: examples: ["hunter2", "${PASSWORD}"]
+			}
+			username: {
+				description: "The username for basic authentication."
+				required:    true
+				type: string: examples: ["AzureDiamond", "admin"]
+			}
+		}
+	}
+	decoding: {
+		description: "Configures how events are decoded from raw bytes."
+		required:    false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    true
+				type: string: enum: {
+					bytes: "Uses the raw bytes as-is."
+					csv: """
+						Decodes the raw bytes as [CSV][csv] records.
+
+						Each record is decoded into an event, with its columns named from the configured `fields`,
+						or from a header row.
+
+						[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+						"""
+					gelf: """
+						Decodes the raw bytes as a [GELF][gelf] message.
+
+						[gelf]: https://docs.graylog.org/docs/gelf
+						"""
+					json: """
+						Decodes the raw bytes as [JSON][json].
+
+						[json]: https://www.json.org/
+						"""
+					logfmt: """
+						Decodes the raw bytes as a [logfmt][logfmt] message.
+
+						The types of unquoted values are inferred, and keys of nested fields are unflattened, so
+						that the events encoded with the `logfmt` encoder are decoded with the same fields and
+						types.
+
+						[logfmt]: https://brandur.org/logfmt
+						"""
+					native: """
+						Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+						This codec is **[experimental][experimental]**.
+
+						[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+						"""
+					native_json: """
+						Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+						This codec is **[experimental][experimental]**.
+
+						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+						"""
+					protobuf: """
+						Decodes the raw bytes as a [Protocol Buffers][protobuf] message.
+
+						The message type is looked up in a compiled file descriptor set.
+
+						[protobuf]: https://protobuf.dev/
+						"""
+					syslog: """
+						Decodes the raw bytes as a Syslog message.
+
+						Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+						[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+						[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+						[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+						"""
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when reading CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					double_quote: {
+						description: """
+							Whether two consecutive quotes in a quoted field stand for a quote.
+
+							When disabled, quotes are escaped with `escape` instead.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					escape: {
+						description: "The escape character to use when reading CSV with `double_quote` disabled."
+						required:    false
+						type: uint: default: 92
+					}
+					fields: {
+						description: """
+							The names of the fields the columns are decoded into, in order.
+
+							The names are used as they are, as top-level fields. They take precedence over the names
+							read from the header row, and the columns past them are named from the header row, if any,
+							or dropped.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Whether the first record of each input is a header row naming the columns.
+
+							An input is a connection of the sources reading streams, and a message, request or
+							datagram of the other sources.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: "The quote character to use when reading CSV."
+						required:    false
+						type: uint: default: 34
+					}
+				}
+			}
+			json: {
+				description:   "JSON-specific decoding options."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: simd: {
+					description: """
+						Whether to parse the JSON with SIMD instructions.
+
+						Parsing with SIMD instructions is faster on large or numerous events, at the cost of
+						copying each frame before parsing it. It falls back to the default parser on CPUs which
+						don't support the required instructions, AVX2 or SSE4.2 on x86-64 and NEON on AArch64, so
+						the events are the same whichever parser is used.
+						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+			logfmt: {
+				description:   "Logfmt-specific decoding options."
+				relevant_when: "codec = \"logfmt\""
+				required:      false
+				type: object: options: {
+					flatten_separator: {
+						description: "The separator joining the names of nested fields into keys."
+						required:    false
+						type: string: default: "."
+					}
+					infer_types: {
+						description: """
+							Whether to infer the types of unquoted values.
+
+							When enabled, `true` and `false` are decoded as booleans, numbers as integers or floats,
+							RFC 3339 timestamps as timestamps, and empty values as null. Quoted values are always
+							decoded as strings. When disabled, all the values are decoded as strings.
+
+							Keys without a value are decoded as `true` either way.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					unflatten: {
+						description: """
+							Whether to unflatten keys into nested fields.
+
+							When enabled, keys are split on `flatten_separator` into nested objects, and the parts
+							suffixed with an index in brackets, such as `tags[0]`, into arrays. Keys that can't be
+							split, such as ones with empty parts, are kept as they are.
+							"""
+						required: false
+						type: bool: default: true
+					}
+				}
+			}
+			protobuf: {
+				description:   "Protocol Buffers-specific decoding options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the compiled file descriptor set of the message type.
+
+							The file descriptor set can be produced with `protoc --include_imports --descriptor_set_out`.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to decode."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
+		}
+	}
+	decompression: {
+		description: "Configuration for decompressing request bodies, according to their `Content-Encoding` header."
+		required:    false
+		type: object: options: {
+			allowed_encodings: {
+				description: """
+					The content encodings that request bodies are accepted with.
+
+					Requests with any other encoding are rejected with a `415 Unsupported Media Type` response.
+					By default, all supported encodings are accepted.
+					"""
+				required: false
+				type: array: items: type: string: {
+					enum: {
+						deflate: """
+							[Deflate][deflate] compression, in the zlib format.
+
+							[deflate]: https://en.wikipedia.org/wiki/DEFLATE
+							"""
+						gzip: """
+							[Gzip][gzip] compression.
+
+							[gzip]: https://www.gzip.org/
+							"""
+						identity: "No compression."
+						snappy: """
+							[Snappy][snappy] compression, in the raw format.
+
+							[snappy]: https://github.com/google/snappy
+							"""
+						zstd: """
+							[Zstandard][zstd] compression.
+
+							[zstd]: https://facebook.github.io/zstd/
+							"""
+					}
+					examples: ["gzip", "zstd"]
+				}
+			}
+			max_decompressed_size_bytes: {
+				description: """
+					The maximum size, in bytes, of a request body once decompressed.
+
+					Requests whose body decompresses to more than this are rejected with a
+					`413 Payload Too Large` response, without being decompressed any further. By default, the
+					size of decompressed bodies isn't limited.
+					"""
+				required: false
+				type: uint: examples: [10485760]
+			}
+		}
+	}
+	encoding: {
+		description: """
+			The expected encoding of received data.
+
+			Note: For `json` and `ndjson` encodings, the fields of the JSON objects are output as separate fields.
+			"""
+		required: false
+		type: string: enum: {
+			binary: "Binary."
+			json:   "JSON."
+			ndjson: "Newline-delimited JSON."
+			text:   "Plaintext."
+		}
+	}
+	framing: {
+		description: """
+			Framing configuration.
+
+			Framing deals with how events are separated when encoded in a raw byte form, where each event is
+			a "frame" that must be prefixed, or delimited, in a way that marks where an event begins and
+			ends within the byte stream.
+			"""
+		required: false
+		type: object: options: {
+			character_delimited: {
+				description:   "Options for the character delimited decoder."
+				relevant_when: "method = \"character_delimited\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The character that delimits byte sequences."
+						required:    true
+						type: uint: {}
+					}
+					max_length: {
+						description: """
+																The maximum length of the byte buffer.
+
+																This length does *not* include the trailing delimiter.
+
+																By default, there is no maximum length enforced. If events are malformed, this can lead to
+																additional resource usage as events continue to be buffered in memory, and can potentially
+																lead to memory exhaustion in extreme cases.
+
+																If there is a risk of processing malformed data, such as logs with user-controlled input,
+																consider setting the maximum length to a reasonably large value as a safety net. This
+																ensures that processing is not actually unbounded.
+																"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					max_length: {
+						description: """
+							The maximum length of the messages, once reassembled and decompressed.
+
+							By default, there is no maximum length enforced.
+							"""
+						required: false
+						type: uint: {}
+					}
+					pending_messages_limit: {
+						description: """
+							The maximum number of messages which are reassembled at the same time.
+
+							The chunks starting new messages are discarded once the limit is reached.
+
+							By default, there is no limit.
+							"""
+						required: false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: """
+							The time to wait for all the chunks of a message, in seconds.
+
+							The messages which are still incomplete after this time are discarded.
+							"""
+						required: false
+						type: float: {
+							default: 5.0
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+			method: {
+				description: "The framing method."
+				required:    true
+				type: string: enum: {
+					bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+					character_delimited: "Byte frames which are delimited by a chosen character."
+					chunked_gelf: """
+						Byte frames which are [GELF][gelf] messages sent over UDP, possibly chunked and compressed.
+
+						Each datagram holds either a whole message or a chunk of one. The chunks are reassembled
+						into their message, and the messages compressed with gzip or zlib are decompressed.
+
+						[gelf]: https://docs.graylog.org/docs/gelf
+						"""
+					length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+					newline_delimited:   "Byte frames which are delimited by a newline character."
+					octet_counting: """
+						Byte frames according to the [octet counting][octet_counting] format.
+
+						[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+						"""
+					varint_length_delimited: """
+						Byte frames which are prefixed by an unsigned varint indicating the length.
+
+						This is how length-delimited Protocol Buffers messages are framed.
+						"""
+				}
+			}
+			newline_delimited: {
+				description:   "Options for the newline delimited decoder."
+				relevant_when: "method = \"newline_delimited\""
+				required:      false
+				type: object: options: max_length: {
+					description: """
+						The maximum length of the byte buffer.
+
+						This length does *not* include the trailing delimiter.
+
+						By default, there is no maximum length enforced. If events are malformed, this can lead to
+						additional resource usage as events continue to be buffered in memory, and can potentially
+						lead to memory exhaustion in extreme cases.
+
+						If there is a risk of processing malformed data, such as logs with user-controlled input,
+						consider setting the maximum length to a reasonably large value as a safety net. This
+						ensures that processing is not actually unbounded.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+			octet_counting: {
+				description:   "Options for the octet counting decoder."
+				relevant_when: "method = \"octet_counting\""
+				required:      false
+				type: object: options: max_length: {
+					description: "The maximum length of the byte buffer."
+					required:    false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	headers: {
+		description: """
+			A list of HTTP headers to include in the log event.
+
+			These override any values included in the JSON payload with conflicting names.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["User-Agent", "X-My-Custom-Header"]
+		}
+	}
+	method: {
+		description: "Specifies the action of the HTTP request."
+		required:    false
+		type: string: {
+			default: "POST"
+			enum: {
+				DELETE: "HTTP DELETE method."
+				GET:    "HTTP GET method."
+				HEAD:   "HTTP HEAD method."
+				PATCH:  "HTTP PATCH method."
+				POST:   "HTTP POST method."
+				PUT:    "HTTP Put method."
+			}
+		}
+	}
+	path: {
+		description: "The URL path on which log event POST requests are sent."
+		required:    false
+		type: string: {
+			default: "/"
+			examples: ["/event/path", "/logs"]
+		}
+	}
+	path_key: {
+		description: "The event key in which the requested URL path used to send the request is stored."
+		required:    false
+		type: string: {
+			default: "path"
+			examples: ["vector_http_path"]
+		}
+	}
+	query_parameters: {
+		description: """
+			A list of URL query parameters to include in the log event.
+
+			These override any values included in the body with conflicting names.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["application", "source"]
+		}
+	}
+	signature: {
+		description: "Signature verification configuration."
+		required:    true
+		type: object: options: {
+			algorithm: {
+				description: "The hash function of the HMAC."
+				required:    false
+				type: string: {
+					default: "sha256"
+					enum: {
+						sha1:   "HMAC-SHA1, only used by older providers."
+						sha256: "HMAC-SHA256."
+						sha512: "HMAC-SHA512."
+					}
+				}
+			}
+			encoding: {
+				description: """
+					The encoding of the signatures, with the `plain` format.
+
+					The `stripe` and `slack` formats always encode the signatures in hexadecimal.
+					"""
+				required: false
+				type: string: {
+					default: "hex"
+					enum: {
+						base64: "Base64, as sent by Shopify for example."
+						hex:    "Hexadecimal."
+					}
+				}
+			}
+			format: {
+				description: "The format of the signatures."
+				required:    false
+				type: string: {
+					default: "plain"
+					enum: {
+						plain: """
+							The header holds the signature of the body, after an optional prefix.
+
+							This is the format of GitHub, with `X-Hub-Signature-256: sha256=<signature>`, and of most
+							providers.
+							"""
+						slack: """
+							The header holds the signature of the timestamp of the `X-Slack-Request-Timestamp` header
+							and the body.
+
+							This is the format of Slack, with `X-Slack-Signature: v0=<signature>`.
+							"""
+						stripe: """
+							The header holds a timestamp and the signatures of the timestamp and the body.
+
+							This is the format of Stripe, with `Stripe-Signature: t=<timestamp>,v1=<signature>`.
+							"""
+					}
+				}
+			}
+			header: {
+				description: """
+					The header holding the signature.
+
+					Defaults to `X-Hub-Signature-256` with the `plain` format, `Stripe-Signature` with the
+					`stripe` format, and `X-Slack-Signature` with the `slack` format.
+					"""
+				required: false
+				type: string: examples: ["X-Hub-Signature-256", "X-Shopify-Hmac-Sha256"]
+			}
+			prefix: {
+				description: """
+					The prefix of the signature in the header, with the `plain` format.
+
+					Defaults to the name of the algorithm followed by `=`, such as `sha256=`, as sent by GitHub.
+					Set this to `""` for the providers sending the signature alone.
+					"""
+				required: false
+				type: string: examples: ["sha256="]
+			}
+			secret: {
+				description: """
+					The secret shared with the provider, used as the key of the HMAC.
+
+					This is usually taken from a secrets backend, such as with `SECRET[backend.webhook_secret]`.
+					"""
+				required: true
+				type: string: examples: ["${WEBHOOK_SECRET}", "SECRET[backend.webhook_secret]"]
+			}
+			timestamp_tolerance_secs: {
+				description: """
+					The maximum difference between the signed timestamp of the requests and the current time,
+					with the `stripe` and `slack` formats.
+
+					The requests signed longer ago are rejected, so that they can't be replayed.
+					"""
+				required: false
+				type: uint: {
+					default: 300
+					examples: [
+						300,
+					]
+					unit: "seconds"
+				}
+			}
+		}
+	}
+	strict_path: {
+		description: """
+			Whether or not to treat the configured `path` as an absolute path.
+
+			If set to `true`, only requests using the exact URL path specified in `path` are accepted. Otherwise,
+			requests sent to a URL path that starts with the value of `path` are accepted.
+
+			With `strict_path` set to `false` and `path` set to `""`, the configured HTTP source accepts requests from
+			any URL path.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	tls: {
+		description: "Configures the TLS options for incoming/outgoing connections."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
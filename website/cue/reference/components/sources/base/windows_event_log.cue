@@ -0,0 +1,87 @@
+package metadata
+
+base: components: sources: windows_event_log: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	batch_size: {
+		description: """
+			The maximum number of events read from a channel at once.
+
+			The bookmark of a channel is checkpointed at the end of each batch.
+			"""
+		required: false
+		type: uint: {
+			default: 100
+			unit:    "events"
+		}
+	}
+	channels: {
+		description: "The channels to read events from."
+		required:    false
+		type: array: {
+			default: ["System", "Application"]
+			items: type: string: examples: ["System", "Application", "Microsoft-Windows-Sysmon/Operational"]
+		}
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the bookmarks of the channels.
+
+			By default, the global `data_dir` option is used. Make sure the running user has write
+			permissions to this directory.
+			"""
+		required: false
+		type: string: examples: ["C:\\ProgramData\\vector"]
+	}
+	query: {
+		description: """
+			An XPath query selecting the events to read from each channel.
+
+			By default, all events are read.
+			"""
+		required: false
+		type: string: {
+			default: "*"
+			examples: ["*[System[(Level=1 or Level=2 or Level=3)]]", "*[System[Provider[@Name='Service Control Manager'] and (EventID=7036)]]"]
+		}
+	}
+	read_existing_events: {
+		description: """
+			Whether to read the events already logged to the channels when there is no checkpoint for
+			them.
+
+			By default, only the events logged after Vector starts are read.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	render_message: {
+		description: """
+			Whether to render the message of events from the metadata of their provider.
+
+			Rendering messages is relatively expensive, and is not possible for events whose provider
+			is not installed on the host, such as forwarded events.
+			"""
+		required: false
+		type: bool: default: true
+	}
+}
@@ -216,6 +216,30 @@ components: sources: datadog_agent: {
 				duration distribution).
 				"""
 		}
+		profiles: {
+			title: "Profiles passthrough"
+			body: """
+				The profiles sent by the Datadog Agent to `/api/v2/profile` are passed through without
+				being decoded. Each one is output as a trace event holding the payload of its request,
+				along with the headers needed to forward it. Such events aren't sampled, and are
+				forwarded as-is to the profiling intake by the `datadog_traces` sink.
+				"""
+		}
+		trace_sampling: {
+			title: "Trace sampling"
+			body: """
+				With `trace_sampling` set, the source samples the received traces itself, so that Vector can
+				act as a sampling tier in front of the `datadog_traces` sink. The rate of each trace is taken
+				from the first rule matching the service of its root span, its environment, or a
+				condition, and falls back to `default_rate`.
+
+				Sampling decisions are derived from the trace ID the same way the Datadog Agent derives
+				them, so that all the chunks of a trace are kept or dropped together, even across Vector
+				instances. The rate is recorded in the `_sample_rate` metric of the root span of the
+				kept traces, and the `datadog_traces` sink weights the APM statistics it computes
+				accordingly.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -14,7 +14,7 @@ components: sources: exec: {
 
 	features: {
 		auto_generated:   true
-		acknowledgements: false
+		acknowledgements: true
 		multiline: enabled: false
 		codecs: {
 			enabled:         true
@@ -53,7 +53,44 @@ components: sources: exec: {
 				required:    false
 				type: string: {
 					default: null
-					examples: ["stdout", "stderr"]
+					output: logs: completion: {
+		description: "An event emitted at the end of every run when `always_emit_completion` is enabled."
+		fields: {
+			host:        fields._local_host
+			timestamp:   fields._current_timestamp
+			command:      components.sources.exec.output.logs.line.fields.command
+			pid:          components.sources.exec.output.logs.line.fields.pid
+			source_type:  components.sources.exec.output.logs.line.fields.source_type
+			window_start: components.sources.exec.output.logs.line.fields.window_start
+			window_end:   components.sources.exec.output.logs.line.fields.window_end
+			exit_code: {
+				description: "The exit code of the command, if it is known."
+				required:    false
+				type: uint: {
+					examples: [0, 1]
+					unit: null
+				}
+			}
+			event_count: {
+				description: "The number of events emitted during the run."
+				required:    true
+				type: uint: {
+					examples: [0, 42]
+					unit: null
+				}
+			}
+			duration_seconds: {
+				description: "The duration of the run, in seconds."
+				required:    true
+				type: float: {
+					examples: [0.25, 12.5]
+					unit: "seconds"
+				}
+			}
+		}
+	}
+
+	examples: ["stdout", "stderr"]
 				}
 			}
 			pid: {
@@ -80,6 +117,16 @@ components: sources: exec: {
 					examples: ["exec"]
 				}
 			}
+			window_start: {
+				description: "The start of the window of the scheduled run that generated this event."
+				required:    false
+				type: timestamp: {}
+			}
+			window_end: {
+				description: "The end of the window of the scheduled run that generated this event."
+				required:    false
+				type: timestamp: {}
+			}
 		}
 	}
 
@@ -102,6 +149,59 @@ components: sources: exec: {
 	]
 
 	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				When acknowledgements are enabled, each batch of events read from a command must be
+				delivered before more of its output is read, so a command writing faster than its events
+				are delivered is slowed down by its output pipe filling up.
+
+				Batches that aren't delivered are resent up to
+				[`streaming.delivery_retries`](#streaming.delivery_retries) times. After that, the
+				command is either killed, dropping the batch, or paused until the batch is delivered,
+				depending on [`streaming.on_delivery_failure`](#streaming.on_delivery_failure).
+				"""
+		}
+		framing_and_decoding: {
+			title: "Framing and Decoding"
+			body: """
+				Command output is read from a pipe as a stream of bytes, so `newline_delimited` or
+				`character_delimited` [`framing`](#framing) can't work with the `native`
+				[`decoding`](#decoding), since native events are binary and can contain the delimiter.
+				This combination is rejected when the configuration is loaded. With `bytes` framing, the
+				whole output of the command is decoded at once, when it exits.
+
+				If a command exits successfully but none of its output could be decoded, a warning is
+				logged once to point out the likely mismatch.
+				"""
+		}
+		heartbeats: {
+			title: "Heartbeats"
+			body: """
+				A streaming command that stops writing output may either be hung, or simply have nothing to
+				write. With [`streaming.heartbeat`](#streaming.heartbeat), the command is given a control
+				channel as file descriptor 3, also named by the `VECTOR_HEARTBEAT_FD` environment variable,
+				to which it writes a line at least once every
+				[`streaming.heartbeat.timeout_secs`](#streaming.heartbeat.timeout_secs), such as with
+				`echo ok >&3` in a shell script. A command that misses its heartbeats is considered hung, and
+				a command can also report itself as unhealthy by writing a line starting with `unhealthy`,
+				optionally followed by a reason. In both cases the command is killed, a dedicated error is
+				reported, and the command is restarted according to the respawn options.
+				"""
+		}
+		incremental_polling: {
+			title: "Incremental Polling"
+			body: """
+				In the scheduled mode, each run covers a window of time, from the end of the window of the
+				last successful run to the start of the run. The boundaries of the window can be passed to
+				the command, with the `{{ window_start }}` and `{{ window_end }}` placeholders in its
+				[`command`](#command) or its [`environment`](#environment), so it can fetch the records
+				since the last run without a wrapper script keeping track of it. A failed run is covered
+				again by the next window.
+
+				The window of each event is also added to it, as `window_start` and `window_end`.
+				"""
+		}
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
@@ -109,6 +209,18 @@ components: sources: exec: {
 				[`maximum_buffer_size_bytes`](#maximum_buffer_size_bytes) is reached.
 				"""
 		}
+		orphans: {
+			title: "Orphaned Children"
+			body: """
+				If Vector exits uncleanly, children spawned by this source may keep running after being
+				reparented to init. When [`reap_orphans`](#reap_orphans) is enabled, the PID and start time
+				of each child are recorded in the data directory, and on startup any recorded child that
+				is still running with the same start time and command line is sent a SIGTERM.
+
+				Since PIDs are reused by the operating system this is a best-effort heuristic, and it is
+				only supported on Linux, where process start times are available.
+				"""
+		}
 		shutdown: {
 			title: "Shutting Down"
 			body: """
@@ -128,7 +240,12 @@ components: sources: exec: {
 	telemetry: metrics: {
 		command_executed_total:               components.sources.internal_metrics.output.metrics.command_executed_total
 		command_execution_duration_seconds:   components.sources.internal_metrics.output.metrics.command_execution_duration_seconds
+		command_restarts_total:               components.sources.internal_metrics.output.metrics.command_restarts_total
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		exec_events_sampled_out_total:        components.sources.internal_metrics.output.metrics.exec_events_sampled_out_total
+		exec_heartbeats_missed_total:         components.sources.internal_metrics.output.metrics.exec_heartbeats_missed_total
+		exec_orphans_reaped_total:            components.sources.internal_metrics.output.metrics.exec_orphans_reaped_total
+		exec_unhealthy_reports_total:         components.sources.internal_metrics.output.metrics.exec_unhealthy_reports_total
 		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
 		processing_errors_total:              components.sources.internal_metrics.output.metrics.processing_errors_total
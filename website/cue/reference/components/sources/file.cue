@@ -245,6 +245,23 @@ components: sources: file: {
 				"""
 		}
 
+		migrating_checkpoints: {
+			title: "Migrating Checkpoints"
+			body: """
+				When moving Vector to another host, or re-provisioning it, the
+				[checkpoints](#checkpointing) can be carried over so that files are not
+				read again from the beginning. With Vector stopped, export them with
+				`vector checkpoints export --data-dir <dir> --output checkpoints.json`,
+				where `<dir>` is the `data_dir` of the source, or the global `data_dir`
+				joined with the ID of the source. Then import them on the new host with
+				`vector checkpoints import --data-dir <dir> --input checkpoints.json`
+				before starting Vector.
+
+				Checkpoints are keyed by file fingerprints, so only the `checksum`
+				fingerprint strategy produces checkpoints that remain valid across hosts.
+				"""
+		}
+
 		multiline_messages: {
 			title: "Multiline Messages"
 			body: """
@@ -365,6 +382,38 @@ components: sources: file: {
 							a timestamp sequence.
 						"""#
 				},
+				{
+					title: "Example 4: VRL Conditions"
+					body: #"""
+						When lines are hard to match with a regular expression, the
+						`start_condition` and `condition` options can be used instead of
+						`start_pattern` and `condition_pattern`. They take a VRL condition,
+						run against an event holding the line in its `message` field. For
+						example, to join Java stack traces including their causes:
+
+						```toml
+						[sources.my_file_source]
+						type = "file"
+						# ...
+
+						[sources.my_file_source.multiline]
+						start_condition = "!match(string!(.message), r'^\\s')"
+						mode = "continue_through"
+						condition = "match(string!(.message), r'^\s+at ') || starts_with(string!(.message), "Caused by:")"
+						timeout_ms = 1000
+						```
+						"""#
+				},
+				{
+					title: "Flushing"
+					body: #"""
+						Aggregated lines are flushed once the message is complete, once
+						`timeout_ms` is reached, when the file they were read from is
+						rotated, or when Vector shuts down, so partial messages are never
+						dropped. The `multiline_flushed_lines_total` internal metric counts
+						the flushed lines by the reason they were flushed for.
+						"""#
+				},
 			]
 		}
 
@@ -439,5 +488,6 @@ components: sources: file: {
 		files_unwatched_total:                components.sources.internal_metrics.output.metrics.files_unwatched_total
 		fingerprint_read_errors_total:        components.sources.internal_metrics.output.metrics.fingerprint_read_errors_total
 		glob_errors_total:                    components.sources.internal_metrics.output.metrics.glob_errors_total
+		multiline_flushed_lines_total:        components.sources.internal_metrics.output.metrics.multiline_flushed_lines_total
 	}
 }
@@ -0,0 +1,102 @@
+package metadata
+
+components: sources: grpc_server: {
+	_port: 50051
+
+	title: "gRPC Server"
+
+	description: """
+		Serves a user-defined gRPC method, and decodes the Protocol Buffers messages of its
+		requests into events.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.socket_receiver
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.grpc_server.configuration
+
+	output: logs: event: {
+		description: "An event decoded from a message of a request."
+		fields: {
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["grpc_server"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			"*": {
+				description: "The fields of the message, decoded with the input type of the method."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		methods: {
+			title: "Methods"
+			body: """
+				The service and its messages are described by the file descriptor set at `desc_file`,
+				which can be compiled from the `.proto` definitions with
+				`protoc --include_imports --descriptor_set_out`. Only the method set in `method` is
+				served.
+
+				Each message of a request is decoded into an event, with enum values decoded into
+				their names. Once the events are accepted, and delivered if acknowledgements are
+				enabled, the message configured in `response` is returned. Requests whose events
+				aren't delivered are answered with an error status, so that clients can retry them.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -167,6 +167,20 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_cpu_seconds_total: _host & _process_counter & {
+			description: "The number of CPU seconds accumulated by this process in different operating modes."
+			tags: mode: {
+				description: "Which mode the CPU was running in during the given time."
+				required:    true
+				examples: ["user", "system"]
+			}
+		}
+		process_memory_rss_bytes:     _host & _process_gauge & {description: "The amount of physical memory used by this process, in bytes."}
+		process_memory_virtual_bytes: _host & _process_gauge & {description: "The amount of virtual memory used by this process, in bytes."}
+		process_open_fds:             _host & _process_gauge & {description: "The number of file descriptors opened by this process. Only reported for processes Vector is permitted to inspect."}
+		process_threads:              _host & _process_gauge & {description: "The number of threads of this process."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -256,6 +270,29 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_counter: _process & {type: "counter"}
+		_process_gauge: _process & {type: "gauge"}
+		_process: {
+			tags: _host_metrics_tags & {
+				collector: examples: ["process"]
+				pid: {
+					description: "The ID of the process."
+					required:    true
+					examples: ["1", "4127"]
+				}
+				name: {
+					description: "The name of the process, truncated to 15 characters."
+					required:    true
+					examples: ["nginx", "java"]
+				}
+				user: {
+					description: "The name of the user running the process, or its ID if it has no name."
+					required:    true
+					examples: ["root", "www-data"]
+				}
+			}
+			relevant_when: "OS is Linux"
+		}
 	}
 
 	telemetry: metrics: {
@@ -185,6 +185,7 @@ components: sources: http_server: {
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
 		http_bad_requests_total:              components.sources.internal_metrics.output.metrics.http_bad_requests_total
 		parse_errors_total:                   components.sources.internal_metrics.output.metrics.parse_errors_total
+		pass_through_active:                  components.sources.internal_metrics.output.metrics.pass_through_active
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
 	}
 
@@ -196,5 +197,19 @@ components: sources: http_server: {
 				Supported algorithms are `gzip`, `deflate`, `snappy`, and `zstd`.
 				"""
 		}
+		pass_through: {
+			title: "Pass-through"
+			body: """
+				When all the consumers of this source are `console`, `file` (with a path without
+				templates), or `socket` sinks, whose codec writes events as this source decodes them,
+				such as the `text` codec for the `bytes` decoding, the frames of the request bodies
+				are relayed without being decoded. They are written by the sinks as they were
+				received, without the fields this source would add to them, and requests are still
+				acknowledged once the sinks write them. This only applies if the sinks don't change
+				the events with `encoding` options, keep the events in memory buffers, and have no
+				outputs consumed by other components. The `pass_through_active` internal metric shows
+				whether it applies.
+				"""
+		}
 	}
 }
@@ -40,6 +40,27 @@ components: sources: internal_metrics: {
 
 	output: metrics: {
 		// Default internal metrics tags
+		_tenant: {
+			description: "The name of the tenant."
+			required:    true
+			examples: ["team_a"]
+		}
+		_bandwidth_limit: {
+			description: "The bandwidth limit delaying the requests."
+			required:    true
+			enum: {
+				global: "The `egress_rate_limit_bytes_per_sec` limit shared by all of the sinks."
+				sink:   "The `request.rate_limit_bytes_per_sec` limit of the sink."
+			}
+		}
+		_tenant_throttle_reason: {
+			description: "The quota exceeded by the tenant."
+			required:    true
+			enum: {
+				buffer_bytes:   "The tenant exceeded its `max_buffer_bytes` quota."
+				events_per_sec: "The tenant exceeded its `max_events_per_sec` quota."
+			}
+		}
 		_internal_metrics_tags: {
 			pid: {
 				description: "The process ID of the Vector instance."
@@ -54,6 +75,12 @@ components: sources: internal_metrics: {
 		}
 
 		// Instance-level "process" metrics
+		active_endpoints: {
+			description:       "The number of endpoints the requests are currently balanced across."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		aggregate_events_recorded_total: {
 			description:       "The number of events recorded by the aggregate transform."
 			type:              "counter"
@@ -72,12 +99,60 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		aggregate_logs_events_recorded_total: {
+			description:       "The number of events recorded by the aggregate_logs transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		aggregate_logs_flushes_total: {
+			description:       "The number of flushes done by the aggregate_logs transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		api_started_total: {
 			description:       "The number of times the Vector GraphQL API has been started."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		cardinality_limit_exceeded_total: {
+			description: """
+				The total number of events of new series exceeding the configured `series_limit` of
+				their metric.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				metric_name: _metric_name
+				action:      _limit_exceeded_action
+			}
+		}
+		cardinality_limit_reached_total: {
+			description:       "The total number of times a metric has reached the configured `series_limit`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		cardinality_limit_stripped_tags_total: {
+			description:       "The total number of tags stripped from new series exceeding the configured `series_limit`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				metric_name: _metric_name
+				tag_key: {
+					description: "The key of the stripped tag."
+					required:    true
+				}
+			}
+		}
+		cardinality_limit_tracked_series: {
+			description:       "The number of series currently counted against the limits of their metrics."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		config_load_errors_total: {
 			description:       "The total number of errors loading the Vector configuration."
 			type:              "counter"
@@ -88,37 +163,63 @@ components: sources: internal_metrics: {
 			description:       "The total number of connection errors for this Vector instance."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		connection_established_total: {
 			description:       "The total number of times a connection has been established."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		connection_failed_total: {
 			description:       "The total number of times a connection has failed."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		connection_send_errors_total: {
 			description:       "The total number of errors sending data via the connection."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		connection_send_ack_errors_total: {
 			description:       "The total number of protocol acknowledgement errors for this Vector instance for source protocols that support acknowledgements."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		connection_shutdown_total: {
 			description:       "The total number of times the connection has been shut down."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
+		}
+		connection_throttle_duration_seconds: {
+			description:       "The durations for which connections weren't read from, for being over `max_connection_bytes_per_sec`."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				mode: _mode
+			}
+		}
+		connections_queued_total: {
+			description:       "The total number of connections queued for being over a limit of connections."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				mode:  _mode
+				limit: _connection_limit
+			}
+		}
+		connections_rejected_total: {
+			description:       "The total number of connections closed for being over a limit of connections."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				mode:  _mode
+				limit: _connection_limit
+			}
 		}
 		quit_total: {
 			description:       "The total number of times the Vector instance has quit."
@@ -187,19 +288,19 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors writing checkpoints. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		checkpoints_total: {
 			description:       "The total number of files checkpointed."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		checksum_errors_total: {
 			description:       "The total number of errors identifying files via checksum."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -207,13 +308,13 @@ components: sources: internal_metrics: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		collect_duration_seconds: {
 			description:       "The duration spent collecting of metrics for this component."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		command_executed_total: {
 			description:       "The total number of times a command has been executed."
@@ -221,6 +322,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		command_restarts_total: {
+			description:       "The total number of times a streaming command has been restarted by the `exec` source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		command_execution_duration_seconds: {
 			description:       "The command execution duration in seconds."
 			type:              "histogram"
@@ -237,7 +344,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors reading datagram."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				mode: {
 					description: ""
 					required:    true
@@ -283,6 +390,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		dedupe_cache_hits_total: {
+			description:       "The total number of events found in the cache of a `dedupe` transform, and dropped as duplicates."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		dedupe_cache_misses_total: {
+			description:       "The total number of events not found in the cache of a `dedupe` transform, and let through."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		k8s_format_picker_edge_cases_total: {
 			description:       "The total number of edge cases encountered while picking format of the Kubernetes log message."
 			type:              "counter"
@@ -301,6 +420,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		k8s_pod_hint_parse_failures_total: {
+			description:       "The total number of failures to parse a message with the format hinted by its Kubernetes Pod."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		k8s_reflector_desyncs_total: {
 			description:       "The total number of desyncs for the reflector."
 			type:              "counter"
@@ -311,7 +436,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of state operations."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				op_kind: {
 					description: "The kind of operation performed."
 					required:    false
@@ -360,17 +485,59 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		late_events_total: {
+			description:       "The total number of events whose time period had already passed when they were partitioned, by the partition they were written to."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				partition: {
+					description: "The partition the events were written to."
+					required:    true
+					enum: {
+						current: "The partition of the current time period, as the events were older than the late event window."
+						past:    "The partition of the time period of the events."
+					}
+				}
+			}
+		}
+		ecs_unmapped_fields_total: {
+			description:       "The number of top-level fields of the events not part of the Elastic Common Schema, kept or removed by the ecs transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		elasticsearch_bulk_item_errors_total: {
+			description:       "The total number of documents of Elasticsearch bulk requests which failed, by the type of error returned for them and what became of them."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				error_code: {
+					description: "The type of the error returned by Elasticsearch, such as `mapper_parsing_exception`."
+					required:    true
+				}
+				outcome: {
+					description: "What became of the documents."
+					required:    true
+					enum: {
+						dead_lettered: "The documents were written to the dead letter index."
+						dropped:       "The documents were dropped."
+						retried:       "The documents were sent again."
+					}
+				}
+				status: _status
+			}
+		}
 		encode_errors_total: {
 			description:       "The total number of errors encountered when encoding an event."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		events_discarded_total: {
 			description:       "The total number of events discarded by this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				reason: _reason
 			}
 		}
@@ -399,7 +566,31 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {output: _output}
+			tags: _component_tags & {output: _output}
+		}
+		exec_events_sampled_out_total: {
+			description:       "The total number of events dropped by the `exec` source due to sampling."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		exec_heartbeats_missed_total: {
+			description:       "The total number of times a streaming command of the `exec` source was restarted for missing its heartbeats."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		exec_orphans_reaped_total: {
+			description:       "The total number of orphaned children of a previous run that were terminated by the `exec` source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		exec_unhealthy_reports_total: {
+			description:       "The total number of times a streaming command of the `exec` source was restarted for reporting itself as unhealthy."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
 		}
 		processed_events_total: {
 			description:       """
@@ -412,6 +603,22 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		bandwidth_throttle_duration_seconds: {
+			description:       "The durations the requests of a sink were delayed for by a bandwidth limit."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				limit: _bandwidth_limit
+			}
+		}
+		bandwidth_throttled: {
+			description:       "Whether the requests of a sink are currently delayed by a bandwidth limit, `1` if they are and `0` otherwise."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				limit: _bandwidth_limit
+			}
+		}
 		buffer_byte_size: {
 			description:       "The number of bytes current in the buffer."
 			type:              "gauge"
@@ -424,6 +631,60 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_compressed_bytes_total: {
+			description:       "The number of bytes of records written compressed by this disk buffer, after compression."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_compression_ratio: {
+			description:       "The ratio of the size of records before and after compression, for records recently written compressed by this disk buffer."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_reclaimed_bytes_total: {
+			description:       "The number of bytes freed by deleting data files whose records were all acknowledged."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_reclaimed_data_files_total: {
+			description:       "The number of data files deleted by this disk buffer once all of their records were acknowledged."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_uncompressed_bytes_total: {
+			description:       "The number of bytes of records written compressed by this disk buffer, before compression."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_drain_events_per_second: {
+			description:       "The rate at which events were drained out of the buffer, by being read or dropped, over the last 30 seconds."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_ingest_events_per_second: {
+			description:       "The rate at which events were written into the buffer over the last 30 seconds."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_oldest_event_age_seconds: {
+			description:       "The age of the oldest event in the buffer, at the 2 seconds resolution of the buffer reports. The events found in a disk buffer once Vector starts are aged from then on."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_time_to_drain_seconds: {
+			description:       "The estimated time for the buffer to drain, if events keep being written and drained at their current rates. It's infinite while the buffer isn't draining faster than events are written into it."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_discarded_events_total: {
 			description:       "The number of events dropped by this non-blocking buffer."
 			type:              "counter"
@@ -464,7 +725,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors encountered by this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				error_type: _error_type
 				stage:      _stage
 			}
@@ -482,7 +743,7 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				file: {
 					description: "The file from which the data originated."
 					required:    false
@@ -519,7 +780,7 @@ components: sources: internal_metrics: {
 				"""
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				file: {
 					description: "The file from which the data originated."
 					required:    false
@@ -560,7 +821,7 @@ components: sources: internal_metrics: {
 			description:       "The number of raw bytes sent by this component to destination sinks."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				endpoint: {
 					description: "The endpoint to which the bytes were sent. For HTTP, this will be the host and path only, excluding the query string."
 					required:    false
@@ -583,13 +844,13 @@ components: sources: internal_metrics: {
 			description:       "The total number of events emitted by this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {output: _output}
+			tags: _component_tags & {output: _output}
 		}
 		component_sent_event_bytes_total: {
 			description:       "The total number of event bytes emitted by this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {output: _output}
+			tags: _component_tags & {output: _output}
 		}
 		datadog_logs_received_in_total: {
 			description:       "Number of Datadog logs received."
@@ -603,6 +864,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		dlq_untracked_events_total: {
+			description:       "The number of events going into this sink which weren't tracked by `reroute_failed`, as too many of its events were pending."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		dropped_stale_events_total: {
+			description:       "The number of events dropped by this sink for being older than its maximum age."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		internal_logs_suppressed_total: {
+			description:       "The total number of internal logs suppressed by the rate limit."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		internal_metrics_cardinality: {
 			description:       "The total number of metrics emitted from the internal metrics registry."
 			type:              "gauge"
@@ -615,6 +894,33 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              internal_metrics_cardinality.tags
 		}
+		internal_metrics_collapsed_series: {
+			description:       "The number of series of a metric collapsed by the cardinality limit of the `internal_metrics` source, at its last scrape."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				metric_name: {
+					description: "The name of the metric whose series were collapsed."
+					required:    true
+				}
+				tag: {
+					description: "The tag whose values were collapsed."
+					required:    true
+				}
+			}
+		}
+		internal_traces_dropped_spans_total: {
+			description:       "The total number of spans of the internal traces dropped, as the export queue was full or their export failed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		internal_traces_export_errors_total: {
+			description:       "The total number of errors exporting the spans of the internal traces to the OTLP collector."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
 		kafka_queue_messages: {
 			description:       "Current number of messages in producer queues."
 			type:              "gauge"
@@ -679,7 +985,7 @@ components: sources: internal_metrics: {
 			description:       "The Kafka consumer lag."
 			type:              "gauge"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				topic_id: {
 					description: "The Kafka topic id."
 					required:    true
@@ -694,7 +1000,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of failures to delete a file. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -702,7 +1008,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors encountered when watching files. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -710,7 +1016,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of files Vector has found to watch."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -718,7 +1024,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of files deleted."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -726,7 +1032,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of times Vector has resumed watching a file."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -734,7 +1040,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of times Vector has stopped watching a file."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -742,7 +1048,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of times Vector failed to read a file for fingerprinting. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				file: _file
 			}
 		}
@@ -750,7 +1056,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors encountered when globbing paths. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags & {
+			tags:              _component_tags & {
 				path: _path
 			}
 		}
@@ -758,13 +1064,13 @@ components: sources: internal_metrics: {
 			description:       "The total number of HTTP `400 Bad Request` errors encountered."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		http_client_response_rtt_seconds: {
 			description:       "The round-trip time (RTT) of HTTP requests, tagged with the response code."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				status: _status
 			}
 		}
@@ -772,7 +1078,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of HTTP requests, tagged with the response code."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				status: _status
 			}
 		}
@@ -786,13 +1092,13 @@ components: sources: internal_metrics: {
 			description:       "The total number of HTTP error responses for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		http_request_errors_total: {
 			description:       "The total number of HTTP request errors for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		http_requests_total: {
 			description:       "The total number of HTTP requests issued by this component."
@@ -825,6 +1131,24 @@ components: sources: internal_metrics: {
 			description:       "The total memory currently being used by the Lua runtime."
 			type:              "gauge"
 			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		memory_budget_throttle_duration_seconds: {
+			description:       "The time a source was held back because the memory budget was used up."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		memory_budget_throttled_total: {
+			description:       "The total number of times a source was held back because the memory budget was used up."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		memory_budget_used_bytes: {
+			description:       "The number of bytes of the events in flight, counted against the `memory_budget`."
+			type:              "gauge"
+			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
 		metadata_refresh_failed_total: {
@@ -839,23 +1163,58 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		multiline_flushed_lines_total: {
+			description:       "The total number of lines flushed by multi-line aggregation, by the reason they were flushed for."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				reason: {
+					description: "The reason the aggregated lines were flushed."
+					required:    true
+					enum: {
+						match:    "A line matched the condition ending the aggregated message."
+						rotation: "The file the lines were read from was rotated."
+						shutdown: "The source was shut down."
+						timeout:  "No line was added to the aggregated message before the timeout."
+					}
+				}
+			}
+		}
 		open_connections: {
 			description:       "The number of current open connections to Vector."
 			type:              "gauge"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
+		}
+		ordering_lane_depth: {
+			description:       "The number of requests queued in the lane of a request, including itself, when a sink preserving the order of events by a field sends it."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		ordering_lanes: {
+			description:       "The number of lanes a sink preserving the order of events by a field currently has requests in flight for."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
 		}
 		parse_errors_total: {
 			description:       "The total number of errors parsing metrics for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
+		}
+		pass_through_active: {
+			description:       "Whether this source relays the frames it receives without decoding them, as all its consumers are sinks writing them as they are: `1` if it does, `0` otherwise."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
 		}
 		processed_bytes_total: {
 			description:       "The number of bytes processed by the component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				file: {
 					description: "The file from which the bytes originate."
 					required:    false
@@ -887,7 +1246,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of processing errors encountered by this component. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				error_type: _error_type
 			}
 		}
@@ -897,17 +1256,46 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		remap_aborts_total: {
+			description:       "The total number of events whose mapping was aborted by the `remap` transform's program."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		remap_errors_total: {
+			description:       "The total number of events whose mapping failed with a runtime error of the `remap` transform's program."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				error_code: {
+					description: "The code of the VRL error."
+					required:    true
+					examples: ["E000"]
+				}
+				function: {
+					description: "The function whose call raised the error, or `none` if the error wasn't raised by a function call."
+					required:    true
+					examples: ["parse_json", "none"]
+				}
+			}
+		}
+		remap_execution_duration_seconds: {
+			description:       "The durations of the runs of the `remap` transform's program, one per event."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		request_errors_total: {
 			description:       "The total number of requests errors for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		request_duration_seconds: {
 			description:       "The total request duration in seconds."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags
 		}
 		request_read_errors_total: {
 			description:       "The total number of request read errors for this component."
@@ -945,12 +1333,41 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		splunk_ack_duration_seconds: {
+			description:       "The time from sending events to Splunk HEC to their indexer acknowledgement being confirmed, rejected, or expired."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		splunk_pending_acks: {
 			description:       "The number of outstanding Splunk HEC indexer acknowledgement acks."
 			type:              "gauge"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		splunk_pending_acks_oldest_age_seconds: {
+			description:       "The age of the oldest outstanding Splunk HEC indexer acknowledgement ack, as of its last query."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		stream_queued_requests: {
+			description:       "The number of requests of a logical stream of the `vector` sink which are queued or in flight."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				stream: {
+					description: "The logical stream of the requests."
+					required:    true
+					enum: {
+						all:     "All of the events, when the streams aren't configured."
+						logs:    "The logs."
+						metrics: "The metrics."
+						traces:  "The traces."
+					}
+				}
+			}
+		}
 		streams_total: {
 			description:       "The total number of streams."
 			type:              "counter"
@@ -1035,12 +1452,59 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tail_sample_groups_dropped_total: {
+			description:       "The number of groups of events dropped by the tail_sample transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		tail_sample_groups_kept_total: {
+			description:       "The number of groups of events kept by the tail_sample transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		tenant_buffered_bytes: {
+			description: """
+				The number of bytes of the events of a tenant currently held in memory across the
+				topology, counted towards its `max_buffer_bytes` quota.
+				"""
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				tenant: _tenant
+			}
+		}
+		tenant_throttle_duration_seconds: {
+			description:       "The time a source was held back because its tenant exceeded one of its quotas."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				tenant: _tenant
+				reason: _tenant_throttle_reason
+			}
+		}
+		tenant_throttled_total: {
+			description:       "The total number of times a source was held back because its tenant exceeded one of its quotas."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				tenant: _tenant
+				reason: _tenant_throttle_reason
+			}
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tls_reloads_total: {
+			description:       "The number of times the TLS certificates of a component were reloaded, after their files changed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
 			type:              "gauge"
@@ -1051,7 +1515,7 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors converting bytes to a UTF-8 string in UDP mode."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags & {
+			tags: _component_tags & {
 				mode: {
 					description: "The connection mode used by the component."
 					required:    true
@@ -1061,6 +1525,12 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		sample_rate: {
+			description:       "The rate a `sample` transform adapting to a target number of events per second currently samples at, expressed as `1/N`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		utilization: {
 			description:       "A ratio from 0 to 1 of the load on a component. A value of 0 would indicate a completely idle component that is simply waiting for input. A value of 1 would indicate a that is never idle. This value is updated every 5 seconds."
 			type:              "gauge"
@@ -1105,6 +1575,51 @@ components: sources: internal_metrics: {
 		}
 
 		// Windows metrics
+		wasm_guest_counter_total: {
+			description:       "The counters incremented by the WebAssembly module of a wasm transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				name: {
+					description: "The name given to the counter by the module."
+					required:    true
+				}
+			}
+		}
+		wasm_guest_gauge: {
+			description:       "The gauges set by the WebAssembly module of a wasm transform."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				name: {
+					description: "The name given to the gauge by the module."
+					required:    true
+				}
+			}
+		}
+		wasm_module_reloads_total: {
+			description:       "The number of times the WebAssembly module of a wasm transform was reloaded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		webhook_signature_verification_failures_total: {
+			description:       "The number of webhooks rejected by the `webhook_receiver` source as their signature failed the verification."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				reason: {
+					description: "Why the verification failed."
+					required:    true
+					enum: {
+						"expired_timestamp":   "The signed timestamp is too far from the current time."
+						"invalid_signature":   "The signature doesn't match the request."
+						"malformed_signature": "The signature couldn't be parsed."
+						"missing_signature":   "The request isn't signed."
+					}
+				}
+			}
+		}
 		windows_service_does_not_exist_total: {
 			description: """
 				The total number of errors raised due to the Windows service not
@@ -1235,6 +1750,27 @@ components: sources: internal_metrics: {
 			required:    true
 			examples: [_values.local_host]
 		}
+		_connection_limit: {
+			description: "The limit of connections the connection was over."
+			required:    true
+			enum: {
+				connection_limit:        "The maximum number of connections of the component."
+				connection_limit_per_ip: "The maximum number of connections from the IP address of the client."
+			}
+		}
+		_limit_exceeded_action: {
+			description: "The action taken on the new series."
+			required:    true
+			enum: {
+				aggregate:   "The series was aggregated into the overflow series."
+				drop_series: "The event was dropped."
+				strip_tags:  "Tags were stripped from the series."
+			}
+		}
+		_metric_name: {
+			description: "The name of the limited metric, prefixed by its namespace."
+			required:    true
+		}
 		_mode: {
 			description: "The connection mode used by the component."
 			required:    false
@@ -138,6 +138,25 @@ components: sources: journald: {
 				[issue #1473](\(urls.vector_issues)/1473).
 				"""
 		}
+		filtering: {
+			title: "Filtering"
+			body: """
+				Journal entries can be filtered on any journal field with the `include_matches`
+				and `exclude_matches` options. An entry is read if it matches any of the
+				`include_matches` (or if that option is empty) and none of the `exclude_matches`.
+				The `include_units`, `exclude_units`, and `exclude_syslog_identifiers` options are
+				shorthands that are merged into these matches on the `_SYSTEMD_UNIT` and
+				`SYSLOG_IDENTIFIER` fields.
+
+				Matches on the `PRIORITY` field can be given as an inclusive range, either
+				numeric or by name, so `exclude_matches.PRIORITY = ["info..debug"]` drops
+				informational and debug entries.
+
+				When [end-to-end acknowledgements](/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/) are enabled, the journal
+				cursor is only checkpointed once all the entries up to it have been acknowledged
+				by the sinks, so entries are read again after a restart if they were not delivered.
+				"""
+		}
 		non_ascii: {
 			title: "Non-ASCII Messages"
 			body: """
@@ -0,0 +1,151 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the events recorded in the Kubernetes API, such as Pods being scheduled, failing
+		their probes, or being evicted.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.kubernetes
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+		]
+		warnings: [
+			"""
+				Each instance of this source emits all the events of the cluster, so it should only be
+				run once, rather than on every Node.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: base.components.sources.kubernetes_events.configuration
+
+	output: logs: event: {
+		description: """
+			An individual Kubernetes event, with the fields of the [Event][event] object, except
+			`metadata.managedFields`.
+
+			[event]: https://kubernetes.io/docs/reference/kubernetes-api/cluster-resources/event-v1/
+			"""
+		fields: {
+			metadata: {
+				description: "The metadata of the event, such as its `name`, `namespace` and `uid`."
+				required:    true
+				type: object: {}
+			}
+			involvedObject: {
+				description: "The object the event is about."
+				required:    true
+				type: object: {}
+			}
+			reason: {
+				description: "Why the event was recorded, in a machine readable form."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["BackOff", "Scheduled"]
+				}
+			}
+			message: {
+				description: "A human readable description of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Back-off restarting failed container"]
+				}
+			}
+			type: {
+				description: "The type of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Normal", "Warning"]
+				}
+			}
+			timestamp: {
+				description: """
+					The last time the event was observed, or the time it was created if it wasn't
+					observed.
+					"""
+				required: true
+				type: timestamp: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["kubernetes_events"]
+			}
+		}
+	}
+
+	how_it_works: {
+		deduplication: {
+			title: "Deduplication"
+			body: """
+				The events are listed again every time the watch of the Events API is restarted, as
+				well as when Vector is. To only emit each event once, the versions of the events that
+				were emitted are persisted in the `data_dir`, and an event is only emitted again when it
+				is updated, such as when it recurs and its count is incremented.
+
+				The events are only kept by the API server for an hour by default, so on its first run
+				the source emits the events of the last hour. Use `ignore_older_secs` to emit fewer of
+				them.
+				"""
+		}
+		kubernetes_api_access_control: {
+			title: "Kubernetes API access control"
+			body: """
+				Vector needs the permission to `list` and `watch` the `events` resource, in every
+				watched Namespace or, if `namespaces` isn't set, cluster-wide, for instance with a
+				ClusterRole bound to its ServiceAccount.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -340,6 +340,33 @@ components: sources: kubernetes_logs: {
 					"""
 		}
 
+		pod_parsing_hints: {
+			title: "Pod parsing hints"
+			body:  """
+					Pods can opt into parsing their own logs by adding **annotations**
+					that the [`kubernetes_logs` source](\(urls.vector_kubernetes_logs_source))
+					applies before emitting events:
+
+					```yaml
+					vector.dev/parse: "json"
+					vector.dev/multiline-pattern: '^\\d{4}-\\d{2}-\\d{2}'
+					```
+
+					The `vector.dev/multiline-pattern` annotation is a regular expression
+					matching the first line of each message. The following lines that don't
+					match it, such as the lines of a stack trace, are appended to the message,
+					separated by newlines, until a line matches or `pod_hints.multiline_timeout_ms`
+					elapses.
+
+					The `vector.dev/parse` annotation selects the format of the messages. Only
+					`json` is supported: the fields of messages that are JSON objects replace the
+					message, while other messages are left as-is.
+
+					Invalid annotations are ignored, and reported as errors. The hints can be
+					disabled with `pod_hints.enabled`.
+					"""
+		}
+
 		kubernetes_api_communication: {
 			title: "Kubernetes API communication"
 			body:  """
@@ -469,6 +496,7 @@ components: sources: kubernetes_logs: {
 		k8s_format_picker_edge_cases_total:     components.sources.internal_metrics.output.metrics.k8s_format_picker_edge_cases_total
 		k8s_docker_format_parse_failures_total: components.sources.internal_metrics.output.metrics.k8s_docker_format_parse_failures_total
 		k8s_event_annotation_failures_total:    components.sources.internal_metrics.output.metrics.k8s_event_annotation_failures_total
+		k8s_pod_hint_parse_failures_total:      components.sources.internal_metrics.output.metrics.k8s_pod_hint_parse_failures_total
 		k8s_reflector_desyncs_total:            components.sources.internal_metrics.output.metrics.k8s_reflector_desyncs_total
 		k8s_state_ops_total:                    components.sources.internal_metrics.output.metrics.k8s_state_ops_total
 		k8s_stream_chunks_processed_total:      components.sources.internal_metrics.output.metrics.k8s_stream_chunks_processed_total
@@ -0,0 +1,88 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: components._mqtt.features.collect.from
+			tls:  components._mqtt.features.tls
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator", "sidecar"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._mqtt.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.mqtt.configuration
+
+	output: logs: record: {
+		description: "An individual MQTT message."
+		fields: {
+			message: {
+				description: "The payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["{\"temperature\": 21.5}"]
+					syntax: "literal"
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["mqtt"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The current time when the message was received."
+			}
+			topic: {
+				description: "The topic the message was published to."
+				required:    true
+				type: string: {
+					examples: ["sensors/kitchen/temperature"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: components._mqtt.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body:  """
+				With acknowledgements enabled, QoS 1 messages are only acknowledged to the broker
+				once their events are delivered by all the connected sinks, in the order the messages
+				were received. Messages whose events fail to be delivered are left unacknowledged, so
+				that the broker delivers them again when the session is resumed.
+				"""
+		}
+	}
+}
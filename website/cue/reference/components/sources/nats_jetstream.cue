@@ -0,0 +1,97 @@
+package metadata
+
+components: sources: nats_jetstream: {
+	title: "NATS JetStream"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: components._nats.features.collect.from
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	support: components._nats.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.nats_jetstream.configuration
+
+	output: logs: record: {
+		description: "An individual NATS JetStream message."
+		fields: {
+			message: {
+				description: "The raw line from the NATS message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["nats_jetstream"]
+				}
+			}
+			subject: {
+				description: "The subject from the NATS message."
+				required:    true
+				type: string: {
+					examples: ["events.us.east"]
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: components._nats.how_it_works & {
+		durable_consumers: {
+			title: "Durable consumers"
+			body:  """
+				The messages of the stream are pulled in batches by the `durable_name` consumer, which is
+				created with explicit acknowledgements if it doesn't exist yet.
+
+				Each message is acknowledged once its events are delivered, or right away unless
+				acknowledgements are enabled. The messages whose events errored are negatively
+				acknowledged, for the server to redeliver them, while the ones whose events were
+				rejected are terminated, for the server to never redeliver them. The messages which
+				aren't acknowledged within `ack_wait_secs`, such as when Vector stops, are redelivered.
+				"""
+		}
+	}
+}
@@ -0,0 +1,165 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	description: """
+		Listens for the NetFlow v5, NetFlow v9, IPFIX and sFlow v5 datagrams of flow exporters, and
+		emits each of their flow records as a structured log event.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+
+				interface: socket: {
+					api: {
+						title: "NetFlow"
+						url:   urls.netflow
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Flow datagrams are not authenticated, and can be forged by anyone able to send packets to
+				Vector.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.netflow.configuration
+
+	output: logs: flow: {
+		description: """
+			A flow record. Besides the fields listed here, the fields of the records are named after the
+			[IPFIX information elements](\(urls.ipfix_information_elements)) in snake case, such as
+			`source_ipv4_address`, `octet_delta_count` or `flow_start_milliseconds`.
+			"""
+		fields: {
+			agent_address: {
+				description: "The address of the sFlow agent which sampled the flow."
+				required:    false
+				type: string: examples: ["192.0.2.254"]
+			}
+			export_time: {
+				description: "The time the datagram was exported at."
+				required:    false
+				type: timestamp: {}
+			}
+			flow_protocol: {
+				description: "The protocol the flow was exported with."
+				required:    true
+				type: string: enum: {
+					ipfix:      "IPFIX."
+					netflow_v5: "NetFlow version 5."
+					netflow_v9: "NetFlow version 9."
+					sflow:      "sFlow version 5."
+				}
+			}
+			host: {
+				description: "The IP address the datagram was received from."
+				required:    true
+				type: string: examples: ["192.0.2.254"]
+			}
+			observation_domain_id: {
+				description: "The observation domain of the IPFIX records, or the source ID of the NetFlow v9 records."
+				required:    false
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			record_type: {
+				description: "The type of the record."
+				required:    false
+				type: string: enum: {
+					flow:    "A flow record."
+					options: "An options record, such as the sampling configuration of an exporter."
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the datagram."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["netflow"]
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		templates: {
+			title: "Templates"
+			body: """
+				The NetFlow v9 and IPFIX records are described by the templates which the exporters send
+				beforehand. The templates are cached per exporter address and port, and per observation domain,
+				until they are updated, withdrawn, or not received again within `template_timeout_secs`. The
+				records of unknown templates are dropped, and an error is reported.
+				"""
+		}
+		fields: {
+			title: "Fields"
+			body: """
+				The fields of the records are named after the [IPFIX information
+				elements](\(urls.ipfix_information_elements)), whatever the protocol they were exported with.
+				The enterprise-specific fields are named `enterprise_<number>_<id>`, and the unknown fields
+				`field_<id>`. The addresses are formatted as text, the times as timestamps, and the other fields
+				as numbers, or as hexadecimal strings when they are longer than eight bytes. The NetFlow v5 and v9
+				times relative to the uptime of the exporters are converted to timestamps.
+				"""
+		}
+		sflow: {
+			title: "sFlow"
+			body: """
+				The flow samples of the sFlow datagrams are emitted as records, with the Ethernet, IP and
+				TCP or UDP headers of their sampled packet decoded into the same fields as the other protocols.
+				The counter samples are skipped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
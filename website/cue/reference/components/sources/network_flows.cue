@@ -0,0 +1,287 @@
+package metadata
+
+components: sources: network_flows: {
+	title: "Network Flows"
+
+	description: """
+		Collects a summary of each network connection of the local system, including the process which
+		opened it and, for TCP connections, the bytes and packets it transferred.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.host
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"x86_64-apple-darwin":   false
+			"x86_64-pc-windows-msv": false
+		}
+
+		requirements: [
+			"""
+				Resolving the processes of connections opened by other users requires Vector to run as root, or
+				with the `CAP_SYS_PTRACE` capability. Set `include_processes` to `false` otherwise.
+				""",
+		]
+		warnings: [
+			"""
+				Each connection is reported individually, so metrics emitted with `event_type` set to `metric` have a
+				high cardinality on hosts handling many short lived connections.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	env_vars: {
+		PROCFS_ROOT: {
+			description: "Sets an arbitrary path to the system's Procfs root, unless `procfs_root` is set. Can be used to collect the flows of the host from within a container. Unset and uses system `/proc` by default."
+			type: string: {
+				default: null
+				examples: ["/mnt/host/proc"]
+			}
+		}
+	}
+
+	configuration: base.components.sources.network_flows.configuration
+
+	output: {
+		logs: flow: {
+			description: "A network flow, emitted when `event_type` is `log`."
+			fields: {
+				bytes_received: {
+					description: "The number of bytes received on the flow since it was opened. Only available for TCP flows read through `sock_diag`."
+					required:    false
+					type: uint: {
+						examples: [5678]
+						unit: "bytes"
+					}
+				}
+				bytes_sent: {
+					description: "The number of bytes sent on the flow and acknowledged by the remote endpoint since it was opened. Only available for TCP flows read through `sock_diag`."
+					required:    false
+					type: uint: {
+						examples: [1234]
+						unit: "bytes"
+					}
+				}
+				host: fields._local_host
+				local_address: {
+					description: "The local IP address of the flow."
+					required:    true
+					type: string: examples: ["10.0.0.1", "::1"]
+				}
+				local_port: {
+					description: "The local port of the flow."
+					required:    true
+					type: uint: {
+						examples: [42166]
+						unit: null
+					}
+				}
+				packets_received: {
+					description: "The number of packets received on the flow since it was opened. Only available for TCP flows read through `sock_diag`."
+					required:    false
+					type: uint: {
+						examples: [34]
+						unit: null
+					}
+				}
+				packets_sent: {
+					description: "The number of packets sent on the flow since it was opened. Only available for TCP flows read through `sock_diag`."
+					required:    false
+					type: uint: {
+						examples: [12]
+						unit: null
+					}
+				}
+				process_id: {
+					description: "The ID of the process which opened the flow, if `include_processes` is enabled and it could be resolved."
+					required:    false
+					type: uint: {
+						examples: [4321]
+						unit: null
+					}
+				}
+				process_name: {
+					description: "The name of the process which opened the flow, if `include_processes` is enabled and it could be resolved."
+					required:    false
+					type: string: examples: ["curl", "nginx"]
+				}
+				protocol: {
+					description: "The transport protocol of the flow."
+					required:    true
+					type: string: examples: ["tcp", "udp"]
+				}
+				receive_queue: {
+					description: "The number of bytes received on the flow which are not yet read by the process."
+					required:    true
+					type: uint: {
+						examples: [0]
+						unit: "bytes"
+					}
+				}
+				remote_address: {
+					description: "The remote IP address of the flow."
+					required:    true
+					type: string: examples: ["10.0.0.2", "2001:db8::1"]
+				}
+				remote_port: {
+					description: "The remote port of the flow."
+					required:    true
+					type: uint: {
+						examples: [443]
+						unit: null
+					}
+				}
+				send_queue: {
+					description: "The number of bytes sent on the flow which are not yet acknowledged by the remote endpoint."
+					required:    true
+					type: uint: {
+						examples: [0]
+						unit: "bytes"
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: examples: ["network_flows"]
+				}
+				state: {
+					description: "The state of the flow, as named by the kernel."
+					required:    false
+					type: string: examples: ["established", "time_wait", "close_wait"]
+				}
+				timestamp: fields._current_timestamp
+				uid: {
+					description: "The ID of the user owning the socket of the flow."
+					required:    true
+					type: uint: {
+						examples: [1000]
+						unit: null
+					}
+				}
+			}
+		}
+
+		metrics: {
+			_flow_tags: {
+				host: {
+					description: "The hostname of the originating system."
+					required:    true
+					examples: [_values.local_host]
+				}
+				local_address: {
+					description: "The local IP address of the flow."
+					required:    true
+					examples: ["10.0.0.1"]
+				}
+				local_port: {
+					description: "The local port of the flow."
+					required:    true
+					examples: ["42166"]
+				}
+				process_id: {
+					description: "The ID of the process which opened the flow."
+					required:    false
+					examples: ["4321"]
+				}
+				process_name: {
+					description: "The name of the process which opened the flow."
+					required:    false
+					examples: ["curl"]
+				}
+				protocol: {
+					description: "The transport protocol of the flow."
+					required:    true
+					examples: ["tcp", "udp"]
+				}
+				remote_address: {
+					description: "The remote IP address of the flow."
+					required:    true
+					examples: ["10.0.0.2"]
+				}
+				remote_port: {
+					description: "The remote port of the flow."
+					required:    true
+					examples: ["443"]
+				}
+				state: {
+					description: "The state of the flow, as named by the kernel."
+					required:    false
+					examples: ["established"]
+				}
+			}
+
+			_flow_counter: {
+				default_namespace: "network_flows"
+				relevant_when:     "The flow is a TCP flow read through `sock_diag`"
+				type:              "counter"
+				tags:              _flow_tags
+			}
+			_flow_gauge: {
+				default_namespace: "network_flows"
+				type:              "gauge"
+				tags:              _flow_tags
+			}
+
+			bytes_received_total:   _flow_counter & {description: "The number of bytes received on the flow since it was opened."}
+			bytes_sent_total:       _flow_counter & {description: "The number of bytes sent on the flow and acknowledged since it was opened."}
+			packets_received_total: _flow_counter & {description: "The number of packets received on the flow since it was opened."}
+			packets_sent_total:     _flow_counter & {description: "The number of packets sent on the flow since it was opened."}
+			receive_queue_bytes:    _flow_gauge & {description:   "The number of bytes received on the flow which are not yet read by the process."}
+			send_queue_bytes:       _flow_gauge & {description:   "The number of bytes sent on the flow which are not yet acknowledged."}
+		}
+	}
+
+	how_it_works: {
+		collectors: {
+			title: "Collectors"
+			body: """
+				By default, flows are read through the `sock_diag` netlink interface of the kernel, which
+				provides the byte and packet counters of TCP connections as accounted by the kernel, without
+				capturing any traffic or loading any eBPF program. If a netlink socket cannot be opened, for
+				example because of a seccomp profile, the source falls back to polling the connection tables
+				of procfs, which list the same connections without their counters.
+
+				Only connected sockets are reported: listening TCP sockets and unconnected UDP sockets are
+				skipped. UDP sockets do not have byte or packet counters with either collector.
+				"""
+		}
+		counters: {
+			title: "Counters"
+			body: """
+				The counters of a flow are cumulative since the connection was opened, and are emitted as
+				absolute metrics. Transfers of connections which open and close between two collections are
+				not reported, so `scrape_interval_secs` should be lower than the lifetime of the connections
+				of interest.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -41,7 +41,7 @@ components: sources: opentelemetry: {
 		requirements: []
 		warnings: [
 			"""
-				The `opentelemetry` source only supports log events at this time.
+				The `opentelemetry` source only supports log and trace events at this time.
 				""",
 		]
 		notices: []
@@ -60,6 +60,12 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "traces"
+			description: """
+				Received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -192,6 +198,31 @@ components: sources: opentelemetry: {
 				}
 			}
 		}
+		traces: event: {
+			description: "The spans of a single trace from a batch of spans received through an OTLP request"
+			fields: {
+				spans: {
+					description: "The list of spans composing the trace, in the shape used by the `datadog_agent` source."
+					required:    true
+					type: array: items: type: object: options: {}
+				}
+				resources: {
+					description: "Set of attributes that identify the entity that produced the spans."
+					required:    false
+					type: object: {
+						examples: [{"service.name": "api"}]
+						options: {}
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["opentelemetry"]
+					}
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
@@ -204,6 +235,27 @@ components: sources: opentelemetry: {
 	}
 
 	how_it_works: {
+		traces: {
+			title: "Traces"
+			body: """
+				Spans received on the `/v1/traces` HTTP path or through the gRPC trace service are
+				grouped by trace id into trace events, and converted to the span model used by the
+				`datadog_agent` source so that they can be sent to any sink accepting traces:
+
+				* The `service.name` resource attribute becomes the `service` of the spans.
+				* The span name becomes both the `name` and the `resource` of the spans.
+				* The lower 64 bits of the trace and span ids become the `trace_id`, `span_id`, and
+				  `parent_id` of the spans, while the full trace id is kept in `meta."otel.trace_id"`.
+				* Numeric attributes are stored in `metrics`, and the other ones in `meta`, along with
+				  the span kind, status, and instrumentation scope.
+				* Well-known resource attributes, such as `deployment.environment` or `host.name`, are
+				  copied to the corresponding fields of the trace.
+				* The spans whose parent isn't part of the trace are marked as top-level with the
+				  `metrics._top_level` metric, as the Datadog Agent does.
+
+				Span events and links are not retained.
+				"""
+		}
 		tls: {
 			title: "Transport Layer Security (TLS)"
 			body:  """
@@ -0,0 +1,155 @@
+package metadata
+
+components: sources: postgres_cdc: {
+	title: "PostgreSQL CDC"
+
+	description: """
+		Reads the changes made to the tables of a [PostgreSQL](\(urls.postgresql)) database, from a
+		logical replication slot.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: {
+					name:     "PostgreSQL Server"
+					thing:    "a \(name)"
+					url:      urls.postgresql
+					versions: ">= 11"
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The server must have `wal_level` set to `logical`, and the user must have the
+				`REPLICATION` attribute.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.postgres_cdc.configuration
+
+	output: logs: record: {
+		description: "An individual change made to a table."
+		fields: {
+			op: {
+				description: "The kind of change."
+				required:    true
+				type: string: enum: {
+					insert:   "A row was inserted."
+					update:   "A row was updated."
+					delete:   "A row was deleted."
+					truncate: "The table was truncated."
+				}
+			}
+			schema: {
+				description: "The schema of the changed table."
+				required:    true
+				type: string: examples: ["public"]
+			}
+			table: {
+				description: "The name of the changed table."
+				required:    true
+				type: string: examples: ["users"]
+			}
+			before: {
+				description: """
+					The values of the row before an update or a delete. Only the columns of the
+					replica identity of the table are included.
+					"""
+				required: false
+				common:   true
+				type: object: {}
+			}
+			after: {
+				description: "The values of the row after an insert or an update."
+				required:    false
+				common:      true
+				type: object: {}
+			}
+			lsn: {
+				description: "The log sequence number of the change."
+				required:    true
+				type: string: examples: ["0/16B3748"]
+			}
+			xid: {
+				description: "The ID of the transaction the change was made in."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					unit:    null
+				}
+			}
+			timestamp: {
+				description: "The time the transaction of the change was committed."
+				required:    true
+				type: timestamp: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["postgres_cdc"]
+			}
+		}
+	}
+
+	how_it_works: {
+		replication_slots: {
+			title: "Replication slots"
+			body: """
+				The changes are read from the logical replication slot named by `slot`, which is
+				created if it doesn't exist and `create_slot` is enabled. The slot is advanced past the
+				changes once their events are sent or, if acknowledgements are enabled, once they're
+				delivered, so the changes that weren't delivered are read again when Vector restarts.
+
+				A slot keeps the write-ahead log of the server from being removed until its changes are
+				read, so slots that are no longer used should be dropped.
+				"""
+		}
+		replica_identity: {
+			title: "Replica identity"
+			body: """
+				The values of a row before an update or a delete are only known for the columns of the
+				replica identity of the table, its primary key by default. To get all the values, set
+				the replica identity of the table to `FULL`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -58,6 +58,25 @@ components: sources: prometheus_scrape: {
 	}
 
 	how_it_works: {
+		discovery: {
+			title: "Service discovery"
+			body: """
+				Endpoints can be discovered from DNS records, or from JSON files in the format of the
+				`file_sd_configs` of Prometheus, and are refreshed at the `refresh_interval_secs` of each
+				discovery mechanism. When a discovery fails, the endpoints it previously discovered keep
+				being scraped.
+
+				Like in Prometheus, each target is discovered with labels: `__address__` is its
+				`host:port`, and `__scheme__` and `__metrics_path__` override the `scheme` and `path` of
+				its endpoint. DNS discovery adds the `__meta_dns_name` label, as well as
+				`__meta_dns_srv_record_target` and `__meta_dns_srv_record_port` for `SRV` records, and
+				file discovery adds the `__meta_filepath` label.
+
+				The `relabel` rules filter the targets with these labels, and the labels of the kept
+				targets that don't start with `__`, such as the `labels` of the files, are added as tags
+				to their metrics, following `honor_labels`.
+				"""
+		}
 		duplicate_tag_names: {
 			title: "Duplicate tag names"
 			body: """
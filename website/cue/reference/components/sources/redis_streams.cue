@@ -0,0 +1,127 @@
+package metadata
+
+components: sources: redis_streams: {
+	title: "Redis Streams"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			tls: enabled:        false
+			from: {
+				service: services.redis
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      6379
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.redis_streams.configuration
+
+	output: logs: record: {
+		description: "An individual entry of the Redis stream"
+		fields: {
+			message:   fields._raw_line
+			timestamp: fields._current_timestamp
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["redis_streams"]
+				}
+			}
+			redis_key: {
+				description: "The key of the stream the event came from"
+				required:    false
+				common:      false
+				type: string: {
+					examples: ["some_stream"]
+					default: null
+				}
+			}
+			redis_id: {
+				description: "The ID of the stream entry the event came from"
+				required:    false
+				common:      false
+				type: string: {
+					examples: ["1526919030474-55"]
+					default: null
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		consumer_groups: {
+			title: "Consumer groups"
+			body: """
+				The `redis_streams` source reads the stream with `XREADGROUP`, as the consumer named by
+				`consumer` in the group named by `group`, which is created if it doesn't exist. Several
+				Vector instances can share the entries of a stream by using the same group with
+				different consumer names.
+
+				Entries are acknowledged with `XACK` once their events are sent or, if
+				acknowledgements are enabled, once they're delivered. When Vector starts, the entries
+				that were delivered to the consumer but not acknowledged are read again.
+				"""
+		}
+		pending_entries: {
+			title: "Pending entries"
+			body: """
+				Every `claim_interval_secs`, the entries of the group that have been pending for longer
+				than `claim_min_idle_secs` are claimed with `XCLAIM`, and read again. This recovers the
+				entries of consumers that stopped, and retries the entries whose events couldn't be
+				delivered.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
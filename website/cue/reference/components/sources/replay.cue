@@ -0,0 +1,91 @@
+package metadata
+
+components: sources: replay: {
+	title: "Replay"
+
+	description: """
+		Replays the raw input recorded from a source with its `record` option, at the pace it was
+		recorded at or faster.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "`bytes`, as the frames were recorded after being framed"
+		}
+		generate: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.replay.configuration
+
+	output: {
+		logs: line: {
+			description: "An event decoded from a recorded frame."
+			fields: {
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["replay"]
+					}
+				}
+				timestamp: {
+					description: "The time the frame was read by the recorded source."
+					required:    true
+					type: timestamp: {}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		recording: {
+			title: "Recording"
+			body: """
+				The `record` option of a source writes each frame it reads, before decoding it, along with
+				the time it was read, to a file. Only the sources decoding their input with a `decoding`
+				option are recorded. The metadata of the input, such as the host it was received from, isn't
+				recorded.
+				"""
+		}
+		replaying: {
+			title: "Replaying"
+			body: """
+				The frames are decoded with the `decoding` option, and sent as long after the first one as
+				they were recorded, divided by the `speed` option. The `vector replay` command runs a
+				configuration with its recorded sources replaced by this source, decoding the frames like
+				the replaced source does, and exits once they're all replayed.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
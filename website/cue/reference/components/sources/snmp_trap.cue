@@ -0,0 +1,197 @@
+package metadata
+
+components: sources: snmp_trap: {
+	_port: 162
+
+	title: "SNMP Trap"
+
+	description: """
+		Listens for the SNMPv1, SNMPv2c and SNMPv3 traps of network devices, resolves their object
+		identifiers to names with the MIB files given, and emits each trap as a structured log event.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+
+				interface: socket: {
+					api: {
+						title: "SNMP"
+						url:   urls.snmp
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				Listening on the default port 162 requires Vector to run as root, or with the
+				`CAP_NET_BIND_SERVICE` capability.
+				""",
+		]
+		warnings: [
+			"""
+				The SNMPv1 and SNMPv2c traps are not authenticated: their communities are sent in clear text, and
+				can be forged by anyone able to send packets to Vector.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.snmp_trap.configuration
+
+	output: logs: trap: {
+		description: "An SNMP trap, or inform request."
+		fields: {
+			agent_address: {
+				description: "The address of the agent which sent the trap, as carried by SNMPv1 traps."
+				required:    false
+				type: string: examples: ["192.0.2.1"]
+			}
+			community: {
+				description: "The community of the SNMPv1 and SNMPv2c traps."
+				required:    false
+				type: string: examples: ["public"]
+			}
+			context_name: {
+				description: "The context name of the SNMPv3 traps."
+				required:    false
+				type: string: examples: [""]
+			}
+			engine_id: {
+				description: "The hexadecimal ID of the engine which sent the SNMPv3 traps."
+				required:    false
+				type: string: examples: ["80001f8804766563746f72"]
+			}
+			host: {
+				description: "The IP address the trap was received from."
+				required:    true
+				type: string: examples: ["192.0.2.1"]
+			}
+			inform: {
+				description: "Whether the trap is an inform request, which is acknowledged."
+				required:    true
+				type: bool: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["snmp_trap"]
+			}
+			timestamp: fields._current_timestamp
+			trap_name: {
+				description: "The name of the trap, resolved from the MIB files."
+				required:    false
+				type: string: examples: ["linkDown", "ciscoConfigManEvent"]
+			}
+			trap_oid: {
+				description: "The numeric object identifier of the trap."
+				required:    false
+				type: string: examples: ["1.3.6.1.6.3.1.1.5.3"]
+			}
+			uptime: {
+				description: "The time since the agent started, in hundredths of a second."
+				required:    false
+				type: uint: {
+					examples: [123456]
+					unit: null
+				}
+			}
+			user: {
+				description: "The user of the SNMPv3 traps."
+				required:    false
+				type: string: examples: ["vector"]
+			}
+			varbinds: {
+				description: """
+					The variable bindings of the trap, keyed by the names of their object identifiers followed by
+					their instance, such as `ifIndex.7`, or by their numeric object identifiers if they can't be
+					resolved.
+					"""
+				required: true
+				type: object: {
+					examples: [{"ifIndex.7": 7, "ifDescr.7": "eth0", "ifOperStatus.7": 2}]
+					options: {}
+				}
+			}
+			version: {
+				description: "The SNMP version of the trap."
+				required:    true
+				type: string: enum: {
+					"1":  "SNMPv1."
+					"2c": "SNMPv2c."
+					"3":  "SNMPv3."
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		translation: {
+			title: "SNMPv1 traps"
+			body: """
+				The SNMPv1 traps are translated to SNMPv2 traps, as described by RFC 3584: the generic traps get
+				the object identifiers of `snmpTraps`, such as `linkDown`, and the enterprise specific traps get
+				the object identifier of their enterprise followed by `0` and their specific trap number.
+				"""
+		}
+		informs: {
+			title: "Inform requests"
+			body: """
+				The SNMPv2c inform requests are acknowledged with a response once they are received. The SNMPv3
+				inform requests are not acknowledged, as their responses would have to be sent by an
+				authoritative SNMP engine.
+				"""
+		}
+		security: {
+			title: "SNMPv3 security"
+			body: """
+				The SNMPv3 traps are authenticated and decrypted with the keys of the [user-based security
+				model](\(urls.snmp_usm)) derived from the passphrases of the `users`. The traps of users which
+				aren't configured, or with a lower security level than the one of their user, are rejected. The
+				engine time of the traps isn't checked against a time window.
+				"""
+		}
+		mibs: {
+			title: "MIB files"
+			body: """
+				Only the object identifiers assigned by the definitions of the MIB files are read, such as those
+				of `OBJECT-TYPE`, `NOTIFICATION-TYPE` or `OBJECT IDENTIFIER` definitions, and the SMIv1
+				`TRAP-TYPE` definitions. The names are resolved across all the files given, regardless of their
+				imports, so that the modules can be listed in any order.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -119,10 +119,30 @@ components: sources: socket: {
 		connection_send_errors_total:         components.sources.internal_metrics.output.metrics.connection_send_errors_total
 		connection_send_ack_errors_total:     components.sources.internal_metrics.output.metrics.connection_send_ack_errors_total
 		connection_shutdown_total:            components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		connection_throttle_duration_seconds: components.sources.internal_metrics.output.metrics.connection_throttle_duration_seconds
+		connections_queued_total:             components.sources.internal_metrics.output.metrics.connections_queued_total
+		connections_rejected_total:           components.sources.internal_metrics.output.metrics.connections_rejected_total
 		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		pass_through_active:                  components.sources.internal_metrics.output.metrics.pass_through_active
+	}
+
+	how_it_works: {
+		pass_through: {
+			title: "Pass-through"
+			body: """
+				When all the consumers of this source are `console`, `file` (with a path without
+				templates), or `socket` sinks, whose codec writes events as this source decodes them,
+				such as the `text` codec for the `bytes` decoding, or `native_json` for
+				`native_json`, the frames received are relayed without being decoded. They are
+				written by the sinks as they were received, without the fields this source would
+				add to them. This only applies if the sinks don't change the events with `encoding`
+				options, keep the events in memory buffers, and have no outputs consumed by other
+				components. The `pass_through_active` internal metric shows whether it applies.
+				"""
+		}
 	}
 }
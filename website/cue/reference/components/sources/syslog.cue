@@ -205,12 +205,15 @@ components: sources: syslog: {
 	}
 
 	telemetry: metrics: {
-		events_in_total:                 components.sources.internal_metrics.output.metrics.events_in_total
-		connection_read_errors_total:    components.sources.internal_metrics.output.metrics.connection_read_errors_total
-		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:          components.sources.internal_metrics.output.metrics.processed_events_total
-		component_received_bytes_total:  components.sources.internal_metrics.output.metrics.component_received_bytes_total
-		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
-		utf8_convert_errors_total:       components.sources.internal_metrics.output.metrics.utf8_convert_errors_total
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		connection_read_errors_total:         components.sources.internal_metrics.output.metrics.connection_read_errors_total
+		connection_throttle_duration_seconds: components.sources.internal_metrics.output.metrics.connection_throttle_duration_seconds
+		connections_queued_total:             components.sources.internal_metrics.output.metrics.connections_queued_total
+		connections_rejected_total:           components.sources.internal_metrics.output.metrics.connections_rejected_total
+		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		utf8_convert_errors_total:            components.sources.internal_metrics.output.metrics.utf8_convert_errors_total
 	}
 }
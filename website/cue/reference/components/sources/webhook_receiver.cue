@@ -0,0 +1,94 @@
+package metadata
+
+components: sources: webhook_receiver: {
+	_port: 80
+
+	title: "Webhook Receiver"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "`newline_delimited` for codecs other than `native`, which defaults to `length_delimited`"
+		}
+		receive: {
+			from: {
+				service: services.http
+
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      _port
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+		auto_generated: true
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.webhook_receiver.configuration
+
+	output: components.sources.http_server.output
+
+	how_it_works: {
+		signature_verification: {
+			title: "Signature verification"
+			body: """
+				Each request must be signed with an HMAC of its body, keyed with `signature.secret`,
+				as done by the providers of webhooks. The signature is verified before the body is
+				decompressed and decoded, and the requests failing the verification are rejected with
+				a `401 Unauthorized` response, so that spoofed payloads never become events.
+
+				The `plain` format covers the providers signing the body alone, such as GitHub, while
+				the `stripe` and `slack` formats also sign a timestamp, which must be within
+				`signature.timestamp_tolerance_secs` of the current time so that the requests can't
+				be replayed.
+				"""
+		}
+		secrets: {
+			title: "Secrets"
+			body: """
+				The secret is best taken from a [secrets backend](\(urls.vector_configuration)global-options#secret),
+				such as with `secret = "SECRET[backend.webhook_secret]"`, rather than being written in
+				the configuration.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:                        components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:                components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:               components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total:          components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		http_bad_requests_total:                       components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		webhook_signature_verification_failures_total: components.sources.internal_metrics.output.metrics.webhook_signature_verification_failures_total
+	}
+}
@@ -0,0 +1,226 @@
+package metadata
+
+components: sources: windows_event_log: {
+	title: "Windows Event Log"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.windows_event_log
+				interface: ffi: {}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				Reading the `Security` channel requires Vector to run as an administrator, or as a user that is a
+				member of the `Event Log Readers` group.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.windows_event_log.configuration
+
+	output: logs: {
+		event: {
+			description: "An event from the Windows Event Log"
+			fields: {
+				channel: {
+					description: "The channel the event was logged to."
+					required:    true
+					type: string: examples: ["System", "Security"]
+				}
+				event_data: {
+					description: """
+						The data of the event. This is an object of the named values of the event when it is logged with a
+						manifest, or an array of its values when it is logged by a classic event source.
+						"""
+					required: false
+					type: object: {}
+				}
+				event_id: {
+					description: "The identifier of the event, specific to its provider."
+					required:    true
+					type: uint: {
+						examples: [7036, 4624]
+						unit: null
+					}
+				}
+				host: fields._local_host & {
+					description: "The name of the computer that logged the event."
+				}
+				level: {
+					description: "The severity level of the event."
+					required:    false
+					type: uint: {
+						examples: [2, 4]
+						unit: null
+					}
+				}
+				level_name: {
+					description: "The name of the severity level of the event."
+					required:    false
+					type: string: examples: ["Critical", "Error", "Warning", "Information", "Verbose"]
+				}
+				message: {
+					description: """
+						The message of the event, rendered from the metadata of its provider. Only present if
+						`render_message` is enabled and the provider of the event is installed on the host.
+						"""
+					required: false
+					type: string: examples: ["The Windows Update service entered the running state."]
+				}
+				provider_name: {
+					description: "The name of the provider that logged the event."
+					required:    true
+					type: string: examples: ["Service Control Manager", "Microsoft-Windows-Security-Auditing"]
+				}
+				record_id: {
+					description: "The record number of the event in its channel."
+					required:    true
+					type: uint: {
+						examples: [12345]
+						unit: null
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: examples: ["windows_event_log"]
+				}
+				timestamp: fields._current_timestamp & {
+					description: "The time at which the event was logged."
+				}
+				user_data: {
+					description: "The provider defined data of the event, for events that do not use `EventData`."
+					required:    false
+					type: object: {}
+				}
+				"*": {
+					common:      false
+					description: """
+						The other fields of the `System` element of the event, such as `provider_guid`, `version`,
+						`task`, `opcode`, `keywords`, `process_id`, `thread_id`, `activity_id`, and `user_id`.
+						"""
+					required: false
+					type: string: {
+						default: null
+						examples: ["0x8080000000000000", "S-1-5-18"]
+					}
+				}
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Service state change"
+
+			configuration: {
+				channels: ["System"]
+			}
+			input: """
+				<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+				  <System>
+				    <Provider Name="Service Control Manager" Guid="{555908d1-a6d7-4695-8e1e-26931d2012f4}" EventSourceName="Service Control Manager"/>
+				    <EventID Qualifiers="16384">7036</EventID>
+				    <Level>4</Level>
+				    <TimeCreated SystemTime="2023-03-01T12:34:56.789012Z"/>
+				    <EventRecordID>12345</EventRecordID>
+				    <Execution ProcessID="636" ThreadID="4868"/>
+				    <Channel>System</Channel>
+				    <Computer>WIN-HOST</Computer>
+				  </System>
+				  <EventData>
+				    <Data Name="param1">Windows Update</Data>
+				    <Data Name="param2">running</Data>
+				  </EventData>
+				</Event>
+				"""
+			output: [{
+				log: {
+					timestamp:         "2023-03-01T12:34:56.789012Z"
+					source_type:       "windows_event_log"
+					message:           "The Windows Update service entered the running state."
+					host:              "WIN-HOST"
+					channel:           "System"
+					event_id:          7036
+					event_source_name: "Service Control Manager"
+					level:             4
+					level_name:        "Information"
+					process_id:        636
+					provider_guid:     "{555908d1-a6d7-4695-8e1e-26931d2012f4}"
+					provider_name:     "Service Control Manager"
+					qualifiers:        16384
+					record_id:         12345
+					thread_id:         4868
+					event_data: {
+						param1: "Windows Update"
+						param2: "running"
+					}
+				}
+			}]
+		},
+	]
+
+	how_it_works: {
+		bookmarks: {
+			title: "Bookmarks"
+			body: """
+				The position of the source in each channel is checkpointed with a Windows Event Log bookmark,
+				persisted in the data directory at the end of each batch of events, or once the batch has
+				been acknowledged by the sinks when acknowledgements are enabled. On restart, events are read
+				from the bookmark of each channel, and, when there is no bookmark, either from the next event
+				logged or, if `read_existing_events` is enabled, from the oldest event of the channel.
+				"""
+		}
+		queries: {
+			title: "Queries"
+			body: """
+				Events are selected from each channel with an [XPath query](\(urls.windows_event_log_xpath)),
+				such as `*[System[(Level=1 or Level=2)]]` to only read critical and error events. Filtering
+				events with a query is much cheaper than filtering them in a transform, as events that do not
+				match it are never rendered.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		checkpoint_write_errors_total:        components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
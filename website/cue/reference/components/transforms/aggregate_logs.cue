@@ -0,0 +1,95 @@
+package metadata
+
+components: transforms: aggregate_logs: {
+	title: "Aggregate Logs"
+
+	description: """
+		Groups log events by a set of fields over a fixed interval window, and emits a single
+		summarizing event for each group with the number of events, aggregations of numeric fields,
+		and a sample of messages.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.aggregate_logs.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Summarize request durations per service"
+			input: [
+				{log: {service: "api", duration: 12, message: "GET /users"}},
+				{log: {service: "api", duration: 30, message: "GET /orders"}},
+				{log: {service: "web", duration: 5, message: "GET /"}},
+			]
+			configuration: {
+				group_by: ["service"]
+				aggregations: duration: ["sum", "max"]
+				max_message_samples: 1
+			}
+			output: [
+				{log: {
+					service: "api"
+					count:   2
+					duration: {sum: 42.0, max: 30.0}
+					messages: ["GET /users"]
+					timestamp: "2021-07-12T07:58:50Z"
+				}},
+				{log: {
+					service: "web"
+					count:   1
+					duration: {sum: 5.0, max: 5.0}
+					messages: ["GET /"]
+					timestamp: "2021-07-12T07:58:50Z"
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		windowing: {
+			title: "Windowing"
+			body: """
+				Windows are tumbling: every `interval_ms`, a summarizing event is emitted for each group of
+				events received since the previous flush, and the groups start again from scratch. The
+				`timestamp` of a summarizing event is the time at which its window ended. Any groups in
+				progress are flushed when Vector shuts down.
+				"""
+		}
+
+		aggregations: {
+			title: "Aggregations"
+			body: """
+				Every summarizing event contains the values of the `group_by` fields and a `count` of the
+				events in the group. Each field listed in `aggregations` is replaced by an object with the
+				result of each of its functions. Values of the field that aren't numbers are ignored, and a
+				function is `null` when there were no numeric values to aggregate.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		aggregate_logs_events_recorded_total: components.sources.internal_metrics.output.metrics.aggregate_logs_events_recorded_total
+		aggregate_logs_flushes_total:         components.sources.internal_metrics.output.metrics.aggregate_logs_flushes_total
+	}
+}
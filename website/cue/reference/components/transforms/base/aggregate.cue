@@ -1,11 +1,35 @@
 package metadata
 
-base: components: transforms: aggregate: configuration: interval_ms: {
-	description: """
-		The interval between flushes, in milliseconds.
-
-		During this time frame, metrics with the same series data (name, namespace, tags, and so on) are aggregated.
-		"""
-	required: false
-	type: uint: default: 10000
+base: components: transforms: aggregate: configuration: {
+	data_dir: {
+		description: """
+			The directory used to save the aggregated metrics when `persist` is enabled.
+
+			By default, the global `data_dir` option is used. Make sure the running user has write
+			permissions to this directory.
+			"""
+		required: false
+		type: string: examples: ["/var/lib/vector"]
+	}
+	interval_ms: {
+		description: """
+			The interval between flushes, in milliseconds.
+
+			During this time frame, metrics with the same series data (name, namespace, tags, and so on) are aggregated.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	persist: {
+		description: """
+			Whether to save the aggregated metrics in the data directory, so that they're restored
+			after Vector restarts.
+
+			The metrics are saved periodically, and after each flush. When the transform stops, the
+			metrics aggregated since the last flush are saved instead of being flushed, so that the
+			next run of the transform keeps aggregating them.
+			"""
+		required: false
+		type: bool: default: false
+	}
 }
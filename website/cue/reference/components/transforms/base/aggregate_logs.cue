@@ -0,0 +1,58 @@
+package metadata
+
+base: components: transforms: aggregate_logs: configuration: {
+	aggregations: {
+		description: """
+			A map of numeric field names to the functions used to aggregate their values.
+
+			In the summarizing event, each field is replaced by an object containing the result of
+			each function, such as `{ "sum": 12, "max": 5 }`. Values that aren't numbers are ignored.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The aggregation functions for the field."
+			required:    true
+			type: array: items: type: string: enum: {
+				max:  "The maximum value."
+				mean: "The arithmetic mean of all values."
+				min:  "The minimum value."
+				sum:  "The sum of all values."
+			}
+		}
+	}
+	group_by: {
+		description: """
+			An ordered list of fields by which to group events.
+
+			Each group with matching values for the specified keys is summarized independently. When no
+			fields are specified, all events received during a window are summarized together.
+
+			The values of these fields are copied to the summarizing event.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["service", "status"]
+		}
+	}
+	interval_ms: {
+		description: """
+			The length of each window, in milliseconds.
+
+			At the end of every window, a single summarizing event is emitted for each group of events
+			received during the window.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	max_message_samples: {
+		description: """
+			The maximum number of messages to keep as a sample in each summarizing event.
+
+			The first messages received during the window are kept, under the `messages` field. When
+			set to `0`, no messages are sampled.
+			"""
+		required: false
+		type: uint: default: 5
+	}
+}
@@ -0,0 +1,47 @@
+package metadata
+
+base: components: transforms: cardinality_limit: configuration: {
+	limit_exceeded_action: {
+		description: """
+			Possible actions to take when an event arrives that would exceed the series limit of its
+			metric.
+			"""
+		required: false
+		type: string: {
+			default: "drop_series"
+			enum: {
+				aggregate: """
+					Replace the tags of the new series with a single `overflow` tag, aggregating all the new
+					series of the metric into one.
+					"""
+				drop_series: "Drop the event of the new series."
+				strip_tags: """
+					Strip the tags of the new series until it matches an accepted series, starting with the tags
+					having the most distinct values for the metric.
+
+					A series left without tags is accepted even though it is new.
+					"""
+			}
+		}
+	}
+	series_limit: {
+		description: """
+			How many distinct series to accept for any given metric name.
+
+			The series of a metric are told apart by their tags.
+			"""
+		required: false
+		type: uint: default: 1000
+	}
+	window_secs: {
+		description: """
+			The time after which a series that was not seen again stops counting against the
+			`series_limit`, in seconds.
+			"""
+		required: false
+		type: float: {
+			default: 3600.0
+			unit:    "seconds"
+		}
+	}
+}
@@ -4,10 +4,46 @@ base: components: transforms: dedupe: configuration: {
 	cache: {
 		description: "Caching configuration for deduplication."
 		required:    false
-		type: object: options: num_events: {
-			description: "Number of events to cache and use for comparing incoming events to previously seen events."
-			required:    false
-			type: uint: default: 5000
+		type: object: options: {
+			data_dir: {
+				description: """
+					The directory used to save the cache when `persist` is enabled.
+
+					By default, the global `data_dir` option is used. Make sure the running user has write
+					permissions to this directory.
+					"""
+				required: false
+				type: string: examples: ["/var/lib/vector"]
+			}
+			num_events: {
+				description: "Number of events to cache and use for comparing incoming events to previously seen events."
+				required:    false
+				type: uint: default: 5000
+			}
+			persist: {
+				description: """
+					Whether to save the cache in the data directory, so that duplicates are still dropped after
+					Vector restarts.
+
+					When enabled, the cache only holds a hash of the matched fields of each event, instead of
+					the fields themselves. The cache is saved periodically, and when the transform stops.
+					"""
+				required: false
+				type: bool: default: false
+			}
+			time_window_secs: {
+				description: """
+					The number of seconds an event is remembered for, since it was last seen.
+
+					Events seen again after that time are no longer considered duplicates. By default, events
+					are remembered until they are evicted from the cache to make room for newer ones.
+					"""
+				required: false
+				type: uint: {
+					examples: [3600]
+					unit: "seconds"
+				}
+			}
 		}
 	}
 	fields: {
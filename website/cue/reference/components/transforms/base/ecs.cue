@@ -0,0 +1,58 @@
+package metadata
+
+base: components: transforms: ecs: configuration: {
+	default_mappings: {
+		description: """
+			Whether to apply the built-in mappings of the fields set by the sources.
+
+			These cover the global log schema fields, along with the fields of the `syslog`,
+			`journald`, `file`, `http_server`, `docker_logs`, and `kubernetes_logs` sources.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	mappings: {
+		description: """
+			A map of the fields to move, to the fields of the schema they are moved to.
+
+			These take precedence over the built-in mappings of the same fields.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				service:  "service.name"
+				trace_id: "trace.id"
+			}]
+			options: "*": {
+				description: "The field of the schema to move the field to."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	mode: {
+		description: "How the fields not part of the schema are handled."
+		required:    false
+		type: string: {
+			default: "lenient"
+			enum: {
+				lenient: "The fields not part of the schema are kept, and reported."
+				strict: """
+					The fields not part of the schema are removed, and reported.
+
+					This includes the fields whose value couldn't be converted to the type of the field of the
+					schema they are mapped to.
+					"""
+			}
+		}
+	}
+	unmapped_fields_key: {
+		description: """
+			The field to set to the names of the top-level fields not part of the schema.
+
+			The field isn't set on the events which only have fields of the schema.
+			"""
+		required: false
+		type: string: examples: ["vector.unmapped_fields"]
+	}
+}
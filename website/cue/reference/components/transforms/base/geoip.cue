@@ -0,0 +1,41 @@
+package metadata
+
+base: components: transforms: geoip: configuration: {
+	fields: {
+		description: """
+			A map of the fields holding the IP addresses to look up, to the fields to set to the data
+			found for them.
+
+			The data is set as an object, such as `{ "city_name": "Boxford", "country_code": "GB", ... }`
+			for the city databases, or `{ "autonomous_system_number": 701, ... }` for the ASN
+			databases. The field isn't set for the addresses not found in the database.
+			"""
+		required: true
+		type: object: {
+			examples: [{
+				client_ip: "client.geo"
+				server_ip: "server.geo"
+			}]
+			options: "*": {
+				description: "The field to set to the data found for the IP address."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	select: {
+		description: "The fields of the data found to set, instead of all of them."
+		required:    false
+		type: array: items: type: string: examples: ["country_code", "city_name"]
+	}
+	table: {
+		description: """
+			The name of the `geoip` enrichment table to look up the IP addresses in.
+
+			The table can download its database from MaxMind, and keep it up to date, with its
+			`download` option.
+			"""
+		required: true
+		type: string: examples: ["geoip"]
+	}
+}
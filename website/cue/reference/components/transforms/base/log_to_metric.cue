@@ -4,13 +4,33 @@ base: components: transforms: log_to_metric: configuration: metrics: {
 	description: "A list of metrics to generate."
 	required:    true
 	type: array: items: type: object: options: {
+		buckets: {
+			description: """
+				The upper limits of the buckets of the histogram, in ascending order.
+
+				If set, an aggregated histogram is emitted, counting the value in the first bucket it fits
+				in, along with the count and the sum of the values. Values greater than the last limit are
+				only counted in the count and the sum. If not set, a distribution of the values is emitted
+				instead.
+				"""
+			relevant_when: "type = \"histogram\""
+			required:      false
+			type: array: items: type: float: examples: [0.005, 0.1, 1.0]
+		}
 		field: {
-			description: "Name of the field in the event to generate the metric."
-			required:    true
+			description: """
+				Name of the field in the event to generate the metric.
+
+				Exactly one of `field` and `value_source` must be set.
+				"""
+			required: false
 			type: string: syntax: "template"
 		}
 		increment_by_value: {
-			description:   "Increments the counter by the value in `field`, instead of only by `1`."
+			description: """
+				Increments the counter by the value in `field`, or computed by `value_source`, instead of
+				only by `1`.
+				"""
 			relevant_when: "type = \"counter\""
 			required:      false
 			type: bool: default: false
@@ -41,7 +61,8 @@ base: components: transforms: log_to_metric: configuration: metrics: {
 			description: """
 				Overrides the name of the counter.
 
-				If not specified, `field` is used as the name of the metric.
+				If not specified, `field` is used as the name of the metric. It must be specified if the
+				value is computed by `value_source`.
 				"""
 			required: false
 			type: string: syntax: "template"
@@ -55,8 +76,16 @@ base: components: transforms: log_to_metric: configuration: metrics: {
 			description: "Tags to apply to the metric."
 			required:    false
 			type: object: options: "*": {
-				description: "A metric tag."
-				required:    true
+				description: """
+					A metric tag.
+
+					This may be a single value, a `null` for a bare tag, an array of either, or an object
+					with a `source` field holding a [VRL][vrl] expression computing them. If the expression
+					returns an array, each of its elements is given to the same tag name.
+
+					[vrl]: https://vector.dev/docs/reference/vrl
+					"""
+				required: true
 				type: string: syntax: "template"
 			}
 		}
@@ -71,5 +100,17 @@ base: components: transforms: log_to_metric: configuration: metrics: {
 				summary:   "A summary."
 			}
 		}
+		value_source: {
+			description: """
+				A [VRL][vrl] expression computing the value of the metric from the event, instead of
+				reading it from `field`.
+
+				The event can't be modified by the expression.
+
+				[vrl]: https://vector.dev/docs/reference/vrl
+				"""
+			required: false
+			type: string: examples: ["to_float!(.duration_ms) / 1000"]
+		}
 	}
 }
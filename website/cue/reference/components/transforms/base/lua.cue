@@ -140,6 +140,37 @@ base: components: transforms: lua: configuration: {
 			require('custom_module')
 			"""]
 	}
+	state: {
+		description: """
+			Persists the global `state` table across restarts.
+
+			When set, the `state` table is restored from the data directory before the `source` is
+			evaluated, and saved back periodically and when the transform stops. It can hold strings,
+			numbers, booleans, and nested tables of them. Set to `{}` to use the defaults.
+			"""
+		required: false
+		type: object: options: {
+			checkpoint_interval_seconds: {
+				description: "The interval to save the `state` table, in seconds."
+				required:    false
+				type: uint: {
+					default: 10
+					unit:    "seconds"
+				}
+			}
+			data_dir: {
+				description: """
+					The directory used to save the `state` table.
+
+					By default, the [global `data_dir` option][global_data_dir] is used.
+
+					[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+					"""
+				required: false
+				type: string: {}
+			}
+		}
+	}
 	timers: {
 		description: "A list of timers which should be configured and executed periodically."
 		required:    false
@@ -153,8 +184,9 @@ base: components: transforms: lua: configuration: {
 						It can produce new events using the `emit` function.
 
 						This can either be inline Lua that defines a closure to use, or the name of the Lua function
-						to call. In both cases, the closure/function takes a single parameter, `emit`, which is a
-						reference to a function for emitting events.
+						to call. In both cases, the closure/function takes two parameters. The first parameter,
+						`emit`, is a reference to a function for emitting events, while the second parameter is the
+						`name` of the timer.
 						"""
 					required: true
 					type: string: examples: ["timer_handler"]
@@ -164,6 +196,15 @@ base: components: transforms: lua: configuration: {
 					required:    true
 					type: uint: unit: "seconds"
 				}
+				name: {
+					description: """
+						The name of the timer, passed to the handler as its second parameter.
+
+						This allows a single handler to serve several timers, and identifies the timer in the logs.
+						"""
+					required: false
+					type: string: examples: ["flush_sessions"]
+				}
 			}
 		}
 	}
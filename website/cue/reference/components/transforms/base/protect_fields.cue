@@ -0,0 +1,86 @@
+package metadata
+
+base: components: transforms: protect_fields: configuration: {
+	fields: {
+		description: """
+			The fields to protect, and how.
+
+			The fields are protected in order, and the fields missing from an event are skipped.
+			"""
+		required: true
+		type: array: items: type: object: options: {
+			action: {
+				description: "The protection to apply to the field."
+				required:    true
+				type: string: enum: {
+					decrypt: "Replaces an encrypted value with its decryption, with the key whose ID it is tagged with."
+					encrypt: """
+						Replaces the value with its encryption with a new data key, itself encrypted with the key.
+
+						The value is encrypted in JSON, so `decrypt` restores its type, except for the timestamps
+						which are restored as strings.
+						"""
+					mask: "Replaces the letters and digits of the value with a character, keeping its format."
+					tokenize: """
+						Replaces the value with a token, the HMAC-SHA256 of the value.
+
+						The tokens of the same value are the same, so the events can still be correlated, but the
+						value can't be recovered from its token.
+						"""
+				}
+			}
+			character: {
+				description:   "The character to mask the letters and digits with."
+				relevant_when: "action = \"mask\""
+				required:      false
+				type: string: default: "*"
+			}
+			field: {
+				description: "The field to protect."
+				required:    true
+				type: string: examples: ["user.email"]
+			}
+			keep_first: {
+				description:   "The number of leading letters and digits to leave unmasked."
+				relevant_when: "action = \"mask\""
+				required:      false
+				type: uint: default: 0
+			}
+			keep_last: {
+				description:   "The number of trailing letters and digits to leave unmasked."
+				relevant_when: "action = \"mask\""
+				required:      false
+				type: uint: {
+					default: 0
+					examples: [4]
+				}
+			}
+			key_id: {
+				description:   "The ID of the key, in `keys`."
+				relevant_when: "action = \"tokenize\" or action = \"encrypt\""
+				required:      true
+				type: string: examples: ["2024"]
+			}
+		}
+	}
+	keys: {
+		description: """
+			A map of key IDs to the keys used to tokenize and encrypt the fields.
+
+			Each key is 32 bytes, encoded in Base64, and is best retrieved from a secrets backend,
+			with `SECRET[<backend>.<secret>]`. The protected values are tagged with the ID of their key,
+			so the previous keys can be kept here to decrypt the values protected before a rotation.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				"2024": "SECRET[vault.pii_key_2024]"
+			}]
+			options: "*": {
+				description: "A key, encoded in Base64."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+}
@@ -1,6 +1,16 @@
 package metadata
 
 base: components: transforms: reduce: configuration: {
+	data_dir: {
+		description: """
+			The directory used to save the events being reduced when `persist` is enabled.
+
+			By default, the global `data_dir` option is used. Make sure the running user has write
+			permissions to this directory.
+			"""
+		required: false
+		type: string: examples: ["/var/lib/vector"]
+	}
 	ends_when: {
 		description: """
 			A condition used to distinguish the final event of a transaction.
@@ -90,6 +100,19 @@ base: components: transforms: reduce: configuration: {
 			}
 		}
 	}
+	persist: {
+		description: """
+			Whether to save the events being reduced in the data directory, so that they're restored
+			after Vector restarts.
+
+			The events are saved periodically. When the transform stops, they're saved instead of
+			being flushed, so that the next run of the transform keeps reducing them. If Vector
+			crashes, the events reduced since the last save are lost, and the ones flushed since then
+			are flushed again.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	starts_when: {
 		description: """
 			A condition used to distinguish the first event of a transaction.
@@ -48,6 +48,26 @@ base: components: transforms: remap: configuration: {
 		required: false
 		type: string: examples: ["./my/program.vrl"]
 	}
+	libraries: {
+		description: """
+			File paths to [Vector Remap Language][vrl] (VRL) libraries, defining functions that the
+			program can call.
+
+			A library defines functions with `fn name(argument, ...) { ... }`, whose body is a VRL
+			program returning the result of the function. The body can only access the arguments of
+			the function, not the event. Functions can call the functions defined before them,
+			including those of the previous libraries.
+
+			If a relative path is provided, its root is the current working directory.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["./common.vrl"]
+		}
+	}
 	metric_tag_values: {
 		description: """
 			When set to `single`, metric tag values are exposed as single strings, the
@@ -1,20 +1,61 @@
 package metadata
 
-base: components: transforms: route: configuration: route: {
-	description: """
-		A table of route identifiers to logical conditions representing the filter of the route.
+base: components: transforms: route: configuration: {
+	route: {
+		description: """
+			A table of route identifiers to logical conditions representing the filter of the route.
 
-		Each route can then be referenced as an input by other components with the name
-		`<transform_name>.<route_id>`. If an event doesn’t match any route, it is sent to the
-		`<transform_name>._unmatched` output.
+			Each route can then be referenced as an input by other components with the name
+			`<transform_name>.<route_id>`. If an event doesn’t match any route, it is sent to the
+			`<transform_name>._unmatched` output.
 
-		Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
-		as a route name.
-		"""
-	required: false
-	type: object: options: "*": {
-		description: "An individual route."
-		required:    true
-		type: condition: {}
+			Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
+			as a route name.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "An individual route."
+			required:    true
+			type: condition: {}
+		}
+	}
+	weight_key: {
+		description: """
+			The name of the field whose value is hashed to pick the weighted route of an event.
+
+			Events with the same value are consistently sent to the same weighted route. For metric
+			events, the value of the tag with this name is used instead. If left unspecified, or if
+			the event doesn't have the field, events are spread evenly over the weighted routes as they
+			arrive.
+			"""
+		required: false
+		type: string: examples: ["user_id"]
+	}
+	weighted_route: {
+		description: """
+			A table of route identifiers to the share of events sent to the route.
+
+			Weighted routes split the stream by percentage rather than by condition, which is useful to
+			mirror a fraction of the traffic to another pipeline. Each event is sent to at most one
+			weighted route, regardless of the conditional routes it matches, and events sent to neither
+			a conditional nor a weighted route are sent to the `<transform_name>._unmatched` output.
+
+			The weights of all weighted routes must add up to at most `100`.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "An individual weighted route."
+			required:    true
+			type: object: options: weight: {
+				description: """
+					The percentage of events sent to the route.
+
+					For example, `weight = 5` sends 5% of events to the route. Weights have a resolution of
+					`0.01`.
+					"""
+				required: true
+				type: float: examples: [5.0]
+			}
+		}
 	}
 }
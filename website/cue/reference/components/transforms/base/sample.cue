@@ -24,8 +24,26 @@ base: components: transforms: sample: configuration: {
 
 			For example, `rate = 10` means 1 out of every 10 events are forwarded and the rest are
 			dropped.
+
+			The rate can be changed at runtime through the API, without reloading the configuration.
+
+			When adapting to `target_events_per_sec`, this is the initial rate.
 			"""
 		required: true
 		type: uint: {}
 	}
+	target_events_per_sec: {
+		description: """
+			The number of events per second to forward, which the rate adapts to.
+
+			The rate is adjusted every second, based on the smoothed number of events per second that
+			aren't excluded from sampling, so the rate can't be changed through the API anymore. The
+			effective rate is reported by the `sample_rate` metric.
+
+			When sampling by `key_field`, the same events are only consistently sampled while the rate
+			doesn't change.
+			"""
+		required: false
+		type: float: examples: [100.0]
+	}
 }
@@ -0,0 +1,54 @@
+package metadata
+
+base: components: transforms: tail_sample: configuration: {
+	group_by: {
+		description: """
+			The name of the field holding the trace or correlation ID by which events are grouped.
+
+			Events without this field are forwarded right away.
+			"""
+		required: true
+		type: string: examples: ["trace_id", "request_id"]
+	}
+	keep_when: {
+		description: """
+			A logical condition used to keep groups of events.
+
+			A group is kept as soon as the condition matches any of its events, such as an event with
+			an error or with a latency above a threshold. Its buffered events are then forwarded, along
+			with the ones received during the rest of its window.
+			"""
+		required: true
+		type: condition: {}
+	}
+	max_events: {
+		description: """
+			The maximum number of events buffered for a group.
+
+			When a group reaches it, the group is kept or dropped right away, without waiting for the end
+			of its window.
+			"""
+		required: false
+		type: uint: {}
+	}
+	sample_rate: {
+		description: """
+			The rate at which the groups not kept by `keep_when` are forwarded anyway, expressed as `1/N`.
+
+			Groups are chosen by hashing their ID, so that every Vector instance makes the same decision
+			for the same group. When unset, these groups are dropped.
+			"""
+		required: false
+		type: uint: {}
+	}
+	window_ms: {
+		description: """
+			How long the events of a group are buffered, in milliseconds, starting with its first event.
+
+			At the end of the window, the group is either kept or dropped as a whole. Events received
+			afterwards with the same ID start a new group.
+			"""
+		required: false
+		type: uint: default: 30000
+	}
+}
@@ -21,11 +21,32 @@ base: components: transforms: throttle: configuration: {
 			syntax: "template"
 		}
 	}
+	mode: {
+		description: "How events are counted against the `threshold`."
+		required:    false
+		type: string: {
+			default: "events"
+			enum: {
+				estimated_bytes: "Each event counts as its estimated size in memory, in bytes."
+				events:          "Each event counts as one."
+				json_bytes: """
+					Each event counts as the estimated size of its JSON encoding, in bytes.
+
+					This is closer to the number of bytes most sinks send for the event than
+					`estimated_bytes`.
+					"""
+			}
+		}
+	}
 	threshold: {
 		description: """
-			The number of events allowed for a given bucket per configured `window_secs`.
+			The number of events, or of bytes depending on `mode`, allowed for a given bucket per
+			configured `window_secs`.
 
 			Each unique key has its own `threshold`.
+
+			The threshold can be changed at runtime through the API, without reloading the
+			configuration. Doing so resets the buckets of all the keys.
 			"""
 		required: true
 		type: uint: {}
@@ -0,0 +1,54 @@
+package metadata
+
+base: components: transforms: wasm: configuration: {
+	limits: {
+		description: "Limits on the resources used by the module."
+		required:    false
+		type: object: options: {
+			fuel: {
+				description: """
+					The amount of fuel that each call into the module can consume.
+
+					Fuel is consumed by running the instructions of the module, about one unit per instruction.
+					A call running out of fuel fails, and its event is dropped.
+					"""
+				required: false
+				type: uint: default: 100000000
+			}
+			max_memory_bytes: {
+				description: "The maximum size of the memory of the module, in bytes."
+				required:    false
+				type: uint: default: 67108864
+			}
+			timeout_ms: {
+				description: """
+					How long each call into the module can run, in milliseconds.
+
+					A call running longer fails, and its event is dropped.
+					"""
+				required: false
+				type: uint: default: 100
+			}
+		}
+	}
+	module: {
+		description: """
+			The path to the WebAssembly module, in the binary or text format.
+
+			The module must implement version 1 of the ABI of the transform.
+			"""
+		required: true
+		type: string: examples: ["/etc/vector/parser.wasm"]
+	}
+	watch: {
+		description: """
+			Reloads the module when its file changes.
+
+			The file is checked every second. The `vector_shutdown` function of the previous module is
+			called before the `vector_init` function of the new one. If the new module fails to load,
+			the previous one keeps running.
+			"""
+		required: false
+		type: bool: default: false
+	}
+}
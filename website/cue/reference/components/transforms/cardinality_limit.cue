@@ -0,0 +1,153 @@
+package metadata
+
+components: transforms: cardinality_limit: {
+	title: "Cardinality Limit"
+
+	description: """
+		Limits the number of series of each metric, protecting against tag explosions that can
+		commonly disrupt the stability of metrics storages.
+
+		A series of a metric is told apart from the others by its tags. Once a metric has
+		`series_limit` series seen within `window_secs`, new series are dropped by default. The
+		action to take can be modified with the `limit_exceeded_action` option.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.cardinality_limit.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Aggregate new series into an overflow series"
+			notes: """
+				In this example the second series of the `logins` metric exceeds the `series_limit`,
+				so its tags are replaced with the `overflow` tag.
+				"""
+			configuration: {
+				series_limit:          1
+				limit_exceeded_action: "aggregate"
+			}
+			input: [
+				{metric: {
+					kind: "incremental"
+					name: "logins"
+					counter: {
+						value: 2.0
+					}
+					tags: {
+						user_id: "user_id_1"
+					}
+				}},
+				{metric: {
+					kind: "incremental"
+					name: "logins"
+					counter: {
+						value: 2.0
+					}
+					tags: {
+						user_id: "user_id_2"
+					}
+				}},
+			]
+			output: [
+				{metric: {
+					kind: "incremental"
+					name: "logins"
+					counter: {
+						value: 2.0
+					}
+					tags: {
+						user_id: "user_id_1"
+					}
+				}},
+				{metric: {
+					kind: "incremental"
+					name: "logins"
+					counter: {
+						value: 2.0
+					}
+					tags: {
+						overflow: "true"
+					}
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		window: {
+			title: "Window"
+			body: """
+				Each series counts against the limit of its metric until it was not seen for
+				`window_secs`. The expired series are forgotten a few times per window, so they may
+				count against the limit for up to a tenth of the window longer. The series are held in
+				memory, so restarting Vector forgets them.
+				"""
+		}
+
+		overflow: {
+			title: "Overflow series"
+			body: """
+				With the `aggregate` action, all the new series of a metric above the limit are sent
+				as a single series, whose only tag is `overflow="true"`. Sinks and the `aggregate`
+				transform combine incremental values of this series, while absolute values of
+				different series overwrite each other. The overflow series, like the series left
+				without tags by the `strip_tags` action, is accepted even though the limit is
+				exceeded.
+				"""
+		}
+
+		stripped_tags: {
+			title: "Stripped tags"
+			body: """
+				With the `strip_tags` action, the tags that no accepted series of the metric has are
+				stripped first from a new series, followed by the tags with the most distinct values
+				among the accepted series, until the series matches an accepted one or has no tags
+				left.
+				"""
+		}
+
+		memory_utilization: {
+			title: "Memory Utilization"
+			body: """
+				This transform keeps a copy of the name of every metric it sees, and of the tags of up
+				to `series_limit` series for each of them, until they expire.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		cardinality_limit_exceeded_total:      components.sources.internal_metrics.output.metrics.cardinality_limit_exceeded_total
+		cardinality_limit_reached_total:       components.sources.internal_metrics.output.metrics.cardinality_limit_reached_total
+		cardinality_limit_stripped_tags_total: components.sources.internal_metrics.output.metrics.cardinality_limit_stripped_tags_total
+		cardinality_limit_tracked_series:      components.sources.internal_metrics.output.metrics.cardinality_limit_tracked_series
+	}
+}
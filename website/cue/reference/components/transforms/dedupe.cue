@@ -47,6 +47,33 @@ components: transforms: dedupe: {
 				"""
 		}
 
+		time_window: {
+			title: "Time Window"
+			body: """
+				By default, an Event is considered a duplicate for as long as it
+				stays in the cache. When `cache.time_window_secs` is set, an Event
+				is only considered a duplicate if it was last seen within that
+				many seconds, so that Events repeated after a quiet period are let
+				through again even if the cache is large.
+				"""
+		}
+
+		persistence: {
+			title: "Persistence"
+			body: """
+				The cache is kept in memory and is lost when Vector restarts, so
+				Events seen before the restart are let through again. When
+				`cache.persist` is enabled, the cache is saved in the data
+				directory periodically and when the transform stops, and loaded
+				back when it starts. Events seen since the last save are still
+				let through again after a crash.
+
+				In this mode, each cache entry only holds a 64-bit hash of the
+				fields being considered for matching, rather than a copy of them,
+				which also bounds its memory usage.
+				"""
+		}
+
 		memory_usage_details: {
 			title: "Memory Usage Details"
 			body: """
@@ -95,6 +122,8 @@ components: transforms: dedupe: {
 	}
 
 	telemetry: metrics: {
-		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		dedupe_cache_hits_total:   components.sources.internal_metrics.output.metrics.dedupe_cache_hits_total
+		dedupe_cache_misses_total: components.sources.internal_metrics.output.metrics.dedupe_cache_misses_total
+		events_discarded_total:    components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
 }
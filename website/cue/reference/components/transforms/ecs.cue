@@ -0,0 +1,94 @@
+package metadata
+
+components: transforms: ecs: {
+	title: "ECS"
+
+	description: """
+		Normalizes log events to the [Elastic Common Schema](\(urls.elastic_common_schema)), by
+		moving the fields set by the sources to their counterparts in the schema, so the events can
+		be used by Elastic Security without any further remapping.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.ecs.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Normalize a syslog event"
+			input: log: {
+				message:   "Connection refused"
+				host:      "web-01"
+				appname:   "nginx"
+				procid:    "1234"
+				severity:  "err"
+				custom:    "value"
+				timestamp: "2021-07-12T07:58:44.223543Z"
+			}
+			configuration: {
+				unmapped_fields_key: "vector.unmapped_fields"
+			}
+			output: log: {
+				"@timestamp": "2021-07-12T07:58:44.223543Z"
+				message:      "Connection refused"
+				host: name: "web-01"
+				process: {name: "nginx", pid: 1234}
+				log: syslog: severity: name: "err"
+				ecs: version: "8.6.0"
+				custom: "value"
+				vector: unmapped_fields: ["custom"]
+			}
+		},
+	]
+
+	how_it_works: {
+		mappings: {
+			title: "Mappings"
+			body: """
+				Each mapped field is moved to its field of the schema, unless the event already has that
+				field, in which case both are left untouched. The built-in mappings cover the `timestamp`,
+				`host`, and `source_type` fields of the global log schema, the syslog fields such as
+				`appname` and `procid`, and the container and pod metadata of the `docker_logs` and
+				`kubernetes_logs` sources. The `procid` field is converted to an integer, as expected by
+				the schema for `process.pid`, and left in place when it isn't a number.
+
+				Every event gets `ecs.version` set to the version of the schema the fields are mapped for.
+				"""
+		}
+
+		unmapped_fields: {
+			title: "Unmapped fields"
+			body: """
+				The top-level fields left that aren't part of the schema are counted by the
+				`ecs_unmapped_fields_total` metric, and their names can be set on the event with
+				`unmapped_fields_key`. In the `lenient` mode, they are kept as they are, while in the
+				`strict` mode, they are removed, so the events only have fields of the schema.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		ecs_unmapped_fields_total: components.sources.internal_metrics.output.metrics.ecs_unmapped_fields_total
+	}
+}
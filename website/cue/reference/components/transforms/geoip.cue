@@ -0,0 +1,92 @@
+package metadata
+
+components: transforms: geoip: {
+	title: "GeoIP"
+
+	description: """
+		Enriches log events with the location or network of their IP addresses, looked up in a
+		`geoip` enrichment table, without having to write any VRL.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		enrich: {
+			from: service: {
+				name:     "MaxMind GeoIP2 and GeoLite2 databases"
+				url:      urls.maxmind
+				versions: null
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.geoip.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Look up the client address"
+			input: log: {
+				message:   "GET /index.html"
+				client_ip: "2.125.160.216"
+			}
+			configuration: {
+				table: "geoip"
+				fields: client_ip: "client.geo"
+				select: ["city_name", "country_code"]
+			}
+			output: log: {
+				message:   "GET /index.html"
+				client_ip: "2.125.160.216"
+				client: geo: {
+					city_name:    "Boxford"
+					country_code: "GB"
+				}
+			}
+		},
+	]
+
+	how_it_works: {
+		databases: {
+			title: "Databases"
+			body: """
+				The addresses are looked up in a `geoip` enrichment table, which loads its database
+				from a local file. With the `download` option of the table, the database is downloaded
+				from MaxMind with the license key of an account, and downloaded again once it's older
+				than `download.refresh_interval_secs`. The downloaded database replaces the previous one
+				in a single step, once it's been checked, so that the events keep being enriched with
+				the previous database until then, or when it can't be downloaded.
+				"""
+		}
+
+		lookups: {
+			title: "Lookups"
+			body: """
+				Each field set to an IP address is looked up, and the data found for it is set as an
+				object on the field it's mapped to. The fields set to addresses not found in the
+				database, such as private addresses, are left alone, while the fields which aren't
+				IP addresses are reported by the `component_errors_total` metric.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -305,6 +305,16 @@ components: transforms: lua: {
 				set, the directories of the configuration files will be used instead.
 				"""
 		}
+		state: {
+			title: "Persistent State"
+			body:  """
+				With the `state` option set, the global `state` table survives
+				restarts: it is restored from the data directory before `source` is
+				evaluated, and saved back every `checkpoint_interval_seconds` and when
+				the transform stops. Values that can't be saved, such as functions,
+				fail the checkpoint and are reported in the logs.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
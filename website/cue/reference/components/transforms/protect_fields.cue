@@ -0,0 +1,94 @@
+package metadata
+
+components: transforms: protect_fields: {
+	title: "Protect Fields"
+
+	description: """
+		Protects the personal data in the events before they leave the host, by tokenizing, masking, or
+		encrypting the values of selected fields, with keys retrieved from a
+		[secrets backend](\(urls.vector_configuration)global-options#secret).
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.protect_fields.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Mask a card number"
+			input: log: {
+				card_number: "4111 1111 1111 1234"
+				message:     "Payment accepted"
+			}
+			configuration: {
+				fields: [{
+					field:     "card_number"
+					action:    "mask"
+					keep_last: 4
+				}]
+			}
+			output: log: {
+				card_number: "**** **** **** 1234"
+				message:     "Payment accepted"
+			}
+		},
+	]
+
+	how_it_works: {
+		actions: {
+			title: "Actions"
+			body: """
+				A `tokenize`d value is replaced by `tok:<key ID>:<token>`, where the token is the
+				HMAC-SHA256 of the value with the key, so the same values still have the same tokens,
+				and can be looked up by computing their tokens, but can't be recovered.
+
+				A `mask`ed value keeps its format, as only its letters and digits are replaced, except
+				for the first `keep_first` and the last `keep_last` of them.
+
+				An `encrypt`ed value is replaced by `enc:v1:<key ID>:<data key>:<ciphertext>`. The value
+				is encrypted with AES-256-GCM using a random data key, which is itself encrypted with the
+				configured key and stored along with the value. It can be decrypted by another
+				`protect_fields` transform with the `decrypt` action, given the same key.
+				"""
+		}
+
+		key_rotation: {
+			title: "Key rotation"
+			body: """
+				The protected values are tagged with the ID of the key they were protected with. To rotate
+				a key, add the new key under a new ID, and use it for the fields, while keeping the former
+				key for the `decrypt` action to decrypt the values encrypted before the rotation.
+				"""
+		}
+
+		errors: {
+			title: "Errors"
+			body: """
+				An event whose field couldn't be tokenized or encrypted is dropped, rather than sent with
+				the field unprotected. A value which couldn't be decrypted, as it wasn't encrypted or its
+				key isn't configured, is left as is.
+				"""
+		}
+	}
+}
@@ -76,6 +76,40 @@ components: transforms: "remap": {
 				[Vector Remap Language reference](\#(urls.vrl_reference)).
 				"""#
 		}
+		program_reloading: {
+			title: "Program reloading"
+			body:  """
+				When the configuration is reloaded and only the `source` or `file` option of a `remap`
+				transform changed, the new program is compiled and swapped into the running transform,
+				without rebuilding it. Its inputs and outputs stay connected, so no events are dropped.
+				The swap only happens once the rest of the reload succeeds, so the previous program
+				keeps running if the reload is rolled back.
+
+				If the new program changes the schema of the transform's outputs, or fails to compile,
+				the transform is rebuilt as it is for any other change.
+				"""
+		}
+		libraries: {
+			title: "Libraries"
+			body: """
+				Functions used by several `remap` transforms can be defined once, in a library listed
+				in the `libraries` option of each of them:
+
+				```coffee
+				# Parses the `key=value` pairs of a legacy message.
+				fn parse_legacy(message) {
+					parse_key_value(string(message) ?? "", field_delimiter: ";") ?? {}
+				}
+				```
+
+				The body of a function is a VRL program, whose last expression is the result of the
+				function. It can use the arguments of the function as variables, but it can't access
+				the event, which is then passed as an argument when needed: `. = parse_legacy(.message)`.
+
+				A function that can fail at runtime has to be called with `!`, or its error handled, as
+				for the built-in functions.
+				"""
+		}
 		event_data_model: {
 			title: "Event Data Model"
 			body:  """
@@ -156,6 +190,9 @@ components: transforms: "remap": {
 	]
 
 	telemetry: metrics: {
-		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+		remap_aborts_total:               components.sources.internal_metrics.output.metrics.remap_aborts_total
+		remap_errors_total:               components.sources.internal_metrics.output.metrics.remap_errors_total
+		remap_execution_duration_seconds: components.sources.internal_metrics.output.metrics.remap_execution_duration_seconds
 	}
 }
@@ -89,10 +89,35 @@ components: transforms: route: {
 		},
 	]
 
+	how_it_works: {
+		weighted_routes: {
+			title: "Weighted routes"
+			body: """
+				Weighted routes send a percentage of events to a route instead of the events matching a
+				condition. For example, the following sends 5% of the events to the `staging` output,
+				which can feed a staging sink to test a new pipeline against production traffic:
+
+				```toml
+				[transforms.split]
+				type = "route"
+				inputs = ["app_logs"]
+				weighted_route.staging.weight = 5
+				weight_key = "user_id"
+				```
+
+				When `weight_key` is set, the value of that field is hashed to pick the weighted route of
+				each event, so all events of a given user go to the same route. Each event goes to at most
+				one weighted route, and events which go to no route are sent to the `_unmatched` output.
+				To mirror rather than split traffic, connect the production sink to the inputs of the
+				transform rather than to its `_unmatched` output.
+				"""
+		}
+	}
+
 	outputs: [
 		{
 			name:        "<route_id>"
-			description: "Each route can be referenced as an input by other components with the name `<transform_name>.<route_id>`."
+			description: "Each route, conditional or weighted, can be referenced as an input by other components with the name `<transform_name>.<route_id>`."
 		},
 	]
 }
@@ -34,5 +34,6 @@ components: transforms: sample: {
 
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		sample_rate:            components.sources.internal_metrics.output.metrics.sample_rate
 	}
 }
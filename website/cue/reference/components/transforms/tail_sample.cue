@@ -0,0 +1,84 @@
+package metadata
+
+components: transforms: tail_sample: {
+	title: "Tail Sample"
+
+	description: """
+		Buffers events sharing a trace or correlation ID for a window of time, and then keeps or drops
+		the whole group based on conditions evaluated against its events. Unlike head sampling, this
+		keeps every trace with an interesting event, such as an error or a slow request.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.tail_sample.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  true
+	}
+
+	examples: [
+		{
+			title: "Keep the traces with an error"
+			input: [
+				{log: {trace_id: "a", status: "ok", message: "GET /users"}},
+				{log: {trace_id: "b", status: "ok", message: "GET /"}},
+				{log: {trace_id: "a", status: "error", message: "SELECT failed"}},
+			]
+			configuration: {
+				group_by:  "trace_id"
+				keep_when: #".status == "error""#
+			}
+			output: [
+				{log: {trace_id: "a", status: "ok", message: "GET /users"}},
+				{log: {trace_id: "a", status: "error", message: "SELECT failed"}},
+			]
+		},
+	]
+
+	how_it_works: {
+		windowing: {
+			title: "Windowing"
+			body: """
+				The window of a group starts with its first event and lasts `window_ms`. As soon as
+				`keep_when` matches one of its events, the buffered events of the group are forwarded, and
+				so are the ones received during the rest of its window. At the end of the window, a group
+				that didn't match is kept if it's sampled by `sample_rate`, and dropped otherwise. Events
+				with the same ID received after the end of a window start a new group.
+
+				Groups still buffered when Vector shuts down are decided right away.
+				"""
+		}
+
+		memory: {
+			title: "Memory usage"
+			body: """
+				The events of every undecided group are kept in memory until the end of its window. Set
+				`max_events` to decide a group as soon as it reaches that number of events.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		tail_sample_groups_dropped_total: components.sources.internal_metrics.output.metrics.tail_sample_groups_dropped_total
+		tail_sample_groups_kept_total:    components.sources.internal_metrics.output.metrics.tail_sample_groups_kept_total
+	}
+}
@@ -101,6 +101,16 @@ components: transforms: throttle: {
 						replenishes a cell every 6 seconds and allows a burst of up to 10 events.
 						"""
 				},
+				{
+					title: "Byte Quotas"
+					body: """
+						When `mode` is `estimated_bytes` or `json_bytes`, each event consumes one cell per byte of its
+						estimated size in memory or of its estimated JSON encoding, respectively, so that `threshold` is a
+						number of bytes per `window_secs`. This keeps a few very large events from using up an egress
+						budget. An event larger than the `threshold` can never fit in the rate limiter, and is always
+						rate limited.
+						"""
+				},
 				{
 					title: "Rate Limited Events"
 					body: """
@@ -0,0 +1,109 @@
+package metadata
+
+components: transforms: wasm: {
+	title: "WebAssembly"
+
+	description: """
+		Runs events through a WebAssembly module implementing a stable ABI, so that processing
+		written in languages such as Rust or Go runs inside Vector, within limits on its fuel, time,
+		and memory.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "WebAssembly"
+				url:     "https://webassembly.org"
+				version: "1.0"
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.wasm.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		abi: {
+			title: "ABI"
+			body: """
+				Modules implement version 1 of the ABI of the transform. They export:
+
+				* `memory`, their memory.
+				* `vector_abi_version() -> i32`, returning `1`.
+				* `vector_alloc(len: i32) -> i32`, returning a buffer of `len` bytes for the transform to
+				  write the event to.
+				* `vector_process(ptr: i32, len: i32) -> i32`, called for each event written to the
+				  buffer, and returning `0` on success.
+				* Optionally, `vector_init() -> i32` and `vector_shutdown() -> i32`, called when the
+				  transform starts and stops.
+
+				They may import the following functions from the `vector` module, which all return `0`
+				on success:
+
+				* `emit(ptr: i32, len: i32) -> i32`, emitting an event downstream. An event that isn't
+				  emitted is dropped, and an event can be emitted several times.
+				* `counter(name_ptr: i32, name_len: i32, value: i64) -> i32`, incrementing the
+				  `wasm_guest_counter_total` internal metric with the given name.
+				* `gauge(name_ptr: i32, name_len: i32, value: f64) -> i32`, setting the `wasm_guest_gauge`
+				  internal metric with the given name.
+				* `log(level: i32, ptr: i32, len: i32) -> i32`, logging a message, with levels from `0`
+				  for errors to `4` for traces.
+				"""
+		}
+		events: {
+			title: "Events"
+			body: """
+				Events are passed to and from the module as JSON, with their metadata:
+
+				```json
+				{"event": {"log": {"message": "..."}}, "metadata": {}}
+				```
+
+				Timestamps are passed as RFC 3339 strings. The metadata can be left out of the emitted
+				events, which then keep the metadata of the event being processed.
+				"""
+		}
+		limits: {
+			title: "Limits"
+			body: """
+				Each call into the module is limited to `limits.fuel` units of fuel, roughly the number
+				of instructions it runs, and to `limits.timeout_ms` milliseconds. A call exceeding a limit
+				fails and its event is dropped. As the state of the module can't be relied on afterwards,
+				the module is instantiated again.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		wasm_guest_counter_total:         components.sources.internal_metrics.output.metrics.wasm_guest_counter_total
+		wasm_guest_gauge:                 components.sources.internal_metrics.output.metrics.wasm_guest_gauge
+		wasm_module_reloads_total:        components.sources.internal_metrics.output.metrics.wasm_module_reloads_total
+	}
+}
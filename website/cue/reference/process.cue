@@ -61,6 +61,10 @@ process: {
 			SIGTERM: {
 				description: "Initiates graceful shutdown process."
 			}
+
+			SIGUSR1: {
+				description: "Dumps the failed requests recorded by the `request_log` of sinks to the logs."
+			}
 		}
 	}
 }
@@ -0,0 +1,52 @@
+package metadata
+
+remap: functions: dns_lookup: {
+	category: "System"
+	description: """
+		Looks up the IP addresses of the host name in `value`, using the DNS servers of the system.
+
+		The answers are cached for as long as their TTL allows. When a lookup doesn't complete
+		within the `timeout`, the last answer for the host name is returned even if it expired, so
+		that a slow DNS server holds up events for a bounded time only. The lookup keeps going in the
+		background, and its answer is used by the following calls.
+		"""
+	notices: [
+		"""
+			Looking up a host name that isn't cached holds up the event for up to `timeout`, and
+			blocks the thread running the VRL program while it waits. Only available when Vector is
+			built with the `vrl-dns` feature, which is enabled by default.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The host name to look up."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "timeout"
+			description: "How long to wait for the lookup, in seconds. Timeouts longer than 5 seconds are shortened to 5 seconds."
+			required:    false
+			default:     1
+			type: ["integer", "float"]
+		},
+	]
+	internal_failure_reasons: [
+		"The host name can't be resolved.",
+		"The lookup didn't complete within `timeout`, and the host name was never resolved before.",
+		"Too many lookups are in progress, and the host name was never resolved before.",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Look up a host name"
+			source: #"""
+				dns_lookup!("example.com")
+				"""#
+			return: ["93.184.216.34"]
+		},
+	]
+}
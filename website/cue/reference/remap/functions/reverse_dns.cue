@@ -0,0 +1,57 @@
+package metadata
+
+remap: functions: reverse_dns: {
+	category: "System"
+	description: """
+		Looks up the host name of the IP address in `value`, using the DNS servers of the system.
+
+		The answers are cached for as long as their TTL allows. When a lookup doesn't complete
+		within the `timeout`, the last answer for the address is returned even if it expired, so
+		that a slow DNS server holds up events for a bounded time only. The lookup keeps going in the
+		background, and its answer is used by the following calls.
+		"""
+	notices: [
+		"""
+			Looking up an address that isn't cached holds up the event for up to `timeout`, and
+			blocks the thread running the VRL program while it waits. Only available when Vector is
+			built with the `vrl-dns` feature, which is enabled by default.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address to look up."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "timeout"
+			description: "How long to wait for the lookup, in seconds. Timeouts longer than 5 seconds are shortened to 5 seconds."
+			required:    false
+			default:     1
+			type: ["integer", "float"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address.",
+		"The address has no host name.",
+		"The lookup didn't complete within `timeout`, and the address was never resolved before.",
+		"Too many lookups are in progress, and the address was never resolved before.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Look up the host name of a firewall log's source address"
+			input: log: source_ip: "8.8.8.8"
+			source: #"""
+				.source_host = reverse_dns(.source_ip) ?? .source_ip
+				"""#
+			output: log: {
+				source_ip:   "8.8.8.8"
+				source_host: "dns.google"
+			}
+		},
+	]
+}
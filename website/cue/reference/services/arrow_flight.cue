@@ -0,0 +1,8 @@
+package metadata
+
+services: arrow_flight: {
+	name:     "Arrow Flight"
+	thing:    "an \(name) server"
+	url:      urls.arrow_flight
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_event_hubs: {
+	name:     "Azure Event Hubs"
+	thing:    "an \(name) event hub"
+	url:      urls.azure_event_hubs
+	versions: null
+
+	description: "[Azure Event Hubs](\(urls.azure_event_hubs)) is a fully managed, real-time data ingestion service on Azure. Event Hubs partitions the events of each event hub, and keeps them for the configured retention period, so that many consumers can read them independently."
+}
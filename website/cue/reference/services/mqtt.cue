@@ -0,0 +1,10 @@
+package metadata
+
+services: mqtt: {
+	name:     "MQTT"
+	thing:    "an \(name) broker"
+	url:      urls.mqtt
+	versions: null
+
+	description: "[MQTT](\(urls.mqtt)) is a lightweight publish/subscribe messaging protocol, designed for connecting remote devices with a small code footprint and minimal network bandwidth. It is the standard messaging protocol of IoT deployments."
+}
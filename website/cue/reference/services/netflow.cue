@@ -0,0 +1,10 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow"
+	thing:    "\(name) exporters"
+	url:      urls.netflow
+	versions: null
+
+	description: "[NetFlow](\(urls.netflow)), its successor [IPFIX](\(urls.ipfix)) and [sFlow](\(urls.sflow)) are protocols routers and switches export summaries, or samples, of the network traffic they forward with."
+}
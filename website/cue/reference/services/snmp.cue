@@ -0,0 +1,10 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP"
+	thing:    "\(name) agents"
+	url:      urls.snmp
+	versions: null
+
+	description: "The [Simple Network Management Protocol](\(urls.snmp)) (SNMP) is a protocol to monitor and manage network devices, such as routers, switches and printers, whose agents send traps to notify of events such as an interface going down."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: windows_event_log: {
+	name:     "Windows Event Log"
+	thing:    name
+	url:      urls.windows_event_log
+	versions: null
+
+	description: "The [Windows Event Log](\(urls.windows_event_log)) is the service through which Windows, its services, and applications record events, such as errors, warnings, and audit records, in channels like `System`, `Application`, and `Security`."
+}
@@ -18,6 +18,8 @@ urls: {
 	apache_extended_status:                     "\(apache)/docs/current/mod/core.html#extendedstatus"
 	apache_install:                             "\(apache)/docs/current/install.html"
 	apache_mod_status:                          "http://httpd.apache.org/docs/current/mod/mod_status.html"
+	arrow:                                      "https://arrow.apache.org"
+	arrow_flight:                               "\(arrow)/docs/format/Flight.html"
 	apt:                                        "\(wikipedia)/wiki/APT_(software)"
 	arm:                                        "\(wikipedia)/wiki/ARM_architecture"
 	aws_access_keys:                            "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
@@ -174,6 +176,7 @@ urls: {
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
 	elastic_beats:                              "https://www.elastic.co/beats/"
+	elastic_common_schema:                      "https://www.elastic.co/guide/en/ecs/current/index.html"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                         "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
 	elasticsearch_data_streams:                 "https://www.elastic.co/guide/en/elasticsearch/reference/current/data-streams.html"
@@ -208,6 +211,7 @@ urls: {
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
 	gcp_cloud_storage:                          "\(gcp)/storage"
 	gcp_chronicle:                              "https://chronicle.security"
+	gcp_chronicle_udm:                          "https://cloud.google.com/chronicle/docs/reference/udm-field-list"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
 	gcp_pubsub:                                 "\(gcp)/pubsub/"
 	gcp_pubsub_rest:                            "\(gcp)/pubsub/docs/reference/rest/"
@@ -269,6 +273,8 @@ urls: {
 	influxdb_authentication_token:              "https://v2.docs.influxdata.com/v2.0/security/tokens/"
 	influxdb_line_protocol:                     "https://v2.docs.influxdata.com/v2.0/reference/syntax/line-protocol/"
 	inode:                                      "\(wikipedia)/wiki/Inode"
+	ipfix:                                      "https://www.rfc-editor.org/rfc/rfc7011"
+	ipfix_information_elements:                 "https://www.iana.org/assignments/ipfix/ipfix.xhtml"
 	ip_aton:                                    "https://linux.die.net/man/3/inet_aton"
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
@@ -354,11 +360,15 @@ urls: {
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	mqtt:                                       "https://mqtt.org/"
+	mqtt_rumqttc:                               "\(github)/bytebeamio/rumqtt"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
+	netflow:                                    "\(wikipedia)/wiki/NetFlow"
+	netflow_v9:                                 "https://www.rfc-editor.org/rfc/rfc3954"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                  "https://newrelic.com/"
@@ -400,6 +410,7 @@ urls: {
 	prometheus:                                 "https://prometheus.io/"
 	prometheus_client:                          "https://prometheus.io/docs/instrumenting/clientlibs/"
 	prometheus_remote_integrations:             "https://prometheus.io/docs/operating/integrations/#remote-endpoints-and-storage"
+	prometheus_base_units:                      "https://prometheus.io/docs/practices/naming/#base-units"
 	prometheus_counter:                         "https://prometheus.io/docs/concepts/metric_types/#counter"
 	prometheus_gauge:                           "https://prometheus.io/docs/concepts/metric_types/#gauge"
 	prometheus_high_cardinality:                "https://prometheus.io/docs/practices/naming/#labels"
@@ -456,12 +467,18 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sentry:                                     "https://sentry.io"
+	sentry_envelopes:                           "https://develop.sentry.dev/sdk/envelopes/"
+	sflow:                                      "https://sflow.org/sflow_version_5.txt"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
+	smtp:                                       "https://datatracker.ietf.org/doc/html/rfc5321"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snmp:                                       "\(wikipedia)/wiki/Simple_Network_Management_Protocol"
+	snmp_usm:                                   "https://datatracker.ietf.org/doc/html/rfc3414"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
@@ -610,6 +627,7 @@ urls: {
 	vector_sources:                             "/docs/reference/configuration/sources"
 	vector_stars:                               "\(vector_repo)/stargazers"
 	vector_stdin_source:                        "/docs/reference/configuration/sources/stdin"
+	vector_syslog_source:                       "/docs/reference/configuration/sources/syslog"
 	vector_systemd_file:                        "\(vector_repo)/blob/master/distribution/systemd/vector.service"
 	vector_test_harness:                        "\(vector_repo)-test-harness/"
 	vector_topologies:                          "/docs/setup/deployment/topologies"
@@ -643,6 +661,8 @@ urls: {
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                  "https://en.wikipedia.org"
 	windows:                                    "https://www.microsoft.com/en-us/windows"
+	windows_event_log:                          "https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log"
+	windows_event_log_xpath:                    "https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations"
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"